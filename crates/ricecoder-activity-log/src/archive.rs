@@ -0,0 +1,640 @@
+//! Tiered log archiving
+//!
+//! Daily log files move through three tiers as they age:
+//!
+//! - **Hot**: plain `activity-YYYY-MM-DD.log` files, queryable by
+//!   [`FileStorage`](crate::storage::FileStorage)
+//! - **Warm**: zstd-compressed segments under `archive/warm`, tracked in a
+//!   manifest with SHA-256 checksums
+//! - **Cold**: segments exported to the policy's `archive_destination`, or
+//!   deleted when no destination is configured
+//!
+//! Transitions run on [`FileStorage`](crate::storage::FileStorage)
+//! maintenance or on a schedule via [`LogArchiver::spawn`]. Archived days can
+//! be read back with [`LogArchiver::restore`] or made queryable again with
+//! [`LogArchiver::rehydrate`].
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+
+use crate::{
+    error::{ActivityLogError, ActivityLogResult},
+    events::ActivityEvent,
+    query::LogQuery,
+    storage::RetentionPolicy,
+};
+
+const MANIFEST_FILE: &str = "manifest.json";
+
+/// Timing of hot/warm transitions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TieringPolicy {
+    /// Days a daily log stays hot before it is compressed
+    pub hot_days: u32,
+    /// zstd compression level for warm segments
+    pub compression_level: i32,
+    /// Interval between scheduled transition runs (in seconds)
+    pub transition_interval_secs: u64,
+}
+
+impl Default for TieringPolicy {
+    fn default() -> Self {
+        Self {
+            hot_days: 7,
+            compression_level: 3,
+            transition_interval_secs: 3600,
+        }
+    }
+}
+
+/// Storage tier of a day's logs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ArchiveTier {
+    /// Uncompressed and queryable
+    Hot,
+    /// Compressed segment in the local archive
+    Warm,
+    /// Exported to the archive destination
+    Cold,
+}
+
+/// A compressed day of logs tracked by the archive manifest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveSegment {
+    pub date: NaiveDate,
+    pub tier: ArchiveTier,
+    /// Path of the compressed segment
+    pub location: PathBuf,
+    pub event_count: u64,
+    pub original_bytes: u64,
+    pub compressed_bytes: u64,
+    /// SHA-256 of the compressed segment
+    pub checksum: String,
+    /// SHA-256 of the uncompressed log content
+    pub content_checksum: String,
+    /// Whether the segment holds extended-retention categories
+    pub extended_retention: bool,
+    pub archived_at: DateTime<Utc>,
+}
+
+/// Outcome of a transition run
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransitionReport {
+    /// Hot files compressed into warm segments
+    pub compressed: usize,
+    /// Warm segments exported to the cold destination
+    pub exported: usize,
+    /// Warm segments deleted at the end of retention
+    pub deleted: usize,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ArchiveManifest {
+    segments: BTreeMap<NaiveDate, ArchiveSegment>,
+}
+
+/// Moves daily log files between retention tiers
+pub struct LogArchiver {
+    base_path: PathBuf,
+    archive_path: PathBuf,
+    policy: RetentionPolicy,
+    manifest: Mutex<ArchiveManifest>,
+}
+
+impl LogArchiver {
+    /// Open the archive for a log directory
+    pub fn open(base_path: impl Into<PathBuf>, policy: RetentionPolicy) -> ActivityLogResult<Self> {
+        let base_path = base_path.into();
+        let archive_path = base_path.join("archive");
+        std::fs::create_dir_all(archive_path.join("warm"))?;
+
+        let manifest_path = archive_path.join(MANIFEST_FILE);
+        let manifest = if manifest_path.exists() {
+            serde_json::from_str(&std::fs::read_to_string(&manifest_path)?)?
+        } else {
+            ArchiveManifest::default()
+        };
+
+        Ok(Self {
+            base_path,
+            archive_path,
+            policy,
+            manifest: Mutex::new(manifest),
+        })
+    }
+
+    /// All archived segments, oldest first
+    pub async fn segments(&self) -> Vec<ArchiveSegment> {
+        self.manifest
+            .lock()
+            .await
+            .segments
+            .values()
+            .cloned()
+            .collect()
+    }
+
+    /// Current tier of a day's logs, if any are kept
+    pub async fn tier_of(&self, date: NaiveDate) -> Option<ArchiveTier> {
+        if self.hot_path(date).exists() {
+            return Some(ArchiveTier::Hot);
+        }
+        self.manifest
+            .lock()
+            .await
+            .segments
+            .get(&date)
+            .map(|segment| segment.tier)
+    }
+
+    /// Run due transitions
+    pub async fn run_transitions(&self) -> ActivityLogResult<TransitionReport> {
+        self.run_transitions_at(Utc::now()).await
+    }
+
+    /// Run transitions that are due at `now`
+    pub async fn run_transitions_at(
+        &self,
+        now: DateTime<Utc>,
+    ) -> ActivityLogResult<TransitionReport> {
+        let mut report = TransitionReport::default();
+        let mut obsolete = Vec::new();
+        let mut manifest = self.manifest.lock().await;
+
+        let result = self
+            .transition(&mut manifest, now.date_naive(), &mut report, &mut obsolete)
+            .await;
+
+        // Source files are only removed once the manifest records where
+        // their data went, even if the run stopped part way
+        self.save_manifest(&manifest).await?;
+        for path in obsolete {
+            tokio::fs::remove_file(&path).await?;
+        }
+        result?;
+
+        if report != TransitionReport::default() {
+            tracing::info!(
+                "Archive transitions: {} compressed, {} exported, {} deleted",
+                report.compressed,
+                report.exported,
+                report.deleted
+            );
+        }
+        Ok(report)
+    }
+
+    /// Move due days between tiers, collecting files whose data has moved
+    async fn transition(
+        &self,
+        manifest: &mut ArchiveManifest,
+        today: NaiveDate,
+        report: &mut TransitionReport,
+        obsolete: &mut Vec<PathBuf>,
+    ) -> ActivityLogResult<()> {
+        let tiering = &self.policy.tiering;
+
+        // Hot -> warm
+        for (date, path) in self.hot_files()? {
+            let age = (today - date).num_days();
+            if age < tiering.hot_days as i64 {
+                continue;
+            }
+
+            let content = tokio::fs::read(&path).await?;
+            let extended = self.has_extended_categories(&content);
+            // Without compression, files stay hot until they leave retention
+            if !self.policy.compress_old_logs && age < self.retention_days(extended) {
+                continue;
+            }
+
+            self.compress_into(manifest, date, content, extended)
+                .await?;
+            obsolete.push(path);
+            report.compressed += 1;
+        }
+
+        // Warm -> cold
+        let due: Vec<ArchiveSegment> = manifest
+            .segments
+            .values()
+            .filter(|segment| segment.tier == ArchiveTier::Warm)
+            .filter(|segment| {
+                (today - segment.date).num_days() >= self.retention_days(segment.extended_retention)
+            })
+            .cloned()
+            .collect();
+
+        for segment in due {
+            match &self.policy.archive_destination {
+                Some(destination) => {
+                    let destination = PathBuf::from(destination);
+                    tokio::fs::create_dir_all(&destination).await?;
+                    let file_name = segment.location.file_name().ok_or_else(|| {
+                        ActivityLogError::StorageError {
+                            message: format!("Invalid segment path {}", segment.location.display()),
+                        }
+                    })?;
+                    let target = destination.join(file_name);
+                    tokio::fs::copy(&segment.location, &target).await?;
+                    // Sidecar checksum so exports can be verified outside ricecoder
+                    tokio::fs::write(
+                        target.with_extension("zst.sha256"),
+                        format!("{}  {}\n", segment.checksum, file_name.to_string_lossy()),
+                    )
+                    .await?;
+                    obsolete.push(segment.location.clone());
+
+                    manifest.segments.insert(
+                        segment.date,
+                        ArchiveSegment {
+                            tier: ArchiveTier::Cold,
+                            location: target,
+                            ..segment
+                        },
+                    );
+                    report.exported += 1;
+                }
+                None => {
+                    obsolete.push(segment.location.clone());
+                    manifest.segments.remove(&segment.date);
+                    report.deleted += 1;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Check a segment's checksum against its stored data
+    pub async fn verify(&self, date: NaiveDate) -> ActivityLogResult<()> {
+        let segment = self.segment(date).await?;
+        self.read_segment(&segment).await.map(|_| ())
+    }
+
+    /// Check every segment, returning the dates that failed verification
+    pub async fn verify_all(&self) -> Vec<NaiveDate> {
+        let mut failed = Vec::new();
+        for segment in self.segments().await {
+            if let Err(e) = self.read_segment(&segment).await {
+                tracing::warn!(
+                    "Archive segment {} failed verification: {}",
+                    segment.date,
+                    e
+                );
+                failed.push(segment.date);
+            }
+        }
+        failed
+    }
+
+    /// Read back the events of an archived day
+    pub async fn restore(&self, date: NaiveDate) -> ActivityLogResult<Vec<ActivityEvent>> {
+        let segment = self.segment(date).await?;
+        let content = self.read_segment(&segment).await?;
+        Ok(parse_events(&content))
+    }
+
+    /// Read back archived events in a date range that match a query, newest first
+    pub async fn restore_range(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+        query: &LogQuery,
+    ) -> ActivityLogResult<Vec<ActivityEvent>> {
+        let dates: Vec<NaiveDate> = self
+            .manifest
+            .lock()
+            .await
+            .segments
+            .range(start..=end)
+            .map(|(date, _)| *date)
+            .collect();
+
+        let mut events = Vec::new();
+        for date in dates {
+            events.extend(
+                self.restore(date)
+                    .await?
+                    .into_iter()
+                    .filter(|event| query.matches(event)),
+            );
+        }
+        events.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        Ok(events)
+    }
+
+    /// Restore an archived day into the hot tier so it can be queried again
+    ///
+    /// The archived segment is kept; if the day is unchanged when it next
+    /// ages out, the hot copy is simply dropped again.
+    pub async fn rehydrate(&self, date: NaiveDate) -> ActivityLogResult<u64> {
+        let hot_path = self.hot_path(date);
+        if hot_path.exists() {
+            return Err(ActivityLogError::StorageError {
+                message: format!("Logs for {} are already hot", date),
+            });
+        }
+
+        let segment = self.segment(date).await?;
+        let content = self.read_segment(&segment).await?;
+        tokio::fs::write(&hot_path, content).await?;
+        Ok(segment.event_count)
+    }
+
+    /// Start a background task that runs transitions on the policy's schedule
+    pub fn spawn(self: Arc<Self>) -> JoinHandle<()> {
+        let interval =
+            std::time::Duration::from_secs(self.policy.tiering.transition_interval_secs.max(1));
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.run_transitions().await {
+                    tracing::warn!("Archive transition run failed: {}", e);
+                }
+            }
+        })
+    }
+
+    fn hot_path(&self, date: NaiveDate) -> PathBuf {
+        self.base_path
+            .join(format!("activity-{}.log", date.format("%Y-%m-%d")))
+    }
+
+    fn hot_files(&self) -> ActivityLogResult<Vec<(NaiveDate, PathBuf)>> {
+        let mut files = Vec::new();
+        for entry in std::fs::read_dir(&self.base_path)? {
+            let entry = entry?;
+            let date = entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.strip_prefix("activity-"))
+                .and_then(|name| name.strip_suffix(".log"))
+                .and_then(|date| date.parse::<NaiveDate>().ok());
+            if let Some(date) = date {
+                files.push((date, entry.path()));
+            }
+        }
+        files.sort_by_key(|(date, _)| *date);
+        Ok(files)
+    }
+
+    fn retention_days(&self, extended: bool) -> i64 {
+        if extended {
+            self.policy
+                .max_age_days
+                .max(self.policy.extended_retention_days) as i64
+        } else {
+            self.policy.max_age_days as i64
+        }
+    }
+
+    fn has_extended_categories(&self, content: &[u8]) -> bool {
+        !self.policy.extended_retention_categories.is_empty()
+            && parse_events(content).iter().any(|event| {
+                self.policy
+                    .extended_retention_categories
+                    .contains(&event.category)
+            })
+    }
+
+    async fn segment(&self, date: NaiveDate) -> ActivityLogResult<ArchiveSegment> {
+        self.manifest
+            .lock()
+            .await
+            .segments
+            .get(&date)
+            .cloned()
+            .ok_or_else(|| ActivityLogError::StorageError {
+                message: format!("No archived logs for {}", date),
+            })
+    }
+
+    /// Read, verify and decompress a segment
+    async fn read_segment(&self, segment: &ArchiveSegment) -> ActivityLogResult<Vec<u8>> {
+        let compressed = tokio::fs::read(&segment.location).await?;
+        if sha256_hex(&compressed) != segment.checksum {
+            return Err(ActivityLogError::IntegrityError {
+                message: format!(
+                    "Checksum mismatch for archived segment {}",
+                    segment.location.display()
+                ),
+            });
+        }
+
+        let content = tokio::task::spawn_blocking(move || zstd::decode_all(&compressed[..]))
+            .await
+            .map_err(|e| ActivityLogError::StorageError {
+                message: e.to_string(),
+            })??;
+        if sha256_hex(&content) != segment.content_checksum {
+            return Err(ActivityLogError::IntegrityError {
+                message: format!("Decompressed content of {} does not match", segment.date),
+            });
+        }
+        Ok(content)
+    }
+
+    /// Compress a hot file into the warm tier
+    ///
+    /// A hot file for a day that already has a segment was rehydrated from
+    /// it, so it holds every archived event and replaces the segment.
+    async fn compress_into(
+        &self,
+        manifest: &mut ArchiveManifest,
+        date: NaiveDate,
+        content: Vec<u8>,
+        extended: bool,
+    ) -> ActivityLogResult<()> {
+        // A rehydrated day that was not written to needs no new segment
+        if manifest
+            .segments
+            .get(&date)
+            .is_some_and(|existing| existing.content_checksum == sha256_hex(&content))
+        {
+            return Ok(());
+        }
+
+        let level = self.policy.tiering.compression_level;
+        let original = content.clone();
+        let compressed =
+            tokio::task::spawn_blocking(move || zstd::encode_all(&original[..], level))
+                .await
+                .map_err(|e| ActivityLogError::StorageError {
+                    message: e.to_string(),
+                })??;
+
+        let location = self
+            .archive_path
+            .join("warm")
+            .join(format!("activity-{}.log.zst", date.format("%Y-%m-%d")));
+        let tmp = location.with_extension("tmp");
+        tokio::fs::write(&tmp, &compressed).await?;
+        tokio::fs::rename(&tmp, &location).await?;
+
+        manifest.segments.insert(
+            date,
+            ArchiveSegment {
+                date,
+                tier: ArchiveTier::Warm,
+                location,
+                event_count: parse_events(&content).len() as u64,
+                original_bytes: content.len() as u64,
+                compressed_bytes: compressed.len() as u64,
+                checksum: sha256_hex(&compressed),
+                content_checksum: sha256_hex(&content),
+                extended_retention: extended,
+                archived_at: Utc::now(),
+            },
+        );
+        Ok(())
+    }
+
+    async fn save_manifest(&self, manifest: &ArchiveManifest) -> ActivityLogResult<()> {
+        let path = self.archive_path.join(MANIFEST_FILE);
+        let tmp = path.with_extension("tmp");
+        tokio::fs::write(&tmp, serde_json::to_vec_pretty(manifest)?).await?;
+        tokio::fs::rename(&tmp, &path).await?;
+        Ok(())
+    }
+}
+
+fn parse_events(content: &[u8]) -> Vec<ActivityEvent> {
+    String::from_utf8_lossy(content)
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+    use crate::events::{EventCategory, LogLevel};
+    use crate::logger::LogStorage;
+    use crate::storage::FileStorage;
+
+    fn event(days_ago: i64, category: EventCategory) -> ActivityEvent {
+        ActivityEvent {
+            timestamp: Utc::now() - Duration::days(days_ago),
+            ..ActivityEvent::new(
+                LogLevel::Info,
+                category,
+                format!("day-{}", days_ago),
+                "alice".to_string(),
+                "/repo".to_string(),
+            )
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tier_transitions_and_restore() {
+        let dir = tempfile::tempdir().unwrap();
+        let cold = tempfile::tempdir().unwrap();
+        let policy = RetentionPolicy {
+            max_age_days: 30,
+            extended_retention_categories: vec![EventCategory::Security],
+            extended_retention_days: 365,
+            archive_destination: Some(cold.path().to_string_lossy().to_string()),
+            tiering: TieringPolicy {
+                hot_days: 7,
+                ..TieringPolicy::default()
+            },
+            ..RetentionPolicy::default()
+        };
+        let storage = FileStorage::new(dir.path().to_path_buf(), policy).unwrap();
+        for (days_ago, category) in [
+            (1, EventCategory::Network),
+            (10, EventCategory::Network),
+            (40, EventCategory::Network),
+            (40, EventCategory::Network),
+            (50, EventCategory::Security),
+        ] {
+            storage
+                .store_event(&event(days_ago, category))
+                .await
+                .unwrap();
+        }
+
+        let archiver = storage.archiver();
+        let report = archiver.run_transitions().await.unwrap();
+        assert_eq!(
+            report,
+            TransitionReport {
+                compressed: 3,
+                exported: 1,
+                deleted: 0,
+            }
+        );
+
+        let today = Utc::now().date_naive();
+        let day = |ago: i64| today - Duration::days(ago);
+        assert_eq!(archiver.tier_of(day(1)).await, Some(ArchiveTier::Hot));
+        assert_eq!(archiver.tier_of(day(10)).await, Some(ArchiveTier::Warm));
+        assert_eq!(archiver.tier_of(day(40)).await, Some(ArchiveTier::Cold));
+        // Security events keep the day warm for the extended period
+        assert_eq!(archiver.tier_of(day(50)).await, Some(ArchiveTier::Warm));
+
+        let restored = archiver.restore(day(40)).await.unwrap();
+        assert_eq!(restored.len(), 2);
+        assert!(archiver.verify_all().await.is_empty());
+
+        // Rehydrated days are queryable again and re-archive without a new segment
+        assert_eq!(archiver.rehydrate(day(10)).await.unwrap(), 1);
+        let page = storage
+            .query(&LogQuery::new().text("day-10"))
+            .await
+            .unwrap();
+        assert_eq!(page.events.len(), 1);
+        let report = archiver.run_transitions().await.unwrap();
+        assert_eq!(report.compressed, 1);
+        assert_eq!(archiver.tier_of(day(10)).await, Some(ArchiveTier::Warm));
+
+        // New events on a rehydrated day replace the segment without duplicates
+        archiver.rehydrate(day(10)).await.unwrap();
+        storage
+            .store_event(&event(10, EventCategory::Security))
+            .await
+            .unwrap();
+        archiver.run_transitions().await.unwrap();
+        let restored = archiver.restore(day(10)).await.unwrap();
+        assert_eq!(restored.len(), 2);
+        assert!(archiver.segments().await.iter().any(|segment| {
+            segment.date == day(10) && segment.event_count == 2 && segment.extended_retention
+        }));
+    }
+
+    #[tokio::test]
+    async fn test_corrupt_segment_fails_verification() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage =
+            FileStorage::new(dir.path().to_path_buf(), RetentionPolicy::default()).unwrap();
+        storage
+            .store_event(&event(10, EventCategory::Network))
+            .await
+            .unwrap();
+
+        let archiver = storage.archiver();
+        archiver.run_transitions().await.unwrap();
+        let segment = archiver.segments().await.remove(0);
+        std::fs::write(&segment.location, b"garbage").unwrap();
+
+        assert!(matches!(
+            archiver.restore(segment.date).await,
+            Err(ActivityLogError::IntegrityError { .. })
+        ));
+        assert_eq!(archiver.verify_all().await, vec![segment.date]);
+    }
+}
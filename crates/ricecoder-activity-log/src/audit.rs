@@ -1,6 +1,6 @@
 //! Audit trail functionality for compliance and security
 
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -10,6 +10,7 @@ use uuid::Uuid;
 use crate::{
     error::{ActivityLogError, ActivityLogResult},
     events::{ActivityEvent, EventCategory, LogLevel},
+    siem::SiemExporter,
 };
 
 /// Audit trail entry for compliance tracking
@@ -129,6 +130,7 @@ pub struct AuditLogger {
     trails: RwLock<Vec<AuditTrail>>,
     compliance_events: RwLock<Vec<ComplianceEvent>>,
     max_entries: usize,
+    siem: Option<Arc<SiemExporter>>,
 }
 
 impl AuditLogger {
@@ -138,11 +140,24 @@ impl AuditLogger {
             trails: RwLock::new(Vec::new()),
             compliance_events: RwLock::new(Vec::new()),
             max_entries,
+            siem: None,
         }
     }
 
+    /// Forward audit trails to a SIEM
+    pub fn with_siem_exporter(mut self, exporter: Arc<SiemExporter>) -> Self {
+        self.siem = Some(exporter);
+        self
+    }
+
     /// Log an audit trail entry
     pub async fn log_audit(&self, trail: AuditTrail) -> ActivityLogResult<()> {
+        if let Some(siem) = &self.siem {
+            if let Err(e) = siem.export_audit(&trail).await {
+                tracing::warn!("Failed to export audit trail to SIEM: {}", e);
+            }
+        }
+
         let mut trails = self.trails.write().await;
 
         // Add new entry
@@ -34,4 +34,13 @@ pub enum ActivityLogError {
 
     #[error("Performance monitoring error: {message}")]
     MonitoringError { message: String },
+
+    #[error("Redaction error: {message}")]
+    RedactionError { message: String },
+
+    #[error("Integrity check failed: {message}")]
+    IntegrityError { message: String },
+
+    #[error("Export error: {message}")]
+    ExportError { message: String },
 }
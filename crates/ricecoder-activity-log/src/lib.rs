@@ -11,6 +11,8 @@
 //! - **Session Activity Tracking**: Monitor user sessions and activity patterns
 //! - **Performance Monitoring**: Track system performance and bottlenecks
 //! - **Compliance Logging**: Regulatory-compliant logging for enterprise use
+//! - **PII Redaction**: Typed placeholders with sealed originals for compliance access
+//! - **SIEM Export**: CEF/JSON forwarding over syslog or Splunk HEC
 //!
 //! ## Architecture
 //!
@@ -45,20 +47,28 @@
 //! # }
 //! ```
 
+pub mod archive;
 pub mod audit;
 pub mod di;
 pub mod error;
 pub mod events;
 pub mod logger;
 pub mod monitoring;
+pub mod query;
+pub mod redaction;
 pub mod session_tracking;
+pub mod siem;
 pub mod storage;
 
 // Re-export commonly used types
+pub use archive::{ArchiveSegment, ArchiveTier, LogArchiver, TieringPolicy, TransitionReport};
 pub use audit::{AuditLogger, AuditTrail, ComplianceEvent};
 pub use error::{ActivityLogError, ActivityLogResult};
 pub use events::{ActivityEvent, EventCategory, LogLevel};
 pub use logger::{ActivityLogger, LogStorage, LoggerConfig};
 pub use monitoring::{MetricsCollector, PerformanceMonitor};
+pub use query::{JsonPath, JsonPredicate, LogAggregation, LogCursor, LogPage, LogQuery};
+pub use redaction::{PiiClass, RedactionConfig, RedactionReport, Redactor, SealingKey};
 pub use session_tracking::{SessionActivity, SessionTracker};
+pub use siem::{SiemDestination, SiemExportConfig, SiemExporter, SiemFormat};
 pub use storage::RetentionPolicy;
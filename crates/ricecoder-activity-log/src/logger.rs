@@ -10,6 +10,9 @@ use tracing::{debug, error, info, warn};
 use crate::{
     error::{ActivityLogError, ActivityLogResult},
     events::{ActivityEvent, EventFilter, LogLevel},
+    query::{LogAggregation, LogPage, LogQuery},
+    redaction::Redactor,
+    siem::SiemExporter,
 };
 
 /// Logger configuration
@@ -65,6 +68,26 @@ pub trait LogStorage: Send + Sync {
 
     /// Perform maintenance operations (cleanup, archiving, etc.)
     async fn maintenance(&self) -> ActivityLogResult<()>;
+
+    /// Retrieve a page of events matching a query
+    ///
+    /// The default implementation narrows retrieval with the query's coarse
+    /// [`EventFilter`] and applies the full query in memory. Storages with
+    /// native indexing should override it.
+    async fn query(&self, query: &LogQuery) -> ActivityLogResult<LogPage> {
+        let events = self.retrieve_events(&query.to_event_filter()).await?;
+        query.paginate(events.into_iter().filter(|e| query.matches(e)).collect())
+    }
+
+    /// Count events matching a query by category and hour
+    async fn aggregate(&self, query: &LogQuery) -> ActivityLogResult<LogAggregation> {
+        let events = self.retrieve_events(&query.to_event_filter()).await?;
+        let mut aggregation = LogAggregation::default();
+        for event in events.iter().filter(|e| query.matches(e)) {
+            aggregation.add(event);
+        }
+        Ok(aggregation)
+    }
 }
 
 /// In-memory storage implementation for testing and development
@@ -139,12 +162,35 @@ impl LogStorage for MemoryStorage {
         // Memory storage doesn't need maintenance
         Ok(())
     }
+
+    async fn query(&self, query: &LogQuery) -> ActivityLogResult<LogPage> {
+        let cursor = query.decoded_cursor()?;
+        let events = self.events.read().await;
+        let matching = events
+            .iter()
+            .filter(|event| cursor.as_ref().map_or(true, |c| c.precedes(event)))
+            .filter(|event| query.matches(event))
+            .cloned()
+            .collect();
+        query.paginate(matching)
+    }
+
+    async fn aggregate(&self, query: &LogQuery) -> ActivityLogResult<LogAggregation> {
+        let events = self.events.read().await;
+        let mut aggregation = LogAggregation::default();
+        for event in events.iter().filter(|event| query.matches(event)) {
+            aggregation.add(event);
+        }
+        Ok(aggregation)
+    }
 }
 
 /// Main activity logger
 pub struct ActivityLogger {
     config: LoggerConfig,
     storage: Arc<dyn LogStorage>,
+    redactor: Option<Arc<Redactor>>,
+    siem: Option<Arc<SiemExporter>>,
 }
 
 impl ActivityLogger {
@@ -160,11 +206,28 @@ impl ActivityLogger {
 
     /// Create a new activity logger with custom storage
     pub fn with_storage(config: LoggerConfig, storage: Arc<dyn LogStorage>) -> Self {
-        Self { config, storage }
+        Self {
+            config,
+            storage,
+            redactor: None,
+            siem: None,
+        }
+    }
+
+    /// Redact sensitive event details before they are stored or exported
+    pub fn with_redactor(mut self, redactor: Arc<Redactor>) -> Self {
+        self.redactor = Some(redactor);
+        self
+    }
+
+    /// Forward logged events to a SIEM
+    pub fn with_siem_exporter(mut self, exporter: Arc<SiemExporter>) -> Self {
+        self.siem = Some(exporter);
+        self
     }
 
     /// Log an activity event
-    pub async fn log_activity(&self, event: ActivityEvent) -> ActivityLogResult<()> {
+    pub async fn log_activity(&self, mut event: ActivityEvent) -> ActivityLogResult<()> {
         // Validate the event
         event.validate()?;
 
@@ -173,12 +236,23 @@ impl ActivityLogger {
             return Ok(());
         }
 
+        // Redact before the event leaves the logger
+        if let Some(redactor) = &self.redactor {
+            redactor.redact(&mut event)?;
+        }
+
         // Store the event
         self.storage.store_event(&event).await?;
 
         // Output to configured destinations
         self.output_event(&event).await?;
 
+        if let Some(siem) = &self.siem {
+            if let Err(e) = siem.export_event(&event).await {
+                warn!("Failed to export event to SIEM: {}", e);
+            }
+        }
+
         Ok(())
     }
 
@@ -228,6 +302,16 @@ impl ActivityLogger {
         self.storage.delete_events(filter).await
     }
 
+    /// Retrieve a page of events matching a query
+    pub async fn query(&self, query: &LogQuery) -> ActivityLogResult<LogPage> {
+        self.storage.query(query).await
+    }
+
+    /// Count events matching a query by category and hour
+    pub async fn aggregate(&self, query: &LogQuery) -> ActivityLogResult<LogAggregation> {
+        self.storage.aggregate(query).await
+    }
+
     /// Perform maintenance operations
     pub async fn maintenance(&self) -> ActivityLogResult<()> {
         self.storage.maintenance().await
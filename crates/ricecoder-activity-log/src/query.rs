@@ -0,0 +1,627 @@
+//! Query language and filtered retrieval for activity logs
+//!
+//! [`LogQuery`] describes which events to fetch: a time range, actors,
+//! categories, severities, free text, and JSONPath predicates over event
+//! details. Results come back newest first in pages; each [`LogPage`] carries
+//! an opaque cursor that resumes the listing where the page ended, so the
+//! audit-log browser can page through large logs without offsets shifting
+//! underneath it as new events arrive.
+//!
+//! ```rust,no_run
+//! use ricecoder_activity_log::{ActivityLogger, EventCategory, LogLevel, LogQuery};
+//!
+//! # async fn example(logger: ActivityLogger) -> ricecoder_activity_log::ActivityLogResult<()> {
+//! let query = LogQuery::new()
+//!     .category(EventCategory::Security)
+//!     .min_level(LogLevel::Warn)
+//!     .text("denied")
+//!     .json_path_equals("$.request.method", serde_json::json!("DELETE"))?
+//!     .page_size(50);
+//!
+//! let page = logger.query(&query).await?;
+//! if let Some(cursor) = page.next_cursor {
+//!     let next = logger.query(&query.clone().after(cursor)).await?;
+//! }
+//!
+//! let counts = logger.aggregate(&query).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap},
+};
+
+use chrono::{DateTime, DurationRound, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    error::{ActivityLogError, ActivityLogResult},
+    events::{ActivityEvent, EventCategory, EventFilter, LogLevel},
+};
+
+/// Default number of events per page
+pub const DEFAULT_PAGE_SIZE: usize = 100;
+
+/// Query over activity events
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogQuery {
+    /// Earliest event timestamp (inclusive)
+    pub start_time: Option<DateTime<Utc>>,
+    /// Latest event timestamp (inclusive)
+    pub end_time: Option<DateTime<Utc>>,
+    /// Actors to include (exact match)
+    pub actors: Vec<String>,
+    /// Categories to include
+    pub categories: Vec<EventCategory>,
+    /// Minimum severity
+    pub min_level: Option<LogLevel>,
+    /// Exact severities to include
+    pub levels: Vec<LogLevel>,
+    /// Case-insensitive text searched in action, actor, resource, and details
+    pub text: Option<String>,
+    /// Predicates over event details, all of which must hold
+    pub json_paths: Vec<JsonPathFilter>,
+    /// Maximum number of events per page
+    pub page_size: usize,
+    /// Cursor returned by the previous page
+    pub cursor: Option<String>,
+}
+
+impl Default for LogQuery {
+    fn default() -> Self {
+        Self {
+            start_time: None,
+            end_time: None,
+            actors: Vec::new(),
+            categories: Vec::new(),
+            min_level: None,
+            levels: Vec::new(),
+            text: None,
+            json_paths: Vec::new(),
+            page_size: DEFAULT_PAGE_SIZE,
+            cursor: None,
+        }
+    }
+}
+
+impl LogQuery {
+    /// Create a query matching all events
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restrict to events between `start` and `end` (inclusive)
+    pub fn time_range(mut self, start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        self.start_time = Some(start);
+        self.end_time = Some(end);
+        self
+    }
+
+    /// Restrict to events at or after `start`
+    pub fn since(mut self, start: DateTime<Utc>) -> Self {
+        self.start_time = Some(start);
+        self
+    }
+
+    /// Restrict to events at or before `end`
+    pub fn until(mut self, end: DateTime<Utc>) -> Self {
+        self.end_time = Some(end);
+        self
+    }
+
+    /// Include events by an actor
+    pub fn actor(mut self, actor: impl Into<String>) -> Self {
+        self.actors.push(actor.into());
+        self
+    }
+
+    /// Include events in a category
+    pub fn category(mut self, category: EventCategory) -> Self {
+        self.categories.push(category);
+        self
+    }
+
+    /// Restrict to events at or above a severity
+    pub fn min_level(mut self, level: LogLevel) -> Self {
+        self.min_level = Some(level);
+        self
+    }
+
+    /// Include events with exactly this severity
+    pub fn level(mut self, level: LogLevel) -> Self {
+        self.levels.push(level);
+        self
+    }
+
+    /// Restrict to events containing `text`
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    /// Require a value at `path` in the event details
+    pub fn json_path_exists(self, path: &str) -> ActivityLogResult<Self> {
+        self.json_path(path, JsonPredicate::Exists)
+    }
+
+    /// Require a value at `path` equal to `value`
+    pub fn json_path_equals(self, path: &str, value: serde_json::Value) -> ActivityLogResult<Self> {
+        self.json_path(path, JsonPredicate::Equals(value))
+    }
+
+    /// Require a string at `path` containing `text`
+    pub fn json_path_contains(
+        self,
+        path: &str,
+        text: impl Into<String>,
+    ) -> ActivityLogResult<Self> {
+        self.json_path(path, JsonPredicate::Contains(text.into()))
+    }
+
+    /// Add a JSONPath predicate over the event details
+    pub fn json_path(mut self, path: &str, predicate: JsonPredicate) -> ActivityLogResult<Self> {
+        self.json_paths.push(JsonPathFilter {
+            path: JsonPath::parse(path)?,
+            predicate,
+        });
+        Ok(self)
+    }
+
+    /// Set the page size
+    pub fn page_size(mut self, page_size: usize) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    /// Continue after the page that returned `cursor`
+    pub fn after(mut self, cursor: impl Into<String>) -> Self {
+        self.cursor = Some(cursor.into());
+        self
+    }
+
+    /// Check if an event matches every criterion of this query
+    ///
+    /// The cursor is not considered; see [`LogQuery::paginate`].
+    pub fn matches(&self, event: &ActivityEvent) -> bool {
+        if self.start_time.is_some_and(|start| event.timestamp < start)
+            || self.end_time.is_some_and(|end| event.timestamp > end)
+        {
+            return false;
+        }
+
+        if !self.actors.is_empty() && !self.actors.contains(&event.actor) {
+            return false;
+        }
+
+        if !self.categories.is_empty() && !self.categories.contains(&event.category) {
+            return false;
+        }
+
+        if self.min_level.is_some_and(|min| event.level < min)
+            || (!self.levels.is_empty() && !self.levels.contains(&event.level))
+        {
+            return false;
+        }
+
+        if let Some(text) = &self.text {
+            if !Self::contains_text(event, &text.to_lowercase()) {
+                return false;
+            }
+        }
+
+        self.json_paths
+            .iter()
+            .all(|filter| filter.matches(&event.details))
+    }
+
+    fn contains_text(event: &ActivityEvent, needle: &str) -> bool {
+        [&event.action, &event.actor, &event.resource]
+            .iter()
+            .any(|field| field.to_lowercase().contains(needle))
+            || event.details.to_string().to_lowercase().contains(needle)
+    }
+
+    /// Decode the query cursor
+    pub fn decoded_cursor(&self) -> ActivityLogResult<Option<LogCursor>> {
+        self.cursor.as_deref().map(LogCursor::decode).transpose()
+    }
+
+    /// Coarse [`EventFilter`] selecting a superset of this query's matches
+    ///
+    /// Lets storages without native query support narrow retrieval before
+    /// [`LogQuery::matches`] is applied.
+    pub fn to_event_filter(&self) -> EventFilter {
+        EventFilter {
+            min_level: self.min_level,
+            categories: (!self.categories.is_empty()).then(|| self.categories.clone()),
+            actor: None,
+            resource: None,
+            session_id: None,
+            start_time: self.start_time,
+            end_time: self.end_time,
+            limit: None,
+        }
+    }
+
+    /// Build a page from events that already match this query
+    ///
+    /// Sorts newest first, skips everything up to the cursor, and truncates
+    /// to the page size.
+    pub fn paginate(&self, mut events: Vec<ActivityEvent>) -> ActivityLogResult<LogPage> {
+        let cursor = self.decoded_cursor()?;
+        events.sort_by(newest_first);
+        if let Some(cursor) = &cursor {
+            events.retain(|event| cursor.precedes(event));
+        }
+
+        let has_more = events.len() > self.page_size;
+        events.truncate(self.page_size);
+        let next_cursor = if has_more {
+            events
+                .last()
+                .map(|event| LogCursor::from_event(event).encode())
+        } else {
+            None
+        };
+
+        Ok(LogPage {
+            events,
+            next_cursor,
+        })
+    }
+}
+
+/// Order events newest first, breaking timestamp ties by ID
+pub fn newest_first(a: &ActivityEvent, b: &ActivityEvent) -> Ordering {
+    b.timestamp.cmp(&a.timestamp).then_with(|| b.id.cmp(&a.id))
+}
+
+/// A page of query results
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogPage {
+    /// Matching events, newest first
+    pub events: Vec<ActivityEvent>,
+    /// Cursor for the next page, if there are more results
+    pub next_cursor: Option<String>,
+}
+
+/// Position in a newest-first listing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogCursor {
+    /// Timestamp of the last event on the previous page
+    pub timestamp: DateTime<Utc>,
+    /// ID of the last event on the previous page
+    pub id: Uuid,
+}
+
+impl LogCursor {
+    /// Cursor positioned after an event
+    pub fn from_event(event: &ActivityEvent) -> Self {
+        Self {
+            timestamp: event.timestamp,
+            id: event.id,
+        }
+    }
+
+    /// Encode as an opaque string
+    pub fn encode(&self) -> String {
+        format!(
+            "{}|{}",
+            self.timestamp.to_rfc3339_opts(SecondsFormat::Nanos, true),
+            self.id
+        )
+    }
+
+    /// Decode a string produced by [`LogCursor::encode`]
+    pub fn decode(cursor: &str) -> ActivityLogResult<Self> {
+        let invalid = || ActivityLogError::ValidationError {
+            message: format!("Invalid log cursor: {}", cursor),
+        };
+        let (timestamp, id) = cursor.split_once('|').ok_or_else(invalid)?;
+
+        Ok(Self {
+            timestamp: DateTime::parse_from_rfc3339(timestamp)
+                .map_err(|_| invalid())?
+                .with_timezone(&Utc),
+            id: Uuid::parse_str(id).map_err(|_| invalid())?,
+        })
+    }
+
+    /// Whether `event` comes after this cursor in newest-first order
+    pub fn precedes(&self, event: &ActivityEvent) -> bool {
+        (event.timestamp, event.id) < (self.timestamp, self.id)
+    }
+}
+
+/// Event counts for a query
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LogAggregation {
+    /// Total number of matching events
+    pub total: u64,
+    /// Matching events by category
+    pub by_category: HashMap<EventCategory, u64>,
+    /// Matching events by hour (bucket start)
+    pub by_hour: BTreeMap<DateTime<Utc>, u64>,
+}
+
+impl LogAggregation {
+    /// Count an event
+    pub fn add(&mut self, event: &ActivityEvent) {
+        self.total += 1;
+        *self.by_category.entry(event.category.clone()).or_insert(0) += 1;
+
+        let hour = event
+            .timestamp
+            .duration_trunc(chrono::Duration::hours(1))
+            .unwrap_or(event.timestamp);
+        *self.by_hour.entry(hour).or_insert(0) += 1;
+    }
+}
+
+/// Predicate applied to the values selected by a JSONPath
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum JsonPredicate {
+    /// A value exists at the path
+    Exists,
+    /// A value at the path equals the given value
+    Equals(serde_json::Value),
+    /// A string at the path contains the given text (case-insensitive)
+    Contains(String),
+}
+
+/// JSONPath predicate over event details
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonPathFilter {
+    /// Path into the details
+    pub path: JsonPath,
+    /// Predicate the selected values must satisfy
+    pub predicate: JsonPredicate,
+}
+
+impl JsonPathFilter {
+    /// Check whether any value selected by the path satisfies the predicate
+    pub fn matches(&self, details: &serde_json::Value) -> bool {
+        let selected = self.path.select(details);
+        match &self.predicate {
+            JsonPredicate::Exists => !selected.is_empty(),
+            JsonPredicate::Equals(expected) => selected.iter().any(|value| *value == expected),
+            JsonPredicate::Contains(text) => {
+                let text = text.to_lowercase();
+                selected.iter().any(|value| {
+                    value
+                        .as_str()
+                        .is_some_and(|value| value.to_lowercase().contains(&text))
+                })
+            }
+        }
+    }
+}
+
+/// One step of a JSONPath
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JsonPathSegment {
+    /// Object member
+    Key(String),
+    /// Array element
+    Index(usize),
+    /// Every member or element
+    Wildcard,
+}
+
+/// Parsed JSONPath
+///
+/// Supports the subset needed for filtering: `$`, `.key`, `['key']`, `[0]`,
+/// and `*` / `[*]` wildcards.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonPath {
+    /// Path segments after the root
+    pub segments: Vec<JsonPathSegment>,
+}
+
+impl JsonPath {
+    /// Parse a JSONPath expression
+    pub fn parse(path: &str) -> ActivityLogResult<Self> {
+        let invalid = |reason: &str| ActivityLogError::ValidationError {
+            message: format!("Invalid JSONPath '{}': {}", path, reason),
+        };
+
+        let rest = path
+            .trim()
+            .strip_prefix('$')
+            .ok_or_else(|| invalid("must start with '$'"))?;
+        let chars: Vec<char> = rest.chars().collect();
+        let mut segments = Vec::new();
+        let mut i = 0;
+
+        while i < chars.len() {
+            match chars[i] {
+                '.' => {
+                    let start = i + 1;
+                    let mut end = start;
+                    while end < chars.len() && chars[end] != '.' && chars[end] != '[' {
+                        end += 1;
+                    }
+                    let key: String = chars[start..end].iter().collect();
+                    if key.is_empty() {
+                        return Err(invalid("empty member name"));
+                    }
+                    segments.push(if key == "*" {
+                        JsonPathSegment::Wildcard
+                    } else {
+                        JsonPathSegment::Key(key)
+                    });
+                    i = end;
+                }
+                '[' => {
+                    let close = chars[i..]
+                        .iter()
+                        .position(|c| *c == ']')
+                        .map(|offset| i + offset)
+                        .ok_or_else(|| invalid("unclosed '['"))?;
+                    let inner: String = chars[i + 1..close].iter().collect();
+                    let inner = inner.trim();
+
+                    let segment = if inner == "*" {
+                        JsonPathSegment::Wildcard
+                    } else if let Some(key) = inner
+                        .strip_prefix('\'')
+                        .and_then(|key| key.strip_suffix('\''))
+                        .or_else(|| {
+                            inner
+                                .strip_prefix('"')
+                                .and_then(|key| key.strip_suffix('"'))
+                        })
+                    {
+                        JsonPathSegment::Key(key.to_string())
+                    } else {
+                        JsonPathSegment::Index(
+                            inner.parse().map_err(|_| invalid("invalid array index"))?,
+                        )
+                    };
+                    segments.push(segment);
+                    i = close + 1;
+                }
+                _ => return Err(invalid("expected '.' or '['")),
+            }
+        }
+
+        Ok(Self { segments })
+    }
+
+    /// Select all values the path points to
+    pub fn select<'a>(&self, value: &'a serde_json::Value) -> Vec<&'a serde_json::Value> {
+        let mut current = vec![value];
+        for segment in &self.segments {
+            current = current
+                .into_iter()
+                .flat_map(|value| -> Vec<&serde_json::Value> {
+                    match segment {
+                        JsonPathSegment::Key(key) => value.get(key).into_iter().collect(),
+                        JsonPathSegment::Index(index) => value.get(*index).into_iter().collect(),
+                        JsonPathSegment::Wildcard => match value {
+                            serde_json::Value::Object(map) => map.values().collect(),
+                            serde_json::Value::Array(items) => items.iter().collect(),
+                            _ => Vec::new(),
+                        },
+                    }
+                })
+                .collect();
+        }
+        current
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn event(action: &str, category: EventCategory, level: LogLevel) -> ActivityEvent {
+        ActivityEvent::new(
+            level,
+            category,
+            action.to_string(),
+            "alice".to_string(),
+            "/repo".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_json_path_parse_and_select() {
+        let details = json!({
+            "request": { "method": "DELETE", "headers": { "x-user": "bob" } },
+            "files": [{ "path": "a.rs" }, { "path": "b.rs" }]
+        });
+
+        let path = JsonPath::parse("$.request['method']").unwrap();
+        assert_eq!(path.select(&details), vec![&json!("DELETE")]);
+
+        let path = JsonPath::parse("$.files[*].path").unwrap();
+        assert_eq!(path.select(&details).len(), 2);
+
+        let path = JsonPath::parse("$.files[1].path").unwrap();
+        assert_eq!(path.select(&details), vec![&json!("b.rs")]);
+
+        assert!(JsonPath::parse("request.method").is_err());
+        assert!(JsonPath::parse("$.files[x]").is_err());
+    }
+
+    #[test]
+    fn test_query_matches() {
+        let denied = event("access_denied", EventCategory::Security, LogLevel::Warn)
+            .with_details(json!({ "request": { "method": "DELETE" } }));
+        let opened = event("file_opened", EventCategory::FileSystem, LogLevel::Info);
+
+        let query = LogQuery::new()
+            .category(EventCategory::Security)
+            .min_level(LogLevel::Warn)
+            .text("DENIED")
+            .json_path_equals("$.request.method", json!("DELETE"))
+            .unwrap();
+
+        assert!(query.matches(&denied));
+        assert!(!query.matches(&opened));
+        assert!(!LogQuery::new().actor("bob").matches(&denied));
+        assert!(LogQuery::new().level(LogLevel::Info).matches(&opened));
+    }
+
+    #[test]
+    fn test_paginate_with_cursor() {
+        let base = Utc::now();
+        let events: Vec<_> = (0..5)
+            .map(|i| ActivityEvent {
+                timestamp: base + chrono::Duration::seconds(i),
+                ..event(
+                    &format!("action-{}", i),
+                    EventCategory::System,
+                    LogLevel::Info,
+                )
+            })
+            .collect();
+
+        let query = LogQuery::new().page_size(2);
+        let first = query.paginate(events.clone()).unwrap();
+        assert_eq!(first.events[0].action, "action-4");
+        assert_eq!(first.events[1].action, "action-3");
+
+        let second = query
+            .clone()
+            .after(first.next_cursor.unwrap())
+            .paginate(events.clone())
+            .unwrap();
+        assert_eq!(second.events[0].action, "action-2");
+
+        let last = query
+            .after(second.next_cursor.unwrap())
+            .paginate(events)
+            .unwrap();
+        assert_eq!(last.events.len(), 1);
+        assert!(last.next_cursor.is_none());
+    }
+
+    #[test]
+    fn test_aggregation_by_category_and_hour() {
+        let base = Utc::now()
+            .duration_trunc(chrono::Duration::hours(1))
+            .unwrap();
+        let mut aggregation = LogAggregation::default();
+        for (minutes, category) in [
+            (5, EventCategory::Security),
+            (10, EventCategory::Security),
+            (70, EventCategory::Network),
+        ] {
+            aggregation.add(&ActivityEvent {
+                timestamp: base + chrono::Duration::minutes(minutes),
+                ..event("action", category, LogLevel::Info)
+            });
+        }
+
+        assert_eq!(aggregation.total, 3);
+        assert_eq!(aggregation.by_category[&EventCategory::Security], 2);
+        assert_eq!(aggregation.by_hour[&base], 2);
+        assert_eq!(aggregation.by_hour[&(base + chrono::Duration::hours(1))], 1);
+    }
+}
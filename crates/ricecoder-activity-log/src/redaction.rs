@@ -0,0 +1,512 @@
+//! PII detection and redaction for activity events
+//!
+//! The [`Redactor`] scans event details for emails, tokens, file contents and
+//! configurable regex classes, replacing matches with typed placeholders such
+//! as `[REDACTED:email]`. Which redactions were applied is recorded in the
+//! event metadata under [`REDACTIONS_METADATA_KEY`].
+//!
+//! When a [`SealingKey`] is configured, the original details are encrypted
+//! with AES-256-GCM and kept under [`SEALED_DETAILS_METADATA_KEY`], so holders
+//! of the key can recover them for compliance investigations.
+
+use aes_gcm::{
+    aead::{Aead, KeyInit},
+    Aes256Gcm,
+};
+use base64::{engine::general_purpose, Engine as _};
+use rand::RngCore;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    error::{ActivityLogError, ActivityLogResult},
+    events::ActivityEvent,
+};
+
+/// Metadata key holding the [`RedactionReport`]
+pub const REDACTIONS_METADATA_KEY: &str = "redactions";
+
+/// Metadata key holding the [`SealedPayload`] with the original details
+pub const SEALED_DETAILS_METADATA_KEY: &str = "sealed_details";
+
+/// Kind of sensitive data that was redacted
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PiiClass {
+    /// Email addresses
+    Email,
+    /// API keys, bearer tokens, JWTs and other credentials
+    Token,
+    /// Contents of files captured in event details
+    FileContents,
+    /// Configured regex class
+    Custom(String),
+}
+
+impl PiiClass {
+    /// Placeholder that replaces redacted values
+    pub fn placeholder(&self) -> String {
+        let label = match self {
+            PiiClass::Email => "email",
+            PiiClass::Token => "token",
+            PiiClass::FileContents => "file_contents",
+            PiiClass::Custom(name) => name,
+        };
+        format!("[REDACTED:{}]", label)
+    }
+}
+
+/// Configured regex class
+///
+/// If the pattern has a capture group named `value`, only that group is
+/// replaced, so surrounding context such as `password=` is kept.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomPiiClass {
+    pub name: String,
+    pub pattern: String,
+}
+
+/// Redaction configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedactionConfig {
+    /// Redact email addresses
+    pub redact_emails: bool,
+    /// Redact credentials and tokens
+    pub redact_tokens: bool,
+    /// Detail keys whose values are file contents and are always redacted
+    pub file_content_keys: Vec<String>,
+    /// Detail keys whose values are secrets and are always redacted
+    pub secret_keys: Vec<String>,
+    /// Additional regex classes
+    pub custom_classes: Vec<CustomPiiClass>,
+}
+
+impl Default for RedactionConfig {
+    fn default() -> Self {
+        Self {
+            redact_emails: true,
+            redact_tokens: true,
+            file_content_keys: vec![
+                "content".to_string(),
+                "contents".to_string(),
+                "file_content".to_string(),
+            ],
+            secret_keys: vec![
+                "password".to_string(),
+                "secret".to_string(),
+                "token".to_string(),
+                "api_key".to_string(),
+            ],
+            custom_classes: Vec::new(),
+        }
+    }
+}
+
+/// A redaction applied to one location in the event details
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AppliedRedaction {
+    pub class: PiiClass,
+    /// JSONPath of the redacted value, e.g. `$.request.headers[0]`
+    pub path: String,
+    /// Number of matches replaced at this location
+    pub count: usize,
+}
+
+/// Redactions applied to an event
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RedactionReport {
+    pub redactions: Vec<AppliedRedaction>,
+    /// Whether the original details were sealed
+    pub sealed: bool,
+}
+
+impl RedactionReport {
+    /// Read the report recorded on an event
+    pub fn from_event(event: &ActivityEvent) -> Option<Self> {
+        event
+            .metadata
+            .get(REDACTIONS_METADATA_KEY)
+            .and_then(|report| serde_json::from_value(report.clone()).ok())
+    }
+}
+
+/// Encrypted original event details
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SealedPayload {
+    /// ID of the key that sealed the payload
+    pub key_id: String,
+    /// Base64-encoded nonce
+    pub nonce: String,
+    /// Base64-encoded ciphertext
+    pub ciphertext: String,
+}
+
+/// AES-256-GCM key used to seal original details
+#[derive(Clone)]
+pub struct SealingKey {
+    id: String,
+    key: [u8; 32],
+}
+
+impl std::fmt::Debug for SealingKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SealingKey")
+            .field("id", &self.id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SealingKey {
+    /// Create a key from raw bytes
+    pub fn from_bytes(id: impl Into<String>, key: [u8; 32]) -> Self {
+        Self { id: id.into(), key }
+    }
+
+    /// Generate a random key
+    pub fn generate(id: impl Into<String>) -> Self {
+        let mut key = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut key);
+        Self::from_bytes(id, key)
+    }
+
+    /// Key ID recorded on sealed payloads
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Encrypt a value
+    pub fn seal(&self, value: &Value) -> ActivityLogResult<SealedPayload> {
+        let mut nonce = [0u8; 12];
+        rand::thread_rng().fill_bytes(&mut nonce);
+
+        let cipher = Aes256Gcm::new(&self.key.into());
+        let ciphertext = cipher
+            .encrypt(
+                aes_gcm::Nonce::from_slice(&nonce),
+                serde_json::to_vec(value)?.as_ref(),
+            )
+            .map_err(|e| ActivityLogError::RedactionError {
+                message: format!("Failed to seal details: {}", e),
+            })?;
+
+        Ok(SealedPayload {
+            key_id: self.id.clone(),
+            nonce: general_purpose::STANDARD.encode(nonce),
+            ciphertext: general_purpose::STANDARD.encode(ciphertext),
+        })
+    }
+
+    /// Decrypt a sealed payload
+    pub fn unseal(&self, sealed: &SealedPayload) -> ActivityLogResult<Value> {
+        if sealed.key_id != self.id {
+            return Err(ActivityLogError::RedactionError {
+                message: format!(
+                    "Payload was sealed with key '{}', not '{}'",
+                    sealed.key_id, self.id
+                ),
+            });
+        }
+
+        let decode = |field: &str| {
+            general_purpose::STANDARD
+                .decode(field)
+                .map_err(|e| ActivityLogError::RedactionError {
+                    message: format!("Invalid sealed payload: {}", e),
+                })
+        };
+        let nonce = decode(&sealed.nonce)?;
+        if nonce.len() != 12 {
+            return Err(ActivityLogError::RedactionError {
+                message: "Invalid sealed payload nonce".to_string(),
+            });
+        }
+
+        let cipher = Aes256Gcm::new(&self.key.into());
+        let plaintext = cipher
+            .decrypt(
+                aes_gcm::Nonce::from_slice(&nonce),
+                decode(&sealed.ciphertext)?.as_ref(),
+            )
+            .map_err(|e| ActivityLogError::RedactionError {
+                message: format!("Failed to unseal details: {}", e),
+            })?;
+
+        Ok(serde_json::from_slice(&plaintext)?)
+    }
+
+    /// Recover the original details of a redacted event
+    ///
+    /// Returns `None` if the event has no sealed details.
+    pub fn unseal_event(&self, event: &ActivityEvent) -> ActivityLogResult<Option<Value>> {
+        match event.metadata.get(SEALED_DETAILS_METADATA_KEY) {
+            Some(sealed) => {
+                let sealed: SealedPayload = serde_json::from_value(sealed.clone())?;
+                self.unseal(&sealed).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+struct PiiPattern {
+    class: PiiClass,
+    regex: Regex,
+}
+
+/// Redaction stage applied to events before they are stored
+pub struct Redactor {
+    config: RedactionConfig,
+    patterns: Vec<PiiPattern>,
+    sealing_key: Option<SealingKey>,
+}
+
+impl Redactor {
+    /// Create a redactor, compiling the configured patterns
+    pub fn new(config: RedactionConfig) -> ActivityLogResult<Self> {
+        let mut patterns = Vec::new();
+
+        if config.redact_tokens {
+            for pattern in [
+                r"sk-(?:ant-)?[A-Za-z0-9_\-]{20,}",
+                r"gh[pousr]_[A-Za-z0-9]{30,}",
+                r"AKIA[0-9A-Z]{16}",
+                r"eyJ[A-Za-z0-9_\-]+\.[A-Za-z0-9_\-]+\.[A-Za-z0-9_\-]+",
+                r"(?i)bearer\s+(?P<value>[A-Za-z0-9._\-/+=]{8,})",
+                r#"(?i)\b(?:api[_-]?key|token|secret|password)\b["']?\s*[:=]\s*["']?(?P<value>[^\s"',;]+)"#,
+            ] {
+                patterns.push(PiiPattern {
+                    class: PiiClass::Token,
+                    regex: Regex::new(pattern).expect("built-in token pattern"),
+                });
+            }
+        }
+
+        if config.redact_emails {
+            patterns.push(PiiPattern {
+                class: PiiClass::Email,
+                regex: Regex::new(r"[A-Za-z0-9._%+\-]+@[A-Za-z0-9.\-]+\.[A-Za-z]{2,}")
+                    .expect("built-in email pattern"),
+            });
+        }
+
+        for custom in &config.custom_classes {
+            let regex = Regex::new(&custom.pattern).map_err(|e| ActivityLogError::ConfigError {
+                field: format!("redaction.custom_classes.{}", custom.name),
+                message: e.to_string(),
+            })?;
+            patterns.push(PiiPattern {
+                class: PiiClass::Custom(custom.name.clone()),
+                regex,
+            });
+        }
+
+        Ok(Self {
+            config,
+            patterns,
+            sealing_key: None,
+        })
+    }
+
+    /// Seal original details with a key before redacting them
+    pub fn with_sealing_key(mut self, key: SealingKey) -> Self {
+        self.sealing_key = Some(key);
+        self
+    }
+
+    /// Redact an event's details in place
+    ///
+    /// Returns the applied redactions, or `None` if nothing was found.
+    pub fn redact(&self, event: &mut ActivityEvent) -> ActivityLogResult<Option<RedactionReport>> {
+        let mut redacted = event.details.clone();
+        let mut applied = Vec::new();
+        self.redact_value(&mut redacted, None, "$".to_string(), &mut applied);
+
+        if applied.is_empty() {
+            return Ok(None);
+        }
+
+        let sealed = match &self.sealing_key {
+            Some(key) => {
+                let payload = key.seal(&event.details)?;
+                event.metadata.insert(
+                    SEALED_DETAILS_METADATA_KEY.to_string(),
+                    serde_json::to_value(payload)?,
+                );
+                true
+            }
+            None => false,
+        };
+
+        let report = RedactionReport {
+            redactions: applied,
+            sealed,
+        };
+        event.details = redacted;
+        event.metadata.insert(
+            REDACTIONS_METADATA_KEY.to_string(),
+            serde_json::to_value(&report)?,
+        );
+        Ok(Some(report))
+    }
+
+    fn redact_value(
+        &self,
+        value: &mut Value,
+        key: Option<&str>,
+        path: String,
+        applied: &mut Vec<AppliedRedaction>,
+    ) {
+        if let Some(class) = key.and_then(|key| self.key_class(key)) {
+            if !value.is_null() {
+                *value = Value::String(class.placeholder());
+                applied.push(AppliedRedaction {
+                    class,
+                    path,
+                    count: 1,
+                });
+            }
+            return;
+        }
+
+        match value {
+            Value::String(text) => {
+                for pattern in &self.patterns {
+                    let (replaced, count) = Self::replace(&pattern.regex, text, &pattern.class);
+                    if count > 0 {
+                        *text = replaced;
+                        applied.push(AppliedRedaction {
+                            class: pattern.class.clone(),
+                            path: path.clone(),
+                            count,
+                        });
+                    }
+                }
+            }
+            Value::Array(items) => {
+                for (index, item) in items.iter_mut().enumerate() {
+                    self.redact_value(item, None, format!("{}[{}]", path, index), applied);
+                }
+            }
+            Value::Object(map) => {
+                for (field, item) in map.iter_mut() {
+                    self.redact_value(item, Some(field), format!("{}.{}", path, field), applied);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn key_class(&self, key: &str) -> Option<PiiClass> {
+        let matches = |keys: &[String]| keys.iter().any(|k| k.eq_ignore_ascii_case(key));
+        if matches(&self.config.file_content_keys) {
+            Some(PiiClass::FileContents)
+        } else if matches(&self.config.secret_keys) {
+            Some(PiiClass::Token)
+        } else {
+            None
+        }
+    }
+
+    /// Replace matches, or only their `value` group when the pattern has one
+    fn replace(regex: &Regex, text: &str, class: &PiiClass) -> (String, usize) {
+        let placeholder = class.placeholder();
+        let mut result = String::with_capacity(text.len());
+        let mut last = 0;
+        let mut count = 0;
+
+        for captures in regex.captures_iter(text) {
+            let span = captures
+                .name("value")
+                .or_else(|| captures.get(0))
+                .expect("capture group 0 always matches");
+            if span.as_str() == placeholder {
+                continue;
+            }
+            result.push_str(&text[last..span.start()]);
+            result.push_str(&placeholder);
+            last = span.end();
+            count += 1;
+        }
+        result.push_str(&text[last..]);
+        (result, count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn event(details: Value) -> ActivityEvent {
+        ActivityEvent {
+            details,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_redacts_emails_tokens_and_file_contents() {
+        let redactor = Redactor::new(RedactionConfig {
+            custom_classes: vec![CustomPiiClass {
+                name: "ticket".to_string(),
+                pattern: r"TICKET-\d+".to_string(),
+            }],
+            ..RedactionConfig::default()
+        })
+        .unwrap();
+
+        let mut event = event(json!({
+            "message": "contact bob@example.com about TICKET-42",
+            "request": {"headers": ["Authorization: Bearer abcdef123456"]},
+            "command": "deploy --password=hunter2",
+            "content": "fn main() {}",
+            "size": 12,
+        }));
+        let report = redactor.redact(&mut event).unwrap().unwrap();
+
+        assert_eq!(
+            event.details["message"],
+            "contact [REDACTED:email] about [REDACTED:ticket]"
+        );
+        assert_eq!(
+            event.details["request"]["headers"][0],
+            "Authorization: Bearer [REDACTED:token]"
+        );
+        assert_eq!(
+            event.details["command"],
+            "deploy --password=[REDACTED:token]"
+        );
+        assert_eq!(event.details["content"], "[REDACTED:file_contents]");
+        assert_eq!(event.details["size"], 12);
+
+        assert!(!report.sealed);
+        assert!(report.redactions.contains(&AppliedRedaction {
+            class: PiiClass::FileContents,
+            path: "$.content".to_string(),
+            count: 1,
+        }));
+        assert_eq!(RedactionReport::from_event(&event), Some(report));
+    }
+
+    #[test]
+    fn test_sealed_original_requires_matching_key() {
+        let key = SealingKey::generate("compliance-2026");
+        let redactor = Redactor::new(RedactionConfig::default())
+            .unwrap()
+            .with_sealing_key(key.clone());
+
+        let original = json!({"user": "alice@example.com"});
+        let mut event = event(original.clone());
+        assert!(redactor.redact(&mut event).unwrap().unwrap().sealed);
+        assert_eq!(event.details["user"], "[REDACTED:email]");
+
+        assert_eq!(key.unseal_event(&event).unwrap(), Some(original));
+        let other = SealingKey::from_bytes("compliance-2026", [7u8; 32]);
+        assert!(other.unseal_event(&event).is_err());
+
+        // Clean events are left untouched
+        let mut clean = self::event(json!({"size": 1}));
+        assert!(redactor.redact(&mut clean).unwrap().is_none());
+        assert!(clean.metadata.is_empty());
+    }
+}
@@ -0,0 +1,277 @@
+//! Batching exporter with persistent retry
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+
+use super::{
+    RetryQueue, SiemDestination, SiemExportConfig, SiemFormatter, SiemRecord, SiemTransport,
+    SplunkHecTransport, SyslogTransport,
+};
+use crate::{
+    audit::AuditTrail,
+    error::{ActivityLogError, ActivityLogResult},
+    events::ActivityEvent,
+};
+
+/// Buffers records and ships them to a SIEM in batches
+///
+/// A batch is sent once `batch_size` records are buffered or when the
+/// background worker started by [`SiemExporter::spawn`] flushes. Batches the
+/// transport rejects are persisted to the retry queue and resent, oldest
+/// first, before newer data on later flushes.
+pub struct SiemExporter {
+    formatter: SiemFormatter,
+    transport: Arc<dyn SiemTransport>,
+    queue: RetryQueue,
+    buffer: Mutex<Vec<SiemRecord>>,
+    batch_size: usize,
+    flush_interval: Duration,
+}
+
+impl SiemExporter {
+    /// Create an exporter for the configured destination
+    pub fn new(config: SiemExportConfig) -> ActivityLogResult<Self> {
+        let transport: Arc<dyn SiemTransport> = match &config.destination {
+            SiemDestination::Syslog {
+                host,
+                port,
+                tls,
+                verify_tls,
+                app_name,
+            } => {
+                let transport = SyslogTransport::new(host.clone(), *port, app_name.clone());
+                if *tls {
+                    Arc::new(transport.with_tls(*verify_tls)?)
+                } else {
+                    Arc::new(transport)
+                }
+            }
+            SiemDestination::SplunkHec {
+                url,
+                token,
+                index,
+                source,
+                sourcetype,
+                verify_tls,
+            } => Arc::new(
+                SplunkHecTransport::new(url, token.clone(), *verify_tls)?
+                    .with_index(index.clone())
+                    .with_source(source.clone())
+                    .with_sourcetype(sourcetype.clone()),
+            ),
+        };
+
+        Self::with_transport(config, transport)
+    }
+
+    /// Create an exporter with a custom transport
+    pub fn with_transport(
+        config: SiemExportConfig,
+        transport: Arc<dyn SiemTransport>,
+    ) -> ActivityLogResult<Self> {
+        if config.batch_size == 0 {
+            return Err(ActivityLogError::ConfigError {
+                field: "batch_size".to_string(),
+                message: "must be greater than zero".to_string(),
+            });
+        }
+
+        Ok(Self {
+            formatter: SiemFormatter::from_config(&config),
+            transport,
+            queue: RetryQueue::open(&config.queue_dir, config.max_queued_batches)?,
+            buffer: Mutex::new(Vec::new()),
+            batch_size: config.batch_size,
+            flush_interval: Duration::from_secs(config.flush_interval_secs.max(1)),
+        })
+    }
+
+    /// Retry queue holding undelivered batches
+    pub fn retry_queue(&self) -> &RetryQueue {
+        &self.queue
+    }
+
+    /// Queue an activity event for export
+    pub async fn export_event(&self, event: &ActivityEvent) -> ActivityLogResult<()> {
+        self.export(SiemRecord::from(event)).await
+    }
+
+    /// Queue an audit trail for export
+    pub async fn export_audit(&self, trail: &AuditTrail) -> ActivityLogResult<()> {
+        self.export(SiemRecord::from(trail)).await
+    }
+
+    /// Queue a record, sending a batch once the buffer is full
+    pub async fn export(&self, record: SiemRecord) -> ActivityLogResult<()> {
+        let full = {
+            let mut buffer = self.buffer.lock().await;
+            buffer.push(record);
+            buffer.len() >= self.batch_size
+        };
+
+        if full {
+            self.flush().await?;
+        }
+        Ok(())
+    }
+
+    /// Send buffered records, returning how many were delivered
+    ///
+    /// Transport failures are not errors: the batch is persisted for retry.
+    pub async fn flush(&self) -> ActivityLogResult<usize> {
+        let records = std::mem::take(&mut *self.buffer.lock().await);
+        if records.is_empty() {
+            return Ok(0);
+        }
+
+        // Keep delivery ordered behind anything already waiting for retry
+        if !self.queue.is_empty().await? {
+            self.queue.push(&records).await?;
+            return Ok(0);
+        }
+
+        let mut delivered = 0;
+        for batch in records.chunks(self.batch_size) {
+            match self.transport.send(batch, &self.formatter).await {
+                Ok(()) => delivered += batch.len(),
+                Err(e) => {
+                    warn!(
+                        "SIEM export via {} failed, queueing {} record(s): {}",
+                        self.transport.name(),
+                        batch.len(),
+                        e
+                    );
+                    self.queue.push(batch).await?;
+                }
+            }
+        }
+        Ok(delivered)
+    }
+
+    /// Resend queued batches oldest first, returning how many records were delivered
+    ///
+    /// Stops at the first batch that still fails so ordering is preserved.
+    pub async fn retry_pending(&self) -> ActivityLogResult<usize> {
+        let mut delivered = 0;
+        for path in self.queue.pending().await? {
+            let records = match self.queue.load(&path).await {
+                Ok(records) => records,
+                Err(e) => {
+                    warn!("Discarding unreadable SIEM batch: {}", e);
+                    self.queue.remove(&path).await?;
+                    continue;
+                }
+            };
+
+            if let Err(e) = self.transport.send(&records, &self.formatter).await {
+                debug!(
+                    "SIEM retry via {} failed, {} will be retried later: {}",
+                    self.transport.name(),
+                    path.display(),
+                    e
+                );
+                break;
+            }
+            self.queue.remove(&path).await?;
+            delivered += records.len();
+        }
+        Ok(delivered)
+    }
+
+    /// Start a background task that retries queued batches and flushes the buffer
+    pub fn spawn(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.flush_interval);
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.retry_pending().await {
+                    warn!("SIEM retry failed: {}", e);
+                }
+                if let Err(e) = self.flush().await {
+                    warn!("SIEM flush failed: {}", e);
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use async_trait::async_trait;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct FlakyTransport {
+        down: AtomicBool,
+        sent: std::sync::Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl SiemTransport for FlakyTransport {
+        fn name(&self) -> &str {
+            "flaky"
+        }
+
+        async fn send(
+            &self,
+            records: &[SiemRecord],
+            _formatter: &SiemFormatter,
+        ) -> ActivityLogResult<()> {
+            if self.down.load(Ordering::SeqCst) {
+                return Err(ActivityLogError::ExportError {
+                    message: "collector unreachable".to_string(),
+                });
+            }
+            self.sent
+                .lock()
+                .unwrap()
+                .extend(records.iter().map(|r| r.action.clone()));
+            Ok(())
+        }
+    }
+
+    fn event(action: &str) -> ActivityEvent {
+        ActivityEvent {
+            action: action.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_batches_are_queued_and_retried_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = SiemExportConfig::new(SiemDestination::Syslog {
+            host: "localhost".to_string(),
+            port: 514,
+            tls: false,
+            verify_tls: true,
+            app_name: "ricecoder".to_string(),
+        });
+        config.batch_size = 2;
+        config.queue_dir = dir.path().to_path_buf();
+
+        let transport = Arc::new(FlakyTransport::default());
+        let exporter = SiemExporter::with_transport(config, transport.clone()).unwrap();
+
+        transport.down.store(true, Ordering::SeqCst);
+        exporter.export_event(&event("a")).await.unwrap();
+        exporter.export_event(&event("b")).await.unwrap();
+        assert_eq!(exporter.retry_queue().len().await.unwrap(), 1);
+
+        // Collector is back, but newer records wait behind the queued batch
+        transport.down.store(false, Ordering::SeqCst);
+        exporter.export_event(&event("c")).await.unwrap();
+        assert_eq!(exporter.flush().await.unwrap(), 0);
+        assert_eq!(exporter.retry_pending().await.unwrap(), 3);
+
+        assert!(exporter.retry_queue().is_empty().await.unwrap());
+        assert_eq!(*transport.sent.lock().unwrap(), vec!["a", "b", "c"]);
+    }
+}
@@ -0,0 +1,201 @@
+//! CEF and JSON formatting of SIEM records
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{FieldMapping, SiemExportConfig, SiemFormat, SiemRecord};
+
+/// Device identification in the CEF header
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CefHeader {
+    pub vendor: String,
+    pub product: String,
+    pub version: String,
+}
+
+impl Default for CefHeader {
+    fn default() -> Self {
+        Self {
+            vendor: "RiceCoder".to_string(),
+            product: "ricecoder".to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+}
+
+/// Renders records in the configured wire format
+#[derive(Debug, Clone)]
+pub struct SiemFormatter {
+    format: SiemFormat,
+    header: CefHeader,
+    mapping: FieldMapping,
+}
+
+impl SiemFormatter {
+    /// Create a formatter
+    pub fn new(format: SiemFormat, header: CefHeader, mapping: FieldMapping) -> Self {
+        Self {
+            format,
+            header,
+            mapping,
+        }
+    }
+
+    /// Create a formatter from export configuration
+    pub fn from_config(config: &SiemExportConfig) -> Self {
+        Self::new(
+            config.format,
+            config.cef_header.clone(),
+            config.field_mapping.clone(),
+        )
+    }
+
+    /// Configured wire format
+    pub fn wire_format(&self) -> SiemFormat {
+        self.format
+    }
+
+    /// Render a record as a single line in the configured format
+    pub fn render(&self, record: &SiemRecord) -> String {
+        match self.format {
+            SiemFormat::Cef => self.to_cef(record),
+            SiemFormat::Json => self.to_json(record).to_string(),
+        }
+    }
+
+    /// Render a record as a JSON object with mapped field names
+    pub fn to_json(&self, record: &SiemRecord) -> Value {
+        Value::Object(
+            self.mapping
+                .apply(record.fields(), |_| None)
+                .into_iter()
+                .collect(),
+        )
+    }
+
+    /// Render a record as a CEF line
+    pub fn to_cef(&self, record: &SiemRecord) -> String {
+        let mut fields = record.fields();
+        // Severity lives in the header; receipt time is epoch millis in CEF
+        fields.retain(|(name, _)| *name != "severity");
+        for (name, value) in fields.iter_mut() {
+            if *name == "timestamp" {
+                *value = Value::from(record.timestamp.timestamp_millis());
+            }
+        }
+
+        let mut extension = Vec::new();
+        for (key, value) in self.mapping.apply(fields, default_cef_key) {
+            let value = match value {
+                Value::String(s) => s,
+                other => other.to_string(),
+            };
+            extension.push(format!("{}={}", key, escape_extension(&value)));
+
+            // Custom string fields are only meaningful with a label
+            if let Some(canonical) = label_for(&key) {
+                extension.push(format!("{}Label={}", key, escape_extension(canonical)));
+            }
+        }
+
+        format!(
+            "CEF:0|{}|{}|{}|{}|{}|{}|{}",
+            escape_header(&self.header.vendor),
+            escape_header(&self.header.product),
+            escape_header(&self.header.version),
+            escape_header(&record.action),
+            escape_header(&record.category),
+            record.severity.min(10),
+            extension.join(" ")
+        )
+    }
+}
+
+/// Standard CEF extension key for a canonical field
+fn default_cef_key(field: &'static str) -> Option<&'static str> {
+    Some(match field {
+        "id" => "externalId",
+        "timestamp" => "rt",
+        "kind" => "cs1",
+        "category" => "cat",
+        "action" => "act",
+        "actor" => "suser",
+        "resource" => "filePath",
+        "outcome" => "outcome",
+        "session_id" => "cs2",
+        "source" => "src",
+        "details" => "msg",
+        _ => return None,
+    })
+}
+
+fn label_for(key: &str) -> Option<&'static str> {
+    match key {
+        "cs1" => Some("kind"),
+        "cs2" => Some("sessionId"),
+        _ => None,
+    }
+}
+
+fn escape_header(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace(['\r', '\n'], " ")
+}
+
+fn escape_extension(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('=', "\\=")
+        .replace('\r', "\\r")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{ActivityEvent, LogLevel};
+
+    fn record() -> SiemRecord {
+        SiemRecord::from(&ActivityEvent {
+            level: LogLevel::Error,
+            action: "run|command".to_string(),
+            actor: "alice".to_string(),
+            resource: "cmd=a\\b".to_string(),
+            details: serde_json::json!({"exit": 1}),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_cef_escaping_and_default_keys() {
+        let formatter = SiemFormatter::new(
+            SiemFormat::Cef,
+            CefHeader::default(),
+            FieldMapping::default(),
+        );
+        let line = formatter.to_cef(&record());
+
+        assert!(line.starts_with("CEF:0|RiceCoder|ricecoder|"));
+        assert!(line.contains("|run\\|command|System|7|"));
+        assert!(line.contains("suser=alice"));
+        assert!(line.contains("filePath=cmd\\=a\\\\b"));
+        assert!(line.contains("cs1=activity cs1Label=kind"));
+        assert!(line.contains("msg={\"exit\":1}"));
+    }
+
+    #[test]
+    fn test_json_uses_mapped_names() {
+        let mut mapping = FieldMapping::default();
+        mapping
+            .rename
+            .insert("actor".to_string(), "user.name".to_string());
+        let formatter = SiemFormatter::new(SiemFormat::Json, CefHeader::default(), mapping);
+
+        let json = formatter.to_json(&record());
+        assert_eq!(json["user.name"], "alice");
+        assert_eq!(json["severity"], 7);
+        assert!(json.get("actor").is_none());
+    }
+}
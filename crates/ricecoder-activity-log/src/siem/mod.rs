@@ -0,0 +1,374 @@
+//! SIEM export pipeline
+//!
+//! Forwards activity events and audit trails to external security tooling.
+//! Records are normalized into [`SiemRecord`]s, renamed and filtered through
+//! a [`FieldMapping`], formatted as CEF or JSON and shipped in batches over
+//! syslog (TCP or TLS) or to a Splunk HTTP Event Collector.
+//!
+//! Batches that fail to send are persisted to a [`RetryQueue`] on disk and
+//! retried in order, so nothing is lost while the collector is unreachable
+//! or across restarts.
+//!
+//! ```rust,no_run
+//! use std::sync::Arc;
+//! use ricecoder_activity_log::siem::{SiemDestination, SiemExportConfig, SiemExporter};
+//!
+//! # async fn example() -> ricecoder_activity_log::ActivityLogResult<()> {
+//! let config = SiemExportConfig::new(SiemDestination::Syslog {
+//!     host: "siem.internal".to_string(),
+//!     port: 6514,
+//!     tls: true,
+//!     verify_tls: true,
+//!     app_name: "ricecoder".to_string(),
+//! });
+//!
+//! let exporter = Arc::new(SiemExporter::new(config)?);
+//! let _worker = Arc::clone(&exporter).spawn();
+//! # Ok(())
+//! # }
+//! ```
+
+mod exporter;
+mod format;
+mod queue;
+mod transport;
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use ricecoder_storage::{PathResolver, StorageDirectory};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    audit::{AuditOutcome, AuditTrail},
+    events::{ActivityEvent, EventCategory, LogLevel},
+};
+
+pub use exporter::SiemExporter;
+pub use format::{CefHeader, SiemFormatter};
+pub use queue::RetryQueue;
+pub use transport::{SiemTransport, SplunkHecTransport, SyslogTransport};
+
+/// Wire format for exported records
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SiemFormat {
+    /// ArcSight Common Event Format
+    Cef,
+    /// One JSON object per record
+    Json,
+}
+
+/// Where exported records are sent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SiemDestination {
+    /// RFC 5424 syslog over TCP, optionally wrapped in TLS (RFC 5425)
+    Syslog {
+        host: String,
+        port: u16,
+        tls: bool,
+        verify_tls: bool,
+        app_name: String,
+    },
+    /// Splunk HTTP Event Collector
+    SplunkHec {
+        /// Base URL of the collector, e.g. `https://splunk:8088`
+        url: String,
+        token: String,
+        index: Option<String>,
+        source: Option<String>,
+        sourcetype: Option<String>,
+        verify_tls: bool,
+    },
+}
+
+/// SIEM export configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SiemExportConfig {
+    pub destination: SiemDestination,
+    pub format: SiemFormat,
+    /// CEF header vendor/product/version
+    pub cef_header: CefHeader,
+    pub field_mapping: FieldMapping,
+    /// Records buffered before a batch is sent
+    pub batch_size: usize,
+    /// Interval between background flushes and retries
+    pub flush_interval_secs: u64,
+    /// Directory holding batches that failed to send, readable by the
+    /// owner only
+    pub queue_dir: PathBuf,
+    /// Oldest batches are dropped once the queue grows beyond this
+    pub max_queued_batches: usize,
+}
+
+impl SiemExportConfig {
+    /// Create a configuration with default batching for a destination
+    pub fn new(destination: SiemDestination) -> Self {
+        Self {
+            destination,
+            format: SiemFormat::Cef,
+            cef_header: CefHeader::default(),
+            field_mapping: FieldMapping::default(),
+            batch_size: 100,
+            flush_interval_secs: 5,
+            queue_dir: Self::default_queue_dir(),
+            max_queued_batches: 1000,
+        }
+    }
+
+    /// Default retry queue location, under the RiceCoder storage directory
+    pub fn default_queue_dir() -> PathBuf {
+        let base = PathResolver::resolve_global_path()
+            .unwrap_or_else(|_| PathBuf::from(PathResolver::GLOBAL_DIR));
+        PathResolver::storage_dir(&base, StorageDirectory::Storage).join("siem-queue")
+    }
+}
+
+/// Renames, drops and adds fields before a record is formatted
+///
+/// Field names refer to the canonical [`SiemRecord`] fields (`actor`,
+/// `action`, `resource`, ...). For CEF, unmapped fields fall back to the
+/// standard CEF extension keys.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FieldMapping {
+    /// Canonical field name to exported field name
+    pub rename: HashMap<String, String>,
+    /// Canonical fields that are never exported
+    pub exclude: Vec<String>,
+    /// Fields added to every exported record
+    pub static_fields: HashMap<String, serde_json::Value>,
+}
+
+impl FieldMapping {
+    /// Map canonical fields to exported names
+    ///
+    /// `default_name` supplies the format's own name for fields without an
+    /// explicit rename.
+    pub fn apply(
+        &self,
+        fields: Vec<(&'static str, serde_json::Value)>,
+        default_name: impl Fn(&'static str) -> Option<&'static str>,
+    ) -> Vec<(String, serde_json::Value)> {
+        let mut mapped: Vec<(String, serde_json::Value)> = fields
+            .into_iter()
+            .filter(|(name, value)| !value.is_null() && !self.exclude.iter().any(|e| e == name))
+            .map(|(name, value)| {
+                let exported = self
+                    .rename
+                    .get(name)
+                    .cloned()
+                    .unwrap_or_else(|| default_name(name).unwrap_or(name).to_string());
+                (exported, value)
+            })
+            .collect();
+
+        let mut statics: Vec<_> = self.static_fields.iter().collect();
+        statics.sort_by(|a, b| a.0.cmp(b.0));
+        mapped.extend(statics.into_iter().map(|(k, v)| (k.clone(), v.clone())));
+        mapped
+    }
+}
+
+/// Origin of an exported record
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SiemRecordKind {
+    Activity,
+    Audit,
+}
+
+/// Activity event or audit trail normalized for export
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SiemRecord {
+    pub id: Uuid,
+    pub timestamp: DateTime<Utc>,
+    pub kind: SiemRecordKind,
+    pub category: String,
+    pub action: String,
+    pub actor: String,
+    pub resource: String,
+    /// Severity on the CEF scale (0-10)
+    pub severity: u8,
+    pub outcome: Option<String>,
+    pub session_id: Option<String>,
+    pub source: Option<String>,
+    pub details: serde_json::Value,
+}
+
+impl SiemRecord {
+    /// Canonical fields in export order
+    pub fn fields(&self) -> Vec<(&'static str, serde_json::Value)> {
+        use serde_json::Value;
+
+        let optional = |value: &Option<String>| value.clone().map_or(Value::Null, Value::String);
+        let details = match &self.details {
+            Value::Null => Value::Null,
+            Value::Object(map) if map.is_empty() => Value::Null,
+            details => details.clone(),
+        };
+
+        vec![
+            ("id", Value::String(self.id.to_string())),
+            ("timestamp", Value::String(self.timestamp.to_rfc3339())),
+            (
+                "kind",
+                Value::String(
+                    match self.kind {
+                        SiemRecordKind::Activity => "activity",
+                        SiemRecordKind::Audit => "audit",
+                    }
+                    .to_string(),
+                ),
+            ),
+            ("category", Value::String(self.category.clone())),
+            ("action", Value::String(self.action.clone())),
+            ("actor", Value::String(self.actor.clone())),
+            ("resource", Value::String(self.resource.clone())),
+            ("severity", Value::from(self.severity)),
+            ("outcome", optional(&self.outcome)),
+            ("session_id", optional(&self.session_id)),
+            ("source", optional(&self.source)),
+            ("details", details),
+        ]
+    }
+}
+
+impl From<&ActivityEvent> for SiemRecord {
+    fn from(event: &ActivityEvent) -> Self {
+        let severity = match event.level {
+            LogLevel::Debug => 1,
+            LogLevel::Info => 3,
+            LogLevel::Warn => 5,
+            LogLevel::Error => 7,
+            LogLevel::Critical => 10,
+        };
+
+        Self {
+            id: event.id,
+            timestamp: event.timestamp,
+            kind: SiemRecordKind::Activity,
+            category: category_name(&event.category),
+            action: event.action.clone(),
+            actor: event.actor.clone(),
+            resource: event.resource.clone(),
+            severity,
+            outcome: None,
+            session_id: event.session_id.clone(),
+            source: event.source.clone(),
+            details: event.details.clone(),
+        }
+    }
+}
+
+impl From<&AuditTrail> for SiemRecord {
+    fn from(trail: &AuditTrail) -> Self {
+        let (outcome, outcome_severity) = match &trail.outcome {
+            AuditOutcome::Success => ("success".to_string(), 3),
+            AuditOutcome::Failure(reason) => (format!("failure: {}", reason), 5),
+            AuditOutcome::Denied(reason) => (format!("denied: {}", reason), 7),
+            AuditOutcome::Blocked(reason) => (format!("blocked: {}", reason), 8),
+        };
+        // Risk scores are 0-100; take whichever is more severe
+        let severity = trail.risk_score.map_or(outcome_severity, |risk| {
+            outcome_severity.max(risk.min(100) / 10)
+        });
+
+        let mut details: serde_json::Map<String, serde_json::Value> = trail
+            .audit_data
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        if !trail.compliance_flags.is_empty() {
+            details.insert(
+                "compliance".to_string(),
+                serde_json::to_value(&trail.compliance_flags).unwrap_or_default(),
+            );
+        }
+
+        Self {
+            id: trail.id,
+            timestamp: trail.timestamp,
+            kind: SiemRecordKind::Audit,
+            category: format!("{:?}", trail.event_type),
+            action: trail.action.clone(),
+            actor: trail.actor.clone(),
+            resource: trail.resource.clone(),
+            severity,
+            outcome: Some(outcome),
+            session_id: trail.session_id.clone(),
+            source: trail.source_ip.clone(),
+            details: serde_json::Value::Object(details),
+        }
+    }
+}
+
+fn category_name(category: &EventCategory) -> String {
+    match category {
+        EventCategory::Custom(name) => name.clone(),
+        other => format!("{:?}", other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::AuditEventType;
+
+    #[test]
+    fn test_audit_record_severity_and_outcome() {
+        let trail = AuditTrail {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            event_type: AuditEventType::Authorization,
+            actor: "alice".to_string(),
+            action: "delete".to_string(),
+            resource: "/etc/passwd".to_string(),
+            outcome: AuditOutcome::Denied("not an admin".to_string()),
+            audit_data: HashMap::new(),
+            compliance_flags: Vec::new(),
+            risk_score: Some(95),
+            session_id: None,
+            source_ip: Some("10.0.0.1".to_string()),
+        };
+
+        let record = SiemRecord::from(&trail);
+        assert_eq!(record.kind, SiemRecordKind::Audit);
+        assert_eq!(record.severity, 9);
+        assert_eq!(record.outcome.as_deref(), Some("denied: not an admin"));
+        assert_eq!(record.category, "Authorization");
+    }
+
+    #[test]
+    fn test_default_queue_dir_is_in_app_storage() {
+        let queue_dir = SiemExportConfig::default_queue_dir();
+        assert!(queue_dir.ends_with("storage/siem-queue"));
+        assert!(!queue_dir.starts_with(std::env::temp_dir()));
+    }
+
+    #[test]
+    fn test_field_mapping_rename_exclude_and_static() {
+        let record = SiemRecord::from(&ActivityEvent {
+            action: "file_opened".to_string(),
+            actor: "bob".to_string(),
+            resource: "main.rs".to_string(),
+            session_id: Some("s1".to_string()),
+            ..Default::default()
+        });
+        let mapping = FieldMapping {
+            rename: HashMap::from([("actor".to_string(), "user".to_string())]),
+            exclude: vec!["session_id".to_string()],
+            static_fields: HashMap::from([("env".to_string(), serde_json::json!("prod"))]),
+        };
+
+        let fields = mapping.apply(record.fields(), |_| None);
+        let names: Vec<&str> = fields.iter().map(|(name, _)| name.as_str()).collect();
+
+        assert!(names.contains(&"user"));
+        assert!(!names.contains(&"actor"));
+        assert!(!names.contains(&"session_id"));
+        // Empty details and missing outcome are dropped
+        assert!(!names.contains(&"details"));
+        assert!(!names.contains(&"outcome"));
+        assert_eq!(fields.last().unwrap().0, "env");
+    }
+}
@@ -0,0 +1,199 @@
+//! Disk-backed queue of batches awaiting retry
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use tracing::warn;
+
+use super::SiemRecord;
+use crate::error::{ActivityLogError, ActivityLogResult};
+
+const BATCH_PREFIX: &str = "batch-";
+const BATCH_EXTENSION: &str = "jsonl";
+
+/// Batches that failed to send, persisted as JSON lines files
+///
+/// Each batch is written to its own file named by a monotonically increasing
+/// sequence number, so batches are retried in the order they failed.
+pub struct RetryQueue {
+    dir: PathBuf,
+    max_batches: usize,
+    next_seq: AtomicU64,
+}
+
+impl RetryQueue {
+    /// Open a queue directory, creating it if needed
+    ///
+    /// Queued batches are audit records, so the directory is restricted to
+    /// its owner.
+    pub fn open(dir: impl Into<PathBuf>, max_batches: usize) -> ActivityLogResult<Self> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+        restrict_to_owner(&dir)?;
+
+        let mut last_seq = 0;
+        for entry in std::fs::read_dir(&dir)? {
+            if let Some(seq) = batch_seq(&entry?.path()) {
+                last_seq = last_seq.max(seq);
+            }
+        }
+
+        Ok(Self {
+            dir,
+            max_batches: max_batches.max(1),
+            next_seq: AtomicU64::new(last_seq + 1),
+        })
+    }
+
+    /// Queue directory
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Persist a batch, dropping the oldest batches beyond the limit
+    pub async fn push(&self, records: &[SiemRecord]) -> ActivityLogResult<()> {
+        if records.is_empty() {
+            return Ok(());
+        }
+
+        let mut content = String::new();
+        for record in records {
+            content.push_str(&serde_json::to_string(record)?);
+            content.push('\n');
+        }
+
+        // Write then rename so a crash never leaves a partial batch behind
+        let seq = self.next_seq.fetch_add(1, Ordering::SeqCst);
+        let path = self
+            .dir
+            .join(format!("{}{:020}.{}", BATCH_PREFIX, seq, BATCH_EXTENSION));
+        let tmp = path.with_extension("tmp");
+        tokio::fs::write(&tmp, content).await?;
+        tokio::fs::rename(&tmp, &path).await?;
+
+        let pending = self.pending().await?;
+        if pending.len() > self.max_batches {
+            let excess = pending.len() - self.max_batches;
+            warn!(
+                "SIEM retry queue full, dropping {} oldest batch(es) from {}",
+                excess,
+                self.dir.display()
+            );
+            for old in &pending[..excess] {
+                self.remove(old).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Queued batch files, oldest first
+    pub async fn pending(&self) -> ActivityLogResult<Vec<PathBuf>> {
+        let mut batches = Vec::new();
+        let mut entries = tokio::fs::read_dir(&self.dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if let Some(seq) = batch_seq(&path) {
+                batches.push((seq, path));
+            }
+        }
+        batches.sort_by_key(|(seq, _)| *seq);
+        Ok(batches.into_iter().map(|(_, path)| path).collect())
+    }
+
+    /// Number of queued batches
+    pub async fn len(&self) -> ActivityLogResult<usize> {
+        Ok(self.pending().await?.len())
+    }
+
+    /// Check if no batches are queued
+    pub async fn is_empty(&self) -> ActivityLogResult<bool> {
+        Ok(self.len().await? == 0)
+    }
+
+    /// Read the records of a queued batch
+    pub async fn load(&self, path: &Path) -> ActivityLogResult<Vec<SiemRecord>> {
+        let content = tokio::fs::read_to_string(path).await?;
+        content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                serde_json::from_str(line).map_err(|e| ActivityLogError::SerializationError {
+                    message: format!("Corrupt SIEM batch {}: {}", path.display(), e),
+                })
+            })
+            .collect()
+    }
+
+    /// Remove a batch after it was delivered or found corrupt
+    pub async fn remove(&self, path: &Path) -> ActivityLogResult<()> {
+        match tokio::fs::remove_file(path).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn batch_seq(path: &Path) -> Option<u64> {
+    if path.extension()?.to_str()? != BATCH_EXTENSION {
+        return None;
+    }
+    path.file_stem()?
+        .to_str()?
+        .strip_prefix(BATCH_PREFIX)?
+        .parse()
+        .ok()
+}
+
+#[cfg(unix)]
+fn restrict_to_owner(dir: &Path) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o700))
+}
+
+#[cfg(not(unix))]
+fn restrict_to_owner(_dir: &Path) -> std::io::Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::ActivityEvent;
+
+    #[tokio::test]
+    async fn test_queue_survives_reopen_and_caps_batches() {
+        let dir = tempfile::tempdir().unwrap();
+        let record = SiemRecord::from(&ActivityEvent::default());
+
+        let queue = RetryQueue::open(dir.path(), 2).unwrap();
+        for _ in 0..3 {
+            queue.push(&[record.clone()]).await.unwrap();
+        }
+        assert_eq!(queue.len().await.unwrap(), 2);
+
+        let reopened = RetryQueue::open(dir.path(), 2).unwrap();
+        let pending = reopened.pending().await.unwrap();
+        assert_eq!(pending.len(), 2);
+        assert!(pending[0].ends_with(format!("batch-{:020}.jsonl", 2)));
+        assert_eq!(reopened.load(&pending[0]).await.unwrap(), vec![record]);
+
+        reopened.push(&[]).await.unwrap();
+        assert_eq!(reopened.next_seq.load(Ordering::SeqCst), 4);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_queue_dir_is_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let queue_dir = dir.path().join("queue");
+        std::fs::create_dir(&queue_dir).unwrap();
+        std::fs::set_permissions(&queue_dir, std::fs::Permissions::from_mode(0o777)).unwrap();
+
+        RetryQueue::open(&queue_dir, 2).unwrap();
+        let mode = std::fs::metadata(&queue_dir).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o700);
+    }
+}
@@ -0,0 +1,403 @@
+//! Transports that deliver formatted batches to a SIEM
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+use super::{SiemFormat, SiemFormatter, SiemRecord, SiemRecordKind};
+use crate::error::{ActivityLogError, ActivityLogResult};
+
+/// Delivers batches of records to a SIEM
+///
+/// A batch either succeeds as a whole or fails as a whole; failed batches
+/// are queued and resent by the exporter.
+#[async_trait]
+pub trait SiemTransport: Send + Sync {
+    /// Transport name for diagnostics
+    fn name(&self) -> &str;
+
+    /// Send a batch of records
+    async fn send(
+        &self,
+        records: &[SiemRecord],
+        formatter: &SiemFormatter,
+    ) -> ActivityLogResult<()>;
+}
+
+/// RFC 5424 syslog over TCP, with optional RFC 5425 TLS
+///
+/// Messages use octet-counting framing so multi-line payloads survive.
+pub struct SyslogTransport {
+    host: String,
+    port: u16,
+    app_name: String,
+    hostname: String,
+    facility: u8,
+    tls: Option<tokio_native_tls::TlsConnector>,
+    timeout: Duration,
+}
+
+impl SyslogTransport {
+    /// Create a plain TCP syslog transport
+    pub fn new(host: impl Into<String>, port: u16, app_name: impl Into<String>) -> Self {
+        let hostname = std::env::var("HOSTNAME")
+            .ok()
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| "-".to_string());
+
+        Self {
+            host: host.into(),
+            port,
+            app_name: header_token(&app_name.into()),
+            hostname: header_token(&hostname),
+            // log audit
+            facility: 13,
+            tls: None,
+            timeout: Duration::from_secs(10),
+        }
+    }
+
+    /// Wrap the connection in TLS
+    pub fn with_tls(mut self, verify: bool) -> ActivityLogResult<Self> {
+        let connector = tokio_native_tls::native_tls::TlsConnector::builder()
+            .danger_accept_invalid_certs(!verify)
+            .danger_accept_invalid_hostnames(!verify)
+            .build()
+            .map_err(|e| ActivityLogError::ConfigError {
+                field: "siem.tls".to_string(),
+                message: e.to_string(),
+            })?;
+        self.tls = Some(connector.into());
+        Ok(self)
+    }
+
+    /// Set the syslog facility (0-23)
+    pub fn with_facility(mut self, facility: u8) -> Self {
+        self.facility = facility.min(23);
+        self
+    }
+
+    /// Set the connect and write timeout
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Build the octet-counted frame for a record
+    pub fn frame(&self, record: &SiemRecord, formatter: &SiemFormatter) -> String {
+        let severity = match record.severity {
+            10 => 2,    // critical
+            7..=9 => 3, // error
+            5 | 6 => 4, // warning
+            _ => 6,     // informational
+        };
+        let msg_id = match record.kind {
+            SiemRecordKind::Activity => "activity",
+            SiemRecordKind::Audit => "audit",
+        };
+        let message = format!(
+            "<{}>1 {} {} {} {} {} - {}",
+            self.facility as u16 * 8 + severity,
+            record
+                .timestamp
+                .to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            self.hostname,
+            self.app_name,
+            std::process::id(),
+            msg_id,
+            formatter.render(record)
+        );
+        format!("{} {}", message.len(), message)
+    }
+
+    async fn write_batch<S>(&self, stream: &mut S, payload: &[u8]) -> ActivityLogResult<()>
+    where
+        S: AsyncWrite + Unpin,
+    {
+        stream.write_all(payload).await?;
+        stream.flush().await?;
+        stream.shutdown().await?;
+        Ok(())
+    }
+
+    async fn deliver(&self, payload: &[u8]) -> ActivityLogResult<()> {
+        let mut tcp = TcpStream::connect((self.host.as_str(), self.port)).await?;
+        match &self.tls {
+            Some(connector) => {
+                let mut tls = connector.connect(&self.host, tcp).await.map_err(|e| {
+                    ActivityLogError::ExportError {
+                        message: format!("TLS handshake with {} failed: {}", self.host, e),
+                    }
+                })?;
+                self.write_batch(&mut tls, payload).await
+            }
+            None => self.write_batch(&mut tcp, payload).await,
+        }
+    }
+}
+
+#[async_trait]
+impl SiemTransport for SyslogTransport {
+    fn name(&self) -> &str {
+        "syslog"
+    }
+
+    async fn send(
+        &self,
+        records: &[SiemRecord],
+        formatter: &SiemFormatter,
+    ) -> ActivityLogResult<()> {
+        let payload: String = records
+            .iter()
+            .map(|record| self.frame(record, formatter))
+            .collect();
+
+        tokio::time::timeout(self.timeout, self.deliver(payload.as_bytes()))
+            .await
+            .map_err(|_| ActivityLogError::ExportError {
+                message: format!("Timed out sending to {}:{}", self.host, self.port),
+            })?
+    }
+}
+
+/// Splunk HTTP Event Collector transport
+pub struct SplunkHecTransport {
+    client: reqwest::Client,
+    endpoint: String,
+    token: String,
+    index: Option<String>,
+    source: Option<String>,
+    sourcetype: Option<String>,
+}
+
+impl SplunkHecTransport {
+    /// Create a transport for a collector base URL
+    pub fn new(url: &str, token: impl Into<String>, verify_tls: bool) -> ActivityLogResult<Self> {
+        let client = reqwest::Client::builder()
+            .danger_accept_invalid_certs(!verify_tls)
+            .timeout(Duration::from_secs(30))
+            .build()
+            .map_err(|e| ActivityLogError::ConfigError {
+                field: "siem.splunk".to_string(),
+                message: e.to_string(),
+            })?;
+
+        Ok(Self {
+            client,
+            endpoint: format!("{}/services/collector/event", url.trim_end_matches('/')),
+            token: token.into(),
+            index: None,
+            source: None,
+            sourcetype: None,
+        })
+    }
+
+    /// Send events to a specific index
+    pub fn with_index(mut self, index: Option<String>) -> Self {
+        self.index = index;
+        self
+    }
+
+    /// Set the event source
+    pub fn with_source(mut self, source: Option<String>) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// Set the event sourcetype
+    pub fn with_sourcetype(mut self, sourcetype: Option<String>) -> Self {
+        self.sourcetype = sourcetype;
+        self
+    }
+
+    /// HEC event envelope for a record
+    pub fn envelope(&self, record: &SiemRecord, formatter: &SiemFormatter) -> Value {
+        let (event, default_sourcetype) = match formatter.wire_format() {
+            SiemFormat::Json => (formatter.to_json(record), "_json"),
+            SiemFormat::Cef => (Value::String(formatter.to_cef(record)), "cef"),
+        };
+
+        let mut envelope = json!({
+            "time": record.timestamp.timestamp_millis() as f64 / 1000.0,
+            "sourcetype": self.sourcetype.as_deref().unwrap_or(default_sourcetype),
+            "event": event,
+        });
+        if let Some(index) = &self.index {
+            envelope["index"] = json!(index);
+        }
+        if let Some(source) = &self.source {
+            envelope["source"] = json!(source);
+        }
+        envelope
+    }
+}
+
+#[async_trait]
+impl SiemTransport for SplunkHecTransport {
+    fn name(&self) -> &str {
+        "splunk-hec"
+    }
+
+    async fn send(
+        &self,
+        records: &[SiemRecord],
+        formatter: &SiemFormatter,
+    ) -> ActivityLogResult<()> {
+        // HEC accepts concatenated event objects in one request
+        let body = records
+            .iter()
+            .map(|record| self.envelope(record, formatter).to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let response = self
+            .client
+            .post(&self.endpoint)
+            .header("Authorization", format!("Splunk {}", self.token))
+            .header("Content-Type", "application/json")
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| ActivityLogError::ExportError {
+                message: format!("Splunk HEC request failed: {}", e),
+            })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(ActivityLogError::ExportError {
+                message: format!("Splunk HEC returned {}: {}", status, text),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Syslog header fields are printable ASCII without spaces
+fn header_token(value: &str) -> String {
+    let token: String = value
+        .chars()
+        .filter(|c| c.is_ascii_graphic())
+        .take(48)
+        .collect();
+    if token.is_empty() {
+        "-".to_string()
+    } else {
+        token
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{ActivityEvent, LogLevel};
+    use crate::siem::{CefHeader, FieldMapping};
+    use tokio::io::AsyncReadExt;
+    use tokio::net::TcpListener;
+
+    fn formatter(format: SiemFormat) -> SiemFormatter {
+        SiemFormatter::new(format, CefHeader::default(), FieldMapping::default())
+    }
+
+    #[tokio::test]
+    async fn test_syslog_octet_counted_frames() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut received = String::new();
+            socket.read_to_string(&mut received).await.unwrap();
+            received
+        });
+
+        let transport = SyslogTransport::new("127.0.0.1", port, "ricecoder");
+        let records = vec![
+            SiemRecord::from(&ActivityEvent {
+                level: LogLevel::Warn,
+                action: "first".to_string(),
+                ..Default::default()
+            }),
+            SiemRecord::from(&ActivityEvent {
+                action: "second".to_string(),
+                ..Default::default()
+            }),
+        ];
+        transport
+            .send(&records, &formatter(SiemFormat::Cef))
+            .await
+            .unwrap();
+
+        let received = server.await.unwrap();
+        let (len, rest) = received.split_once(' ').unwrap();
+        let first = &rest[..len.parse::<usize>().unwrap()];
+        // facility 13 * 8 + warning
+        assert!(first.starts_with("<108>1 "));
+        assert!(first.contains(" ricecoder "));
+        assert!(first.contains("|first|"));
+        assert!(rest[first.len()..].contains("|second|"));
+    }
+
+    #[tokio::test]
+    async fn test_splunk_hec_posts_events_with_token() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            loop {
+                let n = socket.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request);
+                if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                    let length = head
+                        .lines()
+                        .find_map(|line| {
+                            line.to_ascii_lowercase()
+                                .strip_prefix("content-length: ")
+                                .map(|v| v.trim().parse::<usize>().unwrap())
+                        })
+                        .unwrap_or(0);
+                    if body.len() >= length {
+                        break;
+                    }
+                }
+                if n == 0 {
+                    break;
+                }
+            }
+            socket
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\n{}")
+                .await
+                .unwrap();
+            String::from_utf8(request).unwrap()
+        });
+
+        let transport = SplunkHecTransport::new(&format!("http://{}/", addr), "secret", true)
+            .unwrap()
+            .with_index(Some("security".to_string()));
+        let record = SiemRecord::from(&ActivityEvent {
+            action: "login".to_string(),
+            ..Default::default()
+        });
+        transport
+            .send(&[record], &formatter(SiemFormat::Json))
+            .await
+            .unwrap();
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("POST /services/collector/event "));
+        assert!(request
+            .to_ascii_lowercase()
+            .contains("authorization: splunk secret"));
+        let body = request.split_once("\r\n\r\n").unwrap().1;
+        let envelope: Value = serde_json::from_str(body).unwrap();
+        assert_eq!(envelope["index"], "security");
+        assert_eq!(envelope["sourcetype"], "_json");
+        assert_eq!(envelope["event"]["action"], "login");
+    }
+}
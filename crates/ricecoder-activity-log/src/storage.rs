@@ -1,16 +1,18 @@
 //! Log storage and retention management
 
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 
 use async_trait::async_trait;
-use chrono::{DateTime, Duration, Utc};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use tokio::io::AsyncWriteExt;
 
 use crate::{
+    archive::{LogArchiver, TieringPolicy},
     error::{ActivityLogError, ActivityLogResult},
     events::{ActivityEvent, EventFilter},
     logger::LogStorage,
+    query::{LogAggregation, LogPage, LogQuery},
 };
 
 /// Retention policy for log management
@@ -26,10 +28,13 @@ pub struct RetentionPolicy {
     pub extended_retention_categories: Vec<crate::events::EventCategory>,
     /// Extended retention period for special categories (in days)
     pub extended_retention_days: u32,
-    /// Whether to compress old logs
+    /// Whether to compress old logs into the warm tier
     pub compress_old_logs: bool,
-    /// Archive destination (None means delete)
+    /// Cold tier destination for expired logs (None means delete)
     pub archive_destination: Option<String>,
+    /// Hot/warm tier transitions
+    #[serde(default)]
+    pub tiering: TieringPolicy,
 }
 
 /// Storage statistics
@@ -56,6 +61,7 @@ pub struct FileStorage {
     base_path: std::path::PathBuf,
     retention_policy: RetentionPolicy,
     stats: std::sync::Mutex<StorageStats>,
+    archiver: Arc<LogArchiver>,
 }
 
 impl FileStorage {
@@ -76,19 +82,72 @@ impl FileStorage {
             last_maintenance: None,
         };
 
+        let archiver = Arc::new(LogArchiver::open(&base_path, retention_policy.clone())?);
+
         Ok(Self {
             base_path,
             retention_policy,
             stats: std::sync::Mutex::new(stats),
+            archiver,
         })
     }
 
+    /// Retention policy applied during maintenance
+    pub fn retention_policy(&self) -> &RetentionPolicy {
+        &self.retention_policy
+    }
+
+    /// Archive holding logs that have left the hot tier
+    pub fn archiver(&self) -> &Arc<LogArchiver> {
+        &self.archiver
+    }
+
     /// Get file path for a date
     fn get_file_path(&self, date: &DateTime<Utc>) -> std::path::PathBuf {
         let date_str = date.format("%Y-%m-%d").to_string();
         self.base_path.join(format!("activity-{}.log", date_str))
     }
 
+    /// List daily log files within the query's time range, newest first
+    fn day_files(&self, query: &LogQuery) -> Vec<(NaiveDate, std::path::PathBuf)> {
+        let start = query.start_time.map(|start| start.date_naive());
+        let end = query.end_time.map(|end| end.date_naive());
+
+        let mut files: Vec<_> = std::fs::read_dir(&self.base_path)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|entry| {
+                let name = entry.file_name();
+                let date = name
+                    .to_str()?
+                    .strip_prefix("activity-")?
+                    .strip_suffix(".log")?
+                    .parse::<NaiveDate>()
+                    .ok()?;
+                Some((date, entry.path()))
+            })
+            .filter(|(date, _)| start.map_or(true, |start| *date >= start))
+            .filter(|(date, _)| end.map_or(true, |end| *date <= end))
+            .collect();
+
+        files.sort_by(|a, b| b.0.cmp(&a.0));
+        files
+    }
+
+    /// Read the events of one daily log file that match a query
+    async fn read_matching(
+        path: &std::path::Path,
+        query: &LogQuery,
+    ) -> ActivityLogResult<Vec<ActivityEvent>> {
+        let content = tokio::fs::read_to_string(path).await?;
+        Ok(content
+            .lines()
+            .filter_map(|line| serde_json::from_str::<ActivityEvent>(line).ok())
+            .filter(|event| query.matches(event))
+            .collect())
+    }
+
     /// Update storage statistics
     fn update_stats(&self, event: &ActivityEvent, added: bool) {
         let mut stats = self.stats.lock().unwrap();
@@ -186,35 +245,59 @@ impl LogStorage for FileStorage {
     }
 
     async fn maintenance(&self) -> ActivityLogResult<()> {
-        let cutoff_date = Utc::now() - Duration::days(self.retention_policy.max_age_days as i64);
-
-        // Remove old files
-        let mut removed_files = 0;
-        if let Ok(entries) = std::fs::read_dir(&self.base_path) {
-            for entry in entries.flatten() {
-                if let Ok(metadata) = entry.metadata() {
-                    if metadata.modified().is_ok() {
-                        // This is a simplified check - you'd need proper date parsing from filenames
-                        if let Some(filename) = entry.file_name().to_str() {
-                            if filename.starts_with("activity-") && filename.ends_with(".log") {
-                                // Check if file is older than retention period
-                                // (This is a placeholder - real implementation would parse dates)
-                                let _ = cutoff_date;
-                                removed_files += 1;
-                            }
-                        }
-                    }
-                }
-            }
-        }
+        let report = self.archiver.run_transitions().await?;
 
         // Update maintenance timestamp
         let mut stats = self.stats.lock().unwrap();
         stats.last_maintenance = Some(Utc::now());
 
-        tracing::info!("Maintenance completed: {} old files removed", removed_files);
+        tracing::info!(
+            "Maintenance completed: {} files archived, {} exported, {} removed",
+            report.compressed,
+            report.exported,
+            report.deleted
+        );
         Ok(())
     }
+
+    async fn query(&self, query: &LogQuery) -> ActivityLogResult<LogPage> {
+        let cursor = query.decoded_cursor()?;
+        let mut matching = Vec::new();
+
+        // Files are per day, so once a page (plus one event to detect more
+        // results) is filled, older files cannot contribute to it.
+        for (date, path) in self.day_files(query) {
+            if cursor
+                .as_ref()
+                .is_some_and(|cursor| date > cursor.timestamp.date_naive())
+            {
+                continue;
+            }
+
+            let events = Self::read_matching(&path, query).await?;
+            matching.extend(
+                events
+                    .into_iter()
+                    .filter(|event| cursor.as_ref().map_or(true, |c| c.precedes(event))),
+            );
+
+            if matching.len() > query.page_size {
+                break;
+            }
+        }
+
+        query.paginate(matching)
+    }
+
+    async fn aggregate(&self, query: &LogQuery) -> ActivityLogResult<LogAggregation> {
+        let mut aggregation = LogAggregation::default();
+        for (_, path) in self.day_files(query) {
+            for event in Self::read_matching(&path, query).await? {
+                aggregation.add(&event);
+            }
+        }
+        Ok(aggregation)
+    }
 }
 
 /// Database-backed log storage (placeholder for future implementation)
@@ -281,6 +364,56 @@ impl Default for RetentionPolicy {
             extended_retention_days: 365, // 1 year for security/compliance
             compress_old_logs: true,
             archive_destination: None,
+            tiering: TieringPolicy::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{EventCategory, LogLevel};
+
+    #[tokio::test]
+    async fn test_file_storage_query_pages_across_days() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage =
+            FileStorage::new(dir.path().to_path_buf(), RetentionPolicy::default()).unwrap();
+
+        let now = Utc::now();
+        for days_ago in 0..3 {
+            for category in [EventCategory::Security, EventCategory::Network] {
+                let event = ActivityEvent {
+                    timestamp: now - Duration::days(days_ago),
+                    ..ActivityEvent::new(
+                        LogLevel::Info,
+                        category,
+                        format!("day-{}", days_ago),
+                        "alice".to_string(),
+                        "/repo".to_string(),
+                    )
+                };
+                storage.store_event(&event).await.unwrap();
+            }
         }
+
+        let query = LogQuery::new()
+            .category(EventCategory::Security)
+            .page_size(2);
+        let first = storage.query(&query).await.unwrap();
+        let actions: Vec<_> = first.events.iter().map(|e| e.action.as_str()).collect();
+        assert_eq!(actions, vec!["day-0", "day-1"]);
+
+        let second = storage
+            .query(&query.clone().after(first.next_cursor.unwrap()))
+            .await
+            .unwrap();
+        assert_eq!(second.events.len(), 1);
+        assert_eq!(second.events[0].action, "day-2");
+        assert!(second.next_cursor.is_none());
+
+        let aggregation = storage.aggregate(&LogQuery::new()).await.unwrap();
+        assert_eq!(aggregation.total, 6);
+        assert_eq!(aggregation.by_category[&EventCategory::Network], 3);
     }
 }
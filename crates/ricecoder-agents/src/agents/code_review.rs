@@ -1,14 +1,16 @@
 //! Code Review Agent for analyzing code quality, security, and best practices
 
-use std::collections::HashMap;
+use std::{collections::HashMap, path::PathBuf};
 
 use async_trait::async_trait;
+use ricecoder_research::{is_dependency_file, AdvisorySeverity, DependencyAnalyzer};
+use tracing::warn;
 
 use crate::{
     error::Result,
     models::{
-        AgentConfig, AgentInput, AgentMetrics, AgentOutput, ConfigSchema, Finding, Severity,
-        Suggestion, TaskType,
+        AgentConfig, AgentInput, AgentMetrics, AgentOutput, CodeLocation, ConfigSchema, Finding,
+        Severity, Suggestion, TaskType,
     },
     Agent,
 };
@@ -30,12 +32,17 @@ use crate::{
 /// - `enable_best_practice_checks`: Enable best practice checking (default: true)
 /// - `max_complexity`: Maximum allowed cyclomatic complexity (default: 10)
 /// - `max_function_length`: Maximum allowed function length in lines (default: 50)
+///
+/// When a dependency analyzer is attached, tasks touching dependency manifests
+/// or lockfiles are also checked for dependencies with known vulnerabilities.
 #[derive(Debug, Clone)]
 pub struct CodeReviewAgent {
     /// Agent configuration
     config: AgentConfig,
     /// Performance metrics
     metrics: AgentMetrics,
+    /// Analyzer for dependency-related changes
+    dependency_analyzer: Option<DependencyAnalyzer>,
 }
 
 impl CodeReviewAgent {
@@ -44,6 +51,7 @@ impl CodeReviewAgent {
         Self {
             config: AgentConfig::default(),
             metrics: AgentMetrics::default(),
+            dependency_analyzer: None,
         }
     }
 
@@ -52,9 +60,16 @@ impl CodeReviewAgent {
         Self {
             config,
             metrics: AgentMetrics::default(),
+            dependency_analyzer: None,
         }
     }
 
+    /// Check dependency manifests in the task for known vulnerabilities
+    pub fn with_dependency_analyzer(mut self, analyzer: DependencyAnalyzer) -> Self {
+        self.dependency_analyzer = Some(analyzer);
+        self
+    }
+
     /// Check if a specific check is enabled
     fn is_check_enabled(&self, check_name: &str) -> bool {
         self.config
@@ -182,6 +197,79 @@ impl CodeReviewAgent {
         findings
     }
 
+    /// Report vulnerable dependencies declared in the task's manifests
+    async fn review_dependencies(&self, input: &AgentInput) -> Vec<Finding> {
+        let Some(analyzer) = &self.dependency_analyzer else {
+            return Vec::new();
+        };
+        if !self.is_check_enabled("security_checks") {
+            return Vec::new();
+        }
+
+        let root = &input.context.root;
+        let files: Vec<PathBuf> = input
+            .task
+            .target
+            .files
+            .iter()
+            .filter(|file| is_dependency_file(file))
+            .map(|file| root.join(file))
+            .collect();
+        if files.is_empty() {
+            return Vec::new();
+        }
+
+        let report = match analyzer.audit(root).await {
+            Ok(report) => report.for_files(&files),
+            Err(e) => {
+                warn!("Dependency audit failed for {:?}: {}", root, e);
+                return Vec::new();
+            }
+        };
+
+        report
+            .findings
+            .iter()
+            .map(|finding| {
+                let dependency = &finding.dependency;
+                let mut ids = vec![finding.advisory_id.as_str()];
+                ids.extend(finding.cve_ids());
+                Finding {
+                    id: format!("security-dependency-{}", Self::generate_id()),
+                    severity: if finding.severity >= AdvisorySeverity::High {
+                        Severity::Critical
+                    } else {
+                        Severity::Warning
+                    },
+                    category: "security".to_string(),
+                    message: format!(
+                        "{} {} is affected by {}: {}",
+                        dependency.name,
+                        dependency.resolved_version.as_deref().unwrap_or("unknown"),
+                        ids.join(", "),
+                        finding.summary
+                    ),
+                    location: Some(CodeLocation {
+                        file: dependency
+                            .manifest
+                            .strip_prefix(root)
+                            .unwrap_or(&dependency.manifest)
+                            .to_path_buf(),
+                        line: 1,
+                        column: 1,
+                    }),
+                    suggestion: Some(match finding.recommended_version() {
+                        Some(fixed) => format!("Upgrade {} to {} or later", dependency.name, fixed),
+                        None => format!(
+                            "Replace or remove {}; no fixed version is known",
+                            dependency.name
+                        ),
+                    }),
+                }
+            })
+            .collect()
+    }
+
     /// Analyze performance optimization opportunities
     fn analyze_performance(&self, code: &str) -> Vec<Finding> {
         let mut findings = Vec::new();
@@ -664,7 +752,7 @@ impl Agent for CodeReviewAgent {
         matches!(task_type, TaskType::CodeReview | TaskType::SecurityAnalysis)
     }
 
-    async fn execute(&self, _input: AgentInput) -> Result<AgentOutput> {
+    async fn execute(&self, input: AgentInput) -> Result<AgentOutput> {
         // For now, we'll use a simple implementation that analyzes the code
         // In a real implementation, this would use an AI provider
 
@@ -679,6 +767,7 @@ impl Agent for CodeReviewAgent {
         findings.extend(self.scan_security(code));
         findings.extend(self.analyze_performance(code));
         findings.extend(self.check_best_practices(code));
+        findings.extend(self.review_dependencies(&input).await);
 
         // Create suggestions from findings
         for finding in &findings {
@@ -881,6 +970,71 @@ mod tests {
         assert_eq!(output.metadata.agent_id, "code-review-agent");
     }
 
+    #[tokio::test]
+    async fn test_code_review_agent_reports_vulnerable_dependencies() {
+        use std::sync::Arc;
+
+        use ricecoder_research::OfflineOsvDatabase;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("package.json"),
+            r#"{"dependencies": {"lodash": "4.17.20"}}"#,
+        )
+        .unwrap();
+        let database = OfflineOsvDatabase::new();
+        database
+            .add_json(
+                r#"{"id": "GHSA-35jh-r3h4-6jhm", "aliases": ["CVE-2021-23337"],
+                    "summary": "Command Injection in lodash",
+                    "affected": [{"package": {"ecosystem": "npm", "name": "lodash"},
+                                  "ranges": [{"type": "SEMVER", "events": [{"introduced": "0"}, {"fixed": "4.17.21"}]}]}],
+                    "database_specific": {"severity": "HIGH"}}"#,
+            )
+            .unwrap();
+        let agent = CodeReviewAgent::new()
+            .with_dependency_analyzer(DependencyAnalyzer::new().with_database(Arc::new(database)));
+
+        let input = |file: &str| AgentInput {
+            task: AgentTask {
+                id: "task-1".to_string(),
+                task_type: TaskType::SecurityAnalysis,
+                target: TaskTarget {
+                    files: vec![PathBuf::from(file)],
+                    scope: TaskScope::File,
+                },
+                options: TaskOptions::default(),
+            },
+            context: ProjectContext {
+                name: "test-project".to_string(),
+                root: dir.path().to_path_buf(),
+            },
+            config: AgentConfig::default(),
+        };
+
+        let findings = agent.review_dependencies(&input("package.json")).await;
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Critical);
+        assert_eq!(
+            findings[0].message,
+            "lodash 4.17.20 is affected by GHSA-35jh-r3h4-6jhm, CVE-2021-23337: Command Injection in lodash"
+        );
+        assert_eq!(
+            findings[0].suggestion.as_deref(),
+            Some("Upgrade lodash to 4.17.21 or later")
+        );
+        assert_eq!(
+            findings[0].location.as_ref().unwrap().file,
+            PathBuf::from("package.json")
+        );
+
+        // Changes that do not touch dependencies are not audited
+        assert!(agent
+            .review_dependencies(&input("src/main.rs"))
+            .await
+            .is_empty());
+    }
+
     #[test]
     fn test_has_naming_violations() {
         let agent = CodeReviewAgent::new();
@@ -15,7 +15,11 @@ use serde_json::Value;
 use tracing::{debug, error, info, trace, warn};
 use uuid::Uuid;
 
-use ricecoder_providers::provider::Provider;
+use ricecoder_providers::{
+    models::ModelParams,
+    prompt_library::{BindingTarget, PromptAuditLog, PromptLibrary, PromptUsage, ResolvedPrompt},
+    provider::Provider,
+};
 
 use super::context::ChatContext;
 use super::error::{ChatError, Result};
@@ -62,6 +66,18 @@ pub struct ChatService {
 
     /// Default model to use
     default_model: Option<String>,
+
+    /// Model parameters for requests that do not set their own
+    default_params: ModelParams,
+
+    /// Versioned prompt library and the mode/agent whose binding to use
+    prompt_binding: Option<(Arc<PromptLibrary>, BindingTarget)>,
+
+    /// Variables for rendering the bound prompt
+    prompt_variables: HashMap<String, String>,
+
+    /// Audit trail of which prompt version produced each response
+    prompt_audit: Option<Arc<PromptAuditLog>>,
 }
 
 impl ChatService {
@@ -76,6 +92,14 @@ impl ChatService {
             approval_callback: None,
             working_directory: std::env::current_dir().unwrap_or_default(),
             default_model: None,
+            default_params: ModelParams {
+                temperature: Some(0.7),
+                max_tokens: Some(4096),
+                ..ModelParams::default()
+            },
+            prompt_binding: None,
+            prompt_variables: HashMap::new(),
+            prompt_audit: None,
         }
     }
 
@@ -121,6 +145,84 @@ impl ChatService {
         self
     }
 
+    /// Set the default model parameters (e.g. from the active mode)
+    pub fn with_model_params(mut self, params: ModelParams) -> Self {
+        self.default_params = params;
+        self
+    }
+
+    /// Use the prompt bound to a mode or agent instead of the default system prompt
+    pub fn with_prompt_binding(
+        mut self,
+        library: Arc<PromptLibrary>,
+        target: BindingTarget,
+    ) -> Self {
+        self.prompt_binding = Some((library, target));
+        self
+    }
+
+    /// Set the variables used to render the bound prompt
+    pub fn with_prompt_variables(mut self, variables: HashMap<String, String>) -> Self {
+        self.prompt_variables = variables;
+        self
+    }
+
+    /// Record which prompt version produced each response
+    pub fn with_prompt_audit(mut self, audit: Arc<PromptAuditLog>) -> Self {
+        self.prompt_audit = Some(audit);
+        self
+    }
+
+    /// Resolve the system prompt for a conversation
+    ///
+    /// A bound prompt wins over the default system prompt; A/B variants are
+    /// picked per session so a conversation keeps the same prompt.
+    fn resolve_system_prompt(
+        &self,
+        context: &ChatContext,
+    ) -> Result<(Option<String>, Option<ResolvedPrompt>)> {
+        match &self.prompt_binding {
+            Some((library, target)) => {
+                let resolved = library.resolve(
+                    target,
+                    &context.session_id.to_string(),
+                    &self.prompt_variables,
+                )?;
+                Ok((Some(resolved.text.clone()), Some(resolved)))
+            }
+            None => Ok((self.default_system_prompt.clone(), None)),
+        }
+    }
+
+    /// Audit the prompt behind a response
+    fn record_prompt(
+        &self,
+        prompt: Option<&ResolvedPrompt>,
+        context: &ChatContext,
+        model: &str,
+    ) -> Option<PromptUsage> {
+        let prompt = prompt?;
+        let mut usage =
+            PromptUsage::new(prompt, model).with_session(context.session_id.to_string());
+        if let Some((_, target)) = &self.prompt_binding {
+            usage = usage.with_target(target.clone());
+        }
+        if let Some(audit) = &self.prompt_audit {
+            if let Err(e) = audit.record(usage.clone()) {
+                warn!("Failed to record prompt usage: {}", e);
+            }
+        }
+        Some(usage)
+    }
+
+    /// Reject parameters the requested model cannot take
+    fn validate_params(&self, request: &ricecoder_providers::models::ChatRequest) -> Result<()> {
+        if let Some(model) = self.provider.models().iter().find(|m| m.id == request.model) {
+            request.validate_params(model)?;
+        }
+        Ok(())
+    }
+
     /// Get the provider name
     pub fn provider_name(&self) -> &str {
         self.provider.name()
@@ -156,25 +258,32 @@ impl ChatService {
             });
 
         let mut messages = context.to_simple_messages();
+        let (system_prompt, prompt) = self.resolve_system_prompt(context)?;
 
         // Add system prompt if available
-        if let Some(system) = &self.default_system_prompt {
+        if let Some(system) = system_prompt {
             messages.insert(
                 0,
                 ricecoder_providers::models::Message {
                     role: "system".to_string(),
-                    content: system.clone(),
+                    content: system,
                 },
             );
         }
 
-        let request = ricecoder_providers::models::ChatRequest {
+        let mut request = ricecoder_providers::models::ChatRequest {
             model: model_name.clone(),
             messages,
-            temperature: Some(0.7),
-            max_tokens: Some(4096),
+            temperature: None,
+            max_tokens: None,
             stream: false,
+            top_p: None,
+            stop: None,
+            seed: None,
+            reasoning_effort: None,
         };
+        request.apply_defaults(&self.default_params);
+        self.validate_params(&request)?;
 
         // Send to provider
         let response = self.provider.chat(request).await?;
@@ -189,6 +298,7 @@ impl ChatService {
             response.usage.completion_tokens,
         );
         context.add_usage(usage.clone());
+        let prompt = self.record_prompt(prompt.as_ref(), context, &response.model);
 
         Ok(ChatResponse {
             content: response.content,
@@ -196,6 +306,7 @@ impl ChatService {
             usage,
             stop_reason: StopReason::EndTurn,
             tool_calls: Vec::new(),
+            prompt,
         })
     }
 
@@ -231,6 +342,7 @@ impl ChatService {
         let mut total_usage = Usage::default();
         let mut all_tool_calls: Vec<ToolCall> = Vec::new();
         let mut recent_tool_signatures: Vec<String> = Vec::new();
+        let (system_prompt, prompt) = self.resolve_system_prompt(context)?;
 
         while iteration < self.max_tool_iterations {
             iteration += 1;
@@ -240,7 +352,7 @@ impl ChatService {
             let mut messages = context.to_simple_messages();
 
             // Add system prompt if available
-            if let Some(system) = &self.default_system_prompt {
+            if let Some(system) = &system_prompt {
                 messages.insert(
                     0,
                     ricecoder_providers::models::Message {
@@ -250,13 +362,19 @@ impl ChatService {
                 );
             }
 
-            let request = ricecoder_providers::models::ChatRequest {
+            let mut request = ricecoder_providers::models::ChatRequest {
                 model: model_name.clone(),
                 messages,
-                temperature: Some(0.7),
-                max_tokens: Some(4096),
+                temperature: None,
+                max_tokens: None,
                 stream: false,
+                top_p: None,
+                stop: None,
+                seed: None,
+                reasoning_effort: None,
             };
+            request.apply_defaults(&self.default_params);
+            self.validate_params(&request)?;
 
             // Send to provider
             let response = self.provider.chat(request).await?;
@@ -279,6 +397,7 @@ impl ChatService {
                 let assistant_msg = ChatMessage::assistant(&response.content);
                 context.add_message(assistant_msg);
                 context.add_usage(total_usage.clone());
+                let prompt = self.record_prompt(prompt.as_ref(), context, &response.model);
 
                 return Ok(ChatResponse {
                     content: response.content,
@@ -286,6 +405,7 @@ impl ChatService {
                     usage: total_usage,
                     stop_reason: StopReason::EndTurn,
                     tool_calls: all_tool_calls,
+                    prompt,
                 });
             }
 
@@ -297,6 +417,7 @@ impl ChatService {
                 let assistant_msg = ChatMessage::assistant(&response.content);
                 context.add_message(assistant_msg);
                 context.add_usage(total_usage.clone());
+                let prompt = self.record_prompt(prompt.as_ref(), context, &response.model);
 
                 return Ok(ChatResponse {
                     content: response.content,
@@ -304,6 +425,7 @@ impl ChatService {
                     usage: total_usage,
                     stop_reason: StopReason::EndTurn,
                     tool_calls: all_tool_calls,
+                    prompt,
                 });
             }
 
@@ -491,6 +613,9 @@ pub struct ChatResponse {
 
     /// Tool calls executed
     pub tool_calls: Vec<ToolCall>,
+
+    /// Prompt version that produced this response, when a bound prompt was used
+    pub prompt: Option<PromptUsage>,
 }
 
 /// A tool call executed during the conversation
@@ -537,6 +662,7 @@ mod tests {
                 name: "Mock Model".to_string(),
                 provider: "mock".to_string(),
                 context_window: 4096,
+                max_output_tokens: None,
                 capabilities: vec![],
                 pricing: None,
                 is_free: true,
@@ -601,6 +727,54 @@ mod tests {
         assert_eq!(context.messages.len(), 2); // user + assistant
     }
 
+    #[tokio::test]
+    async fn test_send_message_records_bound_prompt() {
+        let provider = Arc::new(MockProvider {
+            response: "Done".to_string(),
+        });
+        let mut library = PromptLibrary::new();
+        library.add_template(ricecoder_storage::VersionedPrompt {
+            id: "coder".to_string(),
+            version: 2,
+            description: String::new(),
+            variables: Default::default(),
+            body: "You work on {{project}}.".to_string(),
+        });
+        let target = BindingTarget::Mode("code".to_string());
+        library.bind(
+            target.clone(),
+            ricecoder_storage::PromptBinding {
+                prompt: "coder".to_string(),
+                version: None,
+                variants: Vec::new(),
+            },
+        );
+        let audit = Arc::new(PromptAuditLog::new());
+        let service = ChatService::new(provider)
+            .with_system_prompt("ignored".to_string())
+            .with_prompt_binding(Arc::new(library), target.clone())
+            .with_prompt_variables(HashMap::from([(
+                "project".to_string(),
+                "ricecoder".to_string(),
+            )]))
+            .with_prompt_audit(audit.clone());
+
+        let mut context = ChatContext::new(Uuid::new_v4(), 4096);
+        let response = service
+            .send_message(&mut context, "Hi".to_string(), None)
+            .await
+            .unwrap();
+
+        let usage = response.prompt.expect("bound prompt is audited");
+        assert_eq!(usage.prompt_id, "coder");
+        assert_eq!(usage.version, 2);
+        assert_eq!(usage.target, Some(target));
+        assert_eq!(
+            audit.for_session(&context.session_id.to_string()),
+            vec![usage]
+        );
+    }
+
     #[tokio::test]
     async fn test_tool_loop_detection() {
         let provider = Arc::new(MockProvider {
@@ -7,9 +7,7 @@
 use std::{collections::HashMap, sync::Arc};
 
 #[cfg(feature = "mcp")]
-use rmcp::client::Client;
-#[cfg(feature = "mcp")]
-use rmcp::transport::StdioTransport;
+use ricecoder_mcp::ServerManager;
 use serde_json::json;
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
@@ -39,12 +37,12 @@ impl ExternalToolBackend {
         }
     }
 
-    /// Create an MCP backend
+    /// Create an MCP backend for a server registered with `server_manager`
     #[cfg(feature = "mcp")]
-    pub fn mcp(server_command: String, server_args: Vec<String>) -> Self {
+    pub fn mcp(server_manager: Arc<ServerManager>, server_id: String) -> Self {
         Self::new(
             "mcp".to_string(),
-            Arc::new(MCPToolExecutor::new(server_command, server_args)),
+            Arc::new(MCPToolExecutor::new(server_manager, server_id)),
         )
     }
 
@@ -235,54 +233,24 @@ impl ToolExecutor for HTTPToolExecutor {
 }
 
 /// MCP (Model Context Protocol) tool executor
+///
+/// Calls go through [`ServerManager::call_tool`], so the server is started
+/// on its first tool call, shares its pooled connection with other callers
+/// and is shut down again when idle.
 #[cfg(feature = "mcp")]
 pub struct MCPToolExecutor {
-    client: Arc<RwLock<Option<Client<StdioTransport>>>>,
-    server_command: String,
-    server_args: Vec<String>,
+    server_manager: Arc<ServerManager>,
+    server_id: String,
 }
 
 #[cfg(feature = "mcp")]
 impl MCPToolExecutor {
-    /// Create a new MCP tool executor
-    pub fn new(server_command: String, server_args: Vec<String>) -> Self {
+    /// Create an MCP tool executor for a server registered with `server_manager`
+    pub fn new(server_manager: Arc<ServerManager>, server_id: String) -> Self {
         Self {
-            client: Arc::new(RwLock::new(None)),
-            server_command,
-            server_args,
-        }
-    }
-
-    /// Ensure MCP client is initialized
-    async fn ensure_client(&self) -> Result<(), String> {
-        let mut client_guard = self.client.write().await;
-
-        if client_guard.is_none() {
-            debug!(
-                command = %self.server_command,
-                args = ?self.server_args,
-                "Initializing MCP client"
-            );
-
-            // Create stdio transport
-            let transport = StdioTransport::new(&self.server_command, &self.server_args)
-                .map_err(|e| format!("Failed to create MCP transport: {}", e))?;
-
-            // Create MCP client
-            let client = Client::new(transport);
-
-            // Initialize the client
-            client
-                .initialize()
-                .await
-                .map_err(|e| format!("Failed to initialize MCP client: {}", e))?;
-
-            *client_guard = Some(client);
-
-            info!("MCP client initialized successfully");
+            server_manager,
+            server_id,
         }
-
-        Ok(())
     }
 }
 
@@ -297,55 +265,29 @@ impl ToolExecutor for MCPToolExecutor {
     ) -> Result<ToolExecutionResult, String> {
         let start_time = std::time::Instant::now();
 
-        // Ensure client is initialized
-        self.ensure_client().await?;
-
-        let client_guard = self.client.read().await;
-        let client = client_guard
-            .as_ref()
-            .ok_or_else(|| "MCP client not initialized".to_string())?;
-
-        debug!(tool_name = %tool_name, "Executing tool via MCP");
-
-        // Execute tool using MCP
-        let result = client
-            .call_tool(tool_name, parameters)
-            .await
-            .map_err(|e| format!("MCP tool execution failed: {}", e))?;
+        debug!(server_id = %self.server_id, tool_name = %tool_name, "Executing tool via MCP");
 
+        let result = self
+            .server_manager
+            .call_tool(&self.server_id, tool_name, parameters)
+            .await;
         let execution_time = start_time.elapsed().as_millis() as u64;
 
-        // Convert MCP result to our format
         match result {
-            rmcp::schema::CallToolResult::Success { content, .. } => {
-                // Extract the first text content if available
-                let data = if let Some(first_content) = content.first() {
-                    match first_content {
-                        rmcp::schema::ToolResultContent::Text { text } => Some(json!(text)),
-                        rmcp::schema::ToolResultContent::Image { .. } => {
-                            Some(json!({"type": "image", "content": "Image content not supported"}))
-                        }
-                        rmcp::schema::ToolResultContent::Resource { .. } => Some(
-                            json!({"type": "resource", "content": "Resource content not supported"}),
-                        ),
-                    }
-                } else {
-                    Some(json!({"content": []}))
-                };
-
-                Ok(ToolExecutionResult {
-                    success: true,
-                    data,
-                    error: None,
-                    execution_time_ms: execution_time,
-                })
-            }
-            rmcp::schema::CallToolResult::Error { error } => Ok(ToolExecutionResult {
+            Ok(data) => Ok(ToolExecutionResult {
+                success: true,
+                data: Some(data),
+                error: None,
+                execution_time_ms: execution_time,
+            }),
+            // The server answered with a tool error
+            Err(ricecoder_mcp::Error::ExecutionError(message)) => Ok(ToolExecutionResult {
                 success: false,
                 data: None,
-                error: Some(error.message),
+                error: Some(message),
                 execution_time_ms: execution_time,
             }),
+            Err(e) => Err(format!("MCP tool execution failed: {}", e)),
         }
     }
 }
@@ -894,3 +836,48 @@ impl Default for ExternalToolIntegrationService {
         Self::new()
     }
 }
+
+#[cfg(all(test, feature = "mcp"))]
+mod tests {
+    use super::*;
+    use crate::use_cases::ConfigureToolBackendUseCase;
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_mcp_backend_starts_server_on_first_call() {
+        // Answers every request with an empty tool list, echoing its ID
+        let script = r#"while read -r line; do
+id=$(printf '%s' "$line" | sed -E 's/.*"id":"([^"]*)".*/\1/')
+printf '{"type":"response","data":{"id":"%s","result":{"tools":[],"id":"%s"}}}\n' "$id" "$id"
+done"#;
+        let manager = Arc::new(ServerManager::new());
+        ConfigureToolBackendUseCase::new(Arc::new(ExternalToolIntegrationService::new()))
+            .with_server_manager(manager.clone())
+            .configure_mcp_backend(
+                "lazy".to_string(),
+                "sh".to_string(),
+                vec!["-c".to_string(), script.to_string()],
+            )
+            .await
+            .unwrap();
+
+        // Configuring the backend does not spawn the server
+        assert!(manager
+            .get_server("lazy")
+            .await
+            .unwrap()
+            .transport
+            .is_none());
+
+        let backend = ExternalToolBackend::mcp(manager.clone(), "lazy".to_string());
+        let output = backend
+            .invoke_tool(json!({ "tool_name": "grep", "parameters": {} }))
+            .await
+            .unwrap();
+        assert_eq!(output["success"], json!(true));
+        assert_eq!(output["result"]["tools"], json!([]));
+        assert!(manager.connection_pool().is_attached("lazy").await);
+
+        manager.stop_server("lazy").await.unwrap();
+    }
+}
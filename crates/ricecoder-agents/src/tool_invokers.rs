@@ -6,7 +6,7 @@
 //! These invokers wire the agent system to the actual tool implementations
 //! in the ricecoder-tools crate.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use serde_json::json;
@@ -14,11 +14,14 @@ use tokio::sync::RwLock;
 use tracing::{debug, info, warn, error};
 
 use crate::tool_registry::{ToolInvoker, ToolMetadata};
+use ricecoder_files::SessionFileTracker;
+use ricecoder_safety::sensitive_paths::{self, PathAccess};
 
 // Import actual tool implementations
 use ricecoder_tools::{
     webfetch::{WebfetchTool, WebfetchInput, OutputFormat},
     search::{SearchTool, SearchInput, SearchType},
+    search_backend::SearchBackendRegistry,
     todo::{Todo, TodoStatus, TodoPriority, TodoTools, TodowriteInput, TodoreadInput},
     patch::{PatchTool, PatchInput},
     read::{FileReadTool, FileReadInput},
@@ -30,6 +33,16 @@ use ricecoder_tools::{
     context::ToolContext,
 };
 
+/// Ask the user for an override before a file tool touches a sensitive path
+///
+/// The tools check the same policy again, so a declined request surfaces
+/// as the usual denial.
+async fn authorize_path(path: &Path, access: PathAccess) -> Result<(), String> {
+    sensitive_paths::authorize(path, access, "agent")
+        .await
+        .map_err(|denied| denied.to_string())
+}
+
 /// Webfetch tool invoker
 ///
 /// Invokes the webfetch tool to fetch web content from URLs.
@@ -434,8 +447,26 @@ impl ToolInvoker for TodoreadToolInvoker {
 
 /// Websearch tool invoker
 ///
-/// Invokes the websearch tool to search the web.
-pub struct WebsearchToolInvoker;
+/// Invokes the websearch tool to search the web, through the configured
+/// search backends when there are any.
+#[derive(Default)]
+pub struct WebsearchToolInvoker {
+    backends: Option<Arc<SearchBackendRegistry>>,
+}
+
+impl WebsearchToolInvoker {
+    /// Create a websearch tool invoker using the built-in provider
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a websearch tool invoker that searches with the given backends
+    pub fn with_backends(backends: Arc<SearchBackendRegistry>) -> Self {
+        Self {
+            backends: Some(backends),
+        }
+    }
+}
 
 #[async_trait::async_trait]
 impl ToolInvoker for WebsearchToolInvoker {
@@ -465,7 +496,10 @@ impl ToolInvoker for WebsearchToolInvoker {
         info!(query = %query, ?limit, ?offset, ?search_type, "Searching web");
 
         // Create search tool and execute
-        let tool = SearchTool::new();
+        let mut tool = SearchTool::new();
+        if let Some(backends) = &self.backends {
+            tool = tool.with_backends(backends.clone());
+        }
 
         let mut search_input = SearchInput::new(query).with_search_type(search_type);
         if let Some(l) = limit {
@@ -559,8 +593,27 @@ impl ToolInvoker for WebsearchToolInvoker {
 
 /// Read tool invoker
 ///
-/// Invokes the read tool to read file contents.
-pub struct ReadToolInvoker;
+/// Invokes the read tool to read file contents. With a session tracker,
+/// reads that carry a `session_id` skip content the session already has
+/// in its context.
+#[derive(Default)]
+pub struct ReadToolInvoker {
+    tracker: Option<Arc<SessionFileTracker>>,
+}
+
+impl ReadToolInvoker {
+    /// Create a read tool invoker without session read tracking
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a read tool invoker that records reads in `tracker`
+    pub fn with_session_tracker(tracker: Arc<SessionFileTracker>) -> Self {
+        Self {
+            tracker: Some(tracker),
+        }
+    }
+}
 
 #[async_trait::async_trait]
 impl ToolInvoker for ReadToolInvoker {
@@ -578,8 +631,11 @@ impl ToolInvoker for ReadToolInvoker {
         let offset = input.get("offset").and_then(|v| v.as_u64()).map(|v| v as usize);
         let limit = input.get("limit").and_then(|v| v.as_u64()).map(|v| v as usize);
         let line_numbers = input.get("line_numbers").and_then(|v| v.as_bool());
+        let max_tokens = input.get("max_tokens").and_then(|v| v.as_u64()).map(|v| v as usize);
+        let session_id = input.get("session_id").and_then(|v| v.as_str());
 
-        info!(file_path = %file_path, ?offset, ?limit, "Reading file");
+        info!(file_path = %file_path, ?offset, ?limit, ?session_id, "Reading file");
+        authorize_path(Path::new(file_path), PathAccess::Read).await?;
 
         // Create read input
         let read_input = FileReadInput {
@@ -596,10 +652,17 @@ impl ToolInvoker for ReadToolInvoker {
             working_dir: None,
             block_env_files: Some(true),
             return_attachments: Some(true),
+            max_tokens,
         };
 
         // Execute synchronous read
-        match FileReadTool::read_file(&read_input) {
+        let result = match (&self.tracker, session_id) {
+            (Some(tracker), Some(session_id)) => {
+                FileReadTool::read_file_in_session(&read_input, session_id, tracker)
+            }
+            _ => FileReadTool::read_file(&read_input),
+        };
+        match result {
             Ok(output) => {
                 if output.success {
                     Ok(json!({
@@ -642,6 +705,10 @@ impl ToolInvoker for ReadToolInvoker {
                     "limit": {
                         "type": "integer",
                         "description": "Maximum number of lines to read (default: 2000)"
+                    },
+                    "session_id": {
+                        "type": "string",
+                        "description": "Session the read belongs to (optional)"
                     }
                 },
                 "required": ["file_path"]
@@ -701,6 +768,7 @@ impl ToolInvoker for WriteToolInvoker {
             .ok_or_else(|| "Missing 'content' field in input".to_string())?;
 
         info!(file_path = %file_path, content_len = content.len(), "Writing file");
+        authorize_path(&self.workspace_root.join(file_path), PathAccess::Write).await?;
 
         // Create write tool and execute
         let mut write_tool = WriteTool::new(self.workspace_root.clone());
@@ -804,6 +872,7 @@ impl ToolInvoker for EditToolInvoker {
             .unwrap_or(false);
 
         info!(file_path = %file_path, replace_all = replace_all, "Editing file");
+        authorize_path(Path::new(file_path), PathAccess::Write).await?;
 
         // Create edit input
         let edit_input = FileEditInput {
@@ -1266,3 +1335,79 @@ pub trait ToolBackend {
     /// Get backend-specific metadata
     fn backend_metadata(&self) -> serde_json::Value;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ricecoder_safety::{
+        approval::ApprovalPrompt, sensitive_paths::SENSITIVE_PATH_CONSTRAINT, ApprovalPrompter,
+        ApprovalRouter, ApprovalRouting, SafetyResult,
+    };
+
+    struct ApprovingPrompter;
+
+    #[async_trait::async_trait]
+    impl ApprovalPrompter for ApprovingPrompter {
+        async fn confirm(&self, _prompt: &ApprovalPrompt) -> SafetyResult<bool> {
+            Ok(true)
+        }
+
+        async fn typed_confirmation(
+            &self,
+            _prompt: &ApprovalPrompt,
+            _phrase: &str,
+        ) -> SafetyResult<String> {
+            Ok(format!("approve {}", SENSITIVE_PATH_CONSTRAINT))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_of_sensitive_path_asks_for_override() {
+        let dir = tempfile::tempdir().unwrap();
+        let env_file = dir.path().join(".env");
+        std::fs::write(&env_file, "TOKEN=secret\n").unwrap();
+        let input = json!({ "file_path": env_file.to_string_lossy() });
+
+        let denied = ReadToolInvoker::new().invoke(input.clone()).await.unwrap_err();
+        assert!(denied.contains("blocked"));
+
+        sensitive_paths::set_override_router(Some(Arc::new(
+            ApprovalRouter::new(ApprovalRouting::default())
+                .with_prompter(Arc::new(ApprovingPrompter)),
+        )));
+        let output = ReadToolInvoker::new().invoke(input).await;
+        sensitive_paths::set_override_router(None);
+
+        let output = output.unwrap();
+        assert!(output["content"].as_str().unwrap().contains("TOKEN=secret"));
+    }
+
+    #[tokio::test]
+    async fn test_repeated_session_read_returns_marker() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("notes.txt");
+        std::fs::write(&file, "first line\nsecond line\n").unwrap();
+        let tracker = Arc::new(SessionFileTracker::new());
+        let invoker = ReadToolInvoker::with_session_tracker(tracker.clone());
+        let input = json!({ "file_path": file.to_string_lossy(), "session_id": "s1" });
+
+        let first = invoker.invoke(input.clone()).await.unwrap();
+        assert!(first["content"].as_str().unwrap().contains("second line"));
+
+        let second = invoker.invoke(input.clone()).await.unwrap();
+        let marker = second["content"].as_str().unwrap();
+        assert!(marker.contains("Unchanged since last read"));
+        assert!(!marker.contains("second line"));
+
+        // Reads without a session always return the content
+        let unsessioned = invoker
+            .invoke(json!({ "file_path": file.to_string_lossy() }))
+            .await
+            .unwrap();
+        assert!(unsessioned["content"].as_str().unwrap().contains("second line"));
+
+        tracker.forget_context("s1").unwrap();
+        let after_compaction = invoker.invoke(input).await.unwrap();
+        assert!(after_compaction["content"].as_str().unwrap().contains("second line"));
+    }
+}
@@ -5,6 +5,8 @@
 
 use std::{collections::HashMap, path::PathBuf, sync::Arc};
 
+use ricecoder_files::SessionFileTracker;
+use ricecoder_tools::search_backend::{SearchBackendRegistry, WebSearchConfig};
 use serde::{Deserialize, Serialize};
 use tracing::{debug, info};
 
@@ -214,7 +216,7 @@ impl ToolRegistry {
         info!("Discovering built-in tools");
 
         // Register file operation tools
-        self.register(Arc::new(ReadToolInvoker));
+        self.register(Arc::new(ReadToolInvoker::new()));
         self.register(Arc::new(WriteToolInvoker::new(workspace_root.clone())));
         self.register(Arc::new(EditToolInvoker));
 
@@ -234,7 +236,7 @@ impl ToolRegistry {
         self.register(Arc::new(TodoreadToolInvoker));
 
         // Register websearch tool
-        self.register(Arc::new(WebsearchToolInvoker));
+        self.register(Arc::new(WebsearchToolInvoker::new()));
 
         info!(tool_count = self.tool_count(), "Built-in tool discovery completed");
         Ok(())
@@ -254,17 +256,38 @@ impl ToolRegistry {
     /// This method loads tool configuration from a configuration source.
     /// The configuration can be used to enable/disable tools or customize
     /// their behavior.
+    ///
+    /// A `web_search` section registers the websearch tool with the
+    /// configured search backends.
     pub fn load_configuration(
         &mut self,
         config: HashMap<String, serde_json::Value>,
     ) -> Result<(), String> {
         info!(config_count = config.len(), "Loading tool configuration");
-        // Configuration loading logic can be implemented here
-        // This allows tools to be configured at runtime
+
+        if let Some(value) = config.get(WebSearchConfig::CONFIG_KEY) {
+            let web_search: WebSearchConfig = serde_json::from_value(value.clone())
+                .map_err(|e| format!("Invalid web_search configuration: {}", e))?;
+            let backends = SearchBackendRegistry::from_config(&web_search, None)
+                .map_err(|e| format!("Failed to configure search backends: {}", e))?;
+            info!(default_backend = ?backends.default_backend(), "Configured search backends");
+            self.register(Arc::new(WebsearchToolInvoker::with_backends(Arc::new(
+                backends,
+            ))));
+        }
+
         debug!("Tool configuration loaded successfully");
         Ok(())
     }
 
+    /// Record reads in `tracker`, per session
+    ///
+    /// Re-registers the read tool so that repeated reads of unchanged
+    /// content in a session return a marker instead of the content.
+    pub fn track_session_reads(&mut self, tracker: Arc<SessionFileTracker>) {
+        self.register(Arc::new(ReadToolInvoker::with_session_tracker(tracker)));
+    }
+
     /// Get all available tool IDs
     ///
     /// # Returns
@@ -472,6 +495,21 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_load_configuration_registers_search_backends() {
+        let mut registry = ToolRegistry::new();
+        let mut config = HashMap::new();
+        config.insert(
+            "web_search".to_string(),
+            serde_json::json!({
+                "backends": [{ "type": "searxng", "base_url": "http://localhost:8888" }]
+            }),
+        );
+
+        registry.load_configuration(config).unwrap();
+        assert!(registry.has_tool("websearch"));
+    }
+
     #[test]
     fn test_registry_empty_discovery() {
         let registry = ToolRegistry::new();
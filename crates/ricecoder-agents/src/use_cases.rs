@@ -5,6 +5,10 @@
 
 use std::sync::Arc;
 
+#[cfg(feature = "mcp")]
+use ricecoder_mcp::{
+    transport::StdioConfig, ServerConfig, ServerManager, TransportConfig, TransportType,
+};
 use ricecoder_providers::{
     community::ProviderAnalytics,
     curation::SelectionConstraints,
@@ -176,15 +180,32 @@ impl ExecuteExternalToolUseCase {
 /// Use case for configuring tool backends
 pub struct ConfigureToolBackendUseCase {
     tool_integration: Arc<ExternalToolIntegrationService>,
+    /// Manager of the servers behind MCP backends, created on first use
+    #[cfg(feature = "mcp")]
+    server_manager: std::sync::OnceLock<Arc<ServerManager>>,
 }
 
 impl ConfigureToolBackendUseCase {
     /// Create a new backend configuration use case
     pub fn new(tool_integration: Arc<ExternalToolIntegrationService>) -> Self {
-        Self { tool_integration }
+        Self {
+            tool_integration,
+            #[cfg(feature = "mcp")]
+            server_manager: std::sync::OnceLock::new(),
+        }
+    }
+
+    /// Register MCP backend servers with an existing server manager
+    #[cfg(feature = "mcp")]
+    pub fn with_server_manager(self, server_manager: Arc<ServerManager>) -> Self {
+        let _ = self.server_manager.set(server_manager);
+        self
     }
 
     /// Configure an MCP backend
+    ///
+    /// The server is registered with the server manager and only started
+    /// on its first tool call.
     #[cfg(feature = "mcp")]
     pub async fn configure_mcp_backend(
         &self,
@@ -199,7 +220,40 @@ impl ConfigureToolBackendUseCase {
             "Configuring MCP backend"
         );
 
-        let backend = ExternalToolBackend::mcp(server_command, server_args);
+        let server_manager = self
+            .server_manager
+            .get_or_init(|| Arc::new(ServerManager::new()))
+            .clone();
+        let config = ServerConfig {
+            id: server_name.clone(),
+            name: server_name.clone(),
+            description: format!("MCP backend {}", server_name),
+            transport_config: TransportConfig {
+                transport_type: TransportType::Stdio,
+                stdio_config: Some(StdioConfig {
+                    command: server_command,
+                    args: server_args,
+                }),
+                http_config: None,
+                sse_config: None,
+            },
+            auto_start: false,
+            health_check_interval_seconds: 30,
+            max_reconnect_attempts: 3,
+            auth_config: None,
+            enabled_tools: Default::default(),
+            idle_timeout_seconds: None,
+            max_in_flight: None,
+            parameter_shims: Default::default(),
+        };
+        server_manager.register_server(config).await.map_err(|e| {
+            AgentError::ExecutionFailed(format!(
+                "Failed to register MCP server {}: {}",
+                server_name, e
+            ))
+        })?;
+
+        let backend = ExternalToolBackend::mcp(server_manager, server_name.clone());
         Arc::as_ref(&self.tool_integration)
             .configure_backend(server_name.clone(), backend)
             .await?;
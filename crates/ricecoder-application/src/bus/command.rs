@@ -0,0 +1,275 @@
+//! Command bus
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use super::pipeline::{unwrap_message, Dispatcher, Terminal};
+use super::{Command, HandlerContext, MessageKind, Middleware, RequestContext};
+use crate::errors::ApplicationResult;
+
+/// Handles one command type
+#[async_trait]
+pub trait CommandHandler<C: Command>: Send + Sync + 'static {
+    /// Handle the command
+    async fn handle(&self, command: C, context: &HandlerContext) -> ApplicationResult<C::Output>;
+}
+
+/// Routes commands to their handlers through the middleware pipeline
+#[derive(Default)]
+pub struct CommandBus {
+    dispatcher: Dispatcher,
+}
+
+impl CommandBus {
+    /// Create a command bus with no middleware or handlers
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append middleware to the pipeline
+    pub fn with_middleware<M: Middleware>(mut self, middleware: M) -> Self {
+        self.dispatcher.add_middleware(Arc::new(middleware));
+        self
+    }
+
+    /// Register the handler for a command type, replacing any previous one
+    pub fn register<C, H>(&mut self, handler: Arc<H>)
+    where
+        C: Command,
+        H: CommandHandler<C>,
+    {
+        let terminal: Terminal = Arc::new(move |envelope| {
+            let handler = Arc::clone(&handler);
+            Box::pin(async move {
+                let (payload, context) = envelope.into_parts();
+                let command = unwrap_message::<C>(payload)?;
+                let output = handler.handle(command, &context).await?;
+                Ok(Box::new(output) as Box<dyn std::any::Any + Send>)
+            })
+        });
+        self.dispatcher.register::<C>(terminal);
+    }
+
+    /// Check whether a handler is registered for a command type
+    pub fn has_handler<C: Command>(&self) -> bool {
+        self.dispatcher.has_handler::<C>()
+    }
+
+    /// Dispatch a command to its handler
+    pub async fn dispatch<C: Command>(
+        &self,
+        command: C,
+        request: RequestContext,
+    ) -> ApplicationResult<C::Output> {
+        self.dispatcher
+            .dispatch::<C, C::Output>(MessageKind::Command, command, request)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::DispatchResult;
+    use crate::bus::{
+        AuthorizationMiddleware, Envelope, EventPublicationMiddleware, Message, Next,
+        TransactionMiddleware, ValidationMiddleware,
+    };
+    use crate::errors::ApplicationError;
+    use crate::events::{ApplicationEvent, InMemoryEventPublisher};
+    use crate::ports::{Authorizer, NoOpUnitOfWork};
+    use chrono::Utc;
+    use std::sync::Mutex;
+
+    struct RenameCmd {
+        name: String,
+    }
+
+    impl Message for RenameCmd {
+        fn action(&self) -> &'static str {
+            "project.rename"
+        }
+
+        fn validate(&self) -> ApplicationResult<()> {
+            if self.name.is_empty() {
+                return Err(ApplicationError::ValidationFailed(
+                    "name is required".into(),
+                ));
+            }
+            Ok(())
+        }
+    }
+
+    impl Command for RenameCmd {
+        type Output = String;
+    }
+
+    struct RenameHandler {
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl CommandHandler<RenameCmd> for RenameHandler {
+        async fn handle(
+            &self,
+            command: RenameCmd,
+            context: &HandlerContext,
+        ) -> ApplicationResult<String> {
+            context.record_event(ApplicationEvent::ProjectUpdated {
+                project_id: command.name.clone(),
+                timestamp: Utc::now(),
+            });
+            if self.fail {
+                return Err(ApplicationError::RepositoryError("disk full".into()));
+            }
+            Ok(command.name)
+        }
+    }
+
+    /// Records the order in which middleware runs
+    struct Trace {
+        label: &'static str,
+        log: Arc<Mutex<Vec<&'static str>>>,
+    }
+
+    #[async_trait]
+    impl Middleware for Trace {
+        async fn handle(&self, envelope: Envelope, next: Next) -> DispatchResult {
+            self.log.lock().unwrap().push(self.label);
+            next.run(envelope).await
+        }
+    }
+
+    /// Allows only actors with the `admin` role
+    struct AdminOnly;
+
+    #[async_trait]
+    impl Authorizer for AdminOnly {
+        async fn authorize(&self, request: &RequestContext, action: &str) -> ApplicationResult<()> {
+            if request.roles.iter().any(|role| role == "admin") {
+                Ok(())
+            } else {
+                Err(ApplicationError::Unauthorized(action.to_string()))
+            }
+        }
+    }
+
+    fn bus(fail: bool, events: Arc<InMemoryEventPublisher>) -> CommandBus {
+        let mut bus = CommandBus::new()
+            .with_middleware(ValidationMiddleware)
+            .with_middleware(AuthorizationMiddleware::new(Arc::new(AdminOnly)))
+            .with_middleware(EventPublicationMiddleware::new(events))
+            .with_middleware(TransactionMiddleware::new(Arc::new(NoOpUnitOfWork)));
+        bus.register::<RenameCmd, _>(Arc::new(RenameHandler { fail }));
+        bus
+    }
+
+    fn admin() -> RequestContext {
+        RequestContext::for_actor("alice", vec!["admin".into()])
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_runs_handler_and_publishes_events() {
+        let events = Arc::new(InMemoryEventPublisher::new());
+        let bus = bus(false, Arc::clone(&events));
+
+        let result = bus
+            .dispatch(
+                RenameCmd {
+                    name: "renamed".into(),
+                },
+                admin(),
+            )
+            .await;
+
+        assert_eq!(result.unwrap(), "renamed");
+        assert_eq!(events.events().len(), 1);
+        assert_eq!(events.events()[0].event_type(), "ProjectUpdated");
+    }
+
+    #[tokio::test]
+    async fn test_failed_handler_discards_events() {
+        let events = Arc::new(InMemoryEventPublisher::new());
+        let bus = bus(true, Arc::clone(&events));
+
+        let result = bus
+            .dispatch(
+                RenameCmd {
+                    name: "renamed".into(),
+                },
+                admin(),
+            )
+            .await;
+
+        assert!(matches!(result, Err(ApplicationError::RepositoryError(_))));
+        assert!(events.events().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_validation_and_authorization_short_circuit() {
+        let events = Arc::new(InMemoryEventPublisher::new());
+        let bus = bus(false, Arc::clone(&events));
+
+        let invalid = bus.dispatch(RenameCmd { name: "".into() }, admin()).await;
+        assert!(matches!(
+            invalid,
+            Err(ApplicationError::ValidationFailed(_))
+        ));
+
+        let anonymous = bus
+            .dispatch(
+                RenameCmd {
+                    name: "renamed".into(),
+                },
+                RequestContext::new(),
+            )
+            .await;
+        assert!(matches!(
+            anonymous,
+            Err(ApplicationError::Unauthorized(action)) if action == "project.rename"
+        ));
+        assert!(events.events().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_middleware_runs_in_registration_order() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut bus = CommandBus::new()
+            .with_middleware(Trace {
+                label: "outer",
+                log: Arc::clone(&log),
+            })
+            .with_middleware(Trace {
+                label: "inner",
+                log: Arc::clone(&log),
+            });
+        bus.register::<RenameCmd, _>(Arc::new(RenameHandler { fail: false }));
+
+        bus.dispatch(
+            RenameCmd {
+                name: "renamed".into(),
+            },
+            RequestContext::new(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(*log.lock().unwrap(), vec!["outer", "inner"]);
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_without_handler() {
+        let bus = CommandBus::new();
+        assert!(!bus.has_handler::<RenameCmd>());
+
+        let result = bus
+            .dispatch(
+                RenameCmd {
+                    name: "renamed".into(),
+                },
+                RequestContext::new(),
+            )
+            .await;
+        assert!(matches!(result, Err(ApplicationError::HandlerNotFound(_))));
+    }
+}
@@ -0,0 +1,224 @@
+//! Bus bindings for application DTOs and services
+//!
+//! Lets the existing services be registered as handlers so presentation
+//! layers can dispatch DTOs instead of calling services directly.
+
+use async_trait::async_trait;
+
+use super::{Command, CommandHandler, HandlerContext, Message, Query, QueryHandler};
+use crate::dto::{
+    CreateProjectCommand, CreateSessionCommand, GetProjectQuery, GetSessionQuery,
+    ListProjectsQuery, ProjectDetailDto, ProjectSummaryDto, SessionDetailDto,
+};
+use crate::errors::{ApplicationError, ApplicationResult};
+use crate::events::EventPublisher;
+use crate::ports::UnitOfWork;
+use crate::services::{ProjectService, SessionService};
+
+use ricecoder_domain::repositories::{ProjectRepository, SessionRepository};
+
+fn require(field: &str, value: &str) -> ApplicationResult<()> {
+    if value.trim().is_empty() {
+        return Err(ApplicationError::RequiredFieldMissing(field.to_string()));
+    }
+    Ok(())
+}
+
+// === Messages ===
+
+impl Message for CreateProjectCommand {
+    fn action(&self) -> &'static str {
+        "project.create"
+    }
+
+    fn validate(&self) -> ApplicationResult<()> {
+        if self.name.trim().is_empty() {
+            return Err(ApplicationError::ValidationFailed(
+                "Project name is required".into(),
+            ));
+        }
+        require("root_path", &self.root_path)
+    }
+}
+
+impl Command for CreateProjectCommand {
+    type Output = String;
+}
+
+impl Message for CreateSessionCommand {
+    fn action(&self) -> &'static str {
+        "session.create"
+    }
+
+    fn validate(&self) -> ApplicationResult<()> {
+        require("project_id", &self.project_id)
+    }
+}
+
+impl Command for CreateSessionCommand {
+    type Output = String;
+}
+
+impl Message for GetProjectQuery {
+    fn action(&self) -> &'static str {
+        "project.read"
+    }
+
+    fn validate(&self) -> ApplicationResult<()> {
+        require("project_id", &self.project_id)
+    }
+}
+
+impl Query for GetProjectQuery {
+    type Output = ProjectDetailDto;
+}
+
+impl Message for ListProjectsQuery {
+    fn action(&self) -> &'static str {
+        "project.list"
+    }
+}
+
+impl Query for ListProjectsQuery {
+    type Output = Vec<ProjectSummaryDto>;
+}
+
+impl Message for GetSessionQuery {
+    fn action(&self) -> &'static str {
+        "session.read"
+    }
+
+    fn validate(&self) -> ApplicationResult<()> {
+        require("session_id", &self.session_id)
+    }
+}
+
+impl Query for GetSessionQuery {
+    type Output = SessionDetailDto;
+}
+
+// === Service Handlers ===
+
+#[async_trait]
+impl<R, U, E> CommandHandler<CreateProjectCommand> for ProjectService<R, U, E>
+where
+    R: ProjectRepository + Send + Sync + 'static,
+    U: UnitOfWork + Send + Sync + 'static,
+    E: EventPublisher + Send + Sync + 'static,
+{
+    async fn handle(
+        &self,
+        command: CreateProjectCommand,
+        _context: &HandlerContext,
+    ) -> ApplicationResult<String> {
+        self.create_project(command).await
+    }
+}
+
+#[async_trait]
+impl<R, U, E> QueryHandler<GetProjectQuery> for ProjectService<R, U, E>
+where
+    R: ProjectRepository + Send + Sync + 'static,
+    U: UnitOfWork + Send + Sync + 'static,
+    E: EventPublisher + Send + Sync + 'static,
+{
+    async fn handle(
+        &self,
+        query: GetProjectQuery,
+        _context: &HandlerContext,
+    ) -> ApplicationResult<ProjectDetailDto> {
+        self.get_project(&query.project_id).await
+    }
+}
+
+#[async_trait]
+impl<R, U, E> QueryHandler<ListProjectsQuery> for ProjectService<R, U, E>
+where
+    R: ProjectRepository + Send + Sync + 'static,
+    U: UnitOfWork + Send + Sync + 'static,
+    E: EventPublisher + Send + Sync + 'static,
+{
+    async fn handle(
+        &self,
+        _query: ListProjectsQuery,
+        _context: &HandlerContext,
+    ) -> ApplicationResult<Vec<ProjectSummaryDto>> {
+        self.list_projects().await
+    }
+}
+
+#[async_trait]
+impl<SR, PR, U, E> CommandHandler<CreateSessionCommand> for SessionService<SR, PR, U, E>
+where
+    SR: SessionRepository + Send + Sync + 'static,
+    PR: ProjectRepository + Send + Sync + 'static,
+    U: UnitOfWork + Send + Sync + 'static,
+    E: EventPublisher + Send + Sync + 'static,
+{
+    async fn handle(
+        &self,
+        command: CreateSessionCommand,
+        _context: &HandlerContext,
+    ) -> ApplicationResult<String> {
+        self.create_session(command).await
+    }
+}
+
+#[async_trait]
+impl<SR, PR, U, E> QueryHandler<GetSessionQuery> for SessionService<SR, PR, U, E>
+where
+    SR: SessionRepository + Send + Sync + 'static,
+    PR: ProjectRepository + Send + Sync + 'static,
+    U: UnitOfWork + Send + Sync + 'static,
+    E: EventPublisher + Send + Sync + 'static,
+{
+    async fn handle(
+        &self,
+        query: GetSessionQuery,
+        _context: &HandlerContext,
+    ) -> ApplicationResult<SessionDetailDto> {
+        self.get_session(&query.session_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ricecoder_domain::value_objects::ProgrammingLanguage;
+
+    #[test]
+    fn test_create_project_command_validation() {
+        let mut cmd = CreateProjectCommand {
+            name: "test-project".into(),
+            root_path: "/path/to/project".into(),
+            language: ProgrammingLanguage::Rust,
+            description: None,
+        };
+        assert!(cmd.validate().is_ok());
+        assert_eq!(cmd.action(), "project.create");
+
+        cmd.root_path = "  ".into();
+        assert!(matches!(
+            cmd.validate(),
+            Err(ApplicationError::RequiredFieldMissing(field)) if field == "root_path"
+        ));
+
+        cmd.name = "".into();
+        assert!(matches!(
+            cmd.validate(),
+            Err(ApplicationError::ValidationFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_query_validation() {
+        let query = GetSessionQuery {
+            session_id: "".into(),
+        };
+        assert!(matches!(
+            query.validate(),
+            Err(ApplicationError::RequiredFieldMissing(_))
+        ));
+        assert!(ListProjectsQuery.validate().is_ok());
+    }
+}
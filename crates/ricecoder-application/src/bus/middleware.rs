@@ -0,0 +1,124 @@
+//! Built-in middleware
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use super::{DispatchResult, Envelope, MessageKind, Middleware, Next};
+use crate::events::EventPublisher;
+use crate::ports::{Authorizer, UnitOfWork};
+
+/// Rejects messages that fail their own validation
+pub struct ValidationMiddleware;
+
+#[async_trait]
+impl Middleware for ValidationMiddleware {
+    async fn handle(&self, envelope: Envelope, next: Next) -> DispatchResult {
+        envelope.validate()?;
+        next.run(envelope).await
+    }
+}
+
+/// Rejects messages the caller is not allowed to perform
+pub struct AuthorizationMiddleware<A>
+where
+    A: Authorizer + Send + Sync,
+{
+    authorizer: Arc<A>,
+}
+
+impl<A> AuthorizationMiddleware<A>
+where
+    A: Authorizer + Send + Sync,
+{
+    /// Create authorization middleware backed by an authorizer
+    pub fn new(authorizer: Arc<A>) -> Self {
+        Self { authorizer }
+    }
+}
+
+#[async_trait]
+impl<A> Middleware for AuthorizationMiddleware<A>
+where
+    A: Authorizer + Send + Sync + 'static,
+{
+    async fn handle(&self, envelope: Envelope, next: Next) -> DispatchResult {
+        self.authorizer
+            .authorize(envelope.request(), envelope.action())
+            .await?;
+        next.run(envelope).await
+    }
+}
+
+/// Runs command handling inside a unit of work
+///
+/// Queries pass through without a transaction.
+pub struct TransactionMiddleware<U>
+where
+    U: UnitOfWork + Send + Sync,
+{
+    uow: Arc<U>,
+}
+
+impl<U> TransactionMiddleware<U>
+where
+    U: UnitOfWork + Send + Sync,
+{
+    /// Create transaction middleware backed by a unit of work
+    pub fn new(uow: Arc<U>) -> Self {
+        Self { uow }
+    }
+}
+
+#[async_trait]
+impl<U> Middleware for TransactionMiddleware<U>
+where
+    U: UnitOfWork + Send + Sync + 'static,
+{
+    async fn handle(&self, envelope: Envelope, next: Next) -> DispatchResult {
+        if envelope.kind() != MessageKind::Command {
+            return next.run(envelope).await;
+        }
+        self.uow.execute(next.run(envelope)).await
+    }
+}
+
+/// Publishes events recorded by handlers once handling succeeds
+///
+/// Events from failed handlers are discarded. Register this before the
+/// transaction middleware so events are only published after commit.
+pub struct EventPublicationMiddleware<E>
+where
+    E: EventPublisher + Send + Sync,
+{
+    publisher: Arc<E>,
+}
+
+impl<E> EventPublicationMiddleware<E>
+where
+    E: EventPublisher + Send + Sync,
+{
+    /// Create event publication middleware backed by a publisher
+    pub fn new(publisher: Arc<E>) -> Self {
+        Self { publisher }
+    }
+}
+
+#[async_trait]
+impl<E> Middleware for EventPublicationMiddleware<E>
+where
+    E: EventPublisher + Send + Sync + 'static,
+{
+    async fn handle(&self, envelope: Envelope, next: Next) -> DispatchResult {
+        let context = envelope.context().clone();
+        let result = next.run(envelope).await;
+
+        let events = context.take_events();
+        if result.is_ok() {
+            for event in events {
+                self.publisher.publish(event).await;
+            }
+        }
+        result
+    }
+}
@@ -0,0 +1,219 @@
+//! Command and Query Buses
+//!
+//! Presentation layers dispatch command and query DTOs through a bus instead of
+//! calling services directly. Each bus routes a message to the handler
+//! registered for its type and runs it through a middleware pipeline, so
+//! cross-cutting concerns live in one place.
+//!
+//! # Architecture
+//!
+//! ```text
+//! ┌──────────────┐   ┌────────────┐   ┌───────────────┐   ┌────────┐   ┌─────────────┐   ┌─────────┐
+//! │ Presentation │──▶│ Validation │──▶│ Authorization │──▶│ Events │──▶│ Transaction │──▶│ Handler │
+//! └──────────────┘   └────────────┘   └───────────────┘   └────────┘   └─────────────┘   └─────────┘
+//! ```
+//!
+//! - **Validation**: rejects messages whose [`Message::validate`] fails
+//! - **Authorization**: asks the [`Authorizer`](crate::ports::Authorizer) port
+//!   whether the caller may perform the message's action
+//! - **Events**: publishes events raised by the handler once it succeeds
+//! - **Transaction**: wraps command handling in a [`UnitOfWork`](crate::ports::UnitOfWork)
+//!
+//! Middleware runs in registration order.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let mut bus = CommandBus::new()
+//!     .with_middleware(ValidationMiddleware)
+//!     .with_middleware(AuthorizationMiddleware::new(authorizer))
+//!     .with_middleware(EventPublicationMiddleware::new(events))
+//!     .with_middleware(TransactionMiddleware::new(uow));
+//! bus.register::<CreateProjectCommand, _>(project_service);
+//!
+//! let project_id = bus.dispatch(cmd, RequestContext::for_actor("alice", vec!["user".into()])).await?;
+//! ```
+
+mod command;
+mod handlers;
+mod middleware;
+mod pipeline;
+mod query;
+
+use std::any::Any;
+use std::sync::{Arc, Mutex};
+
+use uuid::Uuid;
+
+use crate::errors::ApplicationResult;
+use crate::events::ApplicationEvent;
+
+pub use command::{CommandBus, CommandHandler};
+pub use middleware::{
+    AuthorizationMiddleware, EventPublicationMiddleware, TransactionMiddleware,
+    ValidationMiddleware,
+};
+pub use pipeline::{DispatchResult, Middleware, Next};
+pub use query::{QueryBus, QueryHandler};
+
+/// A message routed through a bus
+pub trait Message: Send + Sync + 'static {
+    /// Action name used for authorization and tracing (e.g. `project.create`)
+    fn action(&self) -> &'static str;
+
+    /// Validate the message before it reaches its handler
+    fn validate(&self) -> ApplicationResult<()> {
+        Ok(())
+    }
+}
+
+/// A message that changes application state
+pub trait Command: Message {
+    /// Value returned by the command handler
+    type Output: Send + 'static;
+}
+
+/// A message that reads application state
+pub trait Query: Message {
+    /// Value returned by the query handler
+    type Output: Send + 'static;
+}
+
+/// Whether a message is a command or a query
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageKind {
+    Command,
+    Query,
+}
+
+/// Caller identity attached to every dispatched message
+#[derive(Debug, Clone)]
+pub struct RequestContext {
+    /// Authenticated actor, if any
+    pub actor_id: Option<String>,
+    /// Role names of the actor
+    pub roles: Vec<String>,
+    /// Identifier correlating all work done for one request
+    pub correlation_id: String,
+}
+
+impl RequestContext {
+    /// Create an anonymous request context
+    pub fn new() -> Self {
+        Self {
+            actor_id: None,
+            roles: Vec::new(),
+            correlation_id: Uuid::new_v4().to_string(),
+        }
+    }
+
+    /// Create a request context for an authenticated actor
+    pub fn for_actor(actor_id: impl Into<String>, roles: Vec<String>) -> Self {
+        Self {
+            actor_id: Some(actor_id.into()),
+            roles,
+            ..Self::new()
+        }
+    }
+}
+
+impl Default for RequestContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Context passed to handlers
+///
+/// Handlers record application events here instead of publishing them
+/// directly; the event publication middleware publishes them once the
+/// handler (and its transaction) succeeds.
+#[derive(Debug, Clone, Default)]
+pub struct HandlerContext {
+    request: RequestContext,
+    events: Arc<Mutex<Vec<ApplicationEvent>>>,
+}
+
+impl HandlerContext {
+    /// Create a handler context for a request
+    pub fn new(request: RequestContext) -> Self {
+        Self {
+            request,
+            events: Arc::default(),
+        }
+    }
+
+    /// Get the request context
+    pub fn request(&self) -> &RequestContext {
+        &self.request
+    }
+
+    /// Record an event to publish after successful handling
+    pub fn record_event(&self, event: ApplicationEvent) {
+        self.events.lock().unwrap().push(event);
+    }
+
+    /// Take all recorded events
+    pub fn take_events(&self) -> Vec<ApplicationEvent> {
+        std::mem::take(&mut *self.events.lock().unwrap())
+    }
+}
+
+/// A type-erased message travelling through the middleware pipeline
+pub struct Envelope {
+    kind: MessageKind,
+    action: &'static str,
+    payload: Box<dyn Any + Send + Sync>,
+    validator: fn(&(dyn Any + Send + Sync)) -> ApplicationResult<()>,
+    context: HandlerContext,
+}
+
+impl Envelope {
+    pub(crate) fn new<M: Message>(kind: MessageKind, message: M, request: RequestContext) -> Self {
+        Self {
+            kind,
+            action: message.action(),
+            payload: Box::new(message),
+            validator: validate_erased::<M>,
+            context: HandlerContext::new(request),
+        }
+    }
+
+    /// Whether the message is a command or a query
+    pub fn kind(&self) -> MessageKind {
+        self.kind
+    }
+
+    /// Action name of the message
+    pub fn action(&self) -> &'static str {
+        self.action
+    }
+
+    /// Request context of the caller
+    pub fn request(&self) -> &RequestContext {
+        self.context.request()
+    }
+
+    /// Handler context shared with the handler
+    pub fn context(&self) -> &HandlerContext {
+        &self.context
+    }
+
+    /// Run the message's own validation
+    pub fn validate(&self) -> ApplicationResult<()> {
+        (self.validator)(self.payload.as_ref())
+    }
+
+    /// Borrow the message as its concrete type
+    pub fn message<M: Message>(&self) -> Option<&M> {
+        self.payload.downcast_ref::<M>()
+    }
+
+    pub(crate) fn into_parts(self) -> (Box<dyn Any + Send + Sync>, HandlerContext) {
+        (self.payload, self.context)
+    }
+}
+
+fn validate_erased<M: Message>(payload: &(dyn Any + Send + Sync)) -> ApplicationResult<()> {
+    payload.downcast_ref::<M>().map_or(Ok(()), M::validate)
+}
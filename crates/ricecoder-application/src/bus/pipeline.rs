@@ -0,0 +1,121 @@
+//! Middleware pipeline shared by the command and query buses
+
+use std::any::{type_name, Any, TypeId};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use super::{Envelope, MessageKind, RequestContext};
+use crate::errors::{ApplicationError, ApplicationResult};
+use crate::ports::BoxedFuture;
+
+/// Type-erased result of handling a message
+pub type DispatchResult = ApplicationResult<Box<dyn Any + Send>>;
+
+/// Type-erased handler at the end of the pipeline
+pub(crate) type Terminal =
+    Arc<dyn Fn(Envelope) -> BoxedFuture<'static, DispatchResult> + Send + Sync>;
+
+/// Cross-cutting behaviour wrapped around message handling
+///
+/// Middleware either short-circuits with an error or passes the envelope on
+/// with [`Next::run`].
+#[async_trait]
+pub trait Middleware: Send + Sync + 'static {
+    /// Handle a message, calling `next` to continue the pipeline
+    async fn handle(&self, envelope: Envelope, next: Next) -> DispatchResult;
+}
+
+/// The remainder of a middleware pipeline
+#[derive(Clone)]
+pub struct Next {
+    middleware: Arc<Vec<Arc<dyn Middleware>>>,
+    index: usize,
+    terminal: Terminal,
+}
+
+impl Next {
+    /// Run the rest of the pipeline
+    pub fn run(self, envelope: Envelope) -> BoxedFuture<'static, DispatchResult> {
+        Box::pin(async move {
+            match self.middleware.get(self.index).cloned() {
+                Some(middleware) => {
+                    let next = Next {
+                        index: self.index + 1,
+                        ..self
+                    };
+                    middleware.handle(envelope, next).await
+                }
+                None => (self.terminal)(envelope).await,
+            }
+        })
+    }
+}
+
+/// Handler registry and middleware chain behind a bus
+#[derive(Default)]
+pub(crate) struct Dispatcher {
+    handlers: HashMap<TypeId, Terminal>,
+    middleware: Arc<Vec<Arc<dyn Middleware>>>,
+}
+
+impl Dispatcher {
+    pub(crate) fn add_middleware(&mut self, middleware: Arc<dyn Middleware>) {
+        Arc::make_mut(&mut self.middleware).push(middleware);
+    }
+
+    pub(crate) fn register<M: 'static>(&mut self, terminal: Terminal) {
+        self.handlers.insert(TypeId::of::<M>(), terminal);
+    }
+
+    pub(crate) fn has_handler<M: 'static>(&self) -> bool {
+        self.handlers.contains_key(&TypeId::of::<M>())
+    }
+
+    pub(crate) async fn dispatch<M, O>(
+        &self,
+        kind: MessageKind,
+        message: M,
+        request: RequestContext,
+    ) -> ApplicationResult<O>
+    where
+        M: super::Message,
+        O: Send + 'static,
+    {
+        let terminal = self
+            .handlers
+            .get(&TypeId::of::<M>())
+            .cloned()
+            .ok_or_else(|| ApplicationError::HandlerNotFound(type_name::<M>().to_string()))?;
+
+        let next = Next {
+            middleware: Arc::clone(&self.middleware),
+            index: 0,
+            terminal,
+        };
+        let output = next.run(Envelope::new(kind, message, request)).await?;
+
+        output.downcast::<O>().map(|output| *output).map_err(|_| {
+            ApplicationError::DispatchFailed(format!(
+                "Handler for {} returned an unexpected output type",
+                type_name::<M>()
+            ))
+        })
+    }
+}
+
+/// Recover the concrete message from an envelope at the end of the pipeline
+pub(crate) fn unwrap_message<M: 'static>(
+    payload: Box<dyn Any + Send + Sync>,
+) -> ApplicationResult<M> {
+    payload
+        .downcast::<M>()
+        .map(|message| *message)
+        .map_err(|_| {
+            ApplicationError::DispatchFailed(format!(
+                "Expected message of type {}",
+                type_name::<M>()
+            ))
+        })
+}
@@ -0,0 +1,163 @@
+//! Query bus
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use super::pipeline::{unwrap_message, Dispatcher, Terminal};
+use super::{HandlerContext, MessageKind, Middleware, Query, RequestContext};
+use crate::errors::ApplicationResult;
+
+/// Handles one query type
+#[async_trait]
+pub trait QueryHandler<Q: Query>: Send + Sync + 'static {
+    /// Handle the query
+    async fn handle(&self, query: Q, context: &HandlerContext) -> ApplicationResult<Q::Output>;
+}
+
+/// Routes queries to their handlers through the middleware pipeline
+///
+/// Transaction and event publication middleware pass queries straight
+/// through, so the same middleware can be shared with the [`CommandBus`](super::CommandBus).
+#[derive(Default)]
+pub struct QueryBus {
+    dispatcher: Dispatcher,
+}
+
+impl QueryBus {
+    /// Create a query bus with no middleware or handlers
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append middleware to the pipeline
+    pub fn with_middleware<M: Middleware>(mut self, middleware: M) -> Self {
+        self.dispatcher.add_middleware(Arc::new(middleware));
+        self
+    }
+
+    /// Register the handler for a query type, replacing any previous one
+    pub fn register<Q, H>(&mut self, handler: Arc<H>)
+    where
+        Q: Query,
+        H: QueryHandler<Q>,
+    {
+        let terminal: Terminal = Arc::new(move |envelope| {
+            let handler = Arc::clone(&handler);
+            Box::pin(async move {
+                let (payload, context) = envelope.into_parts();
+                let query = unwrap_message::<Q>(payload)?;
+                let output = handler.handle(query, &context).await?;
+                Ok(Box::new(output) as Box<dyn std::any::Any + Send>)
+            })
+        });
+        self.dispatcher.register::<Q>(terminal);
+    }
+
+    /// Check whether a handler is registered for a query type
+    pub fn has_handler<Q: Query>(&self) -> bool {
+        self.dispatcher.has_handler::<Q>()
+    }
+
+    /// Dispatch a query to its handler
+    pub async fn dispatch<Q: Query>(
+        &self,
+        query: Q,
+        request: RequestContext,
+    ) -> ApplicationResult<Q::Output> {
+        self.dispatcher
+            .dispatch::<Q, Q::Output>(MessageKind::Query, query, request)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::{Message, TransactionMiddleware, ValidationMiddleware};
+    use crate::errors::ApplicationError;
+    use crate::ports::UnitOfWork;
+    use std::future::Future;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct CountQuery {
+        prefix: String,
+    }
+
+    impl Message for CountQuery {
+        fn action(&self) -> &'static str {
+            "project.count"
+        }
+    }
+
+    impl Query for CountQuery {
+        type Output = usize;
+    }
+
+    struct CountHandler;
+
+    #[async_trait]
+    impl QueryHandler<CountQuery> for CountHandler {
+        async fn handle(
+            &self,
+            query: CountQuery,
+            _context: &HandlerContext,
+        ) -> ApplicationResult<usize> {
+            Ok(query.prefix.len())
+        }
+    }
+
+    /// Counts how many transactions were opened
+    #[derive(Default)]
+    struct CountingUnitOfWork {
+        transactions: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl UnitOfWork for CountingUnitOfWork {
+        async fn execute<T, F>(&self, f: F) -> ApplicationResult<T>
+        where
+            T: Send + 'static,
+            F: Future<Output = ApplicationResult<T>> + Send + 'static,
+        {
+            self.transactions.fetch_add(1, Ordering::SeqCst);
+            f.await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_query_skips_transaction() {
+        let uow = Arc::new(CountingUnitOfWork::default());
+        let mut bus = QueryBus::new()
+            .with_middleware(ValidationMiddleware)
+            .with_middleware(TransactionMiddleware::new(Arc::clone(&uow)));
+        bus.register::<CountQuery, _>(Arc::new(CountHandler));
+
+        let count = bus
+            .dispatch(
+                CountQuery {
+                    prefix: "ric".into(),
+                },
+                RequestContext::new(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(count, 3);
+        assert_eq!(uow.transactions.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_query_without_handler() {
+        let bus = QueryBus::new();
+        let result = bus
+            .dispatch(
+                CountQuery {
+                    prefix: "ric".into(),
+                },
+                RequestContext::new(),
+            )
+            .await;
+        assert!(matches!(result, Err(ApplicationError::HandlerNotFound(_))));
+    }
+}
@@ -30,6 +30,16 @@ pub struct RenameProjectCommand {
     pub new_name: String,
 }
 
+/// Query for a single project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetProjectQuery {
+    pub project_id: String,
+}
+
+/// Query for all projects
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ListProjectsQuery;
+
 /// Project summary DTO (list view)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProjectSummaryDto {
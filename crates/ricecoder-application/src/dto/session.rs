@@ -21,6 +21,12 @@ pub struct AddMessageCommand {
     pub role: String,
 }
 
+/// Query for a single session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetSessionQuery {
+    pub session_id: String,
+}
+
 /// Session summary DTO (list view)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionSummaryDto {
@@ -38,6 +38,10 @@ pub enum ApplicationError {
     #[error("Specification not found: {0}")]
     SpecificationNotFound(String),
 
+    /// Saga not found
+    #[error("Saga not found: {0}")]
+    SagaNotFound(String),
+
     // === Conflict Errors ===
     
     /// Project with this name already exists
@@ -58,6 +62,22 @@ pub enum ApplicationError {
     #[error("Business rule violation: {0}")]
     BusinessRuleViolation(String),
 
+    // === Authorization Errors ===
+    
+    /// Caller is not allowed to perform the action
+    #[error("Not authorized to perform: {0}")]
+    Unauthorized(String),
+
+    // === Dispatch Errors ===
+    
+    /// No handler registered for a command or query
+    #[error("No handler registered for: {0}")]
+    HandlerNotFound(String),
+
+    /// Message dispatch failed
+    #[error("Dispatch failed: {0}")]
+    DispatchFailed(String),
+
     // === Infrastructure Errors ===
     
     /// Repository operation failed
@@ -68,6 +88,10 @@ pub enum ApplicationError {
     #[error("Transaction failed: {0}")]
     TransactionFailed(String),
 
+    /// Operation timed out
+    #[error("Operation timed out: {0}")]
+    Timeout(String),
+
     /// Event publication failed
     #[error("Event publication failed: {0}")]
     EventPublicationFailed(String),
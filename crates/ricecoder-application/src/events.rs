@@ -86,6 +86,42 @@ pub enum ApplicationEvent {
         completion_percentage: f32,
         timestamp: DateTime<Utc>,
     },
+
+    // === Saga Events ===
+    
+    /// Saga was started
+    SagaStarted {
+        saga_id: String,
+        saga_type: String,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// Saga step completed
+    SagaStepCompleted {
+        saga_id: String,
+        step: String,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// Saga completed all steps
+    SagaCompleted {
+        saga_id: String,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// Saga was rolled back by compensating its completed steps
+    SagaCompensated {
+        saga_id: String,
+        reason: String,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// Saga compensation failed and needs manual intervention
+    SagaFailed {
+        saga_id: String,
+        reason: String,
+        timestamp: DateTime<Utc>,
+    },
 }
 
 impl ApplicationEvent {
@@ -102,6 +138,11 @@ impl ApplicationEvent {
             ApplicationEvent::SpecificationCreated { .. } => "SpecificationCreated",
             ApplicationEvent::SpecificationApproved { .. } => "SpecificationApproved",
             ApplicationEvent::SpecificationCompleted { .. } => "SpecificationCompleted",
+            ApplicationEvent::SagaStarted { .. } => "SagaStarted",
+            ApplicationEvent::SagaStepCompleted { .. } => "SagaStepCompleted",
+            ApplicationEvent::SagaCompleted { .. } => "SagaCompleted",
+            ApplicationEvent::SagaCompensated { .. } => "SagaCompensated",
+            ApplicationEvent::SagaFailed { .. } => "SagaFailed",
         }
     }
 
@@ -118,6 +159,11 @@ impl ApplicationEvent {
             ApplicationEvent::SpecificationCreated { timestamp, .. } => *timestamp,
             ApplicationEvent::SpecificationApproved { timestamp, .. } => *timestamp,
             ApplicationEvent::SpecificationCompleted { timestamp, .. } => *timestamp,
+            ApplicationEvent::SagaStarted { timestamp, .. } => *timestamp,
+            ApplicationEvent::SagaStepCompleted { timestamp, .. } => *timestamp,
+            ApplicationEvent::SagaCompleted { timestamp, .. } => *timestamp,
+            ApplicationEvent::SagaCompensated { timestamp, .. } => *timestamp,
+            ApplicationEvent::SagaFailed { timestamp, .. } => *timestamp,
         }
     }
 
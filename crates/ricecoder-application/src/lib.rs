@@ -15,7 +15,11 @@
 //! │  ProjectService    │ CreateProjectCmd  │ UnitOfWork         │ AppEvent  │
 //! │  SessionService    │ ProjectDto        │ EventPublisher     │           │
 //! │  SpecService       │ SessionDto        │                    │           │
-//! │  CodeService       │ SpecDto           │                    │           │
+//! │  CodeService       │ SpecDto           │ Authorizer         │           │
+//! ├─────────────────────────────────────────────────────────────────────────┤
+//! │  Bus: CommandBus / QueryBus + middleware (validation, authorization,    │
+//! │       events, transactions)                                             │
+//! │  Saga: SagaOrchestrator (persisted state, compensation, resumption)     │
 //! └─────────────────────────────────────────────────────────────────────────┘
 //!                              ▲
 //!                              │ depends on
@@ -32,6 +36,7 @@
 //! - **Transaction Boundaries**: Wrap atomic operations in UnitOfWork
 //! - **DTO Mapping**: Convert domain objects to/from presentation-safe DTOs
 //! - **Application Events**: Emit use-case-level events for external consumers
+//! - **Long-Running Workflows**: Orchestrate multi-service sagas with compensation
 //! - **Error Mapping**: Translate domain errors to application-level errors
 //!
 //! # Non-Goals
@@ -40,14 +45,17 @@
 //! - Direct I/O operations (belongs in Infrastructure Layer)
 //! - HTTP/CLI handling (belongs in Presentation Layer)
 
+pub mod bus;
 pub mod di;
 pub mod dto;
 pub mod errors;
 pub mod events;
 pub mod ports;
+pub mod saga;
 pub mod services;
 
 // Re-export commonly used types
+pub use bus::{CommandBus, QueryBus, RequestContext};
 pub use dto::*;
 pub use errors::ApplicationError;
 pub use events::{ApplicationEvent, EventPublisher};
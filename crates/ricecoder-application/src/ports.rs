@@ -7,7 +7,8 @@ use async_trait::async_trait;
 use std::future::Future;
 use std::pin::Pin;
 
-use crate::errors::ApplicationResult;
+use crate::bus::RequestContext;
+use crate::errors::{ApplicationError, ApplicationResult};
 
 /// Unit of Work pattern for transaction management
 ///
@@ -54,6 +55,33 @@ impl UnitOfWork for NoOpUnitOfWork {
     }
 }
 
+/// Authorization port for bus dispatch
+///
+/// Decides whether the caller may perform a message's action (e.g.
+/// `project.create`). Infrastructure Layer provides the concrete
+/// implementation, typically mapping actions to `ricecoder-security`
+/// permissions checked against the caller's roles.
+#[async_trait]
+pub trait Authorizer: Send + Sync {
+    /// Return `Err(ApplicationError::Unauthorized)` if the action is not allowed
+    async fn authorize(&self, request: &RequestContext, action: &str) -> ApplicationResult<()>;
+}
+
+/// Authorizer that only requires an authenticated actor
+///
+/// Used for testing or single-user setups
+pub struct AuthenticatedAuthorizer;
+
+#[async_trait]
+impl Authorizer for AuthenticatedAuthorizer {
+    async fn authorize(&self, request: &RequestContext, action: &str) -> ApplicationResult<()> {
+        if request.actor_id.is_none() {
+            return Err(ApplicationError::Unauthorized(action.to_string()));
+        }
+        Ok(())
+    }
+}
+
 /// Box wrapper for async closures in UnitOfWork
 pub type BoxedFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
 
@@ -80,4 +108,17 @@ mod tests {
             .await;
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_authenticated_authorizer() {
+        let authorizer = AuthenticatedAuthorizer;
+        let anonymous = RequestContext::new();
+        let actor = RequestContext::for_actor("alice", vec![]);
+
+        assert!(matches!(
+            authorizer.authorize(&anonymous, "project.create").await,
+            Err(ApplicationError::Unauthorized(_))
+        ));
+        assert!(authorizer.authorize(&actor, "project.create").await.is_ok());
+    }
 }
@@ -0,0 +1,200 @@
+//! Saga orchestration for long-running workflows
+//!
+//! A saga coordinates a multi-step use case spanning several services
+//! (e.g. spec → plan → generation → PR). Each step has a compensating action
+//! that undoes it; when a step fails or times out, the completed steps are
+//! compensated in reverse order.
+//!
+//! Saga state is persisted through the [`SagaStore`] port after every step,
+//! so a saga interrupted by a restart can be picked up again with
+//! [`SagaOrchestrator::resume_incomplete`]. Lifecycle changes are emitted as
+//! [`ApplicationEvent`](crate::events::ApplicationEvent)s.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let definition = SagaDefinition::new("spec-to-pr")
+//!     .step(ValidateSpecStep)
+//!     .step(PlanTasksStep)
+//!     .step(GenerateCodeStep)
+//!     .step(OpenPullRequestStep)
+//!     .with_step_timeout(Duration::from_secs(300));
+//!
+//! let mut orchestrator = SagaOrchestrator::new(store, events);
+//! orchestrator.register(definition);
+//!
+//! // On startup, finish whatever was interrupted
+//! orchestrator.resume_incomplete().await?;
+//!
+//! let state = orchestrator.start("spec-to-pr", json!({ "spec_id": spec_id })).await?;
+//! ```
+
+mod orchestrator;
+mod store;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::errors::ApplicationResult;
+
+pub use orchestrator::SagaOrchestrator;
+pub use store::{InMemorySagaStore, SagaStore};
+
+/// One step of a saga
+///
+/// Steps share a JSON data bag that is persisted with the saga, so anything a
+/// later step or a compensation needs must be written to it.
+#[async_trait]
+pub trait SagaStep: Send + Sync {
+    /// Step name, recorded in saga state and events
+    fn name(&self) -> &str;
+
+    /// Maximum time the step may run, overriding the definition's default
+    fn timeout(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Perform the step
+    async fn execute(&self, data: &mut serde_json::Value) -> ApplicationResult<()>;
+
+    /// Undo the step after a later step failed
+    async fn compensate(&self, _data: &mut serde_json::Value) -> ApplicationResult<()> {
+        Ok(())
+    }
+}
+
+/// Ordered steps that make up a saga type
+#[derive(Clone)]
+pub struct SagaDefinition {
+    saga_type: String,
+    steps: Vec<Arc<dyn SagaStep>>,
+    step_timeout: Option<Duration>,
+    deadline: Option<Duration>,
+}
+
+impl SagaDefinition {
+    /// Create an empty saga definition
+    pub fn new(saga_type: impl Into<String>) -> Self {
+        Self {
+            saga_type: saga_type.into(),
+            steps: Vec::new(),
+            step_timeout: None,
+            deadline: None,
+        }
+    }
+
+    /// Append a step
+    pub fn step<S: SagaStep + 'static>(mut self, step: S) -> Self {
+        self.steps.push(Arc::new(step));
+        self
+    }
+
+    /// Default timeout for steps that don't declare their own
+    pub fn with_step_timeout(mut self, timeout: Duration) -> Self {
+        self.step_timeout = Some(timeout);
+        self
+    }
+
+    /// Maximum total run time; sagas past their deadline are compensated
+    pub fn with_deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Saga type name
+    pub fn saga_type(&self) -> &str {
+        &self.saga_type
+    }
+
+    /// Number of steps
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Check if the saga has no steps
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    pub(crate) fn steps(&self) -> &[Arc<dyn SagaStep>] {
+        &self.steps
+    }
+
+    pub(crate) fn timeout_for(&self, step: &dyn SagaStep) -> Option<Duration> {
+        step.timeout().or(self.step_timeout)
+    }
+}
+
+/// Lifecycle status of a saga
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SagaStatus {
+    /// Steps are being executed
+    Running,
+    /// A step failed; completed steps are being compensated
+    Compensating,
+    /// All steps completed
+    Completed,
+    /// All completed steps were compensated after a failure
+    Compensated,
+    /// A compensation failed; manual intervention is required
+    Failed,
+}
+
+impl SagaStatus {
+    /// Whether the saga still has work to do
+    pub fn is_incomplete(&self) -> bool {
+        matches!(self, SagaStatus::Running | SagaStatus::Compensating)
+    }
+}
+
+/// Persisted state of a saga instance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SagaState {
+    pub saga_id: String,
+    pub saga_type: String,
+    pub status: SagaStatus,
+    /// While running: index of the next step to execute.
+    /// While compensating: number of completed steps left to compensate.
+    pub current_step: usize,
+    /// Names of steps that have completed and not been compensated
+    pub completed_steps: Vec<String>,
+    /// Data shared between steps
+    pub data: serde_json::Value,
+    /// Reason the saga failed, if it did
+    pub error: Option<String>,
+    /// Time after which the saga is compensated instead of continued
+    pub deadline: Option<DateTime<Utc>>,
+    pub started_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl SagaState {
+    pub(crate) fn new(definition: &SagaDefinition, data: serde_json::Value) -> Self {
+        let now = Utc::now();
+        Self {
+            saga_id: Uuid::new_v4().to_string(),
+            saga_type: definition.saga_type.clone(),
+            status: SagaStatus::Running,
+            current_step: 0,
+            completed_steps: Vec::new(),
+            data,
+            error: None,
+            deadline: definition
+                .deadline
+                .and_then(|deadline| chrono::Duration::from_std(deadline).ok())
+                .map(|deadline| now + deadline),
+            started_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Whether the saga's deadline has passed
+    pub fn is_past_deadline(&self) -> bool {
+        self.deadline.is_some_and(|deadline| Utc::now() >= deadline)
+    }
+}
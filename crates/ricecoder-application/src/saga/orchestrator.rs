@@ -0,0 +1,503 @@
+//! Saga orchestrator
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::Utc;
+
+use super::{SagaDefinition, SagaState, SagaStatus, SagaStep, SagaStore};
+use crate::errors::{ApplicationError, ApplicationResult};
+use crate::events::{ApplicationEvent, EventPublisher};
+
+/// Runs sagas step by step, compensating on failure
+///
+/// Step failures do not surface as errors: the returned [`SagaState`] reports
+/// whether the saga completed, was compensated, or failed during
+/// compensation. Errors are reserved for unknown sagas and store failures.
+pub struct SagaOrchestrator<S, E>
+where
+    S: SagaStore,
+    E: EventPublisher + Send + Sync,
+{
+    store: Arc<S>,
+    events: Arc<E>,
+    definitions: HashMap<String, SagaDefinition>,
+}
+
+impl<S, E> SagaOrchestrator<S, E>
+where
+    S: SagaStore,
+    E: EventPublisher + Send + Sync,
+{
+    /// Create a new SagaOrchestrator with injected dependencies
+    pub fn new(store: Arc<S>, events: Arc<E>) -> Self {
+        Self {
+            store,
+            events,
+            definitions: HashMap::new(),
+        }
+    }
+
+    /// Register a saga definition, replacing any with the same type
+    pub fn register(&mut self, definition: SagaDefinition) {
+        self.definitions
+            .insert(definition.saga_type().to_string(), definition);
+    }
+
+    /// Start a new saga and run it to completion or compensation
+    pub async fn start(
+        &self,
+        saga_type: &str,
+        data: serde_json::Value,
+    ) -> ApplicationResult<SagaState> {
+        let definition = self.definition(saga_type)?;
+        let state = SagaState::new(definition, data);
+        self.store.save(&state).await?;
+
+        self.events
+            .publish(ApplicationEvent::SagaStarted {
+                saga_id: state.saga_id.clone(),
+                saga_type: state.saga_type.clone(),
+                timestamp: Utc::now(),
+            })
+            .await;
+
+        self.drive(definition, state).await
+    }
+
+    /// Continue an interrupted saga from its persisted state
+    ///
+    /// Finished sagas are returned unchanged.
+    pub async fn resume(&self, saga_id: &str) -> ApplicationResult<SagaState> {
+        let state = self.status(saga_id).await?;
+        if !state.status.is_incomplete() {
+            return Ok(state);
+        }
+
+        let definition = self.definition(&state.saga_type)?;
+        self.drive(definition, state).await
+    }
+
+    /// Continue every saga left running or compensating, e.g. after a restart
+    ///
+    /// Sagas whose type is no longer registered are logged and left in the
+    /// store, so one retired saga type does not block the others.
+    pub async fn resume_incomplete(&self) -> ApplicationResult<Vec<SagaState>> {
+        let mut resumed = Vec::new();
+        for state in self.store.find_incomplete().await? {
+            let Some(definition) = self.definitions.get(&state.saga_type) else {
+                tracing::warn!(
+                    saga_id = %state.saga_id,
+                    saga_type = %state.saga_type,
+                    "Skipping incomplete saga of unknown type"
+                );
+                continue;
+            };
+            resumed.push(self.drive(definition, state).await?);
+        }
+        Ok(resumed)
+    }
+
+    /// Get the persisted state of a saga
+    pub async fn status(&self, saga_id: &str) -> ApplicationResult<SagaState> {
+        self.store
+            .load(saga_id)
+            .await?
+            .ok_or_else(|| ApplicationError::SagaNotFound(saga_id.to_string()))
+    }
+
+    fn definition(&self, saga_type: &str) -> ApplicationResult<&SagaDefinition> {
+        self.definitions.get(saga_type).ok_or_else(|| {
+            ApplicationError::OperationNotAllowed(format!("Unknown saga type: {}", saga_type))
+        })
+    }
+
+    async fn drive(
+        &self,
+        definition: &SagaDefinition,
+        mut state: SagaState,
+    ) -> ApplicationResult<SagaState> {
+        if state.status == SagaStatus::Running {
+            while state.current_step < definition.len() {
+                if state.is_past_deadline() {
+                    self.begin_compensation(&mut state, "Saga deadline exceeded".into())
+                        .await?;
+                    break;
+                }
+
+                let step = &definition.steps()[state.current_step];
+                match run_step(definition, step.as_ref(), &mut state.data, false).await {
+                    Ok(()) => {
+                        state.completed_steps.push(step.name().to_string());
+                        state.current_step += 1;
+                        self.save(&mut state).await?;
+
+                        self.events
+                            .publish(ApplicationEvent::SagaStepCompleted {
+                                saga_id: state.saga_id.clone(),
+                                step: step.name().to_string(),
+                                timestamp: Utc::now(),
+                            })
+                            .await;
+                    }
+                    Err(e) => {
+                        let reason = format!("Step {} failed: {}", step.name(), e);
+                        self.begin_compensation(&mut state, reason).await?;
+                        break;
+                    }
+                }
+            }
+
+            if state.status == SagaStatus::Running {
+                state.status = SagaStatus::Completed;
+                self.save(&mut state).await?;
+
+                self.events
+                    .publish(ApplicationEvent::SagaCompleted {
+                        saga_id: state.saga_id.clone(),
+                        timestamp: Utc::now(),
+                    })
+                    .await;
+            }
+        }
+
+        if state.status == SagaStatus::Compensating {
+            self.compensate(definition, &mut state).await?;
+        }
+
+        Ok(state)
+    }
+
+    async fn begin_compensation(
+        &self,
+        state: &mut SagaState,
+        reason: String,
+    ) -> ApplicationResult<()> {
+        state.status = SagaStatus::Compensating;
+        state.current_step = state.completed_steps.len();
+        state.error = Some(reason);
+        self.save(state).await
+    }
+
+    /// Compensate completed steps in reverse order
+    async fn compensate(
+        &self,
+        definition: &SagaDefinition,
+        state: &mut SagaState,
+    ) -> ApplicationResult<()> {
+        while state.current_step > 0 {
+            let step = &definition.steps()[state.current_step - 1];
+            if let Err(e) = run_step(definition, step.as_ref(), &mut state.data, true).await {
+                state.status = SagaStatus::Failed;
+                state.error = Some(format!(
+                    "Compensation of step {} failed: {}",
+                    step.name(),
+                    e
+                ));
+                self.save(state).await?;
+
+                self.events
+                    .publish(ApplicationEvent::SagaFailed {
+                        saga_id: state.saga_id.clone(),
+                        reason: state.error.clone().unwrap_or_default(),
+                        timestamp: Utc::now(),
+                    })
+                    .await;
+                return Ok(());
+            }
+
+            state.completed_steps.pop();
+            state.current_step -= 1;
+            self.save(state).await?;
+        }
+
+        state.status = SagaStatus::Compensated;
+        self.save(state).await?;
+
+        self.events
+            .publish(ApplicationEvent::SagaCompensated {
+                saga_id: state.saga_id.clone(),
+                reason: state.error.clone().unwrap_or_default(),
+                timestamp: Utc::now(),
+            })
+            .await;
+        Ok(())
+    }
+
+    async fn save(&self, state: &mut SagaState) -> ApplicationResult<()> {
+        state.updated_at = Utc::now();
+        self.store.save(state).await
+    }
+}
+
+/// Execute or compensate a step, enforcing its timeout
+async fn run_step(
+    definition: &SagaDefinition,
+    step: &dyn SagaStep,
+    data: &mut serde_json::Value,
+    compensate: bool,
+) -> ApplicationResult<()> {
+    let action = async {
+        if compensate {
+            step.compensate(data).await
+        } else {
+            step.execute(data).await
+        }
+    };
+
+    match definition.timeout_for(step) {
+        Some(timeout) => tokio::time::timeout(timeout, action).await.map_err(|_| {
+            ApplicationError::Timeout(format!(
+                "Step {} timed out after {:?}",
+                step.name(),
+                timeout
+            ))
+        })?,
+        None => action.await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::InMemoryEventPublisher;
+    use crate::saga::InMemorySagaStore;
+    use async_trait::async_trait;
+    use serde_json::json;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    /// Step that appends to a shared log and optionally fails or hangs
+    struct LogStep {
+        name: &'static str,
+        log: Arc<Mutex<Vec<String>>>,
+        fail: bool,
+        delay: Option<Duration>,
+        fail_compensation: bool,
+    }
+
+    impl LogStep {
+        fn new(name: &'static str, log: &Arc<Mutex<Vec<String>>>) -> Self {
+            Self {
+                name,
+                log: Arc::clone(log),
+                fail: false,
+                delay: None,
+                fail_compensation: false,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl SagaStep for LogStep {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        async fn execute(&self, data: &mut serde_json::Value) -> ApplicationResult<()> {
+            if let Some(delay) = self.delay {
+                tokio::time::sleep(delay).await;
+            }
+            if self.fail {
+                return Err(ApplicationError::BusinessRuleViolation("boom".into()));
+            }
+            self.log.lock().unwrap().push(format!("do:{}", self.name));
+            data[self.name] = json!(true);
+            Ok(())
+        }
+
+        async fn compensate(&self, data: &mut serde_json::Value) -> ApplicationResult<()> {
+            if self.fail_compensation {
+                return Err(ApplicationError::RepositoryError("cannot undo".into()));
+            }
+            self.log.lock().unwrap().push(format!("undo:{}", self.name));
+            data[self.name] = json!(false);
+            Ok(())
+        }
+    }
+
+    fn orchestrator(
+        definition: SagaDefinition,
+    ) -> (
+        SagaOrchestrator<InMemorySagaStore, InMemoryEventPublisher>,
+        Arc<InMemorySagaStore>,
+        Arc<InMemoryEventPublisher>,
+    ) {
+        let store = Arc::new(InMemorySagaStore::new());
+        let events = Arc::new(InMemoryEventPublisher::new());
+        let mut orchestrator = SagaOrchestrator::new(Arc::clone(&store), Arc::clone(&events));
+        orchestrator.register(definition);
+        (orchestrator, store, events)
+    }
+
+    fn event_types(events: &InMemoryEventPublisher) -> Vec<&'static str> {
+        events.events().iter().map(|e| e.event_type()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_saga_completes_all_steps() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let definition = SagaDefinition::new("spec-to-pr")
+            .step(LogStep::new("plan", &log))
+            .step(LogStep::new("generate", &log));
+        let (orchestrator, store, events) = orchestrator(definition);
+
+        let state = orchestrator.start("spec-to-pr", json!({})).await.unwrap();
+
+        assert_eq!(state.status, SagaStatus::Completed);
+        assert_eq!(state.completed_steps, vec!["plan", "generate"]);
+        assert_eq!(state.data, json!({ "plan": true, "generate": true }));
+        assert_eq!(
+            store.sagas()[0].status,
+            SagaStatus::Completed,
+            "final state is persisted"
+        );
+        assert_eq!(
+            event_types(&events),
+            vec![
+                "SagaStarted",
+                "SagaStepCompleted",
+                "SagaStepCompleted",
+                "SagaCompleted"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_step_compensates_in_reverse_order() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let definition = SagaDefinition::new("spec-to-pr")
+            .step(LogStep::new("plan", &log))
+            .step(LogStep::new("generate", &log))
+            .step(LogStep {
+                fail: true,
+                ..LogStep::new("open-pr", &log)
+            });
+        let (orchestrator, _, events) = orchestrator(definition);
+
+        let state = orchestrator.start("spec-to-pr", json!({})).await.unwrap();
+
+        assert_eq!(state.status, SagaStatus::Compensated);
+        assert!(state.completed_steps.is_empty());
+        assert!(state.error.unwrap().contains("open-pr"));
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec!["do:plan", "do:generate", "undo:generate", "undo:plan"]
+        );
+        assert_eq!(event_types(&events).last(), Some(&"SagaCompensated"));
+    }
+
+    #[tokio::test]
+    async fn test_step_timeout_triggers_compensation() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let definition = SagaDefinition::new("slow")
+            .step(LogStep::new("plan", &log))
+            .step(LogStep {
+                delay: Some(Duration::from_secs(5)),
+                ..LogStep::new("generate", &log)
+            })
+            .with_step_timeout(Duration::from_millis(20));
+        let (orchestrator, _, _) = orchestrator(definition);
+
+        let state = orchestrator.start("slow", json!({})).await.unwrap();
+
+        assert_eq!(state.status, SagaStatus::Compensated);
+        assert!(state.error.unwrap().contains("timed out"));
+        assert_eq!(*log.lock().unwrap(), vec!["do:plan", "undo:plan"]);
+    }
+
+    #[tokio::test]
+    async fn test_failed_compensation_marks_saga_failed() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let definition = SagaDefinition::new("stuck")
+            .step(LogStep {
+                fail_compensation: true,
+                ..LogStep::new("plan", &log)
+            })
+            .step(LogStep {
+                fail: true,
+                ..LogStep::new("generate", &log)
+            });
+        let (orchestrator, _, events) = orchestrator(definition);
+
+        let state = orchestrator.start("stuck", json!({})).await.unwrap();
+
+        assert_eq!(state.status, SagaStatus::Failed);
+        assert_eq!(state.completed_steps, vec!["plan"]);
+        assert!(state.error.unwrap().contains("Compensation of step plan"));
+        assert_eq!(event_types(&events).last(), Some(&"SagaFailed"));
+    }
+
+    #[tokio::test]
+    async fn test_resume_incomplete_after_restart() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let definition = SagaDefinition::new("spec-to-pr")
+            .step(LogStep::new("plan", &log))
+            .step(LogStep::new("generate", &log));
+        let (orchestrator, store, _) = orchestrator(definition.clone());
+
+        // Simulate a crash after the first step was persisted
+        let mut interrupted = SagaState::new(&definition, json!({ "plan": true }));
+        interrupted.current_step = 1;
+        interrupted.completed_steps = vec!["plan".into()];
+        store.save(&interrupted).await.unwrap();
+
+        let resumed = orchestrator.resume_incomplete().await.unwrap();
+
+        assert_eq!(resumed.len(), 1);
+        assert_eq!(resumed[0].status, SagaStatus::Completed);
+        assert_eq!(*log.lock().unwrap(), vec!["do:generate"]);
+        assert!(store.find_incomplete().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_resume_incomplete_skips_unknown_types() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let definition = SagaDefinition::new("spec-to-pr").step(LogStep::new("plan", &log));
+        let (orchestrator, store, _) = orchestrator(definition.clone());
+
+        let retired = SagaState::new(&SagaDefinition::new("retired"), json!({}));
+        store.save(&retired).await.unwrap();
+        store
+            .save(&SagaState::new(&definition, json!({})))
+            .await
+            .unwrap();
+
+        let resumed = orchestrator.resume_incomplete().await.unwrap();
+
+        assert_eq!(resumed.len(), 1);
+        assert_eq!(resumed[0].saga_type, "spec-to-pr");
+        let remaining = store.find_incomplete().await.unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].saga_id, retired.saga_id);
+    }
+
+    #[tokio::test]
+    async fn test_resume_past_deadline_compensates() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let definition = SagaDefinition::new("spec-to-pr")
+            .step(LogStep::new("plan", &log))
+            .step(LogStep::new("generate", &log))
+            .with_deadline(Duration::from_secs(60));
+        let (orchestrator, store, _) = orchestrator(definition.clone());
+
+        let mut interrupted = SagaState::new(&definition, json!({ "plan": true }));
+        interrupted.current_step = 1;
+        interrupted.completed_steps = vec!["plan".into()];
+        interrupted.deadline = Some(Utc::now() - chrono::Duration::seconds(1));
+        store.save(&interrupted).await.unwrap();
+
+        let state = orchestrator.resume(&interrupted.saga_id).await.unwrap();
+
+        assert_eq!(state.status, SagaStatus::Compensated);
+        assert_eq!(state.error.as_deref(), Some("Saga deadline exceeded"));
+        assert_eq!(*log.lock().unwrap(), vec!["undo:plan"]);
+    }
+
+    #[tokio::test]
+    async fn test_resume_unknown_saga() {
+        let (orchestrator, _, _) = orchestrator(SagaDefinition::new("empty"));
+        let result = orchestrator.resume("missing").await;
+        assert!(matches!(result, Err(ApplicationError::SagaNotFound(_))));
+    }
+}
@@ -0,0 +1,68 @@
+//! Saga state persistence port
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+
+use super::SagaState;
+use crate::errors::ApplicationResult;
+
+/// Saga state store port
+///
+/// Infrastructure Layer provides durable implementations (file, database)
+/// so sagas survive restarts, e.g. `FileSagaStore` in ricecoder-persistence.
+#[async_trait]
+pub trait SagaStore: Send + Sync {
+    /// Insert or replace saga state
+    async fn save(&self, state: &SagaState) -> ApplicationResult<()>;
+
+    /// Load saga state by ID
+    async fn load(&self, saga_id: &str) -> ApplicationResult<Option<SagaState>>;
+
+    /// Load all sagas that are still running or compensating
+    async fn find_incomplete(&self) -> ApplicationResult<Vec<SagaState>>;
+}
+
+/// In-memory saga store for testing
+#[derive(Default)]
+pub struct InMemorySagaStore {
+    sagas: Mutex<HashMap<String, SagaState>>,
+}
+
+impl InMemorySagaStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get all stored sagas
+    pub fn sagas(&self) -> Vec<SagaState> {
+        self.sagas.lock().unwrap().values().cloned().collect()
+    }
+}
+
+#[async_trait]
+impl SagaStore for InMemorySagaStore {
+    async fn save(&self, state: &SagaState) -> ApplicationResult<()> {
+        self.sagas
+            .lock()
+            .unwrap()
+            .insert(state.saga_id.clone(), state.clone());
+        Ok(())
+    }
+
+    async fn load(&self, saga_id: &str) -> ApplicationResult<Option<SagaState>> {
+        Ok(self.sagas.lock().unwrap().get(saga_id).cloned())
+    }
+
+    async fn find_incomplete(&self) -> ApplicationResult<Vec<SagaState>> {
+        Ok(self
+            .sagas
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|state| state.status.is_incomplete())
+            .cloned()
+            .collect())
+    }
+}
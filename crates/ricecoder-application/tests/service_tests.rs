@@ -379,3 +379,106 @@ mod session_service_tests {
         ));
     }
 }
+
+// ============================================================================
+// Bus Tests
+// ============================================================================
+
+#[cfg(test)]
+mod bus_tests {
+    use super::*;
+    use ricecoder_application::bus::{
+        AuthorizationMiddleware, CommandBus, QueryBus, RequestContext, TransactionMiddleware,
+        ValidationMiddleware,
+    };
+    use ricecoder_application::dto::{GetProjectQuery, GetSessionQuery, ListProjectsQuery};
+    use ricecoder_application::ports::AuthenticatedAuthorizer;
+
+    #[tokio::test]
+    async fn test_dispatch_through_services() {
+        let project_repo = Arc::new(MockProjectRepository::default());
+        let session_repo = Arc::new(MockSessionRepository::default());
+        let uow = Arc::new(MockUnitOfWork);
+        let events = Arc::new(MockEventPublisher::default());
+
+        let projects = Arc::new(ProjectService::new(
+            Arc::clone(&project_repo),
+            Arc::clone(&uow),
+            Arc::clone(&events),
+        ));
+        let sessions = Arc::new(SessionService::new(
+            session_repo,
+            project_repo,
+            Arc::clone(&uow),
+            Arc::clone(&events),
+        ));
+
+        let mut commands = CommandBus::new()
+            .with_middleware(ValidationMiddleware)
+            .with_middleware(AuthorizationMiddleware::new(Arc::new(AuthenticatedAuthorizer)))
+            .with_middleware(TransactionMiddleware::new(Arc::clone(&uow)));
+        commands.register::<CreateProjectCommand, _>(Arc::clone(&projects));
+        commands.register::<CreateSessionCommand, _>(Arc::clone(&sessions));
+
+        let mut queries = QueryBus::new().with_middleware(ValidationMiddleware);
+        queries.register::<GetProjectQuery, _>(Arc::clone(&projects));
+        queries.register::<ListProjectsQuery, _>(Arc::clone(&projects));
+        queries.register::<GetSessionQuery, _>(sessions);
+
+        let actor = RequestContext::for_actor("alice", vec!["user".to_string()]);
+        let project_id = commands
+            .dispatch(
+                CreateProjectCommand {
+                    name: "bus-project".to_string(),
+                    language: ProgrammingLanguage::Rust,
+                    root_path: "/path/to/project".to_string(),
+                    description: None,
+                },
+                actor.clone(),
+            )
+            .await
+            .unwrap();
+
+        let session_id = commands
+            .dispatch(
+                CreateSessionCommand {
+                    project_id: project_id.clone(),
+                    max_messages: None,
+                },
+                actor.clone(),
+            )
+            .await
+            .unwrap();
+
+        let project = queries
+            .dispatch(GetProjectQuery { project_id }, actor.clone())
+            .await
+            .unwrap();
+        assert_eq!(project.name, "bus-project");
+        assert_eq!(
+            queries.dispatch(ListProjectsQuery, actor.clone()).await.unwrap().len(),
+            1
+        );
+        let session = queries
+            .dispatch(GetSessionQuery { session_id }, actor)
+            .await
+            .unwrap();
+        assert_eq!(session.project_id, project.id);
+        assert_eq!(events.event_count(), 2);
+
+        // Anonymous callers are rejected before reaching the service
+        let result = commands
+            .dispatch(
+                CreateProjectCommand {
+                    name: "anonymous".to_string(),
+                    language: ProgrammingLanguage::Rust,
+                    root_path: "/path".to_string(),
+                    description: None,
+                },
+                RequestContext::new(),
+            )
+            .await;
+        assert!(matches!(result, Err(ApplicationError::Unauthorized(_))));
+        assert_eq!(events.event_count(), 2);
+    }
+}
@@ -3,17 +3,24 @@
 use std::{
     collections::HashMap,
     sync::{Arc, Mutex},
+    time::Duration,
 };
 
 use ricecoder_monitoring::analytics::{AnalyticsEngine, FeatureAdoptionMetrics, UsageStats};
 use tokio::{sync::mpsc, time};
 
-use super::types::*;
+use super::{
+    telemetry::{TelemetryAggregator, TelemetryExporter, TelemetryReport},
+    types::*,
+};
 
 /// Analytics pipeline for feature usage analysis and prioritization
 pub struct AnalyticsPipeline {
     config: AnalyticsPipelineConfig,
     analytics_engine: Arc<Mutex<AnalyticsEngine>>,
+    telemetry: Arc<Mutex<TelemetryAggregator>>,
+    telemetry_config: Arc<Mutex<TelemetryConfig>>,
+    telemetry_exporter: Arc<TelemetryExporter>,
     shutdown_tx: Option<mpsc::Sender<()>>,
     prioritization_task: Option<tokio::task::JoinHandle<()>>,
 }
@@ -29,8 +36,11 @@ impl AnalyticsPipeline {
         };
 
         Self {
+            telemetry_config: Arc::new(Mutex::new(config.telemetry.clone())),
             config,
             analytics_engine: Arc::new(Mutex::new(AnalyticsEngine::new(analytics_config))),
+            telemetry: Arc::new(Mutex::new(TelemetryAggregator::new())),
+            telemetry_exporter: Arc::new(TelemetryExporter::new()),
             shutdown_tx: None,
             prioritization_task: None,
         }
@@ -57,9 +67,16 @@ impl AnalyticsPipeline {
         let prioritization_interval = self.config.prioritization_interval;
         let analytics_engine = Arc::clone(&self.analytics_engine);
         let threshold = self.config.feature_adoption_threshold;
+        let export_interval = self.config.telemetry.export_interval;
+        let telemetry = Arc::clone(&self.telemetry);
+        let telemetry_config = Arc::clone(&self.telemetry_config);
+        let telemetry_exporter = Arc::clone(&self.telemetry_exporter);
 
         let task = tokio::spawn(async move {
             let mut interval = time::interval(prioritization_interval.to_std().unwrap());
+            let mut export_interval = time::interval(export_interval.to_std().unwrap());
+            // The first tick fires immediately; skip it so a window is collected first
+            export_interval.tick().await;
 
             loop {
                 tokio::select! {
@@ -68,6 +85,11 @@ impl AnalyticsPipeline {
                             tracing::error!("Analytics prioritization failed: {}", e);
                         }
                     }
+                    _ = export_interval.tick() => {
+                        if let Err(e) = Self::export_window(&telemetry, &telemetry_config, &telemetry_exporter).await {
+                            tracing::error!("Telemetry export failed: {}", e);
+                        }
+                    }
                     _ = shutdown_rx.recv() => {
                         tracing::info!("Analytics pipeline prioritization task shutting down");
                         break;
@@ -103,6 +125,9 @@ impl AnalyticsPipeline {
     }
 
     /// Track feature usage
+    ///
+    /// The raw event stays in the local analytics engine; only the feature's
+    /// aggregated counter is eligible for telemetry export.
     pub fn track_feature_usage(
         &self,
         user_id: Option<String>,
@@ -113,6 +138,47 @@ impl AnalyticsPipeline {
             .lock()
             .unwrap()
             .track_action(user_id, feature, properties);
+        self.telemetry.lock().unwrap().record_feature(feature);
+    }
+
+    /// Record how long a feature took
+    pub fn track_latency(&self, feature: &str, latency: Duration) {
+        self.telemetry
+            .lock()
+            .unwrap()
+            .record_latency(feature, latency);
+    }
+
+    /// Grant or revoke consent to export aggregated telemetry
+    pub fn set_telemetry_opt_in(&self, opt_in: bool) {
+        self.telemetry_config.lock().unwrap().opt_in = opt_in;
+    }
+
+    /// Current telemetry export settings
+    pub fn telemetry_config(&self) -> TelemetryConfig {
+        self.telemetry_config.lock().unwrap().clone()
+    }
+
+    /// Exactly what the next telemetry export would send
+    pub fn telemetry_preview(&self) -> TelemetryReport {
+        let config = self.telemetry_config();
+        self.telemetry.lock().unwrap().report(&config)
+    }
+
+    /// Export the current telemetry window now
+    ///
+    /// Returns the sent report, or `None` when nothing was sent because the
+    /// user has not opted in, no endpoint is configured or nothing was
+    /// collected.
+    pub async fn export_telemetry(
+        &self,
+    ) -> Result<Option<TelemetryReport>, ContinuousImprovementError> {
+        Self::export_window(
+            &self.telemetry,
+            &self.telemetry_config,
+            &self.telemetry_exporter,
+        )
+        .await
     }
 
     /// Get analytics insights
@@ -209,6 +275,25 @@ impl AnalyticsPipeline {
         ComponentHealth::Healthy
     }
 
+    /// Close the telemetry window and send its report if permitted
+    async fn export_window(
+        telemetry: &Mutex<TelemetryAggregator>,
+        telemetry_config: &Mutex<TelemetryConfig>,
+        exporter: &TelemetryExporter,
+    ) -> Result<Option<TelemetryReport>, ContinuousImprovementError> {
+        let config = telemetry_config.lock().unwrap().clone();
+        if !config.opt_in || config.endpoint.is_none() {
+            return Ok(None);
+        }
+
+        let report = telemetry.lock().unwrap().close_window(&config);
+        if exporter.export(&config, &report).await? {
+            Ok(Some(report))
+        } else {
+            Ok(None)
+        }
+    }
+
     /// Perform prioritization analysis
     async fn perform_prioritization_analysis(
         analytics_engine: &Mutex<AnalyticsEngine>,
@@ -0,0 +1,724 @@
+//! Crash and panic reporting
+//!
+//! Captures panics (message, location, backtrace) through a panic hook,
+//! redacts user paths and secrets, and stores each report as JSON in a local
+//! directory. Reports only leave the machine when the user has given consent,
+//! in which case [`CrashReporter::submit_pending`] feeds them into the
+//! [`IssueDetectionPipeline`], which deduplicates them by stack fingerprint.
+//! [`detect_crash_trends`] compares crash counts across application versions
+//! to flag new, regressed and increasing crashes.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{
+    issue_detection_pipeline::IssueDetectionPipeline,
+    types::{ContinuousImprovementError, TelemetryConfig},
+};
+
+/// Number of application frames used to compute a stack fingerprint
+const FINGERPRINT_FRAMES: usize = 5;
+
+/// Frame prefixes that belong to the runtime rather than the application
+const RUNTIME_FRAME_PREFIXES: &[&str] = &[
+    "std::",
+    "core::",
+    "alloc::",
+    "<std::",
+    "<core::",
+    "<alloc::",
+    "rust_begin_unwind",
+    "rust_panic",
+    "__rust",
+    "backtrace::",
+    "ricecoder_beta::improvement::crash_reporting::",
+    "__libc",
+    "_start",
+    "start_thread",
+    "clone",
+];
+
+static USER_DIR_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(/home/|/Users/|[A-Za-z]:\\Users\\|[A-Za-z]:/Users/)[^/\\\s:]+")
+        .expect("valid user dir regex")
+});
+static CARGO_REGISTRY_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"[^\s]*[/\\]\.cargo[/\\]registry[/\\]src[/\\][^/\\]+[/\\]")
+        .expect("valid cargo registry regex")
+});
+static SECRET_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(api[_-]?key|token|secret|password|passwd|authorization)\b(\s*[=:]\s*)\S+")
+        .expect("valid secret regex")
+});
+static FRAME_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*\d+:\s+(?:0x[0-9a-f]+ - )?(.+?)\s*$").expect("valid frame regex")
+});
+static FRAME_HASH_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"::h[0-9a-f]{16}$").expect("valid frame hash regex"));
+static NUMBER_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\d+").expect("valid number regex"));
+
+/// Redact user directories, home paths and secret-looking values
+pub fn redact_sensitive(text: &str) -> String {
+    let mut redacted = text.to_string();
+    if let Some(home) = dirs::home_dir() {
+        let home = home.to_string_lossy();
+        if home.len() > 1 {
+            redacted = redacted.replace(home.as_ref(), "~");
+        }
+    }
+    let redacted = CARGO_REGISTRY_RE.replace_all(&redacted, "<cargo>/");
+    let redacted = USER_DIR_RE.replace_all(&redacted, "${1}<user>");
+    SECRET_RE
+        .replace_all(&redacted, "${1}${2}<redacted>")
+        .into_owned()
+}
+
+/// Extract function frames from a rendered backtrace
+pub fn parse_backtrace_frames(backtrace: &str) -> Vec<String> {
+    backtrace
+        .lines()
+        .filter_map(|line| FRAME_RE.captures(line))
+        .map(|caps| FRAME_HASH_RE.replace(&caps[1], "").into_owned())
+        .collect()
+}
+
+/// Compute a stable fingerprint for a crash
+///
+/// Uses the top application frames (ignoring runtime frames, addresses and
+/// symbol hashes) so the same crash maps to the same fingerprint across
+/// builds. Falls back to the panic location and the message with numbers
+/// normalized when no application frames are available.
+pub fn stack_fingerprint(frames: &[String], location: Option<&str>, message: &str) -> String {
+    let app_frames: Vec<&str> = frames
+        .iter()
+        .map(String::as_str)
+        .filter(|frame| !RUNTIME_FRAME_PREFIXES.iter().any(|p| frame.starts_with(p)))
+        .take(FINGERPRINT_FRAMES)
+        .collect();
+
+    let key = if app_frames.is_empty() {
+        let file = location
+            .map(|loc| loc.split(':').next().unwrap_or(loc))
+            .unwrap_or("");
+        format!("{}|{}", file, NUMBER_RE.replace_all(message, "N"))
+    } else {
+        app_frames.join("|")
+    };
+
+    format!("{:016x}", fnv1a_64(key.as_bytes()))
+}
+
+/// FNV-1a, used because it is stable across Rust releases
+fn fnv1a_64(bytes: &[u8]) -> u64 {
+    const OFFSET: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+    bytes.iter().fold(OFFSET, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+    })
+}
+
+/// A captured crash, redacted and fingerprinted
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CrashReport {
+    pub id: Uuid,
+    pub fingerprint: String,
+    pub message: String,
+    pub location: Option<String>,
+    pub thread: Option<String>,
+    pub frames: Vec<String>,
+    pub backtrace: String,
+    pub app_version: String,
+    pub os: String,
+    pub occurred_at: DateTime<Utc>,
+    pub submitted: bool,
+}
+
+impl CrashReport {
+    /// Build a report, redacting sensitive data and computing the fingerprint
+    pub fn new(
+        message: &str,
+        location: Option<&str>,
+        backtrace: &str,
+        app_version: impl Into<String>,
+    ) -> Self {
+        let message = redact_sensitive(message);
+        let location = location.map(redact_sensitive);
+        let backtrace = redact_sensitive(backtrace);
+        let frames = parse_backtrace_frames(&backtrace);
+        let fingerprint = stack_fingerprint(&frames, location.as_deref(), &message);
+
+        Self {
+            id: Uuid::new_v4(),
+            fingerprint,
+            message,
+            location,
+            thread: None,
+            frames,
+            backtrace,
+            app_version: app_version.into(),
+            os: std::env::consts::OS.to_string(),
+            occurred_at: Utc::now(),
+            submitted: false,
+        }
+    }
+
+    /// Set the name of the panicking thread
+    pub fn with_thread(mut self, thread: impl Into<String>) -> Self {
+        self.thread = Some(thread.into());
+        self
+    }
+}
+
+/// Local crash report storage (one JSON file per report)
+#[derive(Debug, Clone)]
+pub struct CrashReportStore {
+    dir: PathBuf,
+}
+
+impl CrashReportStore {
+    /// Create a store in the given directory
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Default location in the user's local data directory
+    pub fn default_dir() -> Option<PathBuf> {
+        dirs::data_local_dir().map(|dir| dir.join("ricecoder").join("crashes"))
+    }
+
+    /// Storage directory
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Persist a report
+    pub fn save(&self, report: &CrashReport) -> Result<PathBuf, ContinuousImprovementError> {
+        fs::create_dir_all(&self.dir).map_err(|e| store_error("create crash directory", e))?;
+        let path = self.dir.join(format!("{}.json", report.id));
+        let json = serde_json::to_string_pretty(report)
+            .map_err(|e| ContinuousImprovementError::IssueDetectionError(e.to_string()))?;
+        fs::write(&path, json).map_err(|e| store_error("write crash report", e))?;
+        Ok(path)
+    }
+
+    /// Load all stored reports, oldest first
+    pub fn load_all(&self) -> Result<Vec<CrashReport>, ContinuousImprovementError> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(store_error("read crash directory", e)),
+        };
+
+        let mut reports: Vec<CrashReport> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|path| {
+                let content = fs::read_to_string(&path).ok()?;
+                match serde_json::from_str(&content) {
+                    Ok(report) => Some(report),
+                    Err(e) => {
+                        tracing::warn!(
+                            "Skipping unreadable crash report {}: {}",
+                            path.display(),
+                            e
+                        );
+                        None
+                    }
+                }
+            })
+            .collect();
+        reports.sort_by_key(|report| report.occurred_at);
+        Ok(reports)
+    }
+
+    /// Reports not yet submitted
+    pub fn pending(&self) -> Result<Vec<CrashReport>, ContinuousImprovementError> {
+        Ok(self
+            .load_all()?
+            .into_iter()
+            .filter(|report| !report.submitted)
+            .collect())
+    }
+
+    /// Mark a report as submitted
+    pub fn mark_submitted(&self, report: &CrashReport) -> Result<(), ContinuousImprovementError> {
+        let mut report = report.clone();
+        report.submitted = true;
+        self.save(&report).map(|_| ())
+    }
+
+    /// Keep only the newest `max_reports` reports, returning how many were removed
+    pub fn prune(&self, max_reports: usize) -> Result<usize, ContinuousImprovementError> {
+        let reports = self.load_all()?;
+        let excess = reports.len().saturating_sub(max_reports);
+        for report in &reports[..excess] {
+            let path = self.dir.join(format!("{}.json", report.id));
+            fs::remove_file(&path).map_err(|e| store_error("remove crash report", e))?;
+        }
+        Ok(excess)
+    }
+}
+
+fn store_error(action: &str, error: std::io::Error) -> ContinuousImprovementError {
+    ContinuousImprovementError::IssueDetectionError(format!("Failed to {}: {}", action, error))
+}
+
+/// Crashes sharing a stack fingerprint
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CrashGroup {
+    pub fingerprint: String,
+    pub message: String,
+    pub location: Option<String>,
+    pub frames: Vec<String>,
+    pub count: u64,
+    pub by_version: BTreeMap<String, u64>,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+}
+
+impl CrashGroup {
+    /// Start a group from its first report
+    pub fn from_report(report: &CrashReport) -> Self {
+        let mut group = Self {
+            fingerprint: report.fingerprint.clone(),
+            message: report.message.clone(),
+            location: report.location.clone(),
+            frames: report.frames.clone(),
+            count: 0,
+            by_version: BTreeMap::new(),
+            first_seen: report.occurred_at,
+            last_seen: report.occurred_at,
+        };
+        group.record(report);
+        group
+    }
+
+    /// Add another occurrence
+    pub fn record(&mut self, report: &CrashReport) {
+        self.count += 1;
+        *self
+            .by_version
+            .entry(report.app_version.clone())
+            .or_insert(0) += 1;
+        self.first_seen = self.first_seen.min(report.occurred_at);
+        self.last_seen = self.last_seen.max(report.occurred_at);
+    }
+}
+
+/// Group reports by fingerprint
+pub fn group_crash_reports(reports: &[CrashReport]) -> Vec<CrashGroup> {
+    let mut groups: HashMap<&str, CrashGroup> = HashMap::new();
+    for report in reports {
+        groups
+            .entry(report.fingerprint.as_str())
+            .and_modify(|group| group.record(report))
+            .or_insert_with(|| CrashGroup::from_report(report));
+    }
+    let mut groups: Vec<CrashGroup> = groups.into_values().collect();
+    groups.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then(a.fingerprint.cmp(&b.fingerprint))
+    });
+    groups
+}
+
+/// How a crash evolves across versions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CrashTrendStatus {
+    /// First seen in the latest version
+    New,
+    /// Seen before, absent in the previous version, back in the latest
+    Regressed,
+    /// More occurrences than in the previous version
+    Increasing,
+    /// Fewer occurrences than in the previous version
+    Decreasing,
+    /// Same number of occurrences as in the previous version
+    Stable,
+    /// Not seen in the latest version
+    Resolved,
+}
+
+/// Trend of a crash group across versions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CrashTrend {
+    pub fingerprint: String,
+    pub message: String,
+    pub status: CrashTrendStatus,
+    pub latest_version: String,
+    pub latest_count: u64,
+    pub previous_count: u64,
+    pub total: u64,
+}
+
+/// Compare versions semantically, falling back to string order
+fn compare_app_versions(a: &str, b: &str) -> std::cmp::Ordering {
+    let parse = |v: &str| semver::Version::parse(v.trim_start_matches('v')).ok();
+    match (parse(a), parse(b)) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        _ => a.cmp(b),
+    }
+}
+
+/// Detect crash trends across application versions
+///
+/// The latest version is the newest version seen in any group; each group
+/// is compared between that version and the one before it.
+pub fn detect_crash_trends(groups: &[CrashGroup]) -> Vec<CrashTrend> {
+    let mut versions: Vec<&str> = groups
+        .iter()
+        .flat_map(|group| group.by_version.keys().map(String::as_str))
+        .collect();
+    versions.sort_by(|a, b| compare_app_versions(a, b));
+    versions.dedup();
+
+    let Some(latest) = versions.last().copied() else {
+        return Vec::new();
+    };
+    let previous = versions.len().checked_sub(2).map(|idx| versions[idx]);
+
+    let mut trends: Vec<CrashTrend> = groups
+        .iter()
+        .map(|group| {
+            let count_in = |version: Option<&str>| {
+                version
+                    .and_then(|v| group.by_version.get(v))
+                    .copied()
+                    .unwrap_or(0)
+            };
+            let latest_count = count_in(Some(latest));
+            let previous_count = count_in(previous);
+            let seen_before = group
+                .by_version
+                .keys()
+                .any(|v| compare_app_versions(v, latest).is_lt());
+
+            let status = if latest_count == 0 {
+                CrashTrendStatus::Resolved
+            } else if !seen_before {
+                CrashTrendStatus::New
+            } else if previous_count == 0 {
+                CrashTrendStatus::Regressed
+            } else if latest_count > previous_count {
+                CrashTrendStatus::Increasing
+            } else if latest_count < previous_count {
+                CrashTrendStatus::Decreasing
+            } else {
+                CrashTrendStatus::Stable
+            };
+
+            CrashTrend {
+                fingerprint: group.fingerprint.clone(),
+                message: group.message.clone(),
+                status,
+                latest_version: latest.to_string(),
+                latest_count,
+                previous_count,
+                total: group.count,
+            }
+        })
+        .collect();
+    trends.sort_by(|a, b| {
+        b.latest_count
+            .cmp(&a.latest_count)
+            .then(a.fingerprint.cmp(&b.fingerprint))
+    });
+    trends
+}
+
+/// Result of submitting stored reports
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CrashSubmission {
+    /// Reports handed to the pipeline
+    pub submitted: usize,
+    /// Reports that opened a new issue
+    pub new_issues: usize,
+    /// Reports merged into an existing issue
+    pub duplicates: usize,
+}
+
+/// Captures crashes locally and submits them with consent
+pub struct CrashReporter {
+    store: CrashReportStore,
+    app_version: String,
+    consent: AtomicBool,
+}
+
+impl CrashReporter {
+    /// Create a reporter without submission consent
+    pub fn new(store: CrashReportStore) -> Self {
+        Self {
+            store,
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            consent: AtomicBool::new(false),
+        }
+    }
+
+    /// Set the application version recorded in reports
+    pub fn with_app_version(mut self, version: impl Into<String>) -> Self {
+        self.app_version = version.into();
+        self
+    }
+
+    /// Grant or revoke consent to submit reports
+    pub fn set_consent(&self, granted: bool) {
+        self.consent.store(granted, Ordering::SeqCst);
+    }
+
+    /// Whether reports may be submitted
+    pub fn has_consent(&self) -> bool {
+        self.consent.load(Ordering::SeqCst)
+    }
+
+    /// Local report store
+    pub fn store(&self) -> &CrashReportStore {
+        &self.store
+    }
+
+    /// Capture a crash and store it locally
+    pub fn capture(
+        &self,
+        message: &str,
+        location: Option<&str>,
+        backtrace: &str,
+    ) -> Result<CrashReport, ContinuousImprovementError> {
+        let report = CrashReport::new(message, location, backtrace, self.app_version.clone());
+        self.store.save(&report)?;
+        Ok(report)
+    }
+
+    /// Install a panic hook that stores a report before running the previous hook
+    pub fn install_panic_hook(self: Arc<Self>) {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            let payload = info.payload();
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "panic with non-string payload".to_string());
+            let location = info
+                .location()
+                .map(|loc| format!("{}:{}:{}", loc.file(), loc.line(), loc.column()));
+            let backtrace = std::backtrace::Backtrace::force_capture().to_string();
+
+            let mut report = CrashReport::new(
+                &message,
+                location.as_deref(),
+                &backtrace,
+                self.app_version.clone(),
+            );
+            if let Some(name) = std::thread::current().name() {
+                report = report.with_thread(name);
+            }
+            if let Err(e) = self.store.save(&report) {
+                eprintln!("Failed to store crash report: {}", e);
+            }
+
+            previous(info);
+        }));
+    }
+
+    /// Install the panic hook if the user has consented to telemetry
+    ///
+    /// Startup code calls this with the configured telemetry settings.
+    /// Without consent no hook is installed and crashes are not captured.
+    pub fn install_if_consented(self, telemetry: &TelemetryConfig) -> Option<Arc<Self>> {
+        if !telemetry.opt_in {
+            return None;
+        }
+
+        self.set_consent(true);
+        let reporter = Arc::new(self);
+        reporter.clone().install_panic_hook();
+        Some(reporter)
+    }
+
+    /// Install crash reporting into the default store from config settings
+    ///
+    /// Reads consent from the `telemetry` section of the config's custom
+    /// settings; an invalid section counts as no consent.
+    pub fn install_from_settings(
+        settings: &HashMap<String, serde_json::Value>,
+        app_version: &str,
+    ) -> Option<Arc<Self>> {
+        let telemetry = TelemetryConfig::from_settings(settings)
+            .map_err(|e| tracing::warn!("Crash reporting disabled: {}", e))
+            .ok()?;
+        let store = CrashReportStore::new(CrashReportStore::default_dir()?);
+        Self::new(store)
+            .with_app_version(app_version)
+            .install_if_consented(&telemetry)
+    }
+
+    /// Submit pending reports to the issue detection pipeline
+    ///
+    /// Does nothing without consent; reports then stay local.
+    pub async fn submit_pending(
+        &self,
+        pipeline: &IssueDetectionPipeline,
+    ) -> Result<CrashSubmission, ContinuousImprovementError> {
+        let mut submission = CrashSubmission::default();
+        if !self.has_consent() {
+            return Ok(submission);
+        }
+
+        for report in self.store.pending()? {
+            if pipeline.ingest_crash_report(&report) {
+                submission.new_issues += 1;
+            } else {
+                submission.duplicates += 1;
+            }
+            self.store.mark_submitted(&report)?;
+            submission.submitted += 1;
+        }
+
+        tracing::info!(
+            "Submitted {} crash reports ({} new issues)",
+            submission.submitted,
+            submission.new_issues
+        );
+        Ok(submission)
+    }
+
+    /// Crash trends across versions from all locally stored reports
+    pub fn trends(&self) -> Result<Vec<CrashTrend>, ContinuousImprovementError> {
+        let reports = self.store.load_all()?;
+        Ok(detect_crash_trends(&group_crash_reports(&reports)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::improvement::IssueDetectionPipelineConfig;
+
+    const BACKTRACE: &str = "   0: rust_begin_unwind
+             at /rustc/abc/library/std/src/panicking.rs:645:5
+   1: core::panicking::panic_fmt
+   2: ricecoder_tui::tui::render_messages::h0123456789abcdef
+             at /home/alice/src/ricecoder/crates/ricecoder-tui/src/tui/mod.rs:612:9
+   3: ricecoder_tui::tui::render_ui
+   4: std::rt::lang_start";
+
+    fn report(version: &str, line: u32) -> CrashReport {
+        let backtrace = BACKTRACE.replace("612", &line.to_string());
+        CrashReport::new(
+            "index out of bounds: the len is 3 but the index is 7",
+            Some("crates/ricecoder-tui/src/tui/mod.rs:612:9"),
+            &backtrace,
+            version,
+        )
+    }
+
+    #[test]
+    fn test_redacts_paths_and_secrets() {
+        let redacted = redact_sensitive(
+            "failed at /home/alice/work/app.rs and C:\\Users\\bob\\x.rs with api_key=sk-123",
+        );
+        assert!(!redacted.contains("alice"));
+        assert!(!redacted.contains("bob"));
+        assert!(!redacted.contains("sk-123"));
+        assert!(redacted.contains("api_key=<redacted>"));
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_runtime_frames_and_lines() {
+        let a = report("0.1.0", 612);
+        let b = report("0.2.0", 640);
+        assert_eq!(a.fingerprint, b.fingerprint);
+        assert_eq!(
+            a.frames[2], "ricecoder_tui::tui::render_messages",
+            "symbol hash should be stripped"
+        );
+
+        let other = CrashReport::new("boom", Some("src/main.rs:1:1"), "", "0.1.0");
+        assert_ne!(a.fingerprint, other.fingerprint);
+    }
+
+    #[test]
+    fn test_trends_across_versions() {
+        let mut reports = vec![report("0.1.0", 1), report("0.1.0", 1), report("0.2.0", 1)];
+        let fresh = CrashReport::new("new panic", Some("src/lib.rs:3:1"), "", "0.2.0");
+        reports.push(fresh.clone());
+        let old = CrashReport::new("old panic", Some("src/old.rs:3:1"), "", "0.1.0");
+        reports.push(old.clone());
+
+        let trends = detect_crash_trends(&group_crash_reports(&reports));
+        let status = |fp: &str| trends.iter().find(|t| t.fingerprint == fp).unwrap().status;
+        assert_eq!(
+            status(&reports[0].fingerprint),
+            CrashTrendStatus::Decreasing
+        );
+        assert_eq!(status(&fresh.fingerprint), CrashTrendStatus::New);
+        assert_eq!(status(&old.fingerprint), CrashTrendStatus::Resolved);
+    }
+
+    #[tokio::test]
+    async fn test_submit_requires_consent_and_deduplicates() {
+        let dir = tempfile::tempdir().unwrap();
+        let reporter = CrashReporter::new(CrashReportStore::new(dir.path()));
+        reporter
+            .capture("boom", Some("src/a.rs:1:1"), BACKTRACE)
+            .unwrap();
+        reporter
+            .capture("boom", Some("src/a.rs:1:1"), BACKTRACE)
+            .unwrap();
+
+        let pipeline = IssueDetectionPipeline::new(IssueDetectionPipelineConfig::default());
+        let submission = reporter.submit_pending(&pipeline).await.unwrap();
+        assert_eq!(submission.submitted, 0);
+        assert_eq!(reporter.store().pending().unwrap().len(), 2);
+
+        reporter.set_consent(true);
+        let submission = reporter.submit_pending(&pipeline).await.unwrap();
+        assert_eq!(submission.submitted, 2);
+        assert_eq!(submission.new_issues, 1);
+        assert_eq!(submission.duplicates, 1);
+        assert_eq!(pipeline.crash_groups()[0].count, 2);
+        assert!(reporter.store().pending().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_panic_hook_requires_telemetry_consent() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = CrashReportStore::new(dir.path());
+
+        let declined =
+            CrashReporter::new(store.clone()).install_if_consented(&TelemetryConfig::default());
+        assert!(declined.is_none());
+        let _ = std::panic::catch_unwind(|| panic!("crash without consent"));
+        assert!(store.load_all().unwrap().is_empty());
+
+        let settings = HashMap::from([(
+            TelemetryConfig::CONFIG_KEY.to_string(),
+            serde_json::json!({ "opt_in": true }),
+        )]);
+        let telemetry = TelemetryConfig::from_settings(&settings).unwrap();
+        let reporter = CrashReporter::new(store.clone())
+            .install_if_consented(&telemetry)
+            .unwrap();
+        let _ = std::panic::catch_unwind(|| panic!("crash with consent"));
+        // Put back the default hook for the other tests
+        let _ = std::panic::take_hook();
+
+        assert!(reporter.has_consent());
+        let reports = store.load_all().unwrap();
+        assert!(reports.iter().any(|r| r.message == "crash with consent"));
+    }
+}
@@ -0,0 +1,609 @@
+//! A/B experiments for feature rollouts
+//!
+//! Each install gets a stable identifier; a flagged feature's variant is
+//! derived from that identifier and the experiment key, so the same install
+//! always sees the same variant without any server round trip. Outcome
+//! metrics (acceptance and latency) are recorded per variant and compared
+//! against the control locally with a two-sided z-test. Results feed
+//! [`RoadmapPlanner`](super::roadmap_planning::RoadmapPlanner)
+//! recommendations.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::types::ContinuousImprovementError;
+
+/// Default significance level for experiment results
+pub const DEFAULT_SIGNIFICANCE_LEVEL: f64 = 0.05;
+
+/// Load the install identifier from `path`, creating it on first use
+pub fn load_or_create_install_id(path: &Path) -> Result<Uuid, ContinuousImprovementError> {
+    if let Ok(content) = fs::read_to_string(path) {
+        if let Ok(id) = Uuid::parse_str(content.trim()) {
+            return Ok(id);
+        }
+        tracing::warn!("Replacing invalid install id in {}", path.display());
+    }
+
+    let id = Uuid::new_v4();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| experiment_error("create install id dir", e))?;
+    }
+    fs::write(path, id.to_string()).map_err(|e| experiment_error("write install id", e))?;
+    Ok(id)
+}
+
+/// Default install identifier location
+pub fn default_install_id_path() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|dir| dir.join("ricecoder").join("install_id"))
+}
+
+fn experiment_error(action: &str, error: std::io::Error) -> ContinuousImprovementError {
+    ContinuousImprovementError::AnalyticsError(format!("Failed to {}: {}", action, error))
+}
+
+/// One arm of an experiment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExperimentVariant {
+    pub name: String,
+    /// Relative share of installs assigned to this variant
+    pub weight: u32,
+}
+
+/// A flagged feature under experiment
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Experiment {
+    pub key: String,
+    pub description: String,
+    /// Variants in order; the first one is the control
+    pub variants: Vec<ExperimentVariant>,
+    /// Samples needed in both arms before a result is reported
+    pub min_samples: u64,
+    pub enabled: bool,
+}
+
+impl Experiment {
+    /// Create an experiment with an even split between control and treatment
+    pub fn new(key: impl Into<String>, control: &str, treatment: &str) -> Self {
+        Self {
+            key: key.into(),
+            description: String::new(),
+            variants: vec![
+                ExperimentVariant {
+                    name: control.to_string(),
+                    weight: 50,
+                },
+                ExperimentVariant {
+                    name: treatment.to_string(),
+                    weight: 50,
+                },
+            ],
+            min_samples: 30,
+            enabled: true,
+        }
+    }
+
+    /// Set the description
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    /// Replace the variants (the first one is the control)
+    pub fn with_variants(mut self, variants: Vec<ExperimentVariant>) -> Self {
+        self.variants = variants;
+        self
+    }
+
+    /// Set the minimum number of samples per arm
+    pub fn with_min_samples(mut self, min_samples: u64) -> Self {
+        self.min_samples = min_samples;
+        self
+    }
+
+    /// Control variant name
+    pub fn control(&self) -> Option<&str> {
+        self.variants.first().map(|v| v.name.as_str())
+    }
+
+    /// Variant for an install, stable across runs
+    ///
+    /// Disabled experiments always return the control.
+    pub fn assign(&self, install_id: &Uuid) -> Option<&str> {
+        if !self.enabled {
+            return self.control();
+        }
+        let total: u64 = self.variants.iter().map(|v| u64::from(v.weight)).sum();
+        if total == 0 {
+            return self.control();
+        }
+
+        let bucket = fnv1a_64(format!("{}:{}", install_id, self.key).as_bytes()) % total;
+        let mut upper = 0;
+        self.variants
+            .iter()
+            .find(|variant| {
+                upper += u64::from(variant.weight);
+                bucket < upper
+            })
+            .map(|variant| variant.name.as_str())
+    }
+
+    fn validate(&self) -> Result<(), ContinuousImprovementError> {
+        if self.key.trim().is_empty() {
+            return Err(ContinuousImprovementError::ConfigError(
+                "Experiment key must not be empty".to_string(),
+            ));
+        }
+        if self.variants.len() < 2 {
+            return Err(ContinuousImprovementError::ConfigError(format!(
+                "Experiment {} needs at least two variants",
+                self.key
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// FNV-1a, used because it is stable across Rust releases
+fn fnv1a_64(bytes: &[u8]) -> u64 {
+    const OFFSET: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+    bytes.iter().fold(OFFSET, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+    })
+}
+
+/// An observed outcome for the variant in use
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExperimentOutcome {
+    /// Whether the user accepted the result (e.g. a completion or edit)
+    Acceptance(bool),
+    /// How long the feature took
+    Latency(Duration),
+}
+
+/// Outcome metric compared between variants
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExperimentMetric {
+    AcceptanceRate,
+    LatencyMs,
+}
+
+impl ExperimentMetric {
+    /// Whether larger values are better
+    pub fn higher_is_better(&self) -> bool {
+        matches!(self, Self::AcceptanceRate)
+    }
+}
+
+/// Aggregated outcomes for one variant
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VariantStats {
+    pub exposures: u64,
+    pub trials: u64,
+    pub accepted: u64,
+    pub latency_samples: u64,
+    pub latency_mean_ms: f64,
+    /// Sum of squared deviations (Welford)
+    latency_m2: f64,
+}
+
+impl VariantStats {
+    fn record(&mut self, outcome: ExperimentOutcome) {
+        match outcome {
+            ExperimentOutcome::Acceptance(accepted) => {
+                self.trials += 1;
+                if accepted {
+                    self.accepted += 1;
+                }
+            }
+            ExperimentOutcome::Latency(latency) => {
+                let value = latency.as_secs_f64() * 1000.0;
+                self.latency_samples += 1;
+                let delta = value - self.latency_mean_ms;
+                self.latency_mean_ms += delta / self.latency_samples as f64;
+                self.latency_m2 += delta * (value - self.latency_mean_ms);
+            }
+        }
+    }
+
+    /// Acceptance rate in `[0, 1]`
+    pub fn acceptance_rate(&self) -> Option<f64> {
+        (self.trials > 0).then(|| self.accepted as f64 / self.trials as f64)
+    }
+
+    /// Sample variance of the latency in ms²
+    pub fn latency_variance(&self) -> Option<f64> {
+        (self.latency_samples > 1).then(|| self.latency_m2 / (self.latency_samples - 1) as f64)
+    }
+}
+
+/// Comparison of one treatment variant against the control
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExperimentResult {
+    pub experiment: String,
+    pub metric: ExperimentMetric,
+    pub control: String,
+    pub treatment: String,
+    pub control_value: f64,
+    pub treatment_value: f64,
+    pub control_samples: u64,
+    pub treatment_samples: u64,
+    /// Relative change of the treatment against the control
+    pub relative_change: f64,
+    pub p_value: f64,
+    pub significant: bool,
+}
+
+impl ExperimentResult {
+    /// Whether the treatment is significantly better than the control
+    pub fn treatment_wins(&self) -> bool {
+        self.significant && self.improves()
+    }
+
+    /// Whether the treatment is significantly worse than the control
+    pub fn treatment_loses(&self) -> bool {
+        self.significant && !self.improves()
+    }
+
+    fn improves(&self) -> bool {
+        if self.metric.higher_is_better() {
+            self.treatment_value > self.control_value
+        } else {
+            self.treatment_value < self.control_value
+        }
+    }
+}
+
+/// Two-sided z-test for the difference of two acceptance rates
+fn two_proportion_p_value(control: &VariantStats, treatment: &VariantStats) -> Option<f64> {
+    let (n1, n2) = (control.trials as f64, treatment.trials as f64);
+    if n1 == 0.0 || n2 == 0.0 {
+        return None;
+    }
+    let pooled = (control.accepted + treatment.accepted) as f64 / (n1 + n2);
+    let se = (pooled * (1.0 - pooled) * (1.0 / n1 + 1.0 / n2)).sqrt();
+    let diff = treatment.accepted as f64 / n2 - control.accepted as f64 / n1;
+    Some(p_value_from_z(diff, se))
+}
+
+/// Two-sided Welch z-test for the difference of two latency means
+fn mean_difference_p_value(control: &VariantStats, treatment: &VariantStats) -> Option<f64> {
+    let var1 = control.latency_variance()?;
+    let var2 = treatment.latency_variance()?;
+    let se =
+        (var1 / control.latency_samples as f64 + var2 / treatment.latency_samples as f64).sqrt();
+    let diff = treatment.latency_mean_ms - control.latency_mean_ms;
+    Some(p_value_from_z(diff, se))
+}
+
+fn p_value_from_z(diff: f64, standard_error: f64) -> f64 {
+    if standard_error == 0.0 {
+        return if diff == 0.0 { 1.0 } else { 0.0 };
+    }
+    let z = (diff / standard_error).abs();
+    (2.0 * (1.0 - standard_normal_cdf(z))).clamp(0.0, 1.0)
+}
+
+/// Standard normal CDF (Abramowitz & Stegun 7.1.26 erf approximation)
+fn standard_normal_cdf(z: f64) -> f64 {
+    let x = z / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.3275911 * x.abs());
+    let poly = t
+        * (0.254829592
+            + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    let erf = 1.0 - poly * (-x * x).exp();
+    let erf = if x >= 0.0 { erf } else { -erf };
+    0.5 * (1.0 + erf)
+}
+
+/// Registry of running experiments with per-variant outcome tracking
+pub struct ExperimentManager {
+    install_id: Uuid,
+    significance_level: f64,
+    experiments: Mutex<BTreeMap<String, Experiment>>,
+    stats: Mutex<BTreeMap<(String, String), VariantStats>>,
+}
+
+impl ExperimentManager {
+    /// Create a manager for an install
+    pub fn new(install_id: Uuid) -> Self {
+        Self {
+            install_id,
+            significance_level: DEFAULT_SIGNIFICANCE_LEVEL,
+            experiments: Mutex::new(BTreeMap::new()),
+            stats: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Create a manager using the persisted install identifier
+    ///
+    /// Falls back to a random identifier for this run when it cannot be
+    /// persisted.
+    pub fn for_this_install() -> Self {
+        let install_id = default_install_id_path()
+            .and_then(|path| match load_or_create_install_id(&path) {
+                Ok(id) => Some(id),
+                Err(e) => {
+                    tracing::warn!("Using a temporary install id: {}", e);
+                    None
+                }
+            })
+            .unwrap_or_else(Uuid::new_v4);
+        Self::new(install_id)
+    }
+
+    /// Set the significance level used for results
+    pub fn with_significance_level(mut self, level: f64) -> Self {
+        self.significance_level = level;
+        self
+    }
+
+    /// Install identifier used for assignment
+    pub fn install_id(&self) -> Uuid {
+        self.install_id
+    }
+
+    /// Register (or replace) an experiment
+    pub fn register(&self, experiment: Experiment) -> Result<(), ContinuousImprovementError> {
+        experiment.validate()?;
+        self.experiments
+            .lock()
+            .unwrap()
+            .insert(experiment.key.clone(), experiment);
+        Ok(())
+    }
+
+    /// Registered experiments
+    pub fn experiments(&self) -> Vec<Experiment> {
+        self.experiments.lock().unwrap().values().cloned().collect()
+    }
+
+    /// Variant of an experiment for this install, recording an exposure
+    pub fn variant(&self, key: &str) -> Option<String> {
+        let variant = self
+            .experiments
+            .lock()
+            .unwrap()
+            .get(key)?
+            .assign(&self.install_id)?
+            .to_string();
+        self.stats
+            .lock()
+            .unwrap()
+            .entry((key.to_string(), variant.clone()))
+            .or_default()
+            .exposures += 1;
+        Some(variant)
+    }
+
+    /// Whether this install is in the given variant
+    pub fn is_variant(&self, key: &str, variant: &str) -> bool {
+        self.variant(key).as_deref() == Some(variant)
+    }
+
+    /// Record an outcome for a variant of an experiment
+    pub fn record_outcome(&self, key: &str, variant: &str, outcome: ExperimentOutcome) {
+        let known = self
+            .experiments
+            .lock()
+            .unwrap()
+            .get(key)
+            .is_some_and(|e| e.variants.iter().any(|v| v.name == variant));
+        if !known {
+            tracing::debug!(
+                "Ignoring outcome for unknown experiment variant {}/{}",
+                key,
+                variant
+            );
+            return;
+        }
+        self.stats
+            .lock()
+            .unwrap()
+            .entry((key.to_string(), variant.to_string()))
+            .or_default()
+            .record(outcome);
+    }
+
+    /// Aggregated stats for a variant
+    pub fn stats(&self, key: &str, variant: &str) -> VariantStats {
+        self.stats
+            .lock()
+            .unwrap()
+            .get(&(key.to_string(), variant.to_string()))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Compare every treatment against the control, per metric
+    ///
+    /// Arms with fewer than the experiment's minimum samples are skipped.
+    pub fn results(&self) -> Vec<ExperimentResult> {
+        let experiments = self.experiments();
+        let mut results = Vec::new();
+
+        for experiment in &experiments {
+            let Some(control_name) = experiment.control() else {
+                continue;
+            };
+            let control = self.stats(&experiment.key, control_name);
+
+            for treatment_variant in &experiment.variants[1..] {
+                let treatment = self.stats(&experiment.key, &treatment_variant.name);
+                let candidates = [
+                    (
+                        ExperimentMetric::AcceptanceRate,
+                        control.trials,
+                        treatment.trials,
+                        control.acceptance_rate(),
+                        treatment.acceptance_rate(),
+                        two_proportion_p_value(&control, &treatment),
+                    ),
+                    (
+                        ExperimentMetric::LatencyMs,
+                        control.latency_samples,
+                        treatment.latency_samples,
+                        Some(control.latency_mean_ms),
+                        Some(treatment.latency_mean_ms),
+                        mean_difference_p_value(&control, &treatment),
+                    ),
+                ];
+
+                for (metric, n_control, n_treatment, control_value, treatment_value, p_value) in
+                    candidates
+                {
+                    if n_control < experiment.min_samples || n_treatment < experiment.min_samples {
+                        continue;
+                    }
+                    let (Some(control_value), Some(treatment_value), Some(p_value)) =
+                        (control_value, treatment_value, p_value)
+                    else {
+                        continue;
+                    };
+                    let relative_change = if control_value != 0.0 {
+                        (treatment_value - control_value) / control_value
+                    } else {
+                        0.0
+                    };
+                    results.push(ExperimentResult {
+                        experiment: experiment.key.clone(),
+                        metric,
+                        control: control_name.to_string(),
+                        treatment: treatment_variant.name.clone(),
+                        control_value,
+                        treatment_value,
+                        control_samples: n_control,
+                        treatment_samples: n_treatment,
+                        relative_change,
+                        p_value,
+                        significant: p_value < self.significance_level,
+                    });
+                }
+            }
+        }
+
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manager() -> ExperimentManager {
+        let manager = ExperimentManager::new(Uuid::new_v4());
+        manager
+            .register(Experiment::new("ranker", "old", "new").with_min_samples(50))
+            .unwrap();
+        manager
+    }
+
+    #[test]
+    fn test_assignment_is_stable_and_weighted() {
+        let experiment = Experiment::new("ranker", "old", "new");
+        let install = Uuid::new_v4();
+        assert_eq!(experiment.assign(&install), experiment.assign(&install));
+
+        let treated = (0..2000)
+            .filter(|_| experiment.assign(&Uuid::new_v4()) == Some("new"))
+            .count();
+        assert!(
+            (800..1200).contains(&treated),
+            "unbalanced split: {}",
+            treated
+        );
+
+        let mut disabled = experiment.clone();
+        disabled.enabled = false;
+        assert_eq!(disabled.assign(&install), Some("old"));
+    }
+
+    #[test]
+    fn test_register_rejects_single_variant() {
+        let manager = ExperimentManager::new(Uuid::new_v4());
+        let experiment = Experiment::new("solo", "a", "b").with_variants(vec![ExperimentVariant {
+            name: "a".to_string(),
+            weight: 1,
+        }]);
+        assert!(manager.register(experiment).is_err());
+    }
+
+    #[test]
+    fn test_significant_acceptance_improvement() {
+        let manager = manager();
+        for i in 0..400 {
+            manager.record_outcome("ranker", "old", ExperimentOutcome::Acceptance(i % 10 < 3));
+            manager.record_outcome("ranker", "new", ExperimentOutcome::Acceptance(i % 10 < 5));
+        }
+
+        let results = manager.results();
+        let acceptance = results
+            .iter()
+            .find(|r| r.metric == ExperimentMetric::AcceptanceRate)
+            .unwrap();
+        assert!(acceptance.significant);
+        assert!(acceptance.treatment_wins());
+        assert!((acceptance.control_value - 0.3).abs() < 1e-9);
+        assert!(
+            !results
+                .iter()
+                .any(|r| r.metric == ExperimentMetric::LatencyMs),
+            "latency has no samples"
+        );
+    }
+
+    #[test]
+    fn test_latency_regression_and_min_samples() {
+        let manager = manager();
+        for i in 0..40 {
+            let jitter = (i % 5) as u64;
+            manager.record_outcome(
+                "ranker",
+                "old",
+                ExperimentOutcome::Latency(Duration::from_millis(100 + jitter)),
+            );
+            manager.record_outcome(
+                "ranker",
+                "new",
+                ExperimentOutcome::Latency(Duration::from_millis(150 + jitter)),
+            );
+        }
+        assert!(manager.results().is_empty(), "below min samples");
+
+        for i in 0..20 {
+            let jitter = (i % 5) as u64;
+            manager.record_outcome(
+                "ranker",
+                "old",
+                ExperimentOutcome::Latency(Duration::from_millis(100 + jitter)),
+            );
+            manager.record_outcome(
+                "ranker",
+                "new",
+                ExperimentOutcome::Latency(Duration::from_millis(150 + jitter)),
+            );
+        }
+        let result = &manager.results()[0];
+        assert_eq!(result.metric, ExperimentMetric::LatencyMs);
+        assert!(result.treatment_loses());
+    }
+
+    #[test]
+    fn test_install_id_persists() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("install_id");
+        let first = load_or_create_install_id(&path).unwrap();
+        assert_eq!(load_or_create_install_id(&path).unwrap(), first);
+    }
+}
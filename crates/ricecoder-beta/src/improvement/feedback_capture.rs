@@ -0,0 +1,578 @@
+//! In-product feedback capture
+//!
+//! Front door for feedback entered inside RiceCoder (for example the TUI
+//! `/feedback` dialog). Captured feedback carries a category, an optional
+//! 1-5 rating, free text and a small anonymized context (mode, provider,
+//! model, version, OS). It is forwarded to the [`FeedbackPipeline`] when one
+//! is available, and appended to a local JSON Lines queue otherwise so that
+//! nothing is lost while offline.
+
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{feedback_pipeline::FeedbackPipeline, types::ContinuousImprovementError};
+use crate::feedback::{FeedbackSeverity, FeedbackType};
+
+/// Maximum length of the free-text part of captured feedback
+pub const MAX_FEEDBACK_TEXT_LEN: usize = 4000;
+
+/// Maximum length of a generated feedback title
+const MAX_TITLE_LEN: usize = 80;
+
+/// Feedback category offered by the capture surface
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedbackCategory {
+    Bug,
+    FeatureRequest,
+    Performance,
+    Usability,
+    General,
+}
+
+impl FeedbackCategory {
+    /// All categories, in display order
+    pub fn all() -> &'static [FeedbackCategory] {
+        &[
+            Self::Bug,
+            Self::FeatureRequest,
+            Self::Performance,
+            Self::Usability,
+            Self::General,
+        ]
+    }
+
+    /// Human readable label
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Bug => "Bug",
+            Self::FeatureRequest => "Feature request",
+            Self::Performance => "Performance",
+            Self::Usability => "Usability",
+            Self::General => "General",
+        }
+    }
+
+    /// Stable identifier used in tags and command arguments
+    pub fn slug(&self) -> &'static str {
+        match self {
+            Self::Bug => "bug",
+            Self::FeatureRequest => "feature",
+            Self::Performance => "performance",
+            Self::Usability => "usability",
+            Self::General => "general",
+        }
+    }
+
+    /// Parse a category from its slug or a common alias
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "bug" | "bugs" | "crash" => Some(Self::Bug),
+            "feature" | "features" | "feature_request" | "idea" => Some(Self::FeatureRequest),
+            "performance" | "perf" | "slow" => Some(Self::Performance),
+            "usability" | "ux" | "ui" => Some(Self::Usability),
+            "general" | "other" => Some(Self::General),
+            _ => None,
+        }
+    }
+
+    /// Map to the pipeline feedback type
+    pub fn feedback_type(&self) -> FeedbackType {
+        match self {
+            Self::Bug => FeedbackType::BugReport,
+            Self::FeatureRequest => FeedbackType::FeatureRequest,
+            Self::Performance => FeedbackType::PerformanceIssue,
+            Self::Usability => FeedbackType::UsabilityIssue,
+            Self::General => FeedbackType::GeneralFeedback,
+        }
+    }
+}
+
+/// Anonymized context attached to captured feedback
+///
+/// Only coarse, non-identifying values are kept: no paths, prompts, session
+/// ids or user ids. Values that look like URLs, paths or emails are replaced
+/// by `"custom"`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeedbackContext {
+    pub mode: Option<String>,
+    pub provider: Option<String>,
+    pub model: Option<String>,
+    pub app_version: Option<String>,
+    pub os: String,
+}
+
+impl FeedbackContext {
+    /// Create a context for the current platform
+    pub fn new() -> Self {
+        Self {
+            os: std::env::consts::OS.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the active mode (e.g. agent or route name)
+    pub fn with_mode(mut self, mode: impl AsRef<str>) -> Self {
+        self.mode = anonymize_label(mode.as_ref());
+        self
+    }
+
+    /// Set the active provider id
+    pub fn with_provider(mut self, provider: impl AsRef<str>) -> Self {
+        self.provider = anonymize_label(provider.as_ref());
+        self
+    }
+
+    /// Set the active model id
+    pub fn with_model(mut self, model: impl AsRef<str>) -> Self {
+        self.model = anonymize_label(model.as_ref());
+        self
+    }
+
+    /// Set the application version
+    pub fn with_app_version(mut self, version: impl AsRef<str>) -> Self {
+        self.app_version = anonymize_label(version.as_ref());
+        self
+    }
+
+    /// Convert to pipeline metadata
+    pub fn to_metadata(&self) -> HashMap<String, serde_json::Value> {
+        let mut metadata = HashMap::new();
+        let fields = [
+            ("mode", &self.mode),
+            ("provider", &self.provider),
+            ("model", &self.model),
+            ("app_version", &self.app_version),
+        ];
+        for (key, value) in fields {
+            if let Some(value) = value {
+                metadata.insert(key.to_string(), serde_json::Value::from(value.as_str()));
+            }
+        }
+        metadata.insert("os".to_string(), serde_json::Value::from(self.os.as_str()));
+        metadata
+    }
+}
+
+/// Reduce a context value to a short, non-identifying label
+fn anonymize_label(value: &str) -> Option<String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+    if value.contains("://") || value.contains('/') || value.contains('\\') || value.contains('@') {
+        return Some("custom".to_string());
+    }
+    Some(value.chars().take(64).collect())
+}
+
+/// Feedback captured from the product
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CapturedFeedback {
+    pub id: Uuid,
+    pub category: FeedbackCategory,
+    pub rating: Option<u8>,
+    pub text: String,
+    pub context: FeedbackContext,
+    pub captured_at: DateTime<Utc>,
+}
+
+impl CapturedFeedback {
+    /// Create captured feedback with an empty context
+    pub fn new(category: FeedbackCategory, text: impl Into<String>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            category,
+            rating: None,
+            text: text.into(),
+            context: FeedbackContext::new(),
+            captured_at: Utc::now(),
+        }
+    }
+
+    /// Set a 1-5 rating
+    pub fn with_rating(mut self, rating: u8) -> Self {
+        self.rating = Some(rating);
+        self
+    }
+
+    /// Set the anonymized context
+    pub fn with_context(mut self, context: FeedbackContext) -> Self {
+        self.context = context;
+        self
+    }
+
+    /// Validate the feedback before it is recorded
+    pub fn validate(&self) -> Result<(), ContinuousImprovementError> {
+        if let Some(rating) = self.rating {
+            if !(1..=5).contains(&rating) {
+                return Err(ContinuousImprovementError::FeedbackError(format!(
+                    "Rating must be between 1 and 5, got {}",
+                    rating
+                )));
+            }
+        }
+        if self.text.trim().is_empty() && self.rating.is_none() {
+            return Err(ContinuousImprovementError::FeedbackError(
+                "Feedback needs a rating or some text".to_string(),
+            ));
+        }
+        if self.text.chars().count() > MAX_FEEDBACK_TEXT_LEN {
+            return Err(ContinuousImprovementError::FeedbackError(format!(
+                "Feedback text exceeds {} characters",
+                MAX_FEEDBACK_TEXT_LEN
+            )));
+        }
+        Ok(())
+    }
+
+    /// Severity derived from category and rating
+    pub fn severity(&self) -> FeedbackSeverity {
+        match (self.category, self.rating) {
+            (FeedbackCategory::Bug, Some(1)) => FeedbackSeverity::Critical,
+            (_, Some(1)) | (FeedbackCategory::Bug, _) => FeedbackSeverity::High,
+            (_, Some(2)) | (FeedbackCategory::Performance, _) => FeedbackSeverity::Medium,
+            _ => FeedbackSeverity::Low,
+        }
+    }
+
+    /// Title derived from the first line of the text
+    pub fn title(&self) -> String {
+        let first_line = self.text.lines().next().unwrap_or("").trim();
+        if first_line.is_empty() {
+            return format!("{} feedback", self.category.label());
+        }
+        if first_line.chars().count() <= MAX_TITLE_LEN {
+            first_line.to_string()
+        } else {
+            let truncated: String = first_line.chars().take(MAX_TITLE_LEN - 3).collect();
+            format!("{}...", truncated.trim_end())
+        }
+    }
+
+    /// Tags recorded with the feedback
+    pub fn tags(&self) -> Vec<String> {
+        let mut tags = vec!["in-product".to_string(), self.category.slug().to_string()];
+        if let Some(rating) = self.rating {
+            tags.push(format!("rating:{}", rating));
+        }
+        tags
+    }
+
+    /// Metadata recorded with the feedback
+    pub fn metadata(&self) -> HashMap<String, serde_json::Value> {
+        let mut metadata = self.context.to_metadata();
+        metadata.insert(
+            "capture_id".to_string(),
+            serde_json::Value::from(self.id.to_string()),
+        );
+        metadata.insert(
+            "captured_at".to_string(),
+            serde_json::Value::from(self.captured_at.to_rfc3339()),
+        );
+        if let Some(rating) = self.rating {
+            metadata.insert("rating".to_string(), serde_json::Value::from(rating));
+        }
+        metadata
+    }
+}
+
+/// Local queue of feedback waiting to be recorded, stored as JSON Lines
+#[derive(Debug, Clone)]
+pub struct FeedbackQueue {
+    path: PathBuf,
+}
+
+impl FeedbackQueue {
+    /// Create a queue backed by the given file
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Default queue location in the user's local data directory
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::data_local_dir().map(|dir| dir.join("ricecoder").join("feedback").join("queue.jsonl"))
+    }
+
+    /// Path of the backing file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append feedback to the queue
+    pub fn enqueue(&self, feedback: &CapturedFeedback) -> Result<(), ContinuousImprovementError> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).map_err(|e| queue_error("create queue directory", e))?;
+        }
+        let line = serde_json::to_string(feedback)
+            .map_err(|e| ContinuousImprovementError::FeedbackError(e.to_string()))?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| queue_error("open queue", e))?;
+        writeln!(file, "{}", line).map_err(|e| queue_error("write queue", e))
+    }
+
+    /// Load all queued feedback, skipping lines that cannot be parsed
+    pub fn load(&self) -> Result<Vec<CapturedFeedback>, ContinuousImprovementError> {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(queue_error("read queue", e)),
+        };
+
+        Ok(content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| match serde_json::from_str(line) {
+                Ok(feedback) => Some(feedback),
+                Err(e) => {
+                    tracing::warn!("Skipping unreadable queued feedback: {}", e);
+                    None
+                }
+            })
+            .collect())
+    }
+
+    /// Replace the queue contents
+    pub fn replace(&self, pending: &[CapturedFeedback]) -> Result<(), ContinuousImprovementError> {
+        if pending.is_empty() {
+            return self.clear();
+        }
+
+        let mut content = String::new();
+        for feedback in pending {
+            let line = serde_json::to_string(feedback)
+                .map_err(|e| ContinuousImprovementError::FeedbackError(e.to_string()))?;
+            content.push_str(&line);
+            content.push('\n');
+        }
+
+        let tmp = self.path.with_extension("jsonl.tmp");
+        fs::write(&tmp, content).map_err(|e| queue_error("write queue", e))?;
+        fs::rename(&tmp, &self.path).map_err(|e| queue_error("replace queue", e))
+    }
+
+    /// Remove all queued feedback
+    pub fn clear(&self) -> Result<(), ContinuousImprovementError> {
+        match fs::remove_file(&self.path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(queue_error("clear queue", e)),
+        }
+    }
+
+    /// Number of queued entries
+    pub fn len(&self) -> usize {
+        self.load().map(|pending| pending.len()).unwrap_or(0)
+    }
+
+    /// Whether the queue is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+fn queue_error(action: &str, error: std::io::Error) -> ContinuousImprovementError {
+    ContinuousImprovementError::FeedbackError(format!("Failed to {}: {}", action, error))
+}
+
+/// Outcome of a capture
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CaptureOutcome {
+    /// Recorded in the feedback pipeline
+    Recorded,
+    /// Stored locally until the pipeline is reachable
+    Queued { pending: usize },
+}
+
+/// Capture service that records feedback or queues it while offline
+pub struct FeedbackCaptureService {
+    pipeline: Option<Arc<FeedbackPipeline>>,
+    queue: FeedbackQueue,
+    online: AtomicBool,
+}
+
+impl FeedbackCaptureService {
+    /// Create a service that queues everything until a pipeline is attached
+    pub fn new(queue: FeedbackQueue) -> Self {
+        Self {
+            pipeline: None,
+            queue,
+            online: AtomicBool::new(true),
+        }
+    }
+
+    /// Attach the feedback pipeline
+    pub fn with_pipeline(mut self, pipeline: Arc<FeedbackPipeline>) -> Self {
+        self.pipeline = Some(pipeline);
+        self
+    }
+
+    /// Mark the pipeline as reachable or not
+    pub fn set_online(&self, online: bool) {
+        self.online.store(online, Ordering::SeqCst);
+    }
+
+    /// Whether feedback is currently sent straight to the pipeline
+    pub fn is_online(&self) -> bool {
+        self.pipeline.is_some() && self.online.load(Ordering::SeqCst)
+    }
+
+    /// Number of queued entries
+    pub fn pending(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Local queue
+    pub fn queue(&self) -> &FeedbackQueue {
+        &self.queue
+    }
+
+    /// Capture feedback, falling back to the local queue when offline
+    pub async fn submit(
+        &self,
+        feedback: CapturedFeedback,
+    ) -> Result<CaptureOutcome, ContinuousImprovementError> {
+        feedback.validate()?;
+
+        if let Some(pipeline) = self.pipeline.as_ref().filter(|_| self.is_online()) {
+            match Self::record(pipeline, &feedback).await {
+                Ok(()) => return Ok(CaptureOutcome::Recorded),
+                Err(e) => {
+                    tracing::warn!("Feedback pipeline unavailable, queueing locally: {}", e);
+                    self.set_online(false);
+                }
+            }
+        }
+
+        self.queue.enqueue(&feedback)?;
+        Ok(CaptureOutcome::Queued {
+            pending: self.queue.len(),
+        })
+    }
+
+    /// Record queued feedback in order, returning how many were recorded
+    ///
+    /// Stops at the first failure and keeps the remaining entries queued.
+    pub async fn flush(&self) -> Result<usize, ContinuousImprovementError> {
+        let Some(pipeline) = self.pipeline.as_ref() else {
+            return Ok(0);
+        };
+
+        let pending = self.queue.load()?;
+        if pending.is_empty() {
+            return Ok(0);
+        }
+
+        let mut recorded = 0;
+        for feedback in &pending {
+            if let Err(e) = Self::record(pipeline, feedback).await {
+                tracing::warn!("Stopped flushing queued feedback: {}", e);
+                self.set_online(false);
+                break;
+            }
+            recorded += 1;
+        }
+
+        self.queue.replace(&pending[recorded..])?;
+        if recorded == pending.len() {
+            self.set_online(true);
+        }
+        tracing::info!("Flushed {} queued feedback entries", recorded);
+        Ok(recorded)
+    }
+
+    async fn record(
+        pipeline: &FeedbackPipeline,
+        feedback: &CapturedFeedback,
+    ) -> Result<(), ContinuousImprovementError> {
+        pipeline
+            .collect_feedback(
+                feedback.category.feedback_type(),
+                feedback.severity(),
+                feedback.title(),
+                feedback.text.clone(),
+                None,
+                None,
+                feedback.tags(),
+                feedback.metadata(),
+            )
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::improvement::FeedbackPipelineConfig;
+
+    #[test]
+    fn test_context_is_anonymized() {
+        let context = FeedbackContext::new()
+            .with_mode("build")
+            .with_provider("https://llm.internal.example.com/v1")
+            .with_model("gpt-4o");
+
+        assert_eq!(context.mode.as_deref(), Some("build"));
+        assert_eq!(context.provider.as_deref(), Some("custom"));
+        assert_eq!(context.model.as_deref(), Some("gpt-4o"));
+        assert!(!context.to_metadata().contains_key("user_id"));
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(CapturedFeedback::new(FeedbackCategory::General, "  ")
+            .validate()
+            .is_err());
+        assert!(CapturedFeedback::new(FeedbackCategory::General, "")
+            .with_rating(6)
+            .validate()
+            .is_err());
+        assert!(CapturedFeedback::new(FeedbackCategory::General, "")
+            .with_rating(4)
+            .validate()
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_offline_queue_and_flush() {
+        let dir = tempfile::tempdir().unwrap();
+        let queue = FeedbackQueue::new(dir.path().join("queue.jsonl"));
+        let pipeline = Arc::new(FeedbackPipeline::new(FeedbackPipelineConfig::default()));
+        let service = FeedbackCaptureService::new(queue).with_pipeline(pipeline);
+
+        service.set_online(false);
+        let outcome = service
+            .submit(CapturedFeedback::new(FeedbackCategory::Bug, "Crash on resize").with_rating(2))
+            .await
+            .unwrap();
+        assert_eq!(outcome, CaptureOutcome::Queued { pending: 1 });
+
+        assert_eq!(service.flush().await.unwrap(), 1);
+        assert_eq!(service.pending(), 0);
+        assert!(service.is_online());
+
+        let outcome = service
+            .submit(CapturedFeedback::new(
+                FeedbackCategory::FeatureRequest,
+                "Vim mode",
+            ))
+            .await
+            .unwrap();
+        assert_eq!(outcome, CaptureOutcome::Recorded);
+    }
+}
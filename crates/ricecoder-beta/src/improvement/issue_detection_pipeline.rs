@@ -1,6 +1,9 @@
 //! Automated issue detection and escalation pipeline
 
-use std::sync::{Arc, Mutex};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
 
 use ricecoder_monitoring::{
     error_tracking::{AlertManager, ErrorTracker, IncidentManager},
@@ -8,7 +11,10 @@ use ricecoder_monitoring::{
 };
 use tokio::{sync::mpsc, time};
 
-use super::types::*;
+use super::{
+    crash_reporting::{detect_crash_trends, CrashGroup, CrashReport, CrashTrend},
+    types::*,
+};
 
 /// Issue detection pipeline for automated issue detection and escalation
 pub struct IssueDetectionPipeline {
@@ -16,6 +22,7 @@ pub struct IssueDetectionPipeline {
     error_tracker: Arc<Mutex<ErrorTracker>>,
     alert_manager: Arc<Mutex<AlertManager>>,
     incident_manager: Arc<Mutex<IncidentManager>>,
+    crash_groups: Arc<Mutex<HashMap<String, CrashGroup>>>,
     shutdown_tx: Option<mpsc::Sender<()>>,
     detection_task: Option<tokio::task::JoinHandle<()>>,
 }
@@ -42,6 +49,7 @@ impl IssueDetectionPipeline {
             error_tracker: Arc::new(Mutex::new(ErrorTracker::new(error_config))),
             alert_manager: Arc::new(Mutex::new(AlertManager::new(alerting_config))),
             incident_manager: Arc::new(Mutex::new(IncidentManager::new())),
+            crash_groups: Arc::new(Mutex::new(HashMap::new())),
             shutdown_tx: None,
             detection_task: None,
         }
@@ -161,6 +169,89 @@ impl IssueDetectionPipeline {
         self.error_tracker.lock().unwrap().track_error(event);
     }
 
+    /// Ingest a crash report, deduplicating by stack fingerprint
+    ///
+    /// The first report for a fingerprint is tracked as an error and raises an
+    /// alert; later reports only update the occurrence counts. Returns whether
+    /// the report opened a new crash issue.
+    pub fn ingest_crash_report(&self, report: &CrashReport) -> bool {
+        let mut groups = self.crash_groups.lock().unwrap();
+        if let Some(group) = groups.get_mut(&report.fingerprint) {
+            group.record(report);
+            return false;
+        }
+        groups.insert(report.fingerprint.clone(), CrashGroup::from_report(report));
+        drop(groups);
+
+        let mut context = HashMap::new();
+        context.insert(
+            "fingerprint".to_string(),
+            serde_json::Value::from(report.fingerprint.clone()),
+        );
+        context.insert(
+            "app_version".to_string(),
+            serde_json::Value::from(report.app_version.clone()),
+        );
+        context.insert("os".to_string(), serde_json::Value::from(report.os.clone()));
+        if let Some(location) = &report.location {
+            context.insert(
+                "location".to_string(),
+                serde_json::Value::from(location.clone()),
+            );
+        }
+
+        self.error_tracker.lock().unwrap().track_error(ErrorEvent {
+            id: EventId::new_v4(),
+            message: report.message.clone(),
+            error_type: "crash".to_string(),
+            stack_trace: Some(report.backtrace.clone()),
+            user_id: None,
+            session_id: None,
+            context,
+            timestamp: report.occurred_at,
+            severity: MonitoringSeverity::Critical,
+        });
+
+        let mut labels = HashMap::new();
+        labels.insert("fingerprint".to_string(), report.fingerprint.clone());
+        labels.insert("app_version".to_string(), report.app_version.clone());
+        self.alert_manager.lock().unwrap().create_alert(
+            "crash_detected".to_string(),
+            format!("New crash: {}", report.message),
+            MonitoringSeverity::High,
+            labels,
+        );
+
+        tracing::warn!(
+            "New crash fingerprint {}: {}",
+            report.fingerprint,
+            report.message
+        );
+        true
+    }
+
+    /// Crash groups seen so far, most frequent first
+    pub fn crash_groups(&self) -> Vec<CrashGroup> {
+        let mut groups: Vec<CrashGroup> = self
+            .crash_groups
+            .lock()
+            .unwrap()
+            .values()
+            .cloned()
+            .collect();
+        groups.sort_by(|a, b| {
+            b.count
+                .cmp(&a.count)
+                .then(a.fingerprint.cmp(&b.fingerprint))
+        });
+        groups
+    }
+
+    /// Crash trends across application versions
+    pub fn crash_trends(&self) -> Vec<CrashTrend> {
+        detect_crash_trends(&self.crash_groups())
+    }
+
     /// Get issue insights
     pub async fn get_insights(&self) -> Result<IssueInsights, ContinuousImprovementError> {
         let error_stats = self.error_tracker.lock().unwrap().get_error_stats(None);
@@ -6,18 +6,35 @@
 
 pub mod analytics_pipeline;
 pub mod config;
+pub mod crash_reporting;
+pub mod experiments;
+pub mod feedback_capture;
 pub mod feedback_pipeline;
 pub mod issue_detection_pipeline;
 pub mod roadmap_planning;
 pub mod security_monitoring_pipeline;
+pub mod telemetry;
 pub mod types;
 
 pub use analytics_pipeline::AnalyticsPipeline;
 pub use config::*;
+pub use crash_reporting::{
+    CrashGroup, CrashReport, CrashReportStore, CrashReporter, CrashSubmission, CrashTrend,
+    CrashTrendStatus,
+};
+pub use experiments::{
+    Experiment, ExperimentManager, ExperimentMetric, ExperimentOutcome, ExperimentResult,
+    ExperimentVariant, VariantStats,
+};
+pub use feedback_capture::{
+    CaptureOutcome, CapturedFeedback, FeedbackCaptureService, FeedbackCategory, FeedbackContext,
+    FeedbackQueue,
+};
 pub use feedback_pipeline::FeedbackPipeline;
 pub use issue_detection_pipeline::IssueDetectionPipeline;
 pub use roadmap_planning::RoadmapPlanner;
 pub use security_monitoring_pipeline::SecurityMonitoringPipeline;
+pub use telemetry::{LatencyHistogram, TelemetryAggregator, TelemetryExporter, TelemetryReport};
 pub use types::*;
 
 /// Main continuous improvement orchestrator
@@ -27,6 +44,7 @@ pub struct ContinuousImprovementPipeline {
     issue_detection_pipeline: IssueDetectionPipeline,
     security_monitoring_pipeline: SecurityMonitoringPipeline,
     roadmap_planner: RoadmapPlanner,
+    experiments: ExperimentManager,
     config: ContinuousImprovementConfig,
 }
 
@@ -43,6 +61,7 @@ impl ContinuousImprovementPipeline {
                 config.security_config.clone(),
             ),
             roadmap_planner: RoadmapPlanner::new(config.roadmap_config.clone()),
+            experiments: ExperimentManager::for_this_install(),
             config: config.clone(),
         }
     }
@@ -77,6 +96,11 @@ impl ContinuousImprovementPipeline {
         Ok(())
     }
 
+    /// A/B experiments for feature rollouts
+    pub fn experiments(&self) -> &ExperimentManager {
+        &self.experiments
+    }
+
     /// Generate improvement recommendations
     pub async fn generate_recommendations(
         &self,
@@ -86,6 +110,7 @@ impl ContinuousImprovementPipeline {
         let analytics_insights = self.analytics_pipeline.get_insights().await?;
         let issue_insights = self.issue_detection_pipeline.get_insights().await?;
         let security_insights = self.security_monitoring_pipeline.get_insights().await?;
+        let experiment_results = self.experiments.results();
 
         // Generate roadmap recommendations
         self.roadmap_planner
@@ -94,6 +119,7 @@ impl ContinuousImprovementPipeline {
                 &analytics_insights,
                 &issue_insights,
                 &security_insights,
+                &experiment_results,
             )
             .await
     }
@@ -6,7 +6,10 @@ use chrono::TimeDelta;
 use ricecoder_monitoring::types::ComplianceStatus;
 use tokio::{sync::mpsc, time};
 
-use super::types::*;
+use super::{
+    experiments::{ExperimentMetric, ExperimentResult},
+    types::*,
+};
 
 /// Roadmap planner for generating and managing product roadmap
 pub struct RoadmapPlanner {
@@ -84,6 +87,7 @@ impl RoadmapPlanner {
         analytics_insights: &AnalyticsInsights,
         issue_insights: &IssueInsights,
         security_insights: &SecurityInsights,
+        experiment_results: &[ExperimentResult],
     ) -> Result<ImprovementRecommendations, ContinuousImprovementError> {
         tracing::info!("Generating improvement recommendations");
 
@@ -106,6 +110,9 @@ impl RoadmapPlanner {
         // Generate recommendations from security insights
         recommendations.extend(self.generate_security_recommendations(security_insights));
 
+        // Generate recommendations from experiment results
+        recommendations.extend(self.generate_experiment_recommendations(experiment_results));
+
         // Generate roadmap items
         roadmap_items.extend(self.generate_roadmap_items(&recommendations));
 
@@ -373,6 +380,90 @@ impl RoadmapPlanner {
         recommendations
     }
 
+    /// Generate recommendations from significant experiment results
+    fn generate_experiment_recommendations(
+        &self,
+        results: &[ExperimentResult],
+    ) -> Vec<ImprovementRecommendation> {
+        let mut recommendations = Vec::new();
+
+        for result in results.iter().filter(|r| r.significant) {
+            let supporting_data = HashMap::from([
+                (
+                    "metric".to_string(),
+                    serde_json::to_value(result.metric).unwrap_or_default(),
+                ),
+                (
+                    "control_value".to_string(),
+                    serde_json::Value::from(result.control_value),
+                ),
+                (
+                    "treatment_value".to_string(),
+                    serde_json::Value::from(result.treatment_value),
+                ),
+                (
+                    "p_value".to_string(),
+                    serde_json::Value::from(result.p_value),
+                ),
+            ]);
+            let change = format!("{:+.1}%", result.relative_change * 100.0);
+
+            if result.treatment_wins() {
+                recommendations.push(ImprovementRecommendation {
+                    id: format!(
+                        "experiment-rollout-{}-{}",
+                        result.experiment, result.treatment
+                    ),
+                    title: format!("Roll out {} for {}", result.treatment, result.experiment),
+                    description: format!(
+                        "Variant {} of experiment {} outperforms {}",
+                        result.treatment, result.experiment, result.control
+                    ),
+                    category: RecommendationCategory::FeatureEnhancement,
+                    priority: Priority::High,
+                    effort_estimate: EffortLevel::Small,
+                    impact_score: 7.5,
+                    rationale: format!(
+                        "{:?} changed by {} (p = {:.3})",
+                        result.metric, change, result.p_value
+                    ),
+                    supporting_data,
+                });
+            } else {
+                recommendations.push(ImprovementRecommendation {
+                    id: format!(
+                        "experiment-revert-{}-{}",
+                        result.experiment, result.treatment
+                    ),
+                    title: format!(
+                        "Stop rollout of {} for {}",
+                        result.treatment, result.experiment
+                    ),
+                    description: format!(
+                        "Variant {} of experiment {} performs worse than {}",
+                        result.treatment, result.experiment, result.control
+                    ),
+                    category: match result.metric {
+                        ExperimentMetric::LatencyMs => {
+                            RecommendationCategory::PerformanceImprovement
+                        }
+                        ExperimentMetric::AcceptanceRate => RecommendationCategory::UserExperience,
+                    },
+                    priority: Priority::Medium,
+                    effort_estimate: EffortLevel::Small,
+                    impact_score: 6.5,
+                    rationale: format!(
+                        "{:?} regressed by {} (p = {:.3})",
+                        result.metric, change, result.p_value
+                    ),
+                    supporting_data,
+                });
+            }
+        }
+
+        recommendations
+    }
+
     /// Generate roadmap items from recommendations
     fn generate_roadmap_items(
         &self,
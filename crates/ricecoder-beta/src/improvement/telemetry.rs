@@ -0,0 +1,374 @@
+//! Privacy-preserving telemetry
+//!
+//! Usage events are aggregated locally into per-feature counters and latency
+//! histograms; individual events never leave the machine. At export time
+//! each counter receives Laplace noise (scaled by the configured privacy
+//! budget) and small counters are suppressed, so a single session cannot be
+//! singled out from the report. The noise for a window is derived from a
+//! per-window seed, which makes [`TelemetryAggregator::report`] return exactly
+//! the payload that [`TelemetryExporter::export`] would send.
+
+use std::{collections::BTreeMap, time::Duration};
+
+use chrono::{DateTime, Utc};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use super::types::{ContinuousImprovementError, TelemetryConfig};
+
+/// Upper bounds (inclusive, in milliseconds) of the latency histogram buckets
+pub const LATENCY_BUCKETS_MS: &[u64] = &[50, 100, 250, 500, 1000, 2500, 5000, 10000];
+
+/// Maximum length of a feature name
+const MAX_FEATURE_NAME_LEN: usize = 64;
+
+/// Name used for features that do not look like identifiers
+const OTHER_FEATURE: &str = "other";
+
+/// Normalize a feature name so only identifier-like names are reported
+///
+/// Anything that could carry free text (spaces, paths, overly long values)
+/// is folded into `other`.
+pub fn normalize_feature_name(name: &str) -> String {
+    let name = name.trim().to_ascii_lowercase();
+    let valid = !name.is_empty()
+        && name.len() <= MAX_FEATURE_NAME_LEN
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | ':'));
+    if valid {
+        name
+    } else {
+        OTHER_FEATURE.to_string()
+    }
+}
+
+/// Latency histogram with fixed buckets
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LatencyHistogram {
+    /// One count per bucket in [`LATENCY_BUCKETS_MS`], plus an overflow bucket
+    pub buckets: Vec<u64>,
+}
+
+impl LatencyHistogram {
+    /// Record one sample
+    pub fn record(&mut self, latency: Duration) {
+        if self.buckets.is_empty() {
+            self.buckets = vec![0; LATENCY_BUCKETS_MS.len() + 1];
+        }
+        let millis = latency.as_millis() as u64;
+        let idx = LATENCY_BUCKETS_MS
+            .iter()
+            .position(|bound| millis <= *bound)
+            .unwrap_or(LATENCY_BUCKETS_MS.len());
+        self.buckets[idx] += 1;
+    }
+
+    /// Total number of samples
+    pub fn total(&self) -> u64 {
+        self.buckets.iter().sum()
+    }
+}
+
+/// Aggregated report, the only telemetry payload ever exported
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TelemetryReport {
+    pub window_start: DateTime<Utc>,
+    pub window_end: DateTime<Utc>,
+    pub app_version: String,
+    pub os: String,
+    /// Noised feature usage counters
+    pub feature_usage: BTreeMap<String, u64>,
+    /// Noised latency histograms per feature
+    pub latency_histograms: BTreeMap<String, LatencyHistogram>,
+    /// Bucket upper bounds in milliseconds; the last bucket is open-ended
+    pub latency_buckets_ms: Vec<u64>,
+}
+
+impl TelemetryReport {
+    /// Whether the report carries no data
+    pub fn is_empty(&self) -> bool {
+        self.feature_usage.is_empty() && self.latency_histograms.is_empty()
+    }
+}
+
+/// Local aggregation of usage events
+#[derive(Debug, Clone)]
+pub struct TelemetryAggregator {
+    feature_usage: BTreeMap<String, u64>,
+    latency_histograms: BTreeMap<String, LatencyHistogram>,
+    window_start: DateTime<Utc>,
+    window_seed: u64,
+    app_version: String,
+}
+
+impl Default for TelemetryAggregator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TelemetryAggregator {
+    /// Create an empty aggregator
+    pub fn new() -> Self {
+        Self {
+            feature_usage: BTreeMap::new(),
+            latency_histograms: BTreeMap::new(),
+            window_start: Utc::now(),
+            window_seed: rand::random(),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+
+    /// Set the application version recorded in reports
+    pub fn with_app_version(mut self, version: impl Into<String>) -> Self {
+        self.app_version = version.into();
+        self
+    }
+
+    /// Count one use of a feature
+    pub fn record_feature(&mut self, feature: &str) {
+        *self
+            .feature_usage
+            .entry(normalize_feature_name(feature))
+            .or_insert(0) += 1;
+    }
+
+    /// Record a latency sample for a feature
+    pub fn record_latency(&mut self, feature: &str, latency: Duration) {
+        self.latency_histograms
+            .entry(normalize_feature_name(feature))
+            .or_default()
+            .record(latency);
+    }
+
+    /// Raw (un-noised) usage counters of the current window, for local display
+    pub fn local_feature_usage(&self) -> &BTreeMap<String, u64> {
+        &self.feature_usage
+    }
+
+    /// Start of the current aggregation window
+    pub fn window_start(&self) -> DateTime<Utc> {
+        self.window_start
+    }
+
+    /// Build the report for the current window
+    ///
+    /// Repeated calls return the same report until new events are recorded
+    /// or the window is closed.
+    pub fn report(&self, config: &TelemetryConfig) -> TelemetryReport {
+        let scale = laplace_scale(config.epsilon);
+
+        let feature_usage = self
+            .feature_usage
+            .iter()
+            .filter_map(|(feature, count)| {
+                let mut rng = self.rng_for(&format!("usage:{}", feature));
+                let noised = add_noise(*count, scale, &mut rng);
+                (noised >= config.min_count).then(|| (feature.clone(), noised))
+            })
+            .collect();
+
+        let latency_histograms = self
+            .latency_histograms
+            .iter()
+            .filter_map(|(feature, histogram)| {
+                let mut rng = self.rng_for(&format!("latency:{}", feature));
+                let buckets: Vec<u64> = histogram
+                    .buckets
+                    .iter()
+                    .map(|count| add_noise(*count, scale, &mut rng))
+                    .collect();
+                let noised = LatencyHistogram { buckets };
+                (noised.total() >= config.min_count).then(|| (feature.clone(), noised))
+            })
+            .collect();
+
+        TelemetryReport {
+            window_start: self.window_start,
+            window_end: Utc::now(),
+            app_version: self.app_version.clone(),
+            os: std::env::consts::OS.to_string(),
+            feature_usage,
+            latency_histograms,
+            latency_buckets_ms: LATENCY_BUCKETS_MS.to_vec(),
+        }
+    }
+
+    /// Build the report for the current window and start a new one
+    pub fn close_window(&mut self, config: &TelemetryConfig) -> TelemetryReport {
+        let report = self.report(config);
+        self.feature_usage.clear();
+        self.latency_histograms.clear();
+        self.window_start = report.window_end;
+        self.window_seed = rand::random();
+        report
+    }
+
+    /// Deterministic RNG for one counter in the current window
+    fn rng_for(&self, key: &str) -> StdRng {
+        let key_hash = key.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+        });
+        StdRng::seed_from_u64(self.window_seed ^ key_hash)
+    }
+}
+
+/// Laplace scale for a counter with sensitivity 1
+fn laplace_scale(epsilon: f64) -> f64 {
+    if epsilon > 0.0 {
+        1.0 / epsilon
+    } else {
+        0.0
+    }
+}
+
+/// Add Laplace noise to a count, rounding and clamping at zero
+fn add_noise(count: u64, scale: f64, rng: &mut StdRng) -> u64 {
+    if scale == 0.0 {
+        return count;
+    }
+    let u: f64 = rng.gen_range(-0.5..0.5);
+    let noise = -scale * u.signum() * (1.0 - 2.0 * u.abs()).ln();
+    (count as f64 + noise).round().max(0.0) as u64
+}
+
+/// Sends aggregated reports to the configured endpoint
+pub struct TelemetryExporter {
+    client: reqwest::Client,
+}
+
+impl Default for TelemetryExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TelemetryExporter {
+    /// Create an exporter
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Post a report to the configured endpoint
+    ///
+    /// Returns `Ok(false)` without sending anything when the user has not
+    /// opted in, no endpoint is configured or the report is empty.
+    pub async fn export(
+        &self,
+        config: &TelemetryConfig,
+        report: &TelemetryReport,
+    ) -> Result<bool, ContinuousImprovementError> {
+        let Some(endpoint) = config.endpoint.as_deref().filter(|_| config.opt_in) else {
+            return Ok(false);
+        };
+        if report.is_empty() {
+            return Ok(false);
+        }
+
+        let response = self
+            .client
+            .post(endpoint)
+            .json(report)
+            .send()
+            .await
+            .map_err(|e| ContinuousImprovementError::AnalyticsError(e.to_string()))?;
+        if !response.status().is_success() {
+            return Err(ContinuousImprovementError::AnalyticsError(format!(
+                "Telemetry endpoint returned {}",
+                response.status()
+            )));
+        }
+
+        tracing::info!(
+            "Exported telemetry report ({} features, {} histograms)",
+            report.feature_usage.len(),
+            report.latency_histograms.len()
+        );
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> TelemetryConfig {
+        TelemetryConfig {
+            min_count: 3,
+            ..TelemetryConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_feature_names_cannot_carry_free_text() {
+        assert_eq!(normalize_feature_name("Chat.Send"), "chat.send");
+        assert_eq!(normalize_feature_name("/home/alice/secret.txt"), "other");
+        assert_eq!(normalize_feature_name("fix the login bug"), "other");
+        assert_eq!(normalize_feature_name(&"a".repeat(65)), "other");
+    }
+
+    #[test]
+    fn test_report_is_stable_and_suppresses_small_counts() {
+        let mut aggregator = TelemetryAggregator::new();
+        for _ in 0..200 {
+            aggregator.record_feature("chat.send");
+            aggregator.record_latency("chat.send", Duration::from_millis(300));
+        }
+        aggregator.record_feature("rare.feature");
+
+        let config = TelemetryConfig {
+            min_count: 20,
+            ..config()
+        };
+        let report = aggregator.report(&config);
+        let again = aggregator.report(&config);
+        assert_eq!(report.feature_usage, again.feature_usage);
+        assert_eq!(report.latency_histograms, again.latency_histograms);
+
+        assert!(!report.feature_usage.contains_key("rare.feature"));
+        let count = report.feature_usage["chat.send"];
+        assert!((180..=220).contains(&count), "noise too large: {}", count);
+        assert_eq!(
+            report.latency_histograms["chat.send"].buckets.len(),
+            LATENCY_BUCKETS_MS.len() + 1
+        );
+    }
+
+    #[test]
+    fn test_close_window_resets_counters() {
+        let mut aggregator = TelemetryAggregator::new();
+        let exact = TelemetryConfig {
+            epsilon: 0.0,
+            ..config()
+        };
+        for _ in 0..5 {
+            aggregator.record_feature("search");
+        }
+        let report = aggregator.close_window(&exact);
+        assert_eq!(report.feature_usage["search"], 5);
+        assert!(aggregator.local_feature_usage().is_empty());
+        assert!(aggregator.report(&exact).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_export_requires_opt_in_and_endpoint() {
+        let mut aggregator = TelemetryAggregator::new();
+        for _ in 0..10 {
+            aggregator.record_feature("search");
+        }
+        let exporter = TelemetryExporter::new();
+        let mut config = TelemetryConfig {
+            endpoint: Some("http://127.0.0.1:9/telemetry".to_string()),
+            ..config()
+        };
+        let report = aggregator.report(&config);
+        assert!(!exporter.export(&config, &report).await.unwrap());
+
+        config.opt_in = true;
+        config.endpoint = None;
+        assert!(!exporter.export(&config, &report).await.unwrap());
+    }
+}
@@ -55,6 +55,8 @@ pub struct AnalyticsPipelineConfig {
     pub collection_interval: TimeDelta,
     pub prioritization_interval: TimeDelta,
     pub feature_adoption_threshold: f64,
+    #[serde(default)]
+    pub telemetry: TelemetryConfig,
 }
 
 impl Default for AnalyticsPipelineConfig {
@@ -64,10 +66,66 @@ impl Default for AnalyticsPipelineConfig {
             collection_interval: TimeDelta::seconds(600), // 10 minutes
             prioritization_interval: TimeDelta::seconds(7200), // 2 hours
             feature_adoption_threshold: 10.0,             // 10% adoption rate
+            telemetry: TelemetryConfig::default(),
         }
     }
 }
 
+/// Aggregated telemetry export configuration
+///
+/// Raw events always stay local; only noised aggregate counters are sent,
+/// and only after the user has opted in.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TelemetryConfig {
+    /// Explicit user opt-in; nothing is exported without it
+    pub opt_in: bool,
+    /// Endpoint receiving aggregated reports
+    pub endpoint: Option<String>,
+    pub export_interval: TimeDelta,
+    /// Privacy budget per counter; smaller values add more noise
+    pub epsilon: f64,
+    /// Counters below this value (after noise) are dropped
+    pub min_count: u64,
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            opt_in: false,
+            endpoint: None,
+            export_interval: TimeDelta::seconds(86400), // 1 day
+            epsilon: 1.0,
+            min_count: 5,
+        }
+    }
+}
+
+impl TelemetryConfig {
+    /// Key of the telemetry section in the config's custom settings
+    pub const CONFIG_KEY: &'static str = "telemetry";
+
+    /// Read the telemetry section from custom config settings
+    ///
+    /// A missing section leaves telemetry off, without consent.
+    pub fn from_settings(
+        settings: &HashMap<String, serde_json::Value>,
+    ) -> Result<Self, ContinuousImprovementError> {
+        settings
+            .get(Self::CONFIG_KEY)
+            .map(|value| {
+                serde_json::from_value(value.clone()).map_err(|e| {
+                    ContinuousImprovementError::ConfigError(format!(
+                        "Invalid telemetry configuration: {}",
+                        e
+                    ))
+                })
+            })
+            .transpose()
+            .map(Option::unwrap_or_default)
+    }
+}
+
 /// Issue detection pipeline configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IssueDetectionPipelineConfig {
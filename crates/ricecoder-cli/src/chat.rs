@@ -207,6 +207,10 @@ impl ChatSession {
             temperature: None,
             max_tokens: None,
             stream: true,
+            top_p: None,
+            stop: None,
+            seed: None,
+            reasoning_effort: None,
         };
 
         // Try streaming first, fall back to non-streaming if not supported
@@ -172,6 +172,10 @@ impl ChatCommand {
             temperature: None,
             max_tokens: None,
             stream: true,
+            top_p: None,
+            stop: None,
+            seed: None,
+            reasoning_effort: None,
         };
 
         // Try streaming first, fall back to non-streaming if not supported
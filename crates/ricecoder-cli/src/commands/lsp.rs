@@ -110,7 +110,10 @@ async fn start_lsp_server(config: LspConfig) -> CliResult<()> {
     use ricecoder_lsp::LspServer;
 
     // Create and run the LSP server
-    let mut server = LspServer::new();
+    let mut server = {
+        let _phase = ricecoder_performance::StartupTracer::global().phase("lsp_spawn");
+        LspServer::new()
+    };
 
     info!("LSP server initialized");
     info!("Listening on stdio transport");
@@ -15,6 +15,7 @@ pub mod providers;
 pub mod refactor;
 pub mod review;
 pub mod sessions;
+pub mod skills;
 pub mod tui;
 pub mod version;
 
@@ -32,6 +33,7 @@ pub use providers::{ProvidersAction, ProvidersCommand};
 pub use refactor::RefactorCommand;
 pub use review::ReviewCommand;
 pub use sessions::{SessionsAction, SessionsCommand};
+pub use skills::{SkillsAction, SkillsCommand};
 pub use tui::TuiCommand;
 pub use version::VersionCommand;
 
@@ -0,0 +1,91 @@
+//! Skills command - List and run skills
+
+use async_trait::async_trait;
+use ricecoder_skills::{SkillPermission, SkillRegistry, SkillToolInput, SkillToolProvider};
+
+use crate::{
+    commands::Command,
+    error::{CliError, CliResult},
+};
+
+/// Skills command action
+#[derive(Debug, Clone)]
+pub enum SkillsAction {
+    /// List discovered skills
+    List,
+    /// Render a skill with arguments
+    Run {
+        /// Skill name
+        name: String,
+        /// Raw `--name value` arguments
+        args: Vec<String>,
+    },
+}
+
+/// Skills command handler
+pub struct SkillsCommand {
+    action: SkillsAction,
+}
+
+impl SkillsCommand {
+    /// Create a new skills command
+    pub fn new(action: SkillsAction) -> Self {
+        Self { action }
+    }
+}
+
+#[async_trait]
+impl Command for SkillsCommand {
+    async fn execute(&self) -> CliResult<()> {
+        match &self.action {
+            SkillsAction::List => list_skills().await,
+            SkillsAction::Run { name, args } => run_skill(name, args).await,
+        }
+    }
+}
+
+/// List discovered skills and their declared arguments
+async fn list_skills() -> CliResult<()> {
+    let mut skills = SkillRegistry::all()
+        .await
+        .map_err(|e| CliError::Internal(e.to_string()))?;
+    skills.sort_by(|a, b| a.name.cmp(&b.name));
+
+    if skills.is_empty() {
+        println!("No skills found");
+        return Ok(());
+    }
+
+    for skill in skills {
+        println!("{} - {}", skill.name, skill.description);
+        for arg in &skill.arguments {
+            println!(
+                "    --{} <{:?}>{}{}",
+                arg.name,
+                arg.arg_type,
+                if arg.required { " (required)" } else { "" },
+                arg.description
+                    .as_ref()
+                    .map(|d| format!("  {}", d))
+                    .unwrap_or_default()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Render a skill with the given arguments and print the result
+async fn run_skill(name: &str, args: &[String]) -> CliResult<()> {
+    let input =
+        SkillToolInput::from_cli_args(name, args).map_err(|e| CliError::InvalidArgument {
+            message: e.to_string(),
+        })?;
+
+    let output = SkillToolProvider::new()
+        .execute(input, "cli", &SkillPermission::default())
+        .await
+        .map_err(|e| CliError::Internal(e.to_string()))?;
+
+    println!("{}", output.output);
+    Ok(())
+}
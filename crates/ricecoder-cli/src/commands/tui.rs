@@ -126,9 +126,12 @@ async fn launch_tui(config: TuiConfig) -> CliResult<()> {
     use ricecoder_tui::tui::TuiApp;
     
     // Create and run the TUI application
-    let mut app = TuiApp::new().map_err(|e| {
-        CliError::Internal(format!("Failed to initialize TUI: {}", e))
-    })?;
+    let mut app = {
+        let _phase = ricecoder_performance::StartupTracer::global().phase("tui_init");
+        TuiApp::new().map_err(|e| {
+            CliError::Internal(format!("Failed to initialize TUI: {}", e))
+        })?
+    };
     
     // Run the TUI event loop
     app.run().await.map_err(|e| {
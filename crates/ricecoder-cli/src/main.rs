@@ -2,12 +2,26 @@
 
 use std::path::Path;
 
+use ricecoder_beta::improvement::CrashReporter;
 use ricecoder_cli::{lifecycle, output, router::CommandRouter};
-use ricecoder_storage::DefaultsManager;
+use ricecoder_performance::{StartupBudgets, StartupTracer};
+use ricecoder_storage::{ConfigLoader, DefaultsManager};
 
 
 #[tokio::main]
 async fn main() {
+    // Start the startup clock before any initialization work
+    let startup = StartupTracer::global();
+    if let Ok(path) = std::env::var("RICECODER_STARTUP_BUDGETS") {
+        match StartupBudgets::load_from_file(&path) {
+            Ok(budgets) => startup.set_budgets(budgets),
+            Err(e) => output::print_warning(&format!(
+                "Ignoring startup budgets from {}: {}",
+                path, e
+            )),
+        }
+    }
+
     // Check for multi-call binary pattern
     let binary_name = std::env::args().next().and_then(|s| {
         Path::new(&s)
@@ -32,13 +46,18 @@ async fn main() {
     }
 
     // Initialize first-run setup if needed
-    if let Err(e) = initialize_first_run() {
+    if let Err(e) = startup.time("config_load", initialize_first_run) {
         output::print_error(&format!("First-run initialization failed: {}", e));
         std::process::exit(1);
     }
 
+    // Capture crashes locally when the user has consented to telemetry
+    if let Ok(config) = ConfigLoader::new().load_merged() {
+        CrashReporter::install_from_settings(&config.custom, env!("CARGO_PKG_VERSION"));
+    }
+
     // Initialize DI container
-    if let Err(e) = ricecoder_cli::di::initialize_di_container() {
+    if let Err(e) = startup.time("di_container", ricecoder_cli::di::initialize_di_container) {
         output::print_error(&format!("DI container initialization failed: {}", e));
         std::process::exit(1);
     }
@@ -47,13 +66,21 @@ async fn main() {
     let lifecycle_manager = lifecycle::initialize_lifecycle_manager();
 
     // Initialize all components
-    if let Err(e) = lifecycle_manager.initialize_all().await {
+    let init_result = {
+        let _phase = startup.phase("lifecycle_init");
+        lifecycle_manager.initialize_all().await
+    };
+    if let Err(e) = init_result {
         output::print_error(&format!("Component initialization failed: {}", e));
         std::process::exit(1);
     }
 
     // Start all components
-    if let Err(e) = lifecycle_manager.start_all().await {
+    let start_result = {
+        let _phase = startup.phase("lifecycle_start");
+        lifecycle_manager.start_all().await
+    };
+    if let Err(e) = start_result {
         output::print_error(&format!("Component startup failed: {}", e));
         std::process::exit(1);
     }
@@ -3,6 +3,7 @@
 
 use clap::{Parser, Subcommand};
 use ricecoder_mcp::compliance::ComplianceReportType;
+use ricecoder_performance::StartupTracer;
 
 use crate::{
     commands::*,
@@ -35,6 +36,10 @@ pub struct Cli {
     /// Preview changes without applying them
     #[arg(long, global = true)]
     pub dry_run: bool,
+
+    /// Print a waterfall of startup phases and their budgets on exit
+    #[arg(long, global = true)]
+    pub startup_report: bool,
 }
 
 #[derive(Subcommand, Debug, Clone)]
@@ -200,6 +205,13 @@ pub enum Commands {
         action: Option<HooksSubcommand>,
     },
 
+    /// List and run skills
+    #[command(about = "List and run parameterized skills")]
+    Skill {
+        #[command(subcommand)]
+        action: Option<SkillSubcommand>,
+    },
+
     /// Show help and tutorials
     #[command(about = "Show help, tutorials, and troubleshooting guides")]
     Help {
@@ -256,6 +268,29 @@ pub enum HooksSubcommand {
     },
 }
 
+#[derive(Subcommand, Debug, Clone)]
+pub enum SkillSubcommand {
+    /// List discovered skills
+    #[command(about = "List discovered skills and their arguments")]
+    List,
+
+    /// Run a skill
+    #[command(about = "Render a skill with arguments (e.g. --language rust)")]
+    Run {
+        /// Skill name
+        #[arg(value_name = "NAME")]
+        name: String,
+
+        /// Skill arguments as --name value pairs
+        #[arg(
+            trailing_var_arg = true,
+            allow_hyphen_values = true,
+            value_name = "ARGS"
+        )]
+        args: Vec<String>,
+    },
+}
+
 #[derive(Subcommand, Debug, Clone)]
 pub enum SessionsSubcommand {
     /// List all sessions
@@ -601,7 +636,16 @@ impl CommandRouter {
         // Initialize logging based on CLI flags
         crate::logging::init_logging(cli.verbose, cli.quiet);
 
-        Self::execute(&cli).await
+        let result = Self::execute(&cli).await;
+
+        // Phases are recorded by main and the command entry points
+        let startup = StartupTracer::global().report();
+        startup.emit_alerts();
+        if cli.startup_report {
+            eprint!("{}", startup.waterfall(50));
+        }
+
+        result
     }
 
     /// Execute a command
@@ -915,6 +959,17 @@ impl CommandRouter {
                 let cmd = hooks::HooksCommand::new(hooks_action);
                 cmd.execute()
             }
+            Commands::Skill { action } => {
+                let skills_action = match action {
+                    Some(SkillSubcommand::List) | None => skills::SkillsAction::List,
+                    Some(SkillSubcommand::Run { name, args }) => skills::SkillsAction::Run {
+                        name: name.clone(),
+                        args: args.clone(),
+                    },
+                };
+                let cmd = skills::SkillsCommand::new(skills_action);
+                cmd.execute().await
+            }
             Commands::Help { topic } => {
                 let cmd = HelpCommand::new(topic.clone());
                 cmd.execute().await
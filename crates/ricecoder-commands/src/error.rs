@@ -9,6 +9,9 @@ pub enum CommandError {
     #[error("Invalid command name: {0}")]
     InvalidCommandName(String),
 
+    #[error("Command pack conflict: {0}")]
+    PackConflict(String),
+
     #[error("Invalid argument: {0}")]
     InvalidArgument(String),
 
@@ -39,6 +42,9 @@ pub enum CommandError {
     #[error("Command timeout")]
     Timeout,
 
+    #[error("Command cancelled")]
+    Cancelled,
+
     #[error("Execution error: {0}")]
     ExecutionError(String),
 
@@ -0,0 +1,429 @@
+//! Background command execution with job tracking
+//!
+//! [`CommandManager::execute_background`](crate::CommandManager::execute_background)
+//! starts a command as a tokio task and returns a [`JobHandle`]. Progress is
+//! published as [`JobEvent`]s on the [`JobTracker`]'s broadcast channel so the
+//! TUI can stream output into its job queue or logger widget, and the final
+//! event carries the formatted chat injection and hook event name requested in
+//! [`BackgroundOptions`].
+
+use std::{
+    collections::HashMap,
+    process::Stdio,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncRead, BufReader},
+    sync::{broadcast, watch},
+    task::JoinHandle,
+};
+
+use crate::{
+    error::{CommandError, Result},
+    output_injection::{OutputInjectionConfig, OutputInjector},
+    types::CommandExecutionResult,
+};
+
+/// Capacity of the job event channel
+const EVENT_CHANNEL_CAPACITY: usize = 256;
+
+/// How long to keep reading output after the process exits
+const OUTPUT_DRAIN_GRACE: Duration = Duration::from_secs(1);
+
+/// Options for a background execution
+#[derive(Debug, Clone, Default)]
+pub struct BackgroundOptions {
+    /// Format the result for chat injection when the job finishes
+    pub inject_output: bool,
+    /// Hook event to fire when the job finishes
+    pub hook_event: Option<String>,
+}
+
+/// Status of a background job
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+    TimedOut,
+}
+
+/// Output stream a line came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+/// Progress of a background job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JobEvent {
+    /// The job started
+    Started { job_id: String, command_id: String },
+    /// A line of output was produced
+    Output {
+        job_id: String,
+        stream: OutputStream,
+        line: String,
+    },
+    /// The job finished
+    Finished {
+        job_id: String,
+        status: JobStatus,
+        result: Option<CommandExecutionResult>,
+        /// Output formatted for chat injection, if requested
+        injection: Option<String>,
+        /// Hook event to fire, if requested
+        hook_event: Option<String>,
+    },
+}
+
+/// Snapshot of a tracked job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobInfo {
+    /// Job ID
+    pub id: String,
+    /// Command the job runs
+    pub command_id: String,
+    /// Current status
+    pub status: JobStatus,
+    /// When the job started
+    pub started_at: DateTime<Utc>,
+    /// When the job finished
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// Tracks background jobs and publishes their events
+#[derive(Debug, Clone)]
+pub struct JobTracker {
+    jobs: Arc<Mutex<HashMap<String, TrackedJob>>>,
+    events: broadcast::Sender<JobEvent>,
+}
+
+#[derive(Debug)]
+struct TrackedJob {
+    info: JobInfo,
+    cancel: watch::Sender<bool>,
+}
+
+/// Handle to a running background job
+#[derive(Debug)]
+pub struct JobHandle {
+    id: String,
+    command_id: String,
+    cancel: watch::Sender<bool>,
+    task: JoinHandle<Result<CommandExecutionResult>>,
+}
+
+/// Everything needed to run a shell command in the background
+pub(crate) struct BackgroundJob {
+    pub command_id: String,
+    pub process: std::process::Command,
+    pub timeout: Option<Duration>,
+    pub options: BackgroundOptions,
+    pub output_config: OutputInjectionConfig,
+}
+
+impl JobTracker {
+    /// Create an empty tracker
+    pub fn new() -> Self {
+        let (events, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        Self {
+            jobs: Arc::new(Mutex::new(HashMap::new())),
+            events,
+        }
+    }
+
+    /// Subscribe to events of all jobs
+    pub fn subscribe(&self) -> broadcast::Receiver<JobEvent> {
+        self.events.subscribe()
+    }
+
+    /// List tracked jobs, most recent first
+    pub fn list(&self) -> Vec<JobInfo> {
+        let mut jobs: Vec<JobInfo> = self
+            .jobs
+            .lock()
+            .unwrap()
+            .values()
+            .map(|job| job.info.clone())
+            .collect();
+        jobs.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+        jobs
+    }
+
+    /// Get a tracked job
+    pub fn get(&self, job_id: &str) -> Option<JobInfo> {
+        self.jobs
+            .lock()
+            .unwrap()
+            .get(job_id)
+            .map(|job| job.info.clone())
+    }
+
+    /// Request cancellation of a running job
+    pub fn cancel(&self, job_id: &str) -> Result<()> {
+        let jobs = self.jobs.lock().unwrap();
+        let job = jobs
+            .get(job_id)
+            .ok_or_else(|| CommandError::Other(format!("Job not found: {}", job_id)))?;
+        let _ = job.cancel.send(true);
+        Ok(())
+    }
+
+    /// Remove finished jobs from the tracker
+    pub fn clear_finished(&self) {
+        self.jobs
+            .lock()
+            .unwrap()
+            .retain(|_, job| job.info.status == JobStatus::Running);
+    }
+
+    /// Start a background job
+    pub(crate) fn spawn(&self, job: BackgroundJob) -> JobHandle {
+        let id = uuid::Uuid::new_v4().to_string();
+        let (cancel, cancelled) = watch::channel(false);
+
+        self.jobs.lock().unwrap().insert(
+            id.clone(),
+            TrackedJob {
+                info: JobInfo {
+                    id: id.clone(),
+                    command_id: job.command_id.clone(),
+                    status: JobStatus::Running,
+                    started_at: Utc::now(),
+                    finished_at: None,
+                },
+                cancel: cancel.clone(),
+            },
+        );
+        let _ = self.events.send(JobEvent::Started {
+            job_id: id.clone(),
+            command_id: job.command_id.clone(),
+        });
+
+        let tracker = self.clone();
+        let job_id = id.clone();
+        let command_id = job.command_id.clone();
+        let task = tokio::spawn(async move { tracker.run(job_id, job, cancelled).await });
+
+        JobHandle {
+            id,
+            command_id,
+            cancel,
+            task,
+        }
+    }
+
+    async fn run(
+        &self,
+        job_id: String,
+        job: BackgroundJob,
+        mut cancelled: watch::Receiver<bool>,
+    ) -> Result<CommandExecutionResult> {
+        let start_time = Instant::now();
+        let BackgroundJob {
+            command_id,
+            process,
+            timeout,
+            options,
+            output_config,
+        } = job;
+        let mut process = tokio::process::Command::from(process);
+        process
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+
+        let mut child = match process.spawn() {
+            Ok(child) => child,
+            Err(e) => {
+                let error =
+                    CommandError::ExecutionError(format!("Failed to execute command: {}", e));
+                self.finish(&job_id, JobStatus::Failed, None, &options, &output_config);
+                return Err(error);
+            }
+        };
+
+        let stdout = child
+            .stdout
+            .take()
+            .map(|out| self.forward(&job_id, OutputStream::Stdout, out));
+        let stderr = child
+            .stderr
+            .take()
+            .map(|err| self.forward(&job_id, OutputStream::Stderr, err));
+
+        let cancel_requested = async {
+            while !*cancelled.borrow_and_update() {
+                if cancelled.changed().await.is_err() {
+                    std::future::pending::<()>().await;
+                }
+            }
+        };
+        let timeout = async {
+            match timeout {
+                Some(timeout) => tokio::time::sleep(timeout).await,
+                None => std::future::pending().await,
+            }
+        };
+
+        let (status, exit_code) = tokio::select! {
+            exit = child.wait() => match exit {
+                Ok(exit) if exit.success() => (JobStatus::Completed, exit.code().unwrap_or(0)),
+                Ok(exit) => (JobStatus::Failed, exit.code().unwrap_or(-1)),
+                Err(_) => (JobStatus::Failed, -1),
+            },
+            _ = cancel_requested => {
+                let _ = child.kill().await;
+                (JobStatus::Cancelled, -1)
+            }
+            _ = timeout => {
+                let _ = child.kill().await;
+                (JobStatus::TimedOut, -1)
+            }
+        };
+
+        // Processes left behind by a killed shell can keep the pipes open, so
+        // readers only get a short grace period before being dropped.
+        let grace = match status {
+            JobStatus::Completed | JobStatus::Failed => OUTPUT_DRAIN_GRACE,
+            _ => OUTPUT_DRAIN_GRACE / 10,
+        };
+        let stdout = drain(stdout, grace).await;
+        let stderr = drain(stderr, grace).await;
+
+        let result = CommandExecutionResult::new(command_id, exit_code)
+            .with_stdout(stdout)
+            .with_stderr(stderr)
+            .with_duration(start_time.elapsed().as_millis() as u64);
+        self.finish(
+            &job_id,
+            status,
+            Some(result.clone()),
+            &options,
+            &output_config,
+        );
+
+        match status {
+            JobStatus::Cancelled => Err(CommandError::Cancelled),
+            JobStatus::TimedOut => Err(CommandError::Timeout),
+            _ => Ok(result),
+        }
+    }
+
+    /// Publish each line of a stream and collect it
+    fn forward(
+        &self,
+        job_id: &str,
+        stream: OutputStream,
+        reader: impl AsyncRead + Unpin + Send + 'static,
+    ) -> OutputReader {
+        let events = self.events.clone();
+        let job_id = job_id.to_string();
+        let collected = Arc::new(Mutex::new(String::new()));
+        let buffer = collected.clone();
+        let task = tokio::spawn(async move {
+            let mut lines = BufReader::new(reader).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                {
+                    let mut buffer = buffer.lock().unwrap();
+                    buffer.push_str(&line);
+                    buffer.push('\n');
+                }
+                let _ = events.send(JobEvent::Output {
+                    job_id: job_id.clone(),
+                    stream,
+                    line,
+                });
+            }
+        });
+        OutputReader { task, collected }
+    }
+
+    fn finish(
+        &self,
+        job_id: &str,
+        status: JobStatus,
+        result: Option<CommandExecutionResult>,
+        options: &BackgroundOptions,
+        output_config: &OutputInjectionConfig,
+    ) {
+        if let Some(tracked) = self.jobs.lock().unwrap().get_mut(job_id) {
+            tracked.info.status = status;
+            tracked.info.finished_at = Some(Utc::now());
+        }
+
+        let injection = match (&result, options.inject_output) {
+            (Some(result), true) => OutputInjector::inject(result, output_config).ok(),
+            _ => None,
+        };
+        let _ = self.events.send(JobEvent::Finished {
+            job_id: job_id.to_string(),
+            status,
+            result,
+            injection,
+            hook_event: options.hook_event.clone(),
+        });
+    }
+}
+
+/// A task forwarding one output stream, and the output it collected
+struct OutputReader {
+    task: JoinHandle<()>,
+    collected: Arc<Mutex<String>>,
+}
+
+async fn drain(reader: Option<OutputReader>, grace: Duration) -> String {
+    let Some(mut reader) = reader else {
+        return String::new();
+    };
+    if tokio::time::timeout(grace, &mut reader.task).await.is_err() {
+        reader.task.abort();
+    }
+    let collected = reader.collected.lock().unwrap().clone();
+    collected
+}
+
+impl Default for JobTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JobHandle {
+    /// Job ID
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Command the job runs
+    pub fn command_id(&self) -> &str {
+        &self.command_id
+    }
+
+    /// Request cancellation
+    pub fn cancel(&self) {
+        let _ = self.cancel.send(true);
+    }
+
+    /// Whether the job has finished
+    pub fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+
+    /// Wait for the job to finish
+    pub async fn wait(self) -> Result<CommandExecutionResult> {
+        self.task
+            .await
+            .map_err(|e| CommandError::ExecutionError(format!("Job task failed: {}", e)))?
+    }
+}
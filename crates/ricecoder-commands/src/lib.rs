@@ -31,8 +31,12 @@ pub mod config;
 pub mod di;
 pub mod error;
 pub mod executor;
+pub mod jobs;
 pub mod manager;
 pub mod output_injection;
+pub mod pack;
+pub mod pipeline;
+pub mod prompt;
 pub mod registry;
 pub mod template;
 pub mod types;
@@ -40,8 +44,17 @@ pub mod types;
 pub use config::ConfigManager;
 pub use error::{CommandError, Result};
 pub use executor::CommandExecutor;
+pub use jobs::{
+    BackgroundOptions, JobEvent, JobHandle, JobInfo, JobStatus, JobTracker, OutputStream,
+};
 pub use manager::CommandManager;
 pub use output_injection::{OutputFormat, OutputInjectionConfig, OutputInjector};
+pub use pack::{CommandPack, InstalledPack, PackSource, ProjectPackSettings};
+pub use pipeline::{FailurePolicy, PipelineStep, PipelineStepResult, StepCondition, StepStatus};
+pub use prompt::{
+    ArgumentPrompt, ArgumentPromptHandler, BranchCompletionProvider, CompletionProvider,
+    CompletionSource, PathCompletionProvider, PromptKind,
+};
 pub use registry::CommandRegistry;
 pub use template::TemplateProcessor;
 pub use types::{
@@ -1,10 +1,14 @@
-use std::{collections::HashMap, path::Path};
+use std::{collections::HashMap, path::Path, sync::Arc};
 
 use crate::{
     config::ConfigManager,
     error::{CommandError, Result},
+    jobs::{BackgroundJob, BackgroundOptions, JobHandle, JobInfo, JobTracker},
     output_injection::{OutputInjectionConfig, OutputInjector},
+    pipeline::{self, FailurePolicy, PipelineStepResult, PREVIOUS_STEP},
+    prompt::{self, ArgumentPrompt, ArgumentPromptHandler},
     registry::CommandRegistry,
+    template::TemplateProcessor,
     types::{
         ArgumentType, CommandArgument, CommandContext, CommandDefinition, CommandExecutionResult,
     },
@@ -14,14 +18,21 @@ use crate::{
 pub struct CommandManager {
     registry: CommandRegistry,
     output_config: OutputInjectionConfig,
+    prompt_handler: Option<Arc<dyn ArgumentPromptHandler>>,
+    jobs: JobTracker,
 }
 
+/// How many times an invalid answer is re-prompted before giving up
+const MAX_PROMPT_ATTEMPTS: usize = 3;
+
 impl CommandManager {
     /// Create a new command manager
     pub fn new(registry: CommandRegistry) -> Self {
         Self {
             registry,
             output_config: OutputInjectionConfig::default(),
+            prompt_handler: None,
+            jobs: JobTracker::new(),
         }
     }
 
@@ -41,6 +52,33 @@ impl CommandManager {
         &self.output_config
     }
 
+    /// Set the handler used to prompt for missing required arguments
+    pub fn set_prompt_handler(&mut self, handler: Arc<dyn ArgumentPromptHandler>) {
+        self.prompt_handler = Some(handler);
+    }
+
+    /// Get completion candidates for a command argument
+    pub fn complete_argument(
+        &self,
+        command_id: &str,
+        argument: &str,
+        partial: &str,
+        cwd: &str,
+    ) -> Result<Vec<String>> {
+        let command = self.registry.get(command_id)?;
+        let arg = command
+            .arguments
+            .iter()
+            .find(|a| a.name == argument)
+            .ok_or_else(|| {
+                CommandError::InvalidArgument(format!(
+                    "Command '{}' has no argument '{}'",
+                    command_id, argument
+                ))
+            })?;
+        prompt::complete_argument(arg, partial, Path::new(cwd))
+    }
+
     /// Get the registry
     pub fn registry(&self) -> &CommandRegistry {
         &self.registry
@@ -59,15 +97,7 @@ impl CommandManager {
         cwd: String,
     ) -> Result<CommandExecutionResult> {
         let command = self.registry.get(command_id)?;
-
-        // Validate arguments
-        self.validate_arguments(&command, &arguments)?;
-
-        // Build context with defaults
-        let context = self.build_context_with_defaults(&command, arguments, cwd)?;
-
-        // Execute the command
-        self.execute_command(&command, &context)
+        self.execute_definition(&command, arguments, cwd, &mut Vec::new())
     }
 
     /// Execute a command and get injected output
@@ -81,6 +111,59 @@ impl CommandManager {
         OutputInjector::inject(&result, &self.output_config)
     }
 
+    /// Start a command in the background and return a handle to the job
+    ///
+    /// Arguments are prompted for and validated up front; output is then
+    /// streamed as [`JobEvent`](crate::jobs::JobEvent)s on [`Self::jobs`].
+    /// Must be called from within a tokio runtime. Pipelines cannot run in the
+    /// background.
+    pub fn execute_background(
+        &self,
+        command_id: &str,
+        arguments: HashMap<String, String>,
+        cwd: String,
+        options: BackgroundOptions,
+    ) -> Result<JobHandle> {
+        let command = self.registry.get(command_id)?;
+        if command.is_pipeline() {
+            return Err(CommandError::ExecutionError(format!(
+                "Pipeline '{}' cannot run in the background",
+                command_id
+            )));
+        }
+
+        let arguments = self.prompt_missing_arguments(&command, arguments, &cwd)?;
+        self.validate_arguments(&command, &arguments)?;
+        let context = self.build_context_with_defaults(&command, arguments, cwd)?;
+
+        Ok(self.jobs.spawn(BackgroundJob {
+            command_id: command.id.clone(),
+            process: Self::shell_command(&command, &context),
+            timeout: (command.timeout_seconds > 0)
+                .then(|| std::time::Duration::from_secs(command.timeout_seconds)),
+            options: BackgroundOptions {
+                inject_output: options.inject_output || command.inject_output,
+                ..options
+            },
+            output_config: self.output_config.clone(),
+        }))
+    }
+
+    /// Get the background job tracker
+    pub fn jobs(&self) -> &JobTracker {
+        &self.jobs
+    }
+
+    /// List background jobs
+    pub fn list_jobs(&self) -> Vec<JobInfo> {
+        self.jobs.list()
+    }
+
+    /// Cancel a background job
+    pub fn cancel_job(&self, job_id: &str) -> Result<()> {
+        self.jobs.cancel(job_id)
+    }
+
     /// List all commands
     pub fn list_commands(&self) -> Vec<CommandDefinition> {
         self.registry.list_all()
@@ -137,6 +220,55 @@ impl CommandManager {
         Ok(())
     }
 
+    /// Ask the prompt handler for required arguments that have no value or default
+    fn prompt_missing_arguments(
+        &self,
+        command: &CommandDefinition,
+        mut arguments: HashMap<String, String>,
+        cwd: &str,
+    ) -> Result<HashMap<String, String>> {
+        let Some(handler) = &self.prompt_handler else {
+            return Ok(arguments);
+        };
+
+        for arg in &command.arguments {
+            let missing = arguments
+                .get(&arg.name)
+                .map(|v| v.trim().is_empty())
+                .unwrap_or(true);
+            if !arg.required || !missing || arg.default.is_some() {
+                continue;
+            }
+
+            let completions =
+                prompt::complete_argument(arg, "", Path::new(cwd)).unwrap_or_default();
+            let mut request =
+                ArgumentPrompt::for_argument(&command.id, arg).with_completions(completions);
+
+            let mut value = None;
+            for _ in 0..MAX_PROMPT_ATTEMPTS {
+                let answer = handler.prompt(&request)?.ok_or(CommandError::Cancelled)?;
+                match request
+                    .validate(&answer)
+                    .and_then(|_| self.validate_argument_value(arg, &answer))
+                {
+                    Ok(()) => {
+                        value = Some(answer);
+                        break;
+                    }
+                    Err(e) => request.error = Some(e.to_string()),
+                }
+            }
+
+            let value = value.ok_or_else(|| {
+                CommandError::ValidationError(request.error.clone().unwrap_or_default())
+            })?;
+            arguments.insert(arg.name.clone(), value);
+        }
+
+        Ok(arguments)
+    }
+
     /// Validate command arguments
     fn validate_arguments(
         &self,
@@ -246,18 +378,129 @@ impl CommandManager {
         })
     }
 
-    /// Execute a command with the given context
-    fn execute_command(
+    /// Validate arguments and run a command or pipeline
+    fn execute_definition(
+        &self,
+        command: &CommandDefinition,
+        arguments: HashMap<String, String>,
+        cwd: String,
+        pipeline_stack: &mut Vec<String>,
+    ) -> Result<CommandExecutionResult> {
+        // Prompt for missing required arguments
+        let arguments = self.prompt_missing_arguments(command, arguments, &cwd)?;
+
+        // Validate arguments
+        self.validate_arguments(command, &arguments)?;
+
+        // Build context with defaults
+        let context = self.build_context_with_defaults(command, arguments, cwd)?;
+
+        // Execute the command
+        if command.is_pipeline() {
+            self.execute_pipeline(command, &context, pipeline_stack)
+        } else {
+            self.execute_command(command, &context)
+        }
+    }
+
+    /// Execute the steps of a pipeline command, feeding each step's output
+    /// into the template context of the following steps
+    fn execute_pipeline(
         &self,
         command: &CommandDefinition,
         context: &CommandContext,
+        pipeline_stack: &mut Vec<String>,
     ) -> Result<CommandExecutionResult> {
-        use std::{
-            process::{Command, Stdio},
-            time::Instant,
-        };
+        if pipeline_stack.contains(&command.id) {
+            pipeline_stack.push(command.id.clone());
+            return Err(CommandError::ExecutionError(format!(
+                "Pipeline cycle detected: {}",
+                pipeline_stack.join(" -> ")
+            )));
+        }
+        pipeline_stack.push(command.id.clone());
+
+        let start_time = std::time::Instant::now();
+        let mut variables = context.arguments.clone();
+        let mut steps = Vec::with_capacity(command.pipeline.len());
+        let mut stdout = String::new();
+        let mut stderr = String::new();
+        let mut exit_code = 0;
+        let mut any_failed = false;
+        let mut aborted = false;
+
+        for step in &command.pipeline {
+            if aborted {
+                steps.push(PipelineStepResult::skipped(step, "pipeline aborted"));
+                continue;
+            }
+            if !step.condition.is_met(&variables, any_failed) {
+                steps.push(PipelineStepResult::skipped(step, "condition not met"));
+                continue;
+            }
 
-        let start_time = Instant::now();
+            let outcome = step
+                .validate()
+                .and_then(|_| {
+                    step.arguments
+                        .iter()
+                        .map(|(name, value)| {
+                            Ok((name.clone(), TemplateProcessor::process(value, &variables)?))
+                        })
+                        .collect::<Result<HashMap<_, _>>>()
+                })
+                .and_then(|arguments| {
+                    let step_command = self.registry.get(&step.command)?;
+                    self.execute_definition(
+                        &step_command,
+                        arguments,
+                        context.cwd.clone(),
+                        pipeline_stack,
+                    )
+                });
+
+            let (result, step_result) = match outcome {
+                Ok(result) => (result.clone(), PipelineStepResult::executed(step, result)),
+                Err(e) => (
+                    CommandExecutionResult::new(step.command.clone(), -1)
+                        .with_stderr(e.to_string()),
+                    PipelineStepResult::errored(step, &e),
+                ),
+            };
+
+            pipeline::publish_output(&mut variables, &step.id, &result);
+            pipeline::publish_output(&mut variables, PREVIOUS_STEP, &result);
+            stdout.push_str(&result.stdout);
+            stderr.push_str(&result.stderr);
+
+            if !result.success {
+                any_failed = true;
+                exit_code = if result.exit_code == 0 {
+                    -1
+                } else {
+                    result.exit_code
+                };
+                aborted = step.on_failure == FailurePolicy::Abort;
+            }
+            steps.push(step_result);
+        }
+
+        pipeline_stack.pop();
+
+        let mut result = CommandExecutionResult::new(command.id.clone(), exit_code)
+            .with_stdout(stdout)
+            .with_stderr(stderr)
+            .with_duration(start_time.elapsed().as_millis() as u64);
+        result.steps = steps;
+        Ok(result)
+    }
+
+    /// Build the shell process for a command with the given context
+    fn shell_command(
+        command: &CommandDefinition,
+        context: &CommandContext,
+    ) -> std::process::Command {
+        use std::process::Command;
 
         // Substitute arguments in the command template
         let mut command_str = command.command.clone();
@@ -266,7 +509,6 @@ impl CommandManager {
             command_str = command_str.replace(&placeholder, value);
         }
 
-        // Execute the command
         let mut cmd = if cfg!(target_os = "windows") {
             let mut c = Command::new("cmd");
             c.args(["/C", &command_str]);
@@ -277,11 +519,23 @@ impl CommandManager {
             c
         };
 
-        cmd.current_dir(&context.cwd)
-            .env_clear()
-            .envs(&context.env)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped());
+        cmd.current_dir(&context.cwd).env_clear().envs(&context.env);
+        cmd
+    }
+
+    /// Execute a command with the given context
+    fn execute_command(
+        &self,
+        command: &CommandDefinition,
+        context: &CommandContext,
+    ) -> Result<CommandExecutionResult> {
+        use std::{process::Stdio, time::Instant};
+
+        let start_time = Instant::now();
+
+        // Execute the command
+        let mut cmd = Self::shell_command(command, context);
+        cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
 
         let output = cmd.output().map_err(|e| {
             CommandError::ExecutionError(format!("Failed to execute command: {}", e))
@@ -301,6 +555,7 @@ impl CommandManager {
             stderr,
             success,
             duration_ms: duration,
+            steps: Vec::new(),
         })
     }
 }
@@ -0,0 +1,393 @@
+//! Namespaced command packs
+//!
+//! A command pack bundles commands under a namespace so they can be shared
+//! between projects. Installed commands are registered as `namespace:id`
+//! (e.g. `deploy:staging`). A pack lives in a directory containing a
+//! `pack.yaml` (or `pack.json`) manifest, optionally with one command per
+//! file in a `commands/` subdirectory, and can also be fetched from a git URL.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{CommandError, Result},
+    types::CommandDefinition,
+};
+
+/// Separator between a pack namespace and a command ID
+pub const NAMESPACE_SEPARATOR: char = ':';
+
+/// Manifest file names looked up in a pack directory
+const MANIFEST_FILES: [&str; 3] = ["pack.yaml", "pack.yml", "pack.json"];
+
+/// A bundle of commands under a namespace
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandPack {
+    /// Namespace the commands are registered under
+    pub namespace: String,
+
+    /// Pack version
+    pub version: String,
+
+    /// Description of the pack
+    #[serde(default)]
+    pub description: String,
+
+    /// Pack author
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+
+    /// Commands with pack-local IDs
+    #[serde(default)]
+    pub commands: Vec<CommandDefinition>,
+}
+
+/// Where an installed pack came from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PackSource {
+    /// Built in memory
+    Local,
+    /// Loaded from a directory
+    Directory(PathBuf),
+    /// Cloned from a git URL
+    Git {
+        url: String,
+        reference: Option<String>,
+    },
+}
+
+/// Metadata about an installed pack
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstalledPack {
+    /// Pack namespace
+    pub namespace: String,
+    /// Pack version
+    pub version: String,
+    /// Pack description
+    pub description: String,
+    /// Where the pack came from
+    pub source: PackSource,
+    /// Namespaced IDs of the registered commands
+    pub command_ids: Vec<String>,
+    /// Whether the pack's commands are enabled
+    pub enabled: bool,
+}
+
+/// Per-project pack enablement, stored in the project's `.ricecoder` directory
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProjectPackSettings {
+    /// Enabled state by pack namespace (packs not listed keep their state)
+    #[serde(default)]
+    pub packs: BTreeMap<String, bool>,
+}
+
+impl CommandPack {
+    /// Create an empty pack
+    pub fn new(namespace: impl Into<String>, version: impl Into<String>) -> Self {
+        Self {
+            namespace: namespace.into(),
+            version: version.into(),
+            description: String::new(),
+            author: None,
+            commands: Vec::new(),
+        }
+    }
+
+    /// Set the description
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    /// Add a command
+    pub fn with_command(mut self, command: CommandDefinition) -> Self {
+        self.commands.push(command);
+        self
+    }
+
+    /// Load a pack from a directory
+    pub fn load_from_directory<P: AsRef<Path>>(dir: P) -> Result<Self> {
+        let dir = dir.as_ref();
+        let manifest = MANIFEST_FILES
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file())
+            .ok_or_else(|| {
+                CommandError::ConfigError(format!("No pack manifest found in {}", dir.display()))
+            })?;
+
+        let mut pack: CommandPack = read_file(&manifest)?;
+
+        let commands_dir = dir.join("commands");
+        if commands_dir.is_dir() {
+            let mut paths: Vec<PathBuf> = fs::read_dir(&commands_dir)?
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    matches!(
+                        path.extension().and_then(|ext| ext.to_str()),
+                        Some("yaml" | "yml" | "json")
+                    )
+                })
+                .collect();
+            paths.sort();
+            for path in paths {
+                pack.commands.push(read_file(&path)?);
+            }
+        }
+
+        pack.validate()?;
+        Ok(pack)
+    }
+
+    /// Clone a pack from a git URL into `cache_dir` and load it
+    ///
+    /// An existing checkout is updated in place.
+    pub fn load_from_git(url: &str, reference: Option<&str>, cache_dir: &Path) -> Result<Self> {
+        let checkout = cache_dir.join(checkout_name(url));
+        if checkout.join(".git").is_dir() {
+            run_git(
+                &checkout,
+                &[
+                    "fetch",
+                    "--depth",
+                    "1",
+                    "origin",
+                    reference.unwrap_or("HEAD"),
+                ],
+            )?;
+            run_git(&checkout, &["checkout", "--force", "FETCH_HEAD"])?;
+        } else {
+            fs::create_dir_all(cache_dir)?;
+            let target = checkout.to_string_lossy().to_string();
+            let mut args = vec!["clone", "--depth", "1"];
+            if let Some(reference) = reference {
+                args.extend(["--branch", reference]);
+            }
+            args.extend([url, target.as_str()]);
+            run_git(cache_dir, &args)?;
+        }
+        Self::load_from_directory(&checkout)
+    }
+
+    /// Write the pack as a `pack.yaml` manifest in a directory
+    pub fn save_to_directory<P: AsRef<Path>>(&self, dir: P) -> Result<PathBuf> {
+        self.validate()?;
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        let path = dir.join(MANIFEST_FILES[0]);
+        fs::write(&path, serde_yaml::to_string(self)?)?;
+        Ok(path)
+    }
+
+    /// Check the namespace, version and command IDs
+    pub fn validate(&self) -> Result<()> {
+        let valid_namespace = !self.namespace.is_empty()
+            && self
+                .namespace
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid_namespace {
+            return Err(CommandError::InvalidCommandName(format!(
+                "Invalid pack namespace: {}",
+                self.namespace
+            )));
+        }
+        if self.version.trim().is_empty() {
+            return Err(CommandError::ConfigError(format!(
+                "Pack '{}' has no version",
+                self.namespace
+            )));
+        }
+
+        let mut seen = Vec::with_capacity(self.commands.len());
+        for command in &self.commands {
+            if command.id.is_empty() || command.id.contains(NAMESPACE_SEPARATOR) {
+                return Err(CommandError::InvalidCommandName(format!(
+                    "Invalid command ID in pack '{}': {}",
+                    self.namespace, command.id
+                )));
+            }
+            if seen.contains(&&command.id) {
+                return Err(CommandError::InvalidCommandName(format!(
+                    "Duplicate command in pack '{}': {}",
+                    self.namespace, command.id
+                )));
+            }
+            seen.push(&command.id);
+        }
+        Ok(())
+    }
+
+    /// Namespaced ID of a pack-local command ID
+    pub fn qualified_id(&self, command_id: &str) -> String {
+        format!("{}{}{}", self.namespace, NAMESPACE_SEPARATOR, command_id)
+    }
+
+    /// Commands with namespaced IDs, ready for registration
+    ///
+    /// Pipeline steps that refer to commands of the same pack are rewritten
+    /// to the namespaced IDs.
+    pub fn namespaced_commands(&self) -> Vec<CommandDefinition> {
+        let local: HashMap<&str, String> = self
+            .commands
+            .iter()
+            .map(|c| (c.id.as_str(), self.qualified_id(&c.id)))
+            .collect();
+
+        self.commands
+            .iter()
+            .map(|command| {
+                let mut command = command.clone();
+                command.id = local[command.id.as_str()].clone();
+                for step in &mut command.pipeline {
+                    if let Some(qualified) = local.get(step.command.as_str()) {
+                        step.command = qualified.clone();
+                    }
+                }
+                command
+            })
+            .collect()
+    }
+}
+
+impl ProjectPackSettings {
+    /// Location of the settings file within a project
+    pub fn path(project_root: &Path) -> PathBuf {
+        project_root.join(".ricecoder").join("packs.yaml")
+    }
+
+    /// Load the settings for a project (empty if none are saved)
+    pub fn load(project_root: &Path) -> Result<Self> {
+        let path = Self::path(project_root);
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+        read_file(&path)
+    }
+
+    /// Save the settings for a project
+    pub fn save(&self, project_root: &Path) -> Result<()> {
+        let path = Self::path(project_root);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_yaml::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Record whether a pack is enabled
+    pub fn set_enabled(&mut self, namespace: impl Into<String>, enabled: bool) {
+        self.packs.insert(namespace.into(), enabled);
+    }
+}
+
+fn read_file<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T> {
+    let content = fs::read_to_string(path).map_err(|e| {
+        CommandError::ConfigError(format!("Failed to read {}: {}", path.display(), e))
+    })?;
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("json") => Ok(serde_json::from_str(&content)?),
+        _ => Ok(serde_yaml::from_str(&content)?),
+    }
+}
+
+fn checkout_name(url: &str) -> String {
+    url.trim_end_matches('/')
+        .trim_end_matches(".git")
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+fn run_git(cwd: &Path, args: &[&str]) -> Result<()> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(cwd)
+        .output()
+        .map_err(|e| CommandError::ExecutionError(format!("Failed to run git: {}", e)))?;
+    if !output.status.success() {
+        return Err(CommandError::ExecutionError(format!(
+            "git {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::PipelineStep;
+
+    #[test]
+    fn test_load_pack_from_directory() {
+        let temp = tempfile::tempdir().unwrap();
+        fs::write(
+            temp.path().join("pack.yaml"),
+            "namespace: deploy\nversion: 1.2.0\ncommands:\n  - id: staging\n    name: Staging\n    description: ''\n    command: echo staging\n    arguments: []\n    enabled: true\n    inject_output: false\n    timeout_seconds: 0\n    tags: []\n    metadata: {}\n",
+        )
+        .unwrap();
+        fs::create_dir(temp.path().join("commands")).unwrap();
+        let extra = CommandDefinition::new("prod", "Production", "echo prod");
+        fs::write(
+            temp.path().join("commands/prod.json"),
+            serde_json::to_string(&extra).unwrap(),
+        )
+        .unwrap();
+
+        let pack = CommandPack::load_from_directory(temp.path()).unwrap();
+        assert_eq!(pack.namespace, "deploy");
+        assert_eq!(pack.version, "1.2.0");
+        let ids: Vec<&str> = pack.commands.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["staging", "prod"]);
+    }
+
+    #[test]
+    fn test_namespaced_commands_rewrite_pipeline_steps() {
+        let pack = CommandPack::new("deploy", "1.0.0")
+            .with_command(CommandDefinition::new("build", "Build", "make"))
+            .with_command(
+                CommandDefinition::new("ship", "Ship", "")
+                    .with_step(PipelineStep::new("build", "build"))
+                    .with_step(PipelineStep::new("notify", "notify")),
+            );
+
+        let commands = pack.namespaced_commands();
+        assert_eq!(commands[0].id, "deploy:build");
+        assert_eq!(commands[1].pipeline[0].command, "deploy:build");
+        assert_eq!(commands[1].pipeline[1].command, "notify");
+    }
+
+    #[test]
+    fn test_pack_validation() {
+        assert!(CommandPack::new("deploy", "1.0.0").validate().is_ok());
+        assert!(CommandPack::new("de ploy", "1.0.0").validate().is_err());
+        assert!(CommandPack::new("deploy", "").validate().is_err());
+        assert!(CommandPack::new("deploy", "1.0.0")
+            .with_command(CommandDefinition::new("a:b", "A", "true"))
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_project_settings_roundtrip() {
+        let temp = tempfile::tempdir().unwrap();
+        let mut settings = ProjectPackSettings::load(temp.path()).unwrap();
+        assert!(settings.packs.is_empty());
+
+        settings.set_enabled("deploy", false);
+        settings.save(temp.path()).unwrap();
+
+        let loaded = ProjectPackSettings::load(temp.path()).unwrap();
+        assert_eq!(loaded.packs.get("deploy"), Some(&false));
+    }
+}
@@ -0,0 +1,278 @@
+//! Multi-step command pipelines
+//!
+//! A [`CommandDefinition`](crate::CommandDefinition) with a non-empty
+//! `pipeline` runs each [`PipelineStep`] in order instead of its own shell
+//! command. After a step runs, its output is published to the template
+//! context of later steps as `{{<step>_stdout}}`, `{{<step>_stderr}}`,
+//! `{{<step>_exit_code}}` and `{{<step>_success}}`, and the most recent step
+//! is also available as `{{previous_stdout}}` and friends.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{CommandError, Result},
+    types::CommandExecutionResult,
+};
+
+/// Variable prefix under which the most recently executed step is published
+pub const PREVIOUS_STEP: &str = "previous";
+
+/// What to do when a pipeline step fails
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FailurePolicy {
+    /// Stop the pipeline; remaining steps are skipped
+    #[default]
+    Abort,
+    /// Record the failure and keep running later steps
+    Continue,
+}
+
+/// Condition that decides whether a pipeline step runs
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StepCondition {
+    /// Always run the step
+    #[default]
+    Always,
+    /// Run only if every step so far succeeded
+    OnSuccess,
+    /// Run only if an earlier step failed
+    OnFailure,
+    /// Run only if the variable is set and non-empty
+    IfSet(String),
+    /// Run only if the variable equals the value (after trimming)
+    IfEquals { variable: String, value: String },
+}
+
+/// A single step of a command pipeline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineStep {
+    /// Step identifier, used as the prefix of its output variables
+    pub id: String,
+
+    /// ID of the registered command to run
+    pub command: String,
+
+    /// Arguments passed to the command (support templates)
+    #[serde(default)]
+    pub arguments: HashMap<String, String>,
+
+    /// Condition under which the step runs
+    #[serde(default)]
+    pub condition: StepCondition,
+
+    /// What to do when the step fails
+    #[serde(default)]
+    pub on_failure: FailurePolicy,
+}
+
+/// Outcome of a pipeline step
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StepStatus {
+    Succeeded,
+    Failed,
+    Skipped,
+}
+
+/// Result of a single pipeline step
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineStepResult {
+    /// Step identifier
+    pub step_id: String,
+
+    /// ID of the command the step ran
+    pub command_id: String,
+
+    /// Step outcome
+    pub status: StepStatus,
+
+    /// Execution result (None if the step was skipped)
+    pub result: Option<CommandExecutionResult>,
+
+    /// Why the step was skipped or could not run
+    pub message: Option<String>,
+}
+
+impl PipelineStep {
+    /// Create a new pipeline step running the given command
+    pub fn new(id: impl Into<String>, command: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            command: command.into(),
+            arguments: HashMap::new(),
+            condition: StepCondition::Always,
+            on_failure: FailurePolicy::Abort,
+        }
+    }
+
+    /// Add an argument
+    pub fn with_argument(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.arguments.insert(name.into(), value.into());
+        self
+    }
+
+    /// Set the condition
+    pub fn with_condition(mut self, condition: StepCondition) -> Self {
+        self.condition = condition;
+        self
+    }
+
+    /// Set the failure policy
+    pub fn with_on_failure(mut self, policy: FailurePolicy) -> Self {
+        self.on_failure = policy;
+        self
+    }
+
+    /// Check that the step ID can be used as a template variable prefix
+    pub fn validate(&self) -> Result<()> {
+        let mut chars = self.id.chars();
+        let valid = matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid || self.id == PREVIOUS_STEP {
+            return Err(CommandError::InvalidArgument(format!(
+                "Invalid pipeline step ID: {}",
+                self.id
+            )));
+        }
+        if self.command.is_empty() {
+            return Err(CommandError::InvalidArgument(format!(
+                "Pipeline step '{}' has no command",
+                self.id
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl StepCondition {
+    /// Evaluate the condition against the pipeline state
+    pub fn is_met(&self, variables: &HashMap<String, String>, any_failed: bool) -> bool {
+        match self {
+            StepCondition::Always => true,
+            StepCondition::OnSuccess => !any_failed,
+            StepCondition::OnFailure => any_failed,
+            StepCondition::IfSet(variable) => variables
+                .get(variable)
+                .map(|v| !v.trim().is_empty())
+                .unwrap_or(false),
+            StepCondition::IfEquals { variable, value } => variables
+                .get(variable)
+                .map(|v| v.trim() == value.trim())
+                .unwrap_or(false),
+        }
+    }
+}
+
+impl PipelineStepResult {
+    /// Create a result for a step that ran
+    pub fn executed(step: &PipelineStep, result: CommandExecutionResult) -> Self {
+        Self {
+            step_id: step.id.clone(),
+            command_id: step.command.clone(),
+            status: if result.success {
+                StepStatus::Succeeded
+            } else {
+                StepStatus::Failed
+            },
+            result: Some(result),
+            message: None,
+        }
+    }
+
+    /// Create a result for a step that did not run
+    pub fn skipped(step: &PipelineStep, message: impl Into<String>) -> Self {
+        Self {
+            step_id: step.id.clone(),
+            command_id: step.command.clone(),
+            status: StepStatus::Skipped,
+            result: None,
+            message: Some(message.into()),
+        }
+    }
+
+    /// Create a result for a step that could not be started
+    pub fn errored(step: &PipelineStep, error: &CommandError) -> Self {
+        Self {
+            step_id: step.id.clone(),
+            command_id: step.command.clone(),
+            status: StepStatus::Failed,
+            result: None,
+            message: Some(error.to_string()),
+        }
+    }
+}
+
+/// Publish a step's output as template variables under the given prefix
+pub(crate) fn publish_output(
+    variables: &mut HashMap<String, String>,
+    prefix: &str,
+    result: &CommandExecutionResult,
+) {
+    variables.insert(
+        format!("{prefix}_stdout"),
+        result.stdout.trim_end().to_string(),
+    );
+    variables.insert(
+        format!("{prefix}_stderr"),
+        result.stderr.trim_end().to_string(),
+    );
+    variables.insert(format!("{prefix}_exit_code"), result.exit_code.to_string());
+    variables.insert(format!("{prefix}_success"), result.success.to_string());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_step_validation() {
+        assert!(PipelineStep::new("build", "cargo-build").validate().is_ok());
+        assert!(PipelineStep::new("build-step", "cargo-build")
+            .validate()
+            .is_err());
+        assert!(PipelineStep::new("previous", "cargo-build")
+            .validate()
+            .is_err());
+        assert!(PipelineStep::new("build", "").validate().is_err());
+    }
+
+    #[test]
+    fn test_condition_evaluation() {
+        let mut vars = HashMap::new();
+        vars.insert("lint_success".to_string(), "true".to_string());
+        vars.insert("empty".to_string(), "  ".to_string());
+
+        assert!(StepCondition::Always.is_met(&vars, true));
+        assert!(StepCondition::OnSuccess.is_met(&vars, false));
+        assert!(!StepCondition::OnSuccess.is_met(&vars, true));
+        assert!(StepCondition::OnFailure.is_met(&vars, true));
+        assert!(StepCondition::IfSet("lint_success".to_string()).is_met(&vars, false));
+        assert!(!StepCondition::IfSet("empty".to_string()).is_met(&vars, false));
+        assert!(StepCondition::IfEquals {
+            variable: "lint_success".to_string(),
+            value: "true".to_string(),
+        }
+        .is_met(&vars, false));
+    }
+
+    #[test]
+    fn test_step_deserializes_with_defaults() {
+        let step: PipelineStep = serde_yaml::from_str("id: build\ncommand: cargo-build\n").unwrap();
+        assert_eq!(step.condition, StepCondition::Always);
+        assert_eq!(step.on_failure, FailurePolicy::Abort);
+
+        let step: PipelineStep = serde_yaml::from_str(
+            "id: notify\ncommand: notify\non_failure: continue\ncondition: !if_set build_stdout\n",
+        )
+        .unwrap();
+        assert_eq!(step.on_failure, FailurePolicy::Continue);
+        assert_eq!(
+            step.condition,
+            StepCondition::IfSet("build_stdout".to_string())
+        );
+    }
+}
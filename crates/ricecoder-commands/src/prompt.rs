@@ -0,0 +1,378 @@
+//! Interactive argument prompting and completion
+//!
+//! When a command is executed without one of its required arguments, the
+//! [`CommandManager`](crate::CommandManager) builds an [`ArgumentPrompt`]
+//! describing what is needed (free text, a choice list, a path, ...) and hands
+//! it to an [`ArgumentPromptHandler`], typically implemented by the TUI.
+//! Answers are validated before use and the prompt is repeated with the
+//! validation error until a valid value is given or the user cancels.
+//!
+//! Arguments can declare a [`CompletionSource`] so prompts (and shell-style
+//! completion) can offer candidates: file paths are listed through the
+//! ricecoder-files gitignore filter and branch names come from ricecoder-vcs.
+
+use std::{
+    fs,
+    path::{Path, MAIN_SEPARATOR},
+};
+
+use regex::Regex;
+use ricecoder_files::GitignoreFilter;
+use ricecoder_vcs::{GitRepository, RepositoryQuery};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{CommandError, Result},
+    types::{ArgumentType, CommandArgument},
+};
+
+/// Maximum number of completion candidates returned
+pub const MAX_COMPLETIONS: usize = 50;
+
+/// Where completion candidates for an argument come from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompletionSource {
+    /// Files and directories relative to the working directory
+    FilePaths,
+    /// Directories relative to the working directory
+    Directories,
+    /// Branch names of the enclosing Git repository
+    GitBranches,
+    /// A fixed list of values
+    Values(Vec<String>),
+}
+
+/// The kind of input a prompt asks for
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PromptKind {
+    /// Free text, optionally constrained by a regex
+    Text { pattern: Option<String> },
+    /// A number
+    Number,
+    /// A yes/no answer
+    Confirm,
+    /// One of a fixed list of options
+    Choice { options: Vec<String> },
+    /// A filesystem path
+    Path { directories_only: bool },
+}
+
+/// A structured request for a missing argument value
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArgumentPrompt {
+    /// Command the argument belongs to
+    pub command_id: String,
+    /// Argument name
+    pub argument: String,
+    /// Message to show the user
+    pub message: String,
+    /// Kind of input expected
+    pub kind: PromptKind,
+    /// Suggested value
+    pub default: Option<String>,
+    /// Completion candidates for the empty input
+    pub completions: Vec<String>,
+    /// Validation error from the previous attempt
+    pub error: Option<String>,
+}
+
+/// Renders argument prompts and collects answers
+pub trait ArgumentPromptHandler: Send + Sync {
+    /// Ask the user for a value; `None` cancels the command
+    fn prompt(&self, prompt: &ArgumentPrompt) -> Result<Option<String>>;
+}
+
+/// Produces completion candidates for a partially typed value
+pub trait CompletionProvider: Send + Sync {
+    /// Return candidates starting with `partial`
+    fn complete(&self, partial: &str, cwd: &Path) -> Result<Vec<String>>;
+}
+
+/// Completes file and directory paths, skipping gitignored entries
+#[derive(Debug, Clone, Default)]
+pub struct PathCompletionProvider {
+    directories_only: bool,
+}
+
+/// Completes local branch names of the repository containing `cwd`
+#[derive(Debug, Clone, Default)]
+pub struct BranchCompletionProvider;
+
+impl PathCompletionProvider {
+    /// Create a provider completing files and directories
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a provider completing directories only
+    pub fn directories() -> Self {
+        Self {
+            directories_only: true,
+        }
+    }
+}
+
+impl CompletionProvider for PathCompletionProvider {
+    fn complete(&self, partial: &str, cwd: &Path) -> Result<Vec<String>> {
+        let (dir_part, file_part) = match partial.rfind(['/', MAIN_SEPARATOR]) {
+            Some(pos) => partial.split_at(pos + 1),
+            None => ("", partial),
+        };
+        let dir = cwd.join(dir_part);
+        if !dir.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let filter = GitignoreFilter::load_from_directory(cwd)
+            .map_err(|e| CommandError::Other(e.to_string()))?;
+
+        let mut candidates = Vec::new();
+        for entry in fs::read_dir(&dir)?.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !name.starts_with(file_part)
+                || (name.starts_with('.') && !file_part.starts_with('.'))
+            {
+                continue;
+            }
+            let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+            if (self.directories_only && !is_dir) || filter.should_ignore(&entry.path(), is_dir) {
+                continue;
+            }
+            let suffix = if is_dir { "/" } else { "" };
+            candidates.push(format!("{dir_part}{name}{suffix}"));
+        }
+
+        candidates.sort();
+        candidates.truncate(MAX_COMPLETIONS);
+        Ok(candidates)
+    }
+}
+
+impl CompletionProvider for BranchCompletionProvider {
+    fn complete(&self, partial: &str, cwd: &Path) -> Result<Vec<String>> {
+        let Ok(repo) = GitRepository::discover(cwd) else {
+            return Ok(Vec::new());
+        };
+        let mut branches: Vec<String> = repo
+            .get_branches()
+            .map_err(|e| CommandError::Other(e.to_string()))?
+            .into_iter()
+            .filter(|b| !b.is_remote && b.name.starts_with(partial))
+            .map(|b| b.name)
+            .collect();
+        branches.sort();
+        branches.truncate(MAX_COMPLETIONS);
+        Ok(branches)
+    }
+}
+
+impl CompletionSource {
+    /// Provider for this source
+    pub fn provider(&self) -> Box<dyn CompletionProvider> {
+        match self {
+            CompletionSource::FilePaths => Box::new(PathCompletionProvider::new()),
+            CompletionSource::Directories => Box::new(PathCompletionProvider::directories()),
+            CompletionSource::GitBranches => Box::new(BranchCompletionProvider),
+            CompletionSource::Values(values) => Box::new(StaticCompletionProvider(values.clone())),
+        }
+    }
+}
+
+struct StaticCompletionProvider(Vec<String>);
+
+impl CompletionProvider for StaticCompletionProvider {
+    fn complete(&self, partial: &str, _cwd: &Path) -> Result<Vec<String>> {
+        Ok(self
+            .0
+            .iter()
+            .filter(|v| v.starts_with(partial))
+            .take(MAX_COMPLETIONS)
+            .cloned()
+            .collect())
+    }
+}
+
+/// Complete a value for an argument
+///
+/// Uses the argument's declared completion source, falling back to the
+/// options of choice arguments and to path completion for path arguments.
+pub fn complete_argument(arg: &CommandArgument, partial: &str, cwd: &Path) -> Result<Vec<String>> {
+    let source = match (&arg.completion, &arg.arg_type) {
+        (Some(source), _) => source.clone(),
+        (None, ArgumentType::Choice(options)) => CompletionSource::Values(options.clone()),
+        (None, ArgumentType::Path) => CompletionSource::FilePaths,
+        (None, ArgumentType::Boolean) => {
+            CompletionSource::Values(vec!["true".to_string(), "false".to_string()])
+        }
+        _ => return Ok(Vec::new()),
+    };
+    source.provider().complete(partial, cwd)
+}
+
+impl ArgumentPrompt {
+    /// Build the prompt for a command argument
+    pub fn for_argument(command_id: &str, arg: &CommandArgument) -> Self {
+        let kind = match &arg.arg_type {
+            ArgumentType::String => match &arg.completion {
+                Some(CompletionSource::FilePaths) => PromptKind::Path {
+                    directories_only: false,
+                },
+                Some(CompletionSource::Directories) => PromptKind::Path {
+                    directories_only: true,
+                },
+                _ => PromptKind::Text {
+                    pattern: arg.validation_pattern.clone(),
+                },
+            },
+            ArgumentType::Number => PromptKind::Number,
+            ArgumentType::Boolean => PromptKind::Confirm,
+            ArgumentType::Path => PromptKind::Path {
+                directories_only: arg.completion == Some(CompletionSource::Directories),
+            },
+            ArgumentType::Choice(options) => PromptKind::Choice {
+                options: options.clone(),
+            },
+        };
+        let message = if arg.description.is_empty() {
+            format!("Enter a value for '{}'", arg.name)
+        } else {
+            arg.description.clone()
+        };
+
+        Self {
+            command_id: command_id.to_string(),
+            argument: arg.name.clone(),
+            message,
+            kind,
+            default: arg.default.clone(),
+            completions: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// Attach completion candidates
+    pub fn with_completions(mut self, completions: Vec<String>) -> Self {
+        self.completions = completions;
+        self
+    }
+
+    /// Validate an answer against the prompt kind
+    pub fn validate(&self, value: &str) -> Result<()> {
+        let invalid = |reason: String| Err(CommandError::ValidationError(reason));
+        if value.trim().is_empty() {
+            return invalid(format!("Argument '{}' cannot be empty", self.argument));
+        }
+        match &self.kind {
+            PromptKind::Text {
+                pattern: Some(pattern),
+            } => {
+                if !Regex::new(pattern)?.is_match(value) {
+                    return invalid(format!(
+                        "Argument '{}' does not match pattern {}",
+                        self.argument, pattern
+                    ));
+                }
+            }
+            PromptKind::Number if value.parse::<f64>().is_err() => {
+                return invalid(format!("Argument '{}' must be a number", self.argument));
+            }
+            PromptKind::Confirm
+                if !matches!(
+                    value.to_lowercase().as_str(),
+                    "true" | "false" | "1" | "0" | "yes" | "no"
+                ) =>
+            {
+                return invalid(format!("Argument '{}' must be yes or no", self.argument));
+            }
+            PromptKind::Choice { options } if !options.iter().any(|o| o == value) => {
+                return invalid(format!(
+                    "Argument '{}' must be one of: {}",
+                    self.argument,
+                    options.join(", ")
+                ));
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prompt_kind_from_argument() {
+        let choice = CommandArgument::new(
+            "env",
+            ArgumentType::Choice(vec!["dev".to_string(), "prod".to_string()]),
+        );
+        let prompt = ArgumentPrompt::for_argument("deploy", &choice);
+        assert_eq!(
+            prompt.kind,
+            PromptKind::Choice {
+                options: vec!["dev".to_string(), "prod".to_string()]
+            }
+        );
+        assert!(prompt.validate("prod").is_ok());
+        assert!(prompt.validate("staging").is_err());
+
+        let text = CommandArgument::new("ticket", ArgumentType::String)
+            .with_validation_pattern(r"^[A-Z]+-\d+$");
+        let prompt = ArgumentPrompt::for_argument("deploy", &text);
+        assert!(prompt.validate("RC-42").is_ok());
+        assert!(prompt.validate("rc42").is_err());
+
+        let dir = CommandArgument::new("out", ArgumentType::Path)
+            .with_completion(CompletionSource::Directories);
+        let prompt = ArgumentPrompt::for_argument("deploy", &dir);
+        assert_eq!(
+            prompt.kind,
+            PromptKind::Path {
+                directories_only: true
+            }
+        );
+    }
+
+    #[test]
+    fn test_path_completion() {
+        let temp = tempfile::tempdir().unwrap();
+        fs::create_dir(temp.path().join("src")).unwrap();
+        fs::write(temp.path().join("src/main.rs"), "").unwrap();
+        fs::write(temp.path().join("setup.sh"), "").unwrap();
+        fs::write(temp.path().join("secret.log"), "").unwrap();
+        fs::write(temp.path().join(".gitignore"), "*.log\n").unwrap();
+
+        let files = PathCompletionProvider::new()
+            .complete("s", temp.path())
+            .unwrap();
+        assert_eq!(files, vec!["setup.sh".to_string(), "src/".to_string()]);
+
+        let nested = PathCompletionProvider::new()
+            .complete("src/m", temp.path())
+            .unwrap();
+        assert_eq!(nested, vec!["src/main.rs".to_string()]);
+
+        let dirs = PathCompletionProvider::directories()
+            .complete("", temp.path())
+            .unwrap();
+        assert_eq!(dirs, vec!["src/".to_string()]);
+    }
+
+    #[test]
+    fn test_complete_argument_falls_back_to_type() {
+        let arg = CommandArgument::new(
+            "level",
+            ArgumentType::Choice(vec!["debug".to_string(), "info".to_string()]),
+        );
+        let values = complete_argument(&arg, "d", Path::new(".")).unwrap();
+        assert_eq!(values, vec!["debug".to_string()]);
+
+        let plain = CommandArgument::new("name", ArgumentType::String);
+        assert!(complete_argument(&plain, "", Path::new("."))
+            .unwrap()
+            .is_empty());
+    }
+}
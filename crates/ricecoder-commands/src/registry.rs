@@ -2,6 +2,7 @@ use std::collections::HashMap;
 
 use crate::{
     error::{CommandError, Result},
+    pack::{CommandPack, InstalledPack, PackSource, ProjectPackSettings},
     types::CommandDefinition,
 };
 
@@ -9,6 +10,7 @@ use crate::{
 #[derive(Debug, Clone)]
 pub struct CommandRegistry {
     commands: HashMap<String, CommandDefinition>,
+    packs: HashMap<String, InstalledPack>,
 }
 
 impl CommandRegistry {
@@ -16,6 +18,7 @@ impl CommandRegistry {
     pub fn new() -> Self {
         Self {
             commands: HashMap::new(),
+            packs: HashMap::new(),
         }
     }
 
@@ -123,6 +126,140 @@ impl CommandRegistry {
     /// Clear all commands
     pub fn clear(&mut self) {
         self.commands.clear();
+        self.packs.clear();
+    }
+}
+
+impl CommandRegistry {
+    /// Install a command pack, registering its commands as `namespace:id`
+    ///
+    /// Fails without registering anything if the namespace is already
+    /// installed or any namespaced ID clashes with an existing command.
+    pub fn install_pack(&mut self, pack: CommandPack, source: PackSource) -> Result<()> {
+        pack.validate()?;
+        if self.packs.contains_key(&pack.namespace) {
+            return Err(CommandError::PackConflict(format!(
+                "Pack already installed: {}",
+                pack.namespace
+            )));
+        }
+
+        let commands = pack.namespaced_commands();
+        let conflicts: Vec<&str> = commands
+            .iter()
+            .filter(|c| self.commands.contains_key(&c.id))
+            .map(|c| c.id.as_str())
+            .collect();
+        if !conflicts.is_empty() {
+            return Err(CommandError::PackConflict(format!(
+                "Commands already registered: {}",
+                conflicts.join(", ")
+            )));
+        }
+
+        let command_ids = commands.iter().map(|c| c.id.clone()).collect();
+        for command in commands {
+            self.commands.insert(command.id.clone(), command);
+        }
+        self.packs.insert(
+            pack.namespace.clone(),
+            InstalledPack {
+                namespace: pack.namespace,
+                version: pack.version,
+                description: pack.description,
+                source,
+                command_ids,
+                enabled: true,
+            },
+        );
+        Ok(())
+    }
+
+    /// Uninstall a pack and remove its commands
+    pub fn uninstall_pack(&mut self, namespace: &str) -> Result<()> {
+        let pack = self
+            .packs
+            .remove(namespace)
+            .ok_or_else(|| CommandError::CommandNotFound(format!("pack {}", namespace)))?;
+        for id in &pack.command_ids {
+            self.commands.remove(id);
+        }
+        Ok(())
+    }
+
+    /// Get an installed pack
+    pub fn get_pack(&self, namespace: &str) -> Option<&InstalledPack> {
+        self.packs.get(namespace)
+    }
+
+    /// List installed packs
+    pub fn list_packs(&self) -> Vec<InstalledPack> {
+        let mut packs: Vec<InstalledPack> = self.packs.values().cloned().collect();
+        packs.sort_by(|a, b| a.namespace.cmp(&b.namespace));
+        packs
+    }
+
+    /// Enable or disable all commands of a pack
+    pub fn set_pack_enabled(&mut self, namespace: &str, enabled: bool) -> Result<()> {
+        let pack = self
+            .packs
+            .get_mut(namespace)
+            .ok_or_else(|| CommandError::CommandNotFound(format!("pack {}", namespace)))?;
+        pack.enabled = enabled;
+        for id in &pack.command_ids {
+            if let Some(command) = self.commands.get_mut(id) {
+                command.enabled = enabled;
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply a project's pack settings, ignoring packs that are not installed
+    pub fn apply_project_settings(&mut self, settings: &ProjectPackSettings) {
+        for (namespace, enabled) in &settings.packs {
+            let _ = self.set_pack_enabled(namespace, *enabled);
+        }
+    }
+
+    /// Export commands that do not belong to a pack into a new pack
+    ///
+    /// If `command_ids` is empty, all local commands are exported.
+    pub fn export_pack(
+        &self,
+        namespace: &str,
+        version: &str,
+        command_ids: &[&str],
+    ) -> Result<CommandPack> {
+        let packed: Vec<&String> = self.packs.values().flat_map(|p| &p.command_ids).collect();
+        let mut commands: Vec<CommandDefinition> = if command_ids.is_empty() {
+            self.commands
+                .values()
+                .filter(|c| !packed.contains(&&c.id))
+                .cloned()
+                .collect()
+        } else {
+            command_ids
+                .iter()
+                .map(|id| self.get(id))
+                .collect::<Result<_>>()?
+        };
+        if let Some(command) = commands.iter().find(|c| packed.contains(&&c.id)) {
+            return Err(CommandError::PackConflict(format!(
+                "Command '{}' already belongs to a pack",
+                command.id
+            )));
+        }
+        commands.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let pack = CommandPack {
+            namespace: namespace.to_string(),
+            version: version.to_string(),
+            description: String::new(),
+            author: None,
+            commands,
+        };
+        pack.validate()?;
+        Ok(pack)
     }
 }
 
@@ -197,6 +334,14 @@ impl CommandRegistry {
                 .with_enabled(true),
         );
 
+        let _ = registry.register(
+            CommandDefinition::new("/theme-edit", "Edit Theme Colors", "")
+                .with_description("Override individual theme colors with a live preview")
+                .with_tag("slash-command")
+                .with_tag("utility")
+                .with_enabled(true),
+        );
+
         let _ = registry.register(
             CommandDefinition::new("/settings", "Open Settings", "")
                 .with_description("Open settings interface")
@@ -269,6 +414,32 @@ impl CommandRegistry {
                 .with_enabled(true),
         );
 
+        let _ = registry.register(
+            CommandDefinition::new("/feedback", "Send Feedback", "")
+                .with_description("Send feedback about RiceCoder (queued locally when offline)")
+                .with_tag("slash-command")
+                .with_tag("utility")
+                .with_enabled(true),
+        );
+
+        let _ = registry.register(
+            CommandDefinition::new("/telemetry", "Review Telemetry", "")
+                .with_description("Review the aggregated telemetry payload and opt in or out")
+                .with_tag("slash-command")
+                .with_tag("utility")
+                .with_enabled(true),
+        );
+
+        let _ = registry.register(
+            CommandDefinition::new("/init", "Project Setup", "")
+                .with_description(
+                    "Detect languages and build tools, suggest language servers and write .ricecoder/ config",
+                )
+                .with_tag("slash-command")
+                .with_tag("utility")
+                .with_enabled(true),
+        );
+
         let _ = registry.register(
             CommandDefinition::new("/debug", "Toggle Debug Mode", "")
                 .with_description("Toggle debug mode")
@@ -2,6 +2,11 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
+use crate::{
+    pipeline::{PipelineStep, PipelineStepResult},
+    prompt::CompletionSource,
+};
+
 /// A custom command definition
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CommandDefinition {
@@ -34,6 +39,10 @@ pub struct CommandDefinition {
 
     /// Custom metadata
     pub metadata: HashMap<String, String>,
+
+    /// Pipeline steps (when non-empty, these run instead of `command`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pipeline: Vec<PipelineStep>,
 }
 
 /// A command argument definition
@@ -56,6 +65,10 @@ pub struct CommandArgument {
 
     /// Argument type (string, number, boolean, etc.)
     pub arg_type: ArgumentType,
+
+    /// Where completion candidates come from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completion: Option<CompletionSource>,
 }
 
 /// Argument type enumeration
@@ -102,6 +115,10 @@ pub struct CommandExecutionResult {
 
     /// Execution duration in milliseconds
     pub duration_ms: u64,
+
+    /// Per-step results when the command is a pipeline
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub steps: Vec<PipelineStepResult>,
 }
 
 impl CommandDefinition {
@@ -118,6 +135,7 @@ impl CommandDefinition {
             timeout_seconds: 0,
             tags: Vec::new(),
             metadata: HashMap::new(),
+            pipeline: Vec::new(),
         }
     }
 
@@ -156,6 +174,17 @@ impl CommandDefinition {
         self.enabled = enabled;
         self
     }
+
+    /// Add a pipeline step
+    pub fn with_step(mut self, step: PipelineStep) -> Self {
+        self.pipeline.push(step);
+        self
+    }
+
+    /// Whether this command runs a pipeline instead of a shell command
+    pub fn is_pipeline(&self) -> bool {
+        !self.pipeline.is_empty()
+    }
 }
 
 impl CommandArgument {
@@ -168,6 +197,7 @@ impl CommandArgument {
             default: None,
             validation_pattern: None,
             arg_type,
+            completion: None,
         }
     }
 
@@ -194,6 +224,12 @@ impl CommandArgument {
         self.validation_pattern = Some(pattern.into());
         self
     }
+
+    /// Set the completion source
+    pub fn with_completion(mut self, source: CompletionSource) -> Self {
+        self.completion = Some(source);
+        self
+    }
 }
 
 impl CommandExecutionResult {
@@ -206,6 +242,7 @@ impl CommandExecutionResult {
             stderr: String::new(),
             success: exit_code == 0,
             duration_ms: 0,
+            steps: Vec::new(),
         }
     }
 
@@ -1,4 +1,7 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
 
 use ricecoder_commands::*;
 
@@ -103,4 +106,228 @@ mod tests {
         manager.set_output_config(config);
         assert!(!manager.output_config().inject_stdout);
     }
+
+    fn create_pipeline_manager(pipeline: CommandDefinition) -> CommandManager {
+        let mut registry = CommandRegistry::new();
+        registry
+            .register(CommandDefinition::new("echo", "Echo", "echo {{message}}"))
+            .unwrap();
+        registry
+            .register(CommandDefinition::new("fail", "Fail", "exit 3"))
+            .unwrap();
+        registry.register(pipeline).unwrap();
+        CommandManager::new(registry)
+    }
+
+    #[test]
+    fn test_pipeline_feeds_output_into_next_step() {
+        let pipeline = CommandDefinition::new("greet", "Greet", "")
+            .with_step(PipelineStep::new("first", "echo").with_argument("message", "{{name}}"))
+            .with_step(
+                PipelineStep::new("second", "echo")
+                    .with_argument("message", "hello {{first_stdout}}"),
+            );
+        let manager = create_pipeline_manager(pipeline);
+
+        let mut args = HashMap::new();
+        args.insert("name".to_string(), "world".to_string());
+        let result = manager.execute("greet", args, ".".to_string()).unwrap();
+
+        assert!(result.success);
+        assert_eq!(result.command_id, "greet");
+        assert_eq!(result.steps.len(), 2);
+        let second = result.steps[1].result.as_ref().unwrap();
+        assert_eq!(second.stdout.trim(), "hello world");
+    }
+
+    #[test]
+    fn test_pipeline_failure_policies_and_conditions() {
+        let pipeline = CommandDefinition::new("flow", "Flow", "")
+            .with_step(PipelineStep::new("check", "fail").with_on_failure(FailurePolicy::Continue))
+            .with_step(
+                PipelineStep::new("report", "echo")
+                    .with_argument("message", "exit {{check_exit_code}}")
+                    .with_condition(StepCondition::OnFailure),
+            )
+            .with_step(
+                PipelineStep::new("deploy", "echo")
+                    .with_argument("message", "deploying")
+                    .with_condition(StepCondition::OnSuccess),
+            )
+            .with_step(PipelineStep::new("stop", "fail"))
+            .with_step(PipelineStep::new("after", "echo").with_argument("message", "unreachable"));
+        let manager = create_pipeline_manager(pipeline);
+
+        let result = manager
+            .execute("flow", HashMap::new(), ".".to_string())
+            .unwrap();
+
+        assert!(!result.success);
+        assert_eq!(result.exit_code, 3);
+        let statuses: Vec<StepStatus> = result.steps.iter().map(|s| s.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                StepStatus::Failed,
+                StepStatus::Succeeded,
+                StepStatus::Skipped,
+                StepStatus::Failed,
+                StepStatus::Skipped,
+            ]
+        );
+        assert!(result.stdout.contains("exit 3"));
+        assert!(!result.stdout.contains("unreachable"));
+    }
+
+    #[test]
+    fn test_pipeline_cycle_is_reported() {
+        let pipeline = CommandDefinition::new("loop", "Loop", "")
+            .with_step(PipelineStep::new("again", "loop"));
+        let manager = create_pipeline_manager(pipeline);
+
+        let result = manager
+            .execute("loop", HashMap::new(), ".".to_string())
+            .unwrap();
+
+        assert!(!result.success);
+        assert_eq!(result.steps[0].status, StepStatus::Failed);
+        assert!(result.steps[0]
+            .message
+            .as_ref()
+            .unwrap()
+            .contains("loop -> loop"));
+    }
+
+    struct ScriptedPrompts {
+        answers: Mutex<Vec<Option<String>>>,
+        seen: Mutex<Vec<ArgumentPrompt>>,
+    }
+
+    impl ArgumentPromptHandler for ScriptedPrompts {
+        fn prompt(&self, prompt: &ArgumentPrompt) -> Result<Option<String>> {
+            self.seen.lock().unwrap().push(prompt.clone());
+            Ok(self.answers.lock().unwrap().remove(0))
+        }
+    }
+
+    fn scripted(answers: Vec<Option<&str>>) -> Arc<ScriptedPrompts> {
+        Arc::new(ScriptedPrompts {
+            answers: Mutex::new(answers.into_iter().map(|a| a.map(String::from)).collect()),
+            seen: Mutex::new(Vec::new()),
+        })
+    }
+
+    #[test]
+    fn test_missing_argument_is_prompted_and_revalidated() {
+        let mut registry = CommandRegistry::new();
+        let cmd = CommandDefinition::new("env", "Env", "echo {{target}}").with_argument(
+            CommandArgument::new(
+                "target",
+                ArgumentType::Choice(vec!["dev".to_string(), "prod".to_string()]),
+            )
+            .with_required(true),
+        );
+        registry.register(cmd).unwrap();
+        let mut manager = CommandManager::new(registry);
+        let prompts = scripted(vec![Some("staging"), Some("prod")]);
+        manager.set_prompt_handler(prompts.clone());
+
+        let result = manager
+            .execute("env", HashMap::new(), ".".to_string())
+            .unwrap();
+
+        assert_eq!(result.stdout.trim(), "prod");
+        let seen = prompts.seen.lock().unwrap();
+        assert_eq!(seen.len(), 2);
+        assert_eq!(seen[0].completions, vec!["dev", "prod"]);
+        assert!(seen[0].error.is_none());
+        assert!(seen[1].error.as_ref().unwrap().contains("one of"));
+    }
+
+    #[test]
+    fn test_cancelled_prompt_aborts_execution() {
+        let mut manager = create_test_manager();
+        manager.set_prompt_handler(scripted(vec![None]));
+
+        let result = manager.execute("test", HashMap::new(), ".".to_string());
+        assert!(matches!(result, Err(CommandError::Cancelled)));
+    }
+
+    #[tokio::test]
+    async fn test_background_job_streams_output_and_injects() {
+        let manager = create_test_manager();
+        let mut events = manager.jobs().subscribe();
+        let mut args = HashMap::new();
+        args.insert("message".to_string(), "from-job".to_string());
+
+        let handle = manager
+            .execute_background(
+                "test",
+                args,
+                ".".to_string(),
+                BackgroundOptions {
+                    inject_output: true,
+                    hook_event: Some("build_finished".to_string()),
+                },
+            )
+            .unwrap();
+        let job_id = handle.id().to_string();
+        let result = handle.wait().await.unwrap();
+        assert!(result.success);
+        assert_eq!(result.stdout.trim(), "from-job");
+
+        let mut saw_output = false;
+        let mut finished = None;
+        while let Ok(event) = events.try_recv() {
+            match event {
+                JobEvent::Output { line, .. } => saw_output |= line == "from-job",
+                JobEvent::Finished {
+                    status,
+                    injection,
+                    hook_event,
+                    ..
+                } => finished = Some((status, injection, hook_event)),
+                JobEvent::Started { .. } => {}
+            }
+        }
+        assert!(saw_output);
+        let (status, injection, hook_event) = finished.unwrap();
+        assert_eq!(status, JobStatus::Completed);
+        assert!(injection.unwrap().contains("from-job"));
+        assert_eq!(hook_event.as_deref(), Some("build_finished"));
+        assert_eq!(
+            manager.jobs().get(&job_id).unwrap().status,
+            JobStatus::Completed
+        );
+    }
+
+    #[tokio::test]
+    async fn test_background_job_can_be_cancelled() {
+        let mut registry = CommandRegistry::new();
+        registry
+            .register(CommandDefinition::new("sleep", "Sleep", "sleep 30"))
+            .unwrap();
+        let manager = CommandManager::new(registry);
+
+        let handle = manager
+            .execute_background(
+                "sleep",
+                HashMap::new(),
+                ".".to_string(),
+                BackgroundOptions::default(),
+            )
+            .unwrap();
+        assert_eq!(manager.list_jobs().len(), 1);
+        manager.cancel_job(handle.id()).unwrap();
+
+        let job_id = handle.id().to_string();
+        let result = tokio::time::timeout(std::time::Duration::from_secs(5), handle.wait())
+            .await
+            .unwrap();
+        assert!(matches!(result, Err(CommandError::Cancelled)));
+        assert_eq!(
+            manager.jobs().get(&job_id).unwrap().status,
+            JobStatus::Cancelled
+        );
+    }
 }
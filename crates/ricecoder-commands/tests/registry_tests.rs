@@ -118,4 +118,92 @@ mod tests {
         registry.enable("test").ok();
         assert!(registry.get("test").unwrap().enabled);
     }
+
+    fn deploy_pack() -> CommandPack {
+        CommandPack::new("deploy", "1.0.0")
+            .with_command(CommandDefinition::new("staging", "Staging", "echo staging"))
+            .with_command(CommandDefinition::new("prod", "Production", "echo prod"))
+    }
+
+    #[test]
+    fn test_install_pack_namespaces_commands() {
+        let mut registry = CommandRegistry::new();
+        registry
+            .install_pack(deploy_pack(), PackSource::Local)
+            .unwrap();
+
+        assert!(registry.exists("deploy:staging"));
+        assert!(registry.exists("deploy:prod"));
+        let pack = registry.get_pack("deploy").unwrap();
+        assert_eq!(pack.version, "1.0.0");
+        assert_eq!(pack.command_ids.len(), 2);
+
+        registry.uninstall_pack("deploy").unwrap();
+        assert_eq!(registry.count(), 0);
+        assert!(registry.list_packs().is_empty());
+    }
+
+    #[test]
+    fn test_install_pack_detects_conflicts() {
+        let mut registry = CommandRegistry::new();
+        registry
+            .register(CommandDefinition::new("deploy:prod", "Prod", "true"))
+            .unwrap();
+
+        let result = registry.install_pack(deploy_pack(), PackSource::Local);
+        assert!(matches!(result, Err(CommandError::PackConflict(_))));
+        assert!(!registry.exists("deploy:staging"));
+
+        registry.unregister("deploy:prod").unwrap();
+        registry
+            .install_pack(deploy_pack(), PackSource::Local)
+            .unwrap();
+        let again = registry.install_pack(deploy_pack(), PackSource::Local);
+        assert!(matches!(again, Err(CommandError::PackConflict(_))));
+    }
+
+    #[test]
+    fn test_project_settings_toggle_packs() {
+        let mut registry = CommandRegistry::new();
+        registry
+            .install_pack(deploy_pack(), PackSource::Local)
+            .unwrap();
+
+        let mut settings = ProjectPackSettings::default();
+        settings.set_enabled("deploy", false);
+        settings.set_enabled("missing", true);
+        registry.apply_project_settings(&settings);
+
+        assert!(!registry.get_pack("deploy").unwrap().enabled);
+        assert!(!registry.get("deploy:staging").unwrap().enabled);
+        assert!(registry.list_enabled().is_empty());
+    }
+
+    #[test]
+    fn test_export_and_reload_pack() {
+        let mut registry = CommandRegistry::new();
+        registry
+            .register(CommandDefinition::new("lint", "Lint", "cargo clippy"))
+            .unwrap();
+        registry
+            .install_pack(deploy_pack(), PackSource::Local)
+            .unwrap();
+
+        let pack = registry.export_pack("tools", "0.1.0", &[]).unwrap();
+        assert_eq!(pack.commands.len(), 1);
+        assert_eq!(pack.commands[0].id, "lint");
+        assert!(registry
+            .export_pack("tools", "0.1.0", &["deploy:prod"])
+            .is_err());
+
+        let temp = tempfile::tempdir().unwrap();
+        pack.save_to_directory(temp.path()).unwrap();
+        let loaded = CommandPack::load_from_directory(temp.path()).unwrap();
+
+        let mut other = CommandRegistry::new();
+        other
+            .install_pack(loaded, PackSource::Directory(temp.path().to_path_buf()))
+            .unwrap();
+        assert!(other.exists("tools:lint"));
+    }
 }
@@ -0,0 +1,408 @@
+//! Auto-import edits for completions
+//!
+//! External LSP servers attach import statements to completions as
+//! `additionalTextEdits`. When completions come from the internal fallback
+//! providers instead, [`AutoImporter`] synthesizes the same kind of edit for
+//! known symbols, using a per-language import template:
+//!
+//! ```yaml
+//! templates:
+//!   rust:
+//!     template: "use {module}::{symbol};"
+//!     import_prefixes: ["use "]
+//! symbols:
+//!   rust:
+//!     - { symbol: HashMap, module: std::collections }
+//! ```
+//!
+//! [`apply_completion`] applies the completion and its additional edits in
+//! one pass, so an import inserted above the cursor doesn't shift the
+//! completion out of place.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::context::utils::{byte_offset_to_position, position_to_byte_offset};
+use crate::types::{CompletionError, CompletionItem, CompletionResult, Position, Range, TextEdit};
+
+/// How a language spells an import statement
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportTemplate {
+    /// Statement with `{module}` and `{symbol}` placeholders
+    pub template: String,
+    /// Line prefixes that start an import statement
+    pub import_prefixes: Vec<String>,
+}
+
+impl ImportTemplate {
+    pub fn new(template: &str, import_prefixes: &[&str]) -> Self {
+        Self {
+            template: template.to_string(),
+            import_prefixes: import_prefixes.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+
+    /// Render the import statement for a symbol
+    pub fn render(&self, import: &KnownImport) -> String {
+        self.template
+            .replace("{module}", &import.module)
+            .replace("{symbol}", &import.symbol)
+    }
+
+    fn starts_import(&self, line: &str) -> bool {
+        self.import_prefixes
+            .iter()
+            .any(|prefix| line.starts_with(prefix.as_str()))
+    }
+}
+
+/// A symbol and the module it is imported from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KnownImport {
+    pub symbol: String,
+    pub module: String,
+}
+
+impl KnownImport {
+    pub fn new(symbol: &str, module: &str) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            module: module.to_string(),
+        }
+    }
+}
+
+/// Import templates and known symbols per language
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AutoImportConfig {
+    pub templates: HashMap<String, ImportTemplate>,
+    pub symbols: HashMap<String, Vec<KnownImport>>,
+}
+
+impl Default for AutoImportConfig {
+    fn default() -> Self {
+        let mut templates = HashMap::new();
+        templates.insert(
+            "rust".to_string(),
+            ImportTemplate::new("use {module}::{symbol};", &["use ", "pub use "]),
+        );
+        let es_import = ImportTemplate::new("import { {symbol} } from '{module}';", &["import "]);
+        templates.insert("typescript".to_string(), es_import.clone());
+        templates.insert("javascript".to_string(), es_import);
+        templates.insert(
+            "python".to_string(),
+            ImportTemplate::new("from {module} import {symbol}", &["import ", "from "]),
+        );
+
+        let mut symbols = HashMap::new();
+        symbols.insert(
+            "rust".to_string(),
+            vec![
+                KnownImport::new("HashMap", "std::collections"),
+                KnownImport::new("HashSet", "std::collections"),
+                KnownImport::new("BTreeMap", "std::collections"),
+                KnownImport::new("VecDeque", "std::collections"),
+                KnownImport::new("Arc", "std::sync"),
+                KnownImport::new("Mutex", "std::sync"),
+                KnownImport::new("RwLock", "std::sync"),
+                KnownImport::new("Path", "std::path"),
+                KnownImport::new("PathBuf", "std::path"),
+                KnownImport::new("Duration", "std::time"),
+                KnownImport::new("Instant", "std::time"),
+            ],
+        );
+        let es_symbols = vec![
+            KnownImport::new("readFile", "fs/promises"),
+            KnownImport::new("writeFile", "fs/promises"),
+            KnownImport::new("useState", "react"),
+            KnownImport::new("useEffect", "react"),
+        ];
+        symbols.insert("typescript".to_string(), es_symbols.clone());
+        symbols.insert("javascript".to_string(), es_symbols);
+        symbols.insert(
+            "python".to_string(),
+            vec![
+                KnownImport::new("Path", "pathlib"),
+                KnownImport::new("dataclass", "dataclasses"),
+                KnownImport::new("defaultdict", "collections"),
+                KnownImport::new("Optional", "typing"),
+                KnownImport::new("datetime", "datetime"),
+            ],
+        );
+
+        Self { templates, symbols }
+    }
+}
+
+/// Synthesizes import edits for completions of known symbols
+#[derive(Debug, Clone, Default)]
+pub struct AutoImporter {
+    config: AutoImportConfig,
+}
+
+impl AutoImporter {
+    pub fn new(config: AutoImportConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn config(&self) -> &AutoImportConfig {
+        &self.config
+    }
+
+    /// Known import for a symbol, if any
+    pub fn lookup(&self, language: &str, symbol: &str) -> Option<&KnownImport> {
+        self.config
+            .symbols
+            .get(language)?
+            .iter()
+            .find(|import| import.symbol == symbol)
+    }
+
+    /// Edit that imports `symbol`, or `None` if it is unknown or already imported
+    pub fn import_edit(&self, language: &str, code: &str, symbol: &str) -> Option<TextEdit> {
+        let template = self.config.templates.get(language)?;
+        let import = self.lookup(language, symbol)?;
+        let statements = import_statements(code, template);
+        if statements
+            .iter()
+            .any(|statement| imports(&statement.text, import))
+        {
+            return None;
+        }
+
+        let statement = template.render(import);
+        let line = match statements.last() {
+            Some(last) => last.end_line + 1,
+            None => header_end(code),
+        };
+        let line_count = code.lines().count() as u32;
+        if line < line_count {
+            let position = Position::new(line, 0);
+            return Some(TextEdit::new(
+                Range::new(position, position),
+                format!("{}\n", statement),
+            ));
+        }
+
+        // Appending after the last line
+        let end = byte_offset_to_position(code, code.len());
+        let new_text = if code.is_empty() || code.ends_with('\n') {
+            format!("{}\n", statement)
+        } else {
+            format!("\n{}\n", statement)
+        };
+        Some(TextEdit::new(Range::new(end, end), new_text))
+    }
+
+    /// Attach import edits to completions of known symbols
+    ///
+    /// Items that already carry additional edits, e.g. from an external LSP
+    /// server, are left alone.
+    pub fn add_import_edits(&self, language: &str, code: &str, items: &mut [CompletionItem]) {
+        for item in items
+            .iter_mut()
+            .filter(|item| item.additional_edits.is_empty())
+        {
+            if let Some(edit) = self.import_edit(language, code, &item.label) {
+                if item.detail.is_none() {
+                    item.detail = Some(format!("Auto import: {}", edit.new_text.trim()));
+                }
+                item.additional_edits.push(edit);
+            }
+        }
+    }
+}
+
+/// Result of applying a completion
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppliedCompletion {
+    /// Document text after the edits
+    pub text: String,
+    /// Cursor position after the inserted completion
+    pub cursor: Position,
+}
+
+/// Apply a completion and its additional edits to a document
+///
+/// `replace` is the range the completion replaces, usually the prefix typed
+/// so far. All edits refer to the original document; additional edits that
+/// overlap `replace` or each other are rejected, as the LSP specification
+/// requires.
+pub fn apply_completion(
+    code: &str,
+    replace: Range,
+    item: &CompletionItem,
+) -> CompletionResult<AppliedCompletion> {
+    let primary = TextEdit::new(replace, item.insert_text.clone());
+    let mut edits: Vec<(usize, usize, &TextEdit, bool)> = std::iter::once((&primary, true))
+        .chain(item.additional_edits.iter().map(|edit| (edit, false)))
+        .map(|(edit, is_primary)| {
+            let start = position_to_byte_offset(code, edit.range.start);
+            let end = position_to_byte_offset(code, edit.range.end);
+            (start, end.max(start), edit, is_primary)
+        })
+        .collect();
+    edits.sort_by_key(|(start, end, _, _)| (*start, *end));
+
+    for pair in edits.windows(2) {
+        if pair[1].0 < pair[0].1 {
+            return Err(CompletionError::InvalidPosition(format!(
+                "Completion edits overlap at {}:{}",
+                pair[1].2.range.start.line, pair[1].2.range.start.character
+            )));
+        }
+    }
+
+    let mut text = String::with_capacity(code.len());
+    let mut copied = 0;
+    let mut cursor = 0;
+    for (start, end, edit, is_primary) in edits {
+        text.push_str(&code[copied..start]);
+        text.push_str(&edit.new_text);
+        if is_primary {
+            cursor = text.len();
+        }
+        copied = end;
+    }
+    text.push_str(&code[copied..]);
+
+    let cursor = byte_offset_to_position(&text, cursor);
+    Ok(AppliedCompletion { text, cursor })
+}
+
+/// An import statement, possibly spanning several lines
+struct ImportStatement {
+    text: String,
+    end_line: u32,
+}
+
+/// Top-level import statements in a document
+fn import_statements(code: &str, template: &ImportTemplate) -> Vec<ImportStatement> {
+    let mut statements = Vec::new();
+    let mut lines = code.lines().enumerate();
+    while let Some((index, line)) = lines.next() {
+        if !template.starts_import(line) {
+            continue;
+        }
+        let mut text = line.to_string();
+        let mut end_line = index;
+        let mut depth = bracket_depth(line);
+        // `use std::{\n    fs,\n};` and `from x import (\n    a,\n)`
+        while depth > 0 {
+            let Some((index, line)) = lines.next() else {
+                break;
+            };
+            text.push('\n');
+            text.push_str(line);
+            end_line = index;
+            depth += bracket_depth(line);
+        }
+        statements.push(ImportStatement {
+            text,
+            end_line: end_line as u32,
+        });
+    }
+    statements
+}
+
+fn bracket_depth(line: &str) -> i32 {
+    line.chars()
+        .map(|c| match c {
+            '{' | '(' => 1,
+            '}' | ')' => -1,
+            _ => 0,
+        })
+        .sum()
+}
+
+/// Whether an import statement brings `import` into scope
+fn imports(statement: &str, import: &KnownImport) -> bool {
+    let has_word = |word: &str| {
+        statement
+            .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .any(|token| token == word)
+    };
+    statement.contains(import.module.as_str()) && has_word(&import.symbol)
+}
+
+/// First line after the leading comments, attributes and shebang
+fn header_end(code: &str) -> u32 {
+    let mut end = 0;
+    for (index, line) in code.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("//")
+            || trimmed.starts_with("#")
+            || trimmed.starts_with("/*")
+            || trimmed.starts_with('*')
+        {
+            end = index as u32 + 1;
+        } else if !trimmed.is_empty() {
+            break;
+        }
+    }
+    end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::CompletionItemKind;
+
+    fn item(label: &str) -> CompletionItem {
+        CompletionItem::new(
+            label.to_string(),
+            CompletionItemKind::Struct,
+            label.to_string(),
+        )
+    }
+
+    #[test]
+    fn test_import_edit_goes_after_existing_imports() {
+        let importer = AutoImporter::default();
+        let code = "//! Crate docs\nuse std::{\n    fs,\n};\n\nfn main() {\n    let map = Ha\n}\n";
+
+        let edit = importer.import_edit("rust", code, "HashMap").unwrap();
+        assert_eq!(edit.range.start, Position::new(4, 0));
+        assert_eq!(edit.new_text, "use std::collections::HashMap;\n");
+
+        let imported = "use std::collections::{HashMap, HashSet};\n";
+        assert!(importer.import_edit("rust", imported, "HashMap").is_none());
+        assert!(importer.import_edit("rust", code, "Unknown").is_none());
+    }
+
+    #[test]
+    fn test_import_edit_without_imports_skips_header() {
+        let importer = AutoImporter::default();
+        let code = "#!/usr/bin/env python\n# Tool\nprint(Pa";
+
+        let edit = importer.import_edit("python", code, "Path").unwrap();
+        assert_eq!(edit.range.start, Position::new(2, 0));
+        assert_eq!(edit.new_text, "from pathlib import Path\n");
+    }
+
+    #[test]
+    fn test_apply_completion_keeps_cursor_after_import() {
+        let importer = AutoImporter::default();
+        let code = "use std::fs;\n\nfn main() {\n    let map: Ha\n}\n";
+        let mut items = vec![item("HashMap")];
+        importer.add_import_edits("rust", code, &mut items);
+        assert_eq!(items[0].additional_edits.len(), 1);
+
+        let replace = Range::new(Position::new(3, 13), Position::new(3, 15));
+        let applied = apply_completion(code, replace, &items[0]).unwrap();
+        assert_eq!(
+            applied.text,
+            "use std::fs;\nuse std::collections::HashMap;\n\nfn main() {\n    let map: HashMap\n}\n"
+        );
+        assert_eq!(applied.cursor, Position::new(4, 20));
+
+        // Edits overlapping the completion are rejected
+        let mut overlapping = item("HashMap");
+        overlapping
+            .additional_edits
+            .push(TextEdit::new(replace, String::new()));
+        assert!(apply_completion(code, replace, &overlapping).is_err());
+    }
+}
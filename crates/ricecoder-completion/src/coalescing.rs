@@ -0,0 +1,481 @@
+/// Request coalescing and speculative prefetch for completion engines
+///
+/// Fast typing and editor retriggers often produce the same completion request
+/// several times before the first one has been answered. [`CoalescingCompletionEngine`]
+/// wraps any [`CompletionEngine`] so that identical requests (same code, position
+/// and language) that overlap share a single call to the inner engine, and with it
+/// a single round-trip to the external LSP server.
+///
+/// After the user accepts a completion, [`CoalescingCompletionEngine::on_completion_accepted`]
+/// predicts the context the editor will ask about next (the accepted text inserted,
+/// cursor after it) and computes those completions in the background. When the
+/// request arrives it is answered from the prefetched results, or joins the
+/// prefetch if it is still running.
+///
+/// # Example
+///
+/// ```ignore
+/// use ricecoder_completion::coalescing::CoalescingCompletionEngine;
+/// use std::sync::Arc;
+///
+/// let engine = CoalescingCompletionEngine::new(Arc::new(generic_engine));
+/// let completions = engine.generate_completions(code, position, "rust").await?;
+///
+/// // The user picked the first item
+/// engine.on_completion_accepted(code, position, "rust", &completions[0]);
+/// ```
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use tokio::sync::watch;
+use tracing::debug;
+
+use crate::context::utils::{byte_offset_to_position, extract_prefix, position_to_byte_offset};
+use crate::engine::CompletionEngine;
+use crate::types::*;
+
+/// Result shared with requests that joined an in-flight request
+type SharedResult = Result<Vec<CompletionItem>, String>;
+
+/// Configuration for request coalescing and prefetch
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoalescingConfig {
+    /// Prefetch completions for the next likely context after an acceptance
+    pub prefetch: bool,
+    /// How long prefetched completions stay valid
+    pub prefetch_ttl: Duration,
+    /// Maximum number of prefetched results kept at once
+    pub max_prefetched: usize,
+}
+
+impl Default for CoalescingConfig {
+    fn default() -> Self {
+        Self {
+            prefetch: true,
+            prefetch_ttl: Duration::from_secs(5),
+            max_prefetched: 16,
+        }
+    }
+}
+
+/// Counters describing how requests were served
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CoalescingStats {
+    /// Requests received
+    pub requests: u64,
+    /// Requests that joined an identical in-flight request
+    pub coalesced: u64,
+    /// Requests answered from prefetched completions
+    pub prefetch_hits: u64,
+    /// Prefetches started
+    pub prefetches: u64,
+    /// Calls made to the inner engine
+    pub engine_calls: u64,
+}
+
+/// Identity of a completion request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct RequestKey {
+    code_hash: u64,
+    code_len: usize,
+    language_hash: u64,
+    position: Position,
+}
+
+impl RequestKey {
+    fn new(code: &str, position: Position, language: &str) -> Self {
+        Self {
+            code_hash: hash_of(code),
+            code_len: code.len(),
+            language_hash: hash_of(language),
+            position,
+        }
+    }
+}
+
+fn hash_of(value: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[derive(Default)]
+struct Counters {
+    requests: AtomicU64,
+    coalesced: AtomicU64,
+    prefetch_hits: AtomicU64,
+    prefetches: AtomicU64,
+    engine_calls: AtomicU64,
+}
+
+struct Shared {
+    inner: Arc<dyn CompletionEngine>,
+    config: CoalescingConfig,
+    in_flight: Mutex<HashMap<RequestKey, watch::Receiver<Option<SharedResult>>>>,
+    prefetched: Mutex<HashMap<RequestKey, (Instant, Vec<CompletionItem>)>>,
+    counters: Counters,
+}
+
+/// Removes an in-flight entry even if the leading request is dropped
+struct InFlightGuard<'a> {
+    shared: &'a Shared,
+    key: RequestKey,
+}
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut in_flight) = self.shared.in_flight.lock() {
+            in_flight.remove(&self.key);
+        }
+    }
+}
+
+impl Shared {
+    async fn generate(
+        &self,
+        code: &str,
+        position: Position,
+        language: &str,
+    ) -> CompletionResult<Vec<CompletionItem>> {
+        let key = RequestKey::new(code, position, language);
+
+        if let Some(items) = self.take_prefetched(&key) {
+            self.counters.prefetch_hits.fetch_add(1, Ordering::Relaxed);
+            debug!("Serving completion request from prefetched results");
+            return Ok(items);
+        }
+
+        loop {
+            let (sender, mut receiver) = {
+                let mut in_flight = self.in_flight.lock().map_err(lock_error)?;
+                match in_flight.get(&key) {
+                    Some(receiver) => (None, receiver.clone()),
+                    None => {
+                        let (sender, receiver) = watch::channel(None);
+                        in_flight.insert(key, receiver.clone());
+                        (Some(sender), receiver)
+                    }
+                }
+            };
+
+            let Some(sender) = sender else {
+                self.counters.coalesced.fetch_add(1, Ordering::Relaxed);
+                debug!("Joining identical in-flight completion request");
+                match receiver.wait_for(Option::is_some).await {
+                    Ok(result) => {
+                        let result = result.clone().expect("waited for a result");
+                        return result.map_err(CompletionError::GenerationError);
+                    }
+                    // The leading request was dropped; try again
+                    Err(_) => continue,
+                }
+            };
+
+            let _guard = InFlightGuard { shared: self, key };
+            self.counters.engine_calls.fetch_add(1, Ordering::Relaxed);
+            let result = self
+                .inner
+                .generate_completions(code, position, language)
+                .await;
+            let shared = match &result {
+                Ok(items) => Ok(items.clone()),
+                Err(e) => Err(e.to_string()),
+            };
+            let _ = sender.send(Some(shared));
+            return result;
+        }
+    }
+
+    fn take_prefetched(&self, key: &RequestKey) -> Option<Vec<CompletionItem>> {
+        let mut prefetched = self.prefetched.lock().ok()?;
+        let ttl = self.config.prefetch_ttl;
+        prefetched.retain(|_, (at, _)| at.elapsed() < ttl);
+        prefetched.remove(key).map(|(_, items)| items)
+    }
+
+    fn store_prefetched(&self, key: RequestKey, items: Vec<CompletionItem>) {
+        let Ok(mut prefetched) = self.prefetched.lock() else {
+            return;
+        };
+        if prefetched.len() >= self.config.max_prefetched && !prefetched.contains_key(&key) {
+            // Drop the oldest prefetch to make room
+            if let Some(oldest) = prefetched
+                .iter()
+                .min_by_key(|(_, (at, _))| *at)
+                .map(|(key, _)| *key)
+            {
+                prefetched.remove(&oldest);
+            }
+        }
+        prefetched.insert(key, (Instant::now(), items));
+    }
+}
+
+fn lock_error<T>(_: T) -> CompletionError {
+    CompletionError::InternalError("Completion coalescing lock poisoned".to_string())
+}
+
+/// Completion engine decorator that coalesces duplicate requests and prefetches
+/// the next likely context
+pub struct CoalescingCompletionEngine {
+    shared: Arc<Shared>,
+}
+
+impl CoalescingCompletionEngine {
+    /// Wrap an engine with the default configuration
+    pub fn new(inner: Arc<dyn CompletionEngine>) -> Self {
+        Self::with_config(inner, CoalescingConfig::default())
+    }
+
+    /// Wrap an engine with a custom configuration
+    pub fn with_config(inner: Arc<dyn CompletionEngine>, config: CoalescingConfig) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                inner,
+                config,
+                in_flight: Mutex::new(HashMap::new()),
+                prefetched: Mutex::new(HashMap::new()),
+                counters: Counters::default(),
+            }),
+        }
+    }
+
+    /// Get the configuration
+    pub fn config(&self) -> &CoalescingConfig {
+        &self.shared.config
+    }
+
+    /// Counters describing how requests were served so far
+    pub fn stats(&self) -> CoalescingStats {
+        let counters = &self.shared.counters;
+        CoalescingStats {
+            requests: counters.requests.load(Ordering::Relaxed),
+            coalesced: counters.coalesced.load(Ordering::Relaxed),
+            prefetch_hits: counters.prefetch_hits.load(Ordering::Relaxed),
+            prefetches: counters.prefetches.load(Ordering::Relaxed),
+            engine_calls: counters.engine_calls.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Tell the engine the user accepted `item` at `position`
+    ///
+    /// Starts computing completions for the predicted next context in the
+    /// background. Returns `None` when prefetch is disabled or no Tokio
+    /// runtime is running.
+    pub fn on_completion_accepted(
+        &self,
+        code: &str,
+        position: Position,
+        language: &str,
+        item: &CompletionItem,
+    ) -> Option<tokio::task::JoinHandle<()>> {
+        if !self.shared.config.prefetch {
+            return None;
+        }
+        let runtime = tokio::runtime::Handle::try_current().ok()?;
+        let (next_code, next_position) = predict_next_context(code, position, item);
+        let language = language.to_string();
+        let shared = Arc::clone(&self.shared);
+        shared.counters.prefetches.fetch_add(1, Ordering::Relaxed);
+
+        Some(runtime.spawn(async move {
+            let key = RequestKey::new(&next_code, next_position, &language);
+            match shared.generate(&next_code, next_position, &language).await {
+                Ok(items) => shared.store_prefetched(key, items),
+                Err(e) => debug!("Completion prefetch failed: {}", e),
+            }
+        }))
+    }
+
+    /// Drop all prefetched completions, e.g. after the document changed elsewhere
+    pub fn clear_prefetched(&self) {
+        if let Ok(mut prefetched) = self.shared.prefetched.lock() {
+            prefetched.clear();
+        }
+    }
+}
+
+#[async_trait]
+impl CompletionEngine for CoalescingCompletionEngine {
+    async fn generate_completions(
+        &self,
+        code: &str,
+        position: Position,
+        language: &str,
+    ) -> CompletionResult<Vec<CompletionItem>> {
+        self.shared
+            .counters
+            .requests
+            .fetch_add(1, Ordering::Relaxed);
+        self.shared.generate(code, position, language).await
+    }
+
+    async fn resolve_completion(&self, item: &CompletionItem) -> CompletionResult<CompletionItem> {
+        self.shared.inner.resolve_completion(item).await
+    }
+}
+
+/// Predict the code and cursor position after `item` is accepted at `position`
+///
+/// The word prefix before the cursor is replaced with the item's insert text,
+/// snippet placeholders are expanded to their defaults, and the cursor is
+/// placed after the inserted text.
+pub fn predict_next_context(
+    code: &str,
+    position: Position,
+    item: &CompletionItem,
+) -> (String, Position) {
+    let offset = position_to_byte_offset(code, position);
+    let prefix = extract_prefix(code, position);
+    let start = offset - prefix.len();
+    let inserted = expand_snippet(&item.insert_text);
+
+    let mut next_code = String::with_capacity(code.len() + inserted.len());
+    next_code.push_str(&code[..start]);
+    next_code.push_str(&inserted);
+    let cursor = next_code.len();
+    next_code.push_str(&code[offset..]);
+
+    let next_position = byte_offset_to_position(&next_code, cursor);
+    (next_code, next_position)
+}
+
+/// Replace `${1:default}` with `default` and drop `$1`/`$0` tab stops
+fn expand_snippet(text: &str) -> String {
+    static PLACEHOLDERS: OnceLock<regex::Regex> = OnceLock::new();
+    let placeholders = PLACEHOLDERS.get_or_init(|| {
+        regex::Regex::new(r"\$\{\d+:([^}]*)\}|\$\{\d+\}|\$\d+")
+            .expect("snippet placeholder regex is valid")
+    });
+    placeholders.replace_all(text, "$1").into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+
+    use super::*;
+
+    /// Engine that counts calls and answers after a delay
+    struct SlowEngine {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl CompletionEngine for SlowEngine {
+        async fn generate_completions(
+            &self,
+            code: &str,
+            _position: Position,
+            _language: &str,
+        ) -> CompletionResult<Vec<CompletionItem>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            Ok(vec![CompletionItem::new(
+                format!("item{}", code.len()),
+                CompletionItemKind::Variable,
+                "value".to_string(),
+            )])
+        }
+
+        async fn resolve_completion(
+            &self,
+            item: &CompletionItem,
+        ) -> CompletionResult<CompletionItem> {
+            Ok(item.clone())
+        }
+    }
+
+    fn engine() -> (Arc<SlowEngine>, CoalescingCompletionEngine) {
+        let inner = Arc::new(SlowEngine {
+            calls: AtomicUsize::new(0),
+        });
+        let engine = CoalescingCompletionEngine::new(inner.clone());
+        (inner, engine)
+    }
+
+    #[tokio::test]
+    async fn test_identical_requests_are_coalesced() {
+        let (inner, engine) = engine();
+        let position = Position::new(0, 8);
+
+        let (first, second, other) = tokio::join!(
+            engine.generate_completions("let x = ", position, "rust"),
+            engine.generate_completions("let x = ", position, "rust"),
+            engine.generate_completions("let y = ", position, "rust"),
+        );
+
+        assert_eq!(first.unwrap()[0].label, second.unwrap()[0].label);
+        assert!(other.is_ok());
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+        let stats = engine.stats();
+        assert_eq!(stats.requests, 3);
+        assert_eq!(stats.coalesced, 1);
+
+        // Finished requests are not reused
+        engine
+            .generate_completions("let x = ", position, "rust")
+            .await
+            .unwrap();
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_accepted_completion_prefetches_next_context() {
+        let (inner, engine) = engine();
+        let code = "fn main() { pri }";
+        let position = Position::new(0, 15);
+        let item = CompletionItem::new(
+            "println!".to_string(),
+            CompletionItemKind::Snippet,
+            "println!(${1:msg})$0".to_string(),
+        );
+
+        let (next_code, next_position) = predict_next_context(code, position, &item);
+        assert_eq!(next_code, "fn main() { println!(msg) }");
+        assert_eq!(next_position, Position::new(0, 25));
+
+        engine
+            .on_completion_accepted(code, position, "rust", &item)
+            .unwrap()
+            .await
+            .unwrap();
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+
+        let items = engine
+            .generate_completions(&next_code, next_position, "rust")
+            .await
+            .unwrap();
+        assert_eq!(items[0].label, format!("item{}", next_code.len()));
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(engine.stats().prefetch_hits, 1);
+    }
+
+    #[tokio::test]
+    async fn test_request_joins_running_prefetch() {
+        let (inner, engine) = engine();
+        let item = CompletionItem::new(
+            "value".to_string(),
+            CompletionItemKind::Variable,
+            "value".to_string(),
+        );
+        let handle = engine
+            .on_completion_accepted("x = va", Position::new(0, 6), "python", &item)
+            .unwrap();
+        tokio::task::yield_now().await;
+
+        let items = engine
+            .generate_completions("x = value", Position::new(0, 9), "python")
+            .await
+            .unwrap();
+        handle.await.unwrap();
+
+        assert_eq!(items.len(), 1);
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+    }
+}
@@ -28,6 +28,26 @@ impl ConfigLoader {
         Ok(config)
     }
 
+    /// Load completion configuration from a file, picking the format from its extension
+    pub fn load_from_path(path: &Path) -> CompletionResult<CompletionConfig> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("yaml") | Some("yml") => Self::load_from_yaml(path),
+            Some("json") => Self::load_from_json(path),
+            _ => Err(CompletionError::ConfigError(format!(
+                "Unsupported configuration file: {}",
+                path.display()
+            ))),
+        }
+    }
+
+    /// Whether a file has a configuration extension
+    pub fn is_config_file(path: &Path) -> bool {
+        matches!(
+            path.extension().and_then(|ext| ext.to_str()),
+            Some("yaml") | Some("yml") | Some("json")
+        )
+    }
+
     /// Load completion configuration from a string
     pub fn load_from_string(
         content: &str,
@@ -116,7 +136,7 @@ impl ConfigLoader {
     }
 
     /// Validate completion configuration
-    fn validate_config(config: &CompletionConfig) -> CompletionResult<()> {
+    pub fn validate_config(config: &CompletionConfig) -> CompletionResult<()> {
         if config.language.is_empty() {
             return Err(CompletionError::ConfigError(
                 "Language name cannot be empty".to_string(),
@@ -132,6 +152,33 @@ impl ConfigLoader {
             ));
         }
 
+        if config
+            .keywords
+            .iter()
+            .any(|keyword| keyword.trim().is_empty())
+        {
+            return Err(CompletionError::ConfigError(format!(
+                "Empty keyword in {} configuration",
+                config.language
+            )));
+        }
+
+        let mut labels = std::collections::HashSet::new();
+        for snippet in &config.snippets {
+            if snippet.label.trim().is_empty() || snippet.template.is_empty() {
+                return Err(CompletionError::ConfigError(format!(
+                    "Snippets in {} configuration need a label and a template",
+                    config.language
+                )));
+            }
+            if !labels.insert(snippet.label.as_str()) {
+                return Err(CompletionError::ConfigError(format!(
+                    "Duplicate snippet '{}' in {} configuration",
+                    snippet.label, config.language
+                )));
+            }
+        }
+
         Ok(())
     }
 
@@ -149,14 +196,20 @@ pub enum ConfigFormat {
 }
 
 /// Language configuration registry with storage integration
+///
+/// Every language has a version that increases whenever its configuration
+/// is registered or changes, so consumers can tell when to refresh.
+#[derive(Debug, Clone)]
 pub struct LanguageConfigRegistry {
     configs: HashMap<String, CompletionConfig>,
+    versions: HashMap<String, u64>,
 }
 
 impl LanguageConfigRegistry {
     pub fn new() -> Self {
         Self {
             configs: HashMap::new(),
+            versions: HashMap::new(),
         }
     }
 
@@ -177,9 +230,31 @@ impl LanguageConfigRegistry {
     }
 
     pub fn register(&mut self, config: CompletionConfig) {
+        *self.versions.entry(config.language.clone()).or_insert(0) += 1;
         self.configs.insert(config.language.clone(), config);
     }
 
+    /// Register a configuration only if it differs from the current one
+    ///
+    /// Returns the new version of the language when it changed.
+    pub fn update(&mut self, config: CompletionConfig) -> Option<u64> {
+        if self.configs.get(&config.language) == Some(&config) {
+            return None;
+        }
+        let language = config.language.clone();
+        self.register(config);
+        self.version(&language)
+    }
+
+    /// Current version of a language's configuration
+    pub fn version(&self, language: &str) -> Option<u64> {
+        if self.configs.contains_key(language) {
+            self.versions.get(language).copied()
+        } else {
+            None
+        }
+    }
+
     pub fn get(&self, language: &str) -> Option<&CompletionConfig> {
         self.configs.get(language)
     }
@@ -208,15 +283,8 @@ impl LanguageConfigRegistry {
             let entry = entry?;
             let path = entry.path();
 
-            if path.is_file() {
-                if let Some(ext) = path.extension() {
-                    let config = match ext.to_str() {
-                        Some("yaml") | Some("yml") => ConfigLoader::load_from_yaml(&path)?,
-                        Some("json") => ConfigLoader::load_from_json(&path)?,
-                        _ => continue,
-                    };
-                    self.register(config);
-                }
+            if path.is_file() && ConfigLoader::is_config_file(&path) {
+                self.register(ConfigLoader::load_from_path(&path)?);
             }
         }
 
@@ -301,6 +369,29 @@ mod tests {
         assert!(registry.get("rust").is_none());
     }
 
+    #[test]
+    fn test_language_config_registry_update_tracks_versions() {
+        let mut registry = LanguageConfigRegistry::new();
+        let mut config = CompletionConfig::new("rust".to_string());
+        assert_eq!(registry.update(config.clone()), Some(1));
+        assert_eq!(registry.update(config.clone()), None);
+
+        config.keywords.push("fn".to_string());
+        assert_eq!(registry.update(config), Some(2));
+        assert_eq!(registry.version("rust"), Some(2));
+        assert_eq!(registry.version("python"), None);
+    }
+
+    #[test]
+    fn test_config_loader_validate_duplicate_snippets() {
+        let mut config = CompletionConfig::new("rust".to_string());
+        config.snippets = vec![
+            CompletionSnippet::new("fn".to_string(), "fn $1() {}".to_string()),
+            CompletionSnippet::new("fn".to_string(), "fn main() {}".to_string()),
+        ];
+        assert!(ConfigLoader::validate_config(&config).is_err());
+    }
+
     #[test]
     fn test_config_default_for_language() {
         let config = ConfigLoader::default_for_language("typescript");
@@ -0,0 +1,353 @@
+//! Hot reloading of language completion configurations
+//!
+//! [`LanguageConfigManager`] keeps a [`LanguageConfigRegistry`] in sync with
+//! the configuration directories. Edits to keywords, snippets or ranking
+//! weights are validated, bump the language's version and are announced as
+//! [`LanguageConfigEvent`]s, so open sessions pick them up without a restart.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::sync::{broadcast, mpsc, RwLock};
+use tokio::task::JoinHandle;
+
+use crate::config::{ConfigLoader, LanguageConfigRegistry};
+use crate::types::{CompletionConfig, CompletionError, CompletionResult};
+
+/// Language configuration change notification
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LanguageConfigEvent {
+    /// A language's configuration was added or changed
+    Reloaded { language: String, version: u64 },
+    /// A language's configuration file was removed
+    Removed { language: String },
+    /// A file failed to load or validate; the previous configuration stays in effect
+    ReloadFailed { path: PathBuf, error: String },
+}
+
+/// Registry plus the file each language was loaded from
+struct State {
+    registry: Arc<LanguageConfigRegistry>,
+    sources: HashMap<String, PathBuf>,
+}
+
+/// Owns the language configurations and keeps them in sync with their files
+///
+/// Directories are listed from lowest to highest precedence: a language
+/// configured in a later directory overrides the earlier ones.
+pub struct LanguageConfigManager {
+    dirs: Vec<PathBuf>,
+    state: RwLock<State>,
+    events: broadcast::Sender<LanguageConfigEvent>,
+    debounce: Duration,
+}
+
+impl LanguageConfigManager {
+    /// Load configurations from the given directories
+    pub async fn load(dirs: Vec<PathBuf>) -> Arc<Self> {
+        let (events, _) = broadcast::channel(16);
+        let manager = Arc::new(Self {
+            dirs,
+            state: RwLock::new(State {
+                registry: Arc::new(LanguageConfigRegistry::new()),
+                sources: HashMap::new(),
+            }),
+            events,
+            debounce: Duration::from_millis(250),
+        });
+        manager.reload().await;
+        manager
+    }
+
+    /// Load from the project directory, overridden by the user directory
+    pub async fn with_hierarchy() -> Arc<Self> {
+        let mut dirs = vec![ConfigLoader::get_project_completion_config_dir()];
+        if let Ok(user_dir) = ConfigLoader::get_completion_config_dir() {
+            dirs.push(user_dir);
+        }
+        Self::load(dirs).await
+    }
+
+    /// Directories configurations are loaded from
+    pub fn dirs(&self) -> &[PathBuf] {
+        &self.dirs
+    }
+
+    /// Current registry snapshot
+    pub async fn registry(&self) -> Arc<LanguageConfigRegistry> {
+        Arc::clone(&self.state.read().await.registry)
+    }
+
+    /// Current configuration of a language
+    pub async fn config(&self, language: &str) -> Option<CompletionConfig> {
+        self.state.read().await.registry.get(language).cloned()
+    }
+
+    /// Current version of a language's configuration
+    pub async fn version(&self, language: &str) -> Option<u64> {
+        self.state.read().await.registry.version(language)
+    }
+
+    /// Subscribe to configuration changes
+    pub fn subscribe(&self) -> broadcast::Receiver<LanguageConfigEvent> {
+        self.events.subscribe()
+    }
+
+    /// Re-read every configuration file
+    ///
+    /// Each file is loaded and validated on its own: a file that fails keeps
+    /// the configuration it last provided, the others are still applied.
+    /// Only languages whose configuration actually changed get a new version.
+    pub async fn reload(&self) -> Arc<LanguageConfigRegistry> {
+        let mut loaded: HashMap<String, (CompletionConfig, PathBuf)> = HashMap::new();
+        let mut failed = HashSet::new();
+        for dir in &self.dirs {
+            for path in config_files(dir) {
+                match ConfigLoader::load_from_path(&path) {
+                    Ok(config) => {
+                        loaded.insert(config.language.clone(), (config, path));
+                    }
+                    Err(e) => {
+                        tracing::warn!(path = %path.display(), "Completion config reload failed: {}", e);
+                        let _ = self.events.send(LanguageConfigEvent::ReloadFailed {
+                            path: path.clone(),
+                            error: e.to_string(),
+                        });
+                        failed.insert(path);
+                    }
+                }
+            }
+        }
+
+        let mut state = self.state.write().await;
+        let mut registry = (*state.registry).clone();
+
+        // Broken files keep what they provided before
+        for (language, source) in &state.sources {
+            if failed.contains(source) && !loaded.contains_key(language) {
+                if let Some(config) = registry.get(language) {
+                    loaded.insert(language.clone(), (config.clone(), source.clone()));
+                }
+            }
+        }
+
+        let mut changes = Vec::new();
+        for language in registry.list_languages() {
+            if !loaded.contains_key(&language) {
+                registry.unregister(&language);
+                changes.push(LanguageConfigEvent::Removed { language });
+            }
+        }
+        let mut sources = HashMap::new();
+        for (language, (config, source)) in loaded {
+            if let Some(version) = registry.update(config) {
+                changes.push(LanguageConfigEvent::Reloaded {
+                    language: language.clone(),
+                    version,
+                });
+            }
+            sources.insert(language, source);
+        }
+
+        let registry = Arc::new(registry);
+        state.registry = Arc::clone(&registry);
+        state.sources = sources;
+        drop(state);
+
+        if !changes.is_empty() {
+            tracing::info!(changes = changes.len(), "Completion configs reloaded");
+        }
+        for change in changes {
+            let _ = self.events.send(change);
+        }
+        registry
+    }
+
+    /// Reload whenever a configuration file changes
+    ///
+    /// Watches each configuration directory, or its parent when the
+    /// directory doesn't exist yet. Watching stops when the returned handle
+    /// is dropped.
+    pub fn watch(self: &Arc<Self>) -> CompletionResult<LanguageConfigWatchHandle> {
+        let dirs = self.dirs.clone();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut watcher = RecommendedWatcher::new(
+            move |result: notify::Result<notify::Event>| match result {
+                Ok(event)
+                    if event.paths.iter().any(|path| {
+                        ConfigLoader::is_config_file(path) || dirs.iter().any(|dir| dir == path)
+                    }) =>
+                {
+                    let _ = tx.send(());
+                }
+                Ok(_) => {}
+                Err(e) => tracing::error!("Completion config watch error: {}", e),
+            },
+            notify::Config::default(),
+        )
+        .map_err(watch_error)?;
+
+        let mut watched = Vec::new();
+        for dir in &self.dirs {
+            let target = if dir.is_dir() {
+                dir.clone()
+            } else {
+                match dir.parent().filter(|parent| parent.is_dir()) {
+                    Some(parent) => parent.to_path_buf(),
+                    None => continue,
+                }
+            };
+            if watched.contains(&target) {
+                continue;
+            }
+            watcher
+                .watch(&target, RecursiveMode::NonRecursive)
+                .map_err(watch_error)?;
+            watched.push(target);
+        }
+
+        let manager = Arc::clone(self);
+        let task = tokio::spawn(async move {
+            while rx.recv().await.is_some() {
+                // Editors write in bursts; reload once things settle
+                tokio::time::sleep(manager.debounce).await;
+                while rx.try_recv().is_ok() {}
+                manager.reload().await;
+            }
+        });
+
+        Ok(LanguageConfigWatchHandle {
+            _watcher: watcher,
+            watched,
+            task,
+        })
+    }
+}
+
+/// Configuration files in a directory, in a stable order
+fn config_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && ConfigLoader::is_config_file(path))
+        .collect();
+    files.sort();
+    files
+}
+
+fn watch_error(error: notify::Error) -> CompletionError {
+    CompletionError::ConfigError(format!("Failed to watch completion configs: {}", error))
+}
+
+/// Keeps a configuration watch running
+pub struct LanguageConfigWatchHandle {
+    _watcher: RecommendedWatcher,
+    watched: Vec<PathBuf>,
+    task: JoinHandle<()>,
+}
+
+impl LanguageConfigWatchHandle {
+    /// Directories being watched
+    pub fn watched(&self) -> &[PathBuf] {
+        &self.watched
+    }
+}
+
+impl Drop for LanguageConfigWatchHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WEIGHTS: &str = "ranking_weights: {relevance: 0.5, frequency: 0.3, recency: 0.2}\n";
+
+    fn config_yaml(language: &str, keywords: &str, snippets: &str) -> String {
+        format!(
+            "language: {}\nkeywords: [{}]\nsnippets: {}\n{}",
+            language, keywords, snippets, WEIGHTS
+        )
+    }
+
+    async fn next_event(
+        events: &mut broadcast::Receiver<LanguageConfigEvent>,
+    ) -> LanguageConfigEvent {
+        tokio::time::timeout(Duration::from_secs(5), events.recv())
+            .await
+            .unwrap()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_hot_reload_bumps_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("rust.yaml");
+        std::fs::write(&file, config_yaml("rust", "fn, let", "[]")).unwrap();
+
+        let manager = LanguageConfigManager::load(vec![dir.path().to_path_buf()]).await;
+        assert_eq!(manager.version("rust").await, Some(1));
+
+        let mut events = manager.subscribe();
+        let _handle = manager.watch().unwrap();
+        std::fs::write(&file, config_yaml("rust", "fn, let, match", "[]")).unwrap();
+
+        assert_eq!(
+            next_event(&mut events).await,
+            LanguageConfigEvent::Reloaded {
+                language: "rust".to_string(),
+                version: 2,
+            }
+        );
+        assert_eq!(manager.config("rust").await.unwrap().keywords.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_file_keeps_previous_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let rust = dir.path().join("rust.yaml");
+        std::fs::write(&rust, config_yaml("rust", "fn, let", "[]")).unwrap();
+        std::fs::write(
+            dir.path().join("python.yaml"),
+            config_yaml("python", "def", "[]"),
+        )
+        .unwrap();
+
+        let manager = LanguageConfigManager::load(vec![dir.path().to_path_buf()]).await;
+        let mut events = manager.subscribe();
+
+        // Duplicate snippet labels fail validation
+        std::fs::write(
+            &rust,
+            config_yaml(
+                "rust",
+                "fn",
+                "[{label: fn, template: a}, {label: fn, template: b}]",
+            ),
+        )
+        .unwrap();
+        std::fs::remove_file(dir.path().join("python.yaml")).unwrap();
+        manager.reload().await;
+
+        assert!(matches!(
+            next_event(&mut events).await,
+            LanguageConfigEvent::ReloadFailed { .. }
+        ));
+        assert_eq!(
+            next_event(&mut events).await,
+            LanguageConfigEvent::Removed {
+                language: "python".to_string()
+            }
+        );
+        assert_eq!(manager.version("rust").await, Some(1));
+        assert_eq!(manager.config("rust").await.unwrap().keywords.len(), 2);
+        assert_eq!(manager.version("python").await, None);
+    }
+}
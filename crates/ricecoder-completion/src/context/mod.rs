@@ -7,7 +7,7 @@
 mod analyzer;
 mod generic;
 mod tree_sitter;
-mod utils;
+pub(crate) mod utils;
 
 // Re-export the trait and implementations
 pub use analyzer::ContextAnalyzer;
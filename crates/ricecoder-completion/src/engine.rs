@@ -70,6 +70,7 @@ use async_trait::async_trait;
 ///     "rust",
 /// ).await?;
 /// ```
+use crate::auto_import::AutoImporter;
 use crate::context::ContextAnalyzer;
 use crate::types::*;
 
@@ -142,7 +143,8 @@ pub trait CompletionEngine: Send + Sync {
 ///    - Language-specific provider (if registered)
 ///    - Generic completion generator (fallback)
 /// 4. **Merging**: Merge external and internal completions (external takes priority)
-/// 5. **Ranking**: Rank all completions by relevance, frequency, and recency
+/// 5. **Auto-import**: Attach import edits to internal completions of known symbols, if enabled
+/// 6. **Ranking**: Rank all completions by relevance, frequency, and recency
 ///
 /// # Language Support
 ///
@@ -177,6 +179,7 @@ pub struct GenericCompletionEngine {
     generator: Arc<dyn CompletionGenerator>,
     ranker: Arc<dyn CompletionRanker>,
     provider_registry: ProviderRegistry,
+    auto_importer: Option<Arc<AutoImporter>>,
 }
 
 impl GenericCompletionEngine {
@@ -199,8 +202,15 @@ impl GenericCompletionEngine {
             generator,
             ranker,
             provider_registry,
+            auto_importer: None,
         }
     }
+
+    /// Attach import edits to completions of known symbols
+    pub fn with_auto_import(mut self, auto_importer: Arc<AutoImporter>) -> Self {
+        self.auto_importer = Some(auto_importer);
+        self
+    }
 }
 
 #[async_trait]
@@ -230,6 +240,10 @@ impl CompletionEngine for GenericCompletionEngine {
                 .await?
         };
 
+        if let Some(auto_importer) = &self.auto_importer {
+            auto_importer.add_import_edits(language, code, &mut completions);
+        }
+
         // Rank completions
         completions = self.ranker.rank_completions(completions, &context);
 
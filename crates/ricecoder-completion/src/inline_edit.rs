@@ -0,0 +1,438 @@
+//! Inline edits: rewrite a selection from an instruction
+//!
+//! The editor sends the selected range and an instruction ("add error
+//! handling", "convert to iterator"). [`InlineEditEngine`] asks a provider to
+//! rewrite only the selection, diffs the rewrite against the original with
+//! the [`DiffEngine`], and returns an [`InlineEditProposal`] the user accepts
+//! or rejects. Nothing outside the selection is ever touched.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use ricecoder_files::{DiffEngine, FileDiff};
+use ricecoder_providers::models::{ChatRequest, FinishReason, Message};
+use ricecoder_providers::provider::Provider;
+
+use crate::context::utils::position_to_byte_offset;
+use crate::types::{CompletionError, CompletionResult, Range, TextEdit};
+
+const SYSTEM_PROMPT: &str = "You rewrite a selected piece of code according to an instruction. \
+Reply with the replacement for the selection only, in a single fenced code block, and nothing else. \
+Do not repeat the code before or after the selection. Keep the selection's indentation and style, \
+and change only what the instruction asks for.";
+
+/// Settings for inline edit requests
+#[derive(Debug, Clone)]
+pub struct InlineEditConfig {
+    /// Model to request rewrites from
+    pub model: String,
+    pub temperature: f32,
+    pub max_tokens: usize,
+    /// Lines of surrounding code sent along as context
+    pub context_lines: usize,
+}
+
+impl Default for InlineEditConfig {
+    fn default() -> Self {
+        Self {
+            model: String::new(),
+            temperature: 0.2,
+            max_tokens: 2048,
+            context_lines: 20,
+        }
+    }
+}
+
+/// A selection to rewrite
+#[derive(Debug, Clone)]
+pub struct InlineEditRequest {
+    /// Full document text
+    pub code: String,
+    pub selection: Range,
+    pub instruction: String,
+    pub language: String,
+    /// Document path, used for the diff
+    pub path: Option<PathBuf>,
+}
+
+/// Where a proposal stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InlineEditDecision {
+    Pending,
+    Accepted,
+    Rejected,
+}
+
+/// A proposed rewrite waiting for the user
+#[derive(Debug, Clone)]
+pub struct InlineEditProposal {
+    request: InlineEditRequest,
+    original: String,
+    replacement: String,
+    diff: FileDiff,
+    decision: InlineEditDecision,
+}
+
+impl InlineEditProposal {
+    pub fn request(&self) -> &InlineEditRequest {
+        &self.request
+    }
+
+    /// Selected text before the rewrite
+    pub fn original(&self) -> &str {
+        &self.original
+    }
+
+    pub fn replacement(&self) -> &str {
+        &self.replacement
+    }
+
+    /// Diff of the selection, with line numbers in document coordinates
+    pub fn diff(&self) -> &FileDiff {
+        &self.diff
+    }
+
+    pub fn decision(&self) -> InlineEditDecision {
+        self.decision
+    }
+
+    /// Whether the rewrite changes anything
+    pub fn has_changes(&self) -> bool {
+        !self.diff.hunks.is_empty()
+    }
+
+    /// Document text with the rewrite applied
+    pub fn preview(&self) -> String {
+        let start = position_to_byte_offset(&self.request.code, self.request.selection.start);
+        let end = position_to_byte_offset(&self.request.code, self.request.selection.end);
+        let mut text = String::with_capacity(self.request.code.len() + self.replacement.len());
+        text.push_str(&self.request.code[..start]);
+        text.push_str(&self.replacement);
+        text.push_str(&self.request.code[end..]);
+        text
+    }
+
+    /// Accept the rewrite and get the edit to apply to the document
+    pub fn accept(&mut self) -> CompletionResult<TextEdit> {
+        if self.decision == InlineEditDecision::Rejected {
+            return Err(CompletionError::InternalError(
+                "Inline edit was already rejected".to_string(),
+            ));
+        }
+        self.decision = InlineEditDecision::Accepted;
+        Ok(TextEdit::new(
+            self.request.selection,
+            self.replacement.clone(),
+        ))
+    }
+
+    /// Discard the rewrite
+    pub fn reject(&mut self) {
+        if self.decision == InlineEditDecision::Pending {
+            self.decision = InlineEditDecision::Rejected;
+        }
+    }
+}
+
+/// Requests selection rewrites from a provider
+pub struct InlineEditEngine {
+    provider: Arc<dyn Provider>,
+    config: InlineEditConfig,
+    diff_engine: DiffEngine,
+}
+
+impl InlineEditEngine {
+    pub fn new(provider: Arc<dyn Provider>, config: InlineEditConfig) -> Self {
+        Self {
+            provider,
+            config,
+            diff_engine: DiffEngine::new(),
+        }
+    }
+
+    pub fn config(&self) -> &InlineEditConfig {
+        &self.config
+    }
+
+    /// Ask for a rewrite of the selection
+    pub async fn propose(
+        &self,
+        request: InlineEditRequest,
+    ) -> CompletionResult<InlineEditProposal> {
+        if request.instruction.trim().is_empty() {
+            return Err(CompletionError::GenerationError(
+                "Inline edit needs an instruction".to_string(),
+            ));
+        }
+        let start = position_to_byte_offset(&request.code, request.selection.start);
+        let end = position_to_byte_offset(&request.code, request.selection.end);
+        if start >= end {
+            return Err(CompletionError::InvalidPosition(
+                "Inline edit needs a non-empty selection".to_string(),
+            ));
+        }
+        let original = request.code[start..end].to_string();
+
+        let chat = ChatRequest {
+            model: self.config.model.clone(),
+            messages: vec![
+                Message {
+                    role: "system".to_string(),
+                    content: SYSTEM_PROMPT.to_string(),
+                },
+                Message {
+                    role: "user".to_string(),
+                    content: self.user_prompt(&request, start, end),
+                },
+            ],
+            temperature: Some(self.config.temperature),
+            max_tokens: Some(self.config.max_tokens),
+            stream: false,
+            top_p: None,
+            stop: None,
+            seed: None,
+            reasoning_effort: None,
+        };
+        let response = self
+            .provider
+            .chat(chat)
+            .await
+            .map_err(|e| CompletionError::GenerationError(e.to_string()))?;
+        if response.finish_reason != FinishReason::Stop {
+            return Err(CompletionError::GenerationError(format!(
+                "Inline edit rewrite was cut short ({:?})",
+                response.finish_reason
+            )));
+        }
+
+        let replacement = constrain_rewrite(&original, &response.content)?;
+        let mut diff = self
+            .diff_engine
+            .generate_unified_diff(
+                &original,
+                &replacement,
+                request.path.clone().unwrap_or_default(),
+            )
+            .map_err(|e| CompletionError::InternalError(e.to_string()))?;
+        // Hunks are relative to the selection; report them in document lines
+        let offset = request.selection.start.line as usize;
+        for hunk in &mut diff.hunks {
+            hunk.old_start += offset;
+            hunk.new_start += offset;
+        }
+
+        Ok(InlineEditProposal {
+            request,
+            original,
+            replacement,
+            diff,
+            decision: InlineEditDecision::Pending,
+        })
+    }
+
+    fn user_prompt(&self, request: &InlineEditRequest, start: usize, end: usize) -> String {
+        let before = last_lines(&request.code[..start], self.config.context_lines);
+        let after = first_lines(&request.code[end..], self.config.context_lines);
+        format!(
+            "Language: {}\nInstruction: {}\n\nCode before the selection:\n```\n{}\n```\n\n\
+             Selection to rewrite:\n```\n{}\n```\n\nCode after the selection:\n```\n{}\n```",
+            request.language,
+            request.instruction.trim(),
+            before,
+            &request.code[start..end],
+            after
+        )
+    }
+}
+
+/// Take the replacement out of a reply and fit it to the selection
+///
+/// Unwraps a fenced code block, keeps the selection's trailing newline
+/// convention and restores its indentation if the model dropped it.
+fn constrain_rewrite(original: &str, reply: &str) -> CompletionResult<String> {
+    let mut replacement = fenced_block(reply)
+        .unwrap_or_else(|| reply.trim_matches('\n'))
+        .to_string();
+    if replacement.trim().is_empty() {
+        return Err(CompletionError::GenerationError(
+            "Inline edit rewrite was empty".to_string(),
+        ));
+    }
+
+    let indent = leading_indent(original);
+    if !indent.is_empty() && leading_indent(&replacement).is_empty() {
+        replacement = replacement
+            .lines()
+            .map(|line| {
+                if line.trim().is_empty() {
+                    line.to_string()
+                } else {
+                    format!("{}{}", indent, line)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+    }
+
+    let replacement = replacement.trim_end_matches('\n');
+    if original.ends_with('\n') {
+        Ok(format!("{}\n", replacement))
+    } else {
+        Ok(replacement.to_string())
+    }
+}
+
+/// Contents of the first fenced code block
+fn fenced_block(reply: &str) -> Option<&str> {
+    let open = reply.find("```")?;
+    let body_start = open + reply[open..].find('\n')? + 1;
+    let body_len = reply[body_start..].find("```")?;
+    Some(reply[body_start..body_start + body_len].trim_end_matches(['\n', '\r']))
+}
+
+fn leading_indent(text: &str) -> &str {
+    let line = text
+        .lines()
+        .find(|line| !line.trim().is_empty())
+        .unwrap_or("");
+    &line[..line.len() - line.trim_start().len()]
+}
+
+fn last_lines(text: &str, count: usize) -> &str {
+    let mut start = text.len();
+    for _ in 0..=count {
+        match text[..start].rfind('\n') {
+            Some(index) => start = index,
+            None => return text,
+        }
+    }
+    &text[start + 1..]
+}
+
+fn first_lines(text: &str, count: usize) -> &str {
+    let mut end = 0;
+    for _ in 0..count {
+        match text[end..].find('\n') {
+            Some(index) => end += index + 1,
+            None => return text,
+        }
+    }
+    &text[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use ricecoder_providers::error::ProviderError;
+    use ricecoder_providers::models::{ChatResponse, ModelInfo, TokenUsage};
+    use ricecoder_providers::provider::ChatStream;
+
+    use super::*;
+    use crate::types::Position;
+
+    struct FixedProvider {
+        reply: String,
+    }
+
+    #[async_trait]
+    impl Provider for FixedProvider {
+        fn id(&self) -> &str {
+            "fixed"
+        }
+
+        fn name(&self) -> &str {
+            "Fixed"
+        }
+
+        fn models(&self) -> Vec<ModelInfo> {
+            Vec::new()
+        }
+
+        async fn chat(&self, request: ChatRequest) -> Result<ChatResponse, ProviderError> {
+            assert!(request.messages[1].content.contains("Instruction: rename"));
+            Ok(ChatResponse {
+                content: self.reply.clone(),
+                model: request.model,
+                usage: TokenUsage {
+                    prompt_tokens: 0,
+                    completion_tokens: 0,
+                    total_tokens: 0,
+                },
+                finish_reason: FinishReason::Stop,
+            })
+        }
+
+        async fn chat_stream(&self, _request: ChatRequest) -> Result<ChatStream, ProviderError> {
+            Err(ProviderError::ProviderError("not streamed".to_string()))
+        }
+
+        fn count_tokens(&self, content: &str, _model: &str) -> Result<usize, ProviderError> {
+            Ok(content.len() / 4)
+        }
+
+        async fn health_check(&self) -> Result<bool, ProviderError> {
+            Ok(true)
+        }
+    }
+
+    fn request(code: &str) -> InlineEditRequest {
+        InlineEditRequest {
+            code: code.to_string(),
+            selection: Range::new(Position::new(1, 0), Position::new(3, 0)),
+            instruction: "rename x to total".to_string(),
+            language: "rust".to_string(),
+            path: Some(PathBuf::from("src/main.rs")),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_propose_and_accept() {
+        let code = "fn main() {\n    let x = 1;\n    println!(\"{}\", x);\n}\n";
+        let engine = InlineEditEngine::new(
+            Arc::new(FixedProvider {
+                reply: "Sure:\n```rust\nlet total = 1;\nprintln!(\"{}\", total);\n```\n"
+                    .to_string(),
+            }),
+            InlineEditConfig::default(),
+        );
+
+        let mut proposal = engine.propose(request(code)).await.unwrap();
+        // Indentation and the trailing newline follow the selection
+        assert_eq!(
+            proposal.replacement(),
+            "    let total = 1;\n    println!(\"{}\", total);\n"
+        );
+        assert!(proposal.has_changes());
+        assert_eq!(proposal.diff().hunks[0].old_start, 2);
+        assert_eq!(proposal.diff().stats.additions, 2);
+        assert_eq!(
+            proposal.preview(),
+            "fn main() {\n    let total = 1;\n    println!(\"{}\", total);\n}\n"
+        );
+
+        let edit = proposal.accept().unwrap();
+        assert_eq!(edit.range.start, Position::new(1, 0));
+        assert_eq!(proposal.decision(), InlineEditDecision::Accepted);
+    }
+
+    #[tokio::test]
+    async fn test_reject_and_invalid_requests() {
+        let code = "fn main() {\n    let x = 1;\n    println!(\"{}\", x);\n}\n";
+        let engine = InlineEditEngine::new(
+            Arc::new(FixedProvider {
+                reply: "    let x = 1;\n    println!(\"{}\", x);".to_string(),
+            }),
+            InlineEditConfig::default(),
+        );
+
+        let mut proposal = engine.propose(request(code)).await.unwrap();
+        assert!(!proposal.has_changes());
+        proposal.reject();
+        assert_eq!(proposal.decision(), InlineEditDecision::Rejected);
+        assert!(proposal.accept().is_err());
+
+        let mut empty = request(code);
+        empty.selection = Range::new(Position::new(1, 0), Position::new(1, 0));
+        assert!(engine.propose(empty).await.is_err());
+    }
+}
@@ -74,6 +74,27 @@
 /// - [`TypeScriptCompletionProvider`]: TypeScript-specific completions
 /// - [`PythonCompletionProvider`]: Python-specific completions
 /// - [`GenericTextProvider`]: Generic text-based completions
+/// - [`SpellingCorrectionProvider`]: Wraps a provider to suggest corrections for misspelled
+///   identifiers, ranked below direct matches
+///
+/// # Request Coalescing
+///
+/// [`CoalescingCompletionEngine`] wraps an engine so identical in-flight requests share
+/// one call to the external LSP, and prefetches completions for the next likely context
+/// after the user accepts a completion.
+///
+/// # Auto-Import
+///
+/// Completions may carry `additional_edits`, such as the import statement an external
+/// LSP server attaches. [`AutoImporter`] synthesizes these edits for known symbols when
+/// completions come from internal providers, and [`apply_completion`] applies a
+/// completion together with its edits.
+///
+/// # Inline Edits
+///
+/// [`InlineEditEngine`] rewrites a selected range from an instruction through an LLM
+/// provider and returns an [`InlineEditProposal`] with a diff of the selection, which the
+/// user accepts or rejects.
 ///
 /// # Ghost Text
 ///
@@ -89,6 +110,9 @@
 /// - Ranking weights for relevance, frequency, and recency
 /// - Provider references for language-specific behavior
 ///
+/// [`LanguageConfigManager`] watches the configuration directories and hot-reloads
+/// changed files, bumping each language's version and notifying subscribers.
+///
 /// # Example: Basic Usage
 ///
 /// ```ignore
@@ -148,7 +172,10 @@
 ///
 /// let ghost_text = generator.generate_ghost_text(&completion, Position::new(0, 5))?;
 /// ```
+pub mod auto_import;
+pub mod coalescing;
 pub mod config;
+pub mod config_watcher;
 pub mod context;
 pub mod di;
 pub mod engine;
@@ -156,13 +183,21 @@ pub mod external_lsp_proxy;
 pub mod ghost_text;
 pub mod ghost_text_state;
 pub mod history;
+pub mod inline_edit;
 pub mod language;
 pub mod providers;
 pub mod ranker;
+pub mod spelling;
 pub mod types;
 
 // Re-export public types and traits
+pub use auto_import::{
+    apply_completion, AppliedCompletion, AutoImportConfig, AutoImporter, ImportTemplate,
+    KnownImport,
+};
+pub use coalescing::{CoalescingCompletionEngine, CoalescingConfig, CoalescingStats};
 pub use config::{ConfigFormat, ConfigLoader, LanguageConfigRegistry};
+pub use config_watcher::{LanguageConfigEvent, LanguageConfigManager, LanguageConfigWatchHandle};
 pub use context::{ContextAnalyzer, GenericContextAnalyzer, TreeSitterContextAnalyzer};
 pub use engine::{
     CompletionEngine, CompletionGenerator, CompletionProvider, CompletionRanker,
@@ -178,6 +213,9 @@ pub use ghost_text_state::{
     GhostTextStateManager, PartialAcceptanceMode,
 };
 pub use history::{CompletionHistory, CompletionUsage};
+pub use inline_edit::{
+    InlineEditConfig, InlineEditDecision, InlineEditEngine, InlineEditProposal, InlineEditRequest,
+};
 pub use language::{Language, LanguageDetector};
 pub use providers::{
     CompletionProviderFactory, DartCompletionProvider, GenericTextProvider, GoCompletionProvider,
@@ -185,6 +223,7 @@ pub use providers::{
     RustCompletionProvider, TypeScriptCompletionProvider,
 };
 pub use ranker::{AdvancedCompletionRanker, BasicCompletionRanker};
+pub use spelling::{SpellingCorrectionProvider, WorkspaceSymbols};
 pub use types::*;
 
 // Re-export storage integration
@@ -49,7 +49,7 @@ use async_trait::async_trait;
 use crate::types::*;
 
 /// Helper function to convert symbol kind to completion item kind
-fn symbol_kind_to_completion_kind(kind: SymbolKind) -> CompletionItemKind {
+pub(crate) fn symbol_kind_to_completion_kind(kind: SymbolKind) -> CompletionItemKind {
     match kind {
         SymbolKind::Variable => CompletionItemKind::Variable,
         SymbolKind::Function => CompletionItemKind::Function,
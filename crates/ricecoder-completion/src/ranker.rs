@@ -138,10 +138,13 @@ impl crate::engine::CompletionRanker for BasicCompletionRanker {
             item.score = combined_score.min(1.0);
         }
 
-        // Sort by score (descending), then by label (ascending) for stability
-        filtered.sort_by(|a, b| match b.score.partial_cmp(&a.score) {
-            Some(Ordering::Equal) | None => a.label.cmp(&b.label),
-            other => other.unwrap(),
+        // Sort items whose label matches first, then by score (descending),
+        // then by label (ascending) for stability
+        let prefix = context.prefix.to_lowercase();
+        filtered.sort_by(|a, b| {
+            let a_direct = a.label.to_lowercase().starts_with(&prefix);
+            let b_direct = b.label.to_lowercase().starts_with(&prefix);
+            b_direct.cmp(&a_direct).then_with(|| compare_scores(a, b))
         });
 
         filtered
@@ -240,10 +243,20 @@ impl crate::engine::CompletionRanker for AdvancedCompletionRanker {
             item.score = combined_score.min(1.0);
         }
 
-        // Sort by score (descending), then by label (ascending)
-        filtered.sort_by(|a, b| match b.score.partial_cmp(&a.score) {
-            Some(Ordering::Equal) | None => a.label.cmp(&b.label),
-            other => other.unwrap(),
+        // Sort items whose label matches first, then by score (descending),
+        // then by label (ascending)
+        filtered.sort_by(|a, b| {
+            let a_direct = self
+                .basic_ranker
+                .fuzzy_match_score(&a.label, &context.prefix)
+                > 0.0
+                || context.prefix.is_empty();
+            let b_direct = self
+                .basic_ranker
+                .fuzzy_match_score(&b.label, &context.prefix)
+                > 0.0
+                || context.prefix.is_empty();
+            b_direct.cmp(&a_direct).then_with(|| compare_scores(a, b))
         });
 
         filtered
@@ -258,6 +271,14 @@ impl crate::engine::CompletionRanker for AdvancedCompletionRanker {
     }
 }
 
+/// Score (descending), then label (ascending)
+fn compare_scores(a: &CompletionItem, b: &CompletionItem) -> Ordering {
+    match b.score.partial_cmp(&a.score) {
+        Some(Ordering::Equal) | None => a.label.cmp(&b.label),
+        Some(other) => other,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -357,7 +378,10 @@ mod tests {
         let ranker = BasicCompletionRanker::default_weights();
         let score = ranker.fuzzy_match_score("Test", "test");
         // Case-insensitive matching with Smart case
-        assert!(score > 0.0, "Case-insensitive match should have non-zero score");
+        assert!(
+            score > 0.0,
+            "Case-insensitive match should have non-zero score"
+        );
         assert!(score <= 1.0, "Score should be normalized");
     }
 
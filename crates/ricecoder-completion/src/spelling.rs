@@ -0,0 +1,306 @@
+//! Typo-tolerant completions
+//!
+//! [`SpellingCorrectionProvider`] wraps a fallback provider and adds
+//! corrections for a misspelled prefix: identifiers from the current document,
+//! the analyzed context and an optional [`WorkspaceSymbols`] index that are
+//! within a small edit distance of what was typed. Corrections match through
+//! their `filter_text`, so rankers keep them but order them after completions
+//! whose label matches the prefix directly.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, OnceLock, RwLock};
+
+use async_trait::async_trait;
+
+use crate::engine::CompletionProvider;
+use crate::providers::symbol_kind_to_completion_kind;
+use crate::types::*;
+
+/// Shortest prefix worth correcting
+const MIN_PREFIX_CHARS: usize = 3;
+
+/// Identifiers known across the workspace
+#[derive(Debug, Default)]
+pub struct WorkspaceSymbols {
+    names: RwLock<HashSet<String>>,
+}
+
+impl WorkspaceSymbols {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&self, name: impl Into<String>) {
+        if let Ok(mut names) = self.names.write() {
+            names.insert(name.into());
+        }
+    }
+
+    /// Add every identifier in a document
+    pub fn index_document(&self, code: &str) {
+        if let Ok(mut names) = self.names.write() {
+            names.extend(identifiers(code).map(str::to_string));
+        }
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.names
+            .read()
+            .map(|names| names.contains(name))
+            .unwrap_or(false)
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.read().map(|names| names.len()).unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn snapshot(&self) -> Vec<String> {
+        self.names
+            .read()
+            .map(|names| names.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Provider decorator that suggests corrections for misspelled identifiers
+pub struct SpellingCorrectionProvider {
+    inner: Arc<dyn CompletionProvider>,
+    workspace: Option<Arc<WorkspaceSymbols>>,
+    max_distance: usize,
+    max_suggestions: usize,
+}
+
+impl SpellingCorrectionProvider {
+    pub fn new(inner: Arc<dyn CompletionProvider>) -> Self {
+        Self {
+            inner,
+            workspace: None,
+            max_distance: 2,
+            max_suggestions: 5,
+        }
+    }
+
+    /// Also suggest identifiers from the workspace index
+    pub fn with_workspace_symbols(mut self, workspace: Arc<WorkspaceSymbols>) -> Self {
+        self.workspace = Some(workspace);
+        self
+    }
+
+    /// Largest edit distance to correct; short prefixes allow at most one edit
+    pub fn with_max_distance(mut self, max_distance: usize) -> Self {
+        self.max_distance = max_distance;
+        self
+    }
+
+    pub fn with_max_suggestions(mut self, max_suggestions: usize) -> Self {
+        self.max_suggestions = max_suggestions;
+        self
+    }
+
+    /// Corrections for `prefix` among the candidates, closest first
+    fn corrections(
+        &self,
+        prefix: &str,
+        candidates: HashMap<String, CompletionItemKind>,
+        existing: &HashSet<String>,
+    ) -> Vec<CompletionItem> {
+        let typed: Vec<char> = prefix.to_lowercase().chars().collect();
+        if typed.len() < MIN_PREFIX_CHARS {
+            return Vec::new();
+        }
+        let bound = if typed.len() <= 4 {
+            self.max_distance.min(1)
+        } else {
+            self.max_distance
+        };
+        let prefix_lower = prefix.to_lowercase();
+
+        let mut found: Vec<(usize, String, CompletionItemKind)> = candidates
+            .into_iter()
+            .filter(|(name, _)| !existing.contains(name))
+            .filter(|(name, _)| !name.to_lowercase().starts_with(&prefix_lower))
+            .filter_map(|(name, kind)| {
+                let distance = prefix_distance(&typed, &name.to_lowercase());
+                (distance <= bound).then_some((distance, name, kind))
+            })
+            .collect();
+        found.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+        found.truncate(self.max_suggestions);
+
+        found
+            .into_iter()
+            .map(|(distance, name, kind)| {
+                let mut item = CompletionItem::new(name.clone(), kind, name.clone())
+                    .with_detail(format!("Did you mean `{}`?", name))
+                    .with_score(0.1 / (distance as f32 + 1.0));
+                // Match on what was typed so rankers keep the correction
+                item.filter_text = Some(prefix.to_string());
+                item
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl CompletionProvider for SpellingCorrectionProvider {
+    fn language(&self) -> &str {
+        self.inner.language()
+    }
+
+    async fn generate_completions(
+        &self,
+        code: &str,
+        position: Position,
+        context: &CompletionContext,
+    ) -> CompletionResult<Vec<CompletionItem>> {
+        let mut completions = self
+            .inner
+            .generate_completions(code, position, context)
+            .await?;
+
+        let mut candidates: HashMap<String, CompletionItemKind> = HashMap::new();
+        if let Some(workspace) = &self.workspace {
+            for name in workspace.snapshot() {
+                candidates.insert(name, CompletionItemKind::Text);
+            }
+        }
+        for name in identifiers(code) {
+            candidates.insert(name.to_string(), CompletionItemKind::Text);
+        }
+        for symbol in &context.available_symbols {
+            candidates.insert(
+                symbol.name.clone(),
+                symbol_kind_to_completion_kind(symbol.kind),
+            );
+        }
+
+        let existing: HashSet<String> = completions.iter().map(|c| c.label.clone()).collect();
+        completions.extend(self.corrections(&context.prefix, candidates, &existing));
+        Ok(completions)
+    }
+}
+
+/// Identifiers in source code
+fn identifiers(code: &str) -> impl Iterator<Item = &str> {
+    static IDENTIFIER: OnceLock<regex::Regex> = OnceLock::new();
+    IDENTIFIER
+        .get_or_init(|| {
+            regex::Regex::new(r"[A-Za-z_][A-Za-z0-9_]*").expect("identifier regex is valid")
+        })
+        .find_iter(code)
+        .map(|m| m.as_str())
+}
+
+/// Edit distance between `typed` and the closest prefix of `candidate`
+///
+/// Counts insertions, deletions, substitutions and adjacent transpositions,
+/// so `conut` is one edit from `count`.
+fn prefix_distance(typed: &[char], candidate: &str) -> usize {
+    let candidate: Vec<char> = candidate.chars().collect();
+    let rows = typed.len() + 1;
+    let cols = candidate.len() + 1;
+    let mut d = vec![vec![0usize; cols]; rows];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..rows {
+        for j in 1..cols {
+            let cost = usize::from(typed[i - 1] != candidate[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1
+                && j > 1
+                && typed[i - 1] == candidate[j - 2]
+                && typed[i - 2] == candidate[j - 1]
+            {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    // Any prefix of the candidate may be what the user is typing towards
+    d[typed.len()].iter().copied().min().unwrap_or(typed.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::CompletionRanker;
+    use crate::providers::GenericTextProvider;
+    use crate::ranker::BasicCompletionRanker;
+
+    fn distance(typed: &str, candidate: &str) -> usize {
+        prefix_distance(&typed.chars().collect::<Vec<_>>(), candidate)
+    }
+
+    #[test]
+    fn test_prefix_distance() {
+        assert_eq!(distance("conut", "counter"), 1);
+        assert_eq!(distance("cuont", "counter"), 1);
+        assert_eq!(distance("totl", "total_amount"), 1);
+        assert_eq!(distance("xyz", "counter"), 3);
+    }
+
+    #[tokio::test]
+    async fn test_corrections_rank_below_exact_matches() {
+        let provider = SpellingCorrectionProvider::new(Arc::new(GenericTextProvider));
+        let code = "let counter = 0;\nlet conutry = 1;\nconut";
+        let mut context = CompletionContext::new(
+            "generic".to_string(),
+            Position::new(2, 5),
+            "conut".to_string(),
+        );
+        context.available_symbols.push(Symbol {
+            name: "conutry".to_string(),
+            kind: SymbolKind::Variable,
+            scope: context.scope.clone(),
+            type_info: None,
+            documentation: None,
+        });
+
+        let items = provider
+            .generate_completions(code, Position::new(2, 5), &context)
+            .await
+            .unwrap();
+        let ranked = BasicCompletionRanker::default_weights().rank_completions(items, &context);
+
+        let labels: Vec<&str> = ranked.iter().map(|item| item.label.as_str()).collect();
+        assert_eq!(labels, vec!["conutry", "counter"]);
+        assert_eq!(ranked[1].detail.as_deref(), Some("Did you mean `counter`?"));
+    }
+
+    #[tokio::test]
+    async fn test_workspace_symbols_and_short_prefixes() {
+        let workspace = Arc::new(WorkspaceSymbols::new());
+        workspace.index_document("fn render_frame() {}");
+        let provider = SpellingCorrectionProvider::new(Arc::new(GenericTextProvider))
+            .with_workspace_symbols(workspace);
+
+        let context = CompletionContext::new(
+            "generic".to_string(),
+            Position::new(0, 5),
+            "rnder".to_string(),
+        );
+        let items = provider
+            .generate_completions("rnder", Position::new(0, 5), &context)
+            .await
+            .unwrap();
+        assert!(items.iter().any(|item| item.label == "render_frame"));
+
+        // Two characters are too few to guess from
+        let context =
+            CompletionContext::new("generic".to_string(), Position::new(0, 2), "rn".to_string());
+        let items = provider
+            .generate_completions("rn", Position::new(0, 2), &context)
+            .await
+            .unwrap();
+        assert!(items.is_empty());
+    }
+}
@@ -240,7 +240,7 @@ impl GhostText {
 }
 
 /// Ranking weights for completion scoring
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RankingWeights {
     pub relevance: f32,
     pub frequency: f32,
@@ -258,7 +258,7 @@ impl Default for RankingWeights {
 }
 
 /// Completion configuration for a language
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CompletionConfig {
     pub language: String,
     pub keywords: Vec<String>,
@@ -280,7 +280,7 @@ impl CompletionConfig {
 }
 
 /// Snippet template for code completion
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CompletionSnippet {
     pub label: String,
     pub template: String,
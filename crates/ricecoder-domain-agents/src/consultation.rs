@@ -0,0 +1,89 @@
+//! Cross-domain consultation between domain agents
+//!
+//! An agent can pose scoped sub-questions to other domains (e.g. the frontend
+//! agent asking the backend agent about an API contract) by returning
+//! [`ConsultationRequest`]s from
+//! [`DomainAgent::plan_consultations`](crate::DomainAgent::plan_consultations).
+//! The [`DomainAgentRegistryManager`](crate::DomainAgentRegistryManager)
+//! answers them before running the asking agent, enforcing a depth limit and
+//! refusing cycles, and merges the answers into the output with attribution.
+
+use serde::{Deserialize, Serialize};
+
+/// Default maximum nesting of consultations
+pub const DEFAULT_MAX_CONSULTATION_DEPTH: usize = 2;
+
+/// Input parameter under which answered consultations are passed to the asking agent
+pub const CONSULTATIONS_PARAMETER: &str = "consultations";
+
+/// A scoped question one domain agent poses to another
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConsultationRequest {
+    /// Domain to consult
+    pub domain: String,
+    /// The sub-question
+    pub question: String,
+    /// Context passed to the consulted agent
+    pub context: String,
+}
+
+impl ConsultationRequest {
+    /// Create a consultation request
+    pub fn new(
+        domain: impl Into<String>,
+        question: impl Into<String>,
+        context: impl Into<String>,
+    ) -> Self {
+        Self {
+            domain: domain.into(),
+            question: question.into(),
+            context: context.into(),
+        }
+    }
+}
+
+/// An answered consultation, attributed to the consulted domain
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Consultation {
+    /// Domain that asked the question
+    pub requested_by: String,
+    /// Domain that answered
+    pub domain: String,
+    /// The sub-question
+    pub question: String,
+    /// Answer from the consulted agent
+    pub response: String,
+    /// Suggestions from the consulted agent
+    pub suggestions: Vec<String>,
+    /// Confidence of the consulted agent
+    pub confidence: f32,
+    /// Nesting depth (1 for a direct consultation)
+    pub depth: usize,
+}
+
+/// Why a consultation was not answered
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConsultationSkipReason {
+    /// The consulted domain is already part of the consultation chain
+    Cycle,
+    /// The maximum consultation depth was reached
+    DepthLimit,
+    /// No agent is registered for the consulted domain
+    UnknownDomain,
+    /// The consulted agent failed
+    Failed(String),
+}
+
+/// A consultation that was not answered
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkippedConsultation {
+    /// Domain that asked the question
+    pub requested_by: String,
+    /// Domain that was to be consulted
+    pub domain: String,
+    /// The sub-question
+    pub question: String,
+    /// Why it was skipped
+    pub reason: ConsultationSkipReason,
+}
@@ -1,12 +1,14 @@
 //! Specialized domain agents
 
-use std::collections::HashMap;
+use std::{collections::HashMap, path::Path, sync::Arc};
 
 use async_trait::async_trait;
+use ricecoder_security::{vulnerability::VulnerabilitySeverity, VulnerabilityScanner};
 use serde::{Deserialize, Serialize};
-use tracing::debug;
+use tracing::{debug, warn};
 
 use crate::{
+    consultation::{Consultation, ConsultationRequest, SkippedConsultation},
     error::Result,
     models::{DomainAgentConfig, DomainAgentMetadata},
 };
@@ -37,6 +39,12 @@ pub struct DomainAgentOutput {
     pub confidence: f32,
     /// Metadata about the execution
     pub metadata: HashMap<String, serde_json::Value>,
+    /// Answers from other domains this output draws on
+    #[serde(default)]
+    pub consultations: Vec<Consultation>,
+    /// Consultations that were requested but not answered
+    #[serde(default)]
+    pub skipped_consultations: Vec<SkippedConsultation>,
 }
 
 /// Trait for domain-specific agents
@@ -53,6 +61,14 @@ pub trait DomainAgent: Send + Sync {
 
     /// Validate input for this agent
     fn validate_input(&self, input: &DomainAgentInput) -> Result<()>;
+
+    /// Sub-questions to put to other domains before executing
+    ///
+    /// Answers are passed to [`execute`](Self::execute) under the
+    /// `consultations` input parameter and merged into the output.
+    fn plan_consultations(&self, _input: &DomainAgentInput) -> Vec<ConsultationRequest> {
+        Vec::new()
+    }
 }
 
 /// Frontend domain agent
@@ -124,6 +140,8 @@ impl DomainAgent for FrontendAgent {
             ],
             confidence: 0.85,
             metadata: HashMap::new(),
+            consultations: Vec::new(),
+            skipped_consultations: Vec::new(),
         })
     }
 
@@ -135,6 +153,25 @@ impl DomainAgent for FrontendAgent {
         }
         Ok(())
     }
+
+    fn plan_consultations(&self, input: &DomainAgentInput) -> Vec<ConsultationRequest> {
+        let text = format!("{} {}", input.task, input.context).to_lowercase();
+        if ["api", "endpoint", "request", "fetch"]
+            .iter()
+            .any(|keyword| text.contains(keyword))
+        {
+            vec![ConsultationRequest::new(
+                "backend",
+                format!(
+                    "What API contract should the frontend rely on for: {}",
+                    input.task
+                ),
+                input.context.clone(),
+            )]
+        } else {
+            Vec::new()
+        }
+    }
 }
 
 /// Backend domain agent
@@ -206,6 +243,8 @@ impl DomainAgent for BackendAgent {
             ],
             confidence: 0.88,
             metadata: HashMap::new(),
+            consultations: Vec::new(),
+            skipped_consultations: Vec::new(),
         })
     }
 
@@ -288,6 +327,8 @@ impl DomainAgent for DevOpsAgent {
             ],
             confidence: 0.82,
             metadata: HashMap::new(),
+            consultations: Vec::new(),
+            skipped_consultations: Vec::new(),
         })
     }
 
@@ -301,6 +342,313 @@ impl DomainAgent for DevOpsAgent {
     }
 }
 
+/// Security domain agent
+///
+/// Combines threat modeling and secure-coding guidance with dependency and
+/// code scans. Scans run when a scanner is configured and the input carries a
+/// `manifest_path` (dependency CVEs) or `source_path` (code issues) parameter.
+pub struct SecurityAgent {
+    metadata: DomainAgentMetadata,
+    config: DomainAgentConfig,
+    scanner: Option<Arc<dyn VulnerabilityScanner>>,
+}
+
+impl SecurityAgent {
+    /// Create a new security agent
+    pub fn new() -> Self {
+        Self {
+            metadata: DomainAgentMetadata {
+                domain: "security".to_string(),
+                name: "Security Agent".to_string(),
+                version: "1.0.0".to_string(),
+                capabilities: vec![
+                    "threat-modeling".to_string(),
+                    "dependency-audit".to_string(),
+                    "secure-coding".to_string(),
+                    "secrets-detection".to_string(),
+                ],
+                required_tools: vec!["code-analysis".to_string()],
+                optional_tools: vec!["vulnerability-scanning".to_string()],
+            },
+            config: DomainAgentConfig {
+                domain: "security".to_string(),
+                name: "Security Agent".to_string(),
+                description: "Specialized agent for application security".to_string(),
+                system_prompt: "You are an application security engineer. Model threats with STRIDE, flag vulnerable dependencies, and recommend secure-coding fixes with concrete code changes.".to_string(),
+                tools: vec!["code-analysis".to_string(), "vulnerability-scanning".to_string()],
+                model: None,
+                temperature: Some(0.3),
+                max_tokens: Some(2000),
+                custom_config: HashMap::new(),
+            },
+            scanner: None,
+        }
+    }
+
+    /// Scan dependencies and code with `scanner` when paths are provided
+    pub fn with_scanner(mut self, scanner: Arc<dyn VulnerabilityScanner>) -> Self {
+        self.scanner = Some(scanner);
+        self
+    }
+
+    async fn run_scans(
+        &self,
+        scanner: &dyn VulnerabilityScanner,
+        input: &DomainAgentInput,
+        suggestions: &mut Vec<String>,
+        metadata: &mut HashMap<String, serde_json::Value>,
+    ) {
+        if let Some(manifest) = input
+            .parameters
+            .get("manifest_path")
+            .and_then(|v| v.as_str())
+        {
+            match scanner.scan_dependencies(Path::new(manifest)).await {
+                Ok(result) => {
+                    let mut vulnerabilities = result.vulnerabilities;
+                    vulnerabilities.sort_by(|a, b| b.severity.cmp(&a.severity));
+                    for vulnerability in &vulnerabilities {
+                        suggestions.push(format!(
+                            "Upgrade {} {} to fix {} ({:?}): {}",
+                            vulnerability.package,
+                            vulnerability.version,
+                            vulnerability.id,
+                            vulnerability.severity,
+                            vulnerability.description
+                        ));
+                    }
+                    metadata.insert(
+                        "vulnerabilities".to_string(),
+                        serde_json::to_value(&vulnerabilities).unwrap_or_default(),
+                    );
+                }
+                Err(e) => {
+                    warn!("Dependency scan failed: {}", e);
+                    metadata.insert(
+                        "dependency_scan_error".to_string(),
+                        serde_json::Value::String(e.to_string()),
+                    );
+                }
+            }
+        }
+
+        if let Some(source) = input.parameters.get("source_path").and_then(|v| v.as_str()) {
+            match scanner.scan_code(Path::new(source)).await {
+                Ok(result) => {
+                    for issue in result
+                        .issues
+                        .iter()
+                        .filter(|issue| issue.severity >= VulnerabilitySeverity::High)
+                    {
+                        suggestions.push(format!(
+                            "{}:{}: {} ({})",
+                            issue.file, issue.line, issue.description, issue.rule
+                        ));
+                    }
+                    metadata.insert(
+                        "code_issues".to_string(),
+                        serde_json::to_value(&result.issues).unwrap_or_default(),
+                    );
+                }
+                Err(e) => {
+                    warn!("Code scan failed: {}", e);
+                    metadata.insert(
+                        "code_scan_error".to_string(),
+                        serde_json::Value::String(e.to_string()),
+                    );
+                }
+            }
+        }
+    }
+}
+
+impl Default for SecurityAgent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl DomainAgent for SecurityAgent {
+    fn metadata(&self) -> &DomainAgentMetadata {
+        &self.metadata
+    }
+
+    fn config(&self) -> &DomainAgentConfig {
+        &self.config
+    }
+
+    async fn execute(&self, input: DomainAgentInput) -> Result<DomainAgentOutput> {
+        debug!("Security agent executing task: {}", input.task);
+
+        self.validate_input(&input)?;
+
+        let mut suggestions = Vec::new();
+        let mut metadata = HashMap::new();
+        if let Some(scanner) = &self.scanner {
+            self.run_scans(scanner.as_ref(), &input, &mut suggestions, &mut metadata)
+                .await;
+        }
+
+        if input.task.to_lowercase().contains("threat") {
+            suggestions.push(
+                "Walk each trust boundary through STRIDE: spoofing, tampering, repudiation, information disclosure, denial of service, elevation of privilege".to_string(),
+            );
+        }
+        suggestions.extend([
+            "Validate and encode all untrusted input at trust boundaries".to_string(),
+            "Keep secrets out of source control and rotate exposed credentials".to_string(),
+            "Pin dependencies and audit them for known CVEs in CI".to_string(),
+        ]);
+
+        Ok(DomainAgentOutput {
+            domain: "security".to_string(),
+            response: format!("Security analysis for: {}", input.task),
+            suggestions,
+            confidence: 0.84,
+            metadata,
+            consultations: Vec::new(),
+            skipped_consultations: Vec::new(),
+        })
+    }
+
+    fn validate_input(&self, input: &DomainAgentInput) -> Result<()> {
+        if input.domain != "security" {
+            return Err(crate::error::DomainAgentError::InvalidConfiguration(
+                "Domain mismatch".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Database domain agent
+pub struct DatabaseAgent {
+    metadata: DomainAgentMetadata,
+    config: DomainAgentConfig,
+}
+
+impl DatabaseAgent {
+    /// Create a new database agent
+    pub fn new() -> Self {
+        Self {
+            metadata: DomainAgentMetadata {
+                domain: "database".to_string(),
+                name: "Database Agent".to_string(),
+                version: "1.0.0".to_string(),
+                capabilities: vec![
+                    "schema-design".to_string(),
+                    "migration-review".to_string(),
+                    "query-optimization".to_string(),
+                    "indexing".to_string(),
+                ],
+                required_tools: vec!["code-analysis".to_string()],
+                optional_tools: vec!["query-analysis".to_string()],
+            },
+            config: DomainAgentConfig {
+                domain: "database".to_string(),
+                name: "Database Agent".to_string(),
+                description: "Specialized agent for database design and optimization".to_string(),
+                system_prompt: "You are an expert database engineer specializing in PostgreSQL, MySQL, and SQLite. Review schemas and migrations for safety, and optimize queries and indexes.".to_string(),
+                tools: vec!["code-analysis".to_string(), "query-analysis".to_string()],
+                model: None,
+                temperature: Some(0.5),
+                max_tokens: Some(2000),
+                custom_config: HashMap::new(),
+            },
+        }
+    }
+
+    /// Flag risky statements in SQL context
+    fn review_sql(sql: &str) -> Vec<String> {
+        let upper = sql.to_uppercase();
+        let mut findings = Vec::new();
+
+        if upper.contains("DROP TABLE") || upper.contains("DROP COLUMN") {
+            findings.push(
+                "Migration drops data; ship it separately after a release that stops using the column or table".to_string(),
+            );
+        }
+        if upper.contains("ADD COLUMN") && upper.contains("NOT NULL") && !upper.contains("DEFAULT")
+        {
+            findings.push(
+                "Adding a NOT NULL column without a default fails on non-empty tables; add a default or backfill first".to_string(),
+            );
+        }
+        if upper.contains("CREATE INDEX") && !upper.contains("CONCURRENTLY") {
+            findings.push(
+                "Create indexes CONCURRENTLY (PostgreSQL) to avoid locking writes during the migration".to_string(),
+            );
+        }
+        if upper.contains("SELECT *") {
+            findings.push("Select only the columns you need instead of SELECT *".to_string());
+        }
+        for statement in upper.split(';') {
+            let statement = statement.trim_start();
+            if (statement.starts_with("UPDATE") || statement.starts_with("DELETE"))
+                && !statement.contains("WHERE")
+            {
+                findings.push(
+                    "UPDATE/DELETE without WHERE affects every row; confirm this is intended"
+                        .to_string(),
+                );
+                break;
+            }
+        }
+
+        findings
+    }
+}
+
+impl Default for DatabaseAgent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl DomainAgent for DatabaseAgent {
+    fn metadata(&self) -> &DomainAgentMetadata {
+        &self.metadata
+    }
+
+    fn config(&self) -> &DomainAgentConfig {
+        &self.config
+    }
+
+    async fn execute(&self, input: DomainAgentInput) -> Result<DomainAgentOutput> {
+        debug!("Database agent executing task: {}", input.task);
+
+        self.validate_input(&input)?;
+
+        let mut suggestions = Self::review_sql(&input.context);
+        suggestions.extend([
+            "Index columns used in joins and frequent filters".to_string(),
+            "Make migrations reversible and test them against production-sized data".to_string(),
+            "Check query plans with EXPLAIN before and after changes".to_string(),
+        ]);
+
+        Ok(DomainAgentOutput {
+            domain: "database".to_string(),
+            response: format!("Database analysis for: {}", input.task),
+            suggestions,
+            confidence: 0.86,
+            metadata: HashMap::new(),
+            consultations: Vec::new(),
+            skipped_consultations: Vec::new(),
+        })
+    }
+
+    fn validate_input(&self, input: &DomainAgentInput) -> Result<()> {
+        if input.domain != "database" {
+            return Err(crate::error::DomainAgentError::InvalidConfiguration(
+                "Domain mismatch".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -384,4 +732,86 @@ mod tests {
         let result = agent.execute(input).await;
         assert!(result.is_err());
     }
+
+    struct FakeScanner;
+
+    #[async_trait]
+    impl VulnerabilityScanner for FakeScanner {
+        async fn scan_dependencies(
+            &self,
+            _manifest_path: &Path,
+        ) -> anyhow::Result<ricecoder_security::VulnerabilityScanResult> {
+            Ok(ricecoder_security::VulnerabilityScanResult {
+                vulnerabilities: vec![ricecoder_security::vulnerability::Vulnerability {
+                    id: "RUSTSEC-2024-0001".to_string(),
+                    package: "openssl".to_string(),
+                    version: "0.10.0".to_string(),
+                    severity: VulnerabilitySeverity::Critical,
+                    description: "Use after free".to_string(),
+                    advisory_url: None,
+                    cvss_score: Some(9.1),
+                }],
+                scan_duration: std::time::Duration::from_millis(1),
+                scan_timestamp: chrono::Utc::now(),
+            })
+        }
+
+        async fn scan_code(
+            &self,
+            _source_path: &Path,
+        ) -> anyhow::Result<ricecoder_security::CodeSecurityScanResult> {
+            anyhow::bail!("scanner offline")
+        }
+
+        async fn scan_config(
+            &self,
+            _config_path: &Path,
+        ) -> anyhow::Result<ricecoder_security::ConfigSecurityScanResult> {
+            unimplemented!()
+        }
+
+        async fn scan_licenses(
+            &self,
+            _manifest_path: &Path,
+        ) -> anyhow::Result<ricecoder_security::LicenseScanResult> {
+            unimplemented!()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_security_agent_reports_dependency_cves() {
+        let agent = SecurityAgent::new().with_scanner(Arc::new(FakeScanner));
+        let mut parameters = HashMap::new();
+        parameters.insert("manifest_path".to_string(), serde_json::json!("Cargo.toml"));
+        parameters.insert("source_path".to_string(), serde_json::json!("src"));
+        let input = DomainAgentInput {
+            domain: "security".to_string(),
+            task: "Threat model the login flow".to_string(),
+            context: String::new(),
+            parameters,
+        };
+
+        let output = agent.execute(input).await.unwrap();
+        assert_eq!(output.domain, "security");
+        assert!(output.suggestions[0].contains("RUSTSEC-2024-0001"));
+        assert!(output.suggestions.iter().any(|s| s.contains("STRIDE")));
+        assert!(output.metadata.contains_key("vulnerabilities"));
+        assert!(output.metadata.contains_key("code_scan_error"));
+    }
+
+    #[tokio::test]
+    async fn test_database_agent_reviews_migration() {
+        let agent = DatabaseAgent::new();
+        let input = DomainAgentInput {
+            domain: "database".to_string(),
+            task: "Review migration".to_string(),
+            context: "ALTER TABLE users ADD COLUMN tenant_id INT NOT NULL; DELETE FROM sessions;"
+                .to_string(),
+            parameters: HashMap::new(),
+        };
+
+        let output = agent.execute(input).await.unwrap();
+        assert!(output.suggestions[0].contains("NOT NULL"));
+        assert!(output.suggestions[1].contains("without WHERE"));
+    }
 }
@@ -1,16 +1,43 @@
 //! Domain knowledge base management
 
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
 use ricecoder_storage::PathResolver;
-use tracing::{debug, info};
+use tokio::{sync::RwLock, task::JoinHandle};
+use tracing::{debug, info, warn};
 
 use crate::{
     error::{DomainAgentError, Result},
+    knowledge_source::{build_sources, KnowledgeSource, KnowledgeSourceConfig, McpResourceClient},
     models::{Domain, KnowledgeBase, KnowledgeEntry},
 };
 
+/// An external source attached to a domain's knowledge base
+struct AttachedSource {
+    domain: String,
+    source: Arc<dyn KnowledgeSource>,
+    refresh_interval: Option<Duration>,
+    last_refreshed: Option<Instant>,
+}
+
+impl AttachedSource {
+    fn is_due(&self, now: Instant) -> bool {
+        match (self.last_refreshed, self.refresh_interval) {
+            (None, _) => true,
+            (Some(last), Some(interval)) => now.duration_since(last) >= interval,
+            (Some(_), None) => false,
+        }
+    }
+}
+
 /// Knowledge base manager for domain-specific agents
 pub struct KnowledgeBaseManager {
     knowledge_bases: std::collections::HashMap<String, KnowledgeBase>,
+    sources: Vec<AttachedSource>,
 }
 
 impl KnowledgeBaseManager {
@@ -18,6 +45,7 @@ impl KnowledgeBaseManager {
     pub fn new() -> Self {
         Self {
             knowledge_bases: std::collections::HashMap::new(),
+            sources: Vec::new(),
         }
     }
 }
@@ -42,11 +70,11 @@ impl KnowledgeBaseManager {
             info!("Loaded knowledge base for domain: {}", domain);
         } else {
             debug!(
-                "Knowledge base not found for domain: {}, creating empty",
+                "Knowledge base not found for domain: {}, using built-in entries",
                 domain
             );
-            let kb = KnowledgeBase::new(domain, "1.0.0");
-            self.knowledge_bases.insert(domain.to_string(), kb);
+            self.knowledge_bases
+                .insert(domain.to_string(), builtin_knowledge_base(domain));
         }
 
         Ok(())
@@ -125,6 +153,279 @@ impl KnowledgeBaseManager {
     pub fn get_loaded_domains(&self) -> Vec<&str> {
         self.knowledge_bases.keys().map(|s| s.as_str()).collect()
     }
+
+    /// Search a domain's entries by free text
+    ///
+    /// Matches titles, descriptions and tags case-insensitively. Entries from
+    /// external sources carry their origin in `references`, so results can be
+    /// cited directly.
+    pub fn search(&self, domain: &str, query: &str) -> Result<Vec<&KnowledgeEntry>> {
+        let kb = self.get_knowledge_base(domain)?;
+        let query = query.to_lowercase();
+        Ok(kb
+            .entries
+            .iter()
+            .filter(|entry| {
+                entry.title.to_lowercase().contains(&query)
+                    || entry.description.to_lowercase().contains(&query)
+                    || entry.tags.iter().any(|t| t.to_lowercase().contains(&query))
+            })
+            .collect())
+    }
+
+    /// Attach an external knowledge source to a domain
+    ///
+    /// With a `refresh_interval` the source is refreshed by
+    /// [`refresh_due_sources`](Self::refresh_due_sources) once the interval has
+    /// elapsed; without one it is only fetched on its first refresh and when
+    /// [`refresh_domain`](Self::refresh_domain) is called.
+    pub fn add_source(
+        &mut self,
+        domain: &str,
+        source: Arc<dyn KnowledgeSource>,
+        refresh_interval: Option<Duration>,
+    ) {
+        debug!(
+            "Attaching knowledge source {} to domain {}",
+            source.id(),
+            domain
+        );
+        self.sources.push(AttachedSource {
+            domain: domain.to_string(),
+            source,
+            refresh_interval,
+            last_refreshed: None,
+        });
+    }
+
+    /// Attach sources from configuration
+    ///
+    /// Each source is attached to every domain it lists. MCP sources are
+    /// resolved against `mcp_clients`; sources that cannot be built are
+    /// skipped with a warning. Returns the number of attachments made.
+    pub fn configure_sources(
+        &mut self,
+        configs: &[KnowledgeSourceConfig],
+        mcp_clients: &HashMap<String, Arc<dyn McpResourceClient>>,
+    ) -> usize {
+        let mut attached = 0;
+        for (config, source) in build_sources(configs, mcp_clients) {
+            for domain in &config.domains {
+                self.add_source(domain, source.clone(), config.refresh_interval());
+                attached += 1;
+            }
+        }
+        attached
+    }
+
+    /// IDs of the sources attached to a domain
+    pub fn get_source_ids(&self, domain: &str) -> Vec<&str> {
+        self.sources
+            .iter()
+            .filter(|attached| attached.domain == domain)
+            .map(|attached| attached.source.id())
+            .collect()
+    }
+
+    /// Refresh every source attached to a domain
+    ///
+    /// Returns the number of sources refreshed successfully. A failing source
+    /// keeps its previously fetched entries.
+    pub async fn refresh_domain(&mut self, domain: &str) -> Result<usize> {
+        let indices: Vec<usize> = (0..self.sources.len())
+            .filter(|&i| self.sources[i].domain == domain)
+            .collect();
+        self.refresh_sources(&indices).await
+    }
+
+    /// Refresh sources whose refresh interval has elapsed
+    ///
+    /// Sources that have never been fetched are always due.
+    pub async fn refresh_due_sources(&mut self) -> Result<usize> {
+        let indices = self.due_source_indices(Instant::now());
+        self.refresh_sources(&indices).await
+    }
+
+    fn due_source_indices(&self, now: Instant) -> Vec<usize> {
+        (0..self.sources.len())
+            .filter(|&i| self.sources[i].is_due(now))
+            .collect()
+    }
+
+    async fn refresh_sources(&mut self, indices: &[usize]) -> Result<usize> {
+        let mut refreshed = 0;
+        for &index in indices {
+            let source = self.sources[index].source.clone();
+            match source.fetch().await {
+                Ok(entries) => {
+                    let domain = self.sources[index].domain.clone();
+                    self.apply_source_entries(&domain, source.id(), entries);
+                    refreshed += 1;
+                }
+                Err(e) => warn!("Failed to refresh knowledge source {}: {}", source.id(), e),
+            }
+            self.sources[index].last_refreshed = Some(Instant::now());
+        }
+        Ok(refreshed)
+    }
+
+    /// Replace a source's entries in a domain's knowledge base
+    fn apply_source_entries(
+        &mut self,
+        domain: &str,
+        source_id: &str,
+        entries: Vec<KnowledgeEntry>,
+    ) {
+        let prefix = format!("{}:", source_id);
+        let kb = self
+            .knowledge_bases
+            .entry(domain.to_string())
+            .or_insert_with(|| KnowledgeBase::new(domain, "1.0.0"));
+
+        kb.entries.retain(|entry| !entry.id.starts_with(&prefix));
+        let count = entries.len();
+        for mut entry in entries {
+            entry.id = format!("{}{}", prefix, entry.id);
+            kb.add_entry(entry);
+        }
+        kb.metadata.insert(
+            format!("source.{}.refreshed_at", source_id),
+            chrono::Utc::now().to_rfc3339(),
+        );
+
+        info!(
+            "Refreshed {} entries from source {} into domain {}",
+            count, source_id, domain
+        );
+    }
+
+    /// Periodically refresh due sources in the background
+    ///
+    /// Sources are fetched without holding the write lock, so agents can keep
+    /// reading the knowledge base while slow sources refresh.
+    pub fn spawn_refresh(manager: Arc<RwLock<Self>>, tick: Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(tick);
+            loop {
+                interval.tick().await;
+
+                let due: Vec<(usize, Arc<dyn KnowledgeSource>)> = {
+                    let manager = manager.read().await;
+                    manager
+                        .due_source_indices(Instant::now())
+                        .into_iter()
+                        .map(|i| (i, manager.sources[i].source.clone()))
+                        .collect()
+                };
+
+                for (index, source) in due {
+                    let fetched = source.fetch().await;
+                    let mut manager = manager.write().await;
+                    match fetched {
+                        Ok(entries) => {
+                            let domain = manager.sources[index].domain.clone();
+                            manager.apply_source_entries(&domain, source.id(), entries);
+                        }
+                        Err(e) => {
+                            warn!("Failed to refresh knowledge source {}: {}", source.id(), e)
+                        }
+                    }
+                    manager.sources[index].last_refreshed = Some(Instant::now());
+                }
+            }
+        })
+    }
+}
+
+/// Built-in knowledge base for a domain
+///
+/// Domains without built-in knowledge get an empty knowledge base.
+pub fn builtin_knowledge_base(domain: &str) -> KnowledgeBase {
+    let mut kb = KnowledgeBase::new(domain, "1.0.0");
+    let entries: &[(&str, &str, &str, &str, &[&str], &str)] = match domain {
+        "security" => &[
+            (
+                "stride-threat-modeling",
+                "threat_modeling",
+                "STRIDE threat modeling",
+                "Enumerate spoofing, tampering, repudiation, information disclosure, denial of service and elevation of privilege threats for each trust boundary in a data-flow diagram.",
+                &["threat-modeling", "stride"],
+                "https://learn.microsoft.com/azure/security/develop/threat-modeling-tool-threats",
+            ),
+            (
+                "dependency-cve-audit",
+                "best_practices",
+                "Audit dependencies for known CVEs",
+                "Run cargo audit, npm audit or pip-audit in CI and fail builds on high or critical advisories.",
+                &["dependencies", "cve", "supply-chain"],
+                "https://rustsec.org",
+            ),
+            (
+                "parameterized-queries",
+                "secure_coding",
+                "Use parameterized queries",
+                "Never build SQL by string concatenation; bind parameters so user input cannot change the query structure.",
+                &["injection", "sql"],
+                "https://owasp.org/www-community/attacks/SQL_Injection",
+            ),
+            (
+                "secret-management",
+                "secure_coding",
+                "Keep secrets out of source",
+                "Load credentials from a secret manager or environment, scan commits for secrets, and rotate anything that leaks.",
+                &["secrets", "credentials"],
+                "https://owasp.org/www-project-top-ten/",
+            ),
+        ],
+        "database" => &[
+            (
+                "expand-contract-migrations",
+                "migrations",
+                "Expand/contract migrations",
+                "Add new columns and tables first, migrate readers and writers, then drop the old structures in a later release so every deploy is backward compatible.",
+                &["migration", "zero-downtime"],
+                "https://martinfowler.com/bliki/ParallelChange.html",
+            ),
+            (
+                "index-foreign-keys",
+                "schema_design",
+                "Index foreign keys and filter columns",
+                "Foreign keys and columns in frequent WHERE, JOIN and ORDER BY clauses should be indexed; verify with EXPLAIN.",
+                &["index", "performance"],
+                "https://use-the-index-luke.com",
+            ),
+            (
+                "avoid-n-plus-one",
+                "query_optimization",
+                "Avoid N+1 queries",
+                "Load related rows with joins or batched IN queries instead of one query per parent row.",
+                &["orm", "performance"],
+                "https://use-the-index-luke.com/sql/join/nested-loops-join-n1-problem",
+            ),
+            (
+                "normalize-then-denormalize",
+                "schema_design",
+                "Normalize first, denormalize deliberately",
+                "Start from third normal form and denormalize only for measured read hot paths, documenting how copies stay consistent.",
+                &["normalization", "schema"],
+                "https://en.wikipedia.org/wiki/Third_normal_form",
+            ),
+        ],
+        _ => &[],
+    };
+
+    for (id, category, title, description, tags, reference) in entries {
+        kb.add_entry(KnowledgeEntry {
+            id: id.to_string(),
+            category: category.to_string(),
+            title: title.to_string(),
+            description: description.to_string(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            example: None,
+            references: vec![reference.to_string()],
+        });
+    }
+    kb
 }
 
 #[cfg(test)]
@@ -191,4 +492,80 @@ mod tests {
         let results = kb.search_by_category("patterns");
         assert_eq!(results.len(), 1);
     }
+
+    struct CountingSource {
+        fetches: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl KnowledgeSource for CountingSource {
+        fn id(&self) -> &str {
+            "team"
+        }
+
+        async fn fetch(&self) -> Result<Vec<KnowledgeEntry>> {
+            let n = self
+                .fetches
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(vec![KnowledgeEntry {
+                id: "runbook".to_string(),
+                category: "documentation".to_string(),
+                title: format!("Runbook v{}", n + 1),
+                description: "Rotate database credentials quarterly".to_string(),
+                tags: vec!["ops".to_string()],
+                example: None,
+                references: vec!["docs/runbook.md".to_string()],
+            }])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_source_refresh_replaces_entries() {
+        let mut manager = KnowledgeBaseManager::new();
+        let source = Arc::new(CountingSource {
+            fetches: std::sync::atomic::AtomicUsize::new(0),
+        });
+        manager.add_source("backend", source.clone(), None);
+        assert_eq!(manager.get_source_ids("backend"), vec!["team"]);
+
+        assert_eq!(manager.refresh_due_sources().await.unwrap(), 1);
+        // Not due again without an interval
+        assert_eq!(manager.refresh_due_sources().await.unwrap(), 0);
+        assert_eq!(manager.refresh_domain("backend").await.unwrap(), 1);
+
+        let kb = manager.get_knowledge_base("backend").unwrap();
+        assert_eq!(kb.entries.len(), 1);
+        assert_eq!(kb.entries[0].id, "team:runbook");
+        assert_eq!(kb.entries[0].title, "Runbook v2");
+        assert!(kb.metadata.contains_key("source.team.refreshed_at"));
+
+        let hits = manager.search("backend", "credentials").unwrap();
+        assert_eq!(hits[0].references, vec!["docs/runbook.md"]);
+    }
+
+    #[tokio::test]
+    async fn test_spawn_refresh_honours_interval() {
+        let mut manager = KnowledgeBaseManager::new();
+        let source = Arc::new(CountingSource {
+            fetches: std::sync::atomic::AtomicUsize::new(0),
+        });
+        manager.add_source("devops", source.clone(), Some(Duration::from_secs(3600)));
+        let manager = Arc::new(RwLock::new(manager));
+
+        let handle = KnowledgeBaseManager::spawn_refresh(manager.clone(), Duration::from_millis(5));
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        handle.abort();
+
+        assert_eq!(source.fetches.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert!(manager.read().await.get_knowledge_base("devops").is_ok());
+    }
+
+    #[test]
+    fn test_builtin_knowledge_bases() {
+        let security = builtin_knowledge_base("security");
+        assert!(!security.search_by_tag("cve").is_empty());
+        let database = builtin_knowledge_base("database");
+        assert!(!database.search_by_category("migrations").is_empty());
+        assert!(builtin_knowledge_base("mobile").entries.is_empty());
+    }
 }
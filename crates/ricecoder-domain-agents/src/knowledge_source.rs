@@ -0,0 +1,575 @@
+//! External knowledge sources for domain knowledge bases
+//!
+//! A [`KnowledgeSource`] produces knowledge entries from outside the built-in
+//! knowledge bases: local markdown directories, MCP resource servers, or HTTP
+//! documentation pages. Sources are attached to domains through
+//! [`KnowledgeBaseManager`](crate::KnowledgeBaseManager), which refreshes them
+//! on their configured schedule.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::{
+    error::{DomainAgentError, Result},
+    models::KnowledgeEntry,
+};
+
+/// A source of knowledge entries outside the built-in knowledge bases
+#[async_trait]
+pub trait KnowledgeSource: Send + Sync {
+    /// Unique source identifier, used to prefix entry IDs
+    fn id(&self) -> &str;
+
+    /// Fetch the current entries from the source
+    async fn fetch(&self) -> Result<Vec<KnowledgeEntry>>;
+}
+
+/// Front matter recognised at the top of markdown knowledge files
+#[derive(Debug, Default, Deserialize)]
+struct MarkdownFrontMatter {
+    title: Option<String>,
+    category: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    references: Vec<String>,
+}
+
+/// Knowledge source backed by a directory of markdown files
+///
+/// Each `.md` file becomes one entry. Optional YAML front matter can set
+/// `title`, `category`, `tags` and `references`; otherwise the title is the
+/// first heading and the category is "documentation".
+pub struct MarkdownDirectorySource {
+    id: String,
+    root: PathBuf,
+}
+
+impl MarkdownDirectorySource {
+    /// Create a source reading markdown files under `root`
+    pub fn new(id: impl Into<String>, root: impl Into<PathBuf>) -> Self {
+        Self {
+            id: id.into(),
+            root: root.into(),
+        }
+    }
+
+    fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                Self::collect_files(&path, files)?;
+            } else if path.extension().and_then(|e| e.to_str()) == Some("md") {
+                files.push(path);
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_file(&self, path: &Path, content: &str) -> Result<KnowledgeEntry> {
+        let relative = path.strip_prefix(&self.root).unwrap_or(path);
+        let local_id = relative
+            .with_extension("")
+            .to_string_lossy()
+            .replace('\\', "/");
+
+        let (front_matter, body) = split_front_matter(content)?;
+        let title = front_matter
+            .title
+            .or_else(|| first_heading(body))
+            .unwrap_or_else(|| local_id.clone());
+
+        let mut references = vec![path.to_string_lossy().to_string()];
+        references.extend(front_matter.references);
+
+        Ok(KnowledgeEntry {
+            id: local_id,
+            category: front_matter
+                .category
+                .unwrap_or_else(|| "documentation".to_string()),
+            title,
+            description: body.trim().to_string(),
+            tags: front_matter.tags,
+            example: None,
+            references,
+        })
+    }
+}
+
+#[async_trait]
+impl KnowledgeSource for MarkdownDirectorySource {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    async fn fetch(&self) -> Result<Vec<KnowledgeEntry>> {
+        if !self.root.is_dir() {
+            return Err(DomainAgentError::KnowledgeBaseError(format!(
+                "Markdown source directory not found: {}",
+                self.root.display()
+            )));
+        }
+
+        let mut files = Vec::new();
+        Self::collect_files(&self.root, &mut files)?;
+        files.sort();
+
+        let mut entries = Vec::with_capacity(files.len());
+        for path in files {
+            let content = tokio::fs::read_to_string(&path).await?;
+            entries.push(self.parse_file(&path, &content)?);
+        }
+
+        debug!(
+            "Markdown source {} loaded {} entries",
+            self.id,
+            entries.len()
+        );
+        Ok(entries)
+    }
+}
+
+fn split_front_matter(content: &str) -> Result<(MarkdownFrontMatter, &str)> {
+    let Some(rest) = content.strip_prefix("---\n") else {
+        return Ok((MarkdownFrontMatter::default(), content));
+    };
+    match rest.find("\n---") {
+        Some(end) => {
+            let front_matter = serde_yaml::from_str(&rest[..end])?;
+            let body = rest[end + 4..].trim_start_matches(['-', '\n']);
+            Ok((front_matter, body))
+        }
+        None => Ok((MarkdownFrontMatter::default(), content)),
+    }
+}
+
+fn first_heading(body: &str) -> Option<String> {
+    body.lines()
+        .find_map(|line| line.strip_prefix("# "))
+        .map(|title| title.trim().to_string())
+}
+
+/// A resource advertised by an MCP server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpResource {
+    /// Resource URI
+    pub uri: String,
+    /// Human-readable name
+    pub name: String,
+    /// Optional description
+    pub description: Option<String>,
+    /// MIME type of the resource content
+    pub mime_type: Option<String>,
+}
+
+/// Minimal MCP client surface needed to read resources
+///
+/// Implemented by the MCP integration layer so this crate does not depend on
+/// a specific transport.
+#[async_trait]
+pub trait McpResourceClient: Send + Sync {
+    /// List resources exposed by the server (`resources/list`)
+    async fn list_resources(&self) -> Result<Vec<McpResource>>;
+
+    /// Read the text content of a resource (`resources/read`)
+    async fn read_resource(&self, uri: &str) -> Result<String>;
+}
+
+/// Knowledge source backed by an MCP resource server
+///
+/// Only textual resources are read. An optional URI prefix limits the
+/// resources to a subtree of the server.
+pub struct McpResourceSource {
+    id: String,
+    client: Arc<dyn McpResourceClient>,
+    uri_prefix: Option<String>,
+}
+
+impl McpResourceSource {
+    /// Create a source reading resources through `client`
+    pub fn new(id: impl Into<String>, client: Arc<dyn McpResourceClient>) -> Self {
+        Self {
+            id: id.into(),
+            client,
+            uri_prefix: None,
+        }
+    }
+
+    /// Only read resources whose URI starts with `prefix`
+    pub fn with_uri_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.uri_prefix = Some(prefix.into());
+        self
+    }
+
+    fn is_text(resource: &McpResource) -> bool {
+        match resource.mime_type.as_deref() {
+            None => true,
+            Some(mime) => {
+                mime.starts_with("text/") || mime.ends_with("json") || mime.ends_with("yaml")
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl KnowledgeSource for McpResourceSource {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    async fn fetch(&self) -> Result<Vec<KnowledgeEntry>> {
+        let mut entries = Vec::new();
+        for resource in self.client.list_resources().await? {
+            if let Some(prefix) = &self.uri_prefix {
+                if !resource.uri.starts_with(prefix.as_str()) {
+                    continue;
+                }
+            }
+            if !Self::is_text(&resource) {
+                debug!("Skipping non-text MCP resource {}", resource.uri);
+                continue;
+            }
+
+            let content = self.client.read_resource(&resource.uri).await?;
+            entries.push(KnowledgeEntry {
+                id: resource.uri.clone(),
+                category: "documentation".to_string(),
+                title: resource.name.clone(),
+                description: match &resource.description {
+                    Some(description) => format!("{}\n\n{}", description, content.trim()),
+                    None => content.trim().to_string(),
+                },
+                tags: Vec::new(),
+                example: None,
+                references: vec![resource.uri],
+            });
+        }
+        Ok(entries)
+    }
+}
+
+/// Knowledge source backed by HTTP documentation pages
+///
+/// Each URL becomes one entry. HTML pages are reduced to their text content.
+pub struct HttpDocsSource {
+    id: String,
+    urls: Vec<String>,
+    client: reqwest::Client,
+}
+
+impl HttpDocsSource {
+    /// Create a source fetching `urls`
+    pub fn new(id: impl Into<String>, urls: Vec<String>) -> Self {
+        Self {
+            id: id.into(),
+            urls,
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(30))
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+}
+
+#[async_trait]
+impl KnowledgeSource for HttpDocsSource {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    async fn fetch(&self) -> Result<Vec<KnowledgeEntry>> {
+        let mut entries = Vec::with_capacity(self.urls.len());
+        for url in &self.urls {
+            let response = self
+                .client
+                .get(url)
+                .send()
+                .await
+                .and_then(|r| r.error_for_status())
+                .map_err(|e| {
+                    DomainAgentError::KnowledgeBaseError(format!("Failed to fetch {}: {}", url, e))
+                })?;
+            let is_html = response
+                .headers()
+                .get(reqwest::header::CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok())
+                .is_some_and(|v| v.contains("html"));
+            let body = response.text().await.map_err(|e| {
+                DomainAgentError::KnowledgeBaseError(format!("Failed to read {}: {}", url, e))
+            })?;
+
+            let (title, description) = if is_html {
+                (html_title(&body), html_to_text(&body))
+            } else {
+                (first_heading(&body), body.trim().to_string())
+            };
+
+            entries.push(KnowledgeEntry {
+                id: url.clone(),
+                category: "documentation".to_string(),
+                title: title.unwrap_or_else(|| url.clone()),
+                description,
+                tags: Vec::new(),
+                example: None,
+                references: vec![url.clone()],
+            });
+        }
+        Ok(entries)
+    }
+}
+
+fn html_title(html: &str) -> Option<String> {
+    let start = html.find("<title>")? + "<title>".len();
+    let end = html[start..].find("</title>")? + start;
+    Some(html[start..end].trim().to_string())
+}
+
+fn html_to_text(html: &str) -> String {
+    let body = html
+        .find("<body")
+        .map(|start| &html[start..])
+        .unwrap_or(html);
+    let mut text = String::with_capacity(body.len());
+    let mut in_tag = false;
+    for c in body.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => {
+                in_tag = false;
+                text.push(' ');
+            }
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Kind of an external knowledge source in configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum KnowledgeSourceKind {
+    /// Local directory of markdown files
+    Markdown {
+        /// Directory to read
+        path: PathBuf,
+    },
+    /// MCP resource server
+    Mcp {
+        /// Name of the configured MCP server
+        server: String,
+        /// Only read resources under this URI prefix
+        #[serde(default)]
+        uri_prefix: Option<String>,
+    },
+    /// HTTP documentation pages
+    Http {
+        /// Pages to fetch
+        urls: Vec<String>,
+    },
+}
+
+/// Per-domain configuration of an external knowledge source
+///
+/// ```yaml
+/// id: team-backend-docs
+/// type: markdown
+/// path: docs/backend
+/// domains: [backend, api]
+/// refresh_interval_secs: 3600
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KnowledgeSourceConfig {
+    /// Unique source identifier
+    pub id: String,
+    /// Source kind and location
+    #[serde(flatten)]
+    pub kind: KnowledgeSourceKind,
+    /// Domains whose knowledge base the source feeds
+    pub domains: Vec<String>,
+    /// How often to refresh the source; `None` refreshes only on demand
+    #[serde(default)]
+    pub refresh_interval_secs: Option<u64>,
+}
+
+impl KnowledgeSourceConfig {
+    /// Refresh interval as a duration
+    pub fn refresh_interval(&self) -> Option<Duration> {
+        self.refresh_interval_secs.map(Duration::from_secs)
+    }
+
+    /// Build the source described by this configuration
+    ///
+    /// MCP sources are resolved against `mcp_clients` by server name.
+    pub fn build(
+        &self,
+        mcp_clients: &HashMap<String, Arc<dyn McpResourceClient>>,
+    ) -> Result<Arc<dyn KnowledgeSource>> {
+        Ok(match &self.kind {
+            KnowledgeSourceKind::Markdown { path } => {
+                Arc::new(MarkdownDirectorySource::new(&self.id, path))
+            }
+            KnowledgeSourceKind::Http { urls } => {
+                Arc::new(HttpDocsSource::new(&self.id, urls.clone()))
+            }
+            KnowledgeSourceKind::Mcp { server, uri_prefix } => {
+                let client = mcp_clients.get(server).cloned().ok_or_else(|| {
+                    DomainAgentError::ConfigError(format!(
+                        "Knowledge source {} references unknown MCP server {}",
+                        self.id, server
+                    ))
+                })?;
+                let source = McpResourceSource::new(&self.id, client);
+                match uri_prefix {
+                    Some(prefix) => Arc::new(source.with_uri_prefix(prefix)),
+                    None => Arc::new(source),
+                }
+            }
+        })
+    }
+}
+
+/// Build all configured sources, skipping ones that fail to resolve
+pub(crate) fn build_sources(
+    configs: &[KnowledgeSourceConfig],
+    mcp_clients: &HashMap<String, Arc<dyn McpResourceClient>>,
+) -> Vec<(KnowledgeSourceConfig, Arc<dyn KnowledgeSource>)> {
+    configs
+        .iter()
+        .filter_map(|config| match config.build(mcp_clients) {
+            Ok(source) => Some((config.clone(), source)),
+            Err(e) => {
+                warn!("Skipping knowledge source {}: {}", config.id, e);
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_markdown_directory_source() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join("services")).unwrap();
+        std::fs::write(
+            dir.path().join("services/auth.md"),
+            "---\ntitle: Auth service\ncategory: architecture\ntags: [auth, jwt]\n---\nTokens expire after 15 minutes.\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("deploy.md"),
+            "# Deploy runbook\n\nUse blue/green deploys.\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+
+        let source = MarkdownDirectorySource::new("team-docs", dir.path());
+        let entries = source.fetch().await.unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].id, "deploy");
+        assert_eq!(entries[0].title, "Deploy runbook");
+        assert_eq!(entries[0].category, "documentation");
+        assert_eq!(entries[1].id, "services/auth");
+        assert_eq!(entries[1].title, "Auth service");
+        assert_eq!(entries[1].category, "architecture");
+        assert_eq!(entries[1].tags, vec!["auth", "jwt"]);
+        assert_eq!(entries[1].description, "Tokens expire after 15 minutes.");
+    }
+
+    struct FakeMcp;
+
+    #[async_trait]
+    impl McpResourceClient for FakeMcp {
+        async fn list_resources(&self) -> Result<Vec<McpResource>> {
+            Ok(vec![
+                McpResource {
+                    uri: "docs://backend/errors".to_string(),
+                    name: "Error codes".to_string(),
+                    description: None,
+                    mime_type: Some("text/markdown".to_string()),
+                },
+                McpResource {
+                    uri: "docs://backend/diagram".to_string(),
+                    name: "Diagram".to_string(),
+                    description: None,
+                    mime_type: Some("image/png".to_string()),
+                },
+                McpResource {
+                    uri: "docs://frontend/style".to_string(),
+                    name: "Style guide".to_string(),
+                    description: None,
+                    mime_type: None,
+                },
+            ])
+        }
+
+        async fn read_resource(&self, uri: &str) -> Result<String> {
+            Ok(format!("content of {}", uri))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_mcp_resource_source_filters_prefix_and_mime() {
+        let source = McpResourceSource::new("mcp-docs", Arc::new(FakeMcp))
+            .with_uri_prefix("docs://backend/");
+        let entries = source.fetch().await.unwrap();
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].title, "Error codes");
+        assert_eq!(entries[0].description, "content of docs://backend/errors");
+        assert_eq!(entries[0].references, vec!["docs://backend/errors"]);
+    }
+
+    #[test]
+    fn test_source_config_from_yaml() {
+        let yaml = r#"
+- id: backend-docs
+  type: markdown
+  path: docs/backend
+  domains: [backend]
+  refresh_interval_secs: 600
+- id: platform
+  type: mcp
+  server: docs-server
+  uri_prefix: "docs://platform/"
+  domains: [devops]
+- id: k8s
+  type: http
+  urls: ["https://example.com/k8s.md"]
+  domains: [devops]
+"#;
+        let configs: Vec<KnowledgeSourceConfig> = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(configs.len(), 3);
+        assert_eq!(
+            configs[0].refresh_interval(),
+            Some(Duration::from_secs(600))
+        );
+        assert!(matches!(configs[1].kind, KnowledgeSourceKind::Mcp { .. }));
+
+        let mut clients: HashMap<String, Arc<dyn McpResourceClient>> = HashMap::new();
+        assert_eq!(build_sources(&configs, &clients).len(), 2);
+        clients.insert("docs-server".to_string(), Arc::new(FakeMcp));
+        assert_eq!(build_sources(&configs, &clients).len(), 3);
+    }
+
+    #[test]
+    fn test_html_to_text() {
+        let html = "<html><head><title> Guide </title></head><body><h1>Deploy</h1><p>Use  canaries.</p></body></html>";
+        assert_eq!(html_title(html).as_deref(), Some("Guide"));
+        assert_eq!(html_to_text(html), "Deploy Use canaries.");
+    }
+}
@@ -1,14 +1,17 @@
 //! Domain-Specific Agents for RiceCoder
 //!
 //! This crate provides a framework for specialized agents that focus on specific domains
-//! (frontend, backend, DevOps, etc.) with domain-specific knowledge bases and capabilities.
+//! (frontend, backend, DevOps, security, database, etc.) with domain-specific knowledge bases and capabilities.
 //!
 //! # Architecture
 //!
 //! The framework consists of:
-//! - **Domain Agents**: Specialized agents for specific domains (Frontend, Backend, DevOps)
+//! - **Domain Agents**: Specialized agents for specific domains (Frontend, Backend, DevOps,
+//!   Security, Database)
 //! - **Knowledge Base**: Domain-specific knowledge entries and best practices
+//! - **Knowledge Sources**: External markdown, MCP and HTTP documentation feeding knowledge bases
 //! - **Registry**: Central registry for discovering and managing domain agents
+//! - **Consultation**: Scoped sub-questions between domain agents, orchestrated by the registry
 //! - **Models**: Data structures for domains, agents, and knowledge
 //!
 //! # Example
@@ -39,17 +42,28 @@
 
 #![warn(missing_docs)]
 
+pub mod consultation;
 pub mod domain_agents;
 pub mod error;
 pub mod knowledge_base;
+pub mod knowledge_source;
 pub mod models;
 pub mod registry;
 
+pub use consultation::{
+    Consultation, ConsultationRequest, ConsultationSkipReason, SkippedConsultation,
+    DEFAULT_MAX_CONSULTATION_DEPTH,
+};
 pub use domain_agents::{
-    BackendAgent, DevOpsAgent, DomainAgent, DomainAgentInput, DomainAgentOutput, FrontendAgent,
+    BackendAgent, DatabaseAgent, DevOpsAgent, DomainAgent, DomainAgentInput, DomainAgentOutput,
+    FrontendAgent, SecurityAgent,
 };
 pub use error::{DomainAgentError, Result};
-pub use knowledge_base::KnowledgeBaseManager;
+pub use knowledge_base::{builtin_knowledge_base, KnowledgeBaseManager};
+pub use knowledge_source::{
+    HttpDocsSource, KnowledgeSource, KnowledgeSourceConfig, KnowledgeSourceKind,
+    MarkdownDirectorySource, McpResource, McpResourceClient, McpResourceSource,
+};
 pub use models::{
     Domain, DomainAgentConfig, DomainAgentMetadata, DomainAgentRegistry, KnowledgeBase,
     KnowledgeEntry,
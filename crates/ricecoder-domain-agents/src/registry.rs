@@ -2,10 +2,18 @@
 
 use std::{collections::HashMap, sync::Arc};
 
-use tracing::{debug, info};
+use futures::future::BoxFuture;
+use tracing::{debug, info, warn};
 
 use crate::{
-    domain_agents::{BackendAgent, DevOpsAgent, DomainAgent, DomainAgentInput, FrontendAgent},
+    consultation::{
+        Consultation, ConsultationSkipReason, SkippedConsultation, CONSULTATIONS_PARAMETER,
+        DEFAULT_MAX_CONSULTATION_DEPTH,
+    },
+    domain_agents::{
+        BackendAgent, DatabaseAgent, DevOpsAgent, DomainAgent, DomainAgentInput, FrontendAgent,
+        SecurityAgent,
+    },
     error::{DomainAgentError, Result},
     models::{DomainAgentMetadata, DomainAgentRegistry},
 };
@@ -14,6 +22,7 @@ use crate::{
 pub struct DomainAgentRegistryManager {
     agents: HashMap<String, Arc<dyn DomainAgent>>,
     registry: DomainAgentRegistry,
+    max_consultation_depth: usize,
 }
 
 impl DomainAgentRegistryManager {
@@ -22,9 +31,18 @@ impl DomainAgentRegistryManager {
         Self {
             agents: HashMap::new(),
             registry: DomainAgentRegistry::new(),
+            max_consultation_depth: DEFAULT_MAX_CONSULTATION_DEPTH,
         }
     }
 
+    /// Set how deeply consultations between agents may nest
+    ///
+    /// A depth of 0 disables consultations.
+    pub fn with_max_consultation_depth(mut self, depth: usize) -> Self {
+        self.max_consultation_depth = depth;
+        self
+    }
+
     /// Initialize with default agents
     pub fn with_defaults() -> Self {
         let mut manager = Self::new();
@@ -57,6 +75,25 @@ impl DomainAgentRegistryManager {
         self.registry
             .register_agent("devops", devops_agent.metadata().clone());
 
+        // Register security agent
+        let security_agent = Arc::new(SecurityAgent::new());
+        self.agents
+            .insert("security".to_string(), security_agent.clone());
+        self.registry
+            .register_agent("security", security_agent.metadata().clone());
+
+        // Register database agent
+        let database_agent = Arc::new(DatabaseAgent::new());
+        self.agents
+            .insert("database".to_string(), database_agent.clone());
+        self.registry
+            .register_agent("database", database_agent.metadata().clone());
+
+        for domain in ["security", "database"] {
+            self.registry
+                .register_knowledge_base(domain, format!("knowledge_bases/{}.yaml", domain));
+        }
+
         info!("Registered {} default domain agents", self.agents.len());
     }
 
@@ -77,13 +114,119 @@ impl DomainAgentRegistryManager {
     }
 
     /// Execute agent for domain
+    ///
+    /// Consultations planned by the agent are answered by the consulted
+    /// domains' agents first, and merged into the output with attribution.
     pub async fn execute_agent(
         &self,
         domain: &str,
         input: DomainAgentInput,
     ) -> Result<crate::domain_agents::DomainAgentOutput> {
-        let agent = self.get_agent(domain)?;
-        agent.execute(input).await
+        self.execute_in_chain(domain.to_string(), input, Vec::new())
+            .await
+    }
+
+    /// Execute an agent as part of a consultation chain
+    ///
+    /// `chain` holds the domains already consulting, outermost first, and
+    /// is used for cycle detection and depth limiting.
+    fn execute_in_chain(
+        &self,
+        domain: String,
+        mut input: DomainAgentInput,
+        mut chain: Vec<String>,
+    ) -> BoxFuture<'_, Result<crate::domain_agents::DomainAgentOutput>> {
+        Box::pin(async move {
+            let agent = self.get_agent(&domain)?;
+            chain.push(domain.clone());
+            let depth = chain.len();
+
+            let mut consultations: Vec<Consultation> = Vec::new();
+            let mut skipped: Vec<SkippedConsultation> = Vec::new();
+
+            for request in agent.plan_consultations(&input) {
+                let skip_reason = if chain.contains(&request.domain) {
+                    Some(ConsultationSkipReason::Cycle)
+                } else if depth > self.max_consultation_depth {
+                    Some(ConsultationSkipReason::DepthLimit)
+                } else if !self.has_agent(&request.domain) {
+                    Some(ConsultationSkipReason::UnknownDomain)
+                } else {
+                    None
+                };
+                if let Some(reason) = skip_reason {
+                    debug!(
+                        "Skipping consultation from {} to {}: {:?}",
+                        domain, request.domain, reason
+                    );
+                    skipped.push(SkippedConsultation {
+                        requested_by: domain.clone(),
+                        domain: request.domain,
+                        question: request.question,
+                        reason,
+                    });
+                    continue;
+                }
+
+                debug!(
+                    "{} consulting {}: {}",
+                    domain, request.domain, request.question
+                );
+                let sub_input = DomainAgentInput {
+                    domain: request.domain.clone(),
+                    task: request.question.clone(),
+                    context: request.context,
+                    parameters: HashMap::new(),
+                };
+                match self
+                    .execute_in_chain(request.domain.clone(), sub_input, chain.clone())
+                    .await
+                {
+                    Ok(answer) => {
+                        consultations.push(Consultation {
+                            requested_by: domain.clone(),
+                            domain: request.domain,
+                            question: request.question,
+                            response: answer.response,
+                            suggestions: answer.suggestions,
+                            confidence: answer.confidence,
+                            depth,
+                        });
+                        consultations.extend(answer.consultations);
+                        skipped.extend(answer.skipped_consultations);
+                    }
+                    Err(e) => {
+                        warn!("Consultation of {} failed: {}", request.domain, e);
+                        skipped.push(SkippedConsultation {
+                            requested_by: domain.clone(),
+                            domain: request.domain,
+                            question: request.question,
+                            reason: ConsultationSkipReason::Failed(e.to_string()),
+                        });
+                    }
+                }
+            }
+
+            if !consultations.is_empty() {
+                input.parameters.insert(
+                    CONSULTATIONS_PARAMETER.to_string(),
+                    serde_json::to_value(&consultations)?,
+                );
+            }
+
+            let mut output = agent.execute(input).await?;
+            for consultation in consultations.iter().filter(|c| c.requested_by == domain) {
+                output.suggestions.extend(
+                    consultation
+                        .suggestions
+                        .iter()
+                        .map(|s| format!("[{}] {}", consultation.domain, s)),
+                );
+            }
+            output.consultations.extend(consultations);
+            output.skipped_consultations.extend(skipped);
+            Ok(output)
+        })
     }
 
     /// Get all registered domains
@@ -133,10 +276,16 @@ mod tests {
     #[test]
     fn test_register_default_agents() {
         let registry = DomainAgentRegistryManager::with_defaults();
-        assert_eq!(registry.agents.len(), 3);
+        assert_eq!(registry.agents.len(), 5);
         assert!(registry.has_agent("frontend"));
         assert!(registry.has_agent("backend"));
         assert!(registry.has_agent("devops"));
+        assert!(registry.has_agent("security"));
+        assert!(registry.has_agent("database"));
+        assert!(registry
+            .get_registry()
+            .get_knowledge_base("security")
+            .is_some());
     }
 
     #[test]
@@ -157,7 +306,7 @@ mod tests {
     fn test_get_registered_domains() {
         let registry = DomainAgentRegistryManager::with_defaults();
         let domains = registry.get_registered_domains();
-        assert_eq!(domains.len(), 3);
+        assert_eq!(domains.len(), 5);
     }
 
     #[test]
@@ -179,6 +328,135 @@ mod tests {
     fn test_get_all_agents_metadata() {
         let registry = DomainAgentRegistryManager::with_defaults();
         let all_metadata = registry.get_all_agents_metadata();
-        assert_eq!(all_metadata.len(), 3);
+        assert_eq!(all_metadata.len(), 5);
+    }
+
+    struct AskingAgent {
+        metadata: DomainAgentMetadata,
+        config: crate::models::DomainAgentConfig,
+        asks: Vec<&'static str>,
+    }
+
+    impl AskingAgent {
+        fn new(domain: &str, asks: Vec<&'static str>) -> Self {
+            let mut config = crate::FrontendAgent::new().config().clone();
+            config.domain = domain.to_string();
+            let mut metadata = crate::FrontendAgent::new().metadata().clone();
+            metadata.domain = domain.to_string();
+            Self {
+                metadata,
+                config,
+                asks,
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl DomainAgent for AskingAgent {
+        fn metadata(&self) -> &DomainAgentMetadata {
+            &self.metadata
+        }
+
+        fn config(&self) -> &crate::models::DomainAgentConfig {
+            &self.config
+        }
+
+        async fn execute(
+            &self,
+            input: DomainAgentInput,
+        ) -> Result<crate::domain_agents::DomainAgentOutput> {
+            Ok(crate::domain_agents::DomainAgentOutput {
+                domain: self.metadata.domain.clone(),
+                response: format!(
+                    "{} answered with {} consultations",
+                    self.metadata.domain,
+                    input
+                        .parameters
+                        .get(CONSULTATIONS_PARAMETER)
+                        .and_then(|v| v.as_array())
+                        .map_or(0, |a| a.len())
+                ),
+                suggestions: vec![format!("{} suggestion", self.metadata.domain)],
+                confidence: 0.5,
+                metadata: HashMap::new(),
+                consultations: Vec::new(),
+                skipped_consultations: Vec::new(),
+            })
+        }
+
+        fn validate_input(&self, _input: &DomainAgentInput) -> Result<()> {
+            Ok(())
+        }
+
+        fn plan_consultations(
+            &self,
+            input: &DomainAgentInput,
+        ) -> Vec<crate::consultation::ConsultationRequest> {
+            self.asks
+                .iter()
+                .map(|domain| {
+                    crate::consultation::ConsultationRequest::new(
+                        *domain,
+                        format!("{} asks about {}", self.metadata.domain, input.task),
+                        "",
+                    )
+                })
+                .collect()
+        }
+    }
+
+    fn input(domain: &str) -> DomainAgentInput {
+        DomainAgentInput {
+            domain: domain.to_string(),
+            task: "checkout".to_string(),
+            context: String::new(),
+            parameters: HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_frontend_consults_backend_about_api() {
+        let registry = DomainAgentRegistryManager::with_defaults();
+        let mut request = input("frontend");
+        request.task = "Call the orders API endpoint".to_string();
+
+        let output = registry.execute_agent("frontend", request).await.unwrap();
+
+        assert_eq!(output.consultations.len(), 1);
+        assert_eq!(output.consultations[0].requested_by, "frontend");
+        assert_eq!(output.consultations[0].domain, "backend");
+        assert!(output
+            .suggestions
+            .iter()
+            .any(|s| s.starts_with("[backend] ")));
+    }
+
+    #[tokio::test]
+    async fn test_consultation_cycles_and_depth_are_limited() {
+        let mut registry = DomainAgentRegistryManager::new();
+        registry.register_agent("a", Arc::new(AskingAgent::new("a", vec!["b"])));
+        registry.register_agent("b", Arc::new(AskingAgent::new("b", vec!["a", "c"])));
+        registry.register_agent("c", Arc::new(AskingAgent::new("c", vec!["d", "missing"])));
+        registry.register_agent("d", Arc::new(AskingAgent::new("d", vec![])));
+
+        let output = registry.execute_agent("a", input("a")).await.unwrap();
+
+        // a -> b (depth 1) -> c (depth 2); c cannot go deeper
+        let answered: Vec<(&str, usize)> = output
+            .consultations
+            .iter()
+            .map(|c| (c.domain.as_str(), c.depth))
+            .collect();
+        assert_eq!(answered, vec![("b", 1), ("c", 2)]);
+        assert!(output.response.contains("2 consultations"));
+
+        let skipped: Vec<(&str, &ConsultationSkipReason)> = output
+            .skipped_consultations
+            .iter()
+            .map(|s| (s.domain.as_str(), &s.reason))
+            .collect();
+        assert!(skipped.contains(&("a", &ConsultationSkipReason::Cycle)));
+        assert!(skipped.contains(&("d", &ConsultationSkipReason::DepthLimit)));
+        assert!(skipped.contains(&("missing", &ConsultationSkipReason::DepthLimit)));
     }
 }
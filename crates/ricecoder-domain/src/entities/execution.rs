@@ -0,0 +1,22 @@
+//! Execution checkpoint entity for resumable execution plans
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Snapshot of an execution plan's progress
+///
+/// The plan and its execution state are stored as opaque JSON so the domain
+/// does not depend on the execution engine's types. `file_hashes` records the
+/// content hash of every file completed steps touched (`None` when the file
+/// was deleted), letting a resume verify the workspace is unchanged.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionCheckpoint {
+    pub plan_id: String,
+    pub execution_id: String,
+    pub plan: serde_json::Value,
+    pub state: serde_json::Value,
+    pub file_hashes: HashMap<String, Option<String>>,
+    pub updated_at: DateTime<Utc>,
+}
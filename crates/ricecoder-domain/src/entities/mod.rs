@@ -3,6 +3,7 @@
 //! This module contains the domain entities organized by responsibility:
 //! - `project`: Project entity (legacy representation)
 //! - `code_file`: Source code file entity
+//! - `execution`: Execution plan checkpoints
 //! - `analysis`: Code analysis results and metrics
 //! - `session`: Session entity (legacy representation)
 //! - `security`: Security context, events, and alerts
@@ -14,6 +15,7 @@
 
 mod analysis;
 mod code_file;
+mod execution;
 mod compliance;
 mod gdpr;
 mod performance;
@@ -26,6 +28,7 @@ mod user;
 // Re-export all entities for backward compatibility
 pub use analysis::*;
 pub use code_file::*;
+pub use execution::*;
 pub use compliance::*;
 pub use gdpr::*;
 pub use performance::*;
@@ -165,6 +165,8 @@ pub enum ModelCapability {
     FunctionCalling,
     /// Embeddings generation
     Embeddings,
+    /// Reasoning (extended thinking)
+    Reasoning,
 }
 
 /// Information about an AI model
@@ -18,6 +18,7 @@
 use async_trait::async_trait;
 
 use crate::{
+    entities::ExecutionCheckpoint,
     errors::*,
     project::Project,
     session::Session,
@@ -111,7 +112,23 @@ pub trait SpecificationRepository: SpecificationReader + SpecificationWriter {}
 /// Blanket implementation: Any type implementing Reader + Writer gets Repository
 impl<T: SpecificationReader + SpecificationWriter> SpecificationRepository for T {}
 
+/// Repository for execution plan checkpoints
+///
+/// Infrastructure implements domain interfaces
+#[async_trait]
+pub trait ExecutionCheckpointRepository: Send + Sync {
+    /// Save a checkpoint, replacing any previous one for the plan
+    async fn save(&self, checkpoint: &ExecutionCheckpoint) -> DomainResult<()>;
 
+    /// Find the latest checkpoint for a plan
+    async fn find_by_plan(&self, plan_id: &str) -> DomainResult<Option<ExecutionCheckpoint>>;
+
+    /// List plans with a checkpoint
+    async fn list_plans(&self) -> DomainResult<Vec<String>>;
+
+    /// Delete the checkpoint for a plan
+    async fn delete(&self, plan_id: &str) -> DomainResult<()>;
+}
 
 /// Generic repository trait for common operations
 #[async_trait]
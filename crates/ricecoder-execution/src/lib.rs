@@ -22,6 +22,7 @@ pub mod shell;
 pub mod step_action_handler;
 pub mod step_creator;
 pub mod step_executor;
+pub mod test_runner;
 pub mod validation;
 
 pub use approval::{ApprovalManager, ApprovalSummary};
@@ -44,7 +45,10 @@ pub use plan_builder::PlanBuilder;
 pub use progress_tracker::{ProgressCallback, ProgressTracker, ProgressUpdate};
 pub use risk_scorer::ExecutionRiskScorer;
 pub use rollback_actions::{RestoreFileHandler, UndoCommandHandler};
-pub use rollback_handler::{RollbackHandler, RollbackResult};
+pub use rollback_handler::{
+    PlannedRollback, RollbackCheck, RollbackExpectation, RollbackHandler, RollbackResult,
+    RollbackVerificationReport, VerificationOutcome,
+};
 pub use step_action_handler::{
     CommandHandler, CreateFileHandler, DeleteFileHandler, ModifyFileHandler,
     ShellCommandHandler, TestHandler,
@@ -52,4 +56,8 @@ pub use step_action_handler::{
 pub use shell::{Environment, ProcessTree, ShellDetector};
 pub use step_creator::StepCreator;
 pub use step_executor::StepExecutor;
+pub use test_runner::{
+    CargoTestAdapter, GoTestAdapter, JestAdapter, PytestAdapter, TestFrameworkAdapter,
+    TestInvocation, TestRun, TestRunner,
+};
 pub use validation::ExecutionValidator;
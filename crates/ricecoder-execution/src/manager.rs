@@ -1,14 +1,19 @@
 //! Central execution manager for coordinating plan execution
 
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 
 use chrono::Utc;
+use ricecoder_domain::{
+    entities::ExecutionCheckpoint, repositories::ExecutionCheckpointRepository,
+};
+use sha2::{Digest, Sha256};
 use uuid::Uuid;
 
 use crate::{
     error::{ExecutionError, ExecutionResult},
-    models::{ExecutionMode, ExecutionPlan, ExecutionState},
+    models::{ExecutionMode, ExecutionPlan, ExecutionState, StepResult, StepStatus},
     progress_tracker::ProgressTracker,
+    step_executor::StepExecutor,
 };
 
 /// Central coordinator for execution plan execution
@@ -16,6 +21,10 @@ use crate::{
 /// Manages execution lifecycle (start, pause, resume, cancel) and tracks
 /// active executions. Wraps the WorkflowEngine and provides high-level
 /// execution plan management.
+///
+/// With a checkpoint repository attached, the execution state is persisted
+/// after every step so an interrupted plan can be picked up again with
+/// [`resume`](Self::resume).
 pub struct ExecutionManager {
     /// Active execution states
     active_executions: HashMap<String, ExecutionState>,
@@ -23,6 +32,10 @@ pub struct ExecutionManager {
     plans: HashMap<String, ExecutionPlan>,
     /// Progress trackers for active executions
     progress_trackers: HashMap<String, ProgressTracker>,
+    /// Executes plan steps
+    step_executor: StepExecutor,
+    /// Where execution state is persisted after each step
+    checkpoints: Option<Arc<dyn ExecutionCheckpointRepository>>,
 }
 
 impl Default for ExecutionManager {
@@ -38,9 +51,26 @@ impl ExecutionManager {
             active_executions: HashMap::new(),
             plans: HashMap::new(),
             progress_trackers: HashMap::new(),
+            step_executor: StepExecutor::new(),
+            checkpoints: None,
         }
     }
 
+    /// Use a specific step executor (e.g. one with a sandbox)
+    pub fn with_step_executor(mut self, executor: StepExecutor) -> Self {
+        self.step_executor = executor;
+        self
+    }
+
+    /// Persist execution state to a checkpoint repository after every step
+    ///
+    /// `ricecoder-persistence` provides in-memory and file-backed
+    /// repositories.
+    pub fn with_checkpoints(mut self, repository: Arc<dyn ExecutionCheckpointRepository>) -> Self {
+        self.checkpoints = Some(repository);
+        self
+    }
+
     /// Register an execution plan
     ///
     /// Stores the plan for later execution. Returns the plan ID.
@@ -58,6 +88,34 @@ impl ExecutionManager {
             .ok_or_else(|| ExecutionError::PlanError(format!("Plan not found: {}", plan_id)))
     }
 
+    /// Set the status of a step in a registered plan
+    ///
+    /// Steps marked completed or skipped before they run are passed over by
+    /// [`run_execution`](Self::run_execution).
+    pub fn set_step_status(
+        &mut self,
+        plan_id: &str,
+        step_id: &str,
+        status: StepStatus,
+    ) -> ExecutionResult<()> {
+        let plan = self
+            .plans
+            .get_mut(plan_id)
+            .ok_or_else(|| ExecutionError::PlanError(format!("Plan not found: {}", plan_id)))?;
+        let step = plan
+            .steps
+            .iter_mut()
+            .find(|step| step.id == step_id)
+            .ok_or_else(|| {
+                ExecutionError::PlanError(format!(
+                    "Step not found in plan {}: {}",
+                    plan_id, step_id
+                ))
+            })?;
+        step.status = status;
+        Ok(())
+    }
+
     /// Start execution of a plan
     ///
     /// Creates a new execution state and begins execution in the specified mode.
@@ -72,6 +130,7 @@ impl ExecutionManager {
         let execution_id = Uuid::new_v4().to_string();
         let state = ExecutionState {
             execution_id: execution_id.clone(),
+            plan_id: plan_id.to_string(),
             current_step_index: 0,
             completed_steps: Vec::new(),
             mode,
@@ -157,6 +216,211 @@ impl ExecutionManager {
         Ok(())
     }
 
+    /// Run an execution's remaining steps
+    ///
+    /// Starts at the first incomplete step and checkpoints after each one.
+    /// Stops at the first failed step, which stays incomplete so a later
+    /// resume retries it. The checkpoint is removed once every step succeeds.
+    pub async fn run_execution(&mut self, execution_id: &str) -> ExecutionResult<Vec<StepResult>> {
+        let state = self.get_execution_state(execution_id)?;
+        let plan = self.get_plan(&state.plan_id)?;
+
+        for index in state.current_step_index..plan.steps.len() {
+            let step = &plan.steps[index];
+            let status = self
+                .plans
+                .get(&plan.id)
+                .and_then(|current| current.steps.get(index))
+                .map(|current| current.status);
+            if matches!(status, Some(StepStatus::Completed | StepStatus::Skipped)) {
+                tracing::debug!(step_id = %step.id, "Step already done; passing over it");
+                if let Some(state) = self.active_executions.get_mut(execution_id) {
+                    state.current_step_index += 1;
+                }
+                continue;
+            }
+            self.set_step_status(&plan.id, &step.id, StepStatus::Running)?;
+
+            let started = std::time::Instant::now();
+            let result = match self.step_executor.execute_single_step_async(step).await {
+                Ok(result) => result,
+                Err(e) => StepResult {
+                    step_id: step.id.clone(),
+                    success: false,
+                    error: Some(e.to_string()),
+                    duration: started.elapsed(),
+                    output: None,
+                },
+            };
+            let success = result.success;
+            self.record_step_result(execution_id, result).await?;
+            if !success {
+                tracing::warn!(
+                    execution_id = %execution_id,
+                    step_id = %step.id,
+                    "Step failed; execution can be resumed from this step"
+                );
+                return Ok(self.get_execution_state(execution_id)?.completed_steps);
+            }
+        }
+
+        if let Some(checkpoints) = &self.checkpoints {
+            checkpoints
+                .delete(&plan.id)
+                .await
+                .map_err(|e| ExecutionError::StatePersistenceError(e.to_string()))?;
+        }
+        tracing::info!(execution_id = %execution_id, "Execution completed");
+        Ok(self.get_execution_state(execution_id)?.completed_steps)
+    }
+
+    /// Record a step result and persist a checkpoint
+    ///
+    /// Successful results advance the execution to the next step.
+    pub async fn record_step_result(
+        &mut self,
+        execution_id: &str,
+        result: StepResult,
+    ) -> ExecutionResult<()> {
+        let state = self
+            .active_executions
+            .get_mut(execution_id)
+            .ok_or_else(|| {
+                ExecutionError::ValidationError(format!("Execution not found: {}", execution_id))
+            })?;
+
+        if let Some(tracker) = self.progress_trackers.get_mut(execution_id) {
+            if result.success {
+                tracker.step_completed(result.duration);
+            } else {
+                tracker.step_failed(result.duration);
+            }
+        }
+        let plan_id = state.plan_id.clone();
+        let step_id = result.step_id.clone();
+        let status = if result.success {
+            StepStatus::Completed
+        } else {
+            StepStatus::Failed
+        };
+        if result.success {
+            state.current_step_index += 1;
+            state.completed_steps.push(result);
+        }
+        state.paused_at = Utc::now();
+        if self.plans.contains_key(&plan_id) {
+            self.set_step_status(&plan_id, &step_id, status)?;
+        }
+
+        self.checkpoint(execution_id).await
+    }
+
+    /// Persist the current state of an execution
+    ///
+    /// Records the plan, the execution state and hashes of every file touched
+    /// by completed steps. Does nothing without a checkpoint repository.
+    pub async fn checkpoint(&self, execution_id: &str) -> ExecutionResult<()> {
+        let Some(checkpoints) = &self.checkpoints else {
+            return Ok(());
+        };
+        let state = self.get_execution_state(execution_id)?;
+        let plan = self.get_plan(&state.plan_id)?;
+
+        let mut file_hashes = HashMap::new();
+        for step in &plan.steps[..state.current_step_index.min(plan.steps.len())] {
+            if let Some(path) = step.target_path() {
+                file_hashes.insert(path.to_string(), hash_file(path)?);
+            }
+        }
+
+        let checkpoint = ExecutionCheckpoint {
+            plan_id: plan.id.clone(),
+            execution_id: state.execution_id.clone(),
+            plan: serde_json::to_value(&plan)
+                .map_err(|e| ExecutionError::SerializationError(e.to_string()))?,
+            state: serde_json::to_value(&state)
+                .map_err(|e| ExecutionError::SerializationError(e.to_string()))?,
+            file_hashes,
+            updated_at: Utc::now(),
+        };
+        checkpoints
+            .save(&checkpoint)
+            .await
+            .map_err(|e| ExecutionError::StatePersistenceError(e.to_string()))?;
+
+        tracing::debug!(
+            execution_id = %execution_id,
+            step_index = state.current_step_index,
+            "Execution checkpoint saved"
+        );
+        Ok(())
+    }
+
+    /// Resume a checkpointed plan
+    ///
+    /// Reloads the plan and its execution state, verifies that every file
+    /// touched by completed steps still has the recorded content, then
+    /// continues from the first incomplete step. Fails without running
+    /// anything if the workspace has changed since the checkpoint.
+    pub async fn resume(&mut self, plan_id: &str) -> ExecutionResult<Vec<StepResult>> {
+        let checkpoints = self.checkpoints.clone().ok_or_else(|| {
+            ExecutionError::ConfigError("No checkpoint repository configured".to_string())
+        })?;
+        let checkpoint = checkpoints
+            .find_by_plan(plan_id)
+            .await
+            .map_err(|e| ExecutionError::StatePersistenceError(e.to_string()))?
+            .ok_or_else(|| {
+                ExecutionError::StatePersistenceError(format!(
+                    "No checkpoint found for plan: {}",
+                    plan_id
+                ))
+            })?;
+
+        let plan: ExecutionPlan = serde_json::from_value(checkpoint.plan)
+            .map_err(|e| ExecutionError::SerializationError(e.to_string()))?;
+        let mut state: ExecutionState = serde_json::from_value(checkpoint.state)
+            .map_err(|e| ExecutionError::SerializationError(e.to_string()))?;
+        state.plan_id = plan.id.clone();
+
+        let mut changed: Vec<&String> = Vec::new();
+        for (path, recorded) in &checkpoint.file_hashes {
+            if hash_file(path)? != *recorded {
+                changed.push(path);
+            }
+        }
+        if !changed.is_empty() {
+            changed.sort();
+            return Err(ExecutionError::ValidationError(format!(
+                "Workspace changed since checkpoint; modified files: {}",
+                changed
+                    .iter()
+                    .map(|path| path.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )));
+        }
+
+        let mut tracker = ProgressTracker::new(&plan);
+        for result in &state.completed_steps {
+            tracker.step_completed(result.duration);
+        }
+
+        let execution_id = state.execution_id.clone();
+        tracing::info!(
+            execution_id = %execution_id,
+            plan_id = %plan_id,
+            step_index = state.current_step_index,
+            "Resuming execution from checkpoint"
+        );
+
+        self.plans.insert(plan.id.clone(), plan);
+        self.active_executions.insert(execution_id.clone(), state);
+        self.progress_trackers.insert(execution_id.clone(), tracker);
+
+        self.run_execution(&execution_id).await
+    }
+
     /// Get the current state of an execution
     pub fn get_execution_state(&self, execution_id: &str) -> ExecutionResult<ExecutionState> {
         self.active_executions
@@ -229,9 +493,22 @@ impl ExecutionManager {
     }
 }
 
+/// SHA-256 of a file's content, or `None` if it doesn't exist
+pub(crate) fn hash_file(path: impl AsRef<std::path::Path>) -> ExecutionResult<Option<String>> {
+    match std::fs::read(path) {
+        Ok(content) => Ok(Some(hex::encode(Sha256::digest(&content)))),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(ExecutionError::IoError(e)),
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use async_trait::async_trait;
+    use ricecoder_domain::errors::DomainResult;
+
     use super::*;
+    use crate::models::{ExecutionStep, StepAction};
 
     #[test]
     fn test_create_manager() {
@@ -390,4 +667,155 @@ mod tests {
 
         assert!(result.is_ok());
     }
+
+    /// Checkpoint store shared between "processes" in a test
+    #[derive(Default)]
+    struct MemoryCheckpoints(std::sync::Mutex<HashMap<String, ExecutionCheckpoint>>);
+
+    impl MemoryCheckpoints {
+        fn count(&self) -> usize {
+            self.0.lock().unwrap().len()
+        }
+    }
+
+    #[async_trait]
+    impl ExecutionCheckpointRepository for MemoryCheckpoints {
+        async fn save(&self, checkpoint: &ExecutionCheckpoint) -> DomainResult<()> {
+            self.0
+                .lock()
+                .unwrap()
+                .insert(checkpoint.plan_id.clone(), checkpoint.clone());
+            Ok(())
+        }
+
+        async fn find_by_plan(&self, plan_id: &str) -> DomainResult<Option<ExecutionCheckpoint>> {
+            Ok(self.0.lock().unwrap().get(plan_id).cloned())
+        }
+
+        async fn list_plans(&self) -> DomainResult<Vec<String>> {
+            Ok(self.0.lock().unwrap().keys().cloned().collect())
+        }
+
+        async fn delete(&self, plan_id: &str) -> DomainResult<()> {
+            self.0.lock().unwrap().remove(plan_id);
+            Ok(())
+        }
+    }
+
+    fn create_file_step(path: &str, content: &str) -> ExecutionStep {
+        ExecutionStep::new(
+            format!("Create {}", path),
+            StepAction::CreateFile {
+                path: path.to_string(),
+                content: content.to_string(),
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_resume_continues_from_first_incomplete_step() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = |name: &str| dir.path().join(name).to_string_lossy().to_string();
+        let checkpoints = Arc::new(MemoryCheckpoints::default());
+
+        let plan = ExecutionPlan::new(
+            "resumable".to_string(),
+            vec![
+                create_file_step(&file("a.txt"), "a"),
+                // Fails until its parent directory exists
+                create_file_step(&file("missing/b.txt"), "b"),
+                create_file_step(&file("c.txt"), "c"),
+            ],
+        );
+        let plan_id = plan.id.clone();
+
+        let mut manager = ExecutionManager::new()
+            .with_checkpoints(checkpoints.clone() as Arc<dyn ExecutionCheckpointRepository>);
+        manager.register_plan(plan).unwrap();
+        let execution_id = manager
+            .start_execution(&plan_id, ExecutionMode::Automatic)
+            .unwrap();
+        let completed = manager.run_execution(&execution_id).await.unwrap();
+        assert_eq!(completed.len(), 1);
+        assert_eq!(checkpoints.count(), 1);
+
+        // A new process picks up the checkpoint
+        std::fs::create_dir(dir.path().join("missing")).unwrap();
+        let mut restarted = ExecutionManager::new()
+            .with_checkpoints(checkpoints.clone() as Arc<dyn ExecutionCheckpointRepository>);
+        let completed = restarted.resume(&plan_id).await.unwrap();
+
+        assert_eq!(completed.len(), 3);
+        assert_eq!(std::fs::read_to_string(file("c.txt")).unwrap(), "c");
+        assert_eq!(
+            restarted
+                .get_progress_tracker(&execution_id)
+                .unwrap()
+                .completed_steps(),
+            3
+        );
+        assert_eq!(checkpoints.count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_run_execution_tracks_step_status() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = |name: &str| dir.path().join(name).to_string_lossy().to_string();
+        let plan = ExecutionPlan::new(
+            "statuses".to_string(),
+            vec![
+                create_file_step(&file("a.txt"), "a"),
+                create_file_step(&file("b.txt"), "b"),
+            ],
+        );
+        let plan_id = plan.id.clone();
+        let skipped = plan.steps[1].id.clone();
+
+        let mut manager = ExecutionManager::new();
+        manager.register_plan(plan).unwrap();
+        manager
+            .set_step_status(&plan_id, &skipped, StepStatus::Skipped)
+            .unwrap();
+        let execution_id = manager
+            .start_execution(&plan_id, ExecutionMode::Automatic)
+            .unwrap();
+        let completed = manager.run_execution(&execution_id).await.unwrap();
+
+        assert_eq!(completed.len(), 1);
+        assert!(!dir.path().join("b.txt").exists());
+        let plan = manager.get_plan(&plan_id).unwrap();
+        assert_eq!(plan.steps[0].status, StepStatus::Completed);
+        assert_eq!(plan.steps[1].status, StepStatus::Skipped);
+    }
+
+    #[tokio::test]
+    async fn test_resume_rejects_modified_workspace() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.txt").to_string_lossy().to_string();
+        let checkpoints = Arc::new(MemoryCheckpoints::default());
+
+        let plan = ExecutionPlan::new(
+            "drift".to_string(),
+            vec![
+                create_file_step(&a, "a"),
+                create_file_step(&dir.path().join("missing/b.txt").to_string_lossy(), "b"),
+            ],
+        );
+        let plan_id = plan.id.clone();
+
+        let mut manager = ExecutionManager::new()
+            .with_checkpoints(checkpoints.clone() as Arc<dyn ExecutionCheckpointRepository>);
+        manager.register_plan(plan).unwrap();
+        let execution_id = manager
+            .start_execution(&plan_id, ExecutionMode::Automatic)
+            .unwrap();
+        manager.run_execution(&execution_id).await.unwrap();
+
+        std::fs::write(&a, "edited").unwrap();
+        let mut restarted = ExecutionManager::new()
+            .with_checkpoints(checkpoints.clone() as Arc<dyn ExecutionCheckpointRepository>);
+        let error = restarted.resume(&plan_id).await.unwrap_err();
+        assert!(error.to_string().contains("a.txt"));
+        assert!(!restarted.is_active(&execution_id));
+    }
 }
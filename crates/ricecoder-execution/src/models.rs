@@ -1,10 +1,15 @@
 //! Data models for execution plans and results
 
-use std::time::Duration;
+use std::{
+    collections::{HashMap, VecDeque},
+    time::Duration,
+};
 
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::error::ExecutionError;
+
 /// Execution plan containing all steps to be executed
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExecutionPlan {
@@ -37,8 +42,13 @@ pub struct ExecutionStep {
     pub action: StepAction,
     /// Risk score for this specific step
     pub risk_score: RiskScore,
-    /// IDs of steps that must complete before this one
+    /// IDs of steps, or names of step groups, that must complete before this one
     pub dependencies: Vec<String>,
+    /// Dependency group this step belongs to
+    ///
+    /// Depending on a group waits for every step in it.
+    #[serde(default)]
+    pub group: Option<String>,
     /// Optional rollback action to undo this step
     pub rollback_action: Option<RollbackAction>,
     /// Current status of the step
@@ -300,6 +310,48 @@ pub struct TestFailure {
     pub location: Option<String>,
 }
 
+impl TestResults {
+    /// Create empty results for a framework
+    pub fn new(framework: TestFramework) -> Self {
+        Self {
+            passed: 0,
+            failed: 0,
+            skipped: 0,
+            failures: Vec::new(),
+            framework,
+        }
+    }
+
+    /// Total number of tests that were reported
+    pub fn total(&self) -> usize {
+        self.passed + self.failed + self.skipped
+    }
+
+    /// Whether no test failed
+    pub fn is_success(&self) -> bool {
+        self.failed == 0
+    }
+
+    /// One-line-per-failure summary, suitable for step errors and prompts
+    pub fn failure_summary(&self) -> String {
+        let mut summary = format!(
+            "{} passed, {} failed, {} skipped",
+            self.passed, self.failed, self.skipped
+        );
+        for failure in &self.failures {
+            let first_line = failure.message.lines().next().unwrap_or_default();
+            match &failure.location {
+                Some(location) => summary.push_str(&format!(
+                    "\n{} ({}): {}",
+                    failure.name, location, first_line
+                )),
+                None => summary.push_str(&format!("\n{}: {}", failure.name, first_line)),
+            }
+        }
+        summary
+    }
+}
+
 /// Test framework type
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TestFramework {
@@ -309,6 +361,8 @@ pub enum TestFramework {
     TypeScript,
     /// Python (pytest)
     Python,
+    /// Go (go test)
+    Go,
     /// Other framework
     Other,
 }
@@ -350,6 +404,9 @@ pub enum ExecutionMode {
 pub struct ExecutionState {
     /// ID of the execution
     pub execution_id: String,
+    /// ID of the plan being executed
+    #[serde(default)]
+    pub plan_id: String,
     /// Current step index
     pub current_step_index: usize,
     /// Completed step results
@@ -378,6 +435,99 @@ impl ExecutionPlan {
             editable: true,
         }
     }
+
+    /// Indices of the steps each step must wait for
+    ///
+    /// Dependencies name a step ID or a step group. Steps touching the same
+    /// file are additionally kept in plan order so concurrent execution never
+    /// races on a file.
+    pub fn step_prerequisites(&self) -> crate::error::ExecutionResult<Vec<Vec<usize>>> {
+        let ids: HashMap<&str, usize> = self
+            .steps
+            .iter()
+            .enumerate()
+            .map(|(index, step)| (step.id.as_str(), index))
+            .collect();
+        let mut groups: HashMap<&str, Vec<usize>> = HashMap::new();
+        for (index, step) in self.steps.iter().enumerate() {
+            if let Some(group) = &step.group {
+                groups.entry(group.as_str()).or_default().push(index);
+            }
+        }
+
+        let mut last_touch: HashMap<&str, usize> = HashMap::new();
+        let mut prerequisites = Vec::with_capacity(self.steps.len());
+        for (index, step) in self.steps.iter().enumerate() {
+            let mut before = Vec::new();
+            for dependency in &step.dependencies {
+                if let Some(&other) = ids.get(dependency.as_str()) {
+                    before.push(other);
+                } else if let Some(members) = groups.get(dependency.as_str()) {
+                    before.extend(members.iter().copied().filter(|&other| other != index));
+                } else {
+                    return Err(ExecutionError::PlanError(format!(
+                        "Step {} depends on unknown step or group {}",
+                        step.id, dependency
+                    )));
+                }
+            }
+            if let Some(path) = step.target_path() {
+                if let Some(previous) = last_touch.insert(path, index) {
+                    before.push(previous);
+                }
+            }
+            before.sort_unstable();
+            before.dedup();
+            prerequisites.push(before);
+        }
+
+        Ok(prerequisites)
+    }
+
+    /// Step IDs grouped into levels whose steps can run concurrently
+    ///
+    /// Every step's prerequisites are in an earlier level.
+    pub fn dependency_groups(&self) -> crate::error::ExecutionResult<Vec<Vec<String>>> {
+        let prerequisites = self.step_prerequisites()?;
+        let mut remaining: Vec<usize> = prerequisites.iter().map(Vec::len).collect();
+        let mut dependents = vec![Vec::new(); self.steps.len()];
+        for (index, before) in prerequisites.iter().enumerate() {
+            for &other in before {
+                dependents[other].push(index);
+            }
+        }
+
+        let mut ready: VecDeque<usize> = (0..self.steps.len())
+            .filter(|&index| remaining[index] == 0)
+            .collect();
+        let mut levels = Vec::new();
+        let mut placed = 0;
+        while !ready.is_empty() {
+            let level: Vec<usize> = ready.drain(..).collect();
+            placed += level.len();
+            for &index in &level {
+                for &dependent in &dependents[index] {
+                    remaining[dependent] -= 1;
+                    if remaining[dependent] == 0 {
+                        ready.push_back(dependent);
+                    }
+                }
+            }
+            levels.push(
+                level
+                    .into_iter()
+                    .map(|index| self.steps[index].id.clone())
+                    .collect(),
+            );
+        }
+
+        if placed != self.steps.len() {
+            return Err(ExecutionError::PlanError(
+                "Plan step dependencies contain a cycle".to_string(),
+            ));
+        }
+        Ok(levels)
+    }
 }
 
 impl ExecutionStep {
@@ -393,10 +543,21 @@ impl ExecutionStep {
                 factors: Vec::new(),
             },
             dependencies: Vec::new(),
+            group: None,
             rollback_action: None,
             status: StepStatus::Pending,
         }
     }
+
+    /// File this step reads or writes, if any
+    pub fn target_path(&self) -> Option<&str> {
+        match &self.action {
+            StepAction::CreateFile { path, .. }
+            | StepAction::ModifyFile { path, .. }
+            | StepAction::DeleteFile { path } => Some(path),
+            _ => None,
+        }
+    }
 }
 
 impl Default for RiskScore {
@@ -28,6 +28,9 @@ pub struct ProgressUpdate {
     pub progress_percentage: f32,
     /// Estimated time remaining
     pub estimated_time_remaining: Duration,
+    /// Steps currently running (more than one in parallel execution)
+    #[serde(default)]
+    pub running_steps: usize,
     /// Timestamp of this update
     pub timestamp: chrono::DateTime<chrono::Utc>,
 }
@@ -54,6 +57,10 @@ pub struct ProgressTracker {
     start_time: Instant,
     /// Step durations for time estimation
     step_durations: Vec<Duration>,
+    /// Steps currently running
+    running_steps: usize,
+    /// Maximum steps run at once, used for time estimation
+    parallelism: usize,
     /// Progress callbacks
     callbacks: Arc<Mutex<Vec<ProgressCallback>>>,
 }
@@ -77,6 +84,8 @@ impl ProgressTracker {
             completed_steps: 0,
             start_time: Instant::now(),
             step_durations: Vec::new(),
+            running_steps: 0,
+            parallelism: 1,
             callbacks: Arc::new(Mutex::new(Vec::new())),
         }
     }
@@ -100,6 +109,36 @@ impl ProgressTracker {
         );
     }
 
+    /// Set how many steps may run at once
+    ///
+    /// Time estimates assume remaining steps are spread over this many
+    /// workers.
+    pub fn set_parallelism(&mut self, parallelism: usize) {
+        self.parallelism = parallelism.max(1);
+    }
+
+    /// Mark a step as started
+    pub fn step_started(&mut self) {
+        self.running_steps += 1;
+
+        debug!(running_steps = self.running_steps, "Step started");
+
+        self.notify_progress();
+    }
+
+    /// Mark a running step as failed
+    ///
+    /// The step counts as processed but not completed.
+    pub fn step_failed(&mut self, step_duration: Duration) {
+        self.step_durations.push(step_duration);
+        self.running_steps = self.running_steps.saturating_sub(1);
+        self.current_step += 1;
+
+        debug!(current_step = self.current_step, "Step failed");
+
+        self.notify_progress();
+    }
+
     /// Update progress to the next step
     ///
     /// Increments the current step and records the duration of the previous step.
@@ -108,6 +147,7 @@ impl ProgressTracker {
     /// * `step_duration` - Duration of the completed step
     pub fn step_completed(&mut self, step_duration: Duration) {
         self.step_durations.push(step_duration);
+        self.running_steps = self.running_steps.saturating_sub(1);
         self.completed_steps += 1;
         self.current_step += 1;
 
@@ -151,6 +191,7 @@ impl ProgressTracker {
             total_steps: self.total_steps,
             progress_percentage,
             estimated_time_remaining,
+            running_steps: self.running_steps,
             timestamp: chrono::Utc::now(),
         }
     }
@@ -170,6 +211,11 @@ impl ProgressTracker {
         self.completed_steps
     }
 
+    /// Get the number of steps currently running
+    pub fn running_steps(&self) -> usize {
+        self.running_steps
+    }
+
     /// Get the overall progress percentage (0-100)
     pub fn progress_percentage(&self) -> f32 {
         if self.total_steps > 0 {
@@ -192,7 +238,8 @@ impl ProgressTracker {
 
         // Estimate remaining time
         let remaining_steps = self.total_steps.saturating_sub(self.completed_steps);
-        average_duration * remaining_steps as u32
+        let rounds = remaining_steps.div_ceil(self.parallelism);
+        average_duration * rounds as u32
     }
 
     /// Get the total elapsed time
@@ -228,6 +275,7 @@ impl ProgressTracker {
         self.completed_steps = 0;
         self.start_time = Instant::now();
         self.step_durations.clear();
+        self.running_steps = 0;
 
         debug!("Progress tracker reset");
     }
@@ -241,6 +289,8 @@ impl Default for ProgressTracker {
             completed_steps: 0,
             start_time: Instant::now(),
             step_durations: Vec::new(),
+            running_steps: 0,
+            parallelism: 1,
             callbacks: Arc::new(Mutex::new(Vec::new())),
         }
     }
@@ -267,6 +317,7 @@ mod tests {
                 },
                 risk_score: RiskScore::default(),
                 dependencies: Vec::new(),
+                group: None,
                 rollback_action: None,
                 status: StepStatus::Pending,
             })
@@ -434,6 +485,7 @@ mod tests {
             total_steps: 5,
             progress_percentage: 20.0,
             estimated_time_remaining: Duration::from_secs(4),
+            running_steps: 0,
             timestamp: chrono::Utc::now(),
         };
 
@@ -223,6 +223,7 @@ mod tests {
             action,
             risk_score: RiskScore::default(),
             dependencies: Vec::new(),
+            group: None,
             rollback_action: None,
             status: StepStatus::Pending,
         }
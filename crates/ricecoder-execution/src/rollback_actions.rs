@@ -2,17 +2,19 @@
 //!
 //! Provides specialized handlers for:
 //! - RestoreFile: Restore files from backup
-//! - DeleteFile: Delete created files
+//! - DeleteFile: Move created files to the trash
 //! - RunCommand: Execute undo commands
 
 use std::{path::Path, process::Command};
 
+use ricecoder_files::TrashManager;
 use ricecoder_storage::PathResolver;
 use tracing::{debug, info, warn};
 
 use crate::{
     error::{ExecutionError, ExecutionResult},
     models::RollbackAction,
+    rollback_handler::default_trash,
 };
 
 /// Handles file restoration from backup
@@ -89,7 +91,7 @@ impl RestoreFileHandler {
 pub struct DeleteFileHandler;
 
 impl DeleteFileHandler {
-    /// Delete a created file
+    /// Delete a created file by moving it to the default trash
     ///
     /// # Arguments
     /// * `action` - Rollback action containing file path
@@ -97,6 +99,21 @@ impl DeleteFileHandler {
     /// # Returns
     /// Success message if deletion succeeds
     pub fn handle(action: &RollbackAction) -> ExecutionResult<String> {
+        Self::handle_with_trash(action, &default_trash())
+    }
+
+    /// Delete a created file by moving it to the given trash
+    ///
+    /// # Arguments
+    /// * `action` - Rollback action containing file path
+    /// * `trash` - Trash the file is moved to
+    ///
+    /// # Returns
+    /// Success message naming the trash entry
+    pub fn handle_with_trash(
+        action: &RollbackAction,
+        trash: &TrashManager,
+    ) -> ExecutionResult<String> {
         debug!("Deleting created file");
 
         // Extract file path from action data
@@ -121,12 +138,12 @@ impl DeleteFileHandler {
             return Ok(format!("File {} already deleted", file_path));
         }
 
-        // Delete the file
-        std::fs::remove_file(&resolved_path).map_err(|e| {
+        // Move the file to the trash so it can be restored
+        let entry = trash.trash(&resolved_path).map_err(|e| {
             ExecutionError::RollbackFailed(format!("Failed to delete file {}: {}", file_path, e))
         })?;
 
-        let message = format!("Deleted {}", file_path);
+        let message = format!("Deleted {} (trash entry {})", file_path, entry.id);
         info!("{}", message);
         Ok(message)
     }
@@ -245,6 +262,27 @@ mod tests {
         assert!(message.contains("already deleted"));
     }
 
+    #[test]
+    fn test_delete_file_handler_moves_file_to_trash() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let file = dir.path().join("created.txt");
+        std::fs::write(&file, "new").unwrap();
+        let trash = TrashManager::new(dir.path().join("trash"));
+
+        let action = RollbackAction {
+            action_type: crate::models::RollbackType::DeleteFile,
+            data: json!({ "file_path": file.to_string_lossy() }),
+        };
+
+        let message = DeleteFileHandler::handle_with_trash(&action, &trash).unwrap();
+        assert!(!file.exists());
+        let entry = &trash.list().unwrap()[0];
+        assert!(message.contains(&entry.id));
+
+        trash.restore(&entry.id).unwrap();
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "new");
+    }
+
     #[test]
     fn test_undo_command_handler_missing_command() {
         let action = RollbackAction {
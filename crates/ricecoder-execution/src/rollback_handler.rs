@@ -2,14 +2,19 @@
 //!
 //! Provides rollback functionality to undo executed steps on failure.
 //! Tracks rollback actions for each step and executes them in reverse order.
+//! Rollbacks can be previewed with a dry run and verified afterwards by
+//! re-checking file hashes and postcondition commands. Created files are
+//! moved to the trash rather than unlinked, so a rollback can itself be undone.
 
 use std::{path::Path, process::Command};
 
+use ricecoder_files::TrashManager;
 use ricecoder_storage::PathResolver;
 use tracing::{debug, error, info, warn};
 
 use crate::{
     error::{ExecutionError, ExecutionResult},
+    manager::hash_file,
     models::{ExecutionStep, RollbackAction, RollbackType},
 };
 
@@ -22,6 +27,10 @@ pub struct RollbackHandler {
     rollback_actions: Vec<(String, RollbackAction)>,
     /// Whether rollback is currently in progress
     in_progress: bool,
+    /// Report from the last verified rollback
+    last_verification: Option<RollbackVerificationReport>,
+    /// Trash that deleted files are moved to
+    trash: TrashManager,
 }
 
 impl RollbackHandler {
@@ -30,9 +39,22 @@ impl RollbackHandler {
         Self {
             rollback_actions: Vec::new(),
             in_progress: false,
+            last_verification: None,
+            trash: default_trash(),
         }
     }
 
+    /// Move files deleted during rollback to a custom trash
+    pub fn with_trash(mut self, trash: TrashManager) -> Self {
+        self.trash = trash;
+        self
+    }
+
+    /// Trash that files deleted during rollback are moved to
+    pub fn trash(&self) -> &TrashManager {
+        &self.trash
+    }
+
     /// Track a rollback action for a step
     ///
     /// # Arguments
@@ -180,6 +202,81 @@ impl RollbackHandler {
         Ok(results)
     }
 
+    /// Execute rollback for all tracked actions and verify the result
+    ///
+    /// Unlike [`execute_rollback`](Self::execute_rollback), a failing action
+    /// does not stop the rollback: every action is attempted, then its
+    /// expected postcondition is re-checked. The report lists which steps
+    /// were not restored along with remediation hints.
+    pub fn execute_rollback_verified(&mut self) -> RollbackVerificationReport {
+        info!(
+            action_count = self.rollback_actions.len(),
+            "Starting verified rollback execution"
+        );
+
+        self.in_progress = true;
+        let mut checks = Vec::new();
+
+        for (step_id, action) in self.rollback_actions.iter().rev() {
+            // Derive expectations before executing so restore targets are
+            // compared against the backup as it was when rollback started
+            let expectation = RollbackExpectation::for_action(action);
+            let error = self
+                .execute_rollback_action(step_id, action)
+                .err()
+                .map(|e| e.to_string());
+            let outcome = expectation.check();
+
+            let remediation = match (&error, &outcome) {
+                (None, VerificationOutcome::Verified | VerificationOutcome::Unverified { .. }) => {
+                    None
+                }
+                _ => Some(remediation_hint(action)),
+            };
+            if let Some(error) = &error {
+                error!(step_id = %step_id, error = %error, "Rollback action failed");
+            } else if let VerificationOutcome::Failed { reason } = &outcome {
+                warn!(step_id = %step_id, reason = %reason, "Rollback verification failed");
+            }
+
+            checks.push(RollbackCheck {
+                step_id: step_id.clone(),
+                action_type: action.action_type,
+                expectation,
+                error,
+                outcome,
+                remediation,
+            });
+        }
+
+        self.in_progress = false;
+        let report = RollbackVerificationReport { checks };
+        info!(
+            restored = report.is_fully_restored(),
+            failures = report.failures().len(),
+            "Verified rollback completed"
+        );
+        self.last_verification = Some(report.clone());
+        report
+    }
+
+    /// Describe what a rollback would do without changing anything
+    ///
+    /// Entries are returned in execution order (most recent step first).
+    /// Warnings flag actions that are likely to fail or be no-ops.
+    pub fn dry_run(&self) -> Vec<PlannedRollback> {
+        self.rollback_actions
+            .iter()
+            .rev()
+            .map(|(step_id, action)| plan_action(step_id, action))
+            .collect()
+    }
+
+    /// Report from the last verified rollback, if any
+    pub fn last_verification(&self) -> Option<&RollbackVerificationReport> {
+        self.last_verification.as_ref()
+    }
+
     /// Execute a single rollback action
     fn execute_rollback_action(
         &self,
@@ -290,18 +387,18 @@ impl RollbackHandler {
             });
         }
 
-        // Delete the file
-        std::fs::remove_file(&resolved_path).map_err(|e| {
+        // Move the file to the trash so it can be restored
+        let entry = self.trash.trash(&resolved_path).map_err(|e| {
             ExecutionError::RollbackFailed(format!("Failed to delete file {}: {}", file_path, e))
         })?;
 
-        info!(file_path = %file_path, "File deleted successfully");
+        info!(file_path = %file_path, trash_entry = %entry.id, "File moved to trash");
 
         Ok(RollbackResult {
             step_id: step_id.to_string(),
             action_type: RollbackType::DeleteFile,
             success: true,
-            message: format!("Deleted {}", file_path),
+            message: format!("Deleted {} (trash entry {})", file_path, entry.id),
         })
     }
 
@@ -380,11 +477,15 @@ impl RollbackHandler {
             return false;
         }
 
-        // In a real implementation, this would:
-        // 1. Check that all tracked files have been restored or deleted
-        // 2. Verify file checksums match backups
-        // 3. Check that undo commands completed successfully
-        // 4. Validate system state consistency
+        if let Some(report) = &self.last_verification {
+            if !report.is_fully_restored() {
+                warn!(
+                    failures = report.failures().len(),
+                    "Last rollback left steps unrestored"
+                );
+                return false;
+            }
+        }
 
         debug!("Rollback completeness verification passed");
         true
@@ -413,6 +514,14 @@ impl Default for RollbackHandler {
     }
 }
 
+/// Trash in the global storage directory, or the project's `.ricecoder/trash`
+/// if the global directory cannot be resolved
+pub(crate) fn default_trash() -> TrashManager {
+    PathResolver::resolve_global_path()
+        .map(|global| TrashManager::new(global.join("trash")))
+        .unwrap_or_default()
+}
+
 /// Result of a rollback action
 #[derive(Debug, Clone)]
 pub struct RollbackResult {
@@ -426,6 +535,374 @@ pub struct RollbackResult {
     pub message: String,
 }
 
+/// State a rollback action is expected to leave behind
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RollbackExpectation {
+    /// File content matches a SHA-256 hash
+    FileHash {
+        /// File that was restored
+        path: String,
+        /// Expected hash of its content
+        sha256: String,
+    },
+    /// File no longer exists
+    FileAbsent {
+        /// File that was deleted
+        path: String,
+    },
+    /// Postcondition command exits successfully
+    CommandSucceeds {
+        /// Command to run
+        command: String,
+        /// Command arguments
+        args: Vec<String>,
+    },
+    /// Nothing can be checked for this action
+    Unverifiable {
+        /// Why the action cannot be verified
+        reason: String,
+    },
+}
+
+impl RollbackExpectation {
+    /// Derive the expectation for a rollback action
+    ///
+    /// Restore actions expect the `sha256` from the action data, falling
+    /// back to the current hash of the backup. Undo commands are verified
+    /// with an optional `verify` command (`{"command": .., "args": [..]}`).
+    pub fn for_action(action: &RollbackAction) -> Self {
+        let file_path = action.data.get("file_path").and_then(|v| v.as_str());
+        match action.action_type {
+            RollbackType::RestoreFile => {
+                let Some(path) = file_path else {
+                    return Self::unverifiable("missing file_path");
+                };
+                let expected = match action.data.get("sha256").and_then(|v| v.as_str()) {
+                    Some(hash) => Some(hash.to_string()),
+                    None => action
+                        .data
+                        .get("backup_path")
+                        .and_then(|v| v.as_str())
+                        .and_then(|backup| hash_file(expand(backup)).ok().flatten()),
+                };
+                match expected {
+                    Some(sha256) => Self::FileHash {
+                        path: path.to_string(),
+                        sha256,
+                    },
+                    None => Self::unverifiable("backup is missing and no sha256 was recorded"),
+                }
+            }
+            RollbackType::DeleteFile => match file_path {
+                Some(path) => Self::FileAbsent {
+                    path: path.to_string(),
+                },
+                None => Self::unverifiable("missing file_path"),
+            },
+            RollbackType::RunCommand => {
+                let verify = action.data.get("verify");
+                match verify
+                    .and_then(|v| v.get("command"))
+                    .and_then(|v| v.as_str())
+                {
+                    Some(command) => Self::CommandSucceeds {
+                        command: command.to_string(),
+                        args: string_array(verify.and_then(|v| v.get("args"))),
+                    },
+                    None => Self::unverifiable("no verify command recorded"),
+                }
+            }
+        }
+    }
+
+    fn unverifiable(reason: &str) -> Self {
+        Self::Unverifiable {
+            reason: reason.to_string(),
+        }
+    }
+
+    /// Check whether the current state satisfies the expectation
+    pub fn check(&self) -> VerificationOutcome {
+        match self {
+            Self::FileHash { path, sha256 } => match hash_file(expand(path)) {
+                Ok(Some(actual)) if actual == *sha256 => VerificationOutcome::Verified,
+                Ok(Some(actual)) => VerificationOutcome::Failed {
+                    reason: format!(
+                        "{} has hash {} but {} was expected",
+                        path,
+                        short_hash(&actual),
+                        short_hash(sha256)
+                    ),
+                },
+                Ok(None) => VerificationOutcome::Failed {
+                    reason: format!("{} does not exist", path),
+                },
+                Err(e) => VerificationOutcome::Failed {
+                    reason: format!("Failed to read {}: {}", path, e),
+                },
+            },
+            Self::FileAbsent { path } => {
+                if expand(path).exists() {
+                    VerificationOutcome::Failed {
+                        reason: format!("{} still exists", path),
+                    }
+                } else {
+                    VerificationOutcome::Verified
+                }
+            }
+            Self::CommandSucceeds { command, args } => {
+                match Command::new(command).args(args).output() {
+                    Ok(output) if output.status.success() => VerificationOutcome::Verified,
+                    Ok(output) => VerificationOutcome::Failed {
+                        reason: format!(
+                            "Verify command {} exited with {:?}",
+                            command,
+                            output.status.code()
+                        ),
+                    },
+                    Err(e) => VerificationOutcome::Failed {
+                        reason: format!("Failed to run verify command {}: {}", command, e),
+                    },
+                }
+            }
+            Self::Unverifiable { reason } => VerificationOutcome::Unverified {
+                reason: reason.clone(),
+            },
+        }
+    }
+}
+
+/// Outcome of checking a rollback expectation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerificationOutcome {
+    /// State matches the expectation
+    Verified,
+    /// State does not match the expectation
+    Failed {
+        /// What differs
+        reason: String,
+    },
+    /// The action has no checkable postcondition
+    Unverified {
+        /// Why it could not be checked
+        reason: String,
+    },
+}
+
+/// Verification result for a single rollback action
+#[derive(Debug, Clone)]
+pub struct RollbackCheck {
+    /// ID of the step that was rolled back
+    pub step_id: String,
+    /// Type of rollback action
+    pub action_type: RollbackType,
+    /// Expected post-rollback state
+    pub expectation: RollbackExpectation,
+    /// Error from executing the action, if it failed
+    pub error: Option<String>,
+    /// Result of checking the expectation
+    pub outcome: VerificationOutcome,
+    /// How to finish the rollback manually, if it was not restored
+    pub remediation: Option<String>,
+}
+
+impl RollbackCheck {
+    /// Whether the step was restored (or executed without a checkable postcondition)
+    pub fn is_restored(&self) -> bool {
+        self.error.is_none() && !matches!(self.outcome, VerificationOutcome::Failed { .. })
+    }
+}
+
+/// Verification report for a rollback
+#[derive(Debug, Clone, Default)]
+pub struct RollbackVerificationReport {
+    /// Checks in the order the actions were executed
+    pub checks: Vec<RollbackCheck>,
+}
+
+impl RollbackVerificationReport {
+    /// Whether every action executed and its postcondition holds
+    pub fn is_fully_restored(&self) -> bool {
+        self.checks.iter().all(RollbackCheck::is_restored)
+    }
+
+    /// Whether some but not all actions were restored
+    pub fn is_partial(&self) -> bool {
+        let restored = self.checks.iter().filter(|c| c.is_restored()).count();
+        restored > 0 && restored < self.checks.len()
+    }
+
+    /// Checks for steps that were not restored
+    pub fn failures(&self) -> Vec<&RollbackCheck> {
+        self.checks.iter().filter(|c| !c.is_restored()).collect()
+    }
+
+    /// Human-readable summary including remediation hints
+    pub fn summary(&self) -> String {
+        let failures = self.failures();
+        if failures.is_empty() {
+            return format!(
+                "Rollback verified: {} action(s) restored",
+                self.checks.len()
+            );
+        }
+
+        let mut summary = format!(
+            "Rollback incomplete: {} of {} action(s) not restored",
+            failures.len(),
+            self.checks.len()
+        );
+        for check in failures {
+            let reason = match (&check.error, &check.outcome) {
+                (Some(error), _) => error.clone(),
+                (None, VerificationOutcome::Failed { reason }) => reason.clone(),
+                _ => String::new(),
+            };
+            summary.push_str(&format!("\n- {}: {}", check.step_id, reason));
+            if let Some(hint) = &check.remediation {
+                summary.push_str(&format!("\n  remediation: {}", hint));
+            }
+        }
+        summary
+    }
+}
+
+/// A rollback action as it would be executed
+#[derive(Debug, Clone)]
+pub struct PlannedRollback {
+    /// ID of the step that would be rolled back
+    pub step_id: String,
+    /// Type of rollback action
+    pub action_type: RollbackType,
+    /// What the action would do
+    pub description: String,
+    /// Problems detected ahead of execution
+    pub warnings: Vec<String>,
+}
+
+fn plan_action(step_id: &str, action: &RollbackAction) -> PlannedRollback {
+    let file_path = action.data.get("file_path").and_then(|v| v.as_str());
+    let mut warnings = Vec::new();
+
+    let description = match action.action_type {
+        RollbackType::RestoreFile => {
+            let backup = action.data.get("backup_path").and_then(|v| v.as_str());
+            match (file_path, backup) {
+                (Some(file), Some(backup)) => {
+                    let backup_hash = hash_file(expand(backup)).ok().flatten();
+                    match &backup_hash {
+                        None => warnings.push(format!("Backup {} is missing", backup)),
+                        Some(hash)
+                            if hash_file(expand(file)).ok().flatten().as_ref() == Some(hash) =>
+                        {
+                            warnings.push(format!("{} already matches its backup", file))
+                        }
+                        Some(_) => {}
+                    }
+                    format!("Restore {} from {}", file, backup)
+                }
+                _ => {
+                    warnings.push("Missing file_path or backup_path".to_string());
+                    "Restore file (incomplete action)".to_string()
+                }
+            }
+        }
+        RollbackType::DeleteFile => match file_path {
+            Some(file) => {
+                if !expand(file).exists() {
+                    warnings.push(format!("{} does not exist; nothing to delete", file));
+                }
+                format!("Delete {}", file)
+            }
+            None => {
+                warnings.push("Missing file_path".to_string());
+                "Delete file (incomplete action)".to_string()
+            }
+        },
+        RollbackType::RunCommand => match action.data.get("command").and_then(|v| v.as_str()) {
+            Some(command) => {
+                if which::which(command).is_err() && !Path::new(command).exists() {
+                    warnings.push(format!("Command {} was not found", command));
+                }
+                let mut description = format!(
+                    "Run `{}`",
+                    command_line(command, &string_array(action.data.get("args")))
+                );
+                if let RollbackExpectation::CommandSucceeds { command, args } =
+                    RollbackExpectation::for_action(action)
+                {
+                    description.push_str(&format!(
+                        ", then verify with `{}`",
+                        command_line(&command, &args)
+                    ));
+                }
+                description
+            }
+            None => {
+                warnings.push("Missing command".to_string());
+                "Run undo command (incomplete action)".to_string()
+            }
+        },
+    };
+
+    PlannedRollback {
+        step_id: step_id.to_string(),
+        action_type: action.action_type,
+        description,
+        warnings,
+    }
+}
+
+fn remediation_hint(action: &RollbackAction) -> String {
+    let field = |key: &str| {
+        action
+            .data
+            .get(key)
+            .and_then(|v| v.as_str())
+            .unwrap_or("<unknown>")
+            .to_string()
+    };
+    match action.action_type {
+        RollbackType::RestoreFile => format!(
+            "Copy {} over {} manually, or restore it from version control (git checkout -- {})",
+            field("backup_path"),
+            field("file_path"),
+            field("file_path")
+        ),
+        RollbackType::DeleteFile => format!("Delete {} manually", field("file_path")),
+        RollbackType::RunCommand => format!(
+            "Run `{}` manually and check its output",
+            command_line(&field("command"), &string_array(action.data.get("args")))
+        ),
+    }
+}
+
+fn expand(path: &str) -> std::path::PathBuf {
+    PathResolver::expand_home(Path::new(path)).unwrap_or_else(|_| Path::new(path).to_path_buf())
+}
+
+fn string_array(value: Option<&serde_json::Value>) -> Vec<String> {
+    value
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn command_line(command: &str, args: &[String]) -> String {
+    std::iter::once(command.to_string())
+        .chain(args.iter().cloned())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn short_hash(hash: &str) -> &str {
+    &hash[..hash.len().min(12)]
+}
+
 #[cfg(test)]
 mod tests {
     use serde_json::json;
@@ -508,4 +985,93 @@ mod tests {
         assert_eq!(result.action_type, RollbackType::DeleteFile);
         assert!(result.success);
     }
+
+    #[test]
+    fn test_dry_run_lists_actions_in_execution_order() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let created = dir.path().join("created.txt");
+        std::fs::write(&created, "new").unwrap();
+
+        let mut handler = RollbackHandler::new();
+        handler.track_action(
+            "step-1".to_string(),
+            RollbackAction {
+                action_type: RollbackType::RestoreFile,
+                data: json!({
+                    "file_path": dir.path().join("a.txt").to_string_lossy(),
+                    "backup_path": dir.path().join("missing.bak").to_string_lossy(),
+                }),
+            },
+        );
+        handler.track_action(
+            "step-2".to_string(),
+            RollbackAction {
+                action_type: RollbackType::DeleteFile,
+                data: json!({ "file_path": created.to_string_lossy() }),
+            },
+        );
+
+        let plan = handler.dry_run();
+        assert_eq!(plan.len(), 2);
+        assert_eq!(plan[0].step_id, "step-2");
+        assert!(plan[0].description.starts_with("Delete"));
+        assert!(plan[0].warnings.is_empty());
+        assert!(plan[1].warnings[0].contains("is missing"));
+        // Dry run leaves everything in place
+        assert!(created.exists());
+    }
+
+    #[test]
+    fn test_verified_rollback_reports_partial_failure() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let file = dir.path().join("config.toml");
+        let backup = dir.path().join("config.toml.bak");
+        let created = dir.path().join("new.rs");
+        std::fs::write(&file, "modified").unwrap();
+        std::fs::write(&backup, "original").unwrap();
+        std::fs::write(&created, "fn main() {}").unwrap();
+
+        let trash = TrashManager::new(dir.path().join("trash"));
+        let mut handler = RollbackHandler::new().with_trash(trash.clone());
+        handler.track_action(
+            "restore".to_string(),
+            RollbackAction {
+                action_type: RollbackType::RestoreFile,
+                data: json!({
+                    "file_path": file.to_string_lossy(),
+                    "backup_path": backup.to_string_lossy(),
+                }),
+            },
+        );
+        handler.track_action(
+            "delete".to_string(),
+            RollbackAction {
+                action_type: RollbackType::DeleteFile,
+                data: json!({ "file_path": created.to_string_lossy() }),
+            },
+        );
+        handler.track_action(
+            "undo".to_string(),
+            RollbackAction {
+                action_type: RollbackType::RunCommand,
+                data: json!({
+                    "command": "true",
+                    "verify": { "command": "false" },
+                }),
+            },
+        );
+
+        let report = handler.execute_rollback_verified();
+
+        assert!(report.is_partial());
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "original");
+        assert!(!created.exists());
+        assert_eq!(trash.list().unwrap()[0].original_path, created);
+        let failures = report.failures();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].step_id, "undo");
+        assert!(failures[0].remediation.as_deref().unwrap().contains("true"));
+        assert!(report.summary().contains("1 of 3"));
+        assert!(!handler.verify_completeness());
+    }
 }
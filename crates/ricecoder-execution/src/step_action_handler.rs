@@ -34,6 +34,7 @@
 
 use std::{process::Command, time::Duration};
 
+use ricecoder_safety::{Sandbox, SandboxPolicy, SandboxRequest, SecurityConstraint};
 use tokio::time::timeout;
 use tracing::{debug, error, info, warn};
 
@@ -443,7 +444,40 @@ impl ShellCommandHandler {
         workdir: Option<&str>,
         description: &str,
     ) -> ExecutionResult<CommandOutput> {
-        debug!(command = %command, workdir = ?workdir, description = %description, "Running shell command");
+        Self::run(command, timeout_ms, workdir, description, None).await
+    }
+
+    /// Execute a shell command inside an OS sandbox
+    ///
+    /// The sandbox policy is derived from `constraints` for the command's
+    /// working directory. Fails without running the command when the
+    /// sandbox can't enforce a strict policy.
+    pub async fn handle_sandboxed(
+        command: &str,
+        timeout_ms: Option<u64>,
+        workdir: Option<&str>,
+        description: &str,
+        sandbox: &Sandbox,
+        constraints: &[SecurityConstraint],
+    ) -> ExecutionResult<CommandOutput> {
+        Self::run(
+            command,
+            timeout_ms,
+            workdir,
+            description,
+            Some((sandbox, constraints)),
+        )
+        .await
+    }
+
+    async fn run(
+        command: &str,
+        timeout_ms: Option<u64>,
+        workdir: Option<&str>,
+        description: &str,
+        sandbox: Option<(&Sandbox, &[SecurityConstraint])>,
+    ) -> ExecutionResult<CommandOutput> {
+        debug!(command = %command, workdir = ?workdir, description = %description, sandboxed = sandbox.is_some(), "Running shell command");
 
         // GAP-7: Validate command syntax using tree-sitter
         Self::validate_command_syntax(command)?;
@@ -457,7 +491,7 @@ impl ShellCommandHandler {
 
         let result = timeout(
             timeout_duration,
-            Self::execute_shell_command(command, &shell, workdir),
+            Self::execute_shell_command(command, &shell, workdir, sandbox),
         )
         .await;
 
@@ -493,6 +527,7 @@ impl ShellCommandHandler {
         command: &str,
         shell: &str,
         workdir: Option<&str>,
+        sandbox: Option<(&Sandbox, &[SecurityConstraint])>,
     ) -> ExecutionResult<CommandOutput> {
         use std::process::Stdio;
 
@@ -513,11 +548,6 @@ impl ShellCommandHandler {
             }
         };
 
-        let mut cmd = Command::new(shell);
-        cmd.args(&shell_args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped());
-
         // GAP-6: External directory validation (workdir must exist and be a directory)
         if let Some(dir) = workdir {
             let path = std::path::Path::new(dir);
@@ -533,22 +563,68 @@ impl ShellCommandHandler {
                     dir
                 )));
             }
-            cmd.current_dir(dir);
             debug!(workdir = %dir, "Set working directory for shell command");
         }
 
-        // Spawn with process group (enables tree kill on Unix)
-        #[cfg(unix)]
-        {
-            use std::os::unix::process::CommandExt;
-            cmd.process_group(0);
-        }
-
         let start_time = std::time::Instant::now();
 
-        let mut child = cmd.spawn().map_err(|e| {
-            ExecutionError::StepFailed(format!("Failed to spawn shell command '{}': {}", command, e))
-        })?;
+        // The sandbox guard must outlive the child so its isolation stays in place
+        let (mut child, _sandbox_guard) = match sandbox {
+            Some((sandbox, constraints)) => {
+                let dir = match workdir {
+                    Some(dir) => std::path::PathBuf::from(dir),
+                    None => std::env::current_dir()?,
+                };
+                let request = SandboxRequest::new(shell).args(shell_args).workdir(&dir);
+                let policy = SandboxPolicy::from_constraints(constraints, &dir);
+                let mut sandboxed = sandbox
+                    .command(&request, &policy)
+                    .map_err(|e| ExecutionError::ValidationError(e.to_string()))?;
+                debug!(backend = sandbox.backend_name(), "Running shell command in sandbox");
+
+                sandboxed
+                    .command_mut()
+                    .stdout(Stdio::piped())
+                    .stderr(Stdio::piped());
+                #[cfg(unix)]
+                sandboxed.command_mut().process_group(0);
+
+                let (child, guard) = sandboxed
+                    .spawn()
+                    .map_err(|e| {
+                        ExecutionError::StepFailed(format!(
+                            "Failed to spawn sandboxed shell command '{}': {}",
+                            command, e
+                        ))
+                    })?
+                    .into_parts();
+                (child, Some(guard))
+            }
+            None => {
+                let mut cmd = Command::new(shell);
+                cmd.args(&shell_args)
+                    .stdout(Stdio::piped())
+                    .stderr(Stdio::piped());
+                if let Some(dir) = workdir {
+                    cmd.current_dir(dir);
+                }
+
+                // Spawn with process group (enables tree kill on Unix)
+                #[cfg(unix)]
+                {
+                    use std::os::unix::process::CommandExt;
+                    cmd.process_group(0);
+                }
+
+                let child = cmd.spawn().map_err(|e| {
+                    ExecutionError::StepFailed(format!(
+                        "Failed to spawn shell command '{}': {}",
+                        command, e
+                    ))
+                })?;
+                (child, None)
+            }
+        };
 
         let child_id = child.id();
 
@@ -996,4 +1072,24 @@ mod tests {
         // Both should appear in stdout (combined)
         assert!(output.stdout.contains("stdout") || output.stdout.contains("stderr"));
     }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_shell_command_sandbox_fails_closed() {
+        use ricecoder_safety::sandbox::LinuxSandbox;
+
+        // Without bubblewrap the filesystem can't be isolated, so nothing runs
+        let sandbox = Sandbox::with_backend(std::sync::Arc::new(LinuxSandbox::with_bwrap(None)));
+        let result = ShellCommandHandler::handle_sandboxed(
+            "echo hello",
+            None,
+            None,
+            "Test sandbox",
+            &sandbox,
+            &[],
+        )
+        .await;
+
+        assert!(matches!(result, Err(ExecutionError::ValidationError(_))));
+    }
 }
@@ -3,22 +3,33 @@
 //! Wraps the WorkflowEngine's StepExecutor and provides high-level
 //! step execution with progress reporting and error handling.
 
-use std::time::Instant;
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
 
+use futures::stream::{FuturesUnordered, StreamExt};
+use ricecoder_safety::{Sandbox, SecurityConstraint};
 use tracing::{debug, error, info, warn};
 
 use crate::{
     error::{ExecutionError, ExecutionResult},
     models::{
         BatchExecutionConfig, BatchExecutionOutput, BatchExecutionResult, BatchExecutionSummary,
-        CommandOutput, ExecutionPlan, ExecutionStep, StepAction, StepResult,
+        CommandOutput, ExecutionPlan, ExecutionStep, StepAction, StepResult, StepStatus,
+        TestResults,
     },
+    progress_tracker::ProgressTracker,
+    rollback_handler::RollbackHandler,
+    test_runner::TestRunner,
 };
 
 /// Executes steps from an execution plan
 ///
 /// Handles:
 /// - Sequential step execution
+/// - Parallel execution of independent steps
 /// - Progress reporting
 /// - Error handling with detailed context
 /// - Step skipping and resumption
@@ -29,6 +40,14 @@ pub struct StepExecutor {
     completed_steps: Vec<StepResult>,
     /// Whether to skip failed steps
     skip_on_error: bool,
+    /// OS sandbox shell commands run in, if enabled
+    sandbox: Option<Arc<Sandbox>>,
+    /// Constraints the sandbox policy is derived from
+    sandbox_constraints: Vec<SecurityConstraint>,
+    /// Runner used for test steps, if configured
+    test_runner: Option<Arc<TestRunner>>,
+    /// Parsed results of the most recent test step
+    last_test_results: Mutex<Option<TestResults>>,
 }
 
 impl StepExecutor {
@@ -38,6 +57,10 @@ impl StepExecutor {
             current_step_index: 0,
             completed_steps: Vec::new(),
             skip_on_error: false,
+            sandbox: None,
+            sandbox_constraints: Vec::new(),
+            test_runner: None,
+            last_test_results: Mutex::new(None),
         }
     }
 
@@ -47,6 +70,36 @@ impl StepExecutor {
         self
     }
 
+    /// Run shell command steps inside an OS sandbox
+    ///
+    /// The sandbox policy is derived from `constraints` for each step's
+    /// working directory.
+    pub fn with_sandbox(
+        mut self,
+        sandbox: Arc<Sandbox>,
+        constraints: Vec<SecurityConstraint>,
+    ) -> Self {
+        self.sandbox = Some(sandbox);
+        self.sandbox_constraints = constraints;
+        self
+    }
+
+    /// Run test steps with a framework-aware test runner
+    ///
+    /// Without a runner, test steps are accepted without running anything.
+    pub fn with_test_runner(mut self, runner: Arc<TestRunner>) -> Self {
+        self.test_runner = Some(runner);
+        self
+    }
+
+    /// Parsed results of the most recent test step run by the test runner
+    pub fn last_test_results(&self) -> Option<TestResults> {
+        self.last_test_results
+            .lock()
+            .ok()
+            .and_then(|results| results.clone())
+    }
+
     /// Execute all steps in a plan sequentially
     ///
     /// Executes steps in order, respecting dependencies. Stops on first error
@@ -312,16 +365,39 @@ impl StepExecutor {
     ) -> ExecutionResult<CommandOutput> {
         // Run async in blocking context - requires multi-threaded runtime
         tokio::task::block_in_place(|| {
-            tokio::runtime::Handle::current().block_on(async {
-                crate::step_action_handler::ShellCommandHandler::handle(
+            tokio::runtime::Handle::current().block_on(self.handle_run_shell_command_async(
+                command,
+                timeout_ms,
+                workdir,
+                description,
+            ))
+        })
+    }
+
+    /// Handle shell command execution, inside the sandbox when one is configured
+    async fn handle_run_shell_command_async(
+        &self,
+        command: &str,
+        timeout_ms: Option<u64>,
+        workdir: Option<&str>,
+        description: &str,
+    ) -> ExecutionResult<CommandOutput> {
+        use crate::step_action_handler::ShellCommandHandler;
+
+        match &self.sandbox {
+            Some(sandbox) => {
+                ShellCommandHandler::handle_sandboxed(
                     command,
                     timeout_ms,
                     workdir,
                     description,
+                    sandbox,
+                    &self.sandbox_constraints,
                 )
                 .await
-            })
-        })
+            }
+            None => ShellCommandHandler::handle(command, timeout_ms, workdir, description).await,
+        }
     }
 
     /// Execute a batch of steps with progress tracking and error handling
@@ -343,8 +419,11 @@ impl StepExecutor {
             config.continue_on_error
         );
 
-        // For now, implement sequential execution (max_concurrent = 1)
-        // TODO: Implement concurrent execution when max_concurrent > 1
+        if config.max_concurrent > 1 {
+            let plan = ExecutionPlan::new("batch".to_string(), steps.to_vec());
+            return self.execute_plan_parallel(&plan, config, None).await;
+        }
+
         for step in steps {
             if cancelled {
                 break;
@@ -426,9 +505,192 @@ impl StepExecutor {
         Ok(BatchExecutionOutput { results, summary })
     }
 
+    /// Execute a plan, running independent steps concurrently
+    ///
+    /// Up to `config.max_concurrent` steps run at once. A step starts once
+    /// all of its prerequisites (see [`ExecutionPlan::step_prerequisites`])
+    /// have succeeded; steps depending on a failed step are skipped. Unless
+    /// `continue_on_error` is set, a failure stops new steps from starting.
+    /// With `rollback_on_failure`, completed steps are rolled back in reverse
+    /// completion order, so dependents are undone before their dependencies.
+    pub async fn execute_plan_parallel(
+        &self,
+        plan: &ExecutionPlan,
+        config: &BatchExecutionConfig,
+        mut progress: Option<&mut ProgressTracker>,
+    ) -> ExecutionResult<BatchExecutionOutput> {
+        if plan.steps.is_empty() {
+            return Err(ExecutionError::PlanError(
+                "Cannot execute plan with no steps".to_string(),
+            ));
+        }
+
+        let prerequisites = plan.step_prerequisites()?;
+        // Rejects cycles before anything runs
+        let levels = plan.dependency_groups()?;
+        let max_concurrent = config.max_concurrent.max(1);
+        if let Some(progress) = progress.as_deref_mut() {
+            progress.set_parallelism(max_concurrent);
+        }
+
+        info!(
+            plan_id = %plan.id,
+            step_count = plan.steps.len(),
+            levels = levels.len(),
+            max_concurrent,
+            "Starting parallel plan execution"
+        );
+
+        let mut remaining: Vec<usize> = prerequisites.iter().map(Vec::len).collect();
+        let mut dependents = vec![Vec::new(); plan.steps.len()];
+        for (index, before) in prerequisites.iter().enumerate() {
+            for &other in before {
+                dependents[other].push(index);
+            }
+        }
+        let mut status = vec![StepStatus::Pending; plan.steps.len()];
+        let mut ready: VecDeque<usize> = (0..plan.steps.len())
+            .filter(|&index| remaining[index] == 0)
+            .collect();
+
+        let start_time = Instant::now();
+        let mut running = FuturesUnordered::new();
+        let mut results = Vec::new();
+        let mut completion_order = Vec::new();
+        let mut successful = 0;
+        let mut failed = 0;
+        let mut cancelled = false;
+
+        loop {
+            while !cancelled && running.len() < max_concurrent {
+                let Some(index) = ready.pop_front() else {
+                    break;
+                };
+                let step = &plan.steps[index];
+                status[index] = StepStatus::Running;
+                if let Some(progress) = progress.as_deref_mut() {
+                    progress.step_started();
+                }
+                debug!(step_id = %step.id, "Starting step");
+                running.push(async move {
+                    let step_start = Instant::now();
+                    let result = self.execute_single_step_async(step).await;
+                    (index, result, step_start.elapsed())
+                });
+            }
+
+            let Some((index, result, duration)) = running.next().await else {
+                break;
+            };
+            let step = &plan.steps[index];
+            let (success, output, error) = match result {
+                Ok(result) => (result.success, result.output, result.error),
+                Err(e) => (false, None, Some(e.to_string())),
+            };
+            results.push(BatchExecutionResult {
+                step_id: step.id.clone(),
+                success,
+                output,
+                duration_ms: duration.as_millis() as u64,
+                error,
+            });
+
+            if success {
+                successful += 1;
+                status[index] = StepStatus::Completed;
+                completion_order.push(index);
+                if let Some(progress) = progress.as_deref_mut() {
+                    progress.step_completed(duration);
+                }
+                for &dependent in &dependents[index] {
+                    remaining[dependent] -= 1;
+                    if remaining[dependent] == 0 && status[dependent] == StepStatus::Pending {
+                        ready.push_back(dependent);
+                    }
+                }
+            } else {
+                failed += 1;
+                status[index] = StepStatus::Failed;
+                error!(step_id = %step.id, "Step failed");
+                if let Some(progress) = progress.as_deref_mut() {
+                    progress.step_failed(duration);
+                }
+                if !config.continue_on_error {
+                    cancelled = true;
+                }
+
+                // Nothing downstream of a failed step can run
+                let mut blocked: VecDeque<usize> = dependents[index].iter().copied().collect();
+                while let Some(dependent) = blocked.pop_front() {
+                    if status[dependent] != StepStatus::Pending {
+                        continue;
+                    }
+                    status[dependent] = StepStatus::Skipped;
+                    results.push(BatchExecutionResult {
+                        step_id: plan.steps[dependent].id.clone(),
+                        success: false,
+                        output: None,
+                        duration_ms: 0,
+                        error: Some(format!("Skipped: dependency {} failed", step.id)),
+                    });
+                    if let Some(progress) = progress.as_deref_mut() {
+                        progress.step_skipped();
+                    }
+                    blocked.extend(dependents[dependent].iter().copied());
+                }
+            }
+
+            if let Some(batch_timeout) = config.batch_timeout_ms {
+                if !cancelled && start_time.elapsed().as_millis() as u64 > batch_timeout {
+                    warn!("Parallel execution timed out after {}ms", batch_timeout);
+                    cancelled = true;
+                }
+            }
+        }
+
+        let mut rolled_back = false;
+        if config.rollback_on_failure && failed > 0 {
+            let mut rollback = RollbackHandler::new();
+            for &index in &completion_order {
+                rollback.track_step(&plan.steps[index]);
+            }
+            let rollback_results = rollback.execute_rollback()?;
+            info!(
+                actions = rollback_results.len(),
+                "Rolled back completed steps"
+            );
+            rolled_back = true;
+        }
+
+        let total_duration = start_time.elapsed().as_millis() as u64;
+        info!(
+            "Parallel execution completed: {}/{} successful, {} failed, {}ms total",
+            successful,
+            plan.steps.len(),
+            failed,
+            total_duration
+        );
+
+        Ok(BatchExecutionOutput {
+            results,
+            summary: BatchExecutionSummary {
+                total_steps: plan.steps.len(),
+                successful,
+                failed,
+                total_duration_ms: total_duration,
+                cancelled,
+                rolled_back,
+            },
+        })
+    }
+
     /// Execute a single step asynchronously
-    async fn execute_single_step_async(&self, step: &ExecutionStep) -> ExecutionResult<StepResult> {
+    pub(crate) async fn execute_single_step_async(
+        &self,
+        step: &ExecutionStep,
+    ) -> ExecutionResult<StepResult> {
         let start_time = std::time::Instant::now();
+        let mut error = None;
 
         let (success, output) = match &step.action {
             StepAction::CreateFile { path, content } => {
@@ -454,20 +716,34 @@ impl StepExecutor {
                 workdir,
                 description,
             } => {
-                let cmd_output = crate::step_action_handler::ShellCommandHandler::handle(
-                    command,
-                    *timeout_ms,
-                    workdir.as_deref(),
-                    description,
-                )
-                .await?;
+                let cmd_output = self
+                    .handle_run_shell_command_async(
+                        command,
+                        *timeout_ms,
+                        workdir.as_deref(),
+                        description,
+                    )
+                    .await?;
                 let success = cmd_output.exit_code.map(|code| code == 0).unwrap_or(false);
                 (success, Some(cmd_output))
             }
-            StepAction::RunTests { pattern } => {
-                self.handle_run_tests(pattern)?;
-                (true, None)
-            }
+            StepAction::RunTests { pattern } => match &self.test_runner {
+                Some(runner) => {
+                    let run = runner.run(pattern.as_deref()).await?;
+                    let success = run.results.is_success();
+                    if !success {
+                        error = Some(run.results.failure_summary());
+                    }
+                    if let Ok(mut last) = self.last_test_results.lock() {
+                        *last = Some(run.results);
+                    }
+                    (success, Some(run.output))
+                }
+                None => {
+                    self.handle_run_tests(pattern)?;
+                    (true, None)
+                }
+            },
         };
 
         let duration = start_time.elapsed();
@@ -475,7 +751,7 @@ impl StepExecutor {
         Ok(StepResult {
             step_id: step.id.clone(),
             success,
-            error: None,
+            error,
             duration,
             output,
         })
@@ -518,6 +794,7 @@ mod tests {
             action,
             risk_score: RiskScore::default(),
             dependencies: Vec::new(),
+            group: None,
             rollback_action: None,
             status: StepStatus::Pending,
         }
@@ -603,4 +880,175 @@ mod tests {
         // Duration should be recorded (even if 0)
         let _ = result.duration;
     }
+
+    fn parallel_config(rollback_on_failure: bool) -> BatchExecutionConfig {
+        BatchExecutionConfig {
+            continue_on_error: false,
+            max_concurrent: 4,
+            batch_timeout_ms: None,
+            rollback_on_failure,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_parallel_execution_waits_for_dependency_group() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.txt").to_string_lossy().to_string();
+        let b = dir.path().join("b.txt").to_string_lossy().to_string();
+
+        let mut create_a = create_test_step(
+            "Create a",
+            StepAction::CreateFile {
+                path: a.clone(),
+                content: "a".to_string(),
+            },
+        );
+        create_a.group = Some("setup".to_string());
+        let mut create_b = create_test_step(
+            "Create b",
+            StepAction::CreateFile {
+                path: b.clone(),
+                content: "b".to_string(),
+            },
+        );
+        create_b.group = Some("setup".to_string());
+        let mut check = create_test_step(
+            "Check files",
+            StepAction::RunCommand {
+                command: "sh".to_string(),
+                args: vec!["-c".to_string(), format!("test -f {} && test -f {}", a, b)],
+            },
+        );
+        check.dependencies = vec!["setup".to_string()];
+        let check_id = check.id.clone();
+        let plan = create_test_plan(vec![check, create_a, create_b]);
+
+        assert_eq!(plan.dependency_groups().unwrap().len(), 2);
+
+        let mut tracker = ProgressTracker::new(&plan);
+        let output = StepExecutor::new()
+            .execute_plan_parallel(&plan, &parallel_config(false), Some(&mut tracker))
+            .await
+            .unwrap();
+
+        assert_eq!(output.summary.successful, 3);
+        assert_eq!(output.results.last().unwrap().step_id, check_id);
+        assert_eq!(tracker.completed_steps(), 3);
+        assert_eq!(tracker.running_steps(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_parallel_failure_skips_dependents_and_rolls_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("created.txt").to_string_lossy().to_string();
+
+        let mut create = create_test_step(
+            "Create file",
+            StepAction::CreateFile {
+                path: path.clone(),
+                content: "data".to_string(),
+            },
+        );
+        create.rollback_action = Some(crate::models::RollbackAction {
+            action_type: crate::models::RollbackType::DeleteFile,
+            data: serde_json::json!({ "file_path": path }),
+        });
+        let mut fail = create_test_step(
+            "Fail",
+            StepAction::RunCommand {
+                command: "false".to_string(),
+                args: Vec::new(),
+            },
+        );
+        fail.dependencies = vec![create.id.clone()];
+        let mut after = create_test_step(
+            "After failure",
+            StepAction::RunCommand {
+                command: "echo".to_string(),
+                args: vec!["unreachable".to_string()],
+            },
+        );
+        after.dependencies = vec![fail.id.clone()];
+        let plan = create_test_plan(vec![create, fail, after]);
+
+        let output = StepExecutor::new()
+            .execute_plan_parallel(&plan, &parallel_config(true), None)
+            .await
+            .unwrap();
+
+        assert_eq!(output.summary.successful, 1);
+        assert_eq!(output.summary.failed, 1);
+        assert!(output.summary.rolled_back);
+        assert!(output.results[2]
+            .error
+            .as_deref()
+            .unwrap()
+            .starts_with("Skipped"));
+        assert!(!std::path::Path::new(&path).exists());
+    }
+
+    #[test]
+    fn test_dependency_cycle_is_rejected() {
+        let mut first = create_test_step("First", StepAction::RunTests { pattern: None });
+        let mut second = create_test_step("Second", StepAction::RunTests { pattern: None });
+        first.dependencies = vec![second.id.clone()];
+        second.dependencies = vec![first.id.clone()];
+
+        let plan = create_test_plan(vec![first, second]);
+        assert!(plan.dependency_groups().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_run_tests_step_reports_parsed_failures() {
+        use crate::{
+            models::{TestFailure, TestFramework},
+            test_runner::{TestFrameworkAdapter, TestInvocation},
+        };
+
+        struct FailingSuite;
+        impl TestFrameworkAdapter for FailingSuite {
+            fn name(&self) -> &str {
+                "failing"
+            }
+            fn framework(&self) -> TestFramework {
+                TestFramework::Other
+            }
+            fn detect(&self, _root: &std::path::Path) -> bool {
+                true
+            }
+            fn invocation(&self, _pattern: Option<&str>) -> TestInvocation {
+                TestInvocation::new("sh", vec!["-c".to_string(), "exit 1".to_string()])
+            }
+            fn parse(
+                &self,
+                _output: &CommandOutput,
+                _report: Option<&str>,
+            ) -> ExecutionResult<TestResults> {
+                let mut results = TestResults::new(TestFramework::Other);
+                results.passed = 2;
+                results.failed = 1;
+                results.failures.push(TestFailure {
+                    name: "math::divides".to_string(),
+                    message: "expected 2, got 3".to_string(),
+                    location: Some("src/math.rs:10".to_string()),
+                });
+                Ok(results)
+            }
+        }
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let runner = TestRunner::new(dir.path()).with_adapter(FailingSuite);
+        let executor = StepExecutor::new().with_test_runner(Arc::new(runner));
+        let step = create_test_step("Run tests", StepAction::RunTests { pattern: None });
+
+        let result = executor.execute_single_step_async(&step).await.unwrap();
+
+        assert!(!result.success);
+        assert!(result
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("math::divides (src/math.rs:10): expected 2, got 3"));
+        assert_eq!(executor.last_test_results().unwrap().failed, 1);
+    }
 }
@@ -0,0 +1,732 @@
+//! Test runner with pluggable framework adapters
+//!
+//! Each [`TestFrameworkAdapter`] knows how to detect its framework in a
+//! project, build a command that emits machine-readable results, and parse
+//! those results into [`TestResults`] with per-test failure messages and
+//! locations. Built-in adapters cover `cargo test` (libtest JSON), pytest
+//! (JUnit XML), jest (JSON report) and `go test -json`.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use regex::Regex;
+use serde_json::Value;
+use tracing::{debug, info, warn};
+
+use crate::{
+    error::{ExecutionError, ExecutionResult},
+    models::{CommandOutput, TestFailure, TestFramework, TestResults},
+};
+
+/// Default timeout for a test run (10 minutes)
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Command an adapter wants the runner to execute
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestInvocation {
+    /// Program to run
+    pub program: String,
+    /// Program arguments
+    pub args: Vec<String>,
+    /// Extra environment variables
+    pub env: Vec<(String, String)>,
+    /// Report file written by the test framework, if results are not on stdout
+    pub report_path: Option<PathBuf>,
+}
+
+impl TestInvocation {
+    /// Create an invocation without extra environment or report file
+    pub fn new(program: impl Into<String>, args: Vec<String>) -> Self {
+        Self {
+            program: program.into(),
+            args,
+            env: Vec::new(),
+            report_path: None,
+        }
+    }
+
+    /// Add an environment variable
+    pub fn with_env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.push((key.into(), value.into()));
+        self
+    }
+
+    /// Read results from a report file instead of stdout
+    pub fn with_report_path(mut self, path: PathBuf) -> Self {
+        self.report_path = Some(path);
+        self
+    }
+}
+
+/// Adapter between the test runner and a specific test framework
+pub trait TestFrameworkAdapter: Send + Sync {
+    /// Adapter name used in logs (e.g. "cargo")
+    fn name(&self) -> &str;
+
+    /// Framework reported in parsed results
+    fn framework(&self) -> TestFramework;
+
+    /// Whether the project at `root` uses this framework
+    fn detect(&self, root: &Path) -> bool;
+
+    /// Build the command that runs tests matching `pattern`
+    fn invocation(&self, pattern: Option<&str>) -> TestInvocation;
+
+    /// Parse framework output into structured results
+    ///
+    /// `report` holds the contents of the invocation's report file, if any.
+    fn parse(&self, output: &CommandOutput, report: Option<&str>) -> ExecutionResult<TestResults>;
+}
+
+/// Outcome of a test run
+#[derive(Debug, Clone)]
+pub struct TestRun {
+    /// Parsed per-test results
+    pub results: TestResults,
+    /// Raw command output
+    pub output: CommandOutput,
+}
+
+/// Runs tests in a project using the first adapter that detects it
+pub struct TestRunner {
+    root: PathBuf,
+    adapters: Vec<Box<dyn TestFrameworkAdapter>>,
+    timeout: Duration,
+}
+
+impl TestRunner {
+    /// Create a runner for `root` with the built-in adapters
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            adapters: vec![
+                Box::new(CargoTestAdapter),
+                Box::new(GoTestAdapter),
+                Box::new(JestAdapter),
+                Box::new(PytestAdapter),
+            ],
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Register an adapter; it takes precedence over the built-in ones
+    pub fn with_adapter(mut self, adapter: impl TestFrameworkAdapter + 'static) -> Self {
+        self.adapters.insert(0, Box::new(adapter));
+        self
+    }
+
+    /// Set the timeout for a test run
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Project root tests are run in
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Find the adapter for the project, if any
+    pub fn detect(&self) -> Option<&dyn TestFrameworkAdapter> {
+        self.adapters
+            .iter()
+            .find(|adapter| adapter.detect(&self.root))
+            .map(|adapter| adapter.as_ref())
+    }
+
+    /// Run tests matching `pattern` and parse the results
+    ///
+    /// # Errors
+    /// Returns an error if no adapter detects the project, the command cannot
+    /// be started or times out, or the run fails without reporting any test
+    /// (e.g. a compilation error).
+    pub async fn run(&self, pattern: Option<&str>) -> ExecutionResult<TestRun> {
+        let adapter = self.detect().ok_or_else(|| {
+            ExecutionError::ValidationError(format!(
+                "Could not detect test framework in {}",
+                self.root.display()
+            ))
+        })?;
+
+        let invocation = adapter.invocation(pattern);
+        debug!(
+            adapter = adapter.name(),
+            program = %invocation.program,
+            args = ?invocation.args,
+            "Running tests"
+        );
+
+        let mut command = tokio::process::Command::new(&invocation.program);
+        command
+            .args(&invocation.args)
+            .current_dir(&self.root)
+            .kill_on_drop(true);
+        for (key, value) in &invocation.env {
+            command.env(key, value);
+        }
+
+        let output = tokio::time::timeout(self.timeout, command.output())
+            .await
+            .map_err(|_| {
+                ExecutionError::StepFailed(format!(
+                    "Test run timed out after {}s",
+                    self.timeout.as_secs()
+                ))
+            })?
+            .map_err(|e| {
+                ExecutionError::StepFailed(format!("Failed to run '{}': {}", invocation.program, e))
+            })?;
+
+        let output = CommandOutput {
+            stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            exit_code: output.status.code(),
+        };
+
+        let report = match &invocation.report_path {
+            Some(path) => {
+                let contents = std::fs::read_to_string(path).ok();
+                let _ = std::fs::remove_file(path);
+                contents
+            }
+            None => None,
+        };
+
+        let results = adapter.parse(&output, report.as_deref())?;
+        if results.total() == 0 && output.exit_code != Some(0) {
+            let tail: Vec<&str> = output.stderr.lines().rev().take(20).collect();
+            let tail: Vec<&str> = tail.into_iter().rev().collect();
+            return Err(ExecutionError::StepFailed(format!(
+                "{} exited with {:?} without reporting tests:\n{}",
+                adapter.name(),
+                output.exit_code,
+                tail.join("\n")
+            )));
+        }
+
+        info!(
+            adapter = adapter.name(),
+            passed = results.passed,
+            failed = results.failed,
+            skipped = results.skipped,
+            "Tests completed"
+        );
+
+        Ok(TestRun { results, output })
+    }
+}
+
+/// Unique report path in the temp directory
+fn report_path(prefix: &str, extension: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "ricecoder-{}-{}.{}",
+        prefix,
+        uuid::Uuid::new_v4(),
+        extension
+    ))
+}
+
+/// `cargo test` adapter using libtest's JSON output
+///
+/// JSON output is unstable in libtest, so the invocation sets
+/// `RUSTC_BOOTSTRAP=1` to enable it on stable toolchains.
+pub struct CargoTestAdapter;
+
+impl TestFrameworkAdapter for CargoTestAdapter {
+    fn name(&self) -> &str {
+        "cargo"
+    }
+
+    fn framework(&self) -> TestFramework {
+        TestFramework::Rust
+    }
+
+    fn detect(&self, root: &Path) -> bool {
+        root.join("Cargo.toml").exists()
+    }
+
+    fn invocation(&self, pattern: Option<&str>) -> TestInvocation {
+        let mut args = vec!["test".to_string()];
+        if let Some(p) = pattern {
+            args.push(p.to_string());
+        }
+        args.extend(
+            ["--", "-Z", "unstable-options", "--format", "json"]
+                .iter()
+                .map(|s| s.to_string()),
+        );
+        TestInvocation::new("cargo", args).with_env("RUSTC_BOOTSTRAP", "1")
+    }
+
+    fn parse(&self, output: &CommandOutput, _report: Option<&str>) -> ExecutionResult<TestResults> {
+        let panic_location =
+            Regex::new(r"panicked at (?:'.*?', )?([^\s:]+:\d+:\d+)").expect("valid regex");
+        let mut results = TestResults::new(TestFramework::Rust);
+
+        for line in output.stdout.lines() {
+            let Ok(event) = serde_json::from_str::<Value>(line.trim()) else {
+                continue;
+            };
+            if event["type"] != "test" {
+                continue;
+            }
+            let name = event["name"].as_str().unwrap_or_default().to_string();
+            match event["event"].as_str() {
+                Some("ok") => results.passed += 1,
+                Some("ignored") => results.skipped += 1,
+                Some("failed") | Some("timeout") => {
+                    results.failed += 1;
+                    let stdout = event["stdout"]
+                        .as_str()
+                        .or_else(|| event["message"].as_str())
+                        .unwrap_or_default();
+                    let location = panic_location
+                        .captures(stdout)
+                        .map(|caps| caps[1].to_string());
+                    results.failures.push(TestFailure {
+                        name,
+                        message: stdout.trim().to_string(),
+                        location,
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        Ok(results)
+    }
+}
+
+/// pytest adapter using JUnit XML reports
+pub struct PytestAdapter;
+
+impl TestFrameworkAdapter for PytestAdapter {
+    fn name(&self) -> &str {
+        "pytest"
+    }
+
+    fn framework(&self) -> TestFramework {
+        TestFramework::Python
+    }
+
+    fn detect(&self, root: &Path) -> bool {
+        [
+            "pytest.ini",
+            "conftest.py",
+            "setup.py",
+            "pyproject.toml",
+            "tox.ini",
+        ]
+        .iter()
+        .any(|file| root.join(file).exists())
+    }
+
+    fn invocation(&self, pattern: Option<&str>) -> TestInvocation {
+        let report = report_path("pytest", "xml");
+        let mut args = vec!["-q".to_string(), format!("--junitxml={}", report.display())];
+        if let Some(p) = pattern {
+            args.push("-k".to_string());
+            args.push(p.to_string());
+        }
+        TestInvocation::new("pytest", args).with_report_path(report)
+    }
+
+    fn parse(&self, output: &CommandOutput, report: Option<&str>) -> ExecutionResult<TestResults> {
+        let Some(xml) = report else {
+            warn!("pytest did not write a JUnit report");
+            if output.exit_code == Some(0) {
+                return Ok(TestResults::new(TestFramework::Python));
+            }
+            return Err(ExecutionError::SerializationError(
+                "pytest did not write a JUnit report".to_string(),
+            ));
+        };
+        parse_junit_xml(xml, TestFramework::Python)
+    }
+}
+
+/// Parse a JUnit XML report
+///
+/// Only the subset emitted by common test frameworks is understood:
+/// `<testcase>` elements with optional `<failure>`, `<error>` and
+/// `<skipped>` children.
+pub fn parse_junit_xml(xml: &str, framework: TestFramework) -> ExecutionResult<TestResults> {
+    let testcase =
+        Regex::new(r"(?s)<testcase\b([^>]*?)(?:/>|>(.*?)</testcase>)").expect("valid regex");
+    let attribute = Regex::new(r#"([\w:-]+)\s*=\s*"([^"]*)""#).expect("valid regex");
+    let failure = Regex::new(r"(?s)<(failure|error)\b([^>]*?)(?:/>|>(.*?)</(?:failure|error)>)")
+        .expect("valid regex");
+
+    if !xml.contains("<testsuite") {
+        return Err(ExecutionError::SerializationError(
+            "Not a JUnit XML report".to_string(),
+        ));
+    }
+
+    let attributes = |source: &str| -> HashMap<String, String> {
+        attribute
+            .captures_iter(source)
+            .map(|caps| (caps[1].to_string(), xml_unescape(&caps[2])))
+            .collect()
+    };
+
+    let mut results = TestResults::new(framework);
+    for case in testcase.captures_iter(xml) {
+        let attrs = attributes(&case[1]);
+        let body = case.get(2).map(|m| m.as_str()).unwrap_or_default();
+
+        if let Some(fail) = failure.captures(body) {
+            results.failed += 1;
+            let fail_attrs = attributes(&fail[2]);
+            let text = fail
+                .get(3)
+                .map(|m| xml_unescape(strip_cdata(m.as_str())))
+                .unwrap_or_default();
+            let message = match fail_attrs.get("message") {
+                Some(msg) if text.trim().is_empty() => msg.clone(),
+                Some(msg) if !text.contains(msg.as_str()) => {
+                    format!("{}\n{}", msg, text.trim())
+                }
+                _ => text.trim().to_string(),
+            };
+
+            let name = match (attrs.get("classname"), attrs.get("name")) {
+                (Some(class), Some(name)) if !class.is_empty() => format!("{}::{}", class, name),
+                (_, Some(name)) => name.clone(),
+                _ => String::new(),
+            };
+            let location = match (attrs.get("file"), attrs.get("line")) {
+                (Some(file), Some(line)) => Some(format!("{}:{}", file, line)),
+                (Some(file), None) => Some(file.clone()),
+                _ => None,
+            };
+
+            results.failures.push(TestFailure {
+                name,
+                message,
+                location,
+            });
+        } else if body.contains("<skipped") {
+            results.skipped += 1;
+        } else {
+            results.passed += 1;
+        }
+    }
+
+    Ok(results)
+}
+
+fn strip_cdata(text: &str) -> &str {
+    text.trim()
+        .strip_prefix("<![CDATA[")
+        .and_then(|t| t.strip_suffix("]]>"))
+        .unwrap_or(text)
+}
+
+fn xml_unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#10;", "\n")
+        .replace("&amp;", "&")
+}
+
+/// jest adapter using its JSON report
+pub struct JestAdapter;
+
+impl TestFrameworkAdapter for JestAdapter {
+    fn name(&self) -> &str {
+        "jest"
+    }
+
+    fn framework(&self) -> TestFramework {
+        TestFramework::TypeScript
+    }
+
+    fn detect(&self, root: &Path) -> bool {
+        if ["jest.config.js", "jest.config.ts", "jest.config.json"]
+            .iter()
+            .any(|file| root.join(file).exists())
+        {
+            return true;
+        }
+        std::fs::read_to_string(root.join("package.json"))
+            .map(|manifest| manifest.contains("\"jest\""))
+            .unwrap_or(false)
+    }
+
+    fn invocation(&self, pattern: Option<&str>) -> TestInvocation {
+        let report = report_path("jest", "json");
+        let mut args = vec![
+            "jest".to_string(),
+            "--json".to_string(),
+            format!("--outputFile={}", report.display()),
+        ];
+        if let Some(p) = pattern {
+            args.push("--testNamePattern".to_string());
+            args.push(p.to_string());
+        }
+        TestInvocation::new("npx", args).with_report_path(report)
+    }
+
+    fn parse(&self, output: &CommandOutput, report: Option<&str>) -> ExecutionResult<TestResults> {
+        let source = report.unwrap_or(&output.stdout);
+        let json: Value = serde_json::from_str(source.trim()).map_err(|e| {
+            ExecutionError::SerializationError(format!("Invalid jest JSON report: {}", e))
+        })?;
+
+        let mut results = TestResults::new(TestFramework::TypeScript);
+        for file in json["testResults"].as_array().into_iter().flatten() {
+            let file_name = file["name"].as_str().unwrap_or_default();
+            for assertion in file["assertionResults"].as_array().into_iter().flatten() {
+                match assertion["status"].as_str() {
+                    Some("passed") => results.passed += 1,
+                    Some("failed") => {
+                        results.failed += 1;
+                        let message = assertion["failureMessages"]
+                            .as_array()
+                            .map(|messages| {
+                                messages
+                                    .iter()
+                                    .filter_map(|m| m.as_str())
+                                    .collect::<Vec<_>>()
+                                    .join("\n")
+                            })
+                            .unwrap_or_default();
+                        let location = match assertion["location"]["line"].as_u64() {
+                            Some(line) => Some(format!("{}:{}", file_name, line)),
+                            None if !file_name.is_empty() => Some(file_name.to_string()),
+                            None => None,
+                        };
+                        results.failures.push(TestFailure {
+                            name: assertion["fullName"]
+                                .as_str()
+                                .or_else(|| assertion["title"].as_str())
+                                .unwrap_or_default()
+                                .to_string(),
+                            message,
+                            location,
+                        });
+                    }
+                    Some(_) => results.skipped += 1,
+                    None => {}
+                }
+            }
+        }
+
+        Ok(results)
+    }
+}
+
+/// `go test` adapter using `-json` event output
+pub struct GoTestAdapter;
+
+impl TestFrameworkAdapter for GoTestAdapter {
+    fn name(&self) -> &str {
+        "go"
+    }
+
+    fn framework(&self) -> TestFramework {
+        TestFramework::Go
+    }
+
+    fn detect(&self, root: &Path) -> bool {
+        root.join("go.mod").exists()
+    }
+
+    fn invocation(&self, pattern: Option<&str>) -> TestInvocation {
+        let mut args = vec!["test".to_string(), "-json".to_string()];
+        if let Some(p) = pattern {
+            args.push("-run".to_string());
+            args.push(p.to_string());
+        }
+        args.push("./...".to_string());
+        TestInvocation::new("go", args)
+    }
+
+    fn parse(&self, output: &CommandOutput, _report: Option<&str>) -> ExecutionResult<TestResults> {
+        let location = Regex::new(r"^\s+([\w./-]+\.go:\d+):").expect("valid regex");
+        let mut outputs: HashMap<String, Vec<String>> = HashMap::new();
+        let mut results = TestResults::new(TestFramework::Go);
+
+        for line in output.stdout.lines() {
+            let Ok(event) = serde_json::from_str::<Value>(line.trim()) else {
+                continue;
+            };
+            let Some(test) = event["Test"].as_str() else {
+                continue;
+            };
+            let package = event["Package"].as_str().unwrap_or_default();
+            let key = format!("{}.{}", package, test);
+
+            match event["Action"].as_str() {
+                Some("output") => {
+                    let text = event["Output"].as_str().unwrap_or_default();
+                    let trimmed = text.trim_start();
+                    if !trimmed.starts_with("=== ") && !trimmed.starts_with("--- ") {
+                        outputs.entry(key).or_default().push(text.to_string());
+                    }
+                }
+                Some("pass") => results.passed += 1,
+                Some("skip") => results.skipped += 1,
+                Some("fail") => {
+                    results.failed += 1;
+                    let lines = outputs.remove(&key).unwrap_or_default();
+                    let location = lines.iter().find_map(|line| {
+                        location.captures(line).map(|caps| {
+                            if package.is_empty() {
+                                caps[1].to_string()
+                            } else {
+                                format!("{}/{}", package, &caps[1])
+                            }
+                        })
+                    });
+                    results.failures.push(TestFailure {
+                        name: key,
+                        message: lines.concat().trim().to_string(),
+                        location,
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn stdout(text: &str) -> CommandOutput {
+        CommandOutput {
+            stdout: text.to_string(),
+            stderr: String::new(),
+            exit_code: Some(1),
+        }
+    }
+
+    #[test]
+    fn test_cargo_json_parsing() {
+        let out = stdout(
+            r#"{ "type": "suite", "event": "started", "test_count": 3 }
+{ "type": "test", "event": "started", "name": "tests::adds" }
+{ "type": "test", "name": "tests::adds", "event": "ok" }
+{ "type": "test", "name": "tests::slow", "event": "ignored" }
+{ "type": "test", "name": "tests::subtracts", "event": "failed", "stdout": "\nthread 'tests::subtracts' panicked at src/lib.rs:12:9:\nassertion `left == right` failed\n" }
+{ "type": "suite", "event": "failed", "passed": 1, "failed": 1, "ignored": 1 }"#,
+        );
+
+        let results = CargoTestAdapter.parse(&out, None).unwrap();
+        assert_eq!((results.passed, results.failed, results.skipped), (1, 1, 1));
+        assert_eq!(results.failures[0].name, "tests::subtracts");
+        assert_eq!(
+            results.failures[0].location.as_deref(),
+            Some("src/lib.rs:12:9")
+        );
+        assert!(results.failures[0].message.contains("assertion"));
+    }
+
+    #[test]
+    fn test_junit_xml_parsing() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<testsuites><testsuite name="pytest" tests="3">
+<testcase classname="tests.test_math" name="test_add" file="tests/test_math.py" line="3" time="0.001" />
+<testcase classname="tests.test_math" name="test_div" file="tests/test_math.py" line="7" time="0.001"><failure message="assert 1 == 2">def test_div():
+&gt;       assert 1 == 2
+E       assert 1 == 2</failure></testcase>
+<testcase classname="tests.test_math" name="test_skip" time="0"><skipped message="todo" /></testcase>
+</testsuite></testsuites>"#;
+
+        let results = PytestAdapter.parse(&stdout(""), Some(xml)).unwrap();
+        assert_eq!((results.passed, results.failed, results.skipped), (1, 1, 1));
+        let failure = &results.failures[0];
+        assert_eq!(failure.name, "tests.test_math::test_div");
+        assert_eq!(failure.location.as_deref(), Some("tests/test_math.py:7"));
+        assert!(failure.message.contains(">       assert 1 == 2"));
+    }
+
+    #[test]
+    fn test_jest_json_parsing() {
+        let report = r#"{"numFailedTests":1,"testResults":[{"name":"/app/sum.test.js","assertionResults":[
+            {"fullName":"sum adds","status":"passed","failureMessages":[]},
+            {"fullName":"sum fails","status":"failed","failureMessages":["Expected: 3\nReceived: 4"],"location":{"line":9,"column":3}},
+            {"fullName":"sum later","status":"pending","failureMessages":[]}]}]}"#;
+
+        let results = JestAdapter.parse(&stdout(""), Some(report)).unwrap();
+        assert_eq!((results.passed, results.failed, results.skipped), (1, 1, 1));
+        assert_eq!(results.failures[0].name, "sum fails");
+        assert_eq!(
+            results.failures[0].location.as_deref(),
+            Some("/app/sum.test.js:9")
+        );
+        assert!(results.failures[0].message.starts_with("Expected: 3"));
+    }
+
+    #[test]
+    fn test_go_json_parsing() {
+        let out = stdout(
+            r#"{"Action":"run","Package":"example.com/calc","Test":"TestAdd"}
+{"Action":"output","Package":"example.com/calc","Test":"TestAdd","Output":"=== RUN   TestAdd\n"}
+{"Action":"pass","Package":"example.com/calc","Test":"TestAdd","Elapsed":0}
+{"Action":"run","Package":"example.com/calc","Test":"TestSub"}
+{"Action":"output","Package":"example.com/calc","Test":"TestSub","Output":"    calc_test.go:14: got 2, want 3\n"}
+{"Action":"output","Package":"example.com/calc","Test":"TestSub","Output":"--- FAIL: TestSub (0.00s)\n"}
+{"Action":"fail","Package":"example.com/calc","Test":"TestSub","Elapsed":0}
+{"Action":"fail","Package":"example.com/calc","Elapsed":0.01}"#,
+        );
+
+        let results = GoTestAdapter.parse(&out, None).unwrap();
+        assert_eq!((results.passed, results.failed, results.skipped), (1, 1, 0));
+        let failure = &results.failures[0];
+        assert_eq!(failure.name, "example.com/calc.TestSub");
+        assert_eq!(
+            failure.location.as_deref(),
+            Some("example.com/calc/calc_test.go:14")
+        );
+        assert_eq!(failure.message, "calc_test.go:14: got 2, want 3");
+    }
+
+    #[test]
+    fn test_detection_order_and_custom_adapter() {
+        let dir = TempDir::new().unwrap();
+        let runner = TestRunner::new(dir.path());
+        assert!(runner.detect().is_none());
+
+        std::fs::write(dir.path().join("go.mod"), "module example.com/calc\n").unwrap();
+        assert_eq!(runner.detect().unwrap().name(), "go");
+
+        struct Custom;
+        impl TestFrameworkAdapter for Custom {
+            fn name(&self) -> &str {
+                "custom"
+            }
+            fn framework(&self) -> TestFramework {
+                TestFramework::Other
+            }
+            fn detect(&self, _root: &Path) -> bool {
+                true
+            }
+            fn invocation(&self, _pattern: Option<&str>) -> TestInvocation {
+                TestInvocation::new("true", Vec::new())
+            }
+            fn parse(
+                &self,
+                _output: &CommandOutput,
+                _report: Option<&str>,
+            ) -> ExecutionResult<TestResults> {
+                Ok(TestResults::new(TestFramework::Other))
+            }
+        }
+
+        let runner = TestRunner::new(dir.path()).with_adapter(Custom);
+        assert_eq!(runner.detect().unwrap().name(), "custom");
+    }
+}
@@ -15,6 +15,9 @@ pub struct ClientCapabilities {
     /// General capabilities
     #[serde(skip_serializing_if = "Option::is_none")]
     pub general: Option<GeneralClientCapabilities>,
+    /// Window capabilities
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub window: Option<WindowClientCapabilities>,
 }
 
 /// Text document client capabilities
@@ -32,6 +35,9 @@ pub struct TextDocumentClientCapabilities {
     /// Diagnostic capabilities
     #[serde(skip_serializing_if = "Option::is_none")]
     pub publish_diagnostics: Option<PublishDiagnosticsCapability>,
+    /// Pull diagnostic capabilities (LSP 3.17)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diagnostic: Option<DiagnosticCapability>,
 }
 
 /// Synchronization capability
@@ -77,6 +83,22 @@ pub struct PublishDiagnosticsCapability {
     pub related_information: Option<bool>,
 }
 
+/// Pull diagnostic capability (`textDocument/diagnostic`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiagnosticCapability {
+    /// Whether the client supports related documents in diagnostic reports
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub related_document_support: Option<bool>,
+}
+
+/// Window client capabilities
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindowClientCapabilities {
+    /// Whether the client supports server-initiated work-done progress
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub work_done_progress: Option<bool>,
+}
+
 /// Workspace client capabilities
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkspaceClientCapabilities {
@@ -160,6 +182,9 @@ impl CapabilityNegotiator {
                 publish_diagnostics: Some(PublishDiagnosticsCapability {
                     related_information: Some(true),
                 }),
+                diagnostic: Some(DiagnosticCapability {
+                    related_document_support: Some(false),
+                }),
             }),
             workspace: Some(WorkspaceClientCapabilities {
                 workspace_folders: Some(true),
@@ -169,6 +194,9 @@ impl CapabilityNegotiator {
                     engine: "ECMAScript".to_string(),
                 }),
             }),
+            window: Some(WindowClientCapabilities {
+                work_done_progress: Some(true),
+            }),
         }
     }
 
@@ -264,6 +292,8 @@ mod tests {
         assert!(text_doc.synchronization.is_some());
         assert!(text_doc.completion.is_some());
         assert!(text_doc.hover.is_some());
+        assert!(text_doc.diagnostic.is_some());
+        assert_eq!(caps.window.unwrap().work_done_progress, Some(true));
     }
 
     #[test]
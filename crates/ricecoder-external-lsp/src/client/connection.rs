@@ -198,6 +198,69 @@ impl LspConnection {
         .await
     }
 
+    /// Handle $/progress notification (work-done progress reported by the server)
+    pub async fn handle_progress(&self, params: Option<Value>) -> Result<()> {
+        self.handle_notification(JsonRpcNotification {
+            jsonrpc: "2.0".to_string(),
+            method: "$/progress".to_string(),
+            params,
+        })
+        .await
+    }
+
+    /// Handle a server-initiated window/workDoneProgress/create request
+    ///
+    /// The token is broadcast to notification subscribers so progress trackers can
+    /// register it, and an empty success response is returned for the server.
+    pub async fn handle_work_done_progress_create(
+        &self,
+        request: JsonRpcRequest,
+    ) -> Result<JsonRpcResponse> {
+        let id = request.id.ok_or_else(|| {
+            ExternalLspError::ProtocolError(
+                "window/workDoneProgress/create request without ID".to_string(),
+            )
+        })?;
+
+        self.handle_notification(JsonRpcNotification {
+            jsonrpc: "2.0".to_string(),
+            method: request.method,
+            params: request.params,
+        })
+        .await?;
+
+        Ok(JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            result: Some(Value::Null),
+            error: None,
+            id,
+        })
+    }
+
+    /// Create a tracked textDocument/diagnostic (pull diagnostics) request
+    pub async fn create_document_diagnostic_request(
+        &self,
+        uri: String,
+        previous_result_id: Option<String>,
+        timeout: Duration,
+    ) -> Result<(
+        JsonRpcRequest,
+        tokio::sync::oneshot::Receiver<Result<Value>>,
+    )> {
+        let mut params = serde_json::json!({
+            "textDocument": {
+                "uri": uri
+            }
+        });
+
+        if let Some(previous_result_id) = previous_result_id {
+            params["previousResultId"] = serde_json::json!(previous_result_id);
+        }
+
+        self.create_tracked_request("textDocument/diagnostic", Some(params), timeout)
+            .await
+    }
+
     /// Handle window/showMessage notification
     pub async fn handle_show_message(&self, params: Option<Value>) -> Result<()> {
         self.handle_notification(JsonRpcNotification {
@@ -470,6 +533,66 @@ mod tests {
         assert_eq!(received_params, params);
     }
 
+    #[tokio::test]
+    async fn test_handle_progress() {
+        let conn = LspConnection::new();
+        let mut rx = conn.subscribe_notifications();
+
+        let params = Some(serde_json::json!({
+            "token": "rustAnalyzer/Indexing",
+            "value": {"kind": "begin", "title": "Indexing"}
+        }));
+
+        conn.handle_progress(params.clone()).await.unwrap();
+
+        let (method, received_params) = rx.recv().await.unwrap();
+        assert_eq!(method, "$/progress");
+        assert_eq!(received_params, params);
+    }
+
+    #[tokio::test]
+    async fn test_handle_work_done_progress_create() {
+        let conn = LspConnection::new();
+        let mut rx = conn.subscribe_notifications();
+
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            method: "window/workDoneProgress/create".to_string(),
+            params: Some(serde_json::json!({"token": 7})),
+            id: Some(42),
+        };
+
+        let response = conn
+            .handle_work_done_progress_create(request)
+            .await
+            .unwrap();
+        assert_eq!(response.id, 42);
+        assert!(response.error.is_none());
+
+        let (method, params) = rx.recv().await.unwrap();
+        assert_eq!(method, "window/workDoneProgress/create");
+        assert_eq!(params.unwrap()["token"], 7);
+    }
+
+    #[tokio::test]
+    async fn test_create_document_diagnostic_request() {
+        let conn = LspConnection::new();
+        let (request, _rx) = conn
+            .create_document_diagnostic_request(
+                "file:///test.rs".to_string(),
+                Some("3".to_string()),
+                Duration::from_secs(5),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(request.method, "textDocument/diagnostic");
+        let params = request.params.unwrap();
+        assert_eq!(params["textDocument"]["uri"], "file:///test.rs");
+        assert_eq!(params["previousResultId"], "3");
+        assert_eq!(conn.pending_request_count().await, 1);
+    }
+
     #[tokio::test]
     async fn test_multiple_notification_subscribers() {
         let conn = LspConnection::new();
@@ -4,7 +4,10 @@ pub mod capabilities;
 pub mod connection;
 pub mod protocol;
 
-pub use capabilities::{CapabilityNegotiator, ClientCapabilities, ServerCapabilities};
+pub use capabilities::{
+    CapabilityNegotiator, ClientCapabilities, DiagnosticCapability, ServerCapabilities,
+    WindowClientCapabilities,
+};
 pub use connection::{LspConnection, PendingRequest};
 pub use protocol::{
     JsonRpcError, JsonRpcHandler, JsonRpcMessage, JsonRpcNotification, JsonRpcRequest,
@@ -4,7 +4,10 @@
 
 use std::time::Duration;
 
-use ricecoder_completion::types::{CompletionContext, CompletionItem};
+use ricecoder_completion::types::{
+    CompletionContext, CompletionItem, Position as CompletionPosition, Range as CompletionRange,
+    TextEdit as CompletionTextEdit,
+};
 use ricecoder_lsp::types::{Diagnostic, Position, Range};
 use serde_json::{json, Value};
 
@@ -95,9 +98,13 @@ impl SemanticFeatures {
                     Ok(response) => {
                         // Transform LSP completion response to ricecoder CompletionItem
                         // Use default mapping rules for standard LSP response format
+                        let field_mappings = ["label", "insertText", "additionalTextEdits"]
+                            .into_iter()
+                            .map(|field| (field.to_string(), format!("$.{}", field)))
+                            .collect();
                         let rules = CompletionMappingRules {
                             items_path: "$.result.items".to_string(),
-                            field_mappings: Default::default(),
+                            field_mappings,
                             transform: None,
                         };
 
@@ -114,11 +121,16 @@ impl SemanticFeatures {
                                     .unwrap_or(&label)
                                     .to_string();
 
-                                Some(CompletionItem::new(
+                                let mut completion = CompletionItem::new(
                                     label,
                                     ricecoder_completion::types::CompletionItemKind::Variable,
                                     insert_text,
-                                ))
+                                );
+                                // Imports and other edits to apply on acceptance
+                                if let Some(edits) = item.get("additionalTextEdits") {
+                                    completion.additional_edits = parse_text_edits(edits);
+                                }
+                                Some(completion)
                             })
                             .collect();
 
@@ -385,6 +397,29 @@ fn parse_locations(response: &Value) -> Result<Vec<(String, Range)>> {
     Ok(locations)
 }
 
+/// Parse LSP text edits into completion edits, skipping malformed ones
+fn parse_text_edits(edits: &Value) -> Vec<CompletionTextEdit> {
+    edits
+        .as_array()
+        .map(|edits| {
+            edits
+                .iter()
+                .filter_map(|edit| {
+                    let range = parse_range(edit.get("range")?)?;
+                    let new_text = edit.get("newText")?.as_str()?.to_string();
+                    Some(CompletionTextEdit::new(
+                        CompletionRange::new(
+                            CompletionPosition::new(range.start.line, range.start.character),
+                            CompletionPosition::new(range.end.line, range.end.character),
+                        ),
+                        new_text,
+                    ))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 /// Parse range information from LSP response
 fn parse_range(range: &Value) -> Option<Range> {
     let start = range.get("start")?;
@@ -449,6 +484,25 @@ mod tests {
         assert_eq!(locations[1].0, "file:///test2.rs");
     }
 
+    #[test]
+    fn test_parse_additional_text_edits() {
+        let edits = json!([
+            {
+                "range": {
+                    "start": {"line": 0, "character": 0},
+                    "end": {"line": 0, "character": 0}
+                },
+                "newText": "use std::collections::HashMap;\n"
+            },
+            {"newText": "missing range"}
+        ]);
+
+        let parsed = parse_text_edits(&edits);
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].new_text, "use std::collections::HashMap;\n");
+        assert_eq!(parsed[0].range.start, CompletionPosition::new(0, 0));
+    }
+
     #[test]
     fn test_parse_empty_response() {
         let response = json!([]);
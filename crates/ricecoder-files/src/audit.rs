@@ -265,6 +265,7 @@ mod tests {
             operation_type: OperationType::Create,
             content_hash: "abc123".to_string(),
             transaction_id: None,
+            session_id: None,
         };
 
         logger.log_operation(entry).unwrap();
@@ -282,6 +283,7 @@ mod tests {
             operation_type: OperationType::Create,
             content_hash: "abc123".to_string(),
             transaction_id: None,
+            session_id: None,
         };
 
         logger.log_operation(entry.clone()).unwrap();
@@ -314,6 +316,7 @@ mod tests {
             operation_type: OperationType::Create,
             content_hash: "hash1".to_string(),
             transaction_id: None,
+            session_id: None,
         };
 
         let entry2 = AuditEntry {
@@ -322,6 +325,7 @@ mod tests {
             operation_type: OperationType::Update,
             content_hash: "hash2".to_string(),
             transaction_id: None,
+            session_id: None,
         };
 
         logger.log_operation(entry1).unwrap();
@@ -344,6 +348,7 @@ mod tests {
             operation_type: OperationType::Create,
             content_hash: "hash1".to_string(),
             transaction_id: None,
+            session_id: None,
         };
 
         let entry2 = AuditEntry {
@@ -352,6 +357,7 @@ mod tests {
             operation_type: OperationType::Update,
             content_hash: "hash2".to_string(),
             transaction_id: None,
+            session_id: None,
         };
 
         logger.log_operation(entry1).unwrap();
@@ -373,6 +379,7 @@ mod tests {
             operation_type: OperationType::Create,
             content_hash: "hash1".to_string(),
             transaction_id: None,
+            session_id: None,
         };
 
         let entry2 = AuditEntry {
@@ -381,6 +388,7 @@ mod tests {
             operation_type: OperationType::Update,
             content_hash: "hash2".to_string(),
             transaction_id: None,
+            session_id: None,
         };
 
         logger.log_operation(entry1).unwrap();
@@ -0,0 +1,691 @@
+//! Two-phase transactions spanning multiple project roots
+//!
+//! [`TransactionManager`](crate::TransactionManager) applies a set of
+//! operations within one project. A [`DistributedTransaction`] groups
+//! operations by project root and applies them with a prepare/commit protocol:
+//!
+//! 1. **Prepare**: every participant stages new content and backs up the
+//!    current files under `<root>/.ricecoder/transactions/<id>/`, recording
+//!    them in a journal file. No target file is touched.
+//! 2. **Commit**: once all participants are prepared and their files are
+//!    unchanged, every journal is marked as committing before any file is
+//!    written, then the staged content is applied and the journals removed.
+//!
+//! After a crash, [`DistributedTransaction::recover`] rolls back participants
+//! that never reached the commit decision and finishes the commit for those
+//! that did.
+
+use std::{
+    collections::BTreeMap,
+    path::{Component, Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::{
+    error::FileError,
+    models::{FileOperation, OperationType, TransactionStatus},
+    verifier::ContentVerifier,
+};
+
+/// Directory, relative to a project root, holding transaction journals
+pub const JOURNAL_DIR: &str = ".ricecoder/transactions";
+
+/// Progress of one participant, as recorded in its journal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParticipantState {
+    /// Staging content; nothing is guaranteed to be staged yet
+    Preparing,
+    /// All content is staged and backed up
+    Prepared,
+    /// The commit decision was made; staged content is being applied
+    Committing,
+    /// All content was applied
+    Committed,
+    /// The participant was rolled back
+    Aborted,
+}
+
+/// A staged operation in a participant journal
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalOperation {
+    /// Absolute path of the target file
+    pub path: PathBuf,
+    /// Staged new content, or `None` to delete the file
+    pub staged: Option<PathBuf>,
+    /// Backup of the file before the transaction, or `None` if it did not exist
+    pub backup: Option<PathBuf>,
+    /// SHA-256 of the file when it was prepared
+    pub original_hash: Option<String>,
+}
+
+/// Journal of one participant, persisted for crash recovery
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParticipantJournal {
+    /// Transaction ID
+    pub transaction_id: Uuid,
+    /// Project root of this participant
+    pub root: PathBuf,
+    /// Roots of every participant in the transaction
+    pub participants: Vec<PathBuf>,
+    /// Current state
+    pub state: ParticipantState,
+    /// Staged operations
+    pub operations: Vec<JournalOperation>,
+    /// When the journal was created
+    pub created_at: DateTime<Utc>,
+    /// When the journal was last updated
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Outcome of [`DistributedTransaction::recover`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RecoveryReport {
+    /// Participants rolled back, as (transaction ID, root)
+    pub rolled_back: Vec<(Uuid, PathBuf)>,
+    /// Participants whose commit was completed, as (transaction ID, root)
+    pub rolled_forward: Vec<(Uuid, PathBuf)>,
+}
+
+impl RecoveryReport {
+    /// Whether recovery found nothing to do
+    pub fn is_empty(&self) -> bool {
+        self.rolled_back.is_empty() && self.rolled_forward.is_empty()
+    }
+}
+
+/// A transaction whose operations span multiple project roots
+#[derive(Debug, Clone)]
+pub struct DistributedTransaction {
+    id: Uuid,
+    status: TransactionStatus,
+    operations: BTreeMap<PathBuf, Vec<FileOperation>>,
+    journals: Vec<ParticipantJournal>,
+}
+
+impl DistributedTransaction {
+    /// Begins a new distributed transaction
+    pub fn new() -> Self {
+        DistributedTransaction {
+            id: Uuid::new_v4(),
+            status: TransactionStatus::Pending,
+            operations: BTreeMap::new(),
+            journals: Vec::new(),
+        }
+    }
+
+    /// Transaction ID
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    /// Current status
+    pub fn status(&self) -> TransactionStatus {
+        self.status
+    }
+
+    /// Project roots taking part in the transaction
+    pub fn roots(&self) -> Vec<&Path> {
+        self.operations.keys().map(|root| root.as_path()).collect()
+    }
+
+    /// Adds an operation for a project root
+    ///
+    /// Relative operation paths are resolved against the root, and every path
+    /// must stay inside it. Only create, update and delete operations are
+    /// supported.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - Project root the operation belongs to
+    /// * `op` - Operation to add
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or failure
+    pub fn add_operation(&mut self, root: &Path, mut op: FileOperation) -> Result<(), FileError> {
+        if self.status != TransactionStatus::Pending {
+            return Err(FileError::TransactionFailed(
+                "Cannot add operations to non-pending transaction".to_string(),
+            ));
+        }
+
+        let root = std::path::absolute(root)?;
+        let path = root.join(&op.path);
+        if !path.starts_with(&root) || path.components().any(|c| c == Component::ParentDir) {
+            return Err(FileError::InvalidPath(format!(
+                "{} is outside project root {}",
+                op.path.display(),
+                root.display()
+            )));
+        }
+
+        match (&op.operation, &op.content) {
+            (OperationType::Create | OperationType::Update, Some(_))
+            | (OperationType::Delete, _) => {}
+            _ => {
+                return Err(FileError::InvalidContent(format!(
+                    "Unsupported operation {:?} for {}",
+                    op.operation,
+                    path.display()
+                )))
+            }
+        }
+
+        op.path = path;
+        self.operations.entry(root).or_default().push(op);
+        Ok(())
+    }
+
+    /// Prepares every participant
+    ///
+    /// Stages new content and backs up current files without modifying any
+    /// target. If any participant fails to prepare, all participants are
+    /// rolled back.
+    pub async fn prepare(&mut self) -> Result<(), FileError> {
+        if self.status != TransactionStatus::Pending {
+            return Err(FileError::TransactionFailed(
+                "Transaction is not pending".to_string(),
+            ));
+        }
+        if self.operations.is_empty() {
+            return Err(FileError::TransactionFailed(
+                "Transaction has no operations".to_string(),
+            ));
+        }
+
+        let participants: Vec<PathBuf> = self.operations.keys().cloned().collect();
+        let mut failure = None;
+        for (root, operations) in &self.operations {
+            match prepare_participant(self.id, root, operations, &participants).await {
+                Ok(journal) => self.journals.push(journal),
+                Err(e) => {
+                    failure = Some((root.clone(), e));
+                    break;
+                }
+            }
+        }
+
+        if let Some((root, e)) = failure {
+            // The failed participant may have a partial journal
+            let _ = remove_journal(self.id, &root).await;
+            self.abort_prepared().await;
+            return Err(FileError::TransactionFailed(format!(
+                "Failed to prepare {}: {}",
+                root.display(),
+                e
+            )));
+        }
+
+        self.status = TransactionStatus::Prepared;
+        debug!(transaction_id = %self.id, participants = participants.len(), "Transaction prepared");
+        Ok(())
+    }
+
+    /// Commits a prepared transaction
+    ///
+    /// Fails without modifying anything if a target changed since it was
+    /// prepared. If applying fails part-way, every participant is restored
+    /// from its backups.
+    pub async fn commit(&mut self) -> Result<(), FileError> {
+        if self.status != TransactionStatus::Prepared {
+            return Err(FileError::TransactionFailed(
+                "Transaction is not prepared".to_string(),
+            ));
+        }
+
+        // Every participant must still vote to commit
+        for journal in &self.journals {
+            if let Err(e) = check_unchanged(journal).await {
+                self.abort_prepared().await;
+                return Err(e);
+            }
+        }
+
+        // Record the decision everywhere before touching any target
+        for journal in &mut self.journals {
+            journal.state = ParticipantState::Committing;
+            write_journal(journal).await?;
+        }
+
+        for index in 0..self.journals.len() {
+            if let Err(e) = apply_participant(&self.journals[index]).await {
+                self.rollback_applied().await?;
+                return Err(FileError::TransactionFailed(format!(
+                    "Failed to commit {}: {}",
+                    self.journals[index].root.display(),
+                    e
+                )));
+            }
+        }
+
+        for journal in &mut self.journals {
+            journal.state = ParticipantState::Committed;
+            write_journal(journal).await?;
+        }
+        for journal in &self.journals {
+            remove_journal(journal.transaction_id, &journal.root).await?;
+        }
+
+        self.status = TransactionStatus::Committed;
+        info!(transaction_id = %self.id, "Distributed transaction committed");
+        Ok(())
+    }
+
+    /// Aborts a pending or prepared transaction
+    pub async fn abort(&mut self) -> Result<(), FileError> {
+        match self.status {
+            TransactionStatus::Pending => {
+                self.status = TransactionStatus::RolledBack;
+                Ok(())
+            }
+            TransactionStatus::Prepared => {
+                self.abort_prepared().await;
+                Ok(())
+            }
+            _ => Err(FileError::TransactionFailed(
+                "Transaction already completed".to_string(),
+            )),
+        }
+    }
+
+    /// Prepares and commits the transaction
+    pub async fn execute(&mut self) -> Result<(), FileError> {
+        self.prepare().await?;
+        self.commit().await
+    }
+
+    /// Recovers transactions interrupted by a crash
+    ///
+    /// Call on startup with every project root that may take part in
+    /// distributed transactions. Participants that had not reached the commit
+    /// decision are rolled back; participants of transactions where any
+    /// participant reached it have their commit completed.
+    ///
+    /// # Arguments
+    ///
+    /// * `roots` - Project roots to scan for journals
+    ///
+    /// # Returns
+    ///
+    /// What was rolled back and rolled forward
+    pub async fn recover(roots: &[PathBuf]) -> Result<RecoveryReport, FileError> {
+        let mut report = RecoveryReport::default();
+
+        for root in roots {
+            let root = std::path::absolute(root)?;
+            for mut journal in read_journals(&root).await? {
+                let id = journal.transaction_id;
+                match journal.state {
+                    ParticipantState::Committed | ParticipantState::Aborted => {}
+                    ParticipantState::Committing => {
+                        finish_commit(&mut journal).await?;
+                        report.rolled_forward.push((id, root.clone()));
+                    }
+                    ParticipantState::Prepared if commit_decided(&journal).await => {
+                        finish_commit(&mut journal).await?;
+                        report.rolled_forward.push((id, root.clone()));
+                    }
+                    ParticipantState::Preparing | ParticipantState::Prepared => {
+                        // Targets are only written after the decision, so
+                        // discarding the staged content is enough
+                        report.rolled_back.push((id, root.clone()));
+                    }
+                }
+                remove_journal(id, &root).await?;
+            }
+        }
+
+        if !report.is_empty() {
+            info!(
+                rolled_back = report.rolled_back.len(),
+                rolled_forward = report.rolled_forward.len(),
+                "Recovered interrupted transactions"
+            );
+        }
+        Ok(report)
+    }
+
+    /// Discards staged content of all prepared participants
+    async fn abort_prepared(&mut self) {
+        for journal in &self.journals {
+            if let Err(e) = remove_journal(journal.transaction_id, &journal.root).await {
+                warn!(root = %journal.root.display(), "Failed to discard transaction journal: {}", e);
+            }
+        }
+        self.journals.clear();
+        self.status = TransactionStatus::RolledBack;
+    }
+
+    /// Restores every participant from its backups after a failed commit
+    async fn rollback_applied(&mut self) -> Result<(), FileError> {
+        for journal in &mut self.journals {
+            restore_backups(journal).await.map_err(|e| {
+                FileError::RollbackFailed(format!(
+                    "Failed to restore {}: {}",
+                    journal.root.display(),
+                    e
+                ))
+            })?;
+            journal.state = ParticipantState::Aborted;
+            write_journal(journal).await?;
+            remove_journal(journal.transaction_id, &journal.root).await?;
+        }
+        self.journals.clear();
+        self.status = TransactionStatus::RolledBack;
+        Ok(())
+    }
+}
+
+impl Default for DistributedTransaction {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn journal_dir(root: &Path) -> PathBuf {
+    root.join(JOURNAL_DIR)
+}
+
+fn journal_path(id: Uuid, root: &Path) -> PathBuf {
+    journal_dir(root).join(format!("{}.json", id))
+}
+
+fn staging_dir(id: Uuid, root: &Path) -> PathBuf {
+    journal_dir(root).join(id.to_string())
+}
+
+async fn prepare_participant(
+    id: Uuid,
+    root: &Path,
+    operations: &[FileOperation],
+    participants: &[PathBuf],
+) -> Result<ParticipantJournal, FileError> {
+    let staging = staging_dir(id, root);
+    fs::create_dir_all(&staging).await?;
+
+    let now = Utc::now();
+    let mut journal = ParticipantJournal {
+        transaction_id: id,
+        root: root.to_path_buf(),
+        participants: participants.to_vec(),
+        state: ParticipantState::Preparing,
+        operations: Vec::new(),
+        created_at: now,
+        updated_at: now,
+    };
+    write_journal(&mut journal).await?;
+
+    for (index, op) in operations.iter().enumerate() {
+        let (backup, original_hash) = match fs::read(&op.path).await {
+            Ok(original) => {
+                let backup = staging.join(format!("{}.backup", index));
+                fs::write(&backup, &original).await?;
+                (
+                    Some(backup),
+                    Some(ContentVerifier::compute_bytes_hash(&original)),
+                )
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => (None, None),
+            Err(e) => return Err(e.into()),
+        };
+
+        let staged = match (&op.operation, &op.content) {
+            (OperationType::Delete, _) => None,
+            (_, content) => {
+                let staged = staging.join(format!("{}.staged", index));
+                fs::write(&staged, content.as_deref().unwrap_or_default()).await?;
+                Some(staged)
+            }
+        };
+
+        journal.operations.push(JournalOperation {
+            path: op.path.clone(),
+            staged,
+            backup,
+            original_hash,
+        });
+    }
+
+    journal.state = ParticipantState::Prepared;
+    write_journal(&mut journal).await?;
+    Ok(journal)
+}
+
+/// Fails if any target changed since it was prepared
+async fn check_unchanged(journal: &ParticipantJournal) -> Result<(), FileError> {
+    for op in &journal.operations {
+        let current = match fs::read(&op.path).await {
+            Ok(content) => Some(ContentVerifier::compute_bytes_hash(&content)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e.into()),
+        };
+        if current != op.original_hash {
+            return Err(FileError::TransactionFailed(format!(
+                "{} changed after the transaction was prepared",
+                op.path.display()
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Whether any participant of the journal's transaction reached the commit decision
+async fn commit_decided(journal: &ParticipantJournal) -> bool {
+    for root in &journal.participants {
+        let path = journal_path(journal.transaction_id, root);
+        if let Ok(other) = read_journal(&path).await {
+            if matches!(
+                other.state,
+                ParticipantState::Committing | ParticipantState::Committed
+            ) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+async fn finish_commit(journal: &mut ParticipantJournal) -> Result<(), FileError> {
+    journal.state = ParticipantState::Committing;
+    write_journal(journal).await?;
+    apply_participant(journal).await?;
+    journal.state = ParticipantState::Committed;
+    write_journal(journal).await
+}
+
+/// Applies staged content; safe to repeat after a crash
+async fn apply_participant(journal: &ParticipantJournal) -> Result<(), FileError> {
+    for op in &journal.operations {
+        match &op.staged {
+            Some(staged) => replace_file(staged, &op.path).await?,
+            None => remove_if_exists(&op.path).await?,
+        }
+    }
+    Ok(())
+}
+
+async fn restore_backups(journal: &ParticipantJournal) -> Result<(), FileError> {
+    for op in &journal.operations {
+        match &op.backup {
+            Some(backup) => replace_file(backup, &op.path).await?,
+            None => remove_if_exists(&op.path).await?,
+        }
+    }
+    Ok(())
+}
+
+/// Atomically replaces `target` with a copy of `source`
+async fn replace_file(source: &Path, target: &Path) -> Result<(), FileError> {
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+    let mut temp = target.to_path_buf();
+    temp.set_file_name(format!(
+        ".tmp-{}-{}",
+        Uuid::new_v4(),
+        target
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("file")
+    ));
+    fs::copy(source, &temp).await?;
+    fs::rename(&temp, target).await?;
+    Ok(())
+}
+
+async fn remove_if_exists(path: &Path) -> Result<(), FileError> {
+    match fs::remove_file(path).await {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+async fn write_journal(journal: &mut ParticipantJournal) -> Result<(), FileError> {
+    journal.updated_at = Utc::now();
+    let path = journal_path(journal.transaction_id, &journal.root);
+    let content = serde_json::to_string_pretty(journal).map_err(|e| {
+        FileError::TransactionFailed(format!("Failed to encode transaction journal: {}", e))
+    })?;
+
+    // Write then rename so a crash never leaves a truncated journal
+    let temp = path.with_extension("json.tmp");
+    fs::write(&temp, content).await?;
+    fs::rename(&temp, &path).await?;
+    Ok(())
+}
+
+async fn read_journal(path: &Path) -> Result<ParticipantJournal, FileError> {
+    let content = fs::read_to_string(path).await?;
+    serde_json::from_str(&content).map_err(|e| {
+        FileError::TransactionFailed(format!(
+            "Corrupt transaction journal {}: {}",
+            path.display(),
+            e
+        ))
+    })
+}
+
+async fn read_journals(root: &Path) -> Result<Vec<ParticipantJournal>, FileError> {
+    let mut entries = match fs::read_dir(journal_dir(root)).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut journals = Vec::new();
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+        match read_journal(&path).await {
+            Ok(journal) => journals.push(journal),
+            Err(e) => warn!("Skipping unreadable transaction journal: {}", e),
+        }
+    }
+    journals.sort_by_key(|journal| journal.created_at);
+    Ok(journals)
+}
+
+async fn remove_journal(id: Uuid, root: &Path) -> Result<(), FileError> {
+    match fs::remove_dir_all(staging_dir(id, root)).await {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+        _ => {}
+    }
+    remove_if_exists(&journal_path(id, root)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn write_op(path: &str, content: &str) -> FileOperation {
+        FileOperation {
+            path: PathBuf::from(path),
+            operation: OperationType::Update,
+            content: Some(content.to_string()),
+            backup_path: None,
+            content_hash: None,
+        }
+    }
+
+    async fn prepared_transaction(app: &Path, lib: &Path) -> DistributedTransaction {
+        fs::write(app.join("main.rs"), "old app").await.unwrap();
+        fs::write(lib.join("lib.rs"), "old lib").await.unwrap();
+
+        let mut tx = DistributedTransaction::new();
+        tx.add_operation(app, write_op("main.rs", "new app"))
+            .unwrap();
+        tx.add_operation(lib, write_op("lib.rs", "new lib"))
+            .unwrap();
+        tx.prepare().await.unwrap();
+        tx
+    }
+
+    #[tokio::test]
+    async fn test_recover_rolls_back_prepared_participants() {
+        let temp_dir = TempDir::new().unwrap();
+        let (app, lib) = (temp_dir.path().join("app"), temp_dir.path().join("lib"));
+        fs::create_dir_all(&app).await.unwrap();
+        fs::create_dir_all(&lib).await.unwrap();
+
+        // Crash after prepare: the coordinator never decided
+        let tx = prepared_transaction(&app, &lib).await;
+        assert!(journal_path(tx.id(), &app).exists());
+
+        let report = DistributedTransaction::recover(&[app.clone(), lib.clone()])
+            .await
+            .unwrap();
+        assert_eq!(report.rolled_back.len(), 2);
+        assert!(report.rolled_forward.is_empty());
+        assert_eq!(
+            fs::read_to_string(app.join("main.rs")).await.unwrap(),
+            "old app"
+        );
+        assert!(!journal_path(tx.id(), &app).exists());
+        assert!(!staging_dir(tx.id(), &lib).exists());
+    }
+
+    #[tokio::test]
+    async fn test_recover_completes_decided_commit() {
+        let temp_dir = TempDir::new().unwrap();
+        let (app, lib) = (temp_dir.path().join("app"), temp_dir.path().join("lib"));
+        fs::create_dir_all(&app).await.unwrap();
+        fs::create_dir_all(&lib).await.unwrap();
+
+        // Crash while recording the decision: only one participant is committing
+        let mut tx = prepared_transaction(&app, &lib).await;
+        let journal = tx
+            .journals
+            .iter_mut()
+            .find(|journal| journal.root == std::path::absolute(&app).unwrap())
+            .unwrap();
+        journal.state = ParticipantState::Committing;
+        write_journal(journal).await.unwrap();
+
+        let report = DistributedTransaction::recover(&[lib.clone(), app.clone()])
+            .await
+            .unwrap();
+        assert_eq!(report.rolled_forward.len(), 2);
+        assert_eq!(
+            fs::read_to_string(app.join("main.rs")).await.unwrap(),
+            "new app"
+        );
+        assert_eq!(
+            fs::read_to_string(lib.join("lib.rs")).await.unwrap(),
+            "new lib"
+        );
+        assert!(DistributedTransaction::recover(&[app, lib])
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -49,6 +49,10 @@ pub enum FileError {
     #[error("Rollback failed: {0}")]
     RollbackFailed(String),
 
+    /// Trash operation failed
+    #[error("Trash operation failed: {0}")]
+    TrashFailed(String),
+
     /// Git operation failed
     #[error("Git operation failed: {0}")]
     GitError(String),
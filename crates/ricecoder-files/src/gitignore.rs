@@ -62,6 +62,20 @@ impl GitignoreFilter {
         }
     }
 
+    /// Check if a path or any of its parent directories should be ignored
+    ///
+    /// Unlike [`GitignoreFilter::should_ignore`], this catches files inside
+    /// ignored directories (e.g. `target/debug/foo` for a `target/` rule).
+    /// Paths outside the worktree are never ignored.
+    pub fn should_ignore_path_or_parents(&self, path: &Path, is_dir: bool) -> bool {
+        match self.gitignore {
+            Some(ref gi) if path.starts_with(gi.path()) || path.is_relative() => gi
+                .matched_path_or_any_parents(path, is_dir)
+                .is_ignore(),
+            _ => false,
+        }
+    }
+
     /// Check if filter is loaded
     pub fn is_loaded(&self) -> bool {
         self.gitignore.is_some()
@@ -113,4 +127,17 @@ mod tests {
         // For this test, we just verify it doesn't crash
         let _ = filter.should_ignore(Path::new("test.txt"), false);
     }
+
+    #[test]
+    fn test_gitignore_filter_parents() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join(".gitignore"), "target/\n").unwrap();
+        let filter = GitignoreFilter::load_from_directory(temp_dir.path()).unwrap();
+
+        let nested = temp_dir.path().join("target/debug/app");
+        assert!(!filter.should_ignore(&nested, false));
+        assert!(filter.should_ignore_path_or_parents(&nested, false));
+        assert!(!filter.should_ignore_path_or_parents(&temp_dir.path().join("src/main.rs"), false));
+        assert!(!filter.should_ignore_path_or_parents(Path::new("/elsewhere/target/x"), false));
+    }
 }
@@ -0,0 +1,617 @@
+//! Unified per-file history timeline
+//!
+//! Combines backup snapshots, audit trail entries and git commits into a
+//! single chronological timeline for a file. Entries whose content can be
+//! recovered support diffing against the current file and restoring the file
+//! to that point in time.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use git2::{Oid, Repository, Tree};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+use uuid::Uuid;
+
+use crate::{
+    audit::AuditLogger,
+    diff::DiffEngine,
+    error::FileError,
+    models::{FileDiff, OperationType},
+    verifier::ContentVerifier,
+};
+
+/// Timestamp format used in backup file names (see `BackupManager`)
+const BACKUP_TIMESTAMP_FORMAT: &str = "%Y%m%d_%H%M%S_%f";
+
+/// Where a history entry came from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum HistorySource {
+    /// A backup snapshot taken before the file was modified
+    Backup {
+        /// Path of the backup file
+        backup_path: PathBuf,
+    },
+    /// An operation recorded in the audit trail
+    Audit {
+        /// The recorded operation
+        operation: OperationType,
+    },
+    /// A git commit that changed the file
+    Git {
+        /// Commit ID
+        commit: String,
+        /// Whether the commit deleted the file
+        deleted: bool,
+    },
+}
+
+/// A single point in a file's history
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// When the change happened
+    pub timestamp: DateTime<Utc>,
+    /// Path of the file
+    pub path: PathBuf,
+    /// Source of the entry
+    pub source: HistorySource,
+    /// Who made the change, if known
+    pub author: Option<String>,
+    /// Short description of the change
+    pub description: String,
+    /// Session that made the change, if known
+    pub session_id: Option<String>,
+    /// Transaction the change was part of, if any
+    pub transaction_id: Option<Uuid>,
+    /// SHA-256 hash of the file content at this point, if known
+    pub content_hash: Option<String>,
+    /// Whether the content at this point can be recovered for diffs and restores
+    pub diff_available: bool,
+}
+
+/// Builds per-file history timelines from backups, audit logs and git
+///
+/// Each source is optional; only configured sources contribute entries.
+#[derive(Debug, Default)]
+pub struct FileHistory {
+    backup_dir: Option<PathBuf>,
+    audit: Option<AuditLogger>,
+    repo_path: Option<PathBuf>,
+}
+
+impl FileHistory {
+    /// Creates a FileHistory with no sources configured
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Includes backup snapshots from the given backup directory
+    ///
+    /// Backups are matched by file name, as that is all `BackupManager`
+    /// encodes in backup file names.
+    pub fn with_backup_dir(mut self, backup_dir: PathBuf) -> Self {
+        self.backup_dir = Some(backup_dir);
+        self
+    }
+
+    /// Includes entries from the given audit logger
+    pub fn with_audit_logger(mut self, audit: AuditLogger) -> Self {
+        self.audit = Some(audit);
+        self
+    }
+
+    /// Includes commits from the git repository containing `repo_path`
+    pub fn with_repository(mut self, repo_path: PathBuf) -> Self {
+        self.repo_path = Some(repo_path);
+        self
+    }
+
+    /// Builds the timeline for a file
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file to build the timeline for
+    ///
+    /// # Returns
+    ///
+    /// History entries from all configured sources, ordered by timestamp (oldest first)
+    pub fn timeline(&self, path: &Path) -> Result<Vec<HistoryEntry>, FileError> {
+        let mut entries = Vec::new();
+
+        if let Some(backup_dir) = &self.backup_dir {
+            entries.extend(backup_entries(backup_dir, path)?);
+        }
+        if let Some(audit) = &self.audit {
+            entries.extend(
+                audit
+                    .get_change_history(path)?
+                    .into_iter()
+                    .map(|entry| HistoryEntry {
+                        timestamp: entry.timestamp,
+                        path: entry.path,
+                        description: format!("{} operation", operation_name(&entry.operation_type)),
+                        source: HistorySource::Audit {
+                            operation: entry.operation_type,
+                        },
+                        author: None,
+                        session_id: entry.session_id,
+                        transaction_id: entry.transaction_id,
+                        content_hash: Some(entry.content_hash),
+                        diff_available: false,
+                    }),
+            );
+        }
+        if let Some(repo_path) = &self.repo_path {
+            entries.extend(git_entries(repo_path, path)?);
+        }
+
+        // Audit entries carry no content, but can reuse any snapshot with the same hash
+        let recoverable: Vec<String> = entries
+            .iter()
+            .filter(|e| e.diff_available)
+            .filter_map(|e| e.content_hash.clone())
+            .collect();
+        for entry in entries
+            .iter_mut()
+            .filter(|e| matches!(e.source, HistorySource::Audit { .. }))
+        {
+            entry.diff_available = entry
+                .content_hash
+                .as_ref()
+                .is_some_and(|hash| recoverable.contains(hash));
+        }
+
+        entries.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+        debug!(
+            "Built history timeline with {} entries for {:?}",
+            entries.len(),
+            path
+        );
+        Ok(entries)
+    }
+
+    /// Retrieves the file content recorded by a history entry
+    ///
+    /// # Arguments
+    ///
+    /// * `entry` - An entry from [`FileHistory::timeline`]
+    ///
+    /// # Returns
+    ///
+    /// The content at that point, or `None` if it cannot be recovered
+    pub fn content_of(&self, entry: &HistoryEntry) -> Result<Option<String>, FileError> {
+        match &entry.source {
+            HistorySource::Backup { backup_path } => Ok(Some(fs::read_to_string(backup_path)?)),
+            HistorySource::Git { deleted: true, .. } => Ok(None),
+            HistorySource::Git { commit, .. } => {
+                let repo_path = self.repo_path.as_deref().ok_or_else(|| {
+                    FileError::GitError("No repository configured for history".to_string())
+                })?;
+                git_content(repo_path, commit, &entry.path)
+            }
+            HistorySource::Audit { .. } => {
+                let Some(hash) = &entry.content_hash else {
+                    return Ok(None);
+                };
+                let candidate = self.timeline(&entry.path)?.into_iter().rev().find(|e| {
+                    !matches!(e.source, HistorySource::Audit { .. })
+                        && e.diff_available
+                        && e.content_hash.as_ref() == Some(hash)
+                });
+                match candidate {
+                    Some(candidate) => self.content_of(&candidate),
+                    None => Ok(None),
+                }
+            }
+        }
+    }
+
+    /// Generates a diff from a history entry to the current file content
+    ///
+    /// # Arguments
+    ///
+    /// * `entry` - An entry from [`FileHistory::timeline`]
+    ///
+    /// # Returns
+    ///
+    /// A diff where the entry's content is the old side and the current file the new side
+    pub fn diff_against_current(&self, entry: &HistoryEntry) -> Result<FileDiff, FileError> {
+        let old = self
+            .content_of(entry)?
+            .ok_or_else(|| FileError::NotFound(entry.path.clone()))?;
+        let current = match fs::read_to_string(&entry.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+        DiffEngine::new().generate_unified_diff(&old, &current, entry.path.clone())
+    }
+
+    /// Restores a file to the content recorded by a history entry
+    ///
+    /// If a backup directory is configured and the file exists, its current
+    /// content is backed up first so the restore can itself be undone.
+    ///
+    /// # Arguments
+    ///
+    /// * `entry` - An entry from [`FileHistory::timeline`]
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or failure
+    pub fn restore_entry(&self, entry: &HistoryEntry) -> Result<(), FileError> {
+        let content = self
+            .content_of(entry)?
+            .ok_or_else(|| FileError::NotFound(entry.path.clone()))?;
+
+        if let (Some(backup_dir), true) = (&self.backup_dir, entry.path.is_file()) {
+            snapshot(backup_dir, &entry.path)?;
+        }
+
+        if let Some(parent) = entry.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        let file_name = entry
+            .path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .ok_or_else(|| FileError::InvalidPath(entry.path.display().to_string()))?;
+        let temp_path = entry
+            .path
+            .with_file_name(format!(".tmp-{}-{}", Uuid::new_v4(), file_name));
+        fs::write(&temp_path, &content)?;
+        if let Err(e) = fs::rename(&temp_path, &entry.path) {
+            let _ = fs::remove_file(&temp_path);
+            return Err(e.into());
+        }
+
+        debug!(
+            "Restored {:?} from history entry at {}",
+            entry.path, entry.timestamp
+        );
+        Ok(())
+    }
+
+    /// Restores a file to its latest recoverable state at or before a point in time
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file to restore
+    /// * `at` - The point in time to restore to
+    ///
+    /// # Returns
+    ///
+    /// The history entry the file was restored from
+    pub fn restore_to(&self, path: &Path, at: DateTime<Utc>) -> Result<HistoryEntry, FileError> {
+        let entry = self
+            .timeline(path)?
+            .into_iter()
+            .rev()
+            .find(|e| e.timestamp <= at && e.diff_available)
+            .ok_or_else(|| FileError::NotFound(path.to_path_buf()))?;
+        self.restore_entry(&entry)?;
+        Ok(entry)
+    }
+}
+
+/// Returns a lowercase name for an operation type
+fn operation_name(operation: &OperationType) -> &'static str {
+    match operation {
+        OperationType::Create => "create",
+        OperationType::Update => "update",
+        OperationType::Delete => "delete",
+        OperationType::Rename { .. } => "rename",
+    }
+}
+
+/// Collects backup snapshots of `path` stored in `backup_dir`
+fn backup_entries(backup_dir: &Path, path: &Path) -> Result<Vec<HistoryEntry>, FileError> {
+    let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else {
+        return Ok(Vec::new());
+    };
+    let read_dir = match fs::read_dir(backup_dir) {
+        Ok(read_dir) => read_dir,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let prefix = format!("{}.", file_name);
+    let mut entries = Vec::new();
+    for dir_entry in read_dir {
+        let backup_path = dir_entry?.path();
+        let Some(timestamp) = backup_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(|n| n.strip_prefix(prefix.as_str()))
+            .and_then(|n| n.strip_suffix(".bak"))
+            .and_then(|ts| NaiveDateTime::parse_from_str(ts, BACKUP_TIMESTAMP_FORMAT).ok())
+        else {
+            continue;
+        };
+        let content = match fs::read_to_string(&backup_path) {
+            Ok(content) => content,
+            Err(e) => {
+                debug!("Skipping unreadable backup {:?}: {}", backup_path, e);
+                continue;
+            }
+        };
+
+        entries.push(HistoryEntry {
+            timestamp: timestamp.and_utc(),
+            path: path.to_path_buf(),
+            source: HistorySource::Backup { backup_path },
+            author: None,
+            description: "Backup snapshot".to_string(),
+            session_id: None,
+            transaction_id: None,
+            content_hash: Some(ContentVerifier::compute_hash(&content)),
+            diff_available: true,
+        });
+    }
+    Ok(entries)
+}
+
+/// Copies the current content of `path` into `backup_dir` using the backup naming scheme
+fn snapshot(backup_dir: &Path, path: &Path) -> Result<(), FileError> {
+    let file_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| FileError::BackupFailed("Invalid file path".to_string()))?;
+    fs::create_dir_all(backup_dir).map_err(|e| {
+        FileError::BackupFailed(format!("Failed to create backup directory: {}", e))
+    })?;
+    let backup_path = backup_dir.join(format!(
+        "{}.{}.bak",
+        file_name,
+        Utc::now().format(BACKUP_TIMESTAMP_FORMAT)
+    ));
+    fs::copy(path, &backup_path)
+        .map_err(|e| FileError::BackupFailed(format!("Failed to write backup file: {}", e)))?;
+    Ok(())
+}
+
+/// Opens the repository containing `repo_path` and resolves `path` relative to its work tree
+fn open_repository(repo_path: &Path, path: &Path) -> Result<(Repository, PathBuf), FileError> {
+    let repo = Repository::discover(repo_path)
+        .map_err(|e| FileError::GitError(format!("Failed to open repository: {}", e)))?;
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| FileError::GitError("Repository has no work tree".to_string()))?
+        .canonicalize()?;
+
+    let relative = if path.is_relative() {
+        path.to_path_buf()
+    } else {
+        // The file may no longer exist, so canonicalize its parent instead
+        let parent = path
+            .parent()
+            .ok_or_else(|| FileError::InvalidPath(path.display().to_string()))?
+            .canonicalize()?;
+        let file_name = path
+            .file_name()
+            .ok_or_else(|| FileError::InvalidPath(path.display().to_string()))?;
+        parent
+            .join(file_name)
+            .strip_prefix(&workdir)
+            .map_err(|_| {
+                FileError::InvalidPath(format!("{} is outside the repository", path.display()))
+            })?
+            .to_path_buf()
+    };
+    Ok((repo, relative))
+}
+
+/// Returns the blob ID of `path` in `tree`, if present
+fn blob_id(tree: &Tree, path: &Path) -> Option<Oid> {
+    tree.get_path(path).ok().map(|entry| entry.id())
+}
+
+/// Collects commits that changed `path`
+fn git_entries(repo_path: &Path, path: &Path) -> Result<Vec<HistoryEntry>, FileError> {
+    let (repo, relative) = open_repository(repo_path, path)?;
+    let git_err = |e: git2::Error| FileError::GitError(format!("Failed to read history: {}", e));
+
+    let mut revwalk = repo.revwalk().map_err(git_err)?;
+    if revwalk.push_head().is_err() {
+        // Unborn HEAD: no commits yet
+        return Ok(Vec::new());
+    }
+    // Oldest first, so commits within the same second keep their order after sorting
+    revwalk
+        .set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME | git2::Sort::REVERSE)
+        .map_err(git_err)?;
+
+    let mut entries = Vec::new();
+    for oid in revwalk {
+        let commit = repo.find_commit(oid.map_err(git_err)?).map_err(git_err)?;
+        let current = blob_id(&commit.tree().map_err(git_err)?, &relative);
+        let previous = commit
+            .parent(0)
+            .ok()
+            .and_then(|parent| parent.tree().ok())
+            .and_then(|tree| blob_id(&tree, &relative));
+        if current == previous {
+            continue;
+        }
+
+        let content_hash = current
+            .and_then(|id| repo.find_blob(id).ok())
+            .and_then(|blob| String::from_utf8(blob.content().to_vec()).ok())
+            .map(|content| ContentVerifier::compute_hash(&content));
+        let author = commit.author();
+        entries.push(HistoryEntry {
+            timestamp: Utc
+                .timestamp_opt(commit.time().seconds(), 0)
+                .single()
+                .unwrap_or_else(Utc::now),
+            path: path.to_path_buf(),
+            source: HistorySource::Git {
+                commit: commit.id().to_string(),
+                deleted: current.is_none(),
+            },
+            author: author.name().map(|name| name.to_string()),
+            description: commit.summary().unwrap_or_default().to_string(),
+            session_id: None,
+            transaction_id: None,
+            diff_available: content_hash.is_some(),
+            content_hash,
+        });
+    }
+    Ok(entries)
+}
+
+/// Reads the content of `path` as of `commit`
+fn git_content(repo_path: &Path, commit: &str, path: &Path) -> Result<Option<String>, FileError> {
+    let (repo, relative) = open_repository(repo_path, path)?;
+    let git_err = |e: git2::Error| FileError::GitError(format!("Failed to read content: {}", e));
+
+    let oid = Oid::from_str(commit).map_err(git_err)?;
+    let tree = repo
+        .find_commit(oid)
+        .and_then(|c| c.tree())
+        .map_err(git_err)?;
+    let Some(id) = blob_id(&tree, &relative) else {
+        return Ok(None);
+    };
+    let blob = repo.find_blob(id).map_err(git_err)?;
+    Ok(String::from_utf8(blob.content().to_vec()).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::models::AuditEntry;
+
+    fn commit_file(repo: &Repository, root: &Path, name: &str, content: &str, message: &str) {
+        fs::write(root.join(name), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(name)).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("Alice", "alice@example.com").unwrap();
+        let parents: Vec<_> = repo
+            .head()
+            .ok()
+            .and_then(|h| h.peel_to_commit().ok())
+            .into_iter()
+            .collect();
+        let parent_refs: Vec<_> = parents.iter().collect();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parent_refs,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_timeline_merges_sources() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        let repo = Repository::init(&root).unwrap();
+        commit_file(&repo, &root, "notes.txt", "v1\n", "Add notes");
+        commit_file(&repo, &root, "other.txt", "x\n", "Unrelated");
+        commit_file(&repo, &root, "notes.txt", "v2\n", "Update notes");
+
+        let path = root.join("notes.txt");
+        let backup_dir = root.join(".backups");
+        fs::create_dir_all(&backup_dir).unwrap();
+        let backup_time = Utc::now() + chrono::Duration::seconds(5);
+        fs::write(
+            backup_dir.join(format!(
+                "notes.txt.{}.bak",
+                backup_time.format(BACKUP_TIMESTAMP_FORMAT)
+            )),
+            "v2\n",
+        )
+        .unwrap();
+
+        let audit = AuditLogger::new(root.join(".audit"));
+        audit
+            .log_operation(AuditEntry {
+                timestamp: backup_time + chrono::Duration::seconds(1),
+                path: path.clone(),
+                operation_type: OperationType::Update,
+                content_hash: ContentVerifier::compute_hash("v2\n"),
+                transaction_id: None,
+                session_id: Some("session-1".to_string()),
+            })
+            .unwrap();
+
+        let history = FileHistory::new()
+            .with_backup_dir(backup_dir)
+            .with_audit_logger(audit)
+            .with_repository(root.clone());
+        let timeline = history.timeline(&path).unwrap();
+
+        assert_eq!(timeline.len(), 4);
+        assert_eq!(timeline[0].description, "Add notes");
+        assert_eq!(timeline[0].author.as_deref(), Some("Alice"));
+        assert!(matches!(timeline[2].source, HistorySource::Backup { .. }));
+        assert_eq!(timeline[3].session_id.as_deref(), Some("session-1"));
+        assert!(timeline.iter().all(|e| e.diff_available));
+
+        assert_eq!(
+            history.content_of(&timeline[0]).unwrap().as_deref(),
+            Some("v1\n")
+        );
+        assert_eq!(
+            history.content_of(&timeline[3]).unwrap().as_deref(),
+            Some("v2\n")
+        );
+    }
+
+    #[test]
+    fn test_restore_to_point_in_time() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.toml");
+        let backup_dir = temp_dir.path().join("backups");
+        fs::create_dir_all(&backup_dir).unwrap();
+
+        let first = Utc::now() - chrono::Duration::hours(2);
+        let second = Utc::now() - chrono::Duration::hours(1);
+        for (time, content) in [(first, "a = 1"), (second, "a = 2")] {
+            fs::write(
+                backup_dir.join(format!(
+                    "config.toml.{}.bak",
+                    time.format(BACKUP_TIMESTAMP_FORMAT)
+                )),
+                content,
+            )
+            .unwrap();
+        }
+        fs::write(&path, "a = 3").unwrap();
+
+        let history = FileHistory::new().with_backup_dir(backup_dir.clone());
+        let diff = history
+            .diff_against_current(&history.timeline(&path).unwrap()[0])
+            .unwrap();
+        assert_eq!(diff.stats.additions, 1);
+        assert_eq!(diff.stats.deletions, 1);
+
+        let restored = history
+            .restore_to(&path, first + chrono::Duration::minutes(30))
+            .unwrap();
+        assert_eq!(restored.timestamp, first);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "a = 1");
+
+        // The overwritten content was snapshotted and is now part of the history
+        let timeline = history.timeline(&path).unwrap();
+        assert_eq!(timeline.len(), 3);
+        history.restore_entry(&timeline[2]).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "a = 3");
+
+        assert!(history
+            .restore_to(&path, first - chrono::Duration::minutes(1))
+            .is_err());
+    }
+}
@@ -10,15 +10,19 @@ pub mod backup;
 pub mod conflict;
 pub mod di;
 pub mod diff;
+pub mod distributed;
 pub mod error;
 pub mod file_repository;
 pub mod git;
 pub mod gitignore;
+pub mod history;
 pub mod manager;
 pub mod models;
 pub mod ripgrep;
 pub mod session_tracking;
+pub mod streaming;
 pub mod transaction;
+pub mod trash;
 pub mod verifier;
 pub mod watcher;
 pub mod writer;
@@ -29,9 +33,11 @@ pub use backup::BackupManager;
 pub use file_repository::FileSystemRepository;
 pub use conflict::ConflictResolver;
 pub use diff::DiffEngine;
+pub use distributed::{DistributedTransaction, RecoveryReport};
 pub use error::FileError;
 pub use git::GitIntegration;
 pub use gitignore::GitignoreFilter;
+pub use history::{FileHistory, HistoryEntry, HistorySource};
 pub use manager::FileManager;
 pub use models::{
     AuditEntry, BackupMetadata, ConflictInfo, ConflictResolution, DiffHunk, DiffLine, DiffStats,
@@ -39,7 +45,12 @@ pub use models::{
 };
 pub use ripgrep::{Ripgrep, RipgrepError, SearchMatch};
 pub use session_tracking::{FileReadRecord, SessionFileTracker};
+pub use streaming::{
+    apply_chunked_patch, chunk_hashes, ChunkDigest, ChunkEdit, ChunkedPatchResult, FileWindow,
+    MappedFile, RollingHash, StreamingReader,
+};
 pub use transaction::TransactionManager;
+pub use trash::{TrashEntry, TrashManager};
 pub use verifier::ContentVerifier;
 pub use watcher::{FileChangeBatch, FileChangeEvent, FileWatcher, WatcherConfig};
 pub use writer::SafeWriter;
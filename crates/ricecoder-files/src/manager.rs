@@ -13,6 +13,7 @@ use crate::{
     error::FileError,
     models::{ConflictResolution, FileOperation, OperationType},
     transaction::TransactionManager,
+    trash::{TrashEntry, TrashManager},
     verifier::ContentVerifier,
     writer::SafeWriter,
 };
@@ -35,6 +36,7 @@ pub struct FileManager {
     writer: SafeWriter,
     transaction_manager: TransactionManager,
     backup_dir: PathBuf,
+    trash: TrashManager,
 }
 
 impl FileManager {
@@ -46,11 +48,15 @@ impl FileManager {
             writer: SafeWriter::new(),
             transaction_manager: TransactionManager::new(backup_manager),
             backup_dir,
+            trash: TrashManager::default(),
         }
     }
 
     /// Creates a new FileManager with a custom backup directory
     ///
+    /// Deleted files are moved to a `trash` directory next to the backup
+    /// directory.
+    ///
     /// # Arguments
     ///
     /// * `backup_dir` - Directory where backups will be stored
@@ -59,10 +65,21 @@ impl FileManager {
         FileManager {
             writer: SafeWriter::new(),
             transaction_manager: TransactionManager::new(backup_manager),
+            trash: TrashManager::new(backup_dir.with_file_name("trash")),
             backup_dir,
         }
     }
 
+    /// Uses a custom trash for deletions
+    ///
+    /// # Arguments
+    ///
+    /// * `trash` - Trash that deleted files are moved to
+    pub fn with_trash(mut self, trash: TrashManager) -> Self {
+        self.trash = trash;
+        self
+    }
+
     /// Writes a file safely with atomic operations
     ///
     /// Uses the SafeWriter internally to ensure atomic writes with
@@ -117,7 +134,7 @@ impl FileManager {
             .map_err(FileError::IoError)
     }
 
-    /// Deletes a file
+    /// Deletes a file by moving it to the trash
     ///
     /// # Arguments
     ///
@@ -125,11 +142,30 @@ impl FileManager {
     ///
     /// # Returns
     ///
-    /// Result indicating success or failure
-    pub async fn delete_file(&self, path: &Path) -> Result<(), FileError> {
-        tokio::fs::remove_file(path)
+    /// The trash entry, which can be passed to [`FileManager::restore_file`]
+    pub async fn delete_file(&self, path: &Path) -> Result<TrashEntry, FileError> {
+        let trash = self.trash.clone();
+        let path = path.to_path_buf();
+        tokio::task::spawn_blocking(move || trash.trash(&path))
             .await
-            .map_err(FileError::IoError)
+            .map_err(|e| FileError::TrashFailed(format!("Trash task failed: {}", e)))?
+    }
+
+    /// Restores a deleted file from the trash
+    ///
+    /// # Arguments
+    ///
+    /// * `entry_id` - ID of the trash entry returned by [`FileManager::delete_file`]
+    ///
+    /// # Returns
+    ///
+    /// The restored entry
+    pub async fn restore_file(&self, entry_id: &str) -> Result<TrashEntry, FileError> {
+        let trash = self.trash.clone();
+        let entry_id = entry_id.to_string();
+        tokio::task::spawn_blocking(move || trash.restore(&entry_id))
+            .await
+            .map_err(|e| FileError::TrashFailed(format!("Trash task failed: {}", e)))?
     }
 
     /// Checks if a file exists
@@ -220,6 +256,11 @@ impl FileManager {
     pub fn backup_dir(&self) -> &Path {
         &self.backup_dir
     }
+
+    /// Gets the trash deleted files are moved to
+    pub fn trash(&self) -> &TrashManager {
+        &self.trash
+    }
 }
 
 impl Default for FileManager {
@@ -280,14 +321,18 @@ mod tests {
     #[tokio::test]
     async fn test_delete_file() {
         let temp_dir = TempDir::new().unwrap();
-        let manager = FileManager::new();
+        let manager = FileManager::with_backup_dir(temp_dir.path().join("backups"));
 
         let file_path = temp_dir.path().join("test.txt");
         tokio::fs::write(&file_path, "content").await.unwrap();
         assert!(manager.file_exists(&file_path));
 
-        manager.delete_file(&file_path).await.unwrap();
+        let entry = manager.delete_file(&file_path).await.unwrap();
         assert!(!manager.file_exists(&file_path));
+        assert!(manager.trash().trash_dir().starts_with(temp_dir.path()));
+
+        manager.restore_file(&entry.id).await.unwrap();
+        assert_eq!(manager.read_file(&file_path).await.unwrap(), "content");
     }
 
     #[tokio::test]
@@ -79,6 +79,8 @@ pub struct FileTransaction {
 pub enum TransactionStatus {
     /// Transaction is pending execution
     Pending,
+    /// Transaction has been prepared and awaits commit
+    Prepared,
     /// Transaction has been committed
     Committed,
     /// Transaction has been rolled back
@@ -98,6 +100,9 @@ pub struct AuditEntry {
     pub content_hash: String,
     /// Transaction ID if part of a transaction
     pub transaction_id: Option<Uuid>,
+    /// Session that performed the operation, if known
+    #[serde(default)]
+    pub session_id: Option<String>,
 }
 
 /// Metadata about a backup
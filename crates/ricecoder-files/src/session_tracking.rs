@@ -5,7 +5,8 @@
 //! Matches OpenCode's FileTime module functionality.
 
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
 use std::time::SystemTime;
 use tracing::{debug, warn};
@@ -21,6 +22,23 @@ pub struct FileReadRecord {
     pub read_at: SystemTime,
     /// Last modification time of the file at read time
     pub mtime: SystemTime,
+    /// Hash of the file content, when the content was returned into the
+    /// session context
+    pub content_hash: Option<u64>,
+    /// Lines returned into the session context (0-based, end exclusive)
+    pub lines: Option<Range<usize>>,
+}
+
+impl FileReadRecord {
+    /// Check whether `lines` of a file with `content_hash` are already in the
+    /// session context
+    pub fn covers(&self, content_hash: u64, lines: &Range<usize>) -> bool {
+        self.content_hash == Some(content_hash)
+            && self
+                .lines
+                .as_ref()
+                .is_some_and(|read| read.start <= lines.start && lines.end <= read.end)
+    }
 }
 
 /// Session-aware file tracker
@@ -51,6 +69,8 @@ impl SessionFileTracker {
             path: path.clone(),
             read_at: SystemTime::now(),
             mtime,
+            content_hash: None,
+            lines: None,
         };
 
         let key = (session_id.to_string(), path.clone());
@@ -63,6 +83,73 @@ impl SessionFileTracker {
         Ok(())
     }
 
+    /// Record that lines of a file were returned into the session context
+    ///
+    /// Later reads of the same lines can be answered with a marker instead
+    /// of the content, as long as the content hash still matches.
+    pub fn record_context_read(
+        &self,
+        session_id: &str,
+        path: &Path,
+        mtime: SystemTime,
+        content_hash: u64,
+        lines: Range<usize>,
+    ) -> Result<(), FileError> {
+        let record = FileReadRecord {
+            path: path.to_path_buf(),
+            read_at: SystemTime::now(),
+            mtime,
+            content_hash: Some(content_hash),
+            lines: Some(lines),
+        };
+
+        let key = (session_id.to_string(), path.to_path_buf());
+        let mut records = self.records.write().map_err(|_| {
+            FileError::LockError("Failed to acquire write lock for file records".to_string())
+        })?;
+
+        records.insert(key, record);
+        debug!(
+            "Recorded context read: {} in session {}",
+            path.display(),
+            session_id
+        );
+        Ok(())
+    }
+
+    /// Get the last read of a file in a session
+    pub fn last_read(
+        &self,
+        session_id: &str,
+        path: &Path,
+    ) -> Result<Option<FileReadRecord>, FileError> {
+        let key = (session_id.to_string(), path.to_path_buf());
+        let records = self.records.read().map_err(|_| {
+            FileError::LockError("Failed to acquire read lock for file records".to_string())
+        })?;
+
+        Ok(records.get(&key).cloned())
+    }
+
+    /// Forget which file contents are in a session's context
+    ///
+    /// Call this when the context is compacted or truncated. Read records
+    /// are kept, so read-before-write checks still pass.
+    pub fn forget_context(&self, session_id: &str) -> Result<(), FileError> {
+        let mut records = self.records.write().map_err(|_| {
+            FileError::LockError("Failed to acquire write lock for file records".to_string())
+        })?;
+
+        for ((sid, _), record) in records.iter_mut() {
+            if sid == session_id {
+                record.content_hash = None;
+                record.lines = None;
+            }
+        }
+        debug!("Forgot context reads for session {}", session_id);
+        Ok(())
+    }
+
     /// Assert that a file was read before writing and hasn't been modified externally
     ///
     /// Matches OpenCode's FileTime.assert() behavior
@@ -192,4 +279,36 @@ mod tests {
         tracker.clear_session("session1").unwrap();
         assert_eq!(tracker.session_file_count("session1").unwrap(), 0);
     }
+
+    #[test]
+    fn test_context_read_covers_lines() {
+        let tracker = SessionFileTracker::new();
+        let path = PathBuf::from("/test/file.txt");
+        let mtime = SystemTime::now();
+
+        tracker
+            .record_context_read("session1", &path, mtime, 42, 0..100)
+            .unwrap();
+        let record = tracker.last_read("session1", &path).unwrap().unwrap();
+        assert!(record.covers(42, &(10..20)));
+        assert!(!record.covers(42, &(90..110)));
+        assert!(!record.covers(43, &(10..20)));
+        assert!(tracker.last_read("session2", &path).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_forget_context_keeps_write_check() {
+        let tracker = SessionFileTracker::new();
+        let path = PathBuf::from("/test/file.txt");
+        let mtime = SystemTime::now();
+
+        tracker
+            .record_context_read("session1", &path, mtime, 42, 0..100)
+            .unwrap();
+        tracker.forget_context("session1").unwrap();
+
+        let record = tracker.last_read("session1", &path).unwrap().unwrap();
+        assert!(!record.covers(42, &(0..10)));
+        assert!(tracker.assert_can_write("session1", &path, mtime).is_ok());
+    }
 }
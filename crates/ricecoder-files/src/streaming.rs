@@ -0,0 +1,755 @@
+//! Streaming access to very large files
+//!
+//! The rest of this crate reads whole files into memory, which does not scale
+//! to multi-hundred-megabyte logs or generated assets. This module provides
+//! windowed reads, memory-mapped read-only scans and chunked patch application
+//! that only ever hold a single chunk of the file in memory.
+
+use std::{
+    borrow::Cow,
+    io::SeekFrom,
+    path::{Path, PathBuf},
+};
+
+use memmap2::Mmap;
+use sha2::{Digest, Sha256};
+use tokio::{
+    fs::{self, File},
+    io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt, BufWriter},
+};
+use uuid::Uuid;
+
+use crate::error::FileError;
+
+/// Default chunk size for streaming operations (1 MiB)
+pub const DEFAULT_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// Multiplier of the polynomial rolling hash
+const ROLLING_BASE: u64 = 0x0000_0100_0000_01b3;
+
+/// A window of bytes read from a file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileWindow {
+    /// Offset of the first byte in the file
+    pub offset: u64,
+    /// Bytes read
+    pub data: Vec<u8>,
+    /// Total length of the file
+    pub file_len: u64,
+}
+
+impl FileWindow {
+    /// Offset just past the last byte of the window
+    pub fn end(&self) -> u64 {
+        self.offset + self.data.len() as u64
+    }
+
+    /// Whether the window reaches the end of the file
+    pub fn is_eof(&self) -> bool {
+        self.end() >= self.file_len
+    }
+
+    /// Window content as text, replacing invalid UTF-8
+    pub fn to_string_lossy(&self) -> Cow<'_, str> {
+        String::from_utf8_lossy(&self.data)
+    }
+}
+
+/// Reads a file in offset/length windows without loading it into memory
+#[derive(Debug)]
+pub struct StreamingReader {
+    file: File,
+    path: PathBuf,
+    len: u64,
+    position: u64,
+    chunk_size: usize,
+}
+
+impl StreamingReader {
+    /// Opens a file for streaming reads
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the file
+    ///
+    /// # Returns
+    ///
+    /// A reader positioned at the start of the file, or an error
+    pub async fn open(path: &Path) -> Result<Self, FileError> {
+        let file = File::open(path).await.map_err(|e| open_error(path, e))?;
+        let len = file.metadata().await?.len();
+        Ok(StreamingReader {
+            file,
+            path: path.to_path_buf(),
+            len,
+            position: 0,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+        })
+    }
+
+    /// Sets the chunk size used by [`StreamingReader::next_chunk`]
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Path of the file being read
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Length of the file when it was opened
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Whether the file is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Offset the next chunk will be read from
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Moves the chunk cursor to an offset
+    pub fn seek(&mut self, offset: u64) {
+        self.position = offset.min(self.len);
+    }
+
+    /// Reads up to `length` bytes starting at `offset`
+    ///
+    /// Windows extending past the end of the file are truncated. The chunk
+    /// cursor is moved to the end of the window.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - Offset of the first byte to read
+    /// * `length` - Maximum number of bytes to read
+    ///
+    /// # Returns
+    ///
+    /// The bytes read, or an error if `offset` is past the end of the file
+    pub async fn read_window(
+        &mut self,
+        offset: u64,
+        length: usize,
+    ) -> Result<FileWindow, FileError> {
+        if offset > self.len {
+            return Err(FileError::InvalidContent(format!(
+                "Offset {} is past the end of {} ({} bytes)",
+                offset,
+                self.path.display(),
+                self.len
+            )));
+        }
+
+        let length = (self.len - offset).min(length as u64) as usize;
+        let mut data = vec![0; length];
+        self.file.seek(SeekFrom::Start(offset)).await?;
+        self.file.read_exact(&mut data).await?;
+        self.position = offset + length as u64;
+
+        Ok(FileWindow {
+            offset,
+            data,
+            file_len: self.len,
+        })
+    }
+
+    /// Reads the next chunk from the cursor
+    ///
+    /// # Returns
+    ///
+    /// The next chunk, or `None` once the end of the file is reached
+    pub async fn next_chunk(&mut self) -> Result<Option<FileWindow>, FileError> {
+        if self.position >= self.len {
+            return Ok(None);
+        }
+        self.read_window(self.position, self.chunk_size)
+            .await
+            .map(Some)
+    }
+}
+
+/// Polynomial rolling hash over a fixed-size window of bytes
+///
+/// Rolling the window forward by one byte is O(1), which makes it suitable
+/// for scanning large inputs for a pattern (Rabin-Karp).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RollingHash {
+    hash: u64,
+    /// `ROLLING_BASE` raised to the window length minus one
+    outgoing_weight: u64,
+}
+
+impl RollingHash {
+    /// Computes the hash of an initial window
+    pub fn new(window: &[u8]) -> Self {
+        let hash = window.iter().fold(0u64, |hash, &byte| {
+            hash.wrapping_mul(ROLLING_BASE).wrapping_add(byte as u64)
+        });
+        let outgoing_weight = (1..window.len()).fold(1u64, |w, _| w.wrapping_mul(ROLLING_BASE));
+        RollingHash {
+            hash,
+            outgoing_weight,
+        }
+    }
+
+    /// Current hash value
+    pub fn value(&self) -> u64 {
+        self.hash
+    }
+
+    /// Slides the window forward by one byte
+    ///
+    /// # Arguments
+    ///
+    /// * `outgoing` - Byte leaving the front of the window
+    /// * `incoming` - Byte entering the back of the window
+    pub fn roll(&mut self, outgoing: u8, incoming: u8) {
+        self.hash = self
+            .hash
+            .wrapping_sub((outgoing as u64).wrapping_mul(self.outgoing_weight))
+            .wrapping_mul(ROLLING_BASE)
+            .wrapping_add(incoming as u64);
+    }
+}
+
+/// Read-only memory map of a file for fast scans
+///
+/// Pages are loaded on demand by the OS, so scanning a large file does not
+/// allocate a buffer of its size.
+#[derive(Debug)]
+pub struct MappedFile {
+    path: PathBuf,
+    // Empty files cannot be mapped on every platform
+    map: Option<Mmap>,
+}
+
+impl MappedFile {
+    /// Maps a file into memory read-only
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the file
+    ///
+    /// # Returns
+    ///
+    /// The mapped file, or an error
+    pub fn open(path: &Path) -> Result<Self, FileError> {
+        let file = std::fs::File::open(path).map_err(|e| open_error(path, e))?;
+        let map = if file.metadata()?.len() == 0 {
+            None
+        } else {
+            // SAFETY: the map is read-only and never handed out beyond the
+            // lifetime of `self`. Truncating the file while it is mapped is
+            // undefined behaviour, so this is meant for scans of files that are
+            // not being rewritten concurrently; writers in this crate replace
+            // files by rename, which leaves the mapped inode intact.
+            Some(unsafe { Mmap::map(&file)? })
+        };
+        Ok(MappedFile {
+            path: path.to_path_buf(),
+            map,
+        })
+    }
+
+    /// Path of the mapped file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Contents of the file
+    pub fn as_bytes(&self) -> &[u8] {
+        self.map.as_deref().unwrap_or(&[])
+    }
+
+    /// Length of the file
+    pub fn len(&self) -> u64 {
+        self.as_bytes().len() as u64
+    }
+
+    /// Whether the file is empty
+    pub fn is_empty(&self) -> bool {
+        self.as_bytes().is_empty()
+    }
+
+    /// Finds the offsets of every occurrence of a byte pattern
+    ///
+    /// Overlapping occurrences are all reported. An empty pattern matches
+    /// nothing.
+    pub fn find_all(&self, pattern: &[u8]) -> Vec<u64> {
+        let bytes = self.as_bytes();
+        let n = pattern.len();
+        if n == 0 || n > bytes.len() {
+            return Vec::new();
+        }
+
+        let target = RollingHash::new(pattern).value();
+        let mut window = RollingHash::new(&bytes[..n]);
+        let mut matches = Vec::new();
+        for start in 0..=bytes.len() - n {
+            if start > 0 {
+                window.roll(bytes[start - 1], bytes[start + n - 1]);
+            }
+            if window.value() == target && &bytes[start..start + n] == pattern {
+                matches.push(start as u64);
+            }
+        }
+        matches
+    }
+
+    /// Counts lines in the file
+    ///
+    /// A trailing line without a newline is counted.
+    pub fn count_lines(&self) -> usize {
+        let bytes = self.as_bytes();
+        let newlines = bytes.iter().filter(|&&b| b == b'\n').count();
+        match bytes.last() {
+            Some(&last) if last != b'\n' => newlines + 1,
+            _ => newlines,
+        }
+    }
+
+    /// Offset of the start of a 0-based line
+    pub fn line_offset(&self, line: usize) -> Option<u64> {
+        if line == 0 {
+            return (!self.is_empty()).then_some(0);
+        }
+        let bytes = self.as_bytes();
+        bytes
+            .iter()
+            .enumerate()
+            .filter(|(_, &b)| b == b'\n')
+            .nth(line - 1)
+            .map(|(i, _)| i as u64 + 1)
+            .filter(|&offset| offset < bytes.len() as u64)
+    }
+
+    /// Reads a range of lines, without their line endings
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - 0-based index of the first line
+    /// * `count` - Maximum number of lines to return
+    ///
+    /// # Returns
+    ///
+    /// The lines, with invalid UTF-8 replaced
+    pub fn lines(&self, start: usize, count: usize) -> Vec<Cow<'_, str>> {
+        let Some(offset) = self.line_offset(start) else {
+            return Vec::new();
+        };
+        let mut rest = &self.as_bytes()[offset as usize..];
+        // A final newline terminates the last line rather than starting another
+        if let Some(stripped) = rest.strip_suffix(b"\n") {
+            rest = stripped;
+        }
+        rest.split(|&b| b == b'\n')
+            .take(count)
+            .map(|line| String::from_utf8_lossy(line.strip_suffix(b"\r").unwrap_or(line)))
+            .collect()
+    }
+}
+
+/// SHA-256 hash of one chunk of a file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkDigest {
+    /// Offset of the chunk
+    pub offset: u64,
+    /// Length of the chunk
+    pub length: u64,
+    /// SHA-256 of the chunk
+    pub hash: String,
+}
+
+/// Hashes a file chunk by chunk
+///
+/// Comparing digests of two versions of a file shows which chunks changed
+/// without holding either version in memory.
+///
+/// # Arguments
+///
+/// * `path` - Path to the file
+/// * `chunk_size` - Size of each chunk
+///
+/// # Returns
+///
+/// Digests of each chunk in order, or an error
+pub async fn chunk_hashes(path: &Path, chunk_size: usize) -> Result<Vec<ChunkDigest>, FileError> {
+    let mut reader = StreamingReader::open(path)
+        .await?
+        .with_chunk_size(chunk_size);
+    let mut digests = Vec::new();
+    while let Some(window) = reader.next_chunk().await? {
+        digests.push(ChunkDigest {
+            offset: window.offset,
+            length: window.data.len() as u64,
+            hash: format!("{:x}", Sha256::digest(&window.data)),
+        });
+    }
+    Ok(digests)
+}
+
+/// A byte-range replacement applied by [`apply_chunked_patch`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkEdit {
+    /// Offset of the first byte to replace
+    pub offset: u64,
+    /// Number of bytes to replace
+    pub length: u64,
+    /// Replacement bytes
+    pub replacement: Vec<u8>,
+    /// SHA-256 of the bytes being replaced, checked before the edit is applied
+    pub expected_hash: Option<String>,
+}
+
+impl ChunkEdit {
+    /// Replaces `length` bytes at `offset`
+    pub fn replace(offset: u64, length: u64, replacement: impl Into<Vec<u8>>) -> Self {
+        ChunkEdit {
+            offset,
+            length,
+            replacement: replacement.into(),
+            expected_hash: None,
+        }
+    }
+
+    /// Inserts bytes at `offset`
+    pub fn insert(offset: u64, content: impl Into<Vec<u8>>) -> Self {
+        Self::replace(offset, 0, content)
+    }
+
+    /// Deletes `length` bytes at `offset`
+    pub fn delete(offset: u64, length: u64) -> Self {
+        Self::replace(offset, length, Vec::new())
+    }
+
+    /// Requires the replaced bytes to have a SHA-256 hash
+    ///
+    /// See [`ContentVerifier::compute_bytes_hash`](crate::ContentVerifier::compute_bytes_hash).
+    pub fn with_expected_hash(mut self, hash: impl Into<String>) -> Self {
+        self.expected_hash = Some(hash.into());
+        self
+    }
+
+    fn end(&self) -> u64 {
+        self.offset + self.length
+    }
+}
+
+/// Result of [`apply_chunked_patch`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkedPatchResult {
+    /// Length of the file before patching
+    pub original_len: u64,
+    /// Length of the file after patching
+    pub new_len: u64,
+    /// Number of edits applied
+    pub edits_applied: usize,
+    /// SHA-256 of the patched file
+    pub content_hash: String,
+}
+
+/// Applies byte-range edits to a file without loading it into memory
+///
+/// The file is streamed chunk by chunk into a temporary file next to it with
+/// the edits spliced in, while a running hash of the output is kept. The
+/// temporary file is then re-read and its hash compared before it atomically
+/// replaces the original. If any edit's expected hash does not match, or
+/// verification fails, the original file is left untouched.
+///
+/// # Arguments
+///
+/// * `path` - Path to the file to patch
+/// * `edits` - Non-overlapping edits, with offsets into the original file
+/// * `chunk_size` - Size of the chunks streamed through memory
+///
+/// # Returns
+///
+/// A summary of the patch, or an error
+pub async fn apply_chunked_patch(
+    path: &Path,
+    edits: &[ChunkEdit],
+    chunk_size: usize,
+) -> Result<ChunkedPatchResult, FileError> {
+    let mut source = File::open(path).await.map_err(|e| open_error(path, e))?;
+    let metadata = source.metadata().await?;
+    let original_len = metadata.len();
+
+    let mut ordered: Vec<&ChunkEdit> = edits.iter().collect();
+    ordered.sort_by_key(|edit| edit.offset);
+    validate_edits(&ordered, original_len)?;
+
+    let temp_path = temp_path(path);
+    let result = write_patched(
+        &mut source,
+        &temp_path,
+        &ordered,
+        original_len,
+        chunk_size.max(1),
+    )
+    .await;
+
+    let (new_len, content_hash) = match result {
+        Ok(written) => written,
+        Err(e) => {
+            let _ = fs::remove_file(&temp_path).await;
+            return Err(e);
+        }
+    };
+
+    fs::set_permissions(&temp_path, metadata.permissions()).await?;
+    fs::rename(&temp_path, path).await?;
+
+    Ok(ChunkedPatchResult {
+        original_len,
+        new_len,
+        edits_applied: ordered.len(),
+        content_hash,
+    })
+}
+
+fn validate_edits(edits: &[&ChunkEdit], file_len: u64) -> Result<(), FileError> {
+    let mut previous_end = 0;
+    for edit in edits {
+        if edit.end() > file_len {
+            return Err(FileError::InvalidContent(format!(
+                "Edit at {}..{} extends past the end of the file ({} bytes)",
+                edit.offset,
+                edit.end(),
+                file_len
+            )));
+        }
+        if edit.offset < previous_end {
+            return Err(FileError::InvalidContent(format!(
+                "Edit at offset {} overlaps a previous edit",
+                edit.offset
+            )));
+        }
+        previous_end = edit.end();
+    }
+    Ok(())
+}
+
+/// Streams the patched file into `temp_path`, returning its length and hash
+async fn write_patched(
+    source: &mut File,
+    temp_path: &Path,
+    edits: &[&ChunkEdit],
+    original_len: u64,
+    chunk_size: usize,
+) -> Result<(u64, String), FileError> {
+    let mut output = BufWriter::new(File::create(temp_path).await?);
+    let mut buffer = vec![0; chunk_size];
+    let mut running = Sha256::new();
+    let mut written = 0;
+    let mut position = 0;
+
+    for edit in edits {
+        written += copy_range(
+            source,
+            &mut output,
+            edit.offset - position,
+            &mut buffer,
+            &mut running,
+        )
+        .await?;
+
+        let replaced = hash_range(source, edit.length, &mut buffer).await?;
+        if let Some(expected) = &edit.expected_hash {
+            if &replaced != expected {
+                return Err(FileError::VerificationFailed(format!(
+                    "Content at {}..{} does not match the expected hash",
+                    edit.offset,
+                    edit.end()
+                )));
+            }
+        }
+
+        output.write_all(&edit.replacement).await?;
+        running.update(&edit.replacement);
+        written += edit.replacement.len() as u64;
+        position = edit.end();
+    }
+
+    written += copy_range(
+        source,
+        &mut output,
+        original_len - position,
+        &mut buffer,
+        &mut running,
+    )
+    .await?;
+
+    output.flush().await?;
+    output.get_ref().sync_all().await?;
+    drop(output);
+
+    let expected = format!("{:x}", running.finalize());
+    let mut temp = File::open(temp_path).await?;
+    let actual = hash_range(&mut temp, written, &mut buffer).await?;
+    if actual != expected {
+        return Err(FileError::VerificationFailed(
+            "Patched file does not match the streamed content".to_string(),
+        ));
+    }
+
+    Ok((written, expected))
+}
+
+/// Copies `remaining` bytes from the current position, feeding the running hash
+async fn copy_range(
+    source: &mut File,
+    output: &mut BufWriter<File>,
+    mut remaining: u64,
+    buffer: &mut [u8],
+    running: &mut Sha256,
+) -> Result<u64, FileError> {
+    let total = remaining;
+    while remaining > 0 {
+        let n = remaining.min(buffer.len() as u64) as usize;
+        source.read_exact(&mut buffer[..n]).await?;
+        output.write_all(&buffer[..n]).await?;
+        running.update(&buffer[..n]);
+        remaining -= n as u64;
+    }
+    Ok(total)
+}
+
+/// Hashes `remaining` bytes from the current position
+async fn hash_range(
+    source: &mut File,
+    mut remaining: u64,
+    buffer: &mut [u8],
+) -> Result<String, FileError> {
+    let mut hasher = Sha256::new();
+    while remaining > 0 {
+        let n = remaining.min(buffer.len() as u64) as usize;
+        source.read_exact(&mut buffer[..n]).await?;
+        hasher.update(&buffer[..n]);
+        remaining -= n as u64;
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+fn temp_path(path: &Path) -> PathBuf {
+    let mut temp_path = path.to_path_buf();
+    temp_path.set_file_name(format!(
+        ".tmp-{}-{}",
+        Uuid::new_v4(),
+        path.file_name().and_then(|n| n.to_str()).unwrap_or("file")
+    ));
+    temp_path
+}
+
+fn open_error(path: &Path, error: std::io::Error) -> FileError {
+    if error.kind() == std::io::ErrorKind::NotFound {
+        FileError::NotFound(path.to_path_buf())
+    } else {
+        FileError::IoError(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::verifier::ContentVerifier;
+
+    #[tokio::test]
+    async fn test_read_window_and_chunks() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("log.txt");
+        fs::write(&path, "0123456789").await.unwrap();
+
+        let mut reader = StreamingReader::open(&path)
+            .await
+            .unwrap()
+            .with_chunk_size(4);
+        let window = reader.read_window(8, 100).await.unwrap();
+        assert_eq!(window.data, b"89");
+        assert!(window.is_eof());
+        assert!(reader.read_window(11, 1).await.is_err());
+
+        reader.seek(0);
+        let mut chunks = Vec::new();
+        while let Some(chunk) = reader.next_chunk().await.unwrap() {
+            chunks.push(chunk.to_string_lossy().into_owned());
+        }
+        assert_eq!(chunks, vec!["0123", "4567", "89"]);
+    }
+
+    #[test]
+    fn test_mapped_file_scans() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("log.txt");
+        std::fs::write(&path, "error: a\r\ninfo: b\nerror: c\nerrorerror").unwrap();
+
+        let mapped = MappedFile::open(&path).unwrap();
+        assert_eq!(mapped.find_all(b"error"), vec![0, 18, 27, 32]);
+        assert_eq!(mapped.find_all(b"erro"), vec![0, 18, 27, 32]);
+        assert_eq!(mapped.count_lines(), 4);
+        assert_eq!(mapped.lines(1, 2), vec!["info: b", "error: c"]);
+        assert_eq!(mapped.lines(3, 5), vec!["errorerror"]);
+        assert!(mapped.lines(4, 1).is_empty());
+
+        let empty_path = temp_dir.path().join("empty.txt");
+        std::fs::write(&empty_path, "").unwrap();
+        let empty = MappedFile::open(&empty_path).unwrap();
+        assert!(empty.is_empty());
+        assert!(empty.find_all(b"x").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_apply_chunked_patch() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("asset.txt");
+        fs::write(&path, "hello big world of files").await.unwrap();
+
+        let edits = vec![
+            ChunkEdit::delete(6, 4),
+            ChunkEdit::replace(0, 5, "HELLO")
+                .with_expected_hash(ContentVerifier::compute_bytes_hash(b"hello")),
+            ChunkEdit::insert(24, "!"),
+        ];
+        let result = apply_chunked_patch(&path, &edits, 3).await.unwrap();
+
+        let content = fs::read_to_string(&path).await.unwrap();
+        assert_eq!(content, "HELLO world of files!");
+        assert_eq!(result.edits_applied, 3);
+        assert_eq!(result.new_len, content.len() as u64);
+        assert_eq!(result.content_hash, ContentVerifier::compute_hash(&content));
+
+        let digests = chunk_hashes(&path, 8).await.unwrap();
+        assert_eq!(digests.len(), 3);
+        assert_eq!(digests[2].length, 5);
+    }
+
+    #[tokio::test]
+    async fn test_apply_chunked_patch_rejects_stale_or_overlapping_edits() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("asset.txt");
+        fs::write(&path, "original content").await.unwrap();
+
+        let stale = [ChunkEdit::replace(0, 8, "changed").with_expected_hash("deadbeef")];
+        assert!(matches!(
+            apply_chunked_patch(&path, &stale, 4).await,
+            Err(FileError::VerificationFailed(_))
+        ));
+
+        let overlapping = [ChunkEdit::delete(0, 5), ChunkEdit::delete(3, 2)];
+        assert!(matches!(
+            apply_chunked_patch(&path, &overlapping, 4).await,
+            Err(FileError::InvalidContent(_))
+        ));
+
+        let past_end = [ChunkEdit::delete(10, 10)];
+        assert!(apply_chunked_patch(&path, &past_end, 4).await.is_err());
+
+        assert_eq!(fs::read_to_string(&path).await.unwrap(), "original content");
+        let mut entries = std::fs::read_dir(temp_dir.path()).unwrap();
+        assert!(entries.next().is_some() && entries.next().is_none());
+    }
+}
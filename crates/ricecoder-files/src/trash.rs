@@ -0,0 +1,392 @@
+//! Recoverable deletion through a managed trash directory
+//!
+//! Deleted files and directories are moved into `files/<id>` under the trash
+//! directory, with their original location and deletion time recorded in
+//! `info/<id>.json`. Entries can be restored by ID until they are purged by the
+//! retention policy.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::FileError;
+
+/// Default number of days trashed entries are kept
+const DEFAULT_RETENTION_DAYS: u64 = 30;
+
+/// A file or directory in the trash
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrashEntry {
+    /// Entry ID, used to restore or purge the entry
+    pub id: String,
+    /// Absolute path the entry was deleted from
+    pub original_path: PathBuf,
+    /// When the entry was deleted
+    pub deleted_at: DateTime<Utc>,
+    /// Whether the entry is a directory
+    pub is_dir: bool,
+    /// Size in bytes (total size for directories)
+    pub size: u64,
+}
+
+/// Moves deleted files into a trash directory and restores them on request
+#[derive(Debug, Clone)]
+pub struct TrashManager {
+    trash_dir: PathBuf,
+    retention: Duration,
+}
+
+impl TrashManager {
+    /// Creates a new TrashManager
+    ///
+    /// # Arguments
+    ///
+    /// * `trash_dir` - Directory where trashed entries are kept
+    pub fn new(trash_dir: impl Into<PathBuf>) -> Self {
+        TrashManager {
+            trash_dir: trash_dir.into(),
+            retention: Duration::from_secs(DEFAULT_RETENTION_DAYS * 24 * 60 * 60),
+        }
+    }
+
+    /// Sets how long entries are kept before [`TrashManager::purge_expired`] removes them
+    pub fn with_retention(mut self, retention: Duration) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    /// Directory where trashed entries are kept
+    pub fn trash_dir(&self) -> &Path {
+        &self.trash_dir
+    }
+
+    /// Retention period of trashed entries
+    pub fn retention(&self) -> Duration {
+        self.retention
+    }
+
+    /// Moves a file or directory into the trash
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to delete
+    ///
+    /// # Returns
+    ///
+    /// The trash entry, which can later be passed to [`TrashManager::restore`]
+    pub fn trash(&self, path: &Path) -> Result<TrashEntry, FileError> {
+        let metadata =
+            fs::symlink_metadata(path).map_err(|_| FileError::NotFound(path.to_path_buf()))?;
+        let original_path = std::path::absolute(path)?;
+
+        fs::create_dir_all(self.files_dir())?;
+        fs::create_dir_all(self.info_dir())?;
+
+        let entry = TrashEntry {
+            id: Uuid::new_v4().to_string(),
+            original_path,
+            deleted_at: Utc::now(),
+            is_dir: metadata.is_dir(),
+            size: entry_size(path, &metadata),
+        };
+
+        // Record the entry first so an interrupted move can still be found
+        self.write_info(&entry)?;
+        if let Err(e) = move_entry(path, &self.file_path(&entry.id)) {
+            let _ = fs::remove_file(self.info_path(&entry.id));
+            return Err(FileError::TrashFailed(format!(
+                "Failed to move {} to trash: {}",
+                path.display(),
+                e
+            )));
+        }
+
+        tracing::debug!(id = %entry.id, path = %path.display(), "Moved to trash");
+        Ok(entry)
+    }
+
+    /// Restores a trashed entry to its original location
+    ///
+    /// # Arguments
+    ///
+    /// * `entry_id` - ID of the entry to restore
+    ///
+    /// # Returns
+    ///
+    /// The restored entry, or an error if something now exists at the
+    /// original location
+    pub fn restore(&self, entry_id: &str) -> Result<TrashEntry, FileError> {
+        let entry = self.get(entry_id)?;
+        if fs::symlink_metadata(&entry.original_path).is_ok() {
+            return Err(FileError::ConflictDetected(entry.original_path));
+        }
+
+        if let Some(parent) = entry.original_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        move_entry(&self.file_path(&entry.id), &entry.original_path).map_err(|e| {
+            FileError::TrashFailed(format!(
+                "Failed to restore {}: {}",
+                entry.original_path.display(),
+                e
+            ))
+        })?;
+        fs::remove_file(self.info_path(&entry.id))?;
+
+        tracing::debug!(id = %entry.id, path = %entry.original_path.display(), "Restored from trash");
+        Ok(entry)
+    }
+
+    /// Gets a trashed entry
+    pub fn get(&self, entry_id: &str) -> Result<TrashEntry, FileError> {
+        // IDs are file names inside the trash, so reject anything path-like
+        if entry_id.is_empty() || entry_id.contains(['/', '\\']) || entry_id.starts_with('.') {
+            return Err(FileError::TrashFailed(format!(
+                "Invalid trash entry ID: {}",
+                entry_id
+            )));
+        }
+
+        let info = fs::read_to_string(self.info_path(entry_id))
+            .map_err(|_| FileError::TrashFailed(format!("Trash entry not found: {}", entry_id)))?;
+        serde_json::from_str(&info)
+            .map_err(|e| FileError::TrashFailed(format!("Corrupt trash entry {}: {}", entry_id, e)))
+    }
+
+    /// Lists trashed entries, most recently deleted first
+    ///
+    /// Entries with unreadable metadata are skipped.
+    pub fn list(&self) -> Result<Vec<TrashEntry>, FileError> {
+        let dir = match fs::read_dir(self.info_dir()) {
+            Ok(dir) => dir,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut entries: Vec<TrashEntry> = dir
+            .filter_map(|item| item.ok())
+            .filter_map(|item| fs::read_to_string(item.path()).ok())
+            .filter_map(|info| serde_json::from_str(&info).ok())
+            .collect();
+        entries.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+        Ok(entries)
+    }
+
+    /// Permanently removes a trashed entry
+    pub fn purge(&self, entry_id: &str) -> Result<(), FileError> {
+        let entry = self.get(entry_id)?;
+        remove_entry(&self.file_path(&entry.id))?;
+        fs::remove_file(self.info_path(&entry.id))?;
+        Ok(())
+    }
+
+    /// Permanently removes entries older than the retention period
+    ///
+    /// # Returns
+    ///
+    /// Number of entries purged
+    pub fn purge_expired(&self) -> Result<usize, FileError> {
+        // A retention period too long to represent never expires anything
+        let Some(cutoff) = chrono::Duration::from_std(self.retention)
+            .ok()
+            .and_then(|retention| Utc::now().checked_sub_signed(retention))
+        else {
+            return Ok(0);
+        };
+
+        let mut purged = 0;
+        for entry in self.list()? {
+            if entry.deleted_at < cutoff {
+                self.purge(&entry.id)?;
+                purged += 1;
+            }
+        }
+        Ok(purged)
+    }
+
+    /// Permanently removes every trashed entry
+    ///
+    /// # Returns
+    ///
+    /// Number of entries purged
+    pub fn empty(&self) -> Result<usize, FileError> {
+        let entries = self.list()?;
+        for entry in &entries {
+            self.purge(&entry.id)?;
+        }
+        Ok(entries.len())
+    }
+
+    fn files_dir(&self) -> PathBuf {
+        self.trash_dir.join("files")
+    }
+
+    fn info_dir(&self) -> PathBuf {
+        self.trash_dir.join("info")
+    }
+
+    fn file_path(&self, entry_id: &str) -> PathBuf {
+        self.files_dir().join(entry_id)
+    }
+
+    fn info_path(&self, entry_id: &str) -> PathBuf {
+        self.info_dir().join(format!("{}.json", entry_id))
+    }
+
+    fn write_info(&self, entry: &TrashEntry) -> Result<(), FileError> {
+        let info = serde_json::to_string_pretty(entry)
+            .map_err(|e| FileError::TrashFailed(format!("Failed to encode trash entry: {}", e)))?;
+        fs::write(self.info_path(&entry.id), info)?;
+        Ok(())
+    }
+}
+
+impl Default for TrashManager {
+    fn default() -> Self {
+        Self::new(".ricecoder/trash")
+    }
+}
+
+/// Moves a file or directory, copying when a rename crosses filesystems
+fn move_entry(from: &Path, to: &Path) -> io::Result<()> {
+    match fs::rename(from, to) {
+        Ok(()) => Ok(()),
+        Err(_) => {
+            copy_entry(from, to)?;
+            remove_entry(from)
+        }
+    }
+}
+
+fn copy_entry(from: &Path, to: &Path) -> io::Result<()> {
+    let metadata = fs::symlink_metadata(from)?;
+    if metadata.is_dir() {
+        fs::create_dir_all(to)?;
+        for item in fs::read_dir(from)? {
+            let item = item?;
+            copy_entry(&item.path(), &to.join(item.file_name()))?;
+        }
+        fs::set_permissions(to, metadata.permissions())
+    } else if metadata.file_type().is_symlink() {
+        copy_symlink(&fs::read_link(from)?, to)
+    } else {
+        fs::copy(from, to).map(|_| ())
+    }
+}
+
+#[cfg(unix)]
+fn copy_symlink(target: &Path, link: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+#[cfg(not(unix))]
+fn copy_symlink(target: &Path, link: &Path) -> io::Result<()> {
+    fs::copy(target, link).map(|_| ())
+}
+
+fn remove_entry(path: &Path) -> io::Result<()> {
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.is_dir() => fs::remove_dir_all(path),
+        Ok(_) => fs::remove_file(path),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+fn entry_size(path: &Path, metadata: &fs::Metadata) -> u64 {
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    fs::read_dir(path)
+        .map(|dir| {
+            dir.filter_map(|item| item.ok())
+                .filter_map(|item| {
+                    let path = item.path();
+                    fs::symlink_metadata(&path)
+                        .ok()
+                        .map(|metadata| entry_size(&path, &metadata))
+                })
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_trash_and_restore_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let trash = TrashManager::new(temp_dir.path().join("trash"));
+        let file = temp_dir.path().join("src/main.rs");
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+        fs::write(&file, "fn main() {}").unwrap();
+
+        let entry = trash.trash(&file).unwrap();
+        assert!(!file.exists());
+        assert_eq!(entry.size, 12);
+        assert_eq!(trash.list().unwrap(), vec![entry.clone()]);
+
+        // Something new at the original path blocks the restore
+        fs::write(&file, "new").unwrap();
+        assert!(matches!(
+            trash.restore(&entry.id),
+            Err(FileError::ConflictDetected(_))
+        ));
+        fs::remove_file(&file).unwrap();
+
+        trash.restore(&entry.id).unwrap();
+        assert_eq!(fs::read_to_string(&file).unwrap(), "fn main() {}");
+        assert!(trash.list().unwrap().is_empty());
+        assert!(trash.restore(&entry.id).is_err());
+    }
+
+    #[test]
+    fn test_trash_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let trash = TrashManager::new(temp_dir.path().join("trash"));
+        let dir = temp_dir.path().join("generated");
+        fs::create_dir_all(dir.join("nested")).unwrap();
+        fs::write(dir.join("a.txt"), "aaa").unwrap();
+        fs::write(dir.join("nested/b.txt"), "bb").unwrap();
+
+        let entry = trash.trash(&dir).unwrap();
+        assert!(entry.is_dir);
+        assert_eq!(entry.size, 5);
+        assert!(!dir.exists());
+
+        trash.restore(&entry.id).unwrap();
+        assert_eq!(fs::read_to_string(dir.join("nested/b.txt")).unwrap(), "bb");
+    }
+
+    #[test]
+    fn test_purge_expired() {
+        let temp_dir = TempDir::new().unwrap();
+        let trash = TrashManager::new(temp_dir.path().join("trash"));
+        let old = temp_dir.path().join("old.txt");
+        let recent = temp_dir.path().join("recent.txt");
+        fs::write(&old, "old").unwrap();
+        fs::write(&recent, "recent").unwrap();
+
+        let mut old_entry = trash.trash(&old).unwrap();
+        old_entry.deleted_at = Utc::now() - chrono::Duration::days(31);
+        trash.write_info(&old_entry).unwrap();
+        let recent_entry = trash.trash(&recent).unwrap();
+
+        assert_eq!(trash.purge_expired().unwrap(), 1);
+        assert!(!trash.file_path(&old_entry.id).exists());
+        assert_eq!(trash.list().unwrap(), vec![recent_entry]);
+
+        assert!(trash.get("../info").is_err());
+        assert_eq!(trash.empty().unwrap(), 1);
+    }
+}
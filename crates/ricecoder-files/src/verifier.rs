@@ -27,8 +27,16 @@ impl ContentVerifier {
     ///
     /// Hexadecimal string representation of the SHA-256 hash
     pub fn compute_hash(content: &str) -> String {
+        Self::compute_bytes_hash(content.as_bytes())
+    }
+
+    /// Computes SHA-256 hash of raw bytes
+    ///
+    /// Produces the same hash as [`ContentVerifier::compute_hash`] for UTF-8
+    /// content, and also works for binary data.
+    pub fn compute_bytes_hash(content: &[u8]) -> String {
         let mut hasher = Sha256::new();
-        hasher.update(content.as_bytes());
+        hasher.update(content);
         format!("{:x}", hasher.finalize())
     }
 
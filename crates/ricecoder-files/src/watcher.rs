@@ -2,19 +2,50 @@
 //!
 //! This module provides file system watching capabilities with debouncing,
 //! event batching, and change detection for external file modifications.
+//!
+//! Raw file system events are filtered (gitignore rules, custom globs, size
+//! caps and editor temp files) and coalesced per path before they are batched,
+//! so a build writing thousands of files into `target/` or an editor saving via
+//! a temp-file swap does not flood subscribers. Deletes and creates that belong
+//! to the same move are reported as a single rename, and the debounce delay
+//! grows while events arrive in bursts.
 
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     path::{Path, PathBuf},
     sync::{Arc, Mutex},
     time::{Duration, Instant},
 };
 
-use notify::{Config, RecommendedWatcher, RecursiveMode, Watcher};
+use notify::{
+    event::{ModifyKind, RenameMode},
+    Config, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher,
+};
 use tokio::sync::broadcast;
 use tracing::{debug, error, info, warn};
 
-use crate::error::FileError;
+use crate::{error::FileError, gitignore::GitignoreFilter};
+
+/// Glob patterns ignored by default, relative to the watched directory
+pub const DEFAULT_IGNORE_PATTERNS: &[&str] = &["**/target", "**/node_modules", "**/.git"];
+
+/// File names editors write while saving, which are never reported
+const EDITOR_TEMP_PATTERNS: &[&str] = &[
+    "*.swp",
+    "*.swx",
+    "*.swo",
+    "*~",
+    ".#*",
+    "#*#",
+    "*.tmp",
+    "*.crswap",
+    "*.kate-swp",
+    "4913",
+    ".tmp-*",
+];
+
+/// Batches at least this large count as a burst and lengthen the debounce delay
+const BURST_EVENT_COUNT: usize = 25;
 
 /// File change event types
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -25,6 +56,25 @@ pub enum FileChangeEvent {
     Modified(PathBuf),
     /// File was deleted
     Deleted(PathBuf),
+    /// File was renamed or moved
+    Renamed {
+        /// Previous path
+        from: PathBuf,
+        /// New path
+        to: PathBuf,
+    },
+}
+
+impl FileChangeEvent {
+    /// Path the event applies to (the new path for renames)
+    pub fn path(&self) -> &Path {
+        match self {
+            FileChangeEvent::Created(path)
+            | FileChangeEvent::Modified(path)
+            | FileChangeEvent::Deleted(path) => path,
+            FileChangeEvent::Renamed { to, .. } => to,
+        }
+    }
 }
 
 /// Batched file change events
@@ -43,21 +93,40 @@ pub struct FileChangeBatch {
 pub struct WatcherConfig {
     /// Debounce delay for file change events (default: 100ms)
     pub debounce_delay: Duration,
+    /// Upper bound the debounce delay grows to during bursts of events (default: 1s)
+    pub max_debounce_delay: Duration,
     /// Maximum batch size before forcing a flush (default: 100)
     pub max_batch_size: usize,
     /// Whether to watch subdirectories recursively (default: true)
     pub recursive: bool,
     /// File extensions to watch (empty means all files)
     pub file_extensions: Vec<String>,
+    /// Skip paths ignored by `.gitignore`/`.ignore` in watched directories (default: true)
+    pub respect_gitignore: bool,
+    /// Glob patterns of paths to skip, relative to the watched directory
+    /// (default: [`DEFAULT_IGNORE_PATTERNS`])
+    pub ignore_patterns: Vec<String>,
+    /// Skip files larger than this many bytes (default: 50 MiB)
+    pub max_file_size: Option<u64>,
+    /// Report matching deletes and creates as renames (default: true)
+    pub detect_renames: bool,
 }
 
 impl Default for WatcherConfig {
     fn default() -> Self {
         Self {
             debounce_delay: Duration::from_millis(100),
+            max_debounce_delay: Duration::from_secs(1),
             max_batch_size: 100,
             recursive: true,
             file_extensions: Vec::new(),
+            respect_gitignore: true,
+            ignore_patterns: DEFAULT_IGNORE_PATTERNS
+                .iter()
+                .map(|pattern| pattern.to_string())
+                .collect(),
+            max_file_size: Some(50 * 1024 * 1024),
+            detect_renames: true,
         }
     }
 }
@@ -74,8 +143,8 @@ pub struct FileWatcher {
     running: Arc<Mutex<bool>>,
     /// Watched directories
     watched_dirs: Arc<Mutex<Vec<PathBuf>>>,
-    /// Pending events buffer
-    pending_events: Arc<Mutex<HashMap<PathBuf, (FileChangeEvent, Instant)>>>,
+    /// Filtering, pending events and debounce state
+    state: Arc<Mutex<WatchState>>,
 }
 
 impl FileWatcher {
@@ -87,6 +156,7 @@ impl FileWatcher {
     /// Create a new file watcher with custom configuration
     pub fn with_config(config: WatcherConfig) -> Result<Self, FileError> {
         let (event_tx, _) = broadcast::channel(100);
+        let state = WatchState::new(&config)?;
 
         Ok(Self {
             config,
@@ -94,7 +164,7 @@ impl FileWatcher {
             event_sender: event_tx,
             running: Arc::new(Mutex::new(false)),
             watched_dirs: Arc::new(Mutex::new(Vec::new())),
-            pending_events: Arc::new(Mutex::new(HashMap::new())),
+            state: Arc::new(Mutex::new(state)),
         })
     }
 
@@ -113,14 +183,16 @@ impl FileWatcher {
 
         // Initialize watcher if not already done
         if self.watcher.is_none() {
+            let state = self.state.clone();
+            let running = self.running.clone();
             let watcher = RecommendedWatcher::new(
-                |res| {
-                    // For now, we just log the event. In a real implementation,
-                    // you'd want to handle this properly with channels.
-                    match res {
-                        Ok(event) => debug!("File event: {:?}", event),
-                        Err(e) => error!("File watcher error: {}", e),
+                move |res: notify::Result<Event>| match res {
+                    Ok(event) => {
+                        if *running.lock().unwrap() {
+                            state.lock().unwrap().record(event, Instant::now());
+                        }
                     }
+                    Err(e) => error!("File watcher error: {}", e),
                 },
                 Config::default(),
             )
@@ -141,6 +213,14 @@ impl FileWatcher {
             .watch(path, mode)
             .map_err(|e| FileError::WatcherError(format!("Failed to watch path: {}", e)))?;
 
+        // Event paths are absolute, so ignore rules are rooted at the canonical path
+        let root = path.canonicalize()?;
+        self.state
+            .lock()
+            .unwrap()
+            .filter
+            .add_root(root, self.config.respect_gitignore);
+
         // Track watched directories
         self.watched_dirs.lock().unwrap().push(path.to_path_buf());
 
@@ -155,6 +235,10 @@ impl FileWatcher {
                 .unwatch(path)
                 .map_err(|e| FileError::WatcherError(format!("Failed to unwatch path: {}", e)))?;
 
+            if let Ok(root) = path.canonicalize() {
+                self.state.lock().unwrap().filter.remove_root(&root);
+            }
+
             // Remove from tracked directories
             self.watched_dirs.lock().unwrap().retain(|p| p != path);
 
@@ -205,36 +289,30 @@ impl FileWatcher {
         self.watched_dirs.lock().unwrap().clone()
     }
 
+    /// Current debounce delay, which grows while events arrive in bursts
+    pub fn current_debounce_delay(&self) -> Duration {
+        self.state.lock().unwrap().debounce.current
+    }
+
     /// Process pending events (call this periodically)
+    ///
+    /// Pending events are flushed once no new event has arrived for the
+    /// current debounce delay, when the batch size limit is reached, or when
+    /// the oldest event has waited for the maximum debounce delay.
     pub fn process_pending_events(&self) -> Result<(), FileError> {
         if !self.is_running() {
             return Ok(());
         }
 
-        let mut pending = self.pending_events.lock().unwrap();
         let now = Instant::now();
-        let mut batch_events = Vec::new();
-
-        // Collect events that are old enough to flush
-        let mut to_remove = Vec::new();
-        for (path, (event, timestamp)) in pending.iter() {
-            if now.duration_since(*timestamp) >= self.config.debounce_delay {
-                batch_events.push(event.clone());
-                to_remove.push(path.clone());
-            }
-        }
-
-        // Remove flushed events
-        for path in to_remove {
-            pending.remove(&path);
-        }
+        let events = self.state.lock().unwrap().take_ready(now);
 
-        // Send batch if we have events
-        if !batch_events.is_empty() {
-            let count = batch_events.len();
+        // Send batches if we have events
+        for chunk in events.chunks(self.config.max_batch_size.max(1)) {
+            let count = chunk.len();
             let batch = FileChangeBatch {
                 timestamp: now,
-                events: batch_events,
+                events: chunk.to_vec(),
                 count,
             };
 
@@ -255,14 +333,449 @@ impl Drop for FileWatcher {
     }
 }
 
+/// Decides which paths produce events
+struct WatchFilter {
+    roots: Vec<(PathBuf, GitignoreFilter)>,
+    ignore_patterns: Vec<glob::Pattern>,
+    editor_temp_patterns: Vec<glob::Pattern>,
+    file_extensions: Vec<String>,
+    max_file_size: Option<u64>,
+}
+
+impl WatchFilter {
+    fn new(config: &WatcherConfig) -> Result<Self, FileError> {
+        let ignore_patterns = config
+            .ignore_patterns
+            .iter()
+            .map(|pattern| {
+                glob::Pattern::new(pattern).map_err(|e| {
+                    FileError::WatcherError(format!("Invalid ignore pattern {}: {}", pattern, e))
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        let editor_temp_patterns = EDITOR_TEMP_PATTERNS
+            .iter()
+            .filter_map(|pattern| glob::Pattern::new(pattern).ok())
+            .collect();
+
+        Ok(Self {
+            roots: Vec::new(),
+            ignore_patterns,
+            editor_temp_patterns,
+            file_extensions: config.file_extensions.clone(),
+            max_file_size: config.max_file_size,
+        })
+    }
+
+    fn add_root(&mut self, root: PathBuf, respect_gitignore: bool) {
+        let gitignore = if respect_gitignore {
+            GitignoreFilter::load_from_directory(&root).unwrap_or_default()
+        } else {
+            GitignoreFilter::new()
+        };
+        self.roots.push((root, gitignore));
+    }
+
+    fn remove_root(&mut self, root: &Path) {
+        self.roots.retain(|(watched, _)| watched != root);
+    }
+
+    /// Whether a path is excluded by ignore rules
+    fn is_ignored(&self, path: &Path) -> bool {
+        let root = self
+            .roots
+            .iter()
+            .filter(|(root, _)| path.starts_with(root))
+            .max_by_key(|(root, _)| root.as_os_str().len());
+        let relative = root
+            .and_then(|(root, _)| path.strip_prefix(root).ok())
+            .unwrap_or(path);
+
+        // Match the path and each parent so `**/target` covers everything inside it
+        let ignored_by_pattern = relative.ancestors().any(|ancestor| {
+            !ancestor.as_os_str().is_empty()
+                && self
+                    .ignore_patterns
+                    .iter()
+                    .any(|pattern| pattern.matches_path(ancestor))
+        });
+
+        ignored_by_pattern
+            || root.is_some_and(|(_, gitignore)| {
+                gitignore.should_ignore_path_or_parents(path, path.is_dir())
+            })
+    }
+
+    /// Whether a path is a temp file written by an editor while saving
+    fn is_editor_temp(&self, path: &Path) -> bool {
+        path.file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| {
+                self.editor_temp_patterns
+                    .iter()
+                    .any(|pattern| pattern.matches(name))
+            })
+    }
+
+    /// Whether a path passes the extension filter
+    fn has_watched_extension(&self, path: &Path) -> bool {
+        self.file_extensions.is_empty()
+            || path.is_dir()
+            || path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| {
+                    self.file_extensions
+                        .iter()
+                        .any(|watched| watched.trim_start_matches('.') == ext)
+                })
+    }
+
+    /// Whether an existing file is within the size cap
+    fn within_size_cap(&self, path: &Path) -> bool {
+        match (self.max_file_size, std::fs::metadata(path)) {
+            (Some(max), Ok(metadata)) if metadata.is_file() => metadata.len() <= max,
+            _ => true,
+        }
+    }
+}
+
+/// Debounce delay that grows during bursts of events and decays when quiet
+#[derive(Debug, Clone)]
+struct AdaptiveDebounce {
+    base: Duration,
+    max: Duration,
+    current: Duration,
+}
+
+impl AdaptiveDebounce {
+    fn new(base: Duration, max: Duration) -> Self {
+        Self {
+            base,
+            max: max.max(base),
+            current: base,
+        }
+    }
+
+    fn record_flush(&mut self, count: usize) {
+        self.current = if count >= BURST_EVENT_COUNT {
+            (self.current * 2).min(self.max)
+        } else {
+            (self.current / 2).max(self.base)
+        };
+    }
+}
+
+/// A coalesced event waiting to be flushed
+#[derive(Debug, Clone)]
+struct PendingEvent {
+    event: FileChangeEvent,
+    /// Arrival order of the first raw event for this path
+    order: u64,
+}
+
+/// Filtering, coalescing and debounce state shared with the notify callback
+struct WatchState {
+    filter: WatchFilter,
+    detect_renames: bool,
+    max_batch_size: usize,
+    debounce: AdaptiveDebounce,
+    pending: HashMap<PathBuf, PendingEvent>,
+    /// Source paths of renames reported in two halves, by tracker ID
+    rename_sources: HashMap<usize, PathBuf>,
+    next_order: u64,
+    first_event: Option<Instant>,
+    last_event: Option<Instant>,
+}
+
+impl WatchState {
+    fn new(config: &WatcherConfig) -> Result<Self, FileError> {
+        Ok(Self {
+            filter: WatchFilter::new(config)?,
+            detect_renames: config.detect_renames,
+            max_batch_size: config.max_batch_size.max(1),
+            debounce: AdaptiveDebounce::new(config.debounce_delay, config.max_debounce_delay),
+            pending: HashMap::new(),
+            rename_sources: HashMap::new(),
+            next_order: 0,
+            first_event: None,
+            last_event: None,
+        })
+    }
+
+    /// Record a raw notify event
+    fn record(&mut self, event: Event, now: Instant) {
+        let tracker = event.tracker();
+        let mut paths = event.paths.into_iter();
+
+        match event.kind {
+            EventKind::Create(_) => {
+                for path in paths {
+                    self.record_change(FileChangeEvent::Created(path), now);
+                }
+            }
+            EventKind::Remove(_) => {
+                for path in paths {
+                    self.record_change(FileChangeEvent::Deleted(path), now);
+                }
+            }
+            EventKind::Modify(ModifyKind::Name(RenameMode::Both)) => {
+                if let (Some(from), Some(to)) = (paths.next(), paths.next()) {
+                    self.record_rename(from, to, now);
+                }
+            }
+            EventKind::Modify(ModifyKind::Name(RenameMode::From)) => {
+                for path in paths {
+                    if let Some(tracker) = tracker {
+                        self.rename_sources.insert(tracker, path.clone());
+                    }
+                    self.record_change(FileChangeEvent::Deleted(path), now);
+                }
+            }
+            EventKind::Modify(ModifyKind::Name(RenameMode::To)) => {
+                for path in paths {
+                    match tracker.and_then(|tracker| self.rename_sources.remove(&tracker)) {
+                        Some(from) => self.record_rename(from, path, now),
+                        None => self.record_change(FileChangeEvent::Created(path), now),
+                    }
+                }
+            }
+            EventKind::Modify(ModifyKind::Name(_)) => {
+                for path in paths {
+                    let event = if path.exists() {
+                        FileChangeEvent::Created(path)
+                    } else {
+                        FileChangeEvent::Deleted(path)
+                    };
+                    self.record_change(event, now);
+                }
+            }
+            // Permission and timestamp changes are noise for hooks and the TUI
+            EventKind::Modify(ModifyKind::Metadata(_)) => {}
+            EventKind::Modify(_) => {
+                for path in paths {
+                    self.record_change(FileChangeEvent::Modified(path), now);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Record a rename, resolving editor temp-file swaps and ignored paths
+    fn record_rename(&mut self, from: PathBuf, to: PathBuf, now: Instant) {
+        let from_hidden = self.filter.is_editor_temp(&from) || self.filter.is_ignored(&from);
+        let to_hidden = self.filter.is_editor_temp(&to) || self.filter.is_ignored(&to);
+
+        if to_hidden {
+            // Moved out of view, e.g. an editor renaming the original to a backup
+            self.record_change(FileChangeEvent::Deleted(from), now);
+        } else if self.filter.is_editor_temp(&from) {
+            // Atomic save: the editor wrote a temp file and swapped it in
+            self.record_change(FileChangeEvent::Modified(to), now);
+        } else if from_hidden {
+            self.record_change(FileChangeEvent::Created(to), now);
+        } else if !self.detect_renames {
+            self.record_change(FileChangeEvent::Deleted(from), now);
+            self.record_change(FileChangeEvent::Created(to), now);
+        } else {
+            let event = match self.pending.remove(&from) {
+                // A file created and then renamed within one batch is just created
+                Some(PendingEvent {
+                    event: FileChangeEvent::Created(_),
+                    ..
+                }) => FileChangeEvent::Created(to),
+                Some(PendingEvent {
+                    event: FileChangeEvent::Renamed { from: original, .. },
+                    ..
+                }) => FileChangeEvent::Renamed { from: original, to },
+                _ => FileChangeEvent::Renamed { from, to },
+            };
+            self.insert(event, now);
+        }
+    }
+
+    /// Record a create, modify or delete after filtering
+    fn record_change(&mut self, event: FileChangeEvent, now: Instant) {
+        let path = event.path();
+        if self.filter.is_editor_temp(path)
+            || self.filter.is_ignored(path)
+            || !self.filter.has_watched_extension(path)
+        {
+            return;
+        }
+        if !matches!(event, FileChangeEvent::Deleted(_)) && !self.filter.within_size_cap(path) {
+            debug!("Skipping change to oversized file: {}", path.display());
+            return;
+        }
+        self.insert(event, now);
+    }
+
+    /// Coalesce an event with any pending event for the same path
+    fn insert(&mut self, event: FileChangeEvent, now: Instant) {
+        let path = event.path().to_path_buf();
+        let previous = self.pending.remove(&path);
+        let order = previous.as_ref().map_or_else(
+            || {
+                self.next_order += 1;
+                self.next_order
+            },
+            |pending| pending.order,
+        );
+
+        self.first_event.get_or_insert(now);
+        self.last_event = Some(now);
+
+        let event = match (previous.map(|pending| pending.event), event) {
+            (None, event) => Some(event),
+            (Some(FileChangeEvent::Created(_)), FileChangeEvent::Modified(path)) => {
+                Some(FileChangeEvent::Created(path))
+            }
+            // Created and removed again before anyone saw it
+            (Some(FileChangeEvent::Created(_)), FileChangeEvent::Deleted(_)) => None,
+            // Deleted and recreated, e.g. an editor saving by replacing the file
+            (Some(FileChangeEvent::Deleted(_)), FileChangeEvent::Created(path)) => {
+                Some(FileChangeEvent::Modified(path))
+            }
+            (Some(renamed @ FileChangeEvent::Renamed { .. }), FileChangeEvent::Modified(_)) => {
+                Some(renamed)
+            }
+            (Some(FileChangeEvent::Renamed { from, .. }), FileChangeEvent::Deleted(_)) => {
+                Some(FileChangeEvent::Deleted(from))
+            }
+            (Some(_), event) => Some(event),
+        };
+
+        if let Some(event) = event {
+            self.pending
+                .insert(event.path().to_path_buf(), PendingEvent { event, order });
+        }
+    }
+
+    /// Take all pending events if they are ready to flush
+    fn take_ready(&mut self, now: Instant) -> Vec<FileChangeEvent> {
+        let (Some(first), Some(last)) = (self.first_event, self.last_event) else {
+            return Vec::new();
+        };
+
+        let quiet = now.duration_since(last) >= self.debounce.current;
+        let full = self.pending.len() >= self.max_batch_size;
+        let overdue = now.duration_since(first) >= self.debounce.max;
+        if !(quiet || full || overdue) {
+            return Vec::new();
+        }
+
+        let mut pending: Vec<PendingEvent> = self.pending.drain().map(|(_, p)| p).collect();
+        pending.sort_by_key(|pending| pending.order);
+        let mut events: Vec<FileChangeEvent> = pending.into_iter().map(|p| p.event).collect();
+        if self.detect_renames {
+            events = pair_renames(events);
+        }
+
+        self.first_event = None;
+        self.last_event = None;
+        self.rename_sources.clear();
+        self.debounce.record_flush(events.len());
+        events
+    }
+}
+
+/// Pair deletes with creates that look like the same file being moved
+///
+/// Moves between directories keep the file name; renames within a directory
+/// are only paired when the directory has exactly one delete and one create.
+fn pair_renames(events: Vec<FileChangeEvent>) -> Vec<FileChangeEvent> {
+    let deletes: Vec<usize> = indices(&events, |e| matches!(e, FileChangeEvent::Deleted(_)));
+    let creates: Vec<usize> = indices(&events, |e| matches!(e, FileChangeEvent::Created(_)));
+    let path = |index: usize| events[index].path();
+
+    let mut pairs: Vec<(usize, usize)> = Vec::new();
+    let mut paired: HashSet<usize> = HashSet::new();
+
+    for &delete in &deletes {
+        if let Some(&create) = creates.iter().find(|&&create| {
+            !paired.contains(&create) && path(create).file_name() == path(delete).file_name()
+        }) {
+            paired.extend([delete, create]);
+            pairs.push((delete, create));
+        }
+    }
+
+    for &delete in &deletes {
+        if paired.contains(&delete) {
+            continue;
+        }
+        let parent = path(delete).parent();
+        let unpaired_in_parent = |candidates: &[usize]| -> Vec<usize> {
+            candidates
+                .iter()
+                .copied()
+                .filter(|&i| !paired.contains(&i) && path(i).parent() == parent)
+                .collect()
+        };
+        if let ([_], [create]) = (
+            unpaired_in_parent(&deletes).as_slice(),
+            unpaired_in_parent(&creates).as_slice(),
+        ) {
+            let create = *create;
+            paired.extend([delete, create]);
+            pairs.push((delete, create));
+        }
+    }
+
+    let mut renames: HashMap<usize, FileChangeEvent> = pairs
+        .into_iter()
+        .map(|(delete, create)| {
+            let rename = FileChangeEvent::Renamed {
+                from: path(delete).to_path_buf(),
+                to: path(create).to_path_buf(),
+            };
+            (create, rename)
+        })
+        .collect();
+
+    events
+        .iter()
+        .enumerate()
+        .filter_map(|(index, event)| match renames.remove(&index) {
+            Some(rename) => Some(rename),
+            None if paired.contains(&index) => None,
+            None => Some(event.clone()),
+        })
+        .collect()
+}
+
+fn indices(events: &[FileChangeEvent], predicate: impl Fn(&FileChangeEvent) -> bool) -> Vec<usize> {
+    events
+        .iter()
+        .enumerate()
+        .filter(|(_, event)| predicate(event))
+        .map(|(index, _)| index)
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use std::fs;
 
+    use notify::event::{CreateKind, DataChange, RemoveKind};
     use tempfile::TempDir;
 
     use super::*;
 
+    fn event(kind: EventKind, paths: &[&Path]) -> Event {
+        paths.iter().fold(Event::new(kind), |event, path| {
+            event.add_path(path.to_path_buf())
+        })
+    }
+
+    fn state_for(root: &Path, config: WatcherConfig) -> WatchState {
+        let mut state = WatchState::new(&config).unwrap();
+        state
+            .filter
+            .add_root(root.canonicalize().unwrap(), config.respect_gitignore);
+        state
+    }
+
     #[test]
     fn test_file_watcher_creation() {
         let watcher = FileWatcher::new().unwrap();
@@ -296,4 +809,235 @@ mod tests {
         let result = watcher.watch(&file_path);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_watcher_reports_filtered_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        fs::create_dir_all(root.join("target")).unwrap();
+
+        let mut watcher = FileWatcher::new().unwrap();
+        let mut receiver = watcher.subscribe();
+        watcher.watch(&root).unwrap();
+        watcher.start().unwrap();
+
+        fs::write(root.join("target/out.o"), "artifact").unwrap();
+        fs::write(root.join("main.rs"), "fn main() {}").unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let batch = loop {
+            watcher.process_pending_events().unwrap();
+            if let Ok(batch) = receiver.try_recv() {
+                break batch;
+            }
+            assert!(Instant::now() < deadline, "no file change batch received");
+            std::thread::sleep(Duration::from_millis(20));
+        };
+
+        assert!(!batch.events.is_empty());
+        assert!(batch
+            .events
+            .iter()
+            .all(|event| event.path() == root.join("main.rs")));
+    }
+
+    #[test]
+    fn test_invalid_ignore_pattern() {
+        let config = WatcherConfig {
+            ignore_patterns: vec!["[".to_string()],
+            ..Default::default()
+        };
+        assert!(FileWatcher::with_config(config).is_err());
+    }
+
+    #[test]
+    fn test_ignore_rules() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        fs::write(root.join(".gitignore"), "*.log\n").unwrap();
+        fs::write(root.join("big.bin"), vec![0u8; 64]).unwrap();
+        let config = WatcherConfig {
+            ignore_patterns: vec!["**/target".to_string(), "dist/**".to_string()],
+            max_file_size: Some(32),
+            ..Default::default()
+        };
+        let mut state = state_for(&root, config);
+        let now = Instant::now();
+
+        for path in [
+            "target/debug/app",
+            "crates/core/target/debug/lib.rlib",
+            "dist/bundle.js",
+            "build.log",
+            "big.bin",
+            "main.rs.swp",
+            "main.rs",
+        ] {
+            state.record(
+                event(EventKind::Create(CreateKind::File), &[&root.join(path)]),
+                now,
+            );
+        }
+
+        assert_eq!(
+            state.take_ready(now + Duration::from_secs(1)),
+            vec![FileChangeEvent::Created(root.join("main.rs"))]
+        );
+    }
+
+    #[test]
+    fn test_coalescing_and_editor_saves() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        let mut state = state_for(&root, WatcherConfig::default());
+        let now = Instant::now();
+        let modify = EventKind::Modify(ModifyKind::Data(DataChange::Content));
+
+        // Created then written
+        let new_file = root.join("new.rs");
+        state.record(
+            event(EventKind::Create(CreateKind::File), &[&new_file]),
+            now,
+        );
+        state.record(event(modify, &[&new_file]), now);
+
+        // Written to a temp file and swapped in
+        let lib = root.join("lib.rs");
+        let temp = root.join(".tmp-1234-lib.rs");
+        state.record(event(EventKind::Create(CreateKind::File), &[&temp]), now);
+        state.record(event(modify, &[&temp]), now);
+        state.record(
+            event(
+                EventKind::Modify(ModifyKind::Name(RenameMode::Both)),
+                &[&temp, &lib],
+            ),
+            now,
+        );
+
+        // Deleted and recreated
+        let main = root.join("main.rs");
+        state.record(event(EventKind::Remove(RemoveKind::File), &[&main]), now);
+        state.record(event(EventKind::Create(CreateKind::File), &[&main]), now);
+
+        // Short-lived file
+        let scratch = root.join("scratch.rs");
+        state.record(event(EventKind::Create(CreateKind::File), &[&scratch]), now);
+        state.record(event(EventKind::Remove(RemoveKind::File), &[&scratch]), now);
+
+        assert_eq!(
+            state.take_ready(now + Duration::from_secs(1)),
+            vec![
+                FileChangeEvent::Created(new_file),
+                FileChangeEvent::Modified(lib),
+                FileChangeEvent::Modified(main),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rename_detection() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        let mut state = state_for(&root, WatcherConfig::default());
+        let now = Instant::now();
+
+        // Reported as one event
+        state.record(
+            event(
+                EventKind::Modify(ModifyKind::Name(RenameMode::Both)),
+                &[&root.join("a.rs"), &root.join("b.rs")],
+            ),
+            now,
+        );
+        // Reported in two halves linked by a tracker
+        state.record(
+            event(
+                EventKind::Modify(ModifyKind::Name(RenameMode::From)),
+                &[&root.join("c.rs")],
+            )
+            .set_tracker(7),
+            now,
+        );
+        state.record(
+            event(
+                EventKind::Modify(ModifyKind::Name(RenameMode::To)),
+                &[&root.join("d.rs")],
+            )
+            .set_tracker(7),
+            now,
+        );
+        // Reported as a delete and a create in another directory
+        state.record(
+            event(EventKind::Remove(RemoveKind::File), &[&root.join("e.rs")]),
+            now,
+        );
+        state.record(
+            event(
+                EventKind::Create(CreateKind::File),
+                &[&root.join("src/e.rs")],
+            ),
+            now,
+        );
+
+        assert_eq!(
+            state.take_ready(now + Duration::from_secs(1)),
+            vec![
+                FileChangeEvent::Renamed {
+                    from: root.join("a.rs"),
+                    to: root.join("b.rs"),
+                },
+                FileChangeEvent::Renamed {
+                    from: root.join("c.rs"),
+                    to: root.join("d.rs"),
+                },
+                FileChangeEvent::Renamed {
+                    from: root.join("e.rs"),
+                    to: root.join("src/e.rs"),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_adaptive_debounce() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        let config = WatcherConfig {
+            debounce_delay: Duration::from_millis(100),
+            max_debounce_delay: Duration::from_millis(400),
+            max_batch_size: 1000,
+            ..Default::default()
+        };
+        let mut state = state_for(&root, config);
+        let start = Instant::now();
+
+        // Not flushed until the debounce delay has passed without new events
+        let path = root.join("a.rs");
+        state.record(event(EventKind::Create(CreateKind::File), &[&path]), start);
+        assert!(state
+            .take_ready(start + Duration::from_millis(50))
+            .is_empty());
+        assert_eq!(
+            state.take_ready(start + Duration::from_millis(100)).len(),
+            1
+        );
+
+        // A burst doubles the delay, up to the maximum
+        for round in 0..3 {
+            for i in 0..BURST_EVENT_COUNT {
+                let path = root.join(format!("burst-{}-{}.rs", round, i));
+                state.record(event(EventKind::Create(CreateKind::File), &[&path]), start);
+            }
+            assert_eq!(
+                state.take_ready(start + Duration::from_secs(1)).len(),
+                BURST_EVENT_COUNT
+            );
+        }
+        assert_eq!(state.debounce.current, Duration::from_millis(400));
+
+        // Quiet batches bring it back down
+        state.record(event(EventKind::Create(CreateKind::File), &[&path]), start);
+        state.take_ready(start + Duration::from_secs(1));
+        assert_eq!(state.debounce.current, Duration::from_millis(200));
+    }
 }
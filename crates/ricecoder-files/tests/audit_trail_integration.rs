@@ -26,6 +26,7 @@ fn test_audit_trail_for_multi_file_operations() {
         operation_type: OperationType::Create,
         content_hash: "hash1".to_string(),
         transaction_id: None,
+        session_id: None,
     };
 
     let entry2 = AuditEntry {
@@ -34,6 +35,7 @@ fn test_audit_trail_for_multi_file_operations() {
         operation_type: OperationType::Create,
         content_hash: "hash2".to_string(),
         transaction_id: None,
+        session_id: None,
     };
 
     let entry3 = AuditEntry {
@@ -42,6 +44,7 @@ fn test_audit_trail_for_multi_file_operations() {
         operation_type: OperationType::Update,
         content_hash: "hash3".to_string(),
         transaction_id: None,
+        session_id: None,
     };
 
     // Log all entries
@@ -74,6 +77,7 @@ fn test_change_history_retrieval_for_file() {
         operation_type: OperationType::Create,
         content_hash: "hash1".to_string(),
         transaction_id: None,
+        session_id: None,
     };
 
     thread::sleep(Duration::from_millis(10));
@@ -84,6 +88,7 @@ fn test_change_history_retrieval_for_file() {
         operation_type: OperationType::Update,
         content_hash: "hash2".to_string(),
         transaction_id: None,
+        session_id: None,
     };
 
     thread::sleep(Duration::from_millis(10));
@@ -94,6 +99,7 @@ fn test_change_history_retrieval_for_file() {
         operation_type: OperationType::Update,
         content_hash: "hash3".to_string(),
         transaction_id: None,
+        session_id: None,
     };
 
     // Log all entries
@@ -129,6 +135,7 @@ fn test_change_history_ordered_by_timestamp() {
         operation_type: OperationType::Create,
         content_hash: "hash1".to_string(),
         transaction_id: None,
+        session_id: None,
     };
 
     thread::sleep(Duration::from_millis(50));
@@ -139,6 +146,7 @@ fn test_change_history_ordered_by_timestamp() {
         operation_type: OperationType::Update,
         content_hash: "hash2".to_string(),
         transaction_id: None,
+        session_id: None,
     };
 
     thread::sleep(Duration::from_millis(50));
@@ -149,6 +157,7 @@ fn test_change_history_ordered_by_timestamp() {
         operation_type: OperationType::Update,
         content_hash: "hash3".to_string(),
         transaction_id: None,
+        session_id: None,
     };
 
     // Log all entries
@@ -186,6 +195,7 @@ fn test_audit_trail_includes_transaction_ids() {
         operation_type: OperationType::Create,
         content_hash: "hash".to_string(),
         transaction_id: Some(tx_id),
+        session_id: None,
     };
 
     assert!(logger.log_operation(entry).is_ok());
@@ -214,6 +224,7 @@ fn test_audit_trail_tracks_operation_types() {
         operation_type: OperationType::Create,
         content_hash: "hash1".to_string(),
         transaction_id: None,
+        session_id: None,
     };
 
     std::thread::sleep(std::time::Duration::from_millis(50));
@@ -224,6 +235,7 @@ fn test_audit_trail_tracks_operation_types() {
         operation_type: OperationType::Update,
         content_hash: "hash2".to_string(),
         transaction_id: None,
+        session_id: None,
     };
 
     std::thread::sleep(std::time::Duration::from_millis(50));
@@ -234,6 +246,7 @@ fn test_audit_trail_tracks_operation_types() {
         operation_type: OperationType::Delete,
         content_hash: "hash3".to_string(),
         transaction_id: None,
+        session_id: None,
     };
 
     // Log all entries
@@ -268,6 +281,7 @@ fn test_audit_trail_for_multiple_files() {
         operation_type: OperationType::Create,
         content_hash: "hash1".to_string(),
         transaction_id: None,
+        session_id: None,
     };
 
     let entry2 = AuditEntry {
@@ -276,6 +290,7 @@ fn test_audit_trail_for_multiple_files() {
         operation_type: OperationType::Create,
         content_hash: "hash2".to_string(),
         transaction_id: None,
+        session_id: None,
     };
 
     let entry3 = AuditEntry {
@@ -284,6 +299,7 @@ fn test_audit_trail_for_multiple_files() {
         operation_type: OperationType::Create,
         content_hash: "hash3".to_string(),
         transaction_id: None,
+        session_id: None,
     };
 
     // Log all entries
@@ -321,6 +337,7 @@ fn test_audit_trail_preserves_content_hashes() {
         operation_type: OperationType::Create,
         content_hash: "abc123def456".to_string(),
         transaction_id: None,
+        session_id: None,
     };
 
     assert!(logger.log_operation(entry).is_ok());
@@ -364,6 +381,7 @@ fn test_audit_trail_persists_across_instances() {
             operation_type: OperationType::Create,
             content_hash: "hash1".to_string(),
             transaction_id: None,
+            session_id: None,
         };
         assert!(logger1.log_operation(entry).is_ok());
     }
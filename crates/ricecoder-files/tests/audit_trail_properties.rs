@@ -31,6 +31,7 @@ fn prop_all_operations_logged_with_required_fields() {
             operation_type: OperationType::Create,
             content_hash: content_hash.clone(),
             transaction_id: None,
+            session_id: None,
         };
 
         // Log the operation
@@ -67,6 +68,7 @@ fn prop_audit_entries_ordered_by_timestamp() {
                 operation_type: OperationType::Update,
                 content_hash: format!("{:064x}", i),
                 transaction_id: None,
+                session_id: None,
             };
 
             logger.log_operation(entry).unwrap();
@@ -105,6 +107,7 @@ fn prop_audit_entries_contain_all_required_fields() {
             operation_type: OperationType::Update,
             content_hash: content_hash.clone(),
             transaction_id: Some(tx_id),
+            session_id: None,
         };
 
         logger.log_operation(entry).unwrap();
@@ -141,6 +144,7 @@ fn prop_multiple_files_tracked_independently() {
             operation_type: OperationType::Create,
             content_hash: "hash1".to_string(),
             transaction_id: None,
+            session_id: None,
         };
 
         let entry2 = AuditEntry {
@@ -149,6 +153,7 @@ fn prop_multiple_files_tracked_independently() {
             operation_type: OperationType::Create,
             content_hash: "hash2".to_string(),
             transaction_id: None,
+            session_id: None,
         };
 
         logger.log_operation(entry1).unwrap();
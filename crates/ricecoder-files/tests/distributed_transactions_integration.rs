@@ -0,0 +1,177 @@
+//! Integration tests for distributed transactions
+//!
+//! Tests two-phase commits spanning multiple project roots, including aborts,
+//! conflicting external edits and path validation.
+
+use std::path::{Path, PathBuf};
+
+use ricecoder_files::{
+    distributed::{DistributedTransaction, JOURNAL_DIR},
+    models::{FileOperation, OperationType, TransactionStatus},
+    FileError,
+};
+use tempfile::TempDir;
+use tokio::fs;
+
+fn operation(path: &str, operation: OperationType, content: Option<&str>) -> FileOperation {
+    FileOperation {
+        path: PathBuf::from(path),
+        operation,
+        content: content.map(|c| c.to_string()),
+        backup_path: None,
+        content_hash: None,
+    }
+}
+
+async fn project(temp_dir: &TempDir, name: &str) -> PathBuf {
+    let root = temp_dir.path().join(name);
+    fs::create_dir_all(&root).await.unwrap();
+    root
+}
+
+async fn journal_count(root: &Path) -> usize {
+    match std::fs::read_dir(root.join(JOURNAL_DIR)) {
+        Ok(entries) => entries.count(),
+        Err(_) => 0,
+    }
+}
+
+/// Test committing changes to several projects at once
+#[tokio::test]
+async fn test_distributed_commit_applies_all_participants() {
+    let temp_dir = TempDir::new().unwrap();
+    let app = project(&temp_dir, "app").await;
+    let lib = project(&temp_dir, "lib").await;
+    fs::write(app.join("Cargo.toml"), "lib = \"1.0\"")
+        .await
+        .unwrap();
+    fs::write(lib.join("old.rs"), "legacy").await.unwrap();
+
+    let mut tx = DistributedTransaction::new();
+    tx.add_operation(
+        &app,
+        operation("Cargo.toml", OperationType::Update, Some("lib = \"2.0\"")),
+    )
+    .unwrap();
+    tx.add_operation(
+        &lib,
+        operation("src/new.rs", OperationType::Create, Some("pub fn new() {}")),
+    )
+    .unwrap();
+    tx.add_operation(&lib, operation("old.rs", OperationType::Delete, None))
+        .unwrap();
+    assert_eq!(tx.roots().len(), 2);
+
+    tx.prepare().await.unwrap();
+    assert_eq!(tx.status(), TransactionStatus::Prepared);
+    // Nothing is applied before commit
+    assert_eq!(
+        fs::read_to_string(app.join("Cargo.toml")).await.unwrap(),
+        "lib = \"1.0\""
+    );
+
+    tx.commit().await.unwrap();
+    assert_eq!(tx.status(), TransactionStatus::Committed);
+    assert_eq!(
+        fs::read_to_string(app.join("Cargo.toml")).await.unwrap(),
+        "lib = \"2.0\""
+    );
+    assert_eq!(
+        fs::read_to_string(lib.join("src/new.rs")).await.unwrap(),
+        "pub fn new() {}"
+    );
+    assert!(!lib.join("old.rs").exists());
+    assert_eq!(journal_count(&app).await, 0);
+    assert_eq!(journal_count(&lib).await, 0);
+}
+
+/// Test aborting a prepared transaction
+#[tokio::test]
+async fn test_distributed_abort_discards_staged_changes() {
+    let temp_dir = TempDir::new().unwrap();
+    let app = project(&temp_dir, "app").await;
+    let lib = project(&temp_dir, "lib").await;
+
+    let mut tx = DistributedTransaction::new();
+    tx.add_operation(&app, operation("a.txt", OperationType::Create, Some("a")))
+        .unwrap();
+    tx.add_operation(&lib, operation("b.txt", OperationType::Create, Some("b")))
+        .unwrap();
+    tx.prepare().await.unwrap();
+    assert_eq!(journal_count(&app).await, 2);
+
+    tx.abort().await.unwrap();
+    assert_eq!(tx.status(), TransactionStatus::RolledBack);
+    assert!(!app.join("a.txt").exists());
+    assert_eq!(journal_count(&app).await, 0);
+    assert!(tx.commit().await.is_err());
+}
+
+/// Test that an external edit between prepare and commit aborts everything
+#[tokio::test]
+async fn test_distributed_commit_rejects_changed_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let app = project(&temp_dir, "app").await;
+    let lib = project(&temp_dir, "lib").await;
+    fs::write(lib.join("lib.rs"), "original").await.unwrap();
+
+    let mut tx = DistributedTransaction::new();
+    tx.add_operation(&app, operation("a.txt", OperationType::Create, Some("a")))
+        .unwrap();
+    tx.add_operation(
+        &lib,
+        operation("lib.rs", OperationType::Update, Some("updated")),
+    )
+    .unwrap();
+    tx.execute().await.unwrap();
+    assert_eq!(tx.status(), TransactionStatus::Committed);
+
+    let mut tx = DistributedTransaction::new();
+    tx.add_operation(&app, operation("b.txt", OperationType::Create, Some("b")))
+        .unwrap();
+    tx.add_operation(
+        &lib,
+        operation("lib.rs", OperationType::Update, Some("mine")),
+    )
+    .unwrap();
+    tx.prepare().await.unwrap();
+    fs::write(lib.join("lib.rs"), "theirs").await.unwrap();
+
+    assert!(matches!(
+        tx.commit().await,
+        Err(FileError::TransactionFailed(_))
+    ));
+    assert_eq!(tx.status(), TransactionStatus::RolledBack);
+    assert!(!app.join("b.txt").exists());
+    assert_eq!(
+        fs::read_to_string(lib.join("lib.rs")).await.unwrap(),
+        "theirs"
+    );
+}
+
+/// Test that operations cannot escape their project root
+#[tokio::test]
+async fn test_distributed_operation_paths_stay_in_root() {
+    let temp_dir = TempDir::new().unwrap();
+    let app = project(&temp_dir, "app").await;
+    let mut tx = DistributedTransaction::new();
+
+    assert!(matches!(
+        tx.add_operation(
+            &app,
+            operation("../escape.txt", OperationType::Create, Some("x"))
+        ),
+        Err(FileError::InvalidPath(_))
+    ));
+    assert!(matches!(
+        tx.add_operation(
+            &app,
+            operation("/etc/passwd", OperationType::Update, Some("x"))
+        ),
+        Err(FileError::InvalidPath(_))
+    ));
+    assert!(tx
+        .add_operation(&app, operation("a.txt", OperationType::Create, None))
+        .is_err());
+    assert!(tx.prepare().await.is_err());
+}
@@ -134,6 +134,10 @@ impl CodeGenerator {
             temperature: Some(temperature),
             max_tokens: Some(max_tokens),
             stream: false,
+            top_p: None,
+            stop: None,
+            seed: None,
+            reasoning_effort: None,
         };
 
         // Call the provider
@@ -299,6 +303,10 @@ impl CodeGenerator {
             temperature: Some(temperature),
             max_tokens: Some(max_tokens),
             stream: true,
+            top_p: None,
+            stop: None,
+            seed: None,
+            reasoning_effort: None,
         };
 
         // Call the provider with streaming
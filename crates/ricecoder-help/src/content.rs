@@ -182,6 +182,7 @@ impl HelpContent {
                     .add_item("/new", "Create a new session")
                     .add_item("/sessions", "List and switch between sessions")
                     .add_item("/themes", "List and select themes")
+                    .add_item("/theme-edit", "Override theme colors with a live preview")
                     .add_item("/compact", "Compact current session to reduce token usage")
                     .add_item("/export", "Export session to Markdown")
                     .add_item("/undo", "Undo the last message")
@@ -192,6 +193,9 @@ impl HelpContent {
                     .add_item("/delete", "Delete current session")
                     .add_item("/copy", "Copy last message to clipboard")
                     .add_item("/settings", "Open settings interface")
+                    .add_item("/feedback", "Send feedback (category, rating, comments)")
+                    .add_item("/telemetry", "Review telemetry payload and opt in or out")
+                    .add_item("/init", "Set up this project (language servers, mode, provider)")
                     .add_item("/debug", "Toggle debug mode")
             )
             .add_category(
@@ -269,6 +273,10 @@ impl HelpContent {
                         "Themes",
                         "Use /themes to browse and select from 30+ built-in themes including Dracula, Nord, Tokyo Night, and more."
                     )
+                    .add_item(
+                        "Theme Colors",
+                        "Use /theme-edit to tweak single colors live, or set tui.theme_overrides in your config. Edited theme files reload automatically."
+                    )
                     .add_item(
                         "Vim Mode",
                         "Enable vim-style keybindings in settings for familiar navigation (hjkl, dd, yy, etc.)."
@@ -176,6 +176,16 @@ impl ConfigManager {
             }
         }
 
+        // Validate JetBrains configuration
+        if let Some(jetbrains_config) = &config.jetbrains {
+            if jetbrains_config.enabled && jetbrains_config.port == 0 {
+                return Err(IdeError::config_validation_error(
+                    "JetBrains integration is enabled but port is 0. \
+                     Please specify a valid port number (1-65535).",
+                ));
+            }
+        }
+
         debug!("Configuration validation passed");
         Ok(())
     }
@@ -184,6 +194,7 @@ impl ConfigManager {
     pub fn default_config() -> IdeIntegrationConfig {
         IdeIntegrationConfig {
             vscode: None,
+            jetbrains: None,
             terminal: None,
             providers: crate::types::ProviderChainConfig {
                 external_lsp: crate::types::ExternalLspConfig {
@@ -218,6 +229,7 @@ mod tests {
                 features: vec!["completion".to_string()],
                 settings: serde_json::json!({}),
             }),
+            jetbrains: None,
             terminal: None,
             providers: crate::types::ProviderChainConfig {
                 external_lsp: crate::types::ExternalLspConfig {
@@ -252,6 +264,7 @@ mod tests {
     fn test_validate_config_no_providers_enabled() {
         let config = IdeIntegrationConfig {
             vscode: None,
+            jetbrains: None,
             terminal: None,
             providers: crate::types::ProviderChainConfig {
                 external_lsp: crate::types::ExternalLspConfig {
@@ -279,6 +292,7 @@ mod tests {
     fn test_validate_config_empty_lsp_servers() {
         let config = IdeIntegrationConfig {
             vscode: None,
+            jetbrains: None,
             terminal: None,
             providers: crate::types::ProviderChainConfig {
                 external_lsp: crate::types::ExternalLspConfig {
@@ -306,6 +320,7 @@ mod tests {
     fn test_validate_config_invalid_lsp_command() {
         let config = IdeIntegrationConfig {
             vscode: None,
+            jetbrains: None,
             terminal: None,
             providers: crate::types::ProviderChainConfig {
                 external_lsp: crate::types::ExternalLspConfig {
@@ -347,6 +362,7 @@ mod tests {
                 features: vec![],
                 settings: serde_json::json!({}),
             }),
+            jetbrains: None,
             terminal: None,
             providers: crate::types::ProviderChainConfig {
                 external_lsp: crate::types::ExternalLspConfig {
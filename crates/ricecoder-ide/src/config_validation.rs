@@ -26,6 +26,10 @@ impl ConfigValidator {
             Self::validate_vscode_config(vscode_config)?;
         }
 
+        if let Some(jetbrains_config) = &config.jetbrains {
+            Self::validate_jetbrains_config(jetbrains_config)?;
+        }
+
         if let Some(terminal_config) = &config.terminal {
             Self::validate_terminal_config(terminal_config)?;
         }
@@ -382,6 +386,63 @@ impl ConfigValidator {
         Ok(())
     }
 
+    /// Validate JetBrains configuration
+    fn validate_jetbrains_config(config: &JetBrainsConfig) -> IdeResult<()> {
+        if !config.enabled {
+            return Ok(());
+        }
+
+        debug!("Validating JetBrains configuration");
+
+        if config.port == 0 {
+            return Err(IdeError::config_validation_error(
+                "Configuration validation failed: JetBrains integration is enabled but port is 0.\n\
+                 \n\
+                 Remediation steps:\n\
+                 1. Specify a valid port number (1-65535)\n\
+                 2. Use a different port than the VS Code integration\n\
+                 3. Example configuration:\n\
+                    jetbrains:\n\
+                      enabled: true\n\
+                      port: 8081",
+            ));
+        }
+
+        if config.capabilities.enabled().is_empty() {
+            return Err(IdeError::config_validation_error(
+                "Configuration validation failed: JetBrains integration is enabled but all capabilities are disabled.\n\
+                 \n\
+                 Remediation steps:\n\
+                 1. Enable at least one capability, or\n\
+                 2. Disable JetBrains integration if you don't want to use it\n\
+                 \n\
+                 Example configuration:\n\
+                 jetbrains:\n\
+                   enabled: true\n\
+                   capabilities:\n\
+                     completion: true\n\
+                     diagnostics: true\n\
+                     chat: true",
+            ));
+        }
+
+        if let Some(root) = config.project_roots.iter().find(|root| root.is_empty()) {
+            return Err(IdeError::config_validation_error(format!(
+                "Configuration validation failed: Empty project root '{}' in JetBrains configuration.\n\
+                 \n\
+                 Remediation steps:\n\
+                 1. Remove empty entries from project_roots\n\
+                 2. Example configuration:\n\
+                    jetbrains:\n\
+                      project_roots:\n\
+                        - ~/projects/my-app",
+                root
+            )));
+        }
+
+        Ok(())
+    }
+
     /// Validate terminal editor configuration
     fn validate_terminal_config(config: &TerminalConfig) -> IdeResult<()> {
         debug!("Validating terminal editor configuration");
@@ -455,6 +516,7 @@ mod tests {
                 features: vec!["completion".to_string()],
                 settings: serde_json::json!({}),
             }),
+            jetbrains: None,
             terminal: None,
             providers: ProviderChainConfig {
                 external_lsp: ExternalLspConfig {
@@ -577,6 +639,36 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("port is 0"));
     }
 
+    #[test]
+    fn test_validate_jetbrains_config() {
+        let mut config = create_valid_config();
+        config.jetbrains = Some(JetBrainsConfig {
+            enabled: true,
+            port: 8081,
+            capabilities: IdeCapabilities::default(),
+            project_roots: vec!["/workspace".to_string()],
+            settings: serde_json::json!({}),
+        });
+        assert!(ConfigValidator::validate_complete(&config).is_ok());
+
+        config.jetbrains.as_mut().unwrap().capabilities = IdeCapabilities {
+            completion: false,
+            diagnostics: false,
+            hover: false,
+            definition: false,
+            chat: false,
+        };
+        let result = ConfigValidator::validate_complete(&config);
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("all capabilities are disabled"));
+
+        config.jetbrains.as_mut().unwrap().port = 0;
+        let result = ConfigValidator::validate_complete(&config);
+        assert!(result.unwrap_err().to_string().contains("port is 0"));
+    }
+
     #[test]
     fn test_validate_empty_vscode_features() {
         let mut config = create_valid_config();
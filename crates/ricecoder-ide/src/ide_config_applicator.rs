@@ -15,6 +15,8 @@ use crate::{
 pub enum IdeType {
     /// VS Code
     VsCode,
+    /// JetBrains IDEs (IntelliJ IDEA, PyCharm, WebStorm, ...)
+    JetBrains,
     /// Vim/Neovim
     Vim,
     /// Emacs
@@ -28,6 +30,8 @@ impl IdeType {
     pub fn parse(s: &str) -> Self {
         match s.to_lowercase().as_str() {
             "vscode" | "vs-code" | "vs_code" => IdeType::VsCode,
+            "jetbrains" | "intellij" | "idea" | "pycharm" | "webstorm" | "goland" | "clion"
+            | "rustrover" => IdeType::JetBrains,
             "vim" | "neovim" | "nvim" => IdeType::Vim,
             "emacs" => IdeType::Emacs,
             _ => IdeType::Unknown,
@@ -38,6 +42,7 @@ impl IdeType {
     pub fn as_str(&self) -> &'static str {
         match self {
             IdeType::VsCode => "vscode",
+            IdeType::JetBrains => "jetbrains",
             IdeType::Vim => "vim",
             IdeType::Emacs => "emacs",
             IdeType::Unknown => "unknown",
@@ -123,10 +128,11 @@ impl IdeConfigApplicator {
 
         match ide_type {
             IdeType::VsCode => Self::extract_vscode_settings(config),
+            IdeType::JetBrains => Self::extract_jetbrains_settings(config),
             IdeType::Vim => Self::extract_vim_settings(config),
             IdeType::Emacs => Self::extract_emacs_settings(config),
             IdeType::Unknown => Err(IdeError::config_error(
-                "Unknown IDE type. Supported IDEs: vscode, vim, emacs",
+                "Unknown IDE type. Supported IDEs: vscode, jetbrains, vim, emacs",
             )),
         }
     }
@@ -167,6 +173,47 @@ impl IdeConfigApplicator {
         Ok(settings)
     }
 
+    /// Extract JetBrains specific settings
+    fn extract_jetbrains_settings(config: &IdeIntegrationConfig) -> IdeResult<IdeSpecificSettings> {
+        debug!("Extracting JetBrains specific settings");
+
+        let jetbrains_config = config
+            .jetbrains
+            .as_ref()
+            .ok_or_else(|| IdeError::config_error("JetBrains configuration not found"))?;
+
+        if !jetbrains_config.enabled {
+            return Err(IdeError::config_error("JetBrains integration is disabled"));
+        }
+
+        let mut settings = IdeSpecificSettings::new(IdeType::JetBrains)
+            .with_port(jetbrains_config.port)
+            .with_timeout(5000); // Default timeout for JetBrains
+
+        // Add enabled capabilities as features
+        for capability in jetbrains_config.capabilities.enabled() {
+            settings = settings.with_feature(capability.to_string());
+        }
+
+        // Add custom settings from JetBrains settings
+        if let Some(settings_obj) = jetbrains_config.settings.as_object() {
+            for (key, value) in settings_obj {
+                settings = settings.with_setting(key.clone(), value.clone());
+            }
+        }
+
+        settings = settings.with_setting(
+            "project_roots".to_string(),
+            serde_json::json!(jetbrains_config.project_roots),
+        );
+
+        info!(
+            "Extracted JetBrains settings: {} features enabled",
+            settings.enabled_features.len()
+        );
+        Ok(settings)
+    }
+
     /// Extract Vim specific settings
     fn extract_vim_settings(config: &IdeIntegrationConfig) -> IdeResult<IdeSpecificSettings> {
         debug!("Extracting Vim specific settings");
@@ -293,8 +340,8 @@ impl IdeConfigApplicator {
                     }
                 }
             }
-            IdeType::Emacs => {
-                // Emacs prefers detailed information
+            IdeType::Emacs | IdeType::JetBrains => {
+                // Emacs and JetBrains IDEs show detailed information
                 for item in items.iter_mut() {
                     if item.documentation.is_none() {
                         if let Some(detail) = &item.detail {
@@ -357,6 +404,10 @@ impl IdeConfigApplicator {
                     // Emacs supports some markup
                     // Keep content as-is
                 }
+                IdeType::JetBrains => {
+                    // JetBrains IDEs render markdown in quick documentation
+                    // Keep content as-is
+                }
                 IdeType::Unknown => {}
             }
         }
@@ -410,6 +461,8 @@ mod tests {
     fn test_ide_type_from_str() {
         assert_eq!(IdeType::parse("vscode"), IdeType::VsCode);
         assert_eq!(IdeType::parse("vs-code"), IdeType::VsCode);
+        assert_eq!(IdeType::parse("jetbrains"), IdeType::JetBrains);
+        assert_eq!(IdeType::parse("PyCharm"), IdeType::JetBrains);
         assert_eq!(IdeType::parse("vim"), IdeType::Vim);
         assert_eq!(IdeType::parse("neovim"), IdeType::Vim);
         assert_eq!(IdeType::parse("emacs"), IdeType::Emacs);
@@ -419,6 +472,7 @@ mod tests {
     #[test]
     fn test_ide_type_as_str() {
         assert_eq!(IdeType::VsCode.as_str(), "vscode");
+        assert_eq!(IdeType::JetBrains.as_str(), "jetbrains");
         assert_eq!(IdeType::Vim.as_str(), "vim");
         assert_eq!(IdeType::Emacs.as_str(), "emacs");
         assert_eq!(IdeType::Unknown.as_str(), "unknown");
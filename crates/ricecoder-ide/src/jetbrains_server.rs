@@ -0,0 +1,635 @@
+//! JetBrains plugin protocol server
+//!
+//! A long-lived local server the ricecoder JetBrains plugin connects to. The
+//! protocol is newline-delimited JSON over TCP on `127.0.0.1:<jetbrains.port>`;
+//! every line is one message.
+//!
+//! # Messages
+//!
+//! Requests carry a plugin-chosen `id`, a `method` and `params`:
+//!
+//! ```json
+//! {"id": 1, "method": "completion", "params": {...}}
+//! ```
+//!
+//! Each request gets exactly one response with the same `id`, holding either a
+//! `result` or an `error` with a machine-readable `code`:
+//!
+//! ```json
+//! {"id": 1, "result": {...}}
+//! {"id": 1, "error": {"code": "capability_disabled", "message": "..."}}
+//! ```
+//!
+//! # Methods
+//!
+//! - `handshake` must be sent first. Params: `ide` (product name),
+//!   `ide_version`, `project_roots` (content roots of the open project, most
+//!   specific first). The server picks the first root that exists and is
+//!   allowed by `jetbrains.project_roots`, and returns it with the enabled
+//!   capability flags: `{"protocol_version", "server_version",
+//!   "project_root", "capabilities"}`.
+//! - `completion`: `{file_path, language, position, context}`, returns a list
+//!   of completion items.
+//! - `diagnostics`: `{file_path, language, source}`, returns a list of
+//!   diagnostics.
+//! - `chat`: `{message, file_path?, source?, selection?}`, returns
+//!   `{content, edit?}`; the plugin applies `edit` itself.
+//! - `shutdown`: returns `null` and closes the connection.
+//!
+//! `file_path` may be absolute or relative to the negotiated project root, and
+//! must be inside it.
+//!
+//! # Error codes
+//!
+//! `invalid_request`, `unknown_method`, `not_negotiated`, `no_project_root`,
+//! `capability_disabled`, `outside_project`, `internal`.
+
+use std::{
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader},
+    net::TcpListener,
+};
+use tracing::{debug, info, warn};
+
+use crate::{
+    error::{IdeError, IdeResult},
+    manager::IdeIntegrationManager,
+    types::*,
+    vscode_bridge::{BridgeHandler, ChatParams, TextDocument},
+};
+
+/// Version of the JetBrains protocol spoken by this server
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Protocol error returned to the plugin
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProtocolError {
+    /// Machine-readable error code
+    pub code: String,
+    /// Human-readable message
+    pub message: String,
+}
+
+impl ProtocolError {
+    fn new(code: &str, message: impl Into<String>) -> Self {
+        ProtocolError {
+            code: code.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+impl From<IdeError> for ProtocolError {
+    fn from(error: IdeError) -> Self {
+        ProtocolError::new("internal", error.to_string())
+    }
+}
+
+impl From<serde_json::Error> for ProtocolError {
+    fn from(error: serde_json::Error) -> Self {
+        ProtocolError::new("invalid_request", error.to_string())
+    }
+}
+
+/// `handshake` request parameters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HandshakeParams {
+    /// IDE product name (e.g. "IntelliJ IDEA")
+    pub ide: String,
+    /// IDE version
+    #[serde(default)]
+    pub ide_version: Option<String>,
+    /// Content roots of the open project, most specific first
+    pub project_roots: Vec<String>,
+}
+
+/// `handshake` result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HandshakeResult {
+    /// Protocol version spoken by the server
+    pub protocol_version: u32,
+    /// ricecoder version
+    pub server_version: String,
+    /// Negotiated project root
+    pub project_root: String,
+    /// Capabilities the plugin may use
+    pub capabilities: IdeCapabilities,
+}
+
+#[derive(Deserialize)]
+struct Request {
+    id: Value,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Deserialize)]
+struct JetBrainsChatParams {
+    message: String,
+    #[serde(default)]
+    file_path: Option<String>,
+    #[serde(default)]
+    source: Option<String>,
+    #[serde(default)]
+    selection: Option<Range>,
+}
+
+/// State of a single plugin connection
+#[derive(Debug, Default)]
+struct Session {
+    ide: Option<String>,
+    project_root: Option<PathBuf>,
+}
+
+/// Local protocol server for the JetBrains plugin
+pub struct JetBrainsServer {
+    config: JetBrainsConfig,
+    manager: Arc<IdeIntegrationManager>,
+    handler: Arc<dyn BridgeHandler>,
+}
+
+impl JetBrainsServer {
+    /// Create a new server
+    pub fn new(
+        config: JetBrainsConfig,
+        manager: Arc<IdeIntegrationManager>,
+        handler: Arc<dyn BridgeHandler>,
+    ) -> Self {
+        JetBrainsServer {
+            config,
+            manager,
+            handler,
+        }
+    }
+
+    /// Bind the server socket on localhost at the configured port
+    pub async fn bind(&self) -> IdeResult<TcpListener> {
+        if !self.config.enabled {
+            return Err(IdeError::communication_error(
+                "JetBrains integration is not enabled in configuration",
+            ));
+        }
+        let address = SocketAddr::from(([127, 0, 0, 1], self.config.port));
+        let listener = TcpListener::bind(address).await?;
+        info!("JetBrains server listening on {}", listener.local_addr()?);
+        Ok(listener)
+    }
+
+    /// Accept plugin connections until the listener fails
+    pub async fn serve(self: Arc<Self>, listener: TcpListener) -> IdeResult<()> {
+        loop {
+            let (stream, peer) = listener.accept().await?;
+            info!("JetBrains plugin connected from {}", peer);
+            let server = self.clone();
+            tokio::spawn(async move {
+                if let Err(e) = server.run(stream).await {
+                    warn!("JetBrains connection failed: {}", e);
+                }
+            });
+        }
+    }
+
+    /// Serve a single connection until the plugin disconnects or shuts down
+    pub async fn run<S>(&self, stream: S) -> IdeResult<()>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let (reader, mut writer) = tokio::io::split(stream);
+        let mut lines = BufReader::new(reader).lines();
+        let mut session = Session::default();
+
+        while let Some(line) = lines.next_line().await? {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let (response, shutdown) = match serde_json::from_str::<Request>(&line) {
+                Ok(request) => {
+                    let shutdown = request.method == "shutdown";
+                    let outcome = self
+                        .handle(&mut session, &request.method, request.params)
+                        .await;
+                    (Self::response(request.id, outcome), shutdown)
+                }
+                Err(e) => (Self::response(Value::Null, Err(e.into())), false),
+            };
+
+            let mut line = serde_json::to_vec(&response)?;
+            line.push(b'\n');
+            writer.write_all(&line).await?;
+            writer.flush().await?;
+
+            if shutdown {
+                break;
+            }
+        }
+
+        info!(
+            "JetBrains plugin disconnected: {}",
+            session.ide.as_deref().unwrap_or("unknown IDE")
+        );
+        Ok(())
+    }
+
+    fn response(id: Value, outcome: Result<Value, ProtocolError>) -> Value {
+        match outcome {
+            Ok(result) => json!({ "id": id, "result": result }),
+            Err(error) => json!({ "id": id, "error": error }),
+        }
+    }
+
+    async fn handle(
+        &self,
+        session: &mut Session,
+        method: &str,
+        params: Value,
+    ) -> Result<Value, ProtocolError> {
+        debug!("Handling JetBrains request: {}", method);
+
+        if method == "handshake" {
+            let params: HandshakeParams = serde_json::from_value(params)?;
+            let result = self.handshake(session, params)?;
+            return Ok(serde_json::to_value(result)?);
+        }
+        if method == "shutdown" {
+            return Ok(Value::Null);
+        }
+
+        let root = session.project_root.clone().ok_or_else(|| {
+            ProtocolError::new("not_negotiated", "Send a handshake request first")
+        })?;
+
+        match method {
+            "completion" => {
+                self.require("completion")?;
+                let mut params: CompletionParams = serde_json::from_value(params)?;
+                params.file_path = Self::resolve_path(&root, &params.file_path)?;
+                let items = self.manager.handle_completion_request(&params).await?;
+                Ok(serde_json::to_value(items)?)
+            }
+            "diagnostics" => {
+                self.require("diagnostics")?;
+                let mut params: DiagnosticsParams = serde_json::from_value(params)?;
+                params.file_path = Self::resolve_path(&root, &params.file_path)?;
+                let diagnostics = self.manager.handle_diagnostics_request(&params).await?;
+                Ok(serde_json::to_value(diagnostics)?)
+            }
+            "chat" => {
+                self.require("chat")?;
+                let params: JetBrainsChatParams = serde_json::from_value(params)?;
+                let file_path = params
+                    .file_path
+                    .as_deref()
+                    .map(|path| Self::resolve_path(&root, path))
+                    .transpose()?;
+                let document = match (&file_path, params.source) {
+                    (Some(path), Some(text)) => Some(TextDocument {
+                        uri: format!("file://{}", path),
+                        language_id: String::new(),
+                        version: 0,
+                        text,
+                    }),
+                    _ => None,
+                };
+
+                let reply = self
+                    .handler
+                    .chat(
+                        ChatParams {
+                            message: params.message,
+                            uri: file_path.map(|path| format!("file://{}", path)),
+                            selection: params.selection,
+                        },
+                        document,
+                    )
+                    .await?;
+                Ok(serde_json::to_value(reply)?)
+            }
+            _ => Err(ProtocolError::new(
+                "unknown_method",
+                format!("Unknown method: {}", method),
+            )),
+        }
+    }
+
+    /// Negotiate the project root for a connection
+    fn handshake(
+        &self,
+        session: &mut Session,
+        params: HandshakeParams,
+    ) -> Result<HandshakeResult, ProtocolError> {
+        let allowed: Vec<PathBuf> = self
+            .config
+            .project_roots
+            .iter()
+            .filter_map(|root| Path::new(root).canonicalize().ok())
+            .collect();
+
+        let project_root = params
+            .project_roots
+            .iter()
+            .filter_map(|root| Path::new(root).canonicalize().ok())
+            .find(|root| {
+                root.is_dir()
+                    && (self.config.project_roots.is_empty()
+                        || allowed.iter().any(|allowed| root.starts_with(allowed)))
+            })
+            .ok_or_else(|| {
+                ProtocolError::new(
+                    "no_project_root",
+                    format!(
+                        "None of the offered project roots are allowed: {}",
+                        params.project_roots.join(", ")
+                    ),
+                )
+            })?;
+
+        info!(
+            "JetBrains handshake from {} {} for {}",
+            params.ide,
+            params.ide_version.as_deref().unwrap_or(""),
+            project_root.display()
+        );
+        session.ide = Some(params.ide);
+        session.project_root = Some(project_root.clone());
+
+        Ok(HandshakeResult {
+            protocol_version: PROTOCOL_VERSION,
+            server_version: env!("CARGO_PKG_VERSION").to_string(),
+            project_root: project_root.to_string_lossy().to_string(),
+            capabilities: self.config.capabilities,
+        })
+    }
+
+    fn require(&self, capability: &str) -> Result<(), ProtocolError> {
+        if self.config.capabilities.is_enabled(capability) {
+            Ok(())
+        } else {
+            Err(ProtocolError::new(
+                "capability_disabled",
+                format!("The {} capability is disabled for JetBrains", capability),
+            ))
+        }
+    }
+
+    /// Resolve a file path against the project root, rejecting paths outside it
+    fn resolve_path(root: &Path, file_path: &str) -> Result<String, ProtocolError> {
+        let path = root.join(file_path);
+        let mut resolved = PathBuf::new();
+        for component in path.components() {
+            match component {
+                std::path::Component::ParentDir => {
+                    resolved.pop();
+                }
+                std::path::Component::CurDir => {}
+                other => resolved.push(other),
+            }
+        }
+
+        if !resolved.starts_with(root) {
+            return Err(ProtocolError::new(
+                "outside_project",
+                format!("{} is outside the project root", file_path),
+            ));
+        }
+        Ok(resolved.to_string_lossy().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use async_trait::async_trait;
+    use tokio::io::{DuplexStream, Lines, ReadHalf, WriteHalf};
+
+    use super::*;
+    use crate::{
+        generic_provider::GenericProvider,
+        provider_chain::{ProviderChainManager, ProviderRegistry},
+        vscode_bridge::{ChatReply, SessionStatus},
+    };
+
+    struct EchoHandler;
+
+    #[async_trait]
+    impl BridgeHandler for EchoHandler {
+        async fn chat(
+            &self,
+            params: ChatParams,
+            document: Option<TextDocument>,
+        ) -> IdeResult<ChatReply> {
+            Ok(ChatReply {
+                content: format!(
+                    "{} ({})",
+                    params.message,
+                    document.map(|d| d.uri).unwrap_or_default()
+                ),
+                edit: None,
+            })
+        }
+
+        async fn session_status(&self) -> IdeResult<SessionStatus> {
+            Ok(SessionStatus::default())
+        }
+    }
+
+    fn server(capabilities: IdeCapabilities, project_roots: Vec<String>) -> Arc<JetBrainsServer> {
+        let config = IdeIntegrationConfig {
+            vscode: None,
+            jetbrains: None,
+            terminal: None,
+            providers: ProviderChainConfig {
+                external_lsp: ExternalLspConfig {
+                    enabled: false,
+                    servers: HashMap::new(),
+                    health_check_interval_ms: 5000,
+                },
+                configured_rules: None,
+                builtin_providers: BuiltinProvidersConfig {
+                    enabled: false,
+                    languages: Vec::new(),
+                },
+            },
+        };
+        let chain = Arc::new(ProviderChainManager::new(ProviderRegistry::new(Arc::new(
+            GenericProvider::new(),
+        ))));
+        Arc::new(JetBrainsServer::new(
+            JetBrainsConfig {
+                enabled: true,
+                port: 0,
+                capabilities,
+                project_roots,
+                settings: Value::Null,
+            },
+            Arc::new(IdeIntegrationManager::new(config, chain)),
+            Arc::new(EchoHandler),
+        ))
+    }
+
+    struct Client {
+        lines: Lines<BufReader<ReadHalf<DuplexStream>>>,
+        writer: WriteHalf<DuplexStream>,
+        next_id: u64,
+    }
+
+    impl Client {
+        fn connect(server: &Arc<JetBrainsServer>) -> Self {
+            let (client, stream) = tokio::io::duplex(64 * 1024);
+            let server = server.clone();
+            tokio::spawn(async move { server.run(stream).await });
+            let (reader, writer) = tokio::io::split(client);
+            Client {
+                lines: BufReader::new(reader).lines(),
+                writer,
+                next_id: 1,
+            }
+        }
+
+        async fn request(&mut self, method: &str, params: Value) -> Value {
+            let id = self.next_id;
+            self.next_id += 1;
+            let line = format!(
+                "{}\n",
+                json!({ "id": id, "method": method, "params": params })
+            );
+            self.writer.write_all(line.as_bytes()).await.unwrap();
+            let response: Value =
+                serde_json::from_str(&self.lines.next_line().await.unwrap().unwrap()).unwrap();
+            assert_eq!(response["id"], id);
+            response
+        }
+
+        async fn handshake(&mut self, root: &Path) -> Value {
+            self.request(
+                "handshake",
+                json!({
+                    "ide": "IntelliJ IDEA",
+                    "ide_version": "2024.1",
+                    "project_roots": ["/does/not/exist", root],
+                }),
+            )
+            .await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_handshake_negotiates_project_root() {
+        let project = tempfile::tempdir().unwrap();
+        let root = project.path().canonicalize().unwrap();
+        let server = server(IdeCapabilities::default(), Vec::new());
+        let mut client = Client::connect(&server);
+
+        let response = client
+            .request("completion", json!({ "file_path": "src/main.rs" }))
+            .await;
+        assert_eq!(response["error"]["code"], "not_negotiated");
+
+        let response = client.handshake(&root).await;
+        assert_eq!(response["result"]["protocol_version"], PROTOCOL_VERSION);
+        assert_eq!(
+            response["result"]["project_root"],
+            root.to_string_lossy().as_ref()
+        );
+        assert_eq!(response["result"]["capabilities"]["chat"], true);
+
+        let response = client
+            .request(
+                "completion",
+                json!({
+                    "file_path": "src/main.rs",
+                    "language": "rust",
+                    "position": { "line": 0, "character": 0 },
+                    "context": "let value = other_value;",
+                }),
+            )
+            .await;
+        assert!(!response["result"].as_array().unwrap().is_empty());
+
+        let response = client
+            .request(
+                "diagnostics",
+                json!({ "file_path": "../outside.rs", "language": "rust", "source": "" }),
+            )
+            .await;
+        assert_eq!(response["error"]["code"], "outside_project");
+
+        let response = client.request("refactor", json!({})).await;
+        assert_eq!(response["error"]["code"], "unknown_method");
+
+        let response = client.request("shutdown", Value::Null).await;
+        assert_eq!(response["result"], Value::Null);
+        assert!(client.lines.next_line().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_handshake_rejects_disallowed_roots() {
+        let allowed = tempfile::tempdir().unwrap();
+        let other = tempfile::tempdir().unwrap();
+        let server = server(
+            IdeCapabilities::default(),
+            vec![allowed.path().to_string_lossy().to_string()],
+        );
+        let mut client = Client::connect(&server);
+
+        let response = client.handshake(other.path()).await;
+        assert_eq!(response["error"]["code"], "no_project_root");
+
+        let nested = allowed.path().join("module");
+        std::fs::create_dir(&nested).unwrap();
+        let response = client.handshake(&nested).await;
+        assert!(response["result"]["project_root"]
+            .as_str()
+            .unwrap()
+            .ends_with("module"));
+    }
+
+    #[tokio::test]
+    async fn test_capability_flags_and_chat() {
+        let project = tempfile::tempdir().unwrap();
+        let server = server(
+            IdeCapabilities {
+                completion: false,
+                ..IdeCapabilities::default()
+            },
+            Vec::new(),
+        );
+        let mut client = Client::connect(&server);
+        client.handshake(project.path()).await;
+
+        let response = client
+            .request(
+                "completion",
+                json!({
+                    "file_path": "main.rs",
+                    "language": "rust",
+                    "position": { "line": 0, "character": 0 },
+                    "context": "",
+                }),
+            )
+            .await;
+        assert_eq!(response["error"]["code"], "capability_disabled");
+
+        let response = client
+            .request(
+                "chat",
+                json!({ "message": "explain", "file_path": "main.rs", "source": "fn main() {}" }),
+            )
+            .await;
+        let content = response["result"]["content"].as_str().unwrap();
+        assert!(content.starts_with("explain (file://"));
+        assert!(content.ends_with("main.rs)"));
+
+        let response = client.request("chat", json!({ "text": "missing" })).await;
+        assert_eq!(response["error"]["code"], "invalid_request");
+    }
+}
@@ -1,7 +1,7 @@
 //! IDE Integration for RiceCoder
 //!
 //! This crate provides IDE integration for RiceCoder, enabling seamless integration with
-//! popular IDEs and editors (VS Code, vim, neovim, emacs). It implements an LSP-first
+//! popular IDEs and editors (VS Code, JetBrains, vim, neovim, emacs). It implements an LSP-first
 //! provider chain that queries external LSP servers for semantic intelligence and falls
 //! back through configured rules, built-in providers, and generic features.
 //!
@@ -16,7 +16,7 @@
 //! # Configuration
 //!
 //! IDE integration is configured through YAML/JSON files with support for:
-//! - IDE-specific settings (VS Code, vim, neovim, emacs)
+//! - IDE-specific settings (VS Code, JetBrains, vim, neovim, emacs)
 //! - Provider chain configuration
 //! - LSP server configuration
 //! - Custom IDE rules
@@ -53,14 +53,17 @@ pub mod external_lsp_provider;
 pub mod generic_provider;
 pub mod hot_reload;
 pub mod ide_config_applicator;
+pub mod jetbrains_server;
 pub mod lsp_monitor;
 pub mod manager;
+pub mod neovim;
 pub mod provider;
 pub mod provider_chain;
 pub mod provider_error_handling;
 pub mod response_formatter;
 pub mod themes;
 pub mod types;
+pub mod vscode_bridge;
 
 pub use builtin_provider::{PythonProvider, RustProvider, TypeScriptProvider};
 pub use config::ConfigManager;
@@ -76,10 +79,15 @@ pub use external_lsp_provider::ExternalLspProvider;
 pub use generic_provider::GenericProvider;
 pub use hot_reload::{ConfigChangeCallback, HotReloadManager, ProviderAvailabilityCallback};
 pub use ide_config_applicator::{IdeConfigApplicator, IdeSpecificSettings, IdeType};
-pub use lsp_monitor::{LspHealthStatus, LspMonitor};
+pub use jetbrains_server::{HandshakeParams, HandshakeResult, JetBrainsServer, ProtocolError};
+pub use lsp_monitor::{LspHealthStatus, LspMonitor, ProviderStats};
 pub use manager::IdeIntegrationManager;
+pub use neovim::{GhostText, MsgpackValue, NeovimBuffer, NeovimConnection, NeovimProvider};
 pub use provider::{IdeProvider, ProviderChain};
-pub use provider_chain::{ProviderChainManager, ProviderRegistry};
+pub use provider_chain::{
+    ChainEntry, DemotionReason, FailoverPolicy, ProviderChainDiagnostics, ProviderChainManager,
+    ProviderDiagnostics, ProviderRegistry, ProviderTier,
+};
 pub use provider_error_handling::{ProviderErrorContext, ProviderErrorHandler, RecoveryStrategy};
 pub use response_formatter::ResponseFormatter;
 pub use themes::{
@@ -87,3 +95,7 @@ pub use themes::{
     ThemeManager, ThemeRegistry, ThemeResetManager,
 };
 pub use types::*;
+pub use vscode_bridge::{
+    BridgeConnection, BridgeHandler, ChatParams, ChatReply, CodeAction, ContentChange,
+    DocumentStore, SessionStatus, TextDocument, TextEdit, VsCodeBridge, WorkspaceEdit,
+};
@@ -2,10 +2,13 @@
 //!
 //! This module monitors external LSP server availability and detects when servers
 //! become available or unavailable. It supports periodic health checks and automatic
-//! provider switching based on availability changes.
+//! provider switching based on availability changes. It also records per-provider
+//! request latency and success statistics that the provider chain uses to order
+//! providers adaptively.
 
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
+use serde::Serialize;
 use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
 
@@ -28,6 +31,78 @@ pub enum LspHealthStatus {
     Unknown,
 }
 
+/// Number of recent request latencies kept per provider
+pub const RECENT_LATENCY_WINDOW: usize = 16;
+
+/// Request statistics recorded for a single provider
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ProviderStats {
+    /// Total number of requests served
+    pub requests: u64,
+    /// Number of requests that failed
+    pub failures: u64,
+    /// Failures since the last successful request
+    pub consecutive_failures: u32,
+    /// Sum of all request latencies in milliseconds
+    pub total_latency_ms: u64,
+    /// Most recent latencies in milliseconds, oldest first
+    pub recent_latencies_ms: Vec<u64>,
+    /// Error message of the most recent failure
+    pub last_error: Option<String>,
+}
+
+impl ProviderStats {
+    /// Fraction of requests that succeeded (1.0 when no requests were made)
+    pub fn success_rate(&self) -> f64 {
+        if self.requests == 0 {
+            return 1.0;
+        }
+        (self.requests - self.failures) as f64 / self.requests as f64
+    }
+
+    /// Average latency over all requests
+    pub fn average_latency_ms(&self) -> Option<u64> {
+        if self.requests == 0 {
+            return None;
+        }
+        Some(self.total_latency_ms / self.requests)
+    }
+
+    /// Latency of the most recent request
+    pub fn last_latency_ms(&self) -> Option<u64> {
+        self.recent_latencies_ms.last().copied()
+    }
+
+    /// Number of most recent requests in a row that exceeded the budget
+    pub fn consecutive_over_budget(&self, budget: Duration) -> u32 {
+        let budget_ms = budget.as_millis() as u64;
+        self.recent_latencies_ms
+            .iter()
+            .rev()
+            .take_while(|latency| **latency > budget_ms)
+            .count() as u32
+    }
+
+    fn record(&mut self, latency: Duration, error: Option<String>) {
+        let latency_ms = latency.as_millis() as u64;
+        self.requests += 1;
+        self.total_latency_ms += latency_ms;
+        self.recent_latencies_ms.push(latency_ms);
+        if self.recent_latencies_ms.len() > RECENT_LATENCY_WINDOW {
+            self.recent_latencies_ms.remove(0);
+        }
+
+        match error {
+            Some(error) => {
+                self.failures += 1;
+                self.consecutive_failures += 1;
+                self.last_error = Some(error);
+            }
+            None => self.consecutive_failures = 0,
+        }
+    }
+}
+
 /// LSP server availability monitor
 pub struct LspMonitor {
     /// Server configurations by language
@@ -36,6 +111,8 @@ pub struct LspMonitor {
     health_status: Arc<RwLock<HashMap<String, LspHealthStatus>>>,
     /// Availability change callbacks
     availability_callbacks: Arc<RwLock<Vec<AvailabilityCallback>>>,
+    /// Request statistics by provider name
+    provider_stats: Arc<RwLock<HashMap<String, ProviderStats>>>,
 }
 
 impl LspMonitor {
@@ -50,6 +127,7 @@ impl LspMonitor {
             servers,
             health_status: Arc::new(RwLock::new(health_status)),
             availability_callbacks: Arc::new(RwLock::new(Vec::new())),
+            provider_stats: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
@@ -223,6 +301,38 @@ impl LspMonitor {
     pub fn available_languages(&self) -> Vec<String> {
         self.servers.keys().cloned().collect()
     }
+
+    /// Record the outcome of a request served by a provider
+    ///
+    /// Returns the provider's updated statistics.
+    pub async fn record_request(
+        &self,
+        provider: &str,
+        latency: Duration,
+        error: Option<String>,
+    ) -> ProviderStats {
+        let mut stats = self.provider_stats.write().await;
+        let entry = stats.entry(provider.to_string()).or_default();
+        entry.record(latency, error);
+        entry.clone()
+    }
+
+    /// Get the request statistics of a provider
+    pub async fn provider_stats(&self, provider: &str) -> Option<ProviderStats> {
+        let stats = self.provider_stats.read().await;
+        stats.get(provider).cloned()
+    }
+
+    /// Get the request statistics of all providers
+    pub async fn all_provider_stats(&self) -> HashMap<String, ProviderStats> {
+        self.provider_stats.read().await.clone()
+    }
+
+    /// Clear the request statistics of a provider
+    pub async fn reset_provider_stats(&self, provider: &str) {
+        let mut stats = self.provider_stats.write().await;
+        stats.remove(provider);
+    }
 }
 
 #[cfg(test)]
@@ -281,6 +391,41 @@ mod tests {
         assert_eq!(results.len(), 2);
     }
 
+    #[tokio::test]
+    async fn test_record_provider_requests() {
+        let monitor = LspMonitor::new(HashMap::new());
+        monitor
+            .record_request("rust-analyzer", Duration::from_millis(10), None)
+            .await;
+        monitor
+            .record_request(
+                "rust-analyzer",
+                Duration::from_millis(300),
+                Some("timeout".to_string()),
+            )
+            .await;
+        let stats = monitor
+            .record_request(
+                "rust-analyzer",
+                Duration::from_millis(500),
+                Some("timeout".to_string()),
+            )
+            .await;
+
+        assert_eq!(stats.requests, 3);
+        assert_eq!(stats.failures, 2);
+        assert_eq!(stats.consecutive_failures, 2);
+        assert_eq!(stats.average_latency_ms(), Some(270));
+        assert_eq!(stats.last_latency_ms(), Some(500));
+        assert_eq!(stats.consecutive_over_budget(Duration::from_millis(100)), 2);
+        assert_eq!(stats.last_error.as_deref(), Some("timeout"));
+        assert!((stats.success_rate() - 1.0 / 3.0).abs() < f64::EPSILON);
+        assert_eq!(monitor.provider_stats("rust-analyzer").await, Some(stats));
+
+        monitor.reset_provider_stats("rust-analyzer").await;
+        assert!(monitor.provider_stats("rust-analyzer").await.is_none());
+    }
+
     #[tokio::test]
     async fn test_check_nonexistent_server() {
         let servers = HashMap::new();
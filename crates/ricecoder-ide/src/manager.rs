@@ -150,6 +150,24 @@ impl IdeIntegrationManager {
                     ))
                 }
             }
+            "jetbrains" => {
+                if let Some(jetbrains_config) = &self.config.jetbrains {
+                    if !jetbrains_config.enabled {
+                        return Err(IdeError::communication_error(
+                            "JetBrains integration is not enabled in configuration",
+                        ));
+                    }
+                    info!(
+                        "JetBrains connection established on port {}",
+                        jetbrains_config.port
+                    );
+                    Ok(())
+                } else {
+                    Err(IdeError::communication_error(
+                        "JetBrains configuration not found",
+                    ))
+                }
+            }
             "vim" | "neovim" => {
                 if let Some(terminal_config) = &self.config.terminal {
                     if let Some(vim_config) = &terminal_config.vim {
@@ -225,6 +243,7 @@ mod tests {
                 features: vec!["completion".to_string()],
                 settings: serde_json::json!({}),
             }),
+            jetbrains: None,
             terminal: Some(TerminalConfig {
                 vim: Some(VimConfig {
                     enabled: true,
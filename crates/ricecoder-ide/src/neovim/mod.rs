@@ -0,0 +1,27 @@
+//! Neovim integration
+//!
+//! ricecoder runs as a msgpack-RPC peer of Neovim, either over stdio (started
+//! with `jobstart(..., {'rpc': v:true})`) or over a socket. Once connected it:
+//!
+//! - defines the `:RiceChat` and `:RiceRefactor` commands and the autocommands
+//!   that drive document sync and ghost text
+//! - attaches to buffers with `nvim_buf_attach` and keeps their contents in sync
+//!   from `nvim_buf_lines_event` notifications
+//! - pushes completions from the provider chain as inline ghost text extmarks,
+//!   which the `ricecoder_accept` request inserts into the buffer
+//!
+//! # Example
+//!
+//! ```ignore
+//! use std::sync::Arc;
+//! use ricecoder_ide::neovim::NeovimProvider;
+//!
+//! let provider = Arc::new(NeovimProvider::new(manager, handler));
+//! provider.run_stdio().await?;
+//! ```
+
+pub mod msgpack;
+pub mod provider;
+
+pub use msgpack::MsgpackValue;
+pub use provider::{GhostText, NeovimBuffer, NeovimConnection, NeovimProvider};
@@ -0,0 +1,448 @@
+//! Minimal MessagePack codec for Neovim's msgpack-RPC
+//!
+//! Covers the subset of the MessagePack spec Neovim uses: nil, booleans,
+//! integers, floats, strings, binary, arrays, maps and extension types (Neovim
+//! sends buffer, window and tabpage handles as extension types).
+
+use crate::error::{IdeError, IdeResult};
+
+/// A MessagePack value
+#[derive(Debug, Clone, PartialEq)]
+pub enum MsgpackValue {
+    /// nil
+    Nil,
+    /// Boolean
+    Bool(bool),
+    /// Integer
+    Integer(i64),
+    /// Float
+    Float(f64),
+    /// UTF-8 string
+    String(String),
+    /// Binary data
+    Binary(Vec<u8>),
+    /// Array
+    Array(Vec<MsgpackValue>),
+    /// Map, in wire order
+    Map(Vec<(MsgpackValue, MsgpackValue)>),
+    /// Extension type with its raw payload
+    Ext(i8, Vec<u8>),
+}
+
+impl MsgpackValue {
+    /// Integer value, if this is an integer
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            MsgpackValue::Integer(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// String value, if this is a string (or UTF-8 binary)
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            MsgpackValue::String(value) => Some(value),
+            MsgpackValue::Binary(bytes) => std::str::from_utf8(bytes).ok(),
+            _ => None,
+        }
+    }
+
+    /// Boolean value, if this is a boolean
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            MsgpackValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Array items, if this is an array
+    pub fn as_array(&self) -> Option<&[MsgpackValue]> {
+        match self {
+            MsgpackValue::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// Neovim object handle (buffer, window, tabpage)
+    ///
+    /// Handles arrive as extension types wrapping an integer when Neovim
+    /// sends them, and as plain integers when sent from Vimscript or Lua.
+    pub fn as_handle(&self) -> Option<i64> {
+        match self {
+            MsgpackValue::Integer(value) => Some(*value),
+            MsgpackValue::Ext(_, payload) => match decode(payload) {
+                Ok(Some((MsgpackValue::Integer(value), _))) => Some(value),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Look up a string key in a map
+    pub fn get(&self, key: &str) -> Option<&MsgpackValue> {
+        match self {
+            MsgpackValue::Map(entries) => entries
+                .iter()
+                .find(|(k, _)| k.as_str() == Some(key))
+                .map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    /// Encode this value, appending to `buf`
+    pub fn encode(&self, buf: &mut Vec<u8>) {
+        match self {
+            MsgpackValue::Nil => buf.push(0xc0),
+            MsgpackValue::Bool(false) => buf.push(0xc2),
+            MsgpackValue::Bool(true) => buf.push(0xc3),
+            MsgpackValue::Integer(value) => encode_integer(*value, buf),
+            MsgpackValue::Float(value) => {
+                buf.push(0xcb);
+                buf.extend_from_slice(&value.to_be_bytes());
+            }
+            MsgpackValue::String(value) => {
+                let len = value.len();
+                if len < 32 {
+                    buf.push(0xa0 | len as u8);
+                } else {
+                    encode_length(len, [0xd9, 0xda, 0xdb], buf);
+                }
+                buf.extend_from_slice(value.as_bytes());
+            }
+            MsgpackValue::Binary(bytes) => {
+                encode_length(bytes.len(), [0xc4, 0xc5, 0xc6], buf);
+                buf.extend_from_slice(bytes);
+            }
+            MsgpackValue::Array(items) => {
+                let len = items.len();
+                if len < 16 {
+                    buf.push(0x90 | len as u8);
+                } else {
+                    encode_collection_length(len, [0xdc, 0xdd], buf);
+                }
+                for item in items {
+                    item.encode(buf);
+                }
+            }
+            MsgpackValue::Map(entries) => {
+                let len = entries.len();
+                if len < 16 {
+                    buf.push(0x80 | len as u8);
+                } else {
+                    encode_collection_length(len, [0xde, 0xdf], buf);
+                }
+                for (key, value) in entries {
+                    key.encode(buf);
+                    value.encode(buf);
+                }
+            }
+            MsgpackValue::Ext(kind, payload) => {
+                match payload.len() {
+                    1 => buf.push(0xd4),
+                    2 => buf.push(0xd5),
+                    4 => buf.push(0xd6),
+                    8 => buf.push(0xd7),
+                    16 => buf.push(0xd8),
+                    len => encode_length(len, [0xc7, 0xc8, 0xc9], buf),
+                }
+                buf.push(*kind as u8);
+                buf.extend_from_slice(payload);
+            }
+        }
+    }
+}
+
+impl From<&str> for MsgpackValue {
+    fn from(value: &str) -> Self {
+        MsgpackValue::String(value.to_string())
+    }
+}
+
+impl From<String> for MsgpackValue {
+    fn from(value: String) -> Self {
+        MsgpackValue::String(value)
+    }
+}
+
+impl From<i64> for MsgpackValue {
+    fn from(value: i64) -> Self {
+        MsgpackValue::Integer(value)
+    }
+}
+
+impl From<bool> for MsgpackValue {
+    fn from(value: bool) -> Self {
+        MsgpackValue::Bool(value)
+    }
+}
+
+impl From<Vec<MsgpackValue>> for MsgpackValue {
+    fn from(value: Vec<MsgpackValue>) -> Self {
+        MsgpackValue::Array(value)
+    }
+}
+
+fn encode_integer(value: i64, buf: &mut Vec<u8>) {
+    if (0..128).contains(&value) {
+        buf.push(value as u8);
+    } else if (-32..0).contains(&value) {
+        buf.push(value as i8 as u8);
+    } else if value >= 0 {
+        if value <= u8::MAX as i64 {
+            buf.push(0xcc);
+            buf.push(value as u8);
+        } else if value <= u16::MAX as i64 {
+            buf.push(0xcd);
+            buf.extend_from_slice(&(value as u16).to_be_bytes());
+        } else if value <= u32::MAX as i64 {
+            buf.push(0xce);
+            buf.extend_from_slice(&(value as u32).to_be_bytes());
+        } else {
+            buf.push(0xcf);
+            buf.extend_from_slice(&(value as u64).to_be_bytes());
+        }
+    } else if value >= i8::MIN as i64 {
+        buf.push(0xd0);
+        buf.push(value as i8 as u8);
+    } else if value >= i16::MIN as i64 {
+        buf.push(0xd1);
+        buf.extend_from_slice(&(value as i16).to_be_bytes());
+    } else if value >= i32::MIN as i64 {
+        buf.push(0xd2);
+        buf.extend_from_slice(&(value as i32).to_be_bytes());
+    } else {
+        buf.push(0xd3);
+        buf.extend_from_slice(&value.to_be_bytes());
+    }
+}
+
+/// Encode a byte length using the 8, 16 or 32 bit marker
+fn encode_length(len: usize, markers: [u8; 3], buf: &mut Vec<u8>) {
+    if len <= u8::MAX as usize {
+        buf.push(markers[0]);
+        buf.push(len as u8);
+    } else if len <= u16::MAX as usize {
+        buf.push(markers[1]);
+        buf.extend_from_slice(&(len as u16).to_be_bytes());
+    } else {
+        buf.push(markers[2]);
+        buf.extend_from_slice(&(len as u32).to_be_bytes());
+    }
+}
+
+/// Encode an element count using the 16 or 32 bit marker
+fn encode_collection_length(len: usize, markers: [u8; 2], buf: &mut Vec<u8>) {
+    if len <= u16::MAX as usize {
+        buf.push(markers[0]);
+        buf.extend_from_slice(&(len as u16).to_be_bytes());
+    } else {
+        buf.push(markers[1]);
+        buf.extend_from_slice(&(len as u32).to_be_bytes());
+    }
+}
+
+/// Decode one value from the start of `buf`
+///
+/// Returns the value and the number of bytes consumed, or `None` if `buf`
+/// does not yet hold a complete value.
+pub fn decode(buf: &[u8]) -> IdeResult<Option<(MsgpackValue, usize)>> {
+    let mut decoder = Decoder { buf, pos: 0 };
+    match decoder.value() {
+        Ok(value) => Ok(Some((value, decoder.pos))),
+        Err(DecodeError::Incomplete) => Ok(None),
+        Err(DecodeError::Invalid(message)) => Err(IdeError::communication_error(format!(
+            "Invalid msgpack data: {}",
+            message
+        ))),
+    }
+}
+
+enum DecodeError {
+    Incomplete,
+    Invalid(String),
+}
+
+struct Decoder<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl Decoder<'_> {
+    fn take(&mut self, len: usize) -> Result<&[u8], DecodeError> {
+        let end = self.pos.checked_add(len).ok_or(DecodeError::Incomplete)?;
+        let bytes = self.buf.get(self.pos..end).ok_or(DecodeError::Incomplete)?;
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn byte(&mut self) -> Result<u8, DecodeError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn uint(&mut self, size: usize) -> Result<u64, DecodeError> {
+        Ok(self
+            .take(size)?
+            .iter()
+            .fold(0u64, |acc, byte| (acc << 8) | *byte as u64))
+    }
+
+    fn int(&mut self, size: usize) -> Result<i64, DecodeError> {
+        let value = self.uint(size)?;
+        let shift = 64 - size * 8;
+        Ok(((value << shift) as i64) >> shift)
+    }
+
+    fn string(&mut self, len: usize) -> Result<MsgpackValue, DecodeError> {
+        let bytes = self.take(len)?.to_vec();
+        String::from_utf8(bytes)
+            .map(MsgpackValue::String)
+            .map_err(|e| DecodeError::Invalid(e.to_string()))
+    }
+
+    fn array(&mut self, len: usize) -> Result<MsgpackValue, DecodeError> {
+        let mut items = Vec::with_capacity(len.min(1024));
+        for _ in 0..len {
+            items.push(self.value()?);
+        }
+        Ok(MsgpackValue::Array(items))
+    }
+
+    fn map(&mut self, len: usize) -> Result<MsgpackValue, DecodeError> {
+        let mut entries = Vec::with_capacity(len.min(1024));
+        for _ in 0..len {
+            let key = self.value()?;
+            let value = self.value()?;
+            entries.push((key, value));
+        }
+        Ok(MsgpackValue::Map(entries))
+    }
+
+    fn ext(&mut self, len: usize) -> Result<MsgpackValue, DecodeError> {
+        let kind = self.byte()? as i8;
+        Ok(MsgpackValue::Ext(kind, self.take(len)?.to_vec()))
+    }
+
+    fn value(&mut self) -> Result<MsgpackValue, DecodeError> {
+        let marker = self.byte()?;
+        match marker {
+            0x00..=0x7f => Ok(MsgpackValue::Integer(marker as i64)),
+            0x80..=0x8f => self.map((marker & 0x0f) as usize),
+            0x90..=0x9f => self.array((marker & 0x0f) as usize),
+            0xa0..=0xbf => self.string((marker & 0x1f) as usize),
+            0xc0 => Ok(MsgpackValue::Nil),
+            0xc2 => Ok(MsgpackValue::Bool(false)),
+            0xc3 => Ok(MsgpackValue::Bool(true)),
+            0xc4..=0xc6 => {
+                let len = self.uint(1 << (marker - 0xc4))? as usize;
+                Ok(MsgpackValue::Binary(self.take(len)?.to_vec()))
+            }
+            0xc7..=0xc9 => {
+                let len = self.uint(1 << (marker - 0xc7))? as usize;
+                self.ext(len)
+            }
+            0xca => {
+                let bits = self.uint(4)? as u32;
+                Ok(MsgpackValue::Float(f32::from_bits(bits) as f64))
+            }
+            0xcb => Ok(MsgpackValue::Float(f64::from_bits(self.uint(8)?))),
+            0xcc..=0xcf => {
+                let value = self.uint(1 << (marker - 0xcc))?;
+                i64::try_from(value)
+                    .map(MsgpackValue::Integer)
+                    .map_err(|_| DecodeError::Invalid(format!("integer {} out of range", value)))
+            }
+            0xd0..=0xd3 => Ok(MsgpackValue::Integer(self.int(1 << (marker - 0xd0))?)),
+            0xd4..=0xd8 => self.ext(1 << (marker - 0xd4)),
+            0xd9..=0xdb => {
+                let len = self.uint(1 << (marker - 0xd9))? as usize;
+                self.string(len)
+            }
+            0xdc | 0xdd => {
+                let len = self.uint(if marker == 0xdc { 2 } else { 4 })? as usize;
+                self.array(len)
+            }
+            0xde | 0xdf => {
+                let len = self.uint(if marker == 0xde { 2 } else { 4 })? as usize;
+                self.map(len)
+            }
+            0xe0..=0xff => Ok(MsgpackValue::Integer(marker as i8 as i64)),
+            0xc1 => Err(DecodeError::Invalid("reserved marker 0xc1".to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(value: MsgpackValue) {
+        let mut buf = Vec::new();
+        value.encode(&mut buf);
+        let (decoded, used) = decode(&buf).unwrap().unwrap();
+        assert_eq!(decoded, value);
+        assert_eq!(used, buf.len());
+        // Every strict prefix is incomplete
+        assert!(decode(&buf[..buf.len() - 1]).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_round_trip_scalars() {
+        for value in [
+            0,
+            1,
+            127,
+            128,
+            255,
+            256,
+            65_536,
+            u32::MAX as i64 + 1,
+            -1,
+            -32,
+            -33,
+            -129,
+            -40_000,
+            i64::MIN,
+            i64::MAX,
+        ] {
+            round_trip(MsgpackValue::Integer(value));
+        }
+        round_trip(MsgpackValue::Nil);
+        round_trip(MsgpackValue::Bool(true));
+        round_trip(MsgpackValue::Float(1.5));
+        round_trip(MsgpackValue::from("ghost"));
+        round_trip(MsgpackValue::from("x".repeat(300)));
+        round_trip(MsgpackValue::Binary(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn test_round_trip_collections() {
+        round_trip(MsgpackValue::Array(
+            (0..20).map(MsgpackValue::Integer).collect(),
+        ));
+        round_trip(MsgpackValue::Map(vec![
+            (MsgpackValue::from("id"), MsgpackValue::Integer(1)),
+            (
+                MsgpackValue::from("virt_text"),
+                MsgpackValue::Array(vec![MsgpackValue::from("text")]),
+            ),
+        ]));
+        round_trip(MsgpackValue::Ext(0, vec![5]));
+        round_trip(MsgpackValue::Ext(1, vec![0xcd, 0x01, 0x00]));
+    }
+
+    #[test]
+    fn test_handles() {
+        assert_eq!(MsgpackValue::Integer(3).as_handle(), Some(3));
+        assert_eq!(MsgpackValue::Ext(0, vec![0x07]).as_handle(), Some(7));
+        assert_eq!(
+            MsgpackValue::Ext(0, vec![0xcd, 0x01, 0x00]).as_handle(),
+            Some(256)
+        );
+        assert_eq!(MsgpackValue::Nil.as_handle(), None);
+    }
+
+    #[test]
+    fn test_decode_rejects_reserved_marker() {
+        assert!(decode(&[0xc1]).is_err());
+    }
+}
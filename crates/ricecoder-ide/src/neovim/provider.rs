@@ -0,0 +1,1189 @@
+//! Neovim msgpack-RPC provider
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    sync::{oneshot, Mutex, RwLock},
+};
+use tracing::{debug, info, warn};
+
+use super::msgpack::{decode, MsgpackValue};
+use crate::{
+    error::{IdeError, IdeResult},
+    manager::IdeIntegrationManager,
+    types::*,
+    vscode_bridge::{BridgeHandler, ChatParams, TextDocument, TextEdit},
+};
+
+/// msgpack-RPC message types
+const REQUEST: i64 = 0;
+const RESPONSE: i64 = 1;
+const NOTIFICATION: i64 = 2;
+
+/// Extmark ID used for a buffer's ghost text
+const GHOST_MARK_ID: i64 = 1;
+
+/// Highlight group for ghost text
+const GHOST_HIGHLIGHT: &str = "Comment";
+
+/// Default insert-mode key that accepts ghost text
+const DEFAULT_ACCEPT_KEY: &str = "<C-l>";
+
+/// A Neovim buffer synced through `nvim_buf_attach`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NeovimBuffer {
+    /// Buffer handle
+    pub handle: i64,
+    /// Full path of the buffer
+    pub name: String,
+    /// Neovim filetype
+    pub filetype: String,
+    /// Last `b:changedtick` seen
+    pub changedtick: i64,
+    /// Buffer lines
+    pub lines: Vec<String>,
+}
+
+impl NeovimBuffer {
+    /// Create an empty buffer
+    pub fn new(handle: i64, name: String, filetype: String) -> Self {
+        NeovimBuffer {
+            handle,
+            name,
+            filetype,
+            changedtick: 0,
+            lines: Vec::new(),
+        }
+    }
+
+    /// Full buffer text
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    /// Apply an `nvim_buf_lines_event`: replace lines `first..last` with `lines`
+    ///
+    /// A `last` of -1 replaces everything from `first` to the end.
+    pub fn apply_lines(&mut self, first: i64, last: i64, lines: Vec<String>) {
+        let len = self.lines.len();
+        let first = (first.max(0) as usize).min(len);
+        let last = if last < 0 {
+            len
+        } else {
+            (last as usize).clamp(first, len)
+        };
+        self.lines.splice(first..last, lines);
+    }
+
+    fn language(&self) -> String {
+        if self.filetype.is_empty() {
+            "text".to_string()
+        } else {
+            self.filetype.clone()
+        }
+    }
+
+    fn uri(&self) -> String {
+        format!("file://{}", self.name)
+    }
+
+    fn document(&self) -> TextDocument {
+        TextDocument {
+            uri: self.uri(),
+            language_id: self.filetype.clone(),
+            version: self.changedtick,
+            text: self.text(),
+        }
+    }
+
+    /// Byte column of a UTF-16 character offset in a line
+    fn byte_col(&self, row: u32, character: u32) -> usize {
+        let Some(line) = self.lines.get(row as usize) else {
+            return 0;
+        };
+        let mut units = 0;
+        for (index, ch) in line.char_indices() {
+            if units >= character {
+                return index;
+            }
+            units += ch.len_utf16() as u32;
+        }
+        line.len()
+    }
+}
+
+/// Ghost text shown at a cursor position
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GhostText {
+    /// 0-based row
+    pub row: i64,
+    /// 0-based byte column
+    pub col: i64,
+    /// Text that accepting the ghost text inserts
+    pub text: String,
+}
+
+type PendingRequests =
+    Arc<Mutex<HashMap<u64, oneshot::Sender<Result<MsgpackValue, MsgpackValue>>>>>;
+
+/// msgpack-RPC connection to Neovim
+#[derive(Clone)]
+pub struct NeovimConnection {
+    writer: Arc<Mutex<Box<dyn AsyncWrite + Send + Unpin>>>,
+    pending: PendingRequests,
+    next_id: Arc<AtomicU64>,
+}
+
+impl NeovimConnection {
+    fn new(writer: Box<dyn AsyncWrite + Send + Unpin>) -> Self {
+        NeovimConnection {
+            writer: Arc::new(Mutex::new(writer)),
+            pending: Arc::new(Mutex::new(HashMap::new())),
+            next_id: Arc::new(AtomicU64::new(1)),
+        }
+    }
+
+    /// Call a Neovim API function and wait for its result
+    pub async fn request(
+        &self,
+        method: &str,
+        params: Vec<MsgpackValue>,
+    ) -> IdeResult<MsgpackValue> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let (sender, receiver) = oneshot::channel();
+        self.pending.lock().await.insert(id, sender);
+
+        let message = MsgpackValue::Array(vec![
+            REQUEST.into(),
+            (id as i64).into(),
+            method.into(),
+            params.into(),
+        ]);
+        if let Err(e) = self.send(&message).await {
+            self.pending.lock().await.remove(&id);
+            return Err(e);
+        }
+
+        match receiver.await {
+            Ok(Ok(result)) => Ok(result),
+            Ok(Err(error)) => Err(IdeError::communication_error(format!(
+                "{} failed: {:?}",
+                method, error
+            ))),
+            Err(_) => Err(IdeError::communication_error("Neovim connection closed")),
+        }
+    }
+
+    /// Send a notification to Neovim
+    pub async fn notify(&self, method: &str, params: Vec<MsgpackValue>) -> IdeResult<()> {
+        self.send(&MsgpackValue::Array(vec![
+            NOTIFICATION.into(),
+            method.into(),
+            params.into(),
+        ]))
+        .await
+    }
+
+    async fn respond(&self, id: MsgpackValue, result: IdeResult<MsgpackValue>) -> IdeResult<()> {
+        let (error, result) = match result {
+            Ok(result) => (MsgpackValue::Nil, result),
+            Err(e) => (MsgpackValue::String(e.to_string()), MsgpackValue::Nil),
+        };
+        self.send(&MsgpackValue::Array(vec![
+            RESPONSE.into(),
+            id,
+            error,
+            result,
+        ]))
+        .await
+    }
+
+    async fn send(&self, message: &MsgpackValue) -> IdeResult<()> {
+        let mut buf = Vec::new();
+        message.encode(&mut buf);
+        let mut writer = self.writer.lock().await;
+        writer.write_all(&buf).await?;
+        writer.flush().await?;
+        Ok(())
+    }
+}
+
+/// Neovim integration backed by the provider chain
+pub struct NeovimProvider {
+    manager: Arc<IdeIntegrationManager>,
+    handler: Arc<dyn BridgeHandler>,
+    accept_key: String,
+    namespace: RwLock<Option<i64>>,
+    buffers: RwLock<HashMap<i64, NeovimBuffer>>,
+    ghosts: RwLock<HashMap<i64, GhostText>>,
+}
+
+impl NeovimProvider {
+    /// Create a new Neovim provider
+    pub fn new(manager: Arc<IdeIntegrationManager>, handler: Arc<dyn BridgeHandler>) -> Self {
+        NeovimProvider {
+            manager,
+            handler,
+            accept_key: DEFAULT_ACCEPT_KEY.to_string(),
+            namespace: RwLock::new(None),
+            buffers: RwLock::new(HashMap::new()),
+            ghosts: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Set the insert-mode key that accepts ghost text
+    pub fn with_accept_key(mut self, key: impl Into<String>) -> Self {
+        self.accept_key = key.into();
+        self
+    }
+
+    /// Get a synced buffer
+    pub async fn buffer(&self, handle: i64) -> Option<NeovimBuffer> {
+        self.buffers.read().await.get(&handle).cloned()
+    }
+
+    /// Get the ghost text shown in a buffer
+    pub async fn ghost_text(&self, handle: i64) -> Option<GhostText> {
+        self.ghosts.read().await.get(&handle).cloned()
+    }
+
+    /// Serve Neovim over stdio, as started by `jobstart(..., {'rpc': v:true})`
+    pub async fn run_stdio(self: Arc<Self>) -> IdeResult<()> {
+        self.run(tokio::io::stdin(), tokio::io::stdout()).await
+    }
+
+    /// Connect to a Neovim instance listening on a socket (`nvim --listen`)
+    ///
+    /// Addresses containing `:` are TCP addresses, anything else is a Unix
+    /// socket path.
+    pub async fn run_socket(self: Arc<Self>, address: &str) -> IdeResult<()> {
+        #[cfg(unix)]
+        if !address.contains(':') {
+            let stream = tokio::net::UnixStream::connect(address).await?;
+            let (reader, writer) = stream.into_split();
+            return self.run(reader, writer).await;
+        }
+
+        let stream = tokio::net::TcpStream::connect(address).await?;
+        let (reader, writer) = stream.into_split();
+        self.run(reader, writer).await
+    }
+
+    /// Serve a Neovim connection until it closes
+    pub async fn run<R, W>(self: Arc<Self>, mut reader: R, writer: W) -> IdeResult<()>
+    where
+        R: AsyncRead + Unpin,
+        W: AsyncWrite + Send + Unpin + 'static,
+    {
+        let connection = NeovimConnection::new(Box::new(writer));
+
+        let provider = self.clone();
+        let setup_connection = connection.clone();
+        tokio::spawn(async move {
+            if let Err(e) = provider.setup(&setup_connection).await {
+                warn!("Neovim setup failed: {}", e);
+            }
+        });
+
+        let mut buf = Vec::new();
+        let mut chunk = [0u8; 8192];
+        loop {
+            while let Some((message, used)) = decode(&buf)? {
+                buf.drain(..used);
+                self.dispatch(&connection, message).await;
+            }
+
+            let read = reader.read(&mut chunk).await?;
+            if read == 0 {
+                break;
+            }
+            buf.extend_from_slice(&chunk[..read]);
+        }
+
+        connection.pending.lock().await.clear();
+        info!("Neovim disconnected");
+        Ok(())
+    }
+
+    /// Create the ghost text namespace and define commands and autocommands
+    async fn setup(&self, connection: &NeovimConnection) -> IdeResult<()> {
+        let api_info = connection.request("nvim_get_api_info", Vec::new()).await?;
+        let channel = api_info
+            .as_array()
+            .and_then(|info| info.first())
+            .and_then(MsgpackValue::as_i64)
+            .ok_or_else(|| IdeError::communication_error("Invalid nvim_get_api_info result"))?;
+
+        let namespace = connection
+            .request("nvim_create_namespace", vec!["ricecoder_ghost".into()])
+            .await?
+            .as_i64()
+            .ok_or_else(|| IdeError::communication_error("Invalid namespace ID"))?;
+        *self.namespace.write().await = Some(namespace);
+
+        connection
+            .request(
+                "nvim_exec2",
+                vec![
+                    self.setup_script(channel).into(),
+                    MsgpackValue::Map(vec![("output".into(), false.into())]),
+                ],
+            )
+            .await?;
+
+        info!("Neovim integration ready on channel {}", channel);
+        Ok(())
+    }
+
+    fn setup_script(&self, channel: i64) -> String {
+        [
+            format!(
+                "command! -nargs=+ RiceChat echo rpcrequest({channel}, 'RiceChat', bufnr('%'), <q-args>)"
+            ),
+            format!(
+                "command! -range -nargs=* RiceRefactor echo rpcrequest({channel}, 'RiceRefactor', bufnr('%'), <line1>, <line2>, <q-args>)"
+            ),
+            "augroup ricecoder".to_string(),
+            "autocmd!".to_string(),
+            format!(
+                "autocmd BufEnter * call rpcnotify({channel}, 'ricecoder_attach', bufnr('%'), expand('%:p'), &filetype)"
+            ),
+            format!(
+                "autocmd TextChangedI * call rpcnotify({channel}, 'ricecoder_complete', bufnr('%'), line('.') - 1, col('.') - 1)"
+            ),
+            format!("autocmd InsertLeave * call rpcnotify({channel}, 'ricecoder_clear', bufnr('%'))"),
+            "augroup END".to_string(),
+            format!(
+                "inoremap <silent> {} <Cmd>call rpcrequest({channel}, 'ricecoder_accept', bufnr('%'))<CR>",
+                self.accept_key
+            ),
+            format!(
+                "call rpcnotify({channel}, 'ricecoder_attach', bufnr('%'), expand('%:p'), &filetype)"
+            ),
+        ]
+        .join("\n")
+    }
+
+    async fn dispatch(self: &Arc<Self>, connection: &NeovimConnection, message: MsgpackValue) {
+        let MsgpackValue::Array(mut parts) = message else {
+            debug!("Ignoring non-array msgpack-RPC message");
+            return;
+        };
+
+        match parts.first().and_then(MsgpackValue::as_i64) {
+            Some(REQUEST) if parts.len() == 4 => {
+                let params = args(parts.pop());
+                let method = parts[2].as_str().unwrap_or_default().to_string();
+                let id = parts[1].clone();
+                let provider = self.clone();
+                let connection = connection.clone();
+                tokio::spawn(async move {
+                    let result = provider.handle_request(&connection, &method, params).await;
+                    if let Err(e) = connection.respond(id, result).await {
+                        warn!("Failed to respond to {}: {}", method, e);
+                    }
+                });
+            }
+            Some(RESPONSE) if parts.len() == 4 => {
+                let Some(id) = parts[1].as_i64() else {
+                    return;
+                };
+                if let Some(sender) = connection.pending.lock().await.remove(&(id as u64)) {
+                    let outcome = match &parts[2] {
+                        MsgpackValue::Nil => Ok(parts[3].clone()),
+                        error => Err(error.clone()),
+                    };
+                    let _ = sender.send(outcome);
+                }
+            }
+            Some(NOTIFICATION) if parts.len() == 3 => {
+                let params = args(parts.pop());
+                let method = parts[1].as_str().unwrap_or_default().to_string();
+                self.handle_notification(connection, &method, params).await;
+            }
+            _ => debug!("Ignoring malformed msgpack-RPC message"),
+        }
+    }
+
+    async fn handle_notification(
+        self: &Arc<Self>,
+        connection: &NeovimConnection,
+        method: &str,
+        params: Vec<MsgpackValue>,
+    ) {
+        match method {
+            // Buffer events are applied in order on the read loop
+            "nvim_buf_lines_event" => self.apply_lines_event(&params).await,
+            "nvim_buf_detach_event" => {
+                if let Some(handle) = params.first().and_then(MsgpackValue::as_handle) {
+                    self.buffers.write().await.remove(&handle);
+                    self.ghosts.write().await.remove(&handle);
+                }
+            }
+            "nvim_buf_changedtick_event" => {}
+            // Anything that calls back into Neovim runs in its own task
+            "ricecoder_attach" | "ricecoder_complete" | "ricecoder_clear" => {
+                let provider = self.clone();
+                let connection = connection.clone();
+                let method = method.to_string();
+                tokio::spawn(async move {
+                    let result = match method.as_str() {
+                        "ricecoder_attach" => provider.attach(&connection, &params).await,
+                        "ricecoder_complete" => provider.complete(&connection, &params).await,
+                        _ => match params.first().and_then(MsgpackValue::as_handle) {
+                            Some(handle) => provider.clear_ghost(&connection, handle).await,
+                            None => Ok(()),
+                        },
+                    };
+                    if let Err(e) = result {
+                        warn!("Failed to handle {}: {}", method, e);
+                    }
+                });
+            }
+            _ => debug!("Ignoring Neovim notification: {}", method),
+        }
+    }
+
+    async fn handle_request(
+        &self,
+        connection: &NeovimConnection,
+        method: &str,
+        params: Vec<MsgpackValue>,
+    ) -> IdeResult<MsgpackValue> {
+        let handle = params
+            .first()
+            .and_then(MsgpackValue::as_handle)
+            .ok_or_else(|| IdeError::communication_error("Missing buffer argument"))?;
+
+        match method {
+            "ricecoder_accept" => Ok(self.accept_ghost(connection, handle).await?.into()),
+            "RiceChat" => {
+                let message = params.get(1).and_then(MsgpackValue::as_str).unwrap_or("");
+                Ok(self.chat(connection, handle, message).await?.into())
+            }
+            "RiceRefactor" => {
+                let line1 = params.get(1).and_then(MsgpackValue::as_i64).unwrap_or(1);
+                let line2 = params
+                    .get(2)
+                    .and_then(MsgpackValue::as_i64)
+                    .unwrap_or(line1);
+                let filter = params.get(3).and_then(MsgpackValue::as_str).unwrap_or("");
+                Ok(self
+                    .refactor(connection, handle, line1, line2, filter)
+                    .await?
+                    .into())
+            }
+            _ => Err(IdeError::communication_error(format!(
+                "Unknown method: {}",
+                method
+            ))),
+        }
+    }
+
+    async fn attach(
+        &self,
+        connection: &NeovimConnection,
+        params: &[MsgpackValue],
+    ) -> IdeResult<()> {
+        let handle = params
+            .first()
+            .and_then(MsgpackValue::as_handle)
+            .ok_or_else(|| IdeError::communication_error("Missing buffer argument"))?;
+        let name = params.get(1).and_then(MsgpackValue::as_str).unwrap_or("");
+        let filetype = params.get(2).and_then(MsgpackValue::as_str).unwrap_or("");
+
+        {
+            let mut buffers = self.buffers.write().await;
+            if let Some(buffer) = buffers.get_mut(&handle) {
+                buffer.name = name.to_string();
+                buffer.filetype = filetype.to_string();
+                return Ok(());
+            }
+            buffers.insert(
+                handle,
+                NeovimBuffer::new(handle, name.to_string(), filetype.to_string()),
+            );
+        }
+
+        // send_buffer = true makes Neovim send the whole buffer first
+        let attached = connection
+            .request(
+                "nvim_buf_attach",
+                vec![handle.into(), true.into(), MsgpackValue::Map(Vec::new())],
+            )
+            .await?;
+        if attached.as_bool() != Some(true) {
+            self.buffers.write().await.remove(&handle);
+            return Err(IdeError::communication_error(format!(
+                "Failed to attach to buffer {}",
+                handle
+            )));
+        }
+
+        debug!("Attached to Neovim buffer {} ({})", handle, name);
+        Ok(())
+    }
+
+    async fn apply_lines_event(&self, params: &[MsgpackValue]) {
+        let (Some(handle), Some(first), Some(last), Some(lines)) = (
+            params.first().and_then(MsgpackValue::as_handle),
+            params.get(2).and_then(MsgpackValue::as_i64),
+            params.get(3).and_then(MsgpackValue::as_i64),
+            params.get(4).and_then(MsgpackValue::as_array),
+        ) else {
+            debug!("Ignoring malformed nvim_buf_lines_event");
+            return;
+        };
+
+        let mut buffers = self.buffers.write().await;
+        let Some(buffer) = buffers.get_mut(&handle) else {
+            return;
+        };
+        buffer.apply_lines(
+            first,
+            last,
+            lines
+                .iter()
+                .map(|line| line.as_str().unwrap_or_default().to_string())
+                .collect(),
+        );
+        if let Some(changedtick) = params.get(1).and_then(MsgpackValue::as_i64) {
+            buffer.changedtick = changedtick;
+        }
+    }
+
+    /// Show the best completion for the cursor position as ghost text
+    async fn complete(
+        &self,
+        connection: &NeovimConnection,
+        params: &[MsgpackValue],
+    ) -> IdeResult<()> {
+        let (Some(handle), Some(row), Some(col)) = (
+            params.first().and_then(MsgpackValue::as_handle),
+            params.get(1).and_then(MsgpackValue::as_i64),
+            params.get(2).and_then(MsgpackValue::as_i64),
+        ) else {
+            return Err(IdeError::communication_error(
+                "ricecoder_complete expects buffer, row and column",
+            ));
+        };
+        let Some(buffer) = self.buffer(handle).await else {
+            return Ok(());
+        };
+
+        let line = buffer
+            .lines
+            .get(row as usize)
+            .map(String::as_str)
+            .unwrap_or("");
+        let mut col = (col.max(0) as usize).min(line.len());
+        while !line.is_char_boundary(col) {
+            col -= 1;
+        }
+        let before = &line[..col];
+        let prefix_start = before
+            .char_indices()
+            .rev()
+            .take_while(|(_, c)| c.is_alphanumeric() || *c == '_')
+            .last()
+            .map(|(index, _)| index)
+            .unwrap_or(col);
+        let prefix = &before[prefix_start..];
+        if prefix.is_empty() {
+            return self.clear_ghost(connection, handle).await;
+        }
+
+        let items = self
+            .manager
+            .handle_completion_request(&CompletionParams {
+                language: buffer.language(),
+                file_path: buffer.name.clone(),
+                position: Position {
+                    line: row as u32,
+                    character: col as u32,
+                },
+                context: buffer.text(),
+            })
+            .await?;
+        let Some(item) = items.iter().find(|item| {
+            item.insert_text.starts_with(prefix) && item.insert_text.len() > prefix.len()
+        }) else {
+            return self.clear_ghost(connection, handle).await;
+        };
+
+        let ghost = GhostText {
+            row,
+            col: col as i64,
+            text: item.insert_text[prefix.len()..].to_string(),
+        };
+        self.show_ghost(connection, handle, ghost).await
+    }
+
+    async fn namespace(&self) -> IdeResult<i64> {
+        self.namespace
+            .read()
+            .await
+            .ok_or_else(|| IdeError::communication_error("Neovim integration is not set up"))
+    }
+
+    async fn show_ghost(
+        &self,
+        connection: &NeovimConnection,
+        handle: i64,
+        ghost: GhostText,
+    ) -> IdeResult<()> {
+        let namespace = self.namespace().await?;
+        let chunk = |text: &str| {
+            MsgpackValue::Array(vec![MsgpackValue::Array(vec![
+                text.into(),
+                GHOST_HIGHLIGHT.into(),
+            ])])
+        };
+
+        let mut lines = ghost.text.split('\n');
+        let first = lines.next().unwrap_or_default();
+        let mut options: Vec<(MsgpackValue, MsgpackValue)> = vec![
+            ("id".into(), GHOST_MARK_ID.into()),
+            ("virt_text".into(), chunk(first)),
+            ("virt_text_pos".into(), "inline".into()),
+            ("hl_mode".into(), "combine".into()),
+        ];
+        let rest: Vec<MsgpackValue> = lines.map(chunk).collect();
+        if !rest.is_empty() {
+            options.push(("virt_lines".into(), rest.into()));
+        }
+
+        let (row, col) = (ghost.row, ghost.col);
+        self.ghosts.write().await.insert(handle, ghost);
+        connection
+            .request(
+                "nvim_buf_set_extmark",
+                vec![
+                    handle.into(),
+                    namespace.into(),
+                    row.into(),
+                    col.into(),
+                    MsgpackValue::Map(options),
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn clear_ghost(&self, connection: &NeovimConnection, handle: i64) -> IdeResult<()> {
+        if self.ghosts.write().await.remove(&handle).is_none() {
+            return Ok(());
+        }
+        let namespace = self.namespace().await?;
+        connection
+            .request(
+                "nvim_buf_del_extmark",
+                vec![handle.into(), namespace.into(), GHOST_MARK_ID.into()],
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Insert the ghost text into the buffer; returns whether there was any
+    async fn accept_ghost(&self, connection: &NeovimConnection, handle: i64) -> IdeResult<bool> {
+        let Some(ghost) = self.ghost_text(handle).await else {
+            return Ok(false);
+        };
+        self.clear_ghost(connection, handle).await?;
+
+        let lines: Vec<MsgpackValue> = ghost.text.split('\n').map(MsgpackValue::from).collect();
+        connection
+            .request(
+                "nvim_buf_set_text",
+                vec![
+                    handle.into(),
+                    ghost.row.into(),
+                    ghost.col.into(),
+                    ghost.row.into(),
+                    ghost.col.into(),
+                    lines.into(),
+                ],
+            )
+            .await?;
+        Ok(true)
+    }
+
+    async fn synced_buffer(&self, handle: i64) -> IdeResult<NeovimBuffer> {
+        self.buffer(handle).await.ok_or_else(|| {
+            IdeError::communication_error(format!("Buffer {} is not attached", handle))
+        })
+    }
+
+    /// `:RiceChat`: chat about the buffer, applying any edits in the reply
+    async fn chat(
+        &self,
+        connection: &NeovimConnection,
+        handle: i64,
+        message: &str,
+    ) -> IdeResult<String> {
+        let buffer = self.synced_buffer(handle).await?;
+        let reply = self
+            .handler
+            .chat(
+                ChatParams {
+                    message: message.to_string(),
+                    uri: Some(buffer.uri()),
+                    selection: None,
+                },
+                Some(buffer.document()),
+            )
+            .await?;
+
+        if let Some(edits) = reply
+            .edit
+            .as_ref()
+            .and_then(|e| e.changes.get(&buffer.uri()))
+        {
+            self.apply_edits(connection, &buffer, edits).await?;
+        }
+        Ok(reply.content)
+    }
+
+    /// `:RiceRefactor`: apply a code action for a line range (1-based, inclusive)
+    async fn refactor(
+        &self,
+        connection: &NeovimConnection,
+        handle: i64,
+        line1: i64,
+        line2: i64,
+        filter: &str,
+    ) -> IdeResult<String> {
+        let buffer = self.synced_buffer(handle).await?;
+        let start = (line1.max(1) - 1) as u32;
+        let end = (line2.max(line1).max(1) - 1) as u32;
+        let end_character = buffer
+            .lines
+            .get(end as usize)
+            .map(|line| line.encode_utf16().count() as u32)
+            .unwrap_or(0);
+        let range = Range {
+            start: Position {
+                line: start,
+                character: 0,
+            },
+            end: Position {
+                line: end,
+                character: end_character,
+            },
+        };
+
+        let diagnostics: Vec<Diagnostic> = self
+            .manager
+            .handle_diagnostics_request(&DiagnosticsParams {
+                language: buffer.language(),
+                file_path: buffer.name.clone(),
+                source: buffer.text(),
+            })
+            .await?
+            .into_iter()
+            .filter(|d| d.range.start.line <= end && d.range.end.line >= start)
+            .collect();
+
+        let filter = filter.trim().to_lowercase();
+        let actions = self
+            .handler
+            .code_actions(&buffer.document(), range, &diagnostics)
+            .await?;
+        let action = actions.iter().find(|action| {
+            action.edit.is_some()
+                && (filter.is_empty() || action.title.to_lowercase().contains(&filter))
+        });
+
+        let Some(action) = action else {
+            return Ok("No refactoring available".to_string());
+        };
+        if let Some(edits) = action
+            .edit
+            .as_ref()
+            .and_then(|edit| edit.changes.get(&buffer.uri()))
+        {
+            self.apply_edits(connection, &buffer, edits).await?;
+        }
+        Ok(format!("Applied: {}", action.title))
+    }
+
+    /// Apply text edits with `nvim_buf_set_text`, last edit first
+    async fn apply_edits(
+        &self,
+        connection: &NeovimConnection,
+        buffer: &NeovimBuffer,
+        edits: &[TextEdit],
+    ) -> IdeResult<()> {
+        let mut edits: Vec<&TextEdit> = edits.iter().collect();
+        edits.sort_by_key(|edit| {
+            std::cmp::Reverse((edit.range.start.line, edit.range.start.character))
+        });
+
+        for edit in edits {
+            let start = &edit.range.start;
+            let end = &edit.range.end;
+            let lines: Vec<MsgpackValue> =
+                edit.new_text.split('\n').map(MsgpackValue::from).collect();
+            connection
+                .request(
+                    "nvim_buf_set_text",
+                    vec![
+                        buffer.handle.into(),
+                        (start.line as i64).into(),
+                        (buffer.byte_col(start.line, start.character) as i64).into(),
+                        (end.line as i64).into(),
+                        (buffer.byte_col(end.line, end.character) as i64).into(),
+                        lines.into(),
+                    ],
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+/// Arguments of a request or notification
+fn args(params: Option<MsgpackValue>) -> Vec<MsgpackValue> {
+    match params {
+        Some(MsgpackValue::Array(items)) => items,
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use tokio::io::{DuplexStream, ReadHalf, WriteHalf};
+
+    use super::*;
+    use crate::{
+        generic_provider::GenericProvider,
+        provider_chain::{ProviderChainManager, ProviderRegistry},
+        vscode_bridge::{ChatReply, CodeAction, SessionStatus, WorkspaceEdit},
+    };
+
+    struct TestHandler;
+
+    #[async_trait]
+    impl BridgeHandler for TestHandler {
+        async fn chat(
+            &self,
+            _params: ChatParams,
+            document: Option<TextDocument>,
+        ) -> IdeResult<ChatReply> {
+            Ok(ChatReply {
+                content: document.map(|d| d.text).unwrap_or_default(),
+                edit: None,
+            })
+        }
+
+        async fn code_actions(
+            &self,
+            document: &TextDocument,
+            range: Range,
+            _diagnostics: &[Diagnostic],
+        ) -> IdeResult<Vec<CodeAction>> {
+            let mut edit = WorkspaceEdit::default();
+            edit.add(
+                document.uri.clone(),
+                TextEdit {
+                    range: Range {
+                        start: range.start,
+                        end: Position {
+                            line: range.start.line,
+                            character: 2,
+                        },
+                    },
+                    new_text: "pub fn".to_string(),
+                },
+            );
+            Ok(vec![CodeAction {
+                title: "Make public".to_string(),
+                kind: "refactor".to_string(),
+                diagnostics: Vec::new(),
+                edit: Some(edit),
+            }])
+        }
+
+        async fn session_status(&self) -> IdeResult<SessionStatus> {
+            Ok(SessionStatus::default())
+        }
+    }
+
+    fn provider() -> Arc<NeovimProvider> {
+        let config = IdeIntegrationConfig {
+            vscode: None,
+            jetbrains: None,
+            terminal: None,
+            providers: ProviderChainConfig {
+                external_lsp: ExternalLspConfig {
+                    enabled: false,
+                    servers: HashMap::new(),
+                    health_check_interval_ms: 5000,
+                },
+                configured_rules: None,
+                builtin_providers: BuiltinProvidersConfig {
+                    enabled: false,
+                    languages: Vec::new(),
+                },
+            },
+        };
+        let chain = Arc::new(ProviderChainManager::new(ProviderRegistry::new(Arc::new(
+            GenericProvider::new(),
+        ))));
+        Arc::new(NeovimProvider::new(
+            Arc::new(IdeIntegrationManager::new(config, chain)),
+            Arc::new(TestHandler),
+        ))
+    }
+
+    /// Plays the Neovim side of the connection
+    struct FakeNeovim {
+        reader: ReadHalf<DuplexStream>,
+        writer: WriteHalf<DuplexStream>,
+        buf: Vec<u8>,
+    }
+
+    impl FakeNeovim {
+        /// Connect to the provider and answer its setup requests
+        async fn start(provider: &Arc<NeovimProvider>) -> Self {
+            let (nvim, stream) = tokio::io::duplex(64 * 1024);
+            let (reader, writer) = tokio::io::split(stream);
+            tokio::spawn(provider.clone().run(reader, writer));
+            let (reader, writer) = tokio::io::split(nvim);
+            let mut nvim = FakeNeovim {
+                reader,
+                writer,
+                buf: Vec::new(),
+            };
+
+            let id = nvim.expect_request("nvim_get_api_info").await.0;
+            nvim.respond(id, vec![7.into(), MsgpackValue::Map(Vec::new())].into())
+                .await;
+            let id = nvim.expect_request("nvim_create_namespace").await.0;
+            nvim.respond(id, 3.into()).await;
+            let (id, params) = nvim.expect_request("nvim_exec2").await;
+            let script = params[0].as_str().unwrap();
+            assert!(script.contains("command! -nargs=+ RiceChat echo rpcrequest(7, 'RiceChat'"));
+            assert!(script.contains("RiceRefactor"));
+            assert!(script.contains("inoremap <silent> <C-l>"));
+            nvim.respond(id, MsgpackValue::Map(Vec::new())).await;
+            nvim
+        }
+
+        async fn receive(&mut self) -> Vec<MsgpackValue> {
+            loop {
+                if let Some((value, used)) = decode(&self.buf).unwrap() {
+                    self.buf.drain(..used);
+                    return value.as_array().unwrap().to_vec();
+                }
+                let mut chunk = [0u8; 4096];
+                let read = self.reader.read(&mut chunk).await.unwrap();
+                assert!(read > 0, "provider closed the connection");
+                self.buf.extend_from_slice(&chunk[..read]);
+            }
+        }
+
+        async fn expect_request(&mut self, method: &str) -> (MsgpackValue, Vec<MsgpackValue>) {
+            let message = self.receive().await;
+            assert_eq!(message[0], REQUEST.into());
+            assert_eq!(message[2].as_str(), Some(method));
+            (message[1].clone(), message[3].as_array().unwrap().to_vec())
+        }
+
+        async fn send(&mut self, message: MsgpackValue) {
+            let mut buf = Vec::new();
+            message.encode(&mut buf);
+            self.writer.write_all(&buf).await.unwrap();
+        }
+
+        async fn respond(&mut self, id: MsgpackValue, result: MsgpackValue) {
+            self.send(vec![RESPONSE.into(), id, MsgpackValue::Nil, result].into())
+                .await;
+        }
+
+        async fn notify(&mut self, method: &str, params: Vec<MsgpackValue>) {
+            self.send(vec![NOTIFICATION.into(), method.into(), params.into()].into())
+                .await;
+        }
+
+        /// Send a request from Neovim
+        async fn request(&mut self, id: i64, method: &str, params: Vec<MsgpackValue>) {
+            self.send(vec![REQUEST.into(), id.into(), method.into(), params.into()].into())
+                .await;
+        }
+
+        async fn expect_response(&mut self, id: i64) -> MsgpackValue {
+            let message = self.receive().await;
+            assert_eq!(message[0], RESPONSE.into());
+            assert_eq!(message[1], id.into());
+            assert_eq!(message[2], MsgpackValue::Nil);
+            message[3].clone()
+        }
+
+        /// Attach buffer 1 and sync its initial lines
+        async fn attach(&mut self, lines: &[&str]) {
+            self.notify(
+                "ricecoder_attach",
+                vec![1.into(), "/tmp/main.rs".into(), "rust".into()],
+            )
+            .await;
+            let (id, params) = self.expect_request("nvim_buf_attach").await;
+            assert_eq!(params[0], 1.into());
+            assert_eq!(params[1], true.into());
+            self.respond(id, true.into()).await;
+
+            // Neovim sends buffer handles as extension type 0
+            self.notify(
+                "nvim_buf_lines_event",
+                vec![
+                    MsgpackValue::Ext(0, vec![1]),
+                    5.into(),
+                    0.into(),
+                    (-1).into(),
+                    lines
+                        .iter()
+                        .map(|l| MsgpackValue::from(*l))
+                        .collect::<Vec<_>>()
+                        .into(),
+                    false.into(),
+                ],
+            )
+            .await;
+        }
+    }
+
+    fn values(values: &[i64]) -> Vec<MsgpackValue> {
+        values
+            .iter()
+            .map(|value| MsgpackValue::Integer(*value))
+            .collect()
+    }
+
+    #[test]
+    fn test_apply_lines() {
+        let mut buffer = NeovimBuffer::new(1, "main.rs".to_string(), "rust".to_string());
+        buffer.apply_lines(0, -1, vec!["a".into(), "b".into(), "c".into()]);
+        buffer.apply_lines(1, 2, vec!["x".into(), "y".into()]);
+        assert_eq!(buffer.lines, vec!["a", "x", "y", "c"]);
+        buffer.apply_lines(0, 1, Vec::new());
+        assert_eq!(buffer.text(), "x\ny\nc");
+        assert_eq!(buffer.byte_col(0, 5), 1);
+    }
+
+    #[tokio::test]
+    async fn test_ghost_text_completion_and_accept() {
+        let provider = provider();
+        let mut nvim = FakeNeovim::start(&provider).await;
+        nvim.attach(&["fn compute_total() {}", "comp"]).await;
+
+        nvim.notify("ricecoder_complete", vec![1.into(), 1.into(), 4.into()])
+            .await;
+        let (id, params) = nvim.expect_request("nvim_buf_set_extmark").await;
+        assert_eq!(params[..4].to_vec(), values(&[1, 3, 1, 4]));
+        let virt_text = params[4].get("virt_text").unwrap().as_array().unwrap();
+        assert_eq!(
+            virt_text[0].as_array().unwrap()[0].as_str(),
+            Some("ute_total")
+        );
+        assert_eq!(
+            params[4].get("virt_text_pos").unwrap().as_str(),
+            Some("inline")
+        );
+        nvim.respond(id, 1.into()).await;
+
+        nvim.request(10, "ricecoder_accept", vec![1.into()]).await;
+        let (id, params) = nvim.expect_request("nvim_buf_del_extmark").await;
+        assert_eq!(params, values(&[1, 3, GHOST_MARK_ID]));
+        nvim.respond(id, true.into()).await;
+        let (id, params) = nvim.expect_request("nvim_buf_set_text").await;
+        assert_eq!(params[..5].to_vec(), values(&[1, 1, 4, 1, 4]));
+        assert_eq!(params[5], vec![MsgpackValue::from("ute_total")].into());
+        nvim.respond(id, MsgpackValue::Nil).await;
+        assert_eq!(nvim.expect_response(10).await, true.into());
+        assert!(provider.ghost_text(1).await.is_none());
+
+        // Nothing left to accept
+        nvim.request(11, "ricecoder_accept", vec![1.into()]).await;
+        assert_eq!(nvim.expect_response(11).await, false.into());
+    }
+
+    #[tokio::test]
+    async fn test_buffer_sync_and_chat() {
+        let provider = provider();
+        let mut nvim = FakeNeovim::start(&provider).await;
+        nvim.attach(&["fn main() {}", "old"]).await;
+
+        nvim.notify(
+            "nvim_buf_lines_event",
+            vec![
+                MsgpackValue::Ext(0, vec![1]),
+                6.into(),
+                1.into(),
+                2.into(),
+                vec![
+                    MsgpackValue::from("let x = 1;"),
+                    MsgpackValue::from("let y = 2;"),
+                ]
+                .into(),
+                false.into(),
+            ],
+        )
+        .await;
+
+        nvim.request(20, "RiceChat", vec![1.into(), "explain".into()])
+            .await;
+        assert_eq!(
+            nvim.expect_response(20).await.as_str(),
+            Some("fn main() {}\nlet x = 1;\nlet y = 2;")
+        );
+        assert_eq!(provider.buffer(1).await.unwrap().changedtick, 6);
+
+        nvim.notify("nvim_buf_detach_event", vec![MsgpackValue::Ext(0, vec![1])])
+            .await;
+        nvim.request(21, "RiceChat", vec![1.into(), "explain".into()])
+            .await;
+        let message = nvim.receive().await;
+        assert_ne!(message[2], MsgpackValue::Nil);
+    }
+
+    #[tokio::test]
+    async fn test_refactor_applies_code_action() {
+        let provider = provider();
+        let mut nvim = FakeNeovim::start(&provider).await;
+        nvim.attach(&["fn main() {}"]).await;
+
+        nvim.request(
+            30,
+            "RiceRefactor",
+            vec![1.into(), 1.into(), 1.into(), "public".into()],
+        )
+        .await;
+        let (id, params) = nvim.expect_request("nvim_buf_set_text").await;
+        assert_eq!(params[..5].to_vec(), values(&[1, 0, 0, 0, 2]));
+        assert_eq!(params[5], vec![MsgpackValue::from("pub fn")].into());
+        nvim.respond(id, MsgpackValue::Nil).await;
+        assert_eq!(
+            nvim.expect_response(30).await.as_str(),
+            Some("Applied: Make public")
+        );
+
+        nvim.request(
+            31,
+            "RiceRefactor",
+            vec![1.into(), 1.into(), 1.into(), "inline".into()],
+        )
+        .await;
+        assert_eq!(
+            nvim.expect_response(31).await.as_str(),
+            Some("No refactoring available")
+        );
+    }
+}
@@ -5,13 +5,22 @@
 //! 2. Configured IDE Rules (YAML/JSON configuration)
 //! 3. Built-in Language Providers (Rust, TypeScript, Python)
 //! 4. Generic Text-based Features (fallback for any language)
+//!
+//! The chain adapts at runtime: providers that repeatedly fail or exceed the
+//! latency budget are demoted behind the others and restored once they recover.
 
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
+use serde::Serialize;
 use tracing::{debug, info, warn};
 
 use crate::{
-    error::IdeResult,
+    error::{IdeError, IdeResult},
+    lsp_monitor::{LspMonitor, ProviderStats},
     provider::{IdeProvider, ProviderChange},
     types::*,
 };
@@ -92,6 +101,32 @@ impl ProviderRegistry {
         self.generic_provider.clone()
     }
 
+    /// Get every provider that can serve a language, in priority order
+    ///
+    /// The generic fallback provider is always last.
+    pub fn candidates(&self, language: &str) -> Vec<ChainEntry> {
+        let tiers = [
+            (ProviderTier::Lsp, &self.lsp_providers),
+            (ProviderTier::Configured, &self.configured_providers),
+            (ProviderTier::Builtin, &self.builtin_providers),
+        ];
+
+        let mut candidates: Vec<ChainEntry> = tiers
+            .into_iter()
+            .filter_map(|(tier, providers)| {
+                providers.get(language).map(|provider| ChainEntry {
+                    tier,
+                    provider: provider.clone(),
+                })
+            })
+            .collect();
+        candidates.push(ChainEntry {
+            tier: ProviderTier::Generic,
+            provider: self.generic_provider.clone(),
+        });
+        candidates
+    }
+
     /// Check if a provider is available for a language
     pub fn is_provider_available(&self, language: &str) -> bool {
         self.lsp_providers.contains_key(language)
@@ -132,10 +167,186 @@ impl ProviderRegistry {
     }
 }
 
+/// Position of a provider in the static priority chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProviderTier {
+    /// External LSP server
+    Lsp,
+    /// Configured IDE rules
+    Configured,
+    /// Built-in language provider
+    Builtin,
+    /// Generic text-based fallback
+    Generic,
+}
+
+impl std::fmt::Display for ProviderTier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            ProviderTier::Lsp => "lsp",
+            ProviderTier::Configured => "configured",
+            ProviderTier::Builtin => "builtin",
+            ProviderTier::Generic => "generic",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Thresholds that decide when a provider is demoted in the chain
+#[derive(Debug, Clone, PartialEq)]
+pub struct FailoverPolicy {
+    /// Consecutive failures after which a provider is demoted
+    pub max_consecutive_failures: u32,
+    /// Latency a single request is expected to stay under
+    pub latency_budget: Duration,
+    /// Consecutive over-budget requests after which a provider is demoted
+    pub max_slow_responses: u32,
+    /// How long a demoted provider waits before it is retried at its normal position
+    pub cooldown: Duration,
+}
+
+impl Default for FailoverPolicy {
+    fn default() -> Self {
+        FailoverPolicy {
+            max_consecutive_failures: 3,
+            latency_budget: Duration::from_millis(2000),
+            max_slow_responses: 3,
+            cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Why a provider was demoted
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DemotionReason {
+    /// The provider failed several requests in a row
+    RepeatedFailures {
+        /// Number of consecutive failures
+        consecutive_failures: u32,
+        /// Error message of the most recent failure
+        last_error: Option<String>,
+    },
+    /// The provider repeatedly exceeded the latency budget
+    LatencyBudgetExceeded {
+        /// Latency of the most recent request in milliseconds
+        last_latency_ms: u64,
+        /// Configured latency budget in milliseconds
+        budget_ms: u64,
+    },
+    /// The LSP monitor reported the server as unhealthy
+    Unhealthy,
+}
+
+impl std::fmt::Display for DemotionReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DemotionReason::RepeatedFailures {
+                consecutive_failures,
+                last_error,
+            } => {
+                write!(f, "{} consecutive failures", consecutive_failures)?;
+                if let Some(error) = last_error {
+                    write!(f, " (last error: {})", error)?;
+                }
+                Ok(())
+            }
+            DemotionReason::LatencyBudgetExceeded {
+                last_latency_ms,
+                budget_ms,
+            } => write!(
+                f,
+                "latency {}ms exceeds budget of {}ms",
+                last_latency_ms, budget_ms
+            ),
+            DemotionReason::Unhealthy => write!(f, "LSP server reported unhealthy"),
+        }
+    }
+}
+
+/// Slot of a provider in a language's chain
+///
+/// Health is tracked per slot rather than per provider name: every external
+/// LSP server reports the same name, and one crashing server must not demote
+/// the others.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ChainSlot {
+    language: String,
+    tier: ProviderTier,
+}
+
+impl ChainSlot {
+    fn new(language: &str, tier: ProviderTier) -> Self {
+        ChainSlot {
+            language: language.to_string(),
+            tier,
+        }
+    }
+
+    /// Key the slot's request statistics are recorded under in the monitor
+    fn stats_key(&self) -> String {
+        ProviderChainManager::stats_key(&self.language, self.tier)
+    }
+}
+
+/// A demoted provider
+#[derive(Debug, Clone)]
+struct Demotion {
+    provider_name: String,
+    reason: DemotionReason,
+    since: Instant,
+}
+
+/// A provider together with its tier
+#[derive(Clone)]
+pub struct ChainEntry {
+    /// Tier the provider was registered in
+    pub tier: ProviderTier,
+    /// The provider
+    pub provider: Arc<dyn IdeProvider>,
+}
+
+/// Diagnostics for a single provider in the adaptive chain
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderDiagnostics {
+    /// Provider name
+    pub name: String,
+    /// Tier the provider was registered in
+    pub tier: ProviderTier,
+    /// Whether the provider is currently demoted
+    pub demoted: bool,
+    /// Whether a demoted provider's cooldown has elapsed and it is being retried
+    pub on_probation: bool,
+    /// Why the provider was demoted
+    pub reason: Option<DemotionReason>,
+    /// Human-readable explanation of the provider's position
+    pub explanation: String,
+    /// Recorded request statistics
+    pub stats: ProviderStats,
+}
+
+/// Adaptive provider ordering for a language
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderChainDiagnostics {
+    /// Language the ordering applies to
+    pub language: String,
+    /// Providers in the order they are tried
+    pub providers: Vec<ProviderDiagnostics>,
+}
+
 /// Provider chain manager that orchestrates the provider priority chain
+///
+/// Requests go to providers in priority order, falling over to the next
+/// provider when one fails. Latency and success statistics are recorded
+/// through the [`LspMonitor`]; providers that repeatedly fail or exceed the
+/// latency budget are demoted behind the healthy ones until they recover.
 pub struct ProviderChainManager {
     registry: Arc<tokio::sync::RwLock<ProviderRegistry>>,
     availability_callbacks: Arc<tokio::sync::RwLock<Vec<ProviderAvailabilityCallback>>>,
+    monitor: Arc<LspMonitor>,
+    policy: FailoverPolicy,
+    demotions: Arc<tokio::sync::RwLock<HashMap<ChainSlot, Demotion>>>,
 }
 
 impl ProviderChainManager {
@@ -144,6 +355,232 @@ impl ProviderChainManager {
         ProviderChainManager {
             registry: Arc::new(tokio::sync::RwLock::new(registry)),
             availability_callbacks: Arc::new(tokio::sync::RwLock::new(Vec::new())),
+            monitor: Arc::new(LspMonitor::new(HashMap::new())),
+            policy: FailoverPolicy::default(),
+            demotions: Arc::new(tokio::sync::RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Use the given monitor for request statistics
+    pub fn with_monitor(mut self, monitor: Arc<LspMonitor>) -> Self {
+        self.monitor = monitor;
+        self
+    }
+
+    /// Use the given failover policy
+    pub fn with_failover_policy(mut self, policy: FailoverPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Get the monitor that records request statistics
+    pub fn monitor(&self) -> &Arc<LspMonitor> {
+        &self.monitor
+    }
+
+    /// Get the failover policy
+    pub fn failover_policy(&self) -> &FailoverPolicy {
+        &self.policy
+    }
+
+    /// Key the monitor records statistics under for a tier of a language
+    pub fn stats_key(language: &str, tier: ProviderTier) -> String {
+        format!("{}:{}", language, tier)
+    }
+
+    /// Demote and restore LSP providers as the monitor reports health changes
+    pub async fn watch_lsp_health(self: &Arc<Self>) -> IdeResult<()> {
+        let manager = Arc::downgrade(self);
+        self.monitor
+            .on_availability_changed(Arc::new(move |language: &str, available: bool| {
+                let Some(manager) = manager.upgrade() else {
+                    return;
+                };
+                let language = language.to_string();
+                tokio::spawn(async move {
+                    manager.handle_lsp_health_change(&language, available).await;
+                });
+            }))
+            .await
+    }
+
+    /// Apply an LSP health change reported by the monitor
+    pub async fn handle_lsp_health_change(&self, language: &str, available: bool) {
+        let provider = {
+            let registry = self.registry.read().await;
+            registry.lsp_providers.get(language).cloned()
+        };
+        let Some(provider) = provider else {
+            return;
+        };
+
+        let slot = ChainSlot::new(language, ProviderTier::Lsp);
+        if available {
+            self.restore(&slot).await;
+        } else {
+            self.demote(provider.name(), slot, DemotionReason::Unhealthy)
+                .await;
+        }
+    }
+
+    /// Get the providers for a language in the order they are tried
+    ///
+    /// Demoted providers move behind all others, keeping their relative
+    /// priority. Once a demoted provider's cooldown has elapsed it is retried
+    /// at its normal position.
+    pub async fn adaptive_order(&self, language: &str) -> Vec<ChainEntry> {
+        let candidates = self.registry.read().await.candidates(language);
+        let demotions = self.demotions.read().await;
+
+        let (active, demoted): (Vec<_>, Vec<_>) = candidates.into_iter().partition(|entry| {
+            match demotions.get(&ChainSlot::new(language, entry.tier)) {
+                Some(demotion) => demotion.since.elapsed() >= self.policy.cooldown,
+                None => true,
+            }
+        });
+
+        active.into_iter().chain(demoted).collect()
+    }
+
+    /// Get the adaptive ordering for a language together with the reasons behind it
+    pub async fn diagnostics(&self, language: &str) -> ProviderChainDiagnostics {
+        let order = self.adaptive_order(language).await;
+        let demotions = self.demotions.read().await;
+        let mut providers = Vec::with_capacity(order.len());
+
+        for entry in order {
+            let slot = ChainSlot::new(language, entry.tier);
+            let stats = self
+                .monitor
+                .provider_stats(&slot.stats_key())
+                .await
+                .unwrap_or_default();
+            let demotion = demotions.get(&slot);
+            let on_probation =
+                demotion.is_some_and(|demotion| demotion.since.elapsed() >= self.policy.cooldown);
+            let explanation = match demotion {
+                Some(demotion) if on_probation => format!(
+                    "retrying at {} priority after cooldown; demoted for {}",
+                    entry.tier, demotion.reason
+                ),
+                Some(demotion) => format!("demoted: {}", demotion.reason),
+                None => format!("{} priority", entry.tier),
+            };
+
+            providers.push(ProviderDiagnostics {
+                name: entry.provider.name().to_string(),
+                tier: entry.tier,
+                demoted: demotion.is_some(),
+                on_probation,
+                reason: demotion.map(|demotion| demotion.reason.clone()),
+                explanation,
+                stats,
+            });
+        }
+
+        ProviderChainDiagnostics {
+            language: language.to_string(),
+            providers,
+        }
+    }
+
+    /// Restore the demoted provider of a language's tier to its normal position
+    ///
+    /// Returns `true` if the provider was demoted.
+    pub async fn restore_provider(&self, language: &str, tier: ProviderTier) -> bool {
+        self.restore(&ChainSlot::new(language, tier)).await
+    }
+
+    async fn demote(&self, provider_name: &str, slot: ChainSlot, reason: DemotionReason) {
+        let (language, tier) = (slot.language.clone(), slot.tier);
+        let newly_demoted = {
+            let mut demotions = self.demotions.write().await;
+            demotions
+                .insert(
+                    slot,
+                    Demotion {
+                        provider_name: provider_name.to_string(),
+                        reason: reason.clone(),
+                        since: Instant::now(),
+                    },
+                )
+                .is_none()
+        };
+
+        if newly_demoted {
+            warn!(
+                "Demoting {} provider {} for language {}: {}",
+                tier, provider_name, language, reason
+            );
+            self.notify_provider_change(ProviderChange {
+                provider_name: provider_name.to_string(),
+                language,
+                available: false,
+            })
+            .await;
+        }
+    }
+
+    async fn restore(&self, slot: &ChainSlot) -> bool {
+        let removed = self.demotions.write().await.remove(slot);
+        let Some(demotion) = removed else {
+            return false;
+        };
+
+        info!(
+            "Restoring {} provider {} for language {}",
+            slot.tier, demotion.provider_name, slot.language
+        );
+        self.notify_provider_change(ProviderChange {
+            provider_name: demotion.provider_name,
+            language: slot.language.clone(),
+            available: true,
+        })
+        .await;
+        true
+    }
+
+    /// Record a request outcome and demote or restore the provider accordingly
+    async fn record_outcome(
+        &self,
+        language: &str,
+        entry: &ChainEntry,
+        latency: Duration,
+        error: Option<&IdeError>,
+    ) {
+        let name = entry.provider.name();
+        let slot = ChainSlot::new(language, entry.tier);
+        let stats = self
+            .monitor
+            .record_request(&slot.stats_key(), latency, error.map(|e| e.to_string()))
+            .await;
+
+        if error.is_some() {
+            if stats.consecutive_failures >= self.policy.max_consecutive_failures {
+                self.demote(
+                    name,
+                    slot,
+                    DemotionReason::RepeatedFailures {
+                        consecutive_failures: stats.consecutive_failures,
+                        last_error: stats.last_error.clone(),
+                    },
+                )
+                .await;
+            }
+        } else if stats.consecutive_over_budget(self.policy.latency_budget)
+            >= self.policy.max_slow_responses
+        {
+            self.demote(
+                name,
+                slot,
+                DemotionReason::LatencyBudgetExceeded {
+                    last_latency_ms: stats.last_latency_ms().unwrap_or_default(),
+                    budget_ms: self.policy.latency_budget.as_millis() as u64,
+                },
+            )
+            .await;
+        } else if latency <= self.policy.latency_budget {
+            self.restore(&slot).await;
         }
     }
 
@@ -157,26 +594,40 @@ impl ProviderChainManager {
             params.language
         );
 
-        let registry = self.registry.read().await;
-        let provider = registry.get_provider(&params.language);
-
-        match provider.get_completions(params).await {
-            Ok(completions) => {
-                info!(
-                    "Successfully got {} completions for language: {}",
-                    completions.len(),
-                    params.language
-                );
-                Ok(completions)
-            }
-            Err(e) => {
-                warn!(
-                    "Failed to get completions for language: {}: {}",
-                    params.language, e
-                );
-                Err(e)
+        let mut last_error = None;
+        for entry in self.adaptive_order(&params.language).await {
+            let started = Instant::now();
+            let result = entry.provider.get_completions(params).await;
+            self.record_outcome(
+                &params.language,
+                &entry,
+                started.elapsed(),
+                result.as_ref().err(),
+            )
+            .await;
+
+            match result {
+                Ok(completions) => {
+                    info!(
+                        "Successfully got {} completions for language: {}",
+                        completions.len(),
+                        params.language
+                    );
+                    return Ok(completions);
+                }
+                Err(e) => {
+                    warn!(
+                        "Failed to get completions for language: {} from {}: {}",
+                        params.language,
+                        entry.provider.name(),
+                        e
+                    );
+                    last_error = Some(e);
+                }
             }
         }
+
+        Err(last_error.unwrap_or_else(|| IdeError::provider_error("No providers available")))
     }
 
     /// Get diagnostics through the provider chain
@@ -186,26 +637,40 @@ impl ProviderChainManager {
             params.language
         );
 
-        let registry = self.registry.read().await;
-        let provider = registry.get_provider(&params.language);
-
-        match provider.get_diagnostics(params).await {
-            Ok(diagnostics) => {
-                info!(
-                    "Successfully got {} diagnostics for language: {}",
-                    diagnostics.len(),
-                    params.language
-                );
-                Ok(diagnostics)
-            }
-            Err(e) => {
-                warn!(
-                    "Failed to get diagnostics for language: {}: {}",
-                    params.language, e
-                );
-                Err(e)
+        let mut last_error = None;
+        for entry in self.adaptive_order(&params.language).await {
+            let started = Instant::now();
+            let result = entry.provider.get_diagnostics(params).await;
+            self.record_outcome(
+                &params.language,
+                &entry,
+                started.elapsed(),
+                result.as_ref().err(),
+            )
+            .await;
+
+            match result {
+                Ok(diagnostics) => {
+                    info!(
+                        "Successfully got {} diagnostics for language: {}",
+                        diagnostics.len(),
+                        params.language
+                    );
+                    return Ok(diagnostics);
+                }
+                Err(e) => {
+                    warn!(
+                        "Failed to get diagnostics for language: {} from {}: {}",
+                        params.language,
+                        entry.provider.name(),
+                        e
+                    );
+                    last_error = Some(e);
+                }
             }
         }
+
+        Err(last_error.unwrap_or_else(|| IdeError::provider_error("No providers available")))
     }
 
     /// Get hover information through the provider chain
@@ -215,27 +680,41 @@ impl ProviderChainManager {
             params.language
         );
 
-        let registry = self.registry.read().await;
-        let provider = registry.get_provider(&params.language);
+        let mut last_error = None;
+        for entry in self.adaptive_order(&params.language).await {
+            let started = Instant::now();
+            let result = entry.provider.get_hover(params).await;
+            self.record_outcome(
+                &params.language,
+                &entry,
+                started.elapsed(),
+                result.as_ref().err(),
+            )
+            .await;
 
-        match provider.get_hover(params).await {
-            Ok(hover) => {
-                if hover.is_some() {
-                    info!(
-                        "Successfully got hover information for language: {}",
-                        params.language
+            match result {
+                Ok(hover) => {
+                    if hover.is_some() {
+                        info!(
+                            "Successfully got hover information for language: {}",
+                            params.language
+                        );
+                    }
+                    return Ok(hover);
+                }
+                Err(e) => {
+                    warn!(
+                        "Failed to get hover information for language: {} from {}: {}",
+                        params.language,
+                        entry.provider.name(),
+                        e
                     );
+                    last_error = Some(e);
                 }
-                Ok(hover)
-            }
-            Err(e) => {
-                warn!(
-                    "Failed to get hover information for language: {}: {}",
-                    params.language, e
-                );
-                Err(e)
             }
         }
+
+        Err(last_error.unwrap_or_else(|| IdeError::provider_error("No providers available")))
     }
 
     /// Get definition location through the provider chain
@@ -245,27 +724,41 @@ impl ProviderChainManager {
             params.language
         );
 
-        let registry = self.registry.read().await;
-        let provider = registry.get_provider(&params.language);
+        let mut last_error = None;
+        for entry in self.adaptive_order(&params.language).await {
+            let started = Instant::now();
+            let result = entry.provider.get_definition(params).await;
+            self.record_outcome(
+                &params.language,
+                &entry,
+                started.elapsed(),
+                result.as_ref().err(),
+            )
+            .await;
 
-        match provider.get_definition(params).await {
-            Ok(location) => {
-                if location.is_some() {
-                    info!(
-                        "Successfully got definition for language: {}",
-                        params.language
+            match result {
+                Ok(location) => {
+                    if location.is_some() {
+                        info!(
+                            "Successfully got definition for language: {}",
+                            params.language
+                        );
+                    }
+                    return Ok(location);
+                }
+                Err(e) => {
+                    warn!(
+                        "Failed to get definition for language: {} from {}: {}",
+                        params.language,
+                        entry.provider.name(),
+                        e
                     );
+                    last_error = Some(e);
                 }
-                Ok(location)
-            }
-            Err(e) => {
-                warn!(
-                    "Failed to get definition for language: {}: {}",
-                    params.language, e
-                );
-                Err(e)
             }
         }
+
+        Err(last_error.unwrap_or_else(|| IdeError::provider_error("No providers available")))
     }
 
     /// Register a provider availability change callback
@@ -380,6 +873,76 @@ mod tests {
         }
     }
 
+    /// Provider that always fails, optionally after a delay
+    struct FailingProvider {
+        name: String,
+        delay: Duration,
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl IdeProvider for FailingProvider {
+        async fn get_completions(
+            &self,
+            _params: &CompletionParams,
+        ) -> IdeResult<Vec<CompletionItem>> {
+            tokio::time::sleep(self.delay).await;
+            if self.fail {
+                return Err(IdeError::lsp_error("server crashed"));
+            }
+            Ok(vec![])
+        }
+
+        async fn get_diagnostics(&self, _params: &DiagnosticsParams) -> IdeResult<Vec<Diagnostic>> {
+            Err(IdeError::lsp_error("server crashed"))
+        }
+
+        async fn get_hover(&self, _params: &HoverParams) -> IdeResult<Option<Hover>> {
+            Err(IdeError::lsp_error("server crashed"))
+        }
+
+        async fn get_definition(&self, _params: &DefinitionParams) -> IdeResult<Option<Location>> {
+            Err(IdeError::lsp_error("server crashed"))
+        }
+
+        fn is_available(&self, _language: &str) -> bool {
+            true
+        }
+
+        fn name(&self) -> &str {
+            &self.name
+        }
+    }
+
+    fn completion_params() -> CompletionParams {
+        CompletionParams {
+            language: "rust".to_string(),
+            file_path: "src/main.rs".to_string(),
+            position: Position {
+                line: 0,
+                character: 0,
+            },
+            context: "fn test".to_string(),
+        }
+    }
+
+    fn chain_with_lsp(lsp: FailingProvider, policy: FailoverPolicy) -> ProviderChainManager {
+        let generic = Arc::new(MockProvider {
+            name: "generic".to_string(),
+            language: "generic".to_string(),
+        });
+        let mut registry = ProviderRegistry::new(generic);
+        registry.register_lsp_provider("rust".to_string(), Arc::new(lsp));
+        registry.register_builtin_provider(
+            "rust".to_string(),
+            Arc::new(MockProvider {
+                name: "builtin".to_string(),
+                language: "rust".to_string(),
+            }),
+        );
+        ProviderChainManager::new(registry).with_failover_policy(policy)
+    }
+
     #[test]
     fn test_provider_registry_creation() {
         let generic = Arc::new(MockProvider {
@@ -555,4 +1118,168 @@ mod tests {
         manager.notify_provider_change(change).await;
         assert!(called.load(std::sync::atomic::Ordering::SeqCst));
     }
+
+    #[tokio::test]
+    async fn test_failover_demotes_repeatedly_failing_provider() {
+        let manager = chain_with_lsp(
+            FailingProvider {
+                name: "rust-analyzer".to_string(),
+                delay: Duration::ZERO,
+                fail: true,
+            },
+            FailoverPolicy {
+                max_consecutive_failures: 2,
+                ..FailoverPolicy::default()
+            },
+        );
+
+        // The failing LSP provider falls over to the built-in provider
+        let completions = manager.get_completions(&completion_params()).await.unwrap();
+        assert_eq!(completions.len(), 1);
+        let names: Vec<_> = manager
+            .adaptive_order("rust")
+            .await
+            .iter()
+            .map(|entry| entry.provider.name().to_string())
+            .collect();
+        assert_eq!(names, vec!["rust-analyzer", "builtin", "generic"]);
+
+        manager.get_completions(&completion_params()).await.unwrap();
+        let diagnostics = manager.diagnostics("rust").await;
+        let names: Vec<_> = diagnostics
+            .providers
+            .iter()
+            .map(|provider| provider.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["builtin", "generic", "rust-analyzer"]);
+
+        let demoted = &diagnostics.providers[2];
+        assert!(demoted.demoted);
+        assert_eq!(demoted.tier, ProviderTier::Lsp);
+        assert_eq!(demoted.stats.failures, 2);
+        assert!(matches!(
+            demoted.reason,
+            Some(DemotionReason::RepeatedFailures {
+                consecutive_failures: 2,
+                ..
+            })
+        ));
+
+        // Demoted providers are skipped while healthy ones succeed
+        manager.get_completions(&completion_params()).await.unwrap();
+        let stats = manager
+            .monitor()
+            .provider_stats(&ProviderChainManager::stats_key("rust", ProviderTier::Lsp))
+            .await
+            .unwrap();
+        assert_eq!(stats.requests, 2);
+    }
+
+    #[tokio::test]
+    async fn test_demotion_is_scoped_to_language() {
+        let generic = Arc::new(MockProvider {
+            name: "generic".to_string(),
+            language: "generic".to_string(),
+        });
+        let mut registry = ProviderRegistry::new(generic);
+        // Every external server reports the same provider name
+        for (language, fail) in [("rust", true), ("python", false)] {
+            registry.register_lsp_provider(
+                language.to_string(),
+                Arc::new(FailingProvider {
+                    name: "external-lsp".to_string(),
+                    delay: Duration::ZERO,
+                    fail,
+                }),
+            );
+        }
+        let manager = ProviderChainManager::new(registry).with_failover_policy(FailoverPolicy {
+            max_consecutive_failures: 1,
+            ..FailoverPolicy::default()
+        });
+
+        manager.get_completions(&completion_params()).await.unwrap();
+        let rust = manager.diagnostics("rust").await;
+        assert_eq!(rust.providers[0].name, "generic");
+        assert!(rust.providers[1].demoted);
+
+        let python = manager.diagnostics("python").await;
+        assert_eq!(python.providers[0].name, "external-lsp");
+        assert!(!python.providers[0].demoted);
+        assert_eq!(python.providers[0].stats.requests, 0);
+
+        assert!(manager.restore_provider("rust", ProviderTier::Lsp).await);
+        assert!(!manager.restore_provider("python", ProviderTier::Lsp).await);
+    }
+
+    #[tokio::test]
+    async fn test_slow_provider_demoted_and_restored_on_health_recovery() {
+        let manager = Arc::new(chain_with_lsp(
+            FailingProvider {
+                name: "rust-analyzer".to_string(),
+                delay: Duration::from_millis(20),
+                fail: false,
+            },
+            FailoverPolicy {
+                latency_budget: Duration::from_millis(5),
+                max_slow_responses: 2,
+                ..FailoverPolicy::default()
+            },
+        ));
+
+        let changes = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let changes_clone = changes.clone();
+        manager
+            .on_provider_availability_changed(Box::new(move |change| {
+                changes_clone.lock().unwrap().push(change.available);
+            }))
+            .await;
+
+        manager.get_completions(&completion_params()).await.unwrap();
+        manager.get_completions(&completion_params()).await.unwrap();
+
+        let diagnostics = manager.diagnostics("rust").await;
+        assert_eq!(diagnostics.providers[0].name, "builtin");
+        assert!(matches!(
+            diagnostics.providers[2].reason,
+            Some(DemotionReason::LatencyBudgetExceeded { budget_ms: 5, .. })
+        ));
+
+        manager.handle_lsp_health_change("rust", true).await;
+        let diagnostics = manager.diagnostics("rust").await;
+        assert_eq!(diagnostics.providers[0].name, "rust-analyzer");
+        assert!(!diagnostics.providers[0].demoted);
+        assert_eq!(*changes.lock().unwrap(), vec![false, true]);
+
+        manager.handle_lsp_health_change("rust", false).await;
+        let diagnostics = manager.diagnostics("rust").await;
+        assert_eq!(
+            diagnostics.providers[2].reason,
+            Some(DemotionReason::Unhealthy)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_demoted_provider_retried_after_cooldown() {
+        let manager = chain_with_lsp(
+            FailingProvider {
+                name: "rust-analyzer".to_string(),
+                delay: Duration::ZERO,
+                fail: true,
+            },
+            FailoverPolicy {
+                max_consecutive_failures: 1,
+                cooldown: Duration::ZERO,
+                ..FailoverPolicy::default()
+            },
+        );
+
+        manager.get_completions(&completion_params()).await.unwrap();
+        let diagnostics = manager.diagnostics("rust").await;
+        assert_eq!(diagnostics.providers[0].name, "rust-analyzer");
+        assert!(diagnostics.providers[0].on_probation);
+        assert!(diagnostics.providers[0]
+            .explanation
+            .starts_with("retrying at lsp priority"));
+    }
 }
@@ -9,6 +9,9 @@ use serde::{Deserialize, Serialize};
 pub struct IdeIntegrationConfig {
     /// VS Code configuration
     pub vscode: Option<VsCodeConfig>,
+    /// JetBrains configuration
+    #[serde(default)]
+    pub jetbrains: Option<JetBrainsConfig>,
     /// Terminal editor configuration
     pub terminal: Option<TerminalConfig>,
     /// Provider chain configuration
@@ -29,6 +32,74 @@ pub struct VsCodeConfig {
     pub settings: serde_json::Value,
 }
 
+/// JetBrains IDE specific configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JetBrainsConfig {
+    /// Whether JetBrains integration is enabled
+    pub enabled: bool,
+    /// Port for the local protocol server
+    pub port: u16,
+    /// Features the plugin may use
+    #[serde(default)]
+    pub capabilities: IdeCapabilities,
+    /// Project roots the plugin may negotiate; empty allows any existing directory
+    #[serde(default)]
+    pub project_roots: Vec<String>,
+    /// JetBrains settings
+    #[serde(default)]
+    pub settings: serde_json::Value,
+}
+
+/// Capability flags for an IDE integration
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct IdeCapabilities {
+    /// Completions
+    pub completion: bool,
+    /// Diagnostics
+    pub diagnostics: bool,
+    /// Hover information
+    pub hover: bool,
+    /// Go to definition
+    pub definition: bool,
+    /// Chat with the assistant
+    pub chat: bool,
+}
+
+impl Default for IdeCapabilities {
+    fn default() -> Self {
+        IdeCapabilities {
+            completion: true,
+            diagnostics: true,
+            hover: true,
+            definition: true,
+            chat: true,
+        }
+    }
+}
+
+impl IdeCapabilities {
+    /// Names of the enabled capabilities
+    pub fn enabled(&self) -> Vec<&'static str> {
+        [
+            ("completion", self.completion),
+            ("diagnostics", self.diagnostics),
+            ("hover", self.hover),
+            ("definition", self.definition),
+            ("chat", self.chat),
+        ]
+        .into_iter()
+        .filter(|(_, enabled)| *enabled)
+        .map(|(name, _)| name)
+        .collect()
+    }
+
+    /// Whether a capability is enabled
+    pub fn is_enabled(&self, capability: &str) -> bool {
+        self.enabled().contains(&capability)
+    }
+}
+
 /// Terminal editor configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TerminalConfig {
@@ -335,6 +406,17 @@ mod tests {
         assert_eq!(deserialized.kind, CompletionItemKind::Function);
     }
 
+    #[test]
+    fn test_ide_capabilities_defaults() {
+        let capabilities: IdeCapabilities = serde_json::from_str(r#"{"chat": false}"#).unwrap();
+        assert!(capabilities.is_enabled("completion"));
+        assert!(!capabilities.is_enabled("chat"));
+        assert_eq!(
+            capabilities.enabled(),
+            vec!["completion", "diagnostics", "hover", "definition"]
+        );
+    }
+
     #[test]
     fn test_diagnostic_severity_serialization() {
         let severity = DiagnosticSeverity::Error;
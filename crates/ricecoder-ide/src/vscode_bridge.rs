@@ -0,0 +1,1055 @@
+//! VS Code extension host bridge
+//!
+//! Exposes ricecoder capabilities to a thin VS Code extension over a JSON-RPC
+//! 2.0 socket. Messages use the same `Content-Length` framing as LSP, so the
+//! extension can talk to the bridge with `vscode-jsonrpc` directly.
+//!
+//! Requests handled by the bridge:
+//! - `initialize` / `shutdown`
+//! - `textDocument/completion`: completions through the provider chain
+//! - `textDocument/codeAction`: code actions from the [`BridgeHandler`]
+//! - `ricecoder/chat`: chat with the [`BridgeHandler`]; edits in the reply are
+//!   applied to VS Code buffers with `workspace/applyEdit`
+//! - `ricecoder/sessionStatus`: current session status
+//!
+//! Notifications handled by the bridge:
+//! - `textDocument/didOpen`, `textDocument/didChange`, `textDocument/didClose`
+//!   keep the [`DocumentStore`] in sync with the editor
+//! - `exit` closes the connection
+
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::{
+    io::{AsyncBufRead, AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    net::TcpListener,
+    sync::{oneshot, Mutex, RwLock},
+};
+use tracing::{debug, info, warn};
+
+use crate::{
+    error::{IdeError, IdeResult},
+    manager::IdeIntegrationManager,
+    types::*,
+};
+
+/// JSON-RPC error code for malformed JSON
+pub const PARSE_ERROR: i64 = -32700;
+/// JSON-RPC error code for unknown methods
+pub const METHOD_NOT_FOUND: i64 = -32601;
+/// JSON-RPC error code for invalid parameters
+pub const INVALID_PARAMS: i64 = -32602;
+/// JSON-RPC error code for internal errors
+pub const INTERNAL_ERROR: i64 = -32603;
+
+/// A document open in VS Code
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TextDocument {
+    /// Document URI
+    pub uri: String,
+    /// VS Code language identifier
+    pub language_id: String,
+    /// Document version
+    pub version: i64,
+    /// Full document text
+    pub text: String,
+}
+
+impl TextDocument {
+    /// File path of the document, without the `file://` scheme
+    pub fn file_path(&self) -> &str {
+        self.uri.strip_prefix("file://").unwrap_or(&self.uri)
+    }
+
+    /// Byte offset of a position, with `character` counted in UTF-16 code units
+    ///
+    /// Positions past the end of a line or the document are clamped.
+    pub fn offset_at(&self, position: Position) -> usize {
+        let mut offset = 0;
+        for (line_number, line) in self.text.split_inclusive('\n').enumerate() {
+            if line_number as u32 == position.line {
+                let content = line.trim_end_matches(['\n', '\r']);
+                let mut units = 0;
+                for (index, ch) in content.char_indices() {
+                    if units >= position.character {
+                        return offset + index;
+                    }
+                    units += ch.len_utf16() as u32;
+                }
+                return offset + content.len();
+            }
+            offset += line.len();
+        }
+        self.text.len()
+    }
+
+    /// Apply a content change sent by VS Code
+    pub fn apply_change(&mut self, change: &ContentChange) {
+        match change.range {
+            Some(range) => {
+                let start = self.offset_at(range.start);
+                let end = self.offset_at(range.end).max(start);
+                self.text.replace_range(start..end, &change.text);
+            }
+            None => self.text = change.text.clone(),
+        }
+    }
+}
+
+/// A change to a document's content; a missing range replaces the whole text
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContentChange {
+    /// Range replaced by the change
+    #[serde(default)]
+    pub range: Option<Range>,
+    /// New text for the range
+    pub text: String,
+}
+
+/// Documents open in VS Code, kept in sync through `textDocument/did*`
+#[derive(Debug, Clone, Default)]
+pub struct DocumentStore {
+    documents: Arc<RwLock<HashMap<String, TextDocument>>>,
+}
+
+impl DocumentStore {
+    /// Create an empty document store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Track a newly opened document
+    pub async fn open(&self, document: TextDocument) {
+        self.documents
+            .write()
+            .await
+            .insert(document.uri.clone(), document);
+    }
+
+    /// Apply content changes to an open document
+    pub async fn change(
+        &self,
+        uri: &str,
+        version: i64,
+        changes: &[ContentChange],
+    ) -> IdeResult<()> {
+        let mut documents = self.documents.write().await;
+        let document = documents.get_mut(uri).ok_or_else(|| {
+            IdeError::communication_error(format!("Document is not open: {}", uri))
+        })?;
+        for change in changes {
+            document.apply_change(change);
+        }
+        document.version = version;
+        Ok(())
+    }
+
+    /// Stop tracking a closed document
+    pub async fn close(&self, uri: &str) {
+        self.documents.write().await.remove(uri);
+    }
+
+    /// Get an open document
+    pub async fn get(&self, uri: &str) -> Option<TextDocument> {
+        self.documents.read().await.get(uri).cloned()
+    }
+
+    /// Number of open documents
+    pub async fn len(&self) -> usize {
+        self.documents.read().await.len()
+    }
+
+    /// Whether no documents are open
+    pub async fn is_empty(&self) -> bool {
+        self.documents.read().await.is_empty()
+    }
+}
+
+/// A text edit in a VS Code buffer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextEdit {
+    /// Range to replace
+    pub range: Range,
+    /// Replacement text
+    pub new_text: String,
+}
+
+/// Edits across documents, keyed by document URI
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WorkspaceEdit {
+    /// Edits for each document
+    pub changes: HashMap<String, Vec<TextEdit>>,
+}
+
+impl WorkspaceEdit {
+    /// Add an edit to a document
+    pub fn add(&mut self, uri: impl Into<String>, edit: TextEdit) {
+        self.changes.entry(uri.into()).or_default().push(edit);
+    }
+
+    /// Whether the edit changes nothing
+    pub fn is_empty(&self) -> bool {
+        self.changes.values().all(|edits| edits.is_empty())
+    }
+}
+
+/// A code action offered to VS Code
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CodeAction {
+    /// Title shown in the lightbulb menu
+    pub title: String,
+    /// Code action kind (e.g. `quickfix`, `refactor`)
+    pub kind: String,
+    /// Diagnostics the action resolves
+    #[serde(default)]
+    pub diagnostics: Vec<Diagnostic>,
+    /// Edit applied when the action is chosen
+    #[serde(default)]
+    pub edit: Option<WorkspaceEdit>,
+}
+
+/// `ricecoder/chat` request parameters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatParams {
+    /// User message
+    pub message: String,
+    /// Active document URI
+    #[serde(default)]
+    pub uri: Option<String>,
+    /// Selection in the active document
+    #[serde(default)]
+    pub selection: Option<Range>,
+}
+
+/// Reply to a chat message
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChatReply {
+    /// Assistant response
+    pub content: String,
+    /// Edits to apply to VS Code buffers
+    #[serde(default)]
+    pub edit: Option<WorkspaceEdit>,
+}
+
+/// Status of the active ricecoder session
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionStatus {
+    /// Active session ID
+    pub session_id: Option<String>,
+    /// Active model
+    pub model: Option<String>,
+    /// Whether a response is being generated
+    pub busy: bool,
+    /// Number of messages in the session
+    pub message_count: usize,
+}
+
+/// Capabilities the bridge needs from the rest of ricecoder
+#[async_trait]
+pub trait BridgeHandler: Send + Sync {
+    /// Answer a chat message, with the active document if one is open
+    async fn chat(
+        &self,
+        params: ChatParams,
+        document: Option<TextDocument>,
+    ) -> IdeResult<ChatReply>;
+
+    /// Code actions for a range of a document
+    async fn code_actions(
+        &self,
+        _document: &TextDocument,
+        _range: Range,
+        _diagnostics: &[Diagnostic],
+    ) -> IdeResult<Vec<CodeAction>> {
+        Ok(Vec::new())
+    }
+
+    /// Status of the active session
+    async fn session_status(&self) -> IdeResult<SessionStatus>;
+}
+
+/// Read one `Content-Length` framed message; `None` at end of stream
+pub async fn read_message<R>(reader: &mut R) -> IdeResult<Option<Value>>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let mut content_length = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).await? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = Some(value.trim().parse::<usize>().map_err(|_| {
+                    IdeError::communication_error(format!("Invalid Content-Length: {}", value))
+                })?);
+            }
+        }
+    }
+
+    let length = content_length
+        .ok_or_else(|| IdeError::communication_error("Missing Content-Length header"))?;
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).await?;
+    Ok(Some(serde_json::from_slice(&body)?))
+}
+
+/// Write one `Content-Length` framed message
+pub async fn write_message<W>(writer: &mut W, message: &Value) -> IdeResult<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    let body = serde_json::to_vec(message)?;
+    writer
+        .write_all(format!("Content-Length: {}\r\n\r\n", body.len()).as_bytes())
+        .await?;
+    writer.write_all(&body).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+type PendingRequests = Arc<Mutex<HashMap<u64, oneshot::Sender<Result<Value, Value>>>>>;
+
+/// A connected VS Code extension
+#[derive(Clone)]
+pub struct BridgeConnection {
+    writer: Arc<Mutex<Box<dyn AsyncWrite + Send + Unpin>>>,
+    pending: PendingRequests,
+    next_id: Arc<AtomicU64>,
+}
+
+impl BridgeConnection {
+    /// Send a notification to the extension
+    pub async fn notify(&self, method: &str, params: Value) -> IdeResult<()> {
+        self.send(&json!({ "jsonrpc": "2.0", "method": method, "params": params }))
+            .await
+    }
+
+    /// Send a request to the extension and wait for its result
+    pub async fn request(&self, method: &str, params: Value) -> IdeResult<Value> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let (sender, receiver) = oneshot::channel();
+        self.pending.lock().await.insert(id, sender);
+
+        let message = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+        if let Err(e) = self.send(&message).await {
+            self.pending.lock().await.remove(&id);
+            return Err(e);
+        }
+
+        match receiver.await {
+            Ok(Ok(result)) => Ok(result),
+            Ok(Err(error)) => Err(IdeError::communication_error(format!(
+                "{} failed: {}",
+                method, error
+            ))),
+            Err(_) => Err(IdeError::communication_error("Connection closed")),
+        }
+    }
+
+    /// Apply an edit to VS Code buffers with `workspace/applyEdit`
+    ///
+    /// Returns whether VS Code applied the edit.
+    pub async fn apply_edit(&self, label: &str, edit: &WorkspaceEdit) -> IdeResult<bool> {
+        let result = self
+            .request(
+                "workspace/applyEdit",
+                json!({ "label": label, "edit": edit }),
+            )
+            .await?;
+        Ok(result
+            .get("applied")
+            .and_then(Value::as_bool)
+            .unwrap_or(false))
+    }
+
+    async fn send(&self, message: &Value) -> IdeResult<()> {
+        let mut writer = self.writer.lock().await;
+        write_message(&mut *writer, message).await
+    }
+
+    async fn respond(&self, id: Value, result: IdeResult<Value>, code: i64) -> IdeResult<()> {
+        let message = match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(e) => json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": code, "message": e.to_string() },
+            }),
+        };
+        self.send(&message).await
+    }
+}
+
+/// JSON-RPC bridge between VS Code and ricecoder
+pub struct VsCodeBridge {
+    manager: Arc<IdeIntegrationManager>,
+    handler: Arc<dyn BridgeHandler>,
+    documents: DocumentStore,
+    active: RwLock<Option<BridgeConnection>>,
+}
+
+impl VsCodeBridge {
+    /// Create a new bridge
+    pub fn new(manager: Arc<IdeIntegrationManager>, handler: Arc<dyn BridgeHandler>) -> Self {
+        VsCodeBridge {
+            manager,
+            handler,
+            documents: DocumentStore::new(),
+            active: RwLock::new(None),
+        }
+    }
+
+    /// Documents open in VS Code
+    pub fn documents(&self) -> &DocumentStore {
+        &self.documents
+    }
+
+    /// Bind the bridge socket on localhost at the configured port
+    pub async fn bind(config: &VsCodeConfig) -> IdeResult<TcpListener> {
+        if !config.enabled {
+            return Err(IdeError::communication_error(
+                "VS Code integration is not enabled in configuration",
+            ));
+        }
+        let address = SocketAddr::from(([127, 0, 0, 1], config.port));
+        let listener = TcpListener::bind(address).await?;
+        info!("VS Code bridge listening on {}", listener.local_addr()?);
+        Ok(listener)
+    }
+
+    /// Accept extension connections until the listener fails
+    pub async fn serve(self: Arc<Self>, listener: TcpListener) -> IdeResult<()> {
+        loop {
+            let (stream, peer) = listener.accept().await?;
+            info!("VS Code extension connected from {}", peer);
+            let bridge = self.clone();
+            tokio::spawn(async move {
+                bridge.run(stream).await;
+            });
+        }
+    }
+
+    /// Serve a single connection until the extension disconnects
+    pub async fn run<S>(self: Arc<Self>, stream: S)
+    where
+        S: AsyncRead + AsyncWrite + Send + 'static,
+    {
+        let (reader, writer) = tokio::io::split(stream);
+        let mut reader = tokio::io::BufReader::new(reader);
+        let connection = BridgeConnection {
+            writer: Arc::new(Mutex::new(Box::new(writer))),
+            pending: Arc::new(Mutex::new(HashMap::new())),
+            next_id: Arc::new(AtomicU64::new(1)),
+        };
+        *self.active.write().await = Some(connection.clone());
+
+        loop {
+            let message = match read_message(&mut reader).await {
+                Ok(Some(message)) => message,
+                Ok(None) => break,
+                Err(IdeError::SerializationError(e)) => {
+                    let error = IdeError::SerializationError(e);
+                    if connection
+                        .respond(Value::Null, Err(error), PARSE_ERROR)
+                        .await
+                        .is_err()
+                    {
+                        break;
+                    }
+                    continue;
+                }
+                Err(e) => {
+                    warn!("VS Code bridge connection failed: {}", e);
+                    break;
+                }
+            };
+
+            if message.get("method").and_then(Value::as_str) == Some("exit") {
+                break;
+            }
+            self.dispatch(&connection, message).await;
+        }
+
+        // Fail requests still waiting for the extension
+        connection.pending.lock().await.clear();
+        info!("VS Code extension disconnected");
+    }
+
+    /// Apply an edit through the most recently connected extension
+    pub async fn apply_edit(&self, label: &str, edit: &WorkspaceEdit) -> IdeResult<bool> {
+        let connection = self
+            .active
+            .read()
+            .await
+            .clone()
+            .ok_or_else(|| IdeError::communication_error("No VS Code extension connected"))?;
+        connection.apply_edit(label, edit).await
+    }
+
+    async fn dispatch(self: &Arc<Self>, connection: &BridgeConnection, message: Value) {
+        let method = message
+            .get("method")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        let id = message.get("id").cloned();
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+        match (method, id) {
+            // Request: handled in its own task so responses to our own
+            // requests (e.g. workspace/applyEdit) keep flowing meanwhile
+            (Some(method), Some(id)) => {
+                let bridge = self.clone();
+                let connection = connection.clone();
+                tokio::spawn(async move {
+                    let (result, code) = bridge.handle_request(&connection, &method, params).await;
+                    if let Err(e) = connection.respond(id, result, code).await {
+                        warn!("Failed to respond to {}: {}", method, e);
+                    }
+                });
+            }
+            (Some(method), None) => {
+                if let Err(e) = self.handle_notification(&method, params).await {
+                    warn!("Failed to handle {}: {}", method, e);
+                }
+            }
+            (None, Some(id)) => {
+                let Some(id) = id.as_u64() else {
+                    return;
+                };
+                if let Some(sender) = connection.pending.lock().await.remove(&id) {
+                    let outcome = match message.get("error") {
+                        Some(error) => Err(error.clone()),
+                        None => Ok(message.get("result").cloned().unwrap_or(Value::Null)),
+                    };
+                    let _ = sender.send(outcome);
+                }
+            }
+            (None, None) => debug!("Ignoring message without method or id"),
+        }
+    }
+
+    async fn handle_notification(&self, method: &str, params: Value) -> IdeResult<()> {
+        match method {
+            "textDocument/didOpen" => {
+                #[derive(Deserialize)]
+                #[serde(rename_all = "camelCase")]
+                struct DidOpen {
+                    text_document: TextDocument,
+                }
+                let params: DidOpen = serde_json::from_value(params)?;
+                debug!("Opened {}", params.text_document.uri);
+                self.documents.open(params.text_document).await;
+            }
+            "textDocument/didChange" => {
+                #[derive(Deserialize)]
+                #[serde(rename_all = "camelCase")]
+                struct DidChange {
+                    text_document: VersionedDocument,
+                    content_changes: Vec<ContentChange>,
+                }
+                let params: DidChange = serde_json::from_value(params)?;
+                self.documents
+                    .change(
+                        &params.text_document.uri,
+                        params.text_document.version,
+                        &params.content_changes,
+                    )
+                    .await?;
+            }
+            "textDocument/didClose" => {
+                let params: DocumentParams = serde_json::from_value(params)?;
+                debug!("Closed {}", params.text_document.uri);
+                self.documents.close(&params.text_document.uri).await;
+            }
+            _ => debug!("Ignoring notification: {}", method),
+        }
+        Ok(())
+    }
+
+    async fn handle_request(
+        &self,
+        connection: &BridgeConnection,
+        method: &str,
+        params: Value,
+    ) -> (IdeResult<Value>, i64) {
+        let result = match method {
+            "initialize" => Ok(json!({
+                "capabilities": {
+                    "textDocumentSync": { "openClose": true, "change": 2 },
+                    "completionProvider": {},
+                    "codeActionProvider": true,
+                    "chat": true,
+                    "sessionStatus": true,
+                },
+                "serverInfo": { "name": "ricecoder", "version": env!("CARGO_PKG_VERSION") },
+            })),
+            "shutdown" => Ok(Value::Null),
+            "textDocument/completion" => self.completion(params).await,
+            "textDocument/codeAction" => self.code_action(params).await,
+            "ricecoder/chat" => self.chat(connection, params).await,
+            "ricecoder/sessionStatus" => self
+                .handler
+                .session_status()
+                .await
+                .and_then(|status| Ok(serde_json::to_value(status)?)),
+            _ => {
+                return (
+                    Err(IdeError::communication_error(format!(
+                        "Unknown method: {}",
+                        method
+                    ))),
+                    METHOD_NOT_FOUND,
+                )
+            }
+        };
+
+        let code = match &result {
+            Err(IdeError::SerializationError(_)) => INVALID_PARAMS,
+            _ => INTERNAL_ERROR,
+        };
+        (result, code)
+    }
+
+    async fn document(&self, uri: &str) -> IdeResult<TextDocument> {
+        self.documents
+            .get(uri)
+            .await
+            .ok_or_else(|| IdeError::communication_error(format!("Document is not open: {}", uri)))
+    }
+
+    async fn completion(&self, params: Value) -> IdeResult<Value> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Completion {
+            text_document: DocumentIdentifier,
+            position: Position,
+        }
+        let params: Completion = serde_json::from_value(params)?;
+        let document = self.document(&params.text_document.uri).await?;
+
+        let items = self
+            .manager
+            .handle_completion_request(&CompletionParams {
+                language: document.language_id.clone(),
+                file_path: document.file_path().to_string(),
+                position: params.position,
+                context: document.text.clone(),
+            })
+            .await?;
+        Ok(json!({ "isIncomplete": false, "items": items }))
+    }
+
+    async fn code_action(&self, params: Value) -> IdeResult<Value> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct CodeActionRequest {
+            text_document: DocumentIdentifier,
+            range: Range,
+        }
+        let params: CodeActionRequest = serde_json::from_value(params)?;
+        let document = self.document(&params.text_document.uri).await?;
+
+        let diagnostics: Vec<Diagnostic> = self
+            .manager
+            .handle_diagnostics_request(&DiagnosticsParams {
+                language: document.language_id.clone(),
+                file_path: document.file_path().to_string(),
+                source: document.text.clone(),
+            })
+            .await?
+            .into_iter()
+            .filter(|d| {
+                d.range.start.line <= params.range.end.line
+                    && d.range.end.line >= params.range.start.line
+            })
+            .collect();
+
+        let actions = self
+            .handler
+            .code_actions(&document, params.range, &diagnostics)
+            .await?;
+        Ok(serde_json::to_value(actions)?)
+    }
+
+    async fn chat(&self, connection: &BridgeConnection, params: Value) -> IdeResult<Value> {
+        let params: ChatParams = serde_json::from_value(params)?;
+        let document = match &params.uri {
+            Some(uri) => self.documents.get(uri).await,
+            None => None,
+        };
+
+        let reply = self.handler.chat(params, document).await?;
+        let applied = match &reply.edit {
+            Some(edit) if !edit.is_empty() => {
+                Some(connection.apply_edit("ricecoder chat", edit).await?)
+            }
+            _ => None,
+        };
+
+        Ok(json!({ "content": reply.content, "applied": applied }))
+    }
+}
+
+#[derive(Deserialize)]
+struct DocumentIdentifier {
+    uri: String,
+}
+
+#[derive(Deserialize)]
+struct VersionedDocument {
+    uri: String,
+    version: i64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DocumentParams {
+    text_document: DocumentIdentifier,
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::{BufReader, DuplexStream, ReadHalf, WriteHalf};
+
+    use super::*;
+    use crate::{
+        generic_provider::GenericProvider,
+        provider_chain::{ProviderChainManager, ProviderRegistry},
+    };
+
+    struct EchoHandler;
+
+    #[async_trait]
+    impl BridgeHandler for EchoHandler {
+        async fn chat(
+            &self,
+            params: ChatParams,
+            document: Option<TextDocument>,
+        ) -> IdeResult<ChatReply> {
+            let edit = document.map(|document| {
+                let mut edit = WorkspaceEdit::default();
+                edit.add(
+                    document.uri,
+                    TextEdit {
+                        range: Range {
+                            start: Position {
+                                line: 0,
+                                character: 0,
+                            },
+                            end: Position {
+                                line: 0,
+                                character: 0,
+                            },
+                        },
+                        new_text: "// reviewed\n".to_string(),
+                    },
+                );
+                edit
+            });
+            Ok(ChatReply {
+                content: format!("echo: {}", params.message),
+                edit,
+            })
+        }
+
+        async fn code_actions(
+            &self,
+            _document: &TextDocument,
+            _range: Range,
+            diagnostics: &[Diagnostic],
+        ) -> IdeResult<Vec<CodeAction>> {
+            Ok(vec![CodeAction {
+                title: "Explain with ricecoder".to_string(),
+                kind: "quickfix".to_string(),
+                diagnostics: diagnostics.to_vec(),
+                edit: None,
+            }])
+        }
+
+        async fn session_status(&self) -> IdeResult<SessionStatus> {
+            Ok(SessionStatus {
+                session_id: Some("session-1".to_string()),
+                model: Some("test-model".to_string()),
+                busy: false,
+                message_count: 3,
+            })
+        }
+    }
+
+    fn bridge() -> Arc<VsCodeBridge> {
+        let config = IdeIntegrationConfig {
+            vscode: None,
+            jetbrains: None,
+            terminal: None,
+            providers: ProviderChainConfig {
+                external_lsp: ExternalLspConfig {
+                    enabled: false,
+                    servers: HashMap::new(),
+                    health_check_interval_ms: 5000,
+                },
+                configured_rules: None,
+                builtin_providers: BuiltinProvidersConfig {
+                    enabled: false,
+                    languages: Vec::new(),
+                },
+            },
+        };
+        let chain = Arc::new(ProviderChainManager::new(ProviderRegistry::new(Arc::new(
+            GenericProvider::new(),
+        ))));
+        Arc::new(VsCodeBridge::new(
+            Arc::new(IdeIntegrationManager::new(config, chain)),
+            Arc::new(EchoHandler),
+        ))
+    }
+
+    struct Client {
+        reader: BufReader<ReadHalf<DuplexStream>>,
+        writer: WriteHalf<DuplexStream>,
+    }
+
+    impl Client {
+        fn connect(bridge: &Arc<VsCodeBridge>) -> Self {
+            let (client, server) = tokio::io::duplex(64 * 1024);
+            tokio::spawn(bridge.clone().run(server));
+            let (reader, writer) = tokio::io::split(client);
+            Client {
+                reader: BufReader::new(reader),
+                writer,
+            }
+        }
+
+        async fn send(&mut self, message: Value) {
+            write_message(&mut self.writer, &message).await.unwrap();
+        }
+
+        async fn receive(&mut self) -> Value {
+            read_message(&mut self.reader).await.unwrap().unwrap()
+        }
+
+        async fn request(&mut self, id: u64, method: &str, params: Value) -> Value {
+            self.send(json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }))
+                .await;
+            self.receive().await
+        }
+
+        async fn open(&mut self, uri: &str, text: &str) {
+            self.send(json!({
+                "jsonrpc": "2.0",
+                "method": "textDocument/didOpen",
+                "params": { "textDocument": {
+                    "uri": uri, "languageId": "rust", "version": 1, "text": text,
+                }},
+            }))
+            .await;
+        }
+    }
+
+    fn position(line: u32, character: u32) -> Position {
+        Position { line, character }
+    }
+
+    #[test]
+    fn test_apply_incremental_change() {
+        let mut document = TextDocument {
+            uri: "file:///tmp/main.rs".to_string(),
+            language_id: "rust".to_string(),
+            version: 1,
+            text: "fn main() {\n    let café = 1;\n}\n".to_string(),
+        };
+
+        document.apply_change(&ContentChange {
+            range: Some(Range {
+                start: position(1, 15),
+                end: position(1, 16),
+            }),
+            text: "2".to_string(),
+        });
+        assert_eq!(document.text, "fn main() {\n    let café = 2;\n}\n");
+
+        document.apply_change(&ContentChange {
+            range: None,
+            text: "fn main() {}\n".to_string(),
+        });
+        assert_eq!(document.text, "fn main() {}\n");
+        assert_eq!(document.file_path(), "/tmp/main.rs");
+        assert_eq!(document.offset_at(position(9, 0)), document.text.len());
+    }
+
+    #[tokio::test]
+    async fn test_message_framing_round_trip() {
+        let (client, server) = tokio::io::duplex(1024);
+        let (_, mut writer) = tokio::io::split(client);
+        let (reader, _) = tokio::io::split(server);
+        let mut reader = BufReader::new(reader);
+
+        let message = json!({ "jsonrpc": "2.0", "method": "exit" });
+        write_message(&mut writer, &message).await.unwrap();
+        drop(writer);
+
+        assert_eq!(read_message(&mut reader).await.unwrap(), Some(message));
+    }
+
+    #[tokio::test]
+    async fn test_document_sync_and_completion() {
+        let bridge = bridge();
+        let mut client = Client::connect(&bridge);
+        let uri = "file:///tmp/lib.rs";
+
+        let response = client.request(1, "initialize", json!({})).await;
+        assert_eq!(response["result"]["serverInfo"]["name"], "ricecoder");
+
+        client.open(uri, "fn alpha() {}\n").await;
+        client
+            .send(json!({
+                "jsonrpc": "2.0",
+                "method": "textDocument/didChange",
+                "params": {
+                    "textDocument": { "uri": uri, "version": 2 },
+                    "contentChanges": [{
+                        "range": { "start": position(0, 3), "end": position(0, 8) },
+                        "text": "omega",
+                    }],
+                },
+            }))
+            .await;
+
+        let response = client
+            .request(
+                2,
+                "textDocument/completion",
+                json!({ "textDocument": { "uri": uri }, "position": position(0, 0) }),
+            )
+            .await;
+        let labels: Vec<&str> = response["result"]["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|item| item["label"].as_str().unwrap())
+            .collect();
+        assert!(labels.contains(&"omega"));
+
+        let document = bridge.documents().get(uri).await.unwrap();
+        assert_eq!(document.version, 2);
+        assert_eq!(document.text, "fn omega() {}\n");
+
+        client
+            .send(json!({
+                "jsonrpc": "2.0",
+                "method": "textDocument/didClose",
+                "params": { "textDocument": { "uri": uri } },
+            }))
+            .await;
+        let response = client
+            .request(
+                3,
+                "textDocument/completion",
+                json!({ "textDocument": { "uri": uri }, "position": position(0, 0) }),
+            )
+            .await;
+        assert_eq!(response["error"]["code"], INTERNAL_ERROR);
+        assert!(bridge.documents().is_empty().await);
+    }
+
+    #[tokio::test]
+    async fn test_chat_applies_edits_in_vscode() {
+        let bridge = bridge();
+        let mut client = Client::connect(&bridge);
+        let uri = "file:///tmp/lib.rs";
+        client.open(uri, "fn main() {}\n").await;
+
+        client
+            .send(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "ricecoder/chat",
+                "params": { "message": "review this", "uri": uri },
+            }))
+            .await;
+
+        let apply = client.receive().await;
+        assert_eq!(apply["method"], "workspace/applyEdit");
+        assert_eq!(
+            apply["params"]["edit"]["changes"][uri][0]["newText"],
+            "// reviewed\n"
+        );
+        client
+            .send(json!({ "jsonrpc": "2.0", "id": apply["id"], "result": { "applied": true } }))
+            .await;
+
+        let response = client.receive().await;
+        assert_eq!(response["id"], 1);
+        assert_eq!(response["result"]["content"], "echo: review this");
+        assert_eq!(response["result"]["applied"], true);
+    }
+
+    #[tokio::test]
+    async fn test_code_actions_and_session_status() {
+        let bridge = bridge();
+        let mut client = Client::connect(&bridge);
+        let uri = "file:///tmp/lib.rs";
+        client.open(uri, "fn main() {\n").await;
+
+        let response = client
+            .request(
+                1,
+                "textDocument/codeAction",
+                json!({
+                    "textDocument": { "uri": uri },
+                    "range": { "start": position(0, 0), "end": position(0, 5) },
+                }),
+            )
+            .await;
+        assert_eq!(response["result"][0]["title"], "Explain with ricecoder");
+
+        let response = client
+            .request(2, "ricecoder/sessionStatus", json!(null))
+            .await;
+        assert_eq!(response["result"]["sessionId"], "session-1");
+        assert_eq!(response["result"]["messageCount"], 3);
+
+        let response = client.request(3, "ricecoder/unknown", json!(null)).await;
+        assert_eq!(response["error"]["code"], METHOD_NOT_FOUND);
+
+        let response = client
+            .request(4, "textDocument/completion", json!({ "position": 1 }))
+            .await;
+        assert_eq!(response["error"]["code"], INVALID_PARAMS);
+    }
+
+    #[tokio::test]
+    async fn test_bind_requires_enabled_config() {
+        let config = VsCodeConfig {
+            enabled: false,
+            port: 0,
+            features: Vec::new(),
+            settings: Value::Null,
+        };
+        assert!(VsCodeBridge::bind(&config).await.is_err());
+
+        let config = VsCodeConfig {
+            enabled: true,
+            ..config
+        };
+        let listener = VsCodeBridge::bind(&config).await.unwrap();
+        assert!(listener.local_addr().unwrap().ip().is_loopback());
+    }
+}
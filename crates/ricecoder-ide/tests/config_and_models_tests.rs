@@ -246,6 +246,7 @@ fn test_validate_config_valid_with_all_providers() {
             features: vec!["completion".to_string()],
             settings: serde_json::json!({}),
         }),
+        jetbrains: None,
         terminal: None,
         providers: ProviderChainConfig {
             external_lsp: ExternalLspConfig {
@@ -281,6 +282,7 @@ fn test_validate_config_valid_with_all_providers() {
 fn test_validate_config_no_providers_enabled() {
     let config = IdeIntegrationConfig {
         vscode: None,
+        jetbrains: None,
         terminal: None,
         providers: ProviderChainConfig {
             external_lsp: ExternalLspConfig {
@@ -306,6 +308,7 @@ fn test_validate_config_no_providers_enabled() {
 fn test_validate_config_empty_lsp_servers_when_enabled() {
     let config = IdeIntegrationConfig {
         vscode: None,
+        jetbrains: None,
         terminal: None,
         providers: ProviderChainConfig {
             external_lsp: ExternalLspConfig {
@@ -330,6 +333,7 @@ fn test_validate_config_empty_lsp_servers_when_enabled() {
 fn test_validate_config_invalid_lsp_command() {
     let config = IdeIntegrationConfig {
         vscode: None,
+        jetbrains: None,
         terminal: None,
         providers: ProviderChainConfig {
             external_lsp: ExternalLspConfig {
@@ -366,6 +370,7 @@ fn test_validate_config_invalid_lsp_command() {
 fn test_validate_config_invalid_lsp_timeout() {
     let config = IdeIntegrationConfig {
         vscode: None,
+        jetbrains: None,
         terminal: None,
         providers: ProviderChainConfig {
             external_lsp: ExternalLspConfig {
@@ -407,6 +412,7 @@ fn test_validate_config_invalid_vscode_port() {
             features: vec![],
             settings: serde_json::json!({}),
         }),
+        jetbrains: None,
         terminal: None,
         providers: ProviderChainConfig {
             external_lsp: ExternalLspConfig {
@@ -430,6 +436,7 @@ fn test_validate_config_invalid_vscode_port() {
 fn test_validate_config_configured_rules_enabled_without_path() {
     let config = IdeIntegrationConfig {
         vscode: None,
+        jetbrains: None,
         terminal: None,
         providers: ProviderChainConfig {
             external_lsp: ExternalLspConfig {
@@ -693,6 +700,7 @@ fn test_ide_integration_config_serialization() {
             features: vec!["completion".to_string()],
             settings: serde_json::json!({}),
         }),
+        jetbrains: None,
         terminal: None,
         providers: ProviderChainConfig {
             external_lsp: ExternalLspConfig {
@@ -27,6 +27,7 @@ fn test_valid_config_accepted() {
             features: vec!["completion".to_string()],
             settings: serde_json::json!({}),
         }),
+        jetbrains: None,
         terminal: None,
         providers: ProviderChainConfig {
             external_lsp: ExternalLspConfig {
@@ -56,6 +57,7 @@ fn test_invalid_vscode_port_zero_rejected() {
             features: vec!["completion".to_string()],
             settings: serde_json::json!({}),
         }),
+        jetbrains: None,
         terminal: None,
         providers: ProviderChainConfig {
             external_lsp: ExternalLspConfig {
@@ -91,6 +93,7 @@ fn test_invalid_vscode_port_zero_rejected() {
 fn test_no_providers_enabled_rejected() {
     let config = IdeIntegrationConfig {
         vscode: None,
+        jetbrains: None,
         terminal: None,
         providers: ProviderChainConfig {
             external_lsp: ExternalLspConfig {
@@ -131,6 +134,7 @@ fn test_no_providers_enabled_rejected() {
 fn test_empty_lsp_servers_rejected() {
     let config = IdeIntegrationConfig {
         vscode: None,
+        jetbrains: None,
         terminal: None,
         providers: ProviderChainConfig {
             external_lsp: ExternalLspConfig {
@@ -166,6 +170,7 @@ fn test_empty_lsp_servers_rejected() {
 fn test_empty_builtin_languages_rejected() {
     let config = IdeIntegrationConfig {
         vscode: None,
+        jetbrains: None,
         terminal: None,
         providers: ProviderChainConfig {
             external_lsp: ExternalLspConfig {
@@ -209,6 +214,7 @@ fn test_empty_vscode_features_rejected() {
             features: vec![],
             settings: serde_json::json!({}),
         }),
+        jetbrains: None,
         terminal: None,
         providers: ProviderChainConfig {
             external_lsp: ExternalLspConfig {
@@ -255,6 +261,7 @@ fn test_invalid_lsp_timeout_zero_rejected() {
 
     let config = IdeIntegrationConfig {
         vscode: None,
+        jetbrains: None,
         terminal: None,
         providers: ProviderChainConfig {
             external_lsp: ExternalLspConfig {
@@ -301,6 +308,7 @@ fn test_invalid_lsp_timeout_excessive_rejected() {
 
     let config = IdeIntegrationConfig {
         vscode: None,
+        jetbrains: None,
         terminal: None,
         providers: ProviderChainConfig {
             external_lsp: ExternalLspConfig {
@@ -347,6 +355,7 @@ fn test_invalid_lsp_empty_command_rejected() {
 
     let config = IdeIntegrationConfig {
         vscode: None,
+        jetbrains: None,
         terminal: None,
         providers: ProviderChainConfig {
             external_lsp: ExternalLspConfig {
@@ -382,6 +391,7 @@ fn test_invalid_lsp_empty_command_rejected() {
 fn test_invalid_builtin_language_rejected() {
     let config = IdeIntegrationConfig {
         vscode: None,
+        jetbrains: None,
         terminal: None,
         providers: ProviderChainConfig {
             external_lsp: ExternalLspConfig {
@@ -427,6 +437,7 @@ fn test_invalid_vscode_feature_rejected() {
             features: vec!["invalid_feature".to_string()],
             settings: serde_json::json!({}),
         }),
+        jetbrains: None,
         terminal: None,
         providers: ProviderChainConfig {
             external_lsp: ExternalLspConfig {
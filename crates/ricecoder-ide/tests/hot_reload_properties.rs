@@ -59,6 +59,7 @@ fn arb_ide_config() -> impl Strategy<Value = types::IdeIntegrationConfig> {
     (arb_external_lsp_config(), prop::bool::ANY).prop_map(|(external_lsp, builtin_enabled)| {
         types::IdeIntegrationConfig {
             vscode: None,
+            jetbrains: None,
             terminal: None,
             providers: types::ProviderChainConfig {
                 external_lsp,
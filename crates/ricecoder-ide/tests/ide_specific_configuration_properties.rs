@@ -11,14 +11,16 @@ use std::collections::HashMap;
 use proptest::prelude::*;
 use ricecoder_ide::{
     BuiltinProvidersConfig, CompletionItem, CompletionItemKind, Diagnostic, DiagnosticSeverity,
-    ExternalLspConfig, Hover, IdeConfigApplicator, IdeIntegrationConfig, IdeSpecificSettings,
-    IdeType, Position, ProviderChainConfig, Range, TerminalConfig, VsCodeConfig,
+    ExternalLspConfig, Hover, IdeCapabilities, IdeConfigApplicator, IdeIntegrationConfig,
+    IdeSpecificSettings, IdeType, JetBrainsConfig, Position, ProviderChainConfig, Range,
+    TerminalConfig, VsCodeConfig,
 };
 
 /// Strategy for generating IDE types
 fn ide_type_strategy() -> impl Strategy<Value = IdeType> {
     prop_oneof![
         Just(IdeType::VsCode),
+        Just(IdeType::JetBrains),
         Just(IdeType::Vim),
         Just(IdeType::Emacs),
     ]
@@ -78,6 +80,32 @@ fn create_test_config(ide_type: IdeType) -> IdeIntegrationConfig {
                     "timeout_ms": 5000,
                 }),
             }),
+            jetbrains: None,
+            terminal: None,
+            providers: ProviderChainConfig {
+                external_lsp: ExternalLspConfig {
+                    enabled: true,
+                    servers: HashMap::new(),
+                    health_check_interval_ms: 5000,
+                },
+                configured_rules: None,
+                builtin_providers: BuiltinProvidersConfig {
+                    enabled: true,
+                    languages: vec!["rust".to_string()],
+                },
+            },
+        },
+        IdeType::JetBrains => IdeIntegrationConfig {
+            vscode: None,
+            jetbrains: Some(JetBrainsConfig {
+                enabled: true,
+                port: 8081,
+                capabilities: IdeCapabilities::default(),
+                project_roots: Vec::new(),
+                settings: serde_json::json!({
+                    "max_completion_items": 20,
+                }),
+            }),
             terminal: None,
             providers: ProviderChainConfig {
                 external_lsp: ExternalLspConfig {
@@ -94,6 +122,7 @@ fn create_test_config(ide_type: IdeType) -> IdeIntegrationConfig {
         },
         IdeType::Vim => IdeIntegrationConfig {
             vscode: None,
+            jetbrains: None,
             terminal: Some(TerminalConfig {
                 vim: Some(ricecoder_ide::types::VimConfig {
                     enabled: true,
@@ -116,6 +145,7 @@ fn create_test_config(ide_type: IdeType) -> IdeIntegrationConfig {
         },
         IdeType::Emacs => IdeIntegrationConfig {
             vscode: None,
+            jetbrains: None,
             terminal: Some(TerminalConfig {
                 vim: None,
                 emacs: Some(ricecoder_ide::types::EmacsConfig {
@@ -138,6 +168,7 @@ fn create_test_config(ide_type: IdeType) -> IdeIntegrationConfig {
         },
         IdeType::Unknown => IdeIntegrationConfig {
             vscode: None,
+            jetbrains: None,
             terminal: None,
             providers: ProviderChainConfig {
                 external_lsp: ExternalLspConfig {
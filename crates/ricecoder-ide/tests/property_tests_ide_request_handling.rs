@@ -343,6 +343,7 @@ fn create_test_config() -> IdeIntegrationConfig {
             features: vec!["completion".to_string()],
             settings: serde_json::json!({}),
         }),
+        jetbrains: None,
         terminal: Some(TerminalConfig {
             vim: Some(types::VimConfig {
                 enabled: true,
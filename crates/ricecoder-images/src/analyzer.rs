@@ -293,6 +293,10 @@ impl ImageAnalyzer {
             temperature: Some(0.7),
             max_tokens: Some(1000),
             stream: false,
+            top_p: None,
+            stop: None,
+            seed: None,
+            reasoning_effort: None,
         };
 
         // Send request to provider
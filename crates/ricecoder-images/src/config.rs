@@ -1,10 +1,10 @@
 //! Configuration for image support.
 
-use std::path::PathBuf;
+use std::{collections::BTreeMap, path::PathBuf};
 
 use serde::{Deserialize, Serialize};
 
-use crate::error::ImageResult;
+use crate::{error::ImageResult, protocols::ImageProtocol};
 
 /// Image support configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -35,6 +35,26 @@ pub struct DisplayConfig {
     pub max_height: u32,
     /// ASCII placeholder character
     pub placeholder_char: String,
+    /// Inline image protocol to use instead of the detected one
+    #[serde(default)]
+    pub protocol: Option<ImageProtocol>,
+    /// Protocol overrides by terminal name (e.g. `WezTerm: sixel`)
+    #[serde(default)]
+    pub terminal_overrides: BTreeMap<String, ImageProtocol>,
+    /// Width of a terminal cell in pixels, used to downscale images
+    #[serde(default = "default_cell_width_px")]
+    pub cell_width_px: u32,
+    /// Height of a terminal cell in pixels, used to downscale images
+    #[serde(default = "default_cell_height_px")]
+    pub cell_height_px: u32,
+}
+
+fn default_cell_width_px() -> u32 {
+    10
+}
+
+fn default_cell_height_px() -> u32 {
+    20
 }
 
 /// Cache settings configuration.
@@ -91,6 +111,10 @@ impl Default for DisplayConfig {
             max_width: 80,
             max_height: 30,
             placeholder_char: "█".to_string(),
+            protocol: None,
+            terminal_overrides: BTreeMap::new(),
+            cell_width_px: default_cell_width_px(),
+            cell_height_px: default_cell_height_px(),
         }
     }
 }
@@ -1,33 +1,55 @@
-//! Terminal display of images with ASCII fallback.
+//! Terminal display of images via inline image protocols with ASCII fallback.
 
 use crate::{
     config::DisplayConfig,
     error::{ImageError, ImageResult},
     models::ImageMetadata,
+    protocols::{self, ImageProtocol, ProtocolSupport},
 };
 
 /// Displays images in the terminal with ASCII fallback support.
 ///
 /// Provides terminal rendering of images with:
 /// - Metadata display (format, size, dimensions)
+/// - Sixel and iTerm2/WezTerm inline images when the terminal supports them
 /// - ASCII placeholder for unsupported terminals
 /// - Automatic resizing to fit terminal bounds (max 80x30)
 /// - Multi-image support with vertical organization
 pub struct ImageDisplay {
     pub(crate) config: DisplayConfig,
+    support: ProtocolSupport,
 }
 
 impl ImageDisplay {
     /// Create a new image display with default configuration.
     pub fn new() -> Self {
-        Self {
-            config: DisplayConfig::default(),
-        }
+        Self::with_config(DisplayConfig::default())
     }
 
     /// Create a new image display with custom configuration.
     pub fn with_config(config: DisplayConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            support: ProtocolSupport::default(),
+        }
+    }
+
+    /// Set the inline image protocols supported by the terminal.
+    ///
+    /// Without this only the ASCII placeholder is used, unless the
+    /// configuration forces a protocol.
+    pub fn with_protocol_support(mut self, support: ProtocolSupport) -> Self {
+        self.support = support;
+        self
+    }
+
+    /// The protocol images will be rendered with.
+    pub fn protocol(&self) -> ImageProtocol {
+        protocols::resolve_protocol(
+            &self.support,
+            self.config.protocol,
+            &self.config.terminal_overrides,
+        )
     }
 
     /// Render a single image with metadata.
@@ -46,12 +68,69 @@ impl ImageDisplay {
         output.push_str(&self.render_metadata(metadata)?);
         output.push('\n');
 
-        // Add ASCII placeholder
-        output.push_str(&self.render_ascii_placeholder());
+        // Draw the image inline if possible, otherwise add ASCII placeholder
+        let inline = match self.protocol() {
+            ImageProtocol::Ascii => None,
+            protocol => std::fs::read(&metadata.path)
+                .map_err(ImageError::from)
+                .and_then(|data| self.encode_inline(&data, protocol))
+                .map_err(|e| tracing::debug!("Inline image rendering failed: {}", e))
+                .ok(),
+        };
+        match inline {
+            Some(encoded) => {
+                output.push_str(&encoded);
+                output.push('\n');
+            }
+            None => output.push_str(&self.render_ascii_placeholder()),
+        }
 
         Ok(output)
     }
 
+    /// Render image data with metadata using the selected protocol.
+    ///
+    /// Unlike [`Self::render_image`], encoding errors are returned rather
+    /// than falling back to the ASCII placeholder.
+    pub fn render_image_data(&self, metadata: &ImageMetadata, data: &[u8]) -> ImageResult<String> {
+        let mut output = self.render_metadata(metadata)?;
+        output.push('\n');
+        match self.protocol() {
+            ImageProtocol::Ascii => output.push_str(&self.render_ascii_placeholder()),
+            protocol => {
+                output.push_str(&self.encode_inline(data, protocol)?);
+                output.push('\n');
+            }
+        }
+        Ok(output)
+    }
+
+    /// Decode, downscale to the display bounds and encode an image.
+    fn encode_inline(&self, data: &[u8], protocol: ImageProtocol) -> ImageResult<String> {
+        let image = image::load_from_memory(data)
+            .map_err(|e| ImageError::InvalidFile(format!("Failed to decode image: {}", e)))?;
+
+        let cell_width = self.config.cell_width_px.max(1);
+        let cell_height = self.config.cell_height_px.max(1);
+        let image = protocols::fit_to_box(
+            image,
+            self.config.max_width * cell_width,
+            self.config.max_height * cell_height,
+        );
+
+        match protocol {
+            ImageProtocol::Sixel => Ok(protocols::encode_sixel(&image.to_rgba8())),
+            ImageProtocol::Iterm2 => {
+                let cells = (
+                    image.width().div_ceil(cell_width),
+                    image.height().div_ceil(cell_height),
+                );
+                protocols::encode_iterm2(&image, cells)
+            }
+            ImageProtocol::Ascii => Ok(self.render_ascii_placeholder()),
+        }
+    }
+
     /// Render metadata for an image.
     ///
     /// # Arguments
@@ -337,6 +416,7 @@ mod tests {
             max_width: 100,
             max_height: 50,
             placeholder_char: "█".to_string(),
+            ..Default::default()
         };
         let display = ImageDisplay::with_config(config);
         assert_eq!(display.config.max_width, 100);
@@ -562,4 +642,73 @@ mod tests {
         assert!(result.is_ok());
         assert!(result.unwrap());
     }
+
+    fn png_bytes(width: u32, height: u32) -> Vec<u8> {
+        let image = image::DynamicImage::ImageRgba8(image::RgbaImage::from_pixel(
+            width,
+            height,
+            image::Rgba([0, 128, 255, 255]),
+        ));
+        let mut data = Vec::new();
+        image
+            .write_to(
+                &mut std::io::Cursor::new(&mut data),
+                image::ImageFormat::Png,
+            )
+            .unwrap();
+        data
+    }
+
+    #[test]
+    fn test_render_image_data_with_protocols() {
+        let metadata = ImageMetadata::new(
+            PathBuf::from("/path/to/image.png"),
+            ImageFormat::Png,
+            1024,
+            4000,
+            2000,
+            "abc123".to_string(),
+        );
+        let data = png_bytes(1600, 800);
+
+        let iterm = ImageDisplay::new().with_protocol_support(ProtocolSupport::new(
+            "iTerm.app",
+            false,
+            true,
+        ));
+        let rendered = iterm.render_image_data(&metadata, &data).unwrap();
+        assert!(rendered.contains("4000x2000"));
+        // Downscaled to the 80-cell width of the default configuration
+        assert!(rendered.contains("\x1b]1337;File=inline=1;"));
+        assert!(rendered.contains(";width=80;height=20;"));
+
+        let sixel =
+            ImageDisplay::new().with_protocol_support(ProtocolSupport::new("foot", true, false));
+        let rendered = sixel.render_image_data(&metadata, &data).unwrap();
+        assert!(rendered.contains("\x1bP0;1;0q\"1;1;800;400"));
+
+        let ascii = ImageDisplay::new();
+        assert!(ascii
+            .render_image_data(&metadata, &data)
+            .unwrap()
+            .contains("█"));
+    }
+
+    #[test]
+    fn test_render_image_falls_back_to_ascii() {
+        let display =
+            ImageDisplay::new().with_protocol_support(ProtocolSupport::new("WezTerm", true, true));
+        assert_eq!(display.protocol(), ImageProtocol::Iterm2);
+
+        let metadata = ImageMetadata::new(
+            PathBuf::from("/nonexistent/image.png"),
+            ImageFormat::Png,
+            1024,
+            800,
+            600,
+            "abc123".to_string(),
+        );
+        let rendered = display.render_image(&metadata).unwrap();
+        assert!(rendered.contains("█"));
+    }
 }
@@ -4,7 +4,7 @@
 //! - Format validation (PNG, JPG, GIF, WebP)
 //! - Image analysis via AI providers
 //! - Smart caching with LRU eviction
-//! - Terminal display with ASCII fallback
+//! - Terminal display via Sixel or iTerm2 inline images, with ASCII fallback
 //! - Multi-image support
 
 pub mod analyzer;
@@ -18,6 +18,7 @@ pub mod error;
 pub mod formats;
 pub mod handler;
 pub mod models;
+pub mod protocols;
 pub mod provider_integration;
 pub mod session_integration;
 pub mod session_manager;
@@ -36,6 +37,7 @@ pub use error::{ImageError, ImageResult};
 pub use formats::ImageFormat;
 pub use handler::ImageHandler;
 pub use models::{ImageAnalysisResult, ImageCacheEntry, ImageMetadata};
+pub use protocols::{ImageProtocol, ProtocolSupport};
 pub use provider_integration::{
     ChatRequestWithImages, ImageAuditLogEntry, ImageData, ProviderImageFormat,
 };
@@ -0,0 +1,319 @@
+//! Inline image protocols for terminal display.
+//!
+//! Supports the Sixel graphics protocol and the iTerm2 inline image protocol
+//! (also understood by WezTerm), with ASCII placeholders as the fallback.
+//! Capability flags are normally filled in from the TUI's terminal state
+//! detection; [`ProtocolSupport::from_env`] provides a best-effort guess for
+//! callers without it.
+
+use std::{collections::BTreeMap, io::Cursor};
+
+use base64::{engine::general_purpose, Engine as _};
+use image::{imageops::FilterType, DynamicImage, RgbaImage};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{ImageError, ImageResult};
+
+/// Inline image protocol used to draw an image in the terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageProtocol {
+    /// DEC Sixel graphics
+    Sixel,
+    /// iTerm2 inline images (OSC 1337), also supported by WezTerm
+    Iterm2,
+    /// ASCII placeholder
+    Ascii,
+}
+
+/// Inline image protocols supported by the current terminal.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProtocolSupport {
+    /// Terminal name, used to look up per-terminal overrides
+    pub terminal: Option<String>,
+    /// Sixel graphics are supported
+    pub sixel: bool,
+    /// iTerm2 inline images are supported
+    pub iterm2: bool,
+}
+
+impl ProtocolSupport {
+    /// Create support flags for a named terminal.
+    pub fn new(terminal: impl Into<String>, sixel: bool, iterm2: bool) -> Self {
+        Self {
+            terminal: Some(terminal.into()),
+            sixel,
+            iterm2,
+        }
+    }
+
+    /// Guess protocol support from environment variables.
+    ///
+    /// Prefer the TUI's terminal state detection where available; this only
+    /// looks at `TERM_PROGRAM`, `LC_TERMINAL` and `TERM`.
+    pub fn from_env() -> Self {
+        let term_program = std::env::var("TERM_PROGRAM").unwrap_or_default();
+        let lc_terminal = std::env::var("LC_TERMINAL").unwrap_or_default();
+        let term = std::env::var("TERM").unwrap_or_default();
+
+        let iterm2 =
+            matches!(term_program.as_str(), "iTerm.app" | "WezTerm") || lc_terminal == "iTerm2";
+        let sixel = term.contains("sixel")
+            || matches!(term_program.as_str(), "WezTerm" | "mlterm" | "foot")
+            || term.starts_with("foot")
+            || term == "mlterm";
+
+        let terminal = [term_program, lc_terminal, term]
+            .into_iter()
+            .find(|name| !name.is_empty());
+
+        Self {
+            terminal,
+            sixel,
+            iterm2,
+        }
+    }
+
+    /// Pick the best supported protocol.
+    ///
+    /// iTerm2 is preferred over Sixel because it transfers the image
+    /// losslessly and lets the terminal do the scaling.
+    pub fn best_protocol(&self) -> ImageProtocol {
+        if self.iterm2 {
+            ImageProtocol::Iterm2
+        } else if self.sixel {
+            ImageProtocol::Sixel
+        } else {
+            ImageProtocol::Ascii
+        }
+    }
+
+    /// Whether a protocol can be used.
+    pub fn supports(&self, protocol: ImageProtocol) -> bool {
+        match protocol {
+            ImageProtocol::Sixel => self.sixel,
+            ImageProtocol::Iterm2 => self.iterm2,
+            ImageProtocol::Ascii => true,
+        }
+    }
+}
+
+/// Resolve the protocol for a terminal, honouring overrides.
+///
+/// A per-terminal override wins over the global override, which wins over
+/// detection. Overrides are used even if detection did not report support,
+/// so users can enable protocols on terminals that are not recognised.
+pub fn resolve_protocol(
+    support: &ProtocolSupport,
+    global_override: Option<ImageProtocol>,
+    terminal_overrides: &BTreeMap<String, ImageProtocol>,
+) -> ImageProtocol {
+    support
+        .terminal
+        .as_ref()
+        .and_then(|terminal| {
+            terminal_overrides
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(terminal))
+                .map(|(_, protocol)| *protocol)
+        })
+        .or(global_override)
+        .unwrap_or_else(|| support.best_protocol())
+}
+
+/// Downscale an image to fit within a pixel box, preserving aspect ratio.
+///
+/// Images that already fit are returned unchanged; images are never enlarged.
+pub fn fit_to_box(image: DynamicImage, max_width: u32, max_height: u32) -> DynamicImage {
+    if image.width() <= max_width && image.height() <= max_height {
+        image
+    } else {
+        image.resize(max_width.max(1), max_height.max(1), FilterType::Triangle)
+    }
+}
+
+/// Encode an image with the iTerm2 inline image protocol.
+///
+/// `cells` is the size in terminal cells the image should occupy.
+pub fn encode_iterm2(image: &DynamicImage, cells: (u32, u32)) -> ImageResult<String> {
+    let mut png = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)
+        .map_err(|e| ImageError::DisplayError(format!("Failed to encode PNG: {}", e)))?;
+
+    Ok(format!(
+        "\x1b]1337;File=inline=1;size={};width={};height={};preserveAspectRatio=1:{}\x07",
+        png.len(),
+        cells.0,
+        cells.1,
+        general_purpose::STANDARD.encode(&png)
+    ))
+}
+
+/// Number of levels per channel of the Sixel palette (6 x 6 x 6 = 216 colours).
+const SIXEL_LEVELS: u32 = 6;
+
+/// Encode an image as Sixel graphics.
+///
+/// Colours are quantised to a fixed 216-colour cube; pixels that are more than
+/// half transparent are left unpainted.
+pub fn encode_sixel(image: &RgbaImage) -> String {
+    let (width, height) = image.dimensions();
+    let mut out = String::new();
+
+    // DCS with pixel aspect 1:1 and transparent background, then raster attributes
+    out.push_str("\x1bP0;1;0q");
+    out.push_str(&format!("\"1;1;{};{}", width, height));
+
+    for index in 0..SIXEL_LEVELS.pow(3) {
+        let (r, g, b) = palette_rgb(index);
+        out.push_str(&format!(
+            "#{};2;{};{};{}",
+            index,
+            r * 100 / 255,
+            g * 100 / 255,
+            b * 100 / 255
+        ));
+    }
+
+    let indices: Vec<Option<u32>> = image
+        .pixels()
+        .map(|pixel| {
+            let [r, g, b, a] = pixel.0;
+            (a >= 128).then(|| palette_index(r, g, b))
+        })
+        .collect();
+
+    for band_top in (0..height).step_by(6) {
+        let band_rows = (height - band_top).min(6);
+
+        // Collect the sixel bit pattern of every colour used in this band
+        let mut colours: BTreeMap<u32, Vec<u8>> = BTreeMap::new();
+        for x in 0..width {
+            for row in 0..band_rows {
+                let y = band_top + row;
+                if let Some(colour) = indices[(y * width + x) as usize] {
+                    colours
+                        .entry(colour)
+                        .or_insert_with(|| vec![0; width as usize])[x as usize] |= 1 << row;
+                }
+            }
+        }
+
+        for (i, (colour, bits)) in colours.iter().enumerate() {
+            if i > 0 {
+                // Return to the start of the band for the next colour
+                out.push('$');
+            }
+            out.push_str(&format!("#{}", colour));
+            push_run_length(&mut out, bits);
+        }
+        out.push('-');
+    }
+
+    out.push_str("\x1b\\");
+    out
+}
+
+/// Append sixel characters for a row of bit patterns, run-length encoded.
+fn push_run_length(out: &mut String, bits: &[u8]) {
+    let mut iter = bits.iter().peekable();
+    while let Some(&bit) = iter.next() {
+        let mut count = 1;
+        while iter.peek() == Some(&&bit) {
+            iter.next();
+            count += 1;
+        }
+        let ch = (b'?' + bit) as char;
+        if count > 3 {
+            out.push_str(&format!("!{}{}", count, ch));
+        } else {
+            out.extend(std::iter::repeat(ch).take(count));
+        }
+    }
+}
+
+fn palette_index(r: u8, g: u8, b: u8) -> u32 {
+    let level = |c: u8| (c as u32 * (SIXEL_LEVELS - 1) + 127) / 255;
+    level(r) * SIXEL_LEVELS * SIXEL_LEVELS + level(g) * SIXEL_LEVELS + level(b)
+}
+
+fn palette_rgb(index: u32) -> (u32, u32, u32) {
+    let channel = |level: u32| level * 255 / (SIXEL_LEVELS - 1);
+    (
+        channel(index / (SIXEL_LEVELS * SIXEL_LEVELS)),
+        channel(index / SIXEL_LEVELS % SIXEL_LEVELS),
+        channel(index % SIXEL_LEVELS),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use image::Rgba;
+
+    use super::*;
+
+    #[test]
+    fn test_resolve_protocol_prefers_overrides() {
+        let support = ProtocolSupport::new("WezTerm", true, true);
+        let mut overrides = BTreeMap::new();
+        assert_eq!(
+            resolve_protocol(&support, None, &overrides),
+            ImageProtocol::Iterm2
+        );
+        assert_eq!(
+            resolve_protocol(&support, Some(ImageProtocol::Sixel), &overrides),
+            ImageProtocol::Sixel
+        );
+
+        overrides.insert("wezterm".to_string(), ImageProtocol::Ascii);
+        assert_eq!(
+            resolve_protocol(&support, Some(ImageProtocol::Sixel), &overrides),
+            ImageProtocol::Ascii
+        );
+
+        let none = ProtocolSupport::default();
+        assert_eq!(
+            resolve_protocol(&none, None, &BTreeMap::new()),
+            ImageProtocol::Ascii
+        );
+    }
+
+    #[test]
+    fn test_fit_to_box_only_downscales() {
+        let image = DynamicImage::ImageRgba8(RgbaImage::new(400, 200));
+        let fitted = fit_to_box(image, 100, 100);
+        assert_eq!((fitted.width(), fitted.height()), (100, 50));
+
+        let small = DynamicImage::ImageRgba8(RgbaImage::new(10, 10));
+        let kept = fit_to_box(small, 100, 100);
+        assert_eq!((kept.width(), kept.height()), (10, 10));
+    }
+
+    #[test]
+    fn test_encode_sixel() {
+        let mut image = RgbaImage::new(8, 7);
+        for pixel in image.pixels_mut() {
+            *pixel = Rgba([255, 0, 0, 255]);
+        }
+
+        let sixel = encode_sixel(&image);
+        assert!(sixel.starts_with("\x1bP0;1;0q\"1;1;8;7"));
+        assert!(sixel.ends_with("\x1b\\"));
+        // Pure red is the last red level with no green or blue
+        let red = palette_index(255, 0, 0);
+        // First band: all six rows set, run-length encoded across 8 columns
+        assert!(sixel.contains(&format!("#{}!8~-", red)));
+        // Second band: only the top row set
+        assert!(sixel.contains(&format!("#{}!8@-", red)));
+    }
+
+    #[test]
+    fn test_encode_iterm2() {
+        let image = DynamicImage::ImageRgba8(RgbaImage::new(4, 4));
+        let encoded = encode_iterm2(&image, (2, 1)).unwrap();
+        assert!(encoded.starts_with("\x1b]1337;File=inline=1;size="));
+        assert!(encoded.contains(";width=2;height=1;"));
+        assert!(encoded.ends_with('\x07'));
+    }
+}
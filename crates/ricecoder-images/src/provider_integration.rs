@@ -416,6 +416,10 @@ mod tests {
             temperature: Some(0.7),
             max_tokens: Some(1000),
             stream: false,
+            top_p: None,
+            stop: None,
+            seed: None,
+            reasoning_effort: None,
         };
 
         let chat_with_images = ChatRequestWithImages::new(request, "openai");
@@ -434,6 +438,10 @@ mod tests {
             temperature: Some(0.7),
             max_tokens: Some(1000),
             stream: false,
+            top_p: None,
+            stop: None,
+            seed: None,
+            reasoning_effort: None,
         };
 
         let mut chat_with_images = ChatRequestWithImages::new(request, "openai");
@@ -455,6 +463,10 @@ mod tests {
             temperature: Some(0.7),
             max_tokens: Some(1000),
             stream: false,
+            top_p: None,
+            stop: None,
+            seed: None,
+            reasoning_effort: None,
         };
 
         let mut chat_with_images = ChatRequestWithImages::new(request, "openai");
@@ -244,6 +244,7 @@ fn prop_display_config_respected() {
             max_width,
             max_height,
             placeholder_char: "█".to_string(),
+            ..Default::default()
         };
         let display = ImageDisplay::with_config(config);
         let rendered = display.render_image(&metadata).expect("Should render image");
@@ -316,6 +317,7 @@ fn test_custom_placeholder_character() {
         max_width: 80,
         max_height: 30,
         placeholder_char: "▓".to_string(),
+        ..Default::default()
     };
     let display = ImageDisplay::with_config(config);
     let metadata = ImageMetadata::new(
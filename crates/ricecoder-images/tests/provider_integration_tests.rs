@@ -26,6 +26,10 @@ fn create_test_request() -> ChatRequest {
         temperature: Some(0.7),
         max_tokens: Some(1000),
         stream: false,
+        top_p: None,
+        stop: None,
+        seed: None,
+        reasoning_effort: None,
     }
 }
 
@@ -350,6 +350,7 @@ fn test_image_display_configuration() {
         max_width: 80,
         max_height: 30,
         placeholder_char: "█".to_string(),
+        ..Default::default()
     };
 
     let display = ImageDisplay::with_config(config);
@@ -2,7 +2,7 @@
 
 use std::collections::HashMap;
 
-use crate::models::{KeyCombo, Keybind};
+use crate::models::{KeyCombo, KeySequence, Keybind};
 
 /// Represents a conflict between multiple keybinds
 #[derive(Debug, Clone)]
@@ -11,6 +11,18 @@ pub struct Conflict {
     pub actions: Vec<String>,
 }
 
+/// A binding whose key sequence is a prefix of another binding's sequence
+///
+/// The shorter binding can never fire on its own: the engine keeps waiting for
+/// the longer sequence once the prefix has been typed.
+#[derive(Debug, Clone)]
+pub struct PrefixConflict {
+    pub prefix: KeySequence,
+    pub prefix_action: String,
+    pub sequence: KeySequence,
+    pub sequence_action: String,
+}
+
 /// Represents a suggested resolution for a conflict
 #[derive(Debug, Clone)]
 pub struct Resolution {
@@ -25,14 +37,16 @@ pub struct ConflictDetector;
 impl ConflictDetector {
     /// Detect all conflicts in a set of keybinds
     pub fn detect(keybinds: &[Keybind]) -> Vec<Conflict> {
-        let mut key_to_actions: HashMap<String, Vec<String>> = HashMap::new();
+        // Keyed by (key, when-clause): bindings guarded by different
+        // when-clauses are resolved at dispatch time and do not conflict
+        let mut key_to_actions: HashMap<(String, Option<String>), Vec<String>> = HashMap::new();
 
         // Build reverse index
         for keybind in keybinds {
             if let Ok(key_combo) = keybind.parse_key() {
                 let key_str = key_combo.to_string();
                 key_to_actions
-                    .entry(key_str)
+                    .entry((key_str, keybind.when.clone()))
                     .or_default()
                     .push(keybind.action_id.clone());
             }
@@ -40,7 +54,7 @@ impl ConflictDetector {
 
         // Find conflicts (keys with multiple actions)
         let mut conflicts = Vec::new();
-        for (key_str, actions) in key_to_actions {
+        for ((key_str, _), actions) in key_to_actions {
             if actions.len() > 1 {
                 if let Ok(key_combo) = key_str.parse() {
                     conflicts.push(Conflict { key_combo, actions });
@@ -51,6 +65,43 @@ impl ConflictDetector {
         conflicts
     }
 
+    /// Detect bindings that shadow each other through a shared key prefix
+    /// (e.g. `g` and `g g` in the same context)
+    pub fn detect_prefix_conflicts(keybinds: &[Keybind]) -> Vec<PrefixConflict> {
+        let parsed: Vec<(&Keybind, Vec<KeySequence>)> = keybinds
+            .iter()
+            .filter_map(|kb| kb.parse_all_sequences().ok().map(|seqs| (kb, seqs)))
+            .collect();
+
+        let mut conflicts = Vec::new();
+        for (short_kb, short_seqs) in &parsed {
+            for (long_kb, long_seqs) in &parsed {
+                if !Self::contexts_overlap(short_kb, long_kb) || short_kb.when != long_kb.when {
+                    continue;
+                }
+                for prefix in short_seqs {
+                    for sequence in long_seqs.iter().filter(|seq| prefix.is_prefix_of(seq)) {
+                        conflicts.push(PrefixConflict {
+                            prefix: prefix.clone(),
+                            prefix_action: short_kb.action_id.clone(),
+                            sequence: sequence.clone(),
+                            sequence_action: long_kb.action_id.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        conflicts
+    }
+
+    /// Check if two keybinds can be active at the same time
+    fn contexts_overlap(a: &Keybind, b: &Keybind) -> bool {
+        a.contexts.is_empty()
+            || b.contexts.is_empty()
+            || a.contexts.iter().any(|ctx| b.contexts.contains(ctx))
+    }
+
     /// Suggest resolutions for a conflict
     pub fn suggest_resolution(conflict: &Conflict, keybinds: &[Keybind]) -> Vec<Resolution> {
         let mut suggestions = Vec::new();
@@ -7,11 +7,12 @@ use crate::{
     error::EngineError,
     help::KeybindHelp,
     merge::KeybindMerger,
-    models::{Context, Key, KeyCombo, Keybind, Modifier},
+    models::{Context, ContextFrame, Key, KeyCombo, KeySequence, Keybind},
     parser::ParserRegistry,
     persistence::KeybindPersistence,
     profile::ProfileManager,
     registry::KeybindRegistry,
+    when::ContextState,
 };
 
 /// Leader key state for OpenCode compatibility (G02)
@@ -122,6 +123,17 @@ impl PendingKeyState {
     }
 }
 
+/// Outcome of feeding a key into the sequence state machine
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SequenceMatch {
+    /// A complete binding matched (action ID)
+    Matched(String),
+    /// The keys so far are a prefix of at least one binding
+    Pending,
+    /// The key does not match or continue any binding
+    NoMatch,
+}
+
 /// Main keybind engine combining registry and profile management
 pub struct KeybindEngine {
     registry: KeybindRegistry,
@@ -129,6 +141,8 @@ pub struct KeybindEngine {
     default_keybinds: Vec<Keybind>,
     current_context: Context,
     context_stack: Vec<Context>,
+    /// Runtime UI frames (focus, mode, modals) for when-clause evaluation
+    frames: Vec<ContextFrame>,
     /// Leader key state (OpenCode G02)
     leader_state: LeaderState,
     /// Pending key state for multi-key chords (Helix G08)
@@ -144,6 +158,7 @@ impl KeybindEngine {
             default_keybinds: Vec::new(),
             current_context: Context::Global,
             context_stack: Vec::new(),
+            frames: Vec::new(),
             leader_state: LeaderState::default(),
             pending_keys: PendingKeyState::default(),
         }
@@ -384,17 +399,47 @@ impl KeybindEngine {
         contexts
     }
 
-    /// Get action for a key combination using active contexts
+    /// Push a runtime UI frame (e.g. a modal opening or focus moving)
+    ///
+    /// The frame's context is pushed onto the context stack as well.
+    pub fn push_frame(&mut self, frame: ContextFrame) {
+        self.push_context(frame.context);
+        self.frames.push(frame);
+    }
+
+    /// Pop the top runtime UI frame and its context
+    pub fn pop_frame(&mut self) -> Option<ContextFrame> {
+        let frame = self.frames.pop()?;
+        self.pop_context();
+        Some(frame)
+    }
+
+    /// Get the runtime UI frames (bottom to top)
+    pub fn frames(&self) -> &[ContextFrame] {
+        &self.frames
+    }
+
+    /// Flattened runtime state used to evaluate when-clauses
+    pub fn context_state(&self) -> ContextState {
+        ContextState::from_frames(&self.active_contexts(), &self.frames)
+    }
+
+    /// Get action for a key combination in an explicit runtime state
+    pub fn get_action_in_state(&self, key: &KeyCombo, state: &ContextState) -> Option<&str> {
+        self.registry.lookup_by_key_in_state(key, state)
+    }
+
+    /// Get action for a key combination using active contexts and when-clauses
     pub fn get_action_with_active_contexts(&self, key: &KeyCombo) -> Option<&str> {
-        let contexts = self.active_contexts();
-        self.get_action_with_contexts(key, &contexts)
+        self.get_action_in_state(key, &self.context_state())
     }
 
     /// Validate keybinds for conflicts
     pub fn validate_keybinds(&self, keybinds: &[Keybind]) -> ValidationResult {
         // Detect conflicts
         let conflicts = ConflictDetector::detect(keybinds);
-        let is_valid = conflicts.is_empty();
+        let prefix_conflicts = ConflictDetector::detect_prefix_conflicts(keybinds);
+        let is_valid = conflicts.is_empty() && prefix_conflicts.is_empty();
 
         // Suggest resolutions for each conflict
         let mut resolutions = Vec::new();
@@ -406,6 +451,7 @@ impl KeybindEngine {
         ValidationResult {
             is_valid,
             conflicts,
+            prefix_conflicts,
             resolutions,
             applied_keybinds: keybinds.len(),
         }
@@ -488,6 +534,20 @@ impl KeybindEngine {
         KeybindHelp::paginate(&keybinds, page, page_size)
     }
 
+    /// Import a VS Code, vim or emacs preset as a new profile
+    ///
+    /// The profile is named after the scheme (e.g. `vim`) and is not
+    /// selected; the returned report lists skipped entries and conflicts.
+    pub fn import_preset(
+        &mut self,
+        scheme: crate::parser::ImportScheme,
+        content: &str,
+    ) -> Result<crate::parser::ImportReport, EngineError> {
+        let report = crate::parser::import_preset(scheme, content)?;
+        self.create_profile(report.profile_name(), report.keybinds.clone())?;
+        Ok(report)
+    }
+
     /// Get a profile by name
     pub fn get_profile(&self, name: &str) -> Option<&crate::profile::Profile> {
         self.profile_manager.get_profile(name)
@@ -571,27 +631,74 @@ impl KeybindEngine {
         self.deactivate_leader();
     }
     
+    /// Get the pending keys as a sequence, if a chord is in progress
+    pub fn pending_sequence(&self) -> Option<KeySequence> {
+        if self.is_chord_pending() {
+            Some(KeySequence::new(self.pending_keys.keys.clone()))
+        } else {
+            None
+        }
+    }
+
     /// Try to match pending chord sequence to an action
     pub fn match_pending_chord(&self) -> Option<&str> {
-        if !self.is_chord_pending() {
-            return None;
+        let sequence = self.pending_sequence()?;
+        self.registry
+            .lookup_sequence_in_state(&sequence, &self.context_state())
+    }
+
+    /// Get the bindings that can complete the pending chord sequence
+    pub fn pending_continuations(&self) -> Vec<(KeySequence, &Keybind)> {
+        match self.pending_sequence() {
+            Some(prefix) => self
+                .registry
+                .continuations_in_state(&prefix, &self.context_state()),
+            None => Vec::new(),
         }
-        
-        // Build chord string (e.g., "gg", "dd")
-        let chord_str = self.pending_keys.as_string();
-        
-        // Try to find a keybind that matches this chord sequence
-        // For now, we treat it as a single key combo
-        // TODO: Implement proper trie-based chord matching for Helix G08
-        let chord_combo = KeyCombo {
-            modifiers: Vec::new(),
-            key: Key::Char(chord_str.chars().next().unwrap_or(' ')),
-            leader: false,
-        };
-        
-        self.get_action_with_active_contexts(&chord_combo)
     }
-    
+
+    /// Status-line hint for the pending chord (e.g. `g-  g:Top  d:Definition`)
+    pub fn pending_hint(&self) -> Option<String> {
+        let prefix = self.pending_sequence()?;
+        Some(KeybindHelp::pending_prefix_hint(&prefix, &self.pending_continuations()))
+    }
+
+    /// Feed a key into the sequence state machine
+    ///
+    /// When the keys typed so far both match a binding and prefix a longer
+    /// one, the longer sequence wins (see
+    /// [`ConflictDetector::detect_prefix_conflicts`]). A key that breaks a
+    /// pending sequence discards it and is retried on its own.
+    pub fn feed_key(&mut self, key: KeyCombo) -> SequenceMatch {
+        self.process_chord_timeout();
+
+        let state = self.context_state();
+        let mut combos = self.pending_keys.keys.clone();
+        combos.push(key.clone());
+        let sequence = KeySequence::new(combos);
+
+        if self.registry.has_continuation_in_state(&sequence, &state) {
+            self.push_pending_key(key);
+            return SequenceMatch::Pending;
+        }
+
+        let action = self
+            .registry
+            .lookup_sequence_in_state(&sequence, &state)
+            .map(|s| s.to_string());
+        if let Some(action) = action {
+            self.clear_pending_keys();
+            return SequenceMatch::Matched(action);
+        }
+
+        if sequence.is_chord() {
+            self.clear_pending_keys();
+            return self.feed_key(key);
+        }
+
+        SequenceMatch::NoMatch
+    }
+
     /// Process a key event with leader and chord support
     /// Returns (action_id, consumed_key) tuple
     /// - action_id: matched action if found (owned String to avoid borrow issues)
@@ -620,26 +727,11 @@ impl KeybindEngine {
             }
         }
         
-        // Try direct match first (clone to avoid borrow issues)
-        let action_opt = self.get_action_with_active_contexts(&key).map(|s| s.to_string());
-        if let Some(action) = action_opt {
-            // Clear pending state on match
-            self.clear_pending_keys();
-            return (Some(action), true);
-        }
-        
-        // If no match, check if this could be part of a chord
-        self.push_pending_key(key.clone());
-        
-        // Try to match pending chord (clone to avoid borrow issues)
-        let chord_action_opt = self.match_pending_chord().map(|s| s.to_string());
-        if let Some(action) = chord_action_opt {
-            self.clear_pending_keys();
-            return (Some(action), true);
+        match self.feed_key(key) {
+            SequenceMatch::Matched(action) => (Some(action), true),
+            SequenceMatch::Pending => (None, true),
+            SequenceMatch::NoMatch => (None, false),
         }
-        
-        // Key is pending (not consumed)
-        (None, false)
     }
     
     // ========================================================================
@@ -707,6 +799,8 @@ impl Default for KeybindEngine {
 pub struct ValidationResult {
     pub is_valid: bool,
     pub conflicts: Vec<crate::conflict::Conflict>,
+    /// Bindings shadowed by a longer key sequence
+    pub prefix_conflicts: Vec<crate::conflict::PrefixConflict>,
     pub resolutions: Vec<crate::conflict::Resolution>,
     pub applied_keybinds: usize,
 }
@@ -714,12 +808,12 @@ pub struct ValidationResult {
 impl ValidationResult {
     /// Check if validation passed (no conflicts)
     pub fn passed(&self) -> bool {
-        self.is_valid && self.conflicts.is_empty()
+        self.is_valid && self.conflicts.is_empty() && self.prefix_conflicts.is_empty()
     }
 
     /// Get conflict count
     pub fn conflict_count(&self) -> usize {
-        self.conflicts.len()
+        self.conflicts.len() + self.prefix_conflicts.len()
     }
 }
 
@@ -23,6 +23,9 @@ pub enum ParseError {
     #[error("Duplicate keybind definition: {0}")]
     DuplicateDefinition(String),
 
+    #[error("Invalid when-clause: {0}")]
+    InvalidWhenClause(String),
+
     #[error("Parse error at line {line}: {message}")]
     LineError { line: usize, message: String },
 }
@@ -41,6 +44,9 @@ pub enum RegistryError {
 
     #[error("Key combination not found")]
     KeyNotFound,
+
+    #[error("Invalid when-clause: {0}")]
+    InvalidWhenClause(String),
 }
 
 /// Errors that can occur in profile management
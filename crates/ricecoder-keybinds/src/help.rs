@@ -1,6 +1,6 @@
 //! Help system for displaying keybinds
 
-use crate::models::Keybind;
+use crate::models::{KeySequence, Keybind};
 
 /// Pagination information
 #[derive(Debug, Clone)]
@@ -96,6 +96,38 @@ impl KeybindHelp {
             .collect()
     }
 
+    /// Render a one-line hint for a pending key prefix, listing the next key
+    /// of each binding that can complete it (e.g. `g-  g:Top  d:Definition`)
+    ///
+    /// `continuations` are the bound sequences that start with `prefix`, as
+    /// returned by [`crate::KeybindRegistry::continuations`].
+    pub fn pending_prefix_hint(
+        prefix: &KeySequence,
+        continuations: &[(KeySequence, &Keybind)],
+    ) -> String {
+        let mut output = format!("{}-", prefix);
+        let mut seen = std::collections::HashSet::new();
+
+        for (sequence, keybind) in continuations {
+            let Some(next) = sequence.combos.get(prefix.len()) else {
+                continue;
+            };
+            if !seen.insert(next.to_string()) {
+                continue;
+            }
+            let label = if sequence.len() > prefix.len() + 1 {
+                "…".to_string()
+            } else if keybind.description.is_empty() {
+                keybind.action_id.clone()
+            } else {
+                keybind.description.clone()
+            };
+            output.push_str(&format!("  {}:{}", next, label));
+        }
+
+        output
+    }
+
     /// Paginate keybinds
     pub fn paginate<'a>(
         keybinds: &[&'a Keybind],
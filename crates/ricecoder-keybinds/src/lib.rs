@@ -2,8 +2,10 @@
 //!
 //! This crate provides a comprehensive keybind system for ricecoder with:
 //! - JSON and Markdown configuration parsing
+//! - Preset import from VS Code, vim and emacs keybinding files
 //! - Keybind registry with fast lookup
 //! - Conflict detection with resolution suggestions
+//! - When-clause dispatch against a runtime context stack
 //! - Profile management for switching between configurations
 //! - Help system for displaying available keybinds
 //! - Persistence layer for saving profiles across sessions
@@ -19,15 +21,22 @@ pub mod parser;
 pub mod persistence;
 pub mod profile;
 pub mod registry;
+pub mod when;
 
 // Re-export public types
-pub use conflict::{Conflict, ConflictDetector, Resolution};
-pub use engine::{KeybindEngine, ValidationResult};
+pub use conflict::{Conflict, ConflictDetector, PrefixConflict, Resolution};
+pub use engine::{KeybindEngine, SequenceMatch, ValidationResult};
 pub use error::{EngineError, ParseError, PersistenceError, ProfileError, RegistryError};
 pub use help::{KeybindHelp, Page};
 pub use merge::{KeybindMerger, MergeConflict, MergeResult};
-pub use models::{Context, Key, KeyCombo, Keybind, KeybindManager, Modifier};
-pub use parser::{JsonKeybindParser, KeybindParser, MarkdownKeybindParser, ParserRegistry};
+pub use models::{
+    Context, ContextFrame, Key, KeyCombo, KeySequence, Keybind, KeybindManager, Modifier,
+};
+pub use parser::{
+    import_preset, EmacsImporter, ImportReport, ImportScheme, JsonKeybindParser, KeybindParser,
+    MarkdownKeybindParser, ParserRegistry, SkippedBinding, VimImporter, VsCodeImporter,
+};
 pub use persistence::{FileSystemPersistence, KeybindPersistence};
 pub use profile::{Profile, ProfileManager};
 pub use registry::KeybindRegistry;
+pub use when::{ContextState, WhenClause};
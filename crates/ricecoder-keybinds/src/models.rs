@@ -1,6 +1,6 @@
 //! Core data models for keybinds
 
-use std::{fmt, str::FromStr};
+use std::{collections::BTreeSet, fmt, str::FromStr};
 
 use serde::{Deserialize, Serialize};
 
@@ -216,6 +216,93 @@ impl FromStr for KeyCombo {
     }
 }
 
+/// A sequence of key combinations pressed one after another (e.g. `g g`,
+/// `ctrl+k ctrl+s`)
+///
+/// A single-combo sequence displays exactly like its [`KeyCombo`], so existing
+/// bindings and registry lookups are unaffected.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct KeySequence {
+    pub combos: Vec<KeyCombo>,
+}
+
+impl KeySequence {
+    /// Create a sequence from combos
+    pub fn new(combos: Vec<KeyCombo>) -> Self {
+        KeySequence { combos }
+    }
+
+    /// Number of combos in the sequence
+    pub fn len(&self) -> usize {
+        self.combos.len()
+    }
+
+    /// Check if the sequence has no combos
+    pub fn is_empty(&self) -> bool {
+        self.combos.is_empty()
+    }
+
+    /// Check if this is a multi-key sequence
+    pub fn is_chord(&self) -> bool {
+        self.combos.len() > 1
+    }
+
+    /// Check if this sequence is a strict prefix of another
+    pub fn is_prefix_of(&self, other: &KeySequence) -> bool {
+        self.combos.len() < other.combos.len() && other.combos.starts_with(&self.combos)
+    }
+}
+
+impl From<KeyCombo> for KeySequence {
+    fn from(combo: KeyCombo) -> Self {
+        KeySequence {
+            combos: vec![combo],
+        }
+    }
+}
+
+impl fmt::Display for KeySequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, combo) in self.combos.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", combo)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for KeySequence {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut combos = Vec::new();
+        let mut tokens = s.split_whitespace().peekable();
+
+        while let Some(token) = tokens.next() {
+            // `<leader> x` keeps its existing single-combo meaning
+            if token == "<leader>" {
+                let combo = match tokens.next() {
+                    Some(next) => KeyCombo::from_str(&format!("<leader> {}", next))?,
+                    None => KeyCombo::from_str(token)?,
+                };
+                combos.push(combo);
+            } else {
+                combos.push(KeyCombo::from_str(token)?);
+            }
+        }
+
+        if combos.is_empty() {
+            return Err(ParseError::InvalidKeySyntax(
+                "Empty key sequence".to_string(),
+            ));
+        }
+
+        Ok(KeySequence { combos })
+    }
+}
+
 /// UI context for keybindings
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -280,6 +367,60 @@ impl FromStr for Context {
     }
 }
 
+/// Runtime UI state pushed onto the engine's context stack
+///
+/// A frame records the active [`Context`] together with the finer-grained
+/// state that when-clauses test (focused widget, mode, modal and flags).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextFrame {
+    pub context: Context,
+    /// Focused widget (e.g. "editor", "sidebar")
+    pub focused: Option<String>,
+    /// Editing mode (e.g. "normal", "insert")
+    pub mode: Option<String>,
+    /// Whether this frame is a modal (dialog, popup)
+    pub modal: bool,
+    /// Free-form flags (e.g. "popupOpen")
+    pub flags: BTreeSet<String>,
+}
+
+impl ContextFrame {
+    /// Create a frame for a context
+    pub fn new(context: Context) -> Self {
+        ContextFrame {
+            context,
+            focused: None,
+            mode: None,
+            modal: false,
+            flags: BTreeSet::new(),
+        }
+    }
+
+    /// Set the focused widget
+    pub fn with_focus(mut self, widget: impl Into<String>) -> Self {
+        self.focused = Some(widget.into());
+        self
+    }
+
+    /// Set the mode
+    pub fn with_mode(mut self, mode: impl Into<String>) -> Self {
+        self.mode = Some(mode.into());
+        self
+    }
+
+    /// Mark the frame as modal
+    pub fn with_modal(mut self, modal: bool) -> Self {
+        self.modal = modal;
+        self
+    }
+
+    /// Set a flag
+    pub fn with_flag(mut self, flag: impl Into<String>) -> Self {
+        self.flags.insert(flag.into());
+        self
+    }
+}
+
 /// Represents a single keybind configuration
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Keybind {
@@ -296,6 +437,9 @@ pub struct Keybind {
     /// Contexts where this keybind applies (empty = global)
     #[serde(default)]
     pub contexts: Vec<Context>,
+    /// When-clause evaluated at dispatch time (e.g. `editorFocused && !popupOpen`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<String>,
 }
 
 impl Keybind {
@@ -333,6 +477,37 @@ impl Keybind {
         Ok(combos)
     }
 
+    /// Parse the primary key string into a KeySequence
+    pub fn parse_sequence(&self) -> Result<KeySequence, ParseError> {
+        KeySequence::from_str(&self.key)
+    }
+
+    /// Parse all key bindings (primary + alternatives) into sequences
+    ///
+    /// Unlike [`Keybind::parse_all_keys`], this accepts multi-key sequences
+    /// such as `g g` or `ctrl+k ctrl+s`.
+    pub fn parse_all_sequences(&self) -> Result<Vec<KeySequence>, ParseError> {
+        std::iter::once(&self.key)
+            .chain(self.alternatives.iter())
+            .flat_map(|keys| keys.split(','))
+            .map(|part| KeySequence::from_str(part.trim()))
+            .collect()
+    }
+
+    /// Set the when-clause
+    pub fn with_when(mut self, when: impl Into<String>) -> Self {
+        self.when = Some(when.into());
+        self
+    }
+
+    /// Parse the when-clause, if any
+    pub fn parse_when(&self) -> Result<Option<crate::when::WhenClause>, ParseError> {
+        self.when
+            .as_deref()
+            .map(crate::when::WhenClause::from_str)
+            .transpose()
+    }
+
     /// Create a new keybind
     pub fn new(
         action_id: impl Into<String>,
@@ -348,6 +523,7 @@ impl Keybind {
             description: description.into(),
             is_default: false,
             contexts: Vec::new(),
+            when: None,
         }
     }
 
@@ -367,6 +543,7 @@ impl Keybind {
             description: description.into(),
             is_default: false,
             contexts,
+            when: None,
         }
     }
 
@@ -385,6 +562,7 @@ impl Keybind {
             description: description.into(),
             is_default: true,
             contexts: Vec::new(),
+            when: None,
         }
     }
 
@@ -404,6 +582,7 @@ impl Keybind {
             description: description.into(),
             is_default: true,
             contexts,
+            when: None,
         }
     }
 
@@ -1,9 +1,17 @@
-//! Keybind configuration parsers for JSON and Markdown formats
+//! Keybind configuration parsers for JSON and Markdown formats, plus
+//! importers for VS Code, vim and emacs presets
 
 use std::{collections::HashMap, sync::Arc};
 
 use crate::{error::ParseError, models::Keybind};
 
+pub mod import;
+
+pub use import::{
+    import_preset, EmacsImporter, ImportReport, ImportScheme, ImportedBindings, SkippedBinding,
+    VimImporter, VsCodeImporter,
+};
+
 /// Trait for parsing keybind configurations
 pub trait KeybindParser: Send + Sync {
     /// Parse keybind configuration from content
@@ -31,6 +39,18 @@ impl ParserRegistry {
             "md".to_string(),
             Arc::new(MarkdownKeybindParser) as Arc<dyn KeybindParser>,
         );
+        parsers.insert(
+            "vscode".to_string(),
+            Arc::new(VsCodeImporter) as Arc<dyn KeybindParser>,
+        );
+        parsers.insert(
+            "vim".to_string(),
+            Arc::new(VimImporter) as Arc<dyn KeybindParser>,
+        );
+        parsers.insert(
+            "emacs".to_string(),
+            Arc::new(EmacsImporter) as Arc<dyn KeybindParser>,
+        );
 
         ParserRegistry { parsers }
     }
@@ -205,5 +225,6 @@ fn parse_markdown_entry(
         description,
         is_default: false,
         contexts: Vec::new(),
+        when: None,
     })
 }
@@ -0,0 +1,882 @@
+//! Importers for keybinding presets from other editors
+//!
+//! Translates VS Code `keybindings.json`, vim `map` commands and emacs
+//! `global-set-key`/`define-key` forms into ricecoder [`Keybind`]s. Translation
+//! is best-effort: well-known commands map onto ricecoder actions, everything
+//! else keeps its original command name under a scheme prefix (e.g.
+//! `vim.telescope_find_files`), and entries that cannot be expressed are
+//! reported as [`SkippedBinding`]s instead of failing the whole import.
+
+use std::{fmt, str::FromStr};
+
+use crate::{
+    conflict::{Conflict, ConflictDetector, PrefixConflict},
+    error::ParseError,
+    models::{Context, KeySequence, Keybind},
+    parser::KeybindParser,
+    profile::Profile,
+};
+
+/// Editor whose keybinding format is being imported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImportScheme {
+    VsCode,
+    Vim,
+    Emacs,
+}
+
+impl ImportScheme {
+    /// Short name used for action prefixes, categories and profile names
+    pub fn name(&self) -> &'static str {
+        match self {
+            ImportScheme::VsCode => "vscode",
+            ImportScheme::Vim => "vim",
+            ImportScheme::Emacs => "emacs",
+        }
+    }
+
+    /// Translate content in this scheme's format
+    pub fn translate(&self, content: &str) -> Result<ImportedBindings, ParseError> {
+        match self {
+            ImportScheme::VsCode => VsCodeImporter.translate(content),
+            ImportScheme::Vim => VimImporter.translate(content),
+            ImportScheme::Emacs => EmacsImporter.translate(content),
+        }
+    }
+}
+
+impl fmt::Display for ImportScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for ImportScheme {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "vscode" | "code" | "vs-code" => Ok(ImportScheme::VsCode),
+            "vim" | "nvim" | "neovim" => Ok(ImportScheme::Vim),
+            "emacs" => Ok(ImportScheme::Emacs),
+            _ => Err(ParseError::InvalidKeySyntax(format!(
+                "Unknown import scheme: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// A source entry that could not be translated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedBinding {
+    /// 1-based line (or array index for VS Code) of the entry
+    pub line: usize,
+    /// Original text of the entry
+    pub source: String,
+    /// Why the entry was skipped
+    pub reason: String,
+}
+
+/// Keybinds translated from a foreign format
+#[derive(Debug, Clone, Default)]
+pub struct ImportedBindings {
+    pub keybinds: Vec<Keybind>,
+    pub skipped: Vec<SkippedBinding>,
+}
+
+impl ImportedBindings {
+    /// Record a skipped entry
+    fn skip(&mut self, line: usize, source: &str, reason: impl Into<String>) {
+        self.skipped.push(SkippedBinding {
+            line,
+            source: source.trim().to_string(),
+            reason: reason.into(),
+        });
+    }
+
+    /// Add a translated binding, folding repeated actions into alternatives
+    fn add(
+        &mut self,
+        scheme: ImportScheme,
+        action_id: String,
+        key: &KeySequence,
+        mut contexts: Vec<Context>,
+        command: &str,
+    ) {
+        // Ricecoder input actions only make sense while typing
+        if contexts.is_empty() && action_id.starts_with("input.") {
+            contexts.push(Context::Input);
+        }
+        let key = key.to_string();
+
+        if let Some(existing) = self
+            .keybinds
+            .iter_mut()
+            .find(|kb| kb.action_id == action_id)
+        {
+            if existing.key != key && !existing.alternatives.contains(&key) {
+                existing.alternatives.push(key);
+            }
+            if existing.contexts.is_empty() || contexts.is_empty() {
+                existing.contexts.clear();
+            } else {
+                for context in contexts {
+                    if !existing.contexts.contains(&context) {
+                        existing.contexts.push(context);
+                    }
+                }
+            }
+            return;
+        }
+
+        let category = action_id
+            .split('.')
+            .next()
+            .unwrap_or(scheme.name())
+            .to_string();
+        let description = format!("Imported from {}: {}", scheme, command.trim());
+        self.keybinds.push(Keybind::new_with_contexts(
+            action_id,
+            key,
+            category,
+            description,
+            contexts,
+        ));
+    }
+}
+
+/// Result of importing a preset: keybinds, skipped entries and conflicts
+#[derive(Debug, Clone)]
+pub struct ImportReport {
+    pub scheme: ImportScheme,
+    pub keybinds: Vec<Keybind>,
+    pub skipped: Vec<SkippedBinding>,
+    pub conflicts: Vec<Conflict>,
+    pub prefix_conflicts: Vec<PrefixConflict>,
+}
+
+impl ImportReport {
+    /// Name of the profile generated for this import (e.g. `vim`)
+    pub fn profile_name(&self) -> &'static str {
+        self.scheme.name()
+    }
+
+    /// Build a new profile holding the imported keybinds
+    pub fn to_profile(&self) -> Profile {
+        Profile::new(self.profile_name(), self.keybinds.clone())
+    }
+
+    /// Check if any conflicts were found
+    pub fn has_conflicts(&self) -> bool {
+        !self.conflicts.is_empty() || !self.prefix_conflicts.is_empty()
+    }
+}
+
+/// Translate a preset and check the result for conflicts
+pub fn import_preset(scheme: ImportScheme, content: &str) -> Result<ImportReport, ParseError> {
+    let imported = scheme.translate(content)?;
+    let conflicts = ConflictDetector::detect(&imported.keybinds);
+    let prefix_conflicts = ConflictDetector::detect_prefix_conflicts(&imported.keybinds);
+
+    Ok(ImportReport {
+        scheme,
+        keybinds: imported.keybinds,
+        skipped: imported.skipped,
+        conflicts,
+        prefix_conflicts,
+    })
+}
+
+/// Build a ricecoder combo string from modifier names and a key name
+fn combo_string(modifiers: &[&str], key: &str) -> String {
+    let mut parts: Vec<&str> = modifiers.to_vec();
+    parts.push(key);
+    parts.join("+")
+}
+
+/// Action ID for a command without a ricecoder equivalent
+fn foreign_action(scheme: ImportScheme, command: &str) -> Option<String> {
+    let mut slug = String::new();
+    for c in command.trim().chars() {
+        if c.is_ascii_alphanumeric() || c == '.' {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.ends_with('_') {
+            slug.push('_');
+        }
+    }
+    let slug = slug.trim_matches(|c| c == '_' || c == '.');
+    if slug.is_empty() {
+        None
+    } else {
+        Some(format!("{}.{}", scheme, slug))
+    }
+}
+
+// ============================================================================
+// VS Code
+// ============================================================================
+
+/// Importer for VS Code `keybindings.json` (JSON with comments)
+pub struct VsCodeImporter;
+
+impl VsCodeImporter {
+    /// Well-known VS Code commands with a ricecoder equivalent
+    fn action_for(command: &str) -> Option<&'static str> {
+        Some(match command {
+            "workbench.action.showCommands" => "command.list",
+            "workbench.action.quit" => "app.exit",
+            "workbench.action.toggleSidebarVisibility" => "app.sidebar_toggle",
+            "workbench.action.selectTheme" => "app.theme_list",
+            "workbench.action.files.newUntitledFile" => "session.new",
+            "workbench.action.quickOpen" => "session.list",
+            "editor.action.clipboardPasteAction" => "input.paste",
+            "undo" => "input.undo",
+            "redo" => "input.redo",
+            "cursorLeft" => "input.move_left",
+            "cursorRight" => "input.move_right",
+            "cursorUp" => "input.move_up",
+            "cursorDown" => "input.move_down",
+            "cursorHome" => "input.line_home",
+            "cursorEnd" => "input.line_end",
+            "cursorTop" => "input.buffer_home",
+            "cursorBottom" => "input.buffer_end",
+            "cursorWordLeft" => "input.word_backward",
+            "cursorWordRight" => "input.word_forward",
+            "deleteLeft" => "input.backspace",
+            "deleteRight" => "input.delete",
+            "editor.action.deleteLines" => "input.delete_line",
+            _ => return None,
+        })
+    }
+
+    /// Map a `when` clause onto ricecoder contexts (negated terms are ignored)
+    fn contexts_for(when: &str) -> Vec<Context> {
+        let mut contexts = Vec::new();
+        for term in when.split(['&', '|', '(', ')']) {
+            let term = term.trim();
+            if term.is_empty() || term.starts_with('!') {
+                continue;
+            }
+            let lower = term.to_lowercase();
+            let context = if lower.contains("inquickopen") {
+                Context::CommandPalette
+            } else if lower.contains("chat") {
+                Context::Chat
+            } else if lower.contains("dialog") || lower.contains("modal") {
+                Context::Dialog
+            } else if lower.contains("editortextfocus")
+                || lower.contains("textinputfocus")
+                || lower.contains("inputfocus")
+                || lower.contains("editorfocus")
+            {
+                Context::Input
+            } else {
+                continue;
+            };
+            if !contexts.contains(&context) {
+                contexts.push(context);
+            }
+        }
+        contexts
+    }
+
+    /// Translate a VS Code keybindings file
+    pub fn translate(&self, content: &str) -> Result<ImportedBindings, ParseError> {
+        let value: serde_json::Value = serde_json::from_str(&strip_jsonc(content))
+            .map_err(|e| ParseError::InvalidJson(e.to_string()))?;
+        let entries = value.as_array().ok_or_else(|| {
+            ParseError::InvalidJson("Expected an array of keybindings".to_string())
+        })?;
+
+        let mut imported = ImportedBindings::default();
+        for (idx, entry) in entries.iter().enumerate() {
+            let line = idx + 1;
+            let source = entry.to_string();
+            let key = entry.get("key").and_then(|v| v.as_str());
+            let command = entry.get("command").and_then(|v| v.as_str());
+            let (Some(key), Some(command)) = (key, command) else {
+                imported.skip(line, &source, "Missing key or command");
+                continue;
+            };
+
+            // "-command" removes a default binding; ricecoder profiles
+            // override defaults per action instead
+            if command.starts_with('-') {
+                imported.skip(line, &source, "Binding removals are not supported");
+                continue;
+            }
+
+            let sequence = match KeySequence::from_str(key) {
+                Ok(sequence) => sequence,
+                Err(e) => {
+                    imported.skip(line, &source, e.to_string());
+                    continue;
+                }
+            };
+            let action_id = match Self::action_for(command) {
+                Some(action) => action.to_string(),
+                None => match foreign_action(ImportScheme::VsCode, command) {
+                    Some(action) => action,
+                    None => {
+                        imported.skip(line, &source, "Empty command");
+                        continue;
+                    }
+                },
+            };
+            let contexts = entry
+                .get("when")
+                .and_then(|v| v.as_str())
+                .map(Self::contexts_for)
+                .unwrap_or_default();
+
+            imported.add(
+                ImportScheme::VsCode,
+                action_id,
+                &sequence,
+                contexts,
+                command,
+            );
+        }
+
+        Ok(imported)
+    }
+}
+
+impl KeybindParser for VsCodeImporter {
+    fn parse(&self, content: &str) -> Result<Vec<Keybind>, ParseError> {
+        Ok(self.translate(content)?.keybinds)
+    }
+}
+
+/// Remove `//` and `/* */` comments and trailing commas from JSONC
+fn strip_jsonc(content: &str) -> String {
+    let mut output = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    let mut in_string = false;
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            output.push(c);
+            match c {
+                '\\' => {
+                    if let Some(escaped) = chars.next() {
+                        output.push(escaped);
+                    }
+                }
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                output.push(c);
+            }
+            ('/', Some('/')) => {
+                for next in chars.by_ref() {
+                    if next == '\n' {
+                        output.push('\n');
+                        break;
+                    }
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut previous = ' ';
+                for next in chars.by_ref() {
+                    if previous == '*' && next == '/' {
+                        break;
+                    }
+                    previous = next;
+                }
+            }
+            _ => output.push(c),
+        }
+    }
+
+    // Drop commas directly before a closing bracket or brace
+    let mut cleaned = String::with_capacity(output.len());
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in output.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+        } else if c == '"' {
+            in_string = true;
+        } else if c == ',' {
+            let next = output[i + 1..].trim_start().chars().next();
+            if matches!(next, Some(']') | Some('}')) {
+                continue;
+            }
+        }
+        cleaned.push(c);
+    }
+    cleaned
+}
+
+// ============================================================================
+// Vim
+// ============================================================================
+
+/// Importer for vim `map`/`noremap` commands (vimrc or init.vim)
+pub struct VimImporter;
+
+impl VimImporter {
+    /// Contexts for a map command, `None` for lines that are not mappings,
+    /// or an error for modes ricecoder has no equivalent for
+    fn contexts_for(command: &str) -> Option<Result<Vec<Context>, &'static str>> {
+        Some(Ok(match command {
+            "map" | "noremap" | "nmap" | "nnoremap" | "nm" | "nn" | "no" => Vec::new(),
+            "imap" | "inoremap" | "im" | "ino" => vec![Context::Input],
+            "cmap" | "cnoremap" | "cm" | "cno" => vec![Context::CommandPalette],
+            "map!" | "noremap!" => vec![Context::Input, Context::CommandPalette],
+            "vmap" | "vnoremap" | "xmap" | "xnoremap" | "smap" | "snoremap" | "omap"
+            | "onoremap" | "tmap" | "tnoremap" => {
+                return Some(Err("Mode has no ricecoder equivalent"))
+            }
+            _ => return None,
+        }))
+    }
+
+    /// Well-known ex commands with a ricecoder equivalent
+    fn action_for_ex(command: &str) -> Option<&'static str> {
+        Some(match command {
+            "q" | "q!" | "qa" | "qa!" | "qall" | "quit" | "quitall" => "app.exit",
+            "u" | "undo" => "input.undo",
+            "red" | "redo" => "input.redo",
+            "colorscheme" => "app.theme_list",
+            _ => return None,
+        })
+    }
+
+    /// Well-known normal-mode keystrokes with a ricecoder equivalent
+    fn action_for_keys(keys: &str) -> Option<&'static str> {
+        Some(match keys {
+            "u" => "input.undo",
+            "<C-r>" | "<c-r>" => "input.redo",
+            "p" | "\"+p" => "input.paste",
+            "0" | "^" => "input.line_home",
+            "$" => "input.line_end",
+            "gg" => "input.buffer_home",
+            "G" => "input.buffer_end",
+            "dd" => "input.delete_line",
+            "d$" => "input.delete_to_line_end",
+            "w" => "input.word_forward",
+            "b" => "input.word_backward",
+            _ => return None,
+        })
+    }
+
+    /// Split a rhs into its ex command (`:...<CR>`, `<cmd>...<CR>`), if it is
+    /// one, or the raw keystrokes otherwise
+    fn normalize_rhs(rhs: &str) -> (String, bool) {
+        let mut rhs = rhs.trim().to_string();
+        let mut is_ex = false;
+        if rhs.to_lowercase().starts_with("<cmd>") {
+            rhs = rhs[5..].to_string();
+            is_ex = true;
+        } else if rhs.starts_with(':') {
+            is_ex = true;
+        }
+        if is_ex && rhs.to_lowercase().ends_with("<cr>") {
+            rhs.truncate(rhs.len() - 4);
+        }
+        (rhs.trim_start_matches(':').trim().to_string(), is_ex)
+    }
+
+    /// Translate vim key notation (e.g. `<leader>ff`, `<C-s>`, `gg`)
+    fn key_sequence(lhs: &str) -> Result<KeySequence, ParseError> {
+        let mut tokens = Vec::new();
+        let mut leader = false;
+        let mut chars = lhs.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            let combo = if c == '<' && lhs.contains('>') {
+                let mut name = String::new();
+                for next in chars.by_ref() {
+                    if next == '>' {
+                        break;
+                    }
+                    name.push(next);
+                }
+                let lower = name.to_lowercase();
+                if lower == "leader" || lower == "localleader" {
+                    leader = true;
+                    continue;
+                }
+                Self::special_key(&name)?
+            } else if c.is_ascii_uppercase() {
+                combo_string(&["shift"], &c.to_ascii_lowercase().to_string())
+            } else {
+                c.to_string()
+            };
+
+            if leader {
+                tokens.push(format!("<leader> {}", combo));
+                leader = false;
+            } else {
+                tokens.push(combo);
+            }
+        }
+
+        if leader {
+            return Err(ParseError::InvalidKeySyntax(
+                "Leader must be followed by a key".to_string(),
+            ));
+        }
+        KeySequence::from_str(&tokens.join(" "))
+    }
+
+    /// Translate a `<...>` key name such as `C-S-p`, `CR` or `F5`
+    fn special_key(name: &str) -> Result<String, ParseError> {
+        let mut modifiers = Vec::new();
+        let mut rest = name;
+        while rest.len() > 2 && rest.as_bytes()[1] == b'-' {
+            modifiers.push(match rest.as_bytes()[0].to_ascii_lowercase() {
+                b'c' => "ctrl",
+                b'a' | b'm' => "alt",
+                b's' => "shift",
+                b'd' => "meta",
+                _ => {
+                    return Err(ParseError::InvalidModifier(format!(
+                        "Unknown vim modifier in <{}>",
+                        name
+                    )))
+                }
+            });
+            rest = &rest[2..];
+        }
+
+        let key = match rest.to_lowercase().as_str() {
+            "cr" | "enter" | "return" => "enter".to_string(),
+            "esc" => "esc".to_string(),
+            "tab" => "tab".to_string(),
+            "bs" => "backspace".to_string(),
+            "del" => "del".to_string(),
+            "bar" => "|".to_string(),
+            "bslash" => "\\".to_string(),
+            "lt" => "<".to_string(),
+            "space" => {
+                return Err(ParseError::InvalidKeySyntax(
+                    "Space is not a bindable key".to_string(),
+                ))
+            }
+            key @ ("home" | "end" | "pageup" | "pagedown" | "up" | "down" | "left" | "right") => {
+                key.to_string()
+            }
+            key if key.starts_with('f') && key.len() > 1 => key.to_string(),
+            _ if rest.chars().count() == 1 => rest.to_lowercase(),
+            _ => {
+                return Err(ParseError::InvalidKeySyntax(format!(
+                    "Unknown vim key <{}>",
+                    name
+                )))
+            }
+        };
+        Ok(combo_string(&modifiers, &key))
+    }
+
+    /// Translate vim mapping commands
+    pub fn translate(&self, content: &str) -> Result<ImportedBindings, ParseError> {
+        let mut imported = ImportedBindings::default();
+
+        for (idx, line) in content.lines().enumerate() {
+            let line_num = idx + 1;
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('"') {
+                continue;
+            }
+
+            let mut words = trimmed.split_whitespace();
+            let Some(command) = words.next() else {
+                continue;
+            };
+            let contexts = match Self::contexts_for(command) {
+                None => continue,
+                Some(Err(reason)) => {
+                    imported.skip(line_num, trimmed, reason);
+                    continue;
+                }
+                Some(Ok(contexts)) => contexts,
+            };
+
+            let mut lhs = None;
+            let mut remaining = trimmed[command.len()..].trim_start();
+            while let Some(word) = remaining.split_whitespace().next() {
+                remaining = remaining[word.len()..].trim_start();
+                let lower = word.to_lowercase();
+                if !matches!(
+                    lower.as_str(),
+                    "<silent>" | "<buffer>" | "<expr>" | "<nowait>" | "<unique>" | "<script>"
+                ) {
+                    lhs = Some(word);
+                    break;
+                }
+            }
+            let Some(lhs) = lhs else {
+                imported.skip(line_num, trimmed, "Missing left-hand side");
+                continue;
+            };
+            if remaining.is_empty() {
+                imported.skip(line_num, trimmed, "Missing right-hand side");
+                continue;
+            }
+
+            let sequence = match Self::key_sequence(lhs) {
+                Ok(sequence) => sequence,
+                Err(e) => {
+                    imported.skip(line_num, trimmed, e.to_string());
+                    continue;
+                }
+            };
+            let (rhs, is_ex) = Self::normalize_rhs(remaining);
+            let known = if is_ex {
+                Self::action_for_ex(&rhs)
+            } else {
+                Self::action_for_keys(&rhs)
+            };
+            let action_id = match known {
+                Some(action) => action.to_string(),
+                None => match foreign_action(ImportScheme::Vim, &rhs) {
+                    Some(action) => action,
+                    None => {
+                        imported.skip(line_num, trimmed, "Unsupported right-hand side");
+                        continue;
+                    }
+                },
+            };
+
+            imported.add(ImportScheme::Vim, action_id, &sequence, contexts, remaining);
+        }
+
+        Ok(imported)
+    }
+}
+
+impl KeybindParser for VimImporter {
+    fn parse(&self, content: &str) -> Result<Vec<Keybind>, ParseError> {
+        Ok(self.translate(content)?.keybinds)
+    }
+}
+
+// ============================================================================
+// Emacs
+// ============================================================================
+
+/// Importer for emacs key definitions (`global-set-key`, `define-key`,
+/// `keymap-set` and friends), one form per line
+pub struct EmacsImporter;
+
+impl EmacsImporter {
+    /// Forms that bind keys, and whether they name a keymap first
+    const FORMS: &'static [(&'static str, bool)] = &[
+        ("global-set-key", false),
+        ("local-set-key", false),
+        ("keymap-global-set", false),
+        ("keymap-local-set", false),
+        ("define-key", true),
+        ("keymap-set", true),
+    ];
+
+    /// Well-known emacs commands with a ricecoder equivalent
+    fn action_for(command: &str) -> Option<&'static str> {
+        Some(match command {
+            "save-buffers-kill-terminal" | "save-buffers-kill-emacs" | "kill-emacs" => "app.exit",
+            "execute-extended-command" => "command.list",
+            "switch-to-buffer" => "session.list",
+            "load-theme" => "app.theme_list",
+            "keyboard-quit" => "session.interrupt",
+            "yank" => "input.paste",
+            "undo" => "input.undo",
+            "undo-redo" => "input.redo",
+            "forward-char" => "input.move_right",
+            "backward-char" => "input.move_left",
+            "next-line" => "input.move_down",
+            "previous-line" => "input.move_up",
+            "beginning-of-line" | "move-beginning-of-line" => "input.line_home",
+            "end-of-line" | "move-end-of-line" => "input.line_end",
+            "beginning-of-buffer" => "input.buffer_home",
+            "end-of-buffer" => "input.buffer_end",
+            "forward-word" => "input.word_forward",
+            "backward-word" => "input.word_backward",
+            "kill-line" => "input.delete_to_line_end",
+            "kill-whole-line" => "input.delete_line",
+            "delete-char" => "input.delete",
+            "delete-backward-char" => "input.backspace",
+            _ => return None,
+        })
+    }
+
+    /// Contexts for a keymap name (e.g. `minibuffer-local-map`)
+    fn contexts_for(keymap: Option<&str>) -> Vec<Context> {
+        match keymap {
+            Some(map) if map.contains("minibuffer") => vec![Context::CommandPalette],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Translate emacs key notation (e.g. `C-x C-s`, `M-<f5>`)
+    fn key_sequence(keys: &str) -> Result<KeySequence, ParseError> {
+        let mut tokens = Vec::new();
+        for token in keys.split_whitespace() {
+            let mut modifiers = Vec::new();
+            let mut rest = token;
+            while rest.len() > 2 && rest.as_bytes()[1] == b'-' {
+                modifiers.push(match rest.as_bytes()[0] {
+                    b'C' => "ctrl",
+                    b'M' => "alt",
+                    b'S' => "shift",
+                    b's' => "super",
+                    _ => {
+                        return Err(ParseError::InvalidModifier(format!(
+                            "Unsupported emacs modifier in {}",
+                            token
+                        )))
+                    }
+                });
+                rest = &rest[2..];
+            }
+
+            let key = match rest {
+                "RET" | "<return>" => "enter".to_string(),
+                "TAB" | "<tab>" => "tab".to_string(),
+                "ESC" | "<escape>" => "esc".to_string(),
+                "DEL" | "<backspace>" => "backspace".to_string(),
+                "<delete>" | "<deletechar>" => "del".to_string(),
+                "<prior>" => "pageup".to_string(),
+                "<next>" => "pagedown".to_string(),
+                "SPC" => {
+                    return Err(ParseError::InvalidKeySyntax(
+                        "Space is not a bindable key".to_string(),
+                    ))
+                }
+                named if named.starts_with('<') && named.ends_with('>') => {
+                    named[1..named.len() - 1].to_string()
+                }
+                single if single.chars().count() == 1 => {
+                    let c = single.chars().next().unwrap_or_default();
+                    if c.is_ascii_uppercase() {
+                        modifiers.push("shift");
+                    }
+                    c.to_ascii_lowercase().to_string()
+                }
+                _ => {
+                    return Err(ParseError::InvalidKeySyntax(format!(
+                        "Unknown emacs key: {}",
+                        token
+                    )))
+                }
+            };
+            tokens.push(combo_string(&modifiers, &key));
+        }
+        KeySequence::from_str(&tokens.join(" "))
+    }
+
+    /// Split a binding form into (keymap, key description, command)
+    fn parse_form(line: &str) -> Option<Result<(Option<&str>, &str, &str), &'static str>> {
+        let body = line.trim().strip_prefix('(')?;
+        let (head, takes_map) = Self::FORMS
+            .iter()
+            .find(|(form, _)| {
+                body.strip_prefix(form)
+                    .is_some_and(|rest| rest.starts_with(char::is_whitespace))
+            })
+            .copied()?;
+        let mut rest = body[head.len()..].trim_start();
+
+        let keymap = if takes_map {
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            let map = &rest[..end];
+            rest = rest[end..].trim_start();
+            Some(map.trim_start_matches('\''))
+        } else {
+            None
+        };
+
+        let Some(start) = rest.find('"') else {
+            return Some(Err("Unsupported key description"));
+        };
+        let Some(len) = rest[start + 1..].find('"') else {
+            return Some(Err("Unterminated key string"));
+        };
+        let keys = &rest[start + 1..start + 1 + len];
+        let after = &rest[start + len + 2..];
+
+        let Some(quote) = after.find('\'') else {
+            return Some(Err("Unsupported binding target"));
+        };
+        let command = after[quote + 1..]
+            .split(|c: char| c.is_whitespace() || c == ')')
+            .next()
+            .unwrap_or_default();
+        if command.is_empty() || command == "nil" {
+            return Some(Err("Unsupported binding target"));
+        }
+
+        Some(Ok((keymap, keys, command)))
+    }
+
+    /// Translate emacs key definition forms
+    pub fn translate(&self, content: &str) -> Result<ImportedBindings, ParseError> {
+        let mut imported = ImportedBindings::default();
+
+        for (idx, line) in content.lines().enumerate() {
+            let line_num = idx + 1;
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with(';') {
+                continue;
+            }
+
+            let (keymap, keys, command) = match Self::parse_form(trimmed) {
+                None => continue,
+                Some(Err(reason)) => {
+                    imported.skip(line_num, trimmed, reason);
+                    continue;
+                }
+                Some(Ok(form)) => form,
+            };
+
+            let sequence = match Self::key_sequence(keys) {
+                Ok(sequence) => sequence,
+                Err(e) => {
+                    imported.skip(line_num, trimmed, e.to_string());
+                    continue;
+                }
+            };
+            let action_id = match Self::action_for(command) {
+                Some(action) => action.to_string(),
+                None => match foreign_action(ImportScheme::Emacs, command) {
+                    Some(action) => action,
+                    None => {
+                        imported.skip(line_num, trimmed, "Unsupported binding target");
+                        continue;
+                    }
+                },
+            };
+
+            imported.add(
+                ImportScheme::Emacs,
+                action_id,
+                &sequence,
+                Self::contexts_for(keymap),
+                command,
+            );
+        }
+
+        Ok(imported)
+    }
+}
+
+impl KeybindParser for EmacsImporter {
+    fn parse(&self, content: &str) -> Result<Vec<Keybind>, ParseError> {
+        Ok(self.translate(content)?.keybinds)
+    }
+}
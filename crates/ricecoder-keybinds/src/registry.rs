@@ -4,7 +4,8 @@ use std::collections::HashMap;
 
 use crate::{
     error::RegistryError,
-    models::{Context, KeyCombo, Keybind},
+    models::{Context, KeyCombo, KeySequence, Keybind},
+    when::{ContextState, WhenClause},
 };
 
 /// Registry for storing and looking up keybinds
@@ -15,6 +16,8 @@ pub struct KeybindRegistry {
     by_key_context: HashMap<(Context, String), String>,
     /// Map from key_combo to action_id (for global keybinds)
     by_key_global: HashMap<String, String>,
+    /// Map from key_combo to (action_id, when-clause) for conditional keybinds
+    by_key_conditional: HashMap<String, Vec<(String, WhenClause)>>,
 }
 
 impl KeybindRegistry {
@@ -24,6 +27,7 @@ impl KeybindRegistry {
             by_action: HashMap::new(),
             by_key_context: HashMap::new(),
             by_key_global: HashMap::new(),
+            by_key_conditional: HashMap::new(),
         }
     }
 
@@ -36,44 +40,101 @@ impl KeybindRegistry {
             ));
         }
 
-        // Parse all key combinations (primary + alternatives)
+        // Parse all key sequences (primary + alternatives); single combos
+        // produce the same key string as before
         let key_combos = keybind
-            .parse_all_keys()
+            .parse_all_sequences()
             .map_err(|e| RegistryError::InvalidActionIdFormat(format!("Invalid key: {}", e)))?;
 
-        // Register the keybind by action
+        let when = keybind
+            .parse_when()
+            .map_err(|e| RegistryError::InvalidWhenClause(e.to_string()))?;
+
         let action_id = keybind.action_id.clone();
+        let key_strs: Vec<String> = key_combos.iter().map(|combo| combo.to_string()).collect();
+
+        // Check every key before touching the indexes, so a rejected keybind
+        // leaves the registry unchanged
+        for key_str in &key_strs {
+            self.check_conflicts(&keybind, key_str, when.as_ref())?;
+        }
+
+        // Register the keybind by action
         self.by_action.insert(action_id.clone(), keybind.clone());
 
         // Register each key combination
-        for key_combo in key_combos {
-            let key_str = key_combo.to_string();
-
-            // Register by key and context
-            if keybind.contexts.is_empty() {
-                // Global keybind
-                if let Some(existing_action) = self.by_key_global.get(&key_str) {
-                    if existing_action != &keybind.action_id {
-                        return Err(RegistryError::DuplicateActionId(format!(
-                            "Global key {} already bound to {}",
-                            key_str, existing_action
-                        )));
-                    }
+        for key_str in key_strs {
+            if let Some(when) = &when {
+                // Conditional keybinds may share keys; they are resolved at
+                // dispatch time by evaluating their when-clause
+                let candidates = self.by_key_conditional.entry(key_str).or_default();
+                if !candidates
+                    .iter()
+                    .any(|(existing, _)| existing == &action_id)
+                {
+                    candidates.push((action_id.clone(), when.clone()));
                 }
+            } else if keybind.contexts.is_empty() {
+                // Global keybind
                 self.by_key_global.insert(key_str, action_id.clone());
             } else {
                 // Context-specific keybinds
                 for &context in &keybind.contexts {
-                    let key = (context, key_str.clone());
-                    if let Some(existing_action) = self.by_key_context.get(&key) {
-                        if existing_action != &keybind.action_id {
-                            return Err(RegistryError::DuplicateActionId(format!(
-                                "Key {} in context {} already bound to {}",
-                                key_str, context, existing_action
-                            )));
-                        }
+                    self.by_key_context
+                        .insert((context, key_str.clone()), action_id.clone());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check whether binding `key_str` for `keybind` clashes with another action
+    fn check_conflicts(
+        &self,
+        keybind: &Keybind,
+        key_str: &str,
+        when: Option<&WhenClause>,
+    ) -> Result<(), RegistryError> {
+        let action_id = &keybind.action_id;
+
+        if let Some(when) = when {
+            let candidates = self.by_key_conditional.get(key_str).into_iter().flatten();
+            for (existing_action, existing_when) in candidates {
+                let overlaps = self.by_action.get(existing_action).is_some_and(|existing| {
+                    existing.contexts.is_empty()
+                        || keybind.contexts.is_empty()
+                        || existing
+                            .contexts
+                            .iter()
+                            .any(|c| keybind.contexts.contains(c))
+                });
+                if existing_action != action_id && existing_when == when && overlaps {
+                    return Err(RegistryError::DuplicateActionId(format!(
+                        "Key {} when {} already bound to {}",
+                        key_str, when, existing_action
+                    )));
+                }
+            }
+        } else if keybind.contexts.is_empty() {
+            if let Some(existing_action) = self.by_key_global.get(key_str) {
+                if existing_action != action_id {
+                    return Err(RegistryError::DuplicateActionId(format!(
+                        "Global key {} already bound to {}",
+                        key_str, existing_action
+                    )));
+                }
+            }
+        } else {
+            for &context in &keybind.contexts {
+                let key = (context, key_str.to_string());
+                if let Some(existing_action) = self.by_key_context.get(&key) {
+                    if existing_action != action_id {
+                        return Err(RegistryError::DuplicateActionId(format!(
+                            "Key {} in context {} already bound to {}",
+                            key_str, context, existing_action
+                        )));
                     }
-                    self.by_key_context.insert(key, action_id.clone());
                 }
             }
         }
@@ -111,7 +172,108 @@ impl KeybindRegistry {
         key: &KeyCombo,
         contexts: &[Context],
     ) -> Option<&str> {
-        let key_str = key.to_string();
+        self.lookup_key_str_with_contexts(&key.to_string(), contexts)
+    }
+
+    /// Lookup action ID by key sequence with context hierarchy
+    pub fn lookup_sequence_with_contexts(
+        &self,
+        sequence: &KeySequence,
+        contexts: &[Context],
+    ) -> Option<&str> {
+        self.lookup_key_str_with_contexts(&sequence.to_string(), contexts)
+    }
+
+    /// Lookup action ID by key sequence in a runtime state
+    ///
+    /// Conditional keybinds whose when-clause holds take precedence (most
+    /// specific context first); otherwise falls back to the context lookup.
+    pub fn lookup_sequence_in_state(
+        &self,
+        sequence: &KeySequence,
+        state: &ContextState,
+    ) -> Option<&str> {
+        let key_str = sequence.to_string();
+
+        let conditional = self
+            .by_key_conditional
+            .get(&key_str)
+            .and_then(|candidates| {
+                candidates
+                    .iter()
+                    .filter_map(|(action_id, when)| {
+                        let keybind = self.by_action.get(action_id)?;
+                        let applies = keybind.contexts.is_empty()
+                            || keybind.applies_to_any_context(&state.contexts);
+                        (applies && when.evaluate(state)).then_some(keybind)
+                    })
+                    .max_by_key(|keybind| keybind.primary_context().priority())
+            });
+        if let Some(keybind) = conditional {
+            return Some(keybind.action_id.as_str());
+        }
+
+        self.lookup_key_str_with_contexts(&key_str, &state.contexts)
+    }
+
+    /// Lookup action ID by key combination in a runtime state
+    pub fn lookup_by_key_in_state(&self, key: &KeyCombo, state: &ContextState) -> Option<&str> {
+        self.lookup_sequence_in_state(&KeySequence::from(key.clone()), state)
+    }
+
+    /// Get all bound sequences that continue the given prefix in the contexts
+    ///
+    /// Global keybinds are always included, matching lookup fallback rules.
+    /// Results are sorted by key sequence.
+    pub fn continuations(
+        &self,
+        prefix: &KeySequence,
+        contexts: &[Context],
+    ) -> Vec<(KeySequence, &Keybind)> {
+        self.continuations_in_state(prefix, &ContextState::from_contexts(contexts))
+    }
+
+    /// Get all bound sequences that continue the given prefix in a runtime
+    /// state, skipping keybinds whose when-clause does not hold
+    pub fn continuations_in_state(
+        &self,
+        prefix: &KeySequence,
+        state: &ContextState,
+    ) -> Vec<(KeySequence, &Keybind)> {
+        let mut result: Vec<(KeySequence, &Keybind)> = self
+            .by_action
+            .values()
+            .filter(|kb| kb.contexts.is_empty() || kb.applies_to_any_context(&state.contexts))
+            .filter(|kb| match kb.parse_when() {
+                Ok(Some(when)) => when.evaluate(state),
+                Ok(None) => true,
+                Err(_) => false,
+            })
+            .flat_map(|kb| {
+                kb.parse_all_sequences()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|seq| prefix.is_prefix_of(seq))
+                    .map(move |seq| (seq, kb))
+            })
+            .collect();
+        result.sort_by_key(|(seq, _)| seq.to_string());
+        result
+    }
+
+    /// Check if any bound sequence continues the given prefix
+    pub fn has_continuation(&self, prefix: &KeySequence, contexts: &[Context]) -> bool {
+        !self.continuations(prefix, contexts).is_empty()
+    }
+
+    /// Check if any bound sequence continues the given prefix in a state
+    pub fn has_continuation_in_state(&self, prefix: &KeySequence, state: &ContextState) -> bool {
+        !self.continuations_in_state(prefix, state).is_empty()
+    }
+
+    /// Shared lookup over the key string indexes
+    fn lookup_key_str_with_contexts(&self, key_str: &str, contexts: &[Context]) -> Option<&str> {
+        let key_str = key_str.to_string();
 
         // Sort contexts by priority (highest first)
         let mut sorted_contexts = contexts.to_vec();
@@ -148,12 +310,19 @@ impl KeybindRegistry {
         if let Some(keybind) = self.by_action.remove(action_id) {
             // Remove all key combinations from mappings
             let key_combos = keybind
-                .parse_all_keys()
+                .parse_all_sequences()
                 .map_err(|e| RegistryError::InvalidActionIdFormat(format!("Invalid key: {}", e)))?;
 
             for key_combo in key_combos {
                 let key_str = key_combo.to_string();
 
+                if keybind.when.is_some() {
+                    if let Some(candidates) = self.by_key_conditional.get_mut(&key_str) {
+                        candidates.retain(|(existing, _)| existing != action_id);
+                    }
+                    continue;
+                }
+
                 if keybind.contexts.is_empty() {
                     self.by_key_global.remove(&key_str);
                 } else {
@@ -180,6 +349,7 @@ impl KeybindRegistry {
         self.by_action.clear();
         self.by_key_context.clear();
         self.by_key_global.clear();
+        self.by_key_conditional.clear();
     }
 
     /// Get all keybinds for a category
@@ -0,0 +1,348 @@
+//! When-clause expressions evaluated against the runtime UI state
+//!
+//! Grammar (VS Code style):
+//! - `name` - true if the flag is set, the context is active, or for
+//!   `<widget>Focused` if that widget has focus; `modalOpen` checks modals
+//! - `key == value` / `key != value` - compare `focus` or `mode` (values may be
+//!   quoted)
+//! - `!expr`, `expr && expr`, `expr || expr`, `( expr )`
+
+use std::{collections::BTreeSet, fmt, str::FromStr};
+
+use crate::{
+    error::ParseError,
+    models::{Context, ContextFrame},
+};
+
+/// Flattened view of the context stack used to evaluate when-clauses
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContextState {
+    /// Active contexts, most specific first
+    pub contexts: Vec<Context>,
+    /// Focused widget from the top-most frame that sets one
+    pub focused: Option<String>,
+    /// Mode from the top-most frame that sets one (e.g. "insert")
+    pub mode: Option<String>,
+    /// Whether any frame on the stack is modal
+    pub modal_open: bool,
+    /// Union of all frame flags (e.g. "popupOpen")
+    pub flags: BTreeSet<String>,
+}
+
+impl ContextState {
+    /// State with only the given contexts active
+    pub fn from_contexts(contexts: &[Context]) -> Self {
+        ContextState {
+            contexts: contexts.to_vec(),
+            ..Default::default()
+        }
+    }
+
+    /// Flatten frames (bottom to top) on top of the active contexts
+    pub fn from_frames(contexts: &[Context], frames: &[ContextFrame]) -> Self {
+        let mut state = Self::from_contexts(contexts);
+        for frame in frames {
+            if frame.focused.is_some() {
+                state.focused = frame.focused.clone();
+            }
+            if frame.mode.is_some() {
+                state.mode = frame.mode.clone();
+            }
+            state.modal_open |= frame.modal;
+            state.flags.extend(frame.flags.iter().cloned());
+        }
+        state
+    }
+
+    /// Resolve a bare identifier
+    fn is_set(&self, name: &str) -> bool {
+        if name == "modalOpen" {
+            return self.modal_open;
+        }
+        if self.flags.contains(name) {
+            return true;
+        }
+        if let Some(widget) = name.strip_suffix("Focused") {
+            return self
+                .focused
+                .as_deref()
+                .is_some_and(|focused| focused.eq_ignore_ascii_case(widget));
+        }
+        Context::from_str(name).is_ok_and(|context| self.contexts.contains(&context))
+    }
+
+    /// Resolve a comparison key
+    fn value(&self, key: &str) -> Option<&str> {
+        match key {
+            "focus" | "focused" => self.focused.as_deref(),
+            "mode" => self.mode.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Parsed when-clause expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WhenClause {
+    /// Bare identifier
+    Flag(String),
+    /// `key == value` (or `!=` when `negated`)
+    Equals {
+        key: String,
+        value: String,
+        negated: bool,
+    },
+    Not(Box<WhenClause>),
+    And(Box<WhenClause>, Box<WhenClause>),
+    Or(Box<WhenClause>, Box<WhenClause>),
+}
+
+impl WhenClause {
+    /// Evaluate against the runtime state
+    pub fn evaluate(&self, state: &ContextState) -> bool {
+        match self {
+            WhenClause::Flag(name) => state.is_set(name),
+            WhenClause::Equals {
+                key,
+                value,
+                negated,
+            } => {
+                let matches = state
+                    .value(key)
+                    .is_some_and(|actual| actual.eq_ignore_ascii_case(value));
+                matches != *negated
+            }
+            WhenClause::Not(inner) => !inner.evaluate(state),
+            WhenClause::And(a, b) => a.evaluate(state) && b.evaluate(state),
+            WhenClause::Or(a, b) => a.evaluate(state) || b.evaluate(state),
+        }
+    }
+}
+
+impl fmt::Display for WhenClause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WhenClause::Flag(name) => write!(f, "{}", name),
+            WhenClause::Equals {
+                key,
+                value,
+                negated,
+            } => write!(
+                f,
+                "{} {} {}",
+                key,
+                if *negated { "!=" } else { "==" },
+                value
+            ),
+            WhenClause::Not(inner) => match inner.as_ref() {
+                WhenClause::And(..) | WhenClause::Or(..) => write!(f, "!({})", inner),
+                _ => write!(f, "!{}", inner),
+            },
+            WhenClause::And(a, b) => {
+                write_operand(f, a, true)?;
+                write!(f, " && ")?;
+                write_operand(f, b, true)
+            }
+            WhenClause::Or(a, b) => {
+                write_operand(f, a, false)?;
+                write!(f, " || ")?;
+                write_operand(f, b, false)
+            }
+        }
+    }
+}
+
+/// Write an operand, parenthesizing `||` inside `&&`
+fn write_operand(f: &mut fmt::Formatter<'_>, clause: &WhenClause, in_and: bool) -> fmt::Result {
+    if in_and && matches!(clause, WhenClause::Or(..)) {
+        write!(f, "({})", clause)
+    } else {
+        write!(f, "{}", clause)
+    }
+}
+
+impl FromStr for WhenClause {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens = tokenize(s)?;
+        if tokens.is_empty() {
+            return Err(ParseError::InvalidWhenClause(
+                "Empty when-clause".to_string(),
+            ));
+        }
+        let mut parser = Parser { tokens, pos: 0 };
+        let clause = parser.or()?;
+        if parser.pos != parser.tokens.len() {
+            return Err(ParseError::InvalidWhenClause(format!(
+                "Unexpected token in '{}'",
+                s
+            )));
+        }
+        Ok(clause)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Ident(String),
+    Not,
+    And,
+    Or,
+    Eq,
+    NotEq,
+    Open,
+    Close,
+}
+
+fn tokenize(s: &str) -> Result<Vec<Token>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = s.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::Open);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::Close);
+            }
+            '!' => {
+                chars.next();
+                if chars.peek() == Some(&'=') {
+                    chars.next();
+                    tokens.push(Token::NotEq);
+                } else {
+                    tokens.push(Token::Not);
+                }
+            }
+            '&' | '|' | '=' => {
+                chars.next();
+                if chars.next() != Some(c) {
+                    return Err(ParseError::InvalidWhenClause(format!(
+                        "Expected '{}{}' in '{}'",
+                        c, c, s
+                    )));
+                }
+                tokens.push(match c {
+                    '&' => Token::And,
+                    '|' => Token::Or,
+                    _ => Token::Eq,
+                });
+            }
+            '"' | '\'' => {
+                chars.next();
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some(q) if q == c => break,
+                        Some(ch) => value.push(ch),
+                        None => {
+                            return Err(ParseError::InvalidWhenClause(format!(
+                                "Unterminated string in '{}'",
+                                s
+                            )))
+                        }
+                    }
+                }
+                tokens.push(Token::Ident(value));
+            }
+            _ => {
+                let mut ident = String::new();
+                while let Some(&ch) = chars.peek() {
+                    if ch.is_alphanumeric() || matches!(ch, '_' | '.' | '-' | ':') {
+                        ident.push(ch);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                if ident.is_empty() {
+                    return Err(ParseError::InvalidWhenClause(format!(
+                        "Unexpected character '{}' in '{}'",
+                        c, s
+                    )));
+                }
+                tokens.push(Token::Ident(ident));
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Recursive-descent parser: or := and ('||' and)*, and := unary ('&&' unary)*
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn or(&mut self) -> Result<WhenClause, ParseError> {
+        let mut clause = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            clause = WhenClause::Or(Box::new(clause), Box::new(self.and()?));
+        }
+        Ok(clause)
+    }
+
+    fn and(&mut self) -> Result<WhenClause, ParseError> {
+        let mut clause = self.unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            clause = WhenClause::And(Box::new(clause), Box::new(self.unary()?));
+        }
+        Ok(clause)
+    }
+
+    fn unary(&mut self) -> Result<WhenClause, ParseError> {
+        match self.next() {
+            Some(Token::Not) => Ok(WhenClause::Not(Box::new(self.unary()?))),
+            Some(Token::Open) => {
+                let clause = self.or()?;
+                match self.next() {
+                    Some(Token::Close) => Ok(clause),
+                    _ => Err(ParseError::InvalidWhenClause("Missing ')'".to_string())),
+                }
+            }
+            Some(Token::Ident(name)) => {
+                let negated = match self.peek() {
+                    Some(Token::Eq) => false,
+                    Some(Token::NotEq) => true,
+                    _ => return Ok(WhenClause::Flag(name)),
+                };
+                self.pos += 1;
+                match self.next() {
+                    Some(Token::Ident(value)) => Ok(WhenClause::Equals {
+                        key: name,
+                        value,
+                        negated,
+                    }),
+                    _ => Err(ParseError::InvalidWhenClause(format!(
+                        "Missing value after '{}'",
+                        name
+                    ))),
+                }
+            }
+            _ => Err(ParseError::InvalidWhenClause(
+                "Expected an expression".to_string(),
+            )),
+        }
+    }
+}
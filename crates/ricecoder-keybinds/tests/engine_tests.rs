@@ -5,7 +5,7 @@ use ricecoder_keybinds::{
 
 #[cfg(test)]
 mod tests {
-    use std::str::FromStr;
+    use std::{str::FromStr, time::Duration};
 
     use super::*;
 
@@ -380,4 +380,95 @@ mod tests {
         // Clean up
         let _ = persistence.delete_profile("test_default_persistence");
     }
+
+    #[test]
+    fn test_feed_key_sequences() {
+        let mut engine = KeybindEngine::new();
+        engine
+            .apply_keybinds(vec![
+                Keybind::new("editor.top", "g g", "navigation", "Top"),
+                Keybind::new("goto.definition", "g d", "navigation", "Definition"),
+                Keybind::new("settings.open", "ctrl+k ctrl+s", "view", "Settings"),
+                Keybind::new("editor.down", "j", "navigation", "Down"),
+            ])
+            .unwrap();
+
+        let g = KeyCombo::from_str("g").unwrap();
+        assert_eq!(engine.feed_key(g.clone()), SequenceMatch::Pending);
+        assert_eq!(
+            engine.pending_hint().as_deref(),
+            Some("g-  d:Definition  g:Top")
+        );
+        assert_eq!(
+            engine.feed_key(g.clone()),
+            SequenceMatch::Matched("editor.top".to_string())
+        );
+        assert!(!engine.is_chord_pending());
+
+        // A key that breaks the sequence is retried on its own
+        assert_eq!(engine.feed_key(g), SequenceMatch::Pending);
+        assert_eq!(
+            engine.feed_key(KeyCombo::from_str("j").unwrap()),
+            SequenceMatch::Matched("editor.down".to_string())
+        );
+        assert_eq!(
+            engine.feed_key(KeyCombo::from_str("x").unwrap()),
+            SequenceMatch::NoMatch
+        );
+
+        let (action, consumed) =
+            engine.process_key_with_state(KeyCombo::from_str("ctrl+k").unwrap());
+        assert_eq!((action, consumed), (None, true));
+        let (action, _) = engine.process_key_with_state(KeyCombo::from_str("ctrl+s").unwrap());
+        assert_eq!(action.as_deref(), Some("settings.open"));
+    }
+
+    #[test]
+    fn test_pending_sequence_times_out() {
+        let mut engine = KeybindEngine::new();
+        engine
+            .apply_keybinds(vec![
+                Keybind::new("editor.top", "g g", "navigation", "Top"),
+                Keybind::new("editor.down", "j", "navigation", "Down"),
+            ])
+            .unwrap();
+        engine.set_chord_timeout(Duration::from_millis(10));
+
+        let g = KeyCombo::from_str("g").unwrap();
+        assert_eq!(engine.feed_key(g.clone()), SequenceMatch::Pending);
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(!engine.is_chord_pending());
+        assert_eq!(engine.feed_key(g), SequenceMatch::Pending);
+    }
+
+    #[test]
+    fn test_validate_detects_prefix_conflicts() {
+        let engine = KeybindEngine::new();
+        let keybinds = vec![
+            Keybind::new("goto.prefix", "g", "navigation", "Go"),
+            Keybind::new("editor.top", "g g", "navigation", "Top"),
+            Keybind::new_with_contexts(
+                "chat.clear",
+                "ctrl+k",
+                "chat",
+                "Clear",
+                vec![Context::Chat],
+            ),
+            Keybind::new_with_contexts(
+                "input.kill",
+                "ctrl+k ctrl+k",
+                "input",
+                "Kill line",
+                vec![Context::Input],
+            ),
+        ];
+
+        let result = engine.validate_keybinds(&keybinds);
+        assert!(!result.is_valid);
+        assert_eq!(result.prefix_conflicts.len(), 1);
+        let conflict = &result.prefix_conflicts[0];
+        assert_eq!(conflict.prefix_action, "goto.prefix");
+        assert_eq!(conflict.sequence_action, "editor.top");
+        assert_eq!(conflict.sequence.to_string(), "g g");
+    }
 }
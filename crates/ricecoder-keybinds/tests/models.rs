@@ -101,3 +101,25 @@ fn test_keybind_manager_trait() {
     let action = manager.resolve_action(&KeyCombo::from_str("Ctrl+X").unwrap(), &Context::Global);
     assert!(action.is_none());
 }
+
+#[test]
+fn test_key_sequence_from_str() {
+    let seq = KeySequence::from_str("ctrl+k ctrl+s").unwrap();
+    assert_eq!(seq.len(), 2);
+    assert!(seq.is_chord());
+    assert_eq!(seq.to_string(), "Ctrl+k Ctrl+s");
+
+    // Leader bindings keep their single-combo meaning
+    let leader = KeySequence::from_str("<leader> g").unwrap();
+    assert_eq!(leader.len(), 1);
+    assert!(leader.combos[0].leader);
+    assert_eq!(
+        leader.to_string(),
+        KeyCombo::from_str("<leader> g").unwrap().to_string()
+    );
+
+    let prefix = KeySequence::from_str("g").unwrap();
+    assert!(prefix.is_prefix_of(&KeySequence::from_str("g g").unwrap()));
+    assert!(!prefix.is_prefix_of(&prefix));
+    assert!(KeySequence::from_str("  ").is_err());
+}
@@ -148,4 +148,143 @@ mod tests {
         let keybinds = parser.parse(markdown).unwrap();
         assert_eq!(keybinds.len(), 0);
     }
+
+    #[test]
+    fn test_import_vscode_keybindings() {
+        let content = r#"[
+            // Command palette
+            { "key": "ctrl+shift+p", "command": "workbench.action.showCommands" },
+            { "key": "ctrl+k ctrl+t", "command": "workbench.action.selectTheme" },
+            { "key": "ctrl+z", "command": "undo", "when": "editorTextFocus && !editorReadonly" },
+            /* custom */
+            { "key": "ctrl+alt+r", "command": "extension.runTask", "when": "chatInputHasFocus" },
+            { "key": "ctrl+k", "command": "-deleteAllRight" },
+            { "key": "ctrl+space", "command": "editor.action.triggerSuggest" },
+        ]"#;
+
+        let report = import_preset(ImportScheme::VsCode, content).unwrap();
+        let ids: Vec<&str> = report
+            .keybinds
+            .iter()
+            .map(|kb| kb.action_id.as_str())
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                "command.list",
+                "app.theme_list",
+                "input.undo",
+                "vscode.extension.runtask"
+            ]
+        );
+        assert_eq!(report.keybinds[1].key, "Ctrl+k Ctrl+t");
+        assert_eq!(report.keybinds[2].contexts, vec![Context::Input]);
+        assert_eq!(report.keybinds[3].contexts, vec![Context::Chat]);
+        assert_eq!(report.keybinds[3].category, "vscode");
+        assert_eq!(report.skipped.len(), 2);
+        assert_eq!(report.skipped[0].line, 5);
+
+        let registry = ParserRegistry::new();
+        assert_eq!(registry.parse(content, "vscode").unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_import_vim_maps() {
+        let content = r#"
+" leader mappings
+nnoremap <silent> <leader>ff <cmd>Telescope find_files<CR>
+nnoremap <C-s> :w<CR>
+nmap G G
+inoremap <C-z> u
+nnoremap gd :lua vim.lsp.buf.definition()<CR>
+nnoremap g :echo "go"<CR>
+vnoremap < <gv
+nnoremap <Space>w :w<CR>
+set number
+"#;
+
+        let report = import_preset(ImportScheme::Vim, content).unwrap();
+        let telescope = report
+            .keybinds
+            .iter()
+            .find(|kb| kb.action_id == "vim.telescope_find_files")
+            .unwrap();
+        assert_eq!(telescope.key, "<leader> f f");
+
+        let save = report
+            .keybinds
+            .iter()
+            .find(|kb| kb.action_id == "vim.w")
+            .unwrap();
+        assert_eq!(save.key, "Ctrl+s");
+
+        let bottom = report
+            .keybinds
+            .iter()
+            .find(|kb| kb.action_id == "input.buffer_end")
+            .unwrap();
+        assert_eq!(bottom.key, "Shift+g");
+        assert_eq!(bottom.contexts, vec![Context::Input]);
+
+        // `g` shadows `g d`
+        assert_eq!(report.prefix_conflicts.len(), 1);
+        assert_eq!(report.prefix_conflicts[0].sequence.to_string(), "g d");
+
+        // visual mode and <Space> cannot be expressed
+        assert_eq!(report.skipped.len(), 2);
+        assert_eq!(report.to_profile().name, "vim");
+    }
+
+    #[test]
+    fn test_import_emacs_definitions() {
+        let content = r#"
+;; Emacs bindings
+(global-set-key (kbd "C-x C-c") 'save-buffers-kill-terminal)
+(global-set-key (kbd "M-x") #'execute-extended-command)
+(keymap-global-set "C-c a" 'org-agenda)
+(define-key minibuffer-local-map (kbd "C-y") 'yank)
+(global-set-key (kbd "C-c l") (lambda () (interactive) (message "hi")))
+(global-set-key (kbd "SPC") 'ignore)
+(setq inhibit-startup-screen t)
+"#;
+
+        let report = import_preset(ImportScheme::Emacs, content).unwrap();
+        let ids: Vec<&str> = report
+            .keybinds
+            .iter()
+            .map(|kb| kb.action_id.as_str())
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                "app.exit",
+                "command.list",
+                "emacs.org_agenda",
+                "input.paste"
+            ]
+        );
+        assert_eq!(report.keybinds[0].key, "Ctrl+x Ctrl+c");
+        assert_eq!(report.keybinds[1].key, "Alt+x");
+        assert_eq!(report.keybinds[3].contexts, vec![Context::CommandPalette]);
+        assert_eq!(report.skipped.len(), 2);
+        assert!(!report.has_conflicts());
+    }
+
+    #[test]
+    fn test_engine_imports_preset_as_profile() {
+        let mut engine = KeybindEngine::new();
+        let report = engine
+            .import_preset(
+                "emacs".parse().unwrap(),
+                "(global-set-key (kbd \"C-x C-s\") 'save-buffer)",
+            )
+            .unwrap();
+        assert_eq!(report.keybinds.len(), 1);
+
+        let profile = engine.get_profile("emacs").unwrap();
+        assert_eq!(profile.keybinds[0].action_id, "emacs.save_buffer");
+        assert!(engine
+            .import_preset(ImportScheme::Emacs, "(global-set-key (kbd \"C-a\") 'x)")
+            .is_err());
+    }
 }
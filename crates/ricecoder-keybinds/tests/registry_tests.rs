@@ -46,6 +46,36 @@ mod tests {
         assert!(registry.register(kb2).is_err());
     }
 
+    #[test]
+    fn test_rejected_registration_leaves_registry_unchanged() {
+        let mut registry = KeybindRegistry::new();
+        registry
+            .register(Keybind::new("editor.save", "Ctrl+S", "editing", "Save"))
+            .unwrap();
+
+        // The first key is free; the alternative clashes
+        let mut open = Keybind::new("editor.open", "Ctrl+O", "editing", "Open");
+        open.alternatives = vec!["Ctrl+S".to_string()];
+        assert!(registry.register(open).is_err());
+
+        assert_eq!(registry.len(), 1);
+        assert!(registry.lookup_by_action("editor.open").is_none());
+        let ctrl_o = KeyCombo::from_str("Ctrl+O").unwrap();
+        assert_eq!(registry.lookup_by_key(&ctrl_o), None);
+        let ctrl_s = KeyCombo::from_str("Ctrl+S").unwrap();
+        assert_eq!(registry.lookup_by_key(&ctrl_s), Some("editor.save"));
+
+        let mut focused = Keybind::new("editor.format", "Ctrl+F", "editing", "Format");
+        focused.when = Some("editorFocused".to_string());
+        registry.register(focused).unwrap();
+        let mut search = Keybind::new("editor.search", "Ctrl+F", "editing", "Search");
+        search.when = Some("editorFocused".to_string());
+        assert!(registry.register(search).is_err());
+
+        assert_eq!(registry.len(), 2);
+        assert!(registry.lookup_by_action("editor.search").is_none());
+    }
+
     #[test]
     fn test_all_keybinds() {
         let mut registry = KeybindRegistry::new();
@@ -0,0 +1,108 @@
+use std::str::FromStr;
+
+use ricecoder_keybinds::*;
+
+#[test]
+fn test_when_clause_evaluation() {
+    let clause = WhenClause::from_str("editorFocused && !popupOpen").unwrap();
+    let mut state = ContextState::from_frames(
+        &[Context::Input, Context::Global],
+        &[ContextFrame::new(Context::Input).with_focus("editor")],
+    );
+    assert!(clause.evaluate(&state));
+
+    state.flags.insert("popupOpen".to_string());
+    assert!(!clause.evaluate(&state));
+
+    let clause = WhenClause::from_str("mode == 'insert' || (chat && !modalOpen)").unwrap();
+    assert!(!clause.evaluate(&state));
+    state.contexts.push(Context::Chat);
+    assert!(clause.evaluate(&state));
+    state.modal_open = true;
+    state.mode = Some("insert".to_string());
+    assert!(clause.evaluate(&state));
+    assert_eq!(clause.to_string(), "mode == insert || chat && !modalOpen");
+}
+
+#[test]
+fn test_when_clause_parse_errors() {
+    assert!(WhenClause::from_str("").is_err());
+    assert!(WhenClause::from_str("a &").is_err());
+    assert!(WhenClause::from_str("(a || b").is_err());
+    assert!(WhenClause::from_str("mode ==").is_err());
+    assert!(WhenClause::from_str("a b").is_err());
+}
+
+#[test]
+fn test_dispatch_depends_on_runtime_state() {
+    let mut engine = KeybindEngine::new();
+    engine
+        .apply_keybinds(vec![
+            Keybind::new("editor.find", "ctrl+f", "search", "Find in file")
+                .with_when("editorFocused && !popupOpen"),
+            Keybind::new("popup.filter", "ctrl+f", "search", "Filter popup").with_when("popupOpen"),
+            Keybind::new("app.search", "ctrl+f", "search", "Global search"),
+        ])
+        .unwrap();
+
+    let key = KeyCombo::from_str("ctrl+f").unwrap();
+    assert_eq!(
+        engine.get_action_with_active_contexts(&key),
+        Some("app.search")
+    );
+
+    engine.push_frame(ContextFrame::new(Context::Input).with_focus("editor"));
+    assert_eq!(
+        engine.get_action_with_active_contexts(&key),
+        Some("editor.find")
+    );
+
+    engine.push_frame(
+        ContextFrame::new(Context::Dialog)
+            .with_modal(true)
+            .with_flag("popupOpen"),
+    );
+    assert!(engine.context_state().modal_open);
+    assert_eq!(
+        engine.feed_key(key.clone()),
+        SequenceMatch::Matched("popup.filter".to_string())
+    );
+
+    assert!(engine.pop_frame().is_some());
+    assert_eq!(engine.current_context(), &Context::Input);
+    assert_eq!(
+        engine.get_action_with_active_contexts(&key),
+        Some("editor.find")
+    );
+}
+
+#[test]
+fn test_conditional_bindings_conflicts() {
+    let keybinds = vec![
+        Keybind::new("a", "ctrl+f", "x", "A").with_when("editorFocused"),
+        Keybind::new("b", "ctrl+f", "x", "B").with_when("popupOpen"),
+    ];
+    assert!(ConflictDetector::detect(&keybinds).is_empty());
+
+    let mut registry = KeybindRegistry::new();
+    for keybind in keybinds {
+        registry.register(keybind).unwrap();
+    }
+    assert!(registry
+        .register(Keybind::new("c", "ctrl+f", "x", "C").with_when("popupOpen"))
+        .is_err());
+    assert!(matches!(
+        registry.register(Keybind::new("d", "ctrl+g", "x", "D").with_when("a &&")),
+        Err(RegistryError::InvalidWhenClause(_))
+    ));
+
+    registry.unregister("b").unwrap();
+    let state = ContextState {
+        flags: ["popupOpen".to_string()].into_iter().collect(),
+        ..ContextState::from_contexts(&[Context::Global])
+    };
+    assert_eq!(
+        registry.lookup_by_key_in_state(&KeyCombo::from_str("ctrl+f").unwrap(), &state),
+        None
+    );
+}
@@ -29,6 +29,18 @@ pub struct RuleMetrics {
     pub last_applied: Option<DateTime<Utc>>,
     /// Average time to apply the rule (in milliseconds)
     pub avg_application_time_ms: f64,
+    /// Number of suggestions influenced by the rule that were accepted
+    #[serde(default)]
+    pub accepted_suggestions: u64,
+    /// Number of suggestions influenced by the rule that were rejected
+    #[serde(default)]
+    pub rejected_suggestions: u64,
+    /// Number of suggestions influenced by the rule that were reverted after acceptance
+    #[serde(default)]
+    pub reverted_suggestions: u64,
+    /// Whether the rule is consistently rejected and should be reviewed
+    #[serde(default)]
+    pub flagged_for_review: bool,
 }
 
 impl RuleMetrics {
@@ -44,9 +56,27 @@ impl RuleMetrics {
             first_applied: None,
             last_applied: None,
             avg_application_time_ms: 0.0,
+            accepted_suggestions: 0,
+            rejected_suggestions: 0,
+            reverted_suggestions: 0,
+            flagged_for_review: false,
         }
     }
 
+    /// Total suggestion feedback received for the rule
+    pub fn feedback_count(&self) -> u64 {
+        self.accepted_suggestions + self.rejected_suggestions + self.reverted_suggestions
+    }
+
+    /// Share of suggestion feedback that was a rejection or revert (0.0 to 1.0)
+    pub fn rejection_rate(&self) -> f32 {
+        let total = self.feedback_count();
+        if total == 0 {
+            return 0.0;
+        }
+        (self.rejected_suggestions + self.reverted_suggestions) as f32 / total as f32
+    }
+
     /// Record a successful application
     pub fn record_success(&mut self, application_time_ms: f64) {
         self.usage_count += 1;
@@ -91,6 +121,104 @@ impl RuleMetrics {
     }
 }
 
+/// What happened to a suggestion influenced by learned rules
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SuggestionOutcome {
+    /// The suggestion was accepted
+    Accepted,
+    /// The suggestion was rejected
+    Rejected,
+    /// The suggestion was accepted and later reverted
+    Reverted,
+}
+
+impl std::fmt::Display for SuggestionOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SuggestionOutcome::Accepted => write!(f, "accepted"),
+            SuggestionOutcome::Rejected => write!(f, "rejected"),
+            SuggestionOutcome::Reverted => write!(f, "reverted"),
+        }
+    }
+}
+
+/// Feedback on a rule-influenced suggestion, reported by downstream crates
+/// (completion, generation, agents)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuggestionFeedback {
+    /// Rules that influenced the suggestion
+    pub rule_ids: Vec<String>,
+    /// What happened to the suggestion
+    pub outcome: SuggestionOutcome,
+    /// Component that produced the suggestion (e.g. "completion")
+    pub source: String,
+    /// Optional reason given for the outcome
+    pub reason: Option<String>,
+    /// When the feedback was recorded
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl SuggestionFeedback {
+    /// Create new feedback
+    pub fn new(source: String, rule_ids: Vec<String>, outcome: SuggestionOutcome) -> Self {
+        Self {
+            rule_ids,
+            outcome,
+            source,
+            reason: None,
+            recorded_at: Utc::now(),
+        }
+    }
+
+    /// Feedback for an accepted suggestion
+    pub fn accepted(source: String, rule_ids: Vec<String>) -> Self {
+        Self::new(source, rule_ids, SuggestionOutcome::Accepted)
+    }
+
+    /// Feedback for a rejected suggestion
+    pub fn rejected(source: String, rule_ids: Vec<String>) -> Self {
+        Self::new(source, rule_ids, SuggestionOutcome::Rejected)
+    }
+
+    /// Feedback for a reverted suggestion
+    pub fn reverted(source: String, rule_ids: Vec<String>) -> Self {
+        Self::new(source, rule_ids, SuggestionOutcome::Reverted)
+    }
+
+    /// Attach a reason
+    pub fn with_reason(mut self, reason: String) -> Self {
+        self.reason = Some(reason);
+        self
+    }
+}
+
+/// How suggestion feedback affects rule confidence
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedbackConfig {
+    /// Confidence multiplier applied on rejection
+    pub rejection_decay: f32,
+    /// Confidence multiplier applied on revert
+    pub revert_decay: f32,
+    /// Confidence added on acceptance
+    pub acceptance_boost: f32,
+    /// Minimum feedback before a rule can be flagged for review
+    pub min_feedback_for_review: u64,
+    /// Rejection rate at or above which a rule is flagged for review
+    pub review_rejection_rate: f32,
+}
+
+impl Default for FeedbackConfig {
+    fn default() -> Self {
+        Self {
+            rejection_decay: 0.85,
+            revert_decay: 0.7,
+            acceptance_boost: 0.05,
+            min_feedback_for_review: 5,
+            review_rejection_rate: 0.6,
+        }
+    }
+}
+
 /// Analytics insights about rule usage
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnalyticsInsights {
@@ -110,19 +238,108 @@ pub struct AnalyticsInsights {
     pub top_performing_rules: Vec<String>,
     /// Rules with lowest success rate
     pub bottom_performing_rules: Vec<String>,
+    /// Rules flagged for review because their suggestions keep being rejected
+    pub flagged_rules: Vec<String>,
 }
 
 /// Analytics engine for tracking rule metrics and generating insights
 pub struct AnalyticsEngine {
     /// Metrics for each rule
     metrics: Arc<RwLock<HashMap<String, RuleMetrics>>>,
+    /// How suggestion feedback affects confidence
+    feedback_config: FeedbackConfig,
 }
 
 impl AnalyticsEngine {
     /// Create a new analytics engine
     pub fn new() -> Self {
+        Self::with_feedback_config(FeedbackConfig::default())
+    }
+
+    /// Create a new analytics engine with a custom feedback configuration
+    pub fn with_feedback_config(feedback_config: FeedbackConfig) -> Self {
         Self {
             metrics: Arc::new(RwLock::new(HashMap::new())),
+            feedback_config,
+        }
+    }
+
+    /// Start tracking a rule, seeding its confidence from the rule itself
+    ///
+    /// Does nothing if the rule is already tracked.
+    pub async fn track_rule(&self, rule: &Rule) -> Result<()> {
+        let mut metrics = self.metrics.write().await;
+        metrics.entry(rule.id.clone()).or_insert_with(|| {
+            let mut rule_metrics = RuleMetrics::new(rule.id.clone());
+            rule_metrics.confidence = rule.confidence;
+            rule_metrics
+        });
+        Ok(())
+    }
+
+    /// Record feedback on a rule-influenced suggestion
+    ///
+    /// Rejections and reverts decay the confidence of every rule involved,
+    /// acceptances nudge it back up. Returns the IDs of rules that became
+    /// flagged for review with this feedback.
+    pub async fn record_feedback(&self, feedback: &SuggestionFeedback) -> Result<Vec<String>> {
+        let config = &self.feedback_config;
+        let mut metrics = self.metrics.write().await;
+        let mut newly_flagged = Vec::new();
+
+        for rule_id in &feedback.rule_ids {
+            let rule_metrics = metrics
+                .entry(rule_id.clone())
+                .or_insert_with(|| RuleMetrics::new(rule_id.clone()));
+
+            match feedback.outcome {
+                SuggestionOutcome::Accepted => {
+                    rule_metrics.accepted_suggestions += 1;
+                    rule_metrics.confidence =
+                        (rule_metrics.confidence + config.acceptance_boost).min(1.0);
+                }
+                SuggestionOutcome::Rejected => {
+                    rule_metrics.rejected_suggestions += 1;
+                    rule_metrics.confidence *= config.rejection_decay;
+                }
+                SuggestionOutcome::Reverted => {
+                    rule_metrics.reverted_suggestions += 1;
+                    rule_metrics.confidence *= config.revert_decay;
+                }
+            }
+
+            let should_flag = rule_metrics.feedback_count() >= config.min_feedback_for_review
+                && rule_metrics.rejection_rate() >= config.review_rejection_rate;
+            if should_flag && !rule_metrics.flagged_for_review {
+                rule_metrics.flagged_for_review = true;
+                newly_flagged.push(rule_id.clone());
+            }
+        }
+
+        Ok(newly_flagged)
+    }
+
+    /// Get metrics for rules flagged for review
+    pub async fn get_flagged_rules(&self) -> Result<Vec<RuleMetrics>> {
+        let metrics = self.metrics.read().await;
+        Ok(metrics
+            .values()
+            .filter(|m| m.flagged_for_review)
+            .cloned()
+            .collect())
+    }
+
+    /// Clear the review flag of a rule, e.g. after it has been reviewed
+    pub async fn clear_review_flag(&self, rule_id: &str) -> Result<()> {
+        let mut metrics = self.metrics.write().await;
+        if let Some(rule_metrics) = metrics.get_mut(rule_id) {
+            rule_metrics.flagged_for_review = false;
+            Ok(())
+        } else {
+            Err(LearningError::AnalyticsError(format!(
+                "Rule {} not found in metrics",
+                rule_id
+            )))
         }
     }
 
@@ -193,6 +410,7 @@ impl AnalyticsEngine {
                 least_used_rule: None,
                 top_performing_rules: Vec::new(),
                 bottom_performing_rules: Vec::new(),
+                flagged_rules: Vec::new(),
             });
         }
 
@@ -231,6 +449,13 @@ impl AnalyticsEngine {
             .map(|m| m.rule_id.clone())
             .collect();
 
+        let mut flagged_rules: Vec<String> = metrics
+            .values()
+            .filter(|m| m.flagged_for_review)
+            .map(|m| m.rule_id.clone())
+            .collect();
+        flagged_rules.sort();
+
         Ok(AnalyticsInsights {
             total_rules,
             total_applications,
@@ -240,6 +465,7 @@ impl AnalyticsEngine {
             least_used_rule,
             top_performing_rules,
             bottom_performing_rules,
+            flagged_rules,
         })
     }
 
@@ -428,6 +654,78 @@ mod tests {
         assert_eq!(all_metrics.len(), 0);
     }
 
+    #[tokio::test]
+    async fn test_record_feedback_decays_confidence() {
+        let engine = AnalyticsEngine::new();
+
+        engine
+            .record_feedback(&SuggestionFeedback::rejected(
+                "completion".to_string(),
+                vec!["rule_1".to_string()],
+            ))
+            .await
+            .unwrap();
+        let metrics = engine.get_rule_metrics("rule_1").await.unwrap().unwrap();
+        assert!((metrics.confidence - 0.425).abs() < 0.001);
+        assert_eq!(metrics.rejected_suggestions, 1);
+
+        engine
+            .record_feedback(&SuggestionFeedback::reverted(
+                "agents".to_string(),
+                vec!["rule_1".to_string()],
+            ))
+            .await
+            .unwrap();
+        engine
+            .record_feedback(&SuggestionFeedback::accepted(
+                "generation".to_string(),
+                vec!["rule_1".to_string()],
+            ))
+            .await
+            .unwrap();
+        let metrics = engine.get_rule_metrics("rule_1").await.unwrap().unwrap();
+        assert!((metrics.confidence - (0.425 * 0.7 + 0.05)).abs() < 0.001);
+        assert_eq!(metrics.feedback_count(), 3);
+        assert!((metrics.rejection_rate() - 2.0 / 3.0).abs() < 0.01);
+    }
+
+    #[tokio::test]
+    async fn test_record_feedback_flags_rejected_rules() {
+        let engine = AnalyticsEngine::new();
+        let rejected = SuggestionFeedback::rejected(
+            "completion".to_string(),
+            vec!["rule_1".to_string(), "rule_2".to_string()],
+        );
+        let accepted =
+            SuggestionFeedback::accepted("completion".to_string(), vec!["rule_2".to_string()]);
+
+        for _ in 0..4 {
+            engine.record_feedback(&accepted).await.unwrap();
+        }
+        for _ in 0..4 {
+            assert!(engine.record_feedback(&rejected).await.unwrap().is_empty());
+        }
+        let flagged = engine.record_feedback(&rejected).await.unwrap();
+        assert_eq!(flagged, vec!["rule_1".to_string()]);
+
+        // rule_2 crosses the threshold later; rule_1 is not reported again
+        let flagged = engine.record_feedback(&rejected).await.unwrap();
+        assert_eq!(flagged, vec!["rule_2".to_string()]);
+
+        let insights = engine.generate_insights().await.unwrap();
+        assert!(insights.flagged_rules.contains(&"rule_1".to_string()));
+
+        engine.clear_review_flag("rule_1").await.unwrap();
+        let flagged: Vec<_> = engine
+            .get_flagged_rules()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|m| m.rule_id)
+            .collect();
+        assert!(!flagged.contains(&"rule_1".to_string()));
+    }
+
     #[tokio::test]
     async fn test_analytics_engine_empty_insights() {
         let engine = AnalyticsEngine::new();
@@ -148,6 +148,38 @@ impl ConflictResolver {
         Self::apply_precedence(&matching_rules)
     }
 
+    /// Pick the winner between a local rule and a conflicting incoming rule
+    ///
+    /// Scope precedence decides first; within the same scope the higher version,
+    /// then the more recently updated rule, then the more confident rule wins.
+    /// Ties keep the local rule.
+    pub fn resolve_sync_conflict(local: &Rule, incoming: &Rule) -> Rule {
+        let precedence = |rule: &Rule| match rule.scope {
+            RuleScope::Project => 0,
+            RuleScope::Global => 1,
+            RuleScope::Session => 2,
+        };
+
+        let incoming_wins = precedence(incoming)
+            .cmp(&precedence(local))
+            .reverse()
+            .then(incoming.version.cmp(&local.version))
+            .then(incoming.updated_at.cmp(&local.updated_at))
+            .then(
+                incoming
+                    .confidence
+                    .partial_cmp(&local.confidence)
+                    .unwrap_or(std::cmp::Ordering::Equal),
+            )
+            .is_gt();
+
+        if incoming_wins {
+            incoming.clone()
+        } else {
+            local.clone()
+        }
+    }
+
     /// Check if rules in different scopes conflict
     pub fn check_cross_scope_conflicts(
         project_rules: &[Rule],
@@ -197,6 +229,32 @@ mod tests {
         assert!(ConflictResolver::detect_conflict(&rule1, &rule2));
     }
 
+    #[test]
+    fn test_resolve_sync_conflict() {
+        let local = create_test_rule("local", RuleScope::Global, "pattern1", "action1");
+        let mut incoming = create_test_rule("incoming", RuleScope::Global, "pattern1", "action2");
+        incoming.updated_at = local.updated_at;
+
+        // Ties keep the local rule
+        assert_eq!(
+            ConflictResolver::resolve_sync_conflict(&local, &incoming).id,
+            "local"
+        );
+
+        incoming.version = 2;
+        assert_eq!(
+            ConflictResolver::resolve_sync_conflict(&local, &incoming).id,
+            "incoming"
+        );
+
+        // Scope precedence beats version
+        let project = create_test_rule("project", RuleScope::Project, "pattern1", "action3");
+        assert_eq!(
+            ConflictResolver::resolve_sync_conflict(&project, &incoming).id,
+            "project"
+        );
+    }
+
     #[test]
     fn test_detect_conflict_same_pattern_same_action() {
         let rule1 = create_test_rule("rule1", RuleScope::Global, "pattern1", "action1");
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, path::PathBuf};
 
 use serde::{Deserialize, Serialize};
 
@@ -36,11 +36,13 @@ impl Default for DriftDetectionConfig {
 /// Drift detection engine
 pub struct DriftDetector {
     /// Configuration for drift detection
-    config: DriftDetectionConfig,
+    pub(crate) config: DriftDetectionConfig,
     /// Established patterns
-    patterns: HashMap<String, LearnedPattern>,
+    pub(crate) patterns: HashMap<String, LearnedPattern>,
     /// Detected drifts
-    drifts: Vec<DriftDetection>,
+    pub(crate) drifts: Vec<DriftDetection>,
+    /// IDs of the drifts recorded by the latest re-evaluation of each codebase
+    pub(crate) codebase_drifts: HashMap<PathBuf, Vec<String>>,
 }
 
 impl DriftDetector {
@@ -55,6 +57,7 @@ impl DriftDetector {
             config,
             patterns: HashMap::new(),
             drifts: Vec::new(),
+            codebase_drifts: HashMap::new(),
         }
     }
 
@@ -182,6 +185,7 @@ impl DriftDetector {
     /// Clear all detected drifts
     pub fn clear_drifts(&mut self) {
         self.drifts.clear();
+        self.codebase_drifts.clear();
     }
 
     /// Get drift statistics
@@ -0,0 +1,634 @@
+//! Scheduled drift re-evaluation
+//!
+//! Re-checks the codebase against the conventions captured when patterns were
+//! learned. Each source file is analyzed with ricecoder-research's
+//! [`StandardsDetector`] and compared with a baseline [`StandardsProfile`];
+//! every mismatch becomes a [`CodebaseDrift`] linked to the offending file, to
+//! the learned pattern covering that convention (if any), and to a concrete
+//! [`RemediationSuggestion`].
+//!
+//! [`ScheduledDriftReevaluation`] runs the re-evaluation periodically in the
+//! background and publishes each [`DriftReport`].
+
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use ricecoder_research::{CaseStyle, IndentType, StandardsDetector, StandardsProfile};
+use serde::{Deserialize, Serialize};
+use tokio::{
+    sync::{watch, RwLock},
+    task::JoinHandle,
+};
+
+use crate::drift_detector::DriftDetector;
+use crate::error::{LearningError, Result};
+use crate::intent_tracker::DriftDetection;
+
+/// File extensions checked during re-evaluation
+const SOURCE_EXTENSIONS: &[&str] = &["rs", "py", "js", "jsx", "ts", "tsx", "go", "java"];
+
+/// Convention checked during re-evaluation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Convention {
+    /// Function naming case
+    FunctionNaming,
+    /// Variable naming case
+    VariableNaming,
+    /// Type naming case
+    TypeNaming,
+    /// Indentation with tabs or spaces
+    Indentation,
+}
+
+impl Convention {
+    /// Pattern type keyword used to link learned patterns to this convention
+    pub fn category(&self) -> &'static str {
+        match self {
+            Convention::FunctionNaming | Convention::VariableNaming | Convention::TypeNaming => {
+                "naming"
+            }
+            Convention::Indentation => "formatting",
+        }
+    }
+}
+
+impl std::fmt::Display for Convention {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Convention::FunctionNaming => write!(f, "function naming"),
+            Convention::VariableNaming => write!(f, "variable naming"),
+            Convention::TypeNaming => write!(f, "type naming"),
+            Convention::Indentation => write!(f, "indentation"),
+        }
+    }
+}
+
+/// Kind of refactoring that would remove a drift
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RemediationKind {
+    /// Rename identifiers to another case style
+    RenameIdentifiers {
+        /// Case style found in the file
+        from: String,
+        /// Case style the codebase expects
+        to: String,
+    },
+    /// Re-indent the file
+    Reindent {
+        /// Indentation the codebase expects
+        indent: String,
+    },
+}
+
+/// A candidate refactoring for a drift
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemediationSuggestion {
+    /// File to change
+    pub file: PathBuf,
+    /// What to change
+    pub kind: RemediationKind,
+    /// Human-readable description of the change
+    pub description: String,
+}
+
+/// A convention the code no longer follows
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CodebaseDrift {
+    /// Learned pattern covering the convention, if one is registered
+    pub pattern_id: Option<String>,
+    /// Convention that drifted
+    pub convention: Convention,
+    /// What the codebase expects
+    pub expected: String,
+    /// What the file does
+    pub actual: String,
+    /// Offending file
+    pub file: PathBuf,
+    /// Severity level (low, medium, high)
+    pub severity: String,
+    /// Suggested remediation
+    pub remediation: RemediationSuggestion,
+}
+
+/// Result of re-evaluating a codebase
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DriftReport {
+    /// Root of the evaluated codebase
+    pub root: PathBuf,
+    /// When the report was generated
+    pub generated_at: DateTime<Utc>,
+    /// Number of source files analyzed
+    pub files_scanned: usize,
+    /// Conventions the code no longer follows
+    pub drifts: Vec<CodebaseDrift>,
+}
+
+impl DriftReport {
+    /// Whether the codebase follows all checked conventions
+    pub fn is_clean(&self) -> bool {
+        self.drifts.is_empty()
+    }
+
+    /// Drifts found in a file
+    pub fn drifts_for_file(&self, file: &Path) -> Vec<&CodebaseDrift> {
+        self.drifts.iter().filter(|d| d.file == file).collect()
+    }
+
+    /// IDs of learned patterns the code no longer follows
+    pub fn drifted_patterns(&self) -> Vec<String> {
+        let mut ids: Vec<String> = self
+            .drifts
+            .iter()
+            .filter_map(|d| d.pattern_id.clone())
+            .collect();
+        ids.sort();
+        ids.dedup();
+        ids
+    }
+
+    /// All remediation suggestions in the report
+    pub fn remediations(&self) -> Vec<&RemediationSuggestion> {
+        self.drifts.iter().map(|d| &d.remediation).collect()
+    }
+}
+
+/// Convention mismatches found by scanning a codebase
+///
+/// Scanning touches the filesystem only; [`DriftDetector::record_scan`] turns
+/// the mismatches into drifts.
+#[derive(Debug)]
+struct CodebaseScan {
+    root: PathBuf,
+    files_scanned: usize,
+    /// File relative to the root, convention, expected and actual style
+    mismatches: Vec<(PathBuf, Convention, String, String)>,
+}
+
+/// Analyze every source file below `root` and compare it with the baseline
+fn scan_codebase(root: &Path, baseline: &StandardsProfile) -> Result<CodebaseScan> {
+    if !root.is_dir() {
+        return Err(LearningError::PathResolutionFailed(format!(
+            "{} is not a directory",
+            root.display()
+        )));
+    }
+
+    let detector = StandardsDetector::new();
+    let mut files_scanned = 0;
+    let mut mismatches = Vec::new();
+
+    for entry in ignore::WalkBuilder::new(root).build().flatten() {
+        let path = entry.path();
+        let is_source = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| SOURCE_EXTENSIONS.contains(&ext));
+        if !path.is_file() || !is_source {
+            continue;
+        }
+
+        let Ok(content) = std::fs::read_to_string(path) else {
+            continue;
+        };
+        let profile = detector.detect(&[path]).map_err(|e| {
+            LearningError::PatternExtractionFailed(format!(
+                "Failed to analyze {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        files_scanned += 1;
+
+        let relative = path.strip_prefix(root).unwrap_or(path).to_path_buf();
+        for (convention, expected, actual) in DriftDetector::compare(baseline, &profile, &content) {
+            mismatches.push((relative.clone(), convention, expected, actual));
+        }
+    }
+
+    Ok(CodebaseScan {
+        root: root.to_path_buf(),
+        files_scanned,
+        mismatches,
+    })
+}
+
+impl DriftDetector {
+    /// Re-evaluate a codebase against a baseline standards profile
+    ///
+    /// Every drift found is also recorded as a [`DriftDetection`] with its
+    /// remediation, so it shows up in [`DriftDetector::get_drifts`]. The
+    /// detections replace those from the previous re-evaluation of `root`.
+    pub fn reevaluate_codebase(
+        &mut self,
+        root: &Path,
+        baseline: &StandardsProfile,
+    ) -> Result<DriftReport> {
+        let scan = scan_codebase(root, baseline)?;
+        Ok(self.record_scan(scan))
+    }
+
+    /// Record the drifts of a scan, replacing the previous ones for its root
+    fn record_scan(&mut self, scan: CodebaseScan) -> DriftReport {
+        let drifts: Vec<CodebaseDrift> = scan
+            .mismatches
+            .into_iter()
+            .map(|(file, convention, expected, actual)| {
+                self.codebase_drift(file, convention, expected, actual)
+            })
+            .collect();
+
+        if let Some(previous) = self.codebase_drifts.remove(&scan.root) {
+            let previous: HashSet<String> = previous.into_iter().collect();
+            self.drifts.retain(|d| !previous.contains(&d.id));
+        }
+
+        let mut ids = Vec::with_capacity(drifts.len());
+        for drift in &drifts {
+            let mut detection = DriftDetection::new(
+                drift
+                    .pattern_id
+                    .clone()
+                    .unwrap_or_else(|| "codebase".to_string()),
+                "deviation".to_string(),
+                drift.severity.clone(),
+                format!(
+                    "{} uses {} {} instead of {}",
+                    drift.file.display(),
+                    drift.actual,
+                    drift.convention,
+                    drift.expected
+                ),
+            );
+            detection.remediation = drift.remediation.description.clone();
+            ids.push(detection.id.clone());
+            self.drifts.push(detection);
+        }
+        self.codebase_drifts.insert(scan.root.clone(), ids);
+
+        DriftReport {
+            root: scan.root,
+            generated_at: Utc::now(),
+            files_scanned: scan.files_scanned,
+            drifts,
+        }
+    }
+
+    /// Compare a file's profile with the baseline
+    ///
+    /// Conventions the file gives no evidence for are skipped.
+    fn compare(
+        baseline: &StandardsProfile,
+        profile: &StandardsProfile,
+        content: &str,
+    ) -> Vec<(Convention, String, String)> {
+        let mut mismatches = Vec::new();
+        let expected = &baseline.naming_conventions;
+        let actual = &profile.naming_conventions;
+
+        let naming = [
+            (
+                Convention::FunctionNaming,
+                expected.function_case,
+                actual.function_case,
+            ),
+            (
+                Convention::VariableNaming,
+                expected.variable_case,
+                actual.variable_case,
+            ),
+        ];
+        for (convention, expected, actual) in naming {
+            if actual != CaseStyle::Mixed && expected != CaseStyle::Mixed && actual != expected {
+                mismatches.push((convention, case_name(expected), case_name(actual)));
+            }
+        }
+
+        let declares_types = ["struct ", "class ", "interface "]
+            .iter()
+            .any(|keyword| content.contains(keyword));
+        if declares_types && actual.class_case != expected.class_case {
+            mismatches.push((
+                Convention::TypeNaming,
+                case_name(expected.class_case),
+                case_name(actual.class_case),
+            ));
+        }
+
+        let is_indented = content
+            .lines()
+            .any(|line| line.starts_with(' ') || line.starts_with('\t'));
+        if is_indented
+            && profile.formatting_style.indent_type != baseline.formatting_style.indent_type
+        {
+            mismatches.push((
+                Convention::Indentation,
+                indent_name(baseline),
+                indent_name(profile),
+            ));
+        }
+
+        mismatches
+    }
+
+    /// Build a drift linked to the learned pattern covering the convention
+    fn codebase_drift(
+        &self,
+        file: PathBuf,
+        convention: Convention,
+        expected: String,
+        actual: String,
+    ) -> CodebaseDrift {
+        let pattern = self
+            .patterns
+            .values()
+            .filter(|p| p.pattern_type.contains(convention.category()))
+            .max_by(|a, b| {
+                a.confidence
+                    .partial_cmp(&b.confidence)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+
+        let severity = match pattern {
+            Some(p) if p.confidence >= self.config.confidence_threshold => "high",
+            Some(_) => "medium",
+            None => "low",
+        };
+
+        let (kind, description) = match convention {
+            Convention::Indentation => (
+                RemediationKind::Reindent {
+                    indent: expected.clone(),
+                },
+                format!("Re-indent {} with {}", file.display(), expected),
+            ),
+            _ => (
+                RemediationKind::RenameIdentifiers {
+                    from: actual.clone(),
+                    to: expected.clone(),
+                },
+                format!(
+                    "Rename {} in {} from {} to {}",
+                    match convention {
+                        Convention::FunctionNaming => "functions",
+                        Convention::VariableNaming => "variables",
+                        _ => "types",
+                    },
+                    file.display(),
+                    actual,
+                    expected
+                ),
+            ),
+        };
+
+        CodebaseDrift {
+            pattern_id: pattern.map(|p| p.id.clone()),
+            convention,
+            expected,
+            actual,
+            file: file.clone(),
+            severity: severity.to_string(),
+            remediation: RemediationSuggestion {
+                file,
+                kind,
+                description,
+            },
+        }
+    }
+}
+
+fn case_name(case: CaseStyle) -> String {
+    match case {
+        CaseStyle::CamelCase => "camelCase",
+        CaseStyle::SnakeCase => "snake_case",
+        CaseStyle::PascalCase => "PascalCase",
+        CaseStyle::KebabCase => "kebab-case",
+        CaseStyle::UpperCase => "UPPER_CASE",
+        CaseStyle::Mixed => "mixed case",
+    }
+    .to_string()
+}
+
+fn indent_name(profile: &StandardsProfile) -> String {
+    match profile.formatting_style.indent_type {
+        IndentType::Tabs => "tabs".to_string(),
+        IndentType::Spaces => format!("{} spaces", profile.formatting_style.indent_size),
+    }
+}
+
+/// Periodically re-evaluates a codebase in the background
+///
+/// The task stops when [`Self::stop`] is called or the handle is dropped.
+pub struct ScheduledDriftReevaluation {
+    handle: JoinHandle<()>,
+    reports: watch::Receiver<Option<DriftReport>>,
+}
+
+impl ScheduledDriftReevaluation {
+    /// Start re-evaluating `root` every `interval`, beginning immediately
+    ///
+    /// Must be called from within a tokio runtime.
+    pub fn start(
+        detector: Arc<RwLock<DriftDetector>>,
+        root: PathBuf,
+        baseline: StandardsProfile,
+        interval: Duration,
+    ) -> Self {
+        let (sender, reports) = watch::channel(None);
+
+        let handle = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                // Walk the codebase without holding the detector lock
+                let scan = {
+                    let (root, baseline) = (root.clone(), baseline.clone());
+                    tokio::task::spawn_blocking(move || scan_codebase(&root, &baseline)).await
+                };
+                match scan {
+                    Ok(Ok(scan)) => {
+                        let report = detector.write().await.record_scan(scan);
+                        if sender.send(Some(report)).is_err() {
+                            break;
+                        }
+                    }
+                    Ok(Err(e)) => tracing::warn!("Drift re-evaluation failed: {}", e),
+                    Err(e) => tracing::warn!("Drift re-evaluation task failed: {}", e),
+                }
+            }
+        });
+
+        Self { handle, reports }
+    }
+
+    /// Receiver notified whenever a new report is available
+    pub fn reports(&self) -> watch::Receiver<Option<DriftReport>> {
+        self.reports.clone()
+    }
+
+    /// Most recent report, if a run has completed
+    pub fn latest_report(&self) -> Option<DriftReport> {
+        self.reports.borrow().clone()
+    }
+
+    /// Stop re-evaluating
+    pub fn stop(&self) {
+        self.handle.abort();
+    }
+}
+
+impl Drop for ScheduledDriftReevaluation {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::LearnedPattern;
+
+    fn write(root: &Path, name: &str, content: &str) {
+        std::fs::write(root.join(name), content).unwrap();
+    }
+
+    fn naming_pattern() -> LearnedPattern {
+        let mut pattern = LearnedPattern::new(
+            "naming_convention".to_string(),
+            "snake_case fns".to_string(),
+        );
+        pattern.occurrences = 5;
+        pattern.confidence = 0.9;
+        pattern
+    }
+
+    fn codebase() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        write(
+            dir.path(),
+            "good.rs",
+            "fn parse_input() {\n    let item_count = 1;\n}\n",
+        );
+        write(
+            dir.path(),
+            "bad.js",
+            "function parseInput() {\n\tconst itemCount = 1;\n}\n",
+        );
+        write(dir.path(), "notes.txt", "function notCode() {}\n");
+        dir
+    }
+
+    #[test]
+    fn test_reevaluate_codebase_reports_drift() {
+        let dir = codebase();
+        let mut detector = DriftDetector::new();
+        let pattern = naming_pattern();
+        detector.register_pattern(pattern.clone()).unwrap();
+
+        let report = detector
+            .reevaluate_codebase(dir.path(), &StandardsProfile::default())
+            .unwrap();
+
+        assert_eq!(report.files_scanned, 2);
+        assert!(report.drifts_for_file(Path::new("good.rs")).is_empty());
+
+        let bad = report.drifts_for_file(Path::new("bad.js"));
+        let function_drift = bad
+            .iter()
+            .find(|d| d.convention == Convention::FunctionNaming)
+            .unwrap();
+        assert_eq!(function_drift.expected, "snake_case");
+        assert_eq!(function_drift.actual, "camelCase");
+        assert_eq!(
+            function_drift.pattern_id.as_deref(),
+            Some(pattern.id.as_str())
+        );
+        assert_eq!(function_drift.severity, "high");
+        assert_eq!(
+            function_drift.remediation.kind,
+            RemediationKind::RenameIdentifiers {
+                from: "camelCase".to_string(),
+                to: "snake_case".to_string(),
+            }
+        );
+
+        let indent_drift = bad
+            .iter()
+            .find(|d| d.convention == Convention::Indentation)
+            .unwrap();
+        assert_eq!(indent_drift.pattern_id, None);
+        assert_eq!(indent_drift.severity, "low");
+        assert!(indent_drift.remediation.description.contains("4 spaces"));
+
+        assert_eq!(report.drifted_patterns(), vec![pattern.id.clone()]);
+        assert_eq!(detector.get_drifts().len(), report.drifts.len());
+        assert!(detector
+            .get_drifts()
+            .iter()
+            .all(|d| !d.remediation.is_empty()));
+    }
+
+    #[test]
+    fn test_reevaluation_replaces_previous_drifts() {
+        let dir = codebase();
+        let mut detector = DriftDetector::new();
+        let baseline = StandardsProfile::default();
+
+        let first = detector.reevaluate_codebase(dir.path(), &baseline).unwrap();
+        assert!(!first.is_clean());
+        detector.reevaluate_codebase(dir.path(), &baseline).unwrap();
+        assert_eq!(detector.get_drifts().len(), first.drifts.len());
+
+        write(
+            dir.path(),
+            "bad.js",
+            "function parse_input() {\n    const item_count = 1;\n}\n",
+        );
+        let fixed = detector.reevaluate_codebase(dir.path(), &baseline).unwrap();
+        assert!(fixed.is_clean());
+        assert!(detector.get_drifts().is_empty());
+    }
+
+    #[test]
+    fn test_reevaluate_codebase_clean() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "lib.rs", "fn run() {\n    let value = 1;\n}\n");
+
+        let mut detector = DriftDetector::new();
+        let report = detector
+            .reevaluate_codebase(dir.path(), &StandardsProfile::default())
+            .unwrap();
+
+        assert!(report.is_clean());
+        assert!(detector
+            .reevaluate_codebase(&dir.path().join("missing"), &StandardsProfile::default())
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_scheduled_reevaluation() {
+        let dir = codebase();
+        let detector = Arc::new(RwLock::new(DriftDetector::new()));
+
+        let schedule = ScheduledDriftReevaluation::start(
+            detector.clone(),
+            dir.path().to_path_buf(),
+            StandardsProfile::default(),
+            Duration::from_millis(20),
+        );
+        let mut reports = schedule.reports();
+
+        reports.changed().await.unwrap();
+        let first = schedule.latest_report().unwrap();
+        assert!(!first.is_clean());
+
+        reports.changed().await.unwrap();
+        assert_eq!(detector.read().await.get_drifts().len(), first.drifts.len());
+
+        schedule.stop();
+    }
+}
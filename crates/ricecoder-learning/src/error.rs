@@ -54,6 +54,12 @@ pub enum LearningError {
 
     #[error("Analytics error: {0}")]
     AnalyticsError(String),
+
+    #[error("Embedding failed: {0}")]
+    EmbeddingFailed(String),
+
+    #[error("Rule sync failed: {0}")]
+    SyncFailed(String),
 }
 
 impl From<ricecoder_storage::error::StorageError> for LearningError {
@@ -62,5 +68,11 @@ impl From<ricecoder_storage::error::StorageError> for LearningError {
     }
 }
 
+impl From<ricecoder_providers::ProviderError> for LearningError {
+    fn from(err: ricecoder_providers::ProviderError) -> Self {
+        LearningError::EmbeddingFailed(err.to_string())
+    }
+}
+
 /// Result type for learning system operations
 pub type Result<T> = std::result::Result<T, LearningError>;
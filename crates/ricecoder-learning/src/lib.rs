@@ -10,6 +10,7 @@ pub mod decision_capture_property;
 pub mod decision_logger;
 pub mod di;
 pub mod drift_detector;
+pub mod drift_reevaluation;
 pub mod error;
 pub mod intent_tracker;
 pub mod intent_tracking_integration;
@@ -20,12 +21,15 @@ pub mod pattern_extraction_property;
 pub mod pattern_validation_integration;
 pub mod pattern_validator;
 pub mod rule_application;
+pub mod rule_authoring;
+pub mod rule_embeddings;
 pub mod rule_exchange;
 pub mod rule_persistence_property;
 pub mod rule_promoter;
 pub mod rule_promotion_safety_property;
 pub mod rule_review;
 pub mod rule_storage;
+pub mod rule_sync;
 pub mod rule_validation_property;
 pub mod rule_validator;
 pub mod scope_config;
@@ -33,10 +37,17 @@ pub mod scope_isolation_property;
 pub mod scope_precedence_property;
 
 // Re-export public types
-pub use analytics_engine::{AnalyticsEngine, AnalyticsInsights, RuleMetrics};
+pub use analytics_engine::{
+    AnalyticsEngine, AnalyticsInsights, FeedbackConfig, RuleMetrics, SuggestionFeedback,
+    SuggestionOutcome,
+};
 pub use conflict_resolver::ConflictResolver;
 pub use decision_logger::{DecisionLogger, DecisionStatistics};
 pub use drift_detector::{DriftDetectionConfig, DriftDetector, DriftStatistics};
+pub use drift_reevaluation::{
+    CodebaseDrift, Convention, DriftReport, RemediationKind, RemediationSuggestion,
+    ScheduledDriftReevaluation,
+};
 pub use error::{LearningError, Result};
 pub use intent_tracker::{
     ArchitecturalDecision, ArchitecturalEvolution, ArchitecturalSummary, DriftDetection,
@@ -51,6 +62,11 @@ pub use models::{
 pub use pattern_capturer::{PatternAnalysis, PatternCapturer};
 pub use pattern_validator::{PatternValidator, ValidationResult, ValidationStatistics};
 pub use rule_application::{GenerationContext, RuleApplicationEngine, RuleApplicationResult};
+pub use rule_authoring::{RuleAuthor, RuleDraft};
+pub use rule_embeddings::{
+    cosine_similarity, ProviderEmbedder, RuleEmbedder, RuleEmbedding, RuleEmbeddingStore,
+    SemanticRuleMatcher,
+};
 pub use rule_exchange::{ExportMetadata, RuleExport, RuleExporter, RuleImporter};
 pub use rule_promoter::{
     PromotionHistoryEntry, PromotionMetadata, RulePromoter, RuleReview, VersionChanges,
@@ -59,5 +75,9 @@ pub use rule_review::{
     compare_rules, ReviewComment, ReviewInfo, ReviewStatus, RuleComparison, RuleReviewManager,
 };
 pub use rule_storage::RuleStorage;
+pub use rule_sync::{
+    GitRuleRepository, RuleProvenance, RuleSyncBackend, RuleSyncConflict, RuleSyncReport,
+    RuleSyncService, ScopeMapping,
+};
 pub use rule_validator::{RuleValidator, ValidationReport};
 pub use scope_config::{ScopeConfiguration, ScopeConfigurationLoader, ScopeFilter};
@@ -12,6 +12,7 @@ use crate::{
     pattern_capturer::PatternCapturer,
     pattern_validator::PatternValidator,
     rule_promoter::RulePromoter,
+    rule_review::{ReviewInfo, RuleReviewManager},
     rule_storage::RuleStorage,
     rule_validator::RuleValidator,
     scope_config::{ScopeConfiguration, ScopeConfigurationLoader, ScopeFilter},
@@ -39,6 +40,8 @@ pub struct LearningManager {
     patterns: Arc<RwLock<HashMap<String, LearnedPattern>>>,
     /// Analytics engine for tracking rule metrics
     analytics_engine: Arc<AnalyticsEngine>,
+    /// Reviews of rules flagged by suggestion feedback
+    review_manager: Arc<RwLock<RuleReviewManager>>,
 }
 
 impl LearningManager {
@@ -162,6 +165,7 @@ impl LearningManagerBuilder {
             rule_promoter: self.rule_promoter.unwrap_or_else(|| Arc::new(RwLock::new(RulePromoter::new()))),
             patterns: Arc::new(RwLock::new(HashMap::new())),
             analytics_engine: self.analytics_engine.unwrap_or_else(|| Arc::new(AnalyticsEngine::new())),
+            review_manager: Arc::new(RwLock::new(RuleReviewManager::new())),
         }
     }
 }
@@ -398,6 +402,38 @@ impl LearningManager {
         self.rule_storage.store_rule(rule).await
     }
 
+    /// Author a rule from a natural-language instruction without persisting it
+    ///
+    /// The draft is validated and checked against the stored rules; show its
+    /// preview and call [`Self::save_rule_draft`] once the user confirms.
+    pub async fn draft_rule(
+        &self,
+        author: &crate::rule_authoring::RuleAuthor,
+        instruction: &str,
+    ) -> Result<crate::rule_authoring::RuleDraft> {
+        let existing_rules = self.rule_storage.list_rules().await?;
+        author
+            .draft(instruction, self.get_scope().await, &existing_rules)
+            .await
+    }
+
+    /// Persist a confirmed rule draft
+    pub async fn save_rule_draft(&self, draft: crate::rule_authoring::RuleDraft) -> Result<String> {
+        if draft.validation.has_errors() {
+            return Err(LearningError::RuleValidationFailed(
+                draft.validation.error_message(),
+            ));
+        }
+        if let Some(conflict) = draft.conflicts.first() {
+            return Err(LearningError::ConflictResolutionFailed(format!(
+                "Rule conflicts with existing rule '{}'",
+                conflict.id
+            )));
+        }
+
+        self.store_rule(draft.rule).await
+    }
+
     /// Get a rule by ID
     pub async fn get_rule(&self, rule_id: &str) -> Result<Rule> {
         self.rule_storage.get_rule(rule_id).await
@@ -1071,6 +1107,93 @@ impl LearningManager {
             .await
     }
 
+    // ============================================================================
+    // Suggestion Feedback
+    // ============================================================================
+
+    /// Report what happened to a suggestion influenced by learned rules
+    ///
+    /// Called by completion, generation and agents when a rule-influenced
+    /// suggestion is accepted, rejected or reverted. The stored confidence of
+    /// each known rule follows the analytics confidence, and rules that become
+    /// flagged get a review in the rule review workflow. Returns the IDs of
+    /// newly flagged rules.
+    pub async fn report_suggestion_feedback(
+        &self,
+        feedback: crate::analytics_engine::SuggestionFeedback,
+    ) -> Result<Vec<String>> {
+        let mut rules = Vec::new();
+        for rule_id in &feedback.rule_ids {
+            if let Ok(rule) = self.rule_storage.get_rule(rule_id).await {
+                self.analytics_engine.track_rule(&rule).await?;
+                rules.push(rule);
+            }
+        }
+
+        let newly_flagged = self.analytics_engine.record_feedback(&feedback).await?;
+
+        for mut rule in rules {
+            let Some(metrics) = self.analytics_engine.get_rule_metrics(&rule.id).await? else {
+                continue;
+            };
+            rule.confidence = metrics.confidence;
+            rule.updated_at = chrono::Utc::now();
+            self.rule_storage.update_rule(rule.clone()).await?;
+
+            if newly_flagged.contains(&rule.id) {
+                self.review_manager
+                    .write()
+                    .await
+                    .start_feedback_review(rule, &metrics);
+            }
+        }
+
+        Ok(newly_flagged)
+    }
+
+    /// Get metrics for rules flagged for review by suggestion feedback
+    pub async fn get_rules_flagged_for_review(
+        &self,
+    ) -> Result<Vec<crate::analytics_engine::RuleMetrics>> {
+        self.analytics_engine.get_flagged_rules().await
+    }
+
+    /// Get pending reviews of rules flagged by suggestion feedback
+    pub async fn get_feedback_reviews(&self) -> Vec<ReviewInfo> {
+        self.review_manager
+            .read()
+            .await
+            .get_pending_reviews()
+            .into_iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Complete the review of a flagged rule
+    ///
+    /// Keeping the rule clears its review flag; otherwise the rule is deleted.
+    pub async fn complete_feedback_review(
+        &self,
+        rule_id: &str,
+        reviewer: String,
+        keep_rule: bool,
+    ) -> Result<()> {
+        {
+            let mut review_manager = self.review_manager.write().await;
+            if keep_rule {
+                review_manager.approve_review(rule_id, reviewer, 1.0)?;
+            } else {
+                review_manager.reject_review(rule_id, reviewer, 0.0)?;
+            }
+        }
+
+        if keep_rule {
+            self.analytics_engine.clear_review_flag(rule_id).await
+        } else {
+            self.rule_storage.delete_rule(rule_id).await
+        }
+    }
+
     /// Export rules with metrics to JSON
     pub async fn export_rules_with_metrics(&self, description: Option<String>) -> Result<String> {
         let rules = self.get_rules().await?;
@@ -1982,4 +2105,45 @@ mod tests {
         manager.clear_promotion_history().await;
         assert_eq!(manager.get_promotion_history().await.len(), 0);
     }
+
+    #[tokio::test]
+    async fn test_report_suggestion_feedback() {
+        use crate::analytics_engine::SuggestionFeedback;
+
+        let manager = LearningManager::new(RuleScope::Session);
+        let mut rule = Rule::new(
+            RuleScope::Session,
+            "function".to_string(),
+            "add_documentation".to_string(),
+            crate::models::RuleSource::Learned,
+        );
+        rule.confidence = 0.8;
+        let rule_id = manager.store_rule(rule).await.unwrap();
+
+        let rejected = SuggestionFeedback::rejected("completion".to_string(), vec![rule_id.clone()]);
+        for _ in 0..4 {
+            assert!(manager
+                .report_suggestion_feedback(rejected.clone())
+                .await
+                .unwrap()
+                .is_empty());
+        }
+        let stored = manager.get_rule(&rule_id).await.unwrap();
+        assert!((stored.confidence - 0.8 * 0.85_f32.powi(4)).abs() < 0.001);
+        assert!(manager.get_feedback_reviews().await.is_empty());
+
+        let flagged = manager.report_suggestion_feedback(rejected).await.unwrap();
+        assert_eq!(flagged, vec![rule_id.clone()]);
+        assert_eq!(manager.get_rules_flagged_for_review().await.unwrap().len(), 1);
+        let reviews = manager.get_feedback_reviews().await;
+        assert_eq!(reviews.len(), 1);
+        assert_eq!(reviews[0].critical_comment_count(), 1);
+
+        manager
+            .complete_feedback_review(&rule_id, "reviewer".to_string(), true)
+            .await
+            .unwrap();
+        assert!(manager.get_rules_flagged_for_review().await.unwrap().is_empty());
+        assert!(manager.get_feedback_reviews().await.is_empty());
+    }
 }
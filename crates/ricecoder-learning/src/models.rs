@@ -211,6 +211,13 @@ pub struct LearningConfig {
     pub retention_days: u32,
     /// Maximum number of rules to store
     pub max_rules: usize,
+    /// Minimum cosine similarity for a rule to match a context semantically
+    #[serde(default = "default_similarity_threshold")]
+    pub similarity_threshold: f32,
+}
+
+fn default_similarity_threshold() -> f32 {
+    0.8
 }
 
 impl Default for LearningConfig {
@@ -222,6 +229,7 @@ impl Default for LearningConfig {
             auto_promote: false,
             retention_days: 365,
             max_rules: 10000,
+            similarity_threshold: default_similarity_threshold(),
         }
     }
 }
@@ -249,6 +257,12 @@ impl LearningConfig {
             ));
         }
 
+        if !(0.0..=1.0).contains(&self.similarity_threshold) {
+            return Err(crate::error::LearningError::ConfigurationError(
+                "similarity_threshold must be between 0.0 and 1.0".to_string(),
+            ));
+        }
+
         Ok(())
     }
 }
@@ -369,6 +383,7 @@ mod tests {
         assert!(!config.auto_promote);
         assert_eq!(config.retention_days, 365);
         assert_eq!(config.max_rules, 10000);
+        assert_eq!(config.similarity_threshold, 0.8);
     }
 
     #[test]
@@ -382,6 +397,10 @@ mod tests {
         config.retention_days = 365;
         config.max_rules = 0;
         assert!(config.validate().is_err());
+
+        config.max_rules = 10000;
+        config.similarity_threshold = 1.5;
+        assert!(config.validate().is_err());
     }
 
     #[test]
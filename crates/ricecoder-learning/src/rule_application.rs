@@ -5,6 +5,7 @@ use serde_json::{json, Value};
 /// Rule application engine for guiding code generation
 use crate::error::{LearningError, Result};
 use crate::models::Rule;
+use crate::rule_embeddings::{RuleEmbedder, SemanticRuleMatcher};
 
 /// Context for code generation that rules can match against
 #[derive(Debug, Clone)]
@@ -166,6 +167,56 @@ impl RuleApplicationEngine {
         result
     }
 
+    /// Apply rules using both pattern matching and semantic similarity
+    ///
+    /// A rule matches if its pattern matches the context or if its embedding is
+    /// at least as similar to the context as the matcher's threshold. The
+    /// similarity is recorded in the `similarity` detail.
+    pub async fn apply_rules_semantic<E: RuleEmbedder>(
+        rules: &[Rule],
+        context: &GenerationContext,
+        matcher: &SemanticRuleMatcher<E>,
+    ) -> Result<Vec<RuleApplicationResult>> {
+        let similarities: HashMap<String, f32> = matcher
+            .score_rules(rules, context)
+            .await?
+            .into_iter()
+            .map(|(rule, similarity)| (rule.id, similarity))
+            .collect();
+
+        Ok(rules
+            .iter()
+            .map(|rule| {
+                let similarity = similarities.get(&rule.id).copied().unwrap_or(0.0);
+                let matched = Self::matches_pattern(rule, context)
+                    || similarity >= matcher.threshold();
+                let mut result = RuleApplicationResult::new(rule.clone(), matched)
+                    .with_detail("similarity".to_string(), json!(similarity));
+                if matched {
+                    result = result.with_detail(
+                        "applied_at".to_string(),
+                        json!(chrono::Utc::now().to_rfc3339()),
+                    );
+                }
+                result
+            })
+            .collect())
+    }
+
+    /// Get rules that match a context by pattern or semantic similarity
+    pub async fn get_semantically_matching_rules<E: RuleEmbedder>(
+        rules: &[Rule],
+        context: &GenerationContext,
+        matcher: &SemanticRuleMatcher<E>,
+    ) -> Result<Vec<Rule>> {
+        Ok(Self::apply_rules_semantic(rules, context, matcher)
+            .await?
+            .into_iter()
+            .filter(|result| result.matched)
+            .map(|result| result.rule)
+            .collect())
+    }
+
     /// Apply multiple rules to a generation context
     pub fn apply_rules(rules: &[Rule], context: &GenerationContext) -> Vec<RuleApplicationResult> {
         rules
@@ -0,0 +1,379 @@
+//! Natural-language rule authoring
+//!
+//! [`RuleAuthor`] turns an instruction such as "always use snake_case for DB
+//! columns" into a structured [`Rule`] by asking a provider model, then checks
+//! the result with [`RuleValidator`] and against existing rules. The returned
+//! [`RuleDraft`] carries a preview so the user can confirm before the rule is
+//! persisted (the `/learn` chat command).
+
+use std::sync::Arc;
+
+use ricecoder_providers::{ChatRequest, Message, Provider};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::conflict_resolver::ConflictResolver;
+use crate::error::{LearningError, Result};
+use crate::models::{Rule, RuleScope, RuleSource};
+use crate::rule_validator::{RuleValidator, ValidationReport};
+
+/// Instructions given to the model
+const SYSTEM_PROMPT: &str =
+    "You convert coding instructions into rules for a code generation assistant. \
+Respond with a single JSON object and nothing else, with these fields:\n\
+- \"pattern\": an object describing when the rule applies, using any of \"generation_type\" \
+(e.g. \"function\", \"class\", \"module\"), \"language\" (e.g. \"rust\", \"sql\") and \"metadata\" \
+(an object of context keys and values)\n\
+- \"action\": a short imperative description of what generated code must do\n\
+- \"description\": a one-sentence summary of the rule\n\
+- \"confidence\": how clearly the instruction defines the rule, from 0.0 to 1.0";
+
+/// Model response for an authored rule
+#[derive(Debug, Deserialize)]
+struct AuthoredRule {
+    pattern: Value,
+    action: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    confidence: Option<f32>,
+}
+
+/// A rule authored from an instruction, not yet persisted
+#[derive(Debug, Clone)]
+pub struct RuleDraft {
+    /// The instruction the rule was authored from
+    pub instruction: String,
+    /// Summary of the rule given by the model
+    pub description: Option<String>,
+    /// The authored rule
+    pub rule: Rule,
+    /// Validation result for the rule
+    pub validation: ValidationReport,
+    /// Existing rules the draft conflicts with
+    pub conflicts: Vec<Rule>,
+}
+
+impl RuleDraft {
+    /// Whether the draft can be persisted as is
+    pub fn is_valid(&self) -> bool {
+        !self.validation.has_errors() && self.conflicts.is_empty()
+    }
+
+    /// Human-readable preview shown before persisting
+    pub fn preview(&self) -> String {
+        let mut preview = format!("Instruction: {}\n", self.instruction);
+        if let Some(description) = &self.description {
+            preview.push_str(&format!("Rule: {}\n", description));
+        }
+        preview.push_str(&format!("Scope: {}\n", self.rule.scope));
+        preview.push_str(&format!("When: {}\n", self.rule.pattern));
+        preview.push_str(&format!("Then: {}\n", self.rule.action));
+        preview.push_str(&format!("Confidence: {:.2}\n", self.rule.confidence));
+
+        if self.validation.has_errors() {
+            preview.push_str(&self.validation.error_message());
+        }
+        for conflict in &self.conflicts {
+            preview.push_str(&format!(
+                "Conflicts with rule '{}': {}\n",
+                conflict.id, conflict.action
+            ));
+        }
+
+        preview
+    }
+}
+
+/// Authors rules from natural-language instructions using a provider model
+pub struct RuleAuthor {
+    provider: Arc<dyn Provider>,
+    model: String,
+    validator: RuleValidator,
+}
+
+impl RuleAuthor {
+    /// Create a new rule author using the given chat model
+    pub fn new(provider: Arc<dyn Provider>, model: String) -> Self {
+        Self {
+            provider,
+            model,
+            validator: RuleValidator::new(),
+        }
+    }
+
+    /// Author a rule from an instruction and check it against existing rules
+    pub async fn draft(
+        &self,
+        instruction: &str,
+        scope: RuleScope,
+        existing_rules: &[Rule],
+    ) -> Result<RuleDraft> {
+        let instruction = instruction.trim();
+        if instruction.is_empty() {
+            return Err(LearningError::RuleValidationFailed(
+                "Instruction cannot be empty".to_string(),
+            ));
+        }
+
+        let request = ChatRequest {
+            model: self.model.clone(),
+            messages: vec![
+                Message {
+                    role: "system".to_string(),
+                    content: SYSTEM_PROMPT.to_string(),
+                },
+                Message {
+                    role: "user".to_string(),
+                    content: instruction.to_string(),
+                },
+            ],
+            temperature: Some(0.0),
+            max_tokens: Some(512),
+            stream: false,
+            top_p: None,
+            stop: None,
+            seed: None,
+            reasoning_effort: None,
+        };
+        let response = self.provider.chat(request).await.map_err(|e| {
+            LearningError::RuleValidationFailed(format!("Failed to author rule: {}", e))
+        })?;
+
+        let authored = Self::parse_response(&response.content)?;
+        let rule = Self::build_rule(instruction, scope, &authored);
+
+        let validation = self.validator.validate_with_report(&rule);
+        let conflicts = existing_rules
+            .iter()
+            .filter(|existing| {
+                existing.scope == rule.scope
+                    && (existing.pattern == rule.pattern
+                        || ConflictResolver::detect_conflict(existing, &rule))
+            })
+            .cloned()
+            .collect();
+
+        Ok(RuleDraft {
+            instruction: instruction.to_string(),
+            description: authored.description,
+            rule,
+            validation,
+            conflicts,
+        })
+    }
+
+    /// Parse the model response, tolerating code fences and surrounding prose
+    fn parse_response(content: &str) -> Result<AuthoredRule> {
+        let start = content.find('{');
+        let end = content.rfind('}');
+        let json = match (start, end) {
+            (Some(start), Some(end)) if start < end => &content[start..=end],
+            _ => {
+                return Err(LearningError::RuleValidationFailed(
+                    "Model response does not contain a rule".to_string(),
+                ))
+            }
+        };
+
+        serde_json::from_str(json).map_err(|e| {
+            LearningError::RuleValidationFailed(format!("Model returned an invalid rule: {}", e))
+        })
+    }
+
+    /// Build a manual rule from the model response
+    fn build_rule(instruction: &str, scope: RuleScope, authored: &AuthoredRule) -> Rule {
+        let pattern = match &authored.pattern {
+            Value::String(pattern) => pattern.clone(),
+            other => other.to_string(),
+        };
+
+        let mut rule = Rule::new(
+            scope,
+            pattern,
+            authored.action.trim().to_string(),
+            RuleSource::Manual,
+        );
+        if let Some(confidence) = authored.confidence {
+            rule.confidence = confidence;
+        }
+        rule.metadata = json!({
+            "instruction": instruction,
+            "description": authored.description,
+            "authored_from": "natural_language",
+        });
+
+        rule
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use ricecoder_providers::{
+        provider::ChatStream, ChatResponse, FinishReason, ModelInfo, ProviderError, TokenUsage,
+    };
+
+    use super::*;
+
+    /// Provider that answers every chat request with a fixed response
+    struct CannedProvider {
+        response: String,
+    }
+
+    #[async_trait]
+    impl Provider for CannedProvider {
+        fn id(&self) -> &str {
+            "canned"
+        }
+
+        fn name(&self) -> &str {
+            "Canned"
+        }
+
+        fn models(&self) -> Vec<ModelInfo> {
+            Vec::new()
+        }
+
+        async fn chat(
+            &self,
+            request: ChatRequest,
+        ) -> std::result::Result<ChatResponse, ProviderError> {
+            Ok(ChatResponse {
+                content: self.response.clone(),
+                model: request.model,
+                usage: TokenUsage {
+                    prompt_tokens: 0,
+                    completion_tokens: 0,
+                    total_tokens: 0,
+                },
+                finish_reason: FinishReason::Stop,
+            })
+        }
+
+        async fn chat_stream(
+            &self,
+            _request: ChatRequest,
+        ) -> std::result::Result<ChatStream, ProviderError> {
+            Err(ProviderError::ProviderError("not supported".to_string()))
+        }
+
+        fn count_tokens(
+            &self,
+            content: &str,
+            _model: &str,
+        ) -> std::result::Result<usize, ProviderError> {
+            Ok(content.len())
+        }
+
+        async fn health_check(&self) -> std::result::Result<bool, ProviderError> {
+            Ok(true)
+        }
+    }
+
+    fn author(response: &str) -> RuleAuthor {
+        RuleAuthor::new(
+            Arc::new(CannedProvider {
+                response: response.to_string(),
+            }),
+            "test-model".to_string(),
+        )
+    }
+
+    const SNAKE_CASE_RULE: &str =
+        "Here is the rule:\n```json\n{\"pattern\": {\"language\": \"sql\", \
+        \"generation_type\": \"table\"}, \"action\": \"use snake_case for column names\", \
+        \"description\": \"DB columns are snake_case\", \"confidence\": 0.9}\n```";
+
+    #[tokio::test]
+    async fn test_draft_rule_from_instruction() {
+        let author = author(SNAKE_CASE_RULE);
+
+        let draft = author
+            .draft(
+                "always use snake_case for DB columns",
+                RuleScope::Project,
+                &[],
+            )
+            .await
+            .unwrap();
+
+        assert!(draft.is_valid());
+        assert_eq!(draft.rule.scope, RuleScope::Project);
+        assert_eq!(draft.rule.source, RuleSource::Manual);
+        assert_eq!(draft.rule.action, "use snake_case for column names");
+        assert_eq!(draft.rule.confidence, 0.9);
+        let pattern: Value = serde_json::from_str(&draft.rule.pattern).unwrap();
+        assert_eq!(pattern["language"], "sql");
+        assert_eq!(
+            draft.rule.metadata["instruction"],
+            "always use snake_case for DB columns"
+        );
+
+        let preview = draft.preview();
+        assert!(preview.contains("DB columns are snake_case"));
+        assert!(preview.contains("Then: use snake_case for column names"));
+    }
+
+    #[tokio::test]
+    async fn test_draft_detects_conflicts() {
+        let author = author(SNAKE_CASE_RULE);
+        let first = author
+            .draft(
+                "always use snake_case for DB columns",
+                RuleScope::Project,
+                &[],
+            )
+            .await
+            .unwrap();
+        let mut existing = first.rule.clone();
+        existing.action = "use camelCase for column names".to_string();
+
+        let draft = author
+            .draft(
+                "always use snake_case for DB columns",
+                RuleScope::Project,
+                &[existing.clone()],
+            )
+            .await
+            .unwrap();
+
+        assert!(!draft.is_valid());
+        assert_eq!(draft.conflicts[0].id, existing.id);
+        assert!(draft.preview().contains("Conflicts with rule"));
+
+        // Rules in other scopes do not conflict
+        let draft = author
+            .draft(
+                "always use snake_case for DB columns",
+                RuleScope::Global,
+                &[existing],
+            )
+            .await
+            .unwrap();
+        assert!(draft.is_valid());
+    }
+
+    #[tokio::test]
+    async fn test_draft_reports_validation_errors() {
+        let author = author("{\"pattern\": \"sql\", \"action\": \"   \", \"confidence\": 2.0}");
+
+        let draft = author
+            .draft("be nice", RuleScope::Project, &[])
+            .await
+            .unwrap();
+
+        assert!(!draft.is_valid());
+        assert!(draft.validation.has_errors());
+    }
+
+    #[tokio::test]
+    async fn test_draft_rejects_unparseable_response() {
+        let author = author("I cannot help with that.");
+        assert!(author
+            .draft("do things", RuleScope::Project, &[])
+            .await
+            .is_err());
+        assert!(author.draft("   ", RuleScope::Project, &[]).await.is_err());
+    }
+}
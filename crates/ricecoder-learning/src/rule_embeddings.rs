@@ -0,0 +1,475 @@
+//! Embedding-based rule retrieval
+//!
+//! Rules and generation contexts are embedded through a [`RuleEmbedder`]
+//! (usually a [`ProviderEmbedder`] backed by `ricecoder-providers`) so rules can
+//! match contexts that describe the same thing in different words. Rule vectors
+//! are cached in a [`RuleEmbeddingStore`] beside the scope's rule directory and
+//! are recomputed only when a rule's pattern or action changes.
+
+use std::{collections::HashMap, path::PathBuf, sync::Arc};
+
+use async_trait::async_trait;
+use ricecoder_providers::{EmbeddingRequest, Provider};
+use serde::{Deserialize, Serialize};
+use tokio::{fs, sync::RwLock};
+
+use crate::error::{LearningError, Result};
+use crate::models::{LearningConfig, Rule, RuleScope};
+use crate::rule_application::GenerationContext;
+use crate::rule_storage::RuleStorage;
+
+/// File name of the embedding cache, stored beside the rule directory so it is
+/// not picked up as a rule file
+const EMBEDDINGS_FILE: &str = "rule_embeddings.json";
+
+/// Computes embedding vectors for texts
+#[async_trait]
+pub trait RuleEmbedder: Send + Sync {
+    /// Identifier of the embedding model, used to invalidate cached vectors
+    fn model(&self) -> &str;
+
+    /// Embed texts, returning one vector per text in input order
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>>;
+}
+
+/// Embedder that delegates to a `ricecoder-providers` provider
+pub struct ProviderEmbedder {
+    provider: Arc<dyn Provider>,
+    model: String,
+}
+
+impl ProviderEmbedder {
+    /// Create a new provider embedder using the given embedding model
+    pub fn new(provider: Arc<dyn Provider>, model: String) -> Self {
+        Self { provider, model }
+    }
+}
+
+#[async_trait]
+impl RuleEmbedder for ProviderEmbedder {
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        let response = self
+            .provider
+            .embed(EmbeddingRequest {
+                model: self.model.clone(),
+                input: texts.to_vec(),
+            })
+            .await?;
+
+        if response.embeddings.len() != texts.len() {
+            return Err(LearningError::EmbeddingFailed(format!(
+                "expected {} embeddings, got {}",
+                texts.len(),
+                response.embeddings.len()
+            )));
+        }
+
+        Ok(response.embeddings)
+    }
+}
+
+/// A cached rule embedding
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleEmbedding {
+    /// Model that produced the vector
+    pub model: String,
+    /// Fingerprint of the rule content the vector was computed from
+    pub fingerprint: String,
+    /// The embedding vector
+    pub vector: Vec<f32>,
+}
+
+/// Persistent cache of rule embeddings keyed by rule ID
+pub struct RuleEmbeddingStore {
+    embeddings: Arc<RwLock<HashMap<String, RuleEmbedding>>>,
+    path: Option<PathBuf>,
+}
+
+impl RuleEmbeddingStore {
+    /// Create a store that is never persisted
+    pub fn in_memory() -> Self {
+        Self {
+            embeddings: Arc::new(RwLock::new(HashMap::new())),
+            path: None,
+        }
+    }
+
+    /// Create a store persisted to the given file
+    pub fn with_path(path: PathBuf) -> Self {
+        Self {
+            embeddings: Arc::new(RwLock::new(HashMap::new())),
+            path: Some(path),
+        }
+    }
+
+    /// Create a store persisted beside the rule directory of a scope
+    pub fn for_scope(scope: RuleScope) -> Result<Self> {
+        if scope == RuleScope::Session {
+            return Ok(Self::in_memory());
+        }
+
+        let path = RuleStorage::storage_path_for(scope)?.with_file_name(EMBEDDINGS_FILE);
+        Ok(Self::with_path(path))
+    }
+
+    /// Load cached embeddings from disk, if the file exists
+    pub async fn load(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if !path.exists() {
+            return Ok(());
+        }
+
+        let content = fs::read_to_string(path).await.map_err(|e| {
+            LearningError::RuleStorageFailed(format!("Failed to read embeddings: {}", e))
+        })?;
+        let loaded: HashMap<String, RuleEmbedding> = serde_json::from_str(&content)?;
+        *self.embeddings.write().await = loaded;
+
+        Ok(())
+    }
+
+    /// Persist cached embeddings to disk
+    pub async fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await.map_err(|e| {
+                LearningError::RuleStorageFailed(format!(
+                    "Failed to create storage directory: {}",
+                    e
+                ))
+            })?;
+        }
+
+        let content = serde_json::to_string(&*self.embeddings.read().await)?;
+        fs::write(path, content).await.map_err(|e| {
+            LearningError::RuleStorageFailed(format!("Failed to write embeddings: {}", e))
+        })?;
+
+        Ok(())
+    }
+
+    /// Get the cached embedding for a rule
+    pub async fn get(&self, rule_id: &str) -> Option<RuleEmbedding> {
+        self.embeddings.read().await.get(rule_id).cloned()
+    }
+
+    /// Cache the embedding for a rule
+    pub async fn insert(&self, rule_id: String, embedding: RuleEmbedding) {
+        self.embeddings.write().await.insert(rule_id, embedding);
+    }
+
+    /// Remove the cached embedding for a rule
+    pub async fn remove(&self, rule_id: &str) -> Option<RuleEmbedding> {
+        self.embeddings.write().await.remove(rule_id)
+    }
+
+    /// Number of cached embeddings
+    pub async fn len(&self) -> usize {
+        self.embeddings.read().await.len()
+    }
+
+    /// Whether the cache is empty
+    pub async fn is_empty(&self) -> bool {
+        self.embeddings.read().await.is_empty()
+    }
+}
+
+/// Finds rules that are semantically relevant to a generation context
+pub struct SemanticRuleMatcher<E: RuleEmbedder> {
+    embedder: E,
+    store: RuleEmbeddingStore,
+    threshold: f32,
+}
+
+impl<E: RuleEmbedder> SemanticRuleMatcher<E> {
+    /// Create a new matcher using the similarity threshold from the config
+    pub fn new(embedder: E, store: RuleEmbeddingStore, config: &LearningConfig) -> Self {
+        Self {
+            embedder,
+            store,
+            threshold: config.similarity_threshold,
+        }
+    }
+
+    /// Minimum similarity for a rule to match
+    pub fn threshold(&self) -> f32 {
+        self.threshold
+    }
+
+    /// Get the embedding store
+    pub fn store(&self) -> &RuleEmbeddingStore {
+        &self.store
+    }
+
+    /// Compute and cache embeddings for rules that are missing or stale
+    ///
+    /// Returns the number of rules that were (re)embedded.
+    pub async fn index_rules(&self, rules: &[Rule]) -> Result<usize> {
+        let mut stale = Vec::new();
+        for rule in rules {
+            let fingerprint = Self::fingerprint(rule);
+            let is_fresh = self.store.get(&rule.id).await.is_some_and(|cached| {
+                cached.fingerprint == fingerprint && cached.model == self.embedder.model()
+            });
+            if !is_fresh {
+                stale.push((rule, fingerprint));
+            }
+        }
+
+        if stale.is_empty() {
+            return Ok(0);
+        }
+
+        let texts: Vec<String> = stale.iter().map(|(rule, _)| Self::rule_text(rule)).collect();
+        let vectors = self.embedder.embed(&texts).await?;
+
+        for ((rule, fingerprint), vector) in stale.iter().zip(vectors) {
+            self.store
+                .insert(
+                    rule.id.clone(),
+                    RuleEmbedding {
+                        model: self.embedder.model().to_string(),
+                        fingerprint: fingerprint.clone(),
+                        vector,
+                    },
+                )
+                .await;
+        }
+
+        Ok(stale.len())
+    }
+
+    /// Score every rule against the context, most similar first
+    pub async fn score_rules(
+        &self,
+        rules: &[Rule],
+        context: &GenerationContext,
+    ) -> Result<Vec<(Rule, f32)>> {
+        self.index_rules(rules).await?;
+
+        let context_vector = self
+            .embedder
+            .embed(&[Self::context_text(context)])
+            .await?
+            .pop()
+            .ok_or_else(|| {
+                LearningError::EmbeddingFailed("no embedding returned for context".to_string())
+            })?;
+
+        let mut scored = Vec::with_capacity(rules.len());
+        for rule in rules {
+            if let Some(cached) = self.store.get(&rule.id).await {
+                let similarity = cosine_similarity(&cached.vector, &context_vector);
+                scored.push((rule.clone(), similarity));
+            }
+        }
+
+        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        Ok(scored)
+    }
+
+    /// Find rules whose similarity to the context reaches the threshold, most similar first
+    pub async fn find_similar_rules(
+        &self,
+        rules: &[Rule],
+        context: &GenerationContext,
+    ) -> Result<Vec<(Rule, f32)>> {
+        let mut scored = self.score_rules(rules, context).await?;
+        scored.retain(|(_, similarity)| *similarity >= self.threshold);
+        Ok(scored)
+    }
+
+    /// Text embedded for a rule
+    fn rule_text(rule: &Rule) -> String {
+        format!("{}\n{}", rule.pattern, rule.action)
+    }
+
+    /// Text embedded for a generation context
+    fn context_text(context: &GenerationContext) -> String {
+        format!(
+            "{} {}\n{}",
+            context.language, context.generation_type, context.input
+        )
+    }
+
+    /// Fingerprint of the rule content that is embedded
+    fn fingerprint(rule: &Rule) -> String {
+        format!("{:x}", md5::compute(Self::rule_text(rule)))
+    }
+}
+
+/// Cosine similarity of two vectors, 0.0 if either is empty, zero or lengths differ
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+
+    dot / (norm_a * norm_b)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::models::RuleSource;
+
+    /// Embeds texts as counts of a small concept vocabulary, mapping synonyms together
+    struct ConceptEmbedder {
+        calls: AtomicUsize,
+    }
+
+    impl ConceptEmbedder {
+        fn new() -> Self {
+            Self {
+                calls: AtomicUsize::new(0),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl RuleEmbedder for ConceptEmbedder {
+        fn model(&self) -> &str {
+            "concepts"
+        }
+
+        async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let concepts: [&[&str]; 3] = [
+                &["error", "failure", "fallible", "result"],
+                &["test", "testing", "assert"],
+                &["docs", "documentation", "comment"],
+            ];
+            Ok(texts
+                .iter()
+                .map(|text| {
+                    let text = text.to_lowercase();
+                    concepts
+                        .iter()
+                        .map(|words| words.iter().filter(|w| text.contains(*w)).count() as f32)
+                        .collect()
+                })
+                .collect())
+        }
+    }
+
+    fn rule(pattern: &str, action: &str) -> Rule {
+        Rule::new(
+            RuleScope::Session,
+            pattern.to_string(),
+            action.to_string(),
+            RuleSource::Learned,
+        )
+    }
+
+    fn matcher() -> SemanticRuleMatcher<ConceptEmbedder> {
+        SemanticRuleMatcher::new(
+            ConceptEmbedder::new(),
+            RuleEmbeddingStore::in_memory(),
+            &LearningConfig::default(),
+        )
+    }
+
+    #[test]
+    fn test_cosine_similarity() {
+        assert!((cosine_similarity(&[1.0, 0.0], &[1.0, 0.0]) - 1.0).abs() < f32::EPSILON);
+        assert_eq!(cosine_similarity(&[1.0, 0.0], &[0.0, 1.0]), 0.0);
+        assert_eq!(cosine_similarity(&[0.0, 0.0], &[1.0, 0.0]), 0.0);
+        assert_eq!(cosine_similarity(&[1.0], &[1.0, 0.0]), 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_find_similar_rules_with_different_wording() {
+        let matcher = matcher();
+        let rules = vec![
+            rule("fallible operations", "return a Result instead of panicking"),
+            rule("public items", "add documentation comments"),
+        ];
+        let context = GenerationContext::new(
+            "function".to_string(),
+            "rust".to_string(),
+            "handle the failure when the error occurs".to_string(),
+        );
+
+        let similar = matcher.find_similar_rules(&rules, &context).await.unwrap();
+
+        assert_eq!(similar.len(), 1);
+        assert_eq!(similar[0].0.id, rules[0].id);
+        assert!(similar[0].1 >= matcher.threshold());
+    }
+
+    #[tokio::test]
+    async fn test_apply_rules_semantic() {
+        use crate::rule_application::RuleApplicationEngine;
+
+        let matcher = matcher();
+        let rules = vec![
+            rule("testing", "assert edge cases"),
+            rule("python", "add documentation comments"),
+            rule("rust", "prefer iterators"),
+        ];
+        let context = GenerationContext::new(
+            "function".to_string(),
+            "rust".to_string(),
+            "write a test for the parser".to_string(),
+        );
+
+        let matching =
+            RuleApplicationEngine::get_semantically_matching_rules(&rules, &context, &matcher)
+                .await
+                .unwrap();
+
+        let ids: Vec<_> = matching.iter().map(|r| r.id.clone()).collect();
+        assert_eq!(ids, vec![rules[0].id.clone(), rules[2].id.clone()]);
+    }
+
+    #[tokio::test]
+    async fn test_index_rules_reuses_fresh_embeddings() {
+        let matcher = matcher();
+        let mut rules = vec![rule("tests", "assert edge cases")];
+
+        assert_eq!(matcher.index_rules(&rules).await.unwrap(), 1);
+        assert_eq!(matcher.index_rules(&rules).await.unwrap(), 0);
+        assert_eq!(matcher.embedder.calls.load(Ordering::SeqCst), 1);
+
+        rules[0].action = "add documentation".to_string();
+        assert_eq!(matcher.index_rules(&rules).await.unwrap(), 1);
+        assert_eq!(matcher.store().len().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_embedding_store_persistence() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rules").join(EMBEDDINGS_FILE);
+
+        let store = RuleEmbeddingStore::with_path(path.clone());
+        let embedding = RuleEmbedding {
+            model: "concepts".to_string(),
+            fingerprint: "abc".to_string(),
+            vector: vec![0.5, 1.0],
+        };
+        store.insert("rule-1".to_string(), embedding.clone()).await;
+        store.save().await.unwrap();
+
+        let reloaded = RuleEmbeddingStore::with_path(path);
+        reloaded.load().await.unwrap();
+        assert_eq!(reloaded.get("rule-1").await, Some(embedding));
+    }
+}
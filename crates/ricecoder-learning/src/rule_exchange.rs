@@ -125,7 +125,7 @@ impl RuleImporter {
     }
 
     /// Validate a single rule
-    fn validate_rule(rule: &Rule) -> Result<()> {
+    pub(crate) fn validate_rule(rule: &Rule) -> Result<()> {
         // Validate pattern is not empty
         if rule.pattern.is_empty() {
             return Err(LearningError::RuleValidationFailed(
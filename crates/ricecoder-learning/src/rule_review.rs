@@ -214,6 +214,40 @@ impl RuleReviewManager {
         rule_id
     }
 
+    /// Start a review for a rule flagged by suggestion feedback
+    ///
+    /// The review gets a critical comment summarizing the feedback. An existing
+    /// pending review for the rule is kept and receives the comment instead.
+    pub fn start_feedback_review(
+        &mut self,
+        rule: Rule,
+        metrics: &crate::analytics_engine::RuleMetrics,
+    ) -> String {
+        let rule_id = rule.id.clone();
+        let has_pending = self
+            .reviews
+            .get(&rule_id)
+            .is_some_and(|review| review.status == ReviewStatus::Pending);
+        if !has_pending {
+            self.start_review(rule);
+        }
+
+        let text = format!(
+            "Flagged by suggestion feedback: {} rejected, {} reverted, {} accepted \
+             (rejection rate {:.0}%, confidence {:.2})",
+            metrics.rejected_suggestions,
+            metrics.reverted_suggestions,
+            metrics.accepted_suggestions,
+            metrics.rejection_rate() * 100.0,
+            metrics.confidence
+        );
+        if let Some(review) = self.reviews.get_mut(&rule_id) {
+            review.add_comment(ReviewComment::new("feedback".to_string(), text, true));
+        }
+
+        rule_id
+    }
+
     /// Get a review
     pub fn get_review(&self, rule_id: &str) -> Option<&ReviewInfo> {
         self.reviews.get(rule_id)
@@ -443,6 +477,29 @@ mod tests {
         assert!(!review.is_complete());
     }
 
+    #[test]
+    fn test_start_feedback_review() {
+        let rule = Rule::new(
+            RuleScope::Project,
+            "pattern".to_string(),
+            "action".to_string(),
+            RuleSource::Learned,
+        );
+        let mut metrics = crate::analytics_engine::RuleMetrics::new(rule.id.clone());
+        metrics.rejected_suggestions = 4;
+        metrics.accepted_suggestions = 1;
+
+        let mut manager = RuleReviewManager::new();
+        let rule_id = manager.start_feedback_review(rule.clone(), &metrics);
+        manager.start_feedback_review(rule, &metrics);
+
+        let review = manager.get_review(&rule_id).unwrap();
+        assert_eq!(review.status, ReviewStatus::Pending);
+        assert_eq!(review.critical_comment_count(), 2);
+        assert!(review.comments[0].text.contains("4 rejected"));
+        assert!(review.comments[0].text.contains("80%"));
+    }
+
     #[test]
     fn test_add_comment() {
         let rule = Rule::new(
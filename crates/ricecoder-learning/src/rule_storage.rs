@@ -26,7 +26,12 @@ impl RuleStorage {
 
     /// Get the storage path for the current scope
     fn get_storage_path(&self) -> Result<PathBuf> {
-        match self.scope {
+        Self::storage_path_for(self.scope)
+    }
+
+    /// Get the storage path for a scope
+    pub(crate) fn storage_path_for(scope: RuleScope) -> Result<PathBuf> {
+        match scope {
             RuleScope::Global => {
                 let global_path = PathResolver::resolve_global_path()?;
                 Ok(global_path.join("rules"))
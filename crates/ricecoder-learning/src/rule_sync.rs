@@ -0,0 +1,793 @@
+//! Team rule sync
+//!
+//! [`RuleSyncService`] shares rule sets through a [`RuleSyncBackend`]: pulling
+//! merges the shared rules into the local set, pushing merges first and then
+//! publishes the result. [`GitRuleRepository`] stores the shared set as a
+//! [`RuleExport`] file in a git repository; other backends (such as a
+//! ricecoder-teams server) implement [`RuleSyncBackend`] directly.
+//!
+//! Conflicting rules (same pattern, different action) are settled with
+//! [`ConflictResolver::resolve_sync_conflict`], every synced rule carries a
+//! [`RuleProvenance`] in its metadata, and a [`ScopeMapping`] decides which
+//! local scope an incoming rule lands in.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    process::Output,
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::{fs, process::Command};
+
+use crate::conflict_resolver::ConflictResolver;
+use crate::error::{LearningError, Result};
+use crate::models::{Rule, RuleScope};
+use crate::rule_exchange::{RuleExport, RuleExporter, RuleImporter};
+use crate::rule_storage::RuleStorage;
+
+/// Metadata key under which provenance is stored
+pub const PROVENANCE_KEY: &str = "provenance";
+
+/// Where a shared rule came from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleProvenance {
+    /// Backend the rule was first shared through
+    pub origin: String,
+    /// Who first shared the rule, if known
+    pub author: Option<String>,
+    /// Scope the rule had when it was first shared
+    pub original_scope: RuleScope,
+    /// When the rule was last synced
+    pub synced_at: DateTime<Utc>,
+}
+
+impl RuleProvenance {
+    /// Create provenance for a rule shared now
+    pub fn new(origin: String, author: Option<String>, original_scope: RuleScope) -> Self {
+        Self {
+            origin,
+            author,
+            original_scope,
+            synced_at: Utc::now(),
+        }
+    }
+
+    /// Read the provenance stored in a rule's metadata
+    pub fn from_rule(rule: &Rule) -> Option<Self> {
+        rule.metadata
+            .get(PROVENANCE_KEY)
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+    }
+
+    /// Store this provenance in a rule's metadata
+    pub fn attach(&self, rule: &mut Rule) -> Result<()> {
+        if !rule.metadata.is_object() {
+            rule.metadata = serde_json::json!({});
+        }
+        rule.metadata[PROVENANCE_KEY] = serde_json::to_value(self)?;
+        Ok(())
+    }
+}
+
+/// Maps the scope of incoming rules to a local scope
+///
+/// By default rules keep their scope (project rules stay project-scoped) and
+/// session rules are never shared.
+#[derive(Debug, Clone)]
+pub struct ScopeMapping {
+    mappings: Vec<(RuleScope, Option<RuleScope>)>,
+}
+
+impl Default for ScopeMapping {
+    fn default() -> Self {
+        Self {
+            mappings: vec![
+                (RuleScope::Project, Some(RuleScope::Project)),
+                (RuleScope::Global, Some(RuleScope::Global)),
+                (RuleScope::Session, None),
+            ],
+        }
+    }
+}
+
+impl ScopeMapping {
+    /// Create the default scope mapping
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Import rules from one scope into another
+    pub fn map(mut self, from: RuleScope, to: RuleScope) -> Self {
+        self.set(from, Some(to));
+        self
+    }
+
+    /// Never sync rules of a scope
+    pub fn exclude(mut self, scope: RuleScope) -> Self {
+        self.set(scope, None);
+        self
+    }
+
+    /// Local scope for a rule of the given scope, `None` if it is not synced
+    pub fn resolve(&self, scope: RuleScope) -> Option<RuleScope> {
+        self.mappings
+            .iter()
+            .find(|(from, _)| *from == scope)
+            .and_then(|(_, to)| *to)
+    }
+
+    fn set(&mut self, from: RuleScope, to: Option<RuleScope>) {
+        match self.mappings.iter_mut().find(|(scope, _)| *scope == from) {
+            Some(entry) => entry.1 = to,
+            None => self.mappings.push((from, to)),
+        }
+    }
+}
+
+/// A conflict settled during sync
+#[derive(Debug, Clone)]
+pub struct RuleSyncConflict {
+    /// The local rule
+    pub local: Rule,
+    /// The conflicting incoming rule
+    pub incoming: Rule,
+    /// ID of the rule that was kept
+    pub winner_id: String,
+}
+
+/// Outcome of a sync
+#[derive(Debug, Clone, Default)]
+pub struct RuleSyncReport {
+    /// Incoming rules that are new locally
+    pub added: Vec<Rule>,
+    /// Local rules replaced by a newer incoming version
+    pub updated: Vec<Rule>,
+    /// Local rules that lost a conflict against an incoming rule
+    pub removed: Vec<Rule>,
+    /// Conflicts between different rules for the same pattern
+    pub conflicts: Vec<RuleSyncConflict>,
+    /// Incoming rules rejected by validation, with the reason
+    pub rejected: Vec<String>,
+    /// Incoming rules skipped because their scope is not synced
+    pub skipped: usize,
+    /// Incoming rules identical to, or older than, their local version
+    pub unchanged: usize,
+    /// Number of rules published (push only)
+    pub pushed: usize,
+}
+
+impl RuleSyncReport {
+    /// Whether the sync changed the local rule set
+    pub fn has_local_changes(&self) -> bool {
+        !self.added.is_empty() || !self.updated.is_empty() || !self.removed.is_empty()
+    }
+}
+
+/// Storage for a shared rule set
+#[async_trait]
+pub trait RuleSyncBackend: Send + Sync {
+    /// Backend name, recorded as the origin in rule provenance
+    fn name(&self) -> &str;
+
+    /// Fetch the shared rule set, `None` if nothing has been shared yet
+    async fn pull(&self) -> Result<Option<RuleExport>>;
+
+    /// Replace the shared rule set
+    async fn push(&self, export: &RuleExport, message: &str) -> Result<()>;
+}
+
+/// Shares rules through a git repository
+///
+/// The rule set is kept as a single [`RuleExport`] JSON file. With a remote
+/// configured, pulls read the file straight from the fetched branch and pushes
+/// commit on top of it, so the local checkout never needs merging.
+pub struct GitRuleRepository {
+    repo_dir: PathBuf,
+    remote: Option<String>,
+    branch: String,
+    file_name: String,
+    identity: Option<(String, String)>,
+}
+
+impl GitRuleRepository {
+    /// Create a repository rooted at the given directory, initialized on first use
+    pub fn new(repo_dir: PathBuf) -> Self {
+        Self {
+            repo_dir,
+            remote: None,
+            branch: "main".to_string(),
+            file_name: "rules.json".to_string(),
+            identity: None,
+        }
+    }
+
+    /// Sync with a remote repository on the given branch
+    pub fn with_remote(mut self, remote: String, branch: String) -> Self {
+        self.remote = Some(remote);
+        self.branch = branch;
+        self
+    }
+
+    /// Set the file name of the rule set inside the repository
+    pub fn with_file_name(mut self, file_name: String) -> Self {
+        self.file_name = file_name;
+        self
+    }
+
+    /// Commit as the given name and email instead of the git configuration
+    pub fn with_identity(mut self, name: String, email: String) -> Self {
+        self.identity = Some((name, email));
+        self
+    }
+
+    /// Get the repository directory
+    pub fn repo_dir(&self) -> &Path {
+        &self.repo_dir
+    }
+
+    /// Initialize the repository and remote if needed
+    async fn ensure_repository(&self) -> Result<()> {
+        if self.repo_dir.join(".git").exists() {
+            return Ok(());
+        }
+
+        fs::create_dir_all(&self.repo_dir).await.map_err(|e| {
+            LearningError::SyncFailed(format!("Failed to create repository directory: {}", e))
+        })?;
+        self.git(&["init", "--quiet"]).await?;
+        if let Some(remote) = &self.remote {
+            self.git(&["remote", "add", "origin", remote]).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Fetch the remote branch, returning whether it exists
+    async fn fetch(&self) -> Result<bool> {
+        let output = self
+            .run_git(&[
+                "ls-remote",
+                "--exit-code",
+                "--heads",
+                "origin",
+                &self.branch,
+            ])
+            .await?;
+        if !output.status.success() {
+            // Exit code 2 means the branch does not exist yet
+            if output.status.code() == Some(2) {
+                return Ok(false);
+            }
+            return Err(Self::git_error("ls-remote", &output));
+        }
+
+        self.git(&["fetch", "--quiet", "origin", &self.branch])
+            .await?;
+        Ok(true)
+    }
+
+    async fn git(&self, args: &[&str]) -> Result<Output> {
+        let output = self.run_git(args).await?;
+        if !output.status.success() {
+            return Err(Self::git_error(args[0], &output));
+        }
+        Ok(output)
+    }
+
+    async fn run_git(&self, args: &[&str]) -> Result<Output> {
+        let mut command = Command::new("git");
+        command.arg("-C").arg(&self.repo_dir).args(args);
+        if let Some((name, email)) = &self.identity {
+            command
+                .env("GIT_AUTHOR_NAME", name)
+                .env("GIT_AUTHOR_EMAIL", email)
+                .env("GIT_COMMITTER_NAME", name)
+                .env("GIT_COMMITTER_EMAIL", email);
+        }
+
+        command.output().await.map_err(|e| {
+            LearningError::SyncFailed(format!("Failed to execute git {}: {}", args[0], e))
+        })
+    }
+
+    fn git_error(command: &str, output: &Output) -> LearningError {
+        LearningError::SyncFailed(format!(
+            "git {} failed: {}",
+            command,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+#[async_trait]
+impl RuleSyncBackend for GitRuleRepository {
+    fn name(&self) -> &str {
+        self.remote.as_deref().unwrap_or("git")
+    }
+
+    async fn pull(&self) -> Result<Option<RuleExport>> {
+        self.ensure_repository().await?;
+
+        if self.remote.is_none() {
+            let path = self.repo_dir.join(&self.file_name);
+            if !path.exists() {
+                return Ok(None);
+            }
+            return RuleExport::read_from_file(&path).map(Some);
+        }
+
+        if !self.fetch().await? {
+            return Ok(None);
+        }
+
+        let object = format!("FETCH_HEAD:{}", self.file_name);
+        let output = self.run_git(&["show", &object]).await?;
+        if !output.status.success() {
+            // The branch exists but does not contain a rule set yet
+            return Ok(None);
+        }
+
+        RuleExport::from_json(&String::from_utf8_lossy(&output.stdout)).map(Some)
+    }
+
+    async fn push(&self, export: &RuleExport, message: &str) -> Result<()> {
+        self.ensure_repository().await?;
+
+        if self.remote.is_some() && self.fetch().await? {
+            self.git(&[
+                "checkout",
+                "--quiet",
+                "-f",
+                "-B",
+                &self.branch,
+                "FETCH_HEAD",
+            ])
+            .await?;
+        }
+
+        export.write_to_file(&self.repo_dir.join(&self.file_name))?;
+        self.git(&["add", "--", &self.file_name]).await?;
+
+        let staged = self.run_git(&["diff", "--cached", "--quiet"]).await?;
+        if !staged.status.success() {
+            self.git(&["commit", "--quiet", "-m", message]).await?;
+        }
+
+        if self.remote.is_some() {
+            let refspec = format!("HEAD:refs/heads/{}", self.branch);
+            self.git(&["push", "--quiet", "origin", &refspec]).await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Pushes and pulls rule sets through a [`RuleSyncBackend`]
+pub struct RuleSyncService<B: RuleSyncBackend> {
+    backend: B,
+    author: Option<String>,
+    scope_mapping: ScopeMapping,
+}
+
+impl<B: RuleSyncBackend> RuleSyncService<B> {
+    /// Create a new sync service
+    pub fn new(backend: B) -> Self {
+        Self {
+            backend,
+            author: None,
+            scope_mapping: ScopeMapping::default(),
+        }
+    }
+
+    /// Record the given author in the provenance of rules this service shares
+    pub fn with_author(mut self, author: String) -> Self {
+        self.author = Some(author);
+        self
+    }
+
+    /// Use a custom scope mapping
+    pub fn with_scope_mapping(mut self, scope_mapping: ScopeMapping) -> Self {
+        self.scope_mapping = scope_mapping;
+        self
+    }
+
+    /// Get the backend
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+
+    /// Merge the shared rule set into the local rules
+    ///
+    /// The local rules are not modified; apply the report's `added`,
+    /// `updated` and `removed` rules to persist the result.
+    pub async fn pull(&self, local_rules: &[Rule]) -> Result<RuleSyncReport> {
+        let incoming = self
+            .backend
+            .pull()
+            .await?
+            .map(|export| export.rules)
+            .unwrap_or_default();
+
+        self.merge(local_rules, &incoming)
+    }
+
+    /// Merge the shared rule set into the local rules, then publish the result
+    pub async fn push(&self, local_rules: &[Rule]) -> Result<RuleSyncReport> {
+        let remote_rules = self
+            .backend
+            .pull()
+            .await?
+            .map(|export| export.rules)
+            .unwrap_or_default();
+
+        let mut report = self.merge(local_rules, &remote_rules)?;
+        let merged = Self::apply_report(local_rules, &report);
+
+        // Keep remote rules that were not imported (e.g. other scopes), then
+        // overlay the merged local view
+        let mut shared: HashMap<String, Rule> = remote_rules
+            .into_iter()
+            .map(|rule| (rule.id.clone(), rule))
+            .collect();
+        for conflict in &report.conflicts {
+            if conflict.winner_id != conflict.incoming.id {
+                shared.remove(&conflict.incoming.id);
+            }
+        }
+        for mut rule in merged {
+            if self.scope_mapping.resolve(rule.scope).is_none() {
+                continue;
+            }
+            if RuleProvenance::from_rule(&rule).is_none() {
+                RuleProvenance::new(
+                    self.backend.name().to_string(),
+                    self.author.clone(),
+                    rule.scope,
+                )
+                .attach(&mut rule)?;
+            }
+            // Rules pulled unchanged keep their shared form (e.g. original scope)
+            let is_newer = shared.get(&rule.id).is_none_or(|existing| {
+                (rule.version, rule.updated_at) > (existing.version, existing.updated_at)
+            });
+            if is_newer {
+                shared.insert(rule.id.clone(), rule);
+            }
+        }
+
+        let mut rules: Vec<Rule> = shared.into_values().collect();
+        rules.sort_by(|a, b| a.id.cmp(&b.id));
+        report.pushed = rules.len();
+
+        let description = match &self.author {
+            Some(author) => format!("Shared rules from {}", author),
+            None => "Shared rules".to_string(),
+        };
+        let export = RuleExporter::export_rules(rules, Some(description.clone()))?;
+        self.backend.push(&export, &description).await?;
+
+        Ok(report)
+    }
+
+    /// Pull into a rule storage, then push the storage's rules
+    ///
+    /// Only rules whose mapped scope matches the storage scope are stored.
+    pub async fn sync_storage(&self, storage: &RuleStorage) -> Result<RuleSyncReport> {
+        let local_rules = storage.list_rules().await?;
+        let report = self.pull(&local_rules).await?;
+
+        let scope = storage.get_scope();
+        for rule in &report.removed {
+            storage.delete_rule(&rule.id).await?;
+        }
+        for rule in report.added.iter().filter(|r| r.scope == scope) {
+            storage.store_rule(rule.clone()).await?;
+        }
+        for rule in report.updated.iter().filter(|r| r.scope == scope) {
+            storage.update_rule(rule.clone()).await?;
+        }
+
+        let local_rules = storage.list_rules().await?;
+        let mut push_report = self.push(&local_rules).await?;
+        push_report.added = report.added;
+        push_report.updated = report.updated;
+        push_report.removed = report.removed;
+        push_report.conflicts = report.conflicts;
+        push_report.rejected = report.rejected;
+        push_report.skipped = report.skipped;
+        push_report.unchanged = report.unchanged;
+
+        Ok(push_report)
+    }
+
+    /// Merge incoming rules into local rules
+    fn merge(&self, local_rules: &[Rule], incoming_rules: &[Rule]) -> Result<RuleSyncReport> {
+        let mut report = RuleSyncReport::default();
+        let mut current: Vec<Rule> = local_rules.to_vec();
+
+        for incoming in incoming_rules {
+            if let Err(e) = RuleImporter::validate_rule(incoming) {
+                report.rejected.push(format!("Rule {}: {}", incoming.id, e));
+                continue;
+            }
+
+            let Some(scope) = self.scope_mapping.resolve(incoming.scope) else {
+                report.skipped += 1;
+                continue;
+            };
+
+            let mut incoming = incoming.clone();
+            let mut provenance = RuleProvenance::from_rule(&incoming).unwrap_or_else(|| {
+                RuleProvenance::new(self.backend.name().to_string(), None, incoming.scope)
+            });
+            provenance.synced_at = Utc::now();
+            provenance.attach(&mut incoming)?;
+            incoming.scope = scope;
+
+            // Same rule: keep the newer version
+            if let Some(index) = current.iter().position(|r| r.id == incoming.id) {
+                let local = &current[index];
+                let is_newer =
+                    (incoming.version, incoming.updated_at) > (local.version, local.updated_at);
+                if is_newer {
+                    current[index] = incoming.clone();
+                    report.updated.push(incoming);
+                } else {
+                    report.unchanged += 1;
+                }
+                continue;
+            }
+
+            // Different rule for the same pattern in the same scope
+            if let Some(index) = current.iter().position(|r| {
+                r.scope == incoming.scope && ConflictResolver::detect_conflict(r, &incoming)
+            }) {
+                let local = current[index].clone();
+                let winner = ConflictResolver::resolve_sync_conflict(&local, &incoming);
+                if winner.id == incoming.id {
+                    current[index] = incoming.clone();
+                    report.removed.push(local.clone());
+                    report.added.push(incoming.clone());
+                }
+                report.conflicts.push(RuleSyncConflict {
+                    local,
+                    incoming,
+                    winner_id: winner.id,
+                });
+                continue;
+            }
+
+            current.push(incoming.clone());
+            report.added.push(incoming);
+        }
+
+        Ok(report)
+    }
+
+    /// Apply a report's changes to a set of local rules
+    fn apply_report(local_rules: &[Rule], report: &RuleSyncReport) -> Vec<Rule> {
+        let mut rules: HashMap<String, Rule> = local_rules
+            .iter()
+            .map(|rule| (rule.id.clone(), rule.clone()))
+            .collect();
+        for rule in &report.removed {
+            rules.remove(&rule.id);
+        }
+        for rule in report.added.iter().chain(&report.updated) {
+            rules.insert(rule.id.clone(), rule.clone());
+        }
+        rules.into_values().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::RwLock;
+
+    use super::*;
+    use crate::models::RuleSource;
+
+    /// Backend holding the shared rule set in memory
+    #[derive(Default)]
+    struct MemoryBackend {
+        export: RwLock<Option<RuleExport>>,
+    }
+
+    impl MemoryBackend {
+        fn with_rules(rules: Vec<Rule>) -> Self {
+            Self {
+                export: RwLock::new(Some(RuleExport::new(rules, None))),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl RuleSyncBackend for MemoryBackend {
+        fn name(&self) -> &str {
+            "memory"
+        }
+
+        async fn pull(&self) -> Result<Option<RuleExport>> {
+            Ok(self.export.read().await.clone())
+        }
+
+        async fn push(&self, export: &RuleExport, _message: &str) -> Result<()> {
+            *self.export.write().await = Some(export.clone());
+            Ok(())
+        }
+    }
+
+    fn rule(id: &str, scope: RuleScope, pattern: &str, action: &str) -> Rule {
+        let mut rule = Rule::new(
+            scope,
+            pattern.to_string(),
+            action.to_string(),
+            RuleSource::Learned,
+        );
+        rule.id = id.to_string();
+        rule
+    }
+
+    #[test]
+    fn test_scope_mapping() {
+        let mapping = ScopeMapping::default();
+        assert_eq!(
+            mapping.resolve(RuleScope::Project),
+            Some(RuleScope::Project)
+        );
+        assert_eq!(mapping.resolve(RuleScope::Global), Some(RuleScope::Global));
+        assert_eq!(mapping.resolve(RuleScope::Session), None);
+
+        let mapping = mapping
+            .map(RuleScope::Global, RuleScope::Project)
+            .exclude(RuleScope::Project);
+        assert_eq!(mapping.resolve(RuleScope::Global), Some(RuleScope::Project));
+        assert_eq!(mapping.resolve(RuleScope::Project), None);
+    }
+
+    #[test]
+    fn test_provenance_round_trip() {
+        let mut rule = rule("r1", RuleScope::Project, "pattern", "action");
+        assert!(RuleProvenance::from_rule(&rule).is_none());
+
+        let provenance = RuleProvenance::new(
+            "git".to_string(),
+            Some("alice".to_string()),
+            RuleScope::Project,
+        );
+        provenance.attach(&mut rule).unwrap();
+
+        assert_eq!(RuleProvenance::from_rule(&rule), Some(provenance));
+    }
+
+    #[tokio::test]
+    async fn test_pull_maps_scopes_and_validates() {
+        let mut invalid = rule("invalid", RuleScope::Global, "pattern", "action");
+        invalid.action = String::new();
+        let backend = MemoryBackend::with_rules(vec![
+            rule("project", RuleScope::Project, "project pattern", "action"),
+            rule("session", RuleScope::Session, "session pattern", "action"),
+            invalid,
+        ]);
+        let service = RuleSyncService::new(backend);
+
+        let report = service.pull(&[]).await.unwrap();
+
+        assert_eq!(report.added.len(), 1);
+        assert_eq!(report.added[0].scope, RuleScope::Project);
+        let provenance = RuleProvenance::from_rule(&report.added[0]).unwrap();
+        assert_eq!(provenance.origin, "memory");
+        assert_eq!(provenance.original_scope, RuleScope::Project);
+        assert_eq!(report.skipped, 1);
+        assert_eq!(report.rejected.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_pull_keeps_newer_version() {
+        let local = rule("r1", RuleScope::Global, "pattern", "old action");
+        let mut newer = local.clone();
+        newer.action = "new action".to_string();
+        newer.version = 2;
+        let older = rule("r2", RuleScope::Global, "other", "remote action");
+        let mut local_newer = older.clone();
+        local_newer.version = 3;
+
+        let service = RuleSyncService::new(MemoryBackend::with_rules(vec![newer, older]));
+        let report = service.pull(&[local, local_newer]).await.unwrap();
+
+        assert_eq!(report.updated.len(), 1);
+        assert_eq!(report.updated[0].action, "new action");
+        assert_eq!(report.unchanged, 1);
+    }
+
+    #[tokio::test]
+    async fn test_pull_resolves_conflicts() {
+        let local = rule("local", RuleScope::Global, "pattern", "local action");
+        let mut incoming = rule("incoming", RuleScope::Global, "pattern", "team action");
+        incoming.version = 2;
+        let other = rule("other", RuleScope::Global, "other", "team action");
+        let local_project = rule(
+            "local-project",
+            RuleScope::Project,
+            "other",
+            "project action",
+        );
+
+        let service = RuleSyncService::new(MemoryBackend::with_rules(vec![incoming, other]));
+        let report = service.pull(&[local, local_project]).await.unwrap();
+
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(report.conflicts[0].winner_id, "incoming");
+        assert_eq!(report.removed[0].id, "local");
+        // Different scopes do not conflict; precedence applies at use time
+        let added: Vec<_> = report.added.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(added, vec!["incoming", "other"]);
+    }
+
+    #[tokio::test]
+    async fn test_push_publishes_merged_rules() {
+        let remote = rule("remote", RuleScope::Global, "remote pattern", "action");
+        let service = RuleSyncService::new(MemoryBackend::with_rules(vec![remote]))
+            .with_author("alice".to_string());
+        let local = vec![
+            rule("local", RuleScope::Project, "local pattern", "action"),
+            rule("session", RuleScope::Session, "session pattern", "action"),
+        ];
+
+        let report = service.push(&local).await.unwrap();
+        assert_eq!(report.pushed, 2);
+
+        let export = service.backend().pull().await.unwrap().unwrap();
+        let ids: Vec<_> = export.rules.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["local", "remote"]);
+        let provenance = RuleProvenance::from_rule(&export.rules[0]).unwrap();
+        assert_eq!(provenance.author.as_deref(), Some("alice"));
+        assert_eq!(provenance.original_scope, RuleScope::Project);
+    }
+
+    #[tokio::test]
+    async fn test_git_repository_sync() {
+        let dir = tempfile::tempdir().unwrap();
+        let remote = dir.path().join("remote.git");
+        let status = std::process::Command::new("git")
+            .args(["init", "--quiet", "--bare"])
+            .arg(&remote)
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let repository = |name: &str| {
+            GitRuleRepository::new(dir.path().join(name))
+                .with_remote(remote.display().to_string(), "main".to_string())
+                .with_identity(name.to_string(), format!("{}@example.com", name))
+        };
+
+        let alice = RuleSyncService::new(repository("alice")).with_author("alice".to_string());
+        let bob = RuleSyncService::new(repository("bob"));
+
+        // Nothing shared yet
+        assert!(bob.pull(&[]).await.unwrap().added.is_empty());
+
+        alice
+            .push(&[rule("r1", RuleScope::Project, "pattern", "action")])
+            .await
+            .unwrap();
+        let report = bob
+            .push(&[rule("r2", RuleScope::Global, "other", "action")])
+            .await
+            .unwrap();
+        assert_eq!(report.added.len(), 1);
+        assert_eq!(report.pushed, 2);
+
+        let report = alice
+            .pull(&[rule("r1", RuleScope::Project, "pattern", "action")])
+            .await
+            .unwrap();
+        assert_eq!(report.added.len(), 1);
+        assert_eq!(report.added[0].id, "r2");
+        assert_eq!(report.unchanged, 1);
+    }
+}
@@ -41,6 +41,7 @@ pub mod config;
 pub mod diagnostics;
 pub mod hover;
 pub mod performance;
+pub mod progress;
 pub mod providers;
 pub mod proxy;
 pub mod refactoring;
@@ -61,6 +62,9 @@ pub use config::{
 pub use diagnostics::DiagnosticsEngine;
 pub use hover::HoverProvider;
 pub use performance::{PerformanceAnalyzer, PerformanceTracker, Timer};
+pub use progress::{
+    ActiveProgress, ProgressParams, ProgressToken, ProgressTracker, WorkDoneProgress,
+};
 pub use providers::{
     CodeActionProvider, CodeActionRegistry, DiagnosticsProvider, DiagnosticsRegistry,
     SemanticAnalyzerProvider, SemanticAnalyzerRegistry,
@@ -0,0 +1,350 @@
+//! Work-done progress tracking for external LSP servers
+//!
+//! External servers such as rust-analyzer report long-running work (indexing,
+//! cargo metadata, build script evaluation) through `$/progress` notifications
+//! carrying a work-done token. This module keeps track of the active tokens per
+//! language so that:
+//!
+//! - the TUI status bar can display what each server is currently doing
+//! - pull diagnostics (`textDocument/diagnostic`) can be deferred until the
+//!   server finished indexing, instead of surfacing incomplete results
+//!
+//! # Lifecycle
+//!
+//! ```text
+//! window/workDoneProgress/create (token)   → token registered (idle)
+//! $/progress { kind: "begin", title, ... } → token active
+//! $/progress { kind: "report", ... }       → message/percentage updated
+//! $/progress { kind: "end", ... }          → token removed
+//! ```
+
+use std::collections::{BTreeSet, HashMap};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::types::{LspError, LspResult};
+
+/// Work-done progress token (LSP allows either an integer or a string)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ProgressToken {
+    /// Numeric token
+    Number(i64),
+    /// String token
+    String(String),
+}
+
+impl std::fmt::Display for ProgressToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProgressToken::Number(n) => write!(f, "{}", n),
+            ProgressToken::String(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+/// Work-done progress payload carried by `$/progress`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum WorkDoneProgress {
+    /// Start of a long-running operation
+    Begin {
+        /// Short title of the operation (e.g. "Indexing")
+        title: String,
+        /// Whether the operation can be cancelled
+        #[serde(default)]
+        cancellable: Option<bool>,
+        /// Optional detail message
+        #[serde(default)]
+        message: Option<String>,
+        /// Optional percentage (0-100)
+        #[serde(default)]
+        percentage: Option<u32>,
+    },
+    /// Intermediate progress report
+    Report {
+        /// Whether the operation can be cancelled
+        #[serde(default)]
+        cancellable: Option<bool>,
+        /// Optional detail message
+        #[serde(default)]
+        message: Option<String>,
+        /// Optional percentage (0-100)
+        #[serde(default)]
+        percentage: Option<u32>,
+    },
+    /// End of the operation
+    End {
+        /// Optional final message
+        #[serde(default)]
+        message: Option<String>,
+    },
+}
+
+/// Parameters of a `$/progress` notification
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProgressParams {
+    /// Work-done token
+    pub token: ProgressToken,
+    /// Progress payload
+    pub value: WorkDoneProgress,
+}
+
+impl ProgressParams {
+    /// Parse `$/progress` notification parameters
+    pub fn from_value(params: &Value) -> LspResult<Self> {
+        serde_json::from_value(params.clone())
+            .map_err(|e| LspError::InvalidParams(format!("Invalid $/progress params: {}", e)))
+    }
+}
+
+/// State of a single in-flight operation
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActiveProgress {
+    /// Language served by the reporting server
+    pub language: String,
+    /// Operation title, `None` until `begin` is received
+    pub title: Option<String>,
+    /// Latest detail message
+    pub message: Option<String>,
+    /// Latest percentage
+    pub percentage: Option<u32>,
+}
+
+impl ActiveProgress {
+    /// Human-readable summary of the operation
+    pub fn display_text(&self) -> String {
+        let mut text = format!(
+            "{}: {}",
+            self.language,
+            self.title.as_deref().unwrap_or("working")
+        );
+        if let Some(message) = &self.message {
+            text.push(' ');
+            text.push_str(message);
+        }
+        if let Some(percentage) = self.percentage {
+            text.push_str(&format!(" ({}%)", percentage));
+        }
+        text
+    }
+}
+
+/// Tracks work-done progress tokens reported by external LSP servers
+#[derive(Debug, Default)]
+pub struct ProgressTracker {
+    /// Active operations keyed by (language, token)
+    active: HashMap<(String, ProgressToken), ActiveProgress>,
+    /// Documents whose pull diagnostics were deferred, per language
+    deferred_diagnostics: HashMap<String, BTreeSet<String>>,
+}
+
+impl ProgressTracker {
+    /// Create an empty progress tracker
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a token announced through `window/workDoneProgress/create`
+    pub fn create_token(&mut self, language: &str, token: ProgressToken) {
+        self.active
+            .entry((language.to_string(), token))
+            .or_insert_with(|| ActiveProgress {
+                language: language.to_string(),
+                title: None,
+                message: None,
+                percentage: None,
+            });
+    }
+
+    /// Apply a `$/progress` notification
+    ///
+    /// # Returns
+    ///
+    /// Document URIs whose pull diagnostics were deferred and should now be
+    /// re-requested because the language finished all of its work.
+    pub fn apply(&mut self, language: &str, params: ProgressParams) -> Vec<String> {
+        let key = (language.to_string(), params.token);
+
+        match params.value {
+            WorkDoneProgress::Begin {
+                title,
+                message,
+                percentage,
+                ..
+            } => {
+                self.active.insert(
+                    key,
+                    ActiveProgress {
+                        language: language.to_string(),
+                        title: Some(title),
+                        message,
+                        percentage,
+                    },
+                );
+                Vec::new()
+            }
+            WorkDoneProgress::Report {
+                message,
+                percentage,
+                ..
+            } => {
+                if let Some(progress) = self.active.get_mut(&key) {
+                    if message.is_some() {
+                        progress.message = message;
+                    }
+                    if percentage.is_some() {
+                        progress.percentage = percentage;
+                    }
+                }
+                Vec::new()
+            }
+            WorkDoneProgress::End { .. } => {
+                self.active.remove(&key);
+                if self.is_busy(language) {
+                    Vec::new()
+                } else {
+                    self.deferred_diagnostics
+                        .remove(language)
+                        .map(|uris| uris.into_iter().collect())
+                        .unwrap_or_default()
+                }
+            }
+        }
+    }
+
+    /// Check whether a server for the language has work in progress
+    pub fn is_busy(&self, language: &str) -> bool {
+        self.active.keys().any(|(lang, _)| lang == language)
+    }
+
+    /// Remember that diagnostics for a document were deferred
+    pub fn defer_diagnostics(&mut self, language: &str, uri: &str) {
+        self.deferred_diagnostics
+            .entry(language.to_string())
+            .or_default()
+            .insert(uri.to_string());
+    }
+
+    /// Get the active operations, sorted by language then title
+    pub fn active(&self) -> Vec<&ActiveProgress> {
+        let mut active: Vec<_> = self.active.values().collect();
+        active.sort_by(|a, b| a.language.cmp(&b.language).then(a.title.cmp(&b.title)));
+        active
+    }
+
+    /// Status bar text summarizing the active operations
+    ///
+    /// Returns `None` when no server reports work in progress.
+    pub fn status_text(&self) -> Option<String> {
+        let active = self.active();
+        let first = active.first()?;
+        let mut text = first.display_text();
+        if active.len() > 1 {
+            text.push_str(&format!(" (+{} more)", active.len() - 1));
+        }
+        Some(text)
+    }
+
+    /// Drop all state for a language (e.g. when its server exits)
+    pub fn clear_language(&mut self, language: &str) {
+        self.active.retain(|(lang, _), _| lang != language);
+        self.deferred_diagnostics.remove(language);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn params(value: Value) -> ProgressParams {
+        ProgressParams::from_value(&value).unwrap()
+    }
+
+    #[test]
+    fn test_parse_progress_params() {
+        let parsed = params(json!({
+            "token": "rustAnalyzer/Indexing",
+            "value": {"kind": "begin", "title": "Indexing", "percentage": 0}
+        }));
+
+        assert_eq!(
+            parsed.token,
+            ProgressToken::String("rustAnalyzer/Indexing".to_string())
+        );
+        assert!(matches!(parsed.value, WorkDoneProgress::Begin { .. }));
+        assert!(ProgressParams::from_value(&json!({"token": 1})).is_err());
+    }
+
+    #[test]
+    fn test_progress_lifecycle() {
+        let mut tracker = ProgressTracker::new();
+        tracker.create_token("rust", ProgressToken::Number(1));
+        assert!(tracker.is_busy("rust"));
+
+        tracker.apply(
+            "rust",
+            params(json!({"token": 1, "value": {"kind": "begin", "title": "Indexing"}})),
+        );
+        tracker.apply(
+            "rust",
+            params(json!({"token": 1, "value": {"kind": "report", "message": "12/40", "percentage": 30}})),
+        );
+        assert_eq!(tracker.status_text().unwrap(), "rust: Indexing 12/40 (30%)");
+
+        tracker.apply(
+            "rust",
+            params(json!({"token": 1, "value": {"kind": "end"}})),
+        );
+        assert!(!tracker.is_busy("rust"));
+        assert!(tracker.status_text().is_none());
+    }
+
+    #[test]
+    fn test_deferred_diagnostics_released_after_last_token() {
+        let mut tracker = ProgressTracker::new();
+        for token in [1, 2] {
+            tracker.apply(
+                "rust",
+                params(json!({"token": token, "value": {"kind": "begin", "title": "Indexing"}})),
+            );
+        }
+        tracker.defer_diagnostics("rust", "file:///src/main.rs");
+
+        let released = tracker.apply(
+            "rust",
+            params(json!({"token": 1, "value": {"kind": "end"}})),
+        );
+        assert!(released.is_empty());
+
+        let released = tracker.apply(
+            "rust",
+            params(json!({"token": 2, "value": {"kind": "end"}})),
+        );
+        assert_eq!(released, vec!["file:///src/main.rs".to_string()]);
+    }
+
+    #[test]
+    fn test_status_text_multiple_languages() {
+        let mut tracker = ProgressTracker::new();
+        tracker.apply(
+            "rust",
+            params(json!({"token": "a", "value": {"kind": "begin", "title": "Indexing"}})),
+        );
+        tracker.apply(
+            "python",
+            params(json!({"token": "b", "value": {"kind": "begin", "title": "Analyzing"}})),
+        );
+
+        assert_eq!(
+            tracker.status_text().unwrap(),
+            "python: Analyzing (+1 more)"
+        );
+
+        tracker.clear_language("python");
+        assert_eq!(tracker.status_text().unwrap(), "rust: Indexing");
+    }
+}
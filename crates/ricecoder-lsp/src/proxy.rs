@@ -21,13 +21,25 @@
 //! - If external LSP is configured for the language → forward to external LSP
 //! - If external LSP is unavailable → fall back to internal provider
 //! - If no external LSP configured → use internal provider
+//!
+//! # Pull Diagnostics and Progress
+//!
+//! LSP 3.17 pull diagnostics (`textDocument/diagnostic`) are routed through
+//! [`LspProxy::route_pull_diagnostics`]. While a server reports work-done
+//! progress (e.g. rust-analyzer indexing), pull requests for its language are
+//! deferred and the affected URIs are returned from [`LspProxy::handle_progress`]
+//! once the last progress token ends, so diagnostics are never reported from a
+//! half-indexed workspace.
 
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use serde_json::Value;
 use tracing::{debug, info, warn};
 
-use crate::types::{LspError, LspResult};
+use crate::{
+    progress::{ProgressParams, ProgressToken, ProgressTracker},
+    types::{LspError, LspResult},
+};
 
 /// LSP Proxy for routing requests to external LSP servers
 ///
@@ -37,6 +49,8 @@ pub struct LspProxy {
     external_lsp: Option<Arc<dyn ExternalLspClient>>,
     /// Enable fallback to internal providers
     enable_fallback: bool,
+    /// Work-done progress reported by external servers
+    progress: Mutex<ProgressTracker>,
 }
 
 /// Trait for external LSP client
@@ -53,6 +67,19 @@ pub trait ExternalLspClient: Send + Sync {
     /// Forward diagnostics request to external LSP
     fn forward_diagnostics(&self, language: &str, uri: &str) -> LspResult<Option<Value>>;
 
+    /// Forward pull diagnostics request (`textDocument/diagnostic`) to external LSP
+    ///
+    /// Returns a `DocumentDiagnosticReport`. Servers without pull diagnostics
+    /// support return `Ok(None)`, which is the default.
+    fn forward_pull_diagnostics(
+        &self,
+        _language: &str,
+        _uri: &str,
+        _previous_result_id: Option<&str>,
+    ) -> LspResult<Option<Value>> {
+        Ok(None)
+    }
+
     /// Forward hover request to external LSP
     fn forward_hover(&self, language: &str, uri: &str, position: Value)
         -> LspResult<Option<Value>>;
@@ -83,6 +110,7 @@ impl LspProxy {
         Self {
             external_lsp: None,
             enable_fallback: true,
+            progress: Mutex::new(ProgressTracker::new()),
         }
     }
 
@@ -94,6 +122,7 @@ impl LspProxy {
         Self {
             external_lsp: Some(external_lsp),
             enable_fallback,
+            progress: Mutex::new(ProgressTracker::new()),
         }
     }
 
@@ -384,6 +413,122 @@ impl LspProxy {
         }
     }
 
+    /// Route pull diagnostics request (`textDocument/diagnostic`)
+    ///
+    /// # Arguments
+    ///
+    /// * `language` - Programming language
+    /// * `uri` - Document URI
+    /// * `previous_result_id` - Result ID of the last report for this document
+    /// * `fallback_fn` - Fallback function for internal provider
+    ///
+    /// # Returns
+    ///
+    /// A `DocumentDiagnosticReport`, or `None` if the request was deferred because
+    /// the external server is still indexing. Deferred URIs are returned from
+    /// [`LspProxy::handle_progress`] when indexing completes.
+    pub fn route_pull_diagnostics<F>(
+        &self,
+        language: &str,
+        uri: &str,
+        previous_result_id: Option<&str>,
+        fallback_fn: F,
+    ) -> LspResult<Option<Value>>
+    where
+        F: FnOnce() -> LspResult<Value>,
+    {
+        if let Some(external_lsp) = &self.external_lsp {
+            if external_lsp.is_available(language) {
+                {
+                    let mut progress = self.lock_progress()?;
+                    if progress.is_busy(language) {
+                        debug!(
+                            "Deferring pull diagnostics for {} until {} server finishes indexing",
+                            uri, language
+                        );
+                        progress.defer_diagnostics(language, uri);
+                        return Ok(None);
+                    }
+                }
+
+                debug!(
+                    "Routing pull diagnostics to external LSP for language: {}",
+                    language
+                );
+                match external_lsp.forward_pull_diagnostics(language, uri, previous_result_id) {
+                    Ok(Some(result)) => {
+                        info!("Received pull diagnostics from external LSP");
+                        return Ok(Some(result));
+                    }
+                    Ok(None) => {
+                        debug!("External LSP does not support pull diagnostics");
+                    }
+                    Err(e) => {
+                        warn!("External LSP pull diagnostics failed: {}", e);
+                        if !self.enable_fallback {
+                            return Err(e);
+                        }
+                    }
+                }
+            }
+        }
+
+        // Fall back to internal provider, wrapped as a full report
+        if self.enable_fallback {
+            debug!("Falling back to internal diagnostics provider");
+            let items = fallback_fn()?;
+            Ok(Some(serde_json::json!({
+                "kind": "full",
+                "items": items,
+            })))
+        } else {
+            Err(LspError::InternalError(
+                "External LSP unavailable and fallback disabled".to_string(),
+            ))
+        }
+    }
+
+    /// Handle a `window/workDoneProgress/create` request from an external server
+    pub fn handle_progress_create(&self, language: &str, token: Value) -> LspResult<()> {
+        let token: ProgressToken = serde_json::from_value(token)
+            .map_err(|e| LspError::InvalidParams(format!("Invalid progress token: {}", e)))?;
+        self.lock_progress()?.create_token(language, token);
+        Ok(())
+    }
+
+    /// Handle a `$/progress` notification from an external server
+    ///
+    /// # Returns
+    ///
+    /// Document URIs whose pull diagnostics were deferred and should be
+    /// re-requested now that the server finished its work.
+    pub fn handle_progress(&self, language: &str, params: &Value) -> LspResult<Vec<String>> {
+        let params = ProgressParams::from_value(params)?;
+        Ok(self.lock_progress()?.apply(language, params))
+    }
+
+    /// Check if the external server for a language reports work in progress
+    pub fn is_indexing(&self, language: &str) -> bool {
+        self.progress
+            .lock()
+            .map(|progress| progress.is_busy(language))
+            .unwrap_or(false)
+    }
+
+    /// Status bar text describing external server progress, if any
+    pub fn progress_status(&self) -> Option<String> {
+        self.progress
+            .lock()
+            .ok()
+            .and_then(|progress| progress.status_text())
+    }
+
+    fn lock_progress(&self) -> LspResult<std::sync::MutexGuard<'_, ProgressTracker>> {
+        self.progress
+            .lock()
+            .map_err(|_| LspError::InternalError("Progress tracker lock poisoned".to_string()))
+    }
+
     /// Check if external LSP is available for language
     pub fn is_external_lsp_available(&self, language: &str) -> bool {
         self.external_lsp
@@ -418,4 +563,117 @@ mod tests {
             });
         assert!(result.is_ok());
     }
+
+    struct PullClient;
+
+    impl ExternalLspClient for PullClient {
+        fn forward_completion(
+            &self,
+            _language: &str,
+            _uri: &str,
+            _position: Value,
+            _context: Value,
+        ) -> LspResult<Option<Value>> {
+            Ok(None)
+        }
+
+        fn forward_diagnostics(&self, _language: &str, _uri: &str) -> LspResult<Option<Value>> {
+            Ok(None)
+        }
+
+        fn forward_pull_diagnostics(
+            &self,
+            _language: &str,
+            _uri: &str,
+            previous_result_id: Option<&str>,
+        ) -> LspResult<Option<Value>> {
+            Ok(Some(serde_json::json!({
+                "kind": "unchanged",
+                "resultId": previous_result_id,
+            })))
+        }
+
+        fn forward_hover(
+            &self,
+            _language: &str,
+            _uri: &str,
+            _position: Value,
+        ) -> LspResult<Option<Value>> {
+            Ok(None)
+        }
+
+        fn forward_definition(
+            &self,
+            _language: &str,
+            _uri: &str,
+            _position: Value,
+        ) -> LspResult<Option<Value>> {
+            Ok(None)
+        }
+
+        fn forward_references(
+            &self,
+            _language: &str,
+            _uri: &str,
+            _position: Value,
+        ) -> LspResult<Option<Value>> {
+            Ok(None)
+        }
+
+        fn is_available(&self, language: &str) -> bool {
+            language == "rust"
+        }
+    }
+
+    #[test]
+    fn test_pull_diagnostics_fallback_wraps_full_report() {
+        let proxy = LspProxy::new();
+        let report = proxy
+            .route_pull_diagnostics("rust", "file:///test.rs", None, || Ok(Value::Array(vec![])))
+            .unwrap()
+            .unwrap();
+        assert_eq!(report["kind"], "full");
+        assert!(report["items"].is_array());
+    }
+
+    #[test]
+    fn test_pull_diagnostics_deferred_while_indexing() {
+        let proxy = LspProxy::with_external_lsp(Arc::new(PullClient), true);
+        proxy
+            .handle_progress_create("rust", serde_json::json!("indexing"))
+            .unwrap();
+        proxy
+            .handle_progress(
+                "rust",
+                &serde_json::json!({
+                    "token": "indexing",
+                    "value": {"kind": "begin", "title": "Indexing"}
+                }),
+            )
+            .unwrap();
+        assert!(proxy.is_indexing("rust"));
+        assert_eq!(proxy.progress_status().unwrap(), "rust: Indexing");
+
+        let deferred = proxy
+            .route_pull_diagnostics("rust", "file:///main.rs", None, || Ok(Value::Array(vec![])))
+            .unwrap();
+        assert!(deferred.is_none());
+
+        let released = proxy
+            .handle_progress(
+                "rust",
+                &serde_json::json!({"token": "indexing", "value": {"kind": "end"}}),
+            )
+            .unwrap();
+        assert_eq!(released, vec!["file:///main.rs".to_string()]);
+
+        let report = proxy
+            .route_pull_diagnostics("rust", "file:///main.rs", Some("7"), || {
+                Ok(Value::Array(vec![]))
+            })
+            .unwrap()
+            .unwrap();
+        assert_eq!(report["kind"], "unchanged");
+        assert_eq!(report["resultId"], "7");
+    }
 }
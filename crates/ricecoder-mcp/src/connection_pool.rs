@@ -1,11 +1,29 @@
 //! Connection pool for managing MCP server connections
-
-use std::{collections::VecDeque, sync::Arc};
-
-use tokio::sync::RwLock;
+//!
+//! Besides pooled connection bookkeeping, the pool routes requests to server
+//! transports. Each attached server gets one reader task that hands responses
+//! back to their callers by request ID, so many requests can share a single
+//! transport, bounded by a per-server in-flight limit.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use tokio::{
+    sync::{oneshot, RwLock, Semaphore},
+    task::JoinHandle,
+};
 use tracing::{debug, info, warn};
 
-use crate::error::{Error, Result};
+use crate::{
+    error::{Error, Result},
+    transport::{MCPMessage, MCPRequest, MCPTransport},
+};
 
 /// Represents a pooled connection to an MCP server
 #[derive(Debug, Clone)]
@@ -50,6 +68,8 @@ pub struct PoolConfig {
     pub max_connections: usize,
     pub connection_timeout_ms: u64,
     pub idle_timeout_ms: u64,
+    /// Requests a server may have outstanding at once unless overridden on attach
+    pub max_in_flight_per_server: usize,
 }
 
 impl Default for PoolConfig {
@@ -59,8 +79,126 @@ impl Default for PoolConfig {
             max_connections: 10,
             connection_timeout_ms: 2000,
             idle_timeout_ms: 30000,
+            max_in_flight_per_server: 8,
+        }
+    }
+}
+
+type PendingResponses = Arc<Mutex<HashMap<String, oneshot::Sender<MCPMessage>>>>;
+
+/// Request routing state for one server transport
+#[derive(Debug)]
+struct ServerRoute {
+    transport: Arc<dyn MCPTransport>,
+    permits: Arc<Semaphore>,
+    limit: usize,
+    /// Transports write one message at a time
+    send_lock: tokio::sync::Mutex<()>,
+    pending: PendingResponses,
+    reader: JoinHandle<()>,
+}
+
+impl ServerRoute {
+    fn new(server_id: &str, transport: Arc<dyn MCPTransport>, limit: usize) -> Self {
+        let pending: PendingResponses = Arc::new(Mutex::new(HashMap::new()));
+        let reader = tokio::spawn(route_responses(
+            server_id.to_string(),
+            transport.clone(),
+            pending.clone(),
+        ));
+
+        Self {
+            transport,
+            permits: Arc::new(Semaphore::new(limit)),
+            limit,
+            send_lock: tokio::sync::Mutex::new(()),
+            pending,
+            reader,
+        }
+    }
+
+    fn in_flight(&self) -> usize {
+        self.limit - self.permits.available_permits()
+    }
+
+    fn forget(&self, id: &str) {
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.remove(id);
+        }
+    }
+
+    /// Stop reading and fail every request still waiting for a response
+    fn close(&self) {
+        self.reader.abort();
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.clear();
+        }
+    }
+}
+
+impl Drop for ServerRoute {
+    fn drop(&mut self) {
+        self.reader.abort();
+    }
+}
+
+/// Read messages from a transport and hand each response to its caller
+///
+/// Stops when the transport fails; dropping the pending senders makes every
+/// waiting caller fail instead of hanging.
+async fn route_responses(
+    server_id: String,
+    transport: Arc<dyn MCPTransport>,
+    pending: PendingResponses,
+) {
+    loop {
+        let message = match transport.receive().await {
+            Ok(message) => message,
+            Err(Error::SerializationError(e)) => {
+                warn!(
+                    "Skipping malformed message from server {}: {}",
+                    server_id, e
+                );
+                continue;
+            }
+            Err(e) => {
+                warn!("Stopped routing responses for server {}: {}", server_id, e);
+                break;
+            }
+        };
+
+        let id = match &message {
+            MCPMessage::Response(response) => Some(response.id.clone()),
+            MCPMessage::Error(error) => error.id.clone(),
+            _ => None,
+        };
+        let waiter = id.and_then(|id| pending.lock().ok()?.remove(&id));
+        match waiter {
+            Some(waiter) => {
+                let _ = waiter.send(message);
+            }
+            None => debug!("Dropping unrouted message from server {}", server_id),
         }
     }
+
+    if let Ok(mut pending) = pending.lock() {
+        pending.clear();
+    }
+}
+
+/// Put the caller's request ID back on a routed response
+fn restore_id(message: MCPMessage, id: String) -> MCPMessage {
+    match message {
+        MCPMessage::Response(mut response) => {
+            response.id = id;
+            MCPMessage::Response(response)
+        }
+        MCPMessage::Error(mut error) => {
+            error.id = Some(id);
+            MCPMessage::Error(error)
+        }
+        other => other,
+    }
 }
 
 /// Connection pool for managing MCP server connections
@@ -70,6 +208,8 @@ pub struct ConnectionPool {
     available: Arc<RwLock<VecDeque<PooledConnection>>>,
     in_use: Arc<RwLock<Vec<PooledConnection>>>,
     connection_counter: Arc<RwLock<u64>>,
+    routes: Arc<RwLock<HashMap<String, Arc<ServerRoute>>>>,
+    request_counter: Arc<AtomicU64>,
 }
 
 impl ConnectionPool {
@@ -85,6 +225,8 @@ impl ConnectionPool {
             available: Arc::new(RwLock::new(VecDeque::new())),
             in_use: Arc::new(RwLock::new(Vec::new())),
             connection_counter: Arc::new(RwLock::new(0)),
+            routes: Arc::new(RwLock::new(HashMap::new())),
+            request_counter: Arc::new(AtomicU64::new(0)),
         }
     }
 
@@ -214,16 +356,146 @@ impl ConnectionPool {
         removed
     }
 
+    /// Routes requests for a server over a transport
+    ///
+    /// Replaces any transport previously attached for the server. Requests
+    /// beyond `max_in_flight` (or the pool default) wait for a free slot.
+    pub async fn attach(
+        &self,
+        server_id: &str,
+        transport: Arc<dyn MCPTransport>,
+        max_in_flight: Option<usize>,
+    ) {
+        let limit = max_in_flight
+            .unwrap_or(self.config.max_in_flight_per_server)
+            .max(1);
+        let route = Arc::new(ServerRoute::new(server_id, transport, limit));
+
+        let mut routes = self.routes.write().await;
+        if let Some(previous) = routes.insert(server_id.to_string(), route) {
+            previous.close();
+        }
+        info!(
+            "Routing requests for server {} (max {} in flight)",
+            server_id, limit
+        );
+    }
+
+    /// Stops routing requests for a server
+    ///
+    /// Requests still waiting for a response fail with
+    /// [`Error::ServerDisconnected`].
+    pub async fn detach(&self, server_id: &str) -> bool {
+        let mut routes = self.routes.write().await;
+        match routes.remove(server_id) {
+            Some(route) => {
+                route.close();
+                debug!("Stopped routing requests for server {}", server_id);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Checks whether requests for a server are being routed
+    pub async fn is_attached(&self, server_id: &str) -> bool {
+        self.routes.read().await.contains_key(server_id)
+    }
+
+    /// Number of requests a server has not answered yet
+    pub async fn in_flight(&self, server_id: &str) -> usize {
+        let routes = self.routes.read().await;
+        routes.get(server_id).map(|r| r.in_flight()).unwrap_or(0)
+    }
+
+    /// Sends a request to a server and waits for its response
+    ///
+    /// The request is sent under a pool-unique ID so concurrent requests on
+    /// the same transport cannot be confused; the response carries the
+    /// caller's ID again. `timeout_ms` covers waiting for an in-flight slot
+    /// as well as the response.
+    ///
+    /// # Errors
+    /// Returns error if the server is not attached, the transport fails or
+    /// no response arrives in time
+    pub async fn request(
+        &self,
+        server_id: &str,
+        request: MCPRequest,
+        timeout_ms: u64,
+    ) -> Result<MCPMessage> {
+        let route = {
+            let routes = self.routes.read().await;
+            routes
+                .get(server_id)
+                .cloned()
+                .ok_or_else(|| Error::ServerNotFound(server_id.to_string()))?
+        };
+
+        let caller_id = request.id.clone();
+        let routed_id = format!(
+            "{}-{}",
+            server_id,
+            self.request_counter.fetch_add(1, Ordering::Relaxed)
+        );
+        let message = MCPMessage::Request(MCPRequest {
+            id: routed_id.clone(),
+            ..request
+        });
+
+        let exchange = async {
+            let _permit = route
+                .permits
+                .acquire()
+                .await
+                .map_err(|_| Error::ServerDisconnected(server_id.to_string()))?;
+            if route.reader.is_finished() {
+                return Err(Error::ServerDisconnected(server_id.to_string()));
+            }
+
+            let (sender, receiver) = oneshot::channel();
+            route
+                .pending
+                .lock()
+                .map_err(|e| Error::InternalError(e.to_string()))?
+                .insert(routed_id.clone(), sender);
+
+            let sent = {
+                let _send = route.send_lock.lock().await;
+                route.transport.send(&message).await
+            };
+            if let Err(e) = sent {
+                route.forget(&routed_id);
+                return Err(e);
+            }
+
+            receiver
+                .await
+                .map_err(|_| Error::ServerDisconnected(server_id.to_string()))
+        };
+
+        match tokio::time::timeout(Duration::from_millis(timeout_ms), exchange).await {
+            Ok(response) => response.map(|message| restore_id(message, caller_id)),
+            Err(_) => {
+                route.forget(&routed_id);
+                Err(Error::TimeoutError(timeout_ms))
+            }
+        }
+    }
+
     /// Gets the current pool statistics
     pub async fn get_stats(&self) -> PoolStats {
         let available = self.available.read().await;
         let in_use = self.in_use.read().await;
+        let routes = self.routes.read().await;
 
         PoolStats {
             available_connections: available.len(),
             in_use_connections: in_use.len(),
             total_connections: available.len() + in_use.len(),
             max_connections: self.config.max_connections,
+            routed_servers: routes.len(),
+            in_flight_requests: routes.values().map(|r| r.in_flight()).sum(),
         }
     }
 
@@ -237,6 +509,12 @@ impl ConnectionPool {
         let mut in_use = self.in_use.write().await;
         in_use.clear();
 
+        let mut routes = self.routes.write().await;
+        for route in routes.values() {
+            route.close();
+        }
+        routes.clear();
+
         info!("Connection pool cleared");
     }
 }
@@ -254,12 +532,74 @@ pub struct PoolStats {
     pub in_use_connections: usize,
     pub total_connections: usize,
     pub max_connections: usize,
+    pub routed_servers: usize,
+    pub in_flight_requests: usize,
 }
 
 #[cfg(test)]
 mod tests {
+    use async_trait::async_trait;
+    use tokio::sync::mpsc;
+
     use super::*;
 
+    /// Answers each request with its own params, or never answers
+    #[derive(Debug)]
+    struct EchoTransport {
+        sent: mpsc::UnboundedSender<MCPRequest>,
+        received: tokio::sync::Mutex<mpsc::UnboundedReceiver<MCPRequest>>,
+        silent: bool,
+    }
+
+    impl EchoTransport {
+        fn new(silent: bool) -> Arc<Self> {
+            let (sent, received) = mpsc::unbounded_channel();
+            Arc::new(Self {
+                sent,
+                received: tokio::sync::Mutex::new(received),
+                silent,
+            })
+        }
+    }
+
+    #[async_trait]
+    impl MCPTransport for EchoTransport {
+        async fn send(&self, message: &MCPMessage) -> Result<()> {
+            if let MCPMessage::Request(request) = message {
+                let _ = self.sent.send(request.clone());
+            }
+            Ok(())
+        }
+
+        async fn receive(&self) -> Result<MCPMessage> {
+            if self.silent {
+                std::future::pending::<()>().await;
+            }
+            let request = self.received.lock().await.recv().await;
+            let request = request.ok_or_else(|| Error::ConnectionError("closed".to_string()))?;
+            Ok(MCPMessage::Response(crate::transport::MCPResponse {
+                id: request.id,
+                result: request.params,
+            }))
+        }
+
+        async fn is_connected(&self) -> bool {
+            true
+        }
+
+        async fn close(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn request(params: serde_json::Value) -> MCPRequest {
+        MCPRequest {
+            id: "call".to_string(),
+            method: "tools/call".to_string(),
+            params,
+        }
+    }
+
     #[tokio::test]
     async fn test_create_pool() {
         let pool = ConnectionPool::new();
@@ -307,6 +647,7 @@ mod tests {
             max_connections: 2,
             connection_timeout_ms: 2000,
             idle_timeout_ms: 30000,
+            max_in_flight_per_server: 8,
         };
         let pool = ConnectionPool::with_config(config);
 
@@ -360,4 +701,56 @@ mod tests {
         let stats = pool.get_stats().await;
         assert_eq!(stats.total_connections, 0);
     }
+
+    #[tokio::test]
+    async fn test_multiplexed_requests() {
+        let pool = ConnectionPool::new();
+        pool.attach("server1", EchoTransport::new(false), Some(2))
+            .await;
+
+        let calls = (0..5).map(|n| pool.request("server1", request(serde_json::json!(n)), 1000));
+        let responses = futures::future::join_all(calls).await;
+
+        for (n, response) in responses.into_iter().enumerate() {
+            match response.unwrap() {
+                MCPMessage::Response(response) => {
+                    assert_eq!(response.id, "call");
+                    assert_eq!(response.result, serde_json::json!(n));
+                }
+                other => panic!("unexpected message: {:?}", other),
+            }
+        }
+        assert_eq!(pool.in_flight("server1").await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_in_flight_limit() {
+        let pool = ConnectionPool::new();
+        pool.attach("server1", EchoTransport::new(true), Some(1))
+            .await;
+
+        let waiting = {
+            let pool = pool.clone();
+            tokio::spawn(async move {
+                pool.request("server1", request(serde_json::json!(1)), 5000)
+                    .await
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(pool.in_flight("server1").await, 1);
+
+        // The only slot is taken, so this request times out waiting for it
+        let queued = pool
+            .request("server1", request(serde_json::json!(2)), 50)
+            .await;
+        assert!(matches!(queued, Err(Error::TimeoutError(50))));
+
+        assert!(pool.detach("server1").await);
+        let waiting = waiting.await.unwrap();
+        assert!(matches!(waiting, Err(Error::ServerDisconnected(_))));
+        assert!(pool
+            .request("server1", request(serde_json::json!(3)), 50)
+            .await
+            .is_err());
+    }
 }
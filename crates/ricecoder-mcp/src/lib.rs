@@ -28,6 +28,7 @@ pub mod permissions_integration;
 pub mod protocol_validation;
 pub mod rbac;
 pub mod registry;
+pub mod schema_drift;
 pub mod server_management;
 pub mod storage_integration;
 pub mod tool_execution;
@@ -65,12 +66,14 @@ pub use marshaler::ToolMarshaler;
 pub use metadata::{ParameterMetadata, ToolMetadata, ToolSource};
 pub use permissions::{MCPPermissionManager, PermissionLevelConfig, PermissionRule};
 pub use permissions_integration::{
-    PermissionAwareToolExecution, ToolPermissionChecker, ToolPermissionDecision,
-    ToolPermissionEnforcer, ToolPermissionLevel, ToolPermissionPrompt, UserPermissionDecision,
+    PermissionAwareToolExecution, ToolApprovalGate, ToolApprovalPrompter, ToolPermissionChecker,
+    ToolPermissionDecision, ToolPermissionEnforcer, ToolPermissionLevel, ToolPermissionPrompt,
+    UserPermissionDecision,
 };
 pub use protocol_validation::{MCPComplianceChecker, MCPErrorHandler, MCPProtocolValidator};
 pub use rbac::{MCPAuthorizationMiddleware, MCRBACManager};
 pub use registry::ToolRegistry;
+pub use schema_drift::{ParameterShims, SchemaChange, SchemaDrift, ToolSchemaSnapshot};
 pub use server_management::{
     AuthConfig, AuthType, DiscoveryResult, FileSystemDiscoveryProvider, ServerConfig, ServerHealth,
     ServerManager, ServerRegistration, ServerState,
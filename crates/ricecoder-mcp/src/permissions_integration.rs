@@ -3,11 +3,19 @@
 //! This module provides integration between the MCP tool system and the ricecoder-permissions
 //! framework, enabling permission checking and enforcement for tool execution within agent workflows.
 
-use std::{collections::HashMap, sync::Arc};
-
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use ricecoder_permissions::{
+    prompt::RememberedApproval, ApprovalChoice, ApprovalRequest, AuditAction, AuditLogEntry,
+    AuditLogger, AuditResult, PermissionRepository, RiskLevel, ToolApprovals,
+};
 use serde_json::Value;
 
-use crate::error::Result;
+use crate::error::{Error, Result};
 
 /// Permission level for tool execution
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -272,17 +280,257 @@ impl ToolPermissionEnforcer {
     }
 }
 
+/// Asks the user to approve a tool call
+#[async_trait]
+pub trait ToolApprovalPrompter: Send + Sync {
+    /// Show the approval prompt and return the user's choice
+    async fn prompt(&self, request: &ApprovalRequest) -> Result<ApprovalChoice>;
+}
+
+/// Per-session approval of tools at the `ask` level
+///
+/// The first call of an `ask` tool in a session shows a structured prompt.
+/// "Allow for session" answers are kept until the session ends, "always allow"
+/// answers are persisted for the project, and every prompt and answer is
+/// written to the audit log.
+pub struct ToolApprovalGate {
+    enforcer: Arc<ToolPermissionEnforcer>,
+    prompter: Arc<dyn ToolApprovalPrompter>,
+    approvals: Mutex<ToolApprovals>,
+    audit: AuditLogger,
+    repository: Option<Arc<dyn PermissionRepository>>,
+    descriptions: HashMap<String, String>,
+    risk_overrides: HashMap<String, RiskLevel>,
+}
+
+impl ToolApprovalGate {
+    /// Creates a gate with in-memory approvals
+    pub fn new(
+        enforcer: Arc<ToolPermissionEnforcer>,
+        prompter: Arc<dyn ToolApprovalPrompter>,
+    ) -> Self {
+        Self {
+            enforcer,
+            prompter,
+            approvals: Mutex::new(ToolApprovals::new()),
+            audit: AuditLogger::new(),
+            repository: None,
+            descriptions: HashMap::new(),
+            risk_overrides: HashMap::new(),
+        }
+    }
+
+    /// Uses remembered approvals, e.g. [`ToolApprovals::for_project`]
+    pub fn with_approvals(mut self, approvals: ToolApprovals) -> Self {
+        self.approvals = Mutex::new(approvals);
+        self
+    }
+
+    /// Also appends audit entries to a permission repository
+    pub fn with_repository(mut self, repository: Arc<dyn PermissionRepository>) -> Self {
+        self.repository = Some(repository);
+        self
+    }
+
+    /// Sets the description shown when prompting for a tool
+    pub fn with_description(mut self, tool_id: &str, description: &str) -> Self {
+        self.descriptions
+            .insert(tool_id.to_string(), description.to_string());
+        self
+    }
+
+    /// Overrides the risk level assessed from the tool name
+    pub fn with_risk(mut self, tool_id: &str, risk: RiskLevel) -> Self {
+        self.risk_overrides.insert(tool_id.to_string(), risk);
+        self
+    }
+
+    /// Gets the audit log of prompts and decisions
+    pub fn audit(&self) -> &AuditLogger {
+        &self.audit
+    }
+
+    /// Authorizes a tool call, prompting on its first use in the session
+    ///
+    /// Returns the decision for allowed calls and `PermissionDenied` otherwise.
+    pub async fn authorize(
+        &self,
+        session_id: &str,
+        tool_id: &str,
+        agent_id: Option<&str>,
+        parameters: &HashMap<String, Value>,
+    ) -> Result<ToolPermissionDecision> {
+        let decision = self.enforcer.get_decision(tool_id, agent_id)?;
+        match decision.level {
+            ToolPermissionLevel::Allow => return Ok(decision),
+            ToolPermissionLevel::Deny => {
+                self.enforcer
+                    .log_denial(tool_id, agent_id, "Permission denied");
+                self.record(
+                    AuditLogEntry::new(
+                        tool_id.to_string(),
+                        AuditAction::Denied,
+                        AuditResult::Blocked,
+                    ),
+                    agent_id,
+                    format!("session={}", session_id),
+                )?;
+                return Err(Error::PermissionDenied(tool_id.to_string()));
+            }
+            ToolPermissionLevel::Ask => {}
+        }
+
+        let remembered = self.lock_approvals()?.remembered(session_id, tool_id);
+        if let Some(remembered) = remembered {
+            let scope = match remembered {
+                RememberedApproval::Session => "session",
+                RememberedApproval::Project => "project",
+            };
+            return Ok(self.allowed(tool_id, agent_id, format!("Remembered {} approval", scope)));
+        }
+
+        let mut request = ApprovalRequest::new(session_id, tool_id, parameters);
+        if let Some(description) = self.descriptions.get(tool_id) {
+            request = request.with_description(description.as_str());
+        }
+        if let Some(risk) = self.risk_overrides.get(tool_id) {
+            request = request.with_risk(*risk);
+        }
+        if let Some(agent_id) = agent_id {
+            request = request.with_agent(agent_id);
+        }
+        self.record(
+            AuditLogEntry::new(
+                tool_id.to_string(),
+                AuditAction::Prompted,
+                AuditResult::Success,
+            ),
+            agent_id,
+            format!(
+                "session={} risk={} args={}",
+                session_id, request.risk, request.arguments_summary
+            ),
+        )?;
+
+        let choice = self.prompter.prompt(&request).await?;
+        self.lock_approvals()?
+            .apply(session_id, tool_id, choice)
+            .map_err(|e| Error::StorageError(e.to_string()))?;
+
+        let (action, result) = if choice.is_allowed() {
+            (AuditAction::Approved, AuditResult::Success)
+        } else {
+            (AuditAction::Rejected, AuditResult::Blocked)
+        };
+        self.record(
+            AuditLogEntry::new(tool_id.to_string(), action, result),
+            agent_id,
+            format!("session={} choice={}", session_id, choice.as_str()),
+        )?;
+
+        if !choice.is_allowed() {
+            self.enforcer
+                .log_denial(tool_id, agent_id, "Denied by user");
+            return Err(Error::PermissionDenied(tool_id.to_string()));
+        }
+        Ok(self.allowed(
+            tool_id,
+            agent_id,
+            format!("Approved by user ({})", choice.as_str()),
+        ))
+    }
+
+    /// Forgets the session-wide approvals of a finished session
+    pub fn end_session(&self, session_id: &str) -> Result<()> {
+        self.lock_approvals()?.end_session(session_id);
+        Ok(())
+    }
+
+    /// Stops always allowing a tool in this project
+    pub fn revoke(&self, tool_id: &str) -> Result<bool> {
+        self.lock_approvals()?
+            .revoke(tool_id)
+            .map_err(|e| Error::StorageError(e.to_string()))
+    }
+
+    fn allowed(
+        &self,
+        tool_id: &str,
+        agent_id: Option<&str>,
+        reason: String,
+    ) -> ToolPermissionDecision {
+        let decision =
+            ToolPermissionDecision::new(tool_id.to_string(), ToolPermissionLevel::Allow, reason);
+        let decision = match agent_id {
+            Some(agent_id) => decision.with_agent(agent_id.to_string()),
+            None => decision,
+        };
+        self.enforcer.log_decision(&decision);
+        decision
+    }
+
+    fn lock_approvals(&self) -> Result<std::sync::MutexGuard<'_, ToolApprovals>> {
+        self.approvals
+            .lock()
+            .map_err(|e| Error::InternalError(format!("Failed to lock tool approvals: {}", e)))
+    }
+
+    fn record(&self, entry: AuditLogEntry, agent_id: Option<&str>, context: String) -> Result<()> {
+        let mut entry = entry.with_context(context);
+        entry.agent = agent_id.map(str::to_string);
+        if let Some(repository) = &self.repository {
+            repository
+                .append_audit_log(&entry)
+                .map_err(|e| Error::StorageError(e.to_string()))?;
+        }
+        self.audit.log_entry(entry).map_err(Error::InternalError)
+    }
+}
+
 /// Permission-aware tool execution wrapper
 ///
 /// This struct wraps tool execution with permission checking.
 pub struct PermissionAwareToolExecution {
     enforcer: Arc<ToolPermissionEnforcer>,
+    approvals: Option<Arc<ToolApprovalGate>>,
 }
 
 impl PermissionAwareToolExecution {
     /// Creates a new permission-aware tool execution wrapper
     pub fn new(enforcer: Arc<ToolPermissionEnforcer>) -> Self {
-        Self { enforcer }
+        Self {
+            enforcer,
+            approvals: None,
+        }
+    }
+
+    /// Prompts for `ask` tools through an approval gate in session executions
+    pub fn with_approval_gate(mut self, gate: Arc<ToolApprovalGate>) -> Self {
+        self.approvals = Some(gate);
+        self
+    }
+
+    /// Checks permission for a tool call in a session, prompting when needed,
+    /// then executes it
+    pub async fn check_and_execute_in_session<F, T>(
+        &self,
+        session_id: &str,
+        tool_id: &str,
+        agent_id: Option<&str>,
+        parameters: &HashMap<String, Value>,
+        execute_fn: F,
+    ) -> Result<T>
+    where
+        F: FnOnce() -> Result<T>,
+    {
+        match &self.approvals {
+            Some(gate) => {
+                gate.authorize(session_id, tool_id, agent_id, parameters)
+                    .await?;
+                execute_fn()
+            }
+            None => self.check_and_execute(tool_id, agent_id, execute_fn).await,
+        }
     }
 
     /// Checks if a tool can be executed and returns the decision
@@ -355,6 +603,33 @@ mod tests {
         }
     }
 
+    struct ScriptedPrompter {
+        choices: Mutex<Vec<ApprovalChoice>>,
+        prompts: Mutex<Vec<ApprovalRequest>>,
+    }
+
+    impl ScriptedPrompter {
+        fn new(choices: Vec<ApprovalChoice>) -> Self {
+            Self {
+                choices: Mutex::new(choices),
+                prompts: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl ToolApprovalPrompter for ScriptedPrompter {
+        async fn prompt(&self, request: &ApprovalRequest) -> Result<ApprovalChoice> {
+            self.prompts.lock().unwrap().push(request.clone());
+            Ok(self.choices.lock().unwrap().remove(0))
+        }
+    }
+
+    fn gate(prompter: Arc<ScriptedPrompter>) -> ToolApprovalGate {
+        let checker: Arc<dyn ToolPermissionChecker> = Arc::new(MockPermissionChecker);
+        ToolApprovalGate::new(Arc::new(ToolPermissionEnforcer::new(checker)), prompter)
+    }
+
     #[test]
     fn test_tool_permission_level_conversion() {
         assert_eq!(
@@ -508,4 +783,94 @@ mod tests {
 
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_approval_gate_prompts_once_per_session() {
+        let prompter = Arc::new(ScriptedPrompter::new(vec![
+            ApprovalChoice::AllowForSession,
+            ApprovalChoice::AllowOnce,
+        ]));
+        let gate = gate(prompter.clone()).with_description("ask-write-file", "Writes a file");
+        let mut params = HashMap::new();
+        params.insert("path".to_string(), serde_json::json!("notes.md"));
+
+        let decision = gate
+            .authorize("s1", "ask-write-file", Some("coder"), &params)
+            .await
+            .unwrap();
+        assert_eq!(decision.level, ToolPermissionLevel::Allow);
+        gate.authorize("s1", "ask-write-file", Some("coder"), &params)
+            .await
+            .unwrap();
+        // A new session asks again
+        gate.authorize("s2", "ask-write-file", None, &params)
+            .await
+            .unwrap();
+        // Allowed and denied tools never prompt
+        assert!(gate
+            .authorize("s1", "allowed-tool", None, &params)
+            .await
+            .is_ok());
+        assert!(gate
+            .authorize("s1", "denied-tool", None, &params)
+            .await
+            .is_err());
+
+        let prompts = prompter.prompts.lock().unwrap().clone();
+        assert_eq!(prompts.len(), 2);
+        assert_eq!(prompts[0].arguments_summary, "path=notes.md");
+        assert_eq!(prompts[0].risk, RiskLevel::Medium);
+        assert_eq!(prompts[0].description.as_deref(), Some("Writes a file"));
+
+        let actions: Vec<AuditAction> = gate
+            .audit()
+            .entries()
+            .unwrap()
+            .iter()
+            .map(|entry| entry.action)
+            .collect();
+        assert_eq!(
+            actions,
+            vec![
+                AuditAction::Prompted,
+                AuditAction::Approved,
+                AuditAction::Prompted,
+                AuditAction::Approved,
+                AuditAction::Denied,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_approval_gate_always_allow_persists_per_project() {
+        let dir = tempfile::tempdir().unwrap();
+        let prompter = Arc::new(ScriptedPrompter::new(vec![
+            ApprovalChoice::AlwaysAllow,
+            ApprovalChoice::Deny,
+        ]));
+        let gate =
+            gate(prompter.clone()).with_approvals(ToolApprovals::for_project(dir.path()).unwrap());
+        let params = HashMap::new();
+
+        gate.authorize("s1", "ask-run-tests", None, &params)
+            .await
+            .unwrap();
+
+        // A fresh gate for the same project remembers the choice
+        let reloaded = self::gate(prompter.clone())
+            .with_approvals(ToolApprovals::for_project(dir.path()).unwrap());
+        let decision = reloaded
+            .authorize("s9", "ask-run-tests", None, &params)
+            .await
+            .unwrap();
+        assert!(decision.reason.contains("project"));
+        assert_eq!(prompter.prompts.lock().unwrap().len(), 1);
+
+        assert!(reloaded.revoke("ask-run-tests").unwrap());
+        let denied = reloaded
+            .authorize("s9", "ask-run-tests", None, &params)
+            .await;
+        assert!(matches!(denied, Err(Error::PermissionDenied(_))));
+        assert_eq!(prompter.prompts.lock().unwrap()[1].risk, RiskLevel::High);
+    }
 }
@@ -1,13 +1,26 @@
 //! Tool Registry for managing available tools
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use crate::{error::Result, metadata::ToolMetadata};
+use serde_json::Value;
+use tracing::{info, warn};
+
+use crate::{
+    error::Result,
+    metadata::ToolMetadata,
+    schema_drift::{ParameterShims, SchemaChange, SchemaDrift, ToolSchemaSnapshot},
+};
 
 /// Tool Registry for managing all available tools
+///
+/// Each tool's input schema is snapshotted at registration so later changes
+/// reported by its server can be detected.
 #[derive(Debug, Clone)]
 pub struct ToolRegistry {
     tools: HashMap<String, ToolMetadata>,
+    schemas: HashMap<String, ToolSchemaSnapshot>,
+    shims: HashMap<String, ParameterShims>,
+    drift: Vec<SchemaDrift>,
 }
 
 impl ToolRegistry {
@@ -15,6 +28,9 @@ impl ToolRegistry {
     pub fn new() -> Self {
         Self {
             tools: HashMap::new(),
+            schemas: HashMap::new(),
+            shims: HashMap::new(),
+            drift: Vec::new(),
         }
     }
 
@@ -28,10 +44,138 @@ impl ToolRegistry {
             )));
         }
 
+        self.schemas
+            .entry(tool.id.clone())
+            .or_insert_with(|| ToolSchemaSnapshot::capture(&tool, 1));
         self.tools.insert(tool.id.clone(), tool);
         Ok(())
     }
 
+    /// Replaces the tools of a server with the ones it currently reports
+    ///
+    /// Called when a server (re)connects. Every tool whose schema differs
+    /// from its snapshot gets a new schema version, and removed tools are
+    /// dropped. The detected drift is returned and also kept until
+    /// [`take_schema_drift`](Self::take_schema_drift) is called; breaking
+    /// drift is logged as a warning.
+    pub fn sync_server_tools(
+        &mut self,
+        server_id: &str,
+        tools: Vec<ToolMetadata>,
+    ) -> Vec<SchemaDrift> {
+        let mut drift = Vec::new();
+        let mut reported = HashSet::new();
+
+        for mut tool in tools {
+            if let Some(existing) = self.tools.get(&tool.id) {
+                if existing.server_id.as_deref() != Some(server_id) {
+                    warn!(
+                        "Skipping tool '{}' from server {}: ID already used by another tool",
+                        tool.id, server_id
+                    );
+                    continue;
+                }
+            }
+            tool.server_id = Some(server_id.to_string());
+            reported.insert(tool.id.clone());
+
+            match self.schemas.get(&tool.id) {
+                Some(snapshot) => {
+                    let changes = snapshot.diff(&tool, self.shims.get(server_id));
+                    if !changes.is_empty() {
+                        let version = snapshot.version + 1;
+                        drift.push(SchemaDrift {
+                            tool_id: tool.id.clone(),
+                            server_id: Some(server_id.to_string()),
+                            from_version: snapshot.version,
+                            to_version: Some(version),
+                            changes,
+                        });
+                        self.schemas
+                            .insert(tool.id.clone(), ToolSchemaSnapshot::capture(&tool, version));
+                    }
+                }
+                None => {
+                    self.schemas
+                        .insert(tool.id.clone(), ToolSchemaSnapshot::capture(&tool, 1));
+                }
+            }
+            self.tools.insert(tool.id.clone(), tool);
+        }
+
+        let removed: Vec<String> = self
+            .tools
+            .values()
+            .filter(|t| t.server_id.as_deref() == Some(server_id) && !reported.contains(&t.id))
+            .map(|t| t.id.clone())
+            .collect();
+        for tool_id in removed {
+            self.tools.remove(&tool_id);
+            // The snapshot stays so a returning tool is compared against it
+            if let Some(snapshot) = self.schemas.get(&tool_id) {
+                drift.push(SchemaDrift {
+                    tool_id,
+                    server_id: Some(server_id.to_string()),
+                    from_version: snapshot.version,
+                    to_version: None,
+                    changes: vec![SchemaChange::ToolRemoved],
+                });
+            }
+        }
+
+        for entry in &drift {
+            if entry.is_breaking() {
+                warn!("{}", entry.warning_message());
+            } else {
+                info!("{}", entry.warning_message());
+            }
+        }
+        self.drift.extend(drift.iter().cloned());
+        drift
+    }
+
+    /// Gets the current schema snapshot of a tool
+    pub fn schema(&self, tool_id: &str) -> Option<&ToolSchemaSnapshot> {
+        self.schemas.get(tool_id)
+    }
+
+    /// Gets the current schema version of a tool
+    pub fn schema_version(&self, tool_id: &str) -> Option<u32> {
+        self.schemas.get(tool_id).map(|s| s.version)
+    }
+
+    /// Schema drift detected since it was last taken
+    pub fn pending_schema_drift(&self) -> &[SchemaDrift] {
+        &self.drift
+    }
+
+    /// Takes the detected schema drift, e.g. to warn agents about it
+    pub fn take_schema_drift(&mut self) -> Vec<SchemaDrift> {
+        std::mem::take(&mut self.drift)
+    }
+
+    /// Sets the parameter renames for a server's tools
+    pub fn set_parameter_shims(&mut self, server_id: &str, shims: ParameterShims) {
+        if shims.is_empty() {
+            self.shims.remove(server_id);
+        } else {
+            self.shims.insert(server_id.to_string(), shims);
+        }
+    }
+
+    /// Rewrites renamed parameters in call arguments for a tool
+    pub fn apply_parameter_shims(&self, tool_id: &str, arguments: Value) -> Value {
+        let shims = self
+            .tools
+            .get(tool_id)
+            .and_then(|tool| tool.server_id.as_deref())
+            .and_then(|server_id| self.shims.get(server_id));
+        match shims {
+            Some(shims) => shims.apply(tool_id, arguments),
+            None => arguments,
+        }
+    }
+
     /// Gets a tool by ID
     pub fn get_tool(&self, id: &str) -> Option<&ToolMetadata> {
         self.tools.get(id)
@@ -165,4 +309,85 @@ mod tests {
         assert_eq!(math_tools.len(), 1);
         assert_eq!(math_tools[0].id, "tool1");
     }
+
+    fn server_tool(id: &str, parameters: Vec<crate::metadata::ParameterMetadata>) -> ToolMetadata {
+        ToolMetadata {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: id.to_string(),
+            category: "server1".to_string(),
+            parameters,
+            return_type: "object".to_string(),
+            source: ToolSource::Mcp("server1".to_string()),
+            server_id: Some("server1".to_string()),
+        }
+    }
+
+    fn param(name: &str, required: bool) -> crate::metadata::ParameterMetadata {
+        crate::metadata::ParameterMetadata {
+            name: name.to_string(),
+            type_: "string".to_string(),
+            description: String::new(),
+            required,
+            default: None,
+        }
+    }
+
+    #[test]
+    fn test_sync_server_tools_detects_drift() {
+        let mut registry = ToolRegistry::new();
+        let drift = registry.sync_server_tools(
+            "server1",
+            vec![
+                server_tool("search", vec![param("query", true)]),
+                server_tool("read", vec![param("path", true)]),
+            ],
+        );
+        assert!(drift.is_empty());
+        assert_eq!(registry.schema_version("search"), Some(1));
+
+        // Unchanged schemas keep their version
+        registry.sync_server_tools(
+            "server1",
+            vec![
+                server_tool("search", vec![param("query", true)]),
+                server_tool("read", vec![param("path", true)]),
+            ],
+        );
+        assert_eq!(registry.schema_version("search"), Some(1));
+
+        let drift = registry.sync_server_tools(
+            "server1",
+            vec![server_tool("search", vec![param("pattern", false)])],
+        );
+        assert_eq!(drift.len(), 2);
+        assert!(drift.iter().all(SchemaDrift::is_breaking));
+        assert_eq!(registry.schema_version("search"), Some(2));
+        assert!(registry.get_tool("read").is_none());
+        assert_eq!(registry.take_schema_drift().len(), 2);
+        assert!(registry.pending_schema_drift().is_empty());
+    }
+
+    #[test]
+    fn test_parameter_shims() {
+        let mut registry = ToolRegistry::new();
+        registry.sync_server_tools(
+            "server1",
+            vec![server_tool("search", vec![param("query", true)])],
+        );
+        registry.set_parameter_shims(
+            "server1",
+            ParameterShims::new().with_rename("search", "query", "q"),
+        );
+
+        let drift = registry.sync_server_tools(
+            "server1",
+            vec![server_tool("search", vec![param("q", true)])],
+        );
+        assert_eq!(drift.len(), 1);
+        assert!(!drift[0].is_breaking());
+
+        let args = registry.apply_parameter_shims("search", serde_json::json!({ "query": "x" }));
+        assert_eq!(args, serde_json::json!({ "q": "x" }));
+    }
 }
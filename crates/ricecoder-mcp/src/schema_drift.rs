@@ -0,0 +1,393 @@
+//! Tool schema versioning and drift detection
+//!
+//! The registry snapshots every tool's input schema when the tool is first
+//! registered. When a server reconnects and reports its tools again, each
+//! schema is compared with its snapshot; differences are reported as a
+//! [`SchemaDrift`] and breaking ones are surfaced as warnings. Parameters a
+//! server has renamed can be mapped back with per-server [`ParameterShims`].
+
+use std::{collections::HashMap, fmt, time::SystemTime};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::metadata::{ParameterMetadata, ToolMetadata};
+
+/// Input schema of a tool at one version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolSchemaSnapshot {
+    pub tool_id: String,
+    pub server_id: Option<String>,
+    /// Starts at 1 and increases with every detected change
+    pub version: u32,
+    /// Parameters sorted by name
+    pub parameters: Vec<ParameterMetadata>,
+    pub captured_at: SystemTime,
+}
+
+impl ToolSchemaSnapshot {
+    /// Captures the current schema of a tool
+    pub fn capture(tool: &ToolMetadata, version: u32) -> Self {
+        let mut parameters = tool.parameters.clone();
+        parameters.sort_by(|a, b| a.name.cmp(&b.name));
+        Self {
+            tool_id: tool.id.clone(),
+            server_id: tool.server_id.clone(),
+            version,
+            parameters,
+            captured_at: SystemTime::now(),
+        }
+    }
+
+    /// Lists how a tool's current schema differs from this snapshot
+    ///
+    /// A removed parameter that `shims` renames to a newly added one is
+    /// reported as a rename rather than a removal plus an addition.
+    pub fn diff(&self, tool: &ToolMetadata, shims: Option<&ParameterShims>) -> Vec<SchemaChange> {
+        let old: HashMap<&str, &ParameterMetadata> = self
+            .parameters
+            .iter()
+            .map(|param| (param.name.as_str(), param))
+            .collect();
+        let new: HashMap<&str, &ParameterMetadata> = tool
+            .parameters
+            .iter()
+            .map(|param| (param.name.as_str(), param))
+            .collect();
+
+        let mut changes = Vec::new();
+        let mut renamed_to = Vec::new();
+
+        for before in &self.parameters {
+            let name = before.name.as_str();
+            let after = match new.get(name) {
+                Some(after) => *after,
+                None => {
+                    let target = shims
+                        .and_then(|shims| shims.rename_for(&tool.id, name))
+                        .filter(|target| !old.contains_key(target))
+                        .and_then(|target| new.get(target).copied());
+                    match target {
+                        Some(after) => {
+                            changes.push(SchemaChange::ParameterRenamed {
+                                from: name.to_string(),
+                                to: after.name.clone(),
+                            });
+                            renamed_to.push(after.name.as_str());
+                            after
+                        }
+                        None => {
+                            changes.push(SchemaChange::ParameterRemoved {
+                                name: name.to_string(),
+                                required: before.required,
+                            });
+                            continue;
+                        }
+                    }
+                }
+            };
+
+            if before.type_ != after.type_ {
+                changes.push(SchemaChange::TypeChanged {
+                    name: after.name.clone(),
+                    from: before.type_.clone(),
+                    to: after.type_.clone(),
+                });
+            }
+            if before.required != after.required {
+                changes.push(SchemaChange::RequirednessChanged {
+                    name: after.name.clone(),
+                    required: after.required,
+                });
+            }
+        }
+
+        let mut added: Vec<&ParameterMetadata> = tool
+            .parameters
+            .iter()
+            .filter(|param| {
+                !old.contains_key(param.name.as_str()) && !renamed_to.contains(&param.name.as_str())
+            })
+            .collect();
+        added.sort_by(|a, b| a.name.cmp(&b.name));
+        changes.extend(added.into_iter().map(|param| SchemaChange::ParameterAdded {
+            name: param.name.clone(),
+            required: param.required,
+        }));
+
+        changes
+    }
+}
+
+/// One difference between two versions of a tool schema
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SchemaChange {
+    ParameterAdded {
+        name: String,
+        required: bool,
+    },
+    ParameterRemoved {
+        name: String,
+        required: bool,
+    },
+    /// Covered by a configured shim
+    ParameterRenamed {
+        from: String,
+        to: String,
+    },
+    TypeChanged {
+        name: String,
+        from: String,
+        to: String,
+    },
+    RequirednessChanged {
+        name: String,
+        required: bool,
+    },
+    ToolRemoved,
+}
+
+impl SchemaChange {
+    /// Whether calls that worked against the old schema may now fail
+    pub fn is_breaking(&self) -> bool {
+        match self {
+            SchemaChange::ParameterAdded { required, .. } => *required,
+            SchemaChange::ParameterRemoved { required, .. } => *required,
+            SchemaChange::ParameterRenamed { .. } => false,
+            SchemaChange::TypeChanged { .. } => true,
+            SchemaChange::RequirednessChanged { required, .. } => *required,
+            SchemaChange::ToolRemoved => true,
+        }
+    }
+}
+
+impl fmt::Display for SchemaChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaChange::ParameterAdded { name, required } => write!(
+                f,
+                "added {} parameter '{}'",
+                if *required { "required" } else { "optional" },
+                name
+            ),
+            SchemaChange::ParameterRemoved { name, required } => write!(
+                f,
+                "removed {} parameter '{}'",
+                if *required { "required" } else { "optional" },
+                name
+            ),
+            SchemaChange::ParameterRenamed { from, to } => {
+                write!(f, "renamed parameter '{}' to '{}' (shimmed)", from, to)
+            }
+            SchemaChange::TypeChanged { name, from, to } => {
+                write!(f, "changed type of '{}' from {} to {}", name, from, to)
+            }
+            SchemaChange::RequirednessChanged { name, required } => write!(
+                f,
+                "made parameter '{}' {}",
+                name,
+                if *required { "required" } else { "optional" }
+            ),
+            SchemaChange::ToolRemoved => write!(f, "tool was removed"),
+        }
+    }
+}
+
+/// Schema changes detected for one tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaDrift {
+    pub tool_id: String,
+    pub server_id: Option<String>,
+    pub from_version: u32,
+    /// `None` when the tool was removed
+    pub to_version: Option<u32>,
+    pub changes: Vec<SchemaChange>,
+}
+
+impl SchemaDrift {
+    /// Whether any change may break existing callers
+    pub fn is_breaking(&self) -> bool {
+        self.changes.iter().any(SchemaChange::is_breaking)
+    }
+
+    /// The changes that may break existing callers
+    pub fn breaking_changes(&self) -> Vec<&SchemaChange> {
+        self.changes.iter().filter(|c| c.is_breaking()).collect()
+    }
+
+    /// One-line warning suitable for agents and users
+    pub fn warning_message(&self) -> String {
+        let server = self.server_id.as_deref().unwrap_or("local");
+        let version = match self.to_version {
+            Some(to) => format!("v{} -> v{}", self.from_version, to),
+            None => format!("v{} -> removed", self.from_version),
+        };
+        let changes = self
+            .changes
+            .iter()
+            .map(|change| change.to_string())
+            .collect::<Vec<_>>()
+            .join("; ");
+        format!(
+            "{}schema of tool '{}' on server '{}' changed ({}): {}",
+            if self.is_breaking() { "Breaking: " } else { "" },
+            self.tool_id,
+            server,
+            version,
+            changes
+        )
+    }
+}
+
+/// Parameter renames for one server's tools
+///
+/// Maps tool ID to `old name -> new name`, so callers written against an
+/// older schema keep working after the server renames a parameter.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ParameterShims {
+    renames: HashMap<String, HashMap<String, String>>,
+}
+
+impl ParameterShims {
+    /// Creates an empty shim layer
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a rename for one tool's parameter
+    pub fn with_rename(
+        mut self,
+        tool_id: impl Into<String>,
+        from: impl Into<String>,
+        to: impl Into<String>,
+    ) -> Self {
+        self.renames
+            .entry(tool_id.into())
+            .or_default()
+            .insert(from.into(), to.into());
+        self
+    }
+
+    /// The current name of a renamed parameter
+    pub fn rename_for(&self, tool_id: &str, parameter: &str) -> Option<&str> {
+        self.renames
+            .get(tool_id)
+            .and_then(|renames| renames.get(parameter))
+            .map(String::as_str)
+    }
+
+    /// Checks whether any renames are configured
+    pub fn is_empty(&self) -> bool {
+        self.renames.values().all(HashMap::is_empty)
+    }
+
+    /// Rewrites old parameter names in call arguments to their current names
+    ///
+    /// An argument already passed under its new name is left alone.
+    pub fn apply(&self, tool_id: &str, arguments: Value) -> Value {
+        let Some(renames) = self.renames.get(tool_id) else {
+            return arguments;
+        };
+
+        match arguments {
+            Value::Object(mut args) => {
+                for (from, to) in renames {
+                    if args.contains_key(to) {
+                        continue;
+                    }
+                    if let Some(value) = args.remove(from) {
+                        args.insert(to.clone(), value);
+                    }
+                }
+                Value::Object(args)
+            }
+            other => other,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::ToolSource;
+
+    fn param(name: &str, type_: &str, required: bool) -> ParameterMetadata {
+        ParameterMetadata {
+            name: name.to_string(),
+            type_: type_.to_string(),
+            description: String::new(),
+            required,
+            default: None,
+        }
+    }
+
+    fn tool(parameters: Vec<ParameterMetadata>) -> ToolMetadata {
+        ToolMetadata {
+            id: "search".to_string(),
+            name: "search".to_string(),
+            description: "Search files".to_string(),
+            category: "server1".to_string(),
+            parameters,
+            return_type: "object".to_string(),
+            source: ToolSource::Mcp("server1".to_string()),
+            server_id: Some("server1".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_diff_detects_breaking_changes() {
+        let before = ToolSchemaSnapshot::capture(
+            &tool(vec![
+                param("query", "string", true),
+                param("limit", "integer", false),
+            ]),
+            1,
+        );
+        let after = tool(vec![
+            param("limit", "string", false),
+            param("path", "string", true),
+        ]);
+
+        let changes = before.diff(&after, None);
+        assert_eq!(
+            changes,
+            vec![
+                SchemaChange::TypeChanged {
+                    name: "limit".to_string(),
+                    from: "integer".to_string(),
+                    to: "string".to_string(),
+                },
+                SchemaChange::ParameterRemoved {
+                    name: "query".to_string(),
+                    required: true,
+                },
+                SchemaChange::ParameterAdded {
+                    name: "path".to_string(),
+                    required: true,
+                },
+            ]
+        );
+        assert!(changes.iter().all(SchemaChange::is_breaking));
+    }
+
+    #[test]
+    fn test_shimmed_rename_is_compatible() {
+        let before = ToolSchemaSnapshot::capture(&tool(vec![param("query", "string", true)]), 1);
+        let after = tool(vec![param("q", "string", true)]);
+        let shims = ParameterShims::new().with_rename("search", "query", "q");
+
+        let changes = before.diff(&after, Some(&shims));
+        assert_eq!(
+            changes,
+            vec![SchemaChange::ParameterRenamed {
+                from: "query".to_string(),
+                to: "q".to_string(),
+            }]
+        );
+        assert!(!changes[0].is_breaking());
+
+        let args = shims.apply("search", serde_json::json!({ "query": "todo", "limit": 5 }));
+        assert_eq!(args, serde_json::json!({ "q": "todo", "limit": 5 }));
+    }
+}
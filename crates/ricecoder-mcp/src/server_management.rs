@@ -18,11 +18,20 @@ use tokio::{
 use tracing::{debug, error, info, warn};
 
 use crate::{
+    connection_pool::ConnectionPool,
     error::{Error, Result},
     metadata::ToolMetadata,
+    registry::ToolRegistry,
+    schema_drift::ParameterShims,
     transport::{MCPMessage, MCPRequest, MCPTransport, TransportConfig, TransportFactory},
 };
 
+/// Idle period after which a lazily started server is shut down
+pub const DEFAULT_IDLE_TIMEOUT_SECONDS: u64 = 600;
+
+/// How long a tool call may wait for its response
+const TOOL_CALL_TIMEOUT_MS: u64 = 30000;
+
 /// Server connection state
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum ServerState {
@@ -53,11 +62,35 @@ pub struct ServerConfig {
     pub name: String,
     pub description: String,
     pub transport_config: TransportConfig,
+    /// Start at registration instead of on the first tool call
     pub auto_start: bool,
     pub health_check_interval_seconds: u64,
     pub max_reconnect_attempts: u32,
     pub auth_config: Option<AuthConfig>,
     pub enabled_tools: HashSet<String>,
+    /// Seconds without tool calls before the server is shut down; `None` uses
+    /// [`DEFAULT_IDLE_TIMEOUT_SECONDS`] and `0` keeps it running
+    #[serde(default)]
+    pub idle_timeout_seconds: Option<u64>,
+    /// Tool calls the server may handle at once; `None` uses the pool default
+    #[serde(default)]
+    pub max_in_flight: Option<usize>,
+    /// Parameters the server has renamed, per tool (`old name -> new name`)
+    #[serde(default)]
+    pub parameter_shims: ParameterShims,
+}
+
+impl ServerConfig {
+    /// Idle period after which the server is shut down, if any
+    pub fn idle_timeout(&self) -> Option<Duration> {
+        match self
+            .idle_timeout_seconds
+            .unwrap_or(DEFAULT_IDLE_TIMEOUT_SECONDS)
+        {
+            0 => None,
+            seconds => Some(Duration::from_secs(seconds)),
+        }
+    }
 }
 
 /// Authentication configuration
@@ -85,6 +118,8 @@ pub struct ServerRegistration {
     pub health: ServerHealth,
     pub tools: Vec<ToolMetadata>,
     pub registered_at: SystemTime,
+    /// When the server last started or handled a tool call
+    pub last_used: Option<SystemTime>,
 }
 
 /// Server discovery result
@@ -105,6 +140,9 @@ pub struct ServerManager {
     analytics: Option<Arc<crate::analytics::MCPAnalyticsAggregator>>,
     rbac_manager: Option<Arc<crate::rbac::MCRBACManager>>,
     compliance_monitor: Option<Arc<crate::compliance::MCPComplianceMonitor>>,
+    pool: ConnectionPool,
+    tool_registry: Option<Arc<RwLock<ToolRegistry>>>,
+    start_locks: std::sync::Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>,
     _health_task: tokio::task::JoinHandle<()>,
 }
 
@@ -112,7 +150,8 @@ impl ServerManager {
     /// Create a new server manager
     pub fn new() -> Self {
         let servers = Arc::new(RwLock::new(HashMap::new()));
-        let health_monitor = Arc::new(HealthMonitor::new(servers.clone()));
+        let pool = ConnectionPool::new();
+        let health_monitor = Arc::new(HealthMonitor::new(servers.clone(), pool.clone(), None));
 
         let health_monitor_clone = health_monitor.clone();
         let health_task = tokio::spawn(async move {
@@ -127,6 +166,9 @@ impl ServerManager {
             analytics: None,
             rbac_manager: None,
             compliance_monitor: None,
+            pool,
+            tool_registry: None,
+            start_locks: std::sync::Mutex::new(HashMap::new()),
             _health_task: health_task,
         }
     }
@@ -149,7 +191,12 @@ impl ServerManager {
     /// Create a new server manager with audit logging
     pub fn with_audit_logger(audit_logger: Arc<crate::audit::MCPAuditLogger>) -> Self {
         let servers = Arc::new(RwLock::new(HashMap::new()));
-        let health_monitor = Arc::new(HealthMonitor::new(servers.clone()));
+        let pool = ConnectionPool::new();
+        let health_monitor = Arc::new(HealthMonitor::new(
+            servers.clone(),
+            pool.clone(),
+            Some(audit_logger.clone()),
+        ));
 
         let health_monitor_clone = health_monitor.clone();
         let health_task = tokio::spawn(async move {
@@ -164,6 +211,9 @@ impl ServerManager {
             analytics: None,
             rbac_manager: None,
             compliance_monitor: None,
+            pool,
+            tool_registry: None,
+            start_locks: std::sync::Mutex::new(HashMap::new()),
             _health_task: health_task,
         }
     }
@@ -177,6 +227,15 @@ impl ServerManager {
         self
     }
 
+    /// Keep a tool registry in sync with the tools servers report
+    ///
+    /// Each time a server starts or reconnects, its tools are synced into the
+    /// registry, which detects schema drift against earlier snapshots.
+    pub fn with_tool_registry(mut self, tool_registry: Arc<RwLock<ToolRegistry>>) -> Self {
+        self.tool_registry = Some(tool_registry);
+        self
+    }
+
     /// Register a server with the manager
     pub async fn register_server(&self, config: ServerConfig) -> Result<()> {
         self.register_server_with_auth(config, None).await
@@ -231,6 +290,7 @@ impl ServerManager {
             },
             tools: Vec::new(),
             registered_at: SystemTime::now(),
+            last_used: None,
         };
 
         let mut servers = self.servers.write().await;
@@ -273,10 +333,15 @@ impl ServerManager {
                         .await
                     {
                         Ok(tools) => {
+                            self.sync_tool_registry(config, &tools).await;
+                            self.pool
+                                .attach(server_id, transport_clone, config.max_in_flight)
+                                .await;
                             registration.tools = tools.clone();
                             registration.health.tools_available = tools.len();
                             registration.health.state = ServerState::Connected;
                             registration.health.last_seen = Some(SystemTime::now());
+                            registration.last_used = Some(SystemTime::now());
                             registration.health.connection_attempts += 1;
 
                             info!(
@@ -457,6 +522,7 @@ impl ServerManager {
             if let Some(registration) = servers.get(server_id) {
                 if registration.health.state != ServerState::Stopped
                     && registration.health.state != ServerState::Disconnected
+                    && registration.health.state != ServerState::Error
                 {
                     // Already running or in another state
                     return Ok(());
@@ -496,18 +562,26 @@ impl ServerManager {
 
         // Discover tools from the server
         let tools = match self.discover_tools_from_server(&config, &*transport_clone).await {
-            Ok(t) => t,
+            Ok(t) => {
+                self.sync_tool_registry(&config, &t).await;
+                t
+            }
             Err(e) => {
                 warn!("Failed to discover tools from server {}: {}. Starting anyway.", server_id, e);
                 Vec::new() // Continue without tools, can be discovered later
             }
         };
 
+        self.pool
+            .attach(server_id, transport.clone(), config.max_in_flight)
+            .await;
+
         // Update registration with transport and tools
         {
             let mut servers = self.servers.write().await;
             if let Some(registration) = servers.get_mut(server_id) {
                 registration.transport = Some(transport);
+                registration.last_used = Some(SystemTime::now());
                 registration.tools = tools.clone();
                 registration.health.state = ServerState::Connected;
                 registration.health.last_seen = Some(SystemTime::now());
@@ -530,28 +604,158 @@ impl ServerManager {
 
     /// Stop a server by ID
     pub async fn stop_server(&self, server_id: &str) -> Result<()> {
-        let mut servers = self.servers.write().await;
-        if let Some(registration) = servers.get_mut(server_id) {
-            if registration.health.state == ServerState::Connected
-                || registration.health.state == ServerState::Connecting
+        let transport = {
+            let mut servers = self.servers.write().await;
+            let registration = servers
+                .get_mut(server_id)
+                .ok_or_else(|| Error::ServerNotFound(server_id.to_string()))?;
+            if registration.health.state != ServerState::Connected
+                && registration.health.state != ServerState::Connecting
             {
-                registration.health.state = ServerState::Stopped;
-                registration.transport = None;
-                info!("Stopped server: {}", server_id);
-
-                // Audit logging
-                if let Some(ref audit_logger) = self.audit_logger {
-                    let _ = audit_logger
-                        .log_server_connection(server_id, false, None, None, None)
-                        .await;
-                }
+                return Ok(());
             }
-        } else {
-            return Err(Error::ServerNotFound(server_id.to_string()));
+            registration.health.state = ServerState::Stopped;
+            registration.transport.take()
+        };
+
+        self.pool.detach(server_id).await;
+        if let Some(transport) = transport {
+            let _ = transport.close().await;
+        }
+        info!("Stopped server: {}", server_id);
+
+        // Audit logging
+        if let Some(ref audit_logger) = self.audit_logger {
+            let _ = audit_logger
+                .log_server_connection(server_id, false, None, None, None)
+                .await;
         }
         Ok(())
     }
 
+    /// Start a server unless it is already running
+    ///
+    /// Servers are started lazily: the first tool call spawns the server, and
+    /// concurrent first calls wait for the same startup.
+    pub async fn ensure_started(&self, server_id: &str) -> Result<()> {
+        if self.is_running(server_id).await? {
+            return Ok(());
+        }
+
+        let start_lock = {
+            let mut locks = self
+                .start_locks
+                .lock()
+                .map_err(|e| Error::InternalError(e.to_string()))?;
+            locks.entry(server_id.to_string()).or_default().clone()
+        };
+        let _starting = start_lock.lock().await;
+
+        if !self.is_running(server_id).await? {
+            debug!("Starting server {} on first use", server_id);
+            self.start_server(server_id).await?;
+        }
+        if self.is_running(server_id).await? {
+            Ok(())
+        } else {
+            Err(Error::ServerDisconnected(server_id.to_string()))
+        }
+    }
+
+    /// Call a tool on a server, starting the server if needed
+    ///
+    /// Calls are routed through the connection pool, so several calls can
+    /// share the server's transport up to its in-flight limit. Arguments
+    /// passed under a renamed parameter's old name are shimmed to the new one.
+    pub async fn call_tool(
+        &self,
+        server_id: &str,
+        tool_name: &str,
+        arguments: serde_json::Value,
+    ) -> Result<serde_json::Value> {
+        self.ensure_started(server_id).await?;
+        self.touch(server_id).await;
+
+        let arguments = {
+            let servers = self.servers.read().await;
+            match servers.get(server_id) {
+                Some(registration) => registration
+                    .config
+                    .parameter_shims
+                    .apply(tool_name, arguments),
+                None => arguments,
+            }
+        };
+
+        let request = MCPRequest {
+            id: format!("call-{}", tool_name),
+            method: "tools/call".to_string(),
+            params: serde_json::json!({
+                "name": tool_name,
+                "arguments": arguments,
+            }),
+        };
+        let response = self
+            .pool
+            .request(server_id, request, TOOL_CALL_TIMEOUT_MS)
+            .await;
+        self.touch(server_id).await;
+
+        match response? {
+            MCPMessage::Response(resp) => Ok(resp.result),
+            MCPMessage::Error(err) => Err(Error::ExecutionError(format!(
+                "Tool {} failed on server {}: {} (code: {})",
+                tool_name, server_id, err.error.message, err.error.code
+            ))),
+            _ => Err(Error::ValidationError(
+                "Unexpected response type for tools/call request".to_string(),
+            )),
+        }
+    }
+
+    /// Shut down servers that have been idle longer than their idle timeout
+    ///
+    /// Returns the IDs of the servers that were stopped. The health monitor
+    /// calls this periodically; stopped servers start again on their next
+    /// tool call.
+    pub async fn stop_idle_servers(&self) -> Vec<String> {
+        self.health_monitor.stop_idle_servers().await
+    }
+
+    /// The pool that routes requests to running servers
+    pub fn connection_pool(&self) -> &ConnectionPool {
+        &self.pool
+    }
+
+    async fn is_running(&self, server_id: &str) -> Result<bool> {
+        let servers = self.servers.read().await;
+        let registration = servers
+            .get(server_id)
+            .ok_or_else(|| Error::ServerNotFound(server_id.to_string()))?;
+        if registration.health.state == ServerState::Disabled {
+            return Err(Error::ServerError(format!(
+                "Server {} is disabled",
+                server_id
+            )));
+        }
+        Ok(registration.health.state == ServerState::Connected && registration.transport.is_some())
+    }
+
+    async fn sync_tool_registry(&self, config: &ServerConfig, tools: &[ToolMetadata]) {
+        if let Some(ref tool_registry) = self.tool_registry {
+            let mut registry = tool_registry.write().await;
+            registry.set_parameter_shims(&config.id, config.parameter_shims.clone());
+            registry.sync_server_tools(&config.id, tools.to_vec());
+        }
+    }
+
+    async fn touch(&self, server_id: &str) {
+        let mut servers = self.servers.write().await;
+        if let Some(registration) = servers.get_mut(server_id) {
+            registration.last_used = Some(SystemTime::now());
+        }
+    }
+
     /// Restart a server by ID
     pub async fn restart_server(&self, server_id: &str) -> Result<()> {
         self.stop_server(server_id).await?;
@@ -613,7 +817,12 @@ impl ServerManager {
     /// Unregister a server
     pub async fn unregister_server(&self, server_id: &str) -> Result<()> {
         let mut servers = self.servers.write().await;
-        if servers.remove(server_id).is_some() {
+        if let Some(registration) = servers.remove(server_id) {
+            drop(servers);
+            self.pool.detach(server_id).await;
+            if let Some(transport) = registration.transport {
+                let _ = transport.close().await;
+            }
             info!("Unregistered server: {}", server_id);
 
             // Audit logging
@@ -652,13 +861,25 @@ pub trait ServerDiscoveryProvider: Send + Sync {
 }
 
 /// Health monitor for server connections
+///
+/// Also shuts down servers that have been idle past their idle timeout.
 struct HealthMonitor {
     servers: Arc<RwLock<HashMap<String, ServerRegistration>>>,
+    pool: ConnectionPool,
+    audit_logger: Option<Arc<crate::audit::MCPAuditLogger>>,
 }
 
 impl HealthMonitor {
-    fn new(servers: Arc<RwLock<HashMap<String, ServerRegistration>>>) -> Self {
-        Self { servers }
+    fn new(
+        servers: Arc<RwLock<HashMap<String, ServerRegistration>>>,
+        pool: ConnectionPool,
+        audit_logger: Option<Arc<crate::audit::MCPAuditLogger>>,
+    ) -> Self {
+        Self {
+            servers,
+            pool,
+            audit_logger,
+        }
     }
 
     async fn run(&self) {
@@ -666,10 +887,67 @@ impl HealthMonitor {
 
         loop {
             interval.tick().await;
+            self.stop_idle_servers().await;
             self.check_server_health().await;
         }
     }
 
+    async fn stop_idle_servers(&self) -> Vec<String> {
+        let now = SystemTime::now();
+        let candidates: Vec<String> = {
+            let servers = self.servers.read().await;
+            servers
+                .iter()
+                .filter(|(_, registration)| {
+                    registration.health.state == ServerState::Connected
+                        && registration.transport.is_some()
+                })
+                .filter(|(_, registration)| {
+                    match (registration.config.idle_timeout(), registration.last_used) {
+                        (Some(timeout), Some(last_used)) => now
+                            .duration_since(last_used)
+                            .is_ok_and(|idle| idle >= timeout),
+                        _ => false,
+                    }
+                })
+                .map(|(server_id, _)| server_id.clone())
+                .collect()
+        };
+
+        let mut stopped = Vec::new();
+        for server_id in candidates {
+            // A call that is still running keeps the server alive
+            if self.pool.in_flight(&server_id).await > 0 {
+                continue;
+            }
+
+            let transport = {
+                let mut servers = self.servers.write().await;
+                match servers.get_mut(&server_id) {
+                    Some(registration) if registration.health.state == ServerState::Connected => {
+                        registration.health.state = ServerState::Stopped;
+                        registration.transport.take()
+                    }
+                    _ => continue,
+                }
+            };
+
+            self.pool.detach(&server_id).await;
+            if let Some(transport) = transport {
+                let _ = transport.close().await;
+            }
+            info!("Stopped idle server: {}", server_id);
+
+            if let Some(ref audit_logger) = self.audit_logger {
+                let _ = audit_logger
+                    .log_server_connection(&server_id, false, None, None, None)
+                    .await;
+            }
+            stopped.push(server_id);
+        }
+        stopped
+    }
+
     async fn check_server_health(&self) {
         let server_ids: Vec<String> = {
             let servers = self.servers.read().await;
@@ -684,13 +962,19 @@ impl HealthMonitor {
     }
 
     async fn check_single_server(&self, server_id: &str) -> Result<()> {
+        let transport = {
+            let servers = self.servers.read().await;
+            servers.get(server_id).and_then(|r| r.transport.clone())
+        };
+        // Servers that were never started or have been stopped start on demand
+        let Some(transport) = transport else {
+            return Ok(());
+        };
+        let is_connected = transport.is_connected().await;
+
+        let mut reconnect = None;
         let mut servers = self.servers.write().await;
         if let Some(registration) = servers.get_mut(server_id) {
-            let is_connected = if let Some(ref transport) = registration.transport {
-                transport.as_ref().is_connected().await
-            } else {
-                false
-            };
 
             if is_connected {
                 registration.health.state = ServerState::Connected;
@@ -716,18 +1000,7 @@ impl HealthMonitor {
                         registration.config.max_reconnect_attempts
                     );
 
-                    // Try to reconnect
-                    if let Err(reconnect_err) = self
-                        .attempt_reconnection(server_id, &registration.config)
-                        .await
-                    {
-                        error!(
-                            "Reconnection failed for server {}: {}",
-                            server_id, reconnect_err
-                        );
-                        registration.health.last_error =
-                            Some(format!("Reconnection failed: {}", reconnect_err));
-                    }
+                    reconnect = Some(registration.config.clone());
                 } else {
                     error!("Max reconnection attempts reached for server {}", server_id);
                     registration.health.state = ServerState::Error;
@@ -736,6 +1009,22 @@ impl HealthMonitor {
 
             registration.health.tools_available = registration.tools.len();
         }
+        drop(servers);
+
+        // Try to reconnect
+        if let Some(config) = reconnect {
+            if let Err(reconnect_err) = self.attempt_reconnection(server_id, &config).await {
+                error!(
+                    "Reconnection failed for server {}: {}",
+                    server_id, reconnect_err
+                );
+                let mut servers = self.servers.write().await;
+                if let Some(registration) = servers.get_mut(server_id) {
+                    registration.health.last_error =
+                        Some(format!("Reconnection failed: {}", reconnect_err));
+                }
+            }
+        }
 
         Ok(())
     }
@@ -748,6 +1037,9 @@ impl HealthMonitor {
 
         match transport_result {
             Ok(transport) => {
+                self.pool
+                    .attach(server_id, transport.clone(), config.max_in_flight)
+                    .await;
                 let mut servers = self.servers.write().await;
                 if let Some(registration) = servers.get_mut(server_id) {
                     registration.transport = Some(transport);
@@ -887,6 +1179,9 @@ mod tests {
             max_reconnect_attempts: 3,
             auth_config: None,
             enabled_tools: HashSet::new(),
+            idle_timeout_seconds: None,
+            max_in_flight: None,
+            parameter_shims: ParameterShims::default(),
         };
 
         assert!(manager.register_server(config).await.is_ok());
@@ -918,6 +1213,9 @@ mod tests {
             max_reconnect_attempts: 3,
             auth_config: None,
             enabled_tools: HashSet::new(),
+            idle_timeout_seconds: None,
+            max_in_flight: None,
+            parameter_shims: ParameterShims::default(),
         };
 
         manager.register_server(config).await.unwrap();
@@ -957,6 +1255,9 @@ mod tests {
             max_reconnect_attempts: 3,
             auth_config: None,
             enabled_tools: HashSet::from(["grep".to_string(), "find".to_string()]),
+            idle_timeout_seconds: None,
+            max_in_flight: None,
+            parameter_shims: ParameterShims::default(),
         };
 
         let json = serde_json::to_string(&config).unwrap();
@@ -965,4 +1266,100 @@ mod tests {
         assert_eq!(config.id, deserialized.id);
         assert_eq!(config.enabled_tools, deserialized.enabled_tools);
     }
+
+    #[test]
+    fn test_idle_timeout_defaults() {
+        let json = serde_json::json!({
+            "id": "test_server",
+            "name": "Test Server",
+            "description": "A test MCP server",
+            "transport_config": {
+                "transport_type": "stdio",
+                "stdio_config": { "command": "echo", "args": [] },
+                "http_config": null,
+                "sse_config": null
+            },
+            "auto_start": false,
+            "health_check_interval_seconds": 30,
+            "max_reconnect_attempts": 3,
+            "auth_config": null,
+            "enabled_tools": []
+        });
+        let mut config: ServerConfig = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            config.idle_timeout(),
+            Some(Duration::from_secs(DEFAULT_IDLE_TIMEOUT_SECONDS))
+        );
+        assert_eq!(config.max_in_flight, None);
+
+        config.idle_timeout_seconds = Some(0);
+        assert_eq!(config.idle_timeout(), None);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_lazy_start_and_idle_shutdown() {
+        // Answers every request with an empty tool list, echoing its ID
+        let script = r#"while read -r line; do
+id=$(printf '%s' "$line" | sed -E 's/.*"id":"([^"]*)".*/\1/')
+printf '{"type":"response","data":{"id":"%s","result":{"tools":[],"id":"%s"}}}\n' "$id" "$id"
+done"#;
+        let manager = ServerManager::new();
+        let config = ServerConfig {
+            id: "lazy".to_string(),
+            name: "Lazy Server".to_string(),
+            description: "Starts on first use".to_string(),
+            transport_config: TransportConfig {
+                transport_type: TransportType::Stdio,
+                stdio_config: Some(StdioConfig {
+                    command: "sh".to_string(),
+                    args: vec!["-c".to_string(), script.to_string()],
+                }),
+                http_config: None,
+                sse_config: None,
+            },
+            auto_start: false,
+            health_check_interval_seconds: 30,
+            max_reconnect_attempts: 3,
+            auth_config: None,
+            enabled_tools: HashSet::new(),
+            idle_timeout_seconds: Some(60),
+            max_in_flight: Some(2),
+            parameter_shims: ParameterShims::default(),
+        };
+        manager.register_server(config).await.unwrap();
+
+        // Registration does not spawn the server
+        let server = manager.get_server("lazy").await.unwrap();
+        assert!(server.transport.is_none());
+        assert!(!manager.connection_pool().is_attached("lazy").await);
+
+        let result = manager
+            .call_tool("lazy", "grep", serde_json::json!({}))
+            .await
+            .unwrap();
+        assert_eq!(result["tools"], serde_json::json!([]));
+        let server = manager.get_server("lazy").await.unwrap();
+        assert_eq!(server.health.state, ServerState::Connected);
+
+        // Recently used servers stay up
+        assert!(manager.stop_idle_servers().await.is_empty());
+
+        {
+            let mut servers = manager.servers.write().await;
+            let registration = servers.get_mut("lazy").unwrap();
+            registration.last_used = Some(SystemTime::now() - Duration::from_secs(120));
+        }
+        assert_eq!(manager.stop_idle_servers().await, vec!["lazy".to_string()]);
+        let server = manager.get_server("lazy").await.unwrap();
+        assert_eq!(server.health.state, ServerState::Stopped);
+        assert!(!manager.connection_pool().is_attached("lazy").await);
+
+        // The next call starts it again
+        assert!(manager
+            .call_tool("lazy", "grep", serde_json::json!({}))
+            .await
+            .is_ok());
+        manager.stop_server("lazy").await.unwrap();
+    }
 }
@@ -42,6 +42,8 @@ impl AskMode {
                     require_specs: false,
                     auto_think_more_threshold: None,
                 },
+                tool_policy: None,
+                model_params: Default::default(),
             },
         }
     }
@@ -326,6 +328,8 @@ mod tests {
                 require_specs: false,
                 auto_think_more_threshold: None,
             },
+            tool_policy: None,
+            model_params: Default::default(),
         };
         let mode = AskMode::with_config(custom_config);
         assert_eq!(mode.config().temperature, 0.5);
@@ -54,6 +54,8 @@ impl CodeMode {
                     require_specs: false,
                     auto_think_more_threshold: Some(ComplexityLevel::Complex),
                 },
+                tool_policy: None,
+                model_params: Default::default(),
             },
         }
     }
@@ -589,6 +591,8 @@ mod tests {
                 require_specs: true,
                 auto_think_more_threshold: None,
             },
+            tool_policy: None,
+            model_params: Default::default(),
         };
         let mode = CodeMode::with_config(custom_config);
         assert_eq!(mode.config().temperature, 0.5);
@@ -676,6 +680,8 @@ mod tests {
                 require_specs: false,
                 auto_think_more_threshold: None,
             },
+            tool_policy: None,
+            model_params: Default::default(),
         };
         let mode = CodeMode::with_config(custom_config);
         let temp_dir = std::env::temp_dir().join("ricecoder_test_blocked");
@@ -799,6 +805,8 @@ mod tests {
                 require_specs: false,
                 auto_think_more_threshold: None,
             },
+            tool_policy: None,
+            model_params: Default::default(),
         };
         let mode = CodeMode::with_config(custom_config);
         let result = mode.run_tests(&[PathBuf::from("test.rs")]).await;
@@ -916,6 +924,8 @@ mod tests {
                 require_specs: false,
                 auto_think_more_threshold: None,
             },
+            tool_policy: None,
+            model_params: Default::default(),
         };
         let mode = CodeMode::with_config(custom_config);
         let result = mode.validate_quality(&[PathBuf::from("test.rs")]).await;
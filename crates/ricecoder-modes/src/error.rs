@@ -11,6 +11,15 @@ pub enum ModeError {
     #[error("Invalid mode transition: {0} -> {1}")]
     InvalidTransition(String, String),
 
+    /// Mode transition aborted by middleware
+    #[error("Mode transition aborted by {middleware}: {reason}")]
+    TransitionAborted {
+        /// The middleware name
+        middleware: String,
+        /// Why the middleware aborted the transition
+        reason: String,
+    },
+
     /// Capability not available in the specified mode
     #[error("Capability not available in {mode}: {capability}")]
     CapabilityNotAvailable {
@@ -33,6 +42,10 @@ pub enum ModeError {
     #[error("File operation blocked in Ask Mode")]
     FileOperationBlocked,
 
+    /// Plan could not be parsed, stored or approved
+    #[error("Plan error: {0}")]
+    PlanError(String),
+
     /// Processing failed with the given reason
     #[error("Processing failed: {0}")]
     ProcessingFailed(String),
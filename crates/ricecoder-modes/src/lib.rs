@@ -6,6 +6,7 @@
 //! - Code Mode: Focused code generation and modification
 //! - Ask Mode: Question answering without file modifications
 //! - Vibe Mode: Free-form exploration and rapid prototyping
+//! - Plan Mode: Read-only planning with approvable plan artifacts
 //! - Think More: Extended reasoning for complex tasks
 
 /// Ask Mode implementation
@@ -35,6 +36,8 @@ pub mod mode_switcher;
 mod mode_switching_properties;
 /// Data models for modes
 pub mod models;
+/// Plan Mode implementation
+pub mod plan_mode;
 /// Per-task configuration management
 pub mod task_config;
 /// Property-based tests for Think More Activation
@@ -53,6 +56,10 @@ pub mod think_more_controller;
 mod think_more_performance_properties;
 /// Thinking display and formatting
 pub mod thinking_display;
+/// Per-mode tool and permission policy matrix
+pub mod tool_policy;
+/// Mode transition middleware
+pub mod transition;
 /// Vibe Mode implementation
 pub mod vibe_mode;
 /// Property-based tests for Vibe Mode
@@ -71,7 +78,17 @@ pub use models::{
     ModeConstraints, ModeContext, ModeResponse, Operation, ResponseMetadata, ThinkMoreConfig,
     ThinkingDepth,
 };
+pub use plan_mode::{
+    PlanArtifact, PlanMode, PlanStep, PlanStepAction, APPROVE_PLAN_COMMAND, PLAN_ARTIFACT_KEY,
+    PLAN_MODE_ID,
+};
 pub use task_config::{TaskConfig, TaskConfigManager};
 pub use think_more_controller::{ThinkMoreController, ThinkingMetadata};
 pub use thinking_display::{ThinkingDisplay, ThinkingStatistics};
+pub use tool_policy::{
+    ModeToolPolicy, PolicyDecision, ToolCategory, ToolPolicy, ToolPolicyOverrides,
+};
+pub use transition::{
+    FailurePolicy, ModeTransition, TransitionMiddleware, VibeSpecMiddleware, VIBE_SPEC_KEY,
+};
 pub use vibe_mode::VibeMode;
@@ -182,6 +182,8 @@ mod tests {
                     require_specs: false,
                     auto_think_more_threshold: None,
                 },
+                tool_policy: None,
+                model_params: Default::default(),
             },
         });
 
@@ -209,6 +211,8 @@ mod tests {
                     require_specs: false,
                     auto_think_more_threshold: None,
                 },
+                tool_policy: None,
+                model_params: Default::default(),
             },
         });
 
@@ -244,6 +248,8 @@ mod tests {
                     require_specs: false,
                     auto_think_more_threshold: None,
                 },
+                tool_policy: None,
+                model_params: Default::default(),
             },
         });
 
@@ -5,6 +5,7 @@ use async_trait::async_trait;
 use crate::{
     error::Result,
     models::{Capability, ModeConfig, ModeConstraints, ModeContext, ModeResponse, Operation},
+    tool_policy::ToolPolicy,
 };
 
 /// Trait that all modes must implement
@@ -39,4 +40,14 @@ pub trait Mode: Send + Sync {
 
     /// Get mode-specific constraints
     fn constraints(&self) -> ModeConstraints;
+
+    /// Get the tool policy for this mode
+    ///
+    /// Uses the configured policy, or derives one from the constraints.
+    fn tool_policy(&self) -> ToolPolicy {
+        self.config()
+            .tool_policy
+            .clone()
+            .unwrap_or_else(|| ToolPolicy::from_constraints(&self.constraints()))
+    }
 }
@@ -2,12 +2,16 @@
 
 use std::{collections::HashMap, sync::Arc};
 
+use ricecoder_execution::ExecutionPlan;
 use tokio::sync::RwLock;
+use tracing::{debug, warn};
 
 use crate::{
     error::{ModeError, Result},
     mode::Mode,
     models::ModeContext,
+    plan_mode::{PlanArtifact, PLAN_ARTIFACT_KEY, PLAN_MODE_ID},
+    transition::{FailurePolicy, ModeTransition, TransitionMiddleware},
 };
 
 /// Handles mode transitions with context preservation
@@ -17,6 +21,7 @@ use crate::{
 /// - Preserving context across switches
 /// - Restoring context after switches
 /// - Managing mode-specific data
+/// - Running transition middleware (see [`crate::transition`])
 pub struct ModeSwitcher {
     /// Available modes
     modes: HashMap<String, Arc<dyn Mode>>,
@@ -26,6 +31,8 @@ pub struct ModeSwitcher {
     saved_contexts: Arc<RwLock<HashMap<String, ModeContext>>>,
     /// Current execution context
     context: Arc<RwLock<ModeContext>>,
+    /// Transition middleware, sorted by order
+    middleware: Vec<Arc<dyn TransitionMiddleware>>,
 }
 
 impl ModeSwitcher {
@@ -36,6 +43,7 @@ impl ModeSwitcher {
             current_mode: Arc::new(RwLock::new(None)),
             saved_contexts: Arc::new(RwLock::new(HashMap::new())),
             context: Arc::new(RwLock::new(context)),
+            middleware: Vec::new(),
         }
     }
 
@@ -44,6 +52,24 @@ impl ModeSwitcher {
         self.modes.insert(mode.id().to_string(), mode);
     }
 
+    /// Register transition middleware
+    ///
+    /// Middleware runs in ascending order; equal orders keep registration order.
+    pub fn register_middleware(&mut self, middleware: Arc<dyn TransitionMiddleware>) {
+        let position = self
+            .middleware
+            .partition_point(|m| m.order() <= middleware.order());
+        self.middleware.insert(position, middleware);
+    }
+
+    /// Get the names of the registered middleware, in execution order
+    pub fn middleware_names(&self) -> Vec<String> {
+        self.middleware
+            .iter()
+            .map(|m| m.name().to_string())
+            .collect()
+    }
+
     /// Get a registered mode by ID
     pub fn get_mode(&self, id: &str) -> Result<Arc<dyn Mode>> {
         self.modes
@@ -70,42 +96,114 @@ impl ModeSwitcher {
     ///
     /// This method:
     /// 1. Validates the mode exists
-    /// 2. Saves the current context if switching from a mode
-    /// 3. Restores the saved context for the new mode if available
-    /// 4. Creates a fresh context if the mode hasn't been visited before
-    /// 5. Updates the current mode
+    /// 2. Prepares the context for the new mode: the saved context if available,
+    ///    otherwise a fresh one
+    /// 3. Runs the transition middleware, which may abort the switch
+    /// 4. Saves the current context if switching from a mode and installs the
+    ///    new context
+    /// 5. Updates the current mode and runs the post-transition middleware
     pub async fn switch_mode(&self, mode_id: &str) -> Result<Arc<dyn Mode>> {
         // Validate the target mode exists
         let target_mode = self.get_mode(mode_id)?;
 
-        // Save current context if we're switching from a mode
-        if let Some(current_id) = self.current_mode.read().await.as_ref() {
-            let ctx = self.context.read().await.clone();
-            let mut saved = self.saved_contexts.write().await;
-            saved.insert(current_id.clone(), ctx);
-        }
+        let transition = ModeTransition {
+            from: self.current_mode.read().await.clone(),
+            to: mode_id.to_string(),
+        };
+        let mut outgoing = self.context.read().await.clone();
 
         // Restore context for the new mode if available, otherwise create fresh context
-        let mut saved = self.saved_contexts.write().await;
-        if let Some(saved_ctx) = saved.remove(mode_id) {
-            let mut ctx = self.context.write().await;
-            *ctx = saved_ctx;
+        let mut incoming = if transition.from.as_deref() == Some(mode_id) {
+            outgoing.clone()
+        } else if let Some(saved_ctx) = self.saved_contexts.read().await.get(mode_id) {
+            saved_ctx.clone()
         } else {
             // Create a fresh context for this mode (preserving session_id)
-            let mut ctx = self.context.write().await;
-            let session_id = ctx.session_id.clone();
-            let project_path = ctx.project_path.clone();
-            *ctx = ModeContext::new(session_id);
-            ctx.project_path = project_path;
+            let mut ctx = ModeContext::new(outgoing.session_id.clone());
+            ctx.project_path = outgoing.project_path.clone();
+            ctx
+        };
+
+        for middleware in &self.middleware {
+            debug!(
+                middleware = middleware.name(),
+                ?transition,
+                "Running transition middleware"
+            );
+            if let Err(e) = middleware
+                .on_transition(&transition, &mut outgoing, &mut incoming)
+                .await
+            {
+                match middleware.failure_policy() {
+                    FailurePolicy::Abort => {
+                        return Err(ModeError::TransitionAborted {
+                            middleware: middleware.name().to_string(),
+                            reason: e.to_string(),
+                        });
+                    }
+                    FailurePolicy::Continue => {
+                        warn!(middleware = middleware.name(), error = %e, "Transition middleware failed");
+                    }
+                }
+            }
+        }
+
+        // Commit: save the current context and install the new one
+        {
+            let mut saved = self.saved_contexts.write().await;
+            if let Some(current_id) = &transition.from {
+                saved.insert(current_id.clone(), outgoing);
+            }
+            saved.remove(mode_id);
         }
+        *self.context.write().await = incoming;
+        *self.current_mode.write().await = Some(mode_id.to_string());
 
-        // Update current mode
-        let mut current = self.current_mode.write().await;
-        *current = Some(mode_id.to_string());
+        if !self.middleware.is_empty() {
+            let ctx = self.context.read().await.clone();
+            for middleware in &self.middleware {
+                if let Err(e) = middleware.after_transition(&transition, &ctx).await {
+                    warn!(middleware = middleware.name(), error = %e, "Post-transition middleware failed");
+                }
+            }
+        }
 
         Ok(target_mode)
     }
 
+    /// Approve the plan produced in Plan Mode and switch to the target mode
+    ///
+    /// This is the only way out of Plan Mode that carries the plan along:
+    /// 1. Validates Plan Mode is active and a plan artifact was stored
+    /// 2. Hands the plan to the execution `PlanBuilder`
+    /// 3. Switches to the target mode, carrying the approved plan in its context
+    pub async fn approve_plan(&self, target_mode_id: &str) -> Result<ExecutionPlan> {
+        let current = self.current_mode_id().await;
+        if current.as_deref() != Some(PLAN_MODE_ID) {
+            return Err(ModeError::InvalidTransition(
+                current.unwrap_or_default(),
+                target_mode_id.to_string(),
+            ));
+        }
+
+        let plan = {
+            let ctx = self.context.read().await;
+            PlanArtifact::from_context(&ctx)?
+        }
+        .ok_or_else(|| ModeError::PlanError("No plan to approve".to_string()))?;
+        let execution_plan = plan.to_execution_plan()?;
+
+        self.switch_mode(target_mode_id).await?;
+        plan.store_in(&mut *self.context.write().await)?;
+
+        // The plan has been handed off; don't offer it again when returning to Plan Mode
+        if let Some(plan_ctx) = self.saved_contexts.write().await.get_mut(PLAN_MODE_ID) {
+            plan_ctx.custom.remove(PLAN_ARTIFACT_KEY);
+        }
+
+        Ok(execution_plan)
+    }
+
     /// Get the current context
     pub async fn context(&self) -> ModeContext {
         self.context.read().await.clone()
@@ -263,6 +361,8 @@ mod tests {
                     require_specs: false,
                     auto_think_more_threshold: None,
                 },
+                tool_policy: None,
+                model_params: Default::default(),
             },
         });
 
@@ -290,6 +390,8 @@ mod tests {
                     require_specs: false,
                     auto_think_more_threshold: None,
                 },
+                tool_policy: None,
+                model_params: Default::default(),
             },
         });
 
@@ -329,6 +431,8 @@ mod tests {
                     require_specs: false,
                     auto_think_more_threshold: None,
                 },
+                tool_policy: None,
+                model_params: Default::default(),
             },
         });
 
@@ -346,6 +450,8 @@ mod tests {
                     require_specs: false,
                     auto_think_more_threshold: None,
                 },
+                tool_policy: None,
+                model_params: Default::default(),
             },
         });
 
@@ -446,6 +552,8 @@ mod tests {
                     require_specs: false,
                     auto_think_more_threshold: None,
                 },
+                tool_policy: None,
+                model_params: Default::default(),
             },
         });
 
@@ -478,6 +586,8 @@ mod tests {
                     require_specs: false,
                     auto_think_more_threshold: None,
                 },
+                tool_policy: None,
+                model_params: Default::default(),
             },
         });
 
@@ -495,6 +605,8 @@ mod tests {
                     require_specs: false,
                     auto_think_more_threshold: None,
                 },
+                tool_policy: None,
+                model_params: Default::default(),
             },
         });
 
@@ -521,4 +633,181 @@ mod tests {
 
         assert_eq!(switcher.saved_context_count().await, 0);
     }
+
+    #[tokio::test]
+    async fn test_approve_plan() {
+        let context = ModeContext::new("test-session".to_string());
+        let mut switcher = ModeSwitcher::new(context);
+        switcher.register_mode(Arc::new(crate::plan_mode::PlanMode::new()));
+        switcher.register_mode(Arc::new(crate::code_mode::CodeMode::new()));
+
+        // Approval is only possible from Plan Mode
+        switcher.switch_mode("code").await.unwrap();
+        assert!(matches!(
+            switcher.approve_plan("code").await,
+            Err(ModeError::InvalidTransition(_, _))
+        ));
+
+        switcher.switch_mode("plan").await.unwrap();
+        assert!(matches!(
+            switcher.approve_plan("code").await,
+            Err(ModeError::PlanError(_))
+        ));
+
+        let plan = PlanArtifact::parse("# Plan: Test\n## Steps\n1. [run] cargo build\n").unwrap();
+        switcher
+            .update_context(|ctx| plan.store_in(ctx).unwrap())
+            .await
+            .unwrap();
+
+        let execution_plan = switcher.approve_plan("code").await.unwrap();
+        assert_eq!(execution_plan.name, "Test");
+        assert_eq!(switcher.current_mode_id().await, Some("code".to_string()));
+        assert_eq!(
+            PlanArtifact::from_context(&switcher.context().await).unwrap(),
+            Some(plan)
+        );
+        assert!(!switcher
+            .saved_contexts
+            .read()
+            .await
+            .get("plan")
+            .unwrap()
+            .custom
+            .contains_key(PLAN_ARTIFACT_KEY));
+    }
+
+    struct RecordingMiddleware {
+        name: &'static str,
+        order: i32,
+        fail: Option<FailurePolicy>,
+        calls: Arc<std::sync::Mutex<Vec<String>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl TransitionMiddleware for RecordingMiddleware {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn order(&self) -> i32 {
+            self.order
+        }
+
+        fn failure_policy(&self) -> FailurePolicy {
+            self.fail.unwrap_or_default()
+        }
+
+        async fn on_transition(
+            &self,
+            transition: &ModeTransition,
+            _outgoing: &mut ModeContext,
+            incoming: &mut ModeContext,
+        ) -> Result<()> {
+            self.calls.lock().unwrap().push(self.name.to_string());
+            if self.fail.is_some() {
+                return Err(ModeError::ProcessingFailed("boom".to_string()));
+            }
+            incoming
+                .custom
+                .insert(self.name.to_string(), serde_json::json!(transition.to));
+            Ok(())
+        }
+
+        async fn after_transition(
+            &self,
+            _transition: &ModeTransition,
+            _context: &ModeContext,
+        ) -> Result<()> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push(format!("{}:after", self.name));
+            Ok(())
+        }
+    }
+
+    fn switcher_with_modes() -> ModeSwitcher {
+        let mut switcher = ModeSwitcher::new(ModeContext::new("test-session".to_string()));
+        switcher.register_mode(Arc::new(crate::code_mode::CodeMode::new()));
+        switcher.register_mode(Arc::new(crate::ask_mode::AskMode::new()));
+        switcher
+    }
+
+    #[tokio::test]
+    async fn test_middleware_ordering() {
+        let calls = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut switcher = switcher_with_modes();
+        for (name, order) in [("second", 10), ("first", -5), ("third", 10)] {
+            switcher.register_middleware(Arc::new(RecordingMiddleware {
+                name,
+                order,
+                fail: None,
+                calls: calls.clone(),
+            }));
+        }
+        assert_eq!(
+            switcher.middleware_names(),
+            vec!["first", "second", "third"]
+        );
+
+        switcher.switch_mode("code").await.unwrap();
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec![
+                "first",
+                "second",
+                "third",
+                "first:after",
+                "second:after",
+                "third:after"
+            ]
+        );
+        assert_eq!(switcher.context().await.custom["first"], "code");
+    }
+
+    #[tokio::test]
+    async fn test_middleware_abort_leaves_state_untouched() {
+        let calls = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut switcher = switcher_with_modes();
+        switcher.register_middleware(Arc::new(RecordingMiddleware {
+            name: "guard",
+            order: 0,
+            fail: Some(FailurePolicy::Abort),
+            calls: calls.clone(),
+        }));
+
+        let result = switcher.switch_mode("code").await;
+        assert!(matches!(
+            result,
+            Err(ModeError::TransitionAborted { ref middleware, .. }) if middleware == "guard"
+        ));
+        assert_eq!(switcher.current_mode_id().await, None);
+        assert_eq!(switcher.saved_context_count().await, 0);
+        assert_eq!(*calls.lock().unwrap(), vec!["guard"]);
+    }
+
+    #[tokio::test]
+    async fn test_middleware_continue_on_failure() {
+        let calls = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut switcher = switcher_with_modes();
+        switcher.register_middleware(Arc::new(RecordingMiddleware {
+            name: "optional",
+            order: 0,
+            fail: Some(FailurePolicy::Continue),
+            calls: calls.clone(),
+        }));
+        switcher.register_middleware(Arc::new(RecordingMiddleware {
+            name: "next",
+            order: 1,
+            fail: None,
+            calls: calls.clone(),
+        }));
+
+        switcher.switch_mode("code").await.unwrap();
+        switcher.switch_mode("ask").await.unwrap();
+        assert_eq!(switcher.current_mode_id().await, Some("ask".to_string()));
+        assert!(switcher.has_saved_context("code").await);
+        assert_eq!(switcher.context().await.custom["next"], "ask");
+    }
 }
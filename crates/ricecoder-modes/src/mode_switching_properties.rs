@@ -99,6 +99,8 @@ mod tests {
                         require_specs: false,
                         auto_think_more_threshold: None,
                     },
+                    tool_policy: None,
+                    model_params: Default::default(),
                 },
             });
 
@@ -116,6 +118,8 @@ mod tests {
                         require_specs: false,
                         auto_think_more_threshold: None,
                     },
+                    tool_policy: None,
+                    model_params: Default::default(),
                 },
             });
 
@@ -198,6 +202,8 @@ mod tests {
                             require_specs: false,
                             auto_think_more_threshold: None,
                         },
+                        tool_policy: None,
+                        model_params: Default::default(),
                     },
                 });
                 switcher.register_mode(mode);
@@ -285,6 +291,8 @@ mod tests {
                         require_specs: false,
                         auto_think_more_threshold: None,
                     },
+                    tool_policy: None,
+                    model_params: Default::default(),
                 },
             });
 
@@ -302,6 +310,8 @@ mod tests {
                         require_specs: false,
                         auto_think_more_threshold: None,
                     },
+                    tool_policy: None,
+                    model_params: Default::default(),
                 },
             });
 
@@ -4,8 +4,11 @@ use std::{
     time::{Duration, SystemTime},
 };
 
+use ricecoder_providers::models::ModelParams;
 use serde::{Deserialize, Serialize};
 
+use crate::tool_policy::ToolPolicy;
+
 /// Represents a capability that a mode can have
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Capability {
@@ -27,6 +30,8 @@ pub enum Capability {
     FreeformChat,
     /// Spec conversion capability
     SpecConversion,
+    /// Read-only planning capability
+    Planning,
 }
 
 impl std::fmt::Display for Capability {
@@ -41,6 +46,7 @@ impl std::fmt::Display for Capability {
             Capability::QuestionAnswering => write!(f, "QuestionAnswering"),
             Capability::FreeformChat => write!(f, "FreeformChat"),
             Capability::SpecConversion => write!(f, "SpecConversion"),
+            Capability::Planning => write!(f, "Planning"),
         }
     }
 }
@@ -149,6 +155,25 @@ pub struct ModeConfig {
     pub capabilities: Vec<Capability>,
     /// Constraints for this mode
     pub constraints: ModeConstraints,
+    /// Tool policy for this mode (None = derived from the constraints)
+    #[serde(default)]
+    pub tool_policy: Option<ToolPolicy>,
+    /// Default model parameters for requests made in this mode
+    #[serde(default)]
+    pub model_params: ModelParams,
+}
+
+impl ModeConfig {
+    /// Model parameter defaults for requests made in this mode
+    ///
+    /// Temperature and max tokens fall back to the mode's own settings.
+    pub fn request_params(&self) -> ModelParams {
+        ModelParams {
+            temperature: self.model_params.temperature.or(Some(self.temperature)),
+            max_tokens: self.model_params.max_tokens.or(Some(self.max_tokens)),
+            ..self.model_params.clone()
+        }
+    }
 }
 
 /// A message in the conversation history
@@ -409,6 +434,41 @@ mod tests {
         assert!(constraints.allow_code_generation);
     }
 
+    #[test]
+    fn test_mode_config_request_params() {
+        let mut config = ModeConfig {
+            temperature: 0.5,
+            max_tokens: 2048,
+            system_prompt: String::new(),
+            capabilities: vec![],
+            constraints: ModeConstraints {
+                allow_file_operations: false,
+                allow_command_execution: false,
+                allow_code_generation: false,
+                require_specs: false,
+                auto_think_more_threshold: None,
+            },
+            tool_policy: None,
+            model_params: Default::default(),
+        };
+        let params = config.request_params();
+        assert_eq!(params.temperature, Some(0.5));
+        assert_eq!(params.max_tokens, Some(2048));
+        assert_eq!(params.top_p, None);
+
+        config.model_params.temperature = Some(0.1);
+        config.model_params.seed = Some(3);
+        let params = config.request_params();
+        assert_eq!(params.temperature, Some(0.1));
+        assert_eq!(params.seed, Some(3));
+
+        // Configs written before model_params existed still load
+        let mut json = serde_json::to_value(&config).unwrap();
+        json.as_object_mut().unwrap().remove("model_params");
+        let loaded: ModeConfig = serde_json::from_value(json).unwrap();
+        assert_eq!(loaded.model_params, ModelParams::default());
+    }
+
     #[test]
     fn test_change_summary_default() {
         let summary = ChangeSummary::default();
@@ -0,0 +1,580 @@
+//! Plan Mode implementation for read-only planning with structured plan artifacts
+
+use std::{path::PathBuf, time::Instant};
+
+use async_trait::async_trait;
+use ricecoder_execution::{ExecutionPlan, PlanBuilder};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{ModeError, Result},
+    mode::Mode,
+    models::{
+        Capability, ModeAction, ModeConfig, ModeConstraints, ModeContext, ModeResponse, Operation,
+    },
+};
+
+/// Identifier of the built-in Plan Mode
+pub const PLAN_MODE_ID: &str = "plan";
+
+/// Key under which the current plan artifact is stored in [`ModeContext::custom`]
+pub const PLAN_ARTIFACT_KEY: &str = "plan_artifact";
+
+/// Command that approves the current plan and hands it to execution
+pub const APPROVE_PLAN_COMMAND: &str = "approve plan";
+
+/// Action a plan step will perform once the plan is approved
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PlanStepAction {
+    /// Create a new file
+    CreateFile {
+        /// Path of the file to create
+        path: PathBuf,
+    },
+    /// Modify an existing file
+    ModifyFile {
+        /// Path of the file to modify
+        path: PathBuf,
+    },
+    /// Delete a file
+    DeleteFile {
+        /// Path of the file to delete
+        path: PathBuf,
+    },
+    /// Run a shell command
+    RunCommand {
+        /// The command to run
+        command: String,
+        /// Command arguments
+        args: Vec<String>,
+    },
+    /// Run tests
+    RunTests {
+        /// Optional test filter pattern
+        pattern: Option<String>,
+    },
+    /// Informational step with no direct execution counterpart
+    Note,
+}
+
+/// A single step of a plan artifact
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlanStep {
+    /// Human-readable description of the step
+    pub description: String,
+    /// Action performed by the step
+    pub action: PlanStepAction,
+}
+
+/// Structured plan produced in Plan Mode
+///
+/// The model is instructed to answer with the following layout, which
+/// [`PlanArtifact::parse`] understands:
+///
+/// ```text
+/// # Plan: Add config loader
+/// ## Steps
+/// 1. [create] src/config.rs - Add the loader
+/// 2. [modify] src/lib.rs - Register the module
+/// 3. [test] config
+/// ## Files
+/// - src/config.rs
+/// ## Risks
+/// - Existing config files may not parse
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlanArtifact {
+    /// Plan title
+    pub title: String,
+    /// Ordered plan steps
+    pub steps: Vec<PlanStep>,
+    /// Files the plan will touch
+    pub touched_files: Vec<PathBuf>,
+    /// Risks identified while planning
+    pub risks: Vec<String>,
+}
+
+impl PlanArtifact {
+    /// Parse a plan artifact from model output
+    pub fn parse(text: &str) -> Result<Self> {
+        #[derive(PartialEq)]
+        enum Section {
+            None,
+            Steps,
+            Files,
+            Risks,
+        }
+
+        let mut plan = PlanArtifact::default();
+        let mut section = Section::None;
+
+        for line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            if let Some(heading) = line.strip_prefix("##") {
+                section = match heading.trim().to_lowercase().as_str() {
+                    "steps" => Section::Steps,
+                    "files" | "touched files" => Section::Files,
+                    "risks" => Section::Risks,
+                    _ => Section::None,
+                };
+                continue;
+            }
+            if let Some(heading) = line.strip_prefix('#') {
+                let heading = heading.trim();
+                let title = heading
+                    .strip_prefix("Plan:")
+                    .or_else(|| heading.strip_prefix("plan:"))
+                    .unwrap_or(heading);
+                plan.title = title.trim().to_string();
+                continue;
+            }
+
+            let item = strip_list_marker(line);
+            match section {
+                Section::Steps => {
+                    let step = parse_step(item);
+                    if let Some(path) = step_path(&step.action) {
+                        plan.add_touched_file(path.clone());
+                    }
+                    plan.steps.push(step);
+                }
+                Section::Files => plan.add_touched_file(PathBuf::from(item)),
+                Section::Risks => plan.risks.push(item.to_string()),
+                Section::None => {}
+            }
+        }
+
+        if plan.steps.is_empty() {
+            return Err(ModeError::PlanError(
+                "Plan does not contain any steps".to_string(),
+            ));
+        }
+        if plan.title.is_empty() {
+            plan.title = "Untitled plan".to_string();
+        }
+
+        Ok(plan)
+    }
+
+    /// Store this plan in the mode context so it can be approved later
+    pub fn store_in(&self, context: &mut ModeContext) -> Result<()> {
+        context
+            .custom
+            .insert(PLAN_ARTIFACT_KEY.to_string(), serde_json::to_value(self)?);
+        Ok(())
+    }
+
+    /// Load the plan stored in the mode context, if any
+    pub fn from_context(context: &ModeContext) -> Result<Option<Self>> {
+        match context.custom.get(PLAN_ARTIFACT_KEY) {
+            Some(value) => Ok(Some(serde_json::from_value(value.clone())?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Convert the plan into an execution plan using [`PlanBuilder`]
+    ///
+    /// File contents and diffs are not part of the plan; they are produced by
+    /// the mode that executes it.
+    pub fn to_execution_plan(&self) -> Result<ExecutionPlan> {
+        let mut builder = PlanBuilder::new(self.title.clone());
+
+        for step in &self.steps {
+            builder = match &step.action {
+                PlanStepAction::CreateFile { path } => builder
+                    .add_create_file_step(path.display().to_string(), String::new())
+                    .map_err(|e| ModeError::PlanError(e.to_string()))?,
+                PlanStepAction::ModifyFile { path } => builder
+                    .add_modify_file_step(path.display().to_string(), String::new())
+                    .map_err(|e| ModeError::PlanError(e.to_string()))?,
+                PlanStepAction::DeleteFile { path } => builder
+                    .add_delete_file_step(path.display().to_string())
+                    .map_err(|e| ModeError::PlanError(e.to_string()))?,
+                PlanStepAction::RunCommand { command, args } => {
+                    builder.add_command_step(command.clone(), args.clone())
+                }
+                PlanStepAction::RunTests { pattern } => builder.add_test_step(pattern.clone()),
+                PlanStepAction::Note => builder,
+            };
+        }
+
+        builder
+            .build()
+            .map_err(|e| ModeError::PlanError(e.to_string()))
+    }
+
+    fn add_touched_file(&mut self, path: PathBuf) {
+        if !self.touched_files.contains(&path) {
+            self.touched_files.push(path);
+        }
+    }
+}
+
+fn strip_list_marker(line: &str) -> &str {
+    if let Some(rest) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+        return rest.trim();
+    }
+
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits > 0 {
+        if let Some(rest) = line[digits..]
+            .strip_prefix('.')
+            .or_else(|| line[digits..].strip_prefix(')'))
+        {
+            return rest.trim();
+        }
+    }
+
+    line
+}
+
+fn parse_step(item: &str) -> PlanStep {
+    let (tag, rest) = match item.strip_prefix('[').and_then(|s| s.split_once(']')) {
+        Some((tag, rest)) => (tag.trim().to_lowercase(), rest.trim()),
+        None => {
+            return PlanStep {
+                description: item.to_string(),
+                action: PlanStepAction::Note,
+            }
+        }
+    };
+
+    // File steps are written as "<path> - <description>"
+    let (target, description) = match rest.split_once(" - ") {
+        Some((target, description)) => (target.trim(), description.trim()),
+        None => (rest, rest),
+    };
+
+    let action = match tag.as_str() {
+        "create" => PlanStepAction::CreateFile {
+            path: PathBuf::from(target),
+        },
+        "modify" | "edit" => PlanStepAction::ModifyFile {
+            path: PathBuf::from(target),
+        },
+        "delete" => PlanStepAction::DeleteFile {
+            path: PathBuf::from(target),
+        },
+        "run" | "command" => {
+            let mut parts = target.split_whitespace().map(str::to_string);
+            match parts.next() {
+                Some(command) => PlanStepAction::RunCommand {
+                    command,
+                    args: parts.collect(),
+                },
+                None => PlanStepAction::Note,
+            }
+        }
+        "test" => PlanStepAction::RunTests {
+            pattern: (!target.is_empty()).then(|| target.to_string()),
+        },
+        _ => PlanStepAction::Note,
+    };
+
+    PlanStep {
+        description: description.to_string(),
+        action,
+    }
+}
+
+fn step_path(action: &PlanStepAction) -> Option<&PathBuf> {
+    match action {
+        PlanStepAction::CreateFile { path }
+        | PlanStepAction::ModifyFile { path }
+        | PlanStepAction::DeleteFile { path } => Some(path),
+        _ => None,
+    }
+}
+
+/// Plan Mode for read-only planning
+///
+/// Plan Mode provides capabilities for:
+/// - Analyzing a task without touching the workspace
+/// - Producing a structured plan artifact (steps, touched files, risks)
+/// - Handing the approved plan to execution via `approve plan`
+/// - No file modifications, code generation or command execution
+#[derive(Debug, Clone)]
+pub struct PlanMode {
+    config: ModeConfig,
+}
+
+impl PlanMode {
+    /// Create a new Plan Mode instance
+    pub fn new() -> Self {
+        Self {
+            config: ModeConfig {
+                temperature: 0.3,
+                max_tokens: 4096,
+                system_prompt: "You are a software architect. Analyze the request and produce \
+                    a plan without modifying any files or running commands. Answer with a \
+                    markdown plan: a '# Plan: <title>' heading, a '## Steps' section listing \
+                    numbered steps tagged [create], [modify], [delete], [run] or [test] \
+                    followed by '<target> - <description>', a '## Files' section listing every \
+                    file the plan touches and a '## Risks' section."
+                    .to_string(),
+                capabilities: vec![Capability::QuestionAnswering, Capability::Planning],
+                constraints: ModeConstraints {
+                    allow_file_operations: false,
+                    allow_command_execution: false,
+                    allow_code_generation: false,
+                    require_specs: false,
+                    auto_think_more_threshold: None,
+                },
+                tool_policy: None,
+                model_params: Default::default(),
+            },
+        }
+    }
+
+    /// Create a Plan Mode with custom configuration
+    ///
+    /// The read-only constraints are always enforced, regardless of the
+    /// constraints in the supplied configuration.
+    pub fn with_config(mut config: ModeConfig) -> Self {
+        config.constraints.allow_file_operations = false;
+        config.constraints.allow_command_execution = false;
+        config.constraints.allow_code_generation = false;
+        Self { config }
+    }
+
+    /// Check whether the input is the plan approval command
+    pub fn is_approval_command(input: &str) -> bool {
+        input.trim().eq_ignore_ascii_case(APPROVE_PLAN_COMMAND)
+    }
+
+    /// Validate that an operation is allowed in Plan Mode
+    ///
+    /// Only question answering is allowed; every mutating operation is blocked.
+    pub fn validate_operation(&self, operation: &Operation) -> Result<()> {
+        match operation {
+            Operation::AnswerQuestion => Ok(()),
+            _ => Err(ModeError::OperationNotAllowed {
+                mode: self.id().to_string(),
+                operation: operation.to_string(),
+            }),
+        }
+    }
+}
+
+impl Default for PlanMode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Mode for PlanMode {
+    fn id(&self) -> &str {
+        PLAN_MODE_ID
+    }
+
+    fn name(&self) -> &str {
+        "Plan Mode"
+    }
+
+    fn description(&self) -> &str {
+        "Read-only planning that produces a reviewable plan artifact"
+    }
+
+    fn system_prompt(&self) -> &str {
+        &self.config.system_prompt
+    }
+
+    async fn process(&self, input: &str, context: &ModeContext) -> Result<ModeResponse> {
+        let start = Instant::now();
+
+        let mut response = ModeResponse::new(input.to_string(), self.id().to_string());
+
+        if Self::is_approval_command(input) {
+            if PlanArtifact::from_context(context)?.is_none() {
+                return Err(ModeError::PlanError(
+                    "No plan to approve; produce a plan first".to_string(),
+                ));
+            }
+            response.add_action(ModeAction::SuggestMode {
+                mode: "code".to_string(),
+                reason: "Plan approved; switch to Code Mode to execute it".to_string(),
+            });
+        } else {
+            response.add_action(ModeAction::AskQuestion {
+                question: input.to_string(),
+            });
+            response.add_suggestion(format!(
+                "Review the plan and reply '{}' to hand it to execution.",
+                APPROVE_PLAN_COMMAND
+            ));
+        }
+
+        response.metadata.duration = start.elapsed();
+        response.metadata.think_more_used = context.think_more_enabled;
+
+        Ok(response)
+    }
+
+    fn capabilities(&self) -> Vec<Capability> {
+        self.config.capabilities.clone()
+    }
+
+    fn config(&self) -> &ModeConfig {
+        &self.config
+    }
+
+    fn can_execute(&self, operation: &Operation) -> bool {
+        matches!(operation, Operation::AnswerQuestion)
+    }
+
+    fn constraints(&self) -> ModeConstraints {
+        self.config.constraints.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_PLAN: &str = "# Plan: Add config loader\n\
+        ## Steps\n\
+        1. [create] src/config.rs - Add the loader\n\
+        2. [modify] src/lib.rs - Register the module\n\
+        3. [test] config\n\
+        4. Review the public API\n\
+        ## Files\n\
+        - src/config.rs\n\
+        - Cargo.toml\n\
+        ## Risks\n\
+        - Existing config files may not parse\n";
+
+    #[test]
+    fn test_plan_mode_creation() {
+        let mode = PlanMode::new();
+        assert_eq!(mode.id(), "plan");
+        assert_eq!(mode.name(), "Plan Mode");
+        assert!(mode.capabilities().contains(&Capability::Planning));
+    }
+
+    #[test]
+    fn test_plan_mode_is_read_only() {
+        let mode = PlanMode::new();
+        let constraints = mode.constraints();
+        assert!(!constraints.allow_file_operations);
+        assert!(!constraints.allow_command_execution);
+        assert!(!constraints.allow_code_generation);
+
+        assert!(mode.can_execute(&Operation::AnswerQuestion));
+        assert!(mode.validate_operation(&Operation::AnswerQuestion).is_ok());
+        for operation in [
+            Operation::GenerateCode,
+            Operation::ModifyFile,
+            Operation::ExecuteCommand,
+            Operation::RunTests,
+            Operation::ValidateQuality,
+        ] {
+            assert!(!mode.can_execute(&operation));
+            assert!(mode.validate_operation(&operation).is_err());
+        }
+    }
+
+    #[test]
+    fn test_plan_mode_with_config_keeps_read_only() {
+        let config = ModeConfig {
+            temperature: 0.5,
+            max_tokens: 1024,
+            system_prompt: "Custom prompt".to_string(),
+            capabilities: vec![Capability::Planning],
+            constraints: ModeConstraints {
+                allow_file_operations: true,
+                allow_command_execution: true,
+                allow_code_generation: true,
+                require_specs: false,
+                auto_think_more_threshold: None,
+            },
+            tool_policy: None,
+            model_params: Default::default(),
+        };
+        let mode = PlanMode::with_config(config);
+        assert!(!mode.constraints().allow_file_operations);
+        assert!(!mode.constraints().allow_command_execution);
+        assert!(!mode.constraints().allow_code_generation);
+    }
+
+    #[test]
+    fn test_parse_plan_artifact() {
+        let plan = PlanArtifact::parse(SAMPLE_PLAN).unwrap();
+        assert_eq!(plan.title, "Add config loader");
+        assert_eq!(plan.steps.len(), 4);
+        assert_eq!(
+            plan.steps[0].action,
+            PlanStepAction::CreateFile {
+                path: PathBuf::from("src/config.rs")
+            }
+        );
+        assert_eq!(plan.steps[0].description, "Add the loader");
+        assert_eq!(
+            plan.steps[2].action,
+            PlanStepAction::RunTests {
+                pattern: Some("config".to_string())
+            }
+        );
+        assert_eq!(plan.steps[3].action, PlanStepAction::Note);
+        assert_eq!(
+            plan.touched_files,
+            vec![
+                PathBuf::from("src/config.rs"),
+                PathBuf::from("src/lib.rs"),
+                PathBuf::from("Cargo.toml"),
+            ]
+        );
+        assert_eq!(plan.risks, vec!["Existing config files may not parse"]);
+    }
+
+    #[test]
+    fn test_parse_plan_without_steps_fails() {
+        let result = PlanArtifact::parse("# Plan: Nothing\n## Risks\n- none\n");
+        assert!(matches!(result, Err(ModeError::PlanError(_))));
+    }
+
+    #[test]
+    fn test_plan_artifact_to_execution_plan() {
+        let plan = PlanArtifact::parse(SAMPLE_PLAN).unwrap();
+        let execution_plan = plan.to_execution_plan().unwrap();
+        assert_eq!(execution_plan.name, "Add config loader");
+        // Informational steps are not handed to execution
+        assert_eq!(execution_plan.steps.len(), 3);
+    }
+
+    #[test]
+    fn test_plan_artifact_context_roundtrip() {
+        let plan = PlanArtifact::parse(SAMPLE_PLAN).unwrap();
+        let mut context = ModeContext::new("test-session".to_string());
+        assert!(PlanArtifact::from_context(&context).unwrap().is_none());
+
+        plan.store_in(&mut context).unwrap();
+        assert_eq!(PlanArtifact::from_context(&context).unwrap(), Some(plan));
+    }
+
+    #[tokio::test]
+    async fn test_plan_mode_process_approval_without_plan() {
+        let mode = PlanMode::new();
+        let context = ModeContext::new("test-session".to_string());
+        let result = mode.process("Approve plan", &context).await;
+        assert!(matches!(result, Err(ModeError::PlanError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_plan_mode_process() {
+        let mode = PlanMode::new();
+        let mut context = ModeContext::new("test-session".to_string());
+        let response = mode.process("Add a config loader", &context).await.unwrap();
+        assert_eq!(response.metadata.mode, "plan");
+        assert!(!response.suggestions.is_empty());
+
+        PlanArtifact::parse(SAMPLE_PLAN)
+            .unwrap()
+            .store_in(&mut context)
+            .unwrap();
+        let response = mode.process("approve plan", &context).await.unwrap();
+        assert!(matches!(
+            response.actions[0],
+            ModeAction::SuggestMode { ref mode, .. } if mode == "code"
+        ));
+    }
+}
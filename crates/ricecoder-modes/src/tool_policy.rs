@@ -0,0 +1,370 @@
+//! Per-mode tool and permission policy matrix
+//!
+//! Each mode maps the tool categories of `ricecoder-tools` (filesystem write,
+//! network, shell, MCP servers) to allow/deny/ask decisions. The effective
+//! policy of a mode is built from:
+//!
+//! 1. The `tool_policy` in its [`ModeConfig`], or one derived from its constraints
+//! 2. Project-level overrides from `.rice/tool-policy.yaml`
+//! 3. The mode constraints, which are hard limits no override can lift
+//!
+//! [`ModeToolPolicy`] plugs the result into the tool registry, so the policy is
+//! enforced at the tool-invocation boundary for built-in and MCP tools alike.
+//!
+//! [`ModeConfig`]: crate::models::ModeConfig
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use ricecoder_storage::PathResolver;
+use ricecoder_tools::policy::ToolInvocationPolicy;
+pub use ricecoder_tools::policy::{PolicyDecision, ToolCategory};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{ModeError, Result},
+    mode::Mode,
+    models::ModeConstraints,
+};
+
+/// File name of the project-level tool policy overrides
+pub const TOOL_POLICY_FILE: &str = "tool-policy.yaml";
+
+/// Allow/deny/ask decision for each tool category
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolPolicy {
+    /// Reading workspace files
+    #[serde(default = "allow")]
+    pub filesystem_read: PolicyDecision,
+    /// Creating, modifying or deleting files
+    #[serde(default = "ask")]
+    pub filesystem_write: PolicyDecision,
+    /// Network access
+    #[serde(default = "ask")]
+    pub network: PolicyDecision,
+    /// Shell command execution
+    #[serde(default = "ask")]
+    pub shell: PolicyDecision,
+    /// Tools provided by MCP servers
+    #[serde(default = "ask")]
+    pub mcp: PolicyDecision,
+}
+
+fn allow() -> PolicyDecision {
+    PolicyDecision::Allow
+}
+
+fn ask() -> PolicyDecision {
+    PolicyDecision::Ask
+}
+
+impl Default for ToolPolicy {
+    fn default() -> Self {
+        Self {
+            filesystem_read: PolicyDecision::Allow,
+            filesystem_write: PolicyDecision::Ask,
+            network: PolicyDecision::Ask,
+            shell: PolicyDecision::Ask,
+            mcp: PolicyDecision::Ask,
+        }
+    }
+}
+
+impl ToolPolicy {
+    /// Policy that allows every category
+    pub fn allow_all() -> Self {
+        Self {
+            filesystem_read: PolicyDecision::Allow,
+            filesystem_write: PolicyDecision::Allow,
+            network: PolicyDecision::Allow,
+            shell: PolicyDecision::Allow,
+            mcp: PolicyDecision::Allow,
+        }
+    }
+
+    /// Derive a policy from mode constraints
+    ///
+    /// MCP tools are only allowed without asking when the mode may both write
+    /// files and run commands, since MCP servers can do either.
+    pub fn from_constraints(constraints: &ModeConstraints) -> Self {
+        let permit = |allowed: bool| {
+            if allowed {
+                PolicyDecision::Allow
+            } else {
+                PolicyDecision::Deny
+            }
+        };
+
+        Self {
+            filesystem_read: PolicyDecision::Allow,
+            filesystem_write: permit(constraints.allow_file_operations),
+            network: PolicyDecision::Allow,
+            shell: permit(constraints.allow_command_execution),
+            mcp: if constraints.allow_file_operations && constraints.allow_command_execution {
+                PolicyDecision::Allow
+            } else {
+                PolicyDecision::Ask
+            },
+        }
+    }
+
+    /// Get the decision for a category
+    pub fn decision(&self, category: ToolCategory) -> PolicyDecision {
+        match category {
+            ToolCategory::FilesystemRead => self.filesystem_read,
+            ToolCategory::FilesystemWrite => self.filesystem_write,
+            ToolCategory::Network => self.network,
+            ToolCategory::Shell => self.shell,
+            ToolCategory::Mcp => self.mcp,
+        }
+    }
+
+    /// Set the decision for a category
+    pub fn set_decision(&mut self, category: ToolCategory, decision: PolicyDecision) {
+        match category {
+            ToolCategory::FilesystemRead => self.filesystem_read = decision,
+            ToolCategory::FilesystemWrite => self.filesystem_write = decision,
+            ToolCategory::Network => self.network = decision,
+            ToolCategory::Shell => self.shell = decision,
+            ToolCategory::Mcp => self.mcp = decision,
+        }
+    }
+
+    /// Decide a tool invocation spanning several categories
+    ///
+    /// The most restrictive decision wins; tools without a category need approval.
+    pub fn decide(&self, categories: &[ToolCategory]) -> PolicyDecision {
+        categories
+            .iter()
+            .map(|category| self.decision(*category))
+            .max()
+            .unwrap_or(PolicyDecision::Ask)
+    }
+
+    /// Apply per-category overrides
+    pub fn with_overrides(mut self, overrides: &HashMap<ToolCategory, PolicyDecision>) -> Self {
+        for (category, decision) in overrides {
+            self.set_decision(*category, *decision);
+        }
+        self
+    }
+
+    /// Deny every category the constraints forbid
+    pub fn restricted_by(mut self, constraints: &ModeConstraints) -> Self {
+        if !constraints.allow_file_operations {
+            self.filesystem_write = PolicyDecision::Deny;
+        }
+        if !constraints.allow_command_execution {
+            self.shell = PolicyDecision::Deny;
+        }
+        self
+    }
+}
+
+/// Project-level tool policy overrides, keyed by mode ID
+///
+/// ```yaml
+/// modes:
+///   code:
+///     shell: ask
+///     network: deny
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolPolicyOverrides {
+    /// Per-mode category overrides
+    #[serde(default)]
+    pub modes: HashMap<String, HashMap<ToolCategory, PolicyDecision>>,
+}
+
+impl ToolPolicyOverrides {
+    /// Path of the overrides file for a project
+    pub fn project_path(project_root: &Path) -> PathBuf {
+        project_root
+            .join(PathResolver::PROJECT_DIR)
+            .join(TOOL_POLICY_FILE)
+    }
+
+    /// Load overrides from a project, returning empty overrides if none exist
+    pub fn load(project_root: &Path) -> Result<Self> {
+        let path = Self::project_path(project_root);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(&path).map_err(|e| {
+            ModeError::ConfigError(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        serde_yaml::from_str(&content).map_err(|e| {
+            ModeError::ConfigError(format!("Failed to parse {}: {}", path.display(), e))
+        })
+    }
+
+    /// Overrides for a mode
+    pub fn for_mode(&self, mode_id: &str) -> Option<&HashMap<ToolCategory, PolicyDecision>> {
+        self.modes.get(mode_id)
+    }
+}
+
+/// Effective tool policy of a mode, enforced by the tool registry
+#[derive(Debug, Clone)]
+pub struct ModeToolPolicy {
+    mode_id: String,
+    policy: ToolPolicy,
+}
+
+impl ModeToolPolicy {
+    /// Build the effective tool policy for a mode
+    pub fn new(mode: &dyn Mode, overrides: &ToolPolicyOverrides) -> Self {
+        let constraints = mode.constraints();
+        let mut policy = mode.tool_policy();
+        if let Some(mode_overrides) = overrides.for_mode(mode.id()) {
+            policy = policy.with_overrides(mode_overrides);
+        }
+
+        Self {
+            mode_id: mode.id().to_string(),
+            policy: policy.restricted_by(&constraints),
+        }
+    }
+
+    /// ID of the mode this policy belongs to
+    pub fn mode_id(&self) -> &str {
+        &self.mode_id
+    }
+
+    /// The effective policy matrix
+    pub fn policy(&self) -> &ToolPolicy {
+        &self.policy
+    }
+}
+
+impl ToolInvocationPolicy for ModeToolPolicy {
+    fn decide(&self, _tool_id: &str, categories: &[ToolCategory]) -> PolicyDecision {
+        self.policy.decide(categories)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ask_mode::AskMode, code_mode::CodeMode, models::ModeConfig};
+
+    #[test]
+    fn test_policy_from_constraints() {
+        let ask = ToolPolicy::from_constraints(&AskMode::new().constraints());
+        assert_eq!(ask.filesystem_write, PolicyDecision::Deny);
+        assert_eq!(ask.shell, PolicyDecision::Deny);
+        assert_eq!(ask.mcp, PolicyDecision::Ask);
+
+        let code = ToolPolicy::from_constraints(&CodeMode::new().constraints());
+        assert_eq!(code, ToolPolicy::allow_all());
+    }
+
+    #[test]
+    fn test_decide_uses_most_restrictive() {
+        let policy = ToolPolicy::from_constraints(&AskMode::new().constraints());
+        assert_eq!(
+            policy.decide(&[ToolCategory::FilesystemRead]),
+            PolicyDecision::Allow
+        );
+        assert_eq!(
+            policy.decide(&[ToolCategory::FilesystemRead, ToolCategory::Mcp]),
+            PolicyDecision::Ask
+        );
+        assert_eq!(
+            policy.decide(&[ToolCategory::FilesystemWrite, ToolCategory::Mcp]),
+            PolicyDecision::Deny
+        );
+        assert_eq!(policy.decide(&[]), PolicyDecision::Ask);
+    }
+
+    #[test]
+    fn test_ask_mode_cannot_be_overridden_to_write() {
+        let mut overrides = ToolPolicyOverrides::default();
+        overrides.modes.insert(
+            "ask".to_string(),
+            HashMap::from([
+                (ToolCategory::FilesystemWrite, PolicyDecision::Allow),
+                (ToolCategory::Mcp, PolicyDecision::Allow),
+            ]),
+        );
+
+        let policy = ModeToolPolicy::new(&AskMode::new(), &overrides);
+        assert_eq!(policy.policy().filesystem_write, PolicyDecision::Deny);
+        assert_eq!(policy.policy().mcp, PolicyDecision::Allow);
+        // An MCP tool that may write files is still denied
+        assert_eq!(
+            policy.decide(
+                "mcp_tool",
+                &[ToolCategory::FilesystemWrite, ToolCategory::Mcp]
+            ),
+            PolicyDecision::Deny
+        );
+    }
+
+    #[test]
+    fn test_mode_config_policy_and_overrides() {
+        let mut config = CodeMode::new().config().clone();
+        config.tool_policy = Some(ToolPolicy {
+            network: PolicyDecision::Deny,
+            ..ToolPolicy::allow_all()
+        });
+        let mode = CodeMode::with_config(config);
+
+        let mut overrides = ToolPolicyOverrides::default();
+        overrides.modes.insert(
+            "code".to_string(),
+            HashMap::from([(ToolCategory::Shell, PolicyDecision::Ask)]),
+        );
+
+        let policy = ModeToolPolicy::new(&mode, &overrides);
+        assert_eq!(policy.mode_id(), "code");
+        assert_eq!(policy.policy().network, PolicyDecision::Deny);
+        assert_eq!(policy.policy().shell, PolicyDecision::Ask);
+        assert_eq!(policy.policy().filesystem_write, PolicyDecision::Allow);
+    }
+
+    #[test]
+    fn test_load_project_overrides() {
+        let temp = tempfile::TempDir::new().unwrap();
+        assert_eq!(
+            ToolPolicyOverrides::load(temp.path()).unwrap(),
+            ToolPolicyOverrides::default()
+        );
+
+        let path = ToolPolicyOverrides::project_path(temp.path());
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "modes:\n  code:\n    shell: ask\n    mcp: deny\n").unwrap();
+
+        let overrides = ToolPolicyOverrides::load(temp.path()).unwrap();
+        let code = overrides.for_mode("code").unwrap();
+        assert_eq!(code[&ToolCategory::Shell], PolicyDecision::Ask);
+        assert_eq!(code[&ToolCategory::Mcp], PolicyDecision::Deny);
+    }
+
+    #[test]
+    fn test_tool_policy_deserialize_defaults() {
+        let config: ModeConfig = serde_json::from_value(serde_json::json!({
+            "temperature": 0.7,
+            "max_tokens": 1024,
+            "system_prompt": "prompt",
+            "capabilities": [],
+            "constraints": {
+                "allow_file_operations": false,
+                "allow_command_execution": false,
+                "allow_code_generation": false,
+                "require_specs": false,
+                "auto_think_more_threshold": null
+            }
+        }))
+        .unwrap();
+        assert!(config.tool_policy.is_none());
+
+        let policy: ToolPolicy = serde_json::from_str(r#"{"shell": "deny"}"#).unwrap();
+        assert_eq!(policy.shell, PolicyDecision::Deny);
+        assert_eq!(policy.filesystem_read, PolicyDecision::Allow);
+        assert_eq!(policy.filesystem_write, PolicyDecision::Ask);
+    }
+}
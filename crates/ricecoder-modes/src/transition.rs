@@ -0,0 +1,195 @@
+//! Mode transition middleware
+//!
+//! Middleware lets other crates hook into mode switches, e.g. to turn the
+//! accumulated Vibe Mode context into a spec when switching to Code Mode,
+//! clear pending approvals or notify the TUI.
+//!
+//! # Ordering
+//!
+//! Middleware runs in ascending [`TransitionMiddleware::order`]; middleware with
+//! the same order runs in registration order.
+//!
+//! # Failure semantics
+//!
+//! A transition runs in two phases:
+//!
+//! 1. [`TransitionMiddleware::on_transition`] runs before the switch is
+//!    committed and may transform both the outgoing and the incoming context.
+//!    If middleware with [`FailurePolicy::Abort`] fails, the switch is aborted
+//!    and no state changes; with [`FailurePolicy::Continue`] the failure is
+//!    logged and the remaining middleware still runs.
+//! 2. [`TransitionMiddleware::after_transition`] runs once the switch is
+//!    committed. Failures are always logged and never undo the switch.
+
+use async_trait::async_trait;
+
+use crate::{error::Result, models::ModeContext, vibe_mode::VibeMode};
+
+/// Key under which [`VibeSpecMiddleware`] stores the generated spec
+pub const VIBE_SPEC_KEY: &str = "vibe_spec";
+
+/// A mode transition
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModeTransition {
+    /// Mode being left, `None` for the first switch
+    pub from: Option<String>,
+    /// Mode being entered
+    pub to: String,
+}
+
+impl ModeTransition {
+    /// Check whether this transition goes from one mode to another
+    pub fn is(&self, from: &str, to: &str) -> bool {
+        self.from.as_deref() == Some(from) && self.to == to
+    }
+}
+
+/// What happens when middleware fails before the switch is committed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FailurePolicy {
+    /// Abort the transition
+    #[default]
+    Abort,
+    /// Log the failure and continue with the transition
+    Continue,
+}
+
+/// Hook that runs on mode transitions
+#[async_trait]
+pub trait TransitionMiddleware: Send + Sync {
+    /// Middleware name, used in errors and logs
+    fn name(&self) -> &str;
+
+    /// Position in the middleware chain (lower runs first)
+    fn order(&self) -> i32 {
+        0
+    }
+
+    /// What happens when [`Self::on_transition`] fails
+    fn failure_policy(&self) -> FailurePolicy {
+        FailurePolicy::Abort
+    }
+
+    /// Runs before the switch is committed
+    ///
+    /// `outgoing` is the context saved for the mode being left; `incoming` is
+    /// the context the target mode will start with.
+    async fn on_transition(
+        &self,
+        _transition: &ModeTransition,
+        _outgoing: &mut ModeContext,
+        _incoming: &mut ModeContext,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// Runs after the switch is committed
+    async fn after_transition(
+        &self,
+        _transition: &ModeTransition,
+        _context: &ModeContext,
+    ) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Summarizes the Vibe Mode conversation into a spec when switching to Code Mode
+///
+/// The spec is stored in the incoming context under [`VIBE_SPEC_KEY`].
+#[derive(Debug, Clone, Default)]
+pub struct VibeSpecMiddleware {
+    vibe: VibeMode,
+}
+
+impl VibeSpecMiddleware {
+    /// Create a new Vibe spec middleware
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl TransitionMiddleware for VibeSpecMiddleware {
+    fn name(&self) -> &str {
+        "vibe-spec"
+    }
+
+    fn failure_policy(&self) -> FailurePolicy {
+        FailurePolicy::Continue
+    }
+
+    async fn on_transition(
+        &self,
+        transition: &ModeTransition,
+        outgoing: &mut ModeContext,
+        incoming: &mut ModeContext,
+    ) -> Result<()> {
+        if !transition.is("vibe", "code") || outgoing.conversation_history.is_empty() {
+            return Ok(());
+        }
+
+        let code = outgoing
+            .conversation_history
+            .iter()
+            .map(|message| message.content.as_str())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let spec = self.vibe.convert_project_to_specs(&code, outgoing)?;
+        incoming
+            .custom
+            .insert(VIBE_SPEC_KEY.to_string(), serde_json::Value::String(spec));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::MessageRole;
+
+    #[test]
+    fn test_mode_transition_is() {
+        let transition = ModeTransition {
+            from: Some("vibe".to_string()),
+            to: "code".to_string(),
+        };
+        assert!(transition.is("vibe", "code"));
+        assert!(!transition.is("code", "vibe"));
+
+        let first = ModeTransition {
+            from: None,
+            to: "code".to_string(),
+        };
+        assert!(!first.is("vibe", "code"));
+    }
+
+    #[tokio::test]
+    async fn test_vibe_spec_middleware() {
+        let middleware = VibeSpecMiddleware::new();
+        let mut outgoing = ModeContext::new("test-session".to_string());
+        outgoing.add_message(MessageRole::Assistant, "fn parse() {}".to_string());
+        let mut incoming = ModeContext::new("test-session".to_string());
+
+        let other = ModeTransition {
+            from: Some("ask".to_string()),
+            to: "code".to_string(),
+        };
+        middleware
+            .on_transition(&other, &mut outgoing, &mut incoming)
+            .await
+            .unwrap();
+        assert!(!incoming.custom.contains_key(VIBE_SPEC_KEY));
+
+        let transition = ModeTransition {
+            from: Some("vibe".to_string()),
+            to: "code".to_string(),
+        };
+        middleware
+            .on_transition(&transition, &mut outgoing, &mut incoming)
+            .await
+            .unwrap();
+        let spec = incoming.custom[VIBE_SPEC_KEY].as_str().unwrap();
+        assert!(spec.contains("fn parse() {}"));
+    }
+}
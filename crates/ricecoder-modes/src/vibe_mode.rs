@@ -50,6 +50,8 @@ impl VibeMode {
                     require_specs: false,
                     auto_think_more_threshold: None,
                 },
+                tool_policy: None,
+                model_params: Default::default(),
             },
         }
     }
@@ -589,6 +591,8 @@ mod tests {
                 require_specs: false,
                 auto_think_more_threshold: None,
             },
+            tool_policy: None,
+            model_params: Default::default(),
         };
         let mode = VibeMode::with_config(custom_config);
         assert_eq!(mode.config().temperature, 0.8);
@@ -749,6 +753,8 @@ mod tests {
                 require_specs: true,
                 auto_think_more_threshold: None,
             },
+            tool_policy: None,
+            model_params: Default::default(),
         };
         let mode = VibeMode::with_config(custom_config);
         let result = mode.accept_natural_language("test input");
@@ -769,6 +775,8 @@ mod tests {
                 require_specs: false,
                 auto_think_more_threshold: None,
             },
+            tool_policy: None,
+            model_params: Default::default(),
         };
         let mode = VibeMode::with_config(custom_config);
         let result = mode.generate_code_from_description("test");
@@ -789,6 +797,8 @@ mod tests {
                 require_specs: false,
                 auto_think_more_threshold: None,
             },
+            tool_policy: None,
+            model_params: Default::default(),
         };
         let mode = VibeMode::with_config(custom_config);
         let result = mode.iterate_rapidly("test");
@@ -809,6 +819,8 @@ mod tests {
                 require_specs: false,
                 auto_think_more_threshold: None,
             },
+            tool_policy: None,
+            model_params: Default::default(),
         };
         let mode = VibeMode::with_config(custom_config);
         let result = mode.generate_with_iterations("test", 3);
@@ -0,0 +1,884 @@
+//! Continuous benchmark harness with per-machine baseline history
+//!
+//! Registered micro and macro benchmarks (startup, completion latency, search
+//! throughput, ...) are run on demand, summarized as [`BaselineData`], and
+//! compared against the stored history for the current machine profile. A
+//! change only counts as a regression when it exceeds the configured threshold
+//! *and* is statistically significant (Mann-Whitney U test), so noisy runs do
+//! not fail CI.
+
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    process::Command,
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sysinfo::System;
+use thiserror::Error;
+use walkdir::WalkDir;
+
+use crate::{
+    baseline::BaselineData,
+    monitor::{PerformanceMetrics, PerformanceMonitor},
+    regression::{RegressionAlert, RegressionConfig},
+};
+
+/// Version of the on-disk history format
+pub const HISTORY_FORMAT_VERSION: u32 = 1;
+
+/// Errors produced by the benchmark harness
+#[derive(Debug, Error)]
+pub enum BenchError {
+    #[error("Unknown benchmark: {0}")]
+    UnknownBenchmark(String),
+
+    #[error("Benchmark '{name}' failed: {message}")]
+    BenchmarkFailed { name: String, message: String },
+
+    #[error("Baseline storage error: {0}")]
+    Storage(String),
+
+    #[error("{} benchmark regression(s) detected", .0.regressions().count())]
+    Regression(Box<RegressionReport>),
+}
+
+/// Benchmark granularity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BenchmarkKind {
+    /// Small in-process operation (e.g. completion latency)
+    Micro,
+    /// End-to-end operation (e.g. process startup, workspace search)
+    Macro,
+}
+
+impl fmt::Display for BenchmarkKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BenchmarkKind::Micro => write!(f, "micro"),
+            BenchmarkKind::Macro => write!(f, "macro"),
+        }
+    }
+}
+
+/// A benchmark the harness can time
+pub trait Benchmark: Send + Sync {
+    /// Unique benchmark name, used as the baseline key
+    fn name(&self) -> &str;
+
+    /// Benchmark granularity
+    fn kind(&self) -> BenchmarkKind;
+
+    /// Run a single timed iteration
+    fn run_iteration(&self) -> Result<(), String>;
+}
+
+type IterationFn = Box<dyn Fn() -> Result<(), String> + Send + Sync>;
+
+/// Benchmark backed by a closure
+pub struct FnBenchmark {
+    name: String,
+    kind: BenchmarkKind,
+    iteration: IterationFn,
+}
+
+impl FnBenchmark {
+    /// Create a closure benchmark
+    pub fn new<F>(name: impl Into<String>, kind: BenchmarkKind, iteration: F) -> Self
+    where
+        F: Fn() -> Result<(), String> + Send + Sync + 'static,
+    {
+        Self {
+            name: name.into(),
+            kind,
+            iteration: Box::new(iteration),
+        }
+    }
+}
+
+impl Benchmark for FnBenchmark {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn kind(&self) -> BenchmarkKind {
+        self.kind
+    }
+
+    fn run_iteration(&self) -> Result<(), String> {
+        (self.iteration)()
+    }
+}
+
+/// Times spawning a command to completion (e.g. `ricecoder --version`)
+pub struct CommandBenchmark {
+    name: String,
+    program: PathBuf,
+    args: Vec<String>,
+}
+
+impl CommandBenchmark {
+    /// Create a command benchmark
+    pub fn new(name: impl Into<String>, program: impl Into<PathBuf>) -> Self {
+        Self {
+            name: name.into(),
+            program: program.into(),
+            args: Vec::new(),
+        }
+    }
+
+    /// Cold-start benchmark for a ricecoder binary
+    pub fn startup(binary: impl Into<PathBuf>) -> Self {
+        Self::new("startup", binary).with_args(["--version"])
+    }
+
+    /// Set the command arguments
+    pub fn with_args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.args = args.into_iter().map(Into::into).collect();
+        self
+    }
+}
+
+impl Benchmark for CommandBenchmark {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn kind(&self) -> BenchmarkKind {
+        BenchmarkKind::Macro
+    }
+
+    fn run_iteration(&self) -> Result<(), String> {
+        let output = Command::new(&self.program)
+            .args(&self.args)
+            .output()
+            .map_err(|e| format!("failed to spawn {}: {}", self.program.display(), e))?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(format!(
+                "{} exited with {}",
+                self.program.display(),
+                output.status
+            ))
+        }
+    }
+}
+
+/// Regex search over every file below a root directory
+pub struct SearchBenchmark {
+    name: String,
+    root: PathBuf,
+    pattern: Regex,
+}
+
+impl SearchBenchmark {
+    /// Create a search throughput benchmark
+    pub fn new(root: impl Into<PathBuf>, pattern: &str) -> Result<Self, BenchError> {
+        let pattern = Regex::new(pattern).map_err(|e| BenchError::BenchmarkFailed {
+            name: "search_throughput".to_string(),
+            message: e.to_string(),
+        })?;
+        Ok(Self {
+            name: "search_throughput".to_string(),
+            root: root.into(),
+            pattern,
+        })
+    }
+
+    /// Override the benchmark name
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+}
+
+impl Benchmark for SearchBenchmark {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn kind(&self) -> BenchmarkKind {
+        BenchmarkKind::Macro
+    }
+
+    fn run_iteration(&self) -> Result<(), String> {
+        let mut matches = 0usize;
+        for entry in WalkDir::new(&self.root)
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|e| e.file_type().is_file())
+        {
+            // Binary and unreadable files are skipped, as a real search would
+            if let Ok(content) = std::fs::read_to_string(entry.path()) {
+                matches += self.pattern.find_iter(&content).count();
+            }
+        }
+        std::hint::black_box(matches);
+        Ok(())
+    }
+}
+
+/// Harness configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchConfig {
+    /// Untimed iterations run before sampling
+    pub warmup_iterations: usize,
+    /// Timed iterations per benchmark
+    pub iterations: usize,
+    /// Stop sampling a benchmark after this long (keeps macro benches bounded)
+    pub max_duration: Duration,
+    /// Two-sided significance level for the Mann-Whitney U test
+    pub significance_level: f64,
+    /// Number of previous runs pooled into the comparison baseline
+    pub baseline_window: usize,
+    /// Maximum runs kept per machine history
+    pub history_limit: usize,
+    /// Median change thresholds
+    pub regression: RegressionConfig,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            warmup_iterations: 2,
+            iterations: 20,
+            max_duration: Duration::from_secs(30),
+            significance_level: 0.05,
+            baseline_window: 3,
+            history_limit: 50,
+            regression: RegressionConfig::default(),
+        }
+    }
+}
+
+/// Hardware/OS profile results are keyed by
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MachineProfile {
+    /// Stable identifier derived from the fields below
+    pub id: String,
+    pub os: String,
+    pub arch: String,
+    pub cpu_brand: String,
+    pub cpu_count: usize,
+    pub total_memory_bytes: u64,
+}
+
+impl MachineProfile {
+    /// Detect the profile of the current machine
+    pub fn detect() -> Self {
+        let mut system = System::new();
+        system.refresh_cpu();
+        system.refresh_memory();
+
+        let cpu_brand = system
+            .cpus()
+            .first()
+            .map(|cpu| cpu.brand().trim().to_string())
+            .unwrap_or_default();
+
+        Self::new(
+            System::name().unwrap_or_else(|| std::env::consts::OS.to_string()),
+            std::env::consts::ARCH,
+            cpu_brand,
+            system.cpus().len(),
+            system.total_memory(),
+        )
+    }
+
+    /// Build a profile from explicit values
+    pub fn new(
+        os: impl Into<String>,
+        arch: impl Into<String>,
+        cpu_brand: impl Into<String>,
+        cpu_count: usize,
+        total_memory_bytes: u64,
+    ) -> Self {
+        let os = os.into();
+        let arch = arch.into();
+        let cpu_brand = cpu_brand.into();
+        let memory_gb = (total_memory_bytes as f64 / 1024.0_f64.powi(3)).round() as u64;
+        let id = slug(&format!(
+            "{}-{}-{}-{}c-{}g",
+            os, arch, cpu_brand, cpu_count, memory_gb
+        ));
+        Self {
+            id,
+            os,
+            arch,
+            cpu_brand,
+            cpu_count,
+            total_memory_bytes,
+        }
+    }
+}
+
+/// Lowercase, filesystem-safe identifier
+fn slug(value: &str) -> String {
+    let mut slug = String::with_capacity(value.len());
+    for c in value.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_matches('-').to_string()
+}
+
+/// Result of a single benchmark in a run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchResult {
+    pub name: String,
+    pub kind: BenchmarkKind,
+    /// Summary statistics
+    pub baseline: BaselineData,
+    /// Raw iteration timings, kept for significance testing
+    pub samples_ns: Vec<u64>,
+}
+
+impl BenchResult {
+    /// Median iteration time
+    pub fn median_ns(&self) -> u64 {
+        median(&self.samples_ns)
+    }
+
+    /// Metrics view for [`crate::PerformanceRegressionDetector`]
+    pub fn to_metrics(&self) -> PerformanceMetrics {
+        PerformanceMetrics {
+            test_name: self.name.clone(),
+            mean_time_ns: self.baseline.mean_time_ns,
+            std_dev_ns: self.baseline.std_dev_ns,
+            p95_time_ns: self.baseline.p95_time_ns,
+            p99_time_ns: self.baseline.p99_time_ns,
+            sample_size: self.baseline.sample_size,
+            peak_memory_bytes: 0,
+            avg_cpu_percent: 0.0,
+            timestamp: self.baseline.timestamp,
+        }
+    }
+}
+
+/// One invocation of the harness
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchRun {
+    /// Version of the code under test (crate version, git revision, ...)
+    pub version: String,
+    pub timestamp: DateTime<Utc>,
+    pub results: Vec<BenchResult>,
+}
+
+impl BenchRun {
+    /// Result for a benchmark, if it ran
+    pub fn result(&self, name: &str) -> Option<&BenchResult> {
+        self.results.iter().find(|r| r.name == name)
+    }
+}
+
+/// All stored runs for one machine profile, oldest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchHistory {
+    pub format_version: u32,
+    pub machine: MachineProfile,
+    pub runs: Vec<BenchRun>,
+}
+
+impl BenchHistory {
+    /// Empty history for a machine
+    pub fn new(machine: MachineProfile) -> Self {
+        Self {
+            format_version: HISTORY_FORMAT_VERSION,
+            machine,
+            runs: Vec::new(),
+        }
+    }
+
+    /// Append a run, dropping the oldest beyond `limit`
+    pub fn push(&mut self, run: BenchRun, limit: usize) {
+        self.runs.push(run);
+        if self.runs.len() > limit {
+            let excess = self.runs.len() - limit;
+            self.runs.drain(..excess);
+        }
+    }
+
+    /// Most recent `window` results for a benchmark, newest first
+    pub fn recent_results(&self, name: &str, window: usize) -> Vec<(&BenchRun, &BenchResult)> {
+        self.runs
+            .iter()
+            .rev()
+            .filter_map(|run| run.result(name).map(|result| (run, result)))
+            .take(window)
+            .collect()
+    }
+}
+
+/// Directory of per-machine history files (`<dir>/<machine-id>.json`)
+#[derive(Debug, Clone)]
+pub struct BaselineStore {
+    dir: PathBuf,
+}
+
+impl BaselineStore {
+    /// Store rooted at `dir`
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// History file for a machine
+    pub fn path_for(&self, machine: &MachineProfile) -> PathBuf {
+        self.dir.join(format!("{}.json", machine.id))
+    }
+
+    /// Load the history for a machine, or an empty one if none exists
+    pub fn load(&self, machine: &MachineProfile) -> Result<BenchHistory, BenchError> {
+        let path = self.path_for(machine);
+        if !path.exists() {
+            return Ok(BenchHistory::new(machine.clone()));
+        }
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| BenchError::Storage(format!("{}: {}", path.display(), e)))?;
+        let history: BenchHistory = serde_json::from_str(&content)
+            .map_err(|e| BenchError::Storage(format!("{}: {}", path.display(), e)))?;
+        if history.format_version > HISTORY_FORMAT_VERSION {
+            return Err(BenchError::Storage(format!(
+                "{}: unsupported history format version {}",
+                path.display(),
+                history.format_version
+            )));
+        }
+        Ok(history)
+    }
+
+    /// Persist a history
+    pub fn save(&self, history: &BenchHistory) -> Result<(), BenchError> {
+        std::fs::create_dir_all(&self.dir)
+            .map_err(|e| BenchError::Storage(format!("{}: {}", self.dir.display(), e)))?;
+        let path = self.path_for(&history.machine);
+        let content = serde_json::to_string_pretty(history)
+            .map_err(|e| BenchError::Storage(e.to_string()))?;
+        std::fs::write(&path, content)
+            .map_err(|e| BenchError::Storage(format!("{}: {}", path.display(), e)))
+    }
+}
+
+/// Outcome of comparing a benchmark with its history
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Verdict {
+    /// Significantly slower by more than the threshold
+    Regressed,
+    /// Significantly faster by more than the threshold
+    Improved,
+    /// Within noise or below the threshold
+    Unchanged,
+    /// No history to compare against
+    NoBaseline,
+}
+
+/// Comparison of one benchmark against its baseline window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchComparison {
+    pub name: String,
+    pub kind: BenchmarkKind,
+    /// Version of the most recent run in the baseline window
+    pub baseline_version: Option<String>,
+    pub baseline_median_ns: Option<u64>,
+    pub baseline_p95_ns: Option<u64>,
+    pub current_median_ns: u64,
+    pub current_p95_ns: u64,
+    /// Median change, positive when slower
+    pub change_percent: f64,
+    /// Two-sided Mann-Whitney U p-value, if both sides had enough samples
+    pub p_value: Option<f64>,
+    pub verdict: Verdict,
+}
+
+/// Structured result of a harness run, consumable by the regression detector
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegressionReport {
+    pub machine: MachineProfile,
+    pub version: String,
+    pub timestamp: DateTime<Utc>,
+    pub threshold_percent: f64,
+    pub significance_level: f64,
+    pub comparisons: Vec<BenchComparison>,
+}
+
+impl RegressionReport {
+    /// Comparisons that regressed
+    pub fn regressions(&self) -> impl Iterator<Item = &BenchComparison> {
+        self.comparisons
+            .iter()
+            .filter(|c| c.verdict == Verdict::Regressed)
+    }
+
+    /// Whether any benchmark regressed
+    pub fn has_regressions(&self) -> bool {
+        self.regressions().next().is_some()
+    }
+
+    /// Regressions as detector alerts
+    pub fn to_alerts(&self) -> Vec<RegressionAlert> {
+        self.regressions()
+            .map(|c| RegressionAlert::PerformanceDegradation {
+                test_name: c.name.clone(),
+                baseline_p95_ns: c.baseline_p95_ns.unwrap_or_default(),
+                current_p95_ns: c.current_p95_ns,
+                degradation_percent: c.change_percent,
+                threshold_percent: self.threshold_percent,
+            })
+            .collect()
+    }
+
+    /// Human-readable summary, one line per benchmark
+    pub fn summary(&self) -> String {
+        let mut out = format!(
+            "Benchmarks for {} on {} ({})\n",
+            self.version, self.machine.id, self.timestamp
+        );
+        for c in &self.comparisons {
+            let baseline = c
+                .baseline_median_ns
+                .map(|ns| format!("{:.3}ms", ns as f64 / 1_000_000.0))
+                .unwrap_or_else(|| "-".to_string());
+            let p_value = c
+                .p_value
+                .map(|p| format!("p={:.4}", p))
+                .unwrap_or_else(|| "p=n/a".to_string());
+            out.push_str(&format!(
+                "  {:<24} {:>5} {:>12} -> {:>10.3}ms {:>+8.1}% {:<10} {:?}\n",
+                c.name,
+                c.kind.to_string(),
+                baseline,
+                c.current_median_ns as f64 / 1_000_000.0,
+                c.change_percent,
+                p_value,
+                c.verdict
+            ));
+        }
+        out
+    }
+}
+
+/// Runs registered benchmarks and checks them against per-machine history
+pub struct BenchHarness {
+    benchmarks: Vec<Box<dyn Benchmark>>,
+    config: BenchConfig,
+    store: BaselineStore,
+    machine: MachineProfile,
+    version: String,
+}
+
+impl BenchHarness {
+    /// Harness storing history below `store_dir` for the detected machine
+    pub fn new(store_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            benchmarks: Vec::new(),
+            config: BenchConfig::default(),
+            store: BaselineStore::new(store_dir),
+            machine: MachineProfile::detect(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+
+    /// Set the harness configuration
+    pub fn with_config(mut self, config: BenchConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Override the machine profile (e.g. a fixed CI runner id)
+    pub fn with_machine(mut self, machine: MachineProfile) -> Self {
+        self.machine = machine;
+        self
+    }
+
+    /// Version recorded with each run
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = version.into();
+        self
+    }
+
+    /// Register the startup and search throughput benchmarks
+    pub fn with_default_benchmarks(
+        mut self,
+        binary: Option<&Path>,
+        search_root: Option<&Path>,
+    ) -> Result<Self, BenchError> {
+        if let Some(binary) = binary {
+            self.register(CommandBenchmark::startup(binary));
+        }
+        if let Some(root) = search_root {
+            self.register(SearchBenchmark::new(root, r"fn\s+\w+")?);
+        }
+        Ok(self)
+    }
+
+    /// Register a benchmark, replacing any with the same name
+    pub fn register(&mut self, benchmark: impl Benchmark + 'static) {
+        self.benchmarks.retain(|b| b.name() != benchmark.name());
+        self.benchmarks.push(Box::new(benchmark));
+    }
+
+    /// Register a closure benchmark (e.g. completion latency)
+    pub fn register_fn<F>(&mut self, name: impl Into<String>, kind: BenchmarkKind, iteration: F)
+    where
+        F: Fn() -> Result<(), String> + Send + Sync + 'static,
+    {
+        self.register(FnBenchmark::new(name, kind, iteration));
+    }
+
+    /// Names of registered benchmarks
+    pub fn benchmark_names(&self) -> Vec<&str> {
+        self.benchmarks.iter().map(|b| b.name()).collect()
+    }
+
+    /// Machine profile results are stored under
+    pub fn machine(&self) -> &MachineProfile {
+        &self.machine
+    }
+
+    /// Baseline store
+    pub fn store(&self) -> &BaselineStore {
+        &self.store
+    }
+
+    /// Run benchmarks (all, or only those named) without touching history
+    pub fn run(&self, only: &[String]) -> Result<BenchRun, BenchError> {
+        if let Some(unknown) = only
+            .iter()
+            .find(|name| !self.benchmarks.iter().any(|b| b.name() == name.as_str()))
+        {
+            return Err(BenchError::UnknownBenchmark(unknown.clone()));
+        }
+
+        let mut results = Vec::new();
+        for benchmark in &self.benchmarks {
+            if only.is_empty() || only.iter().any(|name| name == benchmark.name()) {
+                results.push(self.run_benchmark(benchmark.as_ref())?);
+            }
+        }
+
+        Ok(BenchRun {
+            version: self.version.clone(),
+            timestamp: Utc::now(),
+            results,
+        })
+    }
+
+    fn run_benchmark(&self, benchmark: &dyn Benchmark) -> Result<BenchResult, BenchError> {
+        let failed = |message: String| BenchError::BenchmarkFailed {
+            name: benchmark.name().to_string(),
+            message,
+        };
+
+        for _ in 0..self.config.warmup_iterations {
+            benchmark.run_iteration().map_err(failed)?;
+        }
+
+        let mut monitor = PerformanceMonitor::new(benchmark.name().to_string());
+        let mut samples_ns = Vec::with_capacity(self.config.iterations);
+        let started = Instant::now();
+        for _ in 0..self.config.iterations.max(1) {
+            let start = Instant::now();
+            benchmark.run_iteration().map_err(failed)?;
+            let elapsed = start.elapsed();
+            monitor.record_measurement(elapsed);
+            samples_ns.push(elapsed.as_nanos() as u64);
+            if started.elapsed() >= self.config.max_duration {
+                break;
+            }
+        }
+
+        let metrics = monitor.get_metrics();
+        Ok(BenchResult {
+            name: benchmark.name().to_string(),
+            kind: benchmark.kind(),
+            baseline: BaselineData {
+                test_name: metrics.test_name,
+                mean_time_ns: metrics.mean_time_ns,
+                std_dev_ns: metrics.std_dev_ns,
+                p95_time_ns: metrics.p95_time_ns,
+                p99_time_ns: metrics.p99_time_ns,
+                sample_size: metrics.sample_size,
+                timestamp: metrics.timestamp,
+                target_threshold_ns: None,
+            },
+            samples_ns,
+        })
+    }
+
+    /// Compare a run against the history without modifying either
+    pub fn compare(&self, run: &BenchRun, history: &BenchHistory) -> RegressionReport {
+        let threshold = self.config.regression.performance_threshold_percent;
+        let comparisons = run
+            .results
+            .iter()
+            .map(|result| {
+                let window = history.recent_results(&result.name, self.config.baseline_window);
+                let current_median_ns = result.median_ns();
+                if window.is_empty() {
+                    return BenchComparison {
+                        name: result.name.clone(),
+                        kind: result.kind,
+                        baseline_version: None,
+                        baseline_median_ns: None,
+                        baseline_p95_ns: None,
+                        current_median_ns,
+                        current_p95_ns: result.baseline.p95_time_ns,
+                        change_percent: 0.0,
+                        p_value: None,
+                        verdict: Verdict::NoBaseline,
+                    };
+                }
+
+                let pooled: Vec<u64> = window
+                    .iter()
+                    .flat_map(|(_, r)| r.samples_ns.iter().copied())
+                    .collect();
+                let baseline_median_ns = median(&pooled);
+                let change_percent = if baseline_median_ns == 0 {
+                    0.0
+                } else {
+                    (current_median_ns as f64 - baseline_median_ns as f64)
+                        / baseline_median_ns as f64
+                        * 100.0
+                };
+                let p_value = mann_whitney_p_value(&pooled, &result.samples_ns);
+                let significant = p_value.is_some_and(|p| p < self.config.significance_level);
+                let verdict = if significant && change_percent > threshold {
+                    Verdict::Regressed
+                } else if significant && change_percent < -threshold {
+                    Verdict::Improved
+                } else {
+                    Verdict::Unchanged
+                };
+
+                BenchComparison {
+                    name: result.name.clone(),
+                    kind: result.kind,
+                    baseline_version: Some(window[0].0.version.clone()),
+                    baseline_median_ns: Some(baseline_median_ns),
+                    baseline_p95_ns: Some(window[0].1.baseline.p95_time_ns),
+                    current_median_ns,
+                    current_p95_ns: result.baseline.p95_time_ns,
+                    change_percent,
+                    p_value,
+                    verdict,
+                }
+            })
+            .collect();
+
+        RegressionReport {
+            machine: self.machine.clone(),
+            version: run.version.clone(),
+            timestamp: run.timestamp,
+            threshold_percent: threshold,
+            significance_level: self.config.significance_level,
+            comparisons,
+        }
+    }
+
+    /// Append a run to the machine history unconditionally (accepts regressions)
+    pub fn record(&self, run: BenchRun) -> Result<(), BenchError> {
+        let mut history = self.store.load(&self.machine)?;
+        history.push(run, self.config.history_limit);
+        self.store.save(&history)
+    }
+
+    /// Run, compare against history, and record the run if nothing regressed
+    ///
+    /// Regressed runs are not recorded so they cannot silently become the new
+    /// baseline; use [`BenchHarness::record`] to accept them explicitly.
+    pub fn run_and_check(&self, only: &[String]) -> Result<RegressionReport, BenchError> {
+        let run = self.run(only)?;
+        let mut history = self.store.load(&self.machine)?;
+        let report = self.compare(&run, &history);
+        if report.has_regressions() {
+            return Err(BenchError::Regression(Box::new(report)));
+        }
+        history.push(run, self.config.history_limit);
+        self.store.save(&history)?;
+        Ok(report)
+    }
+}
+
+fn median(samples: &[u64]) -> u64 {
+    if samples.is_empty() {
+        return 0;
+    }
+    let mut sorted = samples.to_vec();
+    sorted.sort_unstable();
+    let mid = sorted.len() / 2;
+    if sorted.len() % 2 == 0 {
+        (sorted[mid - 1] + sorted[mid]) / 2
+    } else {
+        sorted[mid]
+    }
+}
+
+/// Two-sided Mann-Whitney U test using the tie-corrected normal approximation
+///
+/// Returns `None` when either side has fewer than 3 samples.
+fn mann_whitney_p_value(a: &[u64], b: &[u64]) -> Option<f64> {
+    let (n1, n2) = (a.len(), b.len());
+    if n1 < 3 || n2 < 3 {
+        return None;
+    }
+
+    let mut combined: Vec<(u64, bool)> = a
+        .iter()
+        .map(|&v| (v, true))
+        .chain(b.iter().map(|&v| (v, false)))
+        .collect();
+    combined.sort_unstable_by_key(|&(v, _)| v);
+
+    // Average ranks over ties
+    let n = combined.len();
+    let mut rank_sum_a = 0.0;
+    let mut tie_term = 0.0;
+    let mut i = 0;
+    while i < n {
+        let mut j = i;
+        while j + 1 < n && combined[j + 1].0 == combined[i].0 {
+            j += 1;
+        }
+        let rank = (i + j) as f64 / 2.0 + 1.0;
+        let ties = (j - i + 1) as f64;
+        tie_term += ties.powi(3) - ties;
+        rank_sum_a += combined[i..=j].iter().filter(|(_, in_a)| *in_a).count() as f64 * rank;
+        i = j + 1;
+    }
+
+    let (n1, n2, n) = (n1 as f64, n2 as f64, n as f64);
+    let u = rank_sum_a - n1 * (n1 + 1.0) / 2.0;
+    let mean = n1 * n2 / 2.0;
+    let variance = n1 * n2 / 12.0 * ((n + 1.0) - tie_term / (n * (n - 1.0)));
+    if variance <= 0.0 {
+        // Every sample identical
+        return Some(1.0);
+    }
+
+    let z = (u - mean).abs() / variance.sqrt();
+    Some((2.0 * (1.0 - standard_normal_cdf(z))).clamp(0.0, 1.0))
+}
+
+/// Standard normal CDF via the Abramowitz-Stegun erf approximation (7.1.26)
+fn standard_normal_cdf(z: f64) -> f64 {
+    let x = z / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.327_591_1 * x.abs());
+    let poly = t
+        * (0.254_829_592
+            + t * (-0.284_496_736
+                + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+    let erf = 1.0 - poly * (-x * x).exp();
+    let erf = if x < 0.0 { -erf } else { erf };
+    0.5 * (1.0 + erf)
+}
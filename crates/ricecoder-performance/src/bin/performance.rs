@@ -2,9 +2,10 @@ use std::path::PathBuf;
 
 use clap::{Parser, Subcommand};
 use ricecoder_performance::{
-    create_default_pipeline, AlertConfig, AlertDestination, AlertSeverity, EnterpriseMonitor,
-    EnterpriseSimulator, OptimizationPipeline, PerformanceBaseline, PerformanceProfiler,
-    PerformanceRegressionDetector, PerformanceValidator,
+    create_default_pipeline, AlertConfig, AlertDestination, AlertSeverity, BenchConfig, BenchError,
+    BenchHarness, EnterpriseMonitor, EnterpriseSimulator, OptimizationPipeline,
+    PerformanceBaseline, PerformanceProfiler, PerformanceRegressionDetector, PerformanceValidator,
+    SamplingConfig,
 };
 
 /// RiceCoder Performance Validation Tool
@@ -57,6 +58,14 @@ enum Commands {
         /// Output profile report file
         #[arg(short, long)]
         output: Option<PathBuf>,
+
+        /// Write a flamegraph SVG of sampled stacks
+        #[arg(long)]
+        flamegraph: Option<PathBuf>,
+
+        /// Write a gzipped pprof profile of sampled stacks
+        #[arg(long)]
+        pprof: Option<PathBuf>,
     },
     /// Run optimization pipeline
     Optimize {
@@ -108,6 +117,40 @@ enum Commands {
         #[arg(short, long)]
         output: Option<PathBuf>,
     },
+    /// Run benchmarks and compare against this machine's baseline history
+    Bench {
+        /// Path to ricecoder binary (enables the startup benchmark)
+        #[arg(short, long)]
+        binary: Option<PathBuf>,
+
+        /// Directory to search (enables the search throughput benchmark)
+        #[arg(long)]
+        search_root: Option<PathBuf>,
+
+        /// Directory holding per-machine baseline history
+        #[arg(long, default_value = ".ricecoder/bench")]
+        store: PathBuf,
+
+        /// Only run the named benchmarks
+        #[arg(long)]
+        only: Vec<String>,
+
+        /// Timed iterations per benchmark
+        #[arg(short, long, default_value = "20")]
+        iterations: usize,
+
+        /// Version label recorded with the run
+        #[arg(long)]
+        version: Option<String>,
+
+        /// Record the run even if it regressed
+        #[arg(long)]
+        accept: bool,
+
+        /// Output JSON regression report file
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
 }
 
 #[tokio::main]
@@ -236,8 +279,19 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             }
         }
 
-        Commands::Profile { binary, output } => {
+        Commands::Profile {
+            binary,
+            output,
+            flamegraph,
+            pprof,
+        } => {
             let mut profiler = PerformanceProfiler::new();
+            if flamegraph.is_some() || pprof.is_some() {
+                profiler.enable_sampling(SamplingConfig {
+                    interval: std::time::Duration::from_millis(1),
+                    ..SamplingConfig::default()
+                });
+            }
 
             // Profile key operations
             profiler.start_profiling();
@@ -256,6 +310,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
             let profile_result = profiler.stop_profiling();
 
+            if let Some(capture) = profiler.capture_samples(None) {
+                if let Some(path) = flamegraph {
+                    std::fs::write(&path, capture.to_flamegraph_svg("ricecoder profile"))?;
+                    println!("🔥 Flamegraph saved to: {}", path.display());
+                }
+                if let Some(path) = pprof {
+                    std::fs::write(&path, capture.to_pprof()?)?;
+                    println!("✅ pprof profile saved to: {}", path.display());
+                }
+            }
+
             // Generate and output report
             let report = profile_result.generate_report();
 
@@ -458,6 +523,67 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 std::process::exit(1);
             }
         }
+
+        Commands::Bench {
+            binary,
+            search_root,
+            store,
+            only,
+            iterations,
+            version,
+            accept,
+            output,
+        } => {
+            let mut harness = BenchHarness::new(store)
+                .with_config(BenchConfig {
+                    iterations,
+                    ..BenchConfig::default()
+                })
+                .with_default_benchmarks(binary.as_deref(), search_root.as_deref())?;
+            if let Some(version) = version {
+                harness = harness.with_version(version);
+            }
+            if harness.benchmark_names().is_empty() {
+                println!("❌ No benchmarks registered. Pass --binary and/or --search-root.");
+                std::process::exit(1);
+            }
+
+            println!(
+                "🏁 Running benchmarks on machine profile {}",
+                harness.machine().id
+            );
+            let (report, regressed) = if accept {
+                let run = harness.run(&only)?;
+                let history = harness.store().load(harness.machine())?;
+                let report = harness.compare(&run, &history);
+                harness.record(run)?;
+                (report, false)
+            } else {
+                match harness.run_and_check(&only) {
+                    Ok(report) => (report, false),
+                    Err(BenchError::Regression(report)) => (*report, true),
+                    Err(e) => return Err(e.into()),
+                }
+            };
+
+            println!("{}", report.summary());
+            if let Some(path) = output {
+                std::fs::write(&path, serde_json::to_string_pretty(&report)?)?;
+                println!("✅ Regression report saved to: {}", path.display());
+            }
+
+            if regressed {
+                println!("❌ Benchmark regressions detected (run not recorded):");
+                for comparison in report.regressions() {
+                    println!(
+                        "  📉 {}: {:+.1}% median change",
+                        comparison.name, comparison.change_percent
+                    );
+                }
+                std::process::exit(1);
+            }
+            println!("✅ No benchmark regressions detected!");
+        }
     }
 
     Ok(())
@@ -7,8 +7,12 @@
 //! - Large project support validation
 //! - Concurrent session testing
 //! - Performance regression detection with automated alerting
+//! - Continuous benchmarking against per-machine baseline history
+//! - Always-on sampling profiler with flamegraph and pprof export
+//! - Startup phase tracing with per-phase budgets
 
 pub mod baseline;
+pub mod bench;
 pub mod detector;
 pub mod enterprise;
 pub mod memory;
@@ -16,18 +20,29 @@ pub mod monitor;
 pub mod optimization;
 pub mod profiler;
 pub mod regression;
+pub mod sampling;
 pub mod simulation;
+pub mod startup;
 pub mod validation;
 
 pub use baseline::{BaselineData, PerformanceBaseline};
+pub use bench::{
+    BaselineStore, BenchConfig, BenchError, BenchHarness, BenchHistory, BenchRun, Benchmark,
+    BenchmarkKind, MachineProfile, RegressionReport, Verdict,
+};
 pub use detector::PerformanceRegressionDetector;
 pub use enterprise::{AlertConfig, AlertDestination, AlertSeverity, EnterpriseMonitor, SmtpConfig};
-pub use memory::MemoryProfiler;
+pub use memory::{
+    AttributionConfig, MemoryAttributionReport, MemoryProfiler, SubsystemTracker, SubsystemUsage,
+    TrackingToken,
+};
 pub use monitor::{PerformanceMetrics, PerformanceMonitor};
 pub use optimization::{
     create_default_pipeline, OptimizationPipeline, OptimizationPriority, OptimizationResult,
 };
 pub use profiler::{PerformanceProfiler, ProfileResult};
 pub use regression::{RegressionAlert, RegressionConfig};
+pub use sampling::{SampleCapture, SamplerHandle, SamplingConfig, SamplingProfiler};
 pub use simulation::{EnterpriseSimulator, SimulationResult};
+pub use startup::{PhaseTiming, StartupBudgets, StartupReport, StartupTracer};
 pub use validation::{PerformanceValidator, ValidationResult};
@@ -1,13 +1,23 @@
 //! Memory profiling utilities
 
-use std::time::{Duration, Instant};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
 
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use sysinfo::{Pid, System};
 
+use crate::regression::RegressionAlert;
+
 /// Memory profiler for tracking memory usage
 pub struct MemoryProfiler {
     system: System,
     process_id: Option<Pid>,
+    tracker: SubsystemTracker,
+    process_watermarks: VecDeque<(DateTime<Utc>, u64)>,
 }
 
 impl MemoryProfiler {
@@ -18,7 +28,56 @@ impl MemoryProfiler {
 
         let process_id = sysinfo::get_current_pid().ok();
 
-        Self { system, process_id }
+        Self {
+            system,
+            process_id,
+            tracker: SubsystemTracker::new(),
+            process_watermarks: VecDeque::new(),
+        }
+    }
+
+    /// Use a shared subsystem tracker (e.g. one created before the profiler)
+    pub fn with_tracker(mut self, tracker: SubsystemTracker) -> Self {
+        self.tracker = tracker;
+        self
+    }
+
+    /// Subsystem attribution tracker; clone it to hand out tokens
+    pub fn tracker(&self) -> &SubsystemTracker {
+        &self.tracker
+    }
+
+    /// Start attributing memory to a subsystem
+    pub fn track(&self, subsystem: impl Into<String>) -> TrackingToken {
+        self.tracker.track(subsystem)
+    }
+
+    /// Record a watermark sample for the process and every subsystem
+    pub fn record_watermarks(&mut self) {
+        let process_bytes = self.get_current_memory_usage();
+        let now = Utc::now();
+        self.process_watermarks.push_back((now, process_bytes));
+        while self.process_watermarks.len() > self.tracker.config.history_len {
+            self.process_watermarks.pop_front();
+        }
+        self.tracker.sample_at(now);
+    }
+
+    /// Ranked "who is using the memory" report from the latest watermarks
+    pub fn attribution_report(&self) -> MemoryAttributionReport {
+        let process_bytes = self
+            .process_watermarks
+            .back()
+            .map(|(_, bytes)| *bytes)
+            .unwrap_or_default();
+        let process_high_watermark_bytes = self
+            .process_watermarks
+            .iter()
+            .map(|(_, bytes)| *bytes)
+            .max()
+            .unwrap_or_default();
+        self.tracker
+            .report(process_bytes, process_high_watermark_bytes)
     }
 
     /// Get current memory usage in bytes
@@ -108,3 +167,321 @@ impl Default for MemoryProfiler {
         Self::new()
     }
 }
+
+/// Subsystem attribution settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttributionConfig {
+    /// Watermark samples kept per subsystem
+    pub history_len: usize,
+    /// Consecutive non-decreasing samples needed to flag growth
+    pub growth_window: usize,
+    /// Minimum growth over the window to flag (bytes)
+    pub min_growth_bytes: u64,
+}
+
+impl Default for AttributionConfig {
+    fn default() -> Self {
+        Self {
+            history_len: 120,
+            growth_window: 10,
+            min_growth_bytes: 1024 * 1024, // 1MB
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct SubsystemState {
+    current_bytes: u64,
+    high_watermark_bytes: u64,
+    live_tokens: usize,
+    history: VecDeque<(DateTime<Utc>, u64)>,
+}
+
+/// Thread-safe per-subsystem memory accounting
+///
+/// Subsystems (sessions, caches, LSP clients, ...) hold [`TrackingToken`]s and
+/// report what they allocate and free; bytes still held when a token drops
+/// are released automatically.
+#[derive(Debug, Clone, Default)]
+pub struct SubsystemTracker {
+    config: AttributionConfig,
+    subsystems: Arc<Mutex<HashMap<String, SubsystemState>>>,
+}
+
+impl SubsystemTracker {
+    /// Tracker with default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Tracker with custom settings
+    pub fn with_config(config: AttributionConfig) -> Self {
+        Self {
+            config,
+            subsystems: Arc::default(),
+        }
+    }
+
+    /// Start attributing memory to a subsystem
+    pub fn track(&self, subsystem: impl Into<String>) -> TrackingToken {
+        let subsystem = subsystem.into();
+        self.subsystems
+            .lock()
+            .unwrap()
+            .entry(subsystem.clone())
+            .or_default()
+            .live_tokens += 1;
+        TrackingToken {
+            tracker: self.clone(),
+            subsystem,
+            bytes: 0,
+        }
+    }
+
+    /// Current bytes attributed to a subsystem
+    pub fn current_bytes(&self, subsystem: &str) -> u64 {
+        self.subsystems
+            .lock()
+            .unwrap()
+            .get(subsystem)
+            .map(|s| s.current_bytes)
+            .unwrap_or_default()
+    }
+
+    /// Record a watermark sample for every subsystem
+    pub fn sample(&self) {
+        self.sample_at(Utc::now());
+    }
+
+    fn sample_at(&self, at: DateTime<Utc>) {
+        let mut subsystems = self.subsystems.lock().unwrap();
+        for state in subsystems.values_mut() {
+            state.history.push_back((at, state.current_bytes));
+            while state.history.len() > self.config.history_len {
+                state.history.pop_front();
+            }
+        }
+    }
+
+    fn adjust(&self, subsystem: &str, delta: i64) {
+        let mut subsystems = self.subsystems.lock().unwrap();
+        let state = subsystems.entry(subsystem.to_string()).or_default();
+        state.current_bytes = state.current_bytes.saturating_add_signed(delta);
+        state.high_watermark_bytes = state.high_watermark_bytes.max(state.current_bytes);
+    }
+
+    fn release(&self, subsystem: &str, bytes: u64) {
+        let mut subsystems = self.subsystems.lock().unwrap();
+        if let Some(state) = subsystems.get_mut(subsystem) {
+            state.current_bytes = state.current_bytes.saturating_sub(bytes);
+            state.live_tokens = state.live_tokens.saturating_sub(1);
+        }
+    }
+
+    /// Growth over the trailing window if it never decreased
+    fn growth(&self, state: &SubsystemState) -> Option<GrowthTrend> {
+        let window = self.config.growth_window.max(2);
+        if state.history.len() < window {
+            return None;
+        }
+        let recent: Vec<_> = state
+            .history
+            .iter()
+            .skip(state.history.len() - window)
+            .collect();
+        let monotonic = recent.windows(2).all(|pair| pair[1].1 >= pair[0].1);
+        let (first_at, from_bytes) = *recent[0];
+        let (last_at, to_bytes) = *recent[window - 1];
+        if !monotonic || to_bytes < from_bytes + self.config.min_growth_bytes {
+            return None;
+        }
+
+        let minutes = (last_at - first_at).num_milliseconds() as f64 / 60_000.0;
+        Some(GrowthTrend {
+            samples: window,
+            from_bytes,
+            to_bytes,
+            bytes_per_minute: if minutes > 0.0 {
+                (to_bytes - from_bytes) as f64 / minutes
+            } else {
+                0.0
+            },
+        })
+    }
+
+    /// Ranked attribution report against the given process totals
+    pub fn report(
+        &self,
+        process_bytes: u64,
+        process_high_watermark_bytes: u64,
+    ) -> MemoryAttributionReport {
+        let subsystems = self.subsystems.lock().unwrap();
+        let attributed_bytes: u64 = subsystems.values().map(|s| s.current_bytes).sum();
+        let denominator = process_bytes.max(attributed_bytes).max(1) as f64;
+
+        let mut usage: Vec<SubsystemUsage> = subsystems
+            .iter()
+            .map(|(name, state)| SubsystemUsage {
+                subsystem: name.clone(),
+                current_bytes: state.current_bytes,
+                high_watermark_bytes: state.high_watermark_bytes,
+                live_tokens: state.live_tokens,
+                share_percent: state.current_bytes as f64 / denominator * 100.0,
+                growth: self.growth(state),
+            })
+            .collect();
+        usage.sort_by(|a, b| {
+            b.current_bytes
+                .cmp(&a.current_bytes)
+                .then_with(|| a.subsystem.cmp(&b.subsystem))
+        });
+
+        MemoryAttributionReport {
+            process_bytes,
+            process_high_watermark_bytes,
+            attributed_bytes,
+            unattributed_bytes: process_bytes.saturating_sub(attributed_bytes),
+            subsystems: usage,
+            timestamp: Utc::now(),
+        }
+    }
+}
+
+/// Scoped attribution handle held by a subsystem
+///
+/// Dropping the token releases whatever it still accounts for.
+#[derive(Debug)]
+pub struct TrackingToken {
+    tracker: SubsystemTracker,
+    subsystem: String,
+    bytes: u64,
+}
+
+impl TrackingToken {
+    /// Subsystem this token reports to
+    pub fn subsystem(&self) -> &str {
+        &self.subsystem
+    }
+
+    /// Bytes currently held by this token
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Record an allocation
+    pub fn record_alloc(&mut self, bytes: u64) {
+        self.bytes += bytes;
+        self.tracker.adjust(&self.subsystem, bytes as i64);
+    }
+
+    /// Record a deallocation
+    pub fn record_free(&mut self, bytes: u64) {
+        let bytes = bytes.min(self.bytes);
+        self.bytes -= bytes;
+        self.tracker.adjust(&self.subsystem, -(bytes as i64));
+    }
+
+    /// Replace the held amount with an absolute size (e.g. a cache's byte count)
+    pub fn set(&mut self, bytes: u64) {
+        let delta = bytes as i64 - self.bytes as i64;
+        self.bytes = bytes;
+        self.tracker.adjust(&self.subsystem, delta);
+    }
+}
+
+impl Drop for TrackingToken {
+    fn drop(&mut self) {
+        self.tracker.release(&self.subsystem, self.bytes);
+    }
+}
+
+/// Sustained growth of a subsystem over the trailing watermark window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrowthTrend {
+    /// Samples in the window
+    pub samples: usize,
+    pub from_bytes: u64,
+    pub to_bytes: u64,
+    pub bytes_per_minute: f64,
+}
+
+/// Memory attributed to one subsystem
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubsystemUsage {
+    pub subsystem: String,
+    pub current_bytes: u64,
+    pub high_watermark_bytes: u64,
+    /// Tokens still alive
+    pub live_tokens: usize,
+    /// Share of process memory
+    pub share_percent: f64,
+    /// Set when usage grew monotonically over the window (possible leak)
+    pub growth: Option<GrowthTrend>,
+}
+
+/// Ranked "who is using the memory" report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryAttributionReport {
+    pub process_bytes: u64,
+    pub process_high_watermark_bytes: u64,
+    pub attributed_bytes: u64,
+    pub unattributed_bytes: u64,
+    /// Subsystems ordered by current usage, largest first
+    pub subsystems: Vec<SubsystemUsage>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl MemoryAttributionReport {
+    /// Subsystems with monotonic growth
+    pub fn suspected_leaks(&self) -> impl Iterator<Item = &SubsystemUsage> {
+        self.subsystems.iter().filter(|s| s.growth.is_some())
+    }
+
+    /// Suspected leaks as memory regression alerts (`memory.<subsystem>`)
+    pub fn to_alerts(&self) -> Vec<RegressionAlert> {
+        self.suspected_leaks()
+            .filter_map(|usage| {
+                usage
+                    .growth
+                    .as_ref()
+                    .map(|growth| RegressionAlert::MemoryRegression {
+                        test_name: format!("memory.{}", usage.subsystem),
+                        baseline_memory_bytes: growth.from_bytes,
+                        current_memory_bytes: growth.to_bytes,
+                        increase_percent: (growth.to_bytes - growth.from_bytes) as f64
+                            / growth.from_bytes.max(1) as f64
+                            * 100.0,
+                    })
+            })
+            .collect()
+    }
+
+    /// Text table of subsystems, largest first
+    pub fn to_text(&self) -> String {
+        let mb = |bytes: u64| bytes as f64 / 1024.0 / 1024.0;
+        let mut out = format!(
+            "Process: {:.1}MB (peak {:.1}MB), attributed {:.1}MB, unattributed {:.1}MB\n",
+            mb(self.process_bytes),
+            mb(self.process_high_watermark_bytes),
+            mb(self.attributed_bytes),
+            mb(self.unattributed_bytes)
+        );
+        for usage in &self.subsystems {
+            out.push_str(&format!(
+                "  {:<20} {:>8.1}MB {:>5.1}%  peak {:>8.1}MB",
+                usage.subsystem,
+                mb(usage.current_bytes),
+                usage.share_percent,
+                mb(usage.high_watermark_bytes)
+            ));
+            if let Some(growth) = &usage.growth {
+                out.push_str(&format!(
+                    "  growing +{:.1}MB/min",
+                    mb(growth.bytes_per_minute as u64)
+                ));
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
@@ -7,13 +7,17 @@ use std::{
 
 use serde::{Deserialize, Serialize};
 
-use crate::monitor::{PerformanceMetrics, PerformanceMonitor};
+use crate::{
+    monitor::{PerformanceMetrics, PerformanceMonitor},
+    sampling::{SampleCapture, SamplerHandle, SamplingConfig, SamplingProfiler},
+};
 
 /// Performance profiler for detailed code path analysis
 pub struct PerformanceProfiler {
     monitors: HashMap<String, PerformanceMonitor>,
     call_stacks: Vec<String>,
     profile_start: Option<Instant>,
+    sampler: Option<SamplingProfiler>,
 }
 
 impl PerformanceProfiler {
@@ -23,7 +27,39 @@ impl PerformanceProfiler {
             monitors: HashMap::new(),
             call_stacks: Vec::new(),
             profile_start: None,
+            sampler: None,
+        }
+    }
+
+    /// Enable the background sampling profiler (restarts it if already running)
+    pub fn enable_sampling(&mut self, config: SamplingConfig) {
+        let sampler = SamplingProfiler::start(config);
+        let handle = sampler.handle();
+        // Mirror paths already being timed so the first samples are attributed
+        for path in &self.call_stacks {
+            handle.push(path);
         }
+        self.sampler = Some(sampler);
+    }
+
+    /// Disable sampling and drop the buffered samples
+    pub fn disable_sampling(&mut self) {
+        self.sampler = None;
+    }
+
+    /// Whether sampling is enabled
+    pub fn is_sampling(&self) -> bool {
+        self.sampler.is_some()
+    }
+
+    /// Handle for marking frames from other threads while sampling
+    pub fn sampler_handle(&self) -> Option<SamplerHandle> {
+        self.sampler.as_ref().map(SamplingProfiler::handle)
+    }
+
+    /// Buffered samples from the last `window` (everything if `None`)
+    pub fn capture_samples(&self, window: Option<Duration>) -> Option<SampleCapture> {
+        self.sampler.as_ref().map(|s| s.capture(window))
     }
 
     /// Start profiling session
@@ -60,6 +96,9 @@ impl PerformanceProfiler {
 
         monitor.start();
         self.call_stacks.push(path_name.to_string());
+        if let Some(sampler) = &self.sampler {
+            sampler.handle().push(path_name);
+        }
     }
 
     /// Stop timing the current code path
@@ -72,6 +111,9 @@ impl PerformanceProfiler {
         if let Some(pos) = self.call_stacks.iter().rposition(|x| x == path_name) {
             self.call_stacks.remove(pos);
         }
+        if let Some(sampler) = &self.sampler {
+            sampler.handle().pop(path_name);
+        }
     }
 
     /// Profile a function with automatic timing
@@ -0,0 +1,548 @@
+//! Always-on sampling profiler with flamegraph and pprof export
+//!
+//! A background thread periodically snapshots every thread's instrumented call
+//! stack (the paths entered via [`crate::PerformanceProfiler::start_timing`] or
+//! a [`SamplerHandle`]) into a fixed-size ring buffer. Because sampling only
+//! copies a few short vectors, it can stay enabled in production; when the UI
+//! stalls, the last seconds can be exported as a flamegraph SVG or a gzipped
+//! pprof profile without restarting.
+
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    io::Write,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle, ThreadId},
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+use flate2::{write::GzEncoder, Compression};
+use serde::{Deserialize, Serialize};
+
+/// Sampling configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SamplingConfig {
+    /// Time between samples
+    pub interval: Duration,
+    /// Maximum samples retained (oldest are dropped)
+    pub capacity: usize,
+    /// Record samples for threads with no active frame
+    pub include_idle: bool,
+}
+
+impl Default for SamplingConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_millis(10), // 100 Hz
+            capacity: 6_000,                     // one minute at 100 Hz
+            include_idle: false,
+        }
+    }
+}
+
+/// One stack snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Sample {
+    /// Offset from sampler start
+    pub offset: Duration,
+    /// Wall-clock time of the sample
+    pub timestamp: DateTime<Utc>,
+    /// Thread name (or id) the stack belongs to
+    pub thread: String,
+    /// Frames from outermost to innermost
+    pub stack: Vec<String>,
+}
+
+#[derive(Default)]
+struct ThreadStack {
+    name: String,
+    frames: Vec<String>,
+}
+
+type Stacks = Arc<Mutex<HashMap<ThreadId, ThreadStack>>>;
+
+/// Cloneable handle for marking frames from any thread
+#[derive(Clone)]
+pub struct SamplerHandle {
+    stacks: Stacks,
+}
+
+impl SamplerHandle {
+    /// Push a frame onto the current thread's stack
+    pub fn push(&self, frame: &str) {
+        let current = thread::current();
+        let mut stacks = self.stacks.lock().unwrap();
+        let stack = stacks.entry(current.id()).or_insert_with(|| ThreadStack {
+            name: current
+                .name()
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("{:?}", current.id())),
+            frames: Vec::new(),
+        });
+        stack.frames.push(frame.to_string());
+    }
+
+    /// Pop the innermost matching frame from the current thread's stack
+    pub fn pop(&self, frame: &str) {
+        let mut stacks = self.stacks.lock().unwrap();
+        if let Some(stack) = stacks.get_mut(&thread::current().id()) {
+            if let Some(pos) = stack.frames.iter().rposition(|f| f == frame) {
+                stack.frames.truncate(pos);
+            }
+        }
+    }
+
+    /// Enter a frame until the guard is dropped
+    pub fn enter(&self, frame: impl Into<String>) -> FrameGuard {
+        let frame = frame.into();
+        self.push(&frame);
+        FrameGuard {
+            handle: self.clone(),
+            frame,
+        }
+    }
+}
+
+/// Guard returned by [`SamplerHandle::enter`]
+pub struct FrameGuard {
+    handle: SamplerHandle,
+    frame: String,
+}
+
+impl Drop for FrameGuard {
+    fn drop(&mut self) {
+        self.handle.pop(&self.frame);
+    }
+}
+
+/// Background sampler owning the ring buffer
+pub struct SamplingProfiler {
+    config: SamplingConfig,
+    stacks: Stacks,
+    samples: Arc<Mutex<VecDeque<Sample>>>,
+    running: Arc<AtomicBool>,
+    worker: Option<JoinHandle<()>>,
+    started: Instant,
+}
+
+impl SamplingProfiler {
+    /// Start sampling in a background thread
+    pub fn start(config: SamplingConfig) -> Self {
+        let stacks: Stacks = Arc::default();
+        let samples = Arc::new(Mutex::new(VecDeque::with_capacity(config.capacity)));
+        let running = Arc::new(AtomicBool::new(true));
+        let started = Instant::now();
+
+        let worker = {
+            let config = config.clone();
+            let stacks = stacks.clone();
+            let samples = samples.clone();
+            let running = running.clone();
+            thread::Builder::new()
+                .name("ricecoder-sampler".to_string())
+                .spawn(move || {
+                    while running.load(Ordering::Relaxed) {
+                        thread::sleep(config.interval);
+                        let offset = started.elapsed();
+                        let timestamp = Utc::now();
+                        let snapshot: Vec<Sample> = stacks
+                            .lock()
+                            .unwrap()
+                            .values()
+                            .filter(|s| config.include_idle || !s.frames.is_empty())
+                            .map(|s| Sample {
+                                offset,
+                                timestamp,
+                                thread: s.name.clone(),
+                                stack: s.frames.clone(),
+                            })
+                            .collect();
+
+                        let mut samples = samples.lock().unwrap();
+                        for sample in snapshot {
+                            if samples.len() == config.capacity {
+                                samples.pop_front();
+                            }
+                            samples.push_back(sample);
+                        }
+                    }
+                })
+                .ok()
+        };
+
+        Self {
+            config,
+            stacks,
+            samples,
+            running,
+            worker,
+            started,
+        }
+    }
+
+    /// Sampling configuration
+    pub fn config(&self) -> &SamplingConfig {
+        &self.config
+    }
+
+    /// Handle for marking frames from other threads
+    pub fn handle(&self) -> SamplerHandle {
+        SamplerHandle {
+            stacks: self.stacks.clone(),
+        }
+    }
+
+    /// Number of samples currently buffered
+    pub fn len(&self) -> usize {
+        self.samples.lock().unwrap().len()
+    }
+
+    /// Whether the ring buffer is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Samples from the last `window` (everything buffered if `None`)
+    pub fn capture(&self, window: Option<Duration>) -> SampleCapture {
+        let now = self.started.elapsed();
+        let from = window.map(|w| now.saturating_sub(w)).unwrap_or_default();
+        let samples = self
+            .samples
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|s| s.offset >= from)
+            .cloned()
+            .collect();
+        SampleCapture {
+            samples,
+            interval: self.config.interval,
+            duration: now - from,
+            captured_at: Utc::now(),
+        }
+    }
+
+    /// Stop the background thread
+    pub fn stop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+impl Drop for SamplingProfiler {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Samples selected for export
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SampleCapture {
+    pub samples: Vec<Sample>,
+    /// Sampling interval the samples were taken at
+    pub interval: Duration,
+    /// Length of the captured window
+    pub duration: Duration,
+    pub captured_at: DateTime<Utc>,
+}
+
+impl SampleCapture {
+    /// Sample counts per full stack (thread name as the root frame)
+    pub fn stack_counts(&self) -> BTreeMap<Vec<String>, u64> {
+        let mut counts = BTreeMap::new();
+        for sample in &self.samples {
+            let mut stack = Vec::with_capacity(sample.stack.len() + 1);
+            stack.push(sample.thread.clone());
+            if sample.stack.is_empty() {
+                stack.push("<idle>".to_string());
+            } else {
+                stack.extend(sample.stack.iter().cloned());
+            }
+            *counts.entry(stack).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Collapsed stack format (`a;b;c 42`), as consumed by flamegraph tools
+    pub fn to_folded(&self) -> String {
+        self.stack_counts()
+            .into_iter()
+            .map(|(stack, count)| format!("{} {}\n", stack.join(";"), count))
+            .collect()
+    }
+
+    /// Render a static flamegraph SVG
+    pub fn to_flamegraph_svg(&self, title: &str) -> String {
+        const WIDTH: f64 = 1200.0;
+        const FRAME_HEIGHT: f64 = 16.0;
+        const PAD: f64 = 10.0;
+        const HEADER: f64 = 30.0;
+
+        let root = FlameNode::build(&self.stack_counts());
+        let depth = root.depth();
+        let height = HEADER + depth as f64 * FRAME_HEIGHT + PAD * 2.0;
+        let scale = if root.count == 0 {
+            0.0
+        } else {
+            (WIDTH - PAD * 2.0) / root.count as f64
+        };
+
+        let mut svg = format!(
+            "<?xml version=\"1.0\" standalone=\"no\"?>\n\
+             <svg version=\"1.1\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" \
+             xmlns=\"http://www.w3.org/2000/svg\">\n\
+             <rect x=\"0\" y=\"0\" width=\"{w}\" height=\"{h}\" fill=\"#f8f8f8\"/>\n\
+             <text x=\"{cx}\" y=\"20\" text-anchor=\"middle\" font-family=\"monospace\" \
+             font-size=\"14\">{t} ({n} samples, {d:.1}s)</text>\n",
+            w = WIDTH,
+            h = height,
+            cx = WIDTH / 2.0,
+            t = xml_escape(title),
+            n = root.count,
+            d = self.duration.as_secs_f64()
+        );
+
+        // Root frame sits at the bottom, callees stack upwards
+        let mut pending = vec![(&root, PAD, 0usize)];
+        while let Some((node, x, level)) = pending.pop() {
+            let mut child_x = x;
+            for (name, child) in &node.children {
+                let width = child.count as f64 * scale;
+                let y = height - PAD - (level + 1) as f64 * FRAME_HEIGHT;
+                let percent = child.count as f64 / root.count.max(1) as f64 * 100.0;
+                svg.push_str(&format!(
+                    "<g><title>{name} ({count} samples, {percent:.2}%)</title>\
+                     <rect x=\"{x:.2}\" y=\"{y:.2}\" width=\"{width:.2}\" height=\"{fh:.1}\" \
+                     fill=\"{color}\" rx=\"2\"/>",
+                    name = xml_escape(name),
+                    count = child.count,
+                    x = child_x,
+                    fh = FRAME_HEIGHT - 1.0,
+                    color = frame_color(name),
+                ));
+                // Roughly 7px per monospace glyph at 12px
+                let max_chars = ((width - 6.0) / 7.0).max(0.0) as usize;
+                if max_chars >= 3 {
+                    let label = if name.chars().count() > max_chars {
+                        let truncated: String = name.chars().take(max_chars - 2).collect();
+                        format!("{}..", truncated)
+                    } else {
+                        name.clone()
+                    };
+                    svg.push_str(&format!(
+                        "<text x=\"{:.2}\" y=\"{:.2}\" font-family=\"monospace\" \
+                         font-size=\"12\">{}</text>",
+                        child_x + 3.0,
+                        y + FRAME_HEIGHT - 4.0,
+                        xml_escape(&label)
+                    ));
+                }
+                svg.push_str("</g>\n");
+                pending.push((child, child_x, level + 1));
+                child_x += width;
+            }
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Encode as a gzipped pprof `profile.proto` (readable by `go tool pprof`)
+    pub fn to_pprof(&self) -> std::io::Result<Vec<u8>> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&self.encode_profile())?;
+        encoder.finish()
+    }
+
+    /// Uncompressed `perftools.profiles.Profile` message
+    fn encode_profile(&self) -> Vec<u8> {
+        let mut strings = StringTable::default();
+        let mut functions: Vec<String> = Vec::new();
+        let mut function_ids: HashMap<String, u64> = HashMap::new();
+        let interval_ns = self.interval.as_nanos() as i64;
+
+        let mut out = Vec::new();
+
+        // sample_type: samples/count, wall/nanoseconds
+        for (kind, unit) in [("samples", "count"), ("wall", "nanoseconds")] {
+            let mut value_type = Vec::new();
+            proto::int_field(&mut value_type, 1, strings.index(kind) as i64);
+            proto::int_field(&mut value_type, 2, strings.index(unit) as i64);
+            proto::bytes_field(&mut out, 1, &value_type);
+        }
+
+        for (stack, count) in self.stack_counts() {
+            // pprof lists locations leaf first
+            let location_ids: Vec<u64> = stack
+                .iter()
+                .rev()
+                .map(|frame| {
+                    *function_ids.entry(frame.clone()).or_insert_with(|| {
+                        functions.push(frame.clone());
+                        functions.len() as u64
+                    })
+                })
+                .collect();
+
+            let mut sample = Vec::new();
+            proto::packed_field(&mut sample, 1, location_ids.iter().copied());
+            proto::packed_field(
+                &mut sample,
+                2,
+                [count, count * interval_ns as u64].into_iter(),
+            );
+            proto::bytes_field(&mut out, 2, &sample);
+        }
+
+        // One location per function, sharing ids
+        for id in 1..=functions.len() as u64 {
+            let mut line = Vec::new();
+            proto::int_field(&mut line, 1, id as i64);
+            let mut location = Vec::new();
+            proto::int_field(&mut location, 1, id as i64);
+            proto::bytes_field(&mut location, 4, &line);
+            proto::bytes_field(&mut out, 4, &location);
+        }
+
+        for (i, name) in functions.iter().enumerate() {
+            let name_index = strings.index(name) as i64;
+            let mut function = Vec::new();
+            proto::int_field(&mut function, 1, i as i64 + 1);
+            proto::int_field(&mut function, 2, name_index);
+            proto::int_field(&mut function, 3, name_index);
+            proto::bytes_field(&mut out, 5, &function);
+        }
+
+        let period_type = {
+            let mut value_type = Vec::new();
+            proto::int_field(&mut value_type, 1, strings.index("wall") as i64);
+            proto::int_field(&mut value_type, 2, strings.index("nanoseconds") as i64);
+            value_type
+        };
+
+        for s in &strings.strings {
+            proto::bytes_field(&mut out, 6, s.as_bytes());
+        }
+        let start =
+            self.captured_at - chrono::Duration::from_std(self.duration).unwrap_or_default();
+        proto::int_field(&mut out, 9, start.timestamp_nanos_opt().unwrap_or_default());
+        proto::int_field(&mut out, 10, self.duration.as_nanos() as i64);
+        proto::bytes_field(&mut out, 11, &period_type);
+        proto::int_field(&mut out, 12, interval_ns);
+        out
+    }
+}
+
+/// Frame tree used for flamegraph layout
+#[derive(Default)]
+struct FlameNode {
+    count: u64,
+    children: BTreeMap<String, FlameNode>,
+}
+
+impl FlameNode {
+    fn build(stacks: &BTreeMap<Vec<String>, u64>) -> Self {
+        let mut root = FlameNode::default();
+        for (stack, count) in stacks {
+            root.count += count;
+            let mut node = &mut root;
+            for frame in stack {
+                node = node.children.entry(frame.clone()).or_default();
+                node.count += count;
+            }
+        }
+        root
+    }
+
+    fn depth(&self) -> usize {
+        self.children
+            .values()
+            .map(|c| c.depth() + 1)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Stable warm color per frame name
+fn frame_color(name: &str) -> String {
+    let hash = name
+        .bytes()
+        .fold(2166136261u32, |h, b| (h ^ b as u32).wrapping_mul(16777619));
+    let r = 205 + (hash % 50);
+    let g = (hash >> 8) % 180;
+    let b = (hash >> 16) % 55;
+    format!("rgb({},{},{})", r, g, b)
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// pprof string table (index 0 is always "")
+struct StringTable {
+    strings: Vec<String>,
+    indices: HashMap<String, usize>,
+}
+
+impl Default for StringTable {
+    fn default() -> Self {
+        Self {
+            strings: vec![String::new()],
+            indices: HashMap::from([(String::new(), 0)]),
+        }
+    }
+}
+
+impl StringTable {
+    fn index(&mut self, value: &str) -> usize {
+        if let Some(&index) = self.indices.get(value) {
+            return index;
+        }
+        self.strings.push(value.to_string());
+        self.indices
+            .insert(value.to_string(), self.strings.len() - 1);
+        self.strings.len() - 1
+    }
+}
+
+/// Minimal protobuf wire encoding
+mod proto {
+    fn varint(out: &mut Vec<u8>, mut value: u64) {
+        while value >= 0x80 {
+            out.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        out.push(value as u8);
+    }
+
+    pub fn int_field(out: &mut Vec<u8>, field: u32, value: i64) {
+        if value == 0 {
+            return;
+        }
+        varint(out, (field as u64) << 3);
+        varint(out, value as u64);
+    }
+
+    pub fn bytes_field(out: &mut Vec<u8>, field: u32, bytes: &[u8]) {
+        varint(out, ((field as u64) << 3) | 2);
+        varint(out, bytes.len() as u64);
+        out.extend_from_slice(bytes);
+    }
+
+    pub fn packed_field(out: &mut Vec<u8>, field: u32, values: impl Iterator<Item = u64>) {
+        let mut packed = Vec::new();
+        for value in values {
+            varint(&mut packed, value);
+        }
+        bytes_field(out, field, &packed);
+    }
+}
@@ -0,0 +1,345 @@
+//! Startup phase instrumentation and budget enforcement
+//!
+//! Initialization code wraps each named phase (config load, DI container
+//! build, LSP spawn, TUI init, ...) with [`StartupTracer::phase`]. The
+//! resulting [`StartupReport`] compares every phase against its budget, renders
+//! a waterfall, and converts overruns into [`RegressionAlert`]s.
+
+use std::{
+    collections::BTreeMap,
+    path::Path,
+    sync::{Mutex, OnceLock},
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::regression::RegressionAlert;
+
+/// Per-phase startup budgets in milliseconds
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StartupBudgets {
+    /// Budget per phase name
+    #[serde(default)]
+    pub phases_ms: BTreeMap<String, u64>,
+    /// Budget for the whole startup
+    #[serde(default)]
+    pub total_ms: Option<u64>,
+}
+
+impl Default for StartupBudgets {
+    fn default() -> Self {
+        let phases_ms = [
+            ("config_load", 100),
+            ("di_container", 150),
+            ("lifecycle_init", 300),
+            ("lifecycle_start", 300),
+            ("lsp_spawn", 500),
+            ("tui_init", 300),
+        ]
+        .into_iter()
+        .map(|(name, ms)| (name.to_string(), ms))
+        .collect();
+
+        Self {
+            phases_ms,
+            total_ms: Some(3_000), // <3s cold start target
+        }
+    }
+}
+
+impl StartupBudgets {
+    /// Load budgets from a YAML or JSON file (by extension)
+    ///
+    /// Phases missing from the file keep their default budgets.
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn std::error::Error>> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)?;
+        let loaded: StartupBudgets = match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => serde_json::from_str(&content)?,
+            _ => serde_yaml::from_str(&content)?,
+        };
+
+        let mut budgets = Self::default();
+        budgets.phases_ms.extend(loaded.phases_ms);
+        if loaded.total_ms.is_some() {
+            budgets.total_ms = loaded.total_ms;
+        }
+        Ok(budgets)
+    }
+
+    /// Set the budget for a phase
+    pub fn with_phase(mut self, name: impl Into<String>, budget: Duration) -> Self {
+        self.phases_ms
+            .insert(name.into(), budget.as_millis() as u64);
+        self
+    }
+
+    /// Budget for a phase, if any
+    pub fn budget_for(&self, name: &str) -> Option<Duration> {
+        self.phases_ms.get(name).copied().map(Duration::from_millis)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct PhaseRecord {
+    name: String,
+    start: Duration,
+    duration: Duration,
+}
+
+struct TracerState {
+    budgets: StartupBudgets,
+    phases: Vec<PhaseRecord>,
+}
+
+/// Records startup phases relative to a fixed origin
+pub struct StartupTracer {
+    origin: Instant,
+    state: Mutex<TracerState>,
+}
+
+impl StartupTracer {
+    /// Tracer whose origin is now
+    pub fn new(budgets: StartupBudgets) -> Self {
+        Self {
+            origin: Instant::now(),
+            state: Mutex::new(TracerState {
+                budgets,
+                phases: Vec::new(),
+            }),
+        }
+    }
+
+    /// Process-wide tracer; its origin is the first call, so call early in `main`
+    pub fn global() -> &'static StartupTracer {
+        static GLOBAL: OnceLock<StartupTracer> = OnceLock::new();
+        GLOBAL.get_or_init(|| StartupTracer::new(StartupBudgets::default()))
+    }
+
+    /// Replace the budgets
+    pub fn set_budgets(&self, budgets: StartupBudgets) {
+        self.state.lock().unwrap().budgets = budgets;
+    }
+
+    /// Start a phase; it is recorded when the guard is dropped
+    pub fn phase(&self, name: impl Into<String>) -> PhaseGuard<'_> {
+        PhaseGuard {
+            tracer: self,
+            name: name.into(),
+            start: Instant::now(),
+        }
+    }
+
+    /// Time a closure as a phase
+    pub fn time<F, R>(&self, name: impl Into<String>, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        let _guard = self.phase(name);
+        f()
+    }
+
+    /// Record a phase measured elsewhere
+    pub fn record(&self, name: impl Into<String>, start: Instant, duration: Duration) {
+        self.state.lock().unwrap().phases.push(PhaseRecord {
+            name: name.into(),
+            start: start.saturating_duration_since(self.origin),
+            duration,
+        });
+    }
+
+    /// Snapshot of the phases recorded so far
+    pub fn report(&self) -> StartupReport {
+        let state = self.state.lock().unwrap();
+        let mut phases: Vec<PhaseTiming> = state
+            .phases
+            .iter()
+            .map(|record| {
+                let budget = state.budgets.budget_for(&record.name);
+                PhaseTiming {
+                    name: record.name.clone(),
+                    start: record.start,
+                    duration: record.duration,
+                    budget,
+                    over_budget: budget.is_some_and(|b| record.duration > b),
+                }
+            })
+            .collect();
+        phases.sort_by_key(|p| p.start);
+
+        let total = phases
+            .iter()
+            .map(|p| p.start + p.duration)
+            .max()
+            .unwrap_or_default();
+
+        StartupReport {
+            phases,
+            total,
+            total_budget: state.budgets.total_ms.map(Duration::from_millis),
+            timestamp: Utc::now(),
+        }
+    }
+}
+
+/// Guard returned by [`StartupTracer::phase`]
+pub struct PhaseGuard<'a> {
+    tracer: &'a StartupTracer,
+    name: String,
+    start: Instant,
+}
+
+impl Drop for PhaseGuard<'_> {
+    fn drop(&mut self) {
+        self.tracer.record(
+            std::mem::take(&mut self.name),
+            self.start,
+            self.start.elapsed(),
+        );
+    }
+}
+
+/// Timing of one phase compared to its budget
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhaseTiming {
+    pub name: String,
+    /// Offset from the tracer origin
+    pub start: Duration,
+    pub duration: Duration,
+    pub budget: Option<Duration>,
+    pub over_budget: bool,
+}
+
+/// Startup phases with budget verdicts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StartupReport {
+    /// Phases ordered by start time
+    pub phases: Vec<PhaseTiming>,
+    /// From origin to the end of the last phase
+    pub total: Duration,
+    pub total_budget: Option<Duration>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl StartupReport {
+    /// Phases that exceeded their budget
+    pub fn over_budget(&self) -> impl Iterator<Item = &PhaseTiming> {
+        self.phases.iter().filter(|p| p.over_budget)
+    }
+
+    /// Whether the whole startup exceeded its budget
+    pub fn total_over_budget(&self) -> bool {
+        self.total_budget.is_some_and(|b| self.total > b)
+    }
+
+    /// Whether every phase and the total are within budget
+    pub fn within_budget(&self) -> bool {
+        self.over_budget().next().is_none() && !self.total_over_budget()
+    }
+
+    /// Budget overruns as regression alerts (`startup.<phase>`, `startup.total`)
+    pub fn to_alerts(&self) -> Vec<RegressionAlert> {
+        let alert =
+            |name: String, budget: Duration, actual: Duration| RegressionAlert::TargetExceeded {
+                test_name: name,
+                target_ns: budget.as_nanos() as u64,
+                current_p95_ns: actual.as_nanos() as u64,
+                exceed_percent: (actual.as_secs_f64() / budget.as_secs_f64().max(f64::EPSILON)
+                    - 1.0)
+                    * 100.0,
+            };
+
+        let mut alerts: Vec<RegressionAlert> = self
+            .over_budget()
+            .filter_map(|p| {
+                p.budget
+                    .map(|budget| alert(format!("startup.{}", p.name), budget, p.duration))
+            })
+            .collect();
+        if let Some(budget) = self.total_budget.filter(|_| self.total_over_budget()) {
+            alerts.push(alert("startup.total".to_string(), budget, self.total));
+        }
+        alerts
+    }
+
+    /// Log each budget overrun as a warning
+    pub fn emit_alerts(&self) {
+        for alert in self.to_alerts() {
+            if let RegressionAlert::TargetExceeded {
+                test_name,
+                target_ns,
+                current_p95_ns,
+                exceed_percent,
+            } = alert
+            {
+                tracing::warn!(
+                    phase = %test_name,
+                    budget_ms = target_ns as f64 / 1_000_000.0,
+                    actual_ms = current_p95_ns as f64 / 1_000_000.0,
+                    "Startup phase over budget by {:.1}%",
+                    exceed_percent
+                );
+            }
+        }
+    }
+
+    /// Text waterfall of the phases, `width` columns for the bar area
+    pub fn waterfall(&self, width: usize) -> String {
+        let width = width.max(10);
+        let name_width = self
+            .phases
+            .iter()
+            .map(|p| p.name.len())
+            .max()
+            .unwrap_or(0)
+            .max(5);
+        let scale = width as f64 / self.total.as_secs_f64().max(f64::EPSILON);
+
+        let mut out = format!("Startup waterfall (total {:.1}ms", ms(self.total));
+        if let Some(budget) = self.total_budget {
+            out.push_str(&format!(", budget {:.0}ms", ms(budget)));
+        }
+        out.push_str(")\n");
+
+        for phase in &self.phases {
+            let offset = ((phase.start.as_secs_f64() * scale) as usize).min(width - 1);
+            let len =
+                ((phase.duration.as_secs_f64() * scale).round() as usize).clamp(1, width - offset);
+            let bar = format!(
+                "{}{}{}",
+                " ".repeat(offset),
+                "█".repeat(len),
+                " ".repeat(width - offset - len)
+            );
+            let budget = phase
+                .budget
+                .map(|b| format!("{:>6.0}ms", ms(b)))
+                .unwrap_or_else(|| format!("{:>8}", "-"));
+            let status = match phase.budget {
+                Some(_) if phase.over_budget => "OVER",
+                Some(_) => "ok",
+                None => "",
+            };
+            out.push_str(&format!(
+                "  {:<name_width$} |{}| {:>8.1}ms / {} {}\n",
+                phase.name,
+                bar,
+                ms(phase.duration),
+                budget,
+                status,
+                name_width = name_width
+            ));
+        }
+
+        if self.total_over_budget() {
+            out.push_str("  total startup exceeded its budget\n");
+        }
+        out
+    }
+}
+
+fn ms(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
@@ -96,6 +96,17 @@ impl AuditLogger {
         Ok(())
     }
 
+    /// Log a prepared entry
+    pub fn log_entry(&self, entry: AuditLogEntry) -> Result<(), String> {
+        let mut entries = self
+            .entries
+            .write()
+            .map_err(|e| format!("Failed to acquire write lock: {}", e))?;
+        entries.push(entry);
+
+        Ok(())
+    }
+
     /// Get all entries
     pub fn entries(&self) -> Result<Vec<AuditLogEntry>, String> {
         let entries = self
@@ -17,5 +17,8 @@ pub use audit::{AuditAction, AuditLogEntry, AuditLogger, AuditResult};
 pub use error::{Error, Result};
 pub use glob_matcher::GlobMatcher;
 pub use permission::{PermissionConfig, PermissionLevel, PermissionManager, ToolPermission};
-pub use prompt::{PermissionPrompt, PromptResult, UserDecision};
+pub use prompt::{
+    ApprovalChoice, ApprovalRequest, PermissionPrompt, PromptResult, RiskLevel, ToolApprovals,
+    UserDecision,
+};
 pub use storage::{FilePermissionRepository, InMemoryPermissionRepository, PermissionRepository};
@@ -0,0 +1,373 @@
+//! Structured tool approval prompts and remembered decisions
+//!
+//! The first use of a tool in a session produces an [`ApprovalRequest`]
+//! describing the tool, a summary of its arguments and a [`RiskLevel`]. The
+//! user answers with an [`ApprovalChoice`]; [`ToolApprovals`] remembers
+//! session-wide choices in memory and "always allow" choices per project.
+
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::Result;
+
+/// Longest argument value shown in a summary
+const MAX_VALUE_CHARS: usize = 60;
+
+/// Tool name fragments that make a call high risk
+const HIGH_RISK: &[&str] = &[
+    "delete", "remove", "rm", "exec", "shell", "bash", "command", "run", "kill", "drop", "deploy",
+    "push",
+];
+
+/// Tool name fragments that make a call medium risk
+const MEDIUM_RISK: &[&str] = &[
+    "write", "edit", "create", "update", "move", "rename", "patch", "install", "fetch", "http",
+    "request", "post", "send",
+];
+
+/// How much damage a tool call could do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RiskLevel {
+    /// Reads or inspects state
+    Low,
+    /// Changes files or talks to the network
+    Medium,
+    /// Runs commands or destroys data
+    High,
+}
+
+impl RiskLevel {
+    /// Estimate the risk of a tool from its name
+    pub fn assess(tool: &str) -> Self {
+        let words: Vec<String> = tool
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(|word| word.to_ascii_lowercase())
+            .collect();
+        let has = |fragments: &[&str]| words.iter().any(|word| fragments.contains(&word.as_str()));
+
+        if has(HIGH_RISK) {
+            RiskLevel::High
+        } else if has(MEDIUM_RISK) {
+            RiskLevel::Medium
+        } else {
+            RiskLevel::Low
+        }
+    }
+}
+
+impl fmt::Display for RiskLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RiskLevel::Low => write!(f, "low"),
+            RiskLevel::Medium => write!(f, "medium"),
+            RiskLevel::High => write!(f, "high"),
+        }
+    }
+}
+
+/// The user's answer to an approval prompt
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalChoice {
+    /// Run this call only
+    AllowOnce,
+    /// Run this tool without asking for the rest of the session
+    AllowForSession,
+    /// Run this tool without asking in this project from now on
+    AlwaysAllow,
+    /// Do not run this call
+    Deny,
+}
+
+impl ApprovalChoice {
+    /// Parse a typed answer (`o`/`once`, `s`/`session`, `a`/`always`, `d`/`deny`)
+    pub fn from_input(input: &str) -> Option<Self> {
+        match input.trim().to_lowercase().as_str() {
+            "o" | "once" | "y" | "yes" => Some(ApprovalChoice::AllowOnce),
+            "s" | "session" => Some(ApprovalChoice::AllowForSession),
+            "a" | "always" => Some(ApprovalChoice::AlwaysAllow),
+            "d" | "deny" | "n" | "no" => Some(ApprovalChoice::Deny),
+            _ => None,
+        }
+    }
+
+    /// Whether the call may run
+    pub fn is_allowed(self) -> bool {
+        self != ApprovalChoice::Deny
+    }
+
+    /// Snake-case name used in audit entries
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ApprovalChoice::AllowOnce => "allow_once",
+            ApprovalChoice::AllowForSession => "allow_for_session",
+            ApprovalChoice::AlwaysAllow => "always_allow",
+            ApprovalChoice::Deny => "deny",
+        }
+    }
+}
+
+/// A structured approval prompt for one tool call
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApprovalRequest {
+    /// Session the call belongs to
+    pub session_id: String,
+    /// Tool identifier
+    pub tool: String,
+    /// Tool description, if known
+    pub description: Option<String>,
+    /// One-line summary of the call arguments
+    pub arguments_summary: String,
+    /// Estimated risk of the call
+    pub risk: RiskLevel,
+    /// Agent making the call
+    pub agent: Option<String>,
+}
+
+impl ApprovalRequest {
+    /// Create a prompt for a tool call, assessing risk from the tool name
+    pub fn new(
+        session_id: impl Into<String>,
+        tool: impl Into<String>,
+        arguments: &HashMap<String, Value>,
+    ) -> Self {
+        let tool = tool.into();
+        Self {
+            session_id: session_id.into(),
+            risk: RiskLevel::assess(&tool),
+            tool,
+            description: None,
+            arguments_summary: summarize_arguments(arguments),
+            agent: None,
+        }
+    }
+
+    /// Set the tool description
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Override the assessed risk
+    pub fn with_risk(mut self, risk: RiskLevel) -> Self {
+        self.risk = risk;
+        self
+    }
+
+    /// Set the agent making the call
+    pub fn with_agent(mut self, agent: impl Into<String>) -> Self {
+        self.agent = Some(agent.into());
+        self
+    }
+
+    /// Text shown to the user
+    pub fn format_message(&self) -> String {
+        let mut msg = format!("Tool: {} (risk: {})\n", self.tool, self.risk);
+        if let Some(description) = &self.description {
+            msg.push_str(&format!("Description: {}\n", description));
+        }
+        if let Some(agent) = &self.agent {
+            msg.push_str(&format!("Agent: {}\n", agent));
+        }
+        if !self.arguments_summary.is_empty() {
+            msg.push_str(&format!("Arguments: {}\n", self.arguments_summary));
+        }
+        msg.push_str("Allow? [o]nce / [s]ession / [a]lways / [d]eny");
+        msg
+    }
+}
+
+/// Summarize call arguments as `key=value` pairs in key order
+///
+/// Long values are shortened so the summary fits on one line.
+pub fn summarize_arguments(arguments: &HashMap<String, Value>) -> String {
+    let sorted: BTreeMap<_, _> = arguments.iter().collect();
+    sorted
+        .into_iter()
+        .map(|(key, value)| {
+            let value = match value {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            let value = value.replace('\n', " ");
+            if value.chars().count() > MAX_VALUE_CHARS {
+                let short: String = value.chars().take(MAX_VALUE_CHARS - 1).collect();
+                format!("{}={}…", key, short)
+            } else {
+                format!("{}={}", key, value)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Why a call was allowed without prompting
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RememberedApproval {
+    /// Allowed for the rest of the session
+    Session,
+    /// Always allowed in this project
+    Project,
+}
+
+/// "Always allow" decisions persisted for a project
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProjectApprovals {
+    /// Tools that are always allowed, with the time they were approved
+    #[serde(default)]
+    pub always_allow: BTreeMap<String, DateTime<Utc>>,
+}
+
+/// Remembered tool approvals: per session in memory, per project on disk
+#[derive(Debug, Default)]
+pub struct ToolApprovals {
+    path: Option<PathBuf>,
+    project: ProjectApprovals,
+    sessions: HashMap<String, HashSet<String>>,
+}
+
+impl ToolApprovals {
+    /// Create approvals that are not persisted
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load approvals from a JSON file, starting empty if it does not exist
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let project = if path.exists() {
+            serde_json::from_str(&std::fs::read_to_string(&path)?)?
+        } else {
+            ProjectApprovals::default()
+        };
+        Ok(Self {
+            path: Some(path),
+            project,
+            sessions: HashMap::new(),
+        })
+    }
+
+    /// Load the approvals of a project (`.ricecoder/tool_approvals.json`)
+    pub fn for_project<P: AsRef<Path>>(project_root: P) -> Result<Self> {
+        Self::load(
+            project_root
+                .as_ref()
+                .join(".ricecoder")
+                .join("tool_approvals.json"),
+        )
+    }
+
+    /// Whether a tool may run in a session without prompting
+    pub fn remembered(&self, session_id: &str, tool: &str) -> Option<RememberedApproval> {
+        if self.project.always_allow.contains_key(tool) {
+            Some(RememberedApproval::Project)
+        } else if self
+            .sessions
+            .get(session_id)
+            .is_some_and(|tools| tools.contains(tool))
+        {
+            Some(RememberedApproval::Session)
+        } else {
+            None
+        }
+    }
+
+    /// Remember a choice; "always allow" is written to the project file
+    pub fn apply(&mut self, session_id: &str, tool: &str, choice: ApprovalChoice) -> Result<()> {
+        match choice {
+            ApprovalChoice::AllowForSession => {
+                self.sessions
+                    .entry(session_id.to_string())
+                    .or_default()
+                    .insert(tool.to_string());
+            }
+            ApprovalChoice::AlwaysAllow => {
+                self.project
+                    .always_allow
+                    .insert(tool.to_string(), Utc::now());
+                self.save()?;
+            }
+            ApprovalChoice::AllowOnce | ApprovalChoice::Deny => {}
+        }
+        Ok(())
+    }
+
+    /// Stop always allowing a tool
+    pub fn revoke(&mut self, tool: &str) -> Result<bool> {
+        let removed = self.project.always_allow.remove(tool).is_some();
+        if removed {
+            self.save()?;
+        }
+        Ok(removed)
+    }
+
+    /// Forget the session-wide approvals of a finished session
+    pub fn end_session(&mut self, session_id: &str) {
+        self.sessions.remove(session_id);
+    }
+
+    /// The persisted project approvals
+    pub fn project(&self) -> &ProjectApprovals {
+        &self.project
+    }
+
+    fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(&self.project)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_risk_level_assess() {
+        assert_eq!(RiskLevel::assess("read_file"), RiskLevel::Low);
+        assert_eq!(RiskLevel::assess("write_file"), RiskLevel::Medium);
+        assert_eq!(RiskLevel::assess("run-shell-command"), RiskLevel::High);
+        // Fragments match whole words only
+        assert_eq!(RiskLevel::assess("grep"), RiskLevel::Low);
+    }
+
+    #[test]
+    fn test_approval_choice_from_input() {
+        assert_eq!(
+            ApprovalChoice::from_input(" S "),
+            Some(ApprovalChoice::AllowForSession)
+        );
+        assert_eq!(
+            ApprovalChoice::from_input("always"),
+            Some(ApprovalChoice::AlwaysAllow)
+        );
+        assert_eq!(ApprovalChoice::from_input("maybe"), None);
+        assert!(!ApprovalChoice::Deny.is_allowed());
+    }
+
+    #[test]
+    fn test_summarize_arguments() {
+        let mut arguments = HashMap::new();
+        arguments.insert("path".to_string(), Value::from("src/main.rs"));
+        arguments.insert("content".to_string(), Value::from("x".repeat(100)));
+        arguments.insert("append".to_string(), Value::from(true));
+
+        let summary = summarize_arguments(&arguments);
+        assert!(summary.starts_with("append=true, content=xxx"));
+        assert!(summary.contains("…, path=src/main.rs"));
+    }
+}
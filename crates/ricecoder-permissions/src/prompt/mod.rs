@@ -1,9 +1,14 @@
 //! Permission prompt module
 
+pub mod approval;
 pub mod decision;
 
 use std::io::{self, Write};
 
+pub use approval::{
+    summarize_arguments, ApprovalChoice, ApprovalRequest, ProjectApprovals, RememberedApproval,
+    RiskLevel, ToolApprovals,
+};
 pub use decision::{PromptResult, UserDecision};
 
 /// Permission prompt for user interaction
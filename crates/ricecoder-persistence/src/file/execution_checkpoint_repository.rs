@@ -0,0 +1,152 @@
+//! File-Backed Execution Checkpoint Repository Implementation
+
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+use ricecoder_domain::{
+    entities::ExecutionCheckpoint,
+    errors::{DomainError, DomainResult},
+    repositories::ExecutionCheckpointRepository,
+};
+
+use crate::error::PersistenceError;
+
+/// Stores one JSON checkpoint file per plan in a directory
+///
+/// Checkpoints are written to a temporary file and renamed into place, so a
+/// crash mid-write leaves the previous checkpoint intact.
+#[derive(Debug, Clone)]
+pub struct FileExecutionCheckpointRepository {
+    dir: PathBuf,
+}
+
+impl FileExecutionCheckpointRepository {
+    /// Store checkpoints in `dir`, created on first save
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Directory holding the checkpoint files
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path_for(&self, plan_id: &str) -> DomainResult<PathBuf> {
+        let valid = !plan_id.is_empty()
+            && plan_id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(DomainError::ValidationError {
+                field: "plan_id".to_string(),
+                reason: format!("Invalid plan id for checkpoint file: {}", plan_id),
+            });
+        }
+        Ok(self.dir.join(format!("{}.json", plan_id)))
+    }
+}
+
+fn io_error(error: std::io::Error) -> DomainError {
+    DomainError::IoError {
+        reason: error.to_string(),
+    }
+}
+
+#[async_trait]
+impl ExecutionCheckpointRepository for FileExecutionCheckpointRepository {
+    async fn save(&self, checkpoint: &ExecutionCheckpoint) -> DomainResult<()> {
+        let path = self.path_for(&checkpoint.plan_id)?;
+        let json = serde_json::to_vec_pretty(checkpoint)
+            .map_err(|e| PersistenceError::Serialization(e.to_string()))?;
+
+        tokio::fs::create_dir_all(&self.dir)
+            .await
+            .map_err(io_error)?;
+        let tmp = path.with_extension("json.tmp");
+        tokio::fs::write(&tmp, json).await.map_err(io_error)?;
+        tokio::fs::rename(&tmp, &path).await.map_err(io_error)?;
+
+        debug!(plan_id = %checkpoint.plan_id, path = %path.display(), "Saved execution checkpoint");
+        Ok(())
+    }
+
+    async fn find_by_plan(&self, plan_id: &str) -> DomainResult<Option<ExecutionCheckpoint>> {
+        let path = self.path_for(plan_id)?;
+        let bytes = match tokio::fs::read(&path).await {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(io_error(e)),
+        };
+        let checkpoint = serde_json::from_slice(&bytes)
+            .map_err(|e| PersistenceError::Deserialization(e.to_string()))?;
+        Ok(Some(checkpoint))
+    }
+
+    async fn list_plans(&self) -> DomainResult<Vec<String>> {
+        let mut entries = match tokio::fs::read_dir(&self.dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(io_error(e)),
+        };
+
+        let mut plans = Vec::new();
+        while let Some(entry) = entries.next_entry().await.map_err(io_error)? {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) == Some("json") {
+                if let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) {
+                    plans.push(stem.to_string());
+                }
+            }
+        }
+        plans.sort();
+        Ok(plans)
+    }
+
+    async fn delete(&self, plan_id: &str) -> DomainResult<()> {
+        let path = self.path_for(plan_id)?;
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(io_error(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn checkpoint(plan_id: &str) -> ExecutionCheckpoint {
+        ExecutionCheckpoint {
+            plan_id: plan_id.to_string(),
+            execution_id: "exec".to_string(),
+            plan: serde_json::json!({ "id": plan_id }),
+            state: serde_json::json!({ "current_step_index": 1 }),
+            file_hashes: HashMap::from([("src/lib.rs".to_string(), Some("abc".to_string()))]),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_round_trip() {
+        let dir =
+            std::env::temp_dir().join(format!("ricecoder-checkpoints-{}", uuid::Uuid::new_v4()));
+        let repo = FileExecutionCheckpointRepository::new(&dir);
+
+        let saved = checkpoint("plan-1");
+        repo.save(&saved).await.unwrap();
+
+        // A fresh repository sees what the previous process wrote
+        let reopened = FileExecutionCheckpointRepository::new(&dir);
+        assert_eq!(reopened.find_by_plan("plan-1").await.unwrap(), Some(saved));
+        assert_eq!(reopened.list_plans().await.unwrap(), vec!["plan-1"]);
+
+        reopened.delete("plan-1").await.unwrap();
+        assert!(reopened.find_by_plan("plan-1").await.unwrap().is_none());
+        assert!(repo.save(&checkpoint("../escape")).await.is_err());
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,10 @@
+//! File-Backed Repository Implementations
+//!
+//! Durable JSON-file implementations of domain repository interfaces and
+//! application ports for state that must survive a crash without a database.
+
+mod execution_checkpoint_repository;
+mod saga_store;
+
+pub use execution_checkpoint_repository::FileExecutionCheckpointRepository;
+pub use saga_store::FileSagaStore;
@@ -0,0 +1,164 @@
+//! File-Backed Saga Store Implementation
+
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+use ricecoder_application::{
+    errors::{ApplicationError, ApplicationResult},
+    saga::{SagaState, SagaStore},
+};
+
+/// Stores one JSON state file per saga in a directory
+///
+/// State is written to a temporary file and renamed into place, so a crash
+/// mid-write leaves the previous state intact and the saga can be resumed.
+#[derive(Debug, Clone)]
+pub struct FileSagaStore {
+    dir: PathBuf,
+}
+
+impl FileSagaStore {
+    /// Store saga state in `dir`, created on first save
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Directory holding the saga state files
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path_for(&self, saga_id: &str) -> ApplicationResult<PathBuf> {
+        let valid = !saga_id.is_empty()
+            && saga_id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(ApplicationError::ValidationFailed(format!(
+                "Invalid saga id for state file: {}",
+                saga_id
+            )));
+        }
+        Ok(self.dir.join(format!("{}.json", saga_id)))
+    }
+
+    async fn read(path: &Path) -> ApplicationResult<Option<SagaState>> {
+        let bytes = match tokio::fs::read(path).await {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(io_error(e)),
+        };
+        let state = serde_json::from_slice(&bytes).map_err(|e| {
+            ApplicationError::RepositoryError(format!(
+                "Failed to parse saga state {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        Ok(Some(state))
+    }
+}
+
+fn io_error(error: std::io::Error) -> ApplicationError {
+    ApplicationError::RepositoryError(error.to_string())
+}
+
+#[async_trait]
+impl SagaStore for FileSagaStore {
+    async fn save(&self, state: &SagaState) -> ApplicationResult<()> {
+        let path = self.path_for(&state.saga_id)?;
+        let json = serde_json::to_vec_pretty(state)
+            .map_err(|e| ApplicationError::RepositoryError(e.to_string()))?;
+
+        tokio::fs::create_dir_all(&self.dir)
+            .await
+            .map_err(io_error)?;
+        let tmp = path.with_extension("json.tmp");
+        tokio::fs::write(&tmp, json).await.map_err(io_error)?;
+        tokio::fs::rename(&tmp, &path).await.map_err(io_error)?;
+
+        debug!(saga_id = %state.saga_id, status = ?state.status, "Saved saga state");
+        Ok(())
+    }
+
+    async fn load(&self, saga_id: &str) -> ApplicationResult<Option<SagaState>> {
+        Self::read(&self.path_for(saga_id)?).await
+    }
+
+    async fn find_incomplete(&self) -> ApplicationResult<Vec<SagaState>> {
+        let mut entries = match tokio::fs::read_dir(&self.dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(io_error(e)),
+        };
+
+        let mut incomplete = Vec::new();
+        while let Some(entry) = entries.next_entry().await.map_err(io_error)? {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            // One unreadable file must not keep the other sagas from resuming
+            match Self::read(&path).await {
+                Ok(Some(state)) if state.status.is_incomplete() => incomplete.push(state),
+                Ok(_) => {}
+                Err(e) => warn!(path = %path.display(), "Skipping saga state: {}", e),
+            }
+        }
+        incomplete.sort_by_key(|state| state.started_at);
+        Ok(incomplete)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ricecoder_application::saga::SagaStatus;
+
+    fn state(saga_id: &str, status: SagaStatus) -> SagaState {
+        let now = chrono::Utc::now();
+        SagaState {
+            saga_id: saga_id.to_string(),
+            saga_type: "spec-to-pr".to_string(),
+            status,
+            current_step: 1,
+            completed_steps: vec!["plan".to_string()],
+            data: serde_json::json!({ "plan": true }),
+            error: None,
+            deadline: None,
+            started_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_incomplete_sagas_survive_restart() {
+        let dir = std::env::temp_dir().join(format!("ricecoder-sagas-{}", uuid::Uuid::new_v4()));
+        let store = FileSagaStore::new(&dir);
+
+        store
+            .save(&state("running", SagaStatus::Running))
+            .await
+            .unwrap();
+        store
+            .save(&state("done", SagaStatus::Completed))
+            .await
+            .unwrap();
+        std::fs::write(dir.join("corrupt.json"), "{").unwrap();
+
+        // A fresh store sees what the previous process wrote
+        let reopened = FileSagaStore::new(&dir);
+        let incomplete = reopened.find_incomplete().await.unwrap();
+        assert_eq!(incomplete.len(), 1);
+        assert_eq!(incomplete[0].saga_id, "running");
+        assert_eq!(incomplete[0].completed_steps, vec!["plan"]);
+
+        let done = reopened.load("done").await.unwrap().unwrap();
+        assert_eq!(done.status, SagaStatus::Completed);
+        assert!(reopened.load("missing").await.unwrap().is_none());
+        assert!(reopened.load("../escape").await.is_err());
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
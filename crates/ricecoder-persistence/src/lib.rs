@@ -6,6 +6,7 @@
 //! ## Features
 //!
 //! - **In-Memory Repositories**: Thread-safe in-memory implementations for testing and development
+//! - **File Repositories**: Durable JSON-file storage for execution checkpoints and saga state
 //! - **SurrealDB Repositories**: Production-ready persistence with SurrealDB backend
 //!
 //! ## Architecture
@@ -69,6 +70,7 @@
 //! ```
 
 pub mod error;
+pub mod file;
 pub mod memory;
 
 // SurrealDB backend for production persistence
@@ -78,7 +80,9 @@ pub mod surreal;
 pub use error::PersistenceError;
 
 // Re-export commonly used types
+pub use file::FileExecutionCheckpointRepository;
 pub use memory::{
+    InMemoryExecutionCheckpointRepository,
     InMemoryProjectRepository,
     InMemorySessionRepository,
     InMemorySpecificationRepository,
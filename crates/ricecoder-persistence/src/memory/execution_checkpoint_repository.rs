@@ -0,0 +1,88 @@
+//! In-Memory Execution Checkpoint Repository Implementation
+//!
+//! Memory backend for tests
+
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use std::collections::HashMap;
+
+use ricecoder_domain::{
+    entities::ExecutionCheckpoint, errors::DomainResult,
+    repositories::ExecutionCheckpointRepository,
+};
+
+/// Thread-safe in-memory implementation of ExecutionCheckpointRepository
+///
+/// Keeps the latest checkpoint per plan.
+#[derive(Debug, Default)]
+pub struct InMemoryExecutionCheckpointRepository {
+    checkpoints: RwLock<HashMap<String, ExecutionCheckpoint>>,
+}
+
+impl InMemoryExecutionCheckpointRepository {
+    /// Create a new empty in-memory checkpoint repository
+    pub fn new() -> Self {
+        Self {
+            checkpoints: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Get the current count of checkpoints (for testing)
+    pub fn count(&self) -> usize {
+        self.checkpoints.read().len()
+    }
+}
+
+#[async_trait]
+impl ExecutionCheckpointRepository for InMemoryExecutionCheckpointRepository {
+    async fn save(&self, checkpoint: &ExecutionCheckpoint) -> DomainResult<()> {
+        self.checkpoints
+            .write()
+            .insert(checkpoint.plan_id.clone(), checkpoint.clone());
+        Ok(())
+    }
+
+    async fn find_by_plan(&self, plan_id: &str) -> DomainResult<Option<ExecutionCheckpoint>> {
+        Ok(self.checkpoints.read().get(plan_id).cloned())
+    }
+
+    async fn list_plans(&self) -> DomainResult<Vec<String>> {
+        Ok(self.checkpoints.read().keys().cloned().collect())
+    }
+
+    async fn delete(&self, plan_id: &str) -> DomainResult<()> {
+        self.checkpoints.write().remove(plan_id);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkpoint(plan_id: &str, step: usize) -> ExecutionCheckpoint {
+        ExecutionCheckpoint {
+            plan_id: plan_id.to_string(),
+            execution_id: "exec".to_string(),
+            plan: serde_json::json!({ "id": plan_id }),
+            state: serde_json::json!({ "current_step_index": step }),
+            file_hashes: HashMap::new(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_save_replaces_previous_checkpoint() {
+        let repo = InMemoryExecutionCheckpointRepository::new();
+
+        repo.save(&checkpoint("plan", 1)).await.unwrap();
+        repo.save(&checkpoint("plan", 2)).await.unwrap();
+
+        assert_eq!(repo.count(), 1);
+        let found = repo.find_by_plan("plan").await.unwrap().unwrap();
+        assert_eq!(found.state["current_step_index"], 2);
+
+        repo.delete("plan").await.unwrap();
+        assert!(repo.find_by_plan("plan").await.unwrap().is_none());
+    }
+}
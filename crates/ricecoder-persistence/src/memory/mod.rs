@@ -5,10 +5,12 @@
 //!
 //! Memory backend for tests
 
+mod execution_checkpoint_repository;
 mod project_repository;
 mod session_repository;
 mod specification_repository;
 
+pub use execution_checkpoint_repository::InMemoryExecutionCheckpointRepository;
 pub use project_repository::InMemoryProjectRepository;
 pub use session_repository::InMemorySessionRepository;
 pub use specification_repository::InMemorySpecificationRepository;
@@ -14,6 +14,8 @@
 //! - **Output Capture**: Capture stdout/stderr with buffering
 //! - **Signal Handling**: Cross-platform signal delivery
 //! - **Process Tree Kill**: Kill process groups on Unix, task trees on Windows
+//! - **Supervision**: Restart policies, backoff caps and dependency ordering for
+//!   long-lived children, with state change events
 //!
 //! ## Usage
 //!
@@ -43,8 +45,12 @@ pub mod config;
 pub mod error;
 pub mod manager;
 pub mod child;
+pub mod supervisor;
 
 pub use config::ProcessConfig;
 pub use error::{ProcessError, Result};
 pub use manager::ProcessManager;
 pub use child::ManagedChild;
+pub use supervisor::{
+    Backoff, ChildSpec, ChildState, RestartPolicy, StartHook, Supervisor, SupervisorEvent,
+};
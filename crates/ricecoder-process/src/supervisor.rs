@@ -0,0 +1,606 @@
+//! Supervision of long-lived child process groups
+//!
+//! A [`Supervisor`] starts a group of long-lived children (LSP servers, MCP
+//! servers) in dependency order, restarts them according to their
+//! [`RestartPolicy`] with capped exponential backoff, stops them in reverse
+//! dependency order, and broadcasts [`SupervisorEvent`]s on every state change
+//! so health checkers can follow along.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use tokio::{
+    sync::{broadcast, watch},
+    task::JoinHandle,
+};
+use tracing::{debug, info, warn};
+
+use crate::{
+    child::ManagedChild,
+    config::ProcessConfig,
+    error::{ProcessError, Result},
+    manager::ProcessManager,
+};
+
+/// Capacity of the state change event channel
+const EVENT_CHANNEL_CAPACITY: usize = 256;
+
+/// When a supervised child is restarted after it exits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestartPolicy {
+    /// Never restart
+    Never,
+    /// Restart whenever the child exits
+    Always,
+    /// Restart only when the child exits unsuccessfully
+    OnFailure,
+}
+
+/// Exponential backoff between restarts
+#[derive(Debug, Clone, PartialEq)]
+pub struct Backoff {
+    /// Delay before the first restart
+    pub initial: Duration,
+    /// Upper bound for the delay
+    pub max: Duration,
+    /// Factor applied to the delay after each restart
+    pub multiplier: f64,
+    /// Give up after this many consecutive restarts (None = never give up)
+    pub max_restarts: Option<u32>,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            initial: Duration::from_millis(500),
+            max: Duration::from_secs(30),
+            multiplier: 2.0,
+            max_restarts: Some(5),
+        }
+    }
+}
+
+impl Backoff {
+    /// Delay before restart number `attempt` (1-based)
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let factor = self.multiplier.powi(attempt.saturating_sub(1) as i32);
+        self.initial.mul_f64(factor).min(self.max)
+    }
+}
+
+/// Hook run after each (re)start, e.g. to take the child's stdio handles
+pub type StartHook = Arc<dyn Fn(&mut ManagedChild) + Send + Sync>;
+
+/// Specification of a supervised child
+#[derive(Clone)]
+pub struct ChildSpec {
+    /// Unique name within the supervisor
+    pub name: String,
+    /// How to spawn the child
+    pub config: ProcessConfig,
+    /// When to restart the child
+    pub restart: RestartPolicy,
+    /// Delay between restarts
+    pub backoff: Backoff,
+    /// Children that must be running before this one starts
+    pub depends_on: Vec<String>,
+    on_start: Option<StartHook>,
+}
+
+impl fmt::Debug for ChildSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChildSpec")
+            .field("name", &self.name)
+            .field("config", &self.config)
+            .field("restart", &self.restart)
+            .field("backoff", &self.backoff)
+            .field("depends_on", &self.depends_on)
+            .finish()
+    }
+}
+
+impl ChildSpec {
+    /// Create a spec restarting on failure with default backoff
+    pub fn new(name: impl Into<String>, config: ProcessConfig) -> Self {
+        Self {
+            name: name.into(),
+            config,
+            restart: RestartPolicy::OnFailure,
+            backoff: Backoff::default(),
+            depends_on: Vec::new(),
+            on_start: None,
+        }
+    }
+
+    /// Set the restart policy
+    pub fn restart(mut self, policy: RestartPolicy) -> Self {
+        self.restart = policy;
+        self
+    }
+
+    /// Set the restart backoff
+    pub fn backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Start this child after `name` (and stop it before)
+    pub fn depends_on(mut self, name: impl Into<String>) -> Self {
+        self.depends_on.push(name.into());
+        self
+    }
+
+    /// Run `hook` each time the child is (re)started
+    pub fn on_start(mut self, hook: impl Fn(&mut ManagedChild) + Send + Sync + 'static) -> Self {
+        self.on_start = Some(Arc::new(hook));
+        self
+    }
+}
+
+/// Lifecycle state of a supervised child
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChildState {
+    /// Not started yet
+    Pending,
+    /// Running
+    Running {
+        /// Process ID
+        pid: u32,
+    },
+    /// Exited; a restart may follow
+    Exited {
+        /// Exit code, if the child was not killed by a signal
+        code: Option<i32>,
+    },
+    /// Waiting to restart
+    Restarting {
+        /// Consecutive restart number
+        attempt: u32,
+        /// Delay before the restart
+        delay: Duration,
+    },
+    /// Gave up on the child
+    Failed {
+        /// Why the child is not restarted
+        reason: String,
+    },
+    /// Stopped by the supervisor
+    Stopped,
+}
+
+impl ChildState {
+    /// Whether the child is running
+    pub fn is_running(&self) -> bool {
+        matches!(self, ChildState::Running { .. })
+    }
+}
+
+/// State change of a supervised child
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SupervisorEvent {
+    /// Name of the child
+    pub name: String,
+    /// New state
+    pub state: ChildState,
+}
+
+/// Shared state table and event channel
+#[derive(Clone)]
+struct StateTable {
+    states: Arc<Mutex<HashMap<String, ChildState>>>,
+    events: broadcast::Sender<SupervisorEvent>,
+}
+
+impl StateTable {
+    fn set(&self, name: &str, state: ChildState) {
+        debug!(child = %name, state = ?state, "Supervised child state changed");
+        if let Ok(mut states) = self.states.lock() {
+            states.insert(name.to_string(), state.clone());
+        }
+        // No subscribers is not an error
+        let _ = self.events.send(SupervisorEvent {
+            name: name.to_string(),
+            state,
+        });
+    }
+
+    fn get(&self, name: &str) -> Option<ChildState> {
+        self.states.lock().ok()?.get(name).cloned()
+    }
+}
+
+struct RunningChild {
+    stop: watch::Sender<bool>,
+    task: JoinHandle<()>,
+}
+
+/// Supervises a group of long-lived child processes
+pub struct Supervisor {
+    specs: Vec<ChildSpec>,
+    table: StateTable,
+    running: HashMap<String, RunningChild>,
+    start_timeout: Duration,
+}
+
+impl Supervisor {
+    /// Create an empty supervisor
+    pub fn new() -> Self {
+        let (events, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        Self {
+            specs: Vec::new(),
+            table: StateTable {
+                states: Arc::new(Mutex::new(HashMap::new())),
+                events,
+            },
+            running: HashMap::new(),
+            start_timeout: Duration::from_secs(10),
+        }
+    }
+
+    /// How long to wait for a child to start before starting its dependents
+    pub fn with_start_timeout(mut self, timeout: Duration) -> Self {
+        self.start_timeout = timeout;
+        self
+    }
+
+    /// Add a child to the group
+    ///
+    /// # Errors
+    /// Returns an error if a child with the same name exists.
+    pub fn add(&mut self, spec: ChildSpec) -> Result<()> {
+        if self.specs.iter().any(|s| s.name == spec.name) {
+            return Err(ProcessError::InvalidConfig(format!(
+                "Duplicate supervised child: {}",
+                spec.name
+            )));
+        }
+        self.table.set(&spec.name, ChildState::Pending);
+        self.specs.push(spec);
+        Ok(())
+    }
+
+    /// Subscribe to state change events
+    pub fn subscribe(&self) -> broadcast::Receiver<SupervisorEvent> {
+        self.table.events.subscribe()
+    }
+
+    /// Current state of a child
+    pub fn state(&self, name: &str) -> Option<ChildState> {
+        self.table.get(name)
+    }
+
+    /// Current state of every child
+    pub fn states(&self) -> HashMap<String, ChildState> {
+        self.table
+            .states
+            .lock()
+            .map(|states| states.clone())
+            .unwrap_or_default()
+    }
+
+    /// Child names in startup order (dependencies first)
+    ///
+    /// # Errors
+    /// Returns an error for unknown dependencies or dependency cycles.
+    pub fn startup_order(&self) -> Result<Vec<String>> {
+        let names: HashSet<&str> = self.specs.iter().map(|s| s.name.as_str()).collect();
+        for spec in &self.specs {
+            if let Some(missing) = spec.depends_on.iter().find(|d| !names.contains(d.as_str())) {
+                return Err(ProcessError::InvalidConfig(format!(
+                    "{} depends on unknown child {}",
+                    spec.name, missing
+                )));
+            }
+        }
+
+        let mut order: Vec<String> = Vec::with_capacity(self.specs.len());
+        while order.len() < self.specs.len() {
+            let ready: Vec<String> = self
+                .specs
+                .iter()
+                .filter(|s| !order.contains(&s.name))
+                .filter(|s| s.depends_on.iter().all(|d| order.contains(d)))
+                .map(|s| s.name.clone())
+                .collect();
+            if ready.is_empty() {
+                let remaining: Vec<&str> = self
+                    .specs
+                    .iter()
+                    .filter(|s| !order.contains(&s.name))
+                    .map(|s| s.name.as_str())
+                    .collect();
+                return Err(ProcessError::InvalidConfig(format!(
+                    "Dependency cycle between supervised children: {}",
+                    remaining.join(", ")
+                )));
+            }
+            order.extend(ready);
+        }
+        Ok(order)
+    }
+
+    /// Start all children in dependency order
+    ///
+    /// Each child must reach the running state before its dependents are
+    /// started.
+    ///
+    /// # Errors
+    /// Returns an error if the dependency graph is invalid or a child fails
+    /// to start within the start timeout; children already started keep
+    /// running.
+    pub async fn start(&mut self) -> Result<()> {
+        let order = self.startup_order()?;
+        for name in order {
+            if self.running.contains_key(&name) {
+                continue;
+            }
+            let spec = self
+                .specs
+                .iter()
+                .find(|s| s.name == name)
+                .cloned()
+                .expect("name comes from specs");
+
+            let mut events = self.subscribe();
+            let (stop, stop_rx) = watch::channel(false);
+            let task = tokio::spawn(supervise(spec, self.table.clone(), stop_rx));
+            self.running
+                .insert(name.clone(), RunningChild { stop, task });
+
+            if !self.table.get(&name).is_some_and(|s| s.is_running()) {
+                self.wait_until_running(&name, &mut events).await?;
+            }
+            info!(child = %name, "Supervised child started");
+        }
+        Ok(())
+    }
+
+    async fn wait_until_running(
+        &self,
+        name: &str,
+        events: &mut broadcast::Receiver<SupervisorEvent>,
+    ) -> Result<()> {
+        let wait = async {
+            loop {
+                match events.recv().await {
+                    Ok(event) if event.name == name => match event.state {
+                        ChildState::Running { .. } => return Ok(()),
+                        ChildState::Failed { reason } => {
+                            return Err(ProcessError::Crashed { reason })
+                        }
+                        _ => {}
+                    },
+                    Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                    Err(broadcast::error::RecvError::Closed) => {
+                        return Err(ProcessError::Crashed {
+                            reason: format!("{} stopped before it started", name),
+                        })
+                    }
+                }
+            }
+        };
+        tokio::time::timeout(self.start_timeout, wait)
+            .await
+            .map_err(|_| ProcessError::Timeout {
+                seconds: self.start_timeout.as_secs(),
+            })?
+    }
+
+    /// Stop all children in reverse dependency order
+    pub async fn stop(&mut self) -> Result<()> {
+        let order = self.startup_order()?;
+        for name in order.iter().rev() {
+            if let Some(child) = self.running.remove(name) {
+                let _ = child.stop.send(true);
+                if let Err(e) = child.task.await {
+                    warn!(child = %name, error = %e, "Supervisor task ended abnormally");
+                }
+                info!(child = %name, "Supervised child stopped");
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for Supervisor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Run one child until it is stopped or given up on
+async fn supervise(spec: ChildSpec, table: StateTable, mut stop: watch::Receiver<bool>) {
+    let manager = ProcessManager::new();
+    let mut attempt: u32 = 0;
+
+    loop {
+        let started_at = Instant::now();
+        let exit = match manager.spawn(spec.config.clone()).await {
+            Ok(mut child) => {
+                if let Some(hook) = &spec.on_start {
+                    hook(&mut child);
+                }
+                table.set(&spec.name, ChildState::Running { pid: child.pid() });
+
+                tokio::select! {
+                    status = child.wait() => status.map(|s| s.code()),
+                    _ = stop.changed() => {
+                        if let Err(e) = child.shutdown().await {
+                            warn!(child = %spec.name, error = %e, "Failed to stop supervised child");
+                        }
+                        table.set(&spec.name, ChildState::Stopped);
+                        return;
+                    }
+                }
+            }
+            Err(e) => Err(e),
+        };
+
+        let succeeded = match &exit {
+            Ok(code) => {
+                table.set(&spec.name, ChildState::Exited { code: *code });
+                *code == Some(0)
+            }
+            Err(e) => {
+                warn!(child = %spec.name, error = %e, "Supervised child failed");
+                false
+            }
+        };
+
+        let restart = match spec.restart {
+            RestartPolicy::Never => false,
+            RestartPolicy::Always => true,
+            RestartPolicy::OnFailure => !succeeded,
+        };
+        if !restart {
+            if !succeeded {
+                let reason = match exit {
+                    Ok(code) => format!("exited with code {:?}", code),
+                    Err(e) => e.to_string(),
+                };
+                table.set(&spec.name, ChildState::Failed { reason });
+            }
+            return;
+        }
+
+        // A child that stayed up longer than the backoff cap is healthy again
+        if started_at.elapsed() >= spec.backoff.max {
+            attempt = 0;
+        }
+        attempt += 1;
+        if spec.backoff.max_restarts.is_some_and(|max| attempt > max) {
+            table.set(
+                &spec.name,
+                ChildState::Failed {
+                    reason: format!("restart limit of {} reached", attempt - 1),
+                },
+            );
+            return;
+        }
+
+        let delay = spec.backoff.delay(attempt);
+        table.set(&spec.name, ChildState::Restarting { attempt, delay });
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            _ = stop.changed() => {
+                table.set(&spec.name, ChildState::Stopped);
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fast_backoff(max_restarts: u32) -> Backoff {
+        Backoff {
+            initial: Duration::from_millis(10),
+            max: Duration::from_millis(40),
+            multiplier: 2.0,
+            max_restarts: Some(max_restarts),
+        }
+    }
+
+    #[test]
+    fn test_backoff_is_capped() {
+        let backoff = fast_backoff(5);
+        assert_eq!(backoff.delay(1), Duration::from_millis(10));
+        assert_eq!(backoff.delay(2), Duration::from_millis(20));
+        assert_eq!(backoff.delay(4), Duration::from_millis(40));
+    }
+
+    #[test]
+    fn test_startup_order_and_cycles() {
+        let mut supervisor = Supervisor::new();
+        supervisor
+            .add(ChildSpec::new("lsp", ProcessConfig::new("true")).depends_on("mcp"))
+            .unwrap();
+        supervisor
+            .add(ChildSpec::new("mcp", ProcessConfig::new("true")))
+            .unwrap();
+        assert_eq!(supervisor.startup_order().unwrap(), vec!["mcp", "lsp"]);
+        assert!(supervisor
+            .add(ChildSpec::new("mcp", ProcessConfig::new("true")))
+            .is_err());
+
+        let mut cyclic = Supervisor::new();
+        cyclic
+            .add(ChildSpec::new("a", ProcessConfig::new("true")).depends_on("b"))
+            .unwrap();
+        cyclic
+            .add(ChildSpec::new("b", ProcessConfig::new("true")).depends_on("a"))
+            .unwrap();
+        assert!(cyclic.startup_order().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_on_failure_gives_up_after_restart_limit() {
+        let mut supervisor = Supervisor::new();
+        supervisor
+            .add(
+                ChildSpec::new("flaky", ProcessConfig::new("sh").args(["-c", "exit 3"]))
+                    .backoff(fast_backoff(2)),
+            )
+            .unwrap();
+        let mut events = supervisor.subscribe();
+
+        supervisor.start().await.unwrap();
+
+        let mut restarts = 0;
+        loop {
+            let event = tokio::time::timeout(Duration::from_secs(5), events.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            match event.state {
+                ChildState::Restarting { .. } => restarts += 1,
+                ChildState::Failed { reason } => {
+                    assert!(reason.contains("restart limit"));
+                    break;
+                }
+                _ => {}
+            }
+        }
+        assert_eq!(restarts, 2);
+        supervisor.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_stop_in_reverse_dependency_order() {
+        let mut supervisor = Supervisor::new();
+        supervisor
+            .add(
+                ChildSpec::new("client", ProcessConfig::new("sleep").args(["10"]))
+                    .depends_on("server")
+                    .restart(RestartPolicy::Always),
+            )
+            .unwrap();
+        supervisor
+            .add(
+                ChildSpec::new("server", ProcessConfig::new("sleep").args(["10"]))
+                    .restart(RestartPolicy::Always),
+            )
+            .unwrap();
+
+        supervisor.start().await.unwrap();
+        assert!(supervisor.state("server").unwrap().is_running());
+        assert!(supervisor.state("client").unwrap().is_running());
+
+        let mut events = supervisor.subscribe();
+        supervisor.stop().await.unwrap();
+
+        let mut stopped = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            if event.state == ChildState::Stopped {
+                stopped.push(event.name);
+            }
+        }
+        assert_eq!(stopped, vec!["client", "server"]);
+    }
+}
@@ -11,8 +11,10 @@ use std::{
 
 use crate::{
     error::ProviderError,
+    evaluation::ProviderEvaluation,
     models::{ModelInfo, TokenUsage},
     performance_monitor::{PerformanceThresholds, ProviderMetrics, ProviderPerformanceMonitor},
+    routing::TaskClass,
 };
 
 /// Quality score for a provider (0.0 to 1.0)
@@ -32,6 +34,26 @@ pub struct QualityScore {
     pub last_updated: SystemTime,
 }
 
+/// Quality and latency of a provider for one task class
+#[derive(Debug, Clone)]
+pub struct TaskScore {
+    /// Benchmark quality (0.0 to 1.0), once the provider has been evaluated
+    pub quality: Option<f64>,
+    /// Smoothed response time in milliseconds
+    pub latency_ms: f64,
+    /// Number of observations folded into the score
+    pub samples: usize,
+    /// Last updated timestamp
+    pub last_updated: SystemTime,
+}
+
+impl TaskScore {
+    /// Latency score (0.0 to 1.0); one second scores 0.5
+    pub fn latency_score(&self) -> f64 {
+        1.0 / (1.0 + self.latency_ms.max(0.0) / 1000.0)
+    }
+}
+
 /// Reliability status of a provider
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ReliabilityStatus {
@@ -66,6 +88,10 @@ pub struct CurationConfig {
     pub speed_weight: f64,
     /// Reliability optimization weight (0.0 to 1.0)
     pub reliability_weight: f64,
+    /// Weight of latency against quality when routing by task (0.0 to 1.0)
+    pub task_latency_weight: f64,
+    /// Weight of each new observation in the smoothed task scores (0.0 to 1.0)
+    pub task_score_smoothing: f64,
 }
 
 impl Default for CurationConfig {
@@ -79,6 +105,8 @@ impl Default for CurationConfig {
             cost_weight: 0.3,
             speed_weight: 0.4,
             reliability_weight: 0.3,
+            task_latency_weight: 0.3,
+            task_score_smoothing: 0.3,
         }
     }
 }
@@ -179,6 +207,7 @@ pub struct ProviderCurator {
     config: CurationConfig,
     quality_scores: HashMap<String, QualityScore>,
     reliability_trackers: HashMap<String, ReliabilityTracker>,
+    task_scores: HashMap<String, HashMap<TaskClass, TaskScore>>,
     performance_monitor: Arc<ProviderPerformanceMonitor>,
 }
 
@@ -192,6 +221,7 @@ impl ProviderCurator {
             config,
             quality_scores: HashMap::new(),
             reliability_trackers: HashMap::new(),
+            task_scores: HashMap::new(),
             performance_monitor,
         }
     }
@@ -439,6 +469,111 @@ impl ProviderCurator {
         true
     }
 
+    /// Record an observed response time for a task
+    pub fn record_task_latency(&mut self, provider_id: &str, task: TaskClass, latency_ms: f64) {
+        self.update_task_score(provider_id, task, None, latency_ms);
+    }
+
+    /// Record a benchmark quality and response time for a task
+    pub fn record_task_result(
+        &mut self,
+        provider_id: &str,
+        task: TaskClass,
+        quality: f64,
+        latency_ms: f64,
+    ) {
+        self.update_task_score(provider_id, task, Some(quality.clamp(0.0, 1.0)), latency_ms);
+    }
+
+    fn update_task_score(
+        &mut self,
+        provider_id: &str,
+        task: TaskClass,
+        quality: Option<f64>,
+        latency_ms: f64,
+    ) {
+        let alpha = self.config.task_score_smoothing.clamp(0.0, 1.0);
+        let smooth = |old: f64, new: f64| old + alpha * (new - old);
+        let scores = self.task_scores.entry(provider_id.to_string()).or_default();
+
+        match scores.get_mut(&task) {
+            Some(score) => {
+                score.latency_ms = smooth(score.latency_ms, latency_ms);
+                if let Some(quality) = quality {
+                    score.quality = Some(score.quality.map_or(quality, |q| smooth(q, quality)));
+                }
+                score.samples += 1;
+                score.last_updated = SystemTime::now();
+            }
+            None => {
+                scores.insert(
+                    task,
+                    TaskScore {
+                        quality,
+                        latency_ms,
+                        samples: 1,
+                        last_updated: SystemTime::now(),
+                    },
+                );
+            }
+        }
+    }
+
+    /// Fold an evaluation's benchmark results into the task scores
+    ///
+    /// Benchmarks that measure no known task class are ignored.
+    pub fn apply_evaluation(&mut self, evaluation: &ProviderEvaluation) {
+        for result in &evaluation.benchmark_results {
+            if let Some(task) = TaskClass::from_benchmark(&result.benchmark_name) {
+                self.record_task_result(
+                    &evaluation.provider_id,
+                    task,
+                    result.score,
+                    result.avg_response_time_ms,
+                );
+            }
+        }
+    }
+
+    /// Get the score of a provider for a task
+    pub fn get_task_score(&self, provider_id: &str, task: TaskClass) -> Option<&TaskScore> {
+        self.task_scores.get(provider_id)?.get(&task)
+    }
+
+    /// Routing score (0.0 to 1.0) of a provider for a task
+    ///
+    /// Blends quality and latency, discounted by reliability. Providers that
+    /// have only been timed count as average quality. `None` when the
+    /// provider has no score for the task.
+    pub fn task_route_score(&self, provider_id: &str, task: TaskClass) -> Option<f64> {
+        let score = self.get_task_score(provider_id, task)?;
+        let latency_weight = self.config.task_latency_weight.clamp(0.0, 1.0);
+        let blended = score.quality.unwrap_or(0.5) * (1.0 - latency_weight)
+            + score.latency_score() * latency_weight;
+        Some(blended * self.calculate_reliability_score(provider_id))
+    }
+
+    /// Select the best provider for a task
+    ///
+    /// Among eligible providers, the one with the highest task routing score
+    /// wins. `None` when no eligible provider has a score for the task yet.
+    pub fn select_provider_for_task(
+        &self,
+        provider_ids: &[String],
+        task: TaskClass,
+        constraints: Option<&SelectionConstraints>,
+    ) -> Option<String> {
+        let default_constraints = SelectionConstraints::default();
+        let constraints = constraints.unwrap_or(&default_constraints);
+
+        provider_ids
+            .iter()
+            .filter(|id| self.is_provider_eligible(id, constraints))
+            .filter_map(|id| Some((id, self.task_route_score(id, task)?)))
+            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(id, _)| id.clone())
+    }
+
     /// Get providers sorted by quality score
     pub fn get_providers_by_quality(&self, provider_ids: &[String]) -> Vec<(String, f64)> {
         let mut providers: Vec<(String, f64)> = provider_ids
@@ -513,6 +648,7 @@ mod tests {
             name: "GPT-4".to_string(),
             provider: "openai".to_string(),
             context_window: 8192,
+            max_output_tokens: None,
             capabilities: vec![Capability::Chat, Capability::FunctionCalling],
             pricing: None,
             is_free: false,
@@ -557,6 +693,7 @@ mod tests {
                 name: "Model 1".to_string(),
                 provider: "provider_a".to_string(),
                 context_window: 4096,
+                max_output_tokens: None,
                 capabilities: vec![Capability::Chat],
                 pricing: None,
                 is_free: false,
@@ -569,6 +706,7 @@ mod tests {
                 name: "Model 2".to_string(),
                 provider: "provider_b".to_string(),
                 context_window: 8192,
+                max_output_tokens: None,
                 capabilities: vec![Capability::Chat, Capability::FunctionCalling],
                 pricing: None,
                 is_free: false,
@@ -584,4 +722,101 @@ mod tests {
         assert!(best.is_some());
         assert!(providers.contains(&best.unwrap()));
     }
+
+    fn evaluation(provider_id: &str, results: &[(&str, f64, f64)]) -> ProviderEvaluation {
+        ProviderEvaluation {
+            provider_id: provider_id.to_string(),
+            model: "model".to_string(),
+            overall_score: 0.0,
+            benchmark_results: results
+                .iter()
+                .map(
+                    |(name, score, latency)| crate::evaluation::BenchmarkResult {
+                        benchmark_name: name.to_string(),
+                        score: *score,
+                        total_tests: 1,
+                        passed_tests: 1,
+                        avg_response_time_ms: *latency,
+                        total_tokens: 0,
+                        cost: 0.0,
+                        timestamp: SystemTime::now(),
+                    },
+                )
+                .collect(),
+            performance_metrics: crate::evaluation::PerformanceMetrics {
+                avg_response_time_ms: 0.0,
+                p95_response_time_ms: 0.0,
+                requests_per_second: 0.0,
+                total_tokens: 0,
+                total_cost: 0.0,
+            },
+            reliability_score: 1.0,
+            cost_efficiency_score: 1.0,
+            timestamp: SystemTime::now(),
+        }
+    }
+
+    #[test]
+    fn test_task_routing_from_evaluations() {
+        let monitor = Arc::new(ProviderPerformanceMonitor::default());
+        let mut curator = ProviderCurator::default(monitor);
+
+        curator.apply_evaluation(&evaluation(
+            "fast_chat",
+            &[("basic_chat", 0.9, 300.0), ("code_generation", 0.3, 300.0)],
+        ));
+        curator.apply_evaluation(&evaluation(
+            "coder",
+            &[
+                ("basic_chat", 0.6, 2000.0),
+                ("code_generation", 1.0, 1500.0),
+            ],
+        ));
+
+        let providers = vec!["fast_chat".to_string(), "coder".to_string()];
+        let pick = |curator: &ProviderCurator, task| {
+            curator.select_provider_for_task(&providers, task, None)
+        };
+        assert_eq!(
+            pick(&curator, TaskClass::Chat).as_deref(),
+            Some("fast_chat")
+        );
+        assert_eq!(
+            pick(&curator, TaskClass::CodeGeneration).as_deref(),
+            Some("coder")
+        );
+        // Nobody has served embeddings yet
+        assert_eq!(pick(&curator, TaskClass::Embeddings), None);
+
+        curator.record_task_latency("coder", TaskClass::Embeddings, 80.0);
+        assert_eq!(
+            pick(&curator, TaskClass::Embeddings).as_deref(),
+            Some("coder")
+        );
+    }
+
+    #[test]
+    fn test_task_scores_are_smoothed() {
+        let monitor = Arc::new(ProviderPerformanceMonitor::default());
+        let mut curator = ProviderCurator::default(monitor);
+
+        curator.record_task_latency("p", TaskClass::Chat, 1000.0);
+        assert_eq!(
+            curator
+                .get_task_score("p", TaskClass::Chat)
+                .unwrap()
+                .quality,
+            None
+        );
+        curator.record_task_result("p", TaskClass::Chat, 1.0, 2000.0);
+        curator.record_task_result("p", TaskClass::Chat, 0.0, 1000.0);
+
+        let score = curator.get_task_score("p", TaskClass::Chat).unwrap();
+        assert_eq!(score.samples, 3);
+        assert!((score.quality.unwrap() - 0.7).abs() < 1e-9);
+        assert!((score.latency_ms - 1210.0).abs() < 1e-9);
+        assert!(curator
+            .get_task_score("p", TaskClass::Summarization)
+            .is_none());
+    }
 }
@@ -60,6 +60,10 @@ impl ProviderErrorMapper {
                 field: "model".to_string(),
                 reason: format!("Invalid model: {}", model),
             },
+            ProviderError::InvalidParameter(reason) => DomainError::ValidationError {
+                field: "parameters".to_string(),
+                reason,
+            },
             ProviderError::ModelNotAvailable(model) => DomainError::InvalidProviderConfig {
                 reason: format!("Model not available: {}", model),
             },
@@ -107,7 +111,7 @@ fn to_domain_request(request: ChatRequest) -> AiChatRequest {
             .collect(),
         temperature: request.temperature,
         max_tokens: request.max_tokens.map(|t| t as u32),
-        stop: None, // Infrastructure ChatRequest doesn't support stop sequences
+        stop: request.stop,
     }
 }
 
@@ -130,6 +134,10 @@ fn to_infra_request(request: AiChatRequest) -> ChatRequest {
         temperature: request.temperature,
         max_tokens: request.max_tokens.map(|t| t as usize),
         stream: false, // Domain requests are non-streaming by default
+        top_p: None,
+        stop: request.stop,
+        seed: None,
+        reasoning_effort: None,
     }
 }
 
@@ -159,6 +167,7 @@ fn to_domain_capability(cap: provider_models::Capability) -> ModelCapability {
         provider_models::Capability::Vision => ModelCapability::Vision,
         provider_models::Capability::Streaming => ModelCapability::Streaming,
         provider_models::Capability::FunctionCalling => ModelCapability::FunctionCalling,
+        provider_models::Capability::Reasoning => ModelCapability::Reasoning,
     }
 }
 
@@ -367,6 +376,7 @@ impl AiProviderChat for DomainProviderAdapter {
                     true
                 }
                 (provider_models::Capability::Streaming, ModelCapability::Streaming) => true,
+                (provider_models::Capability::Reasoning, ModelCapability::Reasoning) => true,
                 // Embeddings not supported in infrastructure yet
                 (_, ModelCapability::Embeddings) => false,
                 _ => false,
@@ -445,6 +455,7 @@ mod tests {
                 name: "Test Model".to_string(),
                 provider: self.id.clone(),
                 context_window: 4096,
+                max_output_tokens: None,
                 capabilities: vec![Capability::Chat],
                 pricing: None,
                 is_free: true,
@@ -560,6 +571,7 @@ mod tests {
             name: "GPT-4".to_string(),
             provider: "openai".to_string(),
             context_window: 8192,
+            max_output_tokens: None,
             capabilities: vec![Capability::Chat, Capability::Code],
             pricing: None,
             is_free: false,
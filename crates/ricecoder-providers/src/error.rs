@@ -37,6 +37,10 @@ pub enum ProviderError {
     #[error("Invalid model: {0}")]
     InvalidModel(String),
 
+    /// Request parameter rejected before sending
+    #[error("Invalid parameter: {0}")]
+    InvalidParameter(String),
+
     /// Model not available in provider
     #[error("Model not available: {0}")]
     ModelNotAvailable(String),
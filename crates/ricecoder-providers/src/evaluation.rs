@@ -158,6 +158,10 @@ impl ProviderEvaluator {
                 max_tokens: Some(512),
                 stream: false,
                 temperature: Some(0.7),
+                top_p: None,
+                stop: None,
+                seed: None,
+                reasoning_effort: None,
             };
 
             match provider.chat(request).await {
@@ -261,6 +265,10 @@ impl ProviderEvaluator {
                 max_tokens: Some(50),
                 stream: false,
                 temperature: Some(0.1),
+                top_p: None,
+                stop: None,
+                seed: None,
+                reasoning_effort: None,
             };
 
             if provider.chat(request).await.is_ok() {
@@ -357,6 +365,17 @@ impl ProviderEvaluator {
                     expected_patterns: vec!["no".to_string(), "not necessarily".to_string()],
                 }],
             },
+            Benchmark {
+                name: "summarization".to_string(),
+                description: "Condensing text while keeping the key facts".to_string(),
+                weight: 1.0,
+                test_cases: vec![TestCase {
+                    prompt: "Summarize in one sentence: The release was delayed by two weeks \
+                             because the database migration failed on the staging servers."
+                        .to_string(),
+                    expected_patterns: vec!["migration".to_string(), "delay".to_string()],
+                }],
+            },
         ]
     }
 }
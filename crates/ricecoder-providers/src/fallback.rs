@@ -92,6 +92,7 @@ mod tests {
                 name: "GPT-4".to_string(),
                 provider: "openai".to_string(),
                 context_window: 8192,
+                max_output_tokens: None,
                 capabilities: vec![Capability::Chat, Capability::Code],
                 pricing: None,
                 is_free: false,
@@ -101,6 +102,7 @@ mod tests {
                 name: "GPT-3.5 Turbo".to_string(),
                 provider: "openai".to_string(),
                 context_window: 4096,
+                max_output_tokens: None,
                 capabilities: vec![Capability::Chat],
                 pricing: None,
                 is_free: false,
@@ -110,6 +112,7 @@ mod tests {
                 name: "Claude 3 Sonnet".to_string(),
                 provider: "anthropic".to_string(),
                 context_window: 200000,
+                max_output_tokens: None,
                 capabilities: vec![Capability::Chat, Capability::Vision],
                 pricing: None,
                 is_free: false,
@@ -120,6 +120,7 @@ mod tests {
                 name: "GPT-4".to_string(),
                 provider: "openai".to_string(),
                 context_window: 8192,
+                max_output_tokens: None,
                 capabilities: vec![Capability::Chat],
                 pricing: None,
                 is_free: false,
@@ -129,6 +130,7 @@ mod tests {
                 name: "Claude 3".to_string(),
                 provider: "anthropic".to_string(),
                 context_window: 200000,
+                max_output_tokens: None,
                 capabilities: vec![Capability::Chat],
                 pricing: None,
                 is_free: false,
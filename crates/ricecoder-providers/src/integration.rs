@@ -99,6 +99,9 @@ impl ProviderIntegration {
             Some("openai") => "OpenAI".to_string(),
             Some("anthropic") => "Anthropic".to_string(),
             Some("ollama") => "Ollama".to_string(),
+            Some("llamacpp") => "llama.cpp".to_string(),
+            Some("lmstudio") => "LM Studio".to_string(),
+            Some("vllm") => "vLLM".to_string(),
             Some("google") => "Google".to_string(),
             Some("zen") => "Zen".to_string(),
             Some(other) => other.to_string(),
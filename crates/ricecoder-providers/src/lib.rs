@@ -22,10 +22,13 @@ pub mod model_registry;
 pub mod models;
 pub mod models_dev;
 pub mod performance_monitor;
+pub mod prompt_library;
 pub mod provider;
 pub mod providers;
 pub mod rate_limiter;
+pub mod recording;
 pub mod redaction;
+pub mod routing;
 pub mod security_headers;
 pub mod streaming;
 pub mod sync;
@@ -42,6 +45,7 @@ pub use community::{
 };
 pub use curation::{
     CurationConfig, ProviderCurator, QualityScore, ReliabilityStatus, SelectionConstraints,
+    TaskScore,
 };
 pub use error::ProviderError;
 pub use evaluation::{
@@ -53,24 +57,35 @@ pub use health_check::{HealthCheckCache, HealthCheckResult};
 pub use integration::ProviderIntegration;
 pub use model_registry::{global_registry, ModelRegistry};
 pub use models::{
-    Capability, ChatRequest, ChatResponse, FinishReason, Message, ModelInfo, TokenUsage,
+    Capability, ChatRequest, ChatResponse, EmbeddingRequest, EmbeddingResponse, FinishReason,
+    Message, ModelInfo, ModelParams, ReasoningEffort, TokenUsage,
 };
 pub use models_dev::{fetch_models, ModelsDevCache, ModelsDevModel, ModelsDevResponse, ModelsFetcher};
 pub use performance_monitor::{
     PerformanceSummary, PerformanceThresholds, ProviderMetrics, ProviderPerformanceMonitor,
 };
+pub use prompt_library::{
+    BindingTarget, PromptAuditLog, PromptLibrary, PromptUsage, ResolvedPrompt,
+};
 pub use provider::{
     manager::{ConnectionState, ModelFilter, ModelFilterCriteria, ProviderStatus},
     Provider, ProviderManager, ProviderRegistry,
 };
 pub use providers::{
-    AnthropicProvider, AzureOpenAiProvider, CohereProvider, GcpVertexProvider, GoogleProvider,
-    OllamaProvider, OpenAiProvider, QwenProvider, ReplicateProvider, TogetherProvider, ZenProvider,
+    discover_local_models, AnthropicProvider, AzureOpenAiProvider, CohereProvider,
+    DiscoveredRuntime, GcpVertexProvider, GoogleProvider, LocalDiscovery, LocalModelProvider,
+    LocalRuntime, OllamaProvider, OpenAiProvider, QwenProvider, ReplicateProvider,
+    TogetherProvider, ZenProvider,
 };
 pub use rate_limiter::{ExponentialBackoff, RateLimiterRegistry, TokenBucketLimiter};
+pub use recording::{
+    rerun_session, Cassette, Interaction, RecordMode, RecordedOutcome, RecordedRequest,
+    RecordingProvider, ReplayMode, ReplayProvider, RerunTurn,
+};
 pub use circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitBreakerRegistry, CircuitState};
 pub use domain_adapter::{DomainProviderAdapter, ProviderErrorMapper};
 pub use redaction::{contains_sensitive_info, redact, Redacted, RedactionFilter};
+pub use routing::{RoutingDecision, TaskClass};
 pub use security_headers::{SecurityHeadersBuilder, SecurityHeadersValidator};
 pub use streaming::{simulate_stream, simulate_word_stream, SimulatedStream, WordStream};
 pub use sync::{
@@ -79,5 +94,6 @@ pub use sync::{
 };
 pub use token_counter::{TokenCounter, TokenCounterTrait};
 pub use transform::{
-    transform_for_claude, transform_for_mistral, transform_schema_for_gemini,
+    params_for, transform_for_claude, transform_for_mistral, transform_schema_for_gemini,
+    ParamDialect,
 };
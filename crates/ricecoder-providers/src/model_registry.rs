@@ -162,6 +162,7 @@ impl ModelRegistry {
                 "vision" => Some(Capability::Vision),
                 "function_calling" => Some(Capability::FunctionCalling),
                 "streaming" => Some(Capability::Streaming),
+                "reasoning" => Some(Capability::Reasoning),
                 _ => None,
             })
             .collect();
@@ -176,6 +177,7 @@ impl ModelRegistry {
             name: model.name,
             provider: provider_id.to_string(),
             context_window: model.context_window,
+            max_output_tokens: model.max_output_tokens,
             capabilities,
             pricing,
             is_free: model.is_free,
@@ -4,6 +4,8 @@ use std::{collections::HashMap, time::Duration};
 
 use serde::{Deserialize, Serialize};
 
+use crate::error::ProviderError;
+
 /// Information about an available model
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModelInfo {
@@ -15,6 +17,9 @@ pub struct ModelInfo {
     pub provider: String,
     /// Maximum context window in tokens
     pub context_window: usize,
+    /// Maximum tokens the model generates in one response, if known
+    #[serde(default)]
+    pub max_output_tokens: Option<usize>,
     /// Model capabilities
     pub capabilities: Vec<Capability>,
     /// Optional pricing information
@@ -37,6 +42,8 @@ pub enum Capability {
     FunctionCalling,
     /// Streaming capability
     Streaming,
+    /// Reasoning (extended thinking) capability
+    Reasoning,
 }
 
 /// Pricing information for a model
@@ -70,6 +77,133 @@ pub struct ChatRequest {
     pub max_tokens: Option<usize>,
     /// Whether to stream the response
     pub stream: bool,
+    /// Nucleus sampling probability mass (0.0 to 1.0)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    /// Sequences that end generation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stop: Option<Vec<String>>,
+    /// Seed for reproducible sampling
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    /// Reasoning effort (requires [`Capability::Reasoning`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning_effort: Option<ReasoningEffort>,
+}
+
+impl ChatRequest {
+    /// Fill parameters the request leaves unset from `defaults`
+    pub fn apply_defaults(&mut self, defaults: &ModelParams) {
+        self.temperature = self.temperature.or(defaults.temperature);
+        self.top_p = self.top_p.or(defaults.top_p);
+        self.max_tokens = self.max_tokens.or(defaults.max_tokens);
+        if self.stop.is_none() {
+            self.stop = defaults.stop.clone();
+        }
+        self.seed = self.seed.or(defaults.seed);
+        self.reasoning_effort = self.reasoning_effort.or(defaults.reasoning_effort);
+    }
+
+    /// Check the request parameters against the capabilities of `model`
+    pub fn validate_params(&self, model: &ModelInfo) -> Result<(), ProviderError> {
+        if let Some(temperature) = self.temperature {
+            if !(0.0..=2.0).contains(&temperature) {
+                return Err(ProviderError::InvalidParameter(format!(
+                    "temperature must be between 0.0 and 2.0, got {}",
+                    temperature
+                )));
+            }
+        }
+        if let Some(top_p) = self.top_p {
+            if !(top_p > 0.0 && top_p <= 1.0) {
+                return Err(ProviderError::InvalidParameter(format!(
+                    "top_p must be in (0.0, 1.0], got {}",
+                    top_p
+                )));
+            }
+        }
+        if let Some(max_tokens) = self.max_tokens {
+            // Without a known output limit, the context window still bounds it
+            let limit = model.max_output_tokens.unwrap_or(model.context_window);
+            if max_tokens == 0 || max_tokens > limit {
+                return Err(ProviderError::InvalidParameter(format!(
+                    "max_tokens must be between 1 and {} for {}, got {}",
+                    limit, model.id, max_tokens
+                )));
+            }
+        }
+        if let Some(stop) = &self.stop {
+            if stop.iter().any(|sequence| sequence.is_empty()) {
+                return Err(ProviderError::InvalidParameter(
+                    "stop sequences must not be empty".to_string(),
+                ));
+            }
+        }
+        if self.reasoning_effort.is_some() && !model.capabilities.contains(&Capability::Reasoning) {
+            return Err(ProviderError::InvalidParameter(format!(
+                "{} does not support reasoning effort",
+                model.id
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// How much a reasoning model thinks before answering
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReasoningEffort {
+    /// Short thinking, fastest answers
+    Low,
+    /// Balanced thinking
+    Medium,
+    /// Longest thinking for hard problems
+    High,
+}
+
+impl ReasoningEffort {
+    /// Effort level as sent to level-based APIs
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReasoningEffort::Low => "low",
+            ReasoningEffort::Medium => "medium",
+            ReasoningEffort::High => "high",
+        }
+    }
+
+    /// Thinking budget in tokens, for budget-based APIs
+    pub fn budget_tokens(&self) -> usize {
+        match self {
+            ReasoningEffort::Low => 1024,
+            ReasoningEffort::Medium => 4096,
+            ReasoningEffort::High => 16384,
+        }
+    }
+}
+
+/// Generation parameters, used as defaults for chat requests
+///
+/// Unset fields leave the choice to the request, then to the provider.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModelParams {
+    /// Temperature for sampling (0.0 to 2.0)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    /// Nucleus sampling probability mass (0.0 to 1.0)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    /// Maximum tokens to generate
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<usize>,
+    /// Sequences that end generation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stop: Option<Vec<String>>,
+    /// Seed for reproducible sampling
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    /// Reasoning effort
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning_effort: Option<ReasoningEffort>,
 }
 
 /// Reason for chat completion finish
@@ -107,6 +241,24 @@ pub struct ChatResponse {
     pub finish_reason: FinishReason,
 }
 
+/// Embedding request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbeddingRequest {
+    /// Embedding model to use
+    pub model: String,
+    /// Texts to embed
+    pub input: Vec<String>,
+}
+
+/// Embedding response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbeddingResponse {
+    /// Model used
+    pub model: String,
+    /// One vector per input text, in input order
+    pub embeddings: Vec<Vec<f32>>,
+}
+
 /// Provider configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProviderConfig {
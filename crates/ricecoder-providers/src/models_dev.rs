@@ -39,6 +39,8 @@ pub struct ModelsDevModel {
     pub name: String,
     pub provider: String,
     pub context_window: usize,
+    #[serde(default)]
+    pub max_output_tokens: Option<usize>,
     pub capabilities: Vec<String>,
     #[serde(default)]
     pub status: String,
@@ -286,6 +288,7 @@ fn convert_model(model: ModelsDevModel) -> ModelInfo {
             "vision" => Some(Capability::Vision),
             "function_calling" => Some(Capability::FunctionCalling),
             "streaming" => Some(Capability::Streaming),
+            "reasoning" => Some(Capability::Reasoning),
             _ => None,
         })
         .collect();
@@ -295,6 +298,7 @@ fn convert_model(model: ModelsDevModel) -> ModelInfo {
         name: model.name,
         provider: model.provider,
         context_window: model.context_window,
+        max_output_tokens: model.max_output_tokens,
         capabilities,
         pricing: None,
         is_free: false,
@@ -331,6 +335,7 @@ mod tests {
             name: "Test Model".to_string(),
             provider: "test".to_string(),
             context_window: 4096,
+            max_output_tokens: Some(1024),
             capabilities: vec!["chat".to_string(), "code".to_string()],
             status: "stable".to_string(),
             experimental: false,
@@ -341,6 +346,7 @@ mod tests {
         assert_eq!(info.name, "Test Model");
         assert_eq!(info.provider, "test");
         assert_eq!(info.context_window, 4096);
+        assert_eq!(info.max_output_tokens, Some(1024));
         assert_eq!(info.capabilities.len(), 2);
         assert!(info.capabilities.contains(&Capability::Chat));
         assert!(info.capabilities.contains(&Capability::Code));
@@ -354,6 +360,7 @@ mod tests {
                 name: "API Model 1".to_string(),
                 provider: "api-provider".to_string(),
                 context_window: 4096,
+                max_output_tokens: None,
                 capabilities: vec![Capability::Chat],
                 pricing: None,
                 is_free: false,
@@ -363,6 +370,7 @@ mod tests {
                 name: "API Model 2".to_string(),
                 provider: "api-provider".to_string(),
                 context_window: 8192,
+                max_output_tokens: None,
                 capabilities: vec![Capability::Code],
                 pricing: None,
                 is_free: false,
@@ -375,6 +383,7 @@ mod tests {
                 name: "Local Model 1".to_string(),
                 provider: "local-provider".to_string(),
                 context_window: 16384,
+                max_output_tokens: None,
                 capabilities: vec![Capability::Chat, Capability::Vision],
                 pricing: Some(Pricing {
                     input_per_1k_tokens: 0.01,
@@ -409,6 +418,7 @@ mod tests {
                 name: "API Only Model".to_string(),
                 provider: "api".to_string(),
                 context_window: 4096,
+                max_output_tokens: None,
                 capabilities: vec![],
                 pricing: None,
                 is_free: false,
@@ -421,6 +431,7 @@ mod tests {
                 name: "Local Only Model".to_string(),
                 provider: "local".to_string(),
                 context_window: 8192,
+                max_output_tokens: None,
                 capabilities: vec![],
                 pricing: None,
                 is_free: true,
@@ -443,6 +454,7 @@ mod tests {
                 name: "Local Model".to_string(),
                 provider: "local".to_string(),
                 context_window: 4096,
+                max_output_tokens: None,
                 capabilities: vec![],
                 pricing: None,
                 is_free: false,
@@ -462,6 +474,7 @@ mod tests {
                 name: "API Model".to_string(),
                 provider: "api".to_string(),
                 context_window: 4096,
+                max_output_tokens: None,
                 capabilities: vec![],
                 pricing: None,
                 is_free: false,
@@ -493,6 +506,7 @@ mod tests {
                 name: "Test".to_string(),
                 provider: "test".to_string(),
                 context_window: 4096,
+                max_output_tokens: None,
                 capabilities: vec![],
                 pricing: None,
                 is_free: false,
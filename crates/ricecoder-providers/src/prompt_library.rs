@@ -0,0 +1,408 @@
+//! Versioned system prompt library
+//!
+//! System prompts are versioned templates with `{{variable}}` placeholders,
+//! bound to modes and agents, optionally split into weighted A/B variants.
+//! Every rendered prompt carries its id, version and content hash so the
+//! [`PromptAuditLog`] can record which prompt version produced each response.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use ricecoder_storage::loaders::{PromptBinding, PromptBindings, PromptLoader, VersionedPrompt};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::{debug, info};
+
+use crate::error::ProviderError;
+
+static PLACEHOLDER: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{\{\s*([A-Za-z_][A-Za-z0-9_]*)\s*\}\}").unwrap());
+
+/// What a prompt is bound to
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "name", rename_all = "lowercase")]
+pub enum BindingTarget {
+    /// A mode (e.g. "code", "ask")
+    Mode(String),
+    /// An agent (e.g. "explore")
+    Agent(String),
+}
+
+impl fmt::Display for BindingTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BindingTarget::Mode(name) => write!(f, "mode:{}", name),
+            BindingTarget::Agent(name) => write!(f, "agent:{}", name),
+        }
+    }
+}
+
+/// A rendered prompt and the exact template it came from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResolvedPrompt {
+    /// Prompt identifier
+    pub prompt_id: String,
+    /// Template version
+    pub version: u32,
+    /// A/B variant name, if the binding has variants
+    pub variant: Option<String>,
+    /// SHA-256 of the template body, so in-place edits of a version are visible
+    pub content_hash: String,
+    /// Rendered prompt text
+    pub text: String,
+}
+
+/// Versioned prompt templates and their mode/agent bindings
+#[derive(Debug, Clone, Default)]
+pub struct PromptLibrary {
+    templates: HashMap<String, BTreeMap<u32, VersionedPrompt>>,
+    bindings: HashMap<BindingTarget, PromptBinding>,
+}
+
+impl PromptLibrary {
+    /// Create an empty library
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load templates and bindings from a prompt loader
+    pub fn from_loader(loader: &PromptLoader) -> Result<Self, ProviderError> {
+        let templates = loader
+            .load_library()
+            .map_err(|e| ProviderError::ConfigError(format!("Failed to load prompts: {}", e)))?;
+        let bindings = loader.load_bindings().map_err(|e| {
+            ProviderError::ConfigError(format!("Failed to load prompt bindings: {}", e))
+        })?;
+
+        let mut library = Self::new();
+        for template in templates {
+            library.add_template(template);
+        }
+        library.add_bindings(bindings);
+        library.validate()?;
+
+        info!(
+            "Loaded prompt library: {} prompts, {} bindings",
+            library.templates.len(),
+            library.bindings.len()
+        );
+        Ok(library)
+    }
+
+    /// Add a template version, replacing an existing one with the same version
+    pub fn add_template(&mut self, template: VersionedPrompt) {
+        self.templates
+            .entry(template.id.clone())
+            .or_default()
+            .insert(template.version, template);
+    }
+
+    /// Bind a mode or agent to a prompt
+    pub fn bind(&mut self, target: BindingTarget, binding: PromptBinding) {
+        self.bindings.insert(target, binding);
+    }
+
+    /// Add all bindings from a bindings file
+    pub fn add_bindings(&mut self, bindings: PromptBindings) {
+        for (mode, binding) in bindings.modes {
+            self.bind(BindingTarget::Mode(mode), binding);
+        }
+        for (agent, binding) in bindings.agents {
+            self.bind(BindingTarget::Agent(agent), binding);
+        }
+    }
+
+    /// Get the binding for a target
+    pub fn binding(&self, target: &BindingTarget) -> Option<&PromptBinding> {
+        self.bindings.get(target)
+    }
+
+    /// List the versions of a prompt in ascending order
+    pub fn versions(&self, prompt_id: &str) -> Vec<u32> {
+        self.templates
+            .get(prompt_id)
+            .map(|versions| versions.keys().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Get a template version, or the latest when `version` is `None`
+    pub fn get(&self, prompt_id: &str, version: Option<u32>) -> Option<&VersionedPrompt> {
+        let versions = self.templates.get(prompt_id)?;
+        match version {
+            Some(version) => versions.get(&version),
+            None => versions.values().next_back(),
+        }
+    }
+
+    /// Check that every binding points at existing versions
+    pub fn validate(&self) -> Result<(), ProviderError> {
+        for (target, binding) in &self.bindings {
+            let versions = binding
+                .variants
+                .iter()
+                .map(|variant| Some(variant.version))
+                .chain(binding.variants.is_empty().then_some(binding.version));
+            for version in versions {
+                if self.get(&binding.prompt, version).is_none() {
+                    return Err(ProviderError::ConfigError(format!(
+                        "{} is bound to missing prompt {}{}",
+                        target,
+                        binding.prompt,
+                        version.map(|v| format!(" v{}", v)).unwrap_or_default()
+                    )));
+                }
+            }
+            if !binding.variants.is_empty() && binding.variants.iter().all(|v| v.weight == 0) {
+                return Err(ProviderError::ConfigError(format!(
+                    "{} has A/B variants that all have zero weight",
+                    target
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Render a template version with the given variables
+    ///
+    /// Variables without a value fall back to the template's declared
+    /// defaults; a placeholder with neither is an error.
+    pub fn render(
+        &self,
+        prompt_id: &str,
+        version: Option<u32>,
+        variables: &HashMap<String, String>,
+    ) -> Result<ResolvedPrompt, ProviderError> {
+        let template = self.get(prompt_id, version).ok_or_else(|| {
+            ProviderError::ConfigError(format!(
+                "prompt {}{} not found",
+                prompt_id,
+                version.map(|v| format!(" v{}", v)).unwrap_or_default()
+            ))
+        })?;
+
+        let mut missing = Vec::new();
+        let text = PLACEHOLDER.replace_all(&template.body, |caps: &regex::Captures| {
+            let name = &caps[1];
+            variables
+                .get(name)
+                .cloned()
+                .or_else(|| template.variables.get(name).cloned().flatten())
+                .unwrap_or_else(|| {
+                    missing.push(name.to_string());
+                    String::new()
+                })
+        });
+        if !missing.is_empty() {
+            return Err(ProviderError::InvalidParameter(format!(
+                "prompt {} v{} is missing variables: {}",
+                template.id,
+                template.version,
+                missing.join(", ")
+            )));
+        }
+
+        Ok(ResolvedPrompt {
+            prompt_id: template.id.clone(),
+            version: template.version,
+            variant: None,
+            content_hash: content_hash(&template.body),
+            text: text.into_owned(),
+        })
+    }
+
+    /// Resolve and render the prompt bound to a target
+    ///
+    /// With A/B variants, `subject` (typically the session id) picks the
+    /// variant deterministically, so a session keeps the same prompt.
+    pub fn resolve(
+        &self,
+        target: &BindingTarget,
+        subject: &str,
+        variables: &HashMap<String, String>,
+    ) -> Result<ResolvedPrompt, ProviderError> {
+        let binding = self
+            .bindings
+            .get(target)
+            .ok_or_else(|| ProviderError::ConfigError(format!("no prompt bound to {}", target)))?;
+
+        let Some(variant) = pick_variant(binding, subject) else {
+            return self.render(&binding.prompt, binding.version, variables);
+        };
+
+        debug!(
+            "Serving variant {} (v{}) of prompt {} to {}",
+            variant.name, variant.version, binding.prompt, target
+        );
+        let mut resolved = self.render(&binding.prompt, Some(variant.version), variables)?;
+        resolved.variant = Some(variant.name.clone());
+        Ok(resolved)
+    }
+}
+
+/// Pick a weighted variant by hashing the subject
+fn pick_variant<'a>(
+    binding: &'a PromptBinding,
+    subject: &str,
+) -> Option<&'a ricecoder_storage::loaders::PromptVariant> {
+    let total: u64 = binding.variants.iter().map(|v| v.weight as u64).sum();
+    if total == 0 {
+        return None;
+    }
+
+    let digest = Sha256::digest(format!("{}:{}", binding.prompt, subject).as_bytes());
+    let mut bucket = u64::from_be_bytes(digest[..8].try_into().expect("8 bytes")) % total;
+    binding.variants.iter().find(|variant| {
+        let weight = variant.weight as u64;
+        if bucket < weight {
+            true
+        } else {
+            bucket -= weight;
+            false
+        }
+    })
+}
+
+fn content_hash(body: &str) -> String {
+    Sha256::digest(body.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Audit record of the prompt behind one response
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PromptUsage {
+    /// When the response was produced
+    pub timestamp: DateTime<Utc>,
+    /// Prompt identifier
+    pub prompt_id: String,
+    /// Template version
+    pub version: u32,
+    /// A/B variant name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variant: Option<String>,
+    /// SHA-256 of the template body
+    pub content_hash: String,
+    /// Mode or agent the prompt was bound to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<BindingTarget>,
+    /// Session that received the response
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    /// Model that produced the response
+    pub model: String,
+}
+
+impl PromptUsage {
+    /// Record that `prompt` produced a response from `model`
+    pub fn new(prompt: &ResolvedPrompt, model: &str) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            prompt_id: prompt.prompt_id.clone(),
+            version: prompt.version,
+            variant: prompt.variant.clone(),
+            content_hash: prompt.content_hash.clone(),
+            target: None,
+            session_id: None,
+            model: model.to_string(),
+        }
+    }
+
+    /// Set the mode or agent the prompt was bound to
+    pub fn with_target(mut self, target: BindingTarget) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    /// Set the session that received the response
+    pub fn with_session(mut self, session_id: impl Into<String>) -> Self {
+        self.session_id = Some(session_id.into());
+        self
+    }
+}
+
+/// Audit trail of which prompt version produced each response
+///
+/// Entries are kept in memory and, when a path is set, appended as JSON lines.
+#[derive(Debug, Default)]
+pub struct PromptAuditLog {
+    path: Option<PathBuf>,
+    entries: Mutex<Vec<PromptUsage>>,
+}
+
+impl PromptAuditLog {
+    /// Create an in-memory audit log
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an audit log that also appends to a JSON lines file
+    pub fn with_file(path: PathBuf) -> Self {
+        Self {
+            path: Some(path),
+            entries: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Record a prompt usage
+    pub fn record(&self, usage: PromptUsage) -> Result<(), ProviderError> {
+        let mut entries = self.entries.lock().unwrap();
+
+        if let Some(path) = &self.path {
+            let line = serde_json::to_string(&usage)?;
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .map_err(|e| {
+                    ProviderError::Internal(format!("Failed to open prompt audit log: {}", e))
+                })?;
+            writeln!(file, "{}", line).map_err(|e| {
+                ProviderError::Internal(format!("Failed to write prompt audit log: {}", e))
+            })?;
+        }
+
+        debug!(
+            prompt = %usage.prompt_id,
+            version = usage.version,
+            model = %usage.model,
+            "Prompt usage recorded"
+        );
+        entries.push(usage);
+        Ok(())
+    }
+
+    /// All recorded usages, oldest first
+    pub fn entries(&self) -> Vec<PromptUsage> {
+        self.entries.lock().unwrap().clone()
+    }
+
+    /// Usages recorded for one session
+    pub fn for_session(&self, session_id: &str) -> Vec<PromptUsage> {
+        self.entries
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|usage| usage.session_id.as_deref() == Some(session_id))
+            .cloned()
+            .collect()
+    }
+
+    /// Number of responses produced per version of a prompt
+    pub fn version_counts(&self, prompt_id: &str) -> BTreeMap<u32, usize> {
+        let mut counts = BTreeMap::new();
+        for usage in self.entries.lock().unwrap().iter() {
+            if usage.prompt_id == prompt_id {
+                *counts.entry(usage.version).or_insert(0) += 1;
+            }
+        }
+        counts
+    }
+}
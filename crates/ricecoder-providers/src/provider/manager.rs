@@ -9,11 +9,16 @@ use crate::{
     community::{CommunityProviderRegistry, ProviderUsage},
     curation::{CurationConfig, ProviderCurator, SelectionConstraints},
     error::ProviderError,
-    evaluation::{ContinuousEvaluator, ProviderEvaluator},
+    evaluation::{ContinuousEvaluator, ProviderEvaluation, ProviderEvaluator},
     health_check::HealthCheckCache,
-    models::{Capability, ChatRequest, ChatResponse, ModelInfo, TokenUsage},
+    models::{
+        Capability, ChatRequest, ChatResponse, EmbeddingRequest, EmbeddingResponse, ModelInfo,
+        TokenUsage,
+    },
     performance_monitor::ProviderPerformanceMonitor,
     provider::ChatStream,
+    providers::LocalRuntime,
+    routing::{RoutingDecision, TaskClass},
     sync::CommunityDatabaseSync,
 };
 
@@ -127,6 +132,7 @@ pub struct ProviderManager {
     evaluator: Option<ProviderEvaluator>,
     continuous_evaluator: Option<ContinuousEvaluator>,
     community_sync: Option<CommunityDatabaseSync>,
+    task_routing: bool,
 }
 
 impl ProviderManager {
@@ -148,6 +154,7 @@ impl ProviderManager {
             evaluator: None,
             continuous_evaluator: None,
             community_sync: None,
+            task_routing: false,
         }
     }
 
@@ -169,6 +176,12 @@ impl ProviderManager {
         self
     }
 
+    /// Route [`chat`](Self::chat) requests to the best provider for their task
+    pub fn with_task_routing(mut self, enabled: bool) -> Self {
+        self.task_routing = enabled;
+        self
+    }
+
     /// Auto-detect available providers based on credentials
     pub async fn auto_detect_providers(&mut self) -> Result<Vec<String>, ProviderError> {
         let mut detected_providers = Vec::new();
@@ -201,6 +214,19 @@ impl ProviderManager {
             }
         }
 
+        // Check for other registered local runtimes (llama.cpp, LM Studio, vLLM)
+        for runtime in LocalRuntime::ALL {
+            if runtime == LocalRuntime::Ollama {
+                continue;
+            }
+            if let Ok(provider) = self.registry.get(runtime.id()) {
+                if self.test_provider_connection(&provider).await.is_ok() {
+                    detected_providers.push(runtime.id().to_string());
+                    self.update_provider_state(runtime.id(), ConnectionState::Connected, None);
+                }
+            }
+        }
+
         // Check for Google API key
         if std::env::var("GOOGLE_API_KEY").is_ok() {
             if let Ok(provider) = self.registry.get("google") {
@@ -332,7 +358,13 @@ impl ProviderManager {
     }
 
     /// Send a chat request with retry logic
+    ///
+    /// Goes to the default provider, or to the best provider for the
+    /// request's task when task routing is enabled.
     pub async fn chat(&mut self, request: ChatRequest) -> Result<ChatResponse, ProviderError> {
+        if self.task_routing {
+            return self.chat_routed(request, None).await;
+        }
         let provider = self.default_provider()?;
         self.chat_with_provider(&provider, request).await
     }
@@ -344,6 +376,8 @@ impl ProviderManager {
         request: ChatRequest,
     ) -> Result<ChatResponse, ProviderError> {
         let provider_id = provider.id();
+        validate_params(provider, &request)?;
+        let task = TaskClass::classify(&request);
         let mut last_error = None;
         let start_time = std::time::Instant::now();
 
@@ -361,6 +395,8 @@ impl ProviderManager {
                         cost,
                     );
                     self.curator.record_success(provider_id);
+                    self.curator
+                        .record_task_latency(provider_id, task, response_time_ms as f64);
 
                     // Record community analytics
                     let usage = ProviderUsage {
@@ -415,10 +451,94 @@ impl ProviderManager {
         }))
     }
 
+    /// Choose the provider for a task
+    ///
+    /// `provider_override` bypasses routing and sends the request to that
+    /// provider. Otherwise the connected provider with the best score for the
+    /// task is chosen, falling back to the default provider while no provider
+    /// has been scored for it.
+    pub fn route_task(
+        &self,
+        task: TaskClass,
+        provider_override: Option<&str>,
+    ) -> Result<RoutingDecision, ProviderError> {
+        if let Some(provider_id) = provider_override {
+            self.registry.get(provider_id)?;
+            return Ok(RoutingDecision {
+                provider_id: provider_id.to_string(),
+                task,
+                overridden: true,
+            });
+        }
+
+        let mut connected: Vec<String> = self
+            .provider_states
+            .iter()
+            .filter(|(_, status)| status.state == ConnectionState::Connected)
+            .map(|(id, _)| id.clone())
+            .collect();
+        connected.sort();
+
+        let provider_id = self
+            .curator
+            .select_provider_for_task(&connected, task, None)
+            .unwrap_or_else(|| self.default_provider_id.clone());
+        Ok(RoutingDecision {
+            provider_id,
+            task,
+            overridden: false,
+        })
+    }
+
+    /// Send a chat request to the best provider for its task
+    ///
+    /// When the chosen provider does not list the requested model, the
+    /// provider's model best suited to the task is used instead.
+    pub async fn chat_routed(
+        &mut self,
+        mut request: ChatRequest,
+        provider_override: Option<&str>,
+    ) -> Result<ChatResponse, ProviderError> {
+        let decision = self.route_task(TaskClass::classify(&request), provider_override)?;
+        let provider = self.registry.get(&decision.provider_id)?;
+
+        let models = provider.models();
+        if !models.iter().any(|m| m.id == request.model) {
+            if let Some(model) = decision.task.preferred_model(&models) {
+                request.model = model.id.clone();
+            }
+        }
+        self.chat_with_provider(&provider, request).await
+    }
+
+    /// Compute embeddings with the best provider for embeddings
+    ///
+    /// Only successful requests are scored, so providers without an
+    /// embeddings API are never routed to.
+    pub async fn embed_routed(
+        &mut self,
+        request: EmbeddingRequest,
+        provider_override: Option<&str>,
+    ) -> Result<EmbeddingResponse, ProviderError> {
+        let decision = self.route_task(TaskClass::Embeddings, provider_override)?;
+        let provider = self.registry.get(&decision.provider_id)?;
+
+        let start_time = std::time::Instant::now();
+        let response = tokio::time::timeout(self.timeout, provider.embed(request))
+            .await
+            .map_err(|_| ProviderError::ProviderError("Request timeout".to_string()))??;
+        self.curator.record_task_latency(
+            &decision.provider_id,
+            TaskClass::Embeddings,
+            start_time.elapsed().as_millis() as f64,
+        );
+        Ok(response)
+    }
+
     /// Stream a chat response
     pub async fn chat_stream(&self, request: ChatRequest) -> Result<ChatStream, ProviderError> {
         let provider = self.default_provider()?;
-        provider.chat_stream(request).await
+        self.chat_stream_with_provider(&provider, request).await
     }
 
     /// Stream a chat response from a specific provider
@@ -427,6 +547,7 @@ impl ProviderManager {
         provider: &Arc<dyn Provider>,
         request: ChatRequest,
     ) -> Result<ChatStream, ProviderError> {
+        validate_params(provider, &request)?;
         provider.chat_stream(request).await
     }
 
@@ -712,6 +833,26 @@ impl ProviderManager {
         }
     }
 
+    /// Feed an evaluation into the per-task routing scores
+    pub fn record_evaluation(&mut self, evaluation: &ProviderEvaluation) {
+        self.curator.apply_evaluation(evaluation);
+    }
+
+    /// Feed the latest continuous evaluations into the per-task routing scores
+    pub async fn refresh_task_scores(&mut self) {
+        let Some(continuous_evaluator) = &self.continuous_evaluator else {
+            return;
+        };
+        for provider_id in self.registry.list_provider_ids() {
+            if let Some(evaluation) = continuous_evaluator
+                .get_latest_evaluation(&provider_id)
+                .await
+            {
+                self.curator.apply_evaluation(&evaluation);
+            }
+        }
+    }
+
     /// Get latest evaluation for a provider
     pub async fn get_latest_evaluation(
         &self,
@@ -754,3 +895,16 @@ impl ProviderManager {
         }
     }
 }
+
+/// Reject request parameters the target model cannot take
+///
+/// Models the provider does not list are passed through unchecked.
+fn validate_params(
+    provider: &Arc<dyn Provider>,
+    request: &ChatRequest,
+) -> Result<(), ProviderError> {
+    match provider.models().iter().find(|m| m.id == request.model) {
+        Some(model) => request.validate_params(model),
+        None => Ok(()),
+    }
+}
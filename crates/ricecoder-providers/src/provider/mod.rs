@@ -5,7 +5,7 @@ use futures;
 
 use crate::{
     error::ProviderError,
-    models::{ChatRequest, ChatResponse, ModelInfo},
+    models::{ChatRequest, ChatResponse, EmbeddingRequest, EmbeddingResponse, ModelInfo},
 };
 
 pub mod manager;
@@ -40,4 +40,15 @@ pub trait Provider: Send + Sync {
 
     /// Check if the provider is available and healthy
     async fn health_check(&self) -> Result<bool, ProviderError>;
+
+    /// Compute embedding vectors for texts
+    ///
+    /// Providers without an embeddings API keep the default, which returns an error.
+    async fn embed(&self, request: EmbeddingRequest) -> Result<EmbeddingResponse, ProviderError> {
+        Err(ProviderError::ProviderError(format!(
+            "{} does not support embeddings (model: {})",
+            self.name(),
+            request.model
+        )))
+    }
 }
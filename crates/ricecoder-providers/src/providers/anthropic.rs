@@ -16,6 +16,7 @@ use crate::{
     models::{Capability, ChatRequest, ChatResponse, FinishReason, ModelInfo, TokenUsage},
     provider::Provider,
     token_counter::TokenCounter,
+    transform::{params_for, ParamDialect},
 };
 
 /// Anthropic provider implementation
@@ -203,6 +204,7 @@ impl Provider for AnthropicProvider {
             max_tokens: request.max_tokens.unwrap_or(1024),
             messages,
             temperature: request.temperature,
+            params: params_for(ParamDialect::Anthropic, &request),
         };
 
         debug!(
@@ -269,6 +271,7 @@ impl Provider for AnthropicProvider {
             messages,
             temperature: request.temperature,
             stream: true,
+            params: params_for(ParamDialect::Anthropic, &request),
         };
 
         debug!(
@@ -373,6 +376,9 @@ struct AnthropicChatRequest {
     messages: Vec<AnthropicMessage>,
     #[serde(skip_serializing_if = "Option::is_none")]
     temperature: Option<f32>,
+    /// Provider-specific generation parameters
+    #[serde(flatten)]
+    params: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Anthropic API message format
@@ -412,6 +418,9 @@ struct AnthropicStreamRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     temperature: Option<f32>,
     stream: bool,
+    /// Provider-specific generation parameters
+    #[serde(flatten)]
+    params: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Anthropic streaming event format (Server-Sent Events)
@@ -14,6 +14,7 @@ use crate::{
     models::{Capability, ChatRequest, ChatResponse, FinishReason, ModelInfo, Pricing, TokenUsage},
     provider::Provider,
     token_counter::{TokenCounter, TokenCounterTrait},
+    transform::{params_for, ParamDialect},
 };
 
 /// Azure OpenAI provider implementation
@@ -147,6 +148,7 @@ impl Provider for AzureOpenAiProvider {
                 name: "GPT-4".to_string(),
                 provider: self.name().to_string(),
                 context_window: 8192,
+                max_output_tokens: None,
                 capabilities: vec![Capability::Chat, Capability::FunctionCalling],
                 pricing: Some(Pricing {
                     input_per_1k_tokens: 0.03,
@@ -159,6 +161,7 @@ impl Provider for AzureOpenAiProvider {
                 name: "GPT-4 Turbo".to_string(),
                 provider: self.name().to_string(),
                 context_window: 128000,
+                max_output_tokens: None,
                 capabilities: vec![
                     Capability::Chat,
                     Capability::FunctionCalling,
@@ -175,6 +178,7 @@ impl Provider for AzureOpenAiProvider {
                 name: "GPT-3.5 Turbo".to_string(),
                 provider: self.name().to_string(),
                 context_window: 16384,
+                max_output_tokens: None,
                 capabilities: vec![Capability::Chat, Capability::FunctionCalling],
                 pricing: Some(Pricing {
                     input_per_1k_tokens: 0.0015,
@@ -206,6 +210,7 @@ impl Provider for AzureOpenAiProvider {
             max_tokens: request.max_tokens,
             temperature: request.temperature,
             stream: false,
+            params: params_for(ParamDialect::OpenAi, &request),
         };
 
         let response = self
@@ -274,6 +279,9 @@ struct AzureOpenAiChatRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     temperature: Option<f32>,
     stream: bool,
+    /// Provider-specific generation parameters
+    #[serde(flatten)]
+    params: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Azure OpenAI message structure
@@ -14,6 +14,7 @@ use crate::{
     models::{Capability, ChatRequest, ChatResponse, FinishReason, ModelInfo, Pricing, TokenUsage},
     provider::Provider,
     token_counter::TokenCounter,
+    transform::{params_for, ParamDialect},
 };
 
 /// Cohere provider implementation
@@ -127,6 +128,7 @@ impl Provider for CohereProvider {
             max_tokens: request.max_tokens,
             temperature: request.temperature,
             num_generations: Some(1),
+            params: params_for(ParamDialect::Cohere, &request),
         };
 
         let response = self
@@ -191,6 +193,9 @@ struct CohereChatRequest {
     temperature: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     num_generations: Option<usize>,
+    /// Provider-specific generation parameters
+    #[serde(flatten)]
+    params: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Cohere chat response structure
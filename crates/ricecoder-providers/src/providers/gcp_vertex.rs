@@ -14,6 +14,7 @@ use crate::{
     models::{Capability, ChatRequest, ChatResponse, FinishReason, ModelInfo, Pricing, TokenUsage},
     provider::Provider,
     token_counter::{TokenCounter, TokenCounterTrait},
+    transform::{params_for, ParamDialect},
 };
 
 /// GCP Vertex AI provider implementation
@@ -165,6 +166,9 @@ impl Provider for GcpVertexProvider {
 
         // For now, implement Gemini support
         if request.model.starts_with("gemini") {
+            // top_p has a default of its own here
+            let mut params = params_for(ParamDialect::Gemini, &request);
+            params.remove("top_p");
             let gemini_request = GeminiGenerateRequest {
                 contents: vec![GeminiContent {
                     role: "user".to_string(),
@@ -180,8 +184,9 @@ impl Provider for GcpVertexProvider {
                 generation_config: Some(GeminiGenerationConfig {
                     temperature: request.temperature,
                     max_output_tokens: request.max_tokens,
-                    top_p: Some(0.8),
+                    top_p: request.top_p.or(Some(0.8)),
                     top_k: Some(10),
+                    params,
                 }),
             };
 
@@ -278,6 +283,9 @@ struct GeminiGenerationConfig {
     top_p: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     top_k: Option<usize>,
+    /// Provider-specific generation parameters
+    #[serde(flatten)]
+    params: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Gemini generate response
@@ -14,6 +14,7 @@ use crate::{
     models::{Capability, ChatRequest, ChatResponse, FinishReason, ModelInfo, TokenUsage},
     provider::Provider,
     token_counter::TokenCounter,
+    transform::{params_for, ParamDialect},
 };
 
 /// Google Gemini provider implementation
@@ -175,6 +176,7 @@ impl Provider for GoogleProvider {
             generation_config: Some(GoogleGenerationConfig {
                 temperature: request.temperature,
                 max_output_tokens: request.max_tokens,
+                params: params_for(ParamDialect::Gemini, &request),
             }),
         };
 
@@ -296,6 +298,9 @@ struct GoogleGenerationConfig {
     temperature: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     max_output_tokens: Option<usize>,
+    /// Provider-specific generation parameters
+    #[serde(flatten)]
+    params: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Google API response format
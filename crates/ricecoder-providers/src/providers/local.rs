@@ -0,0 +1,891 @@
+//! Local model runtime providers
+//!
+//! Supports llama.cpp server, LM Studio and vLLM through their OpenAI-compatible
+//! endpoints, so offline usage is not limited to Ollama.
+//!
+//! ## Features
+//! - Capability probing (context window, tool calling, vision) per runtime
+//! - Discovery of runtimes listening on their common local ports
+//! - Full streaming support via SSE
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tracing::{debug, error, info, trace, warn};
+
+use crate::{
+    error::ProviderError,
+    models::{Capability, ChatRequest, ChatResponse, FinishReason, ModelInfo, TokenUsage},
+    provider::Provider,
+    transform::{params_for, ParamDialect},
+};
+
+/// Context window assumed when a runtime does not report one
+const DEFAULT_CONTEXT_WINDOW: usize = 4096;
+
+const DISCOVERY_TIMEOUT: Duration = Duration::from_secs(3);
+const DISCOVERY_CONNECT_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// A local model runtime
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LocalRuntime {
+    /// Ollama (probed through its native API)
+    Ollama,
+    /// llama.cpp `llama-server`
+    LlamaCpp,
+    /// LM Studio local server
+    LmStudio,
+    /// vLLM OpenAI-compatible server
+    Vllm,
+}
+
+impl LocalRuntime {
+    /// All known runtimes, in discovery order
+    pub const ALL: [LocalRuntime; 4] = [
+        LocalRuntime::Ollama,
+        LocalRuntime::LlamaCpp,
+        LocalRuntime::LmStudio,
+        LocalRuntime::Vllm,
+    ];
+
+    /// Provider identifier used for this runtime
+    pub fn id(&self) -> &'static str {
+        match self {
+            LocalRuntime::Ollama => "ollama",
+            LocalRuntime::LlamaCpp => "llamacpp",
+            LocalRuntime::LmStudio => "lmstudio",
+            LocalRuntime::Vllm => "vllm",
+        }
+    }
+
+    /// Human-readable runtime name
+    pub fn name(&self) -> &'static str {
+        match self {
+            LocalRuntime::Ollama => "Ollama",
+            LocalRuntime::LlamaCpp => "llama.cpp",
+            LocalRuntime::LmStudio => "LM Studio",
+            LocalRuntime::Vllm => "vLLM",
+        }
+    }
+
+    /// Port the runtime listens on by default
+    pub fn default_port(&self) -> u16 {
+        match self {
+            LocalRuntime::Ollama => 11434,
+            LocalRuntime::LlamaCpp => 8080,
+            LocalRuntime::LmStudio => 1234,
+            LocalRuntime::Vllm => 8000,
+        }
+    }
+
+    /// Default base URL on localhost
+    pub fn default_base_url(&self) -> String {
+        format!("http://localhost:{}", self.default_port())
+    }
+
+    /// Endpoint that only this runtime answers, used to identify it
+    fn signature_path(&self) -> &'static str {
+        match self {
+            LocalRuntime::Ollama => "/api/tags",
+            LocalRuntime::LlamaCpp => "/props",
+            LocalRuntime::LmStudio => "/api/v0/models",
+            LocalRuntime::Vllm => "/version",
+        }
+    }
+
+    /// Endpoint listing the models the runtime serves
+    fn models_path(&self) -> &'static str {
+        match self {
+            LocalRuntime::Ollama => "/api/tags",
+            LocalRuntime::LmStudio => "/api/v0/models",
+            LocalRuntime::LlamaCpp | LocalRuntime::Vllm => "/v1/models",
+        }
+    }
+}
+
+impl std::fmt::Display for LocalRuntime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Build model information from a runtime's model listing
+///
+/// Understands the OpenAI `/v1/models` shape (with vLLM's `max_model_len` and
+/// llama.cpp's `meta.n_ctx_train`), LM Studio's `/api/v0/models` and Ollama's
+/// `/api/tags`. Embedding-only models are skipped.
+pub fn parse_model_listing(runtime: LocalRuntime, listing: &Value) -> Vec<ModelInfo> {
+    if let Some(models) = listing.get("models").and_then(Value::as_array) {
+        return models
+            .iter()
+            .filter_map(|model| model.get("name").and_then(Value::as_str))
+            .map(|name| local_model(runtime, name, None))
+            .collect();
+    }
+
+    listing
+        .get("data")
+        .and_then(Value::as_array)
+        .map(|entries| {
+            entries
+                .iter()
+                .filter(|entry| entry.get("type").and_then(Value::as_str) != Some("embeddings"))
+                .filter_map(|entry| {
+                    let id = entry.get("id").and_then(Value::as_str)?;
+                    let context = [
+                        "max_model_len",
+                        "max_context_length",
+                        "loaded_context_length",
+                    ]
+                    .iter()
+                    .find_map(|key| entry.get(*key).and_then(Value::as_u64))
+                    .or_else(|| entry.pointer("/meta/n_ctx_train").and_then(Value::as_u64))
+                    .map(|n| n as usize);
+                    let mut model = local_model(runtime, id, context);
+
+                    if entry.get("type").and_then(Value::as_str) == Some("vlm") {
+                        model.capabilities.push(Capability::Vision);
+                    }
+                    let tool_use = entry
+                        .get("capabilities")
+                        .and_then(Value::as_array)
+                        .is_some_and(|caps| caps.iter().any(|c| c.as_str() == Some("tool_use")));
+                    if tool_use {
+                        model.capabilities.push(Capability::FunctionCalling);
+                    }
+                    Some(model)
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Apply llama.cpp `/props` output to probed models
+///
+/// The server's configured context size wins over the model's training
+/// context, and a chat template that renders `tools` means tool calling works.
+pub fn apply_llama_cpp_props(models: &mut [ModelInfo], props: &Value) {
+    let n_ctx = props
+        .pointer("/default_generation_settings/n_ctx")
+        .or_else(|| props.get("n_ctx"))
+        .and_then(Value::as_u64)
+        .filter(|n| *n > 0);
+    let tools = props
+        .get("chat_template")
+        .and_then(Value::as_str)
+        .is_some_and(|template| template.contains("tools"));
+    let vision = props
+        .pointer("/modalities/vision")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+
+    for model in models.iter_mut() {
+        if let Some(n_ctx) = n_ctx {
+            model.context_window = n_ctx as usize;
+        }
+        if tools {
+            add_capability(model, Capability::FunctionCalling);
+        }
+        if vision {
+            add_capability(model, Capability::Vision);
+        }
+    }
+}
+
+fn local_model(runtime: LocalRuntime, id: &str, context_window: Option<usize>) -> ModelInfo {
+    ModelInfo {
+        id: id.to_string(),
+        name: id.to_string(),
+        provider: runtime.id().to_string(),
+        context_window: context_window.unwrap_or(DEFAULT_CONTEXT_WINDOW),
+        max_output_tokens: None,
+        capabilities: vec![Capability::Chat, Capability::Code, Capability::Streaming],
+        pricing: None,
+        is_free: true,
+    }
+}
+
+fn add_capability(model: &mut ModelInfo, capability: Capability) {
+    if !model.capabilities.contains(&capability) {
+        model.capabilities.push(capability);
+    }
+}
+
+/// Probe a runtime for the models it serves and their capabilities
+pub async fn probe_models(
+    client: &Client,
+    runtime: LocalRuntime,
+    base_url: &str,
+    api_key: Option<&str>,
+) -> Result<Vec<ModelInfo>, ProviderError> {
+    let listing = get_json(client, base_url, runtime.models_path(), api_key).await;
+    let listing = match (runtime, listing) {
+        // Older LM Studio builds only expose the OpenAI listing
+        (LocalRuntime::LmStudio, Err(e)) => {
+            debug!("LM Studio REST API unavailable ({}), using /v1/models", e);
+            get_json(client, base_url, "/v1/models", api_key).await?
+        }
+        (_, listing) => listing?,
+    };
+    let mut models = parse_model_listing(runtime, &listing);
+
+    match runtime {
+        LocalRuntime::LlamaCpp => match get_json(client, base_url, "/props", api_key).await {
+            Ok(props) => apply_llama_cpp_props(&mut models, &props),
+            Err(e) => debug!("llama.cpp /props unavailable: {}", e),
+        },
+        LocalRuntime::Vllm => {
+            if let Some(model) = models.first().map(|m| m.id.clone()) {
+                if probe_tool_calling(client, base_url, api_key, &model).await {
+                    for model in models.iter_mut() {
+                        add_capability(model, Capability::FunctionCalling);
+                    }
+                }
+            }
+        }
+        LocalRuntime::Ollama | LocalRuntime::LmStudio => {}
+    }
+
+    debug!(
+        "Probed {} models from {} at {}",
+        models.len(),
+        runtime,
+        base_url
+    );
+    Ok(models)
+}
+
+/// Check whether a server accepts automatic tool choice
+///
+/// vLLM rejects `tool_choice: "auto"` unless it was started with
+/// `--enable-auto-tool-choice`, so a one-token request tells us either way.
+async fn probe_tool_calling(
+    client: &Client,
+    base_url: &str,
+    api_key: Option<&str>,
+    model: &str,
+) -> bool {
+    let body = json!({
+        "model": model,
+        "messages": [{"role": "user", "content": "ping"}],
+        "max_tokens": 1,
+        "tools": [{
+            "type": "function",
+            "function": {
+                "name": "noop",
+                "description": "Does nothing",
+                "parameters": {"type": "object", "properties": {}}
+            }
+        }],
+        "tool_choice": "auto"
+    });
+
+    let mut builder = client
+        .post(format!("{}/v1/chat/completions", base_url))
+        .json(&body);
+    if let Some(key) = api_key {
+        builder = builder.bearer_auth(key);
+    }
+
+    match builder.send().await {
+        Ok(response) => {
+            trace!("Tool calling probe returned {}", response.status());
+            response.status().is_success()
+        }
+        Err(e) => {
+            debug!("Tool calling probe failed: {}", e);
+            false
+        }
+    }
+}
+
+async fn get_json(
+    client: &Client,
+    base_url: &str,
+    path: &str,
+    api_key: Option<&str>,
+) -> Result<Value, ProviderError> {
+    let mut builder = client.get(format!("{}{}", base_url, path));
+    if let Some(key) = api_key {
+        builder = builder.bearer_auth(key);
+    }
+
+    let response = builder.send().await?;
+    let status = response.status();
+    if !status.is_success() {
+        return match status.as_u16() {
+            401 => Err(ProviderError::AuthError),
+            _ => Err(ProviderError::ProviderError(format!(
+                "{}{} returned {}",
+                base_url, path, status
+            ))),
+        };
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| ProviderError::ParseError(format!("Invalid response from {}: {}", path, e)))
+}
+
+/// A local runtime found by discovery
+#[derive(Debug, Clone)]
+pub struct DiscoveredRuntime {
+    /// The runtime answering on this endpoint
+    pub runtime: LocalRuntime,
+    /// Base URL of the runtime
+    pub base_url: String,
+    /// Models the runtime serves
+    pub models: Vec<ModelInfo>,
+}
+
+/// Finds local model runtimes listening on their common ports
+#[derive(Debug, Clone)]
+pub struct LocalDiscovery {
+    host: String,
+    candidates: Vec<(LocalRuntime, u16)>,
+    timeout: Duration,
+}
+
+impl Default for LocalDiscovery {
+    fn default() -> Self {
+        Self {
+            host: "localhost".to_string(),
+            candidates: LocalRuntime::ALL
+                .iter()
+                .map(|runtime| (*runtime, runtime.default_port()))
+                .collect(),
+            timeout: DISCOVERY_TIMEOUT,
+        }
+    }
+}
+
+impl LocalDiscovery {
+    /// Create a discovery routine for the default ports on localhost
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Probe a different host
+    pub fn with_host(mut self, host: impl Into<String>) -> Self {
+        self.host = host.into();
+        self
+    }
+
+    /// Also look for a runtime on a non-default port
+    pub fn with_candidate(mut self, runtime: LocalRuntime, port: u16) -> Self {
+        if !self.candidates.contains(&(runtime, port)) {
+            self.candidates.push((runtime, port));
+        }
+        self
+    }
+
+    /// Replace the runtime/port pairs to probe
+    pub fn with_candidates(mut self, candidates: Vec<(LocalRuntime, u16)>) -> Self {
+        self.candidates = candidates;
+        self
+    }
+
+    /// Set the per-request timeout
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// List the runtimes that are running and the models they serve
+    pub async fn discover(&self) -> Vec<DiscoveredRuntime> {
+        let client = match Client::builder()
+            .timeout(self.timeout)
+            .connect_timeout(DISCOVERY_CONNECT_TIMEOUT.min(self.timeout))
+            .build()
+        {
+            Ok(client) => client,
+            Err(e) => {
+                error!("Failed to create HTTP client for discovery: {}", e);
+                return Vec::new();
+            }
+        };
+
+        let probes = self.candidates.iter().map(|(runtime, port)| {
+            let client = &client;
+            let base_url = format!("http://{}:{}", self.host, port);
+            async move {
+                let signature = client
+                    .get(format!("{}{}", base_url, runtime.signature_path()))
+                    .send()
+                    .await
+                    .ok()
+                    .filter(|response| response.status().is_success())?;
+                drop(signature);
+
+                match probe_models(client, *runtime, &base_url, None).await {
+                    Ok(models) => {
+                        info!(
+                            "Found {} at {} ({} models)",
+                            runtime,
+                            base_url,
+                            models.len()
+                        );
+                        Some(DiscoveredRuntime {
+                            runtime: *runtime,
+                            base_url,
+                            models,
+                        })
+                    }
+                    Err(e) => {
+                        warn!(
+                            "{} answered at {} but probing failed: {}",
+                            runtime, base_url, e
+                        );
+                        None
+                    }
+                }
+            }
+        });
+
+        futures::future::join_all(probes)
+            .await
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+}
+
+/// List the local runtimes running on their common ports
+pub async fn discover_local_models() -> Vec<DiscoveredRuntime> {
+    LocalDiscovery::default().discover().await
+}
+
+/// Provider for an OpenAI-compatible local runtime
+pub struct LocalModelProvider {
+    runtime: LocalRuntime,
+    client: Arc<Client>,
+    base_url: String,
+    api_key: Option<String>,
+    models: Vec<ModelInfo>,
+}
+
+impl LocalModelProvider {
+    /// Create a new provider for a runtime at the given base URL
+    pub fn new(runtime: LocalRuntime, base_url: String) -> Result<Self, ProviderError> {
+        Self::with_client(Arc::new(Client::new()), runtime, base_url)
+    }
+
+    /// Create a new provider using the runtime's default localhost endpoint
+    pub fn with_default_endpoint(runtime: LocalRuntime) -> Result<Self, ProviderError> {
+        Self::new(runtime, runtime.default_base_url())
+    }
+
+    /// Create a new provider with a custom HTTP client
+    pub fn with_client(
+        client: Arc<Client>,
+        runtime: LocalRuntime,
+        base_url: String,
+    ) -> Result<Self, ProviderError> {
+        let base_url = base_url
+            .trim_end_matches('/')
+            .trim_end_matches("/v1")
+            .to_string();
+        if base_url.is_empty() {
+            return Err(ProviderError::ConfigError(format!(
+                "{} base URL is required",
+                runtime
+            )));
+        }
+
+        Ok(Self {
+            runtime,
+            client,
+            base_url,
+            api_key: None,
+            models: Vec::new(),
+        })
+    }
+
+    /// Create a provider for a discovered runtime
+    pub fn from_discovered(discovered: DiscoveredRuntime) -> Result<Self, ProviderError> {
+        Ok(Self::new(discovered.runtime, discovered.base_url)?.with_models(discovered.models))
+    }
+
+    /// Send an API key (vLLM `--api-key`, llama.cpp `--api-key`)
+    pub fn with_api_key(mut self, api_key: String) -> Self {
+        self.api_key = Some(api_key).filter(|key| !key.is_empty());
+        self
+    }
+
+    /// Use a known model list instead of probing
+    pub fn with_models(mut self, models: Vec<ModelInfo>) -> Self {
+        self.models = models;
+        self
+    }
+
+    /// The runtime this provider talks to
+    pub fn runtime(&self) -> LocalRuntime {
+        self.runtime
+    }
+
+    /// Base URL of the runtime
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Probe the runtime and refresh the model list
+    pub async fn probe(&mut self) -> Result<&[ModelInfo], ProviderError> {
+        self.models = probe_models(
+            &self.client,
+            self.runtime,
+            &self.base_url,
+            self.api_key.as_deref(),
+        )
+        .await?;
+        Ok(&self.models)
+    }
+
+    fn validate_model(&self, model: &str) -> Result<(), ProviderError> {
+        // Before probing, the server is the authority on what it serves
+        if !self.models.is_empty() && !self.models.iter().any(|m| m.id == model) {
+            return Err(ProviderError::InvalidModel(model.to_string()));
+        }
+        Ok(())
+    }
+
+    fn build_request(&self, request: &ChatRequest, stream: bool) -> LocalChatRequest {
+        LocalChatRequest {
+            model: request.model.clone(),
+            messages: request
+                .messages
+                .iter()
+                .map(|m| LocalMessage {
+                    role: m.role.clone(),
+                    content: m.content.clone(),
+                })
+                .collect(),
+            temperature: request.temperature,
+            max_tokens: request.max_tokens,
+            stream,
+            params: params_for(ParamDialect::OpenAi, request),
+        }
+    }
+
+    async fn send(&self, body: &LocalChatRequest) -> Result<reqwest::Response, ProviderError> {
+        let mut builder = self
+            .client
+            .post(format!("{}/v1/chat/completions", self.base_url))
+            .json(body);
+        if let Some(key) = &self.api_key {
+            builder = builder.bearer_auth(key);
+        }
+
+        let response = builder.send().await.map_err(|e| {
+            error!("{} request failed: {}", self.runtime, e);
+            ProviderError::from(e)
+        })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            error!("{} API error ({}): {}", self.runtime, status, error_text);
+
+            return match status.as_u16() {
+                401 => Err(ProviderError::AuthError),
+                404 => Err(ProviderError::ModelNotAvailable(body.model.clone())),
+                _ => Err(ProviderError::ProviderError(format!(
+                    "{} API error: {}",
+                    self.runtime, status
+                ))),
+            };
+        }
+        Ok(response)
+    }
+
+    /// Parse SSE response into a stream of ChatResponse
+    fn parse_sse_response(body: &str, model: String) -> crate::provider::ChatStream {
+        let mut responses: Vec<Result<ChatResponse, ProviderError>> = Vec::new();
+
+        for line in body.lines() {
+            let Some(json_str) = line.strip_prefix("data:").map(str::trim) else {
+                continue;
+            };
+            if json_str == "[DONE]" {
+                trace!("Stream completed with [DONE] marker");
+                continue;
+            }
+
+            match serde_json::from_str::<LocalStreamChunk>(json_str) {
+                Ok(chunk) => {
+                    let Some(choice) = chunk.choices.first() else {
+                        continue;
+                    };
+                    let content = choice
+                        .delta
+                        .as_ref()
+                        .and_then(|delta| delta.content.clone())
+                        .unwrap_or_default();
+                    if content.is_empty() && choice.finish_reason.is_none() {
+                        continue;
+                    }
+                    responses.push(Ok(ChatResponse {
+                        content,
+                        model: model.clone(),
+                        usage: TokenUsage {
+                            prompt_tokens: 0,
+                            completion_tokens: 0,
+                            total_tokens: 0,
+                        },
+                        finish_reason: finish_reason(choice.finish_reason.as_deref()),
+                    }));
+                }
+                Err(e) => {
+                    debug!("Failed to parse SSE chunk: {} - data: {}", e, json_str);
+                }
+            }
+        }
+
+        futures::stream::iter(responses).boxed()
+    }
+}
+
+fn finish_reason(reason: Option<&str>) -> FinishReason {
+    match reason {
+        Some("length") => FinishReason::Length,
+        Some("error") => FinishReason::Error,
+        _ => FinishReason::Stop,
+    }
+}
+
+#[async_trait]
+impl Provider for LocalModelProvider {
+    fn id(&self) -> &str {
+        self.runtime.id()
+    }
+
+    fn name(&self) -> &str {
+        self.runtime.name()
+    }
+
+    fn models(&self) -> Vec<ModelInfo> {
+        self.models.clone()
+    }
+
+    async fn chat(&self, request: ChatRequest) -> Result<ChatResponse, ProviderError> {
+        self.validate_model(&request.model)?;
+        debug!(
+            "Sending chat request to {} for model: {}",
+            self.runtime, request.model
+        );
+
+        let body = self.build_request(&request, false);
+        let response: LocalChatResponse = self.send(&body).await?.json().await?;
+
+        let choice = response.choices.first();
+        let content = choice
+            .and_then(|c| c.message.as_ref())
+            .map(|m| m.content.clone().unwrap_or_default())
+            .ok_or_else(|| ProviderError::ProviderError("No content in response".to_string()))?;
+        let usage = response.usage.unwrap_or_default();
+
+        Ok(ChatResponse {
+            content,
+            model: request.model,
+            usage: TokenUsage {
+                prompt_tokens: usage.prompt_tokens,
+                completion_tokens: usage.completion_tokens,
+                total_tokens: usage.total_tokens,
+            },
+            finish_reason: finish_reason(choice.and_then(|c| c.finish_reason.as_deref())),
+        })
+    }
+
+    async fn chat_stream(
+        &self,
+        request: ChatRequest,
+    ) -> Result<crate::provider::ChatStream, ProviderError> {
+        self.validate_model(&request.model)?;
+        debug!(
+            "Starting streaming chat request to {} for model: {}",
+            self.runtime, request.model
+        );
+
+        let body = self.build_request(&request, true);
+        let text = self.send(&body).await?.text().await.map_err(|e| {
+            error!("Failed to read streaming response body: {}", e);
+            ProviderError::NetworkError(e.to_string())
+        })?;
+
+        Ok(Self::parse_sse_response(&text, request.model))
+    }
+
+    fn count_tokens(&self, content: &str, _model: &str) -> Result<usize, ProviderError> {
+        // Local runtimes tokenize server-side; approximate 1 token ≈ 4 characters
+        Ok(content.len().div_ceil(4))
+    }
+
+    async fn health_check(&self) -> Result<bool, ProviderError> {
+        debug!("Performing health check for {} provider", self.runtime);
+
+        let path = match self.runtime {
+            LocalRuntime::LlamaCpp | LocalRuntime::Vllm => "/health",
+            LocalRuntime::Ollama => "/api/tags",
+            LocalRuntime::LmStudio => "/v1/models",
+        };
+        let mut builder = self.client.get(format!("{}{}", self.base_url, path));
+        if let Some(key) = &self.api_key {
+            builder = builder.bearer_auth(key);
+        }
+
+        let response = builder.send().await.map_err(|e| {
+            warn!("{} health check failed: {}", self.runtime, e);
+            ProviderError::from(e)
+        })?;
+
+        match response.status().as_u16() {
+            200 => Ok(true),
+            401 => Err(ProviderError::AuthError),
+            status => {
+                // llama.cpp answers 503 while the model is still loading
+                warn!(
+                    "{} health check failed with status: {}",
+                    self.runtime, status
+                );
+                Ok(false)
+            }
+        }
+    }
+}
+
+/// OpenAI-compatible chat request format
+#[derive(Debug, Serialize)]
+struct LocalChatRequest {
+    model: String,
+    messages: Vec<LocalMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tokens: Option<usize>,
+    stream: bool,
+    /// Provider-specific generation parameters
+    #[serde(flatten)]
+    params: serde_json::Map<String, serde_json::Value>,
+}
+
+/// OpenAI-compatible message format
+#[derive(Debug, Serialize)]
+struct LocalMessage {
+    role: String,
+    content: String,
+}
+
+/// OpenAI-compatible chat response format
+#[derive(Debug, Deserialize)]
+struct LocalChatResponse {
+    choices: Vec<LocalChoice>,
+    /// Some runtimes omit usage entirely
+    #[serde(default)]
+    usage: Option<LocalUsage>,
+}
+
+/// OpenAI-compatible choice format
+#[derive(Debug, Deserialize)]
+struct LocalChoice {
+    message: Option<LocalResponseMessage>,
+    finish_reason: Option<String>,
+}
+
+/// Response message (content is null for pure tool calls)
+#[derive(Debug, Deserialize)]
+struct LocalResponseMessage {
+    #[serde(default)]
+    content: Option<String>,
+}
+
+/// OpenAI-compatible usage format
+#[derive(Debug, Default, Deserialize)]
+struct LocalUsage {
+    #[serde(default)]
+    prompt_tokens: usize,
+    #[serde(default)]
+    completion_tokens: usize,
+    #[serde(default)]
+    total_tokens: usize,
+}
+
+/// Streaming chunk format (Server-Sent Events)
+#[derive(Debug, Deserialize)]
+struct LocalStreamChunk {
+    choices: Vec<LocalStreamChoice>,
+}
+
+/// Streaming choice format
+#[derive(Debug, Deserialize)]
+struct LocalStreamChoice {
+    delta: Option<LocalDelta>,
+    finish_reason: Option<String>,
+}
+
+/// Delta content in streaming response
+#[derive(Debug, Deserialize)]
+struct LocalDelta {
+    #[serde(default)]
+    content: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_runtime_defaults() {
+        assert_eq!(
+            LocalRuntime::LlamaCpp.default_base_url(),
+            "http://localhost:8080"
+        );
+        assert_eq!(LocalRuntime::LmStudio.default_port(), 1234);
+        assert_eq!(LocalRuntime::Vllm.default_port(), 8000);
+        assert_eq!(LocalRuntime::Ollama.default_port(), 11434);
+    }
+
+    #[test]
+    fn test_base_url_normalized() {
+        let provider =
+            LocalModelProvider::new(LocalRuntime::Vllm, "http://gpu-box:8000/v1/".to_string())
+                .unwrap();
+        assert_eq!(provider.base_url(), "http://gpu-box:8000");
+        assert_eq!(provider.id(), "vllm");
+        assert!(LocalModelProvider::new(LocalRuntime::Vllm, String::new()).is_err());
+    }
+
+    #[test]
+    fn test_unprobed_provider_accepts_any_model() {
+        let provider = LocalModelProvider::with_default_endpoint(LocalRuntime::LlamaCpp).unwrap();
+        assert!(provider.validate_model("whatever.gguf").is_ok());
+
+        let provider = provider.with_models(vec![local_model(LocalRuntime::LlamaCpp, "a", None)]);
+        assert!(provider.validate_model("a").is_ok());
+        assert!(matches!(
+            provider.validate_model("b"),
+            Err(ProviderError::InvalidModel(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_sse_keeps_finish_reason() {
+        let body =
+            "data: {\"choices\":[{\"delta\":{\"content\":\"Hi\"},\"finish_reason\":null}]}\n\n\
+                    data: {\"choices\":[{\"delta\":{},\"finish_reason\":\"length\"}]}\n\n\
+                    data: [DONE]\n";
+        let chunks: Vec<_> = futures::executor::block_on(
+            LocalModelProvider::parse_sse_response(body, "m".to_string()).collect::<Vec<_>>(),
+        );
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].as_ref().unwrap().content, "Hi");
+        assert_eq!(
+            chunks[1].as_ref().unwrap().finish_reason,
+            FinishReason::Length
+        );
+    }
+}
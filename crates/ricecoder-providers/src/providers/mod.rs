@@ -5,6 +5,7 @@ pub mod azure_openai;
 pub mod cohere;
 pub mod gcp_vertex;
 pub mod google;
+pub mod local;
 pub mod ollama;
 pub mod ollama_config;
 pub mod openai;
@@ -18,6 +19,9 @@ pub use azure_openai::AzureOpenAiProvider;
 pub use cohere::CohereProvider;
 pub use gcp_vertex::GcpVertexProvider;
 pub use google::GoogleProvider;
+pub use local::{
+    discover_local_models, DiscoveredRuntime, LocalDiscovery, LocalModelProvider, LocalRuntime,
+};
 pub use ollama::OllamaProvider;
 pub use ollama_config::OllamaConfig;
 pub use openai::OpenAiProvider;
@@ -20,6 +20,7 @@ use crate::{
     error::ProviderError,
     models::{Capability, ChatRequest, ChatResponse, FinishReason, ModelInfo, TokenUsage},
     provider::Provider,
+    transform::{params_for, ParamDialect},
 };
 
 /// Configuration for retry logic
@@ -244,6 +245,7 @@ impl OllamaProvider {
                 name: "Mistral".to_string(),
                 provider: "ollama".to_string(),
                 context_window: 8192,
+                max_output_tokens: None,
                 capabilities: vec![Capability::Chat, Capability::Code, Capability::Streaming],
                 pricing: None,
                 is_free: true,
@@ -253,6 +255,7 @@ impl OllamaProvider {
                 name: "Neural Chat".to_string(),
                 provider: "ollama".to_string(),
                 context_window: 4096,
+                max_output_tokens: None,
                 capabilities: vec![Capability::Chat, Capability::Code, Capability::Streaming],
                 pricing: None,
                 is_free: true,
@@ -262,6 +265,7 @@ impl OllamaProvider {
                 name: "Llama 2".to_string(),
                 provider: "ollama".to_string(),
                 context_window: 4096,
+                max_output_tokens: None,
                 capabilities: vec![Capability::Chat, Capability::Code, Capability::Streaming],
                 pricing: None,
                 is_free: true,
@@ -322,6 +326,7 @@ impl OllamaProvider {
                 name: model.name.clone(),
                 provider: "ollama".to_string(),
                 context_window: 4096, // Default context window for local models
+                max_output_tokens: None,
                 capabilities: vec![Capability::Chat, Capability::Code, Capability::Streaming],
                 pricing: None, // Local models have no pricing
                 is_free: true, // Local models are always free
@@ -377,6 +382,7 @@ impl Provider for OllamaProvider {
                     name: "Mistral".to_string(),
                     provider: "ollama".to_string(),
                     context_window: 8192,
+                    max_output_tokens: None,
                     capabilities: vec![Capability::Chat, Capability::Code, Capability::Streaming],
                     pricing: None,
                     is_free: true,
@@ -386,6 +392,7 @@ impl Provider for OllamaProvider {
                     name: "Neural Chat".to_string(),
                     provider: "ollama".to_string(),
                     context_window: 4096,
+                    max_output_tokens: None,
                     capabilities: vec![Capability::Chat, Capability::Code, Capability::Streaming],
                     pricing: None,
                     is_free: true,
@@ -395,6 +402,7 @@ impl Provider for OllamaProvider {
                     name: "Llama 2".to_string(),
                     provider: "ollama".to_string(),
                     context_window: 4096,
+                    max_output_tokens: None,
                     capabilities: vec![Capability::Chat, Capability::Code, Capability::Streaming],
                     pricing: None,
                     is_free: true,
@@ -422,6 +430,7 @@ impl Provider for OllamaProvider {
                 })
                 .collect(),
             stream: false,
+            params: params_for(ParamDialect::Ollama, &request),
         };
 
         let base_url = self.base_url.clone();
@@ -478,6 +487,7 @@ impl Provider for OllamaProvider {
                 })
                 .collect(),
             stream: true,
+            params: params_for(ParamDialect::Ollama, &request),
         };
 
         let base_url = self.base_url.clone();
@@ -593,6 +603,9 @@ struct OllamaChatRequest {
     model: String,
     messages: Vec<OllamaMessage>,
     stream: bool,
+    /// Provider-specific generation parameters
+    #[serde(flatten)]
+    params: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Ollama API message format
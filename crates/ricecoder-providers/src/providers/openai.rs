@@ -13,9 +13,13 @@ use tracing::{debug, error, trace, warn};
 
 use crate::{
     error::ProviderError,
-    models::{Capability, ChatRequest, ChatResponse, FinishReason, ModelInfo, TokenUsage},
+    models::{
+        Capability, ChatRequest, ChatResponse, EmbeddingRequest, EmbeddingResponse, FinishReason,
+        ModelInfo, TokenUsage,
+    },
     provider::Provider,
     token_counter::TokenCounter,
+    transform::{params_for, ParamDialect},
 };
 
 /// OpenAI provider implementation
@@ -211,6 +215,7 @@ impl Provider for OpenAiProvider {
                 .collect(),
             temperature: request.temperature,
             max_tokens: request.max_tokens,
+            params: params_for(ParamDialect::OpenAi, &request),
         };
 
         debug!(
@@ -273,6 +278,7 @@ impl Provider for OpenAiProvider {
             temperature: request.temperature,
             max_tokens: request.max_tokens,
             stream: true,
+            params: params_for(ParamDialect::OpenAi, &request),
         };
 
         debug!(
@@ -364,6 +370,56 @@ impl Provider for OpenAiProvider {
             }
         }
     }
+
+    async fn embed(&self, request: EmbeddingRequest) -> Result<EmbeddingResponse, ProviderError> {
+        debug!(
+            "Sending embedding request to OpenAI for model: {} ({} inputs)",
+            request.model,
+            request.input.len()
+        );
+
+        let response = self
+            .client
+            .post(format!("{}/embeddings", self.base_url))
+            .header("Authorization", self.get_auth_header())
+            .header("Content-Type", "application/json")
+            .json(&OpenAiEmbeddingRequest {
+                model: request.model.clone(),
+                input: request.input,
+            })
+            .send()
+            .await
+            .map_err(|e| {
+                error!("OpenAI embedding request failed: {}", e);
+                ProviderError::from(e)
+            })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            error!("OpenAI API error ({}): {}", status, error_text);
+
+            return match status.as_u16() {
+                401 => Err(ProviderError::AuthError),
+                429 => Err(ProviderError::RateLimited(60)),
+                _ => Err(ProviderError::ProviderError(format!(
+                    "OpenAI API error: {}",
+                    status
+                ))),
+            };
+        }
+
+        let mut openai_response: OpenAiEmbeddingResponse = response.json().await?;
+        openai_response.data.sort_by_key(|d| d.index);
+        Ok(EmbeddingResponse {
+            model: request.model,
+            embeddings: openai_response
+                .data
+                .into_iter()
+                .map(|d| d.embedding)
+                .collect(),
+        })
+    }
 }
 
 /// OpenAI API request format
@@ -375,6 +431,9 @@ struct OpenAiChatRequest {
     temperature: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     max_tokens: Option<usize>,
+    /// Provider-specific generation parameters
+    #[serde(flatten)]
+    params: serde_json::Map<String, serde_json::Value>,
 }
 
 /// OpenAI API message format
@@ -406,6 +465,26 @@ struct OpenAiUsage {
     total_tokens: usize,
 }
 
+/// OpenAI embeddings request format
+#[derive(Debug, Serialize)]
+struct OpenAiEmbeddingRequest {
+    model: String,
+    input: Vec<String>,
+}
+
+/// OpenAI embeddings response format
+#[derive(Debug, Deserialize)]
+struct OpenAiEmbeddingResponse {
+    data: Vec<OpenAiEmbedding>,
+}
+
+/// OpenAI embedding entry format
+#[derive(Debug, Deserialize)]
+struct OpenAiEmbedding {
+    index: usize,
+    embedding: Vec<f32>,
+}
+
 /// OpenAI streaming request format
 #[derive(Debug, Serialize)]
 struct OpenAiStreamRequest {
@@ -416,6 +495,9 @@ struct OpenAiStreamRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     max_tokens: Option<usize>,
     stream: bool,
+    /// Provider-specific generation parameters
+    #[serde(flatten)]
+    params: serde_json::Map<String, serde_json::Value>,
 }
 
 /// OpenAI streaming chunk format (Server-Sent Events)
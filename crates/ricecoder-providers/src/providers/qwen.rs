@@ -29,6 +29,7 @@ use crate::{
     models::{Capability, ChatRequest, ChatResponse, FinishReason, ModelInfo, TokenUsage},
     provider::Provider,
     token_counter::TokenCounter,
+    transform::{params_for, ParamDialect},
 };
 
 // DashScope API endpoints
@@ -329,6 +330,7 @@ impl Provider for QwenProvider {
             temperature: request.temperature,
             max_tokens: request.max_tokens,
             stream: false,
+            params: params_for(ParamDialect::Qwen, &request),
         };
 
         debug!(
@@ -405,6 +407,7 @@ impl Provider for QwenProvider {
             temperature: request.temperature,
             max_tokens: request.max_tokens,
             stream: true,
+            params: params_for(ParamDialect::Qwen, &request),
         };
 
         debug!(
@@ -505,6 +508,9 @@ struct QwenChatRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     max_tokens: Option<usize>,
     stream: bool,
+    /// Provider-specific generation parameters
+    #[serde(flatten)]
+    params: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -561,6 +567,7 @@ mod tests {
                 name: "Qwen3 8B".to_string(),
                 provider: "qwen".to_string(),
                 context_window: 131072,
+                max_output_tokens: None,
                 capabilities: vec![Capability::Chat, Capability::Code, Capability::Streaming],
                 pricing: None,
                 is_free: false,
@@ -570,6 +577,7 @@ mod tests {
                 name: "Qwen2.5 Coder 32B".to_string(),
                 provider: "qwen".to_string(),
                 context_window: 131072,
+                max_output_tokens: None,
                 capabilities: vec![Capability::Chat, Capability::Code, Capability::Streaming],
                 pricing: None,
                 is_free: false,
@@ -579,6 +587,7 @@ mod tests {
                 name: "Qwen Max".to_string(),
                 provider: "qwen".to_string(),
                 context_window: 32768,
+                max_output_tokens: None,
                 capabilities: vec![Capability::Chat, Capability::Code, Capability::Streaming],
                 pricing: Some(crate::models::Pricing {
                     input_per_1k_tokens: 0.0024,
@@ -14,6 +14,7 @@ use crate::{
     models::{Capability, ChatRequest, ChatResponse, FinishReason, ModelInfo, Pricing, TokenUsage},
     provider::Provider,
     token_counter::TokenCounter,
+    transform::{params_for, ParamDialect},
 };
 
 /// Replicate provider implementation
@@ -117,6 +118,7 @@ impl Provider for ReplicateProvider {
                     .join("\n"),
                 max_new_tokens: request.max_tokens.unwrap_or(512),
                 temperature: request.temperature.unwrap_or(0.7),
+                params: params_for(ParamDialect::Replicate, &request),
             },
         };
 
@@ -186,6 +188,9 @@ struct ReplicateInput {
     prompt: String,
     max_new_tokens: usize,
     temperature: f32,
+    /// Provider-specific generation parameters
+    #[serde(flatten)]
+    params: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Replicate chat response structure
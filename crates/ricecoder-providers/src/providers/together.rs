@@ -14,6 +14,7 @@ use crate::{
     models::{Capability, ChatRequest, ChatResponse, FinishReason, ModelInfo, Pricing, TokenUsage},
     provider::Provider,
     token_counter::{TokenCounter, TokenCounterTrait},
+    transform::{params_for, ParamDialect},
 };
 
 /// Together AI provider implementation
@@ -124,6 +125,7 @@ impl Provider for TogetherProvider {
             max_tokens: request.max_tokens.unwrap_or(512),
             temperature: request.temperature.unwrap_or(0.7),
             stop: None,
+            params: params_for(ParamDialect::OpenAi, &request),
         };
 
         let response = self
@@ -184,6 +186,9 @@ struct TogetherChatRequest {
     temperature: f32,
     #[serde(skip_serializing_if = "Option::is_none")]
     stop: Option<Vec<String>>,
+    /// Provider-specific generation parameters
+    #[serde(flatten)]
+    params: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Together AI chat response structure
@@ -19,6 +19,7 @@ use crate::{
     models::{Capability, ChatRequest, ChatResponse, FinishReason, ModelInfo, TokenUsage},
     provider::Provider,
     token_counter::TokenCounter,
+    transform::{params_for, ParamDialect},
 };
 
 /// Zen provider implementation
@@ -261,6 +262,7 @@ impl ZenProvider {
                             name: m.name.unwrap_or_else(|| m.id.clone()),
                             provider: "zen".to_string(),
                             context_window: m.context_window.unwrap_or(4096),
+                            max_output_tokens: m.max_output_tokens,
                             capabilities: m.capabilities.unwrap_or_default(),
                             pricing: m.pricing.map(|p| crate::models::Pricing {
                                 input_per_1k_tokens: p.input_cost_per_1k,
@@ -475,6 +477,7 @@ impl Provider for ZenProvider {
                 name: "GPT-5 Nano".to_string(),
                 provider: "zen".to_string(),
                 context_window: 400000,
+                max_output_tokens: None,
                 capabilities: vec![Capability::Chat, Capability::Code, Capability::Streaming],
                 pricing: Some(crate::models::Pricing {
                     input_per_1k_tokens: 0.0,
@@ -487,6 +490,7 @@ impl Provider for ZenProvider {
                 name: "GLM 4.7".to_string(),
                 provider: "zen".to_string(),
                 context_window: 204800,
+                max_output_tokens: None,
                 capabilities: vec![Capability::Chat, Capability::Code, Capability::Streaming],
                 pricing: Some(crate::models::Pricing {
                     input_per_1k_tokens: 0.0,
@@ -499,6 +503,7 @@ impl Provider for ZenProvider {
                 name: "Grok Code Fast 1".to_string(),
                 provider: "zen".to_string(),
                 context_window: 256000,
+                max_output_tokens: None,
                 capabilities: vec![Capability::Chat, Capability::Code, Capability::Streaming],
                 pricing: Some(crate::models::Pricing {
                     input_per_1k_tokens: 0.0,
@@ -511,6 +516,7 @@ impl Provider for ZenProvider {
                 name: "Big Pickle".to_string(),
                 provider: "zen".to_string(),
                 context_window: 128000,
+                max_output_tokens: None,
                 capabilities: vec![Capability::Chat, Capability::Code, Capability::Streaming],
                 pricing: Some(crate::models::Pricing {
                     input_per_1k_tokens: 0.0,
@@ -524,6 +530,7 @@ impl Provider for ZenProvider {
                 name: "GPT-5.2".to_string(),
                 provider: "zen".to_string(),
                 context_window: 128000,
+                max_output_tokens: None,
                 capabilities: vec![
                     Capability::Chat,
                     Capability::Code,
@@ -541,6 +548,7 @@ impl Provider for ZenProvider {
                 name: "GPT-5.1 Codex".to_string(),
                 provider: "zen".to_string(),
                 context_window: 128000,
+                max_output_tokens: None,
                 capabilities: vec![Capability::Chat, Capability::Code, Capability::Streaming],
                 pricing: Some(crate::models::Pricing {
                     input_per_1k_tokens: 1.07,
@@ -553,6 +561,7 @@ impl Provider for ZenProvider {
                 name: "Claude Sonnet 4.5".to_string(),
                 provider: "zen".to_string(),
                 context_window: 200000,
+                max_output_tokens: None,
                 capabilities: vec![
                     Capability::Chat,
                     Capability::Code,
@@ -570,6 +579,7 @@ impl Provider for ZenProvider {
                 name: "Claude Sonnet 4".to_string(),
                 provider: "zen".to_string(),
                 context_window: 200000,
+                max_output_tokens: None,
                 capabilities: vec![
                     Capability::Chat,
                     Capability::Code,
@@ -587,6 +597,7 @@ impl Provider for ZenProvider {
                 name: "Gemini 3 Flash".to_string(),
                 provider: "zen".to_string(),
                 context_window: 1000000,
+                max_output_tokens: None,
                 capabilities: vec![
                     Capability::Chat,
                     Capability::Code,
@@ -651,6 +662,7 @@ impl Provider for ZenProvider {
             temperature: request.temperature,
             max_tokens: request.max_tokens,
             stream: false,
+            params: params_for(ParamDialect::OpenAi, &request),
         };
 
         debug!("Sending chat request to Zen for model: {}", request.model);
@@ -882,6 +894,9 @@ struct ZenChatRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     max_tokens: Option<usize>,
     stream: bool,
+    /// Provider-specific generation parameters
+    #[serde(flatten)]
+    params: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Zen API message format
@@ -939,6 +954,8 @@ struct ZenModel {
     #[serde(default)]
     context_window: Option<usize>,
     #[serde(default)]
+    max_output_tokens: Option<usize>,
+    #[serde(default)]
     capabilities: Option<Vec<Capability>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pricing: Option<ZenPricing>,
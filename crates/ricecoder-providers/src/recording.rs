@@ -0,0 +1,681 @@
+//! Provider traffic recording and replay
+//!
+//! [`RecordingProvider`] wraps a provider and writes every request and response,
+//! with secrets redacted, to a [`Cassette`]. [`ReplayProvider`] serves a cassette
+//! back deterministically, so tests and demos run without network access.
+//! [`rerun_session`] replays the recorded user turns against another model to
+//! compare agent behaviour.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::debug;
+
+use crate::{
+    error::ProviderError,
+    models::{ChatRequest, ChatResponse, EmbeddingRequest, EmbeddingResponse, Message, ModelInfo},
+    provider::{ChatStream, Provider},
+    redaction::redact,
+    token_counter::TokenCounter,
+};
+
+/// Current cassette format version
+pub const CASSETTE_VERSION: u32 = 1;
+
+/// A recorded provider request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RecordedRequest {
+    /// A `chat` call
+    Chat(ChatRequest),
+    /// A `chat_stream` call
+    ChatStream(ChatRequest),
+    /// An `embed` call
+    Embed(EmbeddingRequest),
+}
+
+impl RecordedRequest {
+    /// The chat request, for chat and streaming calls
+    pub fn chat_request(&self) -> Option<&ChatRequest> {
+        match self {
+            RecordedRequest::Chat(request) | RecordedRequest::ChatStream(request) => Some(request),
+            RecordedRequest::Embed(_) => None,
+        }
+    }
+
+    /// Copy of the request with secrets redacted
+    fn redacted(&self) -> Self {
+        let redact_chat = |request: &ChatRequest| {
+            let mut request = request.clone();
+            for message in &mut request.messages {
+                message.content = redact(&message.content);
+            }
+            request
+        };
+        match self {
+            RecordedRequest::Chat(request) => RecordedRequest::Chat(redact_chat(request)),
+            RecordedRequest::ChatStream(request) => {
+                RecordedRequest::ChatStream(redact_chat(request))
+            }
+            RecordedRequest::Embed(request) => RecordedRequest::Embed(EmbeddingRequest {
+                model: request.model.clone(),
+                input: request.input.iter().map(|text| redact(text)).collect(),
+            }),
+        }
+    }
+
+    /// Stable hash of the redacted request, used to match replayed calls
+    fn fingerprint(&self) -> String {
+        let json = serde_json::to_string(&self.redacted()).unwrap_or_default();
+        format!("{:x}", Sha256::digest(json.as_bytes()))
+    }
+}
+
+/// What a recorded request produced
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RecordedOutcome {
+    /// A complete chat response
+    Response(ChatResponse),
+    /// Streamed chunks, in order
+    Stream {
+        /// Chunks as they were received
+        chunks: Vec<ChatResponse>,
+    },
+    /// An embeddings response
+    Embedding(EmbeddingResponse),
+    /// The provider returned an error
+    Error {
+        /// Error message
+        message: String,
+    },
+}
+
+impl RecordedOutcome {
+    /// Copy of the outcome with secrets redacted
+    fn redacted(&self) -> Self {
+        let redact_response = |response: &ChatResponse| {
+            let mut response = response.clone();
+            response.content = redact(&response.content);
+            response
+        };
+        match self {
+            RecordedOutcome::Response(response) => {
+                RecordedOutcome::Response(redact_response(response))
+            }
+            RecordedOutcome::Stream { chunks } => RecordedOutcome::Stream {
+                chunks: chunks.iter().map(redact_response).collect(),
+            },
+            RecordedOutcome::Embedding(response) => RecordedOutcome::Embedding(response.clone()),
+            RecordedOutcome::Error { message } => RecordedOutcome::Error {
+                message: redact(message),
+            },
+        }
+    }
+
+    /// The response text, joining streamed chunks
+    pub fn content(&self) -> Option<String> {
+        match self {
+            RecordedOutcome::Response(response) => Some(response.content.clone()),
+            RecordedOutcome::Stream { chunks } => {
+                Some(chunks.iter().map(|chunk| chunk.content.as_str()).collect())
+            }
+            RecordedOutcome::Embedding(_) | RecordedOutcome::Error { .. } => None,
+        }
+    }
+}
+
+/// One recorded request/response pair
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Interaction {
+    /// Hash of the redacted request
+    pub fingerprint: String,
+    /// The request, with secrets redacted
+    pub request: RecordedRequest,
+    /// The response, with secrets redacted
+    pub outcome: RecordedOutcome,
+    /// Time the provider took to answer
+    pub duration_ms: u64,
+}
+
+impl Interaction {
+    /// Record a request and its outcome, redacting both
+    pub fn new(request: &RecordedRequest, outcome: &RecordedOutcome, duration_ms: u64) -> Self {
+        Self {
+            fingerprint: request.fingerprint(),
+            request: request.redacted(),
+            outcome: outcome.redacted(),
+            duration_ms,
+        }
+    }
+}
+
+/// Recorded traffic of one provider
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Cassette {
+    /// Format version
+    pub version: u32,
+    /// Recorded provider id
+    pub provider_id: String,
+    /// Recorded provider name
+    pub provider_name: String,
+    /// Models the provider reported while recording
+    #[serde(default)]
+    pub models: Vec<ModelInfo>,
+    /// When recording started
+    pub recorded_at: DateTime<Utc>,
+    /// Interactions, oldest first
+    pub interactions: Vec<Interaction>,
+}
+
+impl Cassette {
+    /// Create an empty cassette for a provider
+    pub fn new(provider_id: impl Into<String>, provider_name: impl Into<String>) -> Self {
+        Self {
+            version: CASSETTE_VERSION,
+            provider_id: provider_id.into(),
+            provider_name: provider_name.into(),
+            models: Vec::new(),
+            recorded_at: Utc::now(),
+            interactions: Vec::new(),
+        }
+    }
+
+    /// Load a cassette from a JSON file
+    pub fn load(path: &Path) -> Result<Self, ProviderError> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            ProviderError::ConfigError(format!("Failed to read cassette {}: {}", path.display(), e))
+        })?;
+        let cassette: Cassette = serde_json::from_str(&content)?;
+        if cassette.version > CASSETTE_VERSION {
+            return Err(ProviderError::ConfigError(format!(
+                "Unsupported cassette version {} in {}",
+                cassette.version,
+                path.display()
+            )));
+        }
+        Ok(cassette)
+    }
+
+    /// Write the cassette as JSON
+    pub fn save(&self, path: &Path) -> Result<(), ProviderError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                ProviderError::Internal(format!("Failed to create cassette directory: {}", e))
+            })?;
+        }
+        let content = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, content)
+            .map_err(|e| ProviderError::Internal(format!("Failed to write cassette: {}", e)))
+    }
+
+    /// The cassette file for a provider inside a recording directory
+    pub fn path_for(dir: &Path, provider_id: &str) -> PathBuf {
+        dir.join(format!("{}.json", provider_id))
+    }
+}
+
+/// Provider wrapper that records all traffic to a cassette
+pub struct RecordingProvider {
+    inner: Arc<dyn Provider>,
+    cassette: Arc<Mutex<Cassette>>,
+    path: Option<PathBuf>,
+}
+
+impl RecordingProvider {
+    /// Record an inner provider in memory
+    pub fn new(inner: Arc<dyn Provider>) -> Self {
+        let mut cassette = Cassette::new(inner.id(), inner.name());
+        cassette.models = inner.models();
+        Self {
+            inner,
+            cassette: Arc::new(Mutex::new(cassette)),
+            path: None,
+        }
+    }
+
+    /// Also save the cassette to a file after every interaction
+    pub fn with_file(mut self, path: PathBuf) -> Self {
+        self.path = Some(path);
+        self
+    }
+
+    /// Snapshot of everything recorded so far
+    pub fn cassette(&self) -> Cassette {
+        self.cassette.lock().unwrap().clone()
+    }
+
+    /// Write the cassette to a file
+    pub fn save(&self, path: &Path) -> Result<(), ProviderError> {
+        self.cassette.lock().unwrap().save(path)
+    }
+
+    fn record(&self, request: &RecordedRequest, outcome: &RecordedOutcome, started: Instant) {
+        record_interaction(
+            &self.cassette,
+            self.path.as_deref(),
+            Interaction::new(request, outcome, started.elapsed().as_millis() as u64),
+        );
+    }
+}
+
+/// Records a streamed response once the stream is finished or dropped
+struct StreamRecorder {
+    cassette: Arc<Mutex<Cassette>>,
+    path: Option<PathBuf>,
+    request: RecordedRequest,
+    started: Instant,
+    chunks: Vec<ChatResponse>,
+    error: Option<String>,
+}
+
+impl Drop for StreamRecorder {
+    fn drop(&mut self) {
+        let outcome = match self.error.take() {
+            Some(message) => RecordedOutcome::Error { message },
+            None => RecordedOutcome::Stream {
+                chunks: std::mem::take(&mut self.chunks),
+            },
+        };
+        record_interaction(
+            &self.cassette,
+            self.path.as_deref(),
+            Interaction::new(
+                &self.request,
+                &outcome,
+                self.started.elapsed().as_millis() as u64,
+            ),
+        );
+    }
+}
+
+fn record_interaction(cassette: &Mutex<Cassette>, path: Option<&Path>, interaction: Interaction) {
+    let mut cassette = cassette.lock().unwrap();
+    debug!(
+        provider = %cassette.provider_id,
+        fingerprint = %interaction.fingerprint,
+        "Provider interaction recorded"
+    );
+    cassette.interactions.push(interaction);
+    if let Some(path) = path {
+        if let Err(e) = cassette.save(path) {
+            tracing::warn!("Failed to save cassette {}: {}", path.display(), e);
+        }
+    }
+}
+
+fn outcome_of<T>(
+    result: &Result<T, ProviderError>,
+    outcome: impl FnOnce(&T) -> RecordedOutcome,
+) -> RecordedOutcome {
+    match result {
+        Ok(value) => outcome(value),
+        Err(e) => RecordedOutcome::Error {
+            message: e.to_string(),
+        },
+    }
+}
+
+#[async_trait]
+impl Provider for RecordingProvider {
+    fn id(&self) -> &str {
+        self.inner.id()
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn models(&self) -> Vec<ModelInfo> {
+        self.inner.models()
+    }
+
+    async fn chat(&self, request: ChatRequest) -> Result<ChatResponse, ProviderError> {
+        let recorded = RecordedRequest::Chat(request.clone());
+        let started = Instant::now();
+        let result = self.inner.chat(request).await;
+        self.record(
+            &recorded,
+            &outcome_of(&result, |response| {
+                RecordedOutcome::Response(response.clone())
+            }),
+            started,
+        );
+        result
+    }
+
+    async fn chat_stream(&self, request: ChatRequest) -> Result<ChatStream, ProviderError> {
+        let recorded = RecordedRequest::ChatStream(request.clone());
+        let started = Instant::now();
+        let stream = match self.inner.chat_stream(request).await {
+            Ok(stream) => stream,
+            Err(e) => {
+                self.record(
+                    &recorded,
+                    &RecordedOutcome::Error {
+                        message: e.to_string(),
+                    },
+                    started,
+                );
+                return Err(e);
+            }
+        };
+
+        // Chunks pass through as they arrive; the recorder saves them when the
+        // stream ends or is dropped
+        let recorder = StreamRecorder {
+            cassette: self.cassette.clone(),
+            path: self.path.clone(),
+            request: recorded,
+            started,
+            chunks: Vec::new(),
+            error: None,
+        };
+        let stream = futures::stream::unfold(Some((stream, recorder)), |state| async move {
+            let (mut stream, mut recorder) = state?;
+            match stream.next().await {
+                Some(Ok(chunk)) => {
+                    recorder.chunks.push(chunk.clone());
+                    Some((Ok(chunk), Some((stream, recorder))))
+                }
+                Some(Err(e)) => {
+                    recorder.error = Some(e.to_string());
+                    Some((Err(e), None))
+                }
+                None => None,
+            }
+        });
+        Ok(stream.boxed())
+    }
+
+    fn count_tokens(&self, content: &str, model: &str) -> Result<usize, ProviderError> {
+        self.inner.count_tokens(content, model)
+    }
+
+    async fn health_check(&self) -> Result<bool, ProviderError> {
+        self.inner.health_check().await
+    }
+
+    async fn embed(&self, request: EmbeddingRequest) -> Result<EmbeddingResponse, ProviderError> {
+        let recorded = RecordedRequest::Embed(request.clone());
+        let started = Instant::now();
+        let result = self.inner.embed(request).await;
+        self.record(
+            &recorded,
+            &outcome_of(&result, |response| {
+                RecordedOutcome::Embedding(response.clone())
+            }),
+            started,
+        );
+        result
+    }
+}
+
+/// How replayed calls are matched to recorded interactions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReplayMode {
+    /// Serve the first unused interaction with the same redacted request
+    #[default]
+    Match,
+    /// Serve interactions strictly in recorded order
+    Sequential,
+}
+
+#[derive(Debug, Default)]
+struct ReplayState {
+    used: Vec<bool>,
+    cursor: usize,
+}
+
+/// Provider that answers from a cassette instead of the network
+pub struct ReplayProvider {
+    cassette: Cassette,
+    mode: ReplayMode,
+    state: Mutex<ReplayState>,
+    token_counter: TokenCounter,
+}
+
+impl ReplayProvider {
+    /// Replay a cassette
+    pub fn new(cassette: Cassette) -> Self {
+        let state = ReplayState {
+            used: vec![false; cassette.interactions.len()],
+            cursor: 0,
+        };
+        Self {
+            cassette,
+            mode: ReplayMode::default(),
+            state: Mutex::new(state),
+            token_counter: TokenCounter::new(),
+        }
+    }
+
+    /// Replay a cassette file
+    pub fn from_file(path: &Path) -> Result<Self, ProviderError> {
+        Ok(Self::new(Cassette::load(path)?))
+    }
+
+    /// Set how calls are matched to recorded interactions
+    pub fn with_mode(mut self, mode: ReplayMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// The cassette being replayed
+    pub fn cassette(&self) -> &Cassette {
+        &self.cassette
+    }
+
+    /// Number of recorded interactions not yet served
+    pub fn remaining(&self) -> usize {
+        let state = self.state.lock().unwrap();
+        state.used.iter().filter(|used| !**used).count()
+    }
+
+    fn next_outcome(&self, request: &RecordedRequest) -> Result<RecordedOutcome, ProviderError> {
+        let mut state = self.state.lock().unwrap();
+        let index = match self.mode {
+            ReplayMode::Match => {
+                let fingerprint = request.fingerprint();
+                self.cassette
+                    .interactions
+                    .iter()
+                    .enumerate()
+                    .position(|(i, interaction)| {
+                        !state.used[i] && interaction.fingerprint == fingerprint
+                    })
+                    .ok_or_else(|| {
+                        ProviderError::ProviderError(format!(
+                            "No recorded interaction matches request {} for model {}",
+                            &fingerprint[..12],
+                            request_model(request)
+                        ))
+                    })?
+            }
+            ReplayMode::Sequential => {
+                let index = state.cursor;
+                if index >= self.cassette.interactions.len() {
+                    return Err(ProviderError::ProviderError(format!(
+                        "Cassette for {} has no more recorded interactions",
+                        self.cassette.provider_id
+                    )));
+                }
+                state.cursor += 1;
+                index
+            }
+        };
+        state.used[index] = true;
+        Ok(self.cassette.interactions[index].outcome.clone())
+    }
+}
+
+fn request_model(request: &RecordedRequest) -> &str {
+    match request {
+        RecordedRequest::Chat(request) | RecordedRequest::ChatStream(request) => &request.model,
+        RecordedRequest::Embed(request) => &request.model,
+    }
+}
+
+fn unexpected_outcome(expected: &str) -> ProviderError {
+    ProviderError::ProviderError(format!(
+        "Recorded interaction is not a {} response",
+        expected
+    ))
+}
+
+#[async_trait]
+impl Provider for ReplayProvider {
+    fn id(&self) -> &str {
+        &self.cassette.provider_id
+    }
+
+    fn name(&self) -> &str {
+        &self.cassette.provider_name
+    }
+
+    fn models(&self) -> Vec<ModelInfo> {
+        self.cassette.models.clone()
+    }
+
+    async fn chat(&self, request: ChatRequest) -> Result<ChatResponse, ProviderError> {
+        match self.next_outcome(&RecordedRequest::Chat(request))? {
+            RecordedOutcome::Response(response) => Ok(response),
+            RecordedOutcome::Stream { chunks } => {
+                let content = chunks.iter().map(|chunk| chunk.content.as_str()).collect();
+                let last = chunks
+                    .last()
+                    .cloned()
+                    .ok_or_else(|| unexpected_outcome("chat"))?;
+                Ok(ChatResponse { content, ..last })
+            }
+            RecordedOutcome::Error { message } => Err(ProviderError::ProviderError(message)),
+            RecordedOutcome::Embedding(_) => Err(unexpected_outcome("chat")),
+        }
+    }
+
+    async fn chat_stream(&self, request: ChatRequest) -> Result<ChatStream, ProviderError> {
+        let chunks = match self.next_outcome(&RecordedRequest::ChatStream(request))? {
+            RecordedOutcome::Stream { chunks } => chunks,
+            RecordedOutcome::Response(response) => vec![response],
+            RecordedOutcome::Error { message } => {
+                return Err(ProviderError::ProviderError(message))
+            }
+            RecordedOutcome::Embedding(_) => return Err(unexpected_outcome("chat")),
+        };
+        Ok(futures::stream::iter(chunks.into_iter().map(Ok)).boxed())
+    }
+
+    fn count_tokens(&self, content: &str, model: &str) -> Result<usize, ProviderError> {
+        self.token_counter.count(content, model)
+    }
+
+    async fn health_check(&self) -> Result<bool, ProviderError> {
+        Ok(true)
+    }
+
+    async fn embed(&self, request: EmbeddingRequest) -> Result<EmbeddingResponse, ProviderError> {
+        match self.next_outcome(&RecordedRequest::Embed(request))? {
+            RecordedOutcome::Embedding(response) => Ok(response),
+            RecordedOutcome::Error { message } => Err(ProviderError::ProviderError(message)),
+            _ => Err(unexpected_outcome("embedding")),
+        }
+    }
+}
+
+/// Whether providers are used live, recorded or replayed
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum RecordMode {
+    /// Talk to providers directly
+    #[default]
+    Off,
+    /// Record each provider to `<dir>/<provider_id>.json`
+    Record(PathBuf),
+    /// Answer from `<dir>/<provider_id>.json` instead of the provider
+    Replay(PathBuf),
+}
+
+impl RecordMode {
+    /// Wrap a provider according to the mode
+    pub fn wrap(&self, provider: Arc<dyn Provider>) -> Result<Arc<dyn Provider>, ProviderError> {
+        match self {
+            RecordMode::Off => Ok(provider),
+            RecordMode::Record(dir) => {
+                let path = Cassette::path_for(dir, provider.id());
+                Ok(Arc::new(RecordingProvider::new(provider).with_file(path)))
+            }
+            RecordMode::Replay(dir) => {
+                let path = Cassette::path_for(dir, provider.id());
+                Ok(Arc::new(ReplayProvider::from_file(&path)?))
+            }
+        }
+    }
+}
+
+/// One user turn of a recorded session, re-run against another model
+#[derive(Debug, Clone, Serialize)]
+pub struct RerunTurn {
+    /// Messages the user (or system) added in this turn
+    pub prompt: Vec<Message>,
+    /// What the recorded model answered
+    pub original: Option<String>,
+    /// What the new model answered
+    pub replayed: ChatResponse,
+}
+
+/// Re-run the user turns of a recorded session against a different model
+///
+/// Each recorded chat request contributes the messages after its last
+/// assistant message; the conversation is rebuilt with the new model's own
+/// answers so later turns see its replies rather than the recorded ones.
+pub async fn rerun_session(
+    cassette: &Cassette,
+    provider: &dyn Provider,
+    model: &str,
+) -> Result<Vec<RerunTurn>, ProviderError> {
+    let mut conversation: Vec<Message> = Vec::new();
+    let mut turns = Vec::new();
+
+    for interaction in &cassette.interactions {
+        let Some(request) = interaction.request.chat_request() else {
+            continue;
+        };
+        let start = if conversation.is_empty() {
+            0
+        } else {
+            request
+                .messages
+                .iter()
+                .rposition(|message| message.role == "assistant")
+                .map_or(0, |i| i + 1)
+        };
+        let prompt = request.messages[start..].to_vec();
+        if prompt.is_empty() {
+            continue;
+        }
+        conversation.extend(prompt.iter().cloned());
+
+        let mut replay = request.clone();
+        replay.model = model.to_string();
+        replay.messages = conversation.clone();
+        replay.stream = false;
+        let replayed = provider.chat(replay).await?;
+
+        conversation.push(Message {
+            role: "assistant".to_string(),
+            content: replayed.content.clone(),
+        });
+        turns.push(RerunTurn {
+            prompt,
+            original: interaction.outcome.content(),
+            replayed,
+        });
+    }
+
+    Ok(turns)
+}
@@ -0,0 +1,217 @@
+//! Task-aware provider routing
+//!
+//! Requests are classified into a [`TaskClass`] so providers can be scored
+//! and selected per kind of work: a provider that answers chat quickly is not
+//! necessarily the best one for code generation or embeddings.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::{Capability, ChatRequest, ModelInfo};
+
+/// Prompt cues that ask for a summary
+const SUMMARIZATION_CUES: &[&str] = &[
+    "summarize",
+    "summarise",
+    "summary",
+    "tl;dr",
+    "tldr",
+    "condense",
+    "key points",
+];
+
+/// Prompt cues that ask for code
+const CODE_CUES: &[&str] = &[
+    "```",
+    "write a function",
+    "write a script",
+    "implement",
+    "refactor",
+    "unit test",
+    "compile",
+    "stack trace",
+    "fix the bug",
+    "write code",
+    "this code",
+    "snippet",
+    "fn ",
+    "def ",
+    "class ",
+];
+
+/// Kind of work a request asks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskClass {
+    /// General conversation and questions
+    Chat,
+    /// Writing or changing code
+    CodeGeneration,
+    /// Condensing existing text
+    Summarization,
+    /// Computing embedding vectors
+    Embeddings,
+}
+
+impl TaskClass {
+    /// All task classes
+    pub const ALL: [TaskClass; 4] = [
+        TaskClass::Chat,
+        TaskClass::CodeGeneration,
+        TaskClass::Summarization,
+        TaskClass::Embeddings,
+    ];
+
+    /// Stable identifier
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskClass::Chat => "chat",
+            TaskClass::CodeGeneration => "code_generation",
+            TaskClass::Summarization => "summarization",
+            TaskClass::Embeddings => "embeddings",
+        }
+    }
+
+    /// Classify a chat request by its latest user message
+    ///
+    /// Summaries win over code cues, so "summarize this code" is a
+    /// summarization task.
+    pub fn classify(request: &ChatRequest) -> Self {
+        let Some(message) = request.messages.iter().rev().find(|m| m.role == "user") else {
+            return TaskClass::Chat;
+        };
+        let text = message.content.to_lowercase();
+
+        if SUMMARIZATION_CUES.iter().any(|cue| text.contains(cue)) {
+            TaskClass::Summarization
+        } else if CODE_CUES.iter().any(|cue| text.contains(cue)) {
+            TaskClass::CodeGeneration
+        } else {
+            TaskClass::Chat
+        }
+    }
+
+    /// Task class an evaluation benchmark measures
+    pub fn from_benchmark(name: &str) -> Option<Self> {
+        match name {
+            "basic_chat" | "reasoning" => Some(TaskClass::Chat),
+            "code_generation" => Some(TaskClass::CodeGeneration),
+            "summarization" => Some(TaskClass::Summarization),
+            "embeddings" => Some(TaskClass::Embeddings),
+            _ => None,
+        }
+    }
+
+    /// Pick the model of a provider best suited to this task
+    ///
+    /// Prefers code-capable models for code generation and chat models
+    /// otherwise. Embedding models are not described by capabilities, so
+    /// embeddings get `None`.
+    pub fn preferred_model<'a>(&self, models: &'a [ModelInfo]) -> Option<&'a ModelInfo> {
+        let preferred = match self {
+            TaskClass::CodeGeneration => Capability::Code,
+            TaskClass::Chat | TaskClass::Summarization => Capability::Chat,
+            TaskClass::Embeddings => return None,
+        };
+        models
+            .iter()
+            .find(|m| m.capabilities.contains(&preferred))
+            .or_else(|| {
+                models
+                    .iter()
+                    .find(|m| m.capabilities.contains(&Capability::Chat))
+            })
+            .or_else(|| models.first())
+    }
+}
+
+impl fmt::Display for TaskClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Where a request was sent and why
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoutingDecision {
+    /// Provider handling the request
+    pub provider_id: String,
+    /// Task class the request was classified as
+    pub task: TaskClass,
+    /// Whether the caller chose the provider instead of the router
+    pub overridden: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Message;
+
+    fn request(content: &str) -> ChatRequest {
+        ChatRequest {
+            model: "test-model".to_string(),
+            messages: vec![
+                Message {
+                    role: "system".to_string(),
+                    content: "You write code.".to_string(),
+                },
+                Message {
+                    role: "user".to_string(),
+                    content: content.to_string(),
+                },
+            ],
+            temperature: None,
+            max_tokens: None,
+            stream: false,
+            top_p: None,
+            stop: None,
+            seed: None,
+            reasoning_effort: None,
+        }
+    }
+
+    #[test]
+    fn test_classify_requests() {
+        assert_eq!(
+            TaskClass::classify(&request("What's the capital of France?")),
+            TaskClass::Chat
+        );
+        assert_eq!(
+            TaskClass::classify(&request("Write a function that parses dates")),
+            TaskClass::CodeGeneration
+        );
+        assert_eq!(
+            TaskClass::classify(&request("Summarize this code:\n```rust\nfn main() {}\n```")),
+            TaskClass::Summarization
+        );
+        assert_eq!(
+            TaskClass::from_benchmark("code_generation"),
+            Some(TaskClass::CodeGeneration)
+        );
+        assert_eq!(TaskClass::from_benchmark("unknown"), None);
+    }
+
+    #[test]
+    fn test_preferred_model() {
+        let model = |id: &str, capabilities: Vec<Capability>| ModelInfo {
+            id: id.to_string(),
+            name: id.to_string(),
+            provider: "test".to_string(),
+            context_window: 8192,
+            max_output_tokens: None,
+            capabilities,
+            pricing: None,
+            is_free: false,
+        };
+        let models = vec![
+            model("chat", vec![Capability::Chat]),
+            model("coder", vec![Capability::Chat, Capability::Code]),
+        ];
+
+        let pick = |task: TaskClass| task.preferred_model(&models).map(|m| m.id.as_str());
+        assert_eq!(pick(TaskClass::CodeGeneration), Some("coder"));
+        assert_eq!(pick(TaskClass::Summarization), Some("chat"));
+        assert_eq!(pick(TaskClass::Embeddings), None);
+    }
+}
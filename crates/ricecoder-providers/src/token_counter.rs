@@ -1,12 +1,50 @@
 //! Token counting utilities for different providers
 //!
 //! This module provides token counting functionality for various AI providers.
-//! For production use with OpenAI, consider using tiktoken-rs for accurate token counting.
+//! OpenAI models are counted with their tiktoken BPE vocabularies, other model
+//! families can be given a tiktoken-format rank file, and anything without a
+//! tokenizer falls back to a character-based estimate.
 
-use std::{collections::HashMap, sync::Mutex};
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use once_cell::sync::Lazy;
+use ricecoder_storage::TokenizerFileConfig;
+use tiktoken_rs::{
+    tokenizer::{get_tokenizer, Tokenizer},
+    CoreBPE,
+};
+use tracing::warn;
 
 use crate::error::ProviderError;
 
+/// Pre-tokenization pattern used by cl100k_base, the default for tokenizer files
+const DEFAULT_PATTERN: &str = r"(?i:'s|'t|'re|'ve|'m|'ll|'d)|[^\r\n\p{L}\p{N}]?\p{L}+|\p{N}{1,3}| ?[^\s\p{L}\p{N}]+[\r\n]*|\s*[\r\n]+|\s+(?!\S)|\s+";
+
+/// Model prefixes that use o200k_base but are newer than tiktoken's model table
+const O200K_PREFIXES: &[&str] = &[
+    "gpt-4o",
+    "gpt-4.1",
+    "gpt-4.5",
+    "gpt-5",
+    "chatgpt-4o",
+    "o1",
+    "o3",
+    "o4",
+];
+
+/// Tokenizer files loaded so far, keyed by path and pattern
+///
+/// Files that failed to load are cached as `None` so they are not re-read on
+/// every count.
+type FileTokenizerCache = HashMap<(PathBuf, Option<String>), Option<Arc<CoreBPE>>>;
+
+static FILE_TOKENIZERS: Lazy<Mutex<FileTokenizerCache>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
 /// Trait for unified token counting across providers
 pub trait TokenCounterTrait: Send + Sync {
     /// Count tokens for content in a specific model
@@ -20,8 +58,10 @@ pub trait TokenCounterTrait: Send + Sync {
 }
 
 /// Token counter for estimating token usage
+#[derive(Debug)]
 pub struct TokenCounter {
     cache: Mutex<HashMap<String, usize>>,
+    tokenizer_files: Vec<TokenizerFileConfig>,
 }
 
 impl TokenCounter {
@@ -29,12 +69,23 @@ impl TokenCounter {
     pub fn new() -> Self {
         Self {
             cache: Mutex::new(HashMap::new()),
+            tokenizer_files: Vec::new(),
         }
     }
 
-    /// Count tokens for OpenAI models using estimation
+    /// Use tokenizer files for model families without a built-in tokenizer
     ///
-    /// This uses a heuristic-based approach:
+    /// Files are matched in order, so earlier entries win when several match
+    /// the same model. Vocabularies are loaded on first use.
+    pub fn with_tokenizer_files(mut self, files: Vec<TokenizerFileConfig>) -> Self {
+        self.tokenizer_files = files;
+        self
+    }
+
+    /// Count tokens for a model
+    ///
+    /// Uses the model's tokenizer when one is available, otherwise falls back
+    /// to a heuristic estimate:
     /// - Average English word is ~4.7 characters
     /// - Average token is ~4 characters
     /// - Special tokens and formatting add overhead
@@ -47,15 +98,16 @@ impl TokenCounter {
             }
         }
 
-        // Estimate tokens based on model and content
-        let estimated = self.estimate_tokens(content, model);
+        let counted = self
+            .count_with_tokenizer(content, model)
+            .unwrap_or_else(|| self.estimate_tokens(content, model));
 
         // Cache the result
         if let Ok(mut cache) = self.cache.lock() {
-            cache.insert(cache_key, estimated);
+            cache.insert(cache_key, counted);
         }
 
-        estimated
+        counted
     }
 
     /// Count tokens for content (unified interface)
@@ -66,6 +118,33 @@ impl TokenCounter {
         Ok(self.count_tokens_openai(content, model))
     }
 
+    /// Whether counts for this model come from a real tokenizer
+    pub fn has_tokenizer(&self, model: &str) -> bool {
+        self.count_with_tokenizer("", model).is_some()
+    }
+
+    /// Count tokens with a configured or built-in tokenizer
+    fn count_with_tokenizer(&self, content: &str, model: &str) -> Option<usize> {
+        for file in &self.tokenizer_files {
+            if !matches_model(&file.models, model) {
+                continue;
+            }
+            if let Some(bpe) = load_tokenizer_file(file) {
+                return Some(bpe.encode_ordinary(content).len());
+            }
+        }
+
+        let bpe = match builtin_tokenizer(model)? {
+            Tokenizer::O200kBase => tiktoken_rs::o200k_base_singleton(),
+            Tokenizer::Cl100kBase => tiktoken_rs::cl100k_base_singleton(),
+            Tokenizer::P50kBase => tiktoken_rs::p50k_base_singleton(),
+            Tokenizer::P50kEdit => tiktoken_rs::p50k_edit_singleton(),
+            Tokenizer::R50kBase | Tokenizer::Gpt2 => tiktoken_rs::r50k_base_singleton(),
+        };
+        let count = bpe.lock().encode_ordinary(content).len();
+        Some(count)
+    }
+
     /// Estimate token count for content
     fn estimate_tokens(&self, content: &str, _model: &str) -> usize {
         if content.is_empty() {
@@ -115,3 +194,92 @@ impl TokenCounterTrait for TokenCounter {
         self.cache.lock().map(|c| c.len()).unwrap_or(0)
     }
 }
+
+/// Strip a provider prefix such as `openai/` or `azure/` from a model id
+fn model_name(model: &str) -> String {
+    model
+        .rsplit('/')
+        .next()
+        .unwrap_or(model)
+        .to_ascii_lowercase()
+}
+
+/// Check a model id, with or without provider prefix, against tokenizer prefixes
+fn matches_model(prefixes: &[String], model: &str) -> bool {
+    let full = model.to_ascii_lowercase();
+    let name = model_name(model);
+    prefixes.iter().any(|prefix| {
+        let prefix = prefix.to_ascii_lowercase();
+        full.starts_with(&prefix) || name.starts_with(&prefix)
+    })
+}
+
+/// Look up the tiktoken vocabulary for an OpenAI model
+fn builtin_tokenizer(model: &str) -> Option<Tokenizer> {
+    let name = model_name(model);
+    if O200K_PREFIXES
+        .iter()
+        .any(|prefix| name == *prefix || name.starts_with(&format!("{}-", prefix)))
+    {
+        return Some(Tokenizer::O200kBase);
+    }
+    get_tokenizer(&name)
+}
+
+/// Load a tokenizer file, reusing a previously loaded vocabulary
+fn load_tokenizer_file(config: &TokenizerFileConfig) -> Option<Arc<CoreBPE>> {
+    let key = (config.path.clone(), config.pattern.clone());
+    let mut cache = FILE_TOKENIZERS.lock().ok()?;
+    if let Some(cached) = cache.get(&key) {
+        return cached.clone();
+    }
+
+    let loaded = match read_tokenizer_file(config) {
+        Ok(bpe) => Some(Arc::new(bpe)),
+        Err(e) => {
+            warn!(
+                "Failed to load tokenizer file {}: {}",
+                config.path.display(),
+                e
+            );
+            None
+        }
+    };
+    cache.insert(key, loaded.clone());
+    loaded
+}
+
+/// Parse a tiktoken rank file: one `base64-token rank` pair per line
+fn read_tokenizer_file(config: &TokenizerFileConfig) -> Result<CoreBPE, ProviderError> {
+    let content = std::fs::read_to_string(&config.path)
+        .map_err(|e| ProviderError::ConfigError(e.to_string()))?;
+
+    let mut ranks = Vec::new();
+    let mut single_bytes = [false; 256];
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let invalid = || ProviderError::ConfigError(format!("Invalid rank on line {}", index + 1));
+        let (token, rank) = line.split_once(char::is_whitespace).ok_or_else(invalid)?;
+        let token = STANDARD.decode(token).map_err(|_| invalid())?;
+        let rank = rank.trim().parse::<usize>().map_err(|_| invalid())?;
+        if let [byte] = token[..] {
+            single_bytes[byte as usize] = true;
+        }
+        ranks.push((token, rank));
+    }
+
+    // BPE falls back to single bytes, so every byte needs a rank
+    if let Some(byte) = single_bytes.iter().position(|present| !present) {
+        return Err(ProviderError::ConfigError(format!(
+            "No rank for byte 0x{:02x}",
+            byte
+        )));
+    }
+
+    let pattern = config.pattern.as_deref().unwrap_or(DEFAULT_PATTERN);
+    CoreBPE::new(ranks.into_iter().collect(), Default::default(), pattern)
+        .map_err(|e| ProviderError::ConfigError(e.to_string()))
+}
@@ -4,10 +4,12 @@
 //! - Claude: toolCallId sanitization
 //! - Mistral: toolCallId normalization
 //! - Gemini: schema sanitization
+//! - All providers: request parameter translation
 
-use crate::models::Message;
+use crate::models::{ChatRequest, Message};
 use regex::Regex;
-use serde_json::Value;
+use serde_json::{json, Map, Value};
+use tracing::debug;
 
 /// Transform messages for Claude provider
 pub fn transform_for_claude(messages: Vec<Message>) -> Vec<Message> {
@@ -146,6 +148,128 @@ fn transform_schema_recursive(mut value: Value) -> Value {
     }
 }
 
+/// Request parameter conventions of a provider API
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamDialect {
+    /// OpenAI and compatible APIs (Azure, Zen, Together)
+    OpenAi,
+    /// Qwen (DashScope) OpenAI-compatible API with thinking controls
+    Qwen,
+    /// Anthropic Messages API
+    Anthropic,
+    /// Google Gemini `generationConfig`
+    Gemini,
+    /// Ollama chat API; sampling parameters go in `options`
+    Ollama,
+    /// Cohere chat API
+    Cohere,
+    /// Replicate prediction `input`
+    Replicate,
+}
+
+/// Translate the generation parameters of a request for a provider
+///
+/// Covers top_p, stop sequences, seed and reasoning effort; temperature and
+/// max tokens stay on each provider's request type, except for Ollama which
+/// takes all sampling parameters in `options`. The result is flattened into
+/// the provider's request body. Parameters the provider has no equivalent for
+/// are dropped.
+pub fn params_for(dialect: ParamDialect, request: &ChatRequest) -> Map<String, Value> {
+    let mut params = Map::new();
+    let mut dropped = Vec::new();
+
+    match dialect {
+        ParamDialect::OpenAi => {
+            insert(&mut params, "top_p", request.top_p);
+            insert(&mut params, "stop", request.stop.clone());
+            insert(&mut params, "seed", request.seed);
+            insert(
+                &mut params,
+                "reasoning_effort",
+                request.reasoning_effort.map(|effort| effort.as_str()),
+            );
+        }
+        ParamDialect::Qwen => {
+            insert(&mut params, "top_p", request.top_p);
+            insert(&mut params, "stop", request.stop.clone());
+            insert(&mut params, "seed", request.seed);
+            if let Some(effort) = request.reasoning_effort {
+                params.insert("enable_thinking".to_string(), Value::Bool(true));
+                params.insert("thinking_budget".to_string(), json!(effort.budget_tokens()));
+            }
+        }
+        ParamDialect::Anthropic => {
+            insert(&mut params, "top_p", request.top_p);
+            insert(&mut params, "stop_sequences", request.stop.clone());
+            insert(
+                &mut params,
+                "thinking",
+                request.reasoning_effort.map(|effort| {
+                    json!({ "type": "enabled", "budget_tokens": effort.budget_tokens() })
+                }),
+            );
+            if request.seed.is_some() {
+                dropped.push("seed");
+            }
+        }
+        ParamDialect::Gemini => {
+            insert(&mut params, "top_p", request.top_p);
+            insert(&mut params, "stop_sequences", request.stop.clone());
+            insert(&mut params, "seed", request.seed);
+            insert(
+                &mut params,
+                "thinking_config",
+                request
+                    .reasoning_effort
+                    .map(|effort| json!({ "thinking_budget": effort.budget_tokens() })),
+            );
+        }
+        ParamDialect::Ollama => {
+            let mut options = Map::new();
+            insert(&mut options, "temperature", request.temperature);
+            insert(&mut options, "top_p", request.top_p);
+            insert(&mut options, "num_predict", request.max_tokens);
+            insert(&mut options, "stop", request.stop.clone());
+            insert(&mut options, "seed", request.seed);
+            if !options.is_empty() {
+                params.insert("options".to_string(), Value::Object(options));
+            }
+            insert(&mut params, "think", request.reasoning_effort.map(|_| true));
+        }
+        ParamDialect::Cohere => {
+            insert(&mut params, "p", request.top_p);
+            insert(&mut params, "stop_sequences", request.stop.clone());
+            insert(&mut params, "seed", request.seed);
+            if request.reasoning_effort.is_some() {
+                dropped.push("reasoning_effort");
+            }
+        }
+        ParamDialect::Replicate => {
+            insert(&mut params, "top_p", request.top_p);
+            insert(
+                &mut params,
+                "stop_sequences",
+                request.stop.as_ref().map(|stop| stop.join(",")),
+            );
+            insert(&mut params, "seed", request.seed);
+            if request.reasoning_effort.is_some() {
+                dropped.push("reasoning_effort");
+            }
+        }
+    }
+
+    if !dropped.is_empty() {
+        debug!("{:?} does not support {}; dropped", dialect, dropped.join(", "));
+    }
+    params
+}
+
+fn insert<T: serde::Serialize>(params: &mut Map<String, Value>, key: &str, value: Option<T>) {
+    if let Some(value) = value.and_then(|v| serde_json::to_value(v).ok()) {
+        params.insert(key.to_string(), value);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -192,4 +316,47 @@ mod tests {
         let transformed = transform_schema_for_gemini(schema);
         assert_eq!(transformed["type"], "string");
     }
+
+    #[test]
+    fn test_params_for_dialects() {
+        let request = ChatRequest {
+            model: "model".to_string(),
+            messages: vec![],
+            temperature: Some(0.2),
+            max_tokens: Some(256),
+            stream: false,
+            top_p: Some(0.9),
+            stop: Some(vec!["END".to_string()]),
+            seed: Some(7),
+            reasoning_effort: Some(crate::models::ReasoningEffort::High),
+        };
+
+        let openai = Value::Object(params_for(ParamDialect::OpenAi, &request));
+        assert_eq!(
+            openai,
+            json!({ "top_p": 0.9f32, "stop": ["END"], "seed": 7, "reasoning_effort": "high" })
+        );
+
+        let anthropic = Value::Object(params_for(ParamDialect::Anthropic, &request));
+        assert_eq!(anthropic["stop_sequences"], json!(["END"]));
+        assert_eq!(anthropic["thinking"]["budget_tokens"], 16384);
+        assert!(anthropic.get("seed").is_none());
+
+        let ollama = Value::Object(params_for(ParamDialect::Ollama, &request));
+        assert_eq!(ollama["options"]["num_predict"], 256);
+        assert_eq!(ollama["options"]["seed"], 7);
+        assert_eq!(ollama["think"], true);
+
+        let plain = ChatRequest {
+            top_p: None,
+            stop: None,
+            seed: None,
+            reasoning_effort: None,
+            temperature: None,
+            max_tokens: None,
+            ..request
+        };
+        assert!(params_for(ParamDialect::Gemini, &plain).is_empty());
+        assert!(params_for(ParamDialect::Ollama, &plain).is_empty());
+    }
 }
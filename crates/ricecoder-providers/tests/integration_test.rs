@@ -3,12 +3,23 @@
 //! Tests provider manager, registry, and cross-module functionality.
 //! Per R2: Tests should be in tests/ directory.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 
 use ricecoder_providers::{
-    CircuitBreaker, CircuitBreakerConfig, CircuitState, ProviderManager, ProviderRegistry,
+    providers::local::{apply_llama_cpp_props, parse_model_listing},
+    BindingTarget, Capability, ChatRequest, CircuitBreaker, CircuitBreakerConfig, CircuitState,
+    LocalDiscovery, LocalModelProvider, LocalRuntime, Message, ModelInfo, ModelParams,
+    PromptAuditLog, PromptLibrary, PromptUsage, Provider, ProviderError, ProviderManager,
+    ProviderRegistry, ReasoningEffort, TaskClass, TokenCounter,
 };
+use ricecoder_providers::{
+    rerun_session, Cassette, ChatResponse, ConnectionState, FinishReason, Interaction, RecordMode,
+    RecordedOutcome, RecordedRequest, RecordingProvider, ReplayMode, ReplayProvider, TokenUsage,
+};
+use ricecoder_storage::{PromptBinding, PromptVariant, TokenizerFileConfig, VersionedPrompt};
+use serde_json::json;
 
 /// Test provider manager creation and basic functionality
 #[test]
@@ -111,3 +122,715 @@ fn test_circuit_breaker_force_operations() {
     assert_eq!(cb.state(), CircuitState::Closed);
     assert!(cb.can_execute());
 }
+
+fn model(capabilities: Vec<Capability>) -> ModelInfo {
+    ModelInfo {
+        id: "test-model".to_string(),
+        name: "Test Model".to_string(),
+        provider: "test".to_string(),
+        context_window: 8192,
+        max_output_tokens: None,
+        capabilities,
+        pricing: None,
+        is_free: false,
+    }
+}
+
+fn request() -> ChatRequest {
+    ChatRequest {
+        model: "test-model".to_string(),
+        messages: vec![Message {
+            role: "user".to_string(),
+            content: "hello".to_string(),
+        }],
+        temperature: None,
+        max_tokens: None,
+        stream: false,
+        top_p: None,
+        stop: None,
+        seed: None,
+        reasoning_effort: None,
+    }
+}
+
+/// Test that per-mode defaults only fill parameters the request leaves unset
+#[test]
+fn test_request_params_apply_defaults() {
+    let mut request = ChatRequest {
+        temperature: Some(0.1),
+        ..request()
+    };
+    request.apply_defaults(&ModelParams {
+        temperature: Some(0.7),
+        top_p: Some(0.95),
+        max_tokens: Some(2048),
+        stop: Some(vec!["</answer>".to_string()]),
+        seed: Some(42),
+        reasoning_effort: None,
+    });
+
+    assert_eq!(request.temperature, Some(0.1));
+    assert_eq!(request.top_p, Some(0.95));
+    assert_eq!(request.max_tokens, Some(2048));
+    assert_eq!(request.stop, Some(vec!["</answer>".to_string()]));
+    assert_eq!(request.seed, Some(42));
+    assert_eq!(request.reasoning_effort, None);
+}
+
+/// Test request parameter validation against model capabilities
+#[test]
+fn test_request_params_validation() {
+    let chat_model = model(vec![Capability::Chat]);
+    let reasoning_model = model(vec![Capability::Chat, Capability::Reasoning]);
+
+    assert!(request().validate_params(&chat_model).is_ok());
+
+    let invalid = [
+        ChatRequest {
+            temperature: Some(2.5),
+            ..request()
+        },
+        ChatRequest {
+            top_p: Some(0.0),
+            ..request()
+        },
+        ChatRequest {
+            max_tokens: Some(10_000),
+            ..request()
+        },
+        ChatRequest {
+            stop: Some(vec![String::new()]),
+            ..request()
+        },
+        ChatRequest {
+            reasoning_effort: Some(ReasoningEffort::High),
+            ..request()
+        },
+    ];
+    for request in &invalid {
+        assert!(matches!(
+            request.validate_params(&chat_model),
+            Err(ProviderError::InvalidParameter(_))
+        ));
+    }
+
+    let reasoning = ChatRequest {
+        reasoning_effort: Some(ReasoningEffort::High),
+        seed: Some(1),
+        ..request()
+    };
+    assert!(reasoning.validate_params(&reasoning_model).is_ok());
+
+    // max_tokens is bounded by the output limit, not the context window
+    let limited = ModelInfo {
+        max_output_tokens: Some(4096),
+        ..model(vec![Capability::Chat])
+    };
+    let within_context = ChatRequest {
+        max_tokens: Some(6000),
+        ..request()
+    };
+    assert!(within_context.validate_params(&chat_model).is_ok());
+    assert!(matches!(
+        within_context.validate_params(&limited),
+        Err(ProviderError::InvalidParameter(_))
+    ));
+}
+
+/// Test capability probing from local runtime model listings
+#[test]
+fn test_local_runtime_model_listings() {
+    // vLLM reports the served context length
+    let vllm = parse_model_listing(
+        LocalRuntime::Vllm,
+        &json!({"object": "list", "data": [{"id": "Qwen/Qwen3-8B", "max_model_len": 32768}]}),
+    );
+    assert_eq!(vllm[0].context_window, 32768);
+    assert_eq!(vllm[0].provider, "vllm");
+    assert!(vllm[0].is_free);
+
+    // LM Studio reports type, context and tool use; embeddings are skipped
+    let lm_studio = parse_model_listing(
+        LocalRuntime::LmStudio,
+        &json!({"data": [
+            {"id": "qwen2.5-vl-7b", "type": "vlm", "max_context_length": 128000,
+             "capabilities": ["tool_use"]},
+            {"id": "nomic-embed-text", "type": "embeddings", "max_context_length": 2048}
+        ]}),
+    );
+    assert_eq!(lm_studio.len(), 1);
+    assert_eq!(lm_studio[0].context_window, 128000);
+    assert!(lm_studio[0].capabilities.contains(&Capability::Vision));
+    assert!(lm_studio[0]
+        .capabilities
+        .contains(&Capability::FunctionCalling));
+
+    // llama.cpp: /props overrides the training context and enables tools
+    let mut llama = parse_model_listing(
+        LocalRuntime::LlamaCpp,
+        &json!({"data": [{"id": "model.gguf", "meta": {"n_ctx_train": 131072}}]}),
+    );
+    assert_eq!(llama[0].context_window, 131072);
+    assert!(!llama[0].capabilities.contains(&Capability::FunctionCalling));
+    apply_llama_cpp_props(
+        &mut llama,
+        &json!({
+            "default_generation_settings": {"n_ctx": 8192},
+            "chat_template": "{% if tools %}{{ tools | tojson }}{% endif %}"
+        }),
+    );
+    assert_eq!(llama[0].context_window, 8192);
+    assert!(llama[0].capabilities.contains(&Capability::FunctionCalling));
+
+    // Ollama tags have no context information
+    let ollama = parse_model_listing(
+        LocalRuntime::Ollama,
+        &json!({"models": [{"name": "llama3.2:latest"}]}),
+    );
+    assert_eq!(ollama[0].id, "llama3.2:latest");
+    assert_eq!(ollama[0].context_window, 4096);
+}
+
+/// Test discovery and chat against a mocked llama.cpp server
+#[tokio::test]
+async fn test_local_discovery_and_chat() {
+    let mut server = mockito::Server::new_async().await;
+    let _props = server
+        .mock("GET", "/props")
+        .with_body(r#"{"default_generation_settings": {"n_ctx": 16384}, "chat_template": ""}"#)
+        .expect_at_least(1)
+        .create_async()
+        .await;
+    let _models = server
+        .mock("GET", "/v1/models")
+        .with_body(r#"{"data": [{"id": "coder.gguf"}]}"#)
+        .create_async()
+        .await;
+    let _chat = server
+        .mock("POST", "/v1/chat/completions")
+        .with_body(
+            r#"{"choices": [{"message": {"role": "assistant", "content": "hi"}, "finish_reason": "stop"}]}"#,
+        )
+        .create_async()
+        .await;
+
+    let port = server.socket_address().port();
+    let found = LocalDiscovery::new()
+        .with_host("127.0.0.1")
+        .with_candidates(vec![
+            (LocalRuntime::LlamaCpp, port),
+            (LocalRuntime::Vllm, port),
+        ])
+        .with_timeout(Duration::from_secs(2))
+        .discover()
+        .await;
+
+    // Only the runtime whose signature endpoint answers is reported
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].runtime, LocalRuntime::LlamaCpp);
+    assert_eq!(found[0].models[0].id, "coder.gguf");
+    assert_eq!(found[0].models[0].context_window, 16384);
+
+    let provider = LocalModelProvider::from_discovered(found[0].clone()).unwrap();
+    assert_eq!(provider.id(), "llamacpp");
+    let response = provider
+        .chat(ChatRequest {
+            model: "coder.gguf".to_string(),
+            ..request()
+        })
+        .await
+        .unwrap();
+    assert_eq!(response.content, "hi");
+    assert_eq!(response.usage.total_tokens, 0);
+}
+
+fn prompt(version: u32, body: &str) -> VersionedPrompt {
+    VersionedPrompt {
+        id: "coder".to_string(),
+        version,
+        description: String::new(),
+        variables: [("tone".to_string(), Some("concise".to_string()))]
+            .into_iter()
+            .collect(),
+        body: body.to_string(),
+    }
+}
+
+/// Test rendering versioned prompts and serving A/B variants
+#[test]
+fn test_prompt_library_versions_and_variants() {
+    let mut library = PromptLibrary::new();
+    library.add_template(prompt(1, "You write {{tone}} code."));
+    library.add_template(prompt(2, "You write {{ tone }} code for {{project}}."));
+    assert_eq!(library.versions("coder"), vec![1, 2]);
+
+    // Latest version, caller variables override defaults, missing ones fail
+    let vars: HashMap<String, String> = [("project".to_string(), "ricecoder".to_string())]
+        .into_iter()
+        .collect();
+    let latest = library.render("coder", None, &vars).unwrap();
+    assert_eq!(latest.version, 2);
+    assert_eq!(latest.text, "You write concise code for ricecoder.");
+    assert!(matches!(
+        library.render("coder", Some(2), &HashMap::new()),
+        Err(ProviderError::InvalidParameter(_))
+    ));
+    let v1 = library.render("coder", Some(1), &HashMap::new()).unwrap();
+    assert_ne!(v1.content_hash, latest.content_hash);
+
+    // Pinned binding
+    let ask = BindingTarget::Mode("ask".to_string());
+    library.bind(
+        ask.clone(),
+        PromptBinding {
+            prompt: "coder".to_string(),
+            version: Some(1),
+            variants: Vec::new(),
+        },
+    );
+    assert_eq!(library.resolve(&ask, "s1", &vars).unwrap().version, 1);
+
+    // A/B variants: sticky per subject, both served across subjects
+    let code = BindingTarget::Mode("code".to_string());
+    library.bind(
+        code.clone(),
+        PromptBinding {
+            prompt: "coder".to_string(),
+            version: None,
+            variants: vec![
+                PromptVariant {
+                    name: "control".to_string(),
+                    version: 1,
+                    weight: 1,
+                },
+                PromptVariant {
+                    name: "project".to_string(),
+                    version: 2,
+                    weight: 1,
+                },
+            ],
+        },
+    );
+    assert!(library.validate().is_ok());
+    let first = library.resolve(&code, "session-a", &vars).unwrap();
+    assert_eq!(first, library.resolve(&code, "session-a", &vars).unwrap());
+    let served: Vec<_> = (0..32)
+        .map(|i| {
+            library
+                .resolve(&code, &format!("session-{}", i), &vars)
+                .unwrap()
+                .variant
+                .unwrap()
+        })
+        .collect();
+    assert!(served.iter().any(|v| v == "control"));
+    assert!(served.iter().any(|v| v == "project"));
+
+    // Bindings to missing versions are rejected
+    library.bind(
+        BindingTarget::Agent("explore".to_string()),
+        PromptBinding {
+            prompt: "coder".to_string(),
+            version: Some(9),
+            variants: Vec::new(),
+        },
+    );
+    assert!(matches!(
+        library.validate(),
+        Err(ProviderError::ConfigError(_))
+    ));
+}
+
+/// Test the audit of which prompt version produced each response
+#[test]
+fn test_prompt_audit_log() {
+    let mut library = PromptLibrary::new();
+    library.add_template(prompt(3, "You write {{tone}} code."));
+    let resolved = library.render("coder", None, &HashMap::new()).unwrap();
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("prompts.jsonl");
+    let log = PromptAuditLog::with_file(path.clone());
+    log.record(
+        PromptUsage::new(&resolved, "gpt-4o")
+            .with_target(BindingTarget::Mode("code".to_string()))
+            .with_session("s1"),
+    )
+    .unwrap();
+    log.record(PromptUsage::new(&resolved, "gpt-4o").with_session("s2"))
+        .unwrap();
+
+    assert_eq!(log.for_session("s1").len(), 1);
+    assert_eq!(log.version_counts("coder").get(&3), Some(&2));
+
+    let lines: Vec<PromptUsage> = std::fs::read_to_string(&path)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines, log.entries());
+    assert_eq!(lines[0].content_hash, resolved.content_hash);
+}
+
+/// Test tiktoken-backed counting for OpenAI models and the heuristic fallback
+#[test]
+fn test_token_counter_uses_tiktoken_for_openai_models() {
+    let counter = TokenCounter::new();
+
+    assert!(counter.has_tokenizer("gpt-4"));
+    assert!(counter.has_tokenizer("openai/gpt-4o-mini"));
+    assert!(counter.has_tokenizer("o3-mini"));
+    assert!(!counter.has_tokenizer("claude-3-5-sonnet"));
+
+    assert_eq!(counter.count_tokens_openai("hello world", "gpt-4"), 2);
+    assert_eq!(counter.count_tokens_openai("hello world", "gpt-4o"), 2);
+    assert_eq!(counter.count_tokens_openai("", "gpt-4"), 0);
+
+    // No tokenizer: roughly one token per four bytes
+    assert_eq!(
+        counter.count_tokens_openai("hello world", "claude-3-5-sonnet"),
+        3
+    );
+}
+
+/// Test counting with a configured tiktoken-format rank file
+#[test]
+fn test_token_counter_with_tokenizer_file() {
+    use base64::{engine::general_purpose::STANDARD, Engine};
+
+    let dir = tempfile::tempdir().unwrap();
+    let mut ranks: Vec<Vec<u8>> = (0..=u8::MAX).map(|byte| vec![byte]).collect();
+    ranks.extend([b"he".to_vec(), b"ll".to_vec(), b"llo".to_vec()]);
+    let file: String = ranks
+        .iter()
+        .enumerate()
+        .map(|(rank, token)| format!("{} {}\n", STANDARD.encode(token), rank))
+        .collect();
+    let path = dir.path().join("llama.tiktoken");
+    std::fs::write(&path, file).unwrap();
+
+    let broken = dir.path().join("broken.tiktoken");
+    std::fs::write(&broken, "aGU= 0\n").unwrap();
+
+    let counter = TokenCounter::new().with_tokenizer_files(vec![
+        TokenizerFileConfig {
+            models: vec!["mistral".to_string()],
+            path: broken,
+            pattern: None,
+        },
+        TokenizerFileConfig {
+            models: vec!["Llama-3".to_string()],
+            path,
+            pattern: None,
+        },
+    ]);
+
+    assert!(counter.has_tokenizer("meta-llama/llama-3.1-8b"));
+    // "hello" merges to he|llo, " world" stays as single bytes
+    assert_eq!(
+        counter.count_tokens_openai("hello world", "llama-3.1-8b"),
+        8
+    );
+
+    // Unusable files fall back to the heuristic
+    assert!(!counter.has_tokenizer("mistral-large"));
+    assert_eq!(
+        counter.count_tokens_openai("hello world", "mistral-large"),
+        3
+    );
+}
+
+fn user(content: &str) -> Message {
+    Message {
+        role: "user".to_string(),
+        content: content.to_string(),
+    }
+}
+
+fn chunk(content: &str) -> ChatResponse {
+    ChatResponse {
+        content: content.to_string(),
+        model: "test-model".to_string(),
+        usage: TokenUsage {
+            prompt_tokens: 0,
+            completion_tokens: 0,
+            total_tokens: 0,
+        },
+        finish_reason: FinishReason::Stop,
+    }
+}
+
+/// Test recording live traffic with redaction and replaying it from the cassette
+#[tokio::test]
+async fn test_record_and_replay_provider_traffic() {
+    let mut server = mockito::Server::new_async().await;
+    let _chat = server
+        .mock("POST", "/v1/chat/completions")
+        .with_body(
+            r#"{"choices": [{"message": {"role": "assistant", "content": "hi"}, "finish_reason": "stop"}]}"#,
+        )
+        .expect(1)
+        .create_async()
+        .await;
+
+    let dir = tempfile::tempdir().unwrap();
+    let live = LocalModelProvider::new(LocalRuntime::LlamaCpp, server.url()).unwrap();
+    let recorder = RecordMode::Record(dir.path().to_path_buf())
+        .wrap(Arc::new(live))
+        .unwrap();
+
+    let secret = ChatRequest {
+        messages: vec![user("my key is sk-abcdefghijklmnopqrstuvwxyz0123")],
+        ..request()
+    };
+    assert_eq!(recorder.chat(secret.clone()).await.unwrap().content, "hi");
+
+    let path = Cassette::path_for(dir.path(), "llamacpp");
+    let saved = std::fs::read_to_string(&path).unwrap();
+    assert!(!saved.contains("sk-abcdefghijklmnopqrstuvwxyz0123"));
+    assert!(saved.contains("[REDACTED_OPENAI_KEY]"));
+
+    let replay = ReplayProvider::from_file(&path).unwrap();
+    assert_eq!(replay.id(), "llamacpp");
+    assert_eq!(replay.remaining(), 1);
+    assert!(replay.chat(request()).await.is_err());
+    assert_eq!(replay.chat(secret.clone()).await.unwrap().content, "hi");
+    assert_eq!(replay.remaining(), 0);
+    assert!(replay.chat(secret).await.is_err());
+}
+
+/// Test recorded streams and errors replay deterministically
+#[tokio::test]
+async fn test_replay_streams_and_errors() {
+    use futures::StreamExt;
+
+    let mut cassette = Cassette::new("mock", "Mock");
+    cassette.interactions.push(Interaction::new(
+        &RecordedRequest::ChatStream(request()),
+        &RecordedOutcome::Stream {
+            chunks: vec![chunk("Hel"), chunk("lo")],
+        },
+        5,
+    ));
+    cassette.interactions.push(Interaction::new(
+        &RecordedRequest::Chat(request()),
+        &RecordedOutcome::Error {
+            message: "Rate limited, retry after 3 seconds".to_string(),
+        },
+        1,
+    ));
+
+    let replay = ReplayProvider::new(cassette.clone());
+    let chunks: Vec<String> = replay
+        .chat_stream(request())
+        .await
+        .unwrap()
+        .map(|chunk| chunk.unwrap().content)
+        .collect()
+        .await;
+    assert_eq!(chunks, vec!["Hel", "lo"]);
+    let error = replay.chat(request()).await.unwrap_err();
+    assert!(error.to_string().contains("Rate limited"));
+
+    // Sequential replay ignores the request and serves interactions in order
+    let sequential = ReplayProvider::new(cassette).with_mode(ReplayMode::Sequential);
+    let merged = sequential
+        .chat(ChatRequest {
+            model: "other".to_string(),
+            ..request()
+        })
+        .await
+        .unwrap();
+    assert_eq!(merged.content, "Hello");
+
+    // Recording a stream stores its chunks once the stream is consumed
+    let recorder =
+        RecordingProvider::new(Arc::new(ReplayProvider::new(sequential.cassette().clone())));
+    let _: Vec<_> = recorder
+        .chat_stream(request())
+        .await
+        .unwrap()
+        .collect()
+        .await;
+    let recorded = recorder.cassette();
+    assert_eq!(recorded.interactions.len(), 1);
+    assert_eq!(
+        recorded.interactions[0].outcome.content().as_deref(),
+        Some("Hello")
+    );
+}
+
+/// Test re-running recorded user turns against a different model
+#[tokio::test]
+async fn test_rerun_session_against_other_model() {
+    let turn = |messages: Vec<Message>, answer: &str| {
+        Interaction::new(
+            &RecordedRequest::Chat(ChatRequest {
+                messages,
+                ..request()
+            }),
+            &RecordedOutcome::Response(chunk(answer)),
+            1,
+        )
+    };
+    let mut recorded = Cassette::new("mock", "Mock");
+    recorded
+        .interactions
+        .push(turn(vec![user("add a test")], "old one"));
+    recorded.interactions.push(turn(
+        vec![
+            user("add a test"),
+            Message {
+                role: "assistant".to_string(),
+                content: "old one".to_string(),
+            },
+            user("now run it"),
+        ],
+        "old two",
+    ));
+
+    // The new model's cassette expects its own first answer in the history
+    let mut candidate = Cassette::new("candidate", "Candidate");
+    let first = ChatRequest {
+        model: "new-model".to_string(),
+        messages: vec![user("add a test")],
+        ..request()
+    };
+    let second = ChatRequest {
+        messages: vec![
+            user("add a test"),
+            Message {
+                role: "assistant".to_string(),
+                content: "new one".to_string(),
+            },
+            user("now run it"),
+        ],
+        ..first.clone()
+    };
+    candidate.interactions.push(Interaction::new(
+        &RecordedRequest::Chat(first),
+        &RecordedOutcome::Response(chunk("new one")),
+        1,
+    ));
+    candidate.interactions.push(Interaction::new(
+        &RecordedRequest::Chat(second),
+        &RecordedOutcome::Response(chunk("new two")),
+        1,
+    ));
+
+    let provider = ReplayProvider::new(candidate);
+    let turns = rerun_session(&recorded, &provider, "new-model")
+        .await
+        .unwrap();
+
+    assert_eq!(turns.len(), 2);
+    assert_eq!(turns[1].prompt.len(), 1);
+    assert_eq!(turns[1].prompt[0].content, "now run it");
+    assert_eq!(turns[1].original.as_deref(), Some("old two"));
+    assert_eq!(turns[1].replayed.content, "new two");
+}
+
+fn replay_provider(id: &str, models: Vec<ModelInfo>, answers: &[&str]) -> Arc<dyn Provider> {
+    let mut cassette = Cassette::new(id, id);
+    cassette.models = models;
+    for answer in answers {
+        cassette.interactions.push(Interaction::new(
+            &RecordedRequest::Chat(request()),
+            &RecordedOutcome::Response(chunk(answer)),
+            1,
+        ));
+    }
+    Arc::new(ReplayProvider::new(cassette).with_mode(ReplayMode::Sequential))
+}
+
+fn model_info(id: &str, provider: &str, capabilities: Vec<Capability>) -> ModelInfo {
+    ModelInfo {
+        id: id.to_string(),
+        name: id.to_string(),
+        provider: provider.to_string(),
+        context_window: 8192,
+        max_output_tokens: None,
+        capabilities,
+        pricing: None,
+        is_free: false,
+    }
+}
+
+/// Test routing requests to the best provider for their task
+#[tokio::test]
+async fn test_task_aware_routing() {
+    let mut registry = ProviderRegistry::new();
+    registry
+        .register(replay_provider(
+            "fast",
+            vec![model_info("fast-model", "fast", vec![Capability::Chat])],
+            &["chat answer", "overridden answer"],
+        ))
+        .unwrap();
+
+    // The coder only answers requests for its own code model
+    let mut coder = Cassette::new("coder", "Coder");
+    coder.models = vec![
+        model_info("coder-chat", "coder", vec![Capability::Chat]),
+        model_info(
+            "coder-code",
+            "coder",
+            vec![Capability::Chat, Capability::Code],
+        ),
+    ];
+    let code_request = ChatRequest {
+        messages: vec![user("Write a function that adds two numbers")],
+        ..request()
+    };
+    coder.interactions.push(Interaction::new(
+        &RecordedRequest::Chat(ChatRequest {
+            model: "coder-code".to_string(),
+            ..code_request.clone()
+        }),
+        &RecordedOutcome::Response(chunk("fn add")),
+        1,
+    ));
+    registry
+        .register(Arc::new(ReplayProvider::new(coder)))
+        .unwrap();
+
+    let mut manager = ProviderManager::new(registry, "fast".to_string())
+        .with_retry_count(0)
+        .with_task_routing(true);
+    manager.update_provider_state("fast", ConnectionState::Connected, None);
+    manager.update_provider_state("coder", ConnectionState::Connected, None);
+
+    let curator = manager.curator_mut();
+    curator.record_task_result("fast", TaskClass::Chat, 0.9, 300.0);
+    curator.record_task_result("fast", TaskClass::CodeGeneration, 0.3, 300.0);
+    curator.record_task_result("coder", TaskClass::Chat, 0.6, 2000.0);
+    curator.record_task_result("coder", TaskClass::CodeGeneration, 1.0, 1500.0);
+
+    let decision = manager.route_task(TaskClass::CodeGeneration, None).unwrap();
+    assert_eq!(decision.provider_id, "coder");
+    assert!(!decision.overridden);
+    // Nobody has served embeddings yet, so they go to the default provider
+    assert_eq!(
+        manager
+            .route_task(TaskClass::Embeddings, None)
+            .unwrap()
+            .provider_id,
+        "fast"
+    );
+
+    // The code request is rewritten to the coder's code model
+    let response = manager.chat(code_request.clone()).await.unwrap();
+    assert_eq!(response.content, "fn add");
+    let response = manager.chat(request()).await.unwrap();
+    assert_eq!(response.content, "chat answer");
+
+    // Overriding skips routing
+    let response = manager
+        .chat_routed(code_request, Some("fast"))
+        .await
+        .unwrap();
+    assert_eq!(response.content, "overridden answer");
+    assert!(manager
+        .route_task(TaskClass::Chat, Some("missing"))
+        .is_err());
+}
@@ -0,0 +1,1379 @@
+//! Architecture extraction and module dependency mapping
+//!
+//! Builds a package and module dependency graph from manifests (Cargo.toml,
+//! package.json) and source imports (Rust `use`, Python `import`, TypeScript
+//! and JavaScript `import`/`require`), checks it against declared
+//! [`ArchitectureRules`], and exports Graphviz or Mermaid diagrams.
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt::Write as _,
+    fs,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info};
+
+use crate::error::ResearchError;
+
+/// Rules file looked up under the project root when no rules are given
+pub const ARCHITECTURE_RULES_FILE: &str = ".ricecoder/architecture.yaml";
+
+/// Directories never scanned for packages or sources
+const SKIPPED_DIRS: &[&str] = &[
+    "target",
+    "node_modules",
+    ".git",
+    "dist",
+    "build",
+    "__pycache__",
+];
+
+// ============================================================================
+// Rules
+// ============================================================================
+
+/// An architectural layer and the modules that belong to it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LayerRule {
+    /// Layer name
+    pub name: String,
+    /// Glob patterns matched against module and package names
+    /// (`*` stays within one path segment, `**` crosses segments)
+    pub modules: Vec<String>,
+}
+
+/// A dependency between two layers that is never allowed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ForbiddenDependency {
+    /// Depending layer
+    pub from: String,
+    /// Layer it must not depend on
+    pub to: String,
+}
+
+/// Declared architecture the dependency graph is checked against
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchitectureRules {
+    /// Layers from the top (e.g. interface) to the bottom (e.g. domain);
+    /// a layer may only depend on itself and the layers below it
+    #[serde(default)]
+    pub layers: Vec<LayerRule>,
+    /// Only allow dependencies on the same layer or the one directly below
+    #[serde(default)]
+    pub strict: bool,
+    /// Layer pairs that may never depend on each other, regardless of order
+    #[serde(default)]
+    pub forbidden: Vec<ForbiddenDependency>,
+    /// Report imports between two different service packages
+    #[serde(default)]
+    pub isolate_services: bool,
+    /// Report module dependency cycles
+    #[serde(default)]
+    pub forbid_cycles: bool,
+}
+
+impl ArchitectureRules {
+    /// Parse rules from YAML
+    pub fn from_yaml(yaml: &str) -> Result<Self, ResearchError> {
+        let rules: ArchitectureRules = serde_yaml::from_str(yaml)?;
+        rules.validate()?;
+        Ok(rules)
+    }
+
+    /// Load rules from [`ARCHITECTURE_RULES_FILE`] under `root`, if present
+    pub fn load(root: &Path) -> Result<Option<Self>, ResearchError> {
+        let path = root.join(ARCHITECTURE_RULES_FILE);
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let yaml = fs::read_to_string(&path).map_err(|e| ResearchError::IoError {
+            reason: format!("Failed to read {}: {}", path.display(), e),
+        })?;
+        Self::from_yaml(&yaml).map(Some)
+    }
+
+    /// Check that layer names are unique and referenced layers exist
+    pub fn validate(&self) -> Result<(), ResearchError> {
+        let mut names = BTreeSet::new();
+        for layer in &self.layers {
+            if !names.insert(layer.name.as_str()) {
+                return Err(ResearchError::InvalidConfiguration {
+                    reason: format!("Layer '{}' is declared twice", layer.name),
+                    expected: "unique layer names".to_string(),
+                });
+            }
+        }
+        for forbidden in &self.forbidden {
+            for name in [&forbidden.from, &forbidden.to] {
+                if !names.contains(name.as_str()) {
+                    return Err(ResearchError::InvalidConfiguration {
+                        reason: format!("Forbidden dependency references unknown layer '{}'", name),
+                        expected: "a layer declared under `layers`".to_string(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Index of the first layer whose patterns match the module or its package
+    fn layer_of(&self, module: &ModuleNode) -> Option<usize> {
+        self.layers.iter().position(|layer| {
+            layer.modules.iter().any(|pattern| {
+                glob_matches(pattern, &module.name) || glob_matches(pattern, &module.package)
+            })
+        })
+    }
+}
+
+// ============================================================================
+// Model
+// ============================================================================
+
+/// Package manager ecosystem of a package
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Ecosystem {
+    /// Rust crate with a Cargo.toml
+    Cargo,
+    /// JavaScript/TypeScript package with a package.json
+    Npm,
+    /// Directory without a recognized manifest
+    Directory,
+}
+
+/// A crate or package in the project
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackageNode {
+    /// Package name from its manifest
+    pub name: String,
+    /// Package directory relative to the project root
+    pub path: PathBuf,
+    /// Ecosystem of the manifest
+    pub ecosystem: Ecosystem,
+    /// Whether the package is a deployable service (binary entry point or Dockerfile)
+    pub service: bool,
+    /// Other packages of the project this one declares as dependencies
+    pub internal_dependencies: Vec<String>,
+    /// Third-party dependencies declared in the manifest
+    pub external_dependencies: Vec<String>,
+}
+
+/// A source module (a file, or a directory module such as `mod.rs`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModuleNode {
+    /// Language-native module name (`crate::a::b`, `pkg.a.b`, `pkg/a/b`)
+    pub name: String,
+    /// Package the module belongs to
+    pub package: String,
+    /// Source files of the module, relative to the project root
+    pub files: Vec<PathBuf>,
+    /// Layer the module was assigned to by the rules
+    pub layer: Option<String>,
+}
+
+/// How a dependency was found
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum DependencyKind {
+    /// Source-level import
+    Import,
+    /// Declared in a package manifest
+    Manifest,
+}
+
+/// Where a dependency appears in source
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct SourceLocation {
+    /// File relative to the project root
+    pub file: PathBuf,
+    /// Line number (1-indexed)
+    pub line: usize,
+}
+
+/// A directed dependency between two modules or two packages
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DependencyEdge {
+    /// Depending module or package
+    pub from: String,
+    /// Module or package depended upon
+    pub to: String,
+    /// How the dependency was found
+    pub kind: DependencyKind,
+    /// Import sites, empty for manifest dependencies
+    pub locations: Vec<SourceLocation>,
+}
+
+/// Kind of architecture rule that was broken
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ViolationKind {
+    /// A layer depends on a layer above it (or skips a layer in strict mode)
+    Layering,
+    /// A dependency listed under `forbidden`
+    Forbidden,
+    /// A service imports code from another service
+    ServiceBoundary,
+    /// Modules depend on each other in a cycle
+    Cycle,
+}
+
+/// A dependency that breaks the declared architecture
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchitectureViolation {
+    /// Rule that was broken
+    pub kind: ViolationKind,
+    /// Depending module
+    pub from: String,
+    /// Module depended upon
+    pub to: String,
+    /// Human-readable explanation
+    pub message: String,
+    /// Import sites of the offending dependency
+    pub locations: Vec<SourceLocation>,
+}
+
+/// Level of detail for diagrams
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagramGranularity {
+    /// One node per package
+    Package,
+    /// One node per module
+    Module,
+}
+
+/// Structured architecture of a project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchitectureModel {
+    /// Project root
+    pub root: PathBuf,
+    /// Packages, sorted by name
+    pub packages: Vec<PackageNode>,
+    /// Modules, sorted by name
+    pub modules: Vec<ModuleNode>,
+    /// Module-level import dependencies
+    pub module_edges: Vec<DependencyEdge>,
+    /// Package-level dependencies from manifests and cross-package imports
+    pub package_edges: Vec<DependencyEdge>,
+    /// Module dependency cycles
+    pub cycles: Vec<Vec<String>>,
+    /// Layer names from the rules, top to bottom
+    pub layers: Vec<String>,
+    /// Rule violations
+    pub violations: Vec<ArchitectureViolation>,
+}
+
+impl ArchitectureModel {
+    /// Get a module by name
+    pub fn module(&self, name: &str) -> Option<&ModuleNode> {
+        self.modules.iter().find(|module| module.name == name)
+    }
+
+    /// Get a package by name
+    pub fn package(&self, name: &str) -> Option<&PackageNode> {
+        self.packages.iter().find(|package| package.name == name)
+    }
+
+    /// Modules that `module` imports
+    pub fn dependencies_of(&self, module: &str) -> Vec<&str> {
+        self.module_edges
+            .iter()
+            .filter(|edge| edge.from == module)
+            .map(|edge| edge.to.as_str())
+            .collect()
+    }
+
+    /// Modules that import `module`
+    pub fn dependents_of(&self, module: &str) -> Vec<&str> {
+        self.module_edges
+            .iter()
+            .filter(|edge| edge.to == module)
+            .map(|edge| edge.from.as_str())
+            .collect()
+    }
+
+    /// Whether the project follows its declared architecture
+    pub fn is_compliant(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// Serialize the model to pretty-printed JSON
+    pub fn to_json(&self) -> Result<String, ResearchError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Render the dependency graph in Graphviz DOT format
+    ///
+    /// Module diagrams group modules into one cluster per layer; edges that
+    /// violate a rule are drawn in red.
+    pub fn to_graphviz(&self, granularity: DiagramGranularity) -> String {
+        let (nodes, edges) = self.diagram(granularity);
+        let mut dot =
+            String::from("digraph architecture {\n    rankdir=LR;\n    node [shape=box];\n");
+        for (group, members) in &nodes {
+            match group {
+                Some((index, label)) => {
+                    let _ = writeln!(dot, "    subgraph cluster_{} {{", index);
+                    let _ = writeln!(dot, "        label=\"{}\";", escape(label));
+                    for member in members {
+                        let _ = writeln!(dot, "        \"{}\";", escape(member));
+                    }
+                    dot.push_str("    }\n");
+                }
+                None => {
+                    for member in members {
+                        let _ = writeln!(dot, "    \"{}\";", escape(member));
+                    }
+                }
+            }
+        }
+        for (from, to, violated) in &edges {
+            let style = if *violated { " [color=red]" } else { "" };
+            let _ = writeln!(
+                dot,
+                "    \"{}\" -> \"{}\"{};",
+                escape(from),
+                escape(to),
+                style
+            );
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Render the dependency graph as a Mermaid flowchart
+    ///
+    /// Module diagrams group modules into one subgraph per layer; edges that
+    /// violate a rule are labelled and drawn in red.
+    pub fn to_mermaid(&self, granularity: DiagramGranularity) -> String {
+        let (nodes, edges) = self.diagram(granularity);
+        let mut ids: HashMap<&str, String> = HashMap::new();
+        let mut mermaid = String::from("graph LR\n");
+        for (group, members) in &nodes {
+            let indent = if group.is_some() { "        " } else { "    " };
+            if let Some((index, label)) = group {
+                let _ = writeln!(
+                    mermaid,
+                    "    subgraph layer{}[\"{}\"]",
+                    index,
+                    escape(label)
+                );
+            }
+            for member in members {
+                let id = format!("n{}", ids.len());
+                let _ = writeln!(mermaid, "{}{}[\"{}\"]", indent, id, escape(member));
+                ids.insert(member.as_str(), id);
+            }
+            if group.is_some() {
+                mermaid.push_str("    end\n");
+            }
+        }
+        let mut violated_links = Vec::new();
+        for (index, (from, to, violated)) in edges.iter().enumerate() {
+            let arrow = if *violated {
+                violated_links.push(index.to_string());
+                "-->|violation|"
+            } else {
+                "-->"
+            };
+            let _ = writeln!(
+                mermaid,
+                "    {} {} {}",
+                ids[from.as_str()],
+                arrow,
+                ids[to.as_str()]
+            );
+        }
+        if !violated_links.is_empty() {
+            let _ = writeln!(
+                mermaid,
+                "    linkStyle {} stroke:red",
+                violated_links.join(",")
+            );
+        }
+        mermaid
+    }
+
+    /// Nodes grouped by layer and edges flagged as violating, for diagrams
+    #[allow(clippy::type_complexity)]
+    fn diagram(
+        &self,
+        granularity: DiagramGranularity,
+    ) -> (
+        Vec<(Option<(usize, String)>, Vec<String>)>,
+        Vec<(String, String, bool)>,
+    ) {
+        let violated: BTreeSet<(&str, &str)> = self
+            .violations
+            .iter()
+            .map(|violation| (violation.from.as_str(), violation.to.as_str()))
+            .collect();
+
+        match granularity {
+            DiagramGranularity::Package => {
+                let package_of: HashMap<&str, &str> = self
+                    .modules
+                    .iter()
+                    .map(|module| (module.name.as_str(), module.package.as_str()))
+                    .collect();
+                let violated_packages: BTreeSet<(&str, &str)> = violated
+                    .iter()
+                    .filter_map(|(from, to)| Some((*package_of.get(from)?, *package_of.get(to)?)))
+                    .collect();
+                let nodes = vec![(
+                    None,
+                    self.packages
+                        .iter()
+                        .map(|package| package.name.clone())
+                        .collect(),
+                )];
+                let edges = self
+                    .package_edges
+                    .iter()
+                    .map(|edge| {
+                        let flagged =
+                            violated_packages.contains(&(edge.from.as_str(), edge.to.as_str()));
+                        (edge.from.clone(), edge.to.clone(), flagged)
+                    })
+                    .collect();
+                (nodes, edges)
+            }
+            DiagramGranularity::Module => {
+                let mut groups: BTreeMap<Option<usize>, Vec<String>> = BTreeMap::new();
+                for module in &self.modules {
+                    let layer = module
+                        .layer
+                        .as_ref()
+                        .and_then(|layer| self.layers.iter().position(|name| name == layer));
+                    groups.entry(layer).or_default().push(module.name.clone());
+                }
+                let nodes = groups
+                    .into_iter()
+                    .map(|(layer, members)| {
+                        (
+                            layer.map(|index| (index, self.layers[index].clone())),
+                            members,
+                        )
+                    })
+                    .collect();
+                let edges = self
+                    .module_edges
+                    .iter()
+                    .map(|edge| {
+                        let flagged = violated.contains(&(edge.from.as_str(), edge.to.as_str()));
+                        (edge.from.clone(), edge.to.clone(), flagged)
+                    })
+                    .collect();
+                (nodes, edges)
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Analyzer
+// ============================================================================
+
+/// Extracts the architecture of a project
+#[derive(Debug, Clone, Default)]
+pub struct ArchitectureAnalyzer {
+    rules: Option<ArchitectureRules>,
+}
+
+impl ArchitectureAnalyzer {
+    /// Create an analyzer that reads rules from [`ARCHITECTURE_RULES_FILE`] when present
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check against the given rules instead of the project's rules file
+    pub fn with_rules(mut self, rules: ArchitectureRules) -> Self {
+        self.rules = Some(rules);
+        self
+    }
+
+    /// Build the architecture model of the project at `root`
+    pub fn analyze(&self, root: &Path) -> Result<ArchitectureModel, ResearchError> {
+        if !root.is_dir() {
+            return Err(ResearchError::ProjectNotFound {
+                path: root.to_path_buf(),
+                reason: "Root is not a directory".to_string(),
+            });
+        }
+        let rules = match &self.rules {
+            Some(rules) => {
+                rules.validate()?;
+                rules.clone()
+            }
+            None => ArchitectureRules::load(root)?.unwrap_or_default(),
+        };
+
+        let (manifests, sources) = scan(root)?;
+        let mut graph = Graph::new(root);
+        for manifest in &manifests {
+            graph.add_package(manifest)?;
+        }
+        graph.ensure_root_package();
+        graph.resolve_manifest_dependencies();
+        for source in &sources {
+            graph.add_module(source);
+        }
+        for source in &sources {
+            graph.add_imports(source)?;
+        }
+
+        let model = graph.into_model(&rules);
+        info!(
+            "Architecture of {:?}: {} packages, {} modules, {} dependencies, {} violations",
+            root,
+            model.packages.len(),
+            model.modules.len(),
+            model.module_edges.len(),
+            model.violations.len()
+        );
+        Ok(model)
+    }
+}
+
+/// Source language handled by the import scanner
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SourceLanguage {
+    Rust,
+    Python,
+    Script,
+}
+
+impl SourceLanguage {
+    fn of(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "rs" => Some(SourceLanguage::Rust),
+            "py" => Some(SourceLanguage::Python),
+            "ts" | "tsx" | "js" | "jsx" | "mjs" | "cjs" => Some(SourceLanguage::Script),
+            _ => None,
+        }
+    }
+}
+
+/// Collect manifests and source files, skipping build and vendor directories
+fn scan(root: &Path) -> Result<(Vec<PathBuf>, Vec<PathBuf>), ResearchError> {
+    let mut manifests = Vec::new();
+    let mut sources = Vec::new();
+    let walker = ignore::WalkBuilder::new(root)
+        .filter_entry(|entry| {
+            entry
+                .file_name()
+                .to_str()
+                .map_or(true, |name| !SKIPPED_DIRS.contains(&name))
+        })
+        .build();
+    for entry in walker {
+        let entry = entry.map_err(|e| ResearchError::ScanFailed {
+            reason: e.to_string(),
+        })?;
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        match path.file_name().and_then(|name| name.to_str()) {
+            Some("Cargo.toml") | Some("package.json") => manifests.push(path.to_path_buf()),
+            _ if SourceLanguage::of(path).is_some() => sources.push(path.to_path_buf()),
+            _ => {}
+        }
+    }
+    manifests.sort();
+    sources.sort();
+    Ok((manifests, sources))
+}
+
+/// Graph under construction
+struct Graph {
+    root: PathBuf,
+    packages: BTreeMap<String, PackageNode>,
+    /// Manifest dependency names per package, resolved once all packages are known
+    declared: BTreeMap<String, Vec<String>>,
+    modules: BTreeMap<String, ModuleNode>,
+    /// Source file (absolute) -> module name
+    module_of_file: HashMap<PathBuf, String>,
+    edges: BTreeMap<(String, String), Vec<SourceLocation>>,
+}
+
+impl Graph {
+    fn new(root: &Path) -> Self {
+        Graph {
+            root: root.to_path_buf(),
+            packages: BTreeMap::new(),
+            declared: BTreeMap::new(),
+            modules: BTreeMap::new(),
+            module_of_file: HashMap::new(),
+            edges: BTreeMap::new(),
+        }
+    }
+
+    fn relative(&self, path: &Path) -> PathBuf {
+        path.strip_prefix(&self.root).unwrap_or(path).to_path_buf()
+    }
+
+    fn add_package(&mut self, manifest: &Path) -> Result<(), ResearchError> {
+        let dir = manifest.parent().unwrap_or(&self.root);
+        let content = fs::read_to_string(manifest).map_err(|e| ResearchError::IoError {
+            reason: format!("Failed to read {}: {}", manifest.display(), e),
+        })?;
+
+        let (name, ecosystem, dependencies, binary) =
+            if manifest.file_name().and_then(|n| n.to_str()) == Some("Cargo.toml") {
+                let value: toml::Value = toml::from_str(&content)?;
+                let name = match value
+                    .get("package")
+                    .and_then(|package| package.get("name"))
+                    .and_then(|name| name.as_str())
+                {
+                    Some(name) => name.to_string(),
+                    // Virtual workspace manifests are not packages
+                    None => return Ok(()),
+                };
+                let dependencies = value
+                    .get("dependencies")
+                    .and_then(|deps| deps.as_table())
+                    .map(|deps| {
+                        deps.iter()
+                            .map(|(key, spec)| {
+                                spec.get("package")
+                                    .and_then(|package| package.as_str())
+                                    .unwrap_or(key)
+                                    .to_string()
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let binary = dir.join("src/main.rs").is_file()
+                    || dir.join("src/bin").is_dir()
+                    || value.get("bin").is_some();
+                (name, Ecosystem::Cargo, dependencies, binary)
+            } else {
+                let value: serde_json::Value = serde_json::from_str(&content)?;
+                let name = match value.get("name").and_then(|name| name.as_str()) {
+                    Some(name) => name.to_string(),
+                    None => return Ok(()),
+                };
+                let dependencies = value
+                    .get("dependencies")
+                    .and_then(|deps| deps.as_object())
+                    .map(|deps| deps.keys().cloned().collect())
+                    .unwrap_or_default();
+                let binary = value.get("bin").is_some()
+                    || value
+                        .get("scripts")
+                        .and_then(|scripts| scripts.get("start"))
+                        .is_some();
+                (name, Ecosystem::Npm, dependencies, binary)
+            };
+
+        debug!("Found package {} at {:?}", name, dir);
+        self.declared.insert(name.clone(), dependencies);
+        self.packages.insert(
+            name.clone(),
+            PackageNode {
+                name,
+                path: self.relative(dir),
+                ecosystem,
+                service: binary || dir.join("Dockerfile").is_file(),
+                internal_dependencies: Vec::new(),
+                external_dependencies: Vec::new(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Files outside every package belong to an implicit package for the root
+    fn ensure_root_package(&mut self) {
+        if self
+            .packages
+            .values()
+            .any(|package| package.path.as_os_str().is_empty())
+        {
+            return;
+        }
+        let name = self
+            .root
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("root")
+            .to_string();
+        self.packages.entry(name.clone()).or_insert(PackageNode {
+            name,
+            path: PathBuf::new(),
+            ecosystem: Ecosystem::Directory,
+            service: self.root.join("Dockerfile").is_file(),
+            internal_dependencies: Vec::new(),
+            external_dependencies: Vec::new(),
+        });
+    }
+
+    fn resolve_manifest_dependencies(&mut self) {
+        let known: BTreeSet<String> = self.packages.keys().cloned().collect();
+        for (name, dependencies) in &self.declared {
+            let package = self
+                .packages
+                .get_mut(name)
+                .expect("declared package exists");
+            for dependency in dependencies {
+                if known.contains(dependency) && dependency != name {
+                    package.internal_dependencies.push(dependency.clone());
+                } else {
+                    package.external_dependencies.push(dependency.clone());
+                }
+            }
+            package.internal_dependencies.sort();
+            package.external_dependencies.sort();
+        }
+    }
+
+    /// Package owning a file: the one with the deepest directory containing it
+    fn package_of(&self, file: &Path) -> &PackageNode {
+        let relative = self.relative(file);
+        self.packages
+            .values()
+            .filter(|package| relative.starts_with(&package.path))
+            .max_by_key(|package| package.path.components().count())
+            .expect("root package contains every file")
+    }
+
+    fn add_module(&mut self, file: &Path) {
+        let language = match SourceLanguage::of(file) {
+            Some(language) => language,
+            None => return,
+        };
+        let package = self.package_of(file).clone();
+        let within = self
+            .relative(file)
+            .strip_prefix(&package.path)
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        let name = match module_name(language, &package.name, &within) {
+            Some(name) => name,
+            None => return,
+        };
+
+        let relative = self.relative(file);
+        self.modules
+            .entry(name.clone())
+            .or_insert_with(|| ModuleNode {
+                name: name.clone(),
+                package: package.name.clone(),
+                files: Vec::new(),
+                layer: None,
+            })
+            .files
+            .push(relative);
+        self.module_of_file.insert(file.to_path_buf(), name);
+    }
+
+    fn add_imports(&mut self, file: &Path) -> Result<(), ResearchError> {
+        let from = match self.module_of_file.get(file) {
+            Some(module) => module.clone(),
+            None => return Ok(()),
+        };
+        let content = fs::read_to_string(file).map_err(|e| ResearchError::IoError {
+            reason: format!("Failed to read {}: {}", file.display(), e),
+        })?;
+
+        let targets: Vec<(String, usize)> = match SourceLanguage::of(file) {
+            Some(SourceLanguage::Rust) => self.rust_imports(&from, file, &content),
+            Some(SourceLanguage::Python) => self.python_imports(&from, file, &content),
+            Some(SourceLanguage::Script) => self.script_imports(file, &content),
+            None => Vec::new(),
+        };
+
+        let relative = self.relative(file);
+        for (to, line) in targets {
+            if to != from {
+                self.edges
+                    .entry((from.clone(), to))
+                    .or_default()
+                    .push(SourceLocation {
+                        file: relative.clone(),
+                        line,
+                    });
+            }
+        }
+        Ok(())
+    }
+
+    /// Longest known module that is a prefix of `segments`
+    fn longest_module(&self, segments: &[String], separator: &str) -> Option<String> {
+        (1..=segments.len())
+            .rev()
+            .map(|len| segments[..len].join(separator))
+            .find(|name| self.modules.contains_key(name))
+    }
+
+    fn rust_imports(&self, from: &str, file: &Path, content: &str) -> Vec<(String, usize)> {
+        static USE: OnceLock<Regex> = OnceLock::new();
+        let regex = USE.get_or_init(|| {
+            Regex::new(r"(?m)^\s*(?:pub(?:\([^)]*\))?\s+)?use\s+([^;]+);").expect("valid regex")
+        });
+        let krate = self.package_of(file).name.replace('-', "_");
+        let current: Vec<String> = from.split("::").map(str::to_string).collect();
+        let crates: BTreeSet<String> = self
+            .packages
+            .values()
+            .filter(|package| package.ecosystem == Ecosystem::Cargo)
+            .map(|package| package.name.replace('-', "_"))
+            .collect();
+
+        let mut imports = Vec::new();
+        for captures in regex.captures_iter(content) {
+            let tree = captures.get(1).expect("use tree group");
+            let line = line_of(content, tree.start());
+            for path in expand_use_tree(tree.as_str()) {
+                let mut segments = path.into_iter();
+                let first = match segments.next() {
+                    Some(first) => first,
+                    None => continue,
+                };
+                let mut resolved: Vec<String> = match first.as_str() {
+                    "crate" => vec![krate.clone()],
+                    "self" => current.clone(),
+                    "super" => current[..current.len().saturating_sub(1)].to_vec(),
+                    name if crates.contains(name) => vec![name.to_string()],
+                    // Uniform paths: a child of the current module, else an external crate
+                    name => {
+                        let mut child = current.clone();
+                        child.push(name.to_string());
+                        child
+                    }
+                };
+                for segment in segments {
+                    if segment == "super" {
+                        resolved.pop();
+                    } else {
+                        resolved.push(segment);
+                    }
+                }
+                if let Some(module) = self.longest_module(&resolved, "::") {
+                    imports.push((module, line));
+                }
+            }
+        }
+        imports
+    }
+
+    fn python_imports(&self, from: &str, file: &Path, content: &str) -> Vec<(String, usize)> {
+        static IMPORT: OnceLock<Regex> = OnceLock::new();
+        let regex = IMPORT.get_or_init(|| {
+            Regex::new(
+                r"(?m)^\s*(?:from\s+(\.*[A-Za-z0-9_.]*)\s+import\s+([A-Za-z0-9_., ]+)|import\s+([A-Za-z0-9_., ]+))",
+            )
+            .expect("valid regex")
+        });
+        let is_package = file.file_stem().and_then(|stem| stem.to_str()) == Some("__init__");
+        let mut package: Vec<String> = from.split('.').map(str::to_string).collect();
+        if !is_package {
+            package.pop();
+        }
+
+        let mut imports = Vec::new();
+        for captures in regex.captures_iter(content) {
+            let whole = captures.get(0).expect("match");
+            let line = line_of(content, whole.start());
+            let mut candidates: Vec<Vec<String>> = Vec::new();
+            if let Some(module) = captures.get(1) {
+                let module = module.as_str();
+                let dots = module.chars().take_while(|c| *c == '.').count();
+                let mut base = if dots > 0 {
+                    let mut base = package.clone();
+                    base.truncate(base.len().saturating_sub(dots - 1));
+                    base
+                } else {
+                    Vec::new()
+                };
+                base.extend(
+                    module[dots..]
+                        .split('.')
+                        .filter(|segment| !segment.is_empty())
+                        .map(str::to_string),
+                );
+                // `from pkg import name` may name a submodule; otherwise the
+                // longest matching prefix is the package itself
+                for name in captures[2].split(',') {
+                    let name = name.split_whitespace().next().unwrap_or_default();
+                    if !name.is_empty() {
+                        let mut candidate = base.clone();
+                        candidate.push(name.to_string());
+                        candidates.push(candidate);
+                    }
+                }
+            } else if let Some(modules) = captures.get(3) {
+                for name in modules.as_str().split(',') {
+                    let name = name.split_whitespace().next().unwrap_or_default();
+                    candidates.push(name.split('.').map(str::to_string).collect());
+                }
+            }
+            for candidate in candidates {
+                if let Some(module) = self.longest_module(&candidate, ".") {
+                    imports.push((module, line));
+                }
+            }
+        }
+        imports.sort();
+        imports.dedup();
+        imports
+    }
+
+    fn script_imports(&self, file: &Path, content: &str) -> Vec<(String, usize)> {
+        static IMPORT: OnceLock<Regex> = OnceLock::new();
+        let regex = IMPORT.get_or_init(|| {
+            Regex::new(r#"(?:\bfrom\s+|\bimport\s+|\brequire\(\s*)['"]([^'"]+)['"]"#)
+                .expect("valid regex")
+        });
+        let dir = file.parent().unwrap_or(&self.root);
+
+        let mut imports = Vec::new();
+        for captures in regex.captures_iter(content) {
+            let specifier = &captures[1];
+            let line = line_of(content, captures.get(0).expect("match").start());
+            let module = if specifier.starts_with('.') {
+                let base = normalize(&dir.join(specifier));
+                let mut candidates = vec![base.clone()];
+                for extension in ["ts", "tsx", "js", "jsx", "mjs", "cjs"] {
+                    candidates.push(base.with_extension(extension));
+                    candidates.push(base.join(format!("index.{}", extension)));
+                }
+                candidates
+                    .iter()
+                    .find_map(|candidate| self.module_of_file.get(candidate).cloned())
+            } else {
+                // Bare specifiers resolve to another package's entry module
+                let package = match specifier.strip_prefix('@') {
+                    Some(scoped) => format!(
+                        "@{}",
+                        scoped.splitn(3, '/').take(2).collect::<Vec<_>>().join("/")
+                    ),
+                    None => specifier.split('/').next().unwrap_or(specifier).to_string(),
+                };
+                self.modules.contains_key(&package).then_some(package)
+            };
+            if let Some(module) = module {
+                imports.push((module, line));
+            }
+        }
+        imports
+    }
+
+    fn into_model(self, rules: &ArchitectureRules) -> ArchitectureModel {
+        let mut modules: Vec<ModuleNode> = self.modules.into_values().collect();
+        for module in &mut modules {
+            module.layer = rules
+                .layer_of(module)
+                .map(|index| rules.layers[index].name.clone());
+        }
+        let module_index: HashMap<String, usize> = modules
+            .iter()
+            .enumerate()
+            .map(|(index, module)| (module.name.clone(), index))
+            .collect();
+
+        let module_edges: Vec<DependencyEdge> = self
+            .edges
+            .into_iter()
+            .map(|((from, to), mut locations)| {
+                locations.sort();
+                locations.dedup();
+                DependencyEdge {
+                    from,
+                    to,
+                    kind: DependencyKind::Import,
+                    locations,
+                }
+            })
+            .collect();
+
+        // Package edges: declared in manifests, plus imports that cross packages
+        let mut package_edges: BTreeMap<(String, String), DependencyEdge> = BTreeMap::new();
+        for package in self.packages.values() {
+            for dependency in &package.internal_dependencies {
+                package_edges.insert(
+                    (package.name.clone(), dependency.clone()),
+                    DependencyEdge {
+                        from: package.name.clone(),
+                        to: dependency.clone(),
+                        kind: DependencyKind::Manifest,
+                        locations: Vec::new(),
+                    },
+                );
+            }
+        }
+        for edge in &module_edges {
+            let from = &modules[module_index[&edge.from]].package;
+            let to = &modules[module_index[&edge.to]].package;
+            if from != to {
+                package_edges
+                    .entry((from.clone(), to.clone()))
+                    .or_insert_with(|| DependencyEdge {
+                        from: from.clone(),
+                        to: to.clone(),
+                        kind: DependencyKind::Import,
+                        locations: Vec::new(),
+                    })
+                    .locations
+                    .extend(edge.locations.iter().cloned());
+            }
+        }
+
+        let cycles = find_cycles(&modules, &module_edges, &module_index);
+        let packages: Vec<PackageNode> = self.packages.into_values().collect();
+        let violations = check_rules(
+            rules,
+            &modules,
+            &packages,
+            &module_edges,
+            &module_index,
+            &cycles,
+        );
+
+        ArchitectureModel {
+            root: self.root,
+            packages,
+            modules,
+            module_edges,
+            package_edges: package_edges.into_values().collect(),
+            cycles,
+            layers: rules
+                .layers
+                .iter()
+                .map(|layer| layer.name.clone())
+                .collect(),
+            violations,
+        }
+    }
+}
+
+fn check_rules(
+    rules: &ArchitectureRules,
+    modules: &[ModuleNode],
+    packages: &[PackageNode],
+    edges: &[DependencyEdge],
+    module_index: &HashMap<String, usize>,
+    cycles: &[Vec<String>],
+) -> Vec<ArchitectureViolation> {
+    let layer_index = |name: &str| rules.layers.iter().position(|layer| layer.name == name);
+    let services: BTreeSet<&str> = packages
+        .iter()
+        .filter(|package| package.service)
+        .map(|package| package.name.as_str())
+        .collect();
+
+    let mut violations = Vec::new();
+    for edge in edges {
+        let from = &modules[module_index[&edge.from]];
+        let to = &modules[module_index[&edge.to]];
+        let violation = |kind, message| ArchitectureViolation {
+            kind,
+            from: edge.from.clone(),
+            to: edge.to.clone(),
+            message,
+            locations: edge.locations.clone(),
+        };
+
+        if let (Some(from_layer), Some(to_layer)) = (&from.layer, &to.layer) {
+            let forbidden = rules
+                .forbidden
+                .iter()
+                .any(|rule| &rule.from == from_layer && &rule.to == to_layer);
+            let (upper, lower) = (
+                layer_index(from_layer).expect("assigned layer exists"),
+                layer_index(to_layer).expect("assigned layer exists"),
+            );
+            if forbidden {
+                violations.push(violation(
+                    ViolationKind::Forbidden,
+                    format!(
+                        "Layer '{}' must not depend on layer '{}'",
+                        from_layer, to_layer
+                    ),
+                ));
+            } else if lower < upper {
+                violations.push(violation(
+                    ViolationKind::Layering,
+                    format!(
+                        "Layer '{}' depends on layer '{}' above it",
+                        from_layer, to_layer
+                    ),
+                ));
+            } else if rules.strict && lower > upper + 1 {
+                violations.push(violation(
+                    ViolationKind::Layering,
+                    format!(
+                        "Layer '{}' skips the layers between it and '{}'",
+                        from_layer, to_layer
+                    ),
+                ));
+            }
+        }
+
+        if rules.isolate_services
+            && from.package != to.package
+            && services.contains(from.package.as_str())
+            && services.contains(to.package.as_str())
+        {
+            violations.push(violation(
+                ViolationKind::ServiceBoundary,
+                format!(
+                    "Service '{}' imports code from service '{}'",
+                    from.package, to.package
+                ),
+            ));
+        }
+    }
+
+    if rules.forbid_cycles {
+        for cycle in cycles {
+            violations.push(ArchitectureViolation {
+                kind: ViolationKind::Cycle,
+                from: cycle[0].clone(),
+                to: cycle[cycle.len() - 1].clone(),
+                message: format!("Dependency cycle: {}", cycle.join(" -> ")),
+                locations: Vec::new(),
+            });
+        }
+    }
+    violations
+}
+
+/// Strongly connected components with more than one module (Tarjan)
+fn find_cycles(
+    modules: &[ModuleNode],
+    edges: &[DependencyEdge],
+    module_index: &HashMap<String, usize>,
+) -> Vec<Vec<String>> {
+    struct Tarjan<'a> {
+        adjacency: &'a [Vec<usize>],
+        index: Vec<Option<usize>>,
+        lowlink: Vec<usize>,
+        on_stack: Vec<bool>,
+        stack: Vec<usize>,
+        next: usize,
+        components: Vec<Vec<usize>>,
+    }
+
+    impl Tarjan<'_> {
+        fn visit(&mut self, node: usize) {
+            self.index[node] = Some(self.next);
+            self.lowlink[node] = self.next;
+            self.next += 1;
+            self.stack.push(node);
+            self.on_stack[node] = true;
+
+            for &next in &self.adjacency[node] {
+                match self.index[next] {
+                    None => {
+                        self.visit(next);
+                        self.lowlink[node] = self.lowlink[node].min(self.lowlink[next]);
+                    }
+                    Some(index) if self.on_stack[next] => {
+                        self.lowlink[node] = self.lowlink[node].min(index);
+                    }
+                    Some(_) => {}
+                }
+            }
+
+            if Some(self.lowlink[node]) == self.index[node] {
+                let mut component = Vec::new();
+                while let Some(member) = self.stack.pop() {
+                    self.on_stack[member] = false;
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                if component.len() > 1 {
+                    self.components.push(component);
+                }
+            }
+        }
+    }
+
+    let mut adjacency = vec![Vec::new(); modules.len()];
+    for edge in edges {
+        adjacency[module_index[&edge.from]].push(module_index[&edge.to]);
+    }
+    let mut tarjan = Tarjan {
+        adjacency: &adjacency,
+        index: vec![None; modules.len()],
+        lowlink: vec![0; modules.len()],
+        on_stack: vec![false; modules.len()],
+        stack: Vec::new(),
+        next: 0,
+        components: Vec::new(),
+    };
+    for node in 0..modules.len() {
+        if tarjan.index[node].is_none() {
+            tarjan.visit(node);
+        }
+    }
+
+    let mut cycles: Vec<Vec<String>> = tarjan
+        .components
+        .into_iter()
+        .map(|component| {
+            let mut names: Vec<String> = component
+                .into_iter()
+                .map(|index| modules[index].name.clone())
+                .collect();
+            names.sort();
+            names
+        })
+        .collect();
+    cycles.sort();
+    cycles
+}
+
+/// Module name of a file within its package, or `None` if it is not part of the module tree
+fn module_name(language: SourceLanguage, package: &str, within: &Path) -> Option<String> {
+    let mut segments: Vec<String> = within
+        .with_extension("")
+        .components()
+        .filter_map(|component| component.as_os_str().to_str().map(str::to_string))
+        .collect();
+
+    match language {
+        SourceLanguage::Rust => {
+            // Only the library/binary module tree under src/ is architecture
+            if segments.first().map(String::as_str) != Some("src") {
+                return None;
+            }
+            segments.remove(0);
+            if segments.first().map(String::as_str) == Some("bin") {
+                return None;
+            }
+            if matches!(
+                segments.last().map(String::as_str),
+                Some("lib") | Some("main") | Some("mod")
+            ) && (segments.len() == 1 || segments.last().map(String::as_str) == Some("mod"))
+            {
+                segments.pop();
+            }
+            let mut name = vec![package.replace('-', "_")];
+            name.extend(segments);
+            Some(name.join("::"))
+        }
+        SourceLanguage::Python => {
+            if segments.first().map(String::as_str) == Some("src") {
+                segments.remove(0);
+            }
+            if segments.last().map(String::as_str) == Some("__init__") {
+                segments.pop();
+            }
+            if segments.is_empty() {
+                return None;
+            }
+            Some(segments.join("."))
+        }
+        SourceLanguage::Script => {
+            if segments.last().map(String::as_str) == Some("index") {
+                segments.pop();
+            }
+            let mut name = vec![package.to_string()];
+            name.extend(segments);
+            Some(name.join("/"))
+        }
+    }
+}
+
+/// Expand a Rust use tree (`a::{b, c::d as e}`) into its paths
+fn expand_use_tree(tree: &str) -> Vec<Vec<String>> {
+    static ALIAS: OnceLock<Regex> = OnceLock::new();
+    let alias =
+        ALIAS.get_or_init(|| Regex::new(r"\s+as\s+[A-Za-z_][A-Za-z0-9_]*").expect("valid regex"));
+    let tree: String = alias
+        .replace_all(tree, "")
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    let mut paths = Vec::new();
+    expand_into(&tree, &[], &mut paths);
+    paths
+}
+
+fn expand_into(tree: &str, prefix: &[String], paths: &mut Vec<Vec<String>>) {
+    let tree = tree.trim_start_matches("::");
+    if let Some(open) = tree.find('{') {
+        let close = tree.rfind('}').unwrap_or(tree.len());
+        let mut base = prefix.to_vec();
+        base.extend(path_segments(&tree[..open]));
+        let inner = &tree[open + 1..close];
+        let mut depth = 0;
+        let mut start = 0;
+        for (index, c) in inner.char_indices() {
+            match c {
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                ',' if depth == 0 => {
+                    expand_into(&inner[start..index], &base, paths);
+                    start = index + 1;
+                }
+                _ => {}
+            }
+        }
+        expand_into(&inner[start..], &base, paths);
+    } else if !tree.is_empty() {
+        let mut path = prefix.to_vec();
+        for segment in path_segments(tree) {
+            if segment != "self" || path.is_empty() {
+                path.push(segment);
+            }
+        }
+        paths.push(path);
+    }
+}
+
+fn path_segments(path: &str) -> Vec<String> {
+    path.split("::")
+        .filter(|segment| !segment.is_empty() && *segment != "*")
+        .map(str::to_string)
+        .collect()
+}
+
+/// Resolve `.` and `..` components without touching the filesystem
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            std::path::Component::CurDir => {}
+            std::path::Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+fn line_of(content: &str, offset: usize) -> usize {
+    content[..offset].matches('\n').count() + 1
+}
+
+/// Match a glob where `*` stays within a segment and `**` crosses segments
+fn glob_matches(pattern: &str, name: &str) -> bool {
+    let mut regex = String::from("^");
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '*' {
+            if chars.peek() == Some(&'*') {
+                chars.next();
+                regex.push_str(".*");
+            } else {
+                regex.push_str("[^:/.]*");
+            }
+        } else {
+            regex.push_str(&regex::escape(&c.to_string()));
+        }
+    }
+    regex.push('$');
+    Regex::new(&regex).is_ok_and(|regex| regex.is_match(name))
+}
+
+fn escape(label: &str) -> String {
+    label.replace('"', "\\\"")
+}
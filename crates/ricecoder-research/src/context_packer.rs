@@ -0,0 +1,770 @@
+//! Token-budgeted context packing for AI requests
+//!
+//! Given a task description and a token budget, the [`ContextPacker`] ranks
+//! candidate files, semantic search hits and the project's coding standards
+//! (relevance to the task, embedding similarity and recency), removes line
+//! ranges that are already covered by a higher-ranked entry, and greedily
+//! fills the budget. The result is a [`ContextManifest`] recording why each
+//! entry was included and what was left out.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use chrono::{DateTime, Duration, Utc};
+use ricecoder_providers::{TokenCounter, TokenCounterTrait};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::{
+    error::ResearchError,
+    models::{CaseStyle, DocFormat, FileContext, IndentType, StandardsProfile},
+    relevance_scorer::RelevanceScorer,
+    semantic_search::{HitSource, SemanticCodeSearch, SemanticSearchHit},
+};
+
+/// What a packed entry contains
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ContextItemKind {
+    /// A whole (or truncated) file
+    File,
+    /// A line range of a file
+    Snippet,
+    /// The project's coding standards
+    Standards,
+}
+
+/// Signal that caused an entry to be considered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ContextSource {
+    /// Relevance scorer match against the task
+    Relevance,
+    /// Embedding similarity from semantic search
+    Semantic,
+    /// Symbol name match from keyword search
+    Keyword,
+    /// Recently modified file
+    Recency,
+    /// Detected project standards
+    Standards,
+}
+
+/// Why an entry is in the packed context
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Provenance {
+    /// Signal that produced the entry
+    pub source: ContextSource,
+    /// Raw signal strength (0.0 to 1.0)
+    pub score: f32,
+    /// Human-readable explanation
+    pub reason: String,
+}
+
+/// A piece of context selected for the request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PackedEntry {
+    /// What the entry contains
+    pub kind: ContextItemKind,
+    /// Source file, if any
+    pub path: Option<PathBuf>,
+    /// First included line (1-indexed, 0 for standards)
+    pub start_line: usize,
+    /// Last included line (inclusive, 0 for standards)
+    pub end_line: usize,
+    /// Estimated tokens of `content`
+    pub tokens: usize,
+    /// Combined ranking score
+    pub score: f32,
+    /// Whether the entry was cut short to fit the budget
+    pub truncated: bool,
+    /// Included text
+    pub content: String,
+    /// Every signal that selected this range, including deduplicated ones
+    pub provenance: Vec<Provenance>,
+}
+
+impl PackedEntry {
+    /// Citation in `path:start-end` form, or `standards`
+    pub fn citation(&self) -> String {
+        match &self.path {
+            Some(path) if self.start_line == self.end_line => {
+                format!("{}:{}", path.display(), self.start_line)
+            }
+            Some(path) => format!("{}:{}-{}", path.display(), self.start_line, self.end_line),
+            None => "standards".to_string(),
+        }
+    }
+}
+
+/// A candidate that did not make it into the packed context
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DroppedEntry {
+    /// What the candidate contained
+    pub kind: ContextItemKind,
+    /// Source file, if any
+    pub path: Option<PathBuf>,
+    /// First line of the candidate
+    pub start_line: usize,
+    /// Last line of the candidate
+    pub end_line: usize,
+    /// Estimated tokens the candidate needed
+    pub tokens: usize,
+    /// Combined ranking score
+    pub score: f32,
+    /// Why it was dropped
+    pub reason: String,
+}
+
+/// The packed context for one request, with provenance
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContextManifest {
+    /// Task the context was packed for
+    pub task: String,
+    /// Token budget
+    pub budget: usize,
+    /// Tokens used by all entries
+    pub used_tokens: usize,
+    /// Selected entries, standards first and then by descending score
+    pub entries: Vec<PackedEntry>,
+    /// Candidates left out
+    pub dropped: Vec<DroppedEntry>,
+    /// Candidates whose lines were already covered by a selected entry
+    pub deduplicated: usize,
+    /// When the manifest was produced
+    pub created_at: DateTime<Utc>,
+}
+
+impl ContextManifest {
+    /// Tokens still available within the budget
+    pub fn remaining_tokens(&self) -> usize {
+        self.budget.saturating_sub(self.used_tokens)
+    }
+
+    /// Citations of all selected entries, in order
+    pub fn citations(&self) -> Vec<String> {
+        self.entries.iter().map(PackedEntry::citation).collect()
+    }
+
+    /// Render the entries as prompt text, one section per entry
+    pub fn render(&self) -> String {
+        let mut output = String::new();
+        for entry in &self.entries {
+            match entry.kind {
+                ContextItemKind::Standards => output.push_str("## Project standards\n"),
+                _ => output.push_str(&format!("## {}\n", entry.citation())),
+            }
+            output.push_str("```\n");
+            output.push_str(&entry.content);
+            if !entry.content.ends_with('\n') {
+                output.push('\n');
+            }
+            output.push_str("```\n\n");
+        }
+        output
+    }
+
+    /// Serialize the manifest to pretty-printed JSON
+    pub fn to_json(&self) -> Result<String, ResearchError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+/// Inputs for packing one request
+#[derive(Debug, Clone)]
+pub struct ContextRequest {
+    /// Task description the context should serve
+    pub task: String,
+    /// Maximum tokens the packed context may use
+    pub budget: usize,
+    /// Candidate files (content is read from disk when missing)
+    pub files: Vec<FileContext>,
+    /// Candidate locations from semantic/keyword search
+    pub hits: Vec<SemanticSearchHit>,
+    /// Project standards to include ahead of code
+    pub standards: Option<StandardsProfile>,
+    /// Last modification times used for the recency signal
+    pub modified: HashMap<PathBuf, DateTime<Utc>>,
+}
+
+impl ContextRequest {
+    /// Create a request for `task` with a token budget
+    pub fn new(task: impl Into<String>, budget: usize) -> Self {
+        ContextRequest {
+            task: task.into(),
+            budget,
+            files: Vec::new(),
+            hits: Vec::new(),
+            standards: None,
+            modified: HashMap::new(),
+        }
+    }
+
+    /// Add candidate files
+    pub fn with_files(mut self, files: impl IntoIterator<Item = FileContext>) -> Self {
+        self.files.extend(files);
+        self
+    }
+
+    /// Add candidate search hits
+    pub fn with_search_hits(mut self, hits: impl IntoIterator<Item = SemanticSearchHit>) -> Self {
+        self.hits.extend(hits);
+        self
+    }
+
+    /// Include the project's coding standards
+    pub fn with_standards(mut self, standards: StandardsProfile) -> Self {
+        self.standards = Some(standards);
+        self
+    }
+
+    /// Record when `path` was last modified
+    pub fn with_modified(mut self, path: impl Into<PathBuf>, at: DateTime<Utc>) -> Self {
+        self.modified.insert(path.into(), at);
+        self
+    }
+}
+
+/// Relative weight of each ranking signal
+#[derive(Debug, Clone)]
+pub struct PackingWeights {
+    /// Weight for relevance scorer matches
+    pub relevance: f32,
+    /// Weight for semantic/keyword search hits
+    pub semantic: f32,
+    /// Weight for recency
+    pub recency: f32,
+}
+
+impl Default for PackingWeights {
+    fn default() -> Self {
+        PackingWeights {
+            relevance: 0.6,
+            semantic: 0.8,
+            recency: 0.2,
+        }
+    }
+}
+
+/// A ranked candidate before packing
+struct Candidate {
+    kind: ContextItemKind,
+    path: Option<PathBuf>,
+    start_line: usize,
+    lines: Vec<String>,
+    score: f32,
+    provenance: Vec<Provenance>,
+}
+
+impl Candidate {
+    fn end_line(&self) -> usize {
+        (self.start_line + self.lines.len()).saturating_sub(1)
+    }
+}
+
+/// Selects and orders context for AI requests within a token budget
+pub struct ContextPacker {
+    scorer: RelevanceScorer,
+    weights: PackingWeights,
+    counter: Arc<dyn TokenCounterTrait>,
+    model: String,
+    recency_half_life: Duration,
+    min_truncated_tokens: usize,
+    root: Option<PathBuf>,
+}
+
+impl std::fmt::Debug for ContextPacker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ContextPacker")
+            .field("scorer", &self.scorer)
+            .field("weights", &self.weights)
+            .field("model", &self.model)
+            .field("recency_half_life", &self.recency_half_life)
+            .field("min_truncated_tokens", &self.min_truncated_tokens)
+            .field("root", &self.root)
+            .finish()
+    }
+}
+
+impl ContextPacker {
+    /// Create a packer with default weights and the heuristic token counter
+    pub fn new() -> Self {
+        ContextPacker {
+            scorer: RelevanceScorer::new(),
+            weights: PackingWeights::default(),
+            counter: Arc::new(TokenCounter::new()),
+            model: String::new(),
+            recency_half_life: Duration::days(7),
+            min_truncated_tokens: 64,
+            root: None,
+        }
+    }
+
+    /// Use a different relevance scorer
+    pub fn with_scorer(mut self, scorer: RelevanceScorer) -> Self {
+        self.scorer = scorer;
+        self
+    }
+
+    /// Use different signal weights
+    pub fn with_weights(mut self, weights: PackingWeights) -> Self {
+        self.weights = weights;
+        self
+    }
+
+    /// Count tokens with `counter` for `model`
+    pub fn with_token_counter(
+        mut self,
+        counter: Arc<dyn TokenCounterTrait>,
+        model: impl Into<String>,
+    ) -> Self {
+        self.counter = counter;
+        self.model = model.into();
+        self
+    }
+
+    /// Set the age at which the recency signal halves
+    pub fn with_recency_half_life(mut self, half_life: Duration) -> Self {
+        self.recency_half_life = half_life;
+        self
+    }
+
+    /// Set the smallest remaining budget worth filling with a truncated entry
+    pub fn with_min_truncated_tokens(mut self, tokens: usize) -> Self {
+        self.min_truncated_tokens = tokens;
+        self
+    }
+
+    /// Resolve relative candidate paths against `root` when reading from disk
+    pub fn with_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.root = Some(root.into());
+        self
+    }
+
+    /// Run `search` for the task, add its hits to the request, and pack
+    pub async fn pack_with_search(
+        &self,
+        request: ContextRequest,
+        search: &SemanticCodeSearch,
+        limit: usize,
+    ) -> Result<ContextManifest, ResearchError> {
+        let hits = search.search(&request.task, limit).await?;
+        self.pack(&request.with_search_hits(hits))
+    }
+
+    /// Pack the request's candidates into its token budget
+    pub fn pack(&self, request: &ContextRequest) -> Result<ContextManifest, ResearchError> {
+        let now = Utc::now();
+        let mut candidates = self.file_candidates(request, now);
+        candidates.extend(self.hit_candidates(request, now));
+        candidates.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.path.cmp(&b.path))
+                .then_with(|| a.start_line.cmp(&b.start_line))
+        });
+        if let Some(standards) = &request.standards {
+            candidates.insert(0, standards_candidate(standards));
+        }
+
+        let mut manifest = ContextManifest {
+            task: request.task.clone(),
+            budget: request.budget,
+            used_tokens: 0,
+            entries: Vec::new(),
+            dropped: Vec::new(),
+            deduplicated: 0,
+            created_at: now,
+        };
+        // Packed line ranges per file, with the index of the owning entry
+        let mut covered: HashMap<PathBuf, Vec<(usize, usize, usize)>> = HashMap::new();
+
+        for candidate in candidates {
+            let ranges = match &candidate.path {
+                Some(path) => covered.get(path).map(Vec::as_slice).unwrap_or_default(),
+                None => &[],
+            };
+            let segments = uncovered_segments(candidate.start_line, candidate.end_line(), ranges);
+            if segments.is_empty() {
+                // Fully covered: credit the signals to the entries that hold the lines
+                for &(start, end, entry) in ranges {
+                    if start <= candidate.end_line() && candidate.start_line <= end {
+                        manifest.entries[entry]
+                            .provenance
+                            .extend(candidate.provenance.iter().cloned());
+                    }
+                }
+                manifest.deduplicated += 1;
+                continue;
+            }
+
+            for (start, end) in segments {
+                let offset = start - candidate.start_line;
+                let lines = &candidate.lines[offset..=end - candidate.start_line];
+                let kind = if candidate.kind == ContextItemKind::File
+                    && (start, end) != (candidate.start_line, candidate.end_line())
+                {
+                    ContextItemKind::Snippet
+                } else {
+                    candidate.kind
+                };
+                match self.fit(lines, manifest.remaining_tokens())? {
+                    Some((content, tokens, included)) => {
+                        let end = start + included - 1;
+                        let (start_line, end_line) = if kind == ContextItemKind::Standards {
+                            (0, 0)
+                        } else {
+                            (start, end)
+                        };
+                        if let Some(path) = &candidate.path {
+                            covered.entry(path.clone()).or_default().push((
+                                start,
+                                end,
+                                manifest.entries.len(),
+                            ));
+                        }
+                        manifest.used_tokens += tokens;
+                        manifest.entries.push(PackedEntry {
+                            kind,
+                            path: candidate.path.clone(),
+                            start_line,
+                            end_line,
+                            tokens,
+                            score: candidate.score,
+                            truncated: included < lines.len(),
+                            content,
+                            provenance: candidate.provenance.clone(),
+                        });
+                    }
+                    None => {
+                        let tokens = self.count(&lines.join("\n"))?;
+                        manifest.dropped.push(DroppedEntry {
+                            kind,
+                            path: candidate.path.clone(),
+                            start_line: start,
+                            end_line: end,
+                            tokens,
+                            score: candidate.score,
+                            reason: format!(
+                                "needs {} tokens, {} remaining",
+                                tokens,
+                                manifest.remaining_tokens()
+                            ),
+                        });
+                    }
+                }
+            }
+        }
+
+        debug!(
+            "Packed {} entries ({} of {} tokens) for task, dropped {}, deduplicated {}",
+            manifest.entries.len(),
+            manifest.used_tokens,
+            manifest.budget,
+            manifest.dropped.len(),
+            manifest.deduplicated
+        );
+        Ok(manifest)
+    }
+
+    fn count(&self, content: &str) -> Result<usize, ResearchError> {
+        self.counter
+            .count_tokens(content, &self.model)
+            .map_err(|e| ResearchError::AnalysisFailed {
+                reason: format!("Token counting failed: {}", e),
+                context: "Packing request context".to_string(),
+            })
+    }
+
+    /// Fit as many leading lines as the remaining budget allows
+    ///
+    /// Returns the content, its tokens and the number of lines included, or
+    /// `None` when not even a worthwhile truncated prefix fits.
+    fn fit(
+        &self,
+        lines: &[String],
+        remaining: usize,
+    ) -> Result<Option<(String, usize, usize)>, ResearchError> {
+        let content = lines.join("\n");
+        let tokens = self.count(&content)?;
+        if tokens <= remaining {
+            return Ok(Some((content, tokens, lines.len())));
+        }
+        if remaining < self.min_truncated_tokens.max(1) {
+            return Ok(None);
+        }
+
+        // Grow the prefix line by line, then trim if the joined count overshoots
+        let mut included = 0;
+        let mut estimate = 0;
+        for line in lines {
+            let line_tokens = self.count(line)? + 1;
+            if estimate + line_tokens > remaining {
+                break;
+            }
+            estimate += line_tokens;
+            included += 1;
+        }
+        while included > 0 {
+            let content = lines[..included].join("\n");
+            let tokens = self.count(&content)?;
+            if tokens <= remaining {
+                return Ok(Some((content, tokens, included)));
+            }
+            included -= 1;
+        }
+        Ok(None)
+    }
+
+    fn file_candidates(&self, request: &ContextRequest, now: DateTime<Utc>) -> Vec<Candidate> {
+        let mut candidates = Vec::new();
+        for file in &request.files {
+            let content = match &file.content {
+                Some(content) => content.clone(),
+                None => match self.read(&file.path) {
+                    Some(content) => content,
+                    None => continue,
+                },
+            };
+            let lines: Vec<String> = content.lines().map(str::to_string).collect();
+            if lines.is_empty() {
+                continue;
+            }
+
+            // Score against the loaded content so files without inline content still match
+            let loaded = FileContext {
+                content: Some(content),
+                ..file.clone()
+            };
+            let relevance = self
+                .scorer
+                .score_file(&loaded, &request.task)
+                .max(file.relevance)
+                .clamp(0.0, 1.0);
+            let mut provenance = Vec::new();
+            if relevance > 0.0 {
+                provenance.push(Provenance {
+                    source: ContextSource::Relevance,
+                    score: relevance,
+                    reason: format!("relevance {:.2} to the task", relevance),
+                });
+            }
+            let mut score = self.weights.relevance * relevance;
+            if let Some(recency) = self.recency(request, &file.path, now) {
+                score += self.weights.recency * recency.score;
+                provenance.push(recency);
+            }
+            if provenance.is_empty() {
+                continue;
+            }
+
+            candidates.push(Candidate {
+                kind: ContextItemKind::File,
+                path: Some(file.path.clone()),
+                start_line: 1,
+                lines,
+                score,
+                provenance,
+            });
+        }
+        candidates
+    }
+
+    fn hit_candidates(&self, request: &ContextRequest, now: DateTime<Utc>) -> Vec<Candidate> {
+        let mut file_lines: HashMap<&Path, Option<Vec<String>>> = HashMap::new();
+        let mut candidates = Vec::new();
+        for hit in &request.hits {
+            let lines = file_lines
+                .entry(hit.file.as_path())
+                .or_insert_with(|| {
+                    request
+                        .files
+                        .iter()
+                        .find(|file| file.path == hit.file)
+                        .and_then(|file| file.content.clone())
+                        .or_else(|| self.read(&hit.file))
+                        .map(|content| content.lines().map(str::to_string).collect())
+                })
+                .as_ref()
+                .filter(|lines| hit.start_line >= 1 && hit.end_line <= lines.len())
+                .map(|lines| lines[hit.start_line - 1..hit.end_line.max(hit.start_line)].to_vec())
+                .unwrap_or_else(|| hit.snippet.lines().map(str::to_string).collect());
+            if lines.is_empty() {
+                continue;
+            }
+
+            let (source, signal) = if hit.sources.contains(&HitSource::Semantic) {
+                (ContextSource::Semantic, hit.similarity.unwrap_or(hit.score))
+            } else {
+                (
+                    ContextSource::Keyword,
+                    hit.keyword_relevance.unwrap_or(hit.score),
+                )
+            };
+            let signal = signal.clamp(0.0, 1.0);
+            let mut provenance = vec![Provenance {
+                source,
+                score: signal,
+                reason: match &hit.symbol {
+                    Some(symbol) => format!("search hit `{}` at {}", symbol, hit.citation()),
+                    None => format!("search hit at {}", hit.citation()),
+                },
+            }];
+            let mut score = self.weights.semantic * signal;
+            if let Some(recency) = self.recency(request, &hit.file, now) {
+                score += self.weights.recency * recency.score;
+                provenance.push(recency);
+            }
+
+            candidates.push(Candidate {
+                kind: ContextItemKind::Snippet,
+                path: Some(hit.file.clone()),
+                start_line: hit.start_line.max(1),
+                lines,
+                score,
+                provenance,
+            });
+        }
+        candidates
+    }
+
+    fn recency(
+        &self,
+        request: &ContextRequest,
+        path: &Path,
+        now: DateTime<Utc>,
+    ) -> Option<Provenance> {
+        let modified = request.modified.get(path)?;
+        let age = (now - *modified).max(Duration::zero());
+        let half_life = self.recency_half_life.num_seconds().max(1) as f32;
+        let score = 0.5f32.powf(age.num_seconds() as f32 / half_life);
+        Some(Provenance {
+            source: ContextSource::Recency,
+            score,
+            reason: format!("modified {}", describe_age(age)),
+        })
+    }
+
+    fn read(&self, path: &Path) -> Option<String> {
+        let path = match &self.root {
+            Some(root) if path.is_relative() => root.join(path),
+            _ => path.to_path_buf(),
+        };
+        fs::read_to_string(path).ok()
+    }
+}
+
+impl Default for ContextPacker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parts of `start..=end` not covered by any of `ranges`
+fn uncovered_segments(
+    start: usize,
+    end: usize,
+    ranges: &[(usize, usize, usize)],
+) -> Vec<(usize, usize)> {
+    let mut covered: Vec<(usize, usize)> = ranges
+        .iter()
+        .filter(|(s, e, _)| *s <= end && start <= *e)
+        .map(|&(s, e, _)| (s, e))
+        .collect();
+    covered.sort_unstable();
+
+    let mut segments = Vec::new();
+    let mut next = start;
+    for (s, e) in covered {
+        if s > next {
+            segments.push((next, s - 1));
+        }
+        next = next.max(e + 1);
+    }
+    if next <= end {
+        segments.push((next, end));
+    }
+    segments
+}
+
+fn standards_candidate(standards: &StandardsProfile) -> Candidate {
+    let naming = &standards.naming_conventions;
+    let formatting = &standards.formatting_style;
+    let docs = &standards.documentation_style;
+    let indent = match formatting.indent_type {
+        IndentType::Spaces => format!("{} spaces", formatting.indent_size),
+        IndentType::Tabs => "tabs".to_string(),
+    };
+    let lines = vec![
+        format!(
+            "Naming: functions {}, variables {}, types {}, constants {}",
+            case_name(naming.function_case),
+            case_name(naming.variable_case),
+            case_name(naming.class_case),
+            case_name(naming.constant_case)
+        ),
+        format!(
+            "Formatting: indent with {}, max line length {}",
+            indent, formatting.line_length
+        ),
+        format!(
+            "Imports: grouped as {}",
+            standards
+                .import_organization
+                .order
+                .iter()
+                .map(|group| format!("{:?}", group).to_lowercase())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        format!(
+            "Documentation: {}{}",
+            match docs.format {
+                DocFormat::JavaDoc => "JavaDoc",
+                DocFormat::RustDoc => "rustdoc",
+                DocFormat::JSDoc => "JSDoc",
+                DocFormat::PythonDoc => "docstrings",
+            },
+            if docs.required_for_public {
+                ", required for public items"
+            } else {
+                ""
+            }
+        ),
+    ];
+    Candidate {
+        kind: ContextItemKind::Standards,
+        path: None,
+        start_line: 0,
+        lines,
+        score: 1.0,
+        provenance: vec![Provenance {
+            source: ContextSource::Standards,
+            score: 1.0,
+            reason: "detected project standards".to_string(),
+        }],
+    }
+}
+
+fn case_name(case: CaseStyle) -> &'static str {
+    match case {
+        CaseStyle::CamelCase => "camelCase",
+        CaseStyle::SnakeCase => "snake_case",
+        CaseStyle::PascalCase => "PascalCase",
+        CaseStyle::KebabCase => "kebab-case",
+        CaseStyle::UpperCase => "UPPER_CASE",
+        CaseStyle::Mixed => "mixed case",
+    }
+}
+
+fn describe_age(age: Duration) -> String {
+    if age.num_days() > 0 {
+        format!("{} days ago", age.num_days())
+    } else if age.num_hours() > 0 {
+        format!("{} hours ago", age.num_hours())
+    } else {
+        format!("{} minutes ago", age.num_minutes())
+    }
+}
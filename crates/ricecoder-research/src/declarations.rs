@@ -0,0 +1,84 @@
+//! Line-based declaration scanning shared by the incremental analyzer and
+//! semantic code search
+
+use std::{path::Path, sync::OnceLock};
+
+use regex::Regex;
+
+use crate::models::{Language, Symbol, SymbolKind};
+
+/// Language of a source file, for the languages symbols can be extracted from
+pub(crate) fn language_for(path: &Path) -> Option<Language> {
+    match path.extension()?.to_str()? {
+        "rs" => Some(Language::Rust),
+        "py" => Some(Language::Python),
+        "ts" | "tsx" | "js" | "jsx" | "mjs" => Some(Language::TypeScript),
+        "go" => Some(Language::Go),
+        _ => None,
+    }
+}
+
+fn declaration_regex(language: &Language) -> Option<&'static Regex> {
+    static RUST: OnceLock<Regex> = OnceLock::new();
+    static PYTHON: OnceLock<Regex> = OnceLock::new();
+    static TYPESCRIPT: OnceLock<Regex> = OnceLock::new();
+    static GO: OnceLock<Regex> = OnceLock::new();
+
+    let (cell, pattern) = match language {
+        Language::Rust => (
+            &RUST,
+            r#"^\s*(?:pub(?:\([^)]*\))?\s+)?(?:(?:async|const|unsafe|extern\s+"[^"]*")\s+)*(fn|struct|enum|trait|type|const|static|mod)\s+([A-Za-z_][A-Za-z0-9_]*)"#,
+        ),
+        Language::Python => (
+            &PYTHON,
+            r"^\s*(?:async\s+)?(def|class)\s+([A-Za-z_][A-Za-z0-9_]*)",
+        ),
+        Language::TypeScript => (
+            &TYPESCRIPT,
+            r"^\s*(?:export\s+)?(?:default\s+)?(?:async\s+)?(function|class|interface|type|enum|const)\s+([A-Za-z_$][A-Za-z0-9_$]*)",
+        ),
+        Language::Go => (
+            &GO,
+            r"^(func|type)\s+(?:\([^)]*\)\s*)?([A-Za-z_][A-Za-z0-9_]*)",
+        ),
+        _ => return None,
+    };
+    Some(cell.get_or_init(|| Regex::new(pattern).expect("valid declaration regex")))
+}
+
+/// Extract top-level declarations with a line-based scan
+pub(crate) fn extract_symbols(path: &Path, language: &Language, content: &str) -> Vec<Symbol> {
+    let regex = match declaration_regex(language) {
+        Some(regex) => regex,
+        None => return Vec::new(),
+    };
+
+    let mut symbols = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let captures = match regex.captures(line) {
+            Some(captures) => captures,
+            None => continue,
+        };
+        let keyword = &captures[1];
+        let name = captures.get(2).expect("name group");
+        let kind = match keyword {
+            "struct" | "class" => SymbolKind::Class,
+            "enum" => SymbolKind::Enum,
+            "trait" | "interface" => SymbolKind::Trait,
+            "type" => SymbolKind::Type,
+            "const" | "static" => SymbolKind::Constant,
+            "mod" => SymbolKind::Module,
+            _ => SymbolKind::Function,
+        };
+        symbols.push(Symbol {
+            id: format!("{}:{}:{}", path.display(), index + 1, name.as_str()),
+            name: name.as_str().to_string(),
+            kind,
+            file: path.to_path_buf(),
+            line: index + 1,
+            column: name.start() + 1,
+            references: Vec::new(),
+        });
+    }
+    symbols
+}
@@ -0,0 +1,1628 @@
+//! Dependency manifest analysis and vulnerability awareness
+//!
+//! Parses `Cargo.toml`, `package.json`, `pyproject.toml` and `go.mod`
+//! manifests, resolves the installed versions from the matching lockfiles
+//! (`Cargo.lock`, `package-lock.json`, `yarn.lock`, `poetry.lock`, `uv.lock`),
+//! and cross-references them with an [OSV](https://osv.dev) vulnerability
+//! database, either the public API ([`OsvClient`]) or a local export
+//! ([`OfflineOsvDatabase`]).
+
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, OnceLock, PoisonError, RwLock},
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use ignore::WalkBuilder;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::{error::ResearchError, models::Dependency};
+
+/// Base URL of the public OSV API
+pub const OSV_API_URL: &str = "https://api.osv.dev";
+
+/// Manifest file names the analyzer understands
+const MANIFEST_FILES: &[&str] = &["Cargo.toml", "package.json", "pyproject.toml", "go.mod"];
+
+/// Directories holding installed or generated code rather than project manifests
+const SKIPPED_DIRS: &[&str] = &["node_modules", "target", "vendor", ".venv", "venv", "dist"];
+
+/// Package registry a dependency is resolved from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum PackageEcosystem {
+    /// Rust crates from crates.io
+    CratesIo,
+    /// JavaScript packages from npm
+    Npm,
+    /// Python packages from PyPI
+    PyPI,
+    /// Go modules
+    Go,
+}
+
+impl PackageEcosystem {
+    /// Ecosystem name used by OSV
+    pub fn osv_name(&self) -> &'static str {
+        match self {
+            PackageEcosystem::CratesIo => "crates.io",
+            PackageEcosystem::Npm => "npm",
+            PackageEcosystem::PyPI => "PyPI",
+            PackageEcosystem::Go => "Go",
+        }
+    }
+
+    /// Ecosystem for an OSV ecosystem name (ignoring `:suffix` qualifiers)
+    pub fn from_osv_name(name: &str) -> Option<Self> {
+        match name.split(':').next().unwrap_or(name) {
+            "crates.io" => Some(PackageEcosystem::CratesIo),
+            "npm" => Some(PackageEcosystem::Npm),
+            "PyPI" => Some(PackageEcosystem::PyPI),
+            "Go" => Some(PackageEcosystem::Go),
+            _ => None,
+        }
+    }
+
+    /// Canonical form of a package name, as used for matching
+    pub fn normalize_name(&self, name: &str) -> String {
+        match self {
+            // PEP 503 normalization
+            PackageEcosystem::PyPI => name
+                .to_lowercase()
+                .split(['-', '_', '.'])
+                .filter(|part| !part.is_empty())
+                .collect::<Vec<_>>()
+                .join("-"),
+            _ => name.to_string(),
+        }
+    }
+}
+
+/// A dependency declared in a manifest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestDependency {
+    /// Package name in its registry
+    pub name: String,
+    /// Registry the package comes from
+    pub ecosystem: PackageEcosystem,
+    /// Version requirement as written in the manifest
+    pub requirement: Option<String>,
+    /// Exact version from the lockfile (or an exact pin in the manifest)
+    pub resolved_version: Option<String>,
+    /// Whether this is a development-only dependency
+    pub is_dev: bool,
+    /// Whether the manifest requires it directly (Go marks indirect requirements)
+    pub direct: bool,
+    /// Manifest declaring the dependency
+    pub manifest: PathBuf,
+    /// Lockfile the version was resolved from
+    pub lockfile: Option<PathBuf>,
+}
+
+impl ManifestDependency {
+    fn new(
+        name: impl Into<String>,
+        ecosystem: PackageEcosystem,
+        requirement: Option<String>,
+        is_dev: bool,
+        manifest: &Path,
+    ) -> Self {
+        ManifestDependency {
+            name: name.into(),
+            ecosystem,
+            requirement,
+            resolved_version: None,
+            is_dev,
+            direct: true,
+            manifest: manifest.to_path_buf(),
+            lockfile: None,
+        }
+    }
+
+    /// Convert to the project context dependency model
+    pub fn to_dependency(&self) -> Dependency {
+        Dependency {
+            name: self.name.clone(),
+            version: self
+                .resolved_version
+                .clone()
+                .or_else(|| self.requirement.clone())
+                .unwrap_or_default(),
+            constraints: self.requirement.clone(),
+            is_dev: self.is_dev,
+        }
+    }
+}
+
+/// Whether `path` is a dependency manifest or lockfile the analyzer reads
+pub fn is_dependency_file(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+    MANIFEST_FILES.contains(&name)
+        || matches!(
+            name,
+            "Cargo.lock" | "package-lock.json" | "yarn.lock" | "poetry.lock" | "uv.lock" | "go.sum"
+        )
+}
+
+// ============================================================================
+// OSV records
+// ============================================================================
+
+/// Severity of a vulnerability advisory
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum AdvisorySeverity {
+    /// No severity information available
+    Unknown,
+    /// CVSS 0.1-3.9
+    Low,
+    /// CVSS 4.0-6.9
+    Medium,
+    /// CVSS 7.0-8.9
+    High,
+    /// CVSS 9.0-10.0
+    Critical,
+}
+
+impl AdvisorySeverity {
+    /// Severity band for a CVSS base score
+    pub fn from_cvss_score(score: f64) -> Self {
+        match score {
+            s if s >= 9.0 => AdvisorySeverity::Critical,
+            s if s >= 7.0 => AdvisorySeverity::High,
+            s if s >= 4.0 => AdvisorySeverity::Medium,
+            s if s > 0.0 => AdvisorySeverity::Low,
+            _ => AdvisorySeverity::Unknown,
+        }
+    }
+
+    fn from_label(label: &str) -> Self {
+        match label.to_uppercase().as_str() {
+            "CRITICAL" => AdvisorySeverity::Critical,
+            "HIGH" => AdvisorySeverity::High,
+            "MODERATE" | "MEDIUM" => AdvisorySeverity::Medium,
+            "LOW" => AdvisorySeverity::Low,
+            _ => AdvisorySeverity::Unknown,
+        }
+    }
+}
+
+/// An OSV vulnerability record
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OsvVulnerability {
+    /// Advisory ID (e.g. `GHSA-...`, `RUSTSEC-...`)
+    pub id: String,
+    /// One-line summary
+    #[serde(default)]
+    pub summary: Option<String>,
+    /// Full description
+    #[serde(default)]
+    pub details: Option<String>,
+    /// Other IDs for the same issue, such as CVE numbers
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    /// Last modification time
+    #[serde(default)]
+    pub modified: Option<String>,
+    /// Affected packages and versions
+    #[serde(default)]
+    pub affected: Vec<OsvAffected>,
+    /// Severity scores
+    #[serde(default)]
+    pub severity: Vec<OsvSeverity>,
+    /// Reference links
+    #[serde(default)]
+    pub references: Vec<OsvReference>,
+    /// Database-specific metadata
+    #[serde(default)]
+    pub database_specific: Option<serde_json::Value>,
+}
+
+/// A package affected by an OSV vulnerability
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OsvAffected {
+    /// The affected package
+    pub package: OsvPackage,
+    /// Affected version ranges
+    #[serde(default)]
+    pub ranges: Vec<OsvRange>,
+    /// Explicitly enumerated affected versions
+    #[serde(default)]
+    pub versions: Vec<String>,
+    /// Ecosystem-specific metadata
+    #[serde(default)]
+    pub ecosystem_specific: Option<serde_json::Value>,
+    /// Database-specific metadata
+    #[serde(default)]
+    pub database_specific: Option<serde_json::Value>,
+}
+
+/// Package identity in an OSV record
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OsvPackage {
+    /// OSV ecosystem name
+    pub ecosystem: String,
+    /// Package name
+    pub name: String,
+}
+
+/// An OSV affected version range
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OsvRange {
+    /// Range type (`SEMVER`, `ECOSYSTEM` or `GIT`)
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Range events in the record's order
+    #[serde(default)]
+    pub events: Vec<OsvEvent>,
+}
+
+/// A version event in an OSV range
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OsvEvent {
+    /// Versions from this one are affected
+    Introduced(String),
+    /// Versions from this one are fixed
+    Fixed(String),
+    /// Last affected version (inclusive)
+    LastAffected(String),
+    /// Upper limit of the range
+    Limit(String),
+}
+
+/// A severity score in an OSV record
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OsvSeverity {
+    /// Score type (`CVSS_V3`, `CVSS_V4`, ...)
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Score vector or value
+    pub score: String,
+}
+
+/// A reference link in an OSV record
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OsvReference {
+    /// Reference type (`ADVISORY`, `FIX`, `WEB`, ...)
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Link target
+    pub url: String,
+}
+
+impl OsvVulnerability {
+    /// Highest CVSS v3 base score computed from the record's vectors
+    pub fn cvss_score(&self) -> Option<f64> {
+        self.severity
+            .iter()
+            .filter_map(|severity| match severity.score.parse::<f64>() {
+                Ok(score) => Some(score),
+                Err(_) => cvss3_base_score(&severity.score),
+            })
+            .max_by(f64::total_cmp)
+    }
+
+    /// Severity from CVSS scores, falling back to database severity labels
+    pub fn severity_level(&self) -> AdvisorySeverity {
+        if let Some(score) = self.cvss_score() {
+            return AdvisorySeverity::from_cvss_score(score);
+        }
+        let label = |value: &Option<serde_json::Value>| {
+            value
+                .as_ref()
+                .and_then(|value| value.get("severity"))
+                .and_then(|severity| severity.as_str())
+                .map(AdvisorySeverity::from_label)
+        };
+        label(&self.database_specific)
+            .into_iter()
+            .chain(self.affected.iter().flat_map(|affected| {
+                [
+                    label(&affected.database_specific),
+                    label(&affected.ecosystem_specific),
+                ]
+                .into_iter()
+                .flatten()
+            }))
+            .max()
+            .unwrap_or(AdvisorySeverity::Unknown)
+    }
+
+    /// Whether `version` of the package is affected
+    pub fn affects(&self, ecosystem: PackageEcosystem, name: &str, version: &str) -> bool {
+        self.affected_entries(ecosystem, name).any(|affected| {
+            affected.versions.iter().any(|v| same_version(v, version))
+                || affected
+                    .ranges
+                    .iter()
+                    .filter(|range| range.kind != "GIT")
+                    .any(|range| range_contains(&range.events, version))
+        })
+    }
+
+    /// Versions that fix the vulnerability for the package
+    pub fn fixed_versions(&self, ecosystem: PackageEcosystem, name: &str) -> Vec<String> {
+        let mut fixed: Vec<String> = self
+            .affected_entries(ecosystem, name)
+            .flat_map(|affected| affected.ranges.iter())
+            .flat_map(|range| range.events.iter())
+            .filter_map(|event| match event {
+                OsvEvent::Fixed(version) => Some(version.clone()),
+                _ => None,
+            })
+            .collect();
+        fixed.sort_by(|a, b| compare_versions(a, b));
+        fixed.dedup();
+        fixed
+    }
+
+    fn affected_entries<'a>(
+        &'a self,
+        ecosystem: PackageEcosystem,
+        name: &'a str,
+    ) -> impl Iterator<Item = &'a OsvAffected> + 'a {
+        let name = ecosystem.normalize_name(name);
+        self.affected.iter().filter(move |affected| {
+            PackageEcosystem::from_osv_name(&affected.package.ecosystem) == Some(ecosystem)
+                && ecosystem.normalize_name(&affected.package.name) == name
+        })
+    }
+}
+
+/// Whether a version lies within an OSV range's events
+fn range_contains(events: &[OsvEvent], version: &str) -> bool {
+    let key = |event: &OsvEvent| match event {
+        OsvEvent::Introduced(v)
+        | OsvEvent::Fixed(v)
+        | OsvEvent::LastAffected(v)
+        | OsvEvent::Limit(v) => v.clone(),
+    };
+    let mut sorted: Vec<&OsvEvent> = events.iter().collect();
+    sorted.sort_by(|a, b| compare_versions(&key(a), &key(b)));
+
+    let mut affected = false;
+    for event in sorted {
+        match event {
+            OsvEvent::Introduced(v) => {
+                if v == "0" || compare_versions(v, version) != Ordering::Greater {
+                    affected = true;
+                }
+            }
+            OsvEvent::Fixed(v) => {
+                if compare_versions(v, version) != Ordering::Greater {
+                    affected = false;
+                }
+            }
+            OsvEvent::LastAffected(v) => {
+                if compare_versions(v, version) == Ordering::Less {
+                    affected = false;
+                }
+            }
+            OsvEvent::Limit(v) => {
+                if compare_versions(v, version) != Ordering::Greater {
+                    return false;
+                }
+            }
+        }
+    }
+    affected
+}
+
+fn same_version(a: &str, b: &str) -> bool {
+    compare_versions(a, b) == Ordering::Equal
+}
+
+/// Segment of a version string used for ordering
+#[derive(Debug, PartialEq, Eq)]
+enum VersionPart<'a> {
+    Number(u64),
+    Text(&'a str),
+}
+
+fn version_parts(version: &str) -> Vec<VersionPart<'_>> {
+    static PARTS: OnceLock<Regex> = OnceLock::new();
+    let re = PARTS.get_or_init(|| Regex::new(r"\d+|[A-Za-z]+").expect("valid regex"));
+    let version = version.trim().trim_start_matches(['v', '=']);
+    let version = version.split('+').next().unwrap_or(version);
+    re.find_iter(version)
+        .map(|part| match part.as_str().parse() {
+            Ok(number) => VersionPart::Number(number),
+            Err(_) => VersionPart::Text(part.as_str()),
+        })
+        .collect()
+}
+
+/// Order two version strings
+///
+/// Numeric segments compare numerically; textual segments mark pre-releases
+/// (`1.0.0-rc1 < 1.0.0`) except `post`, which sorts after the release. This
+/// covers SemVer, PEP 440 and Go pseudo-versions well enough for range checks.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let a = version_parts(a);
+    let b = version_parts(b);
+    for index in 0..a.len().max(b.len()) {
+        let ordering = match (a.get(index), b.get(index)) {
+            (Some(VersionPart::Number(x)), Some(VersionPart::Number(y))) => x.cmp(y),
+            (Some(VersionPart::Text(x)), Some(VersionPart::Text(y))) => x.cmp(y),
+            // A release segment outranks a pre-release tag at the same position
+            (Some(VersionPart::Number(_)), Some(VersionPart::Text(_))) => Ordering::Greater,
+            (Some(VersionPart::Text(_)), Some(VersionPart::Number(_))) => Ordering::Less,
+            (Some(VersionPart::Number(x)), None) => {
+                if *x == 0 {
+                    Ordering::Equal
+                } else {
+                    Ordering::Greater
+                }
+            }
+            (None, Some(VersionPart::Number(y))) => {
+                if *y == 0 {
+                    Ordering::Equal
+                } else {
+                    Ordering::Less
+                }
+            }
+            (Some(VersionPart::Text(x)), None) => post_release_order(x),
+            (None, Some(VersionPart::Text(y))) => post_release_order(y).reverse(),
+            (None, None) => Ordering::Equal,
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    Ordering::Equal
+}
+
+fn post_release_order(tag: &str) -> Ordering {
+    if tag.eq_ignore_ascii_case("post") {
+        Ordering::Greater
+    } else {
+        Ordering::Less
+    }
+}
+
+/// CVSS v3.x base score from a vector string
+fn cvss3_base_score(vector: &str) -> Option<f64> {
+    if !vector.starts_with("CVSS:3") {
+        return None;
+    }
+    let metrics: HashMap<&str, &str> = vector
+        .split('/')
+        .skip(1)
+        .filter_map(|metric| metric.split_once(':'))
+        .collect();
+    let changed = *metrics.get("S")? == "C";
+    let av = match *metrics.get("AV")? {
+        "N" => 0.85,
+        "A" => 0.62,
+        "L" => 0.55,
+        "P" => 0.2,
+        _ => return None,
+    };
+    let ac = match *metrics.get("AC")? {
+        "L" => 0.77,
+        "H" => 0.44,
+        _ => return None,
+    };
+    let pr = match (*metrics.get("PR")?, changed) {
+        ("N", _) => 0.85,
+        ("L", false) => 0.62,
+        ("L", true) => 0.68,
+        ("H", false) => 0.27,
+        ("H", true) => 0.5,
+        _ => return None,
+    };
+    let ui = match *metrics.get("UI")? {
+        "N" => 0.85,
+        "R" => 0.62,
+        _ => return None,
+    };
+    let impact_of = |metric: &str| match metrics.get(metric).copied() {
+        Some("H") => Some(0.56),
+        Some("L") => Some(0.22),
+        Some("N") => Some(0.0),
+        _ => None,
+    };
+    let iss = 1.0 - (1.0 - impact_of("C")?) * (1.0 - impact_of("I")?) * (1.0 - impact_of("A")?);
+    let impact = if changed {
+        7.52 * (iss - 0.029) - 3.25 * (iss - 0.02f64).powi(15)
+    } else {
+        6.42 * iss
+    };
+    if impact <= 0.0 {
+        return Some(0.0);
+    }
+    let exploitability = 8.22 * av * ac * pr * ui;
+    let score = if changed {
+        (1.08 * (impact + exploitability)).min(10.0)
+    } else {
+        (impact + exploitability).min(10.0)
+    };
+    // Round up to one decimal, guarding against floating point noise
+    Some(((score * 100_000.0).round() / 10_000.0).ceil() / 10.0)
+}
+
+// ============================================================================
+// Vulnerability databases
+// ============================================================================
+
+/// A package version to check for known vulnerabilities
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PackageQuery {
+    /// Package registry
+    pub ecosystem: PackageEcosystem,
+    /// Package name
+    pub name: String,
+    /// Exact installed version
+    pub version: String,
+}
+
+/// Source of vulnerability records in OSV format
+#[async_trait]
+pub trait VulnerabilityDatabase: Send + Sync {
+    /// Vulnerabilities affecting each query, in query order
+    async fn query(
+        &self,
+        packages: &[PackageQuery],
+    ) -> Result<Vec<Vec<OsvVulnerability>>, ResearchError>;
+}
+
+/// Vulnerability database over locally loaded OSV records
+///
+/// Useful offline and in CI, with records from an OSV ecosystem export
+/// (`https://osv-vulnerabilities.storage.googleapis.com/<ecosystem>/all.zip`).
+#[derive(Debug, Default)]
+pub struct OfflineOsvDatabase {
+    records: RwLock<Vec<OsvVulnerability>>,
+}
+
+impl OfflineOsvDatabase {
+    /// Create an empty database
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a vulnerability record
+    pub fn add(&self, vulnerability: OsvVulnerability) {
+        self.records
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(vulnerability);
+    }
+
+    /// Add records from JSON holding one OSV record or an array of them
+    pub fn add_json(&self, json: &str) -> Result<usize, ResearchError> {
+        let value: serde_json::Value = serde_json::from_str(json)?;
+        let records: Vec<OsvVulnerability> = match value {
+            serde_json::Value::Array(_) => serde_json::from_value(value)?,
+            _ => vec![serde_json::from_value(value)?],
+        };
+        let count = records.len();
+        for record in records {
+            self.add(record);
+        }
+        Ok(count)
+    }
+
+    /// Load every `*.json` OSV record in a directory tree
+    pub fn load_dir(&self, dir: &Path) -> Result<usize, ResearchError> {
+        let mut loaded = 0;
+        for entry in walkdir::WalkDir::new(dir)
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().is_file())
+            .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+        {
+            let content = fs::read_to_string(entry.path()).map_err(|e| ResearchError::IoError {
+                reason: format!("Failed to read {}: {}", entry.path().display(), e),
+            })?;
+            match self.add_json(&content) {
+                Ok(count) => loaded += count,
+                Err(e) => warn!("Skipping invalid OSV record {:?}: {}", entry.path(), e),
+            }
+        }
+        Ok(loaded)
+    }
+
+    /// Number of loaded records
+    pub fn len(&self) -> usize {
+        self.records
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    /// Whether no records are loaded
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[async_trait]
+impl VulnerabilityDatabase for OfflineOsvDatabase {
+    async fn query(
+        &self,
+        packages: &[PackageQuery],
+    ) -> Result<Vec<Vec<OsvVulnerability>>, ResearchError> {
+        let records = self.records.read().unwrap_or_else(PoisonError::into_inner);
+        Ok(packages
+            .iter()
+            .map(|package| {
+                records
+                    .iter()
+                    .filter(|record| {
+                        record.affects(package.ecosystem, &package.name, &package.version)
+                    })
+                    .cloned()
+                    .collect()
+            })
+            .collect())
+    }
+}
+
+/// Client for the public OSV API
+#[derive(Debug)]
+pub struct OsvClient {
+    http: reqwest::Client,
+    base_url: String,
+    batch_size: usize,
+    records: RwLock<HashMap<String, OsvVulnerability>>,
+}
+
+#[derive(Serialize)]
+struct OsvBatchQuery<'a> {
+    queries: Vec<OsvQuery<'a>>,
+}
+
+#[derive(Serialize)]
+struct OsvQuery<'a> {
+    package: OsvQueryPackage<'a>,
+    version: &'a str,
+}
+
+#[derive(Serialize)]
+struct OsvQueryPackage<'a> {
+    name: &'a str,
+    ecosystem: &'a str,
+}
+
+#[derive(Deserialize)]
+struct OsvBatchResponse {
+    #[serde(default)]
+    results: Vec<OsvBatchResult>,
+}
+
+#[derive(Deserialize)]
+struct OsvBatchResult {
+    #[serde(default)]
+    vulns: Vec<OsvVulnerabilityId>,
+}
+
+#[derive(Deserialize)]
+struct OsvVulnerabilityId {
+    id: String,
+}
+
+impl OsvClient {
+    /// Create a client for the public OSV API
+    pub fn new() -> Self {
+        OsvClient {
+            http: reqwest::Client::new(),
+            base_url: OSV_API_URL.to_string(),
+            batch_size: 500,
+            records: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Use a different API endpoint (e.g. a mirror)
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Set how many packages are sent per batch request
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.clamp(1, 1000);
+        self
+    }
+
+    fn request_error(e: impl std::fmt::Display) -> ResearchError {
+        ResearchError::AnalysisFailed {
+            reason: format!("OSV request failed: {}", e),
+            context: "Querying vulnerability database".to_string(),
+        }
+    }
+
+    async fn fetch(&self, id: &str) -> Result<OsvVulnerability, ResearchError> {
+        if let Some(record) = self
+            .records
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(id)
+        {
+            return Ok(record.clone());
+        }
+        let record: OsvVulnerability = self
+            .http
+            .get(format!("{}/v1/vulns/{}", self.base_url, id))
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(Self::request_error)?
+            .json()
+            .await
+            .map_err(Self::request_error)?;
+        self.records
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(id.to_string(), record.clone());
+        Ok(record)
+    }
+}
+
+impl Default for OsvClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl VulnerabilityDatabase for OsvClient {
+    async fn query(
+        &self,
+        packages: &[PackageQuery],
+    ) -> Result<Vec<Vec<OsvVulnerability>>, ResearchError> {
+        let mut results = Vec::with_capacity(packages.len());
+        for batch in packages.chunks(self.batch_size) {
+            // The batch endpoint only returns IDs; full records are fetched (and cached) below
+            let body = OsvBatchQuery {
+                queries: batch
+                    .iter()
+                    .map(|package| OsvQuery {
+                        package: OsvQueryPackage {
+                            name: &package.name,
+                            ecosystem: package.ecosystem.osv_name(),
+                        },
+                        version: package.version.trim_start_matches('v'),
+                    })
+                    .collect(),
+            };
+            let response: OsvBatchResponse = self
+                .http
+                .post(format!("{}/v1/querybatch", self.base_url))
+                .json(&body)
+                .send()
+                .await
+                .and_then(reqwest::Response::error_for_status)
+                .map_err(Self::request_error)?
+                .json()
+                .await
+                .map_err(Self::request_error)?;
+
+            for index in 0..batch.len() {
+                let mut records = Vec::new();
+                if let Some(result) = response.results.get(index) {
+                    for vuln in &result.vulns {
+                        records.push(self.fetch(&vuln.id).await?);
+                    }
+                }
+                results.push(records);
+            }
+        }
+        Ok(results)
+    }
+}
+
+// ============================================================================
+// Reports
+// ============================================================================
+
+/// A known vulnerability affecting a project dependency
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DependencyFinding {
+    /// The affected dependency
+    pub dependency: ManifestDependency,
+    /// Advisory ID
+    pub advisory_id: String,
+    /// Alias IDs, including CVE numbers
+    pub aliases: Vec<String>,
+    /// Advisory summary
+    pub summary: String,
+    /// Severity band
+    pub severity: AdvisorySeverity,
+    /// CVSS base score, when available
+    pub cvss_score: Option<f64>,
+    /// Versions that fix the vulnerability
+    pub fixed_versions: Vec<String>,
+    /// Advisory and fix links
+    pub references: Vec<String>,
+}
+
+impl DependencyFinding {
+    /// CVE identifiers for the finding
+    pub fn cve_ids(&self) -> Vec<&str> {
+        std::iter::once(self.advisory_id.as_str())
+            .chain(self.aliases.iter().map(String::as_str))
+            .filter(|id| id.starts_with("CVE-"))
+            .collect()
+    }
+
+    /// Lowest fixed version newer than the installed one
+    pub fn recommended_version(&self) -> Option<&str> {
+        let installed = self.dependency.resolved_version.as_deref()?;
+        self.fixed_versions
+            .iter()
+            .find(|fixed| compare_versions(fixed, installed) == Ordering::Greater)
+            .map(String::as_str)
+    }
+}
+
+/// Dependencies of a project and the vulnerabilities affecting them
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DependencyReport {
+    /// Project root
+    pub root: PathBuf,
+    /// All declared dependencies
+    pub dependencies: Vec<ManifestDependency>,
+    /// Vulnerabilities found, most severe first
+    pub findings: Vec<DependencyFinding>,
+    /// Dependencies that could not be checked because no version was resolved
+    pub unresolved: Vec<String>,
+    /// Whether a vulnerability database was consulted
+    pub checked: bool,
+    /// When the report was produced
+    pub scanned_at: DateTime<Utc>,
+}
+
+impl DependencyReport {
+    /// Whether no vulnerabilities were found
+    pub fn is_clean(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    /// Highest severity among the findings
+    pub fn max_severity(&self) -> Option<AdvisorySeverity> {
+        self.findings.iter().map(|finding| finding.severity).max()
+    }
+
+    /// Findings at or above `severity`
+    pub fn findings_at_least(&self, severity: AdvisorySeverity) -> Vec<&DependencyFinding> {
+        self.findings
+            .iter()
+            .filter(|finding| finding.severity >= severity)
+            .collect()
+    }
+
+    /// The part of the report declared in the given manifests
+    ///
+    /// Lets reviewers of a change focus on the manifests it touches. Lockfile
+    /// paths select every manifest resolved from them.
+    pub fn for_files(&self, files: &[PathBuf]) -> DependencyReport {
+        let selected = |dependency: &ManifestDependency| {
+            files.iter().any(|file| {
+                file == &dependency.manifest || Some(file) == dependency.lockfile.as_ref()
+            })
+        };
+        let dependencies: Vec<ManifestDependency> = self
+            .dependencies
+            .iter()
+            .filter(|dependency| selected(dependency))
+            .cloned()
+            .collect();
+        let names: HashSet<&str> = dependencies.iter().map(|d| d.name.as_str()).collect();
+        DependencyReport {
+            root: self.root.clone(),
+            findings: self
+                .findings
+                .iter()
+                .filter(|finding| selected(&finding.dependency))
+                .cloned()
+                .collect(),
+            unresolved: self
+                .unresolved
+                .iter()
+                .filter(|name| names.contains(name.as_str()))
+                .cloned()
+                .collect(),
+            dependencies,
+            checked: self.checked,
+            scanned_at: self.scanned_at,
+        }
+    }
+
+    /// Serialize the report to pretty-printed JSON
+    pub fn to_json(&self) -> Result<String, ResearchError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+// ============================================================================
+// Analyzer
+// ============================================================================
+
+/// Parses dependency manifests and checks them against a vulnerability database
+#[derive(Clone, Default)]
+pub struct DependencyAnalyzer {
+    database: Option<Arc<dyn VulnerabilityDatabase>>,
+}
+
+impl std::fmt::Debug for DependencyAnalyzer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DependencyAnalyzer")
+            .field("database", &self.database.is_some())
+            .finish()
+    }
+}
+
+impl DependencyAnalyzer {
+    /// Create an analyzer that only reads manifests
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check dependencies against a vulnerability database
+    pub fn with_database(mut self, database: Arc<dyn VulnerabilityDatabase>) -> Self {
+        self.database = Some(database);
+        self
+    }
+
+    /// Find and parse every manifest under `root`
+    pub fn scan(&self, root: &Path) -> Result<Vec<ManifestDependency>, ResearchError> {
+        if !root.exists() {
+            return Err(ResearchError::ProjectNotFound {
+                path: root.to_path_buf(),
+                reason: "Directory does not exist or is not accessible".to_string(),
+            });
+        }
+
+        let mut manifests: Vec<PathBuf> = WalkBuilder::new(root)
+            .hidden(true)
+            .filter_entry(|entry| {
+                !entry
+                    .file_name()
+                    .to_str()
+                    .is_some_and(|name| SKIPPED_DIRS.contains(&name))
+            })
+            .build()
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().is_some_and(|t| t.is_file()))
+            .filter(|entry| {
+                entry
+                    .file_name()
+                    .to_str()
+                    .is_some_and(|name| MANIFEST_FILES.contains(&name))
+            })
+            .map(|entry| entry.into_path())
+            .collect();
+        manifests.sort();
+
+        let mut dependencies = Vec::new();
+        for manifest in manifests {
+            match self.parse_manifest_within(&manifest, root) {
+                Ok(parsed) => dependencies.extend(parsed),
+                Err(e) => warn!("Skipping manifest {:?}: {}", manifest, e),
+            }
+        }
+        debug!("Found {} dependencies under {:?}", dependencies.len(), root);
+        Ok(dependencies)
+    }
+
+    /// Parse one manifest, resolving versions from a lockfile next to it
+    pub fn parse_manifest(
+        &self,
+        manifest: &Path,
+    ) -> Result<Vec<ManifestDependency>, ResearchError> {
+        let dir = manifest.parent().unwrap_or(Path::new("."));
+        self.parse_manifest_within(manifest, dir)
+    }
+
+    /// Scan `root` and check every resolved dependency for vulnerabilities
+    pub async fn audit(&self, root: &Path) -> Result<DependencyReport, ResearchError> {
+        let dependencies = self.scan(root)?;
+        self.check(root, dependencies).await
+    }
+
+    /// Check already parsed dependencies for vulnerabilities
+    pub async fn check(
+        &self,
+        root: &Path,
+        dependencies: Vec<ManifestDependency>,
+    ) -> Result<DependencyReport, ResearchError> {
+        let mut unresolved: Vec<String> = dependencies
+            .iter()
+            .filter(|dependency| dependency.resolved_version.is_none())
+            .map(|dependency| dependency.name.clone())
+            .collect();
+        unresolved.sort();
+        unresolved.dedup();
+
+        let mut findings = Vec::new();
+        if let Some(database) = &self.database {
+            let mut queries: Vec<PackageQuery> = dependencies
+                .iter()
+                .filter_map(|dependency| {
+                    Some(PackageQuery {
+                        ecosystem: dependency.ecosystem,
+                        name: dependency.name.clone(),
+                        version: dependency.resolved_version.clone()?,
+                    })
+                })
+                .collect::<HashSet<_>>()
+                .into_iter()
+                .collect();
+            queries.sort_by(|a, b| {
+                (a.ecosystem, &a.name, &a.version).cmp(&(b.ecosystem, &b.name, &b.version))
+            });
+            let results = database.query(&queries).await?;
+            let by_package: HashMap<&PackageQuery, &Vec<OsvVulnerability>> =
+                queries.iter().zip(results.iter()).collect();
+
+            for dependency in &dependencies {
+                let Some(version) = &dependency.resolved_version else {
+                    continue;
+                };
+                let query = PackageQuery {
+                    ecosystem: dependency.ecosystem,
+                    name: dependency.name.clone(),
+                    version: version.clone(),
+                };
+                for record in by_package.get(&query).into_iter().flat_map(|r| r.iter()) {
+                    findings.push(DependencyFinding {
+                        dependency: dependency.clone(),
+                        advisory_id: record.id.clone(),
+                        aliases: record.aliases.clone(),
+                        summary: record
+                            .summary
+                            .clone()
+                            .or_else(|| record.details.clone())
+                            .unwrap_or_default(),
+                        severity: record.severity_level(),
+                        cvss_score: record.cvss_score(),
+                        fixed_versions: record
+                            .fixed_versions(dependency.ecosystem, &dependency.name),
+                        references: record.references.iter().map(|r| r.url.clone()).collect(),
+                    });
+                }
+            }
+            findings.sort_by(|a, b| {
+                b.severity
+                    .cmp(&a.severity)
+                    .then_with(|| a.dependency.name.cmp(&b.dependency.name))
+                    .then_with(|| a.advisory_id.cmp(&b.advisory_id))
+            });
+        }
+
+        Ok(DependencyReport {
+            root: root.to_path_buf(),
+            dependencies,
+            findings,
+            unresolved,
+            checked: self.database.is_some(),
+            scanned_at: Utc::now(),
+        })
+    }
+
+    fn parse_manifest_within(
+        &self,
+        manifest: &Path,
+        root: &Path,
+    ) -> Result<Vec<ManifestDependency>, ResearchError> {
+        let content = fs::read_to_string(manifest).map_err(|e| ResearchError::IoError {
+            reason: format!("Failed to read {}: {}", manifest.display(), e),
+        })?;
+        let file_name = manifest
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+        let parse_error = |language: &str, reason: String| ResearchError::DependencyParsingFailed {
+            language: language.to_string(),
+            path: Some(manifest.to_path_buf()),
+            reason,
+        };
+
+        match file_name {
+            "Cargo.toml" => {
+                let mut dependencies = parse_cargo_manifest(&content, manifest)
+                    .map_err(|reason| parse_error("Rust", reason))?;
+                if let Some(lockfile) = find_lockfile(manifest, root, &["Cargo.lock"]) {
+                    resolve_cargo(&mut dependencies, &lockfile)
+                        .map_err(|reason| parse_error("Rust", reason))?;
+                }
+                Ok(dependencies)
+            }
+            "package.json" => {
+                let mut dependencies = parse_package_json(&content, manifest)
+                    .map_err(|reason| parse_error("JavaScript", reason))?;
+                if let Some(lockfile) =
+                    find_lockfile(manifest, root, &["package-lock.json", "yarn.lock"])
+                {
+                    resolve_npm(&mut dependencies, manifest, &lockfile)
+                        .map_err(|reason| parse_error("JavaScript", reason))?;
+                }
+                Ok(dependencies)
+            }
+            "pyproject.toml" => {
+                let mut dependencies = parse_pyproject(&content, manifest)
+                    .map_err(|reason| parse_error("Python", reason))?;
+                if let Some(lockfile) = find_lockfile(manifest, root, &["poetry.lock", "uv.lock"]) {
+                    resolve_python(&mut dependencies, &lockfile)
+                        .map_err(|reason| parse_error("Python", reason))?;
+                }
+                Ok(dependencies)
+            }
+            "go.mod" => Ok(parse_go_mod(&content, manifest)),
+            _ => Err(ResearchError::InvalidConfiguration {
+                reason: format!("{} is not a supported manifest", manifest.display()),
+                expected: MANIFEST_FILES.join(", "),
+            }),
+        }
+    }
+}
+
+/// Nearest lockfile from the manifest's directory up to `root`
+fn find_lockfile(manifest: &Path, root: &Path, names: &[&str]) -> Option<PathBuf> {
+    let mut dir = manifest.parent();
+    while let Some(current) = dir {
+        for name in names {
+            let candidate = current.join(name);
+            if candidate.is_file() {
+                return Some(candidate);
+            }
+        }
+        if current == root || !current.starts_with(root) {
+            break;
+        }
+        dir = current.parent();
+    }
+    None
+}
+
+/// Exact version pinned by a requirement such as `=1.2.3` or `==1.2.3`
+fn pinned_version(requirement: &str) -> Option<String> {
+    static PINNED: OnceLock<Regex> = OnceLock::new();
+    let re = PINNED.get_or_init(|| {
+        Regex::new(r"^\s*===?\s*v?([0-9][0-9A-Za-z.+\-]*)\s*$").expect("valid regex")
+    });
+    re.captures(requirement).map(|caps| caps[1].to_string())
+}
+
+/// Pick the locked version matching a requirement among several candidates
+fn select_locked_version(requirement: Option<&str>, candidates: &[String]) -> Option<String> {
+    if candidates.len() <= 1 {
+        return candidates.first().cloned();
+    }
+    static LEADING: OnceLock<Regex> = OnceLock::new();
+    let re = LEADING.get_or_init(|| Regex::new(r"(\d+)(?:\.(\d+))?").expect("valid regex"));
+    let highest = |versions: Vec<&String>| {
+        versions
+            .into_iter()
+            .max_by(|a, b| compare_versions(a, b))
+            .cloned()
+    };
+    let Some(caps) = requirement.and_then(|requirement| re.captures(requirement)) else {
+        return highest(candidates.iter().collect());
+    };
+    // Caret semantics: the first non-zero component must match
+    let major = &caps[1];
+    let minor = caps.get(2).map(|m| m.as_str());
+    let compatible: Vec<&String> = candidates
+        .iter()
+        .filter(|candidate| {
+            let mut parts = candidate.split('.');
+            let candidate_major = parts.next().unwrap_or_default();
+            let candidate_minor = parts.next();
+            candidate_major == major
+                && (major != "0" || minor.is_none() || candidate_minor == minor)
+        })
+        .collect();
+    if compatible.is_empty() {
+        highest(candidates.iter().collect())
+    } else {
+        highest(compatible)
+    }
+}
+
+fn parse_cargo_manifest(content: &str, manifest: &Path) -> Result<Vec<ManifestDependency>, String> {
+    let value: toml::Value = toml::from_str(content).map_err(|e| e.to_string())?;
+    let mut tables: Vec<(&toml::Value, bool)> = Vec::new();
+    for (key, is_dev) in [
+        ("dependencies", false),
+        ("dev-dependencies", true),
+        ("build-dependencies", false),
+    ] {
+        if let Some(table) = value.get(key) {
+            tables.push((table, is_dev));
+        }
+        if let Some(targets) = value.get("target").and_then(|t| t.as_table()) {
+            for target in targets.values() {
+                if let Some(table) = target.get(key) {
+                    tables.push((table, is_dev));
+                }
+            }
+        }
+    }
+    if let Some(table) = value.get("workspace").and_then(|w| w.get("dependencies")) {
+        tables.push((table, false));
+    }
+
+    let mut dependencies = Vec::new();
+    for (table, is_dev) in tables {
+        let Some(table) = table.as_table() else {
+            continue;
+        };
+        for (key, spec) in table {
+            let (name, requirement) = match spec {
+                toml::Value::String(requirement) => (key.as_str(), Some(requirement.clone())),
+                toml::Value::Table(spec) => {
+                    // Local path dependencies are not published packages
+                    if spec.contains_key("path") {
+                        continue;
+                    }
+                    (
+                        spec.get("package")
+                            .and_then(|p| p.as_str())
+                            .unwrap_or(key.as_str()),
+                        spec.get("version")
+                            .and_then(|v| v.as_str())
+                            .map(str::to_string),
+                    )
+                }
+                _ => continue,
+            };
+            let mut dependency = ManifestDependency::new(
+                name,
+                PackageEcosystem::CratesIo,
+                requirement,
+                is_dev,
+                manifest,
+            );
+            dependency.resolved_version =
+                dependency.requirement.as_deref().and_then(pinned_version);
+            dependencies.push(dependency);
+        }
+    }
+    Ok(dependencies)
+}
+
+fn resolve_cargo(
+    dependencies: &mut Vec<ManifestDependency>,
+    lockfile: &Path,
+) -> Result<(), String> {
+    let content = fs::read_to_string(lockfile).map_err(|e| e.to_string())?;
+    let value: toml::Value = toml::from_str(&content).map_err(|e| e.to_string())?;
+    let mut registry: HashMap<String, Vec<String>> = HashMap::new();
+    let mut local: HashSet<String> = HashSet::new();
+    for package in value
+        .get("package")
+        .and_then(|p| p.as_array())
+        .into_iter()
+        .flatten()
+    {
+        let (Some(name), Some(version)) = (
+            package.get("name").and_then(|n| n.as_str()),
+            package.get("version").and_then(|v| v.as_str()),
+        ) else {
+            continue;
+        };
+        if package.get("source").is_some() {
+            registry
+                .entry(name.to_string())
+                .or_default()
+                .push(version.to_string());
+        } else {
+            local.insert(name.to_string());
+        }
+    }
+
+    // Workspace members (no source) are local crates, not registry packages
+    dependencies.retain(|dependency| {
+        !(local.contains(&dependency.name) && !registry.contains_key(&dependency.name))
+    });
+    for dependency in dependencies.iter_mut() {
+        if let Some(candidates) = registry.get(&dependency.name) {
+            dependency.resolved_version =
+                select_locked_version(dependency.requirement.as_deref(), candidates);
+            dependency.lockfile = Some(lockfile.to_path_buf());
+        }
+    }
+    Ok(())
+}
+
+fn parse_package_json(content: &str, manifest: &Path) -> Result<Vec<ManifestDependency>, String> {
+    static EXACT: OnceLock<Regex> = OnceLock::new();
+    let exact = EXACT
+        .get_or_init(|| Regex::new(r"^\d+\.\d+\.\d+(-[0-9A-Za-z.\-]+)?$").expect("valid regex"));
+    let value: serde_json::Value = serde_json::from_str(content).map_err(|e| e.to_string())?;
+    let mut dependencies = Vec::new();
+    for (key, is_dev) in [
+        ("dependencies", false),
+        ("optionalDependencies", false),
+        ("devDependencies", true),
+    ] {
+        let Some(table) = value.get(key).and_then(|t| t.as_object()) else {
+            continue;
+        };
+        for (name, requirement) in table {
+            let requirement = requirement.as_str().unwrap_or_default();
+            // Workspace, file and link protocols point at local packages
+            if ["workspace:", "file:", "link:"]
+                .iter()
+                .any(|protocol| requirement.starts_with(protocol))
+            {
+                continue;
+            }
+            let mut dependency = ManifestDependency::new(
+                name.as_str(),
+                PackageEcosystem::Npm,
+                Some(requirement.to_string()),
+                is_dev,
+                manifest,
+            );
+            // Bare versions are exact in npm
+            dependency.resolved_version = pinned_version(requirement)
+                .or_else(|| exact.is_match(requirement).then(|| requirement.to_string()));
+            dependencies.push(dependency);
+        }
+    }
+    Ok(dependencies)
+}
+
+fn resolve_npm(
+    dependencies: &mut [ManifestDependency],
+    manifest: &Path,
+    lockfile: &Path,
+) -> Result<(), String> {
+    let content = fs::read_to_string(lockfile).map_err(|e| e.to_string())?;
+    let mut versions: HashMap<String, String> = HashMap::new();
+
+    if lockfile.ends_with("yarn.lock") {
+        // Yarn v1: `"name@^1.0.0", name@^1.2.0:` followed by `  version "1.2.3"`
+        let mut current: Vec<(String, String)> = Vec::new();
+        for line in content.lines() {
+            if !line.starts_with(' ') && line.ends_with(':') {
+                current = line
+                    .trim_end_matches(':')
+                    .split(", ")
+                    .filter_map(|spec| {
+                        let spec = spec.trim_matches('"');
+                        let at = spec.rfind('@').filter(|&at| at > 0)?;
+                        Some((spec[..at].to_string(), spec[at + 1..].to_string()))
+                    })
+                    .collect();
+            } else if let Some(version) = line.trim().strip_prefix("version ") {
+                let version = version.trim_matches('"');
+                for (name, range) in current.drain(..) {
+                    versions.insert(format!("{}@{}", name, range), version.to_string());
+                }
+            }
+        }
+        for dependency in dependencies.iter_mut() {
+            let key = format!(
+                "{}@{}",
+                dependency.name,
+                dependency.requirement.as_deref().unwrap_or_default()
+            );
+            if let Some(version) = versions.get(&key) {
+                dependency.resolved_version = Some(version.clone());
+                dependency.lockfile = Some(lockfile.to_path_buf());
+            }
+        }
+        return Ok(());
+    }
+
+    let value: serde_json::Value = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+    if let Some(packages) = value.get("packages").and_then(|p| p.as_object()) {
+        for (key, package) in packages {
+            if let Some(version) = package.get("version").and_then(|v| v.as_str()) {
+                versions.insert(key.clone(), version.to_string());
+            }
+        }
+    } else if let Some(legacy) = value.get("dependencies").and_then(|d| d.as_object()) {
+        for (name, package) in legacy {
+            if let Some(version) = package.get("version").and_then(|v| v.as_str()) {
+                versions.insert(format!("node_modules/{}", name), version.to_string());
+            }
+        }
+    }
+
+    // Workspace packages may have their own node_modules before falling back to hoisted ones
+    let lock_dir = lockfile.parent().unwrap_or(Path::new(""));
+    let package_dir = manifest
+        .parent()
+        .and_then(|dir| dir.strip_prefix(lock_dir).ok())
+        .map(|dir| dir.to_string_lossy().replace('\\', "/"))
+        .unwrap_or_default();
+    for dependency in dependencies.iter_mut() {
+        let nested = format!("{}/node_modules/{}", package_dir, dependency.name);
+        let hoisted = format!("node_modules/{}", dependency.name);
+        let version = (!package_dir.is_empty())
+            .then(|| versions.get(&nested))
+            .flatten()
+            .or_else(|| versions.get(&hoisted));
+        if let Some(version) = version {
+            dependency.resolved_version = Some(version.clone());
+            dependency.lockfile = Some(lockfile.to_path_buf());
+        }
+    }
+    Ok(())
+}
+
+/// Split a PEP 508 requirement into name and version specifier
+fn parse_pep508(requirement: &str) -> Option<(String, Option<String>)> {
+    static PEP508: OnceLock<Regex> = OnceLock::new();
+    let re = PEP508.get_or_init(|| {
+        Regex::new(r"^\s*([A-Za-z0-9][A-Za-z0-9._\-]*)\s*(?:\[[^\]]*\])?\s*\(?([^;)]*)\)?")
+            .expect("valid regex")
+    });
+    let caps = re.captures(requirement)?;
+    let specifier = caps[2].trim();
+    Some((
+        caps[1].to_string(),
+        (!specifier.is_empty()).then(|| specifier.to_string()),
+    ))
+}
+
+fn parse_pyproject(content: &str, manifest: &Path) -> Result<Vec<ManifestDependency>, String> {
+    let value: toml::Value = toml::from_str(content).map_err(|e| e.to_string())?;
+    let mut dependencies = Vec::new();
+    let mut push = |name: &str, requirement: Option<String>, is_dev: bool| {
+        let ecosystem = PackageEcosystem::PyPI;
+        let mut dependency = ManifestDependency::new(
+            ecosystem.normalize_name(name),
+            ecosystem,
+            requirement,
+            is_dev,
+            manifest,
+        );
+        dependency.resolved_version = dependency.requirement.as_deref().and_then(pinned_version);
+        dependencies.push(dependency);
+    };
+    let requirements = |value: Option<&toml::Value>| -> Vec<(String, Option<String>)> {
+        value
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|requirement| parse_pep508(requirement.as_str()?))
+            .collect()
+    };
+
+    // PEP 621 project metadata
+    let project = value.get("project");
+    for (name, requirement) in requirements(project.and_then(|p| p.get("dependencies"))) {
+        push(&name, requirement, false);
+    }
+    if let Some(extras) = project
+        .and_then(|p| p.get("optional-dependencies"))
+        .and_then(|o| o.as_table())
+    {
+        for extra in extras.values() {
+            for (name, requirement) in requirements(Some(extra)) {
+                push(&name, requirement, false);
+            }
+        }
+    }
+    // PEP 735 dependency groups are development-only
+    if let Some(groups) = value.get("dependency-groups").and_then(|g| g.as_table()) {
+        for group in groups.values() {
+            for (name, requirement) in requirements(Some(group)) {
+                push(&name, requirement, true);
+            }
+        }
+    }
+
+    // Poetry tables
+    let poetry = value.get("tool").and_then(|t| t.get("poetry"));
+    let mut poetry_tables: Vec<(&toml::Value, bool)> = Vec::new();
+    if let Some(table) = poetry.and_then(|p| p.get("dependencies")) {
+        poetry_tables.push((table, false));
+    }
+    if let Some(table) = poetry.and_then(|p| p.get("dev-dependencies")) {
+        poetry_tables.push((table, true));
+    }
+    if let Some(groups) = poetry
+        .and_then(|p| p.get("group"))
+        .and_then(|g| g.as_table())
+    {
+        for (group, spec) in groups {
+            if let Some(table) = spec.get("dependencies") {
+                poetry_tables.push((table, group != "main"));
+            }
+        }
+    }
+    for (table, is_dev) in poetry_tables {
+        for (name, spec) in table.as_table().into_iter().flatten() {
+            if name == "python" {
+                continue;
+            }
+            let requirement = match spec {
+                toml::Value::String(requirement) => Some(requirement.clone()),
+                toml::Value::Table(spec) if spec.contains_key("path") => continue,
+                toml::Value::Table(spec) => spec
+                    .get("version")
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string),
+                _ => None,
+            };
+            push(name, requirement, is_dev);
+        }
+    }
+    Ok(dependencies)
+}
+
+fn resolve_python(dependencies: &mut [ManifestDependency], lockfile: &Path) -> Result<(), String> {
+    let content = fs::read_to_string(lockfile).map_err(|e| e.to_string())?;
+    let value: toml::Value = toml::from_str(&content).map_err(|e| e.to_string())?;
+    let mut locked: BTreeMap<String, String> = BTreeMap::new();
+    for package in value
+        .get("package")
+        .and_then(|p| p.as_array())
+        .into_iter()
+        .flatten()
+    {
+        if let (Some(name), Some(version)) = (
+            package.get("name").and_then(|n| n.as_str()),
+            package.get("version").and_then(|v| v.as_str()),
+        ) {
+            locked.insert(
+                PackageEcosystem::PyPI.normalize_name(name),
+                version.to_string(),
+            );
+        }
+    }
+    for dependency in dependencies.iter_mut() {
+        if let Some(version) = locked.get(&dependency.name) {
+            dependency.resolved_version = Some(version.clone());
+            dependency.lockfile = Some(lockfile.to_path_buf());
+        }
+    }
+    Ok(())
+}
+
+fn parse_go_mod(content: &str, manifest: &Path) -> Vec<ManifestDependency> {
+    let mut dependencies = Vec::new();
+    let mut in_block = false;
+    for line in content.lines() {
+        let line = line.trim();
+        let requirement = if in_block {
+            if line == ")" {
+                in_block = false;
+                continue;
+            }
+            line
+        } else if line == "require (" {
+            in_block = true;
+            continue;
+        } else if let Some(rest) = line.strip_prefix("require ") {
+            rest
+        } else {
+            continue;
+        };
+
+        let (requirement, comment) = match requirement.split_once("//") {
+            Some((requirement, comment)) => (requirement.trim(), comment.trim()),
+            None => (requirement, ""),
+        };
+        let mut parts = requirement.split_whitespace();
+        let (Some(module), Some(version)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+        let mut dependency = ManifestDependency::new(
+            module,
+            PackageEcosystem::Go,
+            Some(version.to_string()),
+            false,
+            manifest,
+        );
+        // go.mod already records the selected version
+        dependency.resolved_version = Some(version.to_string());
+        dependency.direct = comment != "indirect";
+        dependencies.push(dependency);
+    }
+    dependencies
+}
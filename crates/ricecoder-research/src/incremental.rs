@@ -0,0 +1,521 @@
+//! Incremental project re-analysis driven by file watch events
+//!
+//! A full scan builds the semantic index once; afterwards each
+//! [`FileChangeBatch`] from `ricecoder-files` only invalidates the symbols and
+//! dependency edges of the files it touches (plus the files that referenced
+//! them), so queries always see an up-to-date index without a full re-scan.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{Arc, OnceLock, PoisonError, RwLock},
+};
+
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use ricecoder_files::{FileChangeBatch, FileChangeEvent};
+use serde::{Deserialize, Serialize};
+use tokio::{sync::broadcast, task::JoinHandle};
+use tracing::{debug, info, warn};
+
+use crate::{
+    declarations::{extract_symbols, language_for},
+    error::ResearchError,
+    models::{ReferenceKind, SymbolReference},
+    semantic_index::SemanticIndex,
+};
+
+/// Freshness metadata for a single indexed file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileFreshness {
+    /// When the file was last (re-)indexed
+    pub analyzed_at: DateTime<Utc>,
+    /// Hash of the content that was indexed
+    pub content_hash: u64,
+    /// Number of symbols defined in the file
+    pub symbol_count: usize,
+}
+
+/// Freshness metadata for the whole incremental index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalysisFreshness {
+    /// Project root being analyzed
+    pub root: PathBuf,
+    /// When the last full scan completed
+    pub last_full_scan: Option<DateTime<Utc>>,
+    /// When the index last changed, by full scan or incremental update
+    pub last_update: Option<DateTime<Utc>>,
+    /// Number of files currently indexed
+    pub indexed_files: usize,
+    /// Files whose changes could not be applied yet
+    pub stale_files: Vec<PathBuf>,
+    /// Number of full scans performed
+    pub full_scans: u64,
+    /// Number of incremental updates applied since the last full scan
+    pub incremental_updates: u64,
+}
+
+impl AnalysisFreshness {
+    /// Whether the index reflects the project as last reported by the watcher
+    pub fn is_fresh(&self) -> bool {
+        self.last_full_scan.is_some() && self.stale_files.is_empty()
+    }
+}
+
+/// Outcome of a full scan or an incremental update
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IncrementalUpdate {
+    /// Files whose symbols were (re-)extracted
+    pub reindexed: Vec<PathBuf>,
+    /// Files dropped from the index
+    pub removed: Vec<PathBuf>,
+    /// Files whose references and dependency edges were re-resolved
+    pub relinked: Vec<PathBuf>,
+    /// Modified files skipped because their content did not change
+    pub unchanged: Vec<PathBuf>,
+    /// Files that could not be read and remain stale
+    pub failed: Vec<PathBuf>,
+}
+
+impl IncrementalUpdate {
+    /// Whether the update changed the index at all
+    pub fn is_empty(&self) -> bool {
+        self.reindexed.is_empty() && self.removed.is_empty() && self.relinked.is_empty()
+    }
+}
+
+/// Per-file analysis state kept between updates
+#[derive(Debug, Clone)]
+struct FileEntry {
+    freshness: FileFreshness,
+    /// Identifier usages in the file, by name, with their line numbers
+    usages: HashMap<String, Vec<usize>>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    index: SemanticIndex,
+    files: HashMap<PathBuf, FileEntry>,
+    /// File -> files defining symbols it references
+    dependencies: HashMap<PathBuf, HashSet<PathBuf>>,
+    /// File -> files referencing symbols it defines
+    dependents: HashMap<PathBuf, HashSet<PathBuf>>,
+    stale: HashSet<PathBuf>,
+    last_full_scan: Option<DateTime<Utc>>,
+    last_update: Option<DateTime<Utc>>,
+    full_scans: u64,
+    incremental_updates: u64,
+}
+
+/// Keeps a semantic index and file dependency graph up to date incrementally
+///
+/// Run [`full_scan`](Self::full_scan) once, then feed watcher batches through
+/// [`apply_batch`](Self::apply_batch) or [`spawn_watch`](Self::spawn_watch).
+#[derive(Debug)]
+pub struct IncrementalAnalyzer {
+    root: PathBuf,
+    state: RwLock<State>,
+}
+
+impl IncrementalAnalyzer {
+    /// Create an analyzer for the project at `root`; the index starts empty
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            state: RwLock::new(State::default()),
+        }
+    }
+
+    /// Project root being analyzed
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Rebuild the index from scratch by scanning every supported file
+    pub fn full_scan(&self) -> Result<IncrementalUpdate, ResearchError> {
+        if !self.root.is_dir() {
+            return Err(ResearchError::ProjectNotFound {
+                path: self.root.clone(),
+                reason: "Root is not a directory".to_string(),
+            });
+        }
+
+        let mut paths = Vec::new();
+        for entry in ignore::WalkBuilder::new(&self.root).build() {
+            let entry = entry.map_err(|e| ResearchError::ScanFailed {
+                reason: e.to_string(),
+            })?;
+            let path = entry.path();
+            if path.is_file() && language_for(path).is_some() {
+                paths.push(path.to_path_buf());
+            }
+        }
+
+        let mut state = self.state.write().unwrap_or_else(PoisonError::into_inner);
+        let full_scans = state.full_scans;
+        *state = State {
+            full_scans: full_scans + 1,
+            ..State::default()
+        };
+
+        let mut update = IncrementalUpdate::default();
+        for path in paths {
+            match read_source(&path) {
+                Ok(Some(content)) => {
+                    index_file(&mut state, &path, &content);
+                    update.reindexed.push(path);
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    warn!("Failed to read {:?} during full scan: {}", path, e);
+                    state.stale.insert(path.clone());
+                    update.failed.push(path);
+                }
+            }
+        }
+
+        let files: Vec<PathBuf> = state.files.keys().cloned().collect();
+        for path in &files {
+            link_file(&mut state, path);
+        }
+
+        let now = Utc::now();
+        state.last_full_scan = Some(now);
+        state.last_update = Some(now);
+        info!(
+            "Full scan of {:?} indexed {} files ({} symbols)",
+            self.root,
+            state.files.len(),
+            state.index.symbol_count()
+        );
+        Ok(update)
+    }
+
+    /// Apply a batch of watcher events, invalidating only the affected files
+    pub fn apply_batch(&self, batch: &FileChangeBatch) -> IncrementalUpdate {
+        self.apply_events(&batch.events)
+    }
+
+    /// Apply file change events, invalidating only the affected files
+    ///
+    /// Changed files are re-indexed; files that referenced their old symbols,
+    /// or use names they now define, have their references and dependency
+    /// edges re-resolved. Events outside the root or for unsupported file
+    /// types are ignored.
+    pub fn apply_events(&self, events: &[FileChangeEvent]) -> IncrementalUpdate {
+        let mut upserts: Vec<PathBuf> = Vec::new();
+        let mut removals: Vec<PathBuf> = Vec::new();
+        for event in events {
+            match event {
+                FileChangeEvent::Created(path) | FileChangeEvent::Modified(path) => {
+                    upserts.push(path.clone())
+                }
+                FileChangeEvent::Deleted(path) => removals.push(path.clone()),
+                FileChangeEvent::Renamed { from, to } => {
+                    removals.push(from.clone());
+                    upserts.push(to.clone());
+                }
+            }
+        }
+        upserts.retain(|path| self.is_tracked(path));
+        removals.retain(|path| self.is_tracked(path) && !upserts.contains(path));
+        upserts.sort();
+        upserts.dedup();
+
+        let mut update = IncrementalUpdate::default();
+        if upserts.is_empty() && removals.is_empty() {
+            return update;
+        }
+
+        // Read outside the lock so queries are not blocked on disk IO
+        let mut contents = Vec::new();
+        for path in upserts {
+            match read_source(&path) {
+                Ok(Some(content)) => contents.push((path, content)),
+                Ok(None) => removals.push(path),
+                Err(e) => {
+                    warn!("Failed to re-analyze {:?}: {}", path, e);
+                    update.failed.push(path);
+                }
+            }
+        }
+
+        let mut state = self.state.write().unwrap_or_else(PoisonError::into_inner);
+        for path in &update.failed {
+            state.stale.insert(path.clone());
+        }
+        contents.retain(|(path, content)| {
+            let unchanged = state
+                .files
+                .get(path)
+                .is_some_and(|entry| entry.freshness.content_hash == fxhash::hash64(content));
+            if unchanged {
+                state.stale.remove(path);
+                update.unchanged.push(path.clone());
+            }
+            !unchanged
+        });
+
+        let mut relink: HashSet<PathBuf> = HashSet::new();
+        let invalidated = removals.iter().chain(contents.iter().map(|(path, _)| path));
+        for path in invalidated {
+            if let Some(dependents) = state.dependents.get(path) {
+                relink.extend(dependents.iter().cloned());
+            }
+            if state.files.contains_key(path) {
+                unindex_file(&mut state, path);
+                if removals.contains(path) {
+                    update.removed.push(path.clone());
+                }
+            }
+            state.stale.remove(path);
+        }
+
+        let mut new_names: HashSet<String> = HashSet::new();
+        for (path, content) in &contents {
+            index_file(&mut state, path, content);
+            new_names.extend(
+                state
+                    .index
+                    .get_symbols_in_file(path)
+                    .into_iter()
+                    .map(|symbol| symbol.name.clone()),
+            );
+            relink.insert(path.clone());
+            update.reindexed.push(path.clone());
+        }
+
+        // Files that use a newly defined name may now resolve to it
+        for (path, entry) in &state.files {
+            if entry.usages.keys().any(|name| new_names.contains(name)) {
+                relink.insert(path.clone());
+            }
+        }
+
+        relink.retain(|path| state.files.contains_key(path));
+        let mut relinked: Vec<PathBuf> = relink.into_iter().collect();
+        relinked.sort();
+        for path in &relinked {
+            link_file(&mut state, path);
+        }
+        update.relinked = relinked;
+
+        if !update.is_empty() {
+            state.last_update = Some(Utc::now());
+            state.incremental_updates += 1;
+        }
+        debug!(
+            "Incremental update: {} reindexed, {} removed, {} relinked",
+            update.reindexed.len(),
+            update.removed.len(),
+            update.relinked.len()
+        );
+        update
+    }
+
+    /// Consume watcher batches in a background task until the channel closes
+    ///
+    /// If the receiver lags behind and events are lost, the index can no
+    /// longer be patched reliably and a full scan is performed instead.
+    pub fn spawn_watch(
+        self: &Arc<Self>,
+        mut receiver: broadcast::Receiver<FileChangeBatch>,
+    ) -> JoinHandle<()> {
+        let analyzer = Arc::clone(self);
+        tokio::spawn(async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(batch) => {
+                        analyzer.apply_batch(&batch);
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!(
+                            "Missed {} file change batches, re-scanning {:?}",
+                            skipped, analyzer.root
+                        );
+                        if let Err(e) = analyzer.full_scan() {
+                            warn!("Full re-scan failed: {}", e);
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
+    /// Run a query against the current index
+    pub fn with_index<R>(&self, query: impl FnOnce(&SemanticIndex) -> R) -> R {
+        let state = self.state.read().unwrap_or_else(PoisonError::into_inner);
+        query(&state.index)
+    }
+
+    /// Freshness metadata for the index as a whole
+    pub fn freshness(&self) -> AnalysisFreshness {
+        let state = self.state.read().unwrap_or_else(PoisonError::into_inner);
+        let mut stale_files: Vec<PathBuf> = state.stale.iter().cloned().collect();
+        stale_files.sort();
+        AnalysisFreshness {
+            root: self.root.clone(),
+            last_full_scan: state.last_full_scan,
+            last_update: state.last_update,
+            indexed_files: state.files.len(),
+            stale_files,
+            full_scans: state.full_scans,
+            incremental_updates: state.incremental_updates,
+        }
+    }
+
+    /// Freshness metadata for a single file, if it is indexed
+    pub fn file_freshness(&self, path: &Path) -> Option<FileFreshness> {
+        let state = self.state.read().unwrap_or_else(PoisonError::into_inner);
+        state.files.get(path).map(|entry| entry.freshness.clone())
+    }
+
+    /// Files defining symbols that `path` references
+    pub fn dependencies_of(&self, path: &Path) -> Vec<PathBuf> {
+        let state = self.state.read().unwrap_or_else(PoisonError::into_inner);
+        sorted(state.dependencies.get(path))
+    }
+
+    /// Files referencing symbols that `path` defines
+    pub fn dependents_of(&self, path: &Path) -> Vec<PathBuf> {
+        let state = self.state.read().unwrap_or_else(PoisonError::into_inner);
+        sorted(state.dependents.get(path))
+    }
+
+    fn is_tracked(&self, path: &Path) -> bool {
+        path.starts_with(&self.root) && language_for(path).is_some()
+    }
+}
+
+fn sorted(paths: Option<&HashSet<PathBuf>>) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = paths.into_iter().flatten().cloned().collect();
+    paths.sort();
+    paths
+}
+
+/// Read a source file, returning `None` if it no longer exists
+fn read_source(path: &Path) -> io::Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(Some(content)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Extract the symbols and identifier usages of a file into the state
+fn index_file(state: &mut State, path: &Path, content: &str) {
+    let language = match language_for(path) {
+        Some(language) => language,
+        None => return,
+    };
+
+    let symbols = extract_symbols(path, &language, content);
+    let freshness = FileFreshness {
+        analyzed_at: Utc::now(),
+        content_hash: fxhash::hash64(content),
+        symbol_count: symbols.len(),
+    };
+    for symbol in symbols {
+        state.index.add_symbol(symbol);
+    }
+
+    state.files.insert(
+        path.to_path_buf(),
+        FileEntry {
+            freshness,
+            usages: extract_usages(content),
+        },
+    );
+}
+
+/// Drop a file's symbols, references and dependency edges
+fn unindex_file(state: &mut State, path: &Path) {
+    state.index.remove_file(path);
+    state.files.remove(path);
+    unlink_file(state, path);
+    // Other files' edges into this one are rebuilt when they are relinked
+    if let Some(dependents) = state.dependents.remove(path) {
+        for dependent in dependents {
+            if let Some(deps) = state.dependencies.get_mut(&dependent) {
+                deps.remove(path);
+            }
+        }
+    }
+}
+
+/// Drop the outgoing dependency edges of a file
+fn unlink_file(state: &mut State, path: &Path) {
+    if let Some(dependencies) = state.dependencies.remove(path) {
+        for dependency in dependencies {
+            if let Some(dependents) = state.dependents.get_mut(&dependency) {
+                dependents.remove(path);
+                if dependents.is_empty() {
+                    state.dependents.remove(&dependency);
+                }
+            }
+        }
+    }
+}
+
+/// Re-resolve a file's references against the current index
+fn link_file(state: &mut State, path: &Path) {
+    state.index.remove_references_in_file(path);
+    unlink_file(state, path);
+
+    let usages = match state.files.get(path) {
+        Some(entry) => entry.usages.clone(),
+        None => return,
+    };
+
+    let mut references = Vec::new();
+    let mut dependencies = HashSet::new();
+    for (name, lines) in &usages {
+        for symbol in state.index.get_symbols_by_name(name) {
+            if symbol.file == path {
+                continue;
+            }
+            dependencies.insert(symbol.file.clone());
+            references.extend(lines.iter().map(|&line| SymbolReference {
+                symbol_id: symbol.id.clone(),
+                file: path.to_path_buf(),
+                line,
+                kind: ReferenceKind::Usage,
+            }));
+        }
+    }
+
+    for reference in references {
+        state.index.add_reference(reference);
+    }
+    for dependency in &dependencies {
+        state
+            .dependents
+            .entry(dependency.clone())
+            .or_default()
+            .insert(path.to_path_buf());
+    }
+    if !dependencies.is_empty() {
+        state.dependencies.insert(path.to_path_buf(), dependencies);
+    }
+}
+
+fn identifier_regex() -> &'static Regex {
+    static IDENTIFIER: OnceLock<Regex> = OnceLock::new();
+    IDENTIFIER.get_or_init(|| Regex::new(r"[A-Za-z_][A-Za-z0-9_]*").expect("valid regex"))
+}
+
+/// Collect the lines on which each identifier appears
+fn extract_usages(content: &str) -> HashMap<String, Vec<usize>> {
+    let mut usages: HashMap<String, Vec<usize>> = HashMap::new();
+    for (index, line) in content.lines().enumerate() {
+        for identifier in identifier_regex().find_iter(line) {
+            let lines = usages.entry(identifier.as_str().to_string()).or_default();
+            if lines.last() != Some(&(index + 1)) {
+                lines.push(index + 1);
+            }
+        }
+    }
+    usages
+}
@@ -6,8 +6,13 @@
 //! Provides core project analysis and context gathering capabilities with MCP integration.
 //! Focuses on semantic understanding, search, and standards detection for AI-assisted development.
 
+pub mod architecture;
+pub mod context_packer;
+mod declarations;
+pub mod dependencies;
 pub mod di;
 pub mod error;
+pub mod incremental;
 pub mod manager;
 pub mod models;
 pub mod project_analyzer;
@@ -15,11 +20,26 @@ pub mod reference_tracker;
 pub mod relevance_scorer;
 pub mod search_engine;
 pub mod semantic_index;
+pub mod semantic_search;
 pub mod standards_detector;
 
 // Re-export from dependencies for convenience (when features are enabled)
 // Re-export core types
+pub use architecture::{
+    ArchitectureAnalyzer, ArchitectureModel, ArchitectureRules, ArchitectureViolation,
+    DependencyEdge, DependencyKind, DiagramGranularity, ModuleNode, PackageNode, ViolationKind,
+};
+pub use context_packer::{
+    ContextItemKind, ContextManifest, ContextPacker, ContextRequest, ContextSource, DroppedEntry,
+    PackedEntry, PackingWeights, Provenance,
+};
+pub use dependencies::{
+    compare_versions, is_dependency_file, AdvisorySeverity, DependencyAnalyzer,
+    DependencyFinding, DependencyReport, ManifestDependency, OfflineOsvDatabase, OsvClient,
+    OsvVulnerability, PackageEcosystem, PackageQuery, VulnerabilityDatabase,
+};
 pub use error::ResearchError;
+pub use incremental::{AnalysisFreshness, FileFreshness, IncrementalAnalyzer, IncrementalUpdate};
 pub use manager::ResearchManager;
 pub use models::*;
 pub use project_analyzer::ProjectAnalyzer;
@@ -34,4 +54,8 @@ pub use ricecoder_patterns as patterns;
 pub use ricecoder_patterns::DetectedPattern;
 pub use search_engine::{SearchEngine, SearchOptions, SearchStatistics};
 pub use semantic_index::SemanticIndex;
+pub use semantic_search::{
+    CodeChunk, CodeChunker, EmbeddingIndexStats, HitSource, InMemoryVectorStore,
+    SemanticCodeSearch, SemanticSearchHit, VectorRecord, VectorStore,
+};
 pub use standards_detector::StandardsDetector;
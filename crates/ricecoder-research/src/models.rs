@@ -113,6 +113,97 @@ pub enum Language {
     Other(String),
 }
 
+/// Build tool or package manager
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum BuildTool {
+    /// Cargo (Rust)
+    Cargo,
+    /// npm
+    Npm,
+    /// Yarn
+    Yarn,
+    /// pnpm
+    Pnpm,
+    /// Bun
+    Bun,
+    /// pip with requirements.txt or pyproject.toml
+    Pip,
+    /// Poetry
+    Poetry,
+    /// uv
+    Uv,
+    /// Go modules
+    GoModules,
+    /// Maven
+    Maven,
+    /// Gradle
+    Gradle,
+    /// .NET CLI
+    DotNet,
+    /// Composer (PHP)
+    Composer,
+    /// Bundler (Ruby)
+    Bundler,
+    /// Swift Package Manager
+    SwiftPm,
+    /// Dart/Flutter pub
+    Pub,
+    /// CMake
+    CMake,
+    /// Make
+    Make,
+}
+
+impl BuildTool {
+    /// Command name of the tool
+    pub fn name(&self) -> &'static str {
+        match self {
+            BuildTool::Cargo => "cargo",
+            BuildTool::Npm => "npm",
+            BuildTool::Yarn => "yarn",
+            BuildTool::Pnpm => "pnpm",
+            BuildTool::Bun => "bun",
+            BuildTool::Pip => "pip",
+            BuildTool::Poetry => "poetry",
+            BuildTool::Uv => "uv",
+            BuildTool::GoModules => "go",
+            BuildTool::Maven => "mvn",
+            BuildTool::Gradle => "gradle",
+            BuildTool::DotNet => "dotnet",
+            BuildTool::Composer => "composer",
+            BuildTool::Bundler => "bundle",
+            BuildTool::SwiftPm => "swift",
+            BuildTool::Pub => "dart",
+            BuildTool::CMake => "cmake",
+            BuildTool::Make => "make",
+        }
+    }
+
+    /// Conventional command that runs the project's tests
+    pub fn test_command(&self) -> &'static str {
+        match self {
+            BuildTool::Cargo => "cargo test",
+            BuildTool::Npm => "npm test",
+            BuildTool::Yarn => "yarn test",
+            BuildTool::Pnpm => "pnpm test",
+            BuildTool::Bun => "bun test",
+            BuildTool::Pip => "pytest",
+            BuildTool::Poetry => "poetry run pytest",
+            BuildTool::Uv => "uv run pytest",
+            BuildTool::GoModules => "go test ./...",
+            BuildTool::Maven => "mvn test",
+            BuildTool::Gradle => "gradle test",
+            BuildTool::DotNet => "dotnet test",
+            BuildTool::Composer => "composer test",
+            BuildTool::Bundler => "bundle exec rake test",
+            BuildTool::SwiftPm => "swift test",
+            BuildTool::Pub => "dart test",
+            BuildTool::CMake => "ctest",
+            BuildTool::Make => "make test",
+        }
+    }
+}
+
 /// Framework or library
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Framework {
@@ -6,7 +6,7 @@ use walkdir::WalkDir;
 
 use crate::{
     error::ResearchError,
-    models::{Framework, Language, ProjectStructure, ProjectType},
+    models::{BuildTool, Framework, Language, ProjectStructure, ProjectType},
 };
 
 /// Analyzes project structure and metadata to understand project type and organization
@@ -164,12 +164,11 @@ impl ProjectAnalyzer {
         Ok(frameworks)
     }
 
-    // ========================================================================
-    // Private helper methods
-    // ========================================================================
-
     /// Detect programming languages used in the project
-    fn detect_languages(&self, root: &Path) -> Result<Vec<Language>, ResearchError> {
+    ///
+    /// Languages are detected from manifest files at the project root, in a
+    /// fixed order with Rust first.
+    pub fn detect_languages(&self, root: &Path) -> Result<Vec<Language>, ResearchError> {
         let mut languages = Vec::new();
 
         // Check for Rust
@@ -230,6 +229,83 @@ impl ProjectAnalyzer {
         Ok(languages)
     }
 
+    /// Detect the build tools and package managers used in the project
+    ///
+    /// Lock files take precedence over the manifest they belong to, so a
+    /// `package.json` with a `pnpm-lock.yaml` is reported as pnpm only.
+    pub fn detect_build_tools(&self, root: &Path) -> Result<Vec<BuildTool>, ResearchError> {
+        if !root.exists() {
+            return Err(ResearchError::ProjectNotFound {
+                path: root.to_path_buf(),
+                reason: "Cannot detect build tools: directory does not exist".to_string(),
+            });
+        }
+
+        let has = |file: &str| root.join(file).exists();
+        let mut tools = Vec::new();
+
+        if has("Cargo.toml") {
+            tools.push(BuildTool::Cargo);
+        }
+
+        if has("package.json") {
+            tools.push(if has("pnpm-lock.yaml") {
+                BuildTool::Pnpm
+            } else if has("yarn.lock") {
+                BuildTool::Yarn
+            } else if has("bun.lockb") || has("bun.lock") {
+                BuildTool::Bun
+            } else {
+                BuildTool::Npm
+            });
+        }
+
+        if has("uv.lock") {
+            tools.push(BuildTool::Uv);
+        } else if has("poetry.lock") {
+            tools.push(BuildTool::Poetry);
+        } else if has("pyproject.toml") || has("requirements.txt") {
+            tools.push(BuildTool::Pip);
+        }
+
+        if has("go.mod") {
+            tools.push(BuildTool::GoModules);
+        }
+        if has("pom.xml") {
+            tools.push(BuildTool::Maven);
+        }
+        if has("build.gradle") || has("build.gradle.kts") {
+            tools.push(BuildTool::Gradle);
+        }
+        if self.has_csproj_files(root)? {
+            tools.push(BuildTool::DotNet);
+        }
+        if has("composer.json") {
+            tools.push(BuildTool::Composer);
+        }
+        if has("Gemfile") {
+            tools.push(BuildTool::Bundler);
+        }
+        if has("Package.swift") {
+            tools.push(BuildTool::SwiftPm);
+        }
+        if has("pubspec.yaml") {
+            tools.push(BuildTool::Pub);
+        }
+        if has("CMakeLists.txt") {
+            tools.push(BuildTool::CMake);
+        }
+        if has("Makefile") {
+            tools.push(BuildTool::Make);
+        }
+
+        Ok(tools)
+    }
+
+    // ========================================================================
+    // Private helper methods
+    // ========================================================================
+
     /// Check if the project is a monorepo
     fn is_monorepo(&self, root: &Path, languages: &[Language]) -> Result<bool, ResearchError> {
         // Rust workspace
@@ -557,6 +633,22 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_detect_build_tools_prefers_lock_files() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("Cargo.toml"), "").unwrap();
+        std::fs::write(temp_dir.path().join("package.json"), "{}").unwrap();
+        std::fs::write(temp_dir.path().join("pnpm-lock.yaml"), "").unwrap();
+        std::fs::write(temp_dir.path().join("Makefile"), "").unwrap();
+
+        let analyzer = ProjectAnalyzer::new();
+        let tools = analyzer.detect_build_tools(temp_dir.path()).unwrap();
+        assert_eq!(
+            tools,
+            vec![BuildTool::Cargo, BuildTool::Pnpm, BuildTool::Make]
+        );
+    }
+
     #[test]
     fn test_find_source_directories() {
         let temp_dir = TempDir::new().unwrap();
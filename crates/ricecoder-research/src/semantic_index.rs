@@ -1,6 +1,9 @@
 //! Semantic index for fast symbol lookup and search
 
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
 
 use crate::models::{SearchResult, Symbol, SymbolKind, SymbolReference};
 
@@ -158,6 +161,45 @@ impl SemanticIndex {
             .sum()
     }
 
+    /// Get all files that have symbols in the index
+    pub fn indexed_files(&self) -> Vec<&PathBuf> {
+        self.symbols_by_file.keys().collect()
+    }
+
+    /// Remove everything indexed from a file
+    ///
+    /// Drops the symbols defined in the file together with all references to
+    /// them, and every reference located in the file. Returns the IDs of the
+    /// removed symbols.
+    pub fn remove_file(&mut self, file: &Path) -> Vec<String> {
+        let removed = self.symbols_by_file.remove(file).unwrap_or_default();
+
+        for symbol_id in &removed {
+            if let Some(symbol) = self.symbols_by_id.remove(symbol_id) {
+                if let Some(ids) = self.symbols_by_name.get_mut(&symbol.name) {
+                    ids.retain(|id| id != symbol_id);
+                    if ids.is_empty() {
+                        self.symbols_by_name.remove(&symbol.name);
+                    }
+                }
+            }
+            self.references_by_symbol.remove(symbol_id);
+        }
+
+        self.remove_references_in_file(file);
+        removed
+    }
+
+    /// Remove every reference located in a file, returning how many were removed
+    pub fn remove_references_in_file(&mut self, file: &Path) -> usize {
+        let before = self.reference_count();
+        self.references_by_symbol.retain(|_, refs| {
+            refs.retain(|reference| reference.file != file);
+            !refs.is_empty()
+        });
+        before - self.reference_count()
+    }
+
     /// Clear the index
     pub fn clear(&mut self) {
         self.symbols_by_id.clear();
@@ -357,4 +399,40 @@ mod tests {
         let all = index.all_symbols();
         assert_eq!(all.len(), 2);
     }
+
+    #[test]
+    fn test_remove_file() {
+        let mut index = SemanticIndex::new();
+        let mut other = create_test_symbol("sym2", "helper", SymbolKind::Function);
+        other.file = PathBuf::from("other.rs");
+        index.add_symbol(create_test_symbol(
+            "sym1",
+            "my_function",
+            SymbolKind::Function,
+        ));
+        index.add_symbol(other);
+
+        // other.rs uses sym1, test.rs uses sym2
+        index.add_reference(SymbolReference {
+            symbol_id: "sym1".to_string(),
+            file: PathBuf::from("other.rs"),
+            line: 3,
+            kind: crate::models::ReferenceKind::Usage,
+        });
+        index.add_reference(SymbolReference {
+            symbol_id: "sym2".to_string(),
+            file: PathBuf::from("test.rs"),
+            line: 7,
+            kind: crate::models::ReferenceKind::Usage,
+        });
+
+        let removed = index.remove_file(Path::new("test.rs"));
+
+        assert_eq!(removed, vec!["sym1".to_string()]);
+        assert!(index.get_symbol("sym1").is_none());
+        assert!(index.get_symbols_by_name("my_function").is_empty());
+        assert_eq!(index.symbol_count(), 1);
+        assert_eq!(index.reference_count(), 0);
+        assert_eq!(index.indexed_files(), vec![&PathBuf::from("other.rs")]);
+    }
 }
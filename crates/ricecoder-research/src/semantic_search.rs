@@ -0,0 +1,674 @@
+//! Embedding-based semantic code search
+//!
+//! Code is split into chunks along symbol boundaries, each chunk is embedded
+//! through a `ricecoder-providers` [`Provider`], and the vectors are kept in a
+//! [`VectorStore`]. Natural-language queries ("where do we validate JWTs?")
+//! are embedded the same way and answered with ranked code locations, merged
+//! with keyword matches from the [`SearchEngine`] when one is attached.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    path::{Path, PathBuf},
+    sync::{Arc, PoisonError, RwLock},
+};
+
+use ricecoder_providers::{EmbeddingRequest, Provider};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+use crate::{
+    declarations::{extract_symbols, language_for},
+    error::ResearchError,
+    models::SymbolKind,
+    search_engine::{SearchEngine, SearchOptions},
+};
+
+/// Rank offset used by reciprocal rank fusion
+const RRF_K: f32 = 60.0;
+
+/// Words ignored when deriving keyword terms from a natural-language query
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "are", "do", "does", "for", "how", "in", "is", "it", "of", "on", "or", "the",
+    "to", "we", "what", "where", "which", "who", "why", "with",
+];
+
+/// A contiguous piece of a source file, usually one symbol
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CodeChunk {
+    /// Stable chunk ID (`path:start-end`)
+    pub id: String,
+    /// File containing the chunk
+    pub file: PathBuf,
+    /// Symbol the chunk belongs to, if any
+    pub symbol: Option<String>,
+    /// Kind of that symbol
+    pub kind: Option<SymbolKind>,
+    /// First line of the chunk (1-indexed)
+    pub start_line: usize,
+    /// Last line of the chunk (inclusive)
+    pub end_line: usize,
+    /// Source text of the chunk
+    pub content: String,
+}
+
+impl CodeChunk {
+    fn new(
+        file: &Path,
+        symbol: Option<(&str, SymbolKind)>,
+        start_line: usize,
+        lines: &[&str],
+    ) -> Self {
+        let end_line = start_line + lines.len() - 1;
+        CodeChunk {
+            id: format!("{}:{}-{}", file.display(), start_line, end_line),
+            file: file.to_path_buf(),
+            symbol: symbol.map(|(name, _)| name.to_string()),
+            kind: symbol.map(|(_, kind)| kind),
+            start_line,
+            end_line,
+            content: lines.join("\n"),
+        }
+    }
+
+    /// Text sent to the embedding model, including the file path for context
+    fn embedding_text(&self) -> String {
+        format!("{}\n{}", self.file.display(), self.content)
+    }
+}
+
+/// Splits source files into chunks along symbol boundaries
+#[derive(Debug, Clone)]
+pub struct CodeChunker {
+    max_lines: usize,
+}
+
+impl Default for CodeChunker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CodeChunker {
+    /// Create a chunker with the default limit of 80 lines per chunk
+    pub fn new() -> Self {
+        CodeChunker { max_lines: 80 }
+    }
+
+    /// Set the maximum chunk length; longer symbols are split into windows
+    pub fn with_max_lines(mut self, max_lines: usize) -> Self {
+        self.max_lines = max_lines.max(1);
+        self
+    }
+
+    /// Chunk a file
+    ///
+    /// Each declaration starts a chunk that runs until the next one; any code
+    /// before the first declaration becomes its own chunk. Files in languages
+    /// without declaration support are chunked by line windows.
+    pub fn chunk(&self, path: &Path, content: &str) -> Vec<CodeChunk> {
+        let lines: Vec<&str> = content.lines().collect();
+        let mut symbols = language_for(path)
+            .map(|language| extract_symbols(path, &language, content))
+            .unwrap_or_default();
+        symbols.sort_by_key(|symbol| symbol.line);
+        symbols.dedup_by_key(|symbol| symbol.line);
+
+        // Doc comments, attributes and decorators belong to the declaration below them
+        let mut starts = Vec::with_capacity(symbols.len());
+        let mut previous = 0;
+        for symbol in &symbols {
+            let mut start = symbol.line;
+            while start - 1 > previous && is_leading_annotation(lines[start - 2]) {
+                start -= 1;
+            }
+            starts.push(start);
+            previous = symbol.line;
+        }
+
+        let mut spans: Vec<(Option<(&str, SymbolKind)>, usize, usize)> = Vec::new();
+        let first = starts.first().map_or(lines.len() + 1, |&start| start);
+        if first > 1 {
+            spans.push((None, 1, first - 1));
+        }
+        for (i, symbol) in symbols.iter().enumerate() {
+            let end = starts.get(i + 1).map_or(lines.len(), |&next| next - 1);
+            spans.push((Some((symbol.name.as_str(), symbol.kind)), starts[i], end));
+        }
+
+        let mut chunks = Vec::new();
+        for (symbol, start, end) in spans {
+            let mut end = end;
+            while end >= start && lines[end - 1].trim().is_empty() {
+                end -= 1;
+            }
+            let mut window_start = start;
+            while window_start <= end {
+                let window_end = (window_start + self.max_lines - 1).min(end);
+                let window = &lines[window_start - 1..window_end];
+                if window.iter().any(|line| !line.trim().is_empty()) {
+                    chunks.push(CodeChunk::new(path, symbol, window_start, window));
+                }
+                window_start = window_end + 1;
+            }
+        }
+        chunks
+    }
+}
+
+/// An embedded chunk held by a [`VectorStore`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VectorRecord {
+    /// The embedded chunk
+    pub chunk: CodeChunk,
+    /// Embedding vector
+    pub vector: Vec<f32>,
+    /// Hash of the embedded text, used to skip re-embedding unchanged chunks
+    pub content_hash: u64,
+}
+
+/// Storage and nearest-neighbour lookup for chunk embeddings
+pub trait VectorStore: Send + Sync + fmt::Debug {
+    /// Insert or replace records by chunk ID
+    fn upsert(&self, records: Vec<VectorRecord>);
+
+    /// Get a record by chunk ID
+    fn get(&self, chunk_id: &str) -> Option<VectorRecord>;
+
+    /// Chunks stored for a file, in line order
+    fn chunks_in_file(&self, file: &Path) -> Vec<CodeChunk>;
+
+    /// Remove every record for a file, returning how many were removed
+    fn remove_file(&self, file: &Path) -> usize;
+
+    /// Find the `limit` records most similar to `query`, most similar first
+    fn search(&self, query: &[f32], limit: usize) -> Vec<(VectorRecord, f32)>;
+
+    /// Number of stored records
+    fn len(&self) -> usize;
+
+    /// Whether the store is empty
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// In-process [`VectorStore`] using exhaustive cosine similarity
+#[derive(Debug, Default)]
+pub struct InMemoryVectorStore {
+    records: RwLock<HashMap<String, VectorRecord>>,
+}
+
+impl InMemoryVectorStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl VectorStore for InMemoryVectorStore {
+    fn upsert(&self, records: Vec<VectorRecord>) {
+        let mut stored = self.records.write().unwrap_or_else(PoisonError::into_inner);
+        for record in records {
+            stored.insert(record.chunk.id.clone(), record);
+        }
+    }
+
+    fn get(&self, chunk_id: &str) -> Option<VectorRecord> {
+        let stored = self.records.read().unwrap_or_else(PoisonError::into_inner);
+        stored.get(chunk_id).cloned()
+    }
+
+    fn chunks_in_file(&self, file: &Path) -> Vec<CodeChunk> {
+        let stored = self.records.read().unwrap_or_else(PoisonError::into_inner);
+        let mut chunks: Vec<CodeChunk> = stored
+            .values()
+            .filter(|record| record.chunk.file == file)
+            .map(|record| record.chunk.clone())
+            .collect();
+        chunks.sort_by_key(|chunk| chunk.start_line);
+        chunks
+    }
+
+    fn remove_file(&self, file: &Path) -> usize {
+        let mut stored = self.records.write().unwrap_or_else(PoisonError::into_inner);
+        let before = stored.len();
+        stored.retain(|_, record| record.chunk.file != file);
+        before - stored.len()
+    }
+
+    fn search(&self, query: &[f32], limit: usize) -> Vec<(VectorRecord, f32)> {
+        let stored = self.records.read().unwrap_or_else(PoisonError::into_inner);
+        let mut scored: Vec<(VectorRecord, f32)> = stored
+            .values()
+            .map(|record| (record.clone(), cosine_similarity(query, &record.vector)))
+            .collect();
+        scored.sort_by(|a, b| {
+            b.1.partial_cmp(&a.1)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.0.chunk.id.cmp(&b.0.chunk.id))
+        });
+        scored.truncate(limit);
+        scored
+    }
+
+    fn len(&self) -> usize {
+        self.records
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+}
+
+/// Cosine similarity of two vectors; 0.0 for mismatched or zero vectors
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+/// Where a search hit came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HitSource {
+    /// Embedding similarity
+    Semantic,
+    /// Symbol name match from the keyword search engine
+    Keyword,
+}
+
+/// A ranked, citable code location answering a query
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SemanticSearchHit {
+    /// File containing the match
+    pub file: PathBuf,
+    /// First line of the match (1-indexed)
+    pub start_line: usize,
+    /// Last line of the match (inclusive)
+    pub end_line: usize,
+    /// Symbol at the location, if known
+    pub symbol: Option<String>,
+    /// Leading lines of the matched code
+    pub snippet: String,
+    /// Fused ranking score (higher is better)
+    pub score: f32,
+    /// Cosine similarity to the query, for semantic matches
+    pub similarity: Option<f32>,
+    /// Keyword relevance, for keyword matches
+    pub keyword_relevance: Option<f32>,
+    /// Which searches produced the hit
+    pub sources: Vec<HitSource>,
+}
+
+impl SemanticSearchHit {
+    /// Citation for the hit in `path:start-end` form
+    pub fn citation(&self) -> String {
+        if self.start_line == self.end_line {
+            format!("{}:{}", self.file.display(), self.start_line)
+        } else {
+            format!(
+                "{}:{}-{}",
+                self.file.display(),
+                self.start_line,
+                self.end_line
+            )
+        }
+    }
+}
+
+/// Counts from an indexing run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmbeddingIndexStats {
+    /// Files chunked
+    pub files: usize,
+    /// Chunks stored
+    pub chunks: usize,
+    /// Chunks sent to the embedding model (unchanged chunks are reused)
+    pub embedded: usize,
+}
+
+/// Natural-language code search over chunk embeddings
+pub struct SemanticCodeSearch {
+    provider: Arc<dyn Provider>,
+    model: String,
+    store: Arc<dyn VectorStore>,
+    chunker: CodeChunker,
+    keyword_engine: Option<Arc<SearchEngine>>,
+    batch_size: usize,
+    snippet_lines: usize,
+}
+
+impl fmt::Debug for SemanticCodeSearch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SemanticCodeSearch")
+            .field("provider", &self.provider.id())
+            .field("model", &self.model)
+            .field("store", &self.store)
+            .field("chunker", &self.chunker)
+            .field("batch_size", &self.batch_size)
+            .finish()
+    }
+}
+
+impl SemanticCodeSearch {
+    /// Create a search using `model` on `provider` and an in-memory vector store
+    pub fn new(provider: Arc<dyn Provider>, model: impl Into<String>) -> Self {
+        SemanticCodeSearch {
+            provider,
+            model: model.into(),
+            store: Arc::new(InMemoryVectorStore::new()),
+            chunker: CodeChunker::new(),
+            keyword_engine: None,
+            batch_size: 32,
+            snippet_lines: 5,
+        }
+    }
+
+    /// Use a different vector store
+    pub fn with_store(mut self, store: Arc<dyn VectorStore>) -> Self {
+        self.store = store;
+        self
+    }
+
+    /// Use a different chunker
+    pub fn with_chunker(mut self, chunker: CodeChunker) -> Self {
+        self.chunker = chunker;
+        self
+    }
+
+    /// Merge keyword results from a search engine into every query
+    pub fn with_keyword_engine(mut self, engine: Arc<SearchEngine>) -> Self {
+        self.keyword_engine = Some(engine);
+        self
+    }
+
+    /// Set how many chunks are sent per embedding request
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// The vector store holding chunk embeddings
+    pub fn store(&self) -> &Arc<dyn VectorStore> {
+        &self.store
+    }
+
+    /// Chunk and embed a file, replacing anything stored for it before
+    ///
+    /// Chunks whose text is unchanged keep their existing vectors.
+    pub async fn index_file(
+        &self,
+        path: &Path,
+        content: &str,
+    ) -> Result<EmbeddingIndexStats, ResearchError> {
+        let chunks = self.chunker.chunk(path, content);
+        let mut records = Vec::with_capacity(chunks.len());
+        let mut pending = Vec::new();
+        for chunk in chunks {
+            let content_hash = fxhash::hash64(&chunk.embedding_text());
+            match self.store.get(&chunk.id) {
+                Some(existing) if existing.content_hash == content_hash => records.push(existing),
+                _ => pending.push((chunk, content_hash)),
+            }
+        }
+
+        let embedded = pending.len();
+        for batch in pending.chunks(self.batch_size) {
+            let texts = batch
+                .iter()
+                .map(|(chunk, _)| chunk.embedding_text())
+                .collect();
+            let vectors = self
+                .embed(texts)
+                .await
+                .map_err(|reason| ResearchError::IndexError {
+                    reason,
+                    operation: format!("embed chunks of {}", path.display()),
+                })?;
+            records.extend(
+                batch
+                    .iter()
+                    .zip(vectors)
+                    .map(|((chunk, hash), vector)| VectorRecord {
+                        chunk: chunk.clone(),
+                        vector,
+                        content_hash: *hash,
+                    }),
+            );
+        }
+
+        let stats = EmbeddingIndexStats {
+            files: 1,
+            chunks: records.len(),
+            embedded,
+        };
+        self.store.remove_file(path);
+        self.store.upsert(records);
+        debug!(
+            "Indexed {:?}: {} chunks, {} embedded",
+            path, stats.chunks, stats.embedded
+        );
+        Ok(stats)
+    }
+
+    /// Chunk and embed every supported source file under `root`
+    pub async fn index_project(&self, root: &Path) -> Result<EmbeddingIndexStats, ResearchError> {
+        let mut stats = EmbeddingIndexStats::default();
+        for entry in ignore::WalkBuilder::new(root).build() {
+            let entry = entry.map_err(|e| ResearchError::ScanFailed {
+                reason: e.to_string(),
+            })?;
+            let path = entry.path();
+            if !path.is_file() || language_for(path).is_none() {
+                continue;
+            }
+            let content = match std::fs::read_to_string(path) {
+                Ok(content) => content,
+                Err(e) => {
+                    warn!("Skipping {:?} for semantic indexing: {}", path, e);
+                    continue;
+                }
+            };
+            let file_stats = self.index_file(path, &content).await?;
+            stats.files += 1;
+            stats.chunks += file_stats.chunks;
+            stats.embedded += file_stats.embedded;
+        }
+        info!(
+            "Semantic index of {:?}: {} files, {} chunks ({} embedded)",
+            root, stats.files, stats.chunks, stats.embedded
+        );
+        Ok(stats)
+    }
+
+    /// Drop the stored chunks of a file
+    pub fn remove_file(&self, path: &Path) -> usize {
+        self.store.remove_file(path)
+    }
+
+    /// Answer a natural-language query with up to `limit` ranked locations
+    ///
+    /// Semantic and keyword rankings are merged with reciprocal rank fusion;
+    /// locations found by both rank highest.
+    pub async fn search(
+        &self,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<SemanticSearchHit>, ResearchError> {
+        let candidates = limit.saturating_mul(2).max(10);
+        let query_vector = self
+            .embed(vec![query.to_string()])
+            .await
+            .map_err(|reason| ResearchError::SearchFailed {
+                query: query.to_string(),
+                reason,
+            })?
+            .into_iter()
+            .next()
+            .unwrap_or_default();
+
+        let mut hits: Vec<SemanticSearchHit> = Vec::new();
+        let semantic = self.store.search(&query_vector, candidates);
+        for (rank, (record, similarity)) in semantic.iter().enumerate() {
+            let mut hit = self.hit_for_chunk(&record.chunk);
+            hit.score += 1.0 / (RRF_K + rank as f32 + 1.0);
+            hit.similarity = Some(*similarity);
+            hit.sources.push(HitSource::Semantic);
+            hits.push(hit);
+        }
+
+        for (rank, (symbol, relevance)) in self
+            .keyword_matches(query, candidates)
+            .into_iter()
+            .enumerate()
+        {
+            let score = 1.0 / (RRF_K + rank as f32 + 1.0);
+            let existing = hits.iter().position(|hit| {
+                hit.file == symbol.file
+                    && hit.start_line <= symbol.line
+                    && symbol.line <= hit.end_line
+            });
+            let position = match existing {
+                Some(position) => position,
+                None => {
+                    let hit = self
+                        .store
+                        .chunks_in_file(&symbol.file)
+                        .into_iter()
+                        .find(|chunk| {
+                            chunk.start_line <= symbol.line && symbol.line <= chunk.end_line
+                        })
+                        .map(|chunk| self.hit_for_chunk(&chunk))
+                        .unwrap_or_else(|| SemanticSearchHit {
+                            file: symbol.file.clone(),
+                            start_line: symbol.line,
+                            end_line: symbol.line,
+                            symbol: Some(symbol.name.clone()),
+                            snippet: String::new(),
+                            score: 0.0,
+                            similarity: None,
+                            keyword_relevance: None,
+                            sources: Vec::new(),
+                        });
+                    hits.push(hit);
+                    hits.len() - 1
+                }
+            };
+            let hit = &mut hits[position];
+            if !hit.sources.contains(&HitSource::Keyword) {
+                hit.score += score;
+                hit.keyword_relevance = Some(relevance);
+                hit.sources.push(HitSource::Keyword);
+            }
+        }
+
+        hits.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.citation().cmp(&b.citation()))
+        });
+        hits.truncate(limit);
+        Ok(hits)
+    }
+
+    async fn embed(&self, input: Vec<String>) -> Result<Vec<Vec<f32>>, String> {
+        let expected = input.len();
+        let response = self
+            .provider
+            .embed(EmbeddingRequest {
+                model: self.model.clone(),
+                input,
+            })
+            .await
+            .map_err(|e| e.to_string())?;
+        if response.embeddings.len() != expected {
+            return Err(format!(
+                "{} returned {} embeddings for {} inputs",
+                self.provider.id(),
+                response.embeddings.len(),
+                expected
+            ));
+        }
+        Ok(response.embeddings)
+    }
+
+    /// Symbols matching the query's keywords, best first
+    fn keyword_matches(&self, query: &str, limit: usize) -> Vec<(crate::models::Symbol, f32)> {
+        let engine = match &self.keyword_engine {
+            Some(engine) => engine,
+            None => return Vec::new(),
+        };
+        let options = SearchOptions {
+            max_results: limit,
+            ..SearchOptions::default()
+        };
+
+        let mut best: HashMap<String, (crate::models::Symbol, f32)> = HashMap::new();
+        for term in keyword_terms(query) {
+            for result in engine.full_text_search(&term, &options) {
+                let entry = best
+                    .entry(result.symbol.id.clone())
+                    .or_insert_with(|| (result.symbol.clone(), 0.0));
+                entry.1 = entry.1.max(result.relevance);
+            }
+        }
+        let mut matches: Vec<(crate::models::Symbol, f32)> = best.into_values().collect();
+        matches.sort_by(|a, b| {
+            b.1.partial_cmp(&a.1)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.0.id.cmp(&b.0.id))
+        });
+        matches.truncate(limit);
+        matches
+    }
+
+    fn hit_for_chunk(&self, chunk: &CodeChunk) -> SemanticSearchHit {
+        SemanticSearchHit {
+            file: chunk.file.clone(),
+            start_line: chunk.start_line,
+            end_line: chunk.end_line,
+            symbol: chunk.symbol.clone(),
+            snippet: chunk
+                .content
+                .lines()
+                .take(self.snippet_lines)
+                .collect::<Vec<_>>()
+                .join("\n"),
+            score: 0.0,
+            similarity: None,
+            keyword_relevance: None,
+            sources: Vec::new(),
+        }
+    }
+}
+
+/// Whether a line is a comment, attribute or decorator attached to the next line
+fn is_leading_annotation(line: &str) -> bool {
+    let line = line.trim_start();
+    ["//", "/*", "*", "#", "@"]
+        .iter()
+        .any(|prefix| line.starts_with(prefix))
+}
+
+/// Keyword terms of a natural-language query, lowercased and singularized
+fn keyword_terms(query: &str) -> Vec<String> {
+    let mut terms: Vec<String> = query
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .map(str::to_lowercase)
+        .filter(|word| word.len() >= 3 && !STOP_WORDS.contains(&word.as_str()))
+        .map(|word| match word.strip_suffix('s') {
+            Some(stem) if stem.len() >= 3 && !stem.ends_with('s') => stem.to_string(),
+            _ => word,
+        })
+        .collect();
+    terms.dedup();
+    terms
+}
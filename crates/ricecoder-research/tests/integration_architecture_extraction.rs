@@ -0,0 +1,330 @@
+//! Integration tests for architecture extraction
+//! Tests package and module dependency graphs, layering rules, service
+//! boundaries, cycles, and diagram export
+
+use std::{fs, path::Path};
+
+use ricecoder_research::{
+    ArchitectureAnalyzer, ArchitectureRules, DependencyKind, DiagramGranularity, ResearchError,
+    ViolationKind,
+};
+use tempfile::TempDir;
+
+fn write(root: &Path, path: &str, content: &str) {
+    let path = root.join(path);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+/// Cargo workspace with a CLI binary, a core library and an infrastructure library
+fn create_workspace() -> TempDir {
+    let dir = TempDir::new().expect("Failed to create temp dir");
+    let root = dir.path();
+    write(
+        root,
+        "Cargo.toml",
+        "[workspace]\nmembers = [\"crates/*\"]\n",
+    );
+
+    write(
+        root,
+        "crates/cli/Cargo.toml",
+        "[package]\nname = \"shop-cli\"\n\n[dependencies]\nshop-core = { path = \"../core\" }\nclap = \"4\"\n",
+    );
+    write(
+        root,
+        "crates/cli/src/main.rs",
+        "use shop_core::service::Checkout;\n\nfn main() {}\n",
+    );
+
+    write(
+        root,
+        "crates/core/Cargo.toml",
+        "[package]\nname = \"shop-core\"\n\n[dependencies]\nshop-infra = { path = \"../infra\" }\nserde = \"1\"\n",
+    );
+    write(
+        root,
+        "crates/core/src/lib.rs",
+        "pub mod domain;\npub mod service;\n\npub use service::Checkout;\n",
+    );
+    write(
+        root,
+        "crates/core/src/service.rs",
+        "use crate::domain::{\n    Order as DomainOrder,\n    User,\n};\n\npub struct Checkout;\n",
+    );
+    write(
+        root,
+        "crates/core/src/domain/mod.rs",
+        "use super::service::Checkout;\nuse shop_infra::db::Pool;\n\npub struct User;\npub struct Order;\n",
+    );
+
+    write(
+        root,
+        "crates/infra/Cargo.toml",
+        "[package]\nname = \"shop-infra\"\n",
+    );
+    write(root, "crates/infra/src/lib.rs", "pub mod db;\n");
+    write(root, "crates/infra/src/db.rs", "pub struct Pool;\n");
+    dir
+}
+
+const RULES: &str = r#"
+layers:
+  - name: interface
+    modules: ["shop-cli"]
+  - name: application
+    modules: ["shop_core::service"]
+  - name: infrastructure
+    modules: ["shop_infra", "shop_infra::**"]
+  - name: domain
+    modules: ["shop_core::domain"]
+forbid_cycles: true
+"#;
+
+#[test]
+fn test_builds_package_and_module_graph() {
+    let project = create_workspace();
+    let model = ArchitectureAnalyzer::new()
+        .analyze(project.path())
+        .expect("Analysis failed");
+
+    let names: Vec<&str> = model.packages.iter().map(|p| p.name.as_str()).collect();
+    assert!(names.contains(&"shop-cli"));
+    assert!(names.contains(&"shop-core"));
+    assert!(names.contains(&"shop-infra"));
+
+    let cli = model.package("shop-cli").unwrap();
+    assert!(cli.service);
+    assert_eq!(cli.internal_dependencies, vec!["shop-core".to_string()]);
+    assert_eq!(cli.external_dependencies, vec!["clap".to_string()]);
+    assert!(!model.package("shop-core").unwrap().service);
+
+    // mod.rs files name their directory; multi-line use trees and aliases resolve
+    let domain = model.module("shop_core::domain").expect("domain module");
+    assert_eq!(domain.files.len(), 1);
+    assert_eq!(
+        model.dependencies_of("shop_core::service"),
+        vec!["shop_core::domain"]
+    );
+    assert_eq!(
+        model.dependencies_of("shop_core::domain"),
+        vec!["shop_core::service", "shop_infra::db"]
+    );
+    assert_eq!(
+        model.dependents_of("shop_core::service"),
+        vec!["shop_cli", "shop_core", "shop_core::domain"]
+    );
+
+    let edge = model
+        .module_edges
+        .iter()
+        .find(|edge| edge.from == "shop_core::domain" && edge.to == "shop_infra::db")
+        .unwrap();
+    assert_eq!(edge.locations[0].line, 2);
+    assert_eq!(
+        edge.locations[0].file,
+        Path::new("crates/core/src/domain/mod.rs")
+    );
+
+    assert!(model
+        .package_edges
+        .iter()
+        .any(|edge| edge.from == "shop-core"
+            && edge.to == "shop-infra"
+            && edge.kind == DependencyKind::Manifest));
+    assert_eq!(
+        model.cycles,
+        vec![vec![
+            "shop_core::domain".to_string(),
+            "shop_core::service".to_string()
+        ]]
+    );
+
+    // Without rules nothing is a violation
+    assert!(model.is_compliant());
+}
+
+#[test]
+fn test_detects_layering_violations() {
+    let project = create_workspace();
+    let rules = ArchitectureRules::from_yaml(RULES).unwrap();
+    let model = ArchitectureAnalyzer::new()
+        .with_rules(rules)
+        .analyze(project.path())
+        .unwrap();
+
+    assert_eq!(
+        model.module("shop_cli").unwrap().layer.as_deref(),
+        Some("interface")
+    );
+    assert_eq!(
+        model.module("shop_infra::db").unwrap().layer.as_deref(),
+        Some("infrastructure")
+    );
+
+    let layering: Vec<_> = model
+        .violations
+        .iter()
+        .filter(|v| v.kind == ViolationKind::Layering)
+        .collect();
+    assert_eq!(layering.len(), 2);
+    assert!(layering
+        .iter()
+        .any(|v| v.from == "shop_core::domain" && v.to == "shop_infra::db"));
+    assert!(layering
+        .iter()
+        .any(|v| v.from == "shop_core::domain" && v.to == "shop_core::service"));
+    assert!(model
+        .violations
+        .iter()
+        .any(|v| v.kind == ViolationKind::Cycle));
+
+    // Strict mode also rejects skipping layers
+    let mut rules = ArchitectureRules::from_yaml(RULES).unwrap();
+    rules.strict = true;
+    rules.forbid_cycles = false;
+    let model = ArchitectureAnalyzer::new()
+        .with_rules(rules)
+        .analyze(project.path())
+        .unwrap();
+    assert!(model
+        .violations
+        .iter()
+        .any(|v| v.from == "shop_core::service"
+            && v.to == "shop_core::domain"
+            && v.kind == ViolationKind::Layering));
+}
+
+#[test]
+fn test_rules_file_and_validation() {
+    let project = create_workspace();
+    write(
+        project.path(),
+        ".ricecoder/architecture.yaml",
+        "layers:\n  - name: app\n    modules: [\"shop_core::**\"]\n  - name: infra\n    modules: [\"shop-infra\"]\nforbidden:\n  - from: app\n    to: infra\n",
+    );
+    let model = ArchitectureAnalyzer::new().analyze(project.path()).unwrap();
+    assert_eq!(model.layers, vec!["app".to_string(), "infra".to_string()]);
+    let forbidden: Vec<_> = model
+        .violations
+        .iter()
+        .filter(|v| v.kind == ViolationKind::Forbidden)
+        .collect();
+    assert_eq!(forbidden.len(), 1);
+    assert_eq!(forbidden[0].to, "shop_infra::db");
+
+    let result = ArchitectureRules::from_yaml(
+        "layers:\n  - name: app\n    modules: []\nforbidden:\n  - from: app\n    to: missing\n",
+    );
+    assert!(matches!(
+        result,
+        Err(ResearchError::InvalidConfiguration { .. })
+    ));
+}
+
+#[test]
+fn test_service_boundaries_and_script_imports() {
+    let dir = TempDir::new().unwrap();
+    let root = dir.path();
+    write(
+        root,
+        "services/orders/package.json",
+        r#"{"name": "orders", "scripts": {"start": "node index.js"}, "dependencies": {"express": "4"}}"#,
+    );
+    write(
+        root,
+        "services/orders/index.ts",
+        "import { charge } from 'billing';\nimport { route } from './routes';\n",
+    );
+    write(
+        root,
+        "services/orders/routes.ts",
+        "export const route = 1;\n",
+    );
+    write(
+        root,
+        "services/billing/package.json",
+        r#"{"name": "billing", "bin": "server.js"}"#,
+    );
+    write(
+        root,
+        "services/billing/index.ts",
+        "export function charge() {}\n",
+    );
+
+    let rules = ArchitectureRules {
+        isolate_services: true,
+        ..Default::default()
+    };
+    let model = ArchitectureAnalyzer::new()
+        .with_rules(rules)
+        .analyze(root)
+        .unwrap();
+
+    assert_eq!(
+        model.dependencies_of("orders"),
+        vec!["billing", "orders/routes"]
+    );
+    let violation = &model.violations[0];
+    assert_eq!(model.violations.len(), 1);
+    assert_eq!(violation.kind, ViolationKind::ServiceBoundary);
+    assert_eq!(
+        (violation.from.as_str(), violation.to.as_str()),
+        ("orders", "billing")
+    );
+}
+
+#[test]
+fn test_python_imports() {
+    let dir = TempDir::new().unwrap();
+    let root = dir.path();
+    write(root, "app/__init__.py", "");
+    write(
+        root,
+        "app/api.py",
+        "from app.models import User\nfrom . import services\nimport os\n",
+    );
+    write(root, "app/models.py", "class User: pass\n");
+    write(
+        root,
+        "app/services/__init__.py",
+        "from ..models import User\n",
+    );
+
+    let model = ArchitectureAnalyzer::new().analyze(root).unwrap();
+    assert_eq!(
+        model.dependencies_of("app.api"),
+        vec!["app.models", "app.services"]
+    );
+    assert_eq!(model.dependencies_of("app.services"), vec!["app.models"]);
+}
+
+#[test]
+fn test_diagram_export() {
+    let project = create_workspace();
+    let rules = ArchitectureRules::from_yaml(RULES).unwrap();
+    let model = ArchitectureAnalyzer::new()
+        .with_rules(rules)
+        .analyze(project.path())
+        .unwrap();
+
+    let dot = model.to_graphviz(DiagramGranularity::Module);
+    assert!(dot.starts_with("digraph architecture {"));
+    assert!(dot.contains("label=\"domain\";"));
+    assert!(dot.contains("\"shop_core::domain\" -> \"shop_infra::db\" [color=red];"));
+    assert!(dot.contains("\"shop_cli\" -> \"shop_core::service\";"));
+
+    let mermaid = model.to_mermaid(DiagramGranularity::Module);
+    assert!(mermaid.starts_with("graph LR\n"));
+    assert!(mermaid.contains("subgraph layer0[\"interface\"]"));
+    assert!(mermaid.contains("-->|violation|"));
+    assert!(mermaid.contains("linkStyle"));
+
+    let packages = model.to_mermaid(DiagramGranularity::Package);
+    assert!(packages.contains("[\"shop-core\"]"));
+    assert!(!packages.contains("subgraph"));
+
+    let json = model.to_json().unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["violations"][0]["kind"], "Layering");
+}
@@ -0,0 +1,194 @@
+//! Integration tests for context budget packing
+//! Tests ranking by relevance, search hits and recency, deduplication of
+//! overlapping ranges, budget enforcement, and the provenance manifest
+
+use std::{fs, path::PathBuf};
+
+use chrono::{Duration, Utc};
+use ricecoder_research::{
+    ContextItemKind, ContextPacker, ContextRequest, ContextSource, FileContext, HitSource,
+    SemanticSearchHit, StandardsProfile,
+};
+use tempfile::TempDir;
+
+const AUTH_RS: &str = "use crate::token::Claims;
+
+/// Validate JWT tokens from the Authorization header
+pub fn validate_jwt(token: &str) -> bool {
+    let claims = decode(token);
+    claims.is_valid()
+}
+
+pub fn refresh_session(user: &str) {
+    store_session(user);
+}";
+
+const BILLING_RS: &str = "pub fn charge_invoice(total: u64) {
+    submit_payment(total);
+}";
+
+fn file(path: &str, content: &str, relevance: f32) -> FileContext {
+    FileContext {
+        path: PathBuf::from(path),
+        relevance,
+        summary: None,
+        content: Some(content.to_string()),
+    }
+}
+
+fn hit(path: &str, start_line: usize, end_line: usize, similarity: f32) -> SemanticSearchHit {
+    SemanticSearchHit {
+        file: PathBuf::from(path),
+        start_line,
+        end_line,
+        symbol: Some("validate_jwt".to_string()),
+        snippet: String::new(),
+        score: 0.03,
+        similarity: Some(similarity),
+        keyword_relevance: None,
+        sources: vec![HitSource::Semantic],
+    }
+}
+
+#[test]
+fn test_selects_relevant_files_after_standards() {
+    let request = ContextRequest::new("validate jwt token", 10_000)
+        .with_files([
+            file("src/auth.rs", AUTH_RS, 0.0),
+            file("src/billing.rs", BILLING_RS, 0.0),
+        ])
+        .with_standards(StandardsProfile::default());
+
+    let manifest = ContextPacker::new().pack(&request).expect("Packing failed");
+
+    assert_eq!(manifest.citations(), vec!["standards", "src/auth.rs:1-11"]);
+    let standards = &manifest.entries[0];
+    assert_eq!(standards.kind, ContextItemKind::Standards);
+    assert!(standards.content.contains("functions snake_case"));
+
+    let auth = &manifest.entries[1];
+    assert_eq!(auth.kind, ContextItemKind::File);
+    assert_eq!(auth.provenance[0].source, ContextSource::Relevance);
+    assert_eq!(
+        manifest.used_tokens,
+        manifest.entries.iter().map(|e| e.tokens).sum::<usize>()
+    );
+    // Unrelated files are not candidates at all
+    assert!(manifest.dropped.is_empty());
+}
+
+#[test]
+fn test_overlapping_ranges_are_deduplicated() {
+    // The whole file outranks the hit, so the hit only adds provenance
+    let request = ContextRequest::new("authentication", 10_000)
+        .with_files([file("src/auth.rs", AUTH_RS, 1.0)])
+        .with_search_hits([hit("src/auth.rs", 3, 7, 0.5)]);
+    let manifest = ContextPacker::new().pack(&request).unwrap();
+
+    assert_eq!(manifest.entries.len(), 1);
+    assert_eq!(manifest.deduplicated, 1);
+    let sources: Vec<_> = manifest.entries[0]
+        .provenance
+        .iter()
+        .map(|p| p.source)
+        .collect();
+    assert_eq!(
+        sources,
+        vec![ContextSource::Relevance, ContextSource::Semantic]
+    );
+
+    // A stronger hit is packed first and the file fills in around it
+    let request = ContextRequest::new("authentication", 10_000)
+        .with_files([file("src/auth.rs", AUTH_RS, 1.0)])
+        .with_search_hits([hit("src/auth.rs", 3, 7, 0.9)]);
+    let manifest = ContextPacker::new().pack(&request).unwrap();
+
+    assert_eq!(
+        manifest.citations(),
+        vec!["src/auth.rs:3-7", "src/auth.rs:1-2", "src/auth.rs:8-11"]
+    );
+    assert!(manifest.entries[0].content.starts_with("/// Validate JWT"));
+    assert_eq!(manifest.entries[1].kind, ContextItemKind::Snippet);
+    assert_eq!(manifest.deduplicated, 0);
+}
+
+#[test]
+fn test_budget_truncates_or_drops() {
+    let long: String = (0..200)
+        .map(|i| format!("pub fn handler_{i}() -> u32 {{ {i} }}\n"))
+        .collect();
+    let request =
+        ContextRequest::new("handler", 100).with_files([file("src/routes.rs", &long, 1.0)]);
+
+    let manifest = ContextPacker::new().pack(&request).unwrap();
+    assert_eq!(manifest.entries.len(), 1);
+    let entry = &manifest.entries[0];
+    assert!(entry.truncated);
+    assert_eq!(entry.start_line, 1);
+    assert!(entry.end_line < 200);
+    assert!(manifest.used_tokens <= 100);
+
+    // Too little room left for a useful prefix: the candidate is dropped
+    let manifest = ContextPacker::new()
+        .with_min_truncated_tokens(500)
+        .pack(&request)
+        .unwrap();
+    assert!(manifest.entries.is_empty());
+    assert_eq!(manifest.dropped.len(), 1);
+    assert_eq!(
+        manifest.dropped[0].path,
+        Some(PathBuf::from("src/routes.rs"))
+    );
+    assert!(manifest.dropped[0].tokens > 100);
+    assert!(manifest.dropped[0].reason.contains("100 remaining"));
+}
+
+#[test]
+fn test_recency_breaks_ties() {
+    let now = Utc::now();
+    let request = ContextRequest::new("invoice", 10_000)
+        .with_files([
+            file("src/old.rs", "fn old_invoice() {}", 0.5),
+            file("src/new.rs", "fn new_invoice() {}", 0.5),
+        ])
+        .with_modified("src/old.rs", now - Duration::days(60))
+        .with_modified("src/new.rs", now - Duration::hours(2));
+
+    let manifest = ContextPacker::new().pack(&request).unwrap();
+
+    assert_eq!(manifest.citations(), vec!["src/new.rs:1", "src/old.rs:1"]);
+    let recency = manifest.entries[0]
+        .provenance
+        .iter()
+        .find(|p| p.source == ContextSource::Recency)
+        .unwrap();
+    assert_eq!(recency.reason, "modified 2 hours ago");
+    assert!(manifest.entries[0].score > manifest.entries[1].score);
+}
+
+#[test]
+fn test_reads_missing_content_from_disk_and_renders() {
+    let dir = TempDir::new().unwrap();
+    fs::create_dir_all(dir.path().join("src")).unwrap();
+    fs::write(dir.path().join("src/auth.rs"), AUTH_RS).unwrap();
+
+    // Hits without file candidates are sliced from the file on disk
+    let request =
+        ContextRequest::new("jwt", 10_000).with_search_hits([hit("src/auth.rs", 4, 7, 0.8)]);
+    let manifest = ContextPacker::new()
+        .with_root(dir.path())
+        .pack(&request)
+        .unwrap();
+
+    let entry = &manifest.entries[0];
+    assert_eq!(entry.citation(), "src/auth.rs:4-7");
+    assert!(entry.content.starts_with("pub fn validate_jwt"));
+    assert!(entry.content.ends_with('}'));
+
+    let rendered = manifest.render();
+    assert!(rendered.starts_with("## src/auth.rs:4-7\n```\npub fn validate_jwt"));
+
+    let value: serde_json::Value = serde_json::from_str(&manifest.to_json().unwrap()).unwrap();
+    assert_eq!(value["entries"][0]["provenance"][0]["source"], "Semantic");
+    assert_eq!(value["budget"], 10_000);
+}
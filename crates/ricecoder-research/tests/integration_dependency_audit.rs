@@ -0,0 +1,349 @@
+//! Integration tests for dependency analysis and vulnerability awareness
+//! Tests manifest parsing, lockfile version resolution, OSV record matching,
+//! and vulnerability reports
+
+use std::{cmp::Ordering, fs, path::Path, sync::Arc};
+
+use ricecoder_research::{
+    compare_versions, is_dependency_file, AdvisorySeverity, DependencyAnalyzer, ManifestDependency,
+    OfflineOsvDatabase, OsvVulnerability, PackageEcosystem, ResearchError,
+};
+use tempfile::TempDir;
+
+fn write(root: &Path, path: &str, content: &str) {
+    let path = root.join(path);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+fn find<'a>(dependencies: &'a [ManifestDependency], name: &str) -> &'a ManifestDependency {
+    dependencies
+        .iter()
+        .find(|dependency| dependency.name == name)
+        .unwrap_or_else(|| panic!("{} not found", name))
+}
+
+/// Project mixing a Cargo workspace, an npm package, a Python app and a Go module
+fn create_project() -> TempDir {
+    let dir = TempDir::new().expect("Failed to create temp dir");
+    let root = dir.path();
+
+    write(
+        root,
+        "Cargo.toml",
+        "[workspace]\nmembers = [\"crates/*\"]\n\n[workspace.dependencies]\nserde = \"1\"\n",
+    );
+    write(
+        root,
+        "crates/app/Cargo.toml",
+        r#"[package]
+name = "app"
+
+[dependencies]
+serde = { workspace = true }
+time = "0.1"
+json = { package = "serde_json", version = "1.0" }
+core = { path = "../core" }
+
+[dev-dependencies]
+tempfile = "3"
+"#,
+    );
+    write(
+        root,
+        "Cargo.lock",
+        r#"version = 3
+
+[[package]]
+name = "app"
+version = "0.1.0"
+
+[[package]]
+name = "core"
+version = "0.1.0"
+
+[[package]]
+name = "serde"
+version = "1.0.190"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "serde_json"
+version = "1.0.108"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "time"
+version = "0.1.45"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "time"
+version = "0.3.30"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#,
+    );
+
+    write(
+        root,
+        "web/package.json",
+        r#"{"name": "web", "dependencies": {"lodash": "^4.17.0", "react": "18.2.0", "ui": "workspace:*"}, "devDependencies": {"jest": "^29.0.0"}}"#,
+    );
+    write(
+        root,
+        "web/package-lock.json",
+        r#"{"lockfileVersion": 3, "packages": {"": {"name": "web"}, "node_modules/lodash": {"version": "4.17.20"}, "node_modules/react": {"version": "18.2.0"}}}"#,
+    );
+    // Installed packages are never scanned as manifests
+    write(
+        root,
+        "web/node_modules/lodash/package.json",
+        r#"{"name": "lodash", "dependencies": {"left-pad": "1.0.0"}}"#,
+    );
+
+    write(
+        root,
+        "api/pyproject.toml",
+        r#"[project]
+name = "api"
+dependencies = ["Django>=4.2", "requests[socks]==2.31.0 ; python_version >= '3.8'"]
+
+[dependency-groups]
+dev = ["pytest>=7"]
+"#,
+    );
+    write(
+        root,
+        "api/poetry.lock",
+        "[[package]]\nname = \"django\"\nversion = \"4.2.1\"\n\n[[package]]\nname = \"requests\"\nversion = \"2.31.0\"\n",
+    );
+
+    write(
+        root,
+        "svc/go.mod",
+        "module example.com/svc\n\ngo 1.21\n\nrequire github.com/gin-gonic/gin v1.9.0\n\nrequire (\n\tgolang.org/x/net v0.17.0 // indirect\n)\n",
+    );
+    dir
+}
+
+/// OSV records affecting `time`, `lodash` and `gin`
+const RECORDS: &str = r#"[
+  {
+    "id": "RUSTSEC-2020-0071",
+    "summary": "Potential segfault in the time crate",
+    "aliases": ["CVE-2020-26235", "GHSA-wcg3-cvx6-7396"],
+    "affected": [{
+      "package": {"ecosystem": "crates.io", "name": "time"},
+      "ranges": [{"type": "SEMVER", "events": [
+        {"introduced": "0"}, {"fixed": "0.2.23"}
+      ]}]
+    }],
+    "severity": [{"type": "CVSS_V3", "score": "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"}],
+    "references": [{"type": "ADVISORY", "url": "https://rustsec.org/advisories/RUSTSEC-2020-0071"}]
+  },
+  {
+    "id": "GHSA-35jh-r3h4-6jhm",
+    "summary": "Command Injection in lodash",
+    "aliases": ["CVE-2021-23337"],
+    "affected": [{
+      "package": {"ecosystem": "npm", "name": "lodash"},
+      "ranges": [{"type": "SEMVER", "events": [
+        {"introduced": "0"}, {"fixed": "4.17.21"}
+      ]}]
+    }],
+    "database_specific": {"severity": "HIGH"}
+  },
+  {
+    "id": "GO-2023-2041",
+    "summary": "Improper handling of filenames in gin",
+    "affected": [{
+      "package": {"ecosystem": "Go", "name": "github.com/gin-gonic/gin"},
+      "ranges": [{"type": "SEMVER", "events": [
+        {"introduced": "1.3.1-0.20190301021747-ccb9e902956d"}, {"last_affected": "1.9.0"}
+      ]}]
+    }],
+    "database_specific": {"severity": "MODERATE"}
+  },
+  {
+    "id": "PYSEC-2023-0001",
+    "summary": "Old Django issue",
+    "affected": [{
+      "package": {"ecosystem": "PyPI", "name": "Django"},
+      "versions": ["3.2.0", "3.2.1"]
+    }]
+  }
+]"#;
+
+fn database() -> Arc<OfflineOsvDatabase> {
+    let database = OfflineOsvDatabase::new();
+    assert_eq!(database.add_json(RECORDS).unwrap(), 4);
+    Arc::new(database)
+}
+
+#[test]
+fn test_parses_manifests_and_resolves_lockfiles() {
+    let project = create_project();
+    let dependencies = DependencyAnalyzer::new()
+        .scan(project.path())
+        .expect("Scan failed");
+
+    // Cargo: workspace inheritance and renames resolve through Cargo.lock
+    let serde: Vec<_> = dependencies.iter().filter(|d| d.name == "serde").collect();
+    assert_eq!(serde.len(), 2);
+    assert!(serde
+        .iter()
+        .all(|d| d.resolved_version.as_deref() == Some("1.0.190")));
+    assert_eq!(
+        find(&dependencies, "serde_json")
+            .resolved_version
+            .as_deref(),
+        Some("1.0.108")
+    );
+    // The requirement picks the compatible version among several locked ones
+    let time = find(&dependencies, "time");
+    assert_eq!(time.resolved_version.as_deref(), Some("0.1.45"));
+    assert_eq!(time.lockfile, Some(project.path().join("Cargo.lock")));
+    assert!(find(&dependencies, "tempfile").is_dev);
+    assert!(!dependencies.iter().any(|d| d.name == "core"));
+
+    // npm: lockfile versions, local workspace packages skipped, node_modules ignored
+    assert_eq!(
+        find(&dependencies, "lodash").resolved_version.as_deref(),
+        Some("4.17.20")
+    );
+    assert!(find(&dependencies, "jest").is_dev);
+    assert!(find(&dependencies, "jest").resolved_version.is_none());
+    assert!(!dependencies
+        .iter()
+        .any(|d| d.name == "ui" || d.name == "left-pad"));
+
+    // Python: names are normalized and markers/extras stripped
+    let django = find(&dependencies, "django");
+    assert_eq!(django.ecosystem, PackageEcosystem::PyPI);
+    assert_eq!(django.requirement.as_deref(), Some(">=4.2"));
+    assert_eq!(django.resolved_version.as_deref(), Some("4.2.1"));
+    assert_eq!(
+        find(&dependencies, "requests").requirement.as_deref(),
+        Some("==2.31.0")
+    );
+    assert!(find(&dependencies, "pytest").is_dev);
+
+    // Go: versions come from go.mod itself
+    let net = find(&dependencies, "golang.org/x/net");
+    assert_eq!(net.resolved_version.as_deref(), Some("v0.17.0"));
+    assert!(!net.direct);
+    assert!(find(&dependencies, "github.com/gin-gonic/gin").direct);
+}
+
+#[tokio::test]
+async fn test_audit_reports_known_vulnerabilities() {
+    let project = create_project();
+    let report = DependencyAnalyzer::new()
+        .with_database(database())
+        .audit(project.path())
+        .await
+        .expect("Audit failed");
+
+    assert!(report.checked);
+    assert!(!report.is_clean());
+    let ids: Vec<&str> = report
+        .findings
+        .iter()
+        .map(|finding| finding.advisory_id.as_str())
+        .collect();
+    // Most severe first; Django 4.2.1 is not in the affected version list
+    assert_eq!(
+        ids,
+        vec!["RUSTSEC-2020-0071", "GHSA-35jh-r3h4-6jhm", "GO-2023-2041"]
+    );
+    assert_eq!(report.max_severity(), Some(AdvisorySeverity::Critical));
+
+    let time = &report.findings[0];
+    assert_eq!(time.cvss_score, Some(9.8));
+    assert_eq!(time.cve_ids(), vec!["CVE-2020-26235"]);
+    assert_eq!(time.recommended_version(), Some("0.2.23"));
+    assert_eq!(report.findings[1].severity, AdvisorySeverity::High);
+    assert_eq!(report.findings[2].severity, AdvisorySeverity::Medium);
+    assert_eq!(report.findings_at_least(AdvisorySeverity::High).len(), 2);
+
+    assert!(report.unresolved.contains(&"jest".to_string()));
+
+    // Reviewers of a package.json change only see the npm part
+    let web = report.for_files(&[project.path().join("web/package.json")]);
+    assert_eq!(web.findings.len(), 1);
+    assert_eq!(web.findings[0].dependency.name, "lodash");
+    assert!(web
+        .dependencies
+        .iter()
+        .all(|d| d.ecosystem == PackageEcosystem::Npm));
+
+    let json = report.to_json().unwrap();
+    assert!(json.contains("CVE-2021-23337"));
+}
+
+#[tokio::test]
+async fn test_without_database_only_lists_dependencies() {
+    let project = create_project();
+    let report = DependencyAnalyzer::new()
+        .audit(project.path())
+        .await
+        .unwrap();
+    assert!(!report.checked);
+    assert!(report.is_clean());
+    assert!(!report.dependencies.is_empty());
+
+    let result = DependencyAnalyzer::new().scan(&project.path().join("missing"));
+    assert!(matches!(result, Err(ResearchError::ProjectNotFound { .. })));
+
+    write(project.path(), "bad/Cargo.toml", "[dependencies\n");
+    let result = DependencyAnalyzer::new().parse_manifest(&project.path().join("bad/Cargo.toml"));
+    assert!(matches!(
+        result,
+        Err(ResearchError::DependencyParsingFailed { .. })
+    ));
+}
+
+#[test]
+fn test_osv_range_matching() {
+    let record: OsvVulnerability = serde_json::from_str(
+        r#"{
+          "id": "TEST-1",
+          "affected": [{
+            "package": {"ecosystem": "PyPI", "name": "Pillow"},
+            "ranges": [{"type": "ECOSYSTEM", "events": [
+              {"introduced": "8.0"}, {"fixed": "9.0.0"},
+              {"introduced": "10.0.0rc1"}, {"last_affected": "10.0.1"}
+            ]}]
+          }]
+        }"#,
+    )
+    .unwrap();
+
+    let affects = |version: &str| record.affects(PackageEcosystem::PyPI, "pillow", version);
+    assert!(!affects("7.2.0"));
+    assert!(affects("8.0.0"));
+    assert!(affects("8.4.0"));
+    assert!(!affects("9.0.0"));
+    assert!(!affects("9.5.0"));
+    assert!(affects("10.0.0"));
+    assert!(affects("10.0.1"));
+    assert!(!affects("10.0.2"));
+    assert!(!record.affects(PackageEcosystem::Npm, "pillow", "8.4.0"));
+    assert_eq!(
+        record.fixed_versions(PackageEcosystem::PyPI, "PILLOW"),
+        vec!["9.0.0".to_string()]
+    );
+}
+
+#[test]
+fn test_version_ordering_and_dependency_files() {
+    assert_eq!(compare_versions("1.2.10", "1.2.9"), Ordering::Greater);
+    assert_eq!(compare_versions("v1.9.0", "1.9"), Ordering::Equal);
+    assert_eq!(compare_versions("1.0.0-rc.1", "1.0.0"), Ordering::Less);
+    assert_eq!(compare_versions("2.0.post1", "2.0"), Ordering::Greater);
+    assert_eq!(compare_versions("1.0.0+build.5", "1.0.0"), Ordering::Equal);
+
+    assert!(is_dependency_file(Path::new("crates/app/Cargo.toml")));
+    assert!(is_dependency_file(Path::new("web/yarn.lock")));
+    assert!(!is_dependency_file(Path::new("src/main.rs")));
+}
@@ -0,0 +1,161 @@
+//! Integration tests for incremental re-analysis
+//! Tests targeted invalidation of symbols and dependency edges on file changes,
+//! freshness metadata, and consuming watcher batches
+
+use std::{fs, path::Path, sync::Arc, time::Instant};
+
+use ricecoder_files::{FileChangeBatch, FileChangeEvent};
+use ricecoder_research::{IncrementalAnalyzer, SymbolKind};
+use tempfile::TempDir;
+use tokio::sync::broadcast;
+
+/// Create a project where `main.rs` uses symbols from `config.rs`
+fn create_project() -> TempDir {
+    let dir = TempDir::new().expect("Failed to create temp dir");
+    let src = dir.path().join("src");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(
+        src.join("config.rs"),
+        "pub struct Config {}\n\npub fn load_config() -> Config {\n    Config {}\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        src.join("main.rs"),
+        "fn main() {\n    let config = load_config();\n}\n",
+    )
+    .unwrap();
+    fs::write(src.join("util.rs"), "pub fn helper() {}\n").unwrap();
+    fs::write(dir.path().join("README.md"), "# Demo\n").unwrap();
+    dir
+}
+
+fn scanned(root: &Path) -> IncrementalAnalyzer {
+    let analyzer = IncrementalAnalyzer::new(root);
+    analyzer.full_scan().expect("Full scan failed");
+    analyzer
+}
+
+#[test]
+fn test_full_scan_builds_index_and_edges() {
+    let project = create_project();
+    let analyzer = scanned(project.path());
+    let config = project.path().join("src/config.rs");
+    let main = project.path().join("src/main.rs");
+
+    let freshness = analyzer.freshness();
+    assert!(freshness.is_fresh());
+    assert_eq!(freshness.indexed_files, 3);
+    assert_eq!(freshness.full_scans, 1);
+
+    analyzer.with_index(|index| {
+        let load = index.get_symbols_by_name("load_config");
+        assert_eq!(load.len(), 1);
+        assert_eq!(load[0].kind, SymbolKind::Function);
+        assert_eq!(load[0].line, 3);
+        let references = index.get_references_to_symbol(&load[0].id);
+        assert_eq!(references.len(), 1);
+        assert_eq!(references[0].file, main);
+    });
+
+    assert_eq!(analyzer.dependencies_of(&main), vec![config.clone()]);
+    assert_eq!(analyzer.dependents_of(&config), vec![main]);
+    assert!(analyzer.file_freshness(&config).unwrap().symbol_count >= 2);
+}
+
+#[test]
+fn test_modification_invalidates_only_affected_files() {
+    let project = create_project();
+    let analyzer = scanned(project.path());
+    let config = project.path().join("src/config.rs");
+    let main = project.path().join("src/main.rs");
+
+    fs::write(&config, "pub struct Config {}\n\npub fn read_config() {}\n").unwrap();
+    let update = analyzer.apply_events(&[FileChangeEvent::Modified(config.clone())]);
+
+    // Only the changed file is re-indexed; its dependent is relinked
+    assert_eq!(update.reindexed, vec![config.clone()]);
+    assert_eq!(update.relinked, vec![config.clone(), main.clone()]);
+    analyzer.with_index(|index| {
+        assert!(index.get_symbols_by_name("load_config").is_empty());
+        assert_eq!(index.get_symbols_by_name("read_config").len(), 1);
+        assert_eq!(index.get_symbols_by_name("helper").len(), 1);
+    });
+    assert!(analyzer.dependencies_of(&main).is_empty());
+    assert!(analyzer.dependents_of(&config).is_empty());
+
+    // Saving identical content is a no-op
+    let update = analyzer.apply_events(&[FileChangeEvent::Modified(config.clone())]);
+    assert!(update.is_empty());
+    assert_eq!(update.unchanged, vec![config]);
+    assert_eq!(analyzer.freshness().incremental_updates, 1);
+}
+
+#[test]
+fn test_new_definitions_resolve_existing_usages() {
+    let project = create_project();
+    let analyzer = scanned(project.path());
+    let main = project.path().join("src/main.rs");
+    let config = project.path().join("src/config.rs");
+    let loader = project.path().join("src/loader.rs");
+
+    fs::remove_file(&config).unwrap();
+    let update = analyzer.apply_events(&[FileChangeEvent::Deleted(config.clone())]);
+    assert_eq!(update.removed, vec![config]);
+    assert!(analyzer.dependencies_of(&main).is_empty());
+
+    // Defining the name again in a new file links the existing usage to it
+    fs::write(&loader, "pub fn load_config() {}\n").unwrap();
+    let update = analyzer.apply_events(&[FileChangeEvent::Created(loader.clone())]);
+    assert!(update.relinked.contains(&main));
+    assert_eq!(analyzer.dependencies_of(&main), vec![loader.clone()]);
+
+    // Renames move symbols without a full scan
+    let renamed = project.path().join("src/load.rs");
+    fs::rename(&loader, &renamed).unwrap();
+    analyzer.apply_events(&[FileChangeEvent::Renamed {
+        from: loader.clone(),
+        to: renamed.clone(),
+    }]);
+    assert!(analyzer.file_freshness(&loader).is_none());
+    assert_eq!(analyzer.dependencies_of(&main), vec![renamed]);
+    assert_eq!(analyzer.freshness().full_scans, 1);
+}
+
+#[test]
+fn test_irrelevant_events_are_ignored() {
+    let project = create_project();
+    let analyzer = scanned(project.path());
+    let outside = TempDir::new().unwrap();
+    fs::write(outside.path().join("other.rs"), "fn other() {}\n").unwrap();
+
+    let update = analyzer.apply_events(&[
+        FileChangeEvent::Modified(project.path().join("README.md")),
+        FileChangeEvent::Created(outside.path().join("other.rs")),
+    ]);
+    assert_eq!(update, Default::default());
+    assert_eq!(analyzer.freshness().indexed_files, 3);
+}
+
+#[tokio::test]
+async fn test_watch_applies_batches() {
+    let project = create_project();
+    let analyzer = Arc::new(scanned(project.path()));
+    let (sender, receiver) = broadcast::channel(8);
+    let handle = analyzer.spawn_watch(receiver);
+
+    let util = project.path().join("src/util.rs");
+    fs::write(&util, "pub fn helper() {}\npub fn format_name() {}\n").unwrap();
+    let events = vec![FileChangeEvent::Modified(util.clone())];
+    sender
+        .send(FileChangeBatch {
+            timestamp: Instant::now(),
+            count: events.len(),
+            events,
+        })
+        .unwrap();
+
+    drop(sender);
+    handle.await.unwrap();
+    assert_eq!(analyzer.file_freshness(&util).unwrap().symbol_count, 2);
+    analyzer.with_index(|index| assert_eq!(index.get_symbols_by_name("format_name").len(), 1));
+}
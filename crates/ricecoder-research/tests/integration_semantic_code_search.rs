@@ -0,0 +1,255 @@
+//! Integration tests for embedding-based semantic code search
+//! Tests symbol chunking, embedding through a provider, ranked and cited
+//! results, and merging with keyword search results
+
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use async_trait::async_trait;
+use ricecoder_providers::{
+    provider::ChatStream, ChatRequest, ChatResponse, EmbeddingRequest, EmbeddingResponse,
+    ModelInfo, Provider, ProviderError,
+};
+use ricecoder_research::{
+    CodeChunker, HitSource, ResearchError, SearchEngine, SemanticCodeSearch, SemanticIndex, Symbol,
+    SymbolKind,
+};
+
+const DIMENSIONS: usize = 64;
+
+/// Provider producing deterministic bag-of-words embeddings
+#[derive(Default)]
+struct BagOfWordsProvider {
+    embedded_texts: AtomicUsize,
+    supports_embeddings: bool,
+}
+
+impl BagOfWordsProvider {
+    fn new() -> Self {
+        BagOfWordsProvider {
+            embedded_texts: AtomicUsize::new(0),
+            supports_embeddings: true,
+        }
+    }
+
+    fn vector(text: &str) -> Vec<f32> {
+        let mut vector = vec![0.0; DIMENSIONS];
+        for word in text
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| word.len() >= 3)
+        {
+            let word = word.to_lowercase();
+            let word = word.strip_suffix('s').unwrap_or(&word);
+            let bucket = word.bytes().fold(7usize, |hash, byte| {
+                hash.wrapping_mul(31).wrapping_add(byte as usize)
+            });
+            vector[bucket % DIMENSIONS] += 1.0;
+        }
+        vector
+    }
+}
+
+#[async_trait]
+impl Provider for BagOfWordsProvider {
+    fn id(&self) -> &str {
+        "bag-of-words"
+    }
+
+    fn name(&self) -> &str {
+        "Bag of Words"
+    }
+
+    fn models(&self) -> Vec<ModelInfo> {
+        Vec::new()
+    }
+
+    async fn chat(&self, _request: ChatRequest) -> Result<ChatResponse, ProviderError> {
+        unimplemented!()
+    }
+
+    async fn chat_stream(&self, _request: ChatRequest) -> Result<ChatStream, ProviderError> {
+        unimplemented!()
+    }
+
+    fn count_tokens(&self, content: &str, _model: &str) -> Result<usize, ProviderError> {
+        Ok(content.len() / 4)
+    }
+
+    async fn health_check(&self) -> Result<bool, ProviderError> {
+        Ok(true)
+    }
+
+    async fn embed(&self, request: EmbeddingRequest) -> Result<EmbeddingResponse, ProviderError> {
+        if !self.supports_embeddings {
+            return Err(ProviderError::ProviderError("no embeddings".to_string()));
+        }
+        self.embedded_texts
+            .fetch_add(request.input.len(), Ordering::SeqCst);
+        Ok(EmbeddingResponse {
+            model: request.model,
+            embeddings: request
+                .input
+                .iter()
+                .map(|text| Self::vector(text))
+                .collect(),
+        })
+    }
+}
+
+const AUTH_RS: &str = "use crate::token::Claims;
+
+/// Validate JWT tokens from the Authorization header
+pub fn validate_jwt(token: &str) -> bool {
+    let claims = decode(token);
+    claims.is_valid()
+}
+
+pub fn refresh_session(user: &str) {
+    store_session(user);
+}
+";
+
+const BILLING_RS: &str = "pub struct Invoice {
+    pub total: u64,
+}
+
+pub fn charge_invoice(invoice: &Invoice) {
+    submit_payment(invoice.total);
+}
+";
+
+async fn indexed(provider: Arc<BagOfWordsProvider>) -> SemanticCodeSearch {
+    let search = SemanticCodeSearch::new(provider, "embed-small");
+    search
+        .index_file(Path::new("src/auth.rs"), AUTH_RS)
+        .await
+        .unwrap();
+    search
+        .index_file(Path::new("src/billing.rs"), BILLING_RS)
+        .await
+        .unwrap();
+    search
+}
+
+#[test]
+fn test_chunker_splits_on_symbols() {
+    let chunks = CodeChunker::new().chunk(Path::new("src/auth.rs"), AUTH_RS);
+
+    assert_eq!(chunks.len(), 3);
+    assert_eq!(chunks[0].symbol, None);
+    assert_eq!((chunks[0].start_line, chunks[0].end_line), (1, 1));
+    assert_eq!(chunks[1].symbol.as_deref(), Some("validate_jwt"));
+    assert_eq!(chunks[1].kind, Some(SymbolKind::Function));
+    // The doc comment is part of the symbol's chunk
+    assert_eq!((chunks[1].start_line, chunks[1].end_line), (3, 7));
+    assert_eq!((chunks[2].start_line, chunks[2].end_line), (9, 11));
+
+    // Long symbols are split into windows
+    let windows = CodeChunker::new()
+        .with_max_lines(2)
+        .chunk(Path::new("src/billing.rs"), BILLING_RS);
+    assert_eq!(windows.len(), 4);
+    assert!(windows
+        .iter()
+        .all(|chunk| chunk.end_line - chunk.start_line < 2));
+}
+
+#[tokio::test]
+async fn test_natural_language_query_returns_cited_locations() {
+    let search = indexed(Arc::new(BagOfWordsProvider::new())).await;
+    assert_eq!(search.store().len(), 5);
+
+    let hits = search
+        .search("where do we validate JWTs?", 3)
+        .await
+        .expect("Search failed");
+    assert_eq!(hits.len(), 3);
+
+    let top = &hits[0];
+    assert_eq!(top.symbol.as_deref(), Some("validate_jwt"));
+    assert_eq!(top.citation(), "src/auth.rs:3-7");
+    assert_eq!(top.sources, vec![HitSource::Semantic]);
+    assert!(top.snippet.contains("pub fn validate_jwt"));
+    assert!(top.similarity.unwrap() > hits[1].similarity.unwrap());
+}
+
+#[tokio::test]
+async fn test_keyword_results_are_merged() {
+    let mut index = SemanticIndex::new();
+    for (id, name, file, line) in [
+        ("s1", "charge_invoice", "src/billing.rs", 5),
+        ("s2", "validate_jwt", "src/auth.rs", 4),
+        ("s3", "InvoiceDto", "src/api.rs", 12),
+    ] {
+        index.add_symbol(Symbol {
+            id: id.to_string(),
+            name: name.to_string(),
+            kind: SymbolKind::Function,
+            file: PathBuf::from(file),
+            line,
+            column: 1,
+            references: Vec::new(),
+        });
+    }
+    let search = indexed(Arc::new(BagOfWordsProvider::new()))
+        .await
+        .with_keyword_engine(Arc::new(SearchEngine::new(index)));
+
+    let hits = search.search("how are invoices charged", 10).await.unwrap();
+
+    // Found by both searches, so it ranks first
+    let top = &hits[0];
+    assert_eq!(top.citation(), "src/billing.rs:5-7");
+    assert!(top.sources.contains(&HitSource::Semantic));
+    assert!(top.sources.contains(&HitSource::Keyword));
+
+    // Keyword-only matches outside the embedded files are still cited
+    let dto = hits
+        .iter()
+        .find(|hit| hit.symbol.as_deref() == Some("InvoiceDto"))
+        .expect("keyword hit missing");
+    assert_eq!(dto.citation(), "src/api.rs:12");
+    assert_eq!(dto.sources, vec![HitSource::Keyword]);
+}
+
+#[tokio::test]
+async fn test_reindexing_reuses_unchanged_chunks() {
+    let provider = Arc::new(BagOfWordsProvider::new());
+    let search = indexed(provider.clone()).await;
+    assert_eq!(provider.embedded_texts.load(Ordering::SeqCst), 5);
+
+    let stats = search
+        .index_file(Path::new("src/auth.rs"), AUTH_RS)
+        .await
+        .unwrap();
+    assert_eq!(stats.chunks, 3);
+    assert_eq!(stats.embedded, 0);
+
+    let edited = AUTH_RS.replace("store_session(user);", "store_session(user, ttl);");
+    let stats = search
+        .index_file(Path::new("src/auth.rs"), &edited)
+        .await
+        .unwrap();
+    assert_eq!(stats.embedded, 1);
+    assert_eq!(provider.embedded_texts.load(Ordering::SeqCst), 6);
+
+    assert_eq!(search.remove_file(Path::new("src/auth.rs")), 3);
+    assert_eq!(search.store().len(), 2);
+}
+
+#[tokio::test]
+async fn test_provider_without_embeddings_fails() {
+    let provider = Arc::new(BagOfWordsProvider::default());
+    let search = SemanticCodeSearch::new(provider, "embed-small");
+
+    let result = search.index_file(Path::new("src/auth.rs"), AUTH_RS).await;
+    assert!(matches!(result, Err(ResearchError::IndexError { .. })));
+
+    let result = search.search("jwt", 5).await;
+    assert!(matches!(result, Err(ResearchError::SearchFailed { .. })));
+}
@@ -0,0 +1,752 @@
+//! Risk-weighted approval routing
+//!
+//! The approval an operation needs scales with its [`RiskScore`]:
+//!
+//! - **Low** risk is approved automatically
+//! - **Medium** risk needs the user to confirm (e.g. a TUI prompt)
+//! - **High** risk needs a typed confirmation phrase or a second approver
+//!   from the project's team
+//! - Anything above the high-risk ceiling is blocked
+//!
+//! Score thresholds can be tuned per project. Every decision is kept in an
+//! in-memory trail and, when configured, written to the audit log together
+//! with the risk factors behind it.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use ricecoder_activity_log::{
+    audit::{AuditEventType, AuditOutcome},
+    AuditLogger, AuditTrail,
+};
+use ricecoder_teams::{models::TeamRole, AccessControlManager};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{oneshot, Mutex, RwLock};
+use uuid::Uuid;
+
+use crate::{
+    blast_radius::BlastRadius,
+    error::{SafetyError, SafetyResult},
+    risk::{RiskFactors, RiskLevel, RiskScore},
+    validation::ApprovalRequest,
+};
+
+/// Approval an operation needs before it may run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalRequirement {
+    /// Approved without asking anyone
+    AutoApprove,
+    /// The requester confirms
+    Confirm,
+    /// The requester types a confirmation phrase
+    TypedConfirmation,
+    /// Another team member approves
+    SecondApprover,
+    /// Too risky to approve
+    Blocked,
+}
+
+/// How high-risk operations are approved
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HighRiskApproval {
+    TypedConfirmation,
+    SecondApprover,
+}
+
+/// Risk score boundaries for each approval requirement
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApprovalThresholds {
+    /// Scores up to this are approved automatically
+    pub auto_approve_max: u8,
+    /// Scores up to this need confirmation
+    pub confirm_max: u8,
+    /// Scores up to this need high-risk approval; higher scores are blocked
+    pub high_risk_max: u8,
+    pub high_risk_approval: HighRiskApproval,
+    /// Team whose admins can act as second approver
+    pub approver_team: Option<String>,
+}
+
+impl Default for ApprovalThresholds {
+    fn default() -> Self {
+        Self {
+            auto_approve_max: RiskLevel::Low.max_score(),
+            confirm_max: RiskLevel::Medium.max_score(),
+            high_risk_max: RiskLevel::High.max_score(),
+            high_risk_approval: HighRiskApproval::TypedConfirmation,
+            approver_team: None,
+        }
+    }
+}
+
+impl ApprovalThresholds {
+    /// Requirement for a risk score
+    pub fn requirement(&self, score: u8) -> ApprovalRequirement {
+        if score <= self.auto_approve_max {
+            ApprovalRequirement::AutoApprove
+        } else if score <= self.confirm_max {
+            ApprovalRequirement::Confirm
+        } else if score <= self.high_risk_max {
+            match self.high_risk_approval {
+                HighRiskApproval::TypedConfirmation => ApprovalRequirement::TypedConfirmation,
+                HighRiskApproval::SecondApprover => ApprovalRequirement::SecondApprover,
+            }
+        } else {
+            ApprovalRequirement::Blocked
+        }
+    }
+}
+
+/// Default thresholds with per-project overrides
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ApprovalRouting {
+    pub default: ApprovalThresholds,
+    pub projects: HashMap<String, ApprovalThresholds>,
+}
+
+impl ApprovalRouting {
+    /// Thresholds that apply to a project
+    pub fn thresholds_for(&self, project: Option<&str>) -> &ApprovalThresholds {
+        project
+            .and_then(|project| self.projects.get(project))
+            .unwrap_or(&self.default)
+    }
+}
+
+/// What an approver is shown
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApprovalPrompt {
+    pub request_id: String,
+    pub request: ApprovalRequest,
+    pub requester: String,
+    pub project: Option<String>,
+    pub risk: RiskScore,
+    pub requirement: ApprovalRequirement,
+}
+
+impl ApprovalPrompt {
+    /// One-line summary with the strongest risk factors
+    pub fn summary(&self) -> String {
+        let factors = top_factors(&self.risk.factors, 3);
+        let mut summary = format!(
+            "{} (risk {} {:?})",
+            self.request.reason, self.risk.score, self.risk.level
+        );
+        if !factors.is_empty() {
+            let factors: Vec<String> = factors
+                .iter()
+                .map(|(name, score)| format!("{} +{}", name, score))
+                .collect();
+            summary.push_str(&format!(": {}", factors.join(", ")));
+        }
+        if let Some(radius) = self
+            .request
+            .context
+            .as_ref()
+            .and_then(BlastRadius::from_context)
+        {
+            summary.push_str(&format!(" [blast radius: {}]", radius.summary()));
+        }
+        summary
+    }
+
+    /// Phrase the requester must type for high-risk approval
+    pub fn confirmation_phrase(&self) -> String {
+        format!("approve {}", self.request.constraint_id)
+    }
+}
+
+fn top_factors(factors: &RiskFactors, limit: usize) -> Vec<(String, u8)> {
+    let mut all: Vec<(String, u8)> = factors
+        .user_factors
+        .iter()
+        .chain(&factors.operation_factors)
+        .chain(&factors.environment_factors)
+        .chain(&factors.historical_factors)
+        .map(|(name, score)| (name.clone(), *score))
+        .collect();
+    all.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    all.truncate(limit);
+    all
+}
+
+/// Asks the requester to approve, e.g. through the TUI
+#[async_trait]
+pub trait ApprovalPrompter: Send + Sync {
+    /// Yes/no confirmation
+    async fn confirm(&self, prompt: &ApprovalPrompt) -> SafetyResult<bool>;
+
+    /// Ask the user to type `phrase`; returns what they typed
+    async fn typed_confirmation(
+        &self,
+        prompt: &ApprovalPrompt,
+        phrase: &str,
+    ) -> SafetyResult<String>;
+}
+
+/// Answer from a second approver
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SecondApproval {
+    pub approver_id: String,
+    pub approved: bool,
+    pub reason: Option<String>,
+}
+
+/// Gets approval from someone other than the requester
+#[async_trait]
+pub trait SecondApprover: Send + Sync {
+    async fn request(&self, prompt: &ApprovalPrompt, team_id: &str)
+        -> SafetyResult<SecondApproval>;
+}
+
+struct PendingSecondApproval {
+    prompt: ApprovalPrompt,
+    team_id: String,
+    respond: oneshot::Sender<SecondApproval>,
+}
+
+/// Second approver backed by team roles
+///
+/// Requests wait until a team admin other than the requester answers
+/// through [`approve`](Self::approve) or [`reject`](Self::reject), or until
+/// the timeout passes.
+pub struct TeamSecondApprover {
+    access: Arc<AccessControlManager>,
+    timeout: Duration,
+    pending: Mutex<HashMap<String, PendingSecondApproval>>,
+}
+
+impl TeamSecondApprover {
+    pub fn new(access: Arc<AccessControlManager>) -> Self {
+        Self {
+            access,
+            timeout: Duration::from_secs(15 * 60),
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// How long to wait for an answer
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Requests waiting for a second approver
+    pub async fn pending(&self) -> Vec<ApprovalPrompt> {
+        self.pending
+            .lock()
+            .await
+            .values()
+            .map(|pending| pending.prompt.clone())
+            .collect()
+    }
+
+    /// Approve a pending request
+    pub async fn approve(&self, request_id: &str, approver_id: &str) -> SafetyResult<()> {
+        self.respond(request_id, approver_id, true, None).await
+    }
+
+    /// Reject a pending request
+    pub async fn reject(
+        &self,
+        request_id: &str,
+        approver_id: &str,
+        reason: String,
+    ) -> SafetyResult<()> {
+        self.respond(request_id, approver_id, false, Some(reason))
+            .await
+    }
+
+    async fn respond(
+        &self,
+        request_id: &str,
+        approver_id: &str,
+        approved: bool,
+        reason: Option<String>,
+    ) -> SafetyResult<()> {
+        let mut pending = self.pending.lock().await;
+        let entry = pending
+            .get(request_id)
+            .ok_or_else(|| SafetyError::ValidationError {
+                field: "request_id".to_string(),
+                message: "No pending second approval for this request".to_string(),
+            })?;
+
+        if entry.prompt.requester == approver_id {
+            return Err(SafetyError::AccessDenied {
+                resource: request_id.to_string(),
+                reason: "Requesters cannot approve their own operations".to_string(),
+            });
+        }
+        let role = self
+            .access
+            .get_member_role(&entry.team_id, approver_id)
+            .await
+            .map_err(|e| SafetyError::SecurityError(e.to_string()))?;
+        if role != Some(TeamRole::Admin) {
+            return Err(SafetyError::AccessDenied {
+                resource: request_id.to_string(),
+                reason: format!("{} is not an admin of team {}", approver_id, entry.team_id),
+            });
+        }
+
+        if let Some(entry) = pending.remove(request_id) {
+            let _ = entry.respond.send(SecondApproval {
+                approver_id: approver_id.to_string(),
+                approved,
+                reason,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SecondApprover for TeamSecondApprover {
+    async fn request(
+        &self,
+        prompt: &ApprovalPrompt,
+        team_id: &str,
+    ) -> SafetyResult<SecondApproval> {
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().await.insert(
+            prompt.request_id.clone(),
+            PendingSecondApproval {
+                prompt: prompt.clone(),
+                team_id: team_id.to_string(),
+                respond: tx,
+            },
+        );
+
+        match tokio::time::timeout(self.timeout, rx).await {
+            Ok(Ok(answer)) => Ok(answer),
+            _ => {
+                self.pending.lock().await.remove(&prompt.request_id);
+                Err(SafetyError::ApprovalRequired {
+                    reason: format!("No second approver from team {} answered in time", team_id),
+                })
+            }
+        }
+    }
+}
+
+/// An approval decision and the risk behind it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApprovalDecision {
+    pub id: Uuid,
+    pub request_id: String,
+    pub constraint_id: String,
+    pub requester: String,
+    pub project: Option<String>,
+    pub risk_score: u8,
+    pub risk_level: RiskLevel,
+    pub risk_factors: RiskFactors,
+    pub requirement: ApprovalRequirement,
+    pub approved: bool,
+    /// Who made the decision; `system` for automatic decisions
+    pub decided_by: String,
+    pub reason: Option<String>,
+    pub decided_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Routes approval requests to the approval their risk calls for
+pub struct ApprovalRouter {
+    routing: RwLock<ApprovalRouting>,
+    prompter: Option<Arc<dyn ApprovalPrompter>>,
+    second_approver: Option<Arc<dyn SecondApprover>>,
+    audit_logger: Option<Arc<AuditLogger>>,
+    decisions: RwLock<Vec<ApprovalDecision>>,
+}
+
+impl ApprovalRouter {
+    pub fn new(routing: ApprovalRouting) -> Self {
+        Self {
+            routing: RwLock::new(routing),
+            prompter: None,
+            second_approver: None,
+            audit_logger: None,
+            decisions: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Prompt used for confirmations
+    pub fn with_prompter(mut self, prompter: Arc<dyn ApprovalPrompter>) -> Self {
+        self.prompter = Some(prompter);
+        self
+    }
+
+    /// Second approver for high-risk operations
+    pub fn with_second_approver(mut self, approver: Arc<dyn SecondApprover>) -> Self {
+        self.second_approver = Some(approver);
+        self
+    }
+
+    /// Write every decision to the audit log
+    pub fn with_audit_logger(mut self, logger: Arc<AuditLogger>) -> Self {
+        self.audit_logger = Some(logger);
+        self
+    }
+
+    /// Override thresholds for a project
+    pub async fn set_project_thresholds(&self, project: &str, thresholds: ApprovalThresholds) {
+        self.routing
+            .write()
+            .await
+            .projects
+            .insert(project.to_string(), thresholds);
+    }
+
+    /// Requirement for a risk score in a project
+    pub async fn requirement_for(
+        &self,
+        risk: &RiskScore,
+        project: Option<&str>,
+    ) -> ApprovalRequirement {
+        self.routing
+            .read()
+            .await
+            .thresholds_for(project)
+            .requirement(risk.score)
+    }
+
+    /// Decisions made so far
+    pub async fn decisions(&self) -> Vec<ApprovalDecision> {
+        self.decisions.read().await.clone()
+    }
+
+    /// Obtain the approval a request needs and record the decision
+    pub async fn route(
+        &self,
+        request_id: &str,
+        request: &ApprovalRequest,
+        requester: &str,
+        project: Option<&str>,
+        risk: &RiskScore,
+    ) -> SafetyResult<ApprovalDecision> {
+        let (mut requirement, approver_team) = {
+            let routing = self.routing.read().await;
+            let thresholds = routing.thresholds_for(project);
+            (
+                thresholds.requirement(risk.score),
+                thresholds.approver_team.clone(),
+            )
+        };
+
+        // Fall back to typed confirmation when no second approver is wired up
+        if requirement == ApprovalRequirement::SecondApprover
+            && (self.second_approver.is_none() || approver_team.is_none())
+        {
+            tracing::warn!(
+                request_id,
+                "No second approver configured; requiring typed confirmation instead"
+            );
+            requirement = ApprovalRequirement::TypedConfirmation;
+        }
+
+        let prompt = ApprovalPrompt {
+            request_id: request_id.to_string(),
+            request: request.clone(),
+            requester: requester.to_string(),
+            project: project.map(str::to_string),
+            risk: risk.clone(),
+            requirement,
+        };
+
+        let (approved, decided_by, reason) = match requirement {
+            ApprovalRequirement::AutoApprove => (
+                true,
+                "system".to_string(),
+                Some("Risk below auto-approval threshold".to_string()),
+            ),
+            ApprovalRequirement::Confirm => {
+                let confirmed = self.prompter()?.confirm(&prompt).await?;
+                (
+                    confirmed,
+                    requester.to_string(),
+                    (!confirmed).then(|| "Declined by requester".to_string()),
+                )
+            }
+            ApprovalRequirement::TypedConfirmation => {
+                let phrase = prompt.confirmation_phrase();
+                let typed = self
+                    .prompter()?
+                    .typed_confirmation(&prompt, &phrase)
+                    .await?;
+                let confirmed = typed.trim() == phrase;
+                (
+                    confirmed,
+                    requester.to_string(),
+                    (!confirmed).then(|| "Confirmation phrase did not match".to_string()),
+                )
+            }
+            ApprovalRequirement::SecondApprover => {
+                let team = approver_team.unwrap_or_default();
+                let approver = self.second_approver.as_ref().expect("checked above");
+                let answer = approver.request(&prompt, &team).await?;
+                if answer.approver_id == requester {
+                    return Err(SafetyError::AccessDenied {
+                        resource: request_id.to_string(),
+                        reason: "Requesters cannot approve their own operations".to_string(),
+                    });
+                }
+                (answer.approved, answer.approver_id, answer.reason)
+            }
+            ApprovalRequirement::Blocked => (
+                false,
+                "system".to_string(),
+                Some(format!(
+                    "Risk score {} exceeds the approval ceiling",
+                    risk.score
+                )),
+            ),
+        };
+
+        let decision = ApprovalDecision {
+            id: Uuid::new_v4(),
+            request_id: request_id.to_string(),
+            constraint_id: request.constraint_id.clone(),
+            requester: requester.to_string(),
+            project: project.map(str::to_string),
+            risk_score: risk.score,
+            risk_level: risk.level,
+            risk_factors: risk.factors.clone(),
+            requirement,
+            approved,
+            decided_by,
+            reason,
+            decided_at: chrono::Utc::now(),
+        };
+
+        self.record(&decision).await?;
+        Ok(decision)
+    }
+
+    fn prompter(&self) -> SafetyResult<&Arc<dyn ApprovalPrompter>> {
+        self.prompter
+            .as_ref()
+            .ok_or_else(|| SafetyError::ApprovalRequired {
+                reason: "No approval prompt is available to confirm this operation".to_string(),
+            })
+    }
+
+    async fn record(&self, decision: &ApprovalDecision) -> SafetyResult<()> {
+        tracing::info!(
+            request_id = %decision.request_id,
+            requirement = ?decision.requirement,
+            approved = decision.approved,
+            risk_score = decision.risk_score,
+            "Approval decision"
+        );
+        self.decisions.write().await.push(decision.clone());
+
+        if let Some(logger) = &self.audit_logger {
+            let mut audit_data = HashMap::new();
+            audit_data.insert("request_id".to_string(), decision.request_id.clone().into());
+            audit_data.insert("requester".to_string(), decision.requester.clone().into());
+            audit_data.insert(
+                "requirement".to_string(),
+                serde_json::to_value(decision.requirement).unwrap_or_default(),
+            );
+            audit_data.insert(
+                "risk_level".to_string(),
+                serde_json::to_value(decision.risk_level).unwrap_or_default(),
+            );
+            audit_data.insert(
+                "risk_factors".to_string(),
+                serde_json::to_value(&decision.risk_factors).unwrap_or_default(),
+            );
+            if let Some(project) = &decision.project {
+                audit_data.insert("project".to_string(), project.clone().into());
+            }
+
+            let reason = decision.reason.clone().unwrap_or_default();
+            logger
+                .log_audit(AuditTrail {
+                    id: decision.id,
+                    timestamp: decision.decided_at,
+                    event_type: AuditEventType::Authorization,
+                    actor: decision.decided_by.clone(),
+                    action: "approval_decision".to_string(),
+                    resource: decision.constraint_id.clone(),
+                    outcome: if decision.approved {
+                        AuditOutcome::Success
+                    } else if decision.requirement == ApprovalRequirement::Blocked {
+                        AuditOutcome::Blocked(reason)
+                    } else {
+                        AuditOutcome::Denied(reason)
+                    },
+                    audit_data,
+                    compliance_flags: Vec::new(),
+                    risk_score: Some(decision.risk_score),
+                    session_id: None,
+                    source_ip: None,
+                })
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn risk(score: u8) -> RiskScore {
+        let mut factors = RiskFactors::default();
+        factors
+            .operation_factors
+            .insert("destructive_operation".to_string(), score);
+        RiskScore {
+            score,
+            level: RiskLevel::from_score(score),
+            factors,
+            confidence: 1.0,
+            recommendations: Vec::new(),
+            assessed_at: chrono::Utc::now(),
+        }
+    }
+
+    fn request() -> ApprovalRequest {
+        ApprovalRequest {
+            constraint_id: "delete-files".to_string(),
+            reason: "Delete build output".to_string(),
+            requested_at: chrono::Utc::now(),
+            context: None,
+        }
+    }
+
+    struct ScriptedPrompter {
+        confirm: bool,
+        typed: String,
+    }
+
+    #[async_trait]
+    impl ApprovalPrompter for ScriptedPrompter {
+        async fn confirm(&self, _prompt: &ApprovalPrompt) -> SafetyResult<bool> {
+            Ok(self.confirm)
+        }
+
+        async fn typed_confirmation(
+            &self,
+            _prompt: &ApprovalPrompt,
+            _phrase: &str,
+        ) -> SafetyResult<String> {
+            Ok(self.typed.clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_routing_scales_with_risk_and_project() {
+        let logger = Arc::new(AuditLogger::new(100));
+        let router = ApprovalRouter::new(ApprovalRouting::default())
+            .with_prompter(Arc::new(ScriptedPrompter {
+                confirm: true,
+                typed: "approve delete-files".to_string(),
+            }))
+            .with_audit_logger(Arc::clone(&logger));
+
+        let low = router
+            .route("r1", &request(), "alice", None, &risk(10))
+            .await
+            .unwrap();
+        assert_eq!(low.requirement, ApprovalRequirement::AutoApprove);
+        assert_eq!(low.decided_by, "system");
+
+        let medium = router
+            .route("r2", &request(), "alice", None, &risk(40))
+            .await
+            .unwrap();
+        assert_eq!(medium.requirement, ApprovalRequirement::Confirm);
+        assert!(medium.approved);
+
+        let high = router
+            .route("r3", &request(), "alice", None, &risk(70))
+            .await
+            .unwrap();
+        assert_eq!(high.requirement, ApprovalRequirement::TypedConfirmation);
+        assert!(high.approved);
+
+        let blocked = router
+            .route("r4", &request(), "alice", None, &risk(90))
+            .await
+            .unwrap();
+        assert!(!blocked.approved);
+
+        // A stricter project needs confirmation even for low risk
+        router
+            .set_project_thresholds(
+                "payments",
+                ApprovalThresholds {
+                    auto_approve_max: 0,
+                    ..ApprovalThresholds::default()
+                },
+            )
+            .await;
+        let strict = router
+            .route("r5", &request(), "alice", Some("payments"), &risk(10))
+            .await
+            .unwrap();
+        assert_eq!(strict.requirement, ApprovalRequirement::Confirm);
+
+        assert_eq!(router.decisions().await.len(), 5);
+        let trails = logger.get_audit_trails(None, None, None, None).await;
+        assert_eq!(trails.len(), 5);
+        assert!(trails[3].audit_data["risk_factors"]["operation_factors"]
+            .get("destructive_operation")
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn test_second_approver_must_be_another_team_admin() {
+        let access = Arc::new(AccessControlManager::new(
+            Arc::new(ricecoder_permissions::PermissionManager::new()),
+            Arc::new(ricecoder_permissions::AuditLogger::new()),
+        ));
+        access
+            .assign_role("core", "bob", TeamRole::Admin)
+            .await
+            .unwrap();
+        access
+            .assign_role("core", "carol", TeamRole::Member)
+            .await
+            .unwrap();
+        let approver = Arc::new(TeamSecondApprover::new(Arc::clone(&access)));
+
+        let router = Arc::new(
+            ApprovalRouter::new(ApprovalRouting {
+                default: ApprovalThresholds {
+                    high_risk_approval: HighRiskApproval::SecondApprover,
+                    approver_team: Some("core".to_string()),
+                    ..ApprovalThresholds::default()
+                },
+                projects: HashMap::new(),
+            })
+            .with_second_approver(Arc::clone(&approver) as Arc<dyn SecondApprover>),
+        );
+
+        let routed = {
+            let router = Arc::clone(&router);
+            tokio::spawn(async move {
+                router
+                    .route("r1", &request(), "alice", None, &risk(60))
+                    .await
+            })
+        };
+        while approver.pending().await.is_empty() {
+            tokio::task::yield_now().await;
+        }
+
+        assert!(approver.approve("r1", "alice").await.is_err());
+        assert!(approver.approve("r1", "carol").await.is_err());
+        approver.approve("r1", "bob").await.unwrap();
+
+        let decision = routed.await.unwrap().unwrap();
+        assert_eq!(decision.requirement, ApprovalRequirement::SecondApprover);
+        assert!(decision.approved);
+        assert_eq!(decision.decided_by, "bob");
+    }
+}
@@ -0,0 +1,511 @@
+//! Blast-radius analysis for destructive operations
+//!
+//! Before a set of file deletes, renames or schema changes runs, the
+//! analyzer works out which files it touches, which workspace projects own
+//! those files, which projects depend on them (through the orchestration
+//! dependency graph) and whether the change can be undone. The result is
+//! attached to a [`RiskContext`] so [`RiskScorer`](crate::RiskScorer) can
+//! weigh it, and to an [`ApprovalRequest`] so the approval prompt can show it.
+
+use std::collections::{BTreeSet, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use ricecoder_orchestration::DependencyGraph;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{SafetyError, SafetyResult},
+    risk::RiskContext,
+    validation::ApprovalRequest,
+};
+
+/// Key under which a [`BlastRadius`] is stored in risk and approval context
+pub const BLAST_RADIUS_KEY: &str = "blast_radius";
+
+/// Upper bound on files listed individually in a [`BlastRadius`]
+const MAX_LISTED_FILES: usize = 1000;
+
+/// Kind of schema change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SchemaChangeKind {
+    CreateTable,
+    AddColumn,
+    AlterColumn,
+    DropColumn,
+    DropTable,
+}
+
+/// A single operation in a planned change set
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PlannedOperation {
+    /// Delete a file
+    DeleteFile { path: PathBuf },
+    /// Delete a directory and everything below it
+    DeleteDirectory { path: PathBuf },
+    /// Rename or move a file; mass renames are many of these
+    Rename { from: PathBuf, to: PathBuf },
+    /// Change a database schema object
+    SchemaChange {
+        /// Table or other schema object
+        object: String,
+        change: SchemaChangeKind,
+        /// Migration or schema file, if the change lives in one
+        file: Option<PathBuf>,
+    },
+}
+
+/// How far a change can be undone
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Reversibility {
+    /// Can be undone directly, e.g. renaming back
+    Reversible,
+    /// Can be restored from version control
+    RecoverableFromVcs,
+    /// Data is lost for good
+    Irreversible,
+}
+
+/// Result of blast-radius analysis
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlastRadius {
+    /// Number of planned operations
+    pub operations: usize,
+    /// Files touched, capped at a listing limit
+    pub affected_files: Vec<PathBuf>,
+    /// Total number of files touched
+    pub affected_file_count: usize,
+    /// Workspace projects that own affected files
+    pub affected_projects: Vec<String>,
+    /// Projects that depend, directly or transitively, on an affected project
+    pub dependent_projects: Vec<String>,
+    /// Schema objects changed
+    pub schema_objects: Vec<String>,
+    /// Worst reversibility across all operations
+    pub reversibility: Reversibility,
+    /// Operations that cannot be undone
+    pub irreversible: Vec<String>,
+}
+
+impl BlastRadius {
+    /// Risk points contributed by this blast radius (0-100)
+    pub fn risk_points(&self) -> u8 {
+        let files: u8 = match self.affected_file_count {
+            0 => 0,
+            1 => 5,
+            2..=10 => 10,
+            11..=100 => 20,
+            _ => 30,
+        };
+        let dependents = (self.dependent_projects.len() * 10).min(30) as u8;
+        let reversibility = match self.reversibility {
+            Reversibility::Reversible => 0,
+            Reversibility::RecoverableFromVcs => 10,
+            Reversibility::Irreversible => 40,
+        };
+        files
+            .saturating_add(dependents)
+            .saturating_add(reversibility)
+            .min(100)
+    }
+
+    /// Short description for approval prompts
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "{} file(s) in {} project(s)",
+            self.affected_file_count,
+            self.affected_projects.len()
+        );
+        if !self.dependent_projects.is_empty() {
+            summary.push_str(&format!(
+                ", {} dependent project(s): {}",
+                self.dependent_projects.len(),
+                self.dependent_projects.join(", ")
+            ));
+        }
+        if !self.schema_objects.is_empty() {
+            summary.push_str(&format!(", schema: {}", self.schema_objects.join(", ")));
+        }
+        let reversibility = match self.reversibility {
+            Reversibility::Reversible => "reversible",
+            Reversibility::RecoverableFromVcs => "recoverable from version control",
+            Reversibility::Irreversible => "irreversible",
+        };
+        summary.push_str(&format!("; {}", reversibility));
+        summary
+    }
+
+    /// Store this blast radius in a risk context for scoring
+    pub fn apply_to_risk_context(&self, context: &mut RiskContext) {
+        if let Ok(value) = serde_json::to_value(self) {
+            context
+                .additional_data
+                .insert(BLAST_RADIUS_KEY.to_string(), value);
+        }
+    }
+
+    /// Store this blast radius in an approval request for the approval prompt
+    pub fn attach_to_request(&self, request: &mut ApprovalRequest) {
+        if let Ok(value) = serde_json::to_value(self) {
+            request
+                .context
+                .get_or_insert_with(Default::default)
+                .insert(BLAST_RADIUS_KEY.to_string(), value);
+        }
+    }
+
+    /// Blast radius stored in risk or approval context, if any
+    pub fn from_context(
+        context: &std::collections::HashMap<String, serde_json::Value>,
+    ) -> Option<Self> {
+        context
+            .get(BLAST_RADIUS_KEY)
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+    }
+}
+
+/// Computes the blast radius of planned operations in a workspace
+pub struct BlastRadiusAnalyzer {
+    workspace_root: PathBuf,
+    graph: Option<DependencyGraph>,
+}
+
+impl BlastRadiusAnalyzer {
+    /// Analyze operations relative to `workspace_root`
+    pub fn new(workspace_root: impl Into<PathBuf>) -> Self {
+        Self {
+            workspace_root: workspace_root.into(),
+            graph: None,
+        }
+    }
+
+    /// Resolve affected and dependent projects through a dependency graph
+    pub fn with_dependency_graph(mut self, graph: DependencyGraph) -> Self {
+        self.graph = Some(graph);
+        self
+    }
+
+    /// Compute the blast radius of a planned operation set
+    pub fn analyze(&self, operations: &[PlannedOperation]) -> SafetyResult<BlastRadius> {
+        let mut files = BTreeSet::new();
+        let mut deleted = Vec::new();
+        let mut schema_objects = BTreeSet::new();
+        let mut irreversible = Vec::new();
+        let mut reversibility = Reversibility::Reversible;
+
+        for operation in operations {
+            match operation {
+                PlannedOperation::DeleteFile { path } => {
+                    let path = self.resolve(path);
+                    files.insert(path.clone());
+                    deleted.push(path);
+                }
+                PlannedOperation::DeleteDirectory { path } => {
+                    let path = self.resolve(path);
+                    let mut contents = Vec::new();
+                    collect_files(&path, &mut contents)?;
+                    files.extend(contents.iter().cloned());
+                    deleted.extend(contents);
+                }
+                PlannedOperation::Rename { from, to } => {
+                    files.insert(self.resolve(from));
+                    files.insert(self.resolve(to));
+                }
+                PlannedOperation::SchemaChange {
+                    object,
+                    change,
+                    file,
+                } => {
+                    schema_objects.insert(object.clone());
+                    if let Some(file) = file {
+                        files.insert(self.resolve(file));
+                    }
+                    let level = match change {
+                        SchemaChangeKind::CreateTable | SchemaChangeKind::AddColumn => {
+                            Reversibility::Reversible
+                        }
+                        SchemaChangeKind::AlterColumn
+                        | SchemaChangeKind::DropColumn
+                        | SchemaChangeKind::DropTable => Reversibility::Irreversible,
+                    };
+                    if level == Reversibility::Irreversible {
+                        irreversible.push(format!("{:?} {}", change, object));
+                    }
+                    reversibility = reversibility.max(level);
+                }
+            }
+        }
+
+        // Deleted files survive only if version control has them
+        let tracked = self.tracked_files(&deleted);
+        for path in &deleted {
+            if tracked.contains(path) {
+                reversibility = reversibility.max(Reversibility::RecoverableFromVcs);
+            } else {
+                reversibility = Reversibility::Irreversible;
+                irreversible.push(format!("delete untracked {}", self.display(path)));
+            }
+        }
+
+        let (affected_projects, dependent_projects) = self.projects_for(&files);
+        let affected_file_count = files.len();
+
+        Ok(BlastRadius {
+            operations: operations.len(),
+            affected_files: files.into_iter().take(MAX_LISTED_FILES).collect(),
+            affected_file_count,
+            affected_projects,
+            dependent_projects,
+            schema_objects: schema_objects.into_iter().collect(),
+            reversibility,
+            irreversible,
+        })
+    }
+
+    fn resolve(&self, path: &Path) -> PathBuf {
+        if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            self.workspace_root.join(path)
+        }
+    }
+
+    fn display(&self, path: &Path) -> String {
+        path.strip_prefix(&self.workspace_root)
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    }
+
+    /// Paths among `paths` that git tracks; empty when git is unavailable
+    fn tracked_files(&self, paths: &[PathBuf]) -> HashSet<PathBuf> {
+        if paths.is_empty() {
+            return HashSet::new();
+        }
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(&self.workspace_root)
+            .args(["ls-files", "--full-name", "-z", "--"])
+            .args(paths)
+            .output();
+        let output = match output {
+            Ok(output) if output.status.success() => output,
+            _ => return HashSet::new(),
+        };
+        let toplevel = Command::new("git")
+            .arg("-C")
+            .arg(&self.workspace_root)
+            .args(["rev-parse", "--show-toplevel"])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| PathBuf::from(String::from_utf8_lossy(&output.stdout).trim()));
+        let Some(toplevel) = toplevel else {
+            return HashSet::new();
+        };
+
+        let tracked: HashSet<PathBuf> = output
+            .stdout
+            .split(|byte| *byte == 0)
+            .filter(|entry| !entry.is_empty())
+            .filter_map(|entry| {
+                std::fs::canonicalize(toplevel.join(String::from_utf8_lossy(entry).as_ref())).ok()
+            })
+            .collect();
+        paths
+            .iter()
+            .filter(|path| {
+                std::fs::canonicalize(path)
+                    .map(|path| tracked.contains(&path))
+                    .unwrap_or(false)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Projects owning `files`, and projects depending on those
+    fn projects_for(&self, files: &BTreeSet<PathBuf>) -> (Vec<String>, Vec<String>) {
+        let Some(graph) = &self.graph else {
+            return (Vec::new(), Vec::new());
+        };
+
+        let projects: Vec<(PathBuf, String)> = graph
+            .get_projects()
+            .into_iter()
+            .map(|project| (self.resolve(&project.path), project.name))
+            .collect();
+
+        let mut affected = BTreeSet::new();
+        for file in files {
+            // The most specific project wins for nested projects
+            let owner = projects
+                .iter()
+                .filter(|(root, _)| file.starts_with(root))
+                .max_by_key(|(root, _)| root.components().count());
+            if let Some((_, name)) = owner {
+                affected.insert(name.clone());
+            }
+        }
+
+        let mut dependents = BTreeSet::new();
+        let mut queue: VecDeque<String> = affected.iter().cloned().collect();
+        while let Some(project) = queue.pop_front() {
+            for dependent in graph.get_dependents(&project) {
+                if !affected.contains(&dependent) && dependents.insert(dependent.clone()) {
+                    queue.push_back(dependent);
+                }
+            }
+        }
+
+        (
+            affected.into_iter().collect(),
+            dependents.into_iter().collect(),
+        )
+    }
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> SafetyResult<()> {
+    let entries = std::fs::read_dir(dir).map_err(|e| SafetyError::ValidationError {
+        field: "path".to_string(),
+        message: format!("Cannot read {}: {}", dir.display(), e),
+    })?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        match entry.file_type() {
+            Ok(kind) if kind.is_dir() => collect_files(&path, files)?,
+            Ok(_) => files.push(path),
+            Err(_) => {}
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use ricecoder_orchestration::{DependencyType, Project, ProjectDependency, ProjectStatus};
+
+    use super::*;
+    use crate::risk::RiskScorer;
+
+    fn project(root: &Path, name: &str) -> Project {
+        Project {
+            path: root.join(name),
+            name: name.to_string(),
+            project_type: "rust".to_string(),
+            version: "0.1.0".to_string(),
+            status: ProjectStatus::Healthy,
+        }
+    }
+
+    fn workspace() -> (tempfile::TempDir, DependencyGraph) {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["core", "api", "cli"] {
+            std::fs::create_dir_all(dir.path().join(name).join("src")).unwrap();
+            std::fs::write(dir.path().join(name).join("src/lib.rs"), "").unwrap();
+        }
+        std::fs::write(dir.path().join("core/src/db.rs"), "").unwrap();
+
+        let mut graph = DependencyGraph::new(false);
+        for name in ["core", "api", "cli"] {
+            graph.add_project(project(dir.path(), name)).unwrap();
+        }
+        for (from, to) in [("api", "core"), ("cli", "api")] {
+            graph
+                .add_dependency(ProjectDependency {
+                    from: from.to_string(),
+                    to: to.to_string(),
+                    dependency_type: DependencyType::Direct,
+                    version_constraint: "*".to_string(),
+                })
+                .unwrap();
+        }
+        (dir, graph)
+    }
+
+    #[test]
+    fn test_delete_reaches_transitive_dependents() {
+        let (dir, graph) = workspace();
+        let analyzer = BlastRadiusAnalyzer::new(dir.path()).with_dependency_graph(graph);
+
+        let radius = analyzer
+            .analyze(&[PlannedOperation::DeleteDirectory {
+                path: PathBuf::from("core/src"),
+            }])
+            .unwrap();
+
+        assert_eq!(radius.affected_file_count, 2);
+        assert_eq!(radius.affected_projects, vec!["core"]);
+        assert_eq!(radius.dependent_projects, vec!["api", "cli"]);
+        // Not under version control, so nothing brings the files back
+        assert_eq!(radius.reversibility, Reversibility::Irreversible);
+        assert_eq!(radius.irreversible.len(), 2);
+    }
+
+    #[test]
+    fn test_renames_and_schema_changes() {
+        let (dir, graph) = workspace();
+        let analyzer = BlastRadiusAnalyzer::new(dir.path()).with_dependency_graph(graph);
+
+        let renames = analyzer
+            .analyze(&[
+                PlannedOperation::Rename {
+                    from: PathBuf::from("cli/src/lib.rs"),
+                    to: PathBuf::from("cli/src/main.rs"),
+                },
+                PlannedOperation::SchemaChange {
+                    object: "users".to_string(),
+                    change: SchemaChangeKind::AddColumn,
+                    file: None,
+                },
+            ])
+            .unwrap();
+        assert_eq!(renames.reversibility, Reversibility::Reversible);
+        assert_eq!(renames.affected_projects, vec!["cli"]);
+        assert!(renames.dependent_projects.is_empty());
+
+        let drop = analyzer
+            .analyze(&[PlannedOperation::SchemaChange {
+                object: "users".to_string(),
+                change: SchemaChangeKind::DropTable,
+                file: Some(PathBuf::from("api/migrations/002_drop_users.sql")),
+            }])
+            .unwrap();
+        assert_eq!(drop.reversibility, Reversibility::Irreversible);
+        assert_eq!(drop.dependent_projects, vec!["cli"]);
+        assert!(drop.risk_points() > renames.risk_points());
+    }
+
+    #[test]
+    fn test_blast_radius_feeds_risk_scorer_and_approval() {
+        let (dir, graph) = workspace();
+        let radius = BlastRadiusAnalyzer::new(dir.path())
+            .with_dependency_graph(graph)
+            .analyze(&[PlannedOperation::DeleteFile {
+                path: PathBuf::from("core/src/db.rs"),
+            }])
+            .unwrap();
+
+        let mut context = RiskContext::default();
+        radius.apply_to_risk_context(&mut context);
+        let score = RiskScorer::new().score_action("delete", &context).unwrap();
+        assert_eq!(
+            score.factors.operation_factors.get(BLAST_RADIUS_KEY),
+            Some(&radius.risk_points())
+        );
+
+        let mut request = ApprovalRequest {
+            constraint_id: "delete".to_string(),
+            reason: "Delete db module".to_string(),
+            requested_at: chrono::Utc::now(),
+            context: None,
+        };
+        radius.attach_to_request(&mut request);
+        assert_eq!(
+            BlastRadius::from_context(request.context.as_ref().unwrap()),
+            Some(radius)
+        );
+    }
+}
@@ -27,6 +27,27 @@ pub enum ConstraintType {
     MaxMemoryUsage(u64),
     /// Required security context
     SecurityContextRequired(String),
+    /// Paths that may be written, in addition to the working directory
+    AllowedPaths(Vec<String>),
+    /// Paths that must never be accessed
+    DeniedPaths(Vec<String>),
+    /// Paths matching any glob, unless they match an exception
+    PathGlobs {
+        patterns: Vec<String>,
+        except: Vec<String>,
+    },
+    /// Shell commands matching any pattern, unless they match an exception
+    ///
+    /// Patterns are globs over the whole command line; `re:` prefixes a
+    /// regular expression instead.
+    CommandPatterns {
+        patterns: Vec<String>,
+        except: Vec<String>,
+    },
+    /// Maximum number of files a single operation may affect
+    MaxBlastRadius(u64),
+    /// Branches that must not be written to directly (globs)
+    ProtectedBranches(Vec<String>),
     /// Custom constraint with validation logic
     Custom(String),
 }
@@ -177,6 +198,15 @@ impl SecurityConstraint {
             ConstraintType::SecurityContextRequired(required) => {
                 self.validate_security_context(context, required)
             }
+            ConstraintType::AllowedPaths(paths) => self.validate_path_access(context, paths, true),
+            ConstraintType::DeniedPaths(paths) => self.validate_path_access(context, paths, false),
+            ConstraintType::PathGlobs { .. }
+            | ConstraintType::CommandPatterns { .. }
+            | ConstraintType::MaxBlastRadius(_)
+            | ConstraintType::ProtectedBranches(_) => match self.match_rule(context)? {
+                RuleMatch::Matched(reason) => Ok(self.rule_result(reason)),
+                RuleMatch::NotApplicable | RuleMatch::Exempted(_) => Ok(ConstraintResult::Passed),
+            },
             ConstraintType::Custom(logic) => self.validate_custom_constraint(context, logic),
         }
     }
@@ -306,6 +336,102 @@ impl SecurityConstraint {
         Ok(ConstraintResult::Passed)
     }
 
+    fn validate_path_access(
+        &self,
+        context: &ValidationContext,
+        paths: &[String],
+        is_allowed: bool,
+    ) -> SafetyResult<ConstraintResult> {
+        if let Some(file_path) = &context.file_path {
+            let target = std::path::Path::new(file_path);
+            let contains = paths
+                .iter()
+                .any(|p| target.starts_with(p.trim_end_matches('/')));
+
+            if is_allowed && !contains {
+                return Ok(ConstraintResult::Failed(format!(
+                    "Path '{}' is outside the allowed paths: {:?}",
+                    file_path, paths
+                )));
+            } else if !is_allowed && contains {
+                return Ok(ConstraintResult::Failed(format!(
+                    "Path '{}' is denied: {:?}",
+                    file_path, paths
+                )));
+            }
+        }
+        Ok(ConstraintResult::Passed)
+    }
+
+    /// Match a policy rule constraint against an operation
+    ///
+    /// Only path, command, blast radius and branch constraints are rules;
+    /// every other type is [`RuleMatch::NotApplicable`].
+    pub fn match_rule(&self, context: &ValidationContext) -> SafetyResult<RuleMatch> {
+        let result = match &self.constraint_type {
+            ConstraintType::PathGlobs { patterns, except } => match &context.file_path {
+                Some(path) => match_patterns(path, patterns, except, glob_matcher)?.map(
+                    |(pattern, exception)| match exception {
+                        Some(exception) => RuleMatch::Exempted(format!(
+                            "Path '{}' matches '{}' but is excepted by '{}'",
+                            path, pattern, exception
+                        )),
+                        None => {
+                            RuleMatch::Matched(format!("Path '{}' matches '{}'", path, pattern))
+                        }
+                    },
+                ),
+                None => None,
+            },
+            ConstraintType::CommandPatterns { patterns, except } => match &context.command {
+                Some(command) => match_patterns(command.trim(), patterns, except, command_matcher)?
+                    .map(|(pattern, exception)| match exception {
+                        Some(exception) => RuleMatch::Exempted(format!(
+                            "Command '{}' matches '{}' but is excepted by '{}'",
+                            command, pattern, exception
+                        )),
+                        None => RuleMatch::Matched(format!(
+                            "Command '{}' matches '{}'",
+                            command, pattern
+                        )),
+                    }),
+                None => None,
+            },
+            ConstraintType::MaxBlastRadius(max_files) => context
+                .affected_files
+                .filter(|affected| affected > max_files)
+                .map(|affected| {
+                    RuleMatch::Matched(format!(
+                        "Operation affects {} files, more than the maximum of {}",
+                        affected, max_files
+                    ))
+                }),
+            ConstraintType::ProtectedBranches(branches) => match &context.target_branch {
+                Some(branch) => {
+                    match_patterns(branch, branches, &[], glob_matcher)?.map(|(pattern, _)| {
+                        RuleMatch::Matched(format!(
+                            "Branch '{}' is protected by '{}'",
+                            branch, pattern
+                        ))
+                    })
+                }
+                None => None,
+            },
+            _ => None,
+        };
+        Ok(result.unwrap_or(RuleMatch::NotApplicable))
+    }
+
+    /// Result of a matched rule, honouring the configured effect
+    fn rule_result(&self, reason: String) -> ConstraintResult {
+        let effect = self.config.get(EFFECT_CONFIG_KEY).and_then(|v| v.as_str());
+        if effect == Some(EFFECT_REQUIRE_APPROVAL) {
+            ConstraintResult::ApprovalRequired(reason)
+        } else {
+            ConstraintResult::Failed(reason)
+        }
+    }
+
     fn validate_custom_constraint(
         &self,
         context: &ValidationContext,
@@ -318,16 +444,88 @@ impl SecurityConstraint {
     }
 }
 
+/// Config key selecting what happens when a rule constraint matches
+pub const EFFECT_CONFIG_KEY: &str = "effect";
+
+/// Effect value that turns a matched rule into an approval request
+pub const EFFECT_REQUIRE_APPROVAL: &str = "require_approval";
+
+/// Outcome of matching a rule constraint against an operation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleMatch {
+    /// The rule doesn't apply to the operation
+    NotApplicable,
+    /// The rule matched, with the reason
+    Matched(String),
+    /// The rule matched but an exception allowed the operation
+    Exempted(String),
+}
+
+fn glob_matcher(pattern: &str) -> SafetyResult<Box<dyn Fn(&str) -> bool>> {
+    let glob = globset::GlobBuilder::new(pattern)
+        .literal_separator(true)
+        .build()
+        .map_err(|e| SafetyError::ConfigError {
+            field: "pattern".to_string(),
+            message: format!("Invalid glob '{}': {}", pattern, e),
+        })?
+        .compile_matcher();
+    Ok(Box::new(move |value: &str| glob.is_match(value)))
+}
+
+fn command_matcher(pattern: &str) -> SafetyResult<Box<dyn Fn(&str) -> bool>> {
+    if let Some(regex) = pattern.strip_prefix("re:") {
+        let regex = regex::Regex::new(regex).map_err(|e| SafetyError::ConfigError {
+            field: "pattern".to_string(),
+            message: format!("Invalid command regex '{}': {}", regex, e),
+        })?;
+        return Ok(Box::new(move |value: &str| regex.is_match(value)));
+    }
+
+    // Commands aren't paths, so `*` crosses `/`
+    let glob = globset::Glob::new(pattern)
+        .map_err(|e| SafetyError::ConfigError {
+            field: "pattern".to_string(),
+            message: format!("Invalid command pattern '{}': {}", pattern, e),
+        })?
+        .compile_matcher();
+    Ok(Box::new(move |value: &str| glob.is_match(value)))
+}
+
+/// First pattern matching `value`, with the exception that cancels it if any
+fn match_patterns<'a>(
+    value: &str,
+    patterns: &'a [String],
+    except: &'a [String],
+    matcher: fn(&str) -> SafetyResult<Box<dyn Fn(&str) -> bool>>,
+) -> SafetyResult<Option<(&'a str, Option<&'a str>)>> {
+    for pattern in patterns {
+        if matcher(pattern)?(value) {
+            for exception in except {
+                if matcher(exception)?(value) {
+                    return Ok(Some((pattern, Some(exception))));
+                }
+            }
+            return Ok(Some((pattern, None)));
+        }
+    }
+    Ok(None)
+}
+
 /// Constraint severity levels
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum ConstraintSeverity {
     /// Low severity - informational
+    #[serde(alias = "low")]
     Low,
     /// Medium severity - requires attention
+    #[serde(alias = "medium")]
     Medium,
     /// High severity - blocks operation
+    #[serde(alias = "high")]
     High,
     /// Critical severity - emergency response required
+    #[serde(alias = "critical")]
     Critical,
 }
 
@@ -361,6 +559,12 @@ pub struct ValidationContext {
     pub user_id: Option<String>,
     /// Session ID
     pub session_id: Option<String>,
+    /// Shell command being run
+    pub command: Option<String>,
+    /// Git branch being written to
+    pub target_branch: Option<String>,
+    /// Number of files the operation affects
+    pub affected_files: Option<u64>,
     /// Additional context data
     pub additional_data: HashMap<String, serde_json::Value>,
 }
@@ -394,4 +598,22 @@ impl ValidationContext {
         self.session_id = Some(session_id);
         self
     }
+
+    /// Set the shell command
+    pub fn with_command(mut self, command: String) -> Self {
+        self.command = Some(command);
+        self
+    }
+
+    /// Set the target branch
+    pub fn with_target_branch(mut self, branch: String) -> Self {
+        self.target_branch = Some(branch);
+        self
+    }
+
+    /// Set the number of affected files
+    pub fn with_affected_files(mut self, count: u64) -> Self {
+        self.affected_files = Some(count);
+        self
+    }
 }
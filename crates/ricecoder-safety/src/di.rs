@@ -8,7 +8,10 @@ use std::sync::Arc;
 
 use ricecoder_common::di::{ServiceEntry, ServiceFactory};
 
-use crate::validation::SafetyValidator;
+use crate::{
+    sensitive_paths::{self, SensitivePathPolicy},
+    validation::SafetyValidator,
+};
 
 // Auto-register safety services with the DI container
 inventory::submit! {
@@ -23,6 +26,8 @@ fn create_safety_services() -> Vec<ServiceEntry> {
     vec![
         // SafetyValidator - Operation safety validation and approval gates
         ServiceEntry::new::<SafetyValidator>(Arc::new(SafetyValidator::new())),
+        // SensitivePathPolicy - Shared with file tools, which consult it directly
+        ServiceEntry::new::<SensitivePathPolicy>(sensitive_paths::global()),
     ]
 }
 
@@ -32,6 +32,9 @@ pub enum SafetyError {
     #[error("Monitoring error: {message}")]
     MonitoringError { message: String },
 
+    #[error("Sandbox error: {message}")]
+    SandboxError { message: String },
+
     #[error("Audit logging error: {0}")]
     AuditError(#[from] ricecoder_activity_log::ActivityLogError),
 
@@ -11,6 +11,11 @@
 //! - **Safety Validation**: Pre-execution safety checks and approval gates
 //! - **Compliance Monitoring**: Enterprise security compliance validation
 //! - **Audit Integration**: Seamless integration with activity logging
+//! - **Policy Files**: Layered `.ricecoder/safety.yaml` rules with hot reload
+//! - **Sandboxing**: OS-level isolation for approved commands
+//! - **Risk-Weighted Approval**: Approval requirements that scale with risk
+//! - **Blast Radius**: Impact and reversibility analysis for destructive changes
+//! - **Sensitive Paths**: Deny-by-default file access to secrets with approved overrides
 //!
 //! ## Architecture
 //!
@@ -40,16 +45,29 @@
 //! let risk_score = RiskScorer::score_action(&action, &context);
 //! ```
 
+pub mod approval;
+pub mod blast_radius;
 pub mod constraints;
 pub mod di;
 pub mod error;
 pub mod monitoring;
+pub mod policy;
 pub mod risk;
+pub mod sandbox;
+pub mod sensitive_paths;
 pub mod validation;
 
 // Re-export commonly used types
+pub use approval::{
+    ApprovalDecision, ApprovalPrompter, ApprovalRequirement, ApprovalRouter, ApprovalRouting,
+    ApprovalThresholds, TeamSecondApprover,
+};
+pub use blast_radius::{BlastRadius, BlastRadiusAnalyzer, PlannedOperation, Reversibility};
 pub use constraints::{ConstraintResult, ConstraintType, SecurityConstraint};
 pub use error::{SafetyError, SafetyResult};
 pub use monitoring::{AlertLevel, SafetyMetrics, SafetyMonitor};
+pub use policy::{EffectivePolicy, PolicyExplanation, PolicyManager, PolicySources};
 pub use risk::{RiskFactors, RiskLevel, RiskScore, RiskScorer};
+pub use sandbox::{NetworkPolicy, Sandbox, SandboxPolicy, SandboxRequest};
+pub use sensitive_paths::{PathAccess, PathDenied, SensitivePathConfig, SensitivePathPolicy};
 pub use validation::{ApprovalGate, ApprovalRequest, SafetyValidator, ValidationResult};
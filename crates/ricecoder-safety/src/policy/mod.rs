@@ -0,0 +1,822 @@
+//! Declarative safety policy files
+//!
+//! Safety rules live in `.ricecoder/safety.yaml` at three levels:
+//! organization, team and project. Files are merged with the more specific
+//! level taking precedence, then compiled into [`SecurityConstraint`]s that
+//! the [`SafetyValidator`](crate::SafetyValidator) enforces.
+//!
+//! ```yaml
+//! version: 1
+//! rules:
+//!   - id: no-env-files
+//!     description: Environment files hold secrets
+//!     paths: ["**/.env*"]
+//!     except_paths: ["**/.env.example"]
+//!     locked: true
+//!   - id: pipe-to-shell
+//!     effect: require_approval
+//!     commands: ["re:curl .*\\|\\s*(ba)?sh"]
+//! max_blast_radius: 200
+//! protected_branches: [main, "release/*"]
+//! disable: [some-team-rule]
+//! sensitive_paths:
+//!   deny: ["secrets/**"]
+//! ```
+//!
+//! Merging rules:
+//!
+//! - Rules are keyed by `id`; a more specific level replaces a rule with the
+//!   same id or removes it through `disable`, unless the rule is `locked`
+//! - `max_blast_radius` comes from the most specific level that sets it
+//! - `protected_branches` accumulate across levels
+//! - `sensitive_paths` globs accumulate across levels; `use_defaults` comes
+//!   from the most specific level that sets it
+//!
+//! Relative path globs match anywhere in a path (`secrets/**` behaves like
+//! `**/secrets/**`). Command patterns are globs over the whole command line,
+//! or regular expressions when prefixed with `re:`.
+//!
+//! [`EffectivePolicy::explain`] reports which rule allowed or denied an
+//! operation, and [`PolicyManager`] keeps the policy current as files change.
+
+mod watcher;
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    constraints::{
+        ConstraintSeverity, ConstraintType, RuleMatch, SecurityConstraint, ValidationContext,
+        EFFECT_CONFIG_KEY, EFFECT_REQUIRE_APPROVAL,
+    },
+    error::{SafetyError, SafetyResult},
+    sensitive_paths::SensitivePathConfig,
+};
+
+pub use watcher::{PolicyEvent, PolicyManager, PolicyWatchHandle};
+
+/// Location of a policy file relative to its level's root directory
+pub const POLICY_FILE: &str = ".ricecoder/safety.yaml";
+
+/// Prefix of constraint ids compiled from policy files
+pub const POLICY_CONSTRAINT_PREFIX: &str = "policy:";
+
+/// Supported policy file version
+const POLICY_VERSION: u32 = 1;
+
+/// Level a policy file applies at, in increasing precedence
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyLevel {
+    Organization,
+    Team,
+    Project,
+}
+
+impl PolicyLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PolicyLevel::Organization => "organization",
+            PolicyLevel::Team => "team",
+            PolicyLevel::Project => "project",
+        }
+    }
+}
+
+impl fmt::Display for PolicyLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// What happens when a rule matches
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleEffect {
+    /// The operation is blocked
+    #[default]
+    Deny,
+    /// The operation needs manual approval
+    RequireApproval,
+}
+
+/// A rule in a policy file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PolicyRule {
+    pub id: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub effect: RuleEffect,
+    #[serde(default = "default_severity")]
+    pub severity: ConstraintSeverity,
+    /// Path globs the rule applies to
+    #[serde(default)]
+    pub paths: Vec<String>,
+    /// Path globs exempt from the rule
+    #[serde(default)]
+    pub except_paths: Vec<String>,
+    /// Command patterns the rule applies to
+    #[serde(default)]
+    pub commands: Vec<String>,
+    /// Command patterns exempt from the rule
+    #[serde(default)]
+    pub except_commands: Vec<String>,
+    /// More specific levels can't replace or disable this rule
+    #[serde(default)]
+    pub locked: bool,
+}
+
+fn default_severity() -> ConstraintSeverity {
+    ConstraintSeverity::High
+}
+
+/// Contents of a `safety.yaml` file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PolicyFile {
+    pub version: u32,
+    pub rules: Vec<PolicyRule>,
+    /// Maximum number of files a single operation may affect
+    pub max_blast_radius: Option<u64>,
+    /// Branch globs that must not be written to directly
+    pub protected_branches: Vec<String>,
+    /// Rule ids from less specific levels to drop
+    pub disable: Vec<String>,
+    /// Paths file tools must not touch, see [`crate::sensitive_paths`]
+    pub sensitive_paths: SensitivePathConfig,
+}
+
+impl Default for PolicyFile {
+    fn default() -> Self {
+        Self {
+            version: POLICY_VERSION,
+            rules: Vec::new(),
+            max_blast_radius: None,
+            protected_branches: Vec::new(),
+            disable: Vec::new(),
+            sensitive_paths: SensitivePathConfig::default(),
+        }
+    }
+}
+
+impl PolicyFile {
+    /// Parse and check a policy file
+    pub fn parse(content: &str, source: &Path) -> SafetyResult<Self> {
+        let file: PolicyFile = if content.trim().is_empty() {
+            PolicyFile::default()
+        } else {
+            serde_yaml::from_str(content).map_err(|e| SafetyError::ConfigError {
+                field: source.display().to_string(),
+                message: e.to_string(),
+            })?
+        };
+        file.check(source)?;
+        Ok(file)
+    }
+
+    fn check(&self, source: &Path) -> SafetyResult<()> {
+        let error = |field: String, message: String| SafetyError::ConfigError {
+            field: format!("{}: {}", source.display(), field),
+            message,
+        };
+
+        if self.version != POLICY_VERSION {
+            return Err(error(
+                "version".to_string(),
+                format!("Unsupported policy version {}", self.version),
+            ));
+        }
+
+        let mut seen = std::collections::HashSet::new();
+        for rule in &self.rules {
+            if rule.id.trim().is_empty() {
+                return Err(error("rules".to_string(), "Rule id is empty".to_string()));
+            }
+            if !seen.insert(rule.id.as_str()) {
+                return Err(error(
+                    format!("rules.{}", rule.id),
+                    "Duplicate rule id".to_string(),
+                ));
+            }
+            if rule.paths.is_empty() && rule.commands.is_empty() {
+                return Err(error(
+                    format!("rules.{}", rule.id),
+                    "Rule must match paths or commands".to_string(),
+                ));
+            }
+            for pattern in rule.paths.iter().chain(&rule.except_paths) {
+                globset::Glob::new(pattern)
+                    .map_err(|e| error(format!("rules.{}.paths", rule.id), e.to_string()))?;
+            }
+            for pattern in rule.commands.iter().chain(&rule.except_commands) {
+                check_command_pattern(pattern)
+                    .map_err(|message| error(format!("rules.{}.commands", rule.id), message))?;
+            }
+        }
+
+        for pattern in &self.protected_branches {
+            globset::Glob::new(pattern)
+                .map_err(|e| error("protected_branches".to_string(), e.to_string()))?;
+        }
+        for pattern in self
+            .sensitive_paths
+            .deny
+            .iter()
+            .chain(&self.sensitive_paths.allow)
+        {
+            globset::Glob::new(pattern)
+                .map_err(|e| error("sensitive_paths".to_string(), e.to_string()))?;
+        }
+        Ok(())
+    }
+}
+
+fn check_command_pattern(pattern: &str) -> Result<(), String> {
+    match pattern.strip_prefix("re:") {
+        Some(regex) => regex::Regex::new(regex)
+            .map(|_| ())
+            .map_err(|e| e.to_string()),
+        None => globset::Glob::new(pattern)
+            .map(|_| ())
+            .map_err(|e| e.to_string()),
+    }
+}
+
+/// Root directories policy files are read from
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PolicySources {
+    pub organization: Option<PathBuf>,
+    pub team: Option<PathBuf>,
+    pub project: Option<PathBuf>,
+}
+
+impl PolicySources {
+    /// Sources for a project
+    ///
+    /// Organization and team roots come from `RICECODER_ORG_POLICY_DIR` and
+    /// `RICECODER_TEAM_POLICY_DIR` when set.
+    pub fn for_project(project_root: impl Into<PathBuf>) -> Self {
+        Self {
+            organization: std::env::var_os("RICECODER_ORG_POLICY_DIR").map(PathBuf::from),
+            team: std::env::var_os("RICECODER_TEAM_POLICY_DIR").map(PathBuf::from),
+            project: Some(project_root.into()),
+        }
+    }
+
+    /// Set the organization root
+    pub fn with_organization(mut self, root: impl Into<PathBuf>) -> Self {
+        self.organization = Some(root.into());
+        self
+    }
+
+    /// Set the project root
+    pub fn with_project(mut self, root: impl Into<PathBuf>) -> Self {
+        self.project = Some(root.into());
+        self
+    }
+
+    /// Set the team root
+    pub fn with_team(mut self, root: impl Into<PathBuf>) -> Self {
+        self.team = Some(root.into());
+        self
+    }
+
+    /// Policy file paths in increasing precedence
+    pub fn files(&self) -> Vec<(PolicyLevel, PathBuf)> {
+        [
+            (PolicyLevel::Organization, &self.organization),
+            (PolicyLevel::Team, &self.team),
+            (PolicyLevel::Project, &self.project),
+        ]
+        .into_iter()
+        .filter_map(|(level, root)| root.as_ref().map(|root| (level, root.join(POLICY_FILE))))
+        .collect()
+    }
+}
+
+/// A merged rule and where it was defined
+#[derive(Debug, Clone, PartialEq)]
+pub struct EffectiveRule {
+    pub rule: PolicyRule,
+    pub level: PolicyLevel,
+    pub source: PathBuf,
+}
+
+/// A compiled constraint and the rule it came from
+#[derive(Debug, Clone)]
+struct CompiledRule {
+    constraint: SecurityConstraint,
+    rule_id: String,
+    level: PolicyLevel,
+    source: PathBuf,
+}
+
+/// Policy merged from all levels
+#[derive(Debug, Clone, Default)]
+pub struct EffectivePolicy {
+    /// Rules, most specific level first
+    pub rules: Vec<EffectiveRule>,
+    pub max_blast_radius: Option<(u64, PolicyLevel)>,
+    pub protected_branches: Vec<(String, PolicyLevel)>,
+    /// Sensitive paths from all levels
+    pub sensitive_paths: SensitivePathConfig,
+    sources: Vec<(PolicyLevel, PathBuf)>,
+    compiled: Vec<CompiledRule>,
+}
+
+impl EffectivePolicy {
+    /// Read and merge the policy files that exist
+    pub fn load(sources: &PolicySources) -> SafetyResult<Self> {
+        let mut files = Vec::new();
+        for (level, path) in sources.files() {
+            let content = match std::fs::read_to_string(&path) {
+                Ok(content) => content,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => {
+                    return Err(SafetyError::ConfigError {
+                        field: path.display().to_string(),
+                        message: e.to_string(),
+                    })
+                }
+            };
+            let file = PolicyFile::parse(&content, &path)?;
+            files.push((level, path, file));
+        }
+        Ok(Self::merge(files))
+    }
+
+    /// Merge parsed files given in increasing precedence
+    pub fn merge(files: Vec<(PolicyLevel, PathBuf, PolicyFile)>) -> Self {
+        let mut rules: Vec<EffectiveRule> = Vec::new();
+        let mut max_blast_radius = None;
+        let mut protected_branches: Vec<(String, PolicyLevel)> = Vec::new();
+        let mut sensitive_paths = SensitivePathConfig::default();
+        let mut sources = Vec::new();
+
+        for (level, source, file) in files {
+            sources.push((level, source.clone()));
+            for id in &file.disable {
+                match rules.iter().position(|existing| &existing.rule.id == id) {
+                    Some(index) if rules[index].rule.locked => tracing::warn!(
+                        rule = %id,
+                        level = %level,
+                        "Ignoring attempt to disable a locked safety rule"
+                    ),
+                    Some(index) => {
+                        rules.remove(index);
+                    }
+                    None => {}
+                }
+            }
+
+            for rule in file.rules {
+                let effective = EffectiveRule {
+                    rule,
+                    level,
+                    source: source.clone(),
+                };
+                match rules
+                    .iter()
+                    .position(|existing| existing.rule.id == effective.rule.id)
+                {
+                    Some(index) if rules[index].rule.locked => tracing::warn!(
+                        rule = %effective.rule.id,
+                        level = %level,
+                        "Ignoring override of a locked safety rule"
+                    ),
+                    Some(index) => rules[index] = effective,
+                    None => rules.push(effective),
+                }
+            }
+
+            if let Some(max) = file.max_blast_radius {
+                max_blast_radius = Some((max, level));
+            }
+            for branch in file.protected_branches {
+                if !protected_branches
+                    .iter()
+                    .any(|(existing, _)| existing == &branch)
+                {
+                    protected_branches.push((branch, level));
+                }
+            }
+
+            if file.sensitive_paths.use_defaults.is_some() {
+                sensitive_paths.use_defaults = file.sensitive_paths.use_defaults;
+            }
+            for (merged, patterns) in [
+                (&mut sensitive_paths.deny, file.sensitive_paths.deny),
+                (&mut sensitive_paths.allow, file.sensitive_paths.allow),
+            ] {
+                for pattern in patterns {
+                    if !merged.contains(&pattern) {
+                        merged.push(pattern);
+                    }
+                }
+            }
+        }
+
+        rules.sort_by(|a, b| b.level.cmp(&a.level));
+        let mut policy = Self {
+            rules,
+            max_blast_radius,
+            protected_branches,
+            sensitive_paths,
+            sources,
+            compiled: Vec::new(),
+        };
+        policy.compiled = policy.compile();
+        policy
+    }
+
+    fn compile(&self) -> Vec<CompiledRule> {
+        let mut compiled = Vec::new();
+
+        for effective in &self.rules {
+            let rule = &effective.rule;
+            let mut push = |kind: &str, constraint_type: ConstraintType| {
+                let mut constraint = SecurityConstraint::new(
+                    format!("{}{}:{}", POLICY_CONSTRAINT_PREFIX, rule.id, kind),
+                    rule.id.clone(),
+                    constraint_type,
+                )
+                .with_description(rule.description.clone())
+                .with_severity(rule.severity);
+                if rule.effect == RuleEffect::RequireApproval {
+                    constraint = constraint.with_config(
+                        EFFECT_CONFIG_KEY.to_string(),
+                        EFFECT_REQUIRE_APPROVAL.into(),
+                    );
+                }
+                compiled.push(CompiledRule {
+                    constraint,
+                    rule_id: rule.id.clone(),
+                    level: effective.level,
+                    source: effective.source.clone(),
+                });
+            };
+
+            if !rule.paths.is_empty() {
+                push(
+                    "paths",
+                    ConstraintType::PathGlobs {
+                        patterns: rule.paths.iter().map(|p| anchor_glob(p)).collect(),
+                        except: rule.except_paths.iter().map(|p| anchor_glob(p)).collect(),
+                    },
+                );
+            }
+            if !rule.commands.is_empty() {
+                push(
+                    "commands",
+                    ConstraintType::CommandPatterns {
+                        patterns: rule.commands.clone(),
+                        except: rule.except_commands.clone(),
+                    },
+                );
+            }
+        }
+
+        if let Some((max, level)) = self.max_blast_radius {
+            compiled.push(self.builtin(
+                "max_blast_radius",
+                ConstraintType::MaxBlastRadius(max),
+                level,
+            ));
+        }
+
+        for level in [
+            PolicyLevel::Project,
+            PolicyLevel::Team,
+            PolicyLevel::Organization,
+        ] {
+            let branches: Vec<String> = self
+                .protected_branches
+                .iter()
+                .filter(|(_, l)| *l == level)
+                .map(|(branch, _)| branch.clone())
+                .collect();
+            if !branches.is_empty() {
+                compiled.push(self.builtin(
+                    &format!("protected_branches:{}", level),
+                    ConstraintType::ProtectedBranches(branches),
+                    level,
+                ));
+            }
+        }
+
+        compiled
+    }
+
+    fn builtin(
+        &self,
+        name: &str,
+        constraint_type: ConstraintType,
+        level: PolicyLevel,
+    ) -> CompiledRule {
+        let rule_id = name.split(':').next().unwrap_or(name).to_string();
+        CompiledRule {
+            constraint: SecurityConstraint::new(
+                format!("{}{}", POLICY_CONSTRAINT_PREFIX, name),
+                rule_id.clone(),
+                constraint_type,
+            )
+            .with_severity(ConstraintSeverity::High),
+            rule_id,
+            level,
+            source: self
+                .sources
+                .iter()
+                .find(|(l, _)| *l == level)
+                .map_or_else(|| PathBuf::from(POLICY_FILE), |(_, path)| path.clone()),
+        }
+    }
+
+    /// Constraints compiled from the policy
+    pub fn constraints(&self) -> Vec<SecurityConstraint> {
+        self.compiled.iter().map(|c| c.constraint.clone()).collect()
+    }
+
+    /// Explain how the policy treats an operation
+    ///
+    /// Lists every rule that matched or was exempted, most specific level
+    /// first, and the rule that decided the outcome.
+    pub fn explain(&self, operation: &ValidationContext) -> SafetyResult<PolicyExplanation> {
+        let mut hits = Vec::new();
+        for compiled in &self.compiled {
+            let outcome = match compiled.constraint.match_rule(operation)? {
+                RuleMatch::NotApplicable => continue,
+                RuleMatch::Matched(reason) => {
+                    let approval = compiled
+                        .constraint
+                        .config
+                        .get(EFFECT_CONFIG_KEY)
+                        .and_then(|v| v.as_str())
+                        == Some(EFFECT_REQUIRE_APPROVAL);
+                    if approval {
+                        (PolicyDecision::RequiresApproval, reason)
+                    } else {
+                        (PolicyDecision::Denied, reason)
+                    }
+                }
+                RuleMatch::Exempted(reason) => (PolicyDecision::Allowed, reason),
+            };
+            hits.push(RuleHit {
+                rule_id: compiled.rule_id.clone(),
+                level: compiled.level,
+                source: compiled.source.clone(),
+                outcome: outcome.0,
+                reason: outcome.1,
+            });
+        }
+
+        let decisive = hits
+            .iter()
+            .filter(|hit| hit.outcome == PolicyDecision::Denied)
+            .chain(
+                hits.iter()
+                    .filter(|hit| hit.outcome == PolicyDecision::RequiresApproval),
+            )
+            .chain(
+                hits.iter()
+                    .filter(|hit| hit.outcome == PolicyDecision::Allowed),
+            )
+            .next()
+            .cloned();
+
+        Ok(PolicyExplanation {
+            decision: decisive
+                .as_ref()
+                .map_or(PolicyDecision::Allowed, |hit| hit.outcome),
+            decided_by: decisive,
+            hits,
+        })
+    }
+}
+
+/// Anchor relative globs so they match anywhere in a path
+pub(crate) fn anchor_glob(pattern: &str) -> String {
+    if pattern.starts_with('/') || pattern.starts_with("**") || Path::new(pattern).is_absolute() {
+        pattern.to_string()
+    } else {
+        format!("**/{}", pattern.trim_start_matches("./"))
+    }
+}
+
+/// Outcome of evaluating an operation against the policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PolicyDecision {
+    Allowed,
+    RequiresApproval,
+    Denied,
+}
+
+/// A rule that matched an operation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleHit {
+    pub rule_id: String,
+    pub level: PolicyLevel,
+    pub source: PathBuf,
+    /// `Allowed` means the rule matched but an exception applied
+    pub outcome: PolicyDecision,
+    pub reason: String,
+}
+
+/// Why the policy allowed or denied an operation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolicyExplanation {
+    pub decision: PolicyDecision,
+    /// Rule that decided the outcome; `None` when no rule applied
+    pub decided_by: Option<RuleHit>,
+    /// Every rule that matched, most specific level first
+    pub hits: Vec<RuleHit>,
+}
+
+impl fmt::Display for PolicyExplanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.decided_by {
+            None => write!(f, "Allowed: no safety rule applies"),
+            Some(hit) => {
+                let verdict = match self.decision {
+                    PolicyDecision::Allowed => "Allowed by exception in",
+                    PolicyDecision::RequiresApproval => "Approval required by",
+                    PolicyDecision::Denied => "Denied by",
+                };
+                write!(
+                    f,
+                    "{} rule '{}' ({} policy, {}): {}",
+                    verdict,
+                    hit.rule_id,
+                    hit.level,
+                    hit.source.display(),
+                    hit.reason
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(content: &str, level: PolicyLevel) -> (PolicyLevel, PathBuf, PolicyFile) {
+        let path = PathBuf::from(format!("/{}/{}", level, POLICY_FILE));
+        (
+            level,
+            path.clone(),
+            PolicyFile::parse(content, &path).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_merge_precedence_and_locked_rules() {
+        let org = parse(
+            r#"
+rules:
+  - id: env-files
+    paths: [".env*"]
+    except_paths: [".env.example"]
+    locked: true
+  - id: no-force-push
+    commands: ["git push*--force*"]
+max_blast_radius: 500
+protected_branches: [main]
+sensitive_paths:
+  deny: ["secrets/**"]
+"#,
+            PolicyLevel::Organization,
+        );
+        let team = parse(
+            r#"
+rules:
+  - id: no-force-push
+    effect: require_approval
+    commands: ["git push*--force*"]
+protected_branches: ["release/*"]
+"#,
+            PolicyLevel::Team,
+        );
+        let project = parse(
+            r#"
+rules:
+  - id: env-files
+    paths: ["never-matches"]
+  - id: vendored
+    severity: medium
+    paths: ["vendor/**"]
+disable: [env-files]
+max_blast_radius: 50
+sensitive_paths:
+  use_defaults: false
+  deny: ["secrets/**", "*.key"]
+"#,
+            PolicyLevel::Project,
+        );
+
+        let policy = EffectivePolicy::merge(vec![org, team, project]);
+
+        // Locked org rule survives both override and disable
+        let env = policy
+            .rules
+            .iter()
+            .find(|r| r.rule.id == "env-files")
+            .unwrap();
+        assert_eq!(env.level, PolicyLevel::Organization);
+        assert_eq!(env.rule.paths, vec![".env*".to_string()]);
+
+        let push = policy
+            .rules
+            .iter()
+            .find(|r| r.rule.id == "no-force-push")
+            .unwrap();
+        assert_eq!(push.level, PolicyLevel::Team);
+        assert_eq!(push.rule.effect, RuleEffect::RequireApproval);
+
+        assert_eq!(policy.rules[0].level, PolicyLevel::Project);
+        assert_eq!(policy.max_blast_radius, Some((50, PolicyLevel::Project)));
+        assert_eq!(policy.protected_branches.len(), 2);
+        assert_eq!(policy.sensitive_paths.use_defaults, Some(false));
+        assert_eq!(
+            policy.sensitive_paths.deny,
+            vec!["secrets/**".to_string(), "*.key".to_string()]
+        );
+        assert!(policy
+            .constraints()
+            .iter()
+            .all(|c| c.id.starts_with(POLICY_CONSTRAINT_PREFIX)));
+    }
+
+    #[test]
+    fn test_explain_reports_deciding_rule() {
+        let policy = EffectivePolicy::merge(vec![parse(
+            r#"
+rules:
+  - id: env-files
+    paths: [".env*"]
+    except_paths: ["**/.env.example"]
+  - id: pipe-to-shell
+    effect: require_approval
+    commands: ["re:curl .*\\|\\s*sh"]
+max_blast_radius: 10
+protected_branches: ["release/*"]
+"#,
+            PolicyLevel::Team,
+        )]);
+
+        let denied = policy
+            .explain(&ValidationContext::new().with_file_path("/repo/app/.env.local".to_string()))
+            .unwrap();
+        assert_eq!(denied.decision, PolicyDecision::Denied);
+        assert_eq!(denied.decided_by.as_ref().unwrap().rule_id, "env-files");
+        assert!(denied
+            .to_string()
+            .starts_with("Denied by rule 'env-files' (team policy"));
+
+        let exempt = policy
+            .explain(&ValidationContext::new().with_file_path("/repo/.env.example".to_string()))
+            .unwrap();
+        assert_eq!(exempt.decision, PolicyDecision::Allowed);
+        assert!(exempt.to_string().starts_with("Allowed by exception"));
+
+        let approval = policy
+            .explain(
+                &ValidationContext::new()
+                    .with_command("curl https://x.sh | sh".to_string())
+                    .with_affected_files(3),
+            )
+            .unwrap();
+        assert_eq!(approval.decision, PolicyDecision::RequiresApproval);
+
+        let blast = policy
+            .explain(
+                &ValidationContext::new()
+                    .with_affected_files(11)
+                    .with_target_branch("release/1.2".to_string()),
+            )
+            .unwrap();
+        assert_eq!(blast.decision, PolicyDecision::Denied);
+        assert_eq!(blast.hits.len(), 2);
+
+        let clean = policy
+            .explain(&ValidationContext::new().with_file_path("src/main.rs".to_string()))
+            .unwrap();
+        assert_eq!(clean.decision, PolicyDecision::Allowed);
+        assert!(clean.decided_by.is_none());
+    }
+
+    #[test]
+    fn test_invalid_policy_files_are_rejected() {
+        let path = PathBuf::from(POLICY_FILE);
+        assert!(PolicyFile::parse("version: 2", &path).is_err());
+        assert!(PolicyFile::parse("rules:\n  - id: empty\n", &path).is_err());
+        assert!(
+            PolicyFile::parse("rules:\n  - id: bad\n    commands: [\"re:(\"]\n", &path).is_err()
+        );
+        assert!(PolicyFile::parse("unknown_key: 1", &path).is_err());
+        assert_eq!(PolicyFile::parse("", &path).unwrap(), PolicyFile::default());
+    }
+}
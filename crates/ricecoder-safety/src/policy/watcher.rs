@@ -0,0 +1,261 @@
+//! Hot reloading of policy files
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::sync::{broadcast, mpsc, RwLock};
+use tokio::task::JoinHandle;
+
+use super::{EffectivePolicy, PolicyExplanation, PolicySources, POLICY_CONSTRAINT_PREFIX};
+use crate::{
+    constraints::ValidationContext,
+    error::{SafetyError, SafetyResult},
+    sensitive_paths::{self, SensitivePathPolicy},
+    validation::SafetyValidator,
+};
+
+/// Policy reload notification
+#[derive(Debug, Clone)]
+pub enum PolicyEvent {
+    /// The policy was reloaded
+    Reloaded { rules: usize, constraints: usize },
+    /// Reloading failed; the previous policy stays in effect
+    ReloadFailed { error: String },
+}
+
+/// Owns the effective policy and keeps it in sync with policy files
+///
+/// When a validator is attached, its policy constraints are replaced on
+/// every successful reload. The process-wide sensitive path policy used by
+/// the file tools is replaced as well.
+pub struct PolicyManager {
+    sources: PolicySources,
+    current: RwLock<Arc<EffectivePolicy>>,
+    validator: Option<Arc<SafetyValidator>>,
+    events: broadcast::Sender<PolicyEvent>,
+    debounce: Duration,
+}
+
+impl PolicyManager {
+    /// Load the policy, optionally installing it into a validator
+    pub async fn load(
+        sources: PolicySources,
+        validator: Option<Arc<SafetyValidator>>,
+    ) -> SafetyResult<Arc<Self>> {
+        let (policy, paths) = load_policy(&sources)?;
+        sensitive_paths::set_global(paths);
+        if let Some(validator) = &validator {
+            validator
+                .replace_constraints(POLICY_CONSTRAINT_PREFIX, policy.constraints())
+                .await;
+        }
+
+        let (events, _) = broadcast::channel(16);
+        Ok(Arc::new(Self {
+            sources,
+            current: RwLock::new(policy),
+            validator,
+            events,
+            debounce: Duration::from_millis(250),
+        }))
+    }
+
+    /// Current effective policy
+    pub async fn policy(&self) -> Arc<EffectivePolicy> {
+        Arc::clone(&*self.current.read().await)
+    }
+
+    /// Explain how the current policy treats an operation
+    pub async fn explain(&self, operation: &ValidationContext) -> SafetyResult<PolicyExplanation> {
+        self.policy().await.explain(operation)
+    }
+
+    /// Subscribe to reload notifications
+    pub fn subscribe(&self) -> broadcast::Receiver<PolicyEvent> {
+        self.events.subscribe()
+    }
+
+    /// Re-read the policy files
+    ///
+    /// On failure the previous policy stays in effect.
+    pub async fn reload(&self) -> SafetyResult<Arc<EffectivePolicy>> {
+        let (policy, paths) = match load_policy(&self.sources) {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                tracing::warn!("Safety policy reload failed: {}", e);
+                let _ = self.events.send(PolicyEvent::ReloadFailed {
+                    error: e.to_string(),
+                });
+                return Err(e);
+            }
+        };
+
+        let constraints = policy.constraints();
+        let count = constraints.len();
+        if let Some(validator) = &self.validator {
+            validator
+                .replace_constraints(POLICY_CONSTRAINT_PREFIX, constraints)
+                .await;
+        }
+        *self.current.write().await = Arc::clone(&policy);
+        sensitive_paths::set_global(paths);
+
+        tracing::info!(rules = policy.rules.len(), "Safety policy reloaded");
+        let _ = self.events.send(PolicyEvent::Reloaded {
+            rules: policy.rules.len(),
+            constraints: count,
+        });
+        Ok(policy)
+    }
+
+    /// Reload whenever a policy file changes
+    ///
+    /// Watches each level's `.ricecoder` directory, or the level root when
+    /// that directory doesn't exist yet. Watching stops when the returned
+    /// handle is dropped.
+    pub fn watch(self: &Arc<Self>) -> SafetyResult<PolicyWatchHandle> {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut watcher = RecommendedWatcher::new(
+            move |result: notify::Result<notify::Event>| match result {
+                Ok(event) if event.paths.iter().any(|path| is_policy_path(path)) => {
+                    let _ = tx.send(());
+                }
+                Ok(_) => {}
+                Err(e) => tracing::error!("Safety policy watch error: {}", e),
+            },
+            notify::Config::default(),
+        )
+        .map_err(watch_error)?;
+
+        let mut watched = Vec::new();
+        for (_, file) in self.sources.files() {
+            let Some(dir) = file.parent() else { continue };
+            let target = if dir.is_dir() {
+                dir.to_path_buf()
+            } else {
+                match dir.parent().filter(|root| root.is_dir()) {
+                    Some(root) => root.to_path_buf(),
+                    None => continue,
+                }
+            };
+            watcher
+                .watch(&target, RecursiveMode::NonRecursive)
+                .map_err(watch_error)?;
+            watched.push(target);
+        }
+
+        let manager = Arc::clone(self);
+        let task = tokio::spawn(async move {
+            while rx.recv().await.is_some() {
+                // Editors write in bursts; reload once things settle
+                tokio::time::sleep(manager.debounce).await;
+                while rx.try_recv().is_ok() {}
+                let _ = manager.reload().await;
+            }
+        });
+
+        Ok(PolicyWatchHandle {
+            _watcher: watcher,
+            watched,
+            task,
+        })
+    }
+}
+
+fn load_policy(
+    sources: &PolicySources,
+) -> SafetyResult<(Arc<EffectivePolicy>, Arc<SensitivePathPolicy>)> {
+    let policy = EffectivePolicy::load(sources)?;
+    let paths = SensitivePathPolicy::new(&policy.sensitive_paths)?;
+    Ok((Arc::new(policy), Arc::new(paths)))
+}
+
+fn is_policy_path(path: &Path) -> bool {
+    matches!(
+        path.file_name().and_then(|name| name.to_str()),
+        Some("safety.yaml") | Some(".ricecoder")
+    )
+}
+
+fn watch_error(error: notify::Error) -> SafetyError {
+    SafetyError::ConfigError {
+        field: "policy.watch".to_string(),
+        message: error.to_string(),
+    }
+}
+
+/// Keeps a policy watch running
+pub struct PolicyWatchHandle {
+    _watcher: RecommendedWatcher,
+    watched: Vec<PathBuf>,
+    task: JoinHandle<()>,
+}
+
+impl PolicyWatchHandle {
+    /// Directories being watched
+    pub fn watched(&self) -> &[PathBuf] {
+        &self.watched
+    }
+}
+
+impl Drop for PolicyWatchHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy::{PolicyDecision, POLICY_FILE};
+    use crate::sensitive_paths::PathAccess;
+
+    #[tokio::test]
+    async fn test_hot_reload_updates_validator() {
+        let project = tempfile::tempdir().unwrap();
+        let file = project.path().join(POLICY_FILE);
+        std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+        std::fs::write(&file, "rules:\n  - id: logs\n    paths: [\"*.log\"]\n").unwrap();
+
+        let validator = Arc::new(SafetyValidator::new());
+        let manager = PolicyManager::load(
+            PolicySources::default().with_project(project.path()),
+            Some(Arc::clone(&validator)),
+        )
+        .await
+        .unwrap();
+        assert_eq!(validator.get_constraints().await.len(), 1);
+        let secret = project.path().join("vault").join("token.txt");
+        assert!(!sensitive_paths::global().is_denied(&secret, PathAccess::Read));
+
+        let mut events = manager.subscribe();
+        let _handle = manager.watch().unwrap();
+        std::fs::write(
+            &file,
+            "rules:\n  - id: logs\n    paths: [\"*.log\"]\n  - id: rm\n    commands: [\"rm -rf *\"]\n\
+             sensitive_paths:\n  deny: [\"vault\"]\n",
+        )
+        .unwrap();
+
+        let event = tokio::time::timeout(Duration::from_secs(5), events.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(event, PolicyEvent::Reloaded { rules: 2, .. }));
+        assert_eq!(validator.get_constraints().await.len(), 2);
+        assert!(sensitive_paths::global().is_denied(&secret, PathAccess::Read));
+
+        let explanation = manager
+            .explain(&ValidationContext::new().with_command("rm -rf /".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(explanation.decision, PolicyDecision::Denied);
+
+        // A broken edit keeps the last good policy
+        std::fs::write(&file, "rules: [").unwrap();
+        assert!(manager.reload().await.is_err());
+        assert_eq!(manager.policy().await.rules.len(), 2);
+    }
+}
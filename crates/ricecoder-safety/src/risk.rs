@@ -289,6 +289,23 @@ impl RiskScorer {
             ],
         });
 
+        self.add_rule(RiskRule {
+            name: crate::blast_radius::BLAST_RADIUS_KEY.to_string(),
+            category: RiskCategory::Operation,
+            condition: Box::new(|ctx| {
+                ctx.additional_data
+                    .contains_key(crate::blast_radius::BLAST_RADIUS_KEY)
+            }),
+            score_calculator: Box::new(|ctx| {
+                crate::blast_radius::BlastRadius::from_context(&ctx.additional_data)
+                    .map_or(0, |radius| radius.risk_points())
+            }),
+            recommendations: vec![
+                "Wide blast radius - review affected and dependent projects".to_string(),
+                "Take a backup before irreversible changes".to_string(),
+            ],
+        });
+
         // Environment-based rules
         self.add_rule(RiskRule {
             name: "unusual_time".to_string(),
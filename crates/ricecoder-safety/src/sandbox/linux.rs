@@ -0,0 +1,393 @@
+//! Linux namespace sandbox
+//!
+//! Uses bubblewrap when installed: a read-only view of the host with the
+//! policy's writable paths bound in, denied paths masked, fresh pid/ipc/uts
+//! namespaces and optionally no network. Without bubblewrap the command still
+//! gets a seccomp filter and, when network is denied, an empty network
+//! namespace, but the filesystem is not isolated.
+
+use std::path::{Path, PathBuf};
+
+use tokio::process::Command;
+
+use super::{
+    NetworkPolicy, PreparedCommand, SandboxBackend, SandboxEnforcement, SandboxPolicy,
+    SandboxRequest,
+};
+use crate::error::SafetyResult;
+
+/// Namespace and seccomp backend for Linux
+pub struct LinuxSandbox {
+    bwrap: Option<PathBuf>,
+}
+
+impl LinuxSandbox {
+    /// Detect bubblewrap on `PATH`
+    pub fn new() -> Self {
+        Self {
+            bwrap: which::which("bwrap").ok(),
+        }
+    }
+
+    /// Use a specific bubblewrap binary, or none
+    pub fn with_bwrap(bwrap: Option<PathBuf>) -> Self {
+        Self { bwrap }
+    }
+
+    /// Whether bubblewrap will be used
+    pub fn uses_bwrap(&self) -> bool {
+        self.bwrap.is_some()
+    }
+
+    /// Arguments passed to bubblewrap for a request
+    pub fn bwrap_args(
+        &self,
+        request: &SandboxRequest,
+        policy: &SandboxPolicy,
+        seccomp_fd: Option<i32>,
+    ) -> Vec<String> {
+        let mut args: Vec<String> = [
+            "--die-with-parent",
+            "--new-session",
+            "--unshare-user",
+            "--unshare-pid",
+            "--unshare-ipc",
+            "--unshare-uts",
+        ]
+        .iter()
+        .map(|arg| arg.to_string())
+        .collect();
+
+        if policy.network == NetworkPolicy::Deny {
+            args.push("--unshare-net".to_string());
+        }
+
+        args.extend(
+            ["--ro-bind", "/", "/", "--dev", "/dev", "--proc", "/proc"]
+                .iter()
+                .map(|arg| arg.to_string()),
+        );
+
+        // Later mounts win, so denied paths inside writable ones stay hidden
+        for path in policy.writable_paths.iter().filter(|path| path.exists()) {
+            let path = display(path);
+            args.extend(["--bind".to_string(), path.clone(), path]);
+        }
+        for path in &policy.denied_paths {
+            if path.is_dir() {
+                args.extend(["--tmpfs".to_string(), display(path)]);
+            } else if path.exists() {
+                args.extend([
+                    "--ro-bind".to_string(),
+                    "/dev/null".to_string(),
+                    display(path),
+                ]);
+            }
+        }
+
+        if let Some(workdir) = &request.workdir {
+            args.extend(["--chdir".to_string(), display(workdir)]);
+        }
+        if let Some(fd) = seccomp_fd {
+            args.extend(["--seccomp".to_string(), fd.to_string()]);
+        }
+
+        args.push("--".to_string());
+        args.push(request.program.clone());
+        args.extend(request.args.iter().cloned());
+        args
+    }
+}
+
+impl Default for LinuxSandbox {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn display(path: &Path) -> String {
+    path.to_string_lossy().into_owned()
+}
+
+impl SandboxBackend for LinuxSandbox {
+    fn name(&self) -> &'static str {
+        if self.bwrap.is_some() {
+            "linux-bwrap"
+        } else {
+            "linux-seccomp"
+        }
+    }
+
+    fn is_available(&self) -> bool {
+        cfg!(target_os = "linux")
+    }
+
+    #[cfg(target_os = "linux")]
+    fn prepare(
+        &self,
+        request: &SandboxRequest,
+        policy: &SandboxPolicy,
+    ) -> SafetyResult<PreparedCommand> {
+        platform::prepare(self, request, policy)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn prepare(
+        &self,
+        request: &SandboxRequest,
+        _policy: &SandboxPolicy,
+    ) -> SafetyResult<PreparedCommand> {
+        let mut enforcement = SandboxEnforcement::new(self.name());
+        enforcement.gap("Linux namespaces are not available on this platform");
+
+        let mut command = Command::new(&request.program);
+        command
+            .args(&request.args)
+            .envs(request.env.iter().cloned());
+        if let Some(workdir) = &request.workdir {
+            command.current_dir(workdir);
+        }
+        Ok(PreparedCommand {
+            command,
+            enforcement,
+            resource: None,
+        })
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use std::ffi::CString;
+    use std::fs::File;
+    use std::io::{self, Seek, SeekFrom, Write};
+    use std::os::fd::{AsRawFd, FromRawFd};
+
+    use super::super::seccomp;
+    use super::*;
+    use crate::error::SafetyError;
+
+    pub(super) fn prepare(
+        sandbox: &LinuxSandbox,
+        request: &SandboxRequest,
+        policy: &SandboxPolicy,
+    ) -> SafetyResult<PreparedCommand> {
+        let mut enforcement = SandboxEnforcement::new(sandbox.name());
+        let program = seccomp::filter();
+        enforcement.syscalls = seccomp::is_supported();
+
+        let (mut command, resource) = match &sandbox.bwrap {
+            Some(bwrap) => {
+                let seccomp_file = if enforcement.syscalls {
+                    Some(program_file(&program)?)
+                } else {
+                    None
+                };
+                let fd = seccomp_file.as_ref().map(|file| file.as_raw_fd());
+
+                let mut command = Command::new(bwrap);
+                command.args(sandbox.bwrap_args(request, policy, fd));
+                if let Some(fd) = fd {
+                    // bwrap reads the program from an inherited descriptor
+                    // SAFETY: fcntl is async-signal-safe
+                    unsafe {
+                        command.pre_exec(move || {
+                            if libc::fcntl(fd, libc::F_SETFD, 0) != 0 {
+                                return Err(io::Error::last_os_error());
+                            }
+                            Ok(())
+                        });
+                    }
+                }
+
+                enforcement.filesystem = true;
+                enforcement.network = true;
+                let resource = seccomp_file.map(|file| Box::new(file) as Box<_>);
+                (command, resource)
+            }
+            None => {
+                let mut command = Command::new(&request.program);
+                command.args(&request.args);
+
+                let isolate_network = policy.network == NetworkPolicy::Deny;
+                let maps = IdMaps::current();
+                let strict = policy.strict;
+                let filter = enforcement.syscalls.then(|| program.clone());
+                // SAFETY: only async-signal-safe calls are made in the hook
+                unsafe {
+                    command.pre_exec(move || {
+                        if isolate_network {
+                            if let Err(e) = maps.unshare_network() {
+                                if strict {
+                                    return Err(e);
+                                }
+                            }
+                        }
+                        if let Some(filter) = &filter {
+                            seccomp::install(filter)?;
+                        }
+                        Ok(())
+                    });
+                }
+
+                enforcement.network = true;
+                if !policy.writable_paths.is_empty() || !policy.denied_paths.is_empty() {
+                    enforcement
+                        .gap("bubblewrap is not installed; filesystem access is not restricted");
+                }
+                (command, None)
+            }
+        };
+
+        command.envs(request.env.iter().cloned());
+        if let Some(workdir) = &request.workdir {
+            command.current_dir(workdir);
+        }
+
+        if let Some(limit) = policy.max_memory_bytes {
+            // SAFETY: setrlimit is async-signal-safe
+            unsafe {
+                command.pre_exec(move || {
+                    let rlimit = libc::rlimit {
+                        rlim_cur: limit as libc::rlim_t,
+                        rlim_max: limit as libc::rlim_t,
+                    };
+                    if libc::setrlimit(libc::RLIMIT_AS, &rlimit) != 0 {
+                        return Err(io::Error::last_os_error());
+                    }
+                    Ok(())
+                });
+            }
+            enforcement.memory = true;
+        }
+
+        Ok(PreparedCommand {
+            command,
+            enforcement,
+            resource,
+        })
+    }
+
+    /// Write the filter to an anonymous in-memory file
+    fn program_file(program: &[libc::sock_filter]) -> SafetyResult<File> {
+        let name = CString::new("ricecoder-seccomp").expect("static name has no NUL");
+        // SAFETY: valid C string; the descriptor is owned by the returned File
+        let fd = unsafe { libc::memfd_create(name.as_ptr(), libc::MFD_CLOEXEC) };
+        if fd < 0 {
+            return Err(sandbox_error(io::Error::last_os_error()));
+        }
+        let mut file = unsafe { File::from_raw_fd(fd) };
+        file.write_all(&seccomp::to_bytes(program))
+            .and_then(|_| file.seek(SeekFrom::Start(0)).map(|_| ()))
+            .map_err(sandbox_error)?;
+        Ok(file)
+    }
+
+    fn sandbox_error(error: io::Error) -> SafetyError {
+        SafetyError::SandboxError {
+            message: format!("Failed to prepare seccomp filter: {}", error),
+        }
+    }
+
+    /// uid/gid maps for an unprivileged user namespace, formatted before fork
+    struct IdMaps {
+        uid_map: CString,
+        gid_map: CString,
+    }
+
+    impl IdMaps {
+        fn current() -> Self {
+            // SAFETY: getuid/getgid cannot fail
+            let (uid, gid) = unsafe { (libc::getuid(), libc::getgid()) };
+            Self {
+                uid_map: CString::new(format!("{uid} {uid} 1")).expect("no NUL"),
+                gid_map: CString::new(format!("{gid} {gid} 1")).expect("no NUL"),
+            }
+        }
+
+        /// Move into new user and network namespaces, keeping the same ids
+        fn unshare_network(&self) -> io::Result<()> {
+            // SAFETY: unshare and raw file writes are async-signal-safe
+            unsafe {
+                if libc::unshare(libc::CLONE_NEWUSER | libc::CLONE_NEWNET) != 0 {
+                    return Err(io::Error::last_os_error());
+                }
+            }
+            write_proc(c"/proc/self/setgroups", c"deny")?;
+            write_proc(c"/proc/self/uid_map", &self.uid_map)?;
+            write_proc(c"/proc/self/gid_map", &self.gid_map)
+        }
+    }
+
+    fn write_proc(path: &std::ffi::CStr, contents: &std::ffi::CStr) -> io::Result<()> {
+        // SAFETY: valid C strings; the descriptor is closed before returning
+        unsafe {
+            let fd = libc::open(path.as_ptr(), libc::O_WRONLY | libc::O_CLOEXEC);
+            if fd < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            let bytes = contents.to_bytes();
+            let written = libc::write(fd, bytes.as_ptr().cast(), bytes.len());
+            libc::close(fd);
+            if written < 0 {
+                return Err(io::Error::last_os_error());
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bwrap_args_follow_policy() {
+        let tmp = tempfile::tempdir().unwrap();
+        let secrets = tmp.path().join("secrets");
+        std::fs::create_dir(&secrets).unwrap();
+        let token = tmp.path().join("token");
+        std::fs::write(&token, "x").unwrap();
+
+        let policy = SandboxPolicy {
+            writable_paths: vec![tmp.path().to_path_buf(), PathBuf::from("/does/not/exist")],
+            denied_paths: vec![secrets.clone(), token.clone()],
+            ..SandboxPolicy::default()
+        };
+        let request = SandboxRequest::shell("make").workdir(tmp.path());
+        let sandbox = LinuxSandbox::with_bwrap(Some(PathBuf::from("/usr/bin/bwrap")));
+        let args = sandbox.bwrap_args(&request, &policy, Some(9));
+        let joined = args.join(" ");
+
+        let root = tmp.path().display().to_string();
+        assert!(joined.contains("--unshare-net"));
+        assert!(joined.contains(&format!("--bind {root} {root}")));
+        assert!(!joined.contains("/does/not/exist"));
+        assert!(joined.contains(&format!("--tmpfs {}", secrets.display())));
+        assert!(joined.contains(&format!("--ro-bind /dev/null {}", token.display())));
+        assert!(joined.contains(&format!("--chdir {root}")));
+        assert!(joined.contains("--seccomp 9"));
+        assert!(joined.ends_with("-- sh -c make"));
+
+        let open = policy.with_network(NetworkPolicy::AllowAll);
+        assert!(!sandbox
+            .bwrap_args(&request, &open, None)
+            .contains(&"--unshare-net".to_string()));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_seccomp_filter_is_applied_without_bwrap() {
+        let sandbox = super::super::Sandbox::with_backend(std::sync::Arc::new(
+            LinuxSandbox::with_bwrap(None),
+        ));
+        let policy = SandboxPolicy::default().best_effort();
+        let request = SandboxRequest::shell("grep '^Seccomp:' /proc/self/status");
+
+        let command = sandbox.command(&request, &policy).unwrap();
+        assert!(command.enforcement().syscalls);
+        let output = command.output().await.unwrap();
+
+        // Mode 2 is SECCOMP_MODE_FILTER
+        assert!(String::from_utf8_lossy(&output.stdout).contains('2'));
+    }
+}
@@ -0,0 +1,187 @@
+//! macOS `sandbox-exec` backend
+//!
+//! Generates a deny-by-default SBPL profile: the whole filesystem is readable
+//! except denied paths, only the policy's writable paths accept writes and
+//! network access is limited to local sockets unless the policy allows it.
+
+use std::path::{Path, PathBuf};
+
+use tokio::process::Command;
+
+use super::{
+    NetworkPolicy, PreparedCommand, SandboxBackend, SandboxEnforcement, SandboxPolicy,
+    SandboxRequest,
+};
+use crate::error::SafetyResult;
+
+const SANDBOX_EXEC: &str = "/usr/bin/sandbox-exec";
+
+/// `sandbox-exec` backend for macOS
+pub struct MacOsSandbox {
+    binary: PathBuf,
+}
+
+impl MacOsSandbox {
+    /// Use the system `sandbox-exec`
+    pub fn new() -> Self {
+        Self {
+            binary: PathBuf::from(SANDBOX_EXEC),
+        }
+    }
+
+    /// Sandbox profile for a policy
+    pub fn profile(&self, policy: &SandboxPolicy) -> String {
+        let mut profile = String::from(
+            "(version 1)\n\
+             (deny default)\n\
+             (allow process-exec)\n\
+             (allow process-fork)\n\
+             (allow signal (target same-sandbox))\n\
+             (allow sysctl-read)\n\
+             (allow mach-lookup)\n\
+             (allow ipc-posix-shm)\n\
+             (allow file-read*)\n",
+        );
+
+        for path in &policy.denied_paths {
+            profile.push_str(&format!("(deny file-read* {})\n", subpath(path)));
+        }
+
+        profile.push_str(
+            "(allow file-write*\n  (literal \"/dev/null\")\n  (literal \"/dev/tty\")\n  \
+             (regex #\"^/dev/fd/\")",
+        );
+        for path in &policy.writable_paths {
+            profile.push_str(&format!("\n  {}", subpath(path)));
+        }
+        profile.push_str(")\n");
+
+        // Denials after the allow take precedence for nested paths
+        for path in &policy.denied_paths {
+            profile.push_str(&format!("(deny file-write* {})\n", subpath(path)));
+        }
+
+        match policy.network {
+            NetworkPolicy::Deny => profile.push_str("(allow network* (local unix))\n"),
+            NetworkPolicy::AllowAll | NetworkPolicy::AllowDomains(_) => {
+                profile.push_str("(allow network*)\n")
+            }
+        }
+
+        profile
+    }
+}
+
+impl Default for MacOsSandbox {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn subpath(path: &Path) -> String {
+    let path = path.to_string_lossy();
+    let escaped = path.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("(subpath \"{}\")", escaped)
+}
+
+impl SandboxBackend for MacOsSandbox {
+    fn name(&self) -> &'static str {
+        "macos-sandbox-exec"
+    }
+
+    fn is_available(&self) -> bool {
+        cfg!(target_os = "macos") && self.binary.exists()
+    }
+
+    fn prepare(
+        &self,
+        request: &SandboxRequest,
+        policy: &SandboxPolicy,
+    ) -> SafetyResult<PreparedCommand> {
+        let mut enforcement = SandboxEnforcement::new(self.name());
+        let mut command = if self.is_available() {
+            let mut command = Command::new(&self.binary);
+            command
+                .arg("-p")
+                .arg(self.profile(policy))
+                .arg("--")
+                .arg(&request.program)
+                .args(&request.args);
+            enforcement.filesystem = true;
+            enforcement.network = true;
+            command
+        } else {
+            enforcement.gap("sandbox-exec is not available");
+            let mut command = Command::new(&request.program);
+            command.args(&request.args);
+            command
+        };
+
+        command.envs(request.env.iter().cloned());
+        if let Some(workdir) = &request.workdir {
+            command.current_dir(workdir);
+        }
+
+        if let Some(limit) = policy.max_memory_bytes {
+            #[cfg(unix)]
+            {
+                // SAFETY: setrlimit is async-signal-safe
+                unsafe {
+                    command.pre_exec(move || {
+                        let rlimit = libc::rlimit {
+                            rlim_cur: limit as libc::rlim_t,
+                            rlim_max: limit as libc::rlim_t,
+                        };
+                        if libc::setrlimit(libc::RLIMIT_AS, &rlimit) != 0 {
+                            return Err(std::io::Error::last_os_error());
+                        }
+                        Ok(())
+                    });
+                }
+                enforcement.memory = true;
+            }
+            #[cfg(not(unix))]
+            {
+                let _ = limit;
+                enforcement.gap("memory limits are not supported by this backend");
+            }
+        }
+
+        Ok(PreparedCommand {
+            command,
+            enforcement,
+            resource: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_restricts_writes_and_network() {
+        let policy = SandboxPolicy {
+            writable_paths: vec![PathBuf::from("/Users/dev/project")],
+            denied_paths: vec![PathBuf::from("/Users/dev/.ssh")],
+            ..SandboxPolicy::default()
+        };
+
+        let profile = MacOsSandbox::new().profile(&policy);
+
+        assert!(profile.starts_with("(version 1)\n(deny default)\n"));
+        assert!(profile.contains("(subpath \"/Users/dev/project\")"));
+        assert!(profile.contains("(deny file-read* (subpath \"/Users/dev/.ssh\"))"));
+        assert!(profile.contains("(allow network* (local unix))"));
+
+        let open = MacOsSandbox::new().profile(&policy.with_network(NetworkPolicy::AllowAll));
+        assert!(open.contains("(allow network*)\n"));
+    }
+
+    #[test]
+    fn test_profile_escapes_paths() {
+        let policy = SandboxPolicy::default().allow_write("/tmp/we\"ird");
+        let profile = MacOsSandbox::new().profile(&policy);
+        assert!(profile.contains("(subpath \"/tmp/we\\\"ird\")"));
+    }
+}
@@ -0,0 +1,555 @@
+//! OS-level sandboxing for approved operations
+//!
+//! Commands that passed validation can still misbehave, so the shell tool and
+//! execution steps run them inside platform isolation:
+//!
+//! - **Linux**: user/mount/pid/net namespaces via bubblewrap, plus a seccomp
+//!   filter and resource limits
+//! - **macOS**: `sandbox-exec` with a generated deny-by-default profile
+//! - **Windows**: job objects with memory and kill-on-close limits
+//!
+//! A [`SandboxPolicy`] declares writable paths, hidden paths and network
+//! access, and is usually derived from the active [`SecurityConstraint`]s.
+//! Backends report what they actually enforced in a [`SandboxEnforcement`];
+//! strict policies refuse to run when any part of the policy can't be
+//! enforced on the current platform.
+//!
+//! ```rust,no_run
+//! use ricecoder_safety::sandbox::{Sandbox, SandboxPolicy, SandboxRequest};
+//!
+//! # async fn example() -> ricecoder_safety::SafetyResult<()> {
+//! let workdir = std::path::PathBuf::from("/work/project");
+//! let policy = SandboxPolicy::from_constraints(&[], &workdir);
+//! let request = SandboxRequest::shell("cargo test").workdir(&workdir);
+//!
+//! let output = Sandbox::detect().command(&request, &policy)?.output().await?;
+//! println!("exit: {:?}", output.status.code());
+//! # Ok(())
+//! # }
+//! ```
+
+mod linux;
+mod macos;
+#[cfg(target_os = "linux")]
+mod seccomp;
+mod windows;
+
+use std::any::Any;
+use std::path::{Path, PathBuf};
+use std::process::Output;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::process::{Child, Command};
+
+use crate::{
+    constraints::{ConstraintType, SecurityConstraint},
+    error::{SafetyError, SafetyResult},
+};
+
+pub use linux::LinuxSandbox;
+pub use macos::MacOsSandbox;
+pub use windows::WindowsJobSandbox;
+
+/// Network access allowed inside the sandbox
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NetworkPolicy {
+    /// No network access
+    Deny,
+    /// Unrestricted network access
+    AllowAll,
+    /// Access limited to these domains
+    ///
+    /// OS sandboxes can't filter by domain, so this is enforced as
+    /// [`NetworkPolicy::AllowAll`] at the OS level and relies on the
+    /// `AllowedDomains` constraint for pre-execution checks.
+    AllowDomains(Vec<String>),
+}
+
+/// Declarative isolation policy for sandboxed commands
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SandboxPolicy {
+    /// Paths the command may write to; everything else is read-only
+    pub writable_paths: Vec<PathBuf>,
+    /// Paths hidden from the command entirely
+    pub denied_paths: Vec<PathBuf>,
+    /// Network access
+    pub network: NetworkPolicy,
+    /// Maximum run time
+    pub max_execution_time: Option<Duration>,
+    /// Maximum address space in bytes
+    pub max_memory_bytes: Option<u64>,
+    /// Refuse to run when part of the policy can't be enforced
+    pub strict: bool,
+}
+
+impl Default for SandboxPolicy {
+    fn default() -> Self {
+        Self {
+            writable_paths: Vec::new(),
+            denied_paths: Vec::new(),
+            network: NetworkPolicy::Deny,
+            max_execution_time: None,
+            max_memory_bytes: None,
+            strict: true,
+        }
+    }
+}
+
+impl SandboxPolicy {
+    /// Derive a policy from security constraints
+    ///
+    /// The working directory and the system temp directory are writable.
+    /// Network access is denied unless an `AllowedDomains` constraint lists
+    /// domains.
+    pub fn from_constraints(constraints: &[SecurityConstraint], workdir: &Path) -> Self {
+        let mut policy = Self {
+            writable_paths: vec![workdir.to_path_buf(), std::env::temp_dir()],
+            ..Self::default()
+        };
+
+        for constraint in constraints.iter().filter(|c| c.enabled) {
+            match &constraint.constraint_type {
+                ConstraintType::AllowedDomains(domains) if !domains.is_empty() => {
+                    policy.network = NetworkPolicy::AllowDomains(domains.clone());
+                }
+                ConstraintType::MaxExecutionTime(seconds) => {
+                    policy.max_execution_time = Some(Duration::from_secs(*seconds));
+                }
+                ConstraintType::MaxMemoryUsage(bytes) => {
+                    policy.max_memory_bytes = Some(*bytes);
+                }
+                ConstraintType::AllowedPaths(paths) => {
+                    policy
+                        .writable_paths
+                        .extend(paths.iter().map(|path| resolve(workdir, path)));
+                }
+                ConstraintType::DeniedPaths(paths) => {
+                    policy
+                        .denied_paths
+                        .extend(paths.iter().map(|path| resolve(workdir, path)));
+                }
+                _ => {}
+            }
+        }
+
+        policy
+    }
+
+    /// Allow writes to an additional path
+    pub fn allow_write(mut self, path: impl Into<PathBuf>) -> Self {
+        self.writable_paths.push(path.into());
+        self
+    }
+
+    /// Hide a path from the command
+    pub fn deny(mut self, path: impl Into<PathBuf>) -> Self {
+        self.denied_paths.push(path.into());
+        self
+    }
+
+    /// Set network access
+    pub fn with_network(mut self, network: NetworkPolicy) -> Self {
+        self.network = network;
+        self
+    }
+
+    /// Run even when parts of the policy can't be enforced
+    pub fn best_effort(mut self) -> Self {
+        self.strict = false;
+        self
+    }
+}
+
+fn resolve(workdir: &Path, path: &str) -> PathBuf {
+    let path = Path::new(path);
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        workdir.join(path)
+    }
+}
+
+/// A command to run inside the sandbox
+#[derive(Debug, Clone)]
+pub struct SandboxRequest {
+    pub program: String,
+    pub args: Vec<String>,
+    pub workdir: Option<PathBuf>,
+    pub env: Vec<(String, String)>,
+}
+
+impl SandboxRequest {
+    /// Run a program
+    pub fn new(program: impl Into<String>) -> Self {
+        Self {
+            program: program.into(),
+            args: Vec::new(),
+            workdir: None,
+            env: Vec::new(),
+        }
+    }
+
+    /// Run a command line through the platform shell
+    pub fn shell(command: impl Into<String>) -> Self {
+        if cfg!(windows) {
+            Self::new("cmd").arg("/C").arg(command)
+        } else {
+            Self::new("sh").arg("-c").arg(command)
+        }
+    }
+
+    /// Append an argument
+    pub fn arg(mut self, arg: impl Into<String>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// Append arguments
+    pub fn args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// Set the working directory
+    pub fn workdir(mut self, workdir: impl Into<PathBuf>) -> Self {
+        self.workdir = Some(workdir.into());
+        self
+    }
+
+    /// Set an environment variable
+    pub fn env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.push((key.into(), value.into()));
+        self
+    }
+}
+
+/// What a backend enforced for a command
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SandboxEnforcement {
+    /// Backend that built the command
+    pub backend: String,
+    /// Writable and denied paths are enforced
+    pub filesystem: bool,
+    /// Network policy is enforced
+    pub network: bool,
+    /// Memory limit is enforced
+    pub memory: bool,
+    /// Dangerous system calls are filtered
+    pub syscalls: bool,
+    /// Parts of the policy that were not enforced, and why
+    pub gaps: Vec<String>,
+}
+
+impl SandboxEnforcement {
+    fn new(backend: &str) -> Self {
+        Self {
+            backend: backend.to_string(),
+            ..Self::default()
+        }
+    }
+
+    fn gap(&mut self, gap: impl Into<String>) {
+        self.gaps.push(gap.into());
+    }
+}
+
+/// Keeps platform resources alive for the lifetime of a sandboxed process
+#[derive(Default)]
+pub struct SandboxGuard {
+    resources: Vec<Box<dyn Any + Send>>,
+}
+
+impl SandboxGuard {
+    fn hold(&mut self, resource: Option<Box<dyn Any + Send>>) {
+        self.resources.extend(resource);
+    }
+}
+
+/// Command prepared by a backend
+pub struct PreparedCommand {
+    pub command: Command,
+    pub enforcement: SandboxEnforcement,
+    /// Resources that must outlive the spawn (e.g. a seccomp program file)
+    pub resource: Option<Box<dyn Any + Send>>,
+}
+
+/// Platform isolation mechanism
+pub trait SandboxBackend: Send + Sync {
+    /// Backend name
+    fn name(&self) -> &'static str;
+
+    /// Whether the backend can run on this machine
+    fn is_available(&self) -> bool;
+
+    /// Build the isolated command
+    fn prepare(
+        &self,
+        request: &SandboxRequest,
+        policy: &SandboxPolicy,
+    ) -> SafetyResult<PreparedCommand>;
+
+    /// Apply isolation that needs the running process
+    fn after_spawn(
+        &self,
+        _child: &Child,
+        _policy: &SandboxPolicy,
+    ) -> SafetyResult<Option<Box<dyn Any + Send>>> {
+        Ok(None)
+    }
+}
+
+/// Runs commands inside the platform sandbox
+#[derive(Clone)]
+pub struct Sandbox {
+    backend: Arc<dyn SandboxBackend>,
+}
+
+impl Sandbox {
+    /// Use the sandbox backend for the current platform
+    pub fn detect() -> Self {
+        let backend: Arc<dyn SandboxBackend> = if cfg!(target_os = "macos") {
+            Arc::new(MacOsSandbox::new())
+        } else if cfg!(windows) {
+            Arc::new(WindowsJobSandbox::new())
+        } else {
+            Arc::new(LinuxSandbox::new())
+        };
+        Self { backend }
+    }
+
+    /// Use a specific backend
+    pub fn with_backend(backend: Arc<dyn SandboxBackend>) -> Self {
+        Self { backend }
+    }
+
+    /// Active backend name
+    pub fn backend_name(&self) -> &'static str {
+        self.backend.name()
+    }
+
+    /// Whether the backend can run on this machine
+    pub fn is_available(&self) -> bool {
+        self.backend.is_available()
+    }
+
+    /// Build a sandboxed command
+    ///
+    /// Fails for strict policies when the backend can't enforce all of it.
+    pub fn command(
+        &self,
+        request: &SandboxRequest,
+        policy: &SandboxPolicy,
+    ) -> SafetyResult<SandboxedCommand> {
+        let prepared = self.backend.prepare(request, policy)?;
+        if policy.strict && !prepared.enforcement.gaps.is_empty() {
+            return Err(SafetyError::PolicyViolation {
+                policy: "sandbox".to_string(),
+                message: format!(
+                    "{} cannot fully enforce the sandbox policy: {}",
+                    self.backend.name(),
+                    prepared.enforcement.gaps.join("; ")
+                ),
+            });
+        }
+
+        for gap in &prepared.enforcement.gaps {
+            tracing::warn!(backend = self.backend.name(), "Sandbox gap: {}", gap);
+        }
+
+        let mut guard = SandboxGuard::default();
+        guard.hold(prepared.resource);
+        Ok(SandboxedCommand {
+            command: prepared.command,
+            enforcement: prepared.enforcement,
+            backend: Arc::clone(&self.backend),
+            policy: policy.clone(),
+            guard,
+        })
+    }
+}
+
+/// A command ready to run in the sandbox
+pub struct SandboxedCommand {
+    command: Command,
+    enforcement: SandboxEnforcement,
+    backend: Arc<dyn SandboxBackend>,
+    policy: SandboxPolicy,
+    guard: SandboxGuard,
+}
+
+impl SandboxedCommand {
+    /// What the sandbox enforces for this command
+    pub fn enforcement(&self) -> &SandboxEnforcement {
+        &self.enforcement
+    }
+
+    /// Underlying command, e.g. to configure stdio
+    pub fn command_mut(&mut self) -> &mut Command {
+        &mut self.command
+    }
+
+    /// Start the command
+    pub fn spawn(mut self) -> SafetyResult<SandboxedChild> {
+        self.command.kill_on_drop(true);
+        let child = self
+            .command
+            .spawn()
+            .map_err(|e| SafetyError::SandboxError {
+                message: format!("Failed to start sandboxed command: {}", e),
+            })?;
+        let resource = self.backend.after_spawn(&child, &self.policy)?;
+        self.guard.hold(resource);
+
+        Ok(SandboxedChild {
+            child,
+            enforcement: self.enforcement,
+            guard: self.guard,
+        })
+    }
+
+    /// Run to completion, capturing output and applying the policy's time limit
+    pub async fn output(mut self) -> SafetyResult<Output> {
+        self.command
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped());
+        let limit = self.policy.max_execution_time;
+        let child = self.spawn()?;
+        let (child, _guard) = child.into_parts();
+
+        match limit {
+            Some(limit) => tokio::time::timeout(limit, child.wait_with_output())
+                .await
+                .map_err(|_| SafetyError::ConstraintViolation {
+                    constraint: "max_execution_time".to_string(),
+                    message: format!("Sandboxed command exceeded {}s", limit.as_secs()),
+                })?
+                .map_err(wait_error),
+            None => child.wait_with_output().await.map_err(wait_error),
+        }
+    }
+}
+
+fn wait_error(error: std::io::Error) -> SafetyError {
+    SafetyError::SandboxError {
+        message: format!("Failed to wait for sandboxed command: {}", error),
+    }
+}
+
+/// A running sandboxed process
+pub struct SandboxedChild {
+    pub child: Child,
+    enforcement: SandboxEnforcement,
+    guard: SandboxGuard,
+}
+
+impl SandboxedChild {
+    /// What the sandbox enforces for this process
+    pub fn enforcement(&self) -> &SandboxEnforcement {
+        &self.enforcement
+    }
+
+    /// Split into the process and the guard that must outlive it
+    pub fn into_parts(self) -> (Child, SandboxGuard) {
+        (self.child, self.guard)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_from_constraints() {
+        let workdir = PathBuf::from("/work/project");
+        let constraints = vec![
+            SecurityConstraint::max_execution_time(30),
+            SecurityConstraint::new(
+                "domains".to_string(),
+                "Allowed Domains".to_string(),
+                ConstraintType::AllowedDomains(vec!["crates.io".to_string()]),
+            ),
+            SecurityConstraint::new(
+                "paths".to_string(),
+                "Allowed Paths".to_string(),
+                ConstraintType::AllowedPaths(vec!["target".to_string(), "/cache".to_string()]),
+            ),
+            SecurityConstraint::new(
+                "secrets".to_string(),
+                "Denied Paths".to_string(),
+                ConstraintType::DeniedPaths(vec![".env".to_string()]),
+            ),
+            SecurityConstraint::new(
+                "memory".to_string(),
+                "Memory".to_string(),
+                ConstraintType::MaxMemoryUsage(1024),
+            )
+            .enabled(false),
+        ];
+
+        let policy = SandboxPolicy::from_constraints(&constraints, &workdir);
+
+        assert_eq!(policy.max_execution_time, Some(Duration::from_secs(30)));
+        assert_eq!(
+            policy.network,
+            NetworkPolicy::AllowDomains(vec!["crates.io".to_string()])
+        );
+        assert!(policy.writable_paths.contains(&workdir));
+        assert!(policy.writable_paths.contains(&workdir.join("target")));
+        assert!(policy.writable_paths.contains(&PathBuf::from("/cache")));
+        assert_eq!(policy.denied_paths, vec![workdir.join(".env")]);
+        assert_eq!(policy.max_memory_bytes, None);
+        assert!(policy.strict);
+    }
+
+    struct PartialBackend;
+
+    impl SandboxBackend for PartialBackend {
+        fn name(&self) -> &'static str {
+            "partial"
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+
+        fn prepare(
+            &self,
+            request: &SandboxRequest,
+            _policy: &SandboxPolicy,
+        ) -> SafetyResult<PreparedCommand> {
+            let mut enforcement = SandboxEnforcement::new(self.name());
+            enforcement.gap("network isolation unavailable");
+            let mut command = Command::new(&request.program);
+            command.args(&request.args);
+            Ok(PreparedCommand {
+                command,
+                enforcement,
+                resource: None,
+            })
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_strict_policy_rejects_gaps() {
+        let sandbox = Sandbox::with_backend(Arc::new(PartialBackend));
+        let request = SandboxRequest::shell("echo hello");
+
+        let strict = sandbox.command(&request, &SandboxPolicy::default());
+        assert!(matches!(strict, Err(SafetyError::PolicyViolation { .. })));
+
+        let output = sandbox
+            .command(&request, &SandboxPolicy::default().best_effort())
+            .unwrap()
+            .output()
+            .await
+            .unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "hello");
+    }
+}
@@ -0,0 +1,170 @@
+//! Windows job object backend
+//!
+//! Each command is placed in its own job object so the whole process tree is
+//! killed when the sandbox is dropped and memory is capped per process. Job
+//! objects don't restrict filesystem or network access; those are reported
+//! as gaps.
+
+use std::any::Any;
+
+use tokio::process::{Child, Command};
+
+use super::{
+    NetworkPolicy, PreparedCommand, SandboxBackend, SandboxEnforcement, SandboxPolicy,
+    SandboxRequest,
+};
+use crate::error::SafetyResult;
+
+/// Job object backend for Windows
+#[derive(Default)]
+pub struct WindowsJobSandbox;
+
+impl WindowsJobSandbox {
+    /// Create the backend
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl SandboxBackend for WindowsJobSandbox {
+    fn name(&self) -> &'static str {
+        "windows-job-object"
+    }
+
+    fn is_available(&self) -> bool {
+        cfg!(windows)
+    }
+
+    fn prepare(
+        &self,
+        request: &SandboxRequest,
+        policy: &SandboxPolicy,
+    ) -> SafetyResult<PreparedCommand> {
+        let mut enforcement = SandboxEnforcement::new(self.name());
+        if !self.is_available() {
+            enforcement.gap("job objects are only available on Windows");
+        }
+        if !policy.writable_paths.is_empty() || !policy.denied_paths.is_empty() {
+            enforcement.gap("job objects do not restrict filesystem access");
+        }
+        if policy.network == NetworkPolicy::Deny {
+            enforcement.gap("job objects do not restrict network access");
+        } else {
+            enforcement.network = true;
+        }
+        enforcement.memory = self.is_available() && policy.max_memory_bytes.is_some();
+
+        let mut command = Command::new(&request.program);
+        command
+            .args(&request.args)
+            .envs(request.env.iter().cloned());
+        if let Some(workdir) = &request.workdir {
+            command.current_dir(workdir);
+        }
+
+        Ok(PreparedCommand {
+            command,
+            enforcement,
+            resource: None,
+        })
+    }
+
+    #[cfg(windows)]
+    fn after_spawn(
+        &self,
+        child: &Child,
+        policy: &SandboxPolicy,
+    ) -> SafetyResult<Option<Box<dyn Any + Send>>> {
+        let job = job::JobObject::create(policy.max_memory_bytes)?;
+        if let Some(process) = child.raw_handle() {
+            job.assign(process)?;
+        }
+        Ok(Some(Box::new(job)))
+    }
+
+    #[cfg(not(windows))]
+    fn after_spawn(
+        &self,
+        _child: &Child,
+        _policy: &SandboxPolicy,
+    ) -> SafetyResult<Option<Box<dyn Any + Send>>> {
+        Ok(None)
+    }
+}
+
+#[cfg(windows)]
+mod job {
+    use std::ffi::c_void;
+    use std::os::windows::io::RawHandle;
+
+    use windows_sys::Win32::Foundation::{CloseHandle, HANDLE};
+    use windows_sys::Win32::System::JobObjects::{
+        AssignProcessToJobObject, CreateJobObjectW, JobObjectExtendedLimitInformation,
+        SetInformationJobObject, JOBOBJECT_EXTENDED_LIMIT_INFORMATION,
+        JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE, JOB_OBJECT_LIMIT_PROCESS_MEMORY,
+    };
+
+    use crate::error::{SafetyError, SafetyResult};
+
+    /// Owned job object handle; closing it kills the processes in the job
+    pub(super) struct JobObject(HANDLE);
+
+    // SAFETY: job object handles may be used and closed from any thread
+    unsafe impl Send for JobObject {}
+
+    impl JobObject {
+        pub(super) fn create(max_memory_bytes: Option<u64>) -> SafetyResult<Self> {
+            // SAFETY: null attributes and name create an anonymous job
+            let handle = unsafe { CreateJobObjectW(std::ptr::null(), std::ptr::null()) };
+            if handle.is_null() {
+                return Err(last_error("create job object"));
+            }
+            let job = Self(handle);
+
+            // SAFETY: the struct is plain data and valid when zeroed
+            let mut limits: JOBOBJECT_EXTENDED_LIMIT_INFORMATION = unsafe { std::mem::zeroed() };
+            limits.BasicLimitInformation.LimitFlags = JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE;
+            if let Some(limit) = max_memory_bytes {
+                limits.BasicLimitInformation.LimitFlags |= JOB_OBJECT_LIMIT_PROCESS_MEMORY;
+                limits.ProcessMemoryLimit = limit as usize;
+            }
+
+            // SAFETY: `limits` matches the information class and outlives the call
+            let ok = unsafe {
+                SetInformationJobObject(
+                    job.0,
+                    JobObjectExtendedLimitInformation,
+                    &limits as *const _ as *const c_void,
+                    std::mem::size_of::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>() as u32,
+                )
+            };
+            if ok == 0 {
+                return Err(last_error("configure job object"));
+            }
+            Ok(job)
+        }
+
+        pub(super) fn assign(&self, process: RawHandle) -> SafetyResult<()> {
+            // SAFETY: both handles are valid for the duration of the call
+            if unsafe { AssignProcessToJobObject(self.0, process as HANDLE) } == 0 {
+                return Err(last_error("assign process to job object"));
+            }
+            Ok(())
+        }
+    }
+
+    impl Drop for JobObject {
+        fn drop(&mut self) {
+            // SAFETY: the handle is owned and closed exactly once
+            unsafe {
+                CloseHandle(self.0);
+            }
+        }
+    }
+
+    fn last_error(action: &str) -> SafetyError {
+        SafetyError::SandboxError {
+            message: format!("Failed to {}: {}", action, std::io::Error::last_os_error()),
+        }
+    }
+}
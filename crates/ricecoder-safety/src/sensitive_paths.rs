@@ -0,0 +1,592 @@
+//! Sensitive paths that file tools must not touch
+//!
+//! A single [`SensitivePathPolicy`] is shared by every tool that reads,
+//! writes or searches files. Access to sensitive paths (`.env` files,
+//! `~/.ssh`, credential stores and any globs from the `sensitive_paths`
+//! section of `safety.yaml`) is denied by default:
+//!
+//! ```yaml
+//! sensitive_paths:
+//!   deny: ["secrets/**", "~/.config/gcloud"]
+//!   allow: [".env.test"]
+//! ```
+//!
+//! Patterns follow the policy file conventions: relative globs match
+//! anywhere in a path, a leading `~` is the home directory, and a pattern
+//! that names a directory also covers everything below it. `allow` patterns
+//! take precedence over `deny` patterns.
+//!
+//! A denied path can still be opened once the user approves an override
+//! through the [`ApprovalRouter`]; the grant covers that path and access
+//! kind until it is revoked. Tool invokers go through [`authorize`], which
+//! asks the router installed with [`set_override_router`].
+
+use std::{
+    collections::HashSet,
+    fmt,
+    path::{Component, Path, PathBuf},
+    sync::{Arc, OnceLock, RwLock},
+};
+
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    approval::{ApprovalDecision, ApprovalRouter},
+    error::{SafetyError, SafetyResult},
+    policy::anchor_glob,
+    risk::{RiskFactors, RiskLevel, RiskScore},
+    validation::ApprovalRequest,
+};
+
+/// Constraint id of sensitive path override requests
+pub const SENSITIVE_PATH_CONSTRAINT: &str = "sensitive_path";
+
+/// Paths denied unless the configuration opts out of the defaults
+pub const DEFAULT_SENSITIVE_PATHS: &[&str] = &[
+    ".env",
+    ".env.*",
+    ".git-credentials",
+    ".netrc",
+    "*.pem",
+    "id_rsa",
+    "id_ecdsa",
+    "id_ed25519",
+    "~/.ssh",
+    "~/.gnupg",
+    "~/.aws/credentials",
+    "~/.docker/config.json",
+    "~/.kube/config",
+];
+
+/// Templates that look sensitive but hold no secrets
+pub const DEFAULT_ALLOWED_PATHS: &[&str] = &[".env*.example", ".env*.sample", ".env*.template"];
+
+/// Kind of access a tool wants
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PathAccess {
+    Read,
+    Write,
+    Search,
+}
+
+impl PathAccess {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PathAccess::Read => "read",
+            PathAccess::Write => "write",
+            PathAccess::Search => "search",
+        }
+    }
+
+    /// Risk of overriding a denial for this access
+    fn risk_score(&self) -> u8 {
+        match self {
+            PathAccess::Read | PathAccess::Search => 60,
+            PathAccess::Write => 70,
+        }
+    }
+}
+
+impl fmt::Display for PathAccess {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// `sensitive_paths` section of a policy file
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SensitivePathConfig {
+    /// Include [`DEFAULT_SENSITIVE_PATHS`] and [`DEFAULT_ALLOWED_PATHS`];
+    /// unset means yes
+    pub use_defaults: Option<bool>,
+    /// Extra globs to deny
+    pub deny: Vec<String>,
+    /// Globs exempt from denial
+    pub allow: Vec<String>,
+}
+
+/// A tool tried to access a sensitive path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathDenied {
+    pub path: PathBuf,
+    pub access: PathAccess,
+    /// Pattern that matched
+    pub pattern: String,
+}
+
+impl fmt::Display for PathDenied {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Access to {} is blocked: it matches the sensitive path pattern '{}'. \
+             DO NOT make further attempts to {} it; ask the user to approve an override instead",
+            self.path.display(),
+            self.pattern,
+            self.access
+        )
+    }
+}
+
+impl std::error::Error for PathDenied {}
+
+impl From<PathDenied> for SafetyError {
+    fn from(denied: PathDenied) -> Self {
+        SafetyError::AccessDenied {
+            resource: denied.path.display().to_string(),
+            reason: format!("matches sensitive path pattern '{}'", denied.pattern),
+        }
+    }
+}
+
+/// Compiled globs and the patterns they came from
+#[derive(Debug, Default)]
+struct PatternSet {
+    set: GlobSet,
+    /// Source pattern of each glob in `set`
+    sources: Vec<String>,
+}
+
+impl PatternSet {
+    fn compile(patterns: &[String]) -> SafetyResult<Self> {
+        let mut builder = GlobSetBuilder::new();
+        let mut sources = Vec::new();
+        for pattern in patterns {
+            let Some(expanded) = expand_home(pattern) else {
+                tracing::warn!(pattern = %pattern, "No home directory; skipping sensitive path");
+                continue;
+            };
+            let anchored = anchor_glob(expanded.trim_end_matches('/'));
+            // A directory pattern also covers what is inside it
+            for glob in [anchored.clone(), format!("{}/**", anchored)] {
+                let glob = GlobBuilder::new(&glob)
+                    .literal_separator(true)
+                    .build()
+                    .map_err(|e| SafetyError::ConfigError {
+                        field: "sensitive_paths".to_string(),
+                        message: format!("Invalid glob '{}': {}", pattern, e),
+                    })?;
+                builder.add(glob);
+                sources.push(pattern.clone());
+            }
+        }
+        let set = builder.build().map_err(|e| SafetyError::ConfigError {
+            field: "sensitive_paths".to_string(),
+            message: e.to_string(),
+        })?;
+        Ok(Self { set, sources })
+    }
+
+    fn matching(&self, path: &Path) -> Option<&str> {
+        self.set
+            .matches(path)
+            .first()
+            .map(|index| self.sources[*index].as_str())
+    }
+}
+
+/// Decides which paths file tools may access
+#[derive(Debug)]
+pub struct SensitivePathPolicy {
+    deny: PatternSet,
+    allow: PatternSet,
+    overrides: RwLock<HashSet<(PathBuf, PathAccess)>>,
+}
+
+impl SensitivePathPolicy {
+    /// Compile a policy from configuration
+    pub fn new(config: &SensitivePathConfig) -> SafetyResult<Self> {
+        let mut deny: Vec<String> = Vec::new();
+        let mut allow: Vec<String> = Vec::new();
+        if config.use_defaults.unwrap_or(true) {
+            deny.extend(DEFAULT_SENSITIVE_PATHS.iter().map(|p| p.to_string()));
+            allow.extend(DEFAULT_ALLOWED_PATHS.iter().map(|p| p.to_string()));
+        }
+        deny.extend(config.deny.iter().cloned());
+        allow.extend(config.allow.iter().cloned());
+
+        Ok(Self {
+            deny: PatternSet::compile(&deny)?,
+            allow: PatternSet::compile(&allow)?,
+            overrides: RwLock::new(HashSet::new()),
+        })
+    }
+
+    /// Policy with only the default patterns
+    pub fn with_defaults() -> Self {
+        Self::new(&SensitivePathConfig::default()).expect("default sensitive paths are valid")
+    }
+
+    /// Check whether `path` may be accessed
+    ///
+    /// Relative paths are resolved against the working directory. Symlinks
+    /// are followed, so a link to a sensitive file is denied too.
+    pub fn check(&self, path: &Path, access: PathAccess) -> Result<(), PathDenied> {
+        let path = normalize(path);
+        let mut candidates = vec![path.clone()];
+        if let Ok(target) = path.canonicalize() {
+            if target != path {
+                candidates.push(target);
+            }
+        }
+
+        for candidate in candidates {
+            if self.allow.matching(&candidate).is_some() {
+                continue;
+            }
+            if let Some(pattern) = self.deny.matching(&candidate) {
+                if self.is_overridden(&path, access) {
+                    continue;
+                }
+                return Err(PathDenied {
+                    path,
+                    access,
+                    pattern: pattern.to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether `path` is denied for `access`
+    pub fn is_denied(&self, path: &Path, access: PathAccess) -> bool {
+        self.check(path, access).is_err()
+    }
+
+    /// Ask for approval to access a denied path and grant it if approved
+    ///
+    /// Overrides always need at least typed confirmation under the default
+    /// approval thresholds.
+    pub async fn request_override(
+        &self,
+        denied: &PathDenied,
+        router: &ApprovalRouter,
+        requester: &str,
+        project: Option<&str>,
+    ) -> SafetyResult<ApprovalDecision> {
+        let score = denied.access.risk_score();
+        let mut factors = RiskFactors::default();
+        factors
+            .operation_factors
+            .insert(SENSITIVE_PATH_CONSTRAINT.to_string(), score);
+        let risk = RiskScore {
+            score,
+            level: RiskLevel::from_score(score),
+            factors,
+            confidence: 1.0,
+            recommendations: vec![format!(
+                "Confirm that {} may {} {}",
+                requester,
+                denied.access,
+                denied.path.display()
+            )],
+            assessed_at: chrono::Utc::now(),
+        };
+        let mut context = std::collections::HashMap::new();
+        context.insert(
+            "path".to_string(),
+            serde_json::Value::String(denied.path.display().to_string()),
+        );
+        context.insert(
+            "pattern".to_string(),
+            serde_json::Value::String(denied.pattern.clone()),
+        );
+        let request = ApprovalRequest {
+            constraint_id: SENSITIVE_PATH_CONSTRAINT.to_string(),
+            reason: format!(
+                "Allow {} access to sensitive path {}",
+                denied.access,
+                denied.path.display()
+            ),
+            requested_at: chrono::Utc::now(),
+            context: Some(context),
+        };
+
+        let request_id = uuid::Uuid::new_v4().to_string();
+        let decision = router
+            .route(&request_id, &request, requester, project, &risk)
+            .await?;
+        if decision.approved {
+            self.grant_override(&denied.path, denied.access);
+        }
+        Ok(decision)
+    }
+
+    /// Allow `access` to `path` despite the policy
+    pub fn grant_override(&self, path: &Path, access: PathAccess) {
+        if let Ok(mut overrides) = self.overrides.write() {
+            overrides.insert((normalize(path), access));
+        }
+    }
+
+    /// Drop every granted override
+    pub fn revoke_overrides(&self) {
+        if let Ok(mut overrides) = self.overrides.write() {
+            overrides.clear();
+        }
+    }
+
+    fn is_overridden(&self, path: &Path, access: PathAccess) -> bool {
+        self.overrides
+            .read()
+            .map(|overrides| overrides.contains(&(path.to_path_buf(), access)))
+            .unwrap_or(false)
+    }
+}
+
+impl Default for SensitivePathPolicy {
+    fn default() -> Self {
+        Self::with_defaults()
+    }
+}
+
+fn global_slot() -> &'static RwLock<Arc<SensitivePathPolicy>> {
+    static GLOBAL: OnceLock<RwLock<Arc<SensitivePathPolicy>>> = OnceLock::new();
+    GLOBAL.get_or_init(|| RwLock::new(Arc::new(SensitivePathPolicy::with_defaults())))
+}
+
+/// Policy shared by all file tools in this process
+pub fn global() -> Arc<SensitivePathPolicy> {
+    match global_slot().read() {
+        Ok(policy) => policy.clone(),
+        Err(poisoned) => poisoned.into_inner().clone(),
+    }
+}
+
+/// Replace the shared policy, e.g. after `safety.yaml` changed
+pub fn set_global(policy: Arc<SensitivePathPolicy>) {
+    match global_slot().write() {
+        Ok(mut slot) => *slot = policy,
+        Err(poisoned) => *poisoned.into_inner() = policy,
+    }
+}
+
+fn router_slot() -> &'static RwLock<Option<Arc<ApprovalRouter>>> {
+    static ROUTER: OnceLock<RwLock<Option<Arc<ApprovalRouter>>>> = OnceLock::new();
+    ROUTER.get_or_init(|| RwLock::new(None))
+}
+
+/// Route override requests from [`authorize`] to `router`
+///
+/// Pass None to deny sensitive paths without asking.
+pub fn set_override_router(router: Option<Arc<ApprovalRouter>>) {
+    match router_slot().write() {
+        Ok(mut slot) => *slot = router,
+        Err(poisoned) => *poisoned.into_inner() = router,
+    }
+}
+
+/// Check `path` against the shared policy, asking for an override if denied
+///
+/// The original denial is returned when no override router is installed or
+/// the request is declined or fails.
+pub async fn authorize(path: &Path, access: PathAccess, requester: &str) -> Result<(), PathDenied> {
+    let policy = global();
+    let denied = match policy.check(path, access) {
+        Ok(()) => return Ok(()),
+        Err(denied) => denied,
+    };
+    let router = match router_slot().read() {
+        Ok(router) => router.clone(),
+        Err(poisoned) => poisoned.into_inner().clone(),
+    };
+    let Some(router) = router else {
+        return Err(denied);
+    };
+
+    match policy
+        .request_override(&denied, &router, requester, None)
+        .await
+    {
+        Ok(decision) if decision.approved => Ok(()),
+        Ok(_) => Err(denied),
+        Err(e) => {
+            tracing::warn!("Sensitive path override request failed: {}", e);
+            Err(denied)
+        }
+    }
+}
+
+fn expand_home(pattern: &str) -> Option<String> {
+    let rest = match pattern.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => rest,
+        _ => return Some(pattern.to_string()),
+    };
+    let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"))?;
+    Some(format!("{}{}", Path::new(&home).display(), rest))
+}
+
+/// Make a path absolute and drop `.` and `..` without touching the disk
+fn normalize(path: &Path) -> PathBuf {
+    let path = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir()
+            .map(|cwd| cwd.join(path))
+            .unwrap_or_else(|_| path.to_path_buf())
+    };
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+
+    use super::*;
+    use crate::approval::{ApprovalPrompt, ApprovalPrompter, ApprovalRouting};
+
+    struct TypedPrompter {
+        typed: String,
+    }
+
+    #[async_trait]
+    impl ApprovalPrompter for TypedPrompter {
+        async fn confirm(&self, _prompt: &ApprovalPrompt) -> SafetyResult<bool> {
+            Ok(false)
+        }
+
+        async fn typed_confirmation(
+            &self,
+            _prompt: &ApprovalPrompt,
+            _phrase: &str,
+        ) -> SafetyResult<String> {
+            Ok(self.typed.clone())
+        }
+    }
+
+    #[test]
+    fn test_default_paths_are_denied() {
+        let policy = SensitivePathPolicy::with_defaults();
+        let denied = policy
+            .check(Path::new("/work/app/.env"), PathAccess::Read)
+            .unwrap_err();
+        assert_eq!(denied.pattern, ".env");
+        assert!(denied.to_string().contains("blocked"));
+
+        assert!(policy.is_denied(Path::new("/work/app/.env.local"), PathAccess::Write));
+        assert!(policy.is_denied(Path::new("/work/certs/server.pem"), PathAccess::Read));
+        assert!(!policy.is_denied(Path::new("/work/app/.env.example"), PathAccess::Read));
+        assert!(!policy.is_denied(Path::new("/work/app/src/env.rs"), PathAccess::Read));
+        // Traversal does not dodge the policy
+        assert!(policy.is_denied(Path::new("/work/app/src/../.env"), PathAccess::Read));
+
+        if let Some(home) = std::env::var_os("HOME") {
+            let key = Path::new(&home).join(".ssh").join("id_ed25519");
+            assert!(policy.is_denied(&key, PathAccess::Search));
+        }
+    }
+
+    #[test]
+    fn test_configured_patterns() {
+        let policy = SensitivePathPolicy::new(&SensitivePathConfig {
+            use_defaults: Some(false),
+            deny: vec!["secrets".to_string()],
+            allow: vec!["secrets/public.txt".to_string()],
+        })
+        .unwrap();
+
+        assert!(policy.is_denied(Path::new("/work/secrets"), PathAccess::Search));
+        assert!(policy.is_denied(Path::new("/work/secrets/db/key.txt"), PathAccess::Read));
+        assert!(!policy.is_denied(Path::new("/work/secrets/public.txt"), PathAccess::Read));
+        assert!(!policy.is_denied(Path::new("/work/.env"), PathAccess::Read));
+
+        let invalid = SensitivePathConfig {
+            deny: vec!["[".to_string()],
+            ..Default::default()
+        };
+        assert!(SensitivePathPolicy::new(&invalid).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlinks_to_sensitive_files_are_denied() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(".env"), "TOKEN=x").unwrap();
+        let link = dir.path().join("settings.txt");
+        std::os::unix::fs::symlink(dir.path().join(".env"), &link).unwrap();
+
+        let policy = SensitivePathPolicy::with_defaults();
+        assert!(policy.is_denied(&link, PathAccess::Read));
+    }
+
+    #[tokio::test]
+    async fn test_override_needs_approval() {
+        let policy = SensitivePathPolicy::with_defaults();
+        let path = Path::new("/work/app/.env");
+        let denied = policy.check(path, PathAccess::Read).unwrap_err();
+
+        let declining = ApprovalRouter::new(ApprovalRouting::default()).with_prompter(Arc::new(
+            TypedPrompter {
+                typed: "no".to_string(),
+            },
+        ));
+        let decision = policy
+            .request_override(&denied, &declining, "alice", None)
+            .await
+            .unwrap();
+        assert!(!decision.approved);
+        assert!(policy.is_denied(path, PathAccess::Read));
+
+        let approving = ApprovalRouter::new(ApprovalRouting::default()).with_prompter(Arc::new(
+            TypedPrompter {
+                typed: format!("approve {}", SENSITIVE_PATH_CONSTRAINT),
+            },
+        ));
+        let decision = policy
+            .request_override(&denied, &approving, "alice", None)
+            .await
+            .unwrap();
+        assert!(decision.approved);
+        assert!(!policy.is_denied(path, PathAccess::Read));
+        // The grant is limited to the approved access
+        assert!(policy.is_denied(path, PathAccess::Write));
+
+        policy.revoke_overrides();
+        assert!(policy.is_denied(path, PathAccess::Read));
+    }
+
+    #[tokio::test]
+    async fn test_authorize_routes_denials_to_override_router() {
+        let path = Path::new("/work/authorize/.env.production");
+        assert!(authorize(path, PathAccess::Read, "alice").await.is_err());
+
+        set_override_router(Some(Arc::new(
+            ApprovalRouter::new(ApprovalRouting::default()).with_prompter(Arc::new(
+                TypedPrompter {
+                    typed: "no".to_string(),
+                },
+            )),
+        )));
+        assert!(authorize(path, PathAccess::Read, "alice").await.is_err());
+
+        set_override_router(Some(Arc::new(
+            ApprovalRouter::new(ApprovalRouting::default()).with_prompter(Arc::new(
+                TypedPrompter {
+                    typed: format!("approve {}", SENSITIVE_PATH_CONSTRAINT),
+                },
+            )),
+        )));
+        assert!(authorize(path, PathAccess::Read, "alice").await.is_ok());
+        set_override_router(None);
+
+        assert!(authorize(
+            Path::new("/work/authorize/src/main.rs"),
+            PathAccess::Read,
+            "alice"
+        )
+        .await
+        .is_ok());
+    }
+}
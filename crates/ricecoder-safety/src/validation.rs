@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 
 use crate::{
+    approval::ApprovalRouter,
     constraints::{ConstraintResult, SecurityConstraint, ValidationContext},
     error::{SafetyError, SafetyResult},
     risk::{RiskContext, RiskScore, RiskScorer},
@@ -17,6 +18,7 @@ pub struct SafetyValidator {
     constraints: RwLock<Vec<SecurityConstraint>>,
     risk_scorer: Arc<RiskScorer>,
     approval_gates: RwLock<HashMap<String, ApprovalGate>>,
+    approval_router: Option<Arc<ApprovalRouter>>,
 }
 
 impl SafetyValidator {
@@ -26,6 +28,7 @@ impl SafetyValidator {
             constraints: RwLock::new(Vec::new()),
             risk_scorer,
             approval_gates: RwLock::new(HashMap::new()),
+            approval_router: None,
         }
     }
 
@@ -39,6 +42,12 @@ impl SafetyValidator {
         Self::new()
     }
 
+    /// Route approval requests by risk through `router`
+    pub fn with_approval_router(mut self, router: Arc<ApprovalRouter>) -> Self {
+        self.approval_router = Some(router);
+        self
+    }
+
     /// Add a security constraint
     pub async fn add_constraint(&self, constraint: SecurityConstraint) -> SafetyResult<()> {
         self.constraints.write().await.push(constraint);
@@ -52,6 +61,13 @@ impl SafetyValidator {
         Ok(())
     }
 
+    /// Replace every constraint whose id starts with `prefix`
+    pub async fn replace_constraints(&self, prefix: &str, replacement: Vec<SecurityConstraint>) {
+        let mut constraints = self.constraints.write().await;
+        constraints.retain(|c| !c.id.starts_with(prefix));
+        constraints.extend(replacement);
+    }
+
     /// Get all constraints
     pub async fn get_constraints(&self) -> Vec<SecurityConstraint> {
         self.constraints.read().await.clone()
@@ -162,7 +178,10 @@ impl SafetyValidator {
 
     /// Request approval for an operation
     pub async fn request_approval(&self, request: ApprovalRequest) -> SafetyResult<String> {
-        let gate = ApprovalGate::new(request.constraint_id.clone());
+        let mut gate = ApprovalGate::new(request.constraint_id.clone());
+        if let Some(router) = &self.approval_router {
+            gate = gate.with_router(Arc::clone(router));
+        }
         let request_id = gate.add_request(request).await?;
 
         self.approval_gates
@@ -205,6 +224,25 @@ impl SafetyValidator {
         Ok(())
     }
 
+    /// Obtain the approval a pending request needs given its risk
+    pub async fn route_approval(
+        &self,
+        request_id: &str,
+        requester: &str,
+        project: Option<&str>,
+        risk: &RiskScore,
+    ) -> SafetyResult<ApprovalStatus> {
+        // Routing may wait on a person, so only hold a read lock
+        let gates = self.approval_gates.read().await;
+        let gate = gates
+            .get(request_id)
+            .ok_or_else(|| SafetyError::ValidationError {
+                field: "request_id".to_string(),
+                message: "Approval request not found".to_string(),
+            })?;
+        gate.route(request_id, requester, project, risk).await
+    }
+
     /// Get pending approval requests
     pub async fn get_pending_approvals(&self) -> Vec<(String, ApprovalRequest)> {
         let gates = self.approval_gates.read().await;
@@ -278,6 +316,7 @@ pub struct ApprovalGate {
     constraint_id: String,
     pending_request: RwLock<Option<ApprovalRequest>>,
     approval_status: RwLock<ApprovalStatus>,
+    router: Option<Arc<ApprovalRouter>>,
 }
 
 impl ApprovalGate {
@@ -287,9 +326,16 @@ impl ApprovalGate {
             constraint_id,
             pending_request: RwLock::new(None),
             approval_status: RwLock::new(ApprovalStatus::Pending),
+            router: None,
         }
     }
 
+    /// Decide requests by risk through `router`
+    pub fn with_router(mut self, router: Arc<ApprovalRouter>) -> Self {
+        self.router = Some(router);
+        self
+    }
+
     /// Add an approval request
     pub async fn add_request(&self, request: ApprovalRequest) -> SafetyResult<String> {
         *self.pending_request.write().await = Some(request);
@@ -327,6 +373,42 @@ impl ApprovalGate {
         Ok(())
     }
 
+    /// Decide the pending request with the approval its risk calls for
+    pub async fn route(
+        &self,
+        request_id: &str,
+        requester: &str,
+        project: Option<&str>,
+        risk: &RiskScore,
+    ) -> SafetyResult<ApprovalStatus> {
+        let router = self
+            .router
+            .as_ref()
+            .ok_or_else(|| SafetyError::ApprovalRequired {
+                reason: "No approval router is configured".to_string(),
+            })?;
+        let request =
+            self.get_pending_request()
+                .await
+                .ok_or_else(|| SafetyError::ValidationError {
+                    field: "request_id".to_string(),
+                    message: "No pending approval request".to_string(),
+                })?;
+
+        let decision = router
+            .route(request_id, &request, requester, project, risk)
+            .await?;
+        if decision.approved {
+            self.approve(&decision.decided_by).await?;
+        } else {
+            let reason = decision
+                .reason
+                .unwrap_or_else(|| "Approval denied".to_string());
+            self.reject(&decision.decided_by, reason).await?;
+        }
+        Ok(self.get_status().await)
+    }
+
     /// Get the current approval status
     pub async fn get_status(&self) -> ApprovalStatus {
         self.approval_status.read().await.clone()
@@ -50,7 +50,7 @@ pub use testing::{
 pub use validation::{validate_input, ValidatedInput, ValidationEngine, ValidationError};
 pub use vulnerability::{
     CodeSecurityScanResult, ConfigSecurityScanResult, DefaultVulnerabilityScanner,
-    LicenseScanResult, VulnerabilityScanResult, VulnerabilityScanner,
+    LicenseScanResult, OsvVulnerabilityScanner, VulnerabilityScanResult, VulnerabilityScanner,
 };
 
 // Service wrappers for DI integration
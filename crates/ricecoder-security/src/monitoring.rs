@@ -7,6 +7,7 @@ use std::{
 
 use chrono::{DateTime, Duration, Utc};
 use regex::Regex;
+use ricecoder_research::{AdvisorySeverity, DependencyReport};
 use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 
@@ -26,6 +27,7 @@ pub enum SecurityEventType {
     AnomalousBehavior,
     DdosAttempt,
     MalwareDetected,
+    VulnerableDependency,
 }
 
 /// Threat level classification
@@ -187,6 +189,69 @@ impl SecurityMonitor {
         Ok(())
     }
 
+    /// Record the vulnerabilities from a dependency audit
+    ///
+    /// Each finding becomes a `VulnerableDependency` event; high and critical
+    /// findings also raise an alert. Returns the number of events recorded.
+    pub async fn record_dependency_report(&self, report: &DependencyReport) -> Result<usize> {
+        for finding in &report.findings {
+            let dependency = &finding.dependency;
+            let threat_level = match finding.severity {
+                AdvisorySeverity::Critical => ThreatLevel::Critical,
+                AdvisorySeverity::High => ThreatLevel::High,
+                AdvisorySeverity::Medium | AdvisorySeverity::Unknown => ThreatLevel::Medium,
+                AdvisorySeverity::Low => ThreatLevel::Low,
+            };
+            let version = dependency.resolved_version.as_deref().unwrap_or("unknown");
+            let event = SecurityEvent {
+                id: uuid::Uuid::new_v4().to_string(),
+                event_type: SecurityEventType::VulnerableDependency,
+                timestamp: Utc::now(),
+                source_ip: None,
+                user_id: None,
+                session_id: None,
+                resource: format!(
+                    "{}:{}@{}",
+                    dependency.manifest.display(),
+                    dependency.name,
+                    version
+                ),
+                details: serde_json::json!({
+                    "advisory_id": finding.advisory_id,
+                    "aliases": finding.aliases,
+                    "summary": finding.summary,
+                    "ecosystem": dependency.ecosystem.osv_name(),
+                    "cvss_score": finding.cvss_score,
+                    "fixed_versions": finding.fixed_versions,
+                }),
+                threat_level: threat_level.clone(),
+                mitigated: false,
+            };
+
+            if threat_level >= ThreatLevel::High {
+                let mut alerts = self.alerts.write().await;
+                alerts.push(SecurityAlert {
+                    id: format!("dependency_{}_{}", finding.advisory_id, event.id),
+                    title: format!(
+                        "Vulnerable Dependency: {} {} ({})",
+                        dependency.name, version, finding.advisory_id
+                    ),
+                    description: match finding.recommended_version() {
+                        Some(fixed) => format!("{} Upgrade to {}.", finding.summary, fixed),
+                        None => finding.summary.clone(),
+                    },
+                    severity: threat_level,
+                    events: vec![event.clone()],
+                    triggered_at: Utc::now(),
+                    resolved_at: None,
+                    actions_taken: vec![],
+                });
+            }
+            self.record_event(event).await?;
+        }
+        Ok(report.findings.len())
+    }
+
     /// Get recent security events
     pub async fn get_recent_events(&self, limit: usize) -> Result<Vec<SecurityEvent>> {
         let events = self.events.read().await;
@@ -548,4 +613,59 @@ mod tests {
         assert!(!anomalies.is_empty());
         assert!(anomalies[0].title.contains("FailedLogin"));
     }
+
+    #[tokio::test]
+    async fn test_record_dependency_report() {
+        use ricecoder_research::{
+            DependencyFinding, DependencyReport, ManifestDependency, PackageEcosystem,
+        };
+
+        let storage = Arc::new(crate::audit::MemoryAuditStorage::new());
+        let audit_logger = Arc::new(crate::audit::AuditLogger::new(storage));
+        let monitor = SecurityMonitor::new(MonitorConfig::default(), audit_logger);
+
+        let finding = |name: &str, severity| DependencyFinding {
+            dependency: ManifestDependency {
+                name: name.to_string(),
+                ecosystem: PackageEcosystem::Npm,
+                requirement: Some("^4.17.0".to_string()),
+                resolved_version: Some("4.17.20".to_string()),
+                is_dev: false,
+                direct: true,
+                manifest: "package.json".into(),
+                lockfile: None,
+            },
+            advisory_id: format!("GHSA-{}", name),
+            aliases: vec![],
+            summary: "Command injection".to_string(),
+            severity,
+            cvss_score: None,
+            fixed_versions: vec!["4.17.21".to_string()],
+            references: vec![],
+        };
+        let report = DependencyReport {
+            root: ".".into(),
+            dependencies: vec![],
+            findings: vec![
+                finding("lodash", AdvisorySeverity::Critical),
+                finding("minimist", AdvisorySeverity::Low),
+            ],
+            unresolved: vec![],
+            checked: true,
+            scanned_at: Utc::now(),
+        };
+
+        assert_eq!(monitor.record_dependency_report(&report).await.unwrap(), 2);
+        let events = monitor.get_recent_events(10).await.unwrap();
+        assert_eq!(events.len(), 2);
+        assert!(events
+            .iter()
+            .all(|e| e.event_type == SecurityEventType::VulnerableDependency));
+        assert_eq!(events[1].resource, "package.json:lodash@4.17.20");
+
+        let alerts = monitor.get_active_alerts().await.unwrap();
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].severity, ThreatLevel::Critical);
+        assert!(alerts[0].description.ends_with("Upgrade to 4.17.21."));
+    }
 }
@@ -404,3 +404,145 @@ impl DefaultVulnerabilityScanner {
         Ok(licenses)
     }
 }
+
+impl From<&ricecoder_research::DependencyFinding> for Vulnerability {
+    fn from(finding: &ricecoder_research::DependencyFinding) -> Self {
+        use ricecoder_research::AdvisorySeverity;
+
+        Self {
+            id: finding.advisory_id.clone(),
+            package: finding.dependency.name.clone(),
+            version: finding
+                .dependency
+                .resolved_version
+                .clone()
+                .unwrap_or_else(|| "unknown".to_string()),
+            severity: match finding.severity {
+                AdvisorySeverity::Critical => VulnerabilitySeverity::Critical,
+                AdvisorySeverity::High => VulnerabilitySeverity::High,
+                AdvisorySeverity::Medium | AdvisorySeverity::Unknown => {
+                    VulnerabilitySeverity::Medium
+                }
+                AdvisorySeverity::Low => VulnerabilitySeverity::Low,
+            },
+            description: finding.summary.clone(),
+            advisory_url: finding.references.first().cloned(),
+            cvss_score: finding.cvss_score,
+        }
+    }
+}
+
+/// Vulnerability scanner that checks dependency manifests against OSV
+///
+/// Works for Cargo, npm, Python and Go manifests without external tooling.
+/// Code, configuration and license scans are delegated to
+/// [`DefaultVulnerabilityScanner`].
+pub struct OsvVulnerabilityScanner {
+    analyzer: ricecoder_research::DependencyAnalyzer,
+    fallback: DefaultVulnerabilityScanner,
+}
+
+impl OsvVulnerabilityScanner {
+    /// Create a scanner using the public OSV API
+    pub fn new() -> Self {
+        Self::with_database(std::sync::Arc::new(ricecoder_research::OsvClient::new()))
+    }
+
+    /// Create a scanner using a specific vulnerability database
+    pub fn with_database(
+        database: std::sync::Arc<dyn ricecoder_research::VulnerabilityDatabase>,
+    ) -> Self {
+        Self {
+            analyzer: ricecoder_research::DependencyAnalyzer::new().with_database(database),
+            fallback: DefaultVulnerabilityScanner::new(),
+        }
+    }
+
+    /// Audit a manifest file, or every manifest under a directory
+    pub async fn audit(
+        &self,
+        manifest_path: &Path,
+    ) -> anyhow::Result<ricecoder_research::DependencyReport> {
+        let report = if manifest_path.is_dir() {
+            self.analyzer.audit(manifest_path).await?
+        } else {
+            let dependencies = self.analyzer.parse_manifest(manifest_path)?;
+            self.analyzer
+                .check(
+                    manifest_path.parent().unwrap_or(Path::new(".")),
+                    dependencies,
+                )
+                .await?
+        };
+        Ok(report)
+    }
+}
+
+#[async_trait::async_trait]
+impl VulnerabilityScanner for OsvVulnerabilityScanner {
+    async fn scan_dependencies(
+        &self,
+        manifest_path: &Path,
+    ) -> anyhow::Result<VulnerabilityScanResult> {
+        let start_time = std::time::Instant::now();
+        let report = self.audit(manifest_path).await?;
+
+        Ok(VulnerabilityScanResult {
+            vulnerabilities: report.findings.iter().map(Vulnerability::from).collect(),
+            scan_duration: start_time.elapsed(),
+            scan_timestamp: report.scanned_at,
+        })
+    }
+
+    async fn scan_code(&self, source_path: &Path) -> anyhow::Result<CodeSecurityScanResult> {
+        self.fallback.scan_code(source_path).await
+    }
+
+    async fn scan_config(&self, config_path: &Path) -> anyhow::Result<ConfigSecurityScanResult> {
+        self.fallback.scan_config(config_path).await
+    }
+
+    async fn scan_licenses(&self, manifest_path: &Path) -> anyhow::Result<LicenseScanResult> {
+        self.fallback.scan_licenses(manifest_path).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use ricecoder_research::OfflineOsvDatabase;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_osv_scanner_scans_manifest() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let manifest = dir.path().join("package.json");
+        std::fs::write(&manifest, r#"{"dependencies": {"lodash": "4.17.20"}}"#).unwrap();
+
+        let database = OfflineOsvDatabase::new();
+        database
+            .add_json(
+                r#"{"id": "GHSA-35jh-r3h4-6jhm",
+                    "summary": "Command Injection in lodash",
+                    "affected": [{"package": {"ecosystem": "npm", "name": "lodash"},
+                                  "ranges": [{"type": "SEMVER", "events": [{"introduced": "0"}, {"fixed": "4.17.21"}]}]}],
+                    "database_specific": {"severity": "HIGH"},
+                    "references": [{"type": "ADVISORY", "url": "https://github.com/advisories/GHSA-35jh-r3h4-6jhm"}]}"#,
+            )
+            .unwrap();
+        let scanner = OsvVulnerabilityScanner::with_database(Arc::new(database));
+
+        let result = scanner.scan_dependencies(&manifest).await.unwrap();
+        assert_eq!(result.vulnerabilities.len(), 1);
+        let vulnerability = &result.vulnerabilities[0];
+        assert_eq!(vulnerability.package, "lodash");
+        assert_eq!(vulnerability.version, "4.17.20");
+        assert_eq!(vulnerability.severity, VulnerabilitySeverity::High);
+        assert_eq!(
+            vulnerability.advisory_url.as_deref(),
+            Some("https://github.com/advisories/GHSA-35jh-r3h4-6jhm")
+        );
+    }
+}
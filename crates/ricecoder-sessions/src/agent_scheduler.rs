@@ -0,0 +1,465 @@
+//! Priority scheduling for background agents
+//!
+//! The scheduler decides which queued background agent runs next. It enforces a
+//! global concurrency limit and per-provider concurrency caps, serves sessions
+//! fairly (the session that was served least recently wins among equal
+//! priorities), supports pausing and resuming individual agents, and can be
+//! persisted so that pending work survives a restart.
+//!
+//! The scheduler itself is synchronous bookkeeping; [`BackgroundAgentManager`]
+//! drives it and spawns the actual agent tasks.
+//!
+//! [`BackgroundAgentManager`]: crate::background_agent::BackgroundAgentManager
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::{
+    error::{SessionError, SessionResult},
+    models::{AgentStatus, BackgroundAgent},
+};
+
+/// Priority of a queued background agent
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+pub enum AgentPriority {
+    /// Run when nothing else is waiting
+    Low,
+    /// Default priority
+    #[default]
+    Normal,
+    /// Run before normal work
+    High,
+    /// Run as soon as a slot is free
+    Critical,
+}
+
+/// A background agent waiting to be scheduled
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledAgentTask {
+    /// The agent to run
+    pub agent: BackgroundAgent,
+    /// Session that owns the agent
+    pub session_id: String,
+    /// Provider the agent will call (used for concurrency caps)
+    pub provider: String,
+    /// Scheduling priority
+    pub priority: AgentPriority,
+    /// When the task was first queued
+    pub enqueued_at: DateTime<Utc>,
+    /// Monotonic sequence number (FIFO tie-breaker)
+    pub sequence: u64,
+}
+
+impl ScheduledAgentTask {
+    /// Create a new task for a background agent
+    pub fn new(
+        agent: BackgroundAgent,
+        session_id: impl Into<String>,
+        provider: impl Into<String>,
+        priority: AgentPriority,
+    ) -> Self {
+        Self {
+            agent,
+            session_id: session_id.into(),
+            provider: provider.into(),
+            priority,
+            enqueued_at: Utc::now(),
+            sequence: 0,
+        }
+    }
+}
+
+/// Scheduler configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchedulerConfig {
+    /// Maximum number of agents running at once
+    pub max_concurrent: usize,
+    /// Concurrency caps per provider
+    pub provider_limits: HashMap<String, usize>,
+    /// Concurrency cap for providers without an explicit limit
+    pub default_provider_limit: usize,
+}
+
+impl Default for SchedulerConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent: 4,
+            provider_limits: HashMap::new(),
+            default_provider_limit: 2,
+        }
+    }
+}
+
+impl SchedulerConfig {
+    /// Set the concurrency cap for a provider
+    pub fn with_provider_limit(mut self, provider: impl Into<String>, limit: usize) -> Self {
+        self.provider_limits.insert(provider.into(), limit);
+        self
+    }
+
+    /// Get the concurrency cap for a provider
+    pub fn provider_limit(&self, provider: &str) -> usize {
+        self.provider_limits
+            .get(provider)
+            .copied()
+            .unwrap_or(self.default_provider_limit)
+    }
+}
+
+/// Persisted scheduler state (queued and paused work)
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PersistedQueue {
+    queued: Vec<ScheduledAgentTask>,
+    paused: Vec<ScheduledAgentTask>,
+}
+
+/// Priority queue with per-provider concurrency caps and fair session scheduling
+#[derive(Debug)]
+pub struct AgentScheduler {
+    /// Scheduler configuration
+    config: SchedulerConfig,
+    /// Tasks waiting for a slot
+    queued: Vec<ScheduledAgentTask>,
+    /// Tasks currently running, indexed by agent ID
+    running: HashMap<String, ScheduledAgentTask>,
+    /// Paused tasks, indexed by agent ID
+    paused: HashMap<String, ScheduledAgentTask>,
+    /// Logical time at which each session was last served
+    last_served: HashMap<String, u64>,
+    /// Logical clock for fairness
+    clock: u64,
+    /// Next sequence number
+    next_sequence: u64,
+}
+
+impl AgentScheduler {
+    /// Create a scheduler with the given configuration
+    pub fn new(config: SchedulerConfig) -> Self {
+        Self {
+            config,
+            queued: Vec::new(),
+            running: HashMap::new(),
+            paused: HashMap::new(),
+            last_served: HashMap::new(),
+            clock: 0,
+            next_sequence: 0,
+        }
+    }
+
+    /// Get the scheduler configuration
+    pub fn config(&self) -> &SchedulerConfig {
+        &self.config
+    }
+
+    /// Queue a task
+    pub fn enqueue(&mut self, mut task: ScheduledAgentTask) {
+        task.sequence = self.next_sequence;
+        self.next_sequence += 1;
+        task.agent.status = AgentStatus::Queued;
+        debug!(
+            "Queued background agent {} ({:?}) for session {}",
+            task.agent.id, task.priority, task.session_id
+        );
+        self.queued.push(task);
+    }
+
+    /// Take the next task that may run, marking it as running
+    ///
+    /// Highest priority wins; among equal priorities the least recently served
+    /// session wins, then FIFO order. Tasks whose provider is at its cap are skipped.
+    pub fn next_ready(&mut self) -> Option<ScheduledAgentTask> {
+        if self.running.len() >= self.config.max_concurrent {
+            return None;
+        }
+
+        let index = self
+            .queued
+            .iter()
+            .enumerate()
+            .filter(|(_, task)| {
+                self.running_for_provider(&task.provider)
+                    < self.config.provider_limit(&task.provider)
+            })
+            .max_by(|(_, a), (_, b)| {
+                a.priority
+                    .cmp(&b.priority)
+                    .then_with(|| {
+                        self.served_at(&b.session_id)
+                            .cmp(&self.served_at(&a.session_id))
+                    })
+                    .then_with(|| b.sequence.cmp(&a.sequence))
+            })
+            .map(|(index, _)| index)?;
+
+        let mut task = self.queued.remove(index);
+        task.agent.status = AgentStatus::Running;
+
+        self.clock += 1;
+        self.last_served.insert(task.session_id.clone(), self.clock);
+        self.running.insert(task.agent.id.clone(), task.clone());
+
+        Some(task)
+    }
+
+    /// Mark a running task as finished, freeing its slot
+    pub fn complete(&mut self, agent_id: &str) -> Option<ScheduledAgentTask> {
+        self.running.remove(agent_id)
+    }
+
+    /// Pause a queued or running task
+    ///
+    /// A paused running task loses its slot and restarts when resumed.
+    pub fn pause(&mut self, agent_id: &str) -> SessionResult<()> {
+        let mut task = if let Some(task) = self.running.remove(agent_id) {
+            task
+        } else if let Some(index) = self.queued.iter().position(|t| t.agent.id == agent_id) {
+            self.queued.remove(index)
+        } else {
+            return Err(SessionError::AgentError(format!(
+                "Agent is not queued or running: {}",
+                agent_id
+            )));
+        };
+
+        task.agent.status = AgentStatus::Paused;
+        self.paused.insert(agent_id.to_string(), task);
+        Ok(())
+    }
+
+    /// Resume a paused task, returning it to the queue with its original position
+    pub fn resume(&mut self, agent_id: &str) -> SessionResult<()> {
+        let mut task = self.paused.remove(agent_id).ok_or_else(|| {
+            SessionError::AgentError(format!("Agent is not paused: {}", agent_id))
+        })?;
+
+        task.agent.status = AgentStatus::Queued;
+        self.queued.push(task);
+        Ok(())
+    }
+
+    /// Remove a task from the scheduler entirely
+    pub fn remove(&mut self, agent_id: &str) -> Option<ScheduledAgentTask> {
+        if let Some(index) = self.queued.iter().position(|t| t.agent.id == agent_id) {
+            return Some(self.queued.remove(index));
+        }
+        self.running
+            .remove(agent_id)
+            .or_else(|| self.paused.remove(agent_id))
+    }
+
+    /// Iterate over queued and paused tasks
+    pub fn pending_tasks(&self) -> impl Iterator<Item = &ScheduledAgentTask> {
+        self.queued.iter().chain(self.paused.values())
+    }
+
+    /// Number of queued tasks
+    pub fn queued_count(&self) -> usize {
+        self.queued.len()
+    }
+
+    /// Number of running tasks
+    pub fn running_count(&self) -> usize {
+        self.running.len()
+    }
+
+    /// Number of paused tasks
+    pub fn paused_count(&self) -> usize {
+        self.paused.len()
+    }
+
+    /// Check whether the scheduler tracks the agent
+    pub fn contains(&self, agent_id: &str) -> bool {
+        self.running.contains_key(agent_id)
+            || self.paused.contains_key(agent_id)
+            || self.queued.iter().any(|t| t.agent.id == agent_id)
+    }
+
+    /// Save queued, running and paused work to disk
+    ///
+    /// Running tasks are saved as queued so they restart after a restart.
+    pub fn save(&self, path: &Path) -> SessionResult<()> {
+        let mut queued: Vec<ScheduledAgentTask> = self
+            .queued
+            .iter()
+            .chain(self.running.values())
+            .cloned()
+            .map(|mut task| {
+                task.agent.status = AgentStatus::Queued;
+                task
+            })
+            .collect();
+        queued.sort_by_key(|task| task.sequence);
+
+        let mut paused: Vec<ScheduledAgentTask> = self.paused.values().cloned().collect();
+        paused.sort_by_key(|task| task.sequence);
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(
+            path,
+            serde_json::to_string_pretty(&PersistedQueue { queued, paused })?,
+        )?;
+        Ok(())
+    }
+
+    /// Restore queued and paused work from disk
+    ///
+    /// Returns the number of restored tasks. A missing file restores nothing.
+    pub fn load(&mut self, path: &Path) -> SessionResult<usize> {
+        if !path.exists() {
+            return Ok(0);
+        }
+
+        let persisted: PersistedQueue = serde_json::from_str(&fs::read_to_string(path)?)?;
+        let restored = persisted.queued.len() + persisted.paused.len();
+
+        for task in persisted.queued {
+            self.next_sequence = self.next_sequence.max(task.sequence + 1);
+            self.queued.push(task);
+        }
+        for task in persisted.paused {
+            self.next_sequence = self.next_sequence.max(task.sequence + 1);
+            self.paused.insert(task.agent.id.clone(), task);
+        }
+
+        Ok(restored)
+    }
+
+    /// Default location of the persisted queue (~/.ricecoder/sessions/agent_queue.json)
+    pub fn default_queue_path() -> SessionResult<PathBuf> {
+        let home = dirs::home_dir().ok_or_else(|| {
+            SessionError::ConfigError("Could not determine home directory".to_string())
+        })?;
+        Ok(home
+            .join(".ricecoder")
+            .join("sessions")
+            .join("agent_queue.json"))
+    }
+
+    fn running_for_provider(&self, provider: &str) -> usize {
+        self.running
+            .values()
+            .filter(|task| task.provider == provider)
+            .count()
+    }
+
+    fn served_at(&self, session_id: &str) -> u64 {
+        self.last_served.get(session_id).copied().unwrap_or(0)
+    }
+}
+
+impl Default for AgentScheduler {
+    fn default() -> Self {
+        Self::new(SchedulerConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(session: &str, provider: &str, priority: AgentPriority) -> ScheduledAgentTask {
+        ScheduledAgentTask::new(
+            BackgroundAgent::new("test_agent".to_string(), None),
+            session,
+            provider,
+            priority,
+        )
+    }
+
+    #[test]
+    fn test_priority_order() {
+        let mut scheduler = AgentScheduler::default();
+        scheduler.enqueue(task("s1", "openai", AgentPriority::Low));
+        scheduler.enqueue(task("s1", "openai", AgentPriority::Critical));
+
+        let next = scheduler.next_ready().unwrap();
+        assert_eq!(next.priority, AgentPriority::Critical);
+        assert_eq!(next.agent.status, AgentStatus::Running);
+    }
+
+    #[test]
+    fn test_provider_concurrency_cap() {
+        let config = SchedulerConfig::default().with_provider_limit("openai", 1);
+        let mut scheduler = AgentScheduler::new(config);
+        scheduler.enqueue(task("s1", "openai", AgentPriority::High));
+        scheduler.enqueue(task("s1", "openai", AgentPriority::High));
+        scheduler.enqueue(task("s1", "anthropic", AgentPriority::Low));
+
+        let first = scheduler.next_ready().unwrap();
+        assert_eq!(first.provider, "openai");
+        let second = scheduler.next_ready().unwrap();
+        assert_eq!(second.provider, "anthropic");
+        assert!(scheduler.next_ready().is_none());
+
+        scheduler.complete(&first.agent.id);
+        assert_eq!(scheduler.next_ready().unwrap().provider, "openai");
+    }
+
+    #[test]
+    fn test_fair_scheduling_across_sessions() {
+        let config = SchedulerConfig {
+            max_concurrent: 10,
+            default_provider_limit: 10,
+            ..Default::default()
+        };
+        let mut scheduler = AgentScheduler::new(config);
+        scheduler.enqueue(task("s1", "openai", AgentPriority::Normal));
+        scheduler.enqueue(task("s1", "openai", AgentPriority::Normal));
+        scheduler.enqueue(task("s2", "openai", AgentPriority::Normal));
+
+        let order: Vec<String> = std::iter::from_fn(|| scheduler.next_ready())
+            .map(|task| task.session_id)
+            .collect();
+        assert_eq!(order, vec!["s1", "s2", "s1"]);
+    }
+
+    #[test]
+    fn test_pause_and_resume() {
+        let mut scheduler = AgentScheduler::default();
+        let queued = task("s1", "openai", AgentPriority::Normal);
+        let agent_id = queued.agent.id.clone();
+        scheduler.enqueue(queued);
+
+        scheduler.pause(&agent_id).unwrap();
+        assert_eq!(scheduler.paused_count(), 1);
+        assert!(scheduler.next_ready().is_none());
+
+        scheduler.resume(&agent_id).unwrap();
+        assert_eq!(scheduler.next_ready().unwrap().agent.id, agent_id);
+        assert!(scheduler.resume(&agent_id).is_err());
+    }
+
+    #[test]
+    fn test_persistence_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("queue.json");
+
+        let mut scheduler = AgentScheduler::default();
+        scheduler.enqueue(task("s1", "openai", AgentPriority::High));
+        scheduler.enqueue(task("s2", "openai", AgentPriority::Low));
+        let paused = task("s3", "openai", AgentPriority::Normal);
+        let paused_id = paused.agent.id.clone();
+        scheduler.enqueue(paused);
+        scheduler.pause(&paused_id).unwrap();
+        let running = scheduler.next_ready().unwrap();
+        scheduler.save(&path).unwrap();
+
+        let mut restored = AgentScheduler::default();
+        assert_eq!(restored.load(&path).unwrap(), 3);
+        assert_eq!(restored.queued_count(), 2);
+        assert_eq!(restored.paused_count(), 1);
+        assert!(restored.contains(&running.agent.id));
+        assert_eq!(restored.next_ready().unwrap().agent.id, running.agent.id);
+    }
+}
@@ -1,14 +1,22 @@
 //! Background agent management
 
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, future::Future, path::PathBuf, pin::Pin, sync::Arc};
 
 use tokio::{sync::RwLock, task::JoinHandle};
+use tracing::{debug, warn};
 
 use crate::{
+    agent_scheduler::{AgentPriority, AgentScheduler, ScheduledAgentTask, SchedulerConfig},
     error::{SessionError, SessionResult},
     models::{AgentStatus, BackgroundAgent},
 };
 
+/// Session charged for agents started without one
+const DEFAULT_SESSION: &str = "default";
+
+/// Provider charged for agents started without one
+const DEFAULT_PROVIDER: &str = "default";
+
 /// Event emitted when a background agent completes
 #[derive(Debug, Clone)]
 pub struct AgentCompletionEvent {
@@ -29,65 +37,186 @@ pub struct BackgroundAgentManager {
     tasks: Arc<RwLock<HashMap<String, JoinHandle<()>>>>,
     /// Completion events for agents
     completion_events: Arc<RwLock<Vec<AgentCompletionEvent>>>,
+    /// Scheduler for queued agents
+    scheduler: Arc<RwLock<AgentScheduler>>,
+    /// Where the pending queue is persisted (if anywhere)
+    queue_path: Option<PathBuf>,
 }
 
 impl BackgroundAgentManager {
     /// Create a new background agent manager
     pub fn new() -> Self {
+        Self::with_scheduler_config(SchedulerConfig::default())
+    }
+
+    /// Create a background agent manager with a custom scheduler configuration
+    pub fn with_scheduler_config(config: SchedulerConfig) -> Self {
         Self {
             agents: Arc::new(RwLock::new(HashMap::new())),
             tasks: Arc::new(RwLock::new(HashMap::new())),
             completion_events: Arc::new(RwLock::new(Vec::new())),
+            scheduler: Arc::new(RwLock::new(AgentScheduler::new(config))),
+            queue_path: None,
         }
     }
 
-    /// Start a background agent asynchronously
-    pub async fn start_agent(&self, agent: BackgroundAgent) -> SessionResult<String> {
-        let agent_id = agent.id.clone();
-        let agent_type = agent.agent_type.clone();
+    /// Persist pending work to the given file after every scheduling change
+    pub fn with_queue_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.queue_path = Some(path.into());
+        self
+    }
+
+    /// Restore queued and paused agents persisted by a previous run and start dispatching
+    ///
+    /// Returns the number of restored agents.
+    pub async fn restore_queue(&self) -> SessionResult<usize> {
+        let path = match &self.queue_path {
+            Some(path) => path,
+            None => return Ok(0),
+        };
+
+        let restored = {
+            let mut scheduler = self.scheduler.write().await;
+            let restored = scheduler.load(path)?;
+
+            let mut agents = self.agents.write().await;
+            for task in scheduler.pending_tasks() {
+                agents.insert(task.agent.id.clone(), task.agent.clone());
+            }
+            restored
+        };
+
+        debug!("Restored {} background agents from {:?}", restored, path);
+        self.dispatch().await;
+        Ok(restored)
+    }
+
+    /// Submit an agent to the scheduler
+    ///
+    /// The agent runs once a slot is free for its provider, ordered by priority
+    /// and fairness across sessions.
+    pub async fn submit_agent(
+        &self,
+        agent: BackgroundAgent,
+        session_id: impl Into<String>,
+        provider: impl Into<String>,
+        priority: AgentPriority,
+    ) -> SessionResult<String> {
+        let task = ScheduledAgentTask::new(agent, session_id, provider, priority);
+        let agent_id = task.agent.id.clone();
 
-        // Store the agent
         {
+            let mut scheduler = self.scheduler.write().await;
+            scheduler.enqueue(task);
+
             let mut agents = self.agents.write().await;
-            agents.insert(agent_id.clone(), agent.clone());
+            let mut agent = scheduler
+                .pending_tasks()
+                .find(|task| task.agent.id == agent_id)
+                .map(|task| task.agent.clone())
+                .ok_or_else(|| SessionError::AgentError("Failed to queue agent".to_string()))?;
+            agent.status = AgentStatus::Queued;
+            agents.insert(agent_id.clone(), agent);
         }
 
-        // Spawn a task to simulate agent execution
-        let agent_id_clone = agent_id.clone();
-        let agents = Arc::clone(&self.agents);
-        let completion_events = Arc::clone(&self.completion_events);
+        self.persist_queue().await;
+        self.dispatch().await;
+        Ok(agent_id)
+    }
 
-        let task = tokio::spawn(async move {
-            // Simulate agent work
-            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    /// Resume a paused agent
+    pub async fn resume_agent(&self, agent_id: &str) -> SessionResult<()> {
+        self.scheduler.write().await.resume(agent_id)?;
+        self.set_status(agent_id, AgentStatus::Queued).await;
 
-            // Update agent status to completed
-            {
-                let mut agents_lock = agents.write().await;
-                if let Some(agent) = agents_lock.get_mut(&agent_id_clone) {
-                    agent.status = AgentStatus::Completed;
-                    agent.completed_at = Some(chrono::Utc::now());
-                }
-            }
+        self.persist_queue().await;
+        self.dispatch().await;
+        Ok(())
+    }
 
-            // Emit completion event
-            {
-                let mut events = completion_events.write().await;
-                events.push(AgentCompletionEvent {
-                    agent_id: agent_id_clone.clone(),
-                    status: AgentStatus::Completed,
-                    message: Some(format!("Agent {} completed successfully", agent_type)),
-                });
+    /// Number of agents waiting in the scheduler queue
+    pub async fn queued_count(&self) -> usize {
+        self.scheduler.read().await.queued_count()
+    }
+
+    /// Start every queued agent that fits within the concurrency limits
+    fn dispatch(&self) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        Box::pin(async move {
+            loop {
+                let task = match self.scheduler.write().await.next_ready() {
+                    Some(task) => task,
+                    None => break,
+                };
+                self.spawn_scheduled(task).await;
             }
+        })
+    }
+
+    /// Spawn a task for a scheduled agent
+    async fn spawn_scheduled(&self, task: ScheduledAgentTask) {
+        let agent_id = task.agent.id.clone();
+        self.set_status(&agent_id, AgentStatus::Running).await;
+
+        let manager = self.clone();
+        let handle = tokio::spawn(async move {
+            // Simulate agent work
+            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+            manager.scheduler.write().await.complete(&task.agent.id);
+            manager.finish_agent(&task.agent).await;
+            manager.persist_queue().await;
+            manager.dispatch().await;
         });
 
-        // Store the task
+        self.tasks.write().await.insert(agent_id, handle);
+    }
+
+    /// Mark an agent as completed and emit its completion event
+    async fn finish_agent(&self, agent: &BackgroundAgent) {
         {
-            let mut tasks = self.tasks.write().await;
-            tasks.insert(agent_id.clone(), task);
+            let mut agents = self.agents.write().await;
+            if let Some(agent) = agents.get_mut(&agent.id) {
+                agent.status = AgentStatus::Completed;
+                agent.completed_at = Some(chrono::Utc::now());
+            }
         }
 
-        Ok(agent_id)
+        self.completion_events
+            .write()
+            .await
+            .push(AgentCompletionEvent {
+                agent_id: agent.id.clone(),
+                status: AgentStatus::Completed,
+                message: Some(format!("Agent {} completed successfully", agent.agent_type)),
+            });
+    }
+
+    async fn set_status(&self, agent_id: &str, status: AgentStatus) {
+        if let Some(agent) = self.agents.write().await.get_mut(agent_id) {
+            agent.status = status;
+        }
+    }
+
+    async fn persist_queue(&self) {
+        if let Some(path) = &self.queue_path {
+            if let Err(e) = self.scheduler.read().await.save(path) {
+                warn!("Failed to persist background agent queue: {}", e);
+            }
+        }
+    }
+
+    /// Start a background agent with normal priority
+    ///
+    /// The agent goes through the scheduler like any submitted agent, charged to
+    /// the default session and provider; use [`Self::submit_agent`] to set those.
+    pub async fn start_agent(&self, agent: BackgroundAgent) -> SessionResult<String> {
+        self.submit_agent(
+            agent,
+            DEFAULT_SESSION,
+            DEFAULT_PROVIDER,
+            AgentPriority::Normal,
+        )
+        .await
     }
 
     /// Get the status of a background agent
@@ -109,28 +238,38 @@ impl BackgroundAgentManager {
     }
 
     /// Pause a background agent
+    ///
+    /// Scheduled agents can be paused while queued or running; a running agent is
+    /// stopped and restarts from the queue when resumed.
     pub async fn pause_agent(&self, agent_id: &str) -> SessionResult<()> {
-        let mut agents = self.agents.write().await;
-        if let Some(agent) = agents.get_mut(agent_id) {
-            if agent.status == AgentStatus::Running {
-                agent.status = AgentStatus::Cancelled;
-                Ok(())
-            } else {
-                Err(SessionError::AgentError(format!(
-                    "Cannot pause agent in {:?} state",
-                    agent.status
-                )))
-            }
-        } else {
-            Err(SessionError::AgentError(format!(
-                "Agent not found: {}",
-                agent_id
-            )))
+        let status = self.get_agent_status(agent_id).await?;
+        if status != AgentStatus::Running && status != AgentStatus::Queued {
+            return Err(SessionError::AgentError(format!(
+                "Cannot pause agent in {:?} state",
+                status
+            )));
         }
+
+        self.scheduler.write().await.pause(agent_id)?;
+        if let Some(handle) = self.tasks.write().await.remove(agent_id) {
+            handle.abort();
+        }
+        self.set_status(agent_id, AgentStatus::Paused).await;
+
+        self.persist_queue().await;
+        self.dispatch().await;
+        Ok(())
     }
 
     /// Cancel a background agent
     pub async fn cancel_agent(&self, agent_id: &str) -> SessionResult<()> {
+        if self.scheduler.write().await.remove(agent_id).is_some() {
+            if let Some(handle) = self.tasks.write().await.remove(agent_id) {
+                handle.abort();
+            }
+            self.persist_queue().await;
+        }
+
         let mut agents = self.agents.write().await;
         if let Some(agent) = agents.get_mut(agent_id) {
             agent.status = AgentStatus::Cancelled;
@@ -184,7 +323,7 @@ impl BackgroundAgentManager {
     pub async fn wait_for_agent(&self, agent_id: &str) -> SessionResult<AgentStatus> {
         loop {
             let status = self.get_agent_status(agent_id).await?;
-            if status != AgentStatus::Running {
+            if status != AgentStatus::Running && status != AgentStatus::Queued {
                 return Ok(status);
             }
             tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
@@ -267,4 +406,125 @@ mod tests {
         assert!(!events.is_empty());
         assert_eq!(events[0].status, AgentStatus::Completed);
     }
+
+    #[tokio::test]
+    async fn test_start_agent_is_scheduled() {
+        let config = SchedulerConfig {
+            max_concurrent: 1,
+            ..Default::default()
+        };
+        let manager = BackgroundAgentManager::with_scheduler_config(config);
+
+        let first = manager
+            .start_agent(BackgroundAgent::new("a".to_string(), None))
+            .await
+            .unwrap();
+        let second = manager
+            .start_agent(BackgroundAgent::new("b".to_string(), None))
+            .await
+            .unwrap();
+        assert_eq!(
+            manager.get_agent_status(&first).await.unwrap(),
+            AgentStatus::Running
+        );
+        assert_eq!(
+            manager.get_agent_status(&second).await.unwrap(),
+            AgentStatus::Queued
+        );
+
+        manager.pause_agent(&second).await.unwrap();
+        assert_eq!(manager.queued_count().await, 0);
+        manager.resume_agent(&second).await.unwrap();
+        let status = manager.wait_for_agent(&second).await.unwrap();
+        assert_eq!(status, AgentStatus::Completed);
+    }
+
+    #[tokio::test]
+    async fn test_submit_agent_respects_provider_cap() {
+        let config = SchedulerConfig::default().with_provider_limit("openai", 1);
+        let manager = BackgroundAgentManager::with_scheduler_config(config);
+
+        let first = manager
+            .submit_agent(
+                BackgroundAgent::new("a".to_string(), None),
+                "s1",
+                "openai",
+                AgentPriority::Normal,
+            )
+            .await
+            .unwrap();
+        let second = manager
+            .submit_agent(
+                BackgroundAgent::new("b".to_string(), None),
+                "s1",
+                "openai",
+                AgentPriority::Normal,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            manager.get_agent_status(&first).await.unwrap(),
+            AgentStatus::Running
+        );
+        assert_eq!(
+            manager.get_agent_status(&second).await.unwrap(),
+            AgentStatus::Queued
+        );
+
+        let status = manager.wait_for_agent(&second).await.unwrap();
+        assert_eq!(status, AgentStatus::Completed);
+    }
+
+    #[tokio::test]
+    async fn test_pause_and_resume_agent() {
+        let manager = BackgroundAgentManager::new();
+        let agent_id = manager
+            .submit_agent(
+                BackgroundAgent::new("a".to_string(), None),
+                "s1",
+                "openai",
+                AgentPriority::High,
+            )
+            .await
+            .unwrap();
+
+        manager.pause_agent(&agent_id).await.unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(150)).await;
+        assert_eq!(
+            manager.get_agent_status(&agent_id).await.unwrap(),
+            AgentStatus::Paused
+        );
+
+        manager.resume_agent(&agent_id).await.unwrap();
+        let status = manager.wait_for_agent(&agent_id).await.unwrap();
+        assert_eq!(status, AgentStatus::Completed);
+    }
+
+    #[tokio::test]
+    async fn test_queue_survives_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("agent_queue.json");
+        let config = SchedulerConfig {
+            max_concurrent: 0,
+            ..Default::default()
+        };
+
+        let manager = BackgroundAgentManager::with_scheduler_config(config).with_queue_path(&path);
+        let agent_id = manager
+            .submit_agent(
+                BackgroundAgent::new("a".to_string(), None),
+                "s1",
+                "openai",
+                AgentPriority::Normal,
+            )
+            .await
+            .unwrap();
+        assert_eq!(manager.queued_count().await, 1);
+
+        let restarted = BackgroundAgentManager::new().with_queue_path(&path);
+        assert_eq!(restarted.restore_queue().await.unwrap(), 1);
+        let status = restarted.wait_for_agent(&agent_id).await.unwrap();
+        assert_eq!(status, AgentStatus::Completed);
+    }
 }
@@ -43,6 +43,22 @@ pub enum SessionEvent {
         /// Unique session identifier
         session_id: String,
     },
+    /// Older session history was compacted into a summary
+    Compacted {
+        /// Unique session identifier
+        session_id: String,
+        /// Compaction identifier (used to expand the summary)
+        compaction_id: String,
+        /// Whether the compaction was triggered automatically
+        auto: bool,
+    },
+    /// A compaction summary was expanded back into the original messages
+    Expanded {
+        /// Unique session identifier
+        session_id: String,
+        /// Compaction identifier
+        compaction_id: String,
+    },
 }
 
 /// Message-related events
@@ -0,0 +1,521 @@
+//! Automatic context compaction for long-running sessions
+//!
+//! When a session's token usage approaches the model's context limit, older
+//! message ranges are summarized by an AI provider and replaced in the history
+//! by a single summary message. The original messages are kept as a
+//! [`CompactedRange`] (persisted through [`SessionStore`](crate::store::SessionStore))
+//! so a summary can be expanded back into the full conversation on demand.
+//!
+//! # Compaction Layout
+//!
+//! ```text
+//! before: [system] [m1] [m2] ... [m20] [m21] ... [m26]
+//!                  └──── compacted ────┘ └ preserved ┘
+//! after:  [system] [summary(compaction_id)] [m21] ... [m26]
+//! ```
+
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use ricecoder_files::SessionFileTracker;
+use ricecoder_providers::{
+    models::{ChatRequest, Message as ProviderMessage},
+    provider::Provider,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::{
+    error::{SessionError, SessionResult},
+    models::{Message, MessagePart, MessageRole, Session},
+    token_estimator::TokenUsageTracker,
+};
+
+/// Metadata key used to link a summary message to its compaction record
+pub const COMPACTION_ID_KEY: &str = "compaction_id";
+
+/// Default system prompt used when asking a provider for a summary
+const SUMMARY_SYSTEM_PROMPT: &str = "You are summarizing an earlier part of a coding \
+conversation so it can continue within a limited context window. Preserve decisions, \
+requirements, file paths, code identifiers, open questions and pending tasks. Omit \
+pleasantries. Respond with the summary only.";
+
+/// Produces a summary for a range of conversation messages
+#[async_trait]
+pub trait ConversationSummarizer: Send + Sync {
+    /// Summarize the given messages for the given model
+    async fn summarize(&self, messages: &[Message], model: &str) -> SessionResult<String>;
+}
+
+/// Summarizer backed by a ricecoder-providers [`Provider`]
+pub struct ProviderSummarizer {
+    /// Provider used for summarization
+    provider: Arc<dyn Provider>,
+    /// Model override (defaults to the session's model)
+    model: Option<String>,
+    /// Maximum tokens for the generated summary
+    max_summary_tokens: usize,
+}
+
+impl ProviderSummarizer {
+    /// Create a summarizer that uses the session's own model
+    pub fn new(provider: Arc<dyn Provider>) -> Self {
+        Self {
+            provider,
+            model: None,
+            max_summary_tokens: 1024,
+        }
+    }
+
+    /// Use a specific (typically cheaper) model for summaries
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    /// Set the maximum number of tokens for generated summaries
+    pub fn with_max_summary_tokens(mut self, max_tokens: usize) -> Self {
+        self.max_summary_tokens = max_tokens;
+        self
+    }
+
+    /// Render messages as a plain-text transcript for the summarization prompt
+    fn transcript(messages: &[Message]) -> String {
+        messages
+            .iter()
+            .map(|msg| format!("{}: {}", msg.role, msg.full_content()))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+#[async_trait]
+impl ConversationSummarizer for ProviderSummarizer {
+    async fn summarize(&self, messages: &[Message], model: &str) -> SessionResult<String> {
+        let request = ChatRequest {
+            model: self.model.clone().unwrap_or_else(|| model.to_string()),
+            messages: vec![
+                ProviderMessage {
+                    role: "system".to_string(),
+                    content: SUMMARY_SYSTEM_PROMPT.to_string(),
+                },
+                ProviderMessage {
+                    role: "user".to_string(),
+                    content: Self::transcript(messages),
+                },
+            ],
+            temperature: Some(0.2),
+            max_tokens: Some(self.max_summary_tokens),
+            stream: false,
+            top_p: None,
+            stop: None,
+            seed: None,
+            reasoning_effort: None,
+        };
+
+        let response =
+            self.provider.chat(request).await.map_err(|e| {
+                SessionError::CompactionFailed(format!("Summarization failed: {}", e))
+            })?;
+
+        let summary = response.content.trim().to_string();
+        if summary.is_empty() {
+            return Err(SessionError::CompactionFailed(
+                "Provider returned an empty summary".to_string(),
+            ));
+        }
+
+        Ok(summary)
+    }
+}
+
+/// Configuration for automatic compaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompactionConfig {
+    /// Usage percentage of the token limit at which compaction triggers
+    pub trigger_percentage: f64,
+    /// Number of most recent messages that are never compacted
+    pub preserve_recent_messages: usize,
+    /// Minimum number of messages required to form a compaction range
+    pub min_messages_to_compact: usize,
+}
+
+impl Default for CompactionConfig {
+    fn default() -> Self {
+        Self {
+            trigger_percentage: 80.0,
+            preserve_recent_messages: 6,
+            min_messages_to_compact: 4,
+        }
+    }
+}
+
+/// Record of a single compaction event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompactionRecord {
+    /// Unique compaction identifier
+    pub id: String,
+    /// Session the compaction belongs to
+    pub session_id: String,
+    /// ID of the summary message that replaced the range
+    pub summary_message_id: String,
+    /// IDs of the original messages, in order
+    pub original_message_ids: Vec<String>,
+    /// Whether the compaction was triggered automatically
+    pub auto: bool,
+    /// Estimated tokens of the original range
+    pub tokens_before: usize,
+    /// Estimated tokens of the summary
+    pub tokens_after: usize,
+    /// When the compaction happened
+    pub created_at: DateTime<Utc>,
+}
+
+impl CompactionRecord {
+    /// Tokens freed by this compaction
+    pub fn tokens_saved(&self) -> usize {
+        self.tokens_before.saturating_sub(self.tokens_after)
+    }
+}
+
+/// Original messages of a compacted range, kept for on-demand expansion
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompactedRange {
+    /// Compaction record
+    pub record: CompactionRecord,
+    /// Original messages replaced by the summary
+    pub messages: Vec<Message>,
+}
+
+/// Summarizes older message ranges when sessions approach their token limit
+pub struct CompactionManager {
+    /// Compaction configuration
+    config: CompactionConfig,
+    /// Summarizer used to produce summaries
+    summarizer: Arc<dyn ConversationSummarizer>,
+    /// Tracker of file contents in session contexts, reset on compaction
+    file_tracker: Option<Arc<SessionFileTracker>>,
+}
+
+impl CompactionManager {
+    /// Create a compaction manager with default configuration
+    pub fn new(summarizer: Arc<dyn ConversationSummarizer>) -> Self {
+        Self::with_config(summarizer, CompactionConfig::default())
+    }
+
+    /// Create a compaction manager with custom configuration
+    pub fn with_config(
+        summarizer: Arc<dyn ConversationSummarizer>,
+        config: CompactionConfig,
+    ) -> Self {
+        Self {
+            config,
+            summarizer,
+            file_tracker: None,
+        }
+    }
+
+    /// Forget the session's file reads in `tracker` whenever it is compacted
+    ///
+    /// File contents in the compacted range leave the context, so later
+    /// reads must return them again instead of an "unchanged" marker.
+    pub fn with_file_tracker(mut self, tracker: Arc<SessionFileTracker>) -> Self {
+        self.file_tracker = Some(tracker);
+        self
+    }
+
+    /// Get the compaction configuration
+    pub fn config(&self) -> &CompactionConfig {
+        &self.config
+    }
+
+    /// Check whether token usage warrants compaction
+    pub fn should_compact(&self, tracker: &TokenUsageTracker) -> bool {
+        tracker.token_limit > 0 && tracker.usage_percentage() >= self.config.trigger_percentage
+    }
+
+    /// Determine the history range eligible for compaction
+    ///
+    /// Leading system messages and the most recent messages are preserved.
+    pub fn compactable_range(&self, session: &Session) -> Option<std::ops::Range<usize>> {
+        let start = session
+            .history
+            .iter()
+            .position(|msg| msg.role != MessageRole::System)?;
+        let end = session
+            .history
+            .len()
+            .saturating_sub(self.config.preserve_recent_messages);
+
+        if end <= start || end - start < self.config.min_messages_to_compact {
+            return None;
+        }
+
+        Some(start..end)
+    }
+
+    /// Compact the session's older messages into a summary message
+    ///
+    /// `estimate_tokens` is used to record token counts before and after compaction.
+    /// Returns `None` when there is not enough history to compact.
+    pub async fn compact<F>(
+        &self,
+        session: &mut Session,
+        auto: bool,
+        mut estimate_tokens: F,
+    ) -> SessionResult<Option<CompactedRange>>
+    where
+        F: FnMut(&[Message]) -> usize,
+    {
+        let range = match self.compactable_range(session) {
+            Some(range) => range,
+            None => {
+                debug!("Session {} has nothing to compact", session.id);
+                return Ok(None);
+            }
+        };
+
+        let originals: Vec<Message> = session.history[range.clone()].to_vec();
+        let summary = self
+            .summarizer
+            .summarize(&originals, &session.context.model)
+            .await?;
+
+        let compaction_id = Uuid::new_v4().to_string();
+        let summary_message = Self::summary_message(&session.id, &compaction_id, summary, auto);
+
+        let record = CompactionRecord {
+            id: compaction_id,
+            session_id: session.id.clone(),
+            summary_message_id: summary_message.id.clone(),
+            original_message_ids: originals.iter().map(|msg| msg.id.clone()).collect(),
+            auto,
+            tokens_before: estimate_tokens(&originals),
+            tokens_after: estimate_tokens(std::slice::from_ref(&summary_message)),
+            created_at: Utc::now(),
+        };
+
+        session
+            .history
+            .splice(range, std::iter::once(summary_message));
+        session.updated_at = Utc::now();
+
+        if let Some(tracker) = &self.file_tracker {
+            if let Err(e) = tracker.forget_context(&session.id) {
+                warn!(
+                    "Failed to reset file reads for session {}: {}",
+                    session.id, e
+                );
+            }
+        }
+
+        info!(
+            "Compacted {} messages in session {} ({} -> {} tokens)",
+            record.original_message_ids.len(),
+            session.id,
+            record.tokens_before,
+            record.tokens_after
+        );
+
+        Ok(Some(CompactedRange {
+            record,
+            messages: originals,
+        }))
+    }
+
+    /// Replace a summary message with the original messages it summarized
+    pub fn expand(session: &mut Session, compacted: &CompactedRange) -> SessionResult<()> {
+        let index = session
+            .history
+            .iter()
+            .position(|msg| msg.id == compacted.record.summary_message_id)
+            .ok_or_else(|| {
+                SessionError::NotFound(format!(
+                    "Summary message for compaction {} not found in session {}",
+                    compacted.record.id, session.id
+                ))
+            })?;
+
+        session
+            .history
+            .splice(index..=index, compacted.messages.iter().cloned());
+        session.updated_at = Utc::now();
+
+        Ok(())
+    }
+
+    /// Get the compaction ID if the message is a compaction summary
+    pub fn compaction_id(message: &Message) -> Option<&str> {
+        message.parts.iter().find_map(|part| match part {
+            MessagePart::Compaction { id, .. } => id.as_deref(),
+            _ => None,
+        })
+    }
+
+    /// Build the summary message that replaces a compacted range
+    fn summary_message(
+        session_id: &str,
+        compaction_id: &str,
+        summary: String,
+        auto: bool,
+    ) -> Message {
+        let mut message = Message::new_empty(MessageRole::Assistant);
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            COMPACTION_ID_KEY.to_string(),
+            Value::String(compaction_id.to_string()),
+        );
+
+        message.parts.push(MessagePart::Compaction {
+            id: Some(compaction_id.to_string()),
+            session_id: Some(session_id.to_string()),
+            message_id: Some(message.id.clone()),
+            auto,
+        });
+        message.parts.push(MessagePart::Text {
+            id: Some(Uuid::new_v4().to_string()),
+            session_id: Some(session_id.to_string()),
+            message_id: Some(message.id.clone()),
+            text: summary,
+            synthetic: Some(true),
+            ignored: None,
+            time: None,
+            metadata: Some(metadata),
+        });
+
+        message
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{SessionContext, SessionMode};
+
+    struct FixedSummarizer;
+
+    #[async_trait]
+    impl ConversationSummarizer for FixedSummarizer {
+        async fn summarize(&self, messages: &[Message], _model: &str) -> SessionResult<String> {
+            Ok(format!("summary of {} messages", messages.len()))
+        }
+    }
+
+    fn session_with_history(count: usize) -> Session {
+        let mut session = Session::new(
+            "test".to_string(),
+            SessionContext::new("openai".to_string(), "gpt-4".to_string(), SessionMode::Chat),
+        );
+        session.history.push(Message::new(
+            MessageRole::System,
+            "system prompt".to_string(),
+        ));
+        for i in 0..count {
+            let role = if i % 2 == 0 {
+                MessageRole::User
+            } else {
+                MessageRole::Assistant
+            };
+            session
+                .history
+                .push(Message::new(role, format!("message {}", i)));
+        }
+        session
+    }
+
+    fn manager() -> CompactionManager {
+        CompactionManager::with_config(
+            Arc::new(FixedSummarizer),
+            CompactionConfig {
+                trigger_percentage: 80.0,
+                preserve_recent_messages: 2,
+                min_messages_to_compact: 3,
+            },
+        )
+    }
+
+    #[test]
+    fn test_should_compact() {
+        let manager = manager();
+        let mut tracker = crate::token_estimator::TokenEstimator::new()
+            .create_usage_tracker("gpt-4")
+            .unwrap();
+        tracker.token_limit = 1000;
+
+        tracker.record_prompt(500);
+        assert!(!manager.should_compact(&tracker));
+
+        tracker.record_prompt(300);
+        assert!(manager.should_compact(&tracker));
+    }
+
+    #[test]
+    fn test_compactable_range_preserves_system_and_recent() {
+        let manager = manager();
+
+        assert_eq!(
+            manager.compactable_range(&session_with_history(6)),
+            Some(1..5)
+        );
+        assert_eq!(manager.compactable_range(&session_with_history(4)), None);
+    }
+
+    #[tokio::test]
+    async fn test_compact_and_expand_roundtrip() {
+        let manager = manager();
+        let mut session = session_with_history(6);
+        let original_ids: Vec<String> = session.history.iter().map(|m| m.id.clone()).collect();
+
+        let compacted = manager
+            .compact(&mut session, true, |messages| messages.len() * 10)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(session.history.len(), 4);
+        assert_eq!(compacted.messages.len(), 4);
+        assert_eq!(compacted.record.tokens_before, 40);
+        assert_eq!(compacted.record.tokens_saved(), 30);
+
+        let summary = &session.history[1];
+        assert_eq!(
+            CompactionManager::compaction_id(summary),
+            Some(compacted.record.id.as_str())
+        );
+        assert_eq!(summary.content(), "summary of 4 messages");
+
+        CompactionManager::expand(&mut session, &compacted).unwrap();
+        let restored_ids: Vec<String> = session.history.iter().map(|m| m.id.clone()).collect();
+        assert_eq!(restored_ids, original_ids);
+    }
+
+    #[tokio::test]
+    async fn test_compact_without_enough_history() {
+        let manager = manager();
+        let mut session = session_with_history(3);
+
+        let result = manager.compact(&mut session, false, |_| 0).await.unwrap();
+        assert!(result.is_none());
+        assert_eq!(session.history.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_compact_forgets_file_reads() {
+        let tracker = Arc::new(SessionFileTracker::new());
+        let manager = manager().with_file_tracker(tracker.clone());
+        let mut session = session_with_history(6);
+        let path = std::path::PathBuf::from("/test/file.txt");
+        let mtime = std::time::SystemTime::now();
+        tracker
+            .record_context_read(&session.id, &path, mtime, 42, 0..10)
+            .unwrap();
+
+        manager.compact(&mut session, true, |_| 0).await.unwrap();
+
+        let record = tracker.last_read(&session.id, &path).unwrap().unwrap();
+        assert!(!record.covers(42, &(0..10)));
+    }
+}
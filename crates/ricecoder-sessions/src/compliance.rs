@@ -45,6 +45,8 @@ impl ComplianceManager {
                     enable_data_purging: true,
                     enable_data_export: true,
                 },
+                archive_retention_days: None,
+                part_retention: Vec::new(),
             },
             privacy_settings: PrivacySettings {
                 enable_differential_privacy: true,
@@ -210,7 +212,7 @@ impl ComplianceManager {
         // Log compliance event
         self.log_compliance_event(ComplianceEvent {
             id: Uuid::new_v4().to_string(),
-            event_type: ComplianceEventType::RetentionViolation, // Using existing type, could add DataErased
+            event_type: ComplianceEventType::DataErased,
             alert_level: ComplianceAlertLevel::Info,
             user_id: Some(request.user_id.clone()),
             session_id: None,
@@ -271,7 +273,7 @@ impl ComplianceManager {
         // Log compliance event
         self.log_compliance_event(ComplianceEvent {
             id: Uuid::new_v4().to_string(),
-            event_type: ComplianceEventType::RetentionViolation,
+            event_type: ComplianceEventType::RetentionEnforced,
             alert_level: if cleaned_items > 0 {
                 ComplianceAlertLevel::Warning
             } else {
@@ -480,6 +482,9 @@ impl ComplianceManager {
                     ComplianceEventType::AuditFailure => {
                         ricecoder_security::audit::AuditEventType::SystemAccess
                     }
+                    ComplianceEventType::RetentionEnforced | ComplianceEventType::DataErased => {
+                        ricecoder_security::audit::AuditEventType::LogRetentionCleanup
+                    }
                 },
                 user_id: event.user_id,
                 session_id: event.session_id,
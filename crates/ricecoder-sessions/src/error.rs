@@ -82,6 +82,10 @@ pub enum SessionError {
     /// Snapshots are disabled
     #[error("Snapshots are disabled")]
     SnapshotDisabled,
+
+    /// Context compaction failed
+    #[error("Compaction failed: {0}")]
+    CompactionFailed(String),
 }
 
 impl From<ricecoder_security::SecurityError> for SessionError {
@@ -3,8 +3,10 @@
 //! This module provides multi-session support with persistence, sharing, and background agent execution.
 //! Sessions allow developers to run multiple agents in parallel, persist session state, and share sessions with teammates.
 
+pub mod agent_scheduler;
 pub mod background_agent;
 pub mod bus;
+pub mod compaction;
 pub mod compliance;
 pub mod context;
 pub mod error;
@@ -13,6 +15,7 @@ pub mod manager;
 pub mod models;
 pub mod performance_monitor;
 pub mod processor;
+pub mod retention;
 pub mod retry_policy;
 pub mod router;
 pub mod runtime_state;
@@ -26,8 +29,13 @@ pub mod token_estimator;
 pub mod tui_session_manager;
 
 // Re-export commonly used types
+pub use agent_scheduler::{AgentPriority, AgentScheduler, ScheduledAgentTask, SchedulerConfig};
 pub use background_agent::BackgroundAgentManager;
 pub use bus::{BusEvent, EventBus, MessageEvent, SessionEvent, ToolEvent};
+pub use compaction::{
+    CompactedRange, CompactionConfig, CompactionManager, CompactionRecord,
+    ConversationSummarizer, ProviderSummarizer,
+};
 pub use compliance::ComplianceManager;
 pub use context::ContextManager;
 pub use error::{SessionError, SessionResult};
@@ -38,13 +46,19 @@ pub use models::{
     ComplianceEventType, DataErasureRequest, DataExportFormat, DataExportRequest,
     DataMinimizationSettings, DataRetentionPolicy, DataType, EnterpriseSessionAnalytics,
     ErasureReason, FileReferencePart, ImagePart, Message, MessageMetadata, MessagePart,
-    MessageRole, PrivacySettings, Session, SessionContext, SessionMode, SessionStatus,
-    SharingTrendPoint, ToolInvocationPart, ToolResultPart, ToolStatus,
+    MessageRole, PartRetentionRule, PrivacySettings, Session, SessionContext, SessionMode,
+    SessionStatus, SharingTrendPoint, ToolInvocationPart, ToolResultPart, ToolStatus,
 };
 pub use performance_monitor::{
     SessionMetrics, SessionPerformanceMonitor, SessionPerformanceSummary,
 };
-pub use processor::{FinishReason, ProcessResult, SessionProcessor, StreamEvent, ToolState};
+pub use processor::{
+    FinishReason, ProcessResult, SessionProcessor, StreamEvent, ToolCallMemo, ToolMemoConfig,
+    ToolState,
+};
+pub use retention::{
+    ErasureReport, RetentionEngine, RetentionReport, RetentionSchedule, SESSION_USER_KEY,
+};
 pub use retry_policy::{RetryPolicy, RetryableError};
 pub use router::SessionRouter;
 pub use runtime_state::{RuntimeStateEvent, RuntimeStateManager, RuntimeStatus};
@@ -53,7 +67,10 @@ pub use share::{
     DataClassification, EnterpriseShareMetrics, EnterpriseSharingPolicy, SessionShare,
     ShareAnalyticsData, SharePermissions, ShareService,
 };
-pub use snapshot::{FileDiff, SnapshotManager, SnapshotPatch};
+pub use snapshot::{
+    FileDiff, RestoreOutcome, RestorePoint, RestorePointFile, SnapshotDiff, SnapshotManager,
+    SnapshotPatch,
+};
 pub use store::{
     EnterpriseBackupInfo, GarbageCollectionConfig, GarbageCollectionResult, SessionStore,
 };
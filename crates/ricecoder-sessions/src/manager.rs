@@ -1,12 +1,17 @@
 //! Session manager for lifecycle management and session switching
 
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 use chrono::{Duration, Utc};
+use ricecoder_providers::TokenCounter;
 use tracing::{debug, error, warn};
 
 use crate::{
     bus::{BusEvent, EventBus, SessionEvent},
+    compaction::{CompactedRange, CompactionManager, CompactionRecord},
     error::{SessionError, SessionResult},
     models::{MessagePart, Session, SessionContext},
     share::ShareService,
@@ -37,6 +42,8 @@ pub struct SessionManager {
     session_children: HashMap<String, Vec<String>>,
     /// Session store for disk persistence
     store: Option<SessionStore>,
+    /// Original messages of compacted ranges, per session
+    compacted_ranges: HashMap<String, Vec<CompactedRange>>,
 }
 
 impl SessionManager {
@@ -65,6 +72,7 @@ impl SessionManager {
             snapshot_manager: SnapshotManager::disabled(),
             session_children: HashMap::new(),
             store,
+            compacted_ranges: HashMap::new(),
         };
 
         // Load existing sessions from disk synchronously at startup
@@ -381,6 +389,11 @@ impl SessionManager {
             .estimate_tokens(content, Some(&session.context.model))
     }
 
+    /// Count tokens with a shared counter, e.g. one with configured tokenizer files
+    pub fn set_token_counter(&mut self, counter: Arc<TokenCounter>) {
+        self.token_estimator.set_token_counter(counter);
+    }
+
     /// Estimate tokens for content using a specific model
     pub fn estimate_tokens_with_model(
         &mut self,
@@ -704,6 +717,136 @@ impl SessionManager {
         Ok(children)
     }
 
+    // === Context compaction ===
+
+    /// Compact a session's older history if its token usage is near the limit
+    pub async fn compact_if_needed(
+        &mut self,
+        session_id: &str,
+        compactor: &CompactionManager,
+    ) -> SessionResult<Option<CompactionRecord>> {
+        let needed = self
+            .token_trackers
+            .get(session_id)
+            .map(|tracker| compactor.should_compact(tracker))
+            .unwrap_or(false);
+
+        if !needed {
+            return Ok(None);
+        }
+
+        self.compact_session(session_id, compactor, true).await
+    }
+
+    /// Compact a session's older history into a summary message
+    ///
+    /// The original messages stay retrievable through [`Self::expand_compaction`].
+    pub async fn compact_session(
+        &mut self,
+        session_id: &str,
+        compactor: &CompactionManager,
+        auto: bool,
+    ) -> SessionResult<Option<CompactionRecord>> {
+        let mut session = self.get_session(session_id)?;
+        let model = session.context.model.clone();
+        let estimator = &mut self.token_estimator;
+
+        let compacted = compactor
+            .compact(&mut session, auto, |messages| {
+                estimator
+                    .estimate_conversation_tokens(messages, Some(&model))
+                    .map(|estimate| estimate.tokens)
+                    .unwrap_or(0)
+            })
+            .await?;
+
+        let compacted = match compacted {
+            Some(compacted) => compacted,
+            None => return Ok(None),
+        };
+        let record = compacted.record.clone();
+
+        if let Some(ref store) = self.store {
+            if let Err(e) = store.save_compaction(&compacted).await {
+                warn!(
+                    "Failed to persist compaction {} for session {}: {}",
+                    record.id, session_id, e
+                );
+            }
+        }
+
+        if let Some(tracker) = self.token_trackers.get_mut(session_id) {
+            tracker.record_compaction(record.tokens_saved());
+        }
+
+        self.compacted_ranges
+            .entry(session_id.to_string())
+            .or_default()
+            .push(compacted);
+        self.persist_session(&session);
+        self.sessions.insert(session_id.to_string(), session);
+
+        self.event_bus
+            .publish(BusEvent::Session(SessionEvent::Compacted {
+                session_id: session_id.to_string(),
+                compaction_id: record.id.clone(),
+                auto,
+            }));
+
+        Ok(Some(record))
+    }
+
+    /// Expand a compaction summary back into the original messages
+    pub async fn expand_compaction(
+        &mut self,
+        session_id: &str,
+        compaction_id: &str,
+    ) -> SessionResult<Session> {
+        let mut session = self.get_session(session_id)?;
+
+        let in_memory = self
+            .compacted_ranges
+            .get(session_id)
+            .and_then(|ranges| ranges.iter().find(|c| c.record.id == compaction_id))
+            .cloned();
+        let compacted = match (in_memory, &self.store) {
+            (Some(compacted), _) => compacted,
+            (None, Some(store)) => store.load_compaction(session_id, compaction_id).await?,
+            (None, None) => {
+                return Err(SessionError::NotFound(format!(
+                    "Compaction not found: {}",
+                    compaction_id
+                )))
+            }
+        };
+
+        CompactionManager::expand(&mut session, &compacted)?;
+
+        self.persist_session(&session);
+        self.sessions.insert(session_id.to_string(), session.clone());
+
+        self.event_bus
+            .publish(BusEvent::Session(SessionEvent::Expanded {
+                session_id: session_id.to_string(),
+                compaction_id: compaction_id.to_string(),
+            }));
+
+        Ok(session)
+    }
+
+    /// List compaction events recorded for a session
+    pub async fn list_compactions(&self, session_id: &str) -> SessionResult<Vec<CompactionRecord>> {
+        if let Some(ref store) = self.store {
+            return store.list_compactions(session_id).await;
+        }
+
+        Ok(self
+            .compacted_ranges
+            .get(session_id)
+            .map(|ranges| ranges.iter().map(|c| c.record.clone()).collect())
+            .unwrap_or_default())
+    }
+
     // === GAP 3: Sharing integration ===
 
     /// Share a session
@@ -1089,6 +1089,10 @@ impl BackgroundAgent {
 /// Status of a background agent
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AgentStatus {
+    /// Agent is waiting in the scheduler queue
+    Queued,
+    /// Agent was paused and will not be scheduled until resumed
+    Paused,
     /// Agent is currently running
     Running,
     /// Agent has completed successfully
@@ -1112,6 +1116,10 @@ pub enum ComplianceEventType {
     EncryptionViolation,
     /// Audit logging failure
     AuditFailure,
+    /// Data retention policy applied to stored sessions
+    RetentionEnforced,
+    /// User data erased on request
+    DataErased,
 }
 
 /// Enterprise compliance alert levels
@@ -1195,6 +1203,21 @@ pub struct DataRetentionPolicy {
     pub auto_delete_expired_data: bool,
     /// Data minimization settings
     pub data_minimization: DataMinimizationSettings,
+    /// Days after its last update that an archived session is purged for good
+    #[serde(default)]
+    pub archive_retention_days: Option<u32>,
+    /// Message content stripped from sessions once it is old enough
+    #[serde(default)]
+    pub part_retention: Vec<PartRetentionRule>,
+}
+
+/// Strip one kind of message content after a retention period
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartRetentionRule {
+    /// Kind of message content to strip
+    pub data_type: DataType,
+    /// Days a message keeps this content
+    pub retention_days: u32,
 }
 
 /// Data minimization settings for GDPR compliance
@@ -1283,10 +1306,54 @@ pub enum DataType {
     SharingHistory,
     /// User preferences
     UserPreferences,
+    /// Tool calls and their output within messages
+    ToolOutputs,
+    /// Model reasoning within messages
+    Reasoning,
+    /// Files attached to or referenced by messages
+    FileAttachments,
+    /// Images within messages
+    Images,
     /// All data types
     All,
 }
 
+impl DataType {
+    /// Whether a message part holds this kind of data
+    pub fn matches_part(&self, part: &MessagePart) -> bool {
+        match self {
+            DataType::ToolOutputs => matches!(
+                part,
+                MessagePart::Tool { .. }
+                    | MessagePart::ToolInvocation(_)
+                    | MessagePart::ToolResult(_)
+            ),
+            DataType::Reasoning => matches!(part, MessagePart::Reasoning { .. }),
+            DataType::FileAttachments => matches!(
+                part,
+                MessagePart::File { .. } | MessagePart::FileReference(_)
+            ),
+            DataType::Images => matches!(part, MessagePart::Image(_)),
+            DataType::All => true,
+            DataType::Sessions
+            | DataType::AuditLogs
+            | DataType::SharingHistory
+            | DataType::UserPreferences => false,
+        }
+    }
+
+    /// Whether this is a kind of content stored inside messages
+    pub fn is_message_content(&self) -> bool {
+        matches!(
+            self,
+            DataType::ToolOutputs
+                | DataType::Reasoning
+                | DataType::FileAttachments
+                | DataType::Images
+        )
+    }
+}
+
 /// Privacy-preserving session handling settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PrivacySettings {
@@ -0,0 +1,396 @@
+//! Tool call memoization
+//!
+//! Agent loops often repeat the same read or search. [`ToolCallMemo`] keeps
+//! the results of side-effect-free tools for a session and serves them again
+//! while the call's inputs are unchanged: files named in the arguments must
+//! still have the same size and modification time, and no tool that may have
+//! side effects can have run since.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// Tools whose results are never reused, whatever the configuration says
+const NEVER_MEMOIZED: &[&str] = &["bash", "shell"];
+
+/// Tools memoized by default
+const DEFAULT_MEMOIZED: &[&str] = &["read", "glob", "grep", "list", "search"];
+
+/// Argument keys that name files or directories the result depends on
+const PATH_KEYS: &[&str] = &[
+    "path",
+    "paths",
+    "file",
+    "file_path",
+    "filePath",
+    "dir",
+    "directory",
+];
+
+/// Memoization settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolMemoConfig {
+    /// Whether results are reused at all
+    pub enabled: bool,
+    /// Per-tool switch; tools not listed are not memoized
+    pub tools: HashMap<String, bool>,
+    /// Maximum number of cached results; the oldest are dropped first
+    pub max_entries: usize,
+}
+
+impl Default for ToolMemoConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            tools: DEFAULT_MEMOIZED
+                .iter()
+                .map(|tool| (tool.to_string(), true))
+                .collect(),
+            max_entries: 256,
+        }
+    }
+}
+
+impl ToolMemoConfig {
+    /// Turn memoization on or off for a tool
+    pub fn with_tool(mut self, tool: impl Into<String>, enabled: bool) -> Self {
+        self.tools.insert(tool.into(), enabled);
+        self
+    }
+
+    /// Whether results of `tool` may be reused
+    ///
+    /// Shell tools never are: their output depends on more than their
+    /// arguments.
+    pub fn is_memoized(&self, tool: &str) -> bool {
+        self.enabled
+            && !NEVER_MEMOIZED.contains(&tool)
+            && self.tools.get(tool).copied().unwrap_or(false)
+    }
+}
+
+/// Size and modification time of a file when a call started
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FileStamp {
+    len: u64,
+    modified: Option<SystemTime>,
+}
+
+impl FileStamp {
+    fn of(path: &Path) -> Option<Self> {
+        let metadata = std::fs::metadata(path).ok()?;
+        Some(Self {
+            len: metadata.len(),
+            modified: metadata.modified().ok(),
+        })
+    }
+}
+
+/// Inputs of a call that missed the cache, captured when it started
+#[derive(Debug, Clone)]
+pub struct MemoTicket {
+    key: String,
+    inputs: Vec<(PathBuf, Option<FileStamp>)>,
+    generation: u64,
+}
+
+/// Outcome of looking up a tool call
+#[derive(Debug, Clone)]
+pub enum MemoLookup {
+    /// An identical call with unchanged inputs already ran
+    Hit(String),
+    /// Not cached yet; store the result with this ticket once it arrives
+    Miss(MemoTicket),
+    /// The tool is not memoized
+    Skip,
+}
+
+/// Cache hit statistics
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoStats {
+    /// Calls served from the cache
+    pub hits: u64,
+    /// Memoizable calls that had to run
+    pub misses: u64,
+    /// Results currently cached
+    pub entries: usize,
+}
+
+#[derive(Debug)]
+struct Entry {
+    output: String,
+    inputs: Vec<(PathBuf, Option<FileStamp>)>,
+}
+
+#[derive(Debug, Default)]
+struct Entries {
+    results: HashMap<String, Entry>,
+    order: VecDeque<String>,
+    generation: u64,
+    hits: u64,
+    misses: u64,
+}
+
+/// Session-wide cache of tool results
+///
+/// Shared between the processors of a session so repeats are caught across
+/// messages, not only within one response.
+#[derive(Debug)]
+pub struct ToolCallMemo {
+    config: ToolMemoConfig,
+    entries: Mutex<Entries>,
+}
+
+impl Default for ToolCallMemo {
+    fn default() -> Self {
+        Self::new(ToolMemoConfig::default())
+    }
+}
+
+impl ToolCallMemo {
+    /// Create an empty cache
+    pub fn new(config: ToolMemoConfig) -> Self {
+        Self {
+            config,
+            entries: Mutex::new(Entries::default()),
+        }
+    }
+
+    /// Get the configuration
+    pub fn config(&self) -> &ToolMemoConfig {
+        &self.config
+    }
+
+    /// Look up a call that is about to run
+    ///
+    /// Calls to tools that are not memoized may change anything, so they
+    /// clear the cache.
+    pub fn lookup(&self, tool: &str, input: &Value) -> MemoLookup {
+        let mut entries = self.entries.lock().unwrap();
+        if !self.config.is_memoized(tool) {
+            entries.clear();
+            return MemoLookup::Skip;
+        }
+
+        let key = call_key(tool, input);
+        if let Some(entry) = entries.results.get(&key) {
+            let unchanged = entry
+                .inputs
+                .iter()
+                .all(|(path, stamp)| FileStamp::of(path) == *stamp);
+            if unchanged {
+                let output = entry.output.clone();
+                entries.hits += 1;
+                return MemoLookup::Hit(output);
+            }
+            entries.remove(&key);
+        }
+
+        entries.misses += 1;
+        let inputs = input_paths(input)
+            .into_iter()
+            .map(|path| {
+                let stamp = FileStamp::of(&path);
+                (path, stamp)
+            })
+            .collect();
+        MemoLookup::Miss(MemoTicket {
+            key,
+            inputs,
+            generation: entries.generation,
+        })
+    }
+
+    /// Cache the result of a call that missed
+    ///
+    /// Dropped if the cache was cleared while the call ran, since the result
+    /// may predate a side effect.
+    pub fn store(&self, ticket: MemoTicket, output: &str) {
+        let mut entries = self.entries.lock().unwrap();
+        if ticket.generation != entries.generation || self.config.max_entries == 0 {
+            return;
+        }
+
+        entries.remove(&ticket.key);
+        while entries.order.len() >= self.config.max_entries {
+            if let Some(oldest) = entries.order.pop_front() {
+                entries.results.remove(&oldest);
+            }
+        }
+        entries.order.push_back(ticket.key.clone());
+        entries.results.insert(
+            ticket.key,
+            Entry {
+                output: output.to_string(),
+                inputs: ticket.inputs,
+            },
+        );
+    }
+
+    /// Drop every cached result
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+
+    /// Get cache statistics
+    pub fn stats(&self) -> MemoStats {
+        let entries = self.entries.lock().unwrap();
+        MemoStats {
+            hits: entries.hits,
+            misses: entries.misses,
+            entries: entries.results.len(),
+        }
+    }
+}
+
+impl Entries {
+    fn clear(&mut self) {
+        self.results.clear();
+        self.order.clear();
+        self.generation += 1;
+    }
+
+    fn remove(&mut self, key: &str) {
+        if self.results.remove(key).is_some() {
+            self.order.retain(|k| k != key);
+        }
+    }
+}
+
+/// Cache key: tool name plus arguments with object keys sorted
+fn call_key(tool: &str, input: &Value) -> String {
+    let mut key = format!("{}:", tool);
+    write_canonical(input, &mut key);
+    key
+}
+
+fn write_canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            out.push('{');
+            for (i, key) in keys.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical(&map[key], out);
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        other => out.push_str(&other.to_string()),
+    }
+}
+
+/// Paths named by the call's arguments
+fn input_paths(input: &Value) -> Vec<PathBuf> {
+    let Value::Object(map) = input else {
+        return Vec::new();
+    };
+    let mut paths = Vec::new();
+    for key in PATH_KEYS {
+        match map.get(*key) {
+            Some(Value::String(path)) => paths.push(PathBuf::from(path)),
+            Some(Value::Array(items)) => {
+                paths.extend(items.iter().filter_map(Value::as_str).map(PathBuf::from))
+            }
+            _ => {}
+        }
+    }
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn hit(lookup: MemoLookup) -> Option<String> {
+        match lookup {
+            MemoLookup::Hit(output) => Some(output),
+            _ => None,
+        }
+    }
+
+    fn ticket(lookup: MemoLookup) -> MemoTicket {
+        match lookup {
+            MemoLookup::Miss(ticket) => ticket,
+            other => panic!("expected a miss, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_identical_calls_are_served_until_the_file_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("notes.txt");
+        std::fs::write(&file, "one").unwrap();
+        let memo = ToolCallMemo::default();
+
+        let input = json!({"path": file.to_str().unwrap(), "limit": 10});
+        memo.store(ticket(memo.lookup("read", &input)), "one");
+
+        // Key order does not matter
+        let reordered = json!({"limit": 10, "path": file.to_str().unwrap()});
+        assert_eq!(hit(memo.lookup("read", &reordered)).as_deref(), Some("one"));
+
+        std::fs::write(&file, "one and two").unwrap();
+        assert!(hit(memo.lookup("read", &input)).is_none());
+        assert_eq!(
+            memo.stats(),
+            MemoStats {
+                hits: 1,
+                misses: 2,
+                entries: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_side_effects_clear_the_cache() {
+        let memo = ToolCallMemo::default();
+        let input = json!({"pattern": "fn main"});
+
+        // A result that arrives after a side effect started is not kept
+        let pending = ticket(memo.lookup("grep", &input));
+        assert!(matches!(
+            memo.lookup("bash", &json!({"command": "ls"})),
+            MemoLookup::Skip
+        ));
+        memo.store(pending, "stale");
+        assert_eq!(memo.stats().entries, 0);
+
+        memo.store(ticket(memo.lookup("grep", &input)), "src/main.rs");
+        assert!(hit(memo.lookup("grep", &input)).is_some());
+        memo.lookup("write", &json!({"path": "x", "content": ""}));
+        assert!(hit(memo.lookup("grep", &input)).is_none());
+    }
+
+    #[test]
+    fn test_per_tool_configuration() {
+        let config = ToolMemoConfig::default()
+            .with_tool("webfetch", true)
+            .with_tool("grep", false)
+            .with_tool("bash", true);
+
+        assert!(config.is_memoized("read"));
+        assert!(config.is_memoized("webfetch"));
+        assert!(!config.is_memoized("grep"));
+        // Shell output is never reused
+        assert!(!config.is_memoized("bash"));
+    }
+}
@@ -2,8 +2,10 @@
 //!
 //! Processes AI model response streams and manages tool execution lifecycle.
 
+mod memo;
 mod stream;
 
+pub use memo::{MemoLookup, MemoStats, MemoTicket, ToolCallMemo, ToolMemoConfig};
 pub use stream::{
     FinishReason, ProcessResult, SessionProcessor, StreamEvent, ToolState,
 };
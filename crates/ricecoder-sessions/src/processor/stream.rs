@@ -5,9 +5,12 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio_util::sync::CancellationToken;
 
+use super::memo::{MemoLookup, MemoTicket, ToolCallMemo};
+
 /// Maximum number of recent tool calls to track for doom loop detection
 const DOOM_LOOP_WINDOW: usize = 10;
 
@@ -89,6 +92,9 @@ pub enum ToolState {
         input: Value,
         output: String,
         duration_ms: u64,
+        /// Served from an earlier identical call instead of running
+        #[serde(default)]
+        cached: bool,
     },
     
     /// Failed with error
@@ -113,6 +119,14 @@ pub enum ProcessResult {
         input: Value,
     },
     
+    /// Tool call repeats an earlier one; its result is reused without running it
+    ToolResultCached {
+        id: String,
+        name: String,
+        input: Value,
+        output: String,
+    },
+    
     /// Stream finished
     Finished {
         reason: FinishReason,
@@ -141,7 +155,11 @@ pub struct SessionProcessor {
     message_id: String,
     cancel: CancellationToken,
     tool_states: HashMap<String, ToolState>,
+    tool_names: HashMap<String, String>,
     recent_calls: VecDeque<ToolCallRecord>,
+    /// Session-wide tool result cache
+    memo: Option<Arc<ToolCallMemo>>,
+    memo_tickets: HashMap<String, MemoTicket>,
     /// Token usage tracking
     input_tokens: usize,
     output_tokens: usize,
@@ -164,7 +182,10 @@ impl SessionProcessor {
             message_id,
             cancel,
             tool_states: HashMap::new(),
+            tool_names: HashMap::new(),
             recent_calls: VecDeque::with_capacity(DOOM_LOOP_WINDOW),
+            memo: None,
+            memo_tickets: HashMap::new(),
             input_tokens: 0,
             output_tokens: 0,
             retry_count: 0,
@@ -185,7 +206,10 @@ impl SessionProcessor {
             message_id,
             cancel,
             tool_states: HashMap::new(),
+            tool_names: HashMap::new(),
             recent_calls: VecDeque::with_capacity(DOOM_LOOP_WINDOW),
+            memo: None,
+            memo_tickets: HashMap::new(),
             input_tokens: 0,
             output_tokens: 0,
             retry_count: 0,
@@ -194,6 +218,17 @@ impl SessionProcessor {
         }
     }
     
+    /// Reuse results of repeated tool calls from a session-wide cache
+    pub fn with_tool_memo(mut self, memo: Arc<ToolCallMemo>) -> Self {
+        self.memo = Some(memo);
+        self
+    }
+    
+    /// Get the tool result cache
+    pub fn tool_memo(&self) -> Option<&Arc<ToolCallMemo>> {
+        self.memo.as_ref()
+    }
+    
     /// Process a stream event
     pub async fn process_event(&mut self, event: StreamEvent) -> ProcessResult {
         // Check cancellation
@@ -226,6 +261,7 @@ impl SessionProcessor {
                         input: Value::Null,
                     },
                 );
+                self.tool_names.insert(id, name);
                 ProcessResult::Continue
             }
             
@@ -249,9 +285,11 @@ impl SessionProcessor {
                                 },
                             );
                             
-                            // Extract tool name - this is a simplification
-                            // In production, track tool names separately
-                            let tool_name = "unknown".to_string();
+                            let tool_name = self
+                                .tool_names
+                                .get(&id)
+                                .cloned()
+                                .unwrap_or_else(|| "unknown".to_string());
                             
                             // Check for doom loop
                             if self.is_doom_loop(&tool_name, &input_value) {
@@ -266,6 +304,32 @@ impl SessionProcessor {
                             // Record call
                             self.record_tool_call(&tool_name, &input_value);
                             
+                            if let Some(memo) = &self.memo {
+                                match memo.lookup(&tool_name, &input_value) {
+                                    MemoLookup::Hit(output) => {
+                                        self.tool_states.insert(
+                                            id.clone(),
+                                            ToolState::Completed {
+                                                input: input_value.clone(),
+                                                output: output.clone(),
+                                                duration_ms: 0,
+                                                cached: true,
+                                            },
+                                        );
+                                        return ProcessResult::ToolResultCached {
+                                            id,
+                                            name: tool_name,
+                                            input: input_value,
+                                            output,
+                                        };
+                                    }
+                                    MemoLookup::Miss(ticket) => {
+                                        self.memo_tickets.insert(id.clone(), ticket);
+                                    }
+                                    MemoLookup::Skip => {}
+                                }
+                            }
+                            
                             ProcessResult::ToolCallRequired {
                                 id,
                                 name: tool_name,
@@ -302,12 +366,19 @@ impl SessionProcessor {
                     
                     let duration_ms = now.saturating_sub(start_time);
                     
+                    if let Some(ticket) = self.memo_tickets.remove(&id) {
+                        if let Some(memo) = &self.memo {
+                            memo.store(ticket, &output);
+                        }
+                    }
+                    
                     self.tool_states.insert(
                         id,
                         ToolState::Completed {
                             input,
                             output,
                             duration_ms,
+                            cached: false,
                         },
                     );
                 }
@@ -333,6 +404,9 @@ impl SessionProcessor {
                     
                     let duration_ms = now.saturating_sub(start_time);
                     
+                    // Errors are not worth repeating
+                    self.memo_tickets.remove(&id);
+                    
                     self.tool_states.insert(
                         id,
                         ToolState::Error {
@@ -458,7 +532,9 @@ impl SessionProcessor {
     /// Reset processor state for retry
     pub fn reset_for_retry(&mut self) {
         self.tool_states.clear();
+        self.tool_names.clear();
         self.recent_calls.clear();
+        self.memo_tickets.clear();
         // Keep token counts for total tracking
         // Keep snapshot_id for rollback capability
     }
@@ -522,6 +598,68 @@ mod tests {
         assert!(matches!(state, Some(ToolState::Completed { .. })));
     }
     
+    #[tokio::test]
+    async fn test_repeated_tool_calls_are_memoized() {
+        let memo = Arc::new(ToolCallMemo::default());
+        let mut processor = SessionProcessor::new(
+            "session-1".to_string(),
+            "message-1".to_string(),
+            CancellationToken::new(),
+        )
+        .with_tool_memo(memo.clone());
+        
+        async fn call(processor: &mut SessionProcessor, id: &str, name: &str) -> ProcessResult {
+            processor
+                .process_event(StreamEvent::ToolCallStart {
+                    id: id.to_string(),
+                    name: name.to_string(),
+                })
+                .await;
+            processor
+                .process_event(StreamEvent::ToolCallInput {
+                    id: id.to_string(),
+                    input: r#"{"pattern": "TODO"}"#.to_string(),
+                })
+                .await
+        }
+        
+        let result = call(&mut processor, "tool-1", "grep").await;
+        assert!(matches!(
+            result,
+            ProcessResult::ToolCallRequired { ref name, .. } if name == "grep"
+        ));
+        processor
+            .process_event(StreamEvent::ToolResult {
+                id: "tool-1".to_string(),
+                output: "src/lib.rs:3".to_string(),
+            })
+            .await;
+        
+        // A later message of the same session reuses the result
+        let mut next = SessionProcessor::new(
+            "session-1".to_string(),
+            "message-2".to_string(),
+            CancellationToken::new(),
+        )
+        .with_tool_memo(memo.clone());
+        let result = call(&mut next, "tool-2", "grep").await;
+        assert!(matches!(
+            result,
+            ProcessResult::ToolResultCached { ref output, .. } if output == "src/lib.rs:3"
+        ));
+        assert!(matches!(
+            next.tool_state("tool-2"),
+            Some(ToolState::Completed { cached: true, .. })
+        ));
+        
+        // Shell calls always run, and clear what they may have invalidated
+        let result = call(&mut next, "tool-3", "bash").await;
+        assert!(matches!(result, ProcessResult::ToolCallRequired { .. }));
+        let result = call(&mut next, "tool-4", "grep").await;
+        assert!(matches!(result, ProcessResult::ToolCallRequired { .. }));
+        assert_eq!(memo.stats().hits, 1);
+    }
+    
     #[tokio::test]
     async fn test_finish_processing() {
         let cancel = CancellationToken::new();
@@ -0,0 +1,679 @@
+//! Data retention and erasure enforcement
+//!
+//! [`DataRetentionPolicy`] and [`DataErasureRequest`] only describe what should
+//! happen to stored data. [`RetentionEngine`] carries them out against the
+//! session store, restore points and share records: it archives and purges
+//! expired sessions, strips old message content by [`DataType`], executes
+//! erasure requests, and reports each run as a [`ComplianceEvent`].
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::error::{SessionError, SessionResult};
+use crate::models::{
+    ComplianceAlertLevel, ComplianceEvent, ComplianceEventType, DataErasureRequest,
+    DataRetentionPolicy, DataType, PartRetentionRule, Session, SessionStatus,
+};
+use crate::share::ShareService;
+use crate::snapshot::SnapshotManager;
+use crate::store::SessionStore;
+
+/// Key in [`SessionContext::custom`](crate::models::SessionContext) naming the
+/// user a session belongs to
+pub const SESSION_USER_KEY: &str = "user_id";
+
+/// Message content erasure requests can target
+const MESSAGE_CONTENT: [DataType; 4] = [
+    DataType::ToolOutputs,
+    DataType::Reasoning,
+    DataType::FileAttachments,
+    DataType::Images,
+];
+
+/// Number of events buffered for slow subscribers
+const EVENT_CAPACITY: usize = 64;
+
+/// What a retention run changed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RetentionReport {
+    /// Expired sessions moved to the archive
+    pub sessions_archived: usize,
+    /// Archived sessions removed for good
+    pub sessions_purged: usize,
+    /// Message parts removed by part retention rules
+    pub parts_stripped: usize,
+    /// Restore points of purged sessions
+    pub restore_points_deleted: usize,
+    /// Share links of archived or purged sessions
+    pub shares_invalidated: usize,
+}
+
+/// What an erasure request removed
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErasureReport {
+    /// User whose data was erased
+    pub user_id: String,
+    /// Sessions removed, active or archived
+    pub sessions_erased: usize,
+    /// Message parts removed from sessions that were kept
+    pub parts_erased: usize,
+    /// Restore points of erased sessions
+    pub restore_points_erased: usize,
+    /// Share records removed
+    pub shares_erased: usize,
+    /// Requested data types this engine does not hold
+    pub skipped: Vec<DataType>,
+}
+
+/// Enforces retention policies and executes erasure requests
+pub struct RetentionEngine {
+    policy: DataRetentionPolicy,
+    store: SessionStore,
+    snapshots: Option<SnapshotManager>,
+    shares: Option<ShareService>,
+    events: broadcast::Sender<ComplianceEvent>,
+}
+
+impl RetentionEngine {
+    /// Create an engine for the sessions in `store`
+    pub fn new(policy: DataRetentionPolicy, store: SessionStore) -> Self {
+        let (events, _) = broadcast::channel(EVENT_CAPACITY);
+        Self {
+            policy,
+            store,
+            snapshots: None,
+            shares: None,
+            events,
+        }
+    }
+
+    /// Also remove restore points of purged sessions
+    pub fn with_snapshots(mut self, snapshots: SnapshotManager) -> Self {
+        self.snapshots = Some(snapshots);
+        self
+    }
+
+    /// Also remove share records of archived and purged sessions
+    pub fn with_shares(mut self, shares: ShareService) -> Self {
+        self.shares = Some(shares);
+        self
+    }
+
+    /// Get the policy being enforced
+    pub fn policy(&self) -> &DataRetentionPolicy {
+        &self.policy
+    }
+
+    /// Receive a [`ComplianceEvent`] for every retention run and erasure
+    pub fn subscribe(&self) -> broadcast::Receiver<ComplianceEvent> {
+        self.events.subscribe()
+    }
+
+    /// Apply the retention policy to every stored session once
+    ///
+    /// Does nothing unless `auto_delete_expired_data` is set. Sessions not
+    /// updated within `session_data_retention_days` are archived, archived
+    /// sessions past `archive_retention_days` are purged, and part retention
+    /// rules strip content from older messages of the sessions that remain.
+    pub async fn run_once(&self) -> SessionResult<RetentionReport> {
+        let mut report = RetentionReport::default();
+        if self.policy.auto_delete_expired_data {
+            self.archive_expired(&mut report).await?;
+            self.purge_archived(&mut report).await?;
+            self.strip_expired_parts(&mut report).await?;
+        }
+
+        info!(
+            archived = report.sessions_archived,
+            purged = report.sessions_purged,
+            parts = report.parts_stripped,
+            "retention policy enforced"
+        );
+        let changed = report != RetentionReport::default();
+        self.emit(
+            ComplianceEventType::RetentionEnforced,
+            if changed {
+                ComplianceAlertLevel::Warning
+            } else {
+                ComplianceAlertLevel::Info
+            },
+            None,
+            format!(
+                "Retention enforced: {} sessions archived, {} purged, {} message parts stripped",
+                report.sessions_archived, report.sessions_purged, report.parts_stripped
+            ),
+            HashMap::from([
+                (
+                    "sessions_archived".to_string(),
+                    report.sessions_archived.into(),
+                ),
+                ("sessions_purged".to_string(), report.sessions_purged.into()),
+                ("parts_stripped".to_string(), report.parts_stripped.into()),
+                (
+                    "restore_points_deleted".to_string(),
+                    report.restore_points_deleted.into(),
+                ),
+                (
+                    "shares_invalidated".to_string(),
+                    report.shares_invalidated.into(),
+                ),
+            ]),
+        );
+
+        Ok(report)
+    }
+
+    /// Run [`run_once`](Self::run_once) every `interval` in the background
+    ///
+    /// The first run starts immediately. Enforcement stops when the returned
+    /// handle is dropped.
+    pub fn schedule(self: &Arc<Self>, interval: Duration) -> RetentionSchedule {
+        let engine = Arc::clone(self);
+        let handle = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = engine.run_once().await {
+                    warn!("Retention run failed: {}", e);
+                }
+            }
+        });
+        RetentionSchedule { handle }
+    }
+
+    /// Execute a right to erasure request (GDPR Article 17)
+    ///
+    /// Sessions belong to the user named under [`SESSION_USER_KEY`] in their
+    /// context. Erasing [`DataType::Sessions`] removes them everywhere they are
+    /// stored; message content types are stripped from sessions that are
+    /// kept. Audit logs and preferences are not held here and are reported
+    /// as skipped. Sets `completed_at` once done.
+    pub async fn erase(&self, request: &mut DataErasureRequest) -> SessionResult<ErasureReport> {
+        if request.data_types_to_erase.is_empty() && !request.erase_all_data {
+            return Err(SessionError::Invalid(
+                "Erasure request must specify data types or erase all data".to_string(),
+            ));
+        }
+        if !self.policy.data_minimization.enable_data_purging {
+            return Err(SessionError::PermissionDenied(
+                "Data purging is disabled by the retention policy".to_string(),
+            ));
+        }
+
+        let wants = |data_type: &DataType| {
+            request.erase_all_data
+                || request
+                    .data_types_to_erase
+                    .iter()
+                    .any(|t| *t == DataType::All || t == data_type)
+        };
+        let content: Vec<DataType> = MESSAGE_CONTENT.into_iter().filter(|t| wants(t)).collect();
+        let user_id = request.user_id.clone();
+        let mut report = ErasureReport {
+            user_id: user_id.clone(),
+            sessions_erased: 0,
+            parts_erased: 0,
+            restore_points_erased: 0,
+            shares_erased: 0,
+            skipped: [DataType::AuditLogs, DataType::UserPreferences]
+                .into_iter()
+                .filter(|t| wants(t))
+                .collect(),
+        };
+
+        for (mut session, archived) in self.owned_sessions(&user_id).await? {
+            if wants(&DataType::Sessions) {
+                self.store.purge(&session.id).await?;
+                report.sessions_erased += 1;
+                report.restore_points_erased += self.delete_restore_points(&session.id).await?;
+                report.shares_erased += self.invalidate_shares(&session.id)?;
+                continue;
+            }
+
+            let now = Utc::now();
+            let removed: usize = content
+                .iter()
+                .map(|data_type| strip_parts(&mut session, data_type, now))
+                .sum();
+            if removed > 0 {
+                self.save(&session, archived).await?;
+                report.parts_erased += removed;
+            }
+        }
+
+        if wants(&DataType::SharingHistory) {
+            if let Some(shares) = &self.shares {
+                report.shares_erased += shares.remove_shares_by_creator(&user_id)?;
+            }
+        }
+
+        let completed_at = Utc::now();
+        request.completed_at = Some(completed_at);
+        info!(
+            user_id = %user_id,
+            sessions = report.sessions_erased,
+            parts = report.parts_erased,
+            "data erasure completed"
+        );
+        self.emit(
+            ComplianceEventType::DataErased,
+            ComplianceAlertLevel::Info,
+            Some(user_id.clone()),
+            format!(
+                "Data erasure completed for user {}: {} sessions, {} message parts, {} shares",
+                user_id, report.sessions_erased, report.parts_erased, report.shares_erased
+            ),
+            HashMap::from([
+                (
+                    "erasure_reason".to_string(),
+                    format!("{:?}", request.reason).into(),
+                ),
+                ("sessions_erased".to_string(), report.sessions_erased.into()),
+                ("parts_erased".to_string(), report.parts_erased.into()),
+                (
+                    "restore_points_erased".to_string(),
+                    report.restore_points_erased.into(),
+                ),
+                ("shares_erased".to_string(), report.shares_erased.into()),
+                (
+                    "skipped".to_string(),
+                    serde_json::Value::Array(
+                        report
+                            .skipped
+                            .iter()
+                            .map(|t| format!("{:?}", t).into())
+                            .collect(),
+                    ),
+                ),
+                ("completed_at".to_string(), completed_at.to_rfc3339().into()),
+            ]),
+        );
+
+        Ok(report)
+    }
+
+    async fn archive_expired(&self, report: &mut RetentionReport) -> SessionResult<()> {
+        let cutoff =
+            Utc::now() - chrono::Duration::days(self.policy.session_data_retention_days as i64);
+        for id in self.store.list_ids()? {
+            let Some(mut session) = self.load(&id, false).await else {
+                continue;
+            };
+            if session.updated_at >= cutoff {
+                continue;
+            }
+
+            session.status = SessionStatus::Archived;
+            self.store.save(&session).await?;
+            self.store.delete(&id).await?;
+            report.sessions_archived += 1;
+            report.shares_invalidated += self.invalidate_shares(&id)?;
+        }
+        Ok(())
+    }
+
+    async fn purge_archived(&self, report: &mut RetentionReport) -> SessionResult<()> {
+        let Some(days) = self.policy.archive_retention_days else {
+            return Ok(());
+        };
+        let cutoff = Utc::now() - chrono::Duration::days(days as i64);
+        for id in self.store.list_archived_ids()? {
+            let Some(session) = self.load(&id, true).await else {
+                continue;
+            };
+            if session.updated_at >= cutoff {
+                continue;
+            }
+
+            self.store.purge(&id).await?;
+            report.sessions_purged += 1;
+            report.restore_points_deleted += self.delete_restore_points(&id).await?;
+            report.shares_invalidated += self.invalidate_shares(&id)?;
+        }
+        Ok(())
+    }
+
+    async fn strip_expired_parts(&self, report: &mut RetentionReport) -> SessionResult<()> {
+        if self.policy.part_retention.is_empty() {
+            return Ok(());
+        }
+
+        for (ids, archived) in [
+            (self.store.list_ids()?, false),
+            (self.store.list_archived_ids()?, true),
+        ] {
+            for id in ids {
+                let Some(mut session) = self.load(&id, archived).await else {
+                    continue;
+                };
+                let removed: usize = self
+                    .policy
+                    .part_retention
+                    .iter()
+                    .map(|rule| strip_expired(&mut session, rule))
+                    .sum();
+                if removed > 0 {
+                    self.save(&session, archived).await?;
+                    report.parts_stripped += removed;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Active and archived sessions owned by `user_id`, flagged if archived
+    async fn owned_sessions(&self, user_id: &str) -> SessionResult<Vec<(Session, bool)>> {
+        let mut owned = Vec::new();
+        for (ids, archived) in [
+            (self.store.list_ids()?, false),
+            (self.store.list_archived_ids()?, true),
+        ] {
+            for id in ids {
+                if let Some(session) = self.load(&id, archived).await {
+                    if session_owner(&session) == Some(user_id) {
+                        owned.push((session, archived));
+                    }
+                }
+            }
+        }
+        Ok(owned)
+    }
+
+    /// Load a session, skipping ones that can't be read
+    async fn load(&self, id: &str, archived: bool) -> Option<Session> {
+        let loaded = if archived {
+            self.store.load_archived(id).await
+        } else {
+            self.store.load(id).await
+        };
+        match loaded {
+            Ok(session) => Some(session),
+            Err(e) => {
+                warn!("Skipping unreadable session {}: {}", id, e);
+                None
+            }
+        }
+    }
+
+    async fn save(&self, session: &Session, archived: bool) -> SessionResult<()> {
+        if archived {
+            self.store.save_archived(session).await
+        } else {
+            self.store.save(session).await
+        }
+    }
+
+    async fn delete_restore_points(&self, session_id: &str) -> SessionResult<usize> {
+        match &self.snapshots {
+            Some(snapshots) => snapshots.delete_restore_points(session_id).await,
+            None => Ok(0),
+        }
+    }
+
+    fn invalidate_shares(&self, session_id: &str) -> SessionResult<usize> {
+        match &self.shares {
+            Some(shares) => shares.invalidate_session_shares(session_id),
+            None => Ok(0),
+        }
+    }
+
+    fn emit(
+        &self,
+        event_type: ComplianceEventType,
+        alert_level: ComplianceAlertLevel,
+        user_id: Option<String>,
+        description: String,
+        metadata: HashMap<String, serde_json::Value>,
+    ) {
+        // No subscribers is not an error
+        let _ = self.events.send(ComplianceEvent {
+            id: Uuid::new_v4().to_string(),
+            event_type,
+            alert_level,
+            user_id,
+            session_id: None,
+            description,
+            metadata,
+            timestamp: Utc::now(),
+        });
+    }
+}
+
+/// Handle to scheduled retention enforcement; stops it when dropped
+pub struct RetentionSchedule {
+    handle: JoinHandle<()>,
+}
+
+impl RetentionSchedule {
+    /// Stop enforcement
+    pub fn stop(self) {}
+}
+
+impl Drop for RetentionSchedule {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+/// User a session belongs to, if recorded
+pub fn session_owner(session: &Session) -> Option<&str> {
+    session
+        .context
+        .custom
+        .get(SESSION_USER_KEY)
+        .and_then(|value| value.as_str())
+}
+
+/// Strip content a rule has expired from messages older than its period
+fn strip_expired(session: &mut Session, rule: &PartRetentionRule) -> usize {
+    let cutoff = Utc::now() - chrono::Duration::days(rule.retention_days as i64);
+    strip_parts(session, &rule.data_type, cutoff)
+}
+
+/// Remove parts of `data_type` from messages sent before `cutoff`
+fn strip_parts(
+    session: &mut Session,
+    data_type: &DataType,
+    cutoff: chrono::DateTime<Utc>,
+) -> usize {
+    let mut removed = 0;
+    for message in session.history.iter_mut().filter(|m| m.timestamp < cutoff) {
+        let before = message.parts.len();
+        message.parts.retain(|part| !data_type.matches_part(part));
+        removed += before - message.parts.len();
+    }
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        DataMinimizationSettings, ErasureReason, Message, MessagePart, MessageRole, SessionContext,
+        SessionMode,
+    };
+    use crate::share::SharePermissions;
+    use ricecoder_security::access_control::{AccessControl, Permission};
+
+    fn policy() -> DataRetentionPolicy {
+        DataRetentionPolicy {
+            session_data_retention_days: 30,
+            audit_log_retention_days: 365,
+            backup_retention_days: 90,
+            auto_delete_expired_data: true,
+            data_minimization: DataMinimizationSettings {
+                anonymize_ip_addresses: true,
+                limit_unnecessary_collection: true,
+                enable_data_purging: true,
+                enable_data_export: true,
+            },
+            archive_retention_days: Some(60),
+            part_retention: vec![PartRetentionRule {
+                data_type: DataType::Reasoning,
+                retention_days: 7,
+            }],
+        }
+    }
+
+    fn store(dir: &tempfile::TempDir) -> SessionStore {
+        SessionStore::with_dirs(dir.path().join("sessions"), dir.path().join("archive")).unwrap()
+    }
+
+    /// A session owned by `user` last updated `age_days` ago
+    fn session(user: &str, age_days: i64) -> Session {
+        let mut context =
+            SessionContext::new("openai".to_string(), "gpt-4".to_string(), SessionMode::Chat);
+        context
+            .custom
+            .insert(SESSION_USER_KEY.to_string(), user.into());
+        let mut session = Session::new(format!("{}'s session", user), context);
+        let then = Utc::now() - chrono::Duration::days(age_days);
+        session.updated_at = then;
+
+        let mut message = Message::new(MessageRole::Assistant, "Done.".to_string());
+        message.timestamp = then;
+        message.parts.push(MessagePart::Reasoning {
+            id: None,
+            session_id: None,
+            message_id: None,
+            text: "thinking".to_string(),
+            metadata: None,
+            time: None,
+        });
+        session.history.push(message);
+        session
+    }
+
+    #[tokio::test]
+    async fn test_run_once_archives_purges_and_strips() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = store(&dir);
+        let recent = session("alice", 10);
+        let expired = session("alice", 45);
+        let stale = session("bob", 90);
+        for s in [&recent, &expired, &stale] {
+            store.save(s).await.unwrap();
+        }
+        store.delete(&stale.id).await.unwrap();
+
+        let mut access = AccessControl::new();
+        access.add_custom_role("user".to_string(), vec![Permission::SessionShare]);
+        let shares =
+            ShareService::with_access_control("https://share.test".to_string(), Arc::new(access));
+        let permissions = SharePermissions {
+            read_only: true,
+            include_history: true,
+            include_context: true,
+        };
+        shares
+            .generate_share_link_with_policy(
+                &expired.id,
+                permissions,
+                None,
+                None,
+                Some("alice".to_string()),
+            )
+            .unwrap();
+
+        let engine = RetentionEngine::new(policy(), store).with_shares(shares.clone());
+        let mut events = engine.subscribe();
+        let report = engine.run_once().await.unwrap();
+
+        assert_eq!(report.sessions_archived, 1);
+        assert_eq!(report.sessions_purged, 1);
+        assert_eq!(report.shares_invalidated, 1);
+        // Old reasoning is stripped from the sessions that remain
+        assert_eq!(report.parts_stripped, 2);
+
+        let store = &engine.store;
+        assert_eq!(store.list_ids().unwrap(), vec![recent.id.clone()]);
+        assert_eq!(store.list_archived_ids().unwrap(), vec![expired.id.clone()]);
+        let archived = store.load_archived(&expired.id).await.unwrap();
+        assert_eq!(archived.status, SessionStatus::Archived);
+        assert_eq!(archived.history[0].parts.len(), 1);
+
+        let event = events.recv().await.unwrap();
+        assert!(matches!(
+            event.event_type,
+            ComplianceEventType::RetentionEnforced
+        ));
+        assert_eq!(event.metadata["sessions_purged"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_erasure_removes_user_data_everywhere() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = store(&dir);
+        let alice = session("alice", 1);
+        let archived = session("alice", 2);
+        let bob = session("bob", 1);
+        for s in [&alice, &archived, &bob] {
+            store.save(s).await.unwrap();
+        }
+        store.delete(&archived.id).await.unwrap();
+
+        let engine = RetentionEngine::new(policy(), store);
+        let mut events = engine.subscribe();
+        let mut request = DataErasureRequest {
+            user_id: "alice".to_string(),
+            reason: ErasureReason::UserRequest,
+            erase_all_data: false,
+            data_types_to_erase: vec![DataType::Sessions, DataType::AuditLogs],
+            requested_at: Utc::now(),
+            completed_at: None,
+        };
+        let report = engine.erase(&mut request).await.unwrap();
+
+        assert_eq!(report.sessions_erased, 2);
+        assert_eq!(report.skipped, vec![DataType::AuditLogs]);
+        assert!(request.completed_at.is_some());
+        assert_eq!(engine.store.list_ids().unwrap(), vec![bob.id.clone()]);
+        assert!(engine.store.list_archived_ids().unwrap().is_empty());
+
+        let event = events.recv().await.unwrap();
+        assert!(matches!(event.event_type, ComplianceEventType::DataErased));
+        assert_eq!(event.user_id.as_deref(), Some("alice"));
+
+        // Erasing content only keeps the session
+        let mut request = DataErasureRequest {
+            user_id: "bob".to_string(),
+            data_types_to_erase: vec![DataType::Reasoning],
+            completed_at: None,
+            ..request
+        };
+        let report = engine.erase(&mut request).await.unwrap();
+        assert_eq!((report.sessions_erased, report.parts_erased), (0, 1));
+        let bob = engine.store.load(&bob.id).await.unwrap();
+        assert_eq!(bob.history[0].parts.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_erasure_requires_purging_enabled() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut policy = policy();
+        policy.data_minimization.enable_data_purging = false;
+        let engine = RetentionEngine::new(policy, store(&dir));
+
+        let mut request = DataErasureRequest {
+            user_id: "alice".to_string(),
+            reason: ErasureReason::ConsentWithdrawn,
+            erase_all_data: true,
+            data_types_to_erase: Vec::new(),
+            requested_at: Utc::now(),
+            completed_at: None,
+        };
+        assert!(matches!(
+            engine.erase(&mut request).await,
+            Err(SessionError::PermissionDenied(_))
+        ));
+        assert!(request.completed_at.is_none());
+    }
+}
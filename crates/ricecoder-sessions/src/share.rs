@@ -589,6 +589,20 @@ impl ShareService {
         Ok(initial_count - shares.len())
     }
 
+    /// Remove every share created by a user, including expired ones
+    pub fn remove_shares_by_creator(&self, user_id: &str) -> SessionResult<usize> {
+        let mut shares = self
+            .shares
+            .lock()
+            .map_err(|e| SessionError::StorageError(format!("Failed to lock shares: {}", e)))?;
+
+        let initial_count = shares.len();
+
+        shares.retain(|_, share| share.creator_user_id.as_deref() != Some(user_id));
+
+        Ok(initial_count - shares.len())
+    }
+
     /// Get sharing analytics
     pub fn get_analytics(&self) -> ShareAnalyticsData {
         self.analytics.get_data()
@@ -2,13 +2,23 @@
 //!
 //! This module implements OpenCode-compatible snapshot functionality using git tree hashes
 //! for content-addressed storage and efficient delta computation.
+//!
+//! Named restore points build on top of this: they pair the conversation state of a
+//! session (its message history) with the workspace state (a git tree hash plus
+//! `ricecoder-files` backups of the files the session touched), so both can be
+//! rolled back together.
 
+use std::collections::{BTreeSet, HashMap};
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
+
+use chrono::{DateTime, Utc};
+use ricecoder_files::{models::BackupMetadata, BackupManager};
+use serde::{Deserialize, Serialize};
 use tokio::process::Command;
 use tracing::{debug, error, info, warn};
 
-use crate::error::SessionError;
+use crate::{error::SessionError, models::Session};
 
 /// Git-based snapshot manager for session workspace state
 #[derive(Debug, Clone)]
@@ -17,6 +27,8 @@ pub struct SnapshotManager {
     git_dir: PathBuf,
     /// Working tree path
     work_tree: PathBuf,
+    /// Directory where restore points are stored
+    restore_dir: PathBuf,
     /// Whether snapshots are enabled
     enabled: bool,
 }
@@ -45,6 +57,70 @@ pub struct FileDiff {
     pub deletions: usize,
 }
 
+/// Named restore point capturing conversation and workspace state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestorePoint {
+    /// Unique restore point identifier
+    pub id: String,
+    /// Human-readable name
+    pub name: String,
+    /// Session the restore point belongs to
+    pub session_id: String,
+    /// When the restore point was created
+    pub created_at: DateTime<Utc>,
+    /// IDs of the messages in the session history, in order
+    pub message_ids: Vec<String>,
+    /// Git tree hash of the workspace
+    pub tree_hash: Option<String>,
+    /// Backed up files
+    pub files: Vec<RestorePointFile>,
+}
+
+/// State of a single file captured by a restore point
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestorePointFile {
+    /// Absolute path of the file
+    pub path: PathBuf,
+    /// Backup of the file, `None` if the file did not exist
+    pub backup: Option<BackupMetadata>,
+}
+
+impl RestorePoint {
+    fn file_hashes(&self) -> HashMap<&Path, Option<&str>> {
+        self.files
+            .iter()
+            .map(|file| {
+                (
+                    file.path.as_path(),
+                    file.backup.as_ref().map(|b| b.content_hash.as_str()),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Difference between two restore points
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SnapshotDiff {
+    /// Messages present in the newer point but not the older one
+    pub messages_added: Vec<String>,
+    /// Messages present in the older point but not the newer one
+    pub messages_removed: Vec<String>,
+    /// Files whose content differs between the two points
+    pub files_changed: Vec<PathBuf>,
+}
+
+/// Result of restoring a restore point
+#[derive(Debug, Clone, Default)]
+pub struct RestoreOutcome {
+    /// Files rewritten from backups or the workspace snapshot
+    pub files_restored: Vec<PathBuf>,
+    /// Files deleted because they did not exist at the restore point
+    pub files_removed: Vec<PathBuf>,
+    /// Number of messages truncated from the session history
+    pub messages_truncated: usize,
+}
+
 impl SnapshotManager {
     /// Creates a new snapshot manager
     ///
@@ -61,9 +137,11 @@ impl SnapshotManager {
         enabled: bool,
     ) -> Self {
         let git_dir = data_dir.as_ref().join("snapshot").join(project_id);
+        let restore_dir = data_dir.as_ref().join("restore_points").join(project_id);
         Self {
             git_dir,
             work_tree: work_tree.as_ref().to_path_buf(),
+            restore_dir,
             enabled,
         }
     }
@@ -73,6 +151,7 @@ impl SnapshotManager {
         Self {
             git_dir: PathBuf::new(),
             work_tree: PathBuf::new(),
+            restore_dir: PathBuf::new(),
             enabled: false,
         }
     }
@@ -419,8 +498,288 @@ impl SnapshotManager {
         Ok(result)
     }
 
+    /// Creates a named restore point for a session
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Human-readable restore point name
+    /// * `session` - Session whose conversation state is captured
+    /// * `files` - Files to back up (relative paths are resolved against the working tree)
+    pub async fn create_restore_point(
+        &self,
+        name: &str,
+        session: &Session,
+        files: &[PathBuf],
+    ) -> Result<RestorePoint, SessionError> {
+        if !self.enabled {
+            return Err(SessionError::SnapshotDisabled);
+        }
+
+        let id = uuid::Uuid::new_v4().to_string();
+        let tree_hash = self.track().await?;
+        let backups = BackupManager::new(self.restore_dir.join(&id), usize::MAX);
+
+        let mut captured = Vec::new();
+        let mut seen = BTreeSet::new();
+        for file in files {
+            let path = self.resolve(file);
+            if !seen.insert(path.clone()) {
+                continue;
+            }
+
+            let backup = if tokio::fs::try_exists(&path).await.unwrap_or(false) {
+                Some(backups.create_backup(&path).await.map_err(|e| {
+                    SessionError::SnapshotFailed(format!(
+                        "failed to back up {}: {}",
+                        path.display(),
+                        e
+                    ))
+                })?)
+            } else {
+                None
+            };
+            captured.push(RestorePointFile { path, backup });
+        }
+
+        let point = RestorePoint {
+            id,
+            name: name.to_string(),
+            session_id: session.id.clone(),
+            created_at: Utc::now(),
+            message_ids: session.history.iter().map(|m| m.id.clone()).collect(),
+            tree_hash,
+            files: captured,
+        };
+        self.save_restore_point(&point).await?;
+
+        info!(id = %point.id, name = %point.name, "restore point created");
+        Ok(point)
+    }
+
+    /// Loads a restore point by ID or name
+    pub async fn load_restore_point(&self, id_or_name: &str) -> Result<RestorePoint, SessionError> {
+        let path = self.restore_point_path(id_or_name);
+        if tokio::fs::try_exists(&path).await.unwrap_or(false) {
+            let json = tokio::fs::read_to_string(&path).await?;
+            return Ok(serde_json::from_str(&json)?);
+        }
+
+        self.list_restore_points(None)
+            .await?
+            .into_iter()
+            .rev()
+            .find(|point| point.name == id_or_name)
+            .ok_or_else(|| {
+                SessionError::NotFound(format!("Restore point not found: {}", id_or_name))
+            })
+    }
+
+    /// Lists restore points, oldest first, optionally filtered by session
+    pub async fn list_restore_points(
+        &self,
+        session_id: Option<&str>,
+    ) -> Result<Vec<RestorePoint>, SessionError> {
+        if !self.enabled || !self.restore_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut points = Vec::new();
+        let mut entries = tokio::fs::read_dir(&self.restore_dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+
+            let json = tokio::fs::read_to_string(&path).await?;
+            match serde_json::from_str::<RestorePoint>(&json) {
+                Ok(point) if session_id.map_or(true, |id| point.session_id == id) => {
+                    points.push(point)
+                }
+                Ok(_) => {}
+                Err(e) => warn!(path = %path.display(), "skipping invalid restore point: {}", e),
+            }
+        }
+
+        points.sort_by_key(|point| point.created_at);
+        Ok(points)
+    }
+
+    /// Deletes the restore points of a session along with their file backups
+    ///
+    /// Returns the number of restore points deleted.
+    pub async fn delete_restore_points(&self, session_id: &str) -> Result<usize, SessionError> {
+        let points = self.list_restore_points(Some(session_id)).await?;
+        for point in &points {
+            tokio::fs::remove_file(self.restore_point_path(&point.id)).await?;
+            let backups = self.restore_dir.join(&point.id);
+            if tokio::fs::try_exists(&backups).await.unwrap_or(false) {
+                tokio::fs::remove_dir_all(&backups).await?;
+            }
+        }
+
+        if !points.is_empty() {
+            info!(session_id, count = points.len(), "restore points deleted");
+        }
+        Ok(points.len())
+    }
+
+    /// Computes the difference between two restore points
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - Older restore point
+    /// * `to` - Newer restore point
+    pub async fn diff_restore_points(
+        &self,
+        from: &RestorePoint,
+        to: &RestorePoint,
+    ) -> Result<SnapshotDiff, SessionError> {
+        let from_messages: BTreeSet<&String> = from.message_ids.iter().collect();
+        let to_messages: BTreeSet<&String> = to.message_ids.iter().collect();
+
+        let messages_added = to
+            .message_ids
+            .iter()
+            .filter(|id| !from_messages.contains(id))
+            .cloned()
+            .collect();
+        let messages_removed = from
+            .message_ids
+            .iter()
+            .filter(|id| !to_messages.contains(id))
+            .cloned()
+            .collect();
+
+        let mut files_changed = BTreeSet::new();
+        let from_files = from.file_hashes();
+        let to_files = to.file_hashes();
+        for path in from_files.keys().chain(to_files.keys()) {
+            if from_files.get(path) != to_files.get(path) {
+                files_changed.insert(path.to_path_buf());
+            }
+        }
+
+        if let (Some(from_hash), Some(to_hash)) = (&from.tree_hash, &to.tree_hash) {
+            if from_hash != to_hash {
+                for diff in self.diff_full(from_hash, to_hash).await? {
+                    files_changed.insert(self.work_tree.join(diff.file));
+                }
+            }
+        }
+
+        Ok(SnapshotDiff {
+            messages_added,
+            messages_removed,
+            files_changed: files_changed.into_iter().collect(),
+        })
+    }
+
+    /// Restores a session and its workspace to a restore point
+    ///
+    /// Files are rolled back first; the session history is only truncated once
+    /// the workspace was restored successfully.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - Restore point to roll back to
+    /// * `session` - Session to truncate (must be the session the point belongs to)
+    pub async fn restore_to_point(
+        &self,
+        point: &RestorePoint,
+        session: &mut Session,
+    ) -> Result<RestoreOutcome, SessionError> {
+        if !self.enabled {
+            return Err(SessionError::SnapshotDisabled);
+        }
+
+        if point.session_id != session.id {
+            return Err(SessionError::Invalid(format!(
+                "Restore point {} belongs to session {}",
+                point.id, point.session_id
+            )));
+        }
+
+        let prefix_matches = session.history.len() >= point.message_ids.len()
+            && session
+                .history
+                .iter()
+                .zip(&point.message_ids)
+                .all(|(message, id)| &message.id == id);
+        if !prefix_matches {
+            return Err(SessionError::SnapshotFailed(format!(
+                "session history diverged from restore point {}",
+                point.id
+            )));
+        }
+
+        info!(id = %point.id, name = %point.name, "restoring to restore point");
+        let mut outcome = RestoreOutcome::default();
+
+        if let Some(hash) = &point.tree_hash {
+            let patch = self.patch(hash).await?;
+            self.revert(std::slice::from_ref(&patch)).await?;
+            outcome.files_restored.extend(patch.files);
+        }
+
+        let backups = BackupManager::new(self.restore_dir.join(&point.id), usize::MAX);
+        for file in &point.files {
+            match &file.backup {
+                Some(backup) => {
+                    backups
+                        .restore_from_backup(&backup.backup_path, &file.path)
+                        .await
+                        .map_err(|e| {
+                            SessionError::SnapshotFailed(format!(
+                                "failed to restore {}: {}",
+                                file.path.display(),
+                                e
+                            ))
+                        })?;
+                    outcome.files_restored.push(file.path.clone());
+                }
+                None => {
+                    if tokio::fs::try_exists(&file.path).await.unwrap_or(false) {
+                        tokio::fs::remove_file(&file.path).await?;
+                        outcome.files_removed.push(file.path.clone());
+                    }
+                }
+            }
+        }
+        outcome.files_restored.sort();
+        outcome.files_restored.dedup();
+        outcome
+            .files_restored
+            .retain(|path| !outcome.files_removed.contains(path));
+
+        outcome.messages_truncated = session.history.len() - point.message_ids.len();
+        session.history.truncate(point.message_ids.len());
+        session.updated_at = Utc::now();
+
+        Ok(outcome)
+    }
+
     // --- Private helper methods ---
 
+    fn resolve(&self, file: &Path) -> PathBuf {
+        if file.is_absolute() {
+            file.to_path_buf()
+        } else {
+            self.work_tree.join(file)
+        }
+    }
+
+    fn restore_point_path(&self, id: &str) -> PathBuf {
+        self.restore_dir.join(format!("{}.json", id))
+    }
+
+    async fn save_restore_point(&self, point: &RestorePoint) -> Result<(), SessionError> {
+        tokio::fs::create_dir_all(&self.restore_dir).await?;
+        let json = serde_json::to_string_pretty(point)?;
+        tokio::fs::write(self.restore_point_path(&point.id), json).await?;
+        Ok(())
+    }
+
     async fn init_git_repo(&self) -> Result<(), SessionError> {
         tokio::fs::create_dir_all(&self.git_dir)
             .await
@@ -582,6 +941,7 @@ impl SnapshotManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::{Message, MessageRole, SessionContext, SessionMode};
     use tempfile::TempDir;
 
     #[tokio::test]
@@ -640,4 +1000,109 @@ mod tests {
         assert_eq!(patch.hash, hash);
         assert_eq!(patch.files.len(), 1);
     }
+
+    fn session_with_messages(count: usize) -> Session {
+        let mut session = Session::new(
+            "test".to_string(),
+            SessionContext::new("openai".to_string(), "gpt-4".to_string(), SessionMode::Chat),
+        );
+        for i in 0..count {
+            session
+                .history
+                .push(Message::new(MessageRole::User, format!("message {}", i)));
+        }
+        session
+    }
+
+    #[tokio::test]
+    async fn test_restore_point_diff() {
+        let temp = TempDir::new().unwrap();
+        let data_dir = temp.path().join("data");
+        let work_tree = temp.path().join("project");
+        tokio::fs::create_dir_all(&work_tree).await.unwrap();
+        tokio::fs::write(work_tree.join("a.txt"), "one")
+            .await
+            .unwrap();
+
+        let manager = SnapshotManager::new(&data_dir, "test-project", &work_tree, true);
+        let mut session = session_with_messages(2);
+        let files = vec![PathBuf::from("a.txt")];
+
+        let before = manager
+            .create_restore_point("before", &session, &files)
+            .await
+            .unwrap();
+
+        session
+            .history
+            .push(Message::new(MessageRole::Assistant, "edit".to_string()));
+        tokio::fs::write(work_tree.join("a.txt"), "two")
+            .await
+            .unwrap();
+
+        let after = manager
+            .create_restore_point("after", &session, &files)
+            .await
+            .unwrap();
+
+        let diff = manager.diff_restore_points(&before, &after).await.unwrap();
+        assert_eq!(diff.messages_added, vec![session.history[2].id.clone()]);
+        assert!(diff.messages_removed.is_empty());
+        assert_eq!(diff.files_changed, vec![work_tree.join("a.txt")]);
+
+        let loaded = manager.load_restore_point("before").await.unwrap();
+        assert_eq!(loaded.id, before.id);
+        assert_eq!(
+            manager
+                .list_restore_points(Some(&session.id))
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_restore_to_point() {
+        let temp = TempDir::new().unwrap();
+        let data_dir = temp.path().join("data");
+        let work_tree = temp.path().join("project");
+        tokio::fs::create_dir_all(&work_tree).await.unwrap();
+        tokio::fs::write(work_tree.join("a.txt"), "original")
+            .await
+            .unwrap();
+
+        let manager = SnapshotManager::new(&data_dir, "test-project", &work_tree, true);
+        let mut session = session_with_messages(2);
+        let files = vec![PathBuf::from("a.txt"), PathBuf::from("new.txt")];
+
+        let point = manager
+            .create_restore_point("checkpoint", &session, &files)
+            .await
+            .unwrap();
+
+        session
+            .history
+            .push(Message::new(MessageRole::Assistant, "edit".to_string()));
+        tokio::fs::write(work_tree.join("a.txt"), "modified")
+            .await
+            .unwrap();
+        tokio::fs::write(work_tree.join("new.txt"), "created")
+            .await
+            .unwrap();
+
+        let outcome = manager
+            .restore_to_point(&point, &mut session)
+            .await
+            .unwrap();
+        assert_eq!(outcome.messages_truncated, 1);
+        assert_eq!(session.history.len(), 2);
+        assert_eq!(
+            tokio::fs::read_to_string(work_tree.join("a.txt"))
+                .await
+                .unwrap(),
+            "original"
+        );
+        assert!(!work_tree.join("new.txt").exists());
+    }
 }
@@ -13,6 +13,7 @@ use ricecoder_security::encryption::{CustomerKeyManager, EncryptedData, KeyManag
 use tracing::{debug, error, info, warn};
 
 use crate::{
+    compaction::{CompactedRange, CompactionRecord},
     error::{SessionError, SessionResult},
     models::Session,
 };
@@ -149,6 +150,16 @@ impl SessionStore {
     pub async fn save(&self, session: &Session) -> SessionResult<()> {
         let path = self.session_path(&session.id);
 
+        // Write to file
+        fs::write(&path, self.encode(session)?)?;
+
+        info!("Session saved: {} at {:?}", session.id, path);
+
+        Ok(())
+    }
+
+    /// Serialize a session, encrypting it if encryption is enabled
+    fn encode(&self, session: &Session) -> SessionResult<String> {
         // Serialize session to JSON
         let json_data = serde_json::to_string_pretty(session)?;
 
@@ -179,12 +190,7 @@ impl SessionStore {
             json_data
         };
 
-        // Write to file
-        fs::write(&path, data_to_write)?;
-
-        info!("Session saved: {} at {:?}", session.id, path);
-
-        Ok(())
+        Ok(data_to_write)
     }
 
     /// Load a session from disk with optional decryption
@@ -200,7 +206,15 @@ impl SessionStore {
 
         // Read file
         let file_data = fs::read_to_string(&path)?;
+        let session = self.decode(file_data)?;
+
+        debug!("Session loaded: {} from {:?}", session_id, path);
+
+        Ok(session)
+    }
 
+    /// Deserialize a session, decrypting it if encryption is enabled
+    fn decode(&self, file_data: String) -> SessionResult<Session> {
         // Decrypt if encryption is enabled
         let json_data = if let Some(ref key_manager) = self.key_manager {
             if let Some(ref customer_key_manager) = self.customer_key_manager {
@@ -227,11 +241,7 @@ impl SessionStore {
         };
 
         // Deserialize from JSON
-        let session: Session = serde_json::from_str(&json_data)?;
-
-        debug!("Session loaded: {} from {:?}", session_id, path);
-
-        Ok(session)
+        Ok(serde_json::from_str(&json_data)?)
     }
 
     /// List all persisted sessions
@@ -289,6 +299,74 @@ impl SessionStore {
         Ok(())
     }
 
+    /// Get the directory holding compacted ranges for a session
+    fn compactions_dir(&self, session_id: &str) -> PathBuf {
+        self.sessions_dir.join("compactions").join(session_id)
+    }
+
+    /// Persist the original messages of a compacted range
+    pub async fn save_compaction(&self, compacted: &CompactedRange) -> SessionResult<()> {
+        let dir = self.compactions_dir(&compacted.record.session_id);
+        fs::create_dir_all(&dir)?;
+
+        let path = dir.join(format!("{}.json", compacted.record.id));
+        fs::write(&path, serde_json::to_string_pretty(compacted)?)?;
+
+        debug!(
+            "Compaction saved: {} for session {}",
+            compacted.record.id, compacted.record.session_id
+        );
+
+        Ok(())
+    }
+
+    /// Load the original messages of a compacted range
+    pub async fn load_compaction(
+        &self,
+        session_id: &str,
+        compaction_id: &str,
+    ) -> SessionResult<CompactedRange> {
+        let path = self
+            .compactions_dir(session_id)
+            .join(format!("{}.json", compaction_id));
+
+        if !path.exists() {
+            return Err(SessionError::NotFound(format!(
+                "Compaction not found: {}",
+                compaction_id
+            )));
+        }
+
+        Ok(serde_json::from_str(&fs::read_to_string(&path)?)?)
+    }
+
+    /// List compaction records for a session, oldest first
+    pub async fn list_compactions(&self, session_id: &str) -> SessionResult<Vec<CompactionRecord>> {
+        let dir = self.compactions_dir(session_id);
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut records = Vec::new();
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|s| s.to_str()) != Some("json") {
+                continue;
+            }
+
+            match fs::read_to_string(&path)
+                .map_err(SessionError::from)
+                .and_then(|data| Ok(serde_json::from_str::<CompactedRange>(&data)?))
+            {
+                Ok(compacted) => records.push(compacted.record),
+                Err(e) => warn!("Failed to read compaction {:?}: {}", path, e),
+            }
+        }
+
+        records.sort_by_key(|record| record.created_at);
+        Ok(records)
+    }
+
     /// Delete a session and archive it
     pub async fn delete(&self, session_id: &str) -> SessionResult<()> {
         let session_path = self.session_path(session_id);
@@ -315,6 +393,80 @@ impl SessionStore {
         Ok(())
     }
 
+    /// Load an archived session
+    pub async fn load_archived(&self, session_id: &str) -> SessionResult<Session> {
+        let path = self.archive_path(session_id);
+        if !path.exists() {
+            return Err(SessionError::NotFound(format!(
+                "Archived session not found: {}",
+                session_id
+            )));
+        }
+        self.decode(fs::read_to_string(&path)?)
+    }
+
+    /// Overwrite an archived session
+    pub async fn save_archived(&self, session: &Session) -> SessionResult<()> {
+        let path = self.archive_path(&session.id);
+        if !path.exists() {
+            return Err(SessionError::NotFound(format!(
+                "Archived session not found: {}",
+                session.id
+            )));
+        }
+        fs::write(&path, self.encode(session)?)?;
+        Ok(())
+    }
+
+    /// IDs of all persisted sessions
+    pub fn list_ids(&self) -> SessionResult<Vec<String>> {
+        Self::session_ids(&self.sessions_dir)
+    }
+
+    /// IDs of all archived sessions
+    pub fn list_archived_ids(&self) -> SessionResult<Vec<String>> {
+        Self::session_ids(&self.archive_dir)
+    }
+
+    fn session_ids(dir: &Path) -> SessionResult<Vec<String>> {
+        let mut ids = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("json") {
+                if let Some(id) = path.file_stem().and_then(|s| s.to_str()) {
+                    ids.push(id.to_string());
+                }
+            }
+        }
+        ids.sort();
+        Ok(ids)
+    }
+
+    /// Permanently remove a session, its archived copy and its compactions
+    ///
+    /// Unlike [`delete`](Self::delete), nothing is kept. Returns whether
+    /// anything was removed.
+    pub async fn purge(&self, session_id: &str) -> SessionResult<bool> {
+        let mut removed = false;
+        for path in [self.session_path(session_id), self.archive_path(session_id)] {
+            if path.exists() {
+                fs::remove_file(&path)?;
+                removed = true;
+            }
+        }
+
+        let compactions = self.compactions_dir(session_id);
+        if compactions.exists() {
+            fs::remove_dir_all(&compactions)?;
+            removed = true;
+        }
+
+        if removed {
+            info!("Session purged: {}", session_id);
+        }
+        Ok(removed)
+    }
+
     /// Check if a session exists
     pub fn exists(&self, session_id: &str) -> bool {
         self.session_path(session_id).exists()
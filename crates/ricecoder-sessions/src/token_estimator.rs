@@ -1,13 +1,14 @@
 //! Token estimation and tracking for AI conversations
 //!
-//! This module provides accurate token counting using tiktoken and cost estimation
-//! for various AI models. It integrates with the session system to track token usage
+//! This module provides token counting through the provider token counter
+//! (tiktoken for OpenAI models, configured tokenizer files for other families)
+//! and cost estimation for various AI models. It integrates with the session system to track token usage
 //! and provide warnings when approaching limits.
 
 use std::{collections::HashMap, sync::Arc};
 
+use ricecoder_providers::TokenCounter;
 use serde::{Deserialize, Serialize};
-use tiktoken_rs::{get_bpe_from_model, CoreBPE};
 
 use crate::error::{SessionError, SessionResult};
 use ricecoder_storage::loaders::ModelLoader;
@@ -45,8 +46,8 @@ pub struct TokenUsage {
 /// Token estimator with caching and model support
 #[derive(Debug)]
 pub struct TokenEstimator {
-    /// Cached tokenizers by model
-    tokenizers: HashMap<String, Arc<CoreBPE>>,
+    /// Token counter with cached tokenizers
+    counter: Arc<TokenCounter>,
     /// Model pricing information
     pricing: HashMap<String, ModelPricing>,
     /// Default model for estimation
@@ -117,7 +118,7 @@ impl TokenEstimator {
     /// Create a new token estimator
     pub fn new() -> Self {
         let mut estimator = Self {
-            tokenizers: HashMap::new(),
+            counter: Arc::new(TokenCounter::new()),
             pricing: HashMap::new(),
             default_model: "gpt-3.5-turbo".to_string(),
         };
@@ -285,6 +286,11 @@ impl TokenEstimator {
         self
     }
 
+    /// Use a shared token counter, e.g. one with configured tokenizer files
+    pub fn set_token_counter(&mut self, counter: Arc<TokenCounter>) {
+        self.counter = counter;
+    }
+
     /// Add custom pricing for a model
     pub fn add_pricing(&mut self, model: impl Into<String>, pricing: ModelPricing) {
         self.pricing.insert(model.into(), pricing);
@@ -299,9 +305,10 @@ impl TokenEstimator {
         let model_name = model
             .map(|s| s.to_string())
             .unwrap_or_else(|| self.default_model.clone());
-        let tokenizer = self.get_tokenizer(&model_name)?;
-
-        let tokens = tokenizer.encode_with_special_tokens(text).len();
+        let tokens = self
+            .counter
+            .count(text, &model_name)
+            .map_err(|e| SessionError::TokenEstimation(e.to_string()))?;
         let characters = text.chars().count();
 
         let estimated_cost = self
@@ -347,32 +354,6 @@ impl TokenEstimator {
         })
     }
 
-    /// Get tokenizer for a model (with caching)
-    fn get_tokenizer(&mut self, model: &str) -> SessionResult<&Arc<CoreBPE>> {
-        if !self.tokenizers.contains_key(model) {
-            let bpe = if model.starts_with("gpt-4") || model.starts_with("gpt-3.5") {
-                get_bpe_from_model(model).map_err(|e| {
-                    SessionError::TokenEstimation(format!(
-                        "Failed to load tokenizer for {}: {}",
-                        model, e
-                    ))
-                })?
-            } else {
-                // Fallback to GPT-3.5 tokenizer for other models
-                get_bpe_from_model("gpt-3.5-turbo").map_err(|e| {
-                    SessionError::TokenEstimation(format!(
-                        "Failed to load fallback tokenizer: {}",
-                        e
-                    ))
-                })?
-            };
-
-            self.tokenizers.insert(model.to_string(), Arc::new(bpe));
-        }
-
-        Ok(self.tokenizers.get(model).unwrap())
-    }
-
     /// Get pricing information for a model
     pub fn get_pricing(&self, model: &str) -> Option<&ModelPricing> {
         self.pricing.get(model)
@@ -529,6 +510,14 @@ impl TokenUsageTracker {
         }
     }
 
+    /// Record tokens freed from the context window by compaction
+    ///
+    /// Only the context counters are reduced; the accumulated cost is kept.
+    pub fn record_compaction(&mut self, tokens_freed: usize) {
+        self.prompt_tokens = self.prompt_tokens.saturating_sub(tokens_freed);
+        self.total_tokens = self.total_tokens.saturating_sub(tokens_freed);
+    }
+
     /// Reset usage counters
     pub fn reset(&mut self) {
         self.total_tokens = 0;
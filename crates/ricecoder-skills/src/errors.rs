@@ -42,6 +42,31 @@ pub enum SkillError {
     #[error("YAML parsing error in skill frontmatter: {0}")]
     YamlParse(String),
 
+    /// Invalid argument passed to a parameterized skill
+    #[error("Invalid argument for skill '{skill}': {message}")]
+    InvalidArgument {
+        skill: String,
+        message: String,
+    },
+
+    /// Skill pack could not be fetched or read
+    #[error("Skill pack fetch failed: {0}")]
+    PackFetch(String),
+
+    /// Skill pack manifest, signature or file digests failed verification
+    #[error("Skill pack '{pack}' failed verification: {message}")]
+    PackVerification {
+        pack: String,
+        message: String,
+    },
+
+    /// Skill pack is pinned to a different version
+    #[error("Skill pack '{pack}' is pinned to version {version}")]
+    PackPinned {
+        pack: String,
+        version: String,
+    },
+
     /// Missing required field
     #[error("Missing required field '{field}' in skill at {path}")]
     MissingField {
@@ -63,6 +88,22 @@ impl SkillError {
         }
     }
 
+    /// Create an invalid argument error
+    pub fn invalid_argument(skill: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::InvalidArgument {
+            skill: skill.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Create a pack verification error
+    pub fn pack_verification(pack: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::PackVerification {
+            pack: pack.into(),
+            message: message.into(),
+        }
+    }
+
     /// Create a permission denied error
     pub fn permission_denied(skill: impl Into<String>, agent: impl Into<String>) -> Self {
         Self::PermissionDenied {
@@ -19,12 +19,23 @@
 
 pub mod errors;
 pub mod models;
+pub mod packs;
 pub mod permissions;
+pub mod recommender;
 pub mod registry;
+pub mod template;
 pub mod tool;
 
 pub use errors::SkillError;
-pub use models::{SkillInfo, SkillMetadata};
+pub use models::{SkillArgument, SkillArgumentType, SkillInfo, SkillMetadata};
+pub use packs::{
+    PackManifest, PackProvenance, PackSource, PackUpdate, SkillPackInstaller, TrustedKeys,
+};
 pub use permissions::{SkillPermission, SkillPermissionAction, SkillPermissionChecker};
+pub use recommender::{
+    NGramEmbedder, RecommenderConfig, SkillContext, SkillEmbedder, SkillRecommendation,
+    SkillRecommender,
+};
 pub use registry::SkillRegistry;
-pub use tool::SkillToolProvider;
+pub use template::SkillTemplate;
+pub use tool::{SkillToolInput, SkillToolProvider};
@@ -1,8 +1,11 @@
 //! Skill data models (Gap G-17-01, G-17-09)
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
+use crate::errors::SkillError;
+
 /// Skill information matching OpenCode Skill.Info schema
 /// Skills are data (markdown + frontmatter), NOT executable code (Gap G-17-09)
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -15,6 +18,60 @@ pub struct SkillInfo {
     
     /// Absolute path to the SKILL.md file
     pub location: PathBuf,
+
+    /// Declared arguments (from frontmatter)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub arguments: Vec<SkillArgument>,
+
+    /// Tags for categorization (from frontmatter)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+/// Type of a declared skill argument
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SkillArgumentType {
+    /// Free-form text (default)
+    #[default]
+    String,
+    /// Integer or floating point number
+    Number,
+    /// `true` or `false`
+    Boolean,
+}
+
+/// Argument declared in skill frontmatter
+///
+/// ```yaml
+/// arguments:
+///   - name: language
+///     type: string
+///     required: true
+///   - name: strict
+///     type: boolean
+///     default: false
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SkillArgument {
+    /// Argument name, referenced as `{{name}}` in the skill body
+    pub name: String,
+
+    /// Argument type
+    #[serde(rename = "type", default)]
+    pub arg_type: SkillArgumentType,
+
+    /// Optional description shown in help output
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Default value used when the argument is not provided
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<serde_json::Value>,
+
+    /// Whether the argument must be provided
+    #[serde(default)]
+    pub required: bool,
 }
 
 /// Parsed skill metadata from frontmatter
@@ -37,6 +94,10 @@ pub struct SkillMetadata {
     /// Optional tags for categorization
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<String>,
+
+    /// Optional declared arguments for parameterized skills
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub arguments: Vec<SkillArgument>,
 }
 
 impl SkillInfo {
@@ -46,6 +107,53 @@ impl SkillInfo {
             name,
             description,
             location,
+            arguments: Vec::new(),
+            tags: Vec::new(),
+        }
+    }
+
+    /// Set the declared arguments
+    pub fn with_arguments(mut self, arguments: Vec<SkillArgument>) -> Self {
+        self.arguments = arguments;
+        self
+    }
+
+    /// Set the tags
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+}
+
+impl SkillArgumentType {
+    /// Parse a raw (command line) value into a typed JSON value
+    pub fn parse(&self, raw: &str) -> Result<serde_json::Value, String> {
+        match self {
+            Self::String => Ok(serde_json::Value::String(raw.to_string())),
+            Self::Number => raw
+                .parse::<i64>()
+                .map(serde_json::Value::from)
+                .or_else(|_| {
+                    raw.parse::<f64>()
+                        .ok()
+                        .and_then(serde_json::Number::from_f64)
+                        .map(serde_json::Value::Number)
+                        .ok_or_else(|| format!("'{}' is not a number", raw))
+                }),
+            Self::Boolean => match raw {
+                "true" | "yes" | "1" => Ok(serde_json::Value::Bool(true)),
+                "false" | "no" | "0" => Ok(serde_json::Value::Bool(false)),
+                _ => Err(format!("'{}' is not a boolean", raw)),
+            },
+        }
+    }
+
+    /// Check whether a JSON value matches this type
+    pub fn matches(&self, value: &serde_json::Value) -> bool {
+        match self {
+            Self::String => value.is_string(),
+            Self::Number => value.is_number(),
+            Self::Boolean => value.is_boolean(),
         }
     }
 }
@@ -59,6 +167,120 @@ impl SkillMetadata {
         if self.description.is_empty() {
             return Err("Skill description cannot be empty".to_string());
         }
+
+        let mut seen = std::collections::HashSet::new();
+        for arg in &self.arguments {
+            if arg.name.is_empty()
+                || !arg
+                    .name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+            {
+                return Err(format!("Invalid argument name '{}'", arg.name));
+            }
+            if !seen.insert(arg.name.as_str()) {
+                return Err(format!("Duplicate argument '{}'", arg.name));
+            }
+            if let Some(default) = &arg.default {
+                if !arg.arg_type.matches(default) {
+                    return Err(format!(
+                        "Default for argument '{}' does not match type {:?}",
+                        arg.name, arg.arg_type
+                    ));
+                }
+            }
+        }
         Ok(())
     }
+
+    /// Resolve provided raw argument values against the declared arguments
+    ///
+    /// Values are parsed according to their declared type and defaults are
+    /// applied. Unknown and missing required arguments are rejected.
+    pub fn resolve_arguments(
+        &self,
+        provided: &HashMap<String, String>,
+    ) -> Result<HashMap<String, serde_json::Value>, SkillError> {
+        if let Some(unknown) = provided
+            .keys()
+            .find(|key| !self.arguments.iter().any(|arg| &arg.name == *key))
+        {
+            return Err(SkillError::invalid_argument(
+                &self.name,
+                format!("unknown argument '{}'", unknown),
+            ));
+        }
+
+        let mut values = HashMap::new();
+        for arg in &self.arguments {
+            let value = match provided.get(&arg.name) {
+                Some(raw) => arg.arg_type.parse(raw).map_err(|msg| {
+                    SkillError::invalid_argument(&self.name, format!("{}: {}", arg.name, msg))
+                })?,
+                None => match &arg.default {
+                    Some(default) => default.clone(),
+                    None if arg.required => {
+                        return Err(SkillError::invalid_argument(
+                            &self.name,
+                            format!("missing required argument '{}'", arg.name),
+                        ));
+                    }
+                    None => continue,
+                },
+            };
+            values.insert(arg.name.clone(), value);
+        }
+        Ok(values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn metadata() -> SkillMetadata {
+        serde_json::from_value(json!({
+            "name": "review",
+            "description": "Review code",
+            "arguments": [
+                { "name": "language", "required": true },
+                { "name": "strict", "type": "boolean", "default": false },
+                { "name": "limit", "type": "number" }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_resolve_arguments() {
+        let metadata = metadata();
+        assert!(metadata.validate().is_ok());
+
+        let mut provided = HashMap::new();
+        provided.insert("language".to_string(), "rust".to_string());
+        provided.insert("limit".to_string(), "5".to_string());
+        let values = metadata.resolve_arguments(&provided).unwrap();
+        assert_eq!(values["language"], json!("rust"));
+        assert_eq!(values["strict"], json!(false));
+        assert_eq!(values["limit"], json!(5));
+
+        provided.insert("limit".to_string(), "many".to_string());
+        assert!(metadata.resolve_arguments(&provided).is_err());
+        provided.remove("limit");
+        provided.insert("unknown".to_string(), "x".to_string());
+        assert!(metadata.resolve_arguments(&provided).is_err());
+        assert!(metadata.resolve_arguments(&HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_arguments() {
+        let mut metadata = metadata();
+        metadata.arguments[1].default = Some(json!("yes"));
+        assert!(metadata.validate().is_err());
+
+        let mut metadata = self::metadata();
+        metadata.arguments.push(metadata.arguments[0].clone());
+        assert!(metadata.validate().is_err());
+    }
 }
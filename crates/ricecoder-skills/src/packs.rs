@@ -0,0 +1,728 @@
+//! Remote skill packs with signed manifests
+//!
+//! A skill pack is a directory of skill files described by a `skillpack.json`
+//! manifest listing each file with its SHA-256 digest. The manifest is signed
+//! with HMAC-SHA256 using a key shared by the publishing organization, and is
+//! only installed when the signing key is trusted locally.
+//!
+//! Packs are fetched from a git repository (optionally at a tag, branch or
+//! commit) or from an HTTP base URL serving the manifest and files, then
+//! installed into the user's `skill/` config directory with provenance
+//! metadata so they can be checked for updates, pinned, or removed.
+
+use crate::errors::SkillError;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Component, Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{debug, info};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Manifest file name at the root of a pack
+pub const PACK_MANIFEST: &str = "skillpack.json";
+
+/// Provenance file written into each installed pack directory
+pub const PROVENANCE_FILE: &str = ".provenance.json";
+
+/// Trusted signing keys file in the user config directory (`~/.ricecoder`)
+pub const TRUSTED_KEYS_FILE: &str = "skill-keys.json";
+
+/// Where a skill pack is fetched from
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum PackSource {
+    /// Git repository with the manifest at its root
+    Git {
+        /// Repository URL or local path
+        url: String,
+        /// Optional tag, branch or commit to check out
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reference: Option<String>,
+    },
+    /// HTTP base URL serving `skillpack.json` and the listed files
+    Http {
+        /// Base URL of the pack
+        url: String,
+    },
+}
+
+/// Signed description of a skill pack
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PackManifest {
+    /// Pack name, used as the install directory name
+    pub name: String,
+
+    /// Pack version (semver)
+    pub version: String,
+
+    /// Optional description
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Relative file path -> hex SHA-256 digest
+    pub files: BTreeMap<String, String>,
+
+    /// Identifier of the signing key
+    pub key_id: String,
+
+    /// Hex HMAC-SHA256 signature of the signing payload
+    #[serde(default)]
+    pub signature: String,
+}
+
+impl PackManifest {
+    /// Canonical bytes covered by the signature
+    pub fn signing_payload(&self) -> Vec<u8> {
+        let mut payload = format!("{}\n{}\n{}\n", self.name, self.version, self.key_id);
+        for (path, digest) in &self.files {
+            payload.push_str(&format!("{} {}\n", path, digest));
+        }
+        payload.into_bytes()
+    }
+
+    /// Sign the manifest with the given key (used by pack publishers)
+    pub fn sign(&mut self, key: &str) -> Result<(), SkillError> {
+        let mut mac = HmacSha256::new_from_slice(key.as_bytes())
+            .map_err(|_| SkillError::pack_verification(&self.name, "invalid signing key"))?;
+        mac.update(&self.signing_payload());
+        self.signature = hex::encode(mac.finalize().into_bytes());
+        Ok(())
+    }
+
+    /// Verify the manifest signature against the trusted keys
+    pub fn verify_signature(&self, keys: &TrustedKeys) -> Result<(), SkillError> {
+        let key = keys.get(&self.key_id).ok_or_else(|| {
+            SkillError::pack_verification(
+                &self.name,
+                format!("signing key '{}' is not trusted", self.key_id),
+            )
+        })?;
+        let signature = hex::decode(&self.signature)
+            .map_err(|_| SkillError::pack_verification(&self.name, "malformed signature"))?;
+
+        let mut mac = HmacSha256::new_from_slice(key.as_bytes())
+            .map_err(|_| SkillError::pack_verification(&self.name, "invalid signing key"))?;
+        mac.update(&self.signing_payload());
+        mac.verify_slice(&signature)
+            .map_err(|_| SkillError::pack_verification(&self.name, "signature mismatch"))
+    }
+
+    /// Verify fetched file contents against the manifest digests
+    pub fn verify_files(&self, files: &HashMap<String, Vec<u8>>) -> Result<(), SkillError> {
+        for (path, expected) in &self.files {
+            let content = files.get(path).ok_or_else(|| {
+                SkillError::pack_verification(&self.name, format!("missing file '{}'", path))
+            })?;
+            if &sha256_hex(content) != expected {
+                return Err(SkillError::pack_verification(
+                    &self.name,
+                    format!("digest mismatch for '{}'", path),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Validate the pack name and file paths
+    fn validate(&self) -> Result<(), SkillError> {
+        if self.name.is_empty()
+            || !self
+                .name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(SkillError::pack_verification(
+                &self.name,
+                "invalid pack name",
+            ));
+        }
+        semver::Version::parse(&self.version).map_err(|e| {
+            SkillError::pack_verification(&self.name, format!("invalid version: {}", e))
+        })?;
+        for path in self.files.keys() {
+            let relative = Path::new(path);
+            let safe = !path.is_empty()
+                && relative
+                    .components()
+                    .all(|c| matches!(c, Component::Normal(_)))
+                && path != PROVENANCE_FILE
+                && path != PACK_MANIFEST;
+            if !safe {
+                return Err(SkillError::pack_verification(
+                    &self.name,
+                    format!("invalid file path '{}'", path),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Signing keys trusted for pack installation (key id -> shared secret)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TrustedKeys {
+    keys: HashMap<String, String>,
+}
+
+impl TrustedKeys {
+    /// Create an empty key set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Trust a signing key
+    pub fn add(&mut self, key_id: impl Into<String>, secret: impl Into<String>) {
+        self.keys.insert(key_id.into(), secret.into());
+    }
+
+    /// Get a trusted key by id
+    pub fn get(&self, key_id: &str) -> Option<&str> {
+        self.keys.get(key_id).map(|s| s.as_str())
+    }
+
+    /// Load trusted keys from a JSON object file (`{"key-id": "secret"}`)
+    pub fn load(path: &Path) -> Result<Self, SkillError> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)?;
+        let keys = serde_json::from_str(&content).map_err(|e| {
+            SkillError::PackFetch(format!(
+                "invalid trusted keys file {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        Ok(Self { keys })
+    }
+}
+
+/// Provenance metadata recorded for an installed pack
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PackProvenance {
+    /// Pack name
+    pub name: String,
+    /// Installed version
+    pub version: String,
+    /// Where the pack was fetched from
+    pub source: PackSource,
+    /// Key that signed the installed manifest
+    pub key_id: String,
+    /// Installation time (seconds since the Unix epoch)
+    pub installed_at: u64,
+    /// Whether the pack is pinned to its installed version
+    #[serde(default)]
+    pub pinned: bool,
+    /// Installed files and their digests
+    pub files: BTreeMap<String, String>,
+}
+
+/// Result of an update check for an installed pack
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PackUpdate {
+    /// Pack name
+    pub name: String,
+    /// Installed version
+    pub installed: String,
+    /// Newer version available at the source, if any
+    pub available: Option<String>,
+    /// Whether the pack is pinned (updates are not applied)
+    pub pinned: bool,
+}
+
+/// Fetches, verifies and installs skill packs
+pub struct SkillPackInstaller {
+    /// Directory packs are installed into (a `skill/` config directory)
+    install_root: PathBuf,
+    trusted_keys: TrustedKeys,
+}
+
+impl SkillPackInstaller {
+    /// Create an installer for the given `skill/` directory
+    pub fn new(install_root: PathBuf, trusted_keys: TrustedKeys) -> Self {
+        Self {
+            install_root,
+            trusted_keys,
+        }
+    }
+
+    /// Create an installer for the user config directory (`~/.ricecoder/skill`)
+    pub fn with_default_dir(trusted_keys: TrustedKeys) -> Result<Self, SkillError> {
+        Ok(Self::new(user_config_dir()?.join("skill"), trusted_keys))
+    }
+
+    /// Create an installer for the user config directory, trusting the keys
+    /// listed in `~/.ricecoder/skill-keys.json`
+    pub fn from_user_config() -> Result<Self, SkillError> {
+        let keys = TrustedKeys::load(&user_config_dir()?.join(TRUSTED_KEYS_FILE))?;
+        Self::with_default_dir(keys)
+    }
+
+    /// Directory packs are installed into
+    pub fn install_root(&self) -> &Path {
+        &self.install_root
+    }
+
+    /// Fetch, verify and install a pack
+    ///
+    /// Reinstalling an already installed pack replaces it, unless the pack is
+    /// pinned to a different version.
+    pub async fn install(&self, source: PackSource) -> Result<PackProvenance, SkillError> {
+        let (manifest, files) = fetch_pack(&source).await?;
+        self.install_verified(source, manifest, files)
+    }
+
+    /// List installed packs
+    pub fn installed(&self) -> Result<Vec<PackProvenance>, SkillError> {
+        if !self.install_root.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut packs = Vec::new();
+        for entry in std::fs::read_dir(&self.install_root)? {
+            let provenance_path = entry?.path().join(PROVENANCE_FILE);
+            if provenance_path.is_file() {
+                packs.push(read_provenance(&provenance_path)?);
+            }
+        }
+        packs.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(packs)
+    }
+
+    /// Check installed packs for newer versions at their sources
+    pub async fn check_updates(&self) -> Result<Vec<PackUpdate>, SkillError> {
+        let mut updates = Vec::new();
+        for pack in self.installed()? {
+            let manifest = fetch_manifest(&pack.source).await?;
+            let newer = match (
+                semver::Version::parse(&manifest.version),
+                semver::Version::parse(&pack.version),
+            ) {
+                (Ok(available), Ok(installed)) => available > installed,
+                _ => manifest.version != pack.version,
+            };
+            updates.push(PackUpdate {
+                available: newer.then_some(manifest.version),
+                installed: pack.version,
+                pinned: pack.pinned,
+                name: pack.name,
+            });
+        }
+        Ok(updates)
+    }
+
+    /// Update a pack from its recorded source
+    ///
+    /// Returns `None` if the pack is pinned or already up to date.
+    pub async fn update(&self, name: &str) -> Result<Option<PackProvenance>, SkillError> {
+        let current = self.provenance(name)?;
+        if current.pinned {
+            debug!("Skill pack '{}' is pinned, skipping update", name);
+            return Ok(None);
+        }
+
+        let (manifest, files) = fetch_pack(&current.source).await?;
+        if manifest.version == current.version {
+            return Ok(None);
+        }
+        self.install_verified(current.source, manifest, files)
+            .map(Some)
+    }
+
+    /// Pin or unpin a pack to its installed version
+    pub fn set_pinned(&self, name: &str, pinned: bool) -> Result<PackProvenance, SkillError> {
+        let mut provenance = self.provenance(name)?;
+        provenance.pinned = pinned;
+        write_provenance(&self.pack_dir(name), &provenance)?;
+        Ok(provenance)
+    }
+
+    /// Remove an installed pack
+    pub fn uninstall(&self, name: &str) -> Result<(), SkillError> {
+        self.provenance(name)?;
+        std::fs::remove_dir_all(self.pack_dir(name))?;
+        info!("Uninstalled skill pack '{}'", name);
+        Ok(())
+    }
+
+    /// Get provenance for an installed pack
+    pub fn provenance(&self, name: &str) -> Result<PackProvenance, SkillError> {
+        let path = self.pack_dir(name).join(PROVENANCE_FILE);
+        if !path.is_file() {
+            let installed: Vec<String> = self
+                .installed()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|p| p.name)
+                .collect();
+            return Err(SkillError::not_found(name, &installed));
+        }
+        read_provenance(&path)
+    }
+
+    fn pack_dir(&self, name: &str) -> PathBuf {
+        self.install_root.join(name)
+    }
+
+    /// Verify a fetched pack and write it into its install directory
+    fn install_verified(
+        &self,
+        source: PackSource,
+        manifest: PackManifest,
+        files: HashMap<String, Vec<u8>>,
+    ) -> Result<PackProvenance, SkillError> {
+        manifest.validate()?;
+        manifest.verify_signature(&self.trusted_keys)?;
+        manifest.verify_files(&files)?;
+
+        let pack_dir = self.pack_dir(&manifest.name);
+        let mut pinned = false;
+        if pack_dir.join(PROVENANCE_FILE).is_file() {
+            let existing = read_provenance(&pack_dir.join(PROVENANCE_FILE))?;
+            if existing.pinned && existing.version != manifest.version {
+                return Err(SkillError::PackPinned {
+                    pack: existing.name,
+                    version: existing.version,
+                });
+            }
+            pinned = existing.pinned;
+        } else if pack_dir.exists() {
+            // Never replace skills the user manages by hand
+            return Err(SkillError::pack_verification(
+                &manifest.name,
+                format!("{} exists and is not an installed pack", pack_dir.display()),
+            ));
+        }
+
+        // Stage next to the final location, then swap in
+        let staging = self
+            .install_root
+            .join(format!(".{}.staging", manifest.name));
+        if staging.exists() {
+            std::fs::remove_dir_all(&staging)?;
+        }
+        for (path, content) in &files {
+            if !manifest.files.contains_key(path) {
+                continue;
+            }
+            let target = staging.join(path);
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(target, content)?;
+        }
+
+        let provenance = PackProvenance {
+            name: manifest.name.clone(),
+            version: manifest.version.clone(),
+            source,
+            key_id: manifest.key_id.clone(),
+            installed_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+            pinned,
+            files: manifest.files.clone(),
+        };
+        write_provenance(&staging, &provenance)?;
+
+        if pack_dir.exists() {
+            std::fs::remove_dir_all(&pack_dir)?;
+        }
+        std::fs::rename(&staging, &pack_dir)?;
+
+        info!(
+            "Installed skill pack '{}' v{} into {}",
+            manifest.name,
+            manifest.version,
+            pack_dir.display()
+        );
+        Ok(provenance)
+    }
+}
+
+fn user_config_dir() -> Result<PathBuf, SkillError> {
+    dirs::home_dir()
+        .map(|home| home.join(".ricecoder"))
+        .ok_or_else(|| SkillError::PackFetch("could not determine home directory".to_string()))
+}
+
+/// Hex SHA-256 digest of content
+pub fn sha256_hex(content: &[u8]) -> String {
+    hex::encode(Sha256::digest(content))
+}
+
+fn read_provenance(path: &Path) -> Result<PackProvenance, SkillError> {
+    let content = std::fs::read_to_string(path)?;
+    serde_json::from_str(&content).map_err(|e| {
+        SkillError::PackFetch(format!("invalid provenance file {}: {}", path.display(), e))
+    })
+}
+
+fn write_provenance(pack_dir: &Path, provenance: &PackProvenance) -> Result<(), SkillError> {
+    std::fs::create_dir_all(pack_dir)?;
+    let json = serde_json::to_string_pretty(provenance)
+        .map_err(|e| SkillError::PackFetch(e.to_string()))?;
+    std::fs::write(pack_dir.join(PROVENANCE_FILE), json)?;
+    Ok(())
+}
+
+fn parse_manifest(content: &[u8]) -> Result<PackManifest, SkillError> {
+    serde_json::from_slice(content)
+        .map_err(|e| SkillError::PackFetch(format!("invalid {}: {}", PACK_MANIFEST, e)))
+}
+
+/// Fetch only the manifest of a pack
+async fn fetch_manifest(source: &PackSource) -> Result<PackManifest, SkillError> {
+    match source {
+        PackSource::Http { url } => parse_manifest(&http_get(&join_url(url, PACK_MANIFEST)).await?),
+        PackSource::Git { .. } => fetch_pack(source).await.map(|(manifest, _)| manifest),
+    }
+}
+
+/// Fetch a pack manifest and all files it lists
+async fn fetch_pack(
+    source: &PackSource,
+) -> Result<(PackManifest, HashMap<String, Vec<u8>>), SkillError> {
+    match source {
+        PackSource::Http { url } => {
+            let manifest = parse_manifest(&http_get(&join_url(url, PACK_MANIFEST)).await?)?;
+            manifest.validate()?;
+            let mut files = HashMap::new();
+            for path in manifest.files.keys() {
+                files.insert(path.clone(), http_get(&join_url(url, path)).await?);
+            }
+            Ok((manifest, files))
+        }
+        PackSource::Git { url, reference } => {
+            let (url, reference) = (url.clone(), reference.clone());
+            tokio::task::spawn_blocking(move || fetch_git(&url, reference.as_deref()))
+                .await
+                .map_err(|e| SkillError::PackFetch(format!("git fetch task failed: {}", e)))?
+        }
+    }
+}
+
+fn join_url(base: &str, path: &str) -> String {
+    format!("{}/{}", base.trim_end_matches('/'), path)
+}
+
+async fn http_get(url: &str) -> Result<Vec<u8>, SkillError> {
+    let response = reqwest::get(url)
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| SkillError::PackFetch(format!("GET {} failed: {}", url, e)))?;
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| SkillError::PackFetch(format!("GET {} failed: {}", url, e)))?;
+    Ok(bytes.to_vec())
+}
+
+/// Clone a pack repository into a temporary directory and read its files
+fn fetch_git(
+    url: &str,
+    reference: Option<&str>,
+) -> Result<(PackManifest, HashMap<String, Vec<u8>>), SkillError> {
+    let nonce = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    let checkout = std::env::temp_dir().join(format!(
+        "ricecoder-skillpack-{}-{}",
+        std::process::id(),
+        nonce
+    ));
+
+    let result = (|| -> Result<(PackManifest, HashMap<String, Vec<u8>>), SkillError> {
+        let git_err = |e: git2::Error| SkillError::PackFetch(format!("git {}: {}", url, e));
+        let repo = git2::Repository::clone(url, &checkout).map_err(git_err)?;
+        if let Some(reference) = reference {
+            let object = repo.revparse_single(reference).map_err(git_err)?;
+            repo.checkout_tree(&object, Some(git2::build::CheckoutBuilder::new().force()))
+                .map_err(git_err)?;
+            repo.set_head_detached(object.peel_to_commit().map_err(git_err)?.id())
+                .map_err(git_err)?;
+        }
+
+        let manifest = parse_manifest(&std::fs::read(checkout.join(PACK_MANIFEST))?)?;
+        manifest.validate()?;
+        let mut files = HashMap::new();
+        for path in manifest.files.keys() {
+            files.insert(path.clone(), std::fs::read(checkout.join(path))?);
+        }
+        Ok((manifest, files))
+    })();
+
+    let _ = std::fs::remove_dir_all(&checkout);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const SKILL: &str = "---\nname: lint\ndescription: Lint code\n---\nRun the linter.\n";
+
+    fn keys() -> TrustedKeys {
+        let mut keys = TrustedKeys::new();
+        keys.add("acme", "s3cret");
+        keys
+    }
+
+    /// Create a signed pack in a local git repository and return its path
+    fn publish(dir: &Path, version: &str, body: &str) -> PathBuf {
+        let repo_dir = dir.join("pack-repo");
+        std::fs::create_dir_all(repo_dir.join("lint")).unwrap();
+        std::fs::write(repo_dir.join("lint/SKILL.md"), body).unwrap();
+
+        let mut manifest = PackManifest {
+            name: "acme-skills".to_string(),
+            version: version.to_string(),
+            description: None,
+            files: BTreeMap::from([("lint/SKILL.md".to_string(), sha256_hex(body.as_bytes()))]),
+            key_id: "acme".to_string(),
+            signature: String::new(),
+        };
+        manifest.sign("s3cret").unwrap();
+        std::fs::write(
+            repo_dir.join(PACK_MANIFEST),
+            serde_json::to_string_pretty(&manifest).unwrap(),
+        )
+        .unwrap();
+
+        let repo = git2::Repository::open(&repo_dir)
+            .or_else(|_| git2::Repository::init(&repo_dir))
+            .unwrap();
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("Acme", "skills@acme.test").unwrap();
+        let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            version,
+            &tree,
+            &parents,
+        )
+        .unwrap();
+        repo_dir
+    }
+
+    #[test]
+    fn test_manifest_signature() {
+        let mut manifest = PackManifest {
+            name: "pack".to_string(),
+            version: "1.0.0".to_string(),
+            description: None,
+            files: BTreeMap::from([("a/SKILL.md".to_string(), sha256_hex(b"a"))]),
+            key_id: "acme".to_string(),
+            signature: String::new(),
+        };
+        manifest.sign("s3cret").unwrap();
+        assert!(manifest.verify_signature(&keys()).is_ok());
+
+        let mut untrusted = TrustedKeys::new();
+        untrusted.add("acme", "other");
+        assert!(manifest.verify_signature(&untrusted).is_err());
+        assert!(manifest.verify_signature(&TrustedKeys::new()).is_err());
+
+        manifest.version = "1.0.1".to_string();
+        assert!(manifest.verify_signature(&keys()).is_err());
+    }
+
+    #[test]
+    fn test_manifest_rejects_unsafe_paths() {
+        let manifest = PackManifest {
+            name: "pack".to_string(),
+            version: "1.0.0".to_string(),
+            description: None,
+            files: BTreeMap::from([("../escape/SKILL.md".to_string(), String::new())]),
+            key_id: "acme".to_string(),
+            signature: String::new(),
+        };
+        assert!(manifest.validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_install_pin_and_update_from_git() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_dir = publish(temp_dir.path(), "1.0.0", SKILL);
+        let installer = SkillPackInstaller::new(temp_dir.path().join("skill"), keys());
+        let source = PackSource::Git {
+            url: repo_dir.display().to_string(),
+            reference: None,
+        };
+
+        let provenance = installer.install(source).await.unwrap();
+        assert_eq!(provenance.version, "1.0.0");
+        let installed = temp_dir.path().join("skill/acme-skills/lint/SKILL.md");
+        assert_eq!(std::fs::read_to_string(&installed).unwrap(), SKILL);
+        assert_eq!(installer.installed().unwrap().len(), 1);
+
+        publish(
+            temp_dir.path(),
+            "1.1.0",
+            &SKILL.replace("linter", "linter twice"),
+        );
+        let updates = installer.check_updates().await.unwrap();
+        assert_eq!(updates[0].available.as_deref(), Some("1.1.0"));
+
+        installer.set_pinned("acme-skills", true).unwrap();
+        assert!(installer.update("acme-skills").await.unwrap().is_none());
+        assert_eq!(std::fs::read_to_string(&installed).unwrap(), SKILL);
+
+        installer.set_pinned("acme-skills", false).unwrap();
+        let updated = installer.update("acme-skills").await.unwrap().unwrap();
+        assert_eq!(updated.version, "1.1.0");
+        assert!(std::fs::read_to_string(&installed)
+            .unwrap()
+            .contains("linter twice"));
+
+        installer.uninstall("acme-skills").unwrap();
+        assert!(installer.installed().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_install_rejects_tampered_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_dir = publish(temp_dir.path(), "1.0.0", SKILL);
+        std::fs::write(repo_dir.join("lint/SKILL.md"), "tampered").unwrap();
+        let repo = git2::Repository::open(&repo_dir).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("lint/SKILL.md")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("Mallory", "m@evil.test").unwrap();
+        let parent = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            "tamper",
+            &tree,
+            &[&parent],
+        )
+        .unwrap();
+
+        let installer = SkillPackInstaller::new(temp_dir.path().join("skill"), keys());
+        let result = installer
+            .install(PackSource::Git {
+                url: repo_dir.display().to_string(),
+                reference: None,
+            })
+            .await;
+        assert!(matches!(result, Err(SkillError::PackVerification { .. })));
+        assert!(installer.installed().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,514 @@
+//! Context-aware skill recommendation
+//!
+//! Scores discovered skills against the current session context (language,
+//! recent errors, open files, mode) so the skill tool can advertise a small,
+//! ranked `<available_skills>` subset instead of every installed skill.
+//!
+//! Each skill gets a keyword score (weighted overlap between context terms and
+//! the skill's name, description and tags) and an embedding score (cosine
+//! similarity computed through a [`SkillEmbedder`]). The default embedder is a
+//! local character n-gram model, so ranking works offline; a provider-backed
+//! embedder can be plugged in with [`SkillRecommender::with_embedder`].
+
+use crate::errors::SkillError;
+use crate::models::SkillInfo;
+use async_trait::async_trait;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+
+/// Dimension of vectors produced by [`NGramEmbedder`]
+const NGRAM_DIMENSIONS: usize = 256;
+
+/// Common words ignored when extracting keywords
+const STOPWORDS: &[&str] = &[
+    "the", "and", "for", "with", "this", "that", "from", "into", "when", "use", "used", "using",
+    "not", "are", "was", "has", "have", "had", "but", "you", "your", "its", "all", "any", "can",
+    "error", "file", "line", "skill",
+];
+
+/// Computes embedding vectors for skill and context texts
+#[async_trait]
+pub trait SkillEmbedder: Send + Sync {
+    /// Identifier of the embedding model, used to invalidate cached vectors
+    fn model(&self) -> &str;
+
+    /// Embed texts, returning one vector per text in input order
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, SkillError>;
+}
+
+/// Local embedder hashing character trigrams of words into a fixed-size vector
+#[derive(Debug, Clone, Default)]
+pub struct NGramEmbedder;
+
+#[async_trait]
+impl SkillEmbedder for NGramEmbedder {
+    fn model(&self) -> &str {
+        "ngram-256"
+    }
+
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, SkillError> {
+        Ok(texts.iter().map(|text| ngram_vector(text)).collect())
+    }
+}
+
+/// Current session context used to rank skills
+#[derive(Debug, Clone, Default)]
+pub struct SkillContext {
+    /// Primary language of the session (e.g. "rust")
+    pub language: Option<String>,
+    /// Recent error messages (compiler, test or tool output)
+    pub recent_errors: Vec<String>,
+    /// Files currently open or recently touched
+    pub open_files: Vec<PathBuf>,
+    /// Current mode (e.g. "code", "ask", "vibe")
+    pub mode: Option<String>,
+    /// Free-form description of the current task
+    pub query: Option<String>,
+}
+
+impl SkillContext {
+    /// Create an empty context
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the session language
+    pub fn with_language(mut self, language: impl Into<String>) -> Self {
+        self.language = Some(language.into());
+        self
+    }
+
+    /// Add a recent error message
+    pub fn with_error(mut self, error: impl Into<String>) -> Self {
+        self.recent_errors.push(error.into());
+        self
+    }
+
+    /// Add an open file
+    pub fn with_open_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.open_files.push(path.into());
+        self
+    }
+
+    /// Set the current mode
+    pub fn with_mode(mut self, mode: impl Into<String>) -> Self {
+        self.mode = Some(mode.into());
+        self
+    }
+
+    /// Set a free-form task description
+    pub fn with_query(mut self, query: impl Into<String>) -> Self {
+        self.query = Some(query.into());
+        self
+    }
+
+    /// Whether the context carries any signal to rank on
+    pub fn is_empty(&self) -> bool {
+        self.language.is_none()
+            && self.recent_errors.is_empty()
+            && self.open_files.is_empty()
+            && self.mode.is_none()
+            && self.query.is_none()
+    }
+
+    /// Weighted keyword terms (language and mode weigh more than error text)
+    fn weighted_terms(&self) -> HashMap<String, f32> {
+        let mut terms = HashMap::new();
+        let mut add = |text: &str, weight: f32| {
+            for term in tokenize(text) {
+                let entry = terms.entry(term).or_insert(0.0f32);
+                *entry = entry.max(weight);
+            }
+        };
+
+        if let Some(language) = &self.language {
+            add(language, 3.0);
+        }
+        if let Some(mode) = &self.mode {
+            add(mode, 2.0);
+        }
+        if let Some(query) = &self.query {
+            add(query, 2.0);
+        }
+        for path in &self.open_files {
+            if let Some(language) = path
+                .extension()
+                .and_then(|e| e.to_str())
+                .and_then(language_for_extension)
+            {
+                add(language, 2.0);
+            }
+            if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+                add(stem, 1.0);
+            }
+        }
+        for error in &self.recent_errors {
+            add(error, 1.0);
+        }
+        terms
+    }
+
+    /// Text representation used for embedding similarity
+    fn embedding_text(&self) -> String {
+        let mut parts = Vec::new();
+        parts.extend(self.language.clone());
+        parts.extend(self.mode.clone());
+        parts.extend(self.query.clone());
+        for path in &self.open_files {
+            parts.push(path.display().to_string());
+            if let Some(language) = path
+                .extension()
+                .and_then(|e| e.to_str())
+                .and_then(language_for_extension)
+            {
+                parts.push(language.to_string());
+            }
+        }
+        parts.extend(self.recent_errors.iter().cloned());
+        parts.join(" ")
+    }
+}
+
+/// Recommender tuning
+#[derive(Debug, Clone)]
+pub struct RecommenderConfig {
+    /// Maximum number of skills to recommend
+    pub max_skills: usize,
+    /// Weight of the keyword score in the final score
+    pub keyword_weight: f32,
+    /// Weight of the embedding score in the final score
+    pub embedding_weight: f32,
+    /// Minimum final score for a skill to be recommended
+    pub min_score: f32,
+}
+
+impl Default for RecommenderConfig {
+    fn default() -> Self {
+        Self {
+            max_skills: 10,
+            keyword_weight: 0.6,
+            embedding_weight: 0.4,
+            min_score: 0.05,
+        }
+    }
+}
+
+/// A ranked skill
+#[derive(Debug, Clone)]
+pub struct SkillRecommendation {
+    /// The recommended skill
+    pub skill: SkillInfo,
+    /// Final weighted score
+    pub score: f32,
+    /// Keyword overlap score (0..1)
+    pub keyword_score: f32,
+    /// Embedding similarity score (0..1)
+    pub embedding_score: f32,
+}
+
+/// Ranks skills by relevance to a session context
+pub struct SkillRecommender {
+    config: RecommenderConfig,
+    embedder: Arc<dyn SkillEmbedder>,
+    /// Skill name -> (model, embedded text, vector)
+    cache: RwLock<HashMap<String, (String, String, Vec<f32>)>>,
+}
+
+impl Default for SkillRecommender {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SkillRecommender {
+    /// Create a recommender using the local n-gram embedder
+    pub fn new() -> Self {
+        Self {
+            config: RecommenderConfig::default(),
+            embedder: Arc::new(NGramEmbedder),
+            cache: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Use a different embedder (e.g. provider-backed)
+    pub fn with_embedder(mut self, embedder: Arc<dyn SkillEmbedder>) -> Self {
+        self.embedder = embedder;
+        self.cache = RwLock::new(HashMap::new());
+        self
+    }
+
+    /// Use custom tuning
+    pub fn with_config(mut self, config: RecommenderConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Recommender tuning
+    pub fn config(&self) -> &RecommenderConfig {
+        &self.config
+    }
+
+    /// Rank skills against the context, returning at most `max_skills` above `min_score`
+    pub async fn rank(
+        &self,
+        skills: &[SkillInfo],
+        context: &SkillContext,
+    ) -> Result<Vec<SkillRecommendation>, SkillError> {
+        if skills.is_empty() || context.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let context_terms = context.weighted_terms();
+        let context_vector = self
+            .embedder
+            .embed(&[context.embedding_text()])
+            .await?
+            .pop()
+            .unwrap_or_default();
+        let skill_vectors = self.skill_vectors(skills).await?;
+
+        let mut ranked: Vec<SkillRecommendation> = skills
+            .iter()
+            .zip(skill_vectors)
+            .map(|(skill, vector)| {
+                let keyword_score = keyword_score(&context_terms, skill);
+                let embedding_score = cosine(&context_vector, &vector).max(0.0);
+                SkillRecommendation {
+                    skill: skill.clone(),
+                    score: self.config.keyword_weight * keyword_score
+                        + self.config.embedding_weight * embedding_score,
+                    keyword_score,
+                    embedding_score,
+                }
+            })
+            .filter(|r| r.score >= self.config.min_score)
+            .collect();
+
+        ranked.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.skill.name.cmp(&b.skill.name))
+        });
+        ranked.truncate(self.config.max_skills);
+        Ok(ranked)
+    }
+
+    /// Embed skills, reusing cached vectors for unchanged skills
+    async fn skill_vectors(&self, skills: &[SkillInfo]) -> Result<Vec<Vec<f32>>, SkillError> {
+        let model = self.embedder.model().to_string();
+        let texts: Vec<String> = skills.iter().map(skill_text).collect();
+
+        let missing: Vec<usize> = {
+            let cache = self.cache.read().map_err(|_| lock_error())?;
+            (0..skills.len())
+                .filter(|&i| match cache.get(&skills[i].name) {
+                    Some((cached_model, cached_text, _)) => {
+                        cached_model != &model || cached_text != &texts[i]
+                    }
+                    None => true,
+                })
+                .collect()
+        };
+
+        if !missing.is_empty() {
+            let batch: Vec<String> = missing.iter().map(|&i| texts[i].clone()).collect();
+            let vectors = self.embedder.embed(&batch).await?;
+            let mut cache = self.cache.write().map_err(|_| lock_error())?;
+            for (&i, vector) in missing.iter().zip(vectors) {
+                cache.insert(
+                    skills[i].name.clone(),
+                    (model.clone(), texts[i].clone(), vector),
+                );
+            }
+        }
+
+        let cache = self.cache.read().map_err(|_| lock_error())?;
+        Ok(skills
+            .iter()
+            .map(|skill| {
+                cache
+                    .get(&skill.name)
+                    .map(|(_, _, vector)| vector.clone())
+                    .unwrap_or_default()
+            })
+            .collect())
+    }
+}
+
+fn lock_error() -> SkillError {
+    SkillError::Io(std::io::Error::new(
+        std::io::ErrorKind::Other,
+        "Failed to acquire recommender cache lock",
+    ))
+}
+
+/// Text used to embed a skill
+fn skill_text(skill: &SkillInfo) -> String {
+    format!(
+        "{} {} {}",
+        skill.name.replace(['-', '_'], " "),
+        skill.description,
+        skill.tags.join(" ")
+    )
+}
+
+/// Weighted fraction of context terms found in the skill, saturating towards 1
+fn keyword_score(context_terms: &HashMap<String, f32>, skill: &SkillInfo) -> f32 {
+    let skill_terms: HashSet<String> = tokenize(&skill_text(skill)).collect();
+    let matched: f32 = context_terms
+        .iter()
+        .filter(|(term, _)| skill_terms.contains(*term))
+        .map(|(_, weight)| weight)
+        .sum();
+    matched / (matched + 3.0)
+}
+
+/// Lowercase keyword tokens, without stopwords and very short fragments
+fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .map(|t| t.to_lowercase())
+        .filter(|t| t.len() >= 2 && !t.chars().all(|c| c.is_ascii_digit()))
+        .filter(|t| !STOPWORDS.contains(&t.as_str()))
+}
+
+/// Map a file extension to a language keyword
+fn language_for_extension(extension: &str) -> Option<&'static str> {
+    Some(match extension.to_lowercase().as_str() {
+        "rs" => "rust",
+        "py" => "python",
+        "ts" | "tsx" => "typescript",
+        "js" | "jsx" | "mjs" => "javascript",
+        "go" => "go",
+        "java" => "java",
+        "kt" | "kts" => "kotlin",
+        "rb" => "ruby",
+        "c" | "h" => "c",
+        "cc" | "cpp" | "hpp" => "cpp",
+        "cs" => "csharp",
+        "swift" => "swift",
+        "php" => "php",
+        "sh" | "bash" | "zsh" => "shell",
+        "sql" => "sql",
+        "md" => "markdown",
+        "toml" => "toml",
+        "yml" | "yaml" => "yaml",
+        "json" => "json",
+        "html" => "html",
+        "css" | "scss" => "css",
+        _ => return None,
+    })
+}
+
+/// Hash word trigrams into a normalized fixed-size vector
+fn ngram_vector(text: &str) -> Vec<f32> {
+    let mut vector = vec![0.0f32; NGRAM_DIMENSIONS];
+    for word in tokenize(text) {
+        let padded: Vec<char> = format!(" {} ", word).chars().collect();
+        for window in padded.windows(3) {
+            let mut hasher = DefaultHasher::new();
+            window.hash(&mut hasher);
+            vector[(hasher.finish() as usize) % NGRAM_DIMENSIONS] += 1.0;
+        }
+    }
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|v| *v /= norm);
+    }
+    vector
+}
+
+fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|v| v * v).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn skill(name: &str, description: &str, tags: &[&str]) -> SkillInfo {
+        SkillInfo::new(
+            name.to_string(),
+            description.to_string(),
+            PathBuf::from(format!("/skills/{}/SKILL.md", name)),
+        )
+        .with_tags(tags.iter().map(|t| t.to_string()).collect())
+    }
+
+    fn skills() -> Vec<SkillInfo> {
+        vec![
+            skill(
+                "rust-borrow-checker",
+                "Fix borrow checker and lifetime errors in Rust code",
+                &["rust"],
+            ),
+            skill(
+                "python-packaging",
+                "Publish Python packages with pyproject",
+                &["python"],
+            ),
+            skill(
+                "sql-migrations",
+                "Write reversible database migrations",
+                &["sql"],
+            ),
+            skill("git-rebase", "Interactive rebase and history cleanup", &[]),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_rank_prefers_matching_language_and_errors() {
+        let recommender = SkillRecommender::new();
+        let context = SkillContext::new()
+            .with_open_file("src/main.rs")
+            .with_error("error[E0502]: cannot borrow `x` as mutable because it is also borrowed");
+
+        let ranked = recommender.rank(&skills(), &context).await.unwrap();
+        assert_eq!(ranked[0].skill.name, "rust-borrow-checker");
+        assert!(ranked[0].keyword_score > 0.0);
+        assert!(ranked
+            .iter()
+            .all(|r| r.skill.name != "python-packaging" || r.score < ranked[0].score));
+    }
+
+    #[tokio::test]
+    async fn test_rank_limits_results_and_ignores_empty_context() {
+        let recommender = SkillRecommender::new().with_config(RecommenderConfig {
+            max_skills: 1,
+            min_score: 0.0,
+            ..RecommenderConfig::default()
+        });
+        let context = SkillContext::new().with_language("python");
+
+        let ranked = recommender.rank(&skills(), &context).await.unwrap();
+        assert_eq!(ranked.len(), 1);
+        assert_eq!(ranked[0].skill.name, "python-packaging");
+
+        assert!(recommender
+            .rank(&skills(), &SkillContext::new())
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_ngram_vectors_are_similar_for_related_text() {
+        let a = ngram_vector("borrow checker lifetime");
+        let b = ngram_vector("borrowed lifetimes");
+        let c = ngram_vector("database migration");
+        assert!(cosine(&a, &b) > cosine(&a, &c));
+    }
+}
@@ -5,6 +5,8 @@
 
 use crate::errors::SkillError;
 use crate::models::{SkillInfo, SkillMetadata};
+use crate::packs::{PackProvenance, PackSource, SkillPackInstaller};
+use crate::template::SkillTemplate;
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -79,6 +81,32 @@ impl SkillRegistry {
         Self::ensure_initialized().await
     }
 
+    /// Install a signed skill pack into the user config directory and reload
+    ///
+    /// Signing keys are trusted via `~/.ricecoder/skill-keys.json`.
+    pub async fn install_pack(source: PackSource) -> Result<PackProvenance, SkillError> {
+        let provenance = SkillPackInstaller::from_user_config()?.install(source).await?;
+        Self::reload().await?;
+        Ok(provenance)
+    }
+
+    /// Update all unpinned skill packs with newer versions and reload
+    pub async fn update_packs() -> Result<Vec<PackProvenance>, SkillError> {
+        let installer = SkillPackInstaller::from_user_config()?;
+        let mut updated = Vec::new();
+        for update in installer.check_updates().await? {
+            if update.available.is_some() && !update.pinned {
+                if let Some(provenance) = installer.update(&update.name).await? {
+                    updated.push(provenance);
+                }
+            }
+        }
+        if !updated.is_empty() {
+            Self::reload().await?;
+        }
+        Ok(updated)
+    }
+
     /// Ensure registry is initialized (Gap G-17-01)
     async fn ensure_initialized() -> Result<(), SkillError> {
         {
@@ -217,10 +245,20 @@ impl SkillRegistry {
             message: msg,
         })?;
 
+        // Validate body template against declared arguments
+        SkillTemplate::parse(&parsed.content)
+            .and_then(|template| template.validate_against(&metadata.arguments))
+            .map_err(|msg| SkillError::InvalidSkill {
+                path: path.display().to_string(),
+                message: msg,
+            })?;
+
         Ok(SkillInfo {
             name: metadata.name,
             description: metadata.description,
             location: path.to_path_buf(),
+            arguments: metadata.arguments,
+            tags: metadata.tags,
         })
     }
 }
@@ -250,4 +288,43 @@ description: A test skill
         // For now, it just verifies the API compiles
         assert!(SkillRegistry::all().await.is_ok());
     }
+
+    #[test]
+    fn test_parse_skill_file_with_arguments() {
+        let temp_dir = TempDir::new().unwrap();
+        let skill_md = temp_dir.path().join("SKILL.md");
+        fs::write(&skill_md, r#"---
+name: review
+description: Review code
+arguments:
+  - name: language
+    required: true
+  - name: strict
+    type: boolean
+    default: false
+---
+Review this {{language}} code.{{#strict}} Be strict.{{/strict}}
+"#).unwrap();
+
+        let info = SkillRegistry::parse_skill_file(&skill_md).unwrap();
+        assert_eq!(info.arguments.len(), 2);
+        assert_eq!(info.arguments[1].arg_type, crate::models::SkillArgumentType::Boolean);
+    }
+
+    #[test]
+    fn test_parse_skill_file_rejects_undeclared_template_variable() {
+        let temp_dir = TempDir::new().unwrap();
+        let skill_md = temp_dir.path().join("SKILL.md");
+        fs::write(&skill_md, r#"---
+name: review
+description: Review code
+---
+Review this {{language}} code.
+"#).unwrap();
+
+        assert!(matches!(
+            SkillRegistry::parse_skill_file(&skill_md),
+            Err(SkillError::InvalidSkill { .. })
+        ));
+    }
 }
@@ -0,0 +1,247 @@
+//! Mustache-style templating for parameterized skill bodies
+//!
+//! Supports a small mustache subset suited to prompts (no HTML escaping):
+//! - `{{name}}` - argument value (empty if not provided)
+//! - `{{#name}}...{{/name}}` - rendered when the argument is truthy
+//! - `{{^name}}...{{/name}}` - rendered when the argument is falsy or missing
+//! - `{{! comment}}` - ignored
+
+use crate::models::SkillArgument;
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+
+/// Parsed template node
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Text(String),
+    Variable(String),
+    Section {
+        name: String,
+        inverted: bool,
+        children: Vec<Node>,
+    },
+}
+
+/// A parsed skill body template
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkillTemplate {
+    nodes: Vec<Node>,
+}
+
+/// Open section on the parser stack: (name, inverted), or `None` for the root
+type Frame = (Option<(String, bool)>, Vec<Node>);
+
+impl SkillTemplate {
+    /// Parse a template, reporting unbalanced or malformed tags
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let mut stack: Vec<Frame> = vec![(None, Vec::new())];
+        let mut rest = source;
+
+        while let Some(start) = rest.find("{{") {
+            push_text(&mut stack, &rest[..start]);
+            let after = &rest[start + 2..];
+            let end = after
+                .find("}}")
+                .ok_or_else(|| "Unclosed '{{' tag".to_string())?;
+            let tag = after[..end].trim();
+            rest = &after[end + 2..];
+
+            if let Some(name) = tag.strip_prefix('#') {
+                stack.push((Some((tag_name(name)?, false)), Vec::new()));
+            } else if let Some(name) = tag.strip_prefix('^') {
+                stack.push((Some((tag_name(name)?, true)), Vec::new()));
+            } else if let Some(name) = tag.strip_prefix('/') {
+                let name = tag_name(name)?;
+                if stack.len() == 1 {
+                    return Err(format!("Unexpected closing tag '{{{{/{}}}}}'", name));
+                }
+                let (open, children) = stack.pop().expect("stack has an open section");
+                let (open_name, inverted) = open.expect("only the root frame has no section");
+                if open_name != name {
+                    return Err(format!(
+                        "Section '{}' closed by '{{{{/{}}}}}'",
+                        open_name, name
+                    ));
+                }
+                current(&mut stack).push(Node::Section {
+                    name,
+                    inverted,
+                    children,
+                });
+            } else if !tag.starts_with('!') {
+                let name = tag_name(tag)?;
+                current(&mut stack).push(Node::Variable(name));
+            }
+        }
+        push_text(&mut stack, rest);
+
+        if stack.len() > 1 {
+            let name = stack
+                .last()
+                .and_then(|(open, _)| open.as_ref())
+                .map(|(name, _)| name.clone())
+                .unwrap_or_default();
+            return Err(format!("Unclosed section '{}'", name));
+        }
+        let (_, nodes) = stack.pop().expect("root frame");
+        Ok(Self { nodes })
+    }
+
+    /// Names referenced by variables and sections, sorted and deduplicated
+    pub fn variables(&self) -> Vec<String> {
+        let mut names = BTreeSet::new();
+        collect_variables(&self.nodes, &mut names);
+        names.into_iter().collect()
+    }
+
+    /// Check that every referenced name is a declared argument
+    pub fn validate_against(&self, arguments: &[SkillArgument]) -> Result<(), String> {
+        match self
+            .variables()
+            .into_iter()
+            .find(|name| !arguments.iter().any(|arg| &arg.name == name))
+        {
+            Some(name) => Err(format!(
+                "Template references undeclared argument '{}'",
+                name
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Render the template with resolved argument values
+    pub fn render(&self, values: &HashMap<String, Value>) -> String {
+        let mut output = String::new();
+        render_nodes(&self.nodes, values, &mut output);
+        output
+    }
+}
+
+fn current(stack: &mut [Frame]) -> &mut Vec<Node> {
+    &mut stack.last_mut().expect("root frame").1
+}
+
+fn push_text(stack: &mut [Frame], text: &str) {
+    if !text.is_empty() {
+        current(stack).push(Node::Text(text.to_string()));
+    }
+}
+
+fn tag_name(raw: &str) -> Result<String, String> {
+    let name = raw.trim();
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        return Err(format!("Invalid template tag '{}'", name));
+    }
+    Ok(name.to_string())
+}
+
+fn collect_variables(nodes: &[Node], names: &mut BTreeSet<String>) {
+    for node in nodes {
+        match node {
+            Node::Text(_) => {}
+            Node::Variable(name) => {
+                names.insert(name.clone());
+            }
+            Node::Section { name, children, .. } => {
+                names.insert(name.clone());
+                collect_variables(children, names);
+            }
+        }
+    }
+}
+
+fn is_truthy(value: Option<&Value>) -> bool {
+    match value {
+        None | Some(Value::Null) => false,
+        Some(Value::Bool(b)) => *b,
+        Some(Value::String(s)) => !s.is_empty(),
+        Some(Value::Number(n)) => n.as_f64().map_or(true, |n| n != 0.0),
+        Some(Value::Array(a)) => !a.is_empty(),
+        Some(Value::Object(_)) => true,
+    }
+}
+
+fn render_nodes(nodes: &[Node], values: &HashMap<String, Value>, output: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => output.push_str(text),
+            Node::Variable(name) => match values.get(name) {
+                None | Some(Value::Null) => {}
+                Some(Value::String(s)) => output.push_str(s),
+                Some(other) => output.push_str(&other.to_string()),
+            },
+            Node::Section {
+                name,
+                inverted,
+                children,
+            } => {
+                if is_truthy(values.get(name)) != *inverted {
+                    render_nodes(children, values, output);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::SkillArgumentType;
+    use serde_json::json;
+
+    fn arg(name: &str) -> SkillArgument {
+        SkillArgument {
+            name: name.to_string(),
+            arg_type: SkillArgumentType::String,
+            description: None,
+            default: None,
+            required: false,
+        }
+    }
+
+    #[test]
+    fn test_render_variables_and_sections() {
+        let template = SkillTemplate::parse(
+            "Review {{ language }} code.{{! note }}{{#strict}} Be strict.{{/strict}}{{^strict}} Be lenient.{{/strict}} Max {{limit}}.",
+        )
+        .unwrap();
+        assert_eq!(template.variables(), vec!["language", "limit", "strict"]);
+
+        let mut values = HashMap::new();
+        values.insert("language".to_string(), json!("Rust"));
+        values.insert("strict".to_string(), json!(true));
+        values.insert("limit".to_string(), json!(3));
+        assert_eq!(
+            template.render(&values),
+            "Review Rust code. Be strict. Max 3."
+        );
+
+        values.insert("strict".to_string(), json!(false));
+        values.remove("limit");
+        assert_eq!(
+            template.render(&values),
+            "Review Rust code. Be lenient. Max ."
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(SkillTemplate::parse("{{name").is_err());
+        assert!(SkillTemplate::parse("{{#a}}x").is_err());
+        assert!(SkillTemplate::parse("{{#a}}x{{/b}}").is_err());
+        assert!(SkillTemplate::parse("x{{/a}}").is_err());
+        assert!(SkillTemplate::parse("{{bad name}}").is_err());
+        assert!(SkillTemplate::parse("plain markdown").is_ok());
+    }
+
+    #[test]
+    fn test_validate_against_declared_arguments() {
+        let template = SkillTemplate::parse("{{a}} {{#b}}{{/b}}").unwrap();
+        assert!(template.validate_against(&[arg("a"), arg("b")]).is_ok());
+        assert!(template.validate_against(&[arg("a")]).is_err());
+    }
+}
@@ -4,9 +4,11 @@
 //! Matches OpenCode SkillTool behavior.
 
 use crate::errors::SkillError;
-use crate::models::SkillInfo;
+use crate::models::{SkillInfo, SkillMetadata};
 use crate::permissions::{SkillPermission, SkillPermissionAction, SkillPermissionChecker};
+use crate::recommender::{SkillContext, SkillRecommender};
 use crate::registry::SkillRegistry;
+use crate::template::SkillTemplate;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::Path;
@@ -16,6 +18,41 @@ use std::path::Path;
 pub struct SkillToolInput {
     /// Skill identifier from available_skills
     pub name: String,
+
+    /// Raw argument values for parameterized skills
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub arguments: HashMap<String, String>,
+}
+
+impl SkillToolInput {
+    /// Build input from command line style arguments
+    ///
+    /// Accepts `--name value`, `--name=value` and bare `--flag` (treated as `true`),
+    /// as in `skill run <name> --language rust --strict`.
+    pub fn from_cli_args(name: impl Into<String>, args: &[String]) -> Result<Self, SkillError> {
+        let name = name.into();
+        let mut arguments = HashMap::new();
+        let mut iter = args.iter().peekable();
+
+        while let Some(arg) = iter.next() {
+            let key = arg.strip_prefix("--").filter(|k| !k.is_empty()).ok_or_else(|| {
+                SkillError::invalid_argument(&name, format!("unexpected value '{}'", arg))
+            })?;
+
+            let (key, value) = match key.split_once('=') {
+                Some((key, value)) => (key.to_string(), value.to_string()),
+                None => match iter.peek() {
+                    Some(next) if !next.starts_with("--") => {
+                        (key.to_string(), iter.next().cloned().unwrap_or_default())
+                    }
+                    _ => (key.to_string(), "true".to_string()),
+                },
+            };
+            arguments.insert(key, value);
+        }
+
+        Ok(Self { name, arguments })
+    }
 }
 
 /// Skill tool output (OpenCode ToolResult format)
@@ -41,6 +78,7 @@ pub struct SkillToolMetadata {
 /// Skill tool provider (Gap G-17-02)
 pub struct SkillToolProvider {
     permission_checker: SkillPermissionChecker,
+    recommender: SkillRecommender,
 }
 
 impl Default for SkillToolProvider {
@@ -54,19 +92,57 @@ impl SkillToolProvider {
     pub fn new() -> Self {
         Self {
             permission_checker: SkillPermissionChecker::new(),
+            recommender: SkillRecommender::new(),
         }
     }
 
+    /// Use a custom recommender for context-aware descriptions
+    pub fn with_recommender(mut self, recommender: SkillRecommender) -> Self {
+        self.recommender = recommender;
+        self
+    }
+
     /// Get tool description with available skills (Gap G-17-11)
     /// Filters skills by agent permissions if provided
     pub async fn get_description(
         &self,
         agent_permissions: Option<&SkillPermission>,
     ) -> Result<String, SkillError> {
+        let skills = Self::accessible_skills(agent_permissions).await?;
+        Ok(Self::format_description(&skills, None))
+    }
+
+    /// Get tool description listing only the skills most relevant to the context
+    ///
+    /// Installs with no more skills than the recommender's `max_skills` are
+    /// listed in full, as is everything when the context has no signal.
+    pub async fn get_description_for_context(
+        &self,
+        agent_permissions: Option<&SkillPermission>,
+        context: &SkillContext,
+    ) -> Result<String, SkillError> {
+        let skills = Self::accessible_skills(agent_permissions).await?;
+        if skills.len() <= self.recommender.config().max_skills || context.is_empty() {
+            return Ok(Self::format_description(&skills, None));
+        }
+
+        let recommended: Vec<SkillInfo> = self
+            .recommender
+            .rank(&skills, context)
+            .await?
+            .into_iter()
+            .map(|r| r.skill)
+            .collect();
+        Ok(Self::format_description(&recommended, Some(skills.len())))
+    }
+
+    /// Skills the agent may load (Gap G-17-11, G-17-12)
+    async fn accessible_skills(
+        agent_permissions: Option<&SkillPermission>,
+    ) -> Result<Vec<SkillInfo>, SkillError> {
         let skills = SkillRegistry::all().await?;
-        
-        // Filter accessible skills (Gap G-17-11, G-17-12)
-        let accessible_skills: Vec<SkillInfo> = if let Some(perms) = agent_permissions {
+
+        Ok(if let Some(perms) = agent_permissions {
             skills
                 .into_iter()
                 .filter(|skill| {
@@ -76,17 +152,28 @@ impl SkillToolProvider {
                 .collect()
         } else {
             skills
-        };
+        })
+    }
 
-        // Build description with XML-like skill list (Gap G-17-11 - OpenCode format)
+    /// Build description with XML-like skill list (Gap G-17-11 - OpenCode format)
+    ///
+    /// `total` is the number of accessible skills when only a subset is listed.
+    fn format_description(skills: &[SkillInfo], total: Option<usize>) -> String {
         let mut desc = vec![
             "Load a skill to get detailed instructions for a specific task.".to_string(),
             "Skills provide specialized knowledge and step-by-step guidance.".to_string(),
             "Use this when a task matches an available skill's description.".to_string(),
-            "<available_skills>".to_string(),
         ];
+        if let Some(total) = total {
+            desc.push(format!(
+                "Showing the {} of {} skills most relevant to the current context; other skills can still be loaded by name.",
+                skills.len(),
+                total
+            ));
+        }
+        desc.push("<available_skills>".to_string());
 
-        for skill in accessible_skills {
+        for skill in skills {
             desc.push(format!("  <skill>"));
             desc.push(format!("    <name>{}</name>", skill.name));
             desc.push(format!("    <description>{}</description>", skill.description));
@@ -95,7 +182,7 @@ impl SkillToolProvider {
 
         desc.push("</available_skills>".to_string());
 
-        Ok(desc.join("\n"))
+        desc.join("\n")
     }
 
     /// Execute skill tool (Gap G-17-02, G-17-03, G-17-04, G-17-10)
@@ -143,8 +230,15 @@ impl SkillToolProvider {
             }
         }
 
-        // Load skill content
-        let content = self.load_skill_content(&skill.location)?;
+        // Load skill content and render declared arguments
+        let (metadata, body) = self.load_skill(&skill.location)?;
+        let values = metadata.resolve_arguments(&input.arguments)?;
+        let content = SkillTemplate::parse(&body)
+            .map_err(|msg| SkillError::InvalidSkill {
+                path: skill.location.display().to_string(),
+                message: msg,
+            })?
+            .render(&values);
         let dir = skill.location
             .parent()
             .and_then(|p| p.to_str())
@@ -180,6 +274,26 @@ impl SkillToolProvider {
         Ok(parsed.content)
     }
 
+    /// Load skill metadata and body
+    fn load_skill(&self, path: &Path) -> Result<(SkillMetadata, String), SkillError> {
+        let content = std::fs::read_to_string(path)?;
+
+        let matter = gray_matter::Matter::<gray_matter::engine::YAML>::new();
+        let parsed = matter.parse(&content);
+        let metadata: SkillMetadata = parsed
+            .data
+            .ok_or_else(|| SkillError::MissingField {
+                field: "frontmatter".to_string(),
+                path: path.display().to_string(),
+            })
+            .and_then(|data| {
+                serde_json::from_value(data.into())
+                    .map_err(|e| SkillError::YamlParse(e.to_string()))
+            })?;
+
+        Ok((metadata, parsed.content))
+    }
+
     /// Get the permission checker (for session management)
     pub fn permission_checker(&self) -> &SkillPermissionChecker {
         &self.permission_checker
@@ -216,6 +330,22 @@ mod tests {
         assert!(desc.is_ok());
     }
 
+    #[test]
+    fn test_format_description_with_subset() {
+        let skills = vec![SkillInfo::new(
+            "lint".to_string(),
+            "Run linters".to_string(),
+            std::path::PathBuf::from("/skills/lint/SKILL.md"),
+        )];
+
+        let full = SkillToolProvider::format_description(&skills, None);
+        assert!(full.contains("<name>lint</name>"));
+        assert!(!full.contains("Showing"));
+
+        let subset = SkillToolProvider::format_description(&skills, Some(40));
+        assert!(subset.contains("Showing the 1 of 40 skills"));
+    }
+
     #[test]
     fn test_load_skill_content() {
         use tempfile::NamedTempFile;
@@ -236,4 +366,21 @@ mod tests {
         assert!(content_str.contains("# Skill Content"));
         assert!(!content_str.contains("---"));
     }
+
+    #[test]
+    fn test_input_from_cli_args() {
+        let args: Vec<String> = ["--language", "rust", "--strict", "--limit=3"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let input = SkillToolInput::from_cli_args("review", &args).unwrap();
+
+        assert_eq!(input.name, "review");
+        assert_eq!(input.arguments["language"], "rust");
+        assert_eq!(input.arguments["strict"], "true");
+        assert_eq!(input.arguments["limit"], "3");
+
+        let args = vec!["rust".to_string()];
+        assert!(SkillToolInput::from_cli_args("review", &args).is_err());
+    }
 }
@@ -134,6 +134,23 @@ impl ConfigMerger {
                 .insert(key.clone(), value.clone());
         }
 
+        // Higher-precedence tokenizer files are matched first
+        let mut position = 0;
+        for tokenizer in &source.providers.tokenizers {
+            if !target.providers.tokenizers.contains(tokenizer) {
+                decisions.push(MergeDecision {
+                    key: "providers.tokenizers".to_string(),
+                    source: source_name.to_string(),
+                    value: tokenizer.path.display().to_string(),
+                });
+                target
+                    .providers
+                    .tokenizers
+                    .insert(position, tokenizer.clone());
+                position += 1;
+            }
+        }
+
         // Merge defaults
         if let Some(ref model) = source.defaults.model {
             if target.defaults.model != source.defaults.model {
@@ -168,6 +185,62 @@ impl ConfigMerger {
             }
         }
 
+        // Merge TUI color overrides
+        for (key, value) in &source.tui.theme_overrides {
+            if target.tui.theme_overrides.get(key) != Some(value) {
+                decisions.push(MergeDecision {
+                    key: format!("tui.theme_overrides.{}", key),
+                    source: source_name.to_string(),
+                    value: value.clone(),
+                });
+            }
+            target
+                .tui
+                .theme_overrides
+                .insert(key.clone(), value.clone());
+        }
+
+        // A status bar layout replaces the previous one as a whole
+        if !source.tui.status_bar.segments.is_empty()
+            && target.tui.status_bar != source.tui.status_bar
+        {
+            decisions.push(MergeDecision {
+                key: "tui.status_bar.segments".to_string(),
+                source: source_name.to_string(),
+                value: source
+                    .tui
+                    .status_bar
+                    .segments
+                    .iter()
+                    .map(|segment| segment.id.as_str())
+                    .collect::<Vec<_>>()
+                    .join(","),
+            });
+            target.tui.status_bar = source.tui.status_bar.clone();
+        }
+
+        // Merge hyperlink settings
+        if let Some(enabled) = source.tui.hyperlinks.enabled {
+            if target.tui.hyperlinks.enabled != Some(enabled) {
+                decisions.push(MergeDecision {
+                    key: "tui.hyperlinks.enabled".to_string(),
+                    source: source_name.to_string(),
+                    value: enabled.to_string(),
+                });
+            }
+            target.tui.hyperlinks.enabled = Some(enabled);
+        }
+        if let Some(editor) = &source.tui.hyperlinks.editor {
+            if target.tui.hyperlinks.editor.as_ref() != Some(editor) {
+                decisions.push(MergeDecision {
+                    key: "tui.hyperlinks.editor".to_string(),
+                    source: source_name.to_string(),
+                    value: editor.clone(),
+                });
+            }
+            target.tui.hyperlinks.editor = Some(editor.clone());
+        }
+
         // Merge Governance
         for rule in &source.Governance {
             if !target.Governance.iter().any(|r| r.name == rule.name) {
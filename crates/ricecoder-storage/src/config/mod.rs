@@ -13,7 +13,10 @@ pub mod modes;
 pub mod validation;
 
 // Re-export commonly used types
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::PathBuf,
+};
 
 pub use cli::CliArgs;
 pub use documents::{Document, DocumentLoader};
@@ -54,6 +57,21 @@ pub struct ProvidersConfig {
     pub endpoints: HashMap<String, String>,
     /// Default provider to use
     pub default_provider: Option<String>,
+    /// Tokenizer files for model families without a built-in tokenizer
+    #[serde(default)]
+    pub tokenizers: Vec<TokenizerFileConfig>,
+}
+
+/// A tiktoken-format BPE vocabulary for a model family
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TokenizerFileConfig {
+    /// Model id prefixes the tokenizer applies to (e.g. "llama-3", "meta-llama/llama-3")
+    pub models: Vec<String>,
+    /// Path to the rank file (one `base64-token rank` pair per line)
+    pub path: PathBuf,
+    /// Pre-tokenization regex; the cl100k pattern when unset
+    #[serde(default)]
+    pub pattern: Option<String>,
 }
 
 /// Default settings
@@ -97,6 +115,55 @@ pub struct TuiConfig {
     /// Enable vim keybindings
     #[serde(default)]
     pub vim_mode: bool,
+    /// Per-color theme overrides (color name -> color value, e.g. `diff.added: "#1e3c1e"`)
+    #[serde(default)]
+    pub theme_overrides: BTreeMap<String, String>,
+    /// Status bar segment order and visibility
+    #[serde(default)]
+    pub status_bar: TuiStatusBarConfig,
+    /// Terminal hyperlinks and opening of `path:line:col` references
+    #[serde(default)]
+    pub hyperlinks: TuiHyperlinkConfig,
+}
+
+/// Hyperlink configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct TuiHyperlinkConfig {
+    /// Emit OSC 8 hyperlinks; unset detects terminal support
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    /// Command that opens file locations (e.g. `code --wait`, `nvim`);
+    /// unset opens them in the built-in editor
+    #[serde(default)]
+    pub editor: Option<String>,
+}
+
+/// Status bar layout configuration
+///
+/// Segments are shown in the listed order. An empty list keeps the built-in
+/// layout; built-in segments missing from a non-empty list are hidden.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct TuiStatusBarConfig {
+    /// Segments in display order
+    #[serde(default)]
+    pub segments: Vec<StatusSegmentConfig>,
+}
+
+/// Status bar segment entry
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StatusSegmentConfig {
+    /// Segment id (`mode`, `model`, `tokens`, `vcs`, `jobs`, `lsp` or a plugin id)
+    pub id: String,
+    /// Whether the segment is shown
+    #[serde(default = "default_segment_visible")]
+    pub visible: bool,
+    /// Truncation priority override; higher priorities are kept longer
+    #[serde(default)]
+    pub priority: Option<u8>,
+}
+
+fn default_segment_visible() -> bool {
+    true
 }
 
 /// TUI accessibility configuration
@@ -122,6 +189,9 @@ impl Default for TuiConfig {
             height: None,
             accessibility: TuiAccessibilityConfig::default(),
             vim_mode: false,
+            theme_overrides: BTreeMap::new(),
+            status_bar: TuiStatusBarConfig::default(),
+            hyperlinks: TuiHyperlinkConfig::default(),
         }
     }
 }
@@ -144,6 +214,7 @@ impl Default for Config {
                 api_keys: HashMap::new(),
                 endpoints: HashMap::new(),
                 default_provider: None,
+                tokenizers: Vec::new(),
             },
             defaults: DefaultsConfig {
                 model: None,
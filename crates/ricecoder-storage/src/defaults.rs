@@ -38,6 +38,22 @@ tui:
   animations: true
   mouse: true
   vim_mode: false
+  # theme_overrides:
+  #   diff.added: '#1e3c1e'
+  #   diff.removed: '#3c1e1e'
+  # status_bar:
+  #   segments:
+  #     - id: mode
+  #     - id: model
+  #     - id: tokens
+  #       priority: 40
+  #     - id: vcs
+  #     - id: jobs
+  #       visible: false
+  #     - id: lsp
+  # hyperlinks:
+  #   enabled: true        # unset detects terminal support
+  #   editor: code --wait  # unset opens files in the built-in editor
 
 # Accessibility settings
 accessibility:
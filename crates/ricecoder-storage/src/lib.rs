@@ -52,7 +52,8 @@ pub use cache_implementations::{
 pub use config::{
     hot_reload::{ConfigConflictResolver, HotReloadManager},
     CliArgs, Config, ConfigLoader, ConfigMerger, DefaultsConfig, DocumentLoader, EnvOverrides,
-    ProvidersConfig, StorageModeHandler, TuiAccessibilityConfig, TuiConfig,
+    ProvidersConfig, StatusSegmentConfig, StorageModeHandler, TokenizerFileConfig,
+    TuiAccessibilityConfig, TuiConfig, TuiHyperlinkConfig, TuiStatusBarConfig,
 };
 pub use config_cache::ConfigCache;
 pub use defaults::{DefaultsManager, EmbeddedDefault};
@@ -70,6 +71,6 @@ pub use types::{
 // Re-export loaders
 pub use loaders::{
     global_lsp_configs, Agent, AgentLoader, AuthLoader, Command, CommandLoader, LspConfig,
-    LspConfigLoader, PromptCategory, PromptLoader, ProviderAuth, ProvidersAuth, Theme,
-    ThemeLoader, TipsLoader,
+    LspConfigLoader, PromptBinding, PromptBindings, PromptCategory, PromptLoader, PromptVariant,
+    ProviderAuth, ProvidersAuth, Theme, ThemeLoader, TipsLoader, VersionedPrompt,
 };
@@ -7,7 +7,7 @@
 //! - LSP: Language Server Protocol server configurations
 //! - Models: AI model definitions with pricing and capabilities
 //! - Themes: JSON theme files with color definitions
-//! - Prompts: Template prompts organized by category
+//! - Prompts: Template prompts organized by category, plus versioned system prompts
 //! - Tips: User tips displayed in the UI
 //! - Tools: External tool descriptions from text files
 
@@ -26,7 +26,9 @@ pub use auth::{AuthLoader, ProviderAuth, ProvidersAuth};
 pub use commands::{Command, CommandLoader};
 pub use lsp::{global_lsp_configs, LspConfig, LspConfigLoader};
 pub use models::{Model, ModelLoader, ModelPricing, Provider};
-pub use prompts::{PromptCategory, PromptLoader};
+pub use prompts::{
+    PromptBinding, PromptBindings, PromptCategory, PromptLoader, PromptVariant, VersionedPrompt,
+};
 pub use themes::{Theme, ThemeLoader};
 pub use tips::TipsLoader;
 pub use tools::{global_tool_descriptions, ToolDescriptionLoader};
@@ -2,11 +2,17 @@
 //!
 //! Loads prompt templates from `config/prompts/{category}/*.txt` files.
 //! Prompts are organized by category (session, agent, tool, command).
+//!
+//! Versioned system prompts live in `config/prompts/library/{id}/v{N}.md`,
+//! with optional YAML frontmatter declaring template variables, and their
+//! mode/agent bindings in `config/prompts/library/bindings.yaml`.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use serde::{Deserialize, Serialize};
+
 use crate::error::{StorageError, StorageResult};
 
 /// Prompt categories matching the directory structure
@@ -45,6 +51,72 @@ impl PromptCategory {
     }
 }
 
+/// One version of a system prompt template
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VersionedPrompt {
+    /// Prompt identifier (the library directory name)
+    pub id: String,
+    /// Version number (from the `v{N}.md` file name)
+    pub version: u32,
+    /// Description from frontmatter
+    #[serde(default)]
+    pub description: String,
+    /// Declared variables with optional default values
+    #[serde(default)]
+    pub variables: BTreeMap<String, Option<String>>,
+    /// Template body with `{{variable}}` placeholders
+    pub body: String,
+}
+
+/// Frontmatter structure for versioned prompt files
+#[derive(Debug, Default, Deserialize)]
+struct PromptFrontmatter {
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    variables: BTreeMap<String, Option<String>>,
+}
+
+/// A weighted A/B variant of a bound prompt
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PromptVariant {
+    /// Variant name recorded in the audit trail
+    pub name: String,
+    /// Prompt version served by this variant
+    pub version: u32,
+    /// Relative share of traffic
+    #[serde(default = "default_weight")]
+    pub weight: u32,
+}
+
+fn default_weight() -> u32 {
+    1
+}
+
+/// Binds a mode or agent to a prompt
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PromptBinding {
+    /// Prompt identifier
+    pub prompt: String,
+    /// Pinned version (latest when unset)
+    #[serde(default)]
+    pub version: Option<u32>,
+    /// A/B variants; when present they take precedence over `version`
+    #[serde(default)]
+    pub variants: Vec<PromptVariant>,
+}
+
+/// Per-mode and per-agent prompt bindings from `bindings.yaml`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PromptBindings {
+    /// Bindings keyed by mode name
+    #[serde(default)]
+    pub modes: HashMap<String, PromptBinding>,
+    /// Bindings keyed by agent name
+    #[serde(default)]
+    pub agents: HashMap<String, PromptBinding>,
+}
+
 /// Loader for prompt templates
 pub struct PromptLoader {
     config_dir: PathBuf,
@@ -146,6 +218,121 @@ impl PromptLoader {
         names.sort();
         Ok(names)
     }
+
+    /// Directory holding the versioned prompt library
+    pub fn library_dir(&self) -> PathBuf {
+        self.config_dir.join("library")
+    }
+
+    /// Load every version of one prompt, ordered by version
+    pub fn load_versions(&self, id: &str) -> StorageResult<Vec<VersionedPrompt>> {
+        let prompt_dir = self.library_dir().join(id);
+        let mut versions = Vec::new();
+
+        if !prompt_dir.exists() {
+            return Ok(versions);
+        }
+
+        let entries = fs::read_dir(&prompt_dir).map_err(|e| {
+            StorageError::io_error(prompt_dir.clone(), crate::error::IoOperation::Read, e)
+        })?;
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "md") {
+                let version = path
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .and_then(|stem| stem.strip_prefix('v'))
+                    .and_then(|n| n.parse::<u32>().ok());
+                if let Some(version) = version {
+                    versions.push(self.load_versioned_file(&path, id, version)?);
+                }
+            }
+        }
+
+        versions.sort_by_key(|prompt| prompt.version);
+        Ok(versions)
+    }
+
+    /// Load all versioned prompts in the library
+    pub fn load_library(&self) -> StorageResult<Vec<VersionedPrompt>> {
+        let library_dir = self.library_dir();
+        let mut prompts = Vec::new();
+
+        if !library_dir.exists() {
+            return Ok(prompts);
+        }
+
+        let entries = fs::read_dir(&library_dir).map_err(|e| {
+            StorageError::io_error(library_dir.clone(), crate::error::IoOperation::Read, e)
+        })?;
+
+        for entry in entries.flatten() {
+            if entry.path().is_dir() {
+                if let Some(id) = entry.file_name().to_str() {
+                    prompts.extend(self.load_versions(id)?);
+                }
+            }
+        }
+
+        Ok(prompts)
+    }
+
+    /// Load mode/agent bindings from `library/bindings.yaml`
+    pub fn load_bindings(&self) -> StorageResult<PromptBindings> {
+        let path = self.library_dir().join("bindings.yaml");
+        if !path.exists() {
+            return Ok(PromptBindings::default());
+        }
+
+        let content = fs::read_to_string(&path).map_err(|e| {
+            StorageError::io_error(path.clone(), crate::error::IoOperation::Read, e)
+        })?;
+        serde_yaml::from_str(&content)
+            .map_err(|e| StorageError::parse_error(path, "YAML", e.to_string()))
+    }
+
+    /// Parse one versioned prompt file with optional YAML frontmatter
+    fn load_versioned_file(
+        &self,
+        path: &Path,
+        id: &str,
+        version: u32,
+    ) -> StorageResult<VersionedPrompt> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            StorageError::io_error(path.to_path_buf(), crate::error::IoOperation::Read, e)
+        })?;
+
+        let (meta, body) = match Self::split_frontmatter(&content) {
+            Some((frontmatter, body)) => {
+                let meta: PromptFrontmatter = serde_yaml::from_str(frontmatter).map_err(|e| {
+                    StorageError::parse_error(
+                        path.to_path_buf(),
+                        "YAML frontmatter",
+                        e.to_string(),
+                    )
+                })?;
+                (meta, body)
+            }
+            None => (PromptFrontmatter::default(), content.as_str()),
+        };
+
+        Ok(VersionedPrompt {
+            id: id.to_string(),
+            version,
+            description: meta.description,
+            variables: meta.variables,
+            body: body.trim().to_string(),
+        })
+    }
+
+    /// Split optional YAML frontmatter from the body
+    fn split_frontmatter(content: &str) -> Option<(&str, &str)> {
+        let rest = content.trim_start().strip_prefix("---")?;
+        let end_idx = rest.find("\n---")?;
+        Some((rest[..end_idx].trim(), &rest[end_idx + 4..]))
+    }
 }
 
 #[cfg(test)]
@@ -168,4 +355,42 @@ mod tests {
         );
         assert_eq!(PromptCategory::from_dir_name("unknown"), None);
     }
+
+    #[test]
+    fn test_load_library_versions_and_bindings() {
+        let dir = tempfile::tempdir().unwrap();
+        let prompt_dir = dir.path().join("library").join("coder");
+        fs::create_dir_all(&prompt_dir).unwrap();
+        fs::write(prompt_dir.join("v1.md"), "You write code.").unwrap();
+        fs::write(
+            prompt_dir.join("v2.md"),
+            "---\ndescription: Adds project name\nvariables:\n  project: ~\n  tone: concise\n---\nYou write {{tone}} code for {{project}}.\n",
+        )
+        .unwrap();
+        fs::write(prompt_dir.join("notes.md"), "not a version").unwrap();
+        fs::write(
+            dir.path().join("library").join("bindings.yaml"),
+            "modes:\n  code:\n    prompt: coder\n    variants:\n      - { name: control, version: 1 }\n      - { name: project, version: 2, weight: 3 }\n",
+        )
+        .unwrap();
+
+        let loader = PromptLoader::new(dir.path().to_path_buf());
+        let versions = loader.load_versions("coder").unwrap();
+        assert_eq!(versions.len(), 2);
+        assert_eq!(versions[0].body, "You write code.");
+        assert_eq!(versions[1].version, 2);
+        assert_eq!(versions[1].description, "Adds project name");
+        assert_eq!(versions[1].variables.get("project"), Some(&None));
+        assert_eq!(
+            versions[1].variables.get("tone"),
+            Some(&Some("concise".to_string()))
+        );
+        assert_eq!(loader.load_library().unwrap().len(), 2);
+
+        let bindings = loader.load_bindings().unwrap();
+        let code = &bindings.modes["code"];
+        assert_eq!(code.prompt, "coder");
+        assert_eq!(code.variants[0].weight, 1);
+        assert_eq!(code.variants[1].weight, 3);
+    }
 }
@@ -150,6 +150,16 @@ impl AccessControlManager {
         Ok(())
     }
 
+    /// Record a team action in the audit log
+    pub fn record_audit(&self, team_id: &str, action: &str, member_id: &str, context: &str) {
+        let tool_name = format!("team:{}:{}", team_id, action);
+        let _ = self.audit_logger.log_execution(
+            tool_name,
+            Some(member_id.to_string()),
+            Some(context.to_string()),
+        );
+    }
+
     /// Get audit log entries
     pub async fn get_audit_log(&self, team_id: &str) -> Result<Vec<AuditLogEntry>> {
         // Query audit log for team-related entries
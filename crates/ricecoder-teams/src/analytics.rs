@@ -1,8 +1,14 @@
+use std::{collections::HashMap, sync::Arc};
+
 use chrono::Utc;
+use tokio::sync::RwLock;
 
 /// Analytics and metrics tracking
+use crate::compliance_checker::ComplianceReport;
 use crate::error::Result;
-use crate::models::{AdoptionMetrics, EffectivenessMetrics, TeamAnalyticsReport};
+use crate::models::{
+    AdoptionMetrics, ComplianceSummary, EffectivenessMetrics, TeamAnalyticsReport,
+};
 
 /// Tracks rule adoption and effectiveness metrics
 ///
@@ -10,10 +16,13 @@ use crate::models::{AdoptionMetrics, EffectivenessMetrics, TeamAnalyticsReport};
 /// - Rule adoption metrics (percentage of team members applying rules)
 /// - Rule effectiveness metrics (positive/negative outcomes from rule application)
 /// - Team analytics reports (comprehensive metrics across all rules)
+/// - Latest standards compliance check per team
 pub struct AnalyticsDashboard {
     // Placeholder for ricecoder-learning AnalyticsEngine integration
     // In production, this would hold a reference to the AnalyticsEngine
     _phantom: std::marker::PhantomData<()>,
+    /// Latest compliance summary per team (team_id -> summary)
+    compliance: Arc<RwLock<HashMap<String, ComplianceSummary>>>,
 }
 
 impl AnalyticsDashboard {
@@ -27,6 +36,7 @@ impl AnalyticsDashboard {
     pub fn new() -> Self {
         AnalyticsDashboard {
             _phantom: std::marker::PhantomData,
+            compliance: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
@@ -108,9 +118,37 @@ impl AnalyticsDashboard {
             total_members: 0,
             adoption_metrics: Vec::new(),
             effectiveness_metrics: Vec::new(),
+            compliance: self.get_compliance_summary(team_id).await,
             generated_at: Utc::now(),
         })
     }
+
+    /// Record the result of a compliance check for a team
+    ///
+    /// Replaces any previously recorded result; it is included in subsequent
+    /// team analytics reports.
+    ///
+    /// # Arguments
+    /// * `team_id` - The ID of the team whose standards were checked
+    /// * `report` - The compliance report to record
+    pub async fn record_compliance(&self, team_id: &str, report: &ComplianceReport) {
+        tracing::info!(
+            team_id = %team_id,
+            errors = report.summary.errors,
+            warnings = report.summary.warnings,
+            "Recording compliance check"
+        );
+
+        self.compliance
+            .write()
+            .await
+            .insert(team_id.to_string(), report.summary.clone());
+    }
+
+    /// Get the latest recorded compliance summary for a team
+    pub async fn get_compliance_summary(&self, team_id: &str) -> Option<ComplianceSummary> {
+        self.compliance.read().await.get(team_id).cloned()
+    }
 }
 
 impl Default for AnalyticsDashboard {
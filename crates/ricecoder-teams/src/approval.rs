@@ -0,0 +1,626 @@
+use std::{collections::HashMap, sync::Arc};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+/// Review workflow for changes to team standards and shared rules
+use crate::{
+    access::AccessControlManager,
+    config::TeamConfigManager,
+    error::{Result, TeamError},
+    models::{RuleScope, SharedRule, TeamRole, TeamStandards},
+    rules::SharedRulesManager,
+};
+
+/// Change awaiting review
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ProposedChange {
+    /// Replace the team's standards
+    Standards(TeamStandards),
+    /// Promote a shared rule between scopes
+    SharedRule {
+        rule: SharedRule,
+        from_scope: RuleScope,
+        to_scope: RuleScope,
+    },
+}
+
+impl ProposedChange {
+    fn kind(&self) -> &'static str {
+        match self {
+            ProposedChange::Standards(_) => "standards",
+            ProposedChange::SharedRule { .. } => "shared_rule",
+        }
+    }
+}
+
+/// Lifecycle of a change proposal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProposalStatus {
+    Pending,
+    Approved,
+    Rejected,
+    Applied,
+    Withdrawn,
+}
+
+impl ProposalStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProposalStatus::Pending => "pending",
+            ProposalStatus::Approved => "approved",
+            ProposalStatus::Rejected => "rejected",
+            ProposalStatus::Applied => "applied",
+            ProposalStatus::Withdrawn => "withdrawn",
+        }
+    }
+}
+
+/// Reviewer verdict
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReviewDecision {
+    Approve,
+    Reject,
+}
+
+/// A single review on a proposal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeReview {
+    pub reviewer_id: String,
+    pub reviewer_role: TeamRole,
+    pub decision: ReviewDecision,
+    pub comment: String,
+    pub reviewed_at: DateTime<Utc>,
+}
+
+/// Record of an admin applying a change without completing review
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewBypass {
+    pub admin_id: String,
+    pub justification: String,
+    pub bypassed_at: DateTime<Utc>,
+}
+
+/// Proposed change with its review state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeProposal {
+    pub id: String,
+    pub team_id: String,
+    pub proposed_by: String,
+    pub description: String,
+    pub change: ProposedChange,
+    /// Standards version the change was proposed against
+    pub base_version: Option<u32>,
+    pub status: ProposalStatus,
+    pub reviews: Vec<ChangeReview>,
+    pub bypass: Option<ReviewBypass>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub applied_at: Option<DateTime<Utc>>,
+}
+
+impl ChangeProposal {
+    /// Number of approving reviews
+    pub fn approvals(&self) -> usize {
+        self.reviews
+            .iter()
+            .filter(|r| r.decision == ReviewDecision::Approve)
+            .count()
+    }
+}
+
+/// Who may review and how many approvals a change needs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApprovalPolicy {
+    /// Approvals needed before a change can be applied
+    pub required_approvals: usize,
+    /// Roles allowed to review
+    pub reviewer_roles: Vec<TeamRole>,
+    /// Whether proposers may review their own changes
+    pub allow_self_review: bool,
+}
+
+impl Default for ApprovalPolicy {
+    fn default() -> Self {
+        ApprovalPolicy {
+            required_approvals: 1,
+            reviewer_roles: vec![TeamRole::Admin],
+            allow_self_review: false,
+        }
+    }
+}
+
+/// Manages propose → review → apply workflows for standards and shared rules
+///
+/// Every step is written to the team audit log. Members can see pending
+/// changes; only reviewers in the team's [`ApprovalPolicy`] can approve or
+/// reject; only admins can bypass review, and must give a justification.
+pub struct ApprovalManager {
+    config_manager: Arc<TeamConfigManager>,
+    rules_manager: Arc<SharedRulesManager>,
+    access_control: Arc<AccessControlManager>,
+    /// Per-team policies (team_id -> policy); teams without one use the default
+    policies: Arc<RwLock<HashMap<String, ApprovalPolicy>>>,
+    /// Proposals by id
+    proposals: Arc<RwLock<HashMap<String, ChangeProposal>>>,
+}
+
+impl ApprovalManager {
+    /// Create a new ApprovalManager
+    pub fn new(
+        config_manager: Arc<TeamConfigManager>,
+        rules_manager: Arc<SharedRulesManager>,
+        access_control: Arc<AccessControlManager>,
+    ) -> Self {
+        ApprovalManager {
+            config_manager,
+            rules_manager,
+            access_control,
+            policies: Arc::new(RwLock::new(HashMap::new())),
+            proposals: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Set the approval policy for a team (admins only)
+    pub async fn set_policy(
+        &self,
+        team_id: &str,
+        admin_id: &str,
+        policy: ApprovalPolicy,
+    ) -> Result<()> {
+        self.require_role(team_id, admin_id, &[TeamRole::Admin], "set approval policy")
+            .await?;
+        if policy.required_approvals == 0 {
+            return Err(TeamError::ConfigError(
+                "Approval policy must require at least one approval".to_string(),
+            ));
+        }
+
+        let context = format!(
+            "Set approval policy: {} approval(s) from {:?}",
+            policy.required_approvals,
+            policy
+                .reviewer_roles
+                .iter()
+                .map(|r| r.as_str())
+                .collect::<Vec<_>>()
+        );
+        self.policies
+            .write()
+            .await
+            .insert(team_id.to_string(), policy);
+        self.access_control
+            .record_audit(team_id, "set_approval_policy", admin_id, &context);
+
+        Ok(())
+    }
+
+    /// Get the approval policy for a team
+    pub async fn get_policy(&self, team_id: &str) -> ApprovalPolicy {
+        self.policies
+            .read()
+            .await
+            .get(team_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Propose a change (admins and members)
+    pub async fn propose(
+        &self,
+        team_id: &str,
+        member_id: &str,
+        description: &str,
+        change: ProposedChange,
+    ) -> Result<ChangeProposal> {
+        self.require_role(
+            team_id,
+            member_id,
+            &[TeamRole::Admin, TeamRole::Member],
+            "propose changes",
+        )
+        .await?;
+
+        if let ProposedChange::Standards(standards) = &change {
+            if standards.team_id != team_id {
+                return Err(TeamError::ConfigError(format!(
+                    "Proposed standards belong to team {}, not {}",
+                    standards.team_id, team_id
+                )));
+            }
+        }
+
+        let base_version = match &change {
+            ProposedChange::Standards(_) => self
+                .config_manager
+                .get_standards(team_id)
+                .await
+                .ok()
+                .map(|s| s.version),
+            ProposedChange::SharedRule { .. } => None,
+        };
+
+        let now = Utc::now();
+        let proposal = ChangeProposal {
+            id: Uuid::new_v4().to_string(),
+            team_id: team_id.to_string(),
+            proposed_by: member_id.to_string(),
+            description: description.to_string(),
+            change,
+            base_version,
+            status: ProposalStatus::Pending,
+            reviews: Vec::new(),
+            bypass: None,
+            created_at: now,
+            updated_at: now,
+            applied_at: None,
+        };
+
+        self.proposals
+            .write()
+            .await
+            .insert(proposal.id.clone(), proposal.clone());
+
+        tracing::info!(
+            team_id = %team_id,
+            proposal_id = %proposal.id,
+            kind = %proposal.change.kind(),
+            "Change proposed"
+        );
+        self.access_control.record_audit(
+            team_id,
+            "propose_change",
+            member_id,
+            &format!(
+                "Proposed {} change {}: {}",
+                proposal.change.kind(),
+                proposal.id,
+                description
+            ),
+        );
+
+        Ok(proposal)
+    }
+
+    /// Approve or reject a pending proposal
+    pub async fn review(
+        &self,
+        proposal_id: &str,
+        reviewer_id: &str,
+        decision: ReviewDecision,
+        comment: &str,
+    ) -> Result<ChangeProposal> {
+        let team_id = self.get_proposal(proposal_id).await?.team_id;
+        let policy = self.get_policy(&team_id).await;
+        let reviewer_role = self
+            .require_role(
+                &team_id,
+                reviewer_id,
+                &policy.reviewer_roles,
+                "review changes",
+            )
+            .await?;
+
+        let mut proposals = self.proposals.write().await;
+        let proposal = proposals
+            .get_mut(proposal_id)
+            .ok_or_else(|| TeamError::ProposalNotFound(proposal_id.to_string()))?;
+
+        if proposal.status != ProposalStatus::Pending {
+            return Err(TeamError::InvalidProposalState(format!(
+                "Proposal {} is {}",
+                proposal_id,
+                proposal.status.as_str()
+            )));
+        }
+        if proposal.proposed_by == reviewer_id && !policy.allow_self_review {
+            return Err(TeamError::PermissionDenied(format!(
+                "Member {} cannot review their own proposal",
+                reviewer_id
+            )));
+        }
+        if proposal
+            .reviews
+            .iter()
+            .any(|r| r.reviewer_id == reviewer_id)
+        {
+            return Err(TeamError::InvalidProposalState(format!(
+                "Member {} has already reviewed proposal {}",
+                reviewer_id, proposal_id
+            )));
+        }
+        if decision == ReviewDecision::Reject && comment.trim().is_empty() {
+            return Err(TeamError::ConfigError(
+                "Rejections require a comment".to_string(),
+            ));
+        }
+
+        let now = Utc::now();
+        proposal.reviews.push(ChangeReview {
+            reviewer_id: reviewer_id.to_string(),
+            reviewer_role,
+            decision,
+            comment: comment.to_string(),
+            reviewed_at: now,
+        });
+        proposal.updated_at = now;
+        proposal.status = match decision {
+            ReviewDecision::Reject => ProposalStatus::Rejected,
+            ReviewDecision::Approve if proposal.approvals() >= policy.required_approvals => {
+                ProposalStatus::Approved
+            }
+            ReviewDecision::Approve => ProposalStatus::Pending,
+        };
+
+        let action = match decision {
+            ReviewDecision::Approve => "approve_change",
+            ReviewDecision::Reject => "reject_change",
+        };
+        self.access_control.record_audit(
+            &team_id,
+            action,
+            reviewer_id,
+            &format!(
+                "Reviewed proposal {} ({}): {}",
+                proposal_id,
+                proposal.status.as_str(),
+                comment
+            ),
+        );
+
+        Ok(proposal.clone())
+    }
+
+    /// Apply an approved proposal (admins and members)
+    pub async fn apply(&self, proposal_id: &str, member_id: &str) -> Result<ChangeProposal> {
+        let proposal = self.get_proposal(proposal_id).await?;
+        self.require_role(
+            &proposal.team_id,
+            member_id,
+            &[TeamRole::Admin, TeamRole::Member],
+            "apply changes",
+        )
+        .await?;
+
+        if proposal.status != ProposalStatus::Approved {
+            return Err(TeamError::InvalidProposalState(format!(
+                "Proposal {} is {}, not approved",
+                proposal_id,
+                proposal.status.as_str()
+            )));
+        }
+
+        self.apply_change(&proposal, member_id).await?;
+        let applied = self.mark_applied(proposal_id, None).await?;
+        self.access_control.record_audit(
+            &proposal.team_id,
+            "apply_change",
+            member_id,
+            &format!("Applied proposal {}", proposal_id),
+        );
+
+        Ok(applied)
+    }
+
+    /// Apply a pending or approved proposal without completing review (admins only)
+    pub async fn bypass_review(
+        &self,
+        proposal_id: &str,
+        admin_id: &str,
+        justification: &str,
+    ) -> Result<ChangeProposal> {
+        let proposal = self.get_proposal(proposal_id).await?;
+        self.require_role(
+            &proposal.team_id,
+            admin_id,
+            &[TeamRole::Admin],
+            "bypass review",
+        )
+        .await?;
+
+        if justification.trim().is_empty() {
+            return Err(TeamError::PermissionDenied(
+                "Bypassing review requires a justification".to_string(),
+            ));
+        }
+        if !matches!(
+            proposal.status,
+            ProposalStatus::Pending | ProposalStatus::Approved
+        ) {
+            return Err(TeamError::InvalidProposalState(format!(
+                "Proposal {} is {}",
+                proposal_id,
+                proposal.status.as_str()
+            )));
+        }
+
+        self.apply_change(&proposal, admin_id).await?;
+        let bypass = ReviewBypass {
+            admin_id: admin_id.to_string(),
+            justification: justification.to_string(),
+            bypassed_at: Utc::now(),
+        };
+        let applied = self.mark_applied(proposal_id, Some(bypass)).await?;
+
+        tracing::warn!(
+            team_id = %proposal.team_id,
+            proposal_id = %proposal_id,
+            admin_id = %admin_id,
+            "Review bypassed"
+        );
+        self.access_control.record_audit(
+            &proposal.team_id,
+            "bypass_review",
+            admin_id,
+            &format!(
+                "Applied proposal {} without review: {}",
+                proposal_id, justification
+            ),
+        );
+
+        Ok(applied)
+    }
+
+    /// Withdraw a pending proposal (proposer or admin)
+    pub async fn withdraw(&self, proposal_id: &str, member_id: &str) -> Result<ChangeProposal> {
+        let proposal = self.get_proposal(proposal_id).await?;
+        if proposal.proposed_by != member_id {
+            self.require_role(
+                &proposal.team_id,
+                member_id,
+                &[TeamRole::Admin],
+                "withdraw others' proposals",
+            )
+            .await?;
+        }
+
+        let mut proposals = self.proposals.write().await;
+        let proposal = proposals
+            .get_mut(proposal_id)
+            .ok_or_else(|| TeamError::ProposalNotFound(proposal_id.to_string()))?;
+        if proposal.status != ProposalStatus::Pending {
+            return Err(TeamError::InvalidProposalState(format!(
+                "Proposal {} is {}",
+                proposal_id,
+                proposal.status.as_str()
+            )));
+        }
+        proposal.status = ProposalStatus::Withdrawn;
+        proposal.updated_at = Utc::now();
+
+        self.access_control.record_audit(
+            &proposal.team_id,
+            "withdraw_change",
+            member_id,
+            &format!("Withdrew proposal {}", proposal_id),
+        );
+
+        Ok(proposal.clone())
+    }
+
+    /// Pending and approved-but-unapplied proposals, visible to any team member
+    pub async fn pending_changes(
+        &self,
+        team_id: &str,
+        member_id: &str,
+    ) -> Result<Vec<ChangeProposal>> {
+        self.require_role(
+            team_id,
+            member_id,
+            &[TeamRole::Admin, TeamRole::Member, TeamRole::Viewer],
+            "view pending changes",
+        )
+        .await?;
+
+        let proposals = self.proposals.read().await;
+        let mut pending: Vec<ChangeProposal> = proposals
+            .values()
+            .filter(|p| {
+                p.team_id == team_id
+                    && matches!(p.status, ProposalStatus::Pending | ProposalStatus::Approved)
+            })
+            .cloned()
+            .collect();
+        pending.sort_by_key(|p| p.created_at);
+
+        Ok(pending)
+    }
+
+    /// Get a proposal by id
+    pub async fn get_proposal(&self, proposal_id: &str) -> Result<ChangeProposal> {
+        self.proposals
+            .read()
+            .await
+            .get(proposal_id)
+            .cloned()
+            .ok_or_else(|| TeamError::ProposalNotFound(proposal_id.to_string()))
+    }
+
+    // Helper functions
+
+    /// Ensure a member holds one of the allowed roles, returning it
+    async fn require_role(
+        &self,
+        team_id: &str,
+        member_id: &str,
+        allowed: &[TeamRole],
+        action: &str,
+    ) -> Result<TeamRole> {
+        match self
+            .access_control
+            .get_member_role(team_id, member_id)
+            .await?
+        {
+            Some(role) if allowed.contains(&role) => Ok(role),
+            Some(role) => Err(TeamError::PermissionDenied(format!(
+                "Role {} cannot {}",
+                role.as_str(),
+                action
+            ))),
+            None => Err(TeamError::MemberNotFound(member_id.to_string())),
+        }
+    }
+
+    /// Perform the underlying change
+    async fn apply_change(&self, proposal: &ChangeProposal, member_id: &str) -> Result<()> {
+        match &proposal.change {
+            ProposedChange::Standards(standards) => {
+                let current_version = self
+                    .config_manager
+                    .get_standards(&proposal.team_id)
+                    .await
+                    .ok()
+                    .map(|s| s.version);
+                if current_version != proposal.base_version {
+                    return Err(TeamError::ConcurrentModification);
+                }
+
+                let mut standards = standards.clone();
+                standards.version = current_version.unwrap_or(0) + 1;
+                standards.updated_at = Utc::now();
+                self.config_manager
+                    .store_standards(&proposal.team_id, standards)
+                    .await?;
+                self.config_manager
+                    .track_changes(
+                        &proposal.team_id,
+                        &format!(
+                            "{} (proposal {}, applied by {})",
+                            proposal.description, proposal.id, member_id
+                        ),
+                    )
+                    .await?;
+            }
+            ProposedChange::SharedRule {
+                rule,
+                from_scope,
+                to_scope,
+            } => {
+                self.rules_manager
+                    .promote_rule(rule.clone(), *from_scope, *to_scope)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn mark_applied(
+        &self,
+        proposal_id: &str,
+        bypass: Option<ReviewBypass>,
+    ) -> Result<ChangeProposal> {
+        let mut proposals = self.proposals.write().await;
+        let proposal = proposals
+            .get_mut(proposal_id)
+            .ok_or_else(|| TeamError::ProposalNotFound(proposal_id.to_string()))?;
+        let now = Utc::now();
+        proposal.status = ProposalStatus::Applied;
+        proposal.bypass = bypass;
+        proposal.applied_at = Some(now);
+        proposal.updated_at = now;
+        Ok(proposal.clone())
+    }
+}
@@ -0,0 +1,584 @@
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use ricecoder_research::{CaseStyle, NamingConventions, ProjectAnalyzer, StandardsDetector};
+use serde::{Deserialize, Serialize};
+use walkdir::{DirEntry, WalkDir};
+
+/// Compliance checking of projects against merged team standards
+use crate::error::{Result, TeamError};
+use crate::models::{ComplianceSummary, MergedStandards, TeamStandards};
+
+/// Source file extensions scanned for violations
+const SOURCE_EXTENSIONS: &[&str] = &["rs", "py", "js", "jsx", "ts", "tsx", "go", "java", "kt"];
+
+/// Directories never scanned
+const SKIPPED_DIRS: &[&str] = &["target", "node_modules", "dist", "build", "vendor"];
+
+/// Severity of a compliance violation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "info" => Some(Severity::Info),
+            "warning" | "warn" => Some(Severity::Warning),
+            "error" => Some(Severity::Error),
+            _ => None,
+        }
+    }
+}
+
+/// Kind of identifier a naming constraint applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NamingTarget {
+    Functions,
+    Types,
+    Constants,
+    Variables,
+}
+
+impl NamingTarget {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "functions" | "function" => Some(NamingTarget::Functions),
+            "types" | "type" | "classes" | "class" => Some(NamingTarget::Types),
+            "constants" | "constant" => Some(NamingTarget::Constants),
+            "variables" | "variable" => Some(NamingTarget::Variables),
+            _ => None,
+        }
+    }
+
+    fn pattern(&self) -> &'static str {
+        match self {
+            NamingTarget::Functions => r"\b(?:fn|def|function)\s+([A-Za-z_][A-Za-z0-9_]*)",
+            NamingTarget::Types => {
+                r"\b(?:struct|enum|trait|class|interface)\s+([A-Za-z_][A-Za-z0-9_]*)"
+            }
+            NamingTarget::Constants => {
+                r"\b(?:const|static)\s+(?:mut\s+)?([A-Za-z_][A-Za-z0-9_]*)\s*:"
+            }
+            NamingTarget::Variables => r"\b(?:let|var)\s+(?:mut\s+)?([A-Za-z_][A-Za-z0-9_]*)",
+        }
+    }
+}
+
+/// Check declared by a standard
+///
+/// Constraints are declared one per line in code review rule descriptions,
+/// governance (steering) doc content, and compliance requirement descriptions:
+///
+/// ```text
+/// enforce: naming.functions = snake_case
+/// enforce: naming.types = PascalCase
+/// enforce: max_line_length = 120
+/// enforce: forbid = unwrap\(\)
+/// enforce: require_file = CODEOWNERS
+/// enforce: require_tests
+/// enforce: severity = error
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ConstraintKind {
+    /// Identifiers of a kind must use a case style
+    Naming {
+        target: NamingTarget,
+        style: CaseStyle,
+    },
+    /// No source line may be longer than this
+    MaxLineLength(usize),
+    /// Source lines must not match this pattern
+    Forbid(String),
+    /// A file must exist relative to the project root
+    RequireFile(String),
+    /// The project must have a test directory
+    RequireTests,
+}
+
+/// Constraint together with the standard that declared it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Constraint {
+    pub rule_id: String,
+    pub rule_name: String,
+    pub kind: ConstraintKind,
+    pub severity: Severity,
+}
+
+/// Single violation of a team standard
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Violation {
+    pub rule_id: String,
+    pub rule_name: String,
+    pub severity: Severity,
+    /// Path relative to the project root, if the violation is file-specific
+    pub file: Option<PathBuf>,
+    /// 1-based line number
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+/// Result of checking a project against team standards
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComplianceReport {
+    pub project_root: PathBuf,
+    pub standards_version: u32,
+    pub files_checked: usize,
+    /// Conventions the project actually follows, as detected by ricecoder-research
+    pub detected_naming: NamingConventions,
+    /// Violations ordered by severity (errors first), then location
+    pub violations: Vec<Violation>,
+    pub summary: ComplianceSummary,
+    pub generated_at: DateTime<Utc>,
+}
+
+impl ComplianceReport {
+    /// Number of violations with the given severity
+    pub fn count(&self, severity: Severity) -> usize {
+        self.violations
+            .iter()
+            .filter(|v| v.severity == severity)
+            .count()
+    }
+
+    /// Whether the project has no error-level violations
+    pub fn passed(&self) -> bool {
+        self.summary.passed
+    }
+
+    /// Violations at or above a severity
+    pub fn at_least(&self, severity: Severity) -> impl Iterator<Item = &Violation> {
+        self.violations
+            .iter()
+            .filter(move |v| v.severity >= severity)
+    }
+
+    /// Pretty JSON for CI pipelines
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+/// Evaluates merged team standards against a project on disk
+pub struct ComplianceChecker {
+    analyzer: ProjectAnalyzer,
+    detector: StandardsDetector,
+}
+
+impl ComplianceChecker {
+    /// Create a new ComplianceChecker
+    pub fn new() -> Self {
+        ComplianceChecker {
+            analyzer: ProjectAnalyzer::new(),
+            detector: StandardsDetector::new(),
+        }
+    }
+
+    /// Extract the enforceable constraints declared by a set of standards
+    ///
+    /// Compliance requirements default to `Error`, code review rules and
+    /// governance docs to `Warning`; an `enforce: severity = ...` line overrides
+    /// the default for the rule it appears in. Disabled review rules are skipped.
+    pub fn extract_constraints(standards: &TeamStandards) -> Result<Vec<Constraint>> {
+        let mut constraints = Vec::new();
+
+        for rule in standards.code_review_rules.iter().filter(|r| r.enabled) {
+            constraints.extend(Self::parse_directives(
+                &rule.id,
+                &rule.name,
+                &rule.description,
+                Severity::Warning,
+            )?);
+        }
+        for doc in &standards.governance_docs {
+            constraints.extend(Self::parse_directives(
+                &doc.id,
+                &doc.name,
+                &doc.content,
+                Severity::Warning,
+            )?);
+        }
+        for requirement in &standards.compliance_requirements {
+            constraints.extend(Self::parse_directives(
+                &requirement.id,
+                &requirement.name,
+                &requirement.description,
+                Severity::Error,
+            )?);
+        }
+
+        Ok(constraints)
+    }
+
+    /// Check a project against merged standards
+    ///
+    /// # Arguments
+    /// * `standards` - Standards merged across organization, team, and project
+    /// * `project_root` - Root directory of the project to check
+    ///
+    /// # Returns
+    /// * `Result<ComplianceReport>` - Violations with file locations and severity
+    pub fn check(
+        &self,
+        standards: &MergedStandards,
+        project_root: &Path,
+    ) -> Result<ComplianceReport> {
+        let final_standards = &standards.final_standards;
+        let constraints = Self::extract_constraints(final_standards)?;
+
+        let structure = self
+            .analyzer
+            .analyze_structure(project_root)
+            .map_err(|e| TeamError::Internal(format!("Project analysis failed: {}", e)))?;
+
+        let files = Self::collect_source_files(project_root, &structure.source_dirs);
+        let file_refs: Vec<&Path> = files.iter().map(PathBuf::as_path).collect();
+        let detected = self
+            .detector
+            .detect(&file_refs)
+            .map_err(|e| TeamError::Internal(format!("Standards detection failed: {}", e)))?;
+
+        let line_checks = Self::compile_line_checks(&constraints)?;
+        let mut violations = Vec::new();
+
+        for constraint in &constraints {
+            match &constraint.kind {
+                ConstraintKind::RequireFile(file) if !project_root.join(file).exists() => {
+                    violations.push(Violation {
+                        rule_id: constraint.rule_id.clone(),
+                        rule_name: constraint.rule_name.clone(),
+                        severity: constraint.severity,
+                        file: Some(PathBuf::from(file)),
+                        line: None,
+                        message: format!("Required file '{}' is missing", file),
+                    });
+                }
+                ConstraintKind::RequireTests if structure.test_dirs.is_empty() => {
+                    violations.push(Violation {
+                        rule_id: constraint.rule_id.clone(),
+                        rule_name: constraint.rule_name.clone(),
+                        severity: constraint.severity,
+                        file: None,
+                        line: None,
+                        message: "Project has no test directory".to_string(),
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        for path in &files {
+            let content = match std::fs::read_to_string(path) {
+                Ok(content) => content,
+                // Skip binary or unreadable files
+                Err(_) => continue,
+            };
+            let relative = path.strip_prefix(project_root).unwrap_or(path);
+            for (index, line) in content.lines().enumerate() {
+                for check in &line_checks {
+                    if let Some(message) = check.evaluate(line) {
+                        violations.push(Violation {
+                            rule_id: check.constraint.rule_id.clone(),
+                            rule_name: check.constraint.rule_name.clone(),
+                            severity: check.constraint.severity,
+                            file: Some(relative.to_path_buf()),
+                            line: Some(index + 1),
+                            message,
+                        });
+                    }
+                }
+            }
+        }
+
+        violations.sort_by(|a, b| {
+            b.severity
+                .cmp(&a.severity)
+                .then_with(|| a.file.cmp(&b.file))
+                .then_with(|| a.line.cmp(&b.line))
+        });
+
+        let generated_at = Utc::now();
+        let count =
+            |severity: Severity| violations.iter().filter(|v| v.severity == severity).count();
+        let summary = ComplianceSummary {
+            project_root: project_root.display().to_string(),
+            files_checked: files.len(),
+            errors: count(Severity::Error),
+            warnings: count(Severity::Warning),
+            infos: count(Severity::Info),
+            passed: count(Severity::Error) == 0,
+            checked_at: generated_at,
+        };
+
+        tracing::info!(
+            project_root = %project_root.display(),
+            files_checked = files.len(),
+            errors = summary.errors,
+            warnings = summary.warnings,
+            "Compliance check completed"
+        );
+
+        Ok(ComplianceReport {
+            project_root: project_root.to_path_buf(),
+            standards_version: final_standards.version,
+            files_checked: files.len(),
+            detected_naming: detected.naming_conventions,
+            violations,
+            summary,
+            generated_at,
+        })
+    }
+
+    // Helper functions
+
+    fn parse_directives(
+        rule_id: &str,
+        rule_name: &str,
+        text: &str,
+        default_severity: Severity,
+    ) -> Result<Vec<Constraint>> {
+        let invalid = |line: &str, reason: &str| {
+            TeamError::RuleValidationFailed(format!(
+                "Invalid constraint in '{}': '{}' ({})",
+                rule_id, line, reason
+            ))
+        };
+
+        let mut severity = default_severity;
+        let mut kinds = Vec::new();
+
+        for line in text.lines() {
+            let Some(directive) = line.trim().strip_prefix("enforce:") else {
+                continue;
+            };
+            let directive = directive.trim();
+            let (key, value) = match directive.split_once('=') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None => (directive, ""),
+            };
+
+            match key {
+                "severity" => {
+                    severity =
+                        Severity::parse(value).ok_or_else(|| invalid(line, "unknown severity"))?;
+                }
+                "max_line_length" => {
+                    let max = value
+                        .parse()
+                        .map_err(|_| invalid(line, "expected a number"))?;
+                    kinds.push(ConstraintKind::MaxLineLength(max));
+                }
+                "forbid" => {
+                    Regex::new(value).map_err(|e| invalid(line, &e.to_string()))?;
+                    kinds.push(ConstraintKind::Forbid(value.to_string()));
+                }
+                "require_file" if !value.is_empty() => {
+                    kinds.push(ConstraintKind::RequireFile(value.to_string()));
+                }
+                "require_tests" => kinds.push(ConstraintKind::RequireTests),
+                _ => {
+                    let target = key
+                        .strip_prefix("naming.")
+                        .and_then(NamingTarget::parse)
+                        .ok_or_else(|| invalid(line, "unknown constraint"))?;
+                    let style = parse_case_style(value)
+                        .ok_or_else(|| invalid(line, "unknown case style"))?;
+                    kinds.push(ConstraintKind::Naming { target, style });
+                }
+            }
+        }
+
+        Ok(kinds
+            .into_iter()
+            .map(|kind| Constraint {
+                rule_id: rule_id.to_string(),
+                rule_name: rule_name.to_string(),
+                kind,
+                severity,
+            })
+            .collect())
+    }
+
+    fn compile_line_checks(constraints: &[Constraint]) -> Result<Vec<LineCheck<'_>>> {
+        let mut checks = Vec::new();
+        for constraint in constraints {
+            let matcher = match &constraint.kind {
+                ConstraintKind::Naming { target, style } => LineMatcher::Naming {
+                    target: *target,
+                    style: *style,
+                    regex: Regex::new(target.pattern())
+                        .map_err(|e| TeamError::Internal(e.to_string()))?,
+                },
+                ConstraintKind::MaxLineLength(max) => LineMatcher::MaxLength(*max),
+                ConstraintKind::Forbid(pattern) => LineMatcher::Forbid(
+                    Regex::new(pattern)
+                        .map_err(|e| TeamError::RuleValidationFailed(e.to_string()))?,
+                ),
+                ConstraintKind::RequireFile(_) | ConstraintKind::RequireTests => continue,
+            };
+            checks.push(LineCheck {
+                constraint,
+                matcher,
+            });
+        }
+        Ok(checks)
+    }
+
+    fn collect_source_files(root: &Path, source_dirs: &[PathBuf]) -> Vec<PathBuf> {
+        let roots: Vec<&Path> = if source_dirs.is_empty() {
+            vec![root]
+        } else {
+            source_dirs.iter().map(PathBuf::as_path).collect()
+        };
+
+        let mut files: Vec<PathBuf> = roots
+            .into_iter()
+            .flat_map(|dir| {
+                WalkDir::new(dir)
+                    .into_iter()
+                    .filter_entry(|entry| entry.depth() == 0 || !is_skipped_dir(entry))
+                    .filter_map(|entry| entry.ok())
+            })
+            .filter(|entry| entry.file_type().is_file())
+            .map(|entry| entry.into_path())
+            .filter(|path| {
+                path.extension()
+                    .and_then(|e| e.to_str())
+                    .is_some_and(|e| SOURCE_EXTENSIONS.contains(&e))
+            })
+            .collect();
+        files.sort();
+        files.dedup();
+        files
+    }
+}
+
+impl Default for ComplianceChecker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+enum LineMatcher {
+    Naming {
+        target: NamingTarget,
+        style: CaseStyle,
+        regex: Regex,
+    },
+    MaxLength(usize),
+    Forbid(Regex),
+}
+
+struct LineCheck<'a> {
+    constraint: &'a Constraint,
+    matcher: LineMatcher,
+}
+
+impl LineCheck<'_> {
+    /// Violation message for a line, if it breaks the constraint
+    fn evaluate(&self, line: &str) -> Option<String> {
+        match &self.matcher {
+            LineMatcher::MaxLength(max) => {
+                let length = line.chars().count();
+                (length > *max).then(|| format!("Line is {} characters (max {})", length, max))
+            }
+            LineMatcher::Forbid(regex) => regex
+                .find(line)
+                .map(|m| format!("Forbidden pattern '{}' found", m.as_str())),
+            LineMatcher::Naming {
+                target,
+                style,
+                regex,
+            } => {
+                if is_comment(line) {
+                    return None;
+                }
+                regex
+                    .captures_iter(line)
+                    .filter_map(|c| c.get(1))
+                    .map(|m| m.as_str())
+                    .find(|name| !matches_case(name, *style))
+                    .map(|name| {
+                        format!(
+                            "{:?} name '{}' should be {}",
+                            target,
+                            name,
+                            case_style_name(*style)
+                        )
+                    })
+            }
+        }
+    }
+}
+
+fn is_skipped_dir(entry: &DirEntry) -> bool {
+    entry.file_type().is_dir()
+        && entry
+            .file_name()
+            .to_str()
+            .is_some_and(|name| name.starts_with('.') || SKIPPED_DIRS.contains(&name))
+}
+
+fn is_comment(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.starts_with("//") || trimmed.starts_with('#') || trimmed.starts_with('*')
+}
+
+fn parse_case_style(value: &str) -> Option<CaseStyle> {
+    let normalized: String = value
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_ascii_lowercase();
+    match normalized.as_str() {
+        "snakecase" => Some(CaseStyle::SnakeCase),
+        "camelcase" => Some(CaseStyle::CamelCase),
+        "pascalcase" => Some(CaseStyle::PascalCase),
+        "kebabcase" => Some(CaseStyle::KebabCase),
+        "uppercase" | "screamingsnakecase" => Some(CaseStyle::UpperCase),
+        "mixed" => Some(CaseStyle::Mixed),
+        _ => None,
+    }
+}
+
+fn case_style_name(style: CaseStyle) -> &'static str {
+    match style {
+        CaseStyle::SnakeCase => "snake_case",
+        CaseStyle::CamelCase => "camelCase",
+        CaseStyle::PascalCase => "PascalCase",
+        CaseStyle::KebabCase => "kebab-case",
+        CaseStyle::UpperCase => "UPPER_CASE",
+        CaseStyle::Mixed => "mixed case",
+    }
+}
+
+fn matches_case(name: &str, style: CaseStyle) -> bool {
+    let name = name.trim_start_matches('_');
+    let Some(first) = name.chars().next() else {
+        return true;
+    };
+    match style {
+        CaseStyle::SnakeCase => name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_'),
+        CaseStyle::CamelCase => {
+            first.is_ascii_lowercase() && name.chars().all(|c| c.is_ascii_alphanumeric())
+        }
+        CaseStyle::PascalCase => {
+            first.is_ascii_uppercase() && name.chars().all(|c| c.is_ascii_alphanumeric())
+        }
+        CaseStyle::KebabCase => name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-'),
+        CaseStyle::UpperCase => name
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_'),
+        CaseStyle::Mixed => true,
+    }
+}
@@ -46,6 +46,21 @@ pub enum TeamError {
     #[error("Invalid scope: {0}")]
     InvalidScope(String),
 
+    #[error("Proposal not found: {0}")]
+    ProposalNotFound(String),
+
+    #[error("Invalid proposal state: {0}")]
+    InvalidProposalState(String),
+
+    #[error("Template not found: {0}")]
+    TemplateNotFound(String),
+
+    #[error("Invalid template: {0}")]
+    InvalidTemplate(String),
+
+    #[error("Organization baseline not found: {0}")]
+    BaselineNotFound(String),
+
     #[error("Concurrent modification detected")]
     ConcurrentModification,
 
@@ -0,0 +1,479 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use chrono::{DateTime, Utc};
+use ricecoder_generation::{PlaceholderResolver, TemplateEngine};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+/// Gallery of parameterized team templates
+use crate::{
+    access::AccessControlManager,
+    error::{Result, TeamError},
+    models::{StandardsOverride, TeamRole, Template, TemplateKind, TemplateParameter},
+};
+
+/// Template published to the gallery
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GalleryEntry {
+    pub template: Template,
+    pub team_id: String,
+    pub published_by: String,
+    pub published_at: DateTime<Utc>,
+    /// Incremented each time the template is republished
+    pub revision: u32,
+    pub instantiations: Vec<TemplateInstantiation>,
+}
+
+/// Record of a template being instantiated into a project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateInstantiation {
+    pub id: String,
+    pub template_id: String,
+    /// Template revision that was instantiated
+    pub revision: u32,
+    pub project_id: String,
+    pub member_id: String,
+    pub target: PathBuf,
+    /// Resolved parameter values, defaults included
+    pub values: HashMap<String, String>,
+    /// Parameters set to something other than the template default
+    pub overrides: StandardsOverride,
+    pub files_written: Vec<PathBuf>,
+    pub instantiated_at: DateTime<Utc>,
+}
+
+/// Adoption of a gallery template
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateAdoption {
+    pub template_id: String,
+    pub instantiations: usize,
+    pub adopting_projects: usize,
+    pub adopting_members: usize,
+    pub last_instantiated_at: Option<DateTime<Utc>>,
+}
+
+/// Filter for browsing the gallery; unset fields match everything
+#[derive(Debug, Clone, Default)]
+pub struct GalleryQuery {
+    pub tag: Option<String>,
+    pub kind: Option<TemplateKind>,
+    pub team_id: Option<String>,
+    /// Case-insensitive match against name and description
+    pub text: Option<String>,
+}
+
+/// Gallery listing of a template with its adoption
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GalleryListing {
+    pub template_id: String,
+    pub name: String,
+    pub description: String,
+    pub kind: TemplateKind,
+    pub tags: Vec<String>,
+    pub parameters: Vec<TemplateParameter>,
+    pub team_id: String,
+    pub revision: u32,
+    pub adoption: TemplateAdoption,
+}
+
+/// Publishes, lists, and instantiates team templates
+///
+/// Rendering is delegated to the ricecoder-generation template engine.
+/// Publishing and instantiation are recorded in the team audit log, and every
+/// instantiation keeps the parameters that deviated from the template defaults
+/// as a [`StandardsOverride`].
+pub struct TemplateGallery {
+    access_control: Arc<AccessControlManager>,
+    /// Published templates (template_id -> entry)
+    entries: Arc<RwLock<HashMap<String, GalleryEntry>>>,
+}
+
+impl TemplateGallery {
+    /// Create a new TemplateGallery
+    pub fn new(access_control: Arc<AccessControlManager>) -> Self {
+        TemplateGallery {
+            access_control,
+            entries: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Publish a template, or a new revision of one the team already published
+    ///
+    /// Only admins and members may publish. Every placeholder used by the
+    /// template must be a declared parameter.
+    pub async fn publish(
+        &self,
+        team_id: &str,
+        member_id: &str,
+        template: Template,
+    ) -> Result<GalleryEntry> {
+        match self
+            .access_control
+            .get_member_role(team_id, member_id)
+            .await?
+        {
+            Some(TeamRole::Admin | TeamRole::Member) => {}
+            Some(role) => {
+                return Err(TeamError::PermissionDenied(format!(
+                    "Role {} cannot publish templates",
+                    role.as_str()
+                )))
+            }
+            None => return Err(TeamError::MemberNotFound(member_id.to_string())),
+        }
+
+        Self::validate_template(&template)?;
+
+        let mut entries = self.entries.write().await;
+        let (revision, instantiations) = match entries.get_mut(&template.id) {
+            Some(existing) if existing.team_id != team_id => {
+                return Err(TeamError::InvalidTemplate(format!(
+                    "Template {} is owned by another team",
+                    template.id
+                )));
+            }
+            Some(existing) => (
+                existing.revision + 1,
+                std::mem::take(&mut existing.instantiations),
+            ),
+            None => (1, Vec::new()),
+        };
+        let entry = GalleryEntry {
+            template,
+            team_id: team_id.to_string(),
+            published_by: member_id.to_string(),
+            published_at: Utc::now(),
+            revision,
+            instantiations,
+        };
+        entries.insert(entry.template.id.clone(), entry.clone());
+        drop(entries);
+
+        self.access_control.record_audit(
+            team_id,
+            "publish_template",
+            member_id,
+            &format!("{} (revision {})", entry.template.id, entry.revision),
+        );
+
+        tracing::info!(
+            team_id = %team_id,
+            template_id = %entry.template.id,
+            revision = entry.revision,
+            "Template published to gallery"
+        );
+
+        Ok(entry)
+    }
+
+    /// Get a published template
+    pub async fn get(&self, template_id: &str) -> Result<GalleryEntry> {
+        self.entries
+            .read()
+            .await
+            .get(template_id)
+            .cloned()
+            .ok_or_else(|| TeamError::TemplateNotFound(template_id.to_string()))
+    }
+
+    /// List templates matching a query, most adopted first
+    pub async fn browse(&self, query: &GalleryQuery) -> Vec<GalleryListing> {
+        let text = query.text.as_deref().map(str::to_lowercase);
+        let entries = self.entries.read().await;
+
+        let mut listings: Vec<GalleryListing> = entries
+            .values()
+            .filter(|entry| {
+                let template = &entry.template;
+                query
+                    .tag
+                    .as_ref()
+                    .map_or(true, |tag| template.tags.contains(tag))
+                    && query.kind.map_or(true, |kind| template.kind == kind)
+                    && query
+                        .team_id
+                        .as_ref()
+                        .map_or(true, |team_id| &entry.team_id == team_id)
+                    && text.as_ref().map_or(true, |text| {
+                        template.name.to_lowercase().contains(text)
+                            || template.description.to_lowercase().contains(text)
+                    })
+            })
+            .map(|entry| GalleryListing {
+                template_id: entry.template.id.clone(),
+                name: entry.template.name.clone(),
+                description: entry.template.description.clone(),
+                kind: entry.template.kind,
+                tags: entry.template.tags.clone(),
+                parameters: entry.template.parameters.clone(),
+                team_id: entry.team_id.clone(),
+                revision: entry.revision,
+                adoption: Self::adoption_of(entry),
+            })
+            .collect();
+
+        listings.sort_by(|a, b| {
+            b.adoption
+                .instantiations
+                .cmp(&a.adoption.instantiations)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        listings
+    }
+
+    /// Tags in use with the number of templates carrying each
+    pub async fn tags(&self) -> BTreeMap<String, usize> {
+        let mut tags = BTreeMap::new();
+        for entry in self.entries.read().await.values() {
+            for tag in &entry.template.tags {
+                *tags.entry(tag.clone()).or_insert(0) += 1;
+            }
+        }
+        tags
+    }
+
+    /// Get adoption metrics for a template
+    pub async fn get_adoption(&self, template_id: &str) -> Result<TemplateAdoption> {
+        self.get(template_id)
+            .await
+            .map(|entry| Self::adoption_of(&entry))
+    }
+
+    /// Instantiate a template locally
+    ///
+    /// For project templates `target` is the directory the files are written
+    /// under; for file templates it is the output file. Existing files are
+    /// never overwritten.
+    ///
+    /// # Arguments
+    /// * `template_id` - The published template to instantiate
+    /// * `project_id` - The project the template is instantiated into
+    /// * `member_id` - The member instantiating the template
+    /// * `values` - Parameter values; missing ones fall back to their defaults
+    /// * `target` - Output directory or file
+    pub async fn instantiate(
+        &self,
+        template_id: &str,
+        project_id: &str,
+        member_id: &str,
+        values: HashMap<String, String>,
+        target: &Path,
+    ) -> Result<TemplateInstantiation> {
+        let entry = self.get(template_id).await?;
+        let template = &entry.template;
+
+        let (resolved, overridden) = Self::resolve_values(template, values)?;
+
+        let mut engine = TemplateEngine::new();
+        engine.add_values(resolved.clone());
+        let render = |text: &str| {
+            engine.render_simple(text).map_err(|e| {
+                TeamError::InvalidTemplate(format!("Failed to render {}: {}", template_id, e))
+            })
+        };
+
+        let outputs: Vec<(PathBuf, String)> = match template.kind {
+            TemplateKind::File => vec![(target.to_path_buf(), render(&template.content)?)],
+            TemplateKind::Project => template
+                .files
+                .iter()
+                .map(|file| Ok((target.join(render(&file.path)?), render(&file.content)?)))
+                .collect::<Result<_>>()?,
+        };
+
+        if let Some((existing, _)) = outputs.iter().find(|(path, _)| path.exists()) {
+            return Err(TeamError::InvalidTemplate(format!(
+                "Refusing to overwrite existing file {}",
+                existing.display()
+            )));
+        }
+
+        for (path, content) in &outputs {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(path, content)?;
+        }
+
+        let now = Utc::now();
+        let instantiation = TemplateInstantiation {
+            id: Uuid::new_v4().to_string(),
+            template_id: template_id.to_string(),
+            revision: entry.revision,
+            project_id: project_id.to_string(),
+            member_id: member_id.to_string(),
+            target: target.to_path_buf(),
+            values: resolved,
+            overrides: StandardsOverride {
+                project_id: project_id.to_string(),
+                overridden_standards: overridden
+                    .iter()
+                    .map(|name| format!("template:{}:{}", template_id, name))
+                    .collect(),
+                created_at: now,
+            },
+            files_written: outputs.into_iter().map(|(path, _)| path).collect(),
+            instantiated_at: now,
+        };
+
+        if let Some(entry) = self.entries.write().await.get_mut(template_id) {
+            entry.instantiations.push(instantiation.clone());
+        }
+
+        self.access_control.record_audit(
+            &entry.team_id,
+            "instantiate_template",
+            member_id,
+            &format!(
+                "{} -> {} (overrides: {})",
+                template_id,
+                project_id,
+                overridden.join(", ")
+            ),
+        );
+
+        tracing::info!(
+            template_id = %template_id,
+            project_id = %project_id,
+            files = instantiation.files_written.len(),
+            overrides = overridden.len(),
+            "Template instantiated"
+        );
+
+        Ok(instantiation)
+    }
+
+    /// Instantiations recorded for a project, oldest first
+    pub async fn get_project_instantiations(&self, project_id: &str) -> Vec<TemplateInstantiation> {
+        let mut instantiations: Vec<TemplateInstantiation> = self
+            .entries
+            .read()
+            .await
+            .values()
+            .flat_map(|entry| entry.instantiations.iter())
+            .filter(|i| i.project_id == project_id)
+            .cloned()
+            .collect();
+        instantiations.sort_by_key(|i| i.instantiated_at);
+        instantiations
+    }
+
+    // Helper functions
+
+    fn adoption_of(entry: &GalleryEntry) -> TemplateAdoption {
+        let projects: BTreeSet<&str> = entry
+            .instantiations
+            .iter()
+            .map(|i| i.project_id.as_str())
+            .collect();
+        let members: BTreeSet<&str> = entry
+            .instantiations
+            .iter()
+            .map(|i| i.member_id.as_str())
+            .collect();
+
+        TemplateAdoption {
+            template_id: entry.template.id.clone(),
+            instantiations: entry.instantiations.len(),
+            adopting_projects: projects.len(),
+            adopting_members: members.len(),
+            last_instantiated_at: entry.instantiations.iter().map(|i| i.instantiated_at).max(),
+        }
+    }
+
+    /// Check parameters are well-formed and every placeholder is declared
+    fn validate_template(template: &Template) -> Result<()> {
+        let invalid =
+            |message: String| TeamError::InvalidTemplate(format!("{}: {}", template.id, message));
+
+        let mut declared = BTreeSet::new();
+        for parameter in &template.parameters {
+            if parameter.name.is_empty() || parameter.name != parameter.name.to_lowercase() {
+                return Err(invalid(format!(
+                    "parameter '{}' must be a non-empty lowercase name",
+                    parameter.name
+                )));
+            }
+            if !declared.insert(parameter.name.as_str()) {
+                return Err(invalid(format!(
+                    "parameter '{}' is declared twice",
+                    parameter.name
+                )));
+            }
+        }
+
+        let bodies: Vec<&str> = match template.kind {
+            TemplateKind::File => vec![template.content.as_str()],
+            TemplateKind::Project if template.files.is_empty() => {
+                return Err(invalid("project template has no files".to_string()));
+            }
+            TemplateKind::Project => template
+                .files
+                .iter()
+                .flat_map(|file| [file.path.as_str(), file.content.as_str()])
+                .collect(),
+        };
+
+        // The engine looks values up by lowercase name (`{{Name}}` reads `name`)
+        let resolver = PlaceholderResolver::new();
+        for body in bodies {
+            if let Some(name) = resolver
+                .extract_placeholder_names(body)
+                .into_iter()
+                .find(|name| !declared.contains(name.to_lowercase().as_str()))
+            {
+                return Err(invalid(format!("placeholder '{}' is not declared", name)));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Merge provided values with defaults
+    ///
+    /// Returns the resolved values and the names of parameters that differ
+    /// from their defaults.
+    fn resolve_values(
+        template: &Template,
+        mut values: HashMap<String, String>,
+    ) -> Result<(HashMap<String, String>, Vec<String>)> {
+        if let Some(unknown) = values
+            .keys()
+            .find(|name| !template.parameters.iter().any(|p| &p.name == *name))
+        {
+            return Err(TeamError::InvalidTemplate(format!(
+                "{}: unknown parameter '{}'",
+                template.id, unknown
+            )));
+        }
+
+        let mut resolved = HashMap::new();
+        let mut overridden = Vec::new();
+        for parameter in &template.parameters {
+            let value = match (values.remove(&parameter.name), &parameter.default) {
+                (Some(value), default) => {
+                    if default.as_ref() != Some(&value) {
+                        overridden.push(parameter.name.clone());
+                    }
+                    value
+                }
+                (None, Some(default)) => default.clone(),
+                (None, None) if parameter.required => {
+                    return Err(TeamError::InvalidTemplate(format!(
+                        "{}: missing required parameter '{}'",
+                        template.id, parameter.name
+                    )));
+                }
+                (None, None) => String::new(),
+            };
+            resolved.insert(parameter.name.clone(), value);
+        }
+
+        Ok((resolved, overridden))
+    }
+}
@@ -0,0 +1,596 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+/// Organization baselines and policy-aware standards inheritance
+use crate::{
+    config::TeamConfigManager,
+    error::{Result, TeamError},
+    models::{
+        CodeReviewRule, ComplianceRequirement, GovernanceDoc, MergedStandards, RuleScope,
+        StandardsOverride, TeamStandards, Template,
+    },
+};
+
+/// Whether teams and projects may override an organization rule
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OverridePolicy {
+    /// Overrides are ignored and reported as blocked
+    Locked,
+    #[default]
+    Overridable,
+}
+
+/// Kind of item within a set of standards
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum StandardsItemKind {
+    CodeReviewRule,
+    Template,
+    GovernanceDoc,
+    ComplianceRequirement,
+}
+
+/// Reference to an item within a set of standards
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct StandardsItemRef {
+    pub kind: StandardsItemKind,
+    pub id: String,
+}
+
+/// Organization-level standards that teams inherit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrgBaseline {
+    pub org_id: String,
+    pub standards: TeamStandards,
+    /// Policy per item id; items not listed use `default_policy`
+    #[serde(default)]
+    pub policies: BTreeMap<String, OverridePolicy>,
+    #[serde(default)]
+    pub default_policy: OverridePolicy,
+    /// Fraction of baseline items a team may override before drift is reported
+    pub max_drift: f64,
+}
+
+impl OrgBaseline {
+    /// Create a baseline where every item is overridable and up to 30% may drift
+    pub fn new(org_id: impl Into<String>, standards: TeamStandards) -> Self {
+        OrgBaseline {
+            org_id: org_id.into(),
+            standards,
+            policies: BTreeMap::new(),
+            default_policy: OverridePolicy::Overridable,
+            max_drift: 0.3,
+        }
+    }
+
+    /// Set the policy for an item
+    pub fn with_policy(mut self, item_id: impl Into<String>, policy: OverridePolicy) -> Self {
+        self.policies.insert(item_id.into(), policy);
+        self
+    }
+
+    /// Set the policy for items without an explicit one
+    pub fn with_default_policy(mut self, policy: OverridePolicy) -> Self {
+        self.default_policy = policy;
+        self
+    }
+
+    /// Set the drift threshold (0.0 - 1.0)
+    pub fn with_max_drift(mut self, max_drift: f64) -> Self {
+        self.max_drift = max_drift;
+        self
+    }
+
+    /// Policy that applies to an item
+    pub fn policy_for(&self, item_id: &str) -> OverridePolicy {
+        self.policies
+            .get(item_id)
+            .copied()
+            .unwrap_or(self.default_policy)
+    }
+}
+
+/// Where an effective item came from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleProvenance {
+    pub item: StandardsItemRef,
+    /// Scope that supplied the effective definition
+    pub source: RuleScope,
+    /// Organization, team, or project id of the source
+    pub source_id: String,
+    /// Organization policy, for items defined in the baseline
+    pub policy: Option<OverridePolicy>,
+    /// Scopes whose definitions were replaced, outermost first
+    pub overrode: Vec<RuleScope>,
+}
+
+/// Override that was ignored because the organization locked the item
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockedOverride {
+    pub item: StandardsItemRef,
+    pub scope: RuleScope,
+    pub source_id: String,
+}
+
+/// Standards after applying organization policies, with provenance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EffectiveStandards {
+    pub org_id: Option<String>,
+    pub team_id: String,
+    pub project_id: Option<String>,
+    /// Per-level standards; `final_standards` is the policy-aware result
+    pub standards: MergedStandards,
+    pub provenance: Vec<RuleProvenance>,
+    pub blocked_overrides: Vec<BlockedOverride>,
+}
+
+impl EffectiveStandards {
+    /// Explain where the effective item with this id came from
+    pub fn explain(&self, item_id: &str) -> Option<&RuleProvenance> {
+        self.provenance.iter().find(|p| p.item.id == item_id)
+    }
+
+    /// Items whose effective definition comes from a scope
+    pub fn from_scope(&self, scope: RuleScope) -> impl Iterator<Item = &RuleProvenance> {
+        self.provenance.iter().filter(move |p| p.source == scope)
+    }
+}
+
+/// How far a team's standards diverge from its organization baseline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DriftReport {
+    pub org_id: String,
+    pub team_id: String,
+    pub baseline_items: usize,
+    /// Baseline items the team redefines
+    pub overridden: Vec<StandardsItemRef>,
+    /// Baseline review rules the team disables
+    pub disabled: Vec<StandardsItemRef>,
+    /// Team items that are not in the baseline
+    pub added: Vec<StandardsItemRef>,
+    /// Overridden or disabled items the organization has locked
+    pub locked_conflicts: Vec<StandardsItemRef>,
+    /// Fraction of baseline items overridden or disabled
+    pub drift: f64,
+    pub max_drift: f64,
+    pub exceeds_threshold: bool,
+}
+
+/// Resolves effective standards across organization, team, and project
+///
+/// Each team may be attached to one organization baseline. Team and project
+/// standards override baseline items with the same id unless the organization
+/// locked them.
+pub struct StandardsResolver {
+    config_manager: Arc<TeamConfigManager>,
+    /// Organization baselines (org_id -> baseline)
+    baselines: Arc<RwLock<HashMap<String, OrgBaseline>>>,
+    /// Organization of each team (team_id -> org_id)
+    team_orgs: Arc<RwLock<HashMap<String, String>>>,
+}
+
+impl StandardsResolver {
+    /// Create a new StandardsResolver
+    pub fn new(config_manager: Arc<TeamConfigManager>) -> Self {
+        StandardsResolver {
+            config_manager,
+            baselines: Arc::new(RwLock::new(HashMap::new())),
+            team_orgs: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Set or replace an organization baseline
+    pub async fn set_baseline(&self, baseline: OrgBaseline) -> Result<()> {
+        if !(0.0..=1.0).contains(&baseline.max_drift) {
+            return Err(TeamError::ConfigError(format!(
+                "Drift threshold must be between 0 and 1, got {}",
+                baseline.max_drift
+            )));
+        }
+        let items = items_of(&baseline.standards);
+        if let Some(unknown) = baseline
+            .policies
+            .keys()
+            .find(|id| !items.keys().any(|item| &item.id == *id))
+        {
+            return Err(TeamError::ConfigError(format!(
+                "Policy for unknown baseline item: {}",
+                unknown
+            )));
+        }
+
+        tracing::info!(
+            org_id = %baseline.org_id,
+            items = items.len(),
+            locked = baseline
+                .policies
+                .values()
+                .filter(|p| **p == OverridePolicy::Locked)
+                .count(),
+            "Organization baseline set"
+        );
+
+        self.baselines
+            .write()
+            .await
+            .insert(baseline.org_id.clone(), baseline);
+        Ok(())
+    }
+
+    /// Get an organization baseline
+    pub async fn get_baseline(&self, org_id: &str) -> Result<OrgBaseline> {
+        self.baselines
+            .read()
+            .await
+            .get(org_id)
+            .cloned()
+            .ok_or_else(|| TeamError::BaselineNotFound(org_id.to_string()))
+    }
+
+    /// Attach a team to an organization baseline
+    pub async fn attach_team(&self, team_id: &str, org_id: &str) -> Result<()> {
+        self.get_baseline(org_id).await?;
+        self.team_orgs
+            .write()
+            .await
+            .insert(team_id.to_string(), org_id.to_string());
+
+        tracing::info!(team_id = %team_id, org_id = %org_id, "Team attached to organization");
+        Ok(())
+    }
+
+    /// Organization a team inherits from, if any
+    pub async fn organization_of(&self, team_id: &str) -> Option<String> {
+        self.team_orgs.read().await.get(team_id).cloned()
+    }
+
+    /// Resolve the effective standards for a team and optionally a project
+    ///
+    /// Missing team or project standards are treated as empty.
+    pub async fn resolve(
+        &self,
+        team_id: &str,
+        project_id: Option<&str>,
+    ) -> Result<EffectiveStandards> {
+        let baseline = match self.organization_of(team_id).await {
+            Some(org_id) => Some(self.get_baseline(&org_id).await?),
+            None => None,
+        };
+        let team_standards = self.config_manager.get_standards(team_id).await.ok();
+        let project_standards = match project_id {
+            Some(project_id) => self.config_manager.get_standards(project_id).await.ok(),
+            None => None,
+        };
+
+        let mut resolution = Resolution::new(team_id);
+        if let Some(baseline) = &baseline {
+            resolution.apply_baseline(baseline);
+        }
+        if let Some(team) = &team_standards {
+            resolution.apply_layer(team, RuleScope::Team, team_id, baseline.as_ref());
+        }
+        if let (Some(project), Some(project_id)) = (&project_standards, project_id) {
+            resolution.apply_layer(project, RuleScope::Project, project_id, baseline.as_ref());
+        }
+
+        for blocked in &resolution.blocked {
+            tracing::warn!(
+                item = %blocked.item.id,
+                scope = %blocked.scope.as_str(),
+                source_id = %blocked.source_id,
+                "Override of locked organization rule ignored"
+            );
+        }
+
+        let Resolution {
+            standards,
+            provenance,
+            blocked,
+        } = resolution;
+
+        Ok(EffectiveStandards {
+            org_id: baseline.as_ref().map(|b| b.org_id.clone()),
+            team_id: team_id.to_string(),
+            project_id: project_id.map(str::to_string),
+            standards: MergedStandards {
+                organization_standards: baseline.map(|b| b.standards),
+                team_standards,
+                project_standards,
+                final_standards: standards,
+            },
+            provenance,
+            blocked_overrides: blocked,
+        })
+    }
+
+    /// Compare a team's standards with its organization baseline
+    pub async fn drift_report(&self, team_id: &str) -> Result<DriftReport> {
+        let org_id = self.organization_of(team_id).await.ok_or_else(|| {
+            TeamError::BaselineNotFound(format!("no organization for team {}", team_id))
+        })?;
+        let baseline = self.get_baseline(&org_id).await?;
+        let team = self.config_manager.get_standards(team_id).await.ok();
+
+        let baseline_items = items_of(&baseline.standards);
+        let team_items = team.as_ref().map(items_of).unwrap_or_default();
+
+        let mut overridden = Vec::new();
+        let mut disabled = Vec::new();
+        let mut added = Vec::new();
+        for (item, value) in &team_items {
+            match baseline_items.get(item) {
+                Some(base) if base == value => {}
+                Some(base) if is_disabling(base, value) => disabled.push(item.clone()),
+                Some(_) => overridden.push(item.clone()),
+                None => added.push(item.clone()),
+            }
+        }
+
+        let locked_conflicts: Vec<StandardsItemRef> = overridden
+            .iter()
+            .chain(&disabled)
+            .filter(|item| baseline.policy_for(&item.id) == OverridePolicy::Locked)
+            .cloned()
+            .collect();
+
+        let drift = if baseline_items.is_empty() {
+            0.0
+        } else {
+            (overridden.len() + disabled.len()) as f64 / baseline_items.len() as f64
+        };
+
+        let report = DriftReport {
+            org_id,
+            team_id: team_id.to_string(),
+            baseline_items: baseline_items.len(),
+            overridden,
+            disabled,
+            added,
+            locked_conflicts,
+            drift,
+            max_drift: baseline.max_drift,
+            exceeds_threshold: drift > baseline.max_drift,
+        };
+
+        if report.exceeds_threshold {
+            tracing::warn!(
+                team_id = %team_id,
+                org_id = %report.org_id,
+                drift = report.drift,
+                max_drift = report.max_drift,
+                "Team standards drifted from organization baseline"
+            );
+        }
+
+        Ok(report)
+    }
+
+    /// Reject overrides that target locked organization rules
+    pub async fn check_override(&self, team_id: &str, overrides: &StandardsOverride) -> Result<()> {
+        let Some(org_id) = self.organization_of(team_id).await else {
+            return Ok(());
+        };
+        let baseline = self.get_baseline(&org_id).await?;
+        let baseline_items = items_of(&baseline.standards);
+
+        if let Some(locked) = overrides.overridden_standards.iter().find(|id| {
+            baseline_items.keys().any(|item| &item.id == *id)
+                && baseline.policy_for(id) == OverridePolicy::Locked
+        }) {
+            return Err(TeamError::PermissionDenied(format!(
+                "Rule {} is locked by organization {}",
+                locked, org_id
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Accumulates the effective standards layer by layer
+struct Resolution {
+    standards: TeamStandards,
+    provenance: Vec<RuleProvenance>,
+    blocked: Vec<BlockedOverride>,
+}
+
+impl Resolution {
+    fn new(team_id: &str) -> Self {
+        let now = Utc::now();
+        Resolution {
+            standards: TeamStandards {
+                id: "effective".to_string(),
+                team_id: team_id.to_string(),
+                code_review_rules: Vec::new(),
+                templates: Vec::new(),
+                governance_docs: Vec::new(),
+                compliance_requirements: Vec::new(),
+                version: 1,
+                created_at: now,
+                updated_at: now,
+            },
+            provenance: Vec::new(),
+            blocked: Vec::new(),
+        }
+    }
+
+    fn apply_baseline(&mut self, baseline: &OrgBaseline) {
+        self.apply_layer(
+            &baseline.standards,
+            RuleScope::Organization,
+            &baseline.org_id,
+            Some(baseline),
+        );
+    }
+
+    fn apply_layer(
+        &mut self,
+        layer: &TeamStandards,
+        scope: RuleScope,
+        source_id: &str,
+        baseline: Option<&OrgBaseline>,
+    ) {
+        let mut rules = std::mem::take(&mut self.standards.code_review_rules);
+        self.merge(
+            &mut rules,
+            &layer.code_review_rules,
+            scope,
+            source_id,
+            baseline,
+        );
+        self.standards.code_review_rules = rules;
+
+        let mut templates = std::mem::take(&mut self.standards.templates);
+        self.merge(&mut templates, &layer.templates, scope, source_id, baseline);
+        self.standards.templates = templates;
+
+        let mut docs = std::mem::take(&mut self.standards.governance_docs);
+        self.merge(
+            &mut docs,
+            &layer.governance_docs,
+            scope,
+            source_id,
+            baseline,
+        );
+        self.standards.governance_docs = docs;
+
+        let mut requirements = std::mem::take(&mut self.standards.compliance_requirements);
+        self.merge(
+            &mut requirements,
+            &layer.compliance_requirements,
+            scope,
+            source_id,
+            baseline,
+        );
+        self.standards.compliance_requirements = requirements;
+
+        self.standards.version = self.standards.version.max(layer.version);
+        self.standards.updated_at = self.standards.updated_at.max(layer.updated_at);
+    }
+
+    fn merge<T: StandardsItem>(
+        &mut self,
+        effective: &mut Vec<T>,
+        layer: &[T],
+        scope: RuleScope,
+        source_id: &str,
+        baseline: Option<&OrgBaseline>,
+    ) {
+        for item in layer {
+            let item_ref = StandardsItemRef {
+                kind: T::KIND,
+                id: item.item_id().to_string(),
+            };
+            let position = effective.iter().position(|e| e.item_id() == item.item_id());
+            let provenance = self.provenance.iter_mut().find(|p| p.item == item_ref);
+
+            match (position, provenance) {
+                (Some(index), Some(provenance)) => {
+                    if effective[index] == *item {
+                        continue;
+                    }
+                    if provenance.policy == Some(OverridePolicy::Locked) {
+                        self.blocked.push(BlockedOverride {
+                            item: item_ref,
+                            scope,
+                            source_id: source_id.to_string(),
+                        });
+                        continue;
+                    }
+                    effective[index] = item.clone();
+                    provenance.overrode.push(provenance.source);
+                    provenance.source = scope;
+                    provenance.source_id = source_id.to_string();
+                }
+                _ => {
+                    let policy = match (scope, baseline) {
+                        (RuleScope::Organization, Some(baseline)) => {
+                            Some(baseline.policy_for(item.item_id()))
+                        }
+                        _ => None,
+                    };
+                    effective.push(item.clone());
+                    self.provenance.push(RuleProvenance {
+                        item: item_ref,
+                        source: scope,
+                        source_id: source_id.to_string(),
+                        policy,
+                        overrode: Vec::new(),
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Item of a standards set that can be inherited and overridden by id
+trait StandardsItem: Clone + PartialEq + Serialize {
+    const KIND: StandardsItemKind;
+
+    fn item_id(&self) -> &str;
+}
+
+impl StandardsItem for CodeReviewRule {
+    const KIND: StandardsItemKind = StandardsItemKind::CodeReviewRule;
+
+    fn item_id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl StandardsItem for Template {
+    const KIND: StandardsItemKind = StandardsItemKind::Template;
+
+    fn item_id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl StandardsItem for GovernanceDoc {
+    const KIND: StandardsItemKind = StandardsItemKind::GovernanceDoc;
+
+    fn item_id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl StandardsItem for ComplianceRequirement {
+    const KIND: StandardsItemKind = StandardsItemKind::ComplianceRequirement;
+
+    fn item_id(&self) -> &str {
+        &self.id
+    }
+}
+
+/// All items of a standards set, as comparable JSON values
+fn items_of(standards: &TeamStandards) -> BTreeMap<StandardsItemRef, serde_json::Value> {
+    fn collect<T: StandardsItem>(
+        items: &[T],
+        out: &mut BTreeMap<StandardsItemRef, serde_json::Value>,
+    ) {
+        for item in items {
+            let item_ref = StandardsItemRef {
+                kind: T::KIND,
+                id: item.item_id().to_string(),
+            };
+            out.insert(item_ref, serde_json::to_value(item).unwrap_or_default());
+        }
+    }
+
+    let mut items = BTreeMap::new();
+    collect(&standards.code_review_rules, &mut items);
+    collect(&standards.templates, &mut items);
+    collect(&standards.governance_docs, &mut items);
+    collect(&standards.compliance_requirements, &mut items);
+    items
+}
+
+/// Whether a team's definition only turns off an enabled baseline review rule
+fn is_disabling(base: &serde_json::Value, team: &serde_json::Value) -> bool {
+    base.get("enabled") == Some(&serde_json::Value::Bool(true))
+        && team.get("enabled") == Some(&serde_json::Value::Bool(false))
+}
@@ -9,11 +9,21 @@
 /// - ricecoder-storage: Configuration management and path resolution
 /// - ricecoder-learning: Rule promotion and analytics
 /// - ricecoder-permissions: Access control and audit logging
+///
+/// Changes to standards and shared rules go through a role-based review workflow
+/// (see [`ApprovalManager`]). Projects can be checked against merged standards with
+/// [`ComplianceChecker`], which reports violations with file locations and severity.
+/// Parameterized templates are shared through the [`TemplateGallery`], and
+/// [`StandardsResolver`] applies organization baselines with locked or overridable rules.
 pub mod access;
 pub mod analytics;
+pub mod approval;
+pub mod compliance_checker;
 pub mod config;
 pub mod di;
 pub mod error;
+pub mod gallery;
+pub mod inheritance;
 pub mod manager;
 pub mod models;
 pub mod rules;
@@ -22,13 +32,30 @@ pub mod sync;
 // Re-export public types
 pub use access::AccessControlManager;
 pub use analytics::AnalyticsDashboard;
+pub use approval::{
+    ApprovalManager, ApprovalPolicy, ChangeProposal, ChangeReview, ProposalStatus, ProposedChange,
+    ReviewBypass, ReviewDecision,
+};
+pub use compliance_checker::{
+    ComplianceChecker, ComplianceReport, Constraint, ConstraintKind, NamingTarget, Severity,
+    Violation,
+};
 pub use config::TeamConfigManager;
 pub use error::{Result, TeamError};
+pub use gallery::{
+    GalleryEntry, GalleryListing, GalleryQuery, TemplateAdoption, TemplateGallery,
+    TemplateInstantiation,
+};
+pub use inheritance::{
+    BlockedOverride, DriftReport, EffectiveStandards, OrgBaseline, OverridePolicy,
+    RuleProvenance, StandardsItemKind, StandardsItemRef, StandardsResolver,
+};
 pub use manager::TeamManager;
 pub use models::{
-    AdoptionMetrics, AuditLogEntry, CodeReviewRule, ComplianceRequirement, EffectivenessMetrics,
-    MergedStandards, RuleScope, SharedRule, StandardsOverride, GovernanceDoc, Team,
-    TeamAnalyticsReport, TeamMember, TeamRole, TeamStandards, Template,
+    AdoptionMetrics, AuditLogEntry, CodeReviewRule, ComplianceRequirement, ComplianceSummary,
+    EffectivenessMetrics, MergedStandards, RuleScope, SharedRule, StandardsOverride, GovernanceDoc,
+    Team, TeamAnalyticsReport, TeamMember, TeamRole, TeamStandards, Template, TemplateFile,
+    TemplateKind, TemplateParameter,
 };
 pub use rules::SharedRulesManager;
 pub use sync::SyncService;
@@ -9,8 +9,11 @@ use uuid::Uuid;
 use crate::access::AccessControlManager;
 use crate::{
     analytics::AnalyticsDashboard,
+    approval::ApprovalManager,
     config::TeamConfigManager,
     error::{Result, TeamError},
+    gallery::TemplateGallery,
+    inheritance::StandardsResolver,
     models::{Team, TeamMember, TeamStandards},
     rules::SharedRulesManager,
     sync::SyncService,
@@ -23,6 +26,9 @@ pub struct TeamManager {
     access_control: Arc<AccessControlManager>,
     sync_service: Arc<SyncService>,
     analytics: Arc<AnalyticsDashboard>,
+    approvals: Arc<ApprovalManager>,
+    gallery: Arc<TemplateGallery>,
+    resolver: Arc<StandardsResolver>,
     /// In-memory cache of teams (team_id -> Team)
     teams_cache: Arc<RwLock<HashMap<String, Team>>>,
 }
@@ -46,12 +52,23 @@ impl TeamManager {
         sync_service: Arc<SyncService>,
         analytics: Arc<AnalyticsDashboard>,
     ) -> Self {
+        let approvals = Arc::new(ApprovalManager::new(
+            config_manager.clone(),
+            rules_manager.clone(),
+            access_control.clone(),
+        ));
+        let gallery = Arc::new(TemplateGallery::new(access_control.clone()));
+        let resolver = Arc::new(StandardsResolver::new(config_manager.clone()));
+
         TeamManager {
             config_manager,
             rules_manager,
             access_control,
             sync_service,
             analytics,
+            approvals,
+            gallery,
+            resolver,
             teams_cache: Arc::new(RwLock::new(HashMap::new())),
         }
     }
@@ -269,6 +286,21 @@ impl TeamManager {
         self.analytics.clone()
     }
 
+    /// Get reference to the standards change approval workflow
+    pub fn approvals(&self) -> Arc<ApprovalManager> {
+        self.approvals.clone()
+    }
+
+    /// Get reference to the team template gallery
+    pub fn gallery(&self) -> Arc<TemplateGallery> {
+        self.gallery.clone()
+    }
+
+    /// Get reference to the organization standards resolver
+    pub fn standards_resolver(&self) -> Arc<StandardsResolver> {
+        self.resolver.clone()
+    }
+
     // Helper functions
 
     /// Store team to persistent storage
@@ -69,7 +69,7 @@ pub struct TeamStandards {
 }
 
 /// Code review rule
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CodeReviewRule {
     pub id: String,
     pub name: String,
@@ -78,16 +78,57 @@ pub struct CodeReviewRule {
 }
 
 /// Project template
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Placeholders use ricecoder-generation syntax (`{{name}}`, `{{Name}}`,
+/// `{{name_snake}}`, ...) and must be declared in `parameters`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Template {
     pub id: String,
     pub name: String,
     pub description: String,
+    /// Body of a single-file template
+    pub content: String,
+    #[serde(default)]
+    pub kind: TemplateKind,
+    #[serde(default)]
+    pub parameters: Vec<TemplateParameter>,
+    /// Files of a project template; paths may contain placeholders
+    #[serde(default)]
+    pub files: Vec<TemplateFile>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// Whether a template produces a single file or a project tree
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TemplateKind {
+    Project,
+    #[default]
+    File,
+}
+
+/// Parameter accepted by a template
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateParameter {
+    /// Lowercase placeholder name
+    pub name: String,
+    pub description: String,
+    #[serde(default)]
+    pub default: Option<String>,
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// File produced by a project template
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateFile {
+    /// Path relative to the instantiation target
+    pub path: String,
     pub content: String,
 }
 
 /// Governance document
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GovernanceDoc {
     pub id: String,
     pub name: String,
@@ -95,7 +136,7 @@ pub struct GovernanceDoc {
 }
 
 /// Compliance requirement
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ComplianceRequirement {
     pub id: String,
     pub name: String,
@@ -202,9 +243,25 @@ pub struct TeamAnalyticsReport {
     pub total_members: u32,
     pub adoption_metrics: Vec<AdoptionMetrics>,
     pub effectiveness_metrics: Vec<EffectivenessMetrics>,
+    /// Latest compliance check result, if one has been recorded
+    #[serde(default)]
+    pub compliance: Option<ComplianceSummary>,
     pub generated_at: DateTime<Utc>,
 }
 
+/// Outcome of a compliance check against team standards
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComplianceSummary {
+    pub project_root: String,
+    pub files_checked: usize,
+    pub errors: usize,
+    pub warnings: usize,
+    pub infos: usize,
+    /// True when there are no error-level violations
+    pub passed: bool,
+    pub checked_at: DateTime<Utc>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -248,6 +305,10 @@ mod tests {
                 name: "Test Template".to_string(),
                 description: "A test template".to_string(),
                 content: "template content".to_string(),
+                kind: TemplateKind::File,
+                parameters: Vec::new(),
+                files: Vec::new(),
+                tags: Vec::new(),
             }],
             governance_docs: vec![GovernanceDoc {
                 id: "doc-1".to_string(),
@@ -480,6 +541,7 @@ updated_at: 2024-01-01T00:00:00Z
             total_members: 10,
             adoption_metrics: vec![],
             effectiveness_metrics: vec![],
+            compliance: None,
             generated_at: Utc::now(),
         };
         let json = serde_json::to_string(&report).expect("Failed to serialize to JSON");
@@ -5,7 +5,9 @@ use chrono::Utc;
 use proptest::prelude::*;
 use ricecoder_teams::{
     config::TeamConfigManager,
-    models::{CodeReviewRule, ComplianceRequirement, GovernanceDoc, TeamStandards, Template},
+    models::{
+        CodeReviewRule, ComplianceRequirement, GovernanceDoc, TeamStandards, Template, TemplateKind,
+    },
 };
 
 /// Strategy for generating random CodeReviewRule
@@ -37,6 +39,10 @@ fn arb_template() -> impl Strategy<Value = Template> {
             name,
             description,
             content,
+            kind: TemplateKind::File,
+            parameters: Vec::new(),
+            files: Vec::new(),
+            tags: Vec::new(),
         })
 }
 
@@ -0,0 +1,290 @@
+/// Unit tests for ApprovalManager
+/// Tests the propose → review → apply workflow, role enforcement, admin bypass,
+/// pending-change visibility, and audit entries
+use chrono::Utc;
+use ricecoder_teams::{
+    ApprovalPolicy, ProposalStatus, ProposedChange, ReviewDecision, RuleScope, SharedRule,
+    TeamError, TeamManager, TeamRole, TeamStandards,
+};
+use uuid::Uuid;
+
+/// Helper to create a manager with an admin, a member, and a viewer on a team
+async fn setup() -> (TeamManager, String) {
+    let manager = TeamManager::with_defaults();
+    let team_id = Uuid::new_v4().to_string();
+    let access = manager.access_control();
+    access
+        .assign_role(&team_id, "admin-1", TeamRole::Admin)
+        .await
+        .unwrap();
+    access
+        .assign_role(&team_id, "admin-2", TeamRole::Admin)
+        .await
+        .unwrap();
+    access
+        .assign_role(&team_id, "member-1", TeamRole::Member)
+        .await
+        .unwrap();
+    access
+        .assign_role(&team_id, "viewer-1", TeamRole::Viewer)
+        .await
+        .unwrap();
+    (manager, team_id)
+}
+
+fn rule_change(rule_id: &str) -> ProposedChange {
+    ProposedChange::SharedRule {
+        rule: SharedRule {
+            id: rule_id.to_string(),
+            name: format!("Rule {}", rule_id),
+            description: "Require error handling".to_string(),
+            scope: RuleScope::Project,
+            enforced: true,
+            promoted_by: "member-1".to_string(),
+            promoted_at: Utc::now(),
+            version: 1,
+        },
+        from_scope: RuleScope::Project,
+        to_scope: RuleScope::Team,
+    }
+}
+
+#[tokio::test]
+async fn test_propose_approve_apply() {
+    let (manager, team_id) = setup().await;
+    let approvals = manager.approvals();
+
+    let proposal = approvals
+        .propose(&team_id, "member-1", "Promote rule", rule_change("rule-1"))
+        .await
+        .expect("Failed to propose change");
+    assert_eq!(proposal.status, ProposalStatus::Pending);
+
+    // Cannot apply before approval
+    let result = approvals.apply(&proposal.id, "member-1").await;
+    assert!(matches!(result, Err(TeamError::InvalidProposalState(_))));
+
+    let reviewed = approvals
+        .review(
+            &proposal.id,
+            "admin-1",
+            ReviewDecision::Approve,
+            "Looks good",
+        )
+        .await
+        .expect("Failed to review");
+    assert_eq!(reviewed.status, ProposalStatus::Approved);
+
+    let applied = approvals
+        .apply(&proposal.id, "member-1")
+        .await
+        .expect("Failed to apply");
+    assert_eq!(applied.status, ProposalStatus::Applied);
+    assert!(applied.applied_at.is_some());
+    assert!(applied.bypass.is_none());
+
+    // Every step is audited
+    let audit = manager
+        .access_control()
+        .get_audit_log(&team_id)
+        .await
+        .unwrap();
+    for action in ["propose_change", "approve_change", "apply_change"] {
+        assert!(
+            audit.iter().any(|e| e.resource.ends_with(action)),
+            "missing audit entry for {}",
+            action
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_review_requires_reviewer_role() {
+    let (manager, team_id) = setup().await;
+    let approvals = manager.approvals();
+
+    let proposal = approvals
+        .propose(&team_id, "member-1", "Promote rule", rule_change("rule-2"))
+        .await
+        .unwrap();
+
+    let result = approvals
+        .review(&proposal.id, "viewer-1", ReviewDecision::Approve, "")
+        .await;
+    assert!(matches!(result, Err(TeamError::PermissionDenied(_))));
+
+    // Viewers cannot propose either
+    let result = approvals
+        .propose(&team_id, "viewer-1", "Promote rule", rule_change("rule-3"))
+        .await;
+    assert!(matches!(result, Err(TeamError::PermissionDenied(_))));
+
+    // Members can review once the policy allows it, but not their own proposals
+    approvals
+        .set_policy(
+            &team_id,
+            "admin-1",
+            ApprovalPolicy {
+                required_approvals: 2,
+                reviewer_roles: vec![TeamRole::Admin, TeamRole::Member],
+                allow_self_review: false,
+            },
+        )
+        .await
+        .unwrap();
+    let result = approvals
+        .review(&proposal.id, "member-1", ReviewDecision::Approve, "")
+        .await;
+    assert!(matches!(result, Err(TeamError::PermissionDenied(_))));
+
+    let reviewed = approvals
+        .review(&proposal.id, "admin-1", ReviewDecision::Approve, "")
+        .await
+        .unwrap();
+    assert_eq!(reviewed.status, ProposalStatus::Pending);
+    let reviewed = approvals
+        .review(&proposal.id, "admin-2", ReviewDecision::Approve, "")
+        .await
+        .unwrap();
+    assert_eq!(reviewed.status, ProposalStatus::Approved);
+}
+
+#[tokio::test]
+async fn test_reject_requires_comment() {
+    let (manager, team_id) = setup().await;
+    let approvals = manager.approvals();
+
+    let proposal = approvals
+        .propose(&team_id, "member-1", "Promote rule", rule_change("rule-4"))
+        .await
+        .unwrap();
+
+    let result = approvals
+        .review(&proposal.id, "admin-1", ReviewDecision::Reject, "  ")
+        .await;
+    assert!(result.is_err());
+
+    let rejected = approvals
+        .review(
+            &proposal.id,
+            "admin-1",
+            ReviewDecision::Reject,
+            "Too strict for legacy code",
+        )
+        .await
+        .unwrap();
+    assert_eq!(rejected.status, ProposalStatus::Rejected);
+    assert_eq!(rejected.reviews[0].comment, "Too strict for legacy code");
+
+    let result = approvals.apply(&proposal.id, "admin-1").await;
+    assert!(matches!(result, Err(TeamError::InvalidProposalState(_))));
+}
+
+#[tokio::test]
+async fn test_bypass_requires_admin_and_justification() {
+    let (manager, team_id) = setup().await;
+    let approvals = manager.approvals();
+
+    let proposal = approvals
+        .propose(&team_id, "member-1", "Hotfix rule", rule_change("rule-5"))
+        .await
+        .unwrap();
+
+    let result = approvals
+        .bypass_review(&proposal.id, "member-1", "urgent")
+        .await;
+    assert!(matches!(result, Err(TeamError::PermissionDenied(_))));
+
+    let result = approvals.bypass_review(&proposal.id, "admin-1", "").await;
+    assert!(matches!(result, Err(TeamError::PermissionDenied(_))));
+
+    let applied = approvals
+        .bypass_review(&proposal.id, "admin-1", "Production incident INC-42")
+        .await
+        .unwrap();
+    assert_eq!(applied.status, ProposalStatus::Applied);
+    let bypass = applied.bypass.expect("bypass should be recorded");
+    assert_eq!(bypass.admin_id, "admin-1");
+    assert_eq!(bypass.justification, "Production incident INC-42");
+
+    let audit = manager
+        .access_control()
+        .get_audit_log(&team_id)
+        .await
+        .unwrap();
+    assert!(audit.iter().any(|e| e.resource.ends_with("bypass_review")));
+}
+
+#[tokio::test]
+async fn test_pending_changes_visible_to_members() {
+    let (manager, team_id) = setup().await;
+    let approvals = manager.approvals();
+
+    let first = approvals
+        .propose(&team_id, "member-1", "First", rule_change("rule-6"))
+        .await
+        .unwrap();
+    let second = approvals
+        .propose(&team_id, "admin-1", "Second", rule_change("rule-7"))
+        .await
+        .unwrap();
+    approvals.withdraw(&second.id, "admin-1").await.unwrap();
+
+    let pending = approvals
+        .pending_changes(&team_id, "viewer-1")
+        .await
+        .unwrap();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].id, first.id);
+
+    // Non-members cannot see pending changes
+    let result = approvals.pending_changes(&team_id, "outsider").await;
+    assert!(matches!(result, Err(TeamError::MemberNotFound(_))));
+}
+
+#[tokio::test]
+async fn test_standards_change_bumps_version() {
+    let (manager, team_id) = setup().await;
+    let approvals = manager.approvals();
+
+    let standards = TeamStandards {
+        id: Uuid::new_v4().to_string(),
+        team_id: team_id.clone(),
+        code_review_rules: Vec::new(),
+        templates: Vec::new(),
+        governance_docs: Vec::new(),
+        compliance_requirements: Vec::new(),
+        version: 1,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    };
+
+    let proposal = approvals
+        .propose(
+            &team_id,
+            "member-1",
+            "Initial standards",
+            ProposedChange::Standards(standards),
+        )
+        .await
+        .unwrap();
+    approvals
+        .review(&proposal.id, "admin-1", ReviewDecision::Approve, "")
+        .await
+        .unwrap();
+    approvals.apply(&proposal.id, "admin-1").await.unwrap();
+
+    let stored = manager
+        .config_manager()
+        .get_standards(&team_id)
+        .await
+        .unwrap();
+    assert_eq!(stored.version, 1);
+
+    let history = manager
+        .config_manager()
+        .get_change_history(&team_id)
+        .await
+        .unwrap();
+    assert_eq!(history.len(), 1);
+}
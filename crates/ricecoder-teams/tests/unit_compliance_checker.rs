@@ -0,0 +1,224 @@
+/// Unit tests for ComplianceChecker
+/// Tests constraint extraction, violation locations and severities, JSON output,
+/// and the analytics dashboard hookup
+use std::fs;
+use std::path::Path;
+
+use chrono::Utc;
+use ricecoder_teams::{
+    AnalyticsDashboard, CodeReviewRule, ComplianceChecker, ComplianceRequirement, GovernanceDoc,
+    MergedStandards, Severity, TeamError, TeamStandards,
+};
+use tempfile::TempDir;
+
+fn standards(
+    code_review_rules: Vec<CodeReviewRule>,
+    governance_docs: Vec<GovernanceDoc>,
+    compliance_requirements: Vec<ComplianceRequirement>,
+) -> MergedStandards {
+    let final_standards = TeamStandards {
+        id: "standards-1".to_string(),
+        team_id: "team-1".to_string(),
+        code_review_rules,
+        templates: Vec::new(),
+        governance_docs,
+        compliance_requirements,
+        version: 3,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    };
+    MergedStandards {
+        organization_standards: None,
+        team_standards: Some(final_standards.clone()),
+        project_standards: None,
+        final_standards,
+    }
+}
+
+fn review_rule(id: &str, description: &str, enabled: bool) -> CodeReviewRule {
+    CodeReviewRule {
+        id: id.to_string(),
+        name: format!("Rule {}", id),
+        description: description.to_string(),
+        enabled,
+    }
+}
+
+/// Helper to create a small Rust project with one naming violation
+fn create_project() -> TempDir {
+    let dir = TempDir::new().expect("Failed to create temp dir");
+    let src = dir.path().join("src");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(
+        src.join("lib.rs"),
+        "pub struct Config {}\n\npub fn load_config() {}\n\npub fn loadAll() {}\n",
+    )
+    .unwrap();
+    dir
+}
+
+fn check(standards: &MergedStandards, root: &Path) -> ricecoder_teams::ComplianceReport {
+    ComplianceChecker::new()
+        .check(standards, root)
+        .expect("Compliance check failed")
+}
+
+#[test]
+fn test_naming_violation_has_location() {
+    let project = create_project();
+    let standards = standards(
+        vec![review_rule(
+            "naming",
+            "Functions use snake_case\nenforce: naming.functions = snake_case",
+            true,
+        )],
+        Vec::new(),
+        Vec::new(),
+    );
+
+    let report = check(&standards, project.path());
+    assert_eq!(report.files_checked, 1);
+    assert_eq!(report.standards_version, 3);
+    assert_eq!(report.violations.len(), 1);
+
+    let violation = &report.violations[0];
+    assert_eq!(violation.rule_id, "naming");
+    assert_eq!(violation.severity, Severity::Warning);
+    assert_eq!(violation.file.as_deref(), Some(Path::new("src/lib.rs")));
+    assert_eq!(violation.line, Some(5));
+    assert!(violation.message.contains("loadAll"));
+
+    // Warnings alone do not fail the check
+    assert!(report.passed());
+}
+
+#[test]
+fn test_compliance_requirements_are_errors() {
+    let project = create_project();
+    let standards = standards(
+        Vec::new(),
+        vec![GovernanceDoc {
+            id: "steering".to_string(),
+            name: "Steering".to_string(),
+            content: "enforce: require_file = CODEOWNERS\nenforce: severity = info".to_string(),
+        }],
+        vec![ComplianceRequirement {
+            id: "tests".to_string(),
+            name: "Tested code".to_string(),
+            description: "enforce: require_tests".to_string(),
+        }],
+    );
+
+    let report = check(&standards, project.path());
+    assert!(!report.passed());
+    assert_eq!(report.summary.errors, 1);
+    assert_eq!(report.summary.infos, 1);
+
+    // Errors are listed first
+    assert_eq!(report.violations[0].rule_id, "tests");
+    assert_eq!(report.violations[0].severity, Severity::Error);
+    assert_eq!(report.violations[1].severity, Severity::Info);
+    assert_eq!(
+        report.violations[1].file.as_deref(),
+        Some(Path::new("CODEOWNERS"))
+    );
+
+    fs::create_dir_all(project.path().join("tests")).unwrap();
+    fs::write(project.path().join("CODEOWNERS"), "* @team\n").unwrap();
+    let report = check(&standards, project.path());
+    assert!(report.passed());
+    assert!(report.violations.is_empty());
+}
+
+#[test]
+fn test_disabled_rules_and_prose_are_ignored() {
+    let project = create_project();
+    let standards = standards(
+        vec![
+            review_rule("off", "enforce: forbid = pub fn", false),
+            review_rule("prose", "Keep lines under 10 characters", true),
+        ],
+        Vec::new(),
+        Vec::new(),
+    );
+
+    let report = check(&standards, project.path());
+    assert!(report.violations.is_empty());
+}
+
+#[test]
+fn test_invalid_constraint_is_rejected() {
+    let project = create_project();
+    for description in [
+        "enforce: naming.functions = shouting",
+        "enforce: forbid = (unclosed",
+        "enforce: max_line_length = long",
+        "enforce: no_such_check",
+    ] {
+        let standards = standards(
+            vec![review_rule("bad", description, true)],
+            Vec::new(),
+            Vec::new(),
+        );
+        let result = ComplianceChecker::new().check(&standards, project.path());
+        assert!(
+            matches!(result, Err(TeamError::RuleValidationFailed(_))),
+            "expected validation failure for {:?}",
+            description
+        );
+    }
+}
+
+#[test]
+fn test_report_json_output() {
+    let project = create_project();
+    let standards = standards(
+        vec![review_rule(
+            "length",
+            "enforce: max_line_length = 20\nenforce: severity = error",
+            true,
+        )],
+        Vec::new(),
+        Vec::new(),
+    );
+
+    let report = check(&standards, project.path());
+    let json = report.to_json().expect("Failed to serialize report");
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(value["summary"]["passed"], false);
+    assert_eq!(value["summary"]["errors"], 1);
+    assert_eq!(value["violations"][0]["severity"], "error");
+    assert_eq!(value["violations"][0]["line"], 3);
+}
+
+#[tokio::test]
+async fn test_dashboard_includes_latest_compliance() {
+    let project = create_project();
+    let standards = standards(
+        vec![review_rule(
+            "naming",
+            "enforce: naming.functions = snake_case",
+            true,
+        )],
+        Vec::new(),
+        Vec::new(),
+    );
+    let report = check(&standards, project.path());
+
+    let dashboard = AnalyticsDashboard::new();
+    assert!(dashboard
+        .generate_report("team-1")
+        .await
+        .unwrap()
+        .compliance
+        .is_none());
+
+    dashboard.record_compliance("team-1", &report).await;
+    let analytics = dashboard.generate_report("team-1").await.unwrap();
+    let compliance = analytics.compliance.expect("compliance should be recorded");
+    assert_eq!(compliance.warnings, 1);
+    assert!(compliance.passed);
+
+    assert!(dashboard.get_compliance_summary("team-2").await.is_none());
+}
@@ -4,6 +4,7 @@ use chrono::Utc;
 use ricecoder_teams::config::TeamConfigManager;
 use ricecoder_teams::models::{
     CodeReviewRule, ComplianceRequirement, StandardsOverride, GovernanceDoc, TeamStandards, Template,
+    TemplateKind,
 };
 
 /// Helper function to create test standards
@@ -22,6 +23,10 @@ fn create_test_standards(team_id: &str, version: u32) -> TeamStandards {
             name: "Test Template".to_string(),
             description: "A test template".to_string(),
             content: "template content".to_string(),
+            kind: TemplateKind::File,
+            parameters: Vec::new(),
+            files: Vec::new(),
+            tags: Vec::new(),
         }],
         governance_docs: vec![GovernanceDoc {
             id: "doc-1".to_string(),
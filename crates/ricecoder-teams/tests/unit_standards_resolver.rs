@@ -0,0 +1,256 @@
+/// Unit tests for StandardsResolver
+/// Tests organization baselines, locked vs overridable rules, provenance of
+/// effective rules, and drift reports
+use chrono::Utc;
+use ricecoder_teams::{
+    CodeReviewRule, ComplianceRequirement, OrgBaseline, OverridePolicy, RuleScope,
+    StandardsItemKind, StandardsOverride, TeamError, TeamManager, TeamStandards,
+};
+use uuid::Uuid;
+
+fn rule(id: &str, description: &str, enabled: bool) -> CodeReviewRule {
+    CodeReviewRule {
+        id: id.to_string(),
+        name: format!("Rule {}", id),
+        description: description.to_string(),
+        enabled,
+    }
+}
+
+fn standards(
+    team_id: &str,
+    rules: Vec<CodeReviewRule>,
+    requirements: Vec<ComplianceRequirement>,
+) -> TeamStandards {
+    TeamStandards {
+        id: format!("standards-{}", team_id),
+        team_id: team_id.to_string(),
+        code_review_rules: rules,
+        templates: Vec::new(),
+        governance_docs: Vec::new(),
+        compliance_requirements: requirements,
+        version: 1,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+/// Org baseline with a locked security rule and two overridable rules
+fn baseline(org_id: &str) -> OrgBaseline {
+    OrgBaseline::new(
+        org_id,
+        standards(
+            org_id,
+            vec![
+                rule("security-review", "Security review required", true),
+                rule("line-length", "Max 100 columns", true),
+                rule("docs", "Public items documented", true),
+            ],
+            vec![ComplianceRequirement {
+                id: "license".to_string(),
+                name: "License headers".to_string(),
+                description: "All files carry a license header".to_string(),
+            }],
+        ),
+    )
+    .with_policy("security-review", OverridePolicy::Locked)
+    .with_max_drift(0.25)
+}
+
+async fn setup() -> (TeamManager, String, String) {
+    let manager = TeamManager::with_defaults();
+    let org_id = format!("org-{}", Uuid::new_v4());
+    let team_id = format!("team-{}", Uuid::new_v4());
+    let resolver = manager.standards_resolver();
+    resolver.set_baseline(baseline(&org_id)).await.unwrap();
+    resolver.attach_team(&team_id, &org_id).await.unwrap();
+    (manager, org_id, team_id)
+}
+
+#[tokio::test]
+async fn test_resolution_explains_sources() {
+    let (manager, org_id, team_id) = setup().await;
+    let project_id = format!("project-{}", Uuid::new_v4());
+    let config = manager.config_manager();
+    config
+        .store_standards(
+            &team_id,
+            standards(
+                &team_id,
+                vec![
+                    rule("line-length", "Max 120 columns", true),
+                    rule("team-only", "Prefer iterators", true),
+                ],
+                Vec::new(),
+            ),
+        )
+        .await
+        .unwrap();
+    config
+        .store_standards(
+            &project_id,
+            standards(
+                &project_id,
+                vec![rule("line-length", "Max 80 columns", true)],
+                Vec::new(),
+            ),
+        )
+        .await
+        .unwrap();
+
+    let effective = manager
+        .standards_resolver()
+        .resolve(&team_id, Some(&project_id))
+        .await
+        .expect("Failed to resolve standards");
+    assert_eq!(effective.org_id.as_deref(), Some(org_id.as_str()));
+
+    let final_rules = &effective.standards.final_standards.code_review_rules;
+    assert_eq!(final_rules.len(), 4);
+    let line_length = final_rules.iter().find(|r| r.id == "line-length").unwrap();
+    assert_eq!(line_length.description, "Max 80 columns");
+
+    let provenance = effective.explain("line-length").unwrap();
+    assert_eq!(provenance.source, RuleScope::Project);
+    assert_eq!(provenance.source_id, project_id);
+    assert_eq!(provenance.policy, Some(OverridePolicy::Overridable));
+    assert_eq!(
+        provenance.overrode,
+        vec![RuleScope::Organization, RuleScope::Team]
+    );
+
+    let docs = effective.explain("docs").unwrap();
+    assert_eq!(docs.source, RuleScope::Organization);
+    assert!(docs.overrode.is_empty());
+
+    let team_only = effective.explain("team-only").unwrap();
+    assert_eq!(team_only.source, RuleScope::Team);
+    assert_eq!(team_only.policy, None);
+
+    let license = effective.explain("license").unwrap();
+    assert_eq!(license.item.kind, StandardsItemKind::ComplianceRequirement);
+}
+
+#[tokio::test]
+async fn test_locked_rules_cannot_be_overridden() {
+    let (manager, _org_id, team_id) = setup().await;
+    manager
+        .config_manager()
+        .store_standards(
+            &team_id,
+            standards(
+                &team_id,
+                vec![rule("security-review", "Optional", false)],
+                Vec::new(),
+            ),
+        )
+        .await
+        .unwrap();
+
+    let resolver = manager.standards_resolver();
+    let effective = resolver.resolve(&team_id, None).await.unwrap();
+    let security = effective
+        .standards
+        .final_standards
+        .code_review_rules
+        .iter()
+        .find(|r| r.id == "security-review")
+        .unwrap();
+    assert!(security.enabled);
+    assert_eq!(effective.blocked_overrides.len(), 1);
+    assert_eq!(effective.blocked_overrides[0].scope, RuleScope::Team);
+    assert_eq!(
+        effective.explain("security-review").unwrap().source,
+        RuleScope::Organization
+    );
+
+    let overrides = StandardsOverride {
+        project_id: "project-1".to_string(),
+        overridden_standards: vec!["security-review".to_string()],
+        created_at: Utc::now(),
+    };
+    let result = resolver.check_override(&team_id, &overrides).await;
+    assert!(matches!(result, Err(TeamError::PermissionDenied(_))));
+
+    let overrides = StandardsOverride {
+        overridden_standards: vec!["docs".to_string()],
+        ..overrides
+    };
+    assert!(resolver.check_override(&team_id, &overrides).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_drift_report() {
+    let (manager, org_id, team_id) = setup().await;
+    let resolver = manager.standards_resolver();
+    let config = manager.config_manager();
+
+    // One of four baseline items changed: within the 25% threshold
+    config
+        .store_standards(
+            &team_id,
+            standards(
+                &team_id,
+                vec![
+                    rule("line-length", "Max 120 columns", true),
+                    rule("docs", "Public items documented", true),
+                    rule("team-only", "Prefer iterators", true),
+                ],
+                Vec::new(),
+            ),
+        )
+        .await
+        .unwrap();
+    let report = resolver.drift_report(&team_id).await.unwrap();
+    assert_eq!(report.org_id, org_id);
+    assert_eq!(report.baseline_items, 4);
+    assert_eq!(report.overridden.len(), 1);
+    assert_eq!(report.added.len(), 1);
+    assert!(report.disabled.is_empty());
+    assert!(!report.exceeds_threshold);
+
+    // Disabling the locked rule as well pushes the team over the threshold
+    config
+        .store_standards(
+            &team_id,
+            standards(
+                &team_id,
+                vec![
+                    rule("line-length", "Max 120 columns", true),
+                    rule("security-review", "Security review required", false),
+                ],
+                Vec::new(),
+            ),
+        )
+        .await
+        .unwrap();
+    let report = resolver.drift_report(&team_id).await.unwrap();
+    assert_eq!(report.disabled.len(), 1);
+    assert_eq!(report.locked_conflicts.len(), 1);
+    assert_eq!(report.locked_conflicts[0].id, "security-review");
+    assert!((report.drift - 0.5).abs() < f64::EPSILON);
+    assert!(report.exceeds_threshold);
+}
+
+#[tokio::test]
+async fn test_baseline_validation() {
+    let manager = TeamManager::with_defaults();
+    let resolver = manager.standards_resolver();
+    let org_id = format!("org-{}", Uuid::new_v4());
+
+    let result = resolver
+        .set_baseline(baseline(&org_id).with_policy("missing", OverridePolicy::Locked))
+        .await;
+    assert!(matches!(result, Err(TeamError::ConfigError(_))));
+
+    let result = resolver.attach_team("team-x", &org_id).await;
+    assert!(matches!(result, Err(TeamError::BaselineNotFound(_))));
+
+    // Teams without an organization resolve to their own standards
+    let effective = resolver.resolve("team-x", None).await.unwrap();
+    assert!(effective.org_id.is_none());
+    assert!(effective.provenance.is_empty());
+
+    let result = resolver.drift_report("team-x").await;
+    assert!(matches!(result, Err(TeamError::BaselineNotFound(_))));
+}
@@ -0,0 +1,268 @@
+/// Unit tests for TemplateGallery
+/// Tests publishing, gallery browsing with tags and adoption, and parameterized
+/// instantiation with tracked overrides
+use std::collections::HashMap;
+use std::fs;
+
+use ricecoder_teams::{
+    GalleryQuery, TeamError, TeamManager, TeamRole, Template, TemplateFile, TemplateKind,
+    TemplateParameter,
+};
+use tempfile::TempDir;
+use uuid::Uuid;
+
+/// Helper to create a manager with a member and a viewer on a team
+async fn setup() -> (TeamManager, String) {
+    let manager = TeamManager::with_defaults();
+    let team_id = Uuid::new_v4().to_string();
+    let access = manager.access_control();
+    access
+        .assign_role(&team_id, "member-1", TeamRole::Member)
+        .await
+        .unwrap();
+    access
+        .assign_role(&team_id, "viewer-1", TeamRole::Viewer)
+        .await
+        .unwrap();
+    (manager, team_id)
+}
+
+fn parameter(name: &str, default: Option<&str>, required: bool) -> TemplateParameter {
+    TemplateParameter {
+        name: name.to_string(),
+        description: format!("The {}", name),
+        default: default.map(str::to_string),
+        required,
+    }
+}
+
+fn service_template() -> Template {
+    Template {
+        id: "rust-service".to_string(),
+        name: "Rust Service".to_string(),
+        description: "Axum service skeleton".to_string(),
+        content: String::new(),
+        kind: TemplateKind::Project,
+        parameters: vec![
+            parameter("name", None, true),
+            parameter("port", Some("8080"), false),
+        ],
+        files: vec![
+            TemplateFile {
+                path: "{{name-kebab}}/Cargo.toml".to_string(),
+                content: "[package]\nname = \"{{name-kebab}}\"\n".to_string(),
+            },
+            TemplateFile {
+                path: "{{name-kebab}}/src/main.rs".to_string(),
+                content: "struct {{Name}};\nconst PORT: u16 = {{port}};\n".to_string(),
+            },
+        ],
+        tags: vec!["rust".to_string(), "service".to_string()],
+    }
+}
+
+fn license_template() -> Template {
+    Template {
+        id: "license-header".to_string(),
+        name: "License Header".to_string(),
+        description: "MIT header for source files".to_string(),
+        content: "// Copyright {{owner}}\n".to_string(),
+        kind: TemplateKind::File,
+        parameters: vec![parameter("owner", Some("ricecoder"), false)],
+        files: Vec::new(),
+        tags: vec!["legal".to_string()],
+    }
+}
+
+#[tokio::test]
+async fn test_publish_requires_member_role() {
+    let (manager, team_id) = setup().await;
+    let gallery = manager.gallery();
+
+    let result = gallery
+        .publish(&team_id, "viewer-1", service_template())
+        .await;
+    assert!(matches!(result, Err(TeamError::PermissionDenied(_))));
+
+    let entry = gallery
+        .publish(&team_id, "member-1", service_template())
+        .await
+        .expect("Failed to publish template");
+    assert_eq!(entry.revision, 1);
+
+    // Republishing creates a new revision
+    let entry = gallery
+        .publish(&team_id, "member-1", service_template())
+        .await
+        .unwrap();
+    assert_eq!(entry.revision, 2);
+
+    let audit = manager
+        .access_control()
+        .get_audit_log(&team_id)
+        .await
+        .unwrap();
+    assert!(audit
+        .iter()
+        .any(|e| e.resource.ends_with("publish_template")));
+}
+
+#[tokio::test]
+async fn test_publish_rejects_undeclared_placeholders() {
+    let (manager, team_id) = setup().await;
+    let mut template = license_template();
+    template.content = "// Copyright {{owner}} {{year}}\n".to_string();
+
+    let result = manager
+        .gallery()
+        .publish(&team_id, "member-1", template)
+        .await;
+    assert!(matches!(result, Err(TeamError::InvalidTemplate(_))));
+}
+
+#[tokio::test]
+async fn test_instantiate_project_template() {
+    let (manager, team_id) = setup().await;
+    let gallery = manager.gallery();
+    gallery
+        .publish(&team_id, "member-1", service_template())
+        .await
+        .unwrap();
+
+    let target = TempDir::new().unwrap();
+    let values = HashMap::from([("name".to_string(), "billing api".to_string())]);
+    let instantiation = gallery
+        .instantiate(
+            "rust-service",
+            "project-1",
+            "member-1",
+            values,
+            target.path(),
+        )
+        .await
+        .expect("Failed to instantiate template");
+
+    assert_eq!(instantiation.files_written.len(), 2);
+    let main = fs::read_to_string(target.path().join("billing-api/src/main.rs")).unwrap();
+    assert!(main.contains("struct BillingApi;"));
+    assert!(main.contains("const PORT: u16 = 8080;"));
+
+    // Only the parameter that differs from its default is an override
+    assert_eq!(instantiation.overrides.project_id, "project-1");
+    assert_eq!(
+        instantiation.overrides.overridden_standards,
+        vec!["template:rust-service:name".to_string()]
+    );
+
+    // Existing files are never overwritten
+    let values = HashMap::from([("name".to_string(), "billing api".to_string())]);
+    let result = gallery
+        .instantiate(
+            "rust-service",
+            "project-1",
+            "member-1",
+            values,
+            target.path(),
+        )
+        .await;
+    assert!(matches!(result, Err(TeamError::InvalidTemplate(_))));
+
+    let recorded = gallery.get_project_instantiations("project-1").await;
+    assert_eq!(recorded.len(), 1);
+}
+
+#[tokio::test]
+async fn test_instantiate_validates_parameters() {
+    let (manager, team_id) = setup().await;
+    let gallery = manager.gallery();
+    gallery
+        .publish(&team_id, "member-1", service_template())
+        .await
+        .unwrap();
+    let target = TempDir::new().unwrap();
+
+    let result = gallery
+        .instantiate(
+            "rust-service",
+            "p",
+            "member-1",
+            HashMap::new(),
+            target.path(),
+        )
+        .await;
+    assert!(matches!(result, Err(TeamError::InvalidTemplate(_))));
+
+    let values = HashMap::from([
+        ("name".to_string(), "svc".to_string()),
+        ("colour".to_string(), "blue".to_string()),
+    ]);
+    let result = gallery
+        .instantiate("rust-service", "p", "member-1", values, target.path())
+        .await;
+    assert!(matches!(result, Err(TeamError::InvalidTemplate(_))));
+
+    let result = gallery
+        .instantiate("missing", "p", "member-1", HashMap::new(), target.path())
+        .await;
+    assert!(matches!(result, Err(TeamError::TemplateNotFound(_))));
+}
+
+#[tokio::test]
+async fn test_browse_by_tag_and_adoption() {
+    let (manager, team_id) = setup().await;
+    let gallery = manager.gallery();
+    gallery
+        .publish(&team_id, "member-1", service_template())
+        .await
+        .unwrap();
+    gallery
+        .publish(&team_id, "member-1", license_template())
+        .await
+        .unwrap();
+
+    let target = TempDir::new().unwrap();
+    for project in ["project-1", "project-2"] {
+        gallery
+            .instantiate(
+                "license-header",
+                project,
+                "member-1",
+                HashMap::new(),
+                &target.path().join(format!("{}.rs", project)),
+            )
+            .await
+            .unwrap();
+    }
+
+    let listings = gallery.browse(&GalleryQuery::default()).await;
+    assert_eq!(listings.len(), 2);
+    assert_eq!(listings[0].template_id, "license-header");
+    assert_eq!(listings[0].adoption.instantiations, 2);
+    assert_eq!(listings[0].adoption.adopting_projects, 2);
+    assert_eq!(listings[0].adoption.adopting_members, 1);
+
+    let rust = gallery
+        .browse(&GalleryQuery {
+            tag: Some("rust".to_string()),
+            ..Default::default()
+        })
+        .await;
+    assert_eq!(rust.len(), 1);
+    assert_eq!(rust[0].template_id, "rust-service");
+
+    let files = gallery
+        .browse(&GalleryQuery {
+            kind: Some(TemplateKind::File),
+            text: Some("mit".to_string()),
+            ..Default::default()
+        })
+        .await;
+    assert_eq!(files.len(), 1);
+
+    let tags = gallery.tags().await;
+    assert_eq!(tags.get("legal"), Some(&1));
+    assert_eq!(tags.len(), 3);
+
+    let header = fs::read_to_string(target.path().join("project-1.rs")).unwrap();
+    assert_eq!(header, "// Copyright ricecoder\n");
+}
@@ -7,15 +7,19 @@ pub mod di;
 pub mod error;
 pub mod loader;
 pub mod manager;
+pub mod overrides;
 pub mod registry;
 pub mod reset;
 pub mod types;
+pub mod watcher;
 
 pub use error::{Result, ThemeError};
 pub use loader::ThemeLoader;
 pub use manager::ThemeManager;
+pub use overrides::{theme_color, ThemeOverride, OVERRIDABLE_COLORS};
 pub use registry::ThemeRegistry;
 pub use reset::ThemeResetManager;
+pub use watcher::ThemeWatcher;
 pub use types::{
     AgentColors, SyntaxTheme, Theme, ThemeConfig, ThemeError as ThemeErrorType,
     ThemeManager as ThemeManagerTrait, ThemeMetadata,
@@ -11,6 +11,7 @@ use ricecoder_storage::TuiConfig;
 
 use crate::{
     loader::ThemeLoader,
+    overrides::ThemeOverride,
     registry::ThemeRegistry,
     reset::ThemeResetManager,
     types::{Theme, ThemeError, ThemeManager as ThemeManagerTrait},
@@ -22,8 +23,12 @@ type ThemeListeners = Arc<Mutex<Vec<Box<dyn Fn(&Theme) + Send>>>>;
 /// Theme manager for runtime theme management and switching
 #[derive(Clone)]
 pub struct ThemeManager {
-    /// Current active theme
+    /// Current active theme (with overrides applied)
     current_theme: Arc<Mutex<Theme>>,
+    /// Active theme as loaded, before overrides
+    base_theme: Arc<Mutex<Theme>>,
+    /// Per-color overrides applied on top of the active theme
+    overrides: Arc<Mutex<ThemeOverride>>,
     /// Theme change listeners
     listeners: ThemeListeners,
     /// Theme registry for managing all themes
@@ -45,6 +50,8 @@ impl ThemeManager {
     pub fn new() -> Self {
         Self {
             current_theme: Arc::new(Mutex::new(Theme::default())),
+            base_theme: Arc::new(Mutex::new(Theme::default())),
+            overrides: Arc::new(Mutex::new(ThemeOverride::new())),
             listeners: Arc::new(Mutex::new(Vec::new())),
             registry: ThemeRegistry::new(),
             reset_manager: Arc::new(ThemeResetManager::new()),
@@ -54,7 +61,9 @@ impl ThemeManager {
     /// Create a theme manager with a specific theme
     pub fn with_theme(theme: Theme) -> Self {
         Self {
-            current_theme: Arc::new(Mutex::new(theme)),
+            current_theme: Arc::new(Mutex::new(theme.clone())),
+            base_theme: Arc::new(Mutex::new(theme)),
+            overrides: Arc::new(Mutex::new(ThemeOverride::new())),
             listeners: Arc::new(Mutex::new(Vec::new())),
             registry: ThemeRegistry::new(),
             reset_manager: Arc::new(ThemeResetManager::new()),
@@ -65,6 +74,8 @@ impl ThemeManager {
     pub fn with_registry(registry: ThemeRegistry) -> Self {
         Self {
             current_theme: Arc::new(Mutex::new(Theme::default())),
+            base_theme: Arc::new(Mutex::new(Theme::default())),
+            overrides: Arc::new(Mutex::new(ThemeOverride::new())),
             listeners: Arc::new(Mutex::new(Vec::new())),
             registry,
             reset_manager: Arc::new(ThemeResetManager::new()),
@@ -91,7 +102,19 @@ impl ThemeManager {
     }
 
     /// Switch to a specific theme
+    ///
+    /// Color overrides are applied on top of the new theme.
     pub fn switch_to(&self, theme: Theme) -> Result<()> {
+        *self
+            .base_theme
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Failed to lock theme: {}", e))? = theme.clone();
+        let theme = self
+            .overrides
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Failed to lock overrides: {}", e))?
+            .applied(&theme);
+
         let mut current = self
             .current_theme
             .lock()
@@ -110,6 +133,85 @@ impl ThemeManager {
         Ok(())
     }
 
+    /// Current color overrides
+    pub fn overrides(&self) -> Result<ThemeOverride> {
+        Ok(self
+            .overrides
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Failed to lock overrides: {}", e))?
+            .clone())
+    }
+
+    /// Replace the color overrides and re-apply the active theme
+    pub fn set_overrides(&self, overrides: ThemeOverride) -> Result<()> {
+        *self
+            .overrides
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Failed to lock overrides: {}", e))? = overrides;
+        self.reapply()
+    }
+
+    /// Override a single color of the active theme
+    pub fn set_color_override(&self, name: &str, value: &str) -> Result<()> {
+        let mut overrides = self.overrides()?;
+        overrides.set(name, value)?;
+        self.set_overrides(overrides)
+    }
+
+    /// Remove a single color override
+    pub fn remove_color_override(&self, name: &str) -> Result<bool> {
+        let mut overrides = self.overrides()?;
+        let removed = overrides.remove(name);
+        if removed {
+            self.set_overrides(overrides)?;
+        }
+        Ok(removed)
+    }
+
+    /// Active theme with one extra override, without committing it
+    pub fn preview_color_override(&self, name: &str, value: &str) -> Result<Theme> {
+        let mut overrides = self.overrides()?;
+        overrides.set(name, value)?;
+        let base = self
+            .base_theme
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Failed to lock theme: {}", e))?
+            .clone();
+        Ok(overrides.applied(&base))
+    }
+
+    /// Reload a theme file that changed on disk
+    ///
+    /// The theme is re-registered; if it is the active theme it is applied
+    /// immediately. Returns whether the active theme changed.
+    pub fn reload_theme_file(&self, path: &Path) -> Result<bool> {
+        let theme = ThemeLoader::load_from_file(path)?;
+        self.register_theme(theme.clone())?;
+
+        let active = self
+            .base_theme
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Failed to lock theme: {}", e))?
+            .name
+            .clone();
+        if theme.name != active {
+            return Ok(false);
+        }
+        tracing::info!("Reloaded theme {} from {}", theme.name, path.display());
+        self.switch_to(theme)?;
+        Ok(true)
+    }
+
+    /// Re-apply the active theme with the current overrides
+    fn reapply(&self) -> Result<()> {
+        let base = self
+            .base_theme
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Failed to lock theme: {}", e))?
+            .clone();
+        self.switch_to(base)
+    }
+
     /// Get all available theme names
     pub fn available_themes(&self) -> Vec<String> {
         self.registry.list_all().unwrap_or_default()
@@ -120,14 +222,30 @@ impl ThemeManager {
         Ok(self.current()?.name)
     }
 
-    /// Load theme from config
+    /// Load theme and color overrides from config
+    ///
+    /// Invalid overrides are logged and skipped.
     pub fn load_from_config(&self, config: &TuiConfig) -> Result<()> {
-        self.switch_by_name(&config.theme)
+        let (overrides, errors) = ThemeOverride::from_config(config);
+        for error in errors {
+            tracing::warn!("Ignoring theme override: {}", error);
+        }
+        *self
+            .overrides
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Failed to lock overrides: {}", e))? = overrides;
+        if let Err(e) = self.switch_by_name(&config.theme) {
+            // Keep the overrides visible even if the theme name is unknown
+            self.reapply()?;
+            return Err(e);
+        }
+        Ok(())
     }
 
-    /// Save current theme to config
+    /// Save current theme and color overrides to config
     pub fn save_to_config(&self, config: &mut TuiConfig) -> Result<()> {
         config.theme = self.current_name()?;
+        self.overrides()?.save_to_config(config);
         Ok(())
     }
 
@@ -0,0 +1,268 @@
+//! Per-color theme overrides
+//!
+//! Users can tweak individual colors of whichever theme is active through
+//! `tui.theme_overrides` in their config, for example:
+//!
+//! ```yaml
+//! tui:
+//!   theme_overrides:
+//!     diff.added: "#1e3c1e"
+//!     border_active: cyan
+//! ```
+//!
+//! Color names match the [`Theme`] fields; nested groups use a dot
+//! (`diff.*`, `syntax.*`, `agent.*`). Values accept anything ratatui can
+//! parse: `#rrggbb`, named colors or an indexed color.
+
+use std::{collections::BTreeMap, str::FromStr};
+
+use anyhow::{anyhow, Result};
+use ratatui::style::Color;
+use ricecoder_storage::TuiConfig;
+
+use crate::types::Theme;
+
+/// All color names that can be overridden
+pub const OVERRIDABLE_COLORS: &[&str] = &[
+    "primary",
+    "secondary",
+    "background",
+    "foreground",
+    "accent",
+    "error",
+    "warning",
+    "success",
+    "info",
+    "text_muted",
+    "selected_list_item_text",
+    "background_panel",
+    "background_element",
+    "background_menu",
+    "border",
+    "border_active",
+    "border_subtle",
+    "syntax.keyword",
+    "syntax.string",
+    "syntax.number",
+    "syntax.comment",
+    "syntax.function",
+    "syntax.variable",
+    "syntax.type",
+    "syntax.constant",
+    "agent.build",
+    "agent.plan",
+    "agent.general",
+    "agent.explore",
+    "diff.added",
+    "diff.removed",
+    "diff.context",
+    "diff.hunk_header",
+    "diff.highlight_added",
+    "diff.highlight_removed",
+    "diff.line_number_added",
+    "diff.line_number_removed",
+];
+
+/// Mutable reference to a theme color by name
+fn color_slot<'a>(theme: &'a mut Theme, name: &str) -> Option<&'a mut Color> {
+    let slot = match name {
+        "primary" => &mut theme.primary,
+        "secondary" => &mut theme.secondary,
+        "background" => &mut theme.background,
+        "foreground" => &mut theme.foreground,
+        "accent" => &mut theme.accent,
+        "error" => &mut theme.error,
+        "warning" => &mut theme.warning,
+        "success" => &mut theme.success,
+        "info" => &mut theme.info,
+        "text_muted" => &mut theme.text_muted,
+        "selected_list_item_text" => &mut theme.selected_list_item_text,
+        "background_panel" => &mut theme.background_panel,
+        "background_element" => &mut theme.background_element,
+        "background_menu" => &mut theme.background_menu,
+        "border" => &mut theme.border,
+        "border_active" => &mut theme.border_active,
+        "border_subtle" => &mut theme.border_subtle,
+        "syntax.keyword" => &mut theme.syntax.keyword,
+        "syntax.string" => &mut theme.syntax.string,
+        "syntax.number" => &mut theme.syntax.number,
+        "syntax.comment" => &mut theme.syntax.comment,
+        "syntax.function" => &mut theme.syntax.function,
+        "syntax.variable" => &mut theme.syntax.variable,
+        "syntax.type" => &mut theme.syntax.r#type,
+        "syntax.constant" => &mut theme.syntax.constant,
+        "agent.build" => &mut theme.agent_colors.build,
+        "agent.plan" => &mut theme.agent_colors.plan,
+        "agent.general" => &mut theme.agent_colors.general,
+        "agent.explore" => &mut theme.agent_colors.explore,
+        "diff.added" => &mut theme.diff.added,
+        "diff.removed" => &mut theme.diff.removed,
+        "diff.context" => &mut theme.diff.context,
+        "diff.hunk_header" => &mut theme.diff.hunk_header,
+        "diff.highlight_added" => &mut theme.diff.highlight_added,
+        "diff.highlight_removed" => &mut theme.diff.highlight_removed,
+        "diff.line_number_added" => &mut theme.diff.line_number_added,
+        "diff.line_number_removed" => &mut theme.diff.line_number_removed,
+        _ => return None,
+    };
+    Some(slot)
+}
+
+/// Current value of a theme color by name
+pub fn theme_color(theme: &Theme, name: &str) -> Option<Color> {
+    color_slot(&mut theme.clone(), &normalize_name(name)).copied()
+}
+
+/// Normalize a user-supplied color name (`Diff.Added`, `diff-added` → `diff.added`)
+fn normalize_name(name: &str) -> String {
+    let name = name.trim().to_lowercase();
+    if OVERRIDABLE_COLORS.contains(&name.as_str()) {
+        return name;
+    }
+    // Accept `diff-added` / `diff_added` for grouped colors
+    for group in ["syntax", "agent", "diff"] {
+        for separator in ['-', '_'] {
+            if let Some(rest) = name.strip_prefix(&format!("{}{}", group, separator)) {
+                return format!("{}.{}", group, rest);
+            }
+        }
+    }
+    name
+}
+
+/// Parse a color value
+pub fn parse_color_value(value: &str) -> Result<Color> {
+    Color::from_str(value.trim()).map_err(|_| anyhow!("Invalid color value: {}", value))
+}
+
+/// Set of color overrides applied on top of the active theme
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ThemeOverride {
+    colors: BTreeMap<String, Color>,
+}
+
+impl ThemeOverride {
+    /// Create an empty override set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build overrides from string pairs, collecting invalid entries
+    ///
+    /// Invalid entries are skipped so one typo does not discard the rest;
+    /// their errors are returned alongside the valid overrides.
+    pub fn from_map(entries: &BTreeMap<String, String>) -> (Self, Vec<String>) {
+        let mut overrides = Self::new();
+        let mut errors = Vec::new();
+        for (name, value) in entries {
+            if let Err(e) = overrides.set(name, value) {
+                errors.push(e.to_string());
+            }
+        }
+        (overrides, errors)
+    }
+
+    /// Build overrides from `tui.theme_overrides`
+    pub fn from_config(config: &TuiConfig) -> (Self, Vec<String>) {
+        Self::from_map(&config.theme_overrides)
+    }
+
+    /// Override one color
+    pub fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        let name = normalize_name(name);
+        if !OVERRIDABLE_COLORS.contains(&name.as_str()) {
+            return Err(anyhow!("Unknown theme color: {}", name));
+        }
+        let color = parse_color_value(value)?;
+        self.colors.insert(name, color);
+        Ok(())
+    }
+
+    /// Remove an override, returning whether one existed
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.colors.remove(&normalize_name(name)).is_some()
+    }
+
+    /// Overridden colors by name
+    pub fn colors(&self) -> &BTreeMap<String, Color> {
+        &self.colors
+    }
+
+    /// Whether no colors are overridden
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+
+    /// Apply the overrides to a theme in place
+    pub fn apply_to(&self, theme: &mut Theme) {
+        for (name, color) in &self.colors {
+            if let Some(slot) = color_slot(theme, name) {
+                *slot = *color;
+            }
+        }
+    }
+
+    /// Copy of `theme` with the overrides applied
+    pub fn applied(&self, theme: &Theme) -> Theme {
+        let mut theme = theme.clone();
+        self.apply_to(&mut theme);
+        theme
+    }
+
+    /// Write the overrides back to `tui.theme_overrides`
+    pub fn save_to_config(&self, config: &mut TuiConfig) {
+        config.theme_overrides = self
+            .colors
+            .iter()
+            .map(|(name, color)| (name.clone(), color.to_string()))
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_overridable_color_has_a_slot() {
+        let mut theme = Theme::default();
+        for name in OVERRIDABLE_COLORS {
+            assert!(color_slot(&mut theme, name).is_some(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_apply_overrides() {
+        let mut overrides = ThemeOverride::new();
+        overrides.set("Diff-Added", "#102030").unwrap();
+        overrides.set("border_active", "magenta").unwrap();
+        assert!(overrides.set("nope", "#000000").is_err());
+        assert!(overrides.set("error", "not-a-color").is_err());
+
+        let theme = overrides.applied(&Theme::default());
+        assert_eq!(theme.diff.added, Color::Rgb(0x10, 0x20, 0x30));
+        assert_eq!(theme.border_active, Color::Magenta);
+        assert_eq!(theme.error, Theme::default().error);
+    }
+
+    #[test]
+    fn test_config_round_trip_keeps_valid_entries() {
+        let mut config = TuiConfig::default();
+        config
+            .theme_overrides
+            .insert("diff.removed".to_string(), "#3c1e1e".to_string());
+        config
+            .theme_overrides
+            .insert("bogus".to_string(), "#3c1e1e".to_string());
+
+        let (overrides, errors) = ThemeOverride::from_config(&config);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(overrides.colors().len(), 1);
+
+        let mut saved = TuiConfig::default();
+        overrides.save_to_config(&mut saved);
+        let (reloaded, errors) = ThemeOverride::from_config(&saved);
+        assert!(errors.is_empty());
+        assert_eq!(reloaded, overrides);
+    }
+}
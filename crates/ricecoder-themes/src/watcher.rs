@@ -0,0 +1,148 @@
+//! Theme file watching for hot reload
+//!
+//! [`ThemeWatcher`] watches theme directories and reports changed theme
+//! files once writes have settled. It is polled rather than callback driven
+//! so the TUI event loop can pick up changes between frames and hand them to
+//! [`ThemeManager::reload_theme_file`](crate::ThemeManager::reload_theme_file).
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver},
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::loader::ThemeLoader;
+
+/// Quiet period after the last write before a change is reported
+const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Whether a path looks like a theme file
+fn is_theme_file(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("json" | "yaml" | "yml")
+    )
+}
+
+/// Watches theme directories for changes
+pub struct ThemeWatcher {
+    watcher: RecommendedWatcher,
+    events: Receiver<PathBuf>,
+    pending: HashMap<PathBuf, Instant>,
+    debounce: Duration,
+    watched: Vec<PathBuf>,
+}
+
+impl ThemeWatcher {
+    /// Create a watcher that is not watching anything yet
+    pub fn new() -> Result<Self> {
+        let (tx, events) = mpsc::channel();
+        let watcher = RecommendedWatcher::new(
+            move |res: std::result::Result<notify::Event, notify::Error>| match res {
+                Ok(event) => {
+                    if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+                        for path in event.paths.into_iter().filter(|p| is_theme_file(p)) {
+                            let _ = tx.send(path);
+                        }
+                    }
+                }
+                Err(e) => tracing::warn!("Theme watch error: {}", e),
+            },
+            notify::Config::default(),
+        )?;
+
+        Ok(Self {
+            watcher,
+            events,
+            pending: HashMap::new(),
+            debounce: DEFAULT_DEBOUNCE,
+            watched: Vec::new(),
+        })
+    }
+
+    /// Watch the user and custom theme directories that exist
+    pub fn for_user_themes() -> Result<Self> {
+        let mut watcher = Self::new()?;
+        let dirs = [
+            ThemeLoader::user_themes_directory().ok(),
+            dirs::home_dir().map(|home| home.join(".ricecoder").join("themes").join("custom")),
+        ];
+        for dir in dirs.into_iter().flatten() {
+            if dir.is_dir() {
+                watcher.watch_dir(&dir)?;
+            }
+        }
+        Ok(watcher)
+    }
+
+    /// Set the debounce period
+    pub fn with_debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    /// Start watching a directory
+    pub fn watch_dir(&mut self, dir: &Path) -> Result<()> {
+        self.watcher.watch(dir, RecursiveMode::NonRecursive)?;
+        self.watched.push(dir.to_path_buf());
+        Ok(())
+    }
+
+    /// Directories being watched
+    pub fn watched_dirs(&self) -> &[PathBuf] {
+        &self.watched
+    }
+
+    /// Theme files whose changes have settled since the last poll
+    ///
+    /// Never blocks.
+    pub fn poll_changes(&mut self) -> Vec<PathBuf> {
+        let now = Instant::now();
+        while let Ok(path) = self.events.try_recv() {
+            self.pending.insert(path, now);
+        }
+
+        let debounce = self.debounce;
+        let mut ready: Vec<PathBuf> = self
+            .pending
+            .iter()
+            .filter(|(_, last)| now.duration_since(**last) >= debounce)
+            .map(|(path, _)| path.clone())
+            .collect();
+        for path in &ready {
+            self.pending.remove(path);
+        }
+        ready.sort();
+        ready
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reports_changed_theme_files_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut watcher = ThemeWatcher::new()
+            .unwrap()
+            .with_debounce(Duration::from_millis(0));
+        watcher.watch_dir(dir.path()).unwrap();
+
+        std::fs::write(dir.path().join("mine.json"), "{}").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "x").unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut changes = Vec::new();
+        while changes.is_empty() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(50));
+            changes = watcher.poll_changes();
+        }
+        assert!(changes.iter().any(|p| p.ends_with("mine.json")));
+        assert!(!changes.iter().any(|p| p.ends_with("notes.txt")));
+    }
+}
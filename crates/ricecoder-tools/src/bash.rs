@@ -8,10 +8,13 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Instant;
 use tokio::process::Command;
 use tokio::time::{timeout, Duration};
 
+use ricecoder_safety::{Sandbox, SandboxPolicy, SandboxRequest, SecurityConstraint};
+
 use crate::context::ToolContext;
 use crate::descriptions::get_description;
 use crate::error::ToolError;
@@ -68,23 +71,44 @@ pub struct BashOutput {
 pub struct BashTool {
     /// Default workspace root
     workspace_root: PathBuf,
+    /// OS sandbox commands run in, if enabled
+    sandbox: Option<Arc<Sandbox>>,
+    /// Constraints the sandbox policy is derived from
+    sandbox_constraints: Vec<SecurityConstraint>,
 }
 
 impl BashTool {
     /// Create a new BashTool with a workspace root
     pub fn new(workspace_root: PathBuf) -> Self {
-        Self { workspace_root }
+        Self {
+            workspace_root,
+            sandbox: None,
+            sandbox_constraints: Vec::new(),
+        }
     }
 
     /// Create a new BashTool using current directory as workspace
     pub fn with_current_dir() -> Result<Self, ToolError> {
         let workspace_root = std::env::current_dir()
             .map_err(|e| ToolError::new("INIT_ERROR", format!("Failed to get current directory: {}", e)))?;
-        Ok(Self { workspace_root })
+        Ok(Self::new(workspace_root))
+    }
+
+    /// Run commands inside an OS sandbox
+    ///
+    /// The sandbox policy is derived from `constraints` for each command's
+    /// working directory.
+    pub fn with_sandbox(mut self, sandbox: Arc<Sandbox>, constraints: Vec<SecurityConstraint>) -> Self {
+        self.sandbox = Some(sandbox);
+        self.sandbox_constraints = constraints;
+        self
     }
 
     /// Execute bash command with given input
-    pub async fn execute_command(&self, input: &BashInput, _ctx: &ToolContext) -> Result<BashOutput, ToolError> {
+    ///
+    /// Variables and secrets the session scoped to `bash` are set in the
+    /// command's environment, and secret values are redacted from its output.
+    pub async fn execute_command(&self, input: &BashInput, ctx: &ToolContext) -> Result<BashOutput, ToolError> {
         let start = Instant::now();
 
         // Validate command
@@ -113,6 +137,8 @@ impl BashTool {
         // Get timeout
         let timeout_ms = input.timeout.unwrap_or(DEFAULT_TIMEOUT_MS);
 
+        let env = ctx.environment_for("bash")?;
+
         // Prepare shell command
         let (shell, shell_arg) = if cfg!(target_os = "windows") {
             // On Windows, prefer Git Bash if available, else cmd
@@ -127,26 +153,38 @@ impl BashTool {
         };
 
         // Execute command with timeout
-        let command_future = Command::new(&shell)
-            .arg(&shell_arg)
-            .arg(&input.command)
-            .current_dir(&workdir)
-            .env("TERM", "dumb") // Disable terminal formatting
-            .output();
-
-        let output = match timeout(Duration::from_millis(timeout_ms), command_future).await {
-            Ok(Ok(output)) => output,
-            Ok(Err(e)) => {
-                return Err(ToolError::new(
-                    "EXECUTION_ERROR",
-                    format!("Command execution failed: {}", e),
-                ));
+        let output = if let Some(sandbox) = &self.sandbox {
+            let request = env.vars().fold(
+                SandboxRequest::new(&shell)
+                    .arg(&shell_arg)
+                    .arg(&input.command)
+                    .workdir(&workdir)
+                    .env("TERM", "dumb"),
+                |request, (name, value)| request.env(name, value),
+            );
+            let policy = SandboxPolicy::from_constraints(&self.sandbox_constraints, &workdir);
+            let command = sandbox
+                .command(&request, &policy)
+                .map_err(|e| ToolError::new("SANDBOX_ERROR", e.to_string()))?;
+
+            match timeout(Duration::from_millis(timeout_ms), command.output()).await {
+                Ok(Ok(output)) => output,
+                Ok(Err(e)) => return Err(execution_error(e)),
+                Err(_) => return Err(timeout_error(timeout_ms)),
             }
-            Err(_) => {
-                return Err(ToolError::new(
-                    "TIMEOUT",
-                    format!("Command timed out after {}ms", timeout_ms),
-                ));
+        } else {
+            let command_future = Command::new(&shell)
+                .arg(&shell_arg)
+                .arg(&input.command)
+                .current_dir(&workdir)
+                .env("TERM", "dumb") // Disable terminal formatting
+                .envs(env.vars())
+                .output();
+
+            match timeout(Duration::from_millis(timeout_ms), command_future).await {
+                Ok(Ok(output)) => output,
+                Ok(Err(e)) => return Err(execution_error(e)),
+                Err(_) => return Err(timeout_error(timeout_ms)),
             }
         };
 
@@ -167,6 +205,9 @@ impl BashTool {
             combined.push_str(&stderr);
         }
 
+        let combined = env.redact(&combined);
+        drop(env);
+
         // Truncate if necessary
         let truncated = combined.len() > MAX_OUTPUT_SIZE;
         let output_text = if truncated {
@@ -195,11 +236,23 @@ impl BashTool {
     }
 }
 
+fn execution_error(error: impl std::fmt::Display) -> ToolError {
+    ToolError::new(
+        "EXECUTION_ERROR",
+        format!("Command execution failed: {}", error),
+    )
+}
+
+fn timeout_error(timeout_ms: u64) -> ToolError {
+    ToolError::new(
+        "TIMEOUT",
+        format!("Command timed out after {}ms", timeout_ms),
+    )
+}
+
 impl Default for BashTool {
     fn default() -> Self {
-        Self::with_current_dir().unwrap_or_else(|_| Self {
-            workspace_root: PathBuf::from("."),
-        })
+        Self::with_current_dir().unwrap_or_else(|_| Self::new(PathBuf::from(".")))
     }
 }
 
@@ -458,4 +511,55 @@ mod tests {
         let result = tool.execute(args, &ctx).await;
         assert!(result.is_err());
     }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_bash_tool_session_secrets() {
+        use ricecoder_security::KeyManager;
+
+        use crate::session_env::SessionEnvironment;
+
+        let env = Arc::new(SessionEnvironment::new(Arc::new(
+            KeyManager::new("test-password").unwrap(),
+        )));
+        env.set_secret("s1", "DEPLOY_TOKEN", "tok-123", ["bash"]).unwrap();
+        let ctx = ToolContext::new("s1".to_string(), "m1".to_string(), "build".to_string())
+            .with_environment(env.clone());
+        let input = BashInput {
+            command: "echo \"token=$DEPLOY_TOKEN\"".to_string(),
+            workdir: None,
+            timeout: None,
+            description: None,
+        };
+
+        let output = BashTool::default().execute_command(&input, &ctx).await.unwrap();
+        assert_eq!(output.output.trim(), "token=[REDACTED]");
+
+        // Nothing is injected once the session has ended
+        env.end_session("s1");
+        let output = BashTool::default().execute_command(&input, &ctx).await.unwrap();
+        assert_eq!(output.output.trim(), "token=");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_bash_tool_sandbox_fails_closed() {
+        use ricecoder_safety::sandbox::LinuxSandbox;
+
+        // Without bubblewrap the filesystem can't be isolated
+        let sandbox = Sandbox::with_backend(Arc::new(LinuxSandbox::with_bwrap(None)));
+        let tool = BashTool::default().with_sandbox(Arc::new(sandbox), Vec::new());
+        let input = BashInput {
+            command: "echo hello".to_string(),
+            workdir: None,
+            timeout: None,
+            description: None,
+        };
+
+        let err = tool
+            .execute_command(&input, &ToolContext::default())
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, "SANDBOX_ERROR");
+    }
 }
@@ -10,6 +10,9 @@ use tokio::sync::RwLock;
 use serde::{Deserialize, Serialize};
 use futures::FutureExt;
 
+use crate::error::ToolError;
+use crate::session_env::{ResolvedEnv, SessionEnvironment};
+
 /// Tool execution context (OpenCode-compatible)
 ///
 /// Matches OpenCode `Tool.Context<M>` interface with all fields:
@@ -41,6 +44,9 @@ pub struct ToolContext {
     
     /// Metadata callback for incremental reporting
     metadata_callback: Arc<RwLock<Option<MetadataCallback>>>,
+
+    /// Session-scoped variables and secrets injected into tool processes
+    environment: Option<Arc<SessionEnvironment>>,
 }
 
 /// Metadata update for incremental reporting (OpenCode-compatible)
@@ -68,6 +74,7 @@ impl ToolContext {
             call_id: None,
             extra: None,
             metadata_callback: Arc::new(RwLock::new(None)),
+            environment: None,
         }
     }
 
@@ -83,6 +90,22 @@ impl ToolContext {
         self
     }
 
+    /// Set the session environment tools may draw variables and secrets from
+    pub fn with_environment(mut self, environment: Arc<SessionEnvironment>) -> Self {
+        self.environment = Some(environment);
+        self
+    }
+
+    /// Resolve the variables and secrets this session exposes to a tool
+    ///
+    /// Empty when no session environment is set.
+    pub fn environment_for(&self, tool: &str) -> Result<ResolvedEnv, ToolError> {
+        match &self.environment {
+            Some(environment) => environment.resolve(&self.session_id, tool),
+            None => Ok(ResolvedEnv::default()),
+        }
+    }
+
     /// Set a metadata callback for incremental reporting (OpenCode-compatible)
     ///
     /// Matches OpenCode `metadata({ title?, metadata? })` signature
@@ -155,6 +178,7 @@ impl fmt::Debug for ToolContext {
             .field("agent", &self.agent)
             .field("call_id", &self.call_id)
             .field("extra", &self.extra)
+            .field("has_environment", &self.environment.is_some())
             .field("has_metadata_callback", &self.metadata_callback.try_read().map(|cb| cb.is_some()).unwrap_or(false))
             .finish()
     }
@@ -6,6 +6,7 @@
 
 use std::path::Path;
 
+use ricecoder_safety::PathAccess;
 use serde::{Deserialize, Serialize};
 
 use crate::error::ToolError;
@@ -280,6 +281,7 @@ impl FileEditTool {
         }
 
         let file_path = Path::new(&input.file_path);
+        ricecoder_safety::sensitive_paths::global().check(file_path, PathAccess::Write)?;
         
         // GAP-10: File type/existence checks with specific error messages
         if !file_path.exists() {
@@ -94,6 +94,15 @@ impl From<std::io::Error> for ToolError {
     }
 }
 
+/// Conversion from sensitive path denials
+impl From<ricecoder_safety::PathDenied> for ToolError {
+    fn from(denied: ricecoder_safety::PathDenied) -> Self {
+        ToolError::new("SENSITIVE_PATH", denied.to_string())
+            .with_details(format!("Matched pattern: {}", denied.pattern))
+            .with_suggestion("Ask the user to approve an override for this path")
+    }
+}
+
 /// Conversion from serde_json errors
 impl From<serde_json::Error> for ToolError {
     fn from(err: serde_json::Error) -> Self {
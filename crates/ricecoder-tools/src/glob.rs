@@ -6,6 +6,7 @@
 use async_trait::async_trait;
 use ::glob::Pattern;
 use ignore::WalkBuilder;
+use ricecoder_safety::{sensitive_paths, PathAccess};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -109,12 +110,17 @@ impl GlobTool {
             ));
         }
 
+        // Sensitive paths are never searched
+        let policy = sensitive_paths::global();
+        policy.check(&search_root, PathAccess::Search)?;
+
         // Build walker
         let walker = WalkBuilder::new(&search_root)
             .hidden(false) // Include hidden files
             .git_ignore(true) // Respect .gitignore
             .git_global(true)
             .git_exclude(true)
+            .filter_entry(move |entry| !policy.is_denied(entry.path(), PathAccess::Search))
             .build();
 
         // Collect matching files with mtimes
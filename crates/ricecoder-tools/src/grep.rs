@@ -7,6 +7,7 @@ use async_trait::async_trait;
 use ::glob::Pattern as GlobPattern;
 use ignore::WalkBuilder;
 use regex::Regex;
+use ricecoder_safety::{sensitive_paths, PathAccess};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -148,12 +149,17 @@ impl GrepTool {
             ));
         }
 
+        // Sensitive paths are never searched
+        let policy = sensitive_paths::global();
+        policy.check(&search_root, PathAccess::Search)?;
+
         // Build walker
         let walker = WalkBuilder::new(&search_root)
             .hidden(false) // Include hidden files
             .git_ignore(true) // Respect .gitignore
             .git_global(true)
             .git_exclude(true)
+            .filter_entry(move |entry| !policy.is_denied(entry.path(), PathAccess::Search))
             .build();
 
         // Collect matches
@@ -492,4 +498,28 @@ mod tests {
         assert_eq!(result.count, 0);
         assert!(!result.truncated);
     }
+
+    #[tokio::test]
+    async fn test_grep_skips_sensitive_paths() {
+        let dir = setup_test_dir().await;
+        std::fs::write(dir.path().join(".env"), "GREETING=Hello\n").unwrap();
+        let tool = GrepTool::new(dir.path().to_path_buf());
+        let ctx = ToolContext::default();
+
+        let input = GrepInput {
+            pattern: "Hello".to_string(),
+            path: None,
+            include: None,
+        };
+        let result = tool.search(&input, &ctx).await.unwrap();
+        assert!(result.matches.iter().all(|m| !m.file.ends_with(".env")));
+
+        let input = GrepInput {
+            pattern: "Hello".to_string(),
+            path: Some(".env".to_string()),
+            include: None,
+        };
+        let err = tool.search(&input, &ctx).await.unwrap_err();
+        assert_eq!(err.code, "SENSITIVE_PATH");
+    }
 }
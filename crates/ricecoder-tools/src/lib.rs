@@ -27,10 +27,12 @@
 //! - [`error`] - Error types with context and suggestions
 //! - [`result`] - Result types with metadata about execution
 //! - [`provider`] - Provider trait and registry for tool implementations
+//! - [`policy`] - Tool categories and invocation policy enforced by the registry
 //! - [`webfetch`] - Webfetch tool for fetching web content
 //! - [`patch`] - Patch tool for applying unified diff patches
 //! - [`todo`] - Todo tools for managing task lists
 //! - [`search`] - Web search tool for searching the web
+//! - [`search_backend`] - Pluggable web search backends (Brave, SearXNG, Kagi)
 //!
 //! # Example
 //!
@@ -67,12 +69,16 @@ pub mod list;
 pub mod locale;
 pub mod lsp;
 pub mod patch;
+pub mod policy;
 pub mod provider;
 pub mod read;
 pub mod registry;
 pub mod result;
 pub mod search;
+pub mod search_backend;
+pub mod session_env;
 pub mod todo;
+pub mod todo_sync;
 pub mod tool;
 pub mod webfetch;
 pub mod write;
@@ -90,6 +96,7 @@ pub use lsp::{
     ExternalLspClient, LspError, LspMetadata, LspOperation, LspPosition, LspTool, LspToolInput,
     LspToolOutput,
 };
+pub use policy::{PolicyDecision, ToolCategory, ToolInvocationPolicy};
 pub use provider::{Provider, ProviderRegistry};
 pub use read::{
     BatchFileReadInput, BatchFileReadOutput, ContentFilter, FileReadInput, FileReadOutput,
@@ -98,10 +105,15 @@ pub use read::{
 pub use registry::{AgentPermissions, PluginTool, ToolMetadata, ToolRegistry};
 pub use result::{FileAttachment, ResultMetadata, ToolErrorInfo, ToolResult};
 pub use search::{SearchInput, SearchOutput, SearchResult, SearchTool};
+pub use search_backend::{
+    SearchApiKey, SearchBackend, SearchBackendConfig, SearchBackendRegistry, WebSearchConfig,
+};
+pub use session_env::{ResolvedEnv, SessionEnvironment};
 pub use todo::{
-    Todo, TodoPriority, TodoStatus, TodoTools, TodoreadInput, TodoreadOutput, TodowriteInput,
-    TodowriteOutput,
+    todo_tree, Todo, TodoPriority, TodoStatus, TodoTools, TodoreadInput, TodoreadOutput,
+    TodowriteInput, TodowriteOutput,
 };
+pub use todo_sync::{sync_plan_todos, todos_from_plan, PlanSyncReport};
 pub use tool::{
     ParameterSchema, Tool, ToolDefinition, ToolExecutionResult, ToolParameters, ToolWrapper,
 };
@@ -0,0 +1,141 @@
+//! Tool invocation policy enforced at the tool-invocation boundary
+//!
+//! Tools are grouped into coarse categories (filesystem write, network, shell,
+//! MCP servers). A [`ToolInvocationPolicy`] decides per category whether an
+//! invocation is allowed, denied or needs user approval; the [`ToolRegistry`]
+//! consults it before executing any tool.
+//!
+//! [`ToolRegistry`]: crate::registry::ToolRegistry
+
+use serde::{Deserialize, Serialize};
+
+/// Coarse category of side effects a tool may have
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolCategory {
+    /// Reads files from the workspace
+    FilesystemRead,
+    /// Creates, modifies or deletes files
+    FilesystemWrite,
+    /// Accesses the network
+    Network,
+    /// Executes shell commands
+    Shell,
+    /// Provided by an external MCP server
+    Mcp,
+}
+
+impl ToolCategory {
+    /// All categories, in declaration order
+    pub const ALL: [ToolCategory; 5] = [
+        ToolCategory::FilesystemRead,
+        ToolCategory::FilesystemWrite,
+        ToolCategory::Network,
+        ToolCategory::Shell,
+        ToolCategory::Mcp,
+    ];
+
+    /// Categories of a built-in tool, `None` if the tool is not built in
+    pub fn for_builtin(tool_id: &str) -> Option<Vec<ToolCategory>> {
+        let categories = match tool_id {
+            "read" | "glob" | "grep" | "list" | "lsp" | "todoread" => {
+                vec![ToolCategory::FilesystemRead]
+            }
+            "write" | "edit" | "patch" | "todowrite" => vec![ToolCategory::FilesystemWrite],
+            "bash" => vec![ToolCategory::Shell],
+            "webfetch" | "websearch" | "search" => vec![ToolCategory::Network],
+            _ => return None,
+        };
+        Some(categories)
+    }
+
+    /// Parse a category from a permission name (e.g. `"filesystem_write"`)
+    pub fn from_permission(permission: &str) -> Option<Self> {
+        match permission {
+            "filesystem_read" | "read" => Some(ToolCategory::FilesystemRead),
+            "filesystem_write" | "write" => Some(ToolCategory::FilesystemWrite),
+            "network" => Some(ToolCategory::Network),
+            "shell" => Some(ToolCategory::Shell),
+            "mcp" => Some(ToolCategory::Mcp),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for ToolCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ToolCategory::FilesystemRead => write!(f, "filesystem_read"),
+            ToolCategory::FilesystemWrite => write!(f, "filesystem_write"),
+            ToolCategory::Network => write!(f, "network"),
+            ToolCategory::Shell => write!(f, "shell"),
+            ToolCategory::Mcp => write!(f, "mcp"),
+        }
+    }
+}
+
+/// Decision for a tool invocation
+///
+/// Variants are ordered from least to most restrictive, so the decision for a
+/// tool spanning several categories is the maximum of the per-category ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyDecision {
+    /// Invocation is allowed
+    Allow,
+    /// Invocation requires explicit user approval
+    Ask,
+    /// Invocation is denied
+    Deny,
+}
+
+/// Policy consulted before a tool is invoked
+pub trait ToolInvocationPolicy: Send + Sync {
+    /// Decide whether a tool may be invoked
+    ///
+    /// `categories` contains every category the tool belongs to.
+    fn decide(&self, tool_id: &str, categories: &[ToolCategory]) -> PolicyDecision;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_categories() {
+        assert_eq!(
+            ToolCategory::for_builtin("write"),
+            Some(vec![ToolCategory::FilesystemWrite])
+        );
+        assert_eq!(
+            ToolCategory::for_builtin("bash"),
+            Some(vec![ToolCategory::Shell])
+        );
+        assert_eq!(ToolCategory::for_builtin("custom_mcp_tool"), None);
+    }
+
+    #[test]
+    fn test_decision_ordering() {
+        assert_eq!(
+            [
+                PolicyDecision::Allow,
+                PolicyDecision::Deny,
+                PolicyDecision::Ask
+            ]
+            .into_iter()
+            .max(),
+            Some(PolicyDecision::Deny)
+        );
+    }
+
+    #[test]
+    fn test_category_from_permission() {
+        for category in ToolCategory::ALL {
+            assert_eq!(
+                ToolCategory::from_permission(&category.to_string()),
+                Some(category)
+            );
+        }
+        assert_eq!(ToolCategory::from_permission("unknown"), None);
+    }
+}
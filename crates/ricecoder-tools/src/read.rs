@@ -3,8 +3,17 @@
 //! This module provides safe file reading capabilities with content filtering,
 //! size limits, and binary file detection for enhanced security.
 
-use std::{fs, path::Path};
-
+use std::{
+    collections::hash_map::DefaultHasher,
+    fs,
+    hash::{Hash, Hasher},
+    ops::Range,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use ricecoder_files::SessionFileTracker;
+use ricecoder_safety::{sensitive_paths, PathAccess};
 use serde::{Deserialize, Serialize};
 
 use crate::error::ToolError;
@@ -38,10 +47,15 @@ pub struct FileReadInput {
     pub max_line_length: Option<usize>,
     /// Working directory for resolving relative paths
     pub working_dir: Option<String>,
-    /// Whether to block .env files (except whitelisted)
+    /// Whether to block sensitive paths such as .env files and ~/.ssh
+    /// (default: true; see `ricecoder_safety::sensitive_paths`)
     pub block_env_files: Option<bool>,
     /// Whether to return base64 attachments for images/PDFs
     pub return_attachments: Option<bool>,
+    /// Token budget for the returned content (default: None = unlimited)
+    /// Content over budget is cut down to imports, signatures and the
+    /// requested region
+    pub max_tokens: Option<usize>,
 }
 
 /// Content filtering options
@@ -147,16 +161,77 @@ impl FileReadTool {
     /// Default max line length before truncation (2000 chars, OpenCode parity)
     const DEFAULT_MAX_LINE_LENGTH: usize = 2000;
 
-    /// .env file whitelist (OpenCode parity)
-    const ENV_FILE_WHITELIST: &'static [&'static str] = &[
-        ".env.sample",
-        ".env.example",
-        ".env.template",
-        ".example",
+    /// Import lines kept when content is truncated to a token budget
+    const IMPORT_PREFIXES: &'static [&'static str] = &[
+        "use ",
+        "pub use ",
+        "extern crate ",
+        "import ",
+        "#include",
+        "require ",
+        "package ",
+    ];
+
+    /// Keywords that start a signature line kept when content is truncated
+    const SIGNATURE_KEYWORDS: &'static [&'static str] = &[
+        "fn ",
+        "struct ",
+        "enum ",
+        "trait ",
+        "impl ",
+        "impl<",
+        "mod ",
+        "type ",
+        "macro_rules!",
+        "class ",
+        "interface ",
+        "def ",
+        "function ",
+        "func ",
+    ];
+
+    /// Modifiers skipped before a signature keyword
+    const SIGNATURE_MODIFIERS: &'static [&'static str] = &[
+        "pub ",
+        "pub(crate) ",
+        "pub(super) ",
+        "async ",
+        "unsafe ",
+        "const ",
+        "extern ",
+        "export ",
+        "default ",
+        "abstract ",
+        "public ",
+        "private ",
+        "protected ",
+        "static ",
+        "final ",
     ];
 
     /// Read a single file with safety checks
     pub fn read_file(input: &FileReadInput) -> Result<FileReadOutput, ToolError> {
+        Self::read_file_tracked(input, None)
+    }
+
+    /// Read a file on behalf of a session
+    ///
+    /// When the session already has the requested lines in its context and
+    /// the file content has not changed since, an "unchanged since last
+    /// read" marker is returned instead of the content. Every read is
+    /// recorded in `tracker`.
+    pub fn read_file_in_session(
+        input: &FileReadInput,
+        session_id: &str,
+        tracker: &SessionFileTracker,
+    ) -> Result<FileReadOutput, ToolError> {
+        Self::read_file_tracked(input, Some((session_id, tracker)))
+    }
+
+    fn read_file_tracked(
+        input: &FileReadInput,
+        session: Option<(&str, &SessionFileTracker)>,
+    ) -> Result<FileReadOutput, ToolError> {
         // Validate input
         Self::validate_input(input)?;
 
@@ -181,29 +256,21 @@ impl FileReadTool {
             });
         }
 
-        // Gap 9: Block .env files with whitelist
+        // Gap 9: Block sensitive paths (.env files, ~/.ssh, configured globs)
         if input.block_env_files.unwrap_or(true) {
-            if let Some(filename) = path.file_name().and_then(|n| n.to_str()) {
-                let is_whitelisted = Self::ENV_FILE_WHITELIST.iter().any(|w| filename.ends_with(w));
-                
-                // Block .env* files except whitelisted
-                if !is_whitelisted && filename.starts_with(".env") && (filename == ".env" || filename.chars().nth(4) == Some('.')) {
-                    return Ok(FileReadOutput {
-                        success: false,
-                        content: None,
-                        file_size: 0,
-                        mime_type: None,
-                        is_binary: false,
-                        lines_read: 0,
-                        total_lines: None,
-                        error: Some(format!(
-                            "The user has blocked you from reading {}, DO NOT make further attempts to read it",
-                            input.file_path
-                        )),
-                        preview: None,
-                        attachment: None,
-                    });
-                }
+            if let Err(denied) = sensitive_paths::global().check(path, PathAccess::Read) {
+                return Ok(FileReadOutput {
+                    success: false,
+                    content: None,
+                    file_size: 0,
+                    mime_type: None,
+                    is_binary: false,
+                    lines_read: 0,
+                    total_lines: None,
+                    error: Some(denied.to_string()),
+                    preview: None,
+                    attachment: None,
+                });
             }
         }
 
@@ -318,6 +385,10 @@ impl FileReadTool {
         })?;
 
         // Determine line range using offset/limit or start_line/end_line (legacy)
+        let explicit_range = input.offset.is_some()
+            || input.limit.is_some()
+            || input.start_line.is_some()
+            || input.end_line.is_some();
         let (start_idx, line_count) = if input.offset.is_some() || input.limit.is_some() {
             // offset is 0-based, limit is count
             let offset = input.offset.unwrap_or(0);
@@ -334,13 +405,76 @@ impl FileReadTool {
             (0, Some(Self::DEFAULT_LINE_LIMIT))
         };
 
+        // Answer repeated reads of unchanged content with a marker
+        let total = content.lines().count();
+        let requested = start_idx.min(total)
+            ..line_count.map_or(total, |count| start_idx.saturating_add(count).min(total));
+        let content_hash = Self::hash_content(&content);
+        let tracked_path = PathBuf::from(&file_path);
+        let mtime = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+        if let Some((session_id, tracker)) = session {
+            let last_read = tracker
+                .last_read(session_id, &tracked_path)
+                .map_err(Self::session_error)?;
+            if !requested.is_empty()
+                && last_read.is_some_and(|record| record.covers(content_hash, &requested))
+            {
+                return Ok(FileReadOutput {
+                    success: true,
+                    content: Some(format!(
+                        "<file>\n(Unchanged since last read in this session: lines {}-{} are already in context)\n</file>",
+                        requested.start + 1,
+                        requested.end
+                    )),
+                    file_size,
+                    mime_type,
+                    is_binary: false,
+                    lines_read: 0,
+                    total_lines: Some(total),
+                    error: None,
+                    preview: None,
+                    attachment: None,
+                });
+            }
+        }
+
         // Apply line filtering with truncation
         let max_line_len = input.max_line_length.unwrap_or(Self::DEFAULT_MAX_LINE_LENGTH);
         let use_line_numbers = input.line_numbers.unwrap_or(true); // Default true for OpenCode parity
 
-        let (filtered_content, lines_read, total_lines_val) =
+        let (mut filtered_content, mut lines_read, total_lines_val) =
             Self::filter_lines_with_options(&content, start_idx, line_count, max_line_len, use_line_numbers)?;
 
+        // Cut content over the token budget down to its structure
+        let budget = input
+            .max_tokens
+            .filter(|budget| Self::estimate_tokens(&filtered_content) > *budget);
+        if let Some(budget) = budget {
+            let region = explicit_range.then(|| requested.clone());
+            (filtered_content, lines_read) = Self::truncate_to_structure(
+                &content,
+                region,
+                budget,
+                max_line_len,
+                use_line_numbers,
+            );
+        }
+
+        if let Some((session_id, tracker)) = session {
+            let recorded = if budget.is_none() && lines_read > 0 {
+                tracker.record_context_read(
+                    session_id,
+                    &tracked_path,
+                    mtime,
+                    content_hash,
+                    start_idx..start_idx + lines_read,
+                )
+            } else {
+                tracker.record_read(session_id, &tracked_path, mtime)
+            };
+            recorded.map_err(Self::session_error)?;
+        }
+
         // Gap 6: Add OpenCode-style footer
         let mut output = String::from("<file>\n");
         output.push_str(&filtered_content);
@@ -348,7 +482,12 @@ impl FileReadTool {
         let last_read_line = start_idx + lines_read;
         let has_more_lines = total_lines_val.map(|t| t > last_read_line).unwrap_or(false);
         
-        if has_more_lines {
+        if let Some(budget) = budget {
+            output.push_str(&format!(
+                "\n\n(File truncated to a budget of {} tokens. Imports, signatures and the requested lines were kept; use 'offset' and 'limit' to read omitted lines)",
+                budget
+            ));
+        } else if has_more_lines {
             output.push_str(&format!("\n\n(File has more lines. Use 'offset' parameter to read beyond line {})", last_read_line));
         } else if let Some(total) = total_lines_val {
             output.push_str(&format!("\n\n(End of file - total {} lines)", total));
@@ -620,6 +759,143 @@ impl FileReadTool {
             })
     }
 
+    /// Estimate tokens as one per four bytes, like the providers' fallback
+    fn estimate_tokens(text: &str) -> usize {
+        text.len().div_ceil(4)
+    }
+
+    /// Hash file content to detect changes between reads
+    fn hash_content(content: &str) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        content.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn session_error(error: ricecoder_files::FileError) -> ToolError {
+        ToolError::new("INTERNAL_ERROR", "Failed to track file read")
+            .with_details(error.to_string())
+    }
+
+    /// Whether a line imports another module or file
+    fn is_import_line(line: &str) -> bool {
+        let line = line.trim_start();
+        Self::IMPORT_PREFIXES
+            .iter()
+            .any(|prefix| line.starts_with(prefix))
+            || (line.starts_with("from ") && line.contains(" import "))
+            || line.contains("= require(")
+    }
+
+    /// Whether a line starts a function, type or module definition
+    fn is_signature_line(line: &str) -> bool {
+        let mut line = line.trim_start();
+        while let Some(rest) = Self::SIGNATURE_MODIFIERS
+            .iter()
+            .find_map(|modifier| line.strip_prefix(modifier))
+        {
+            line = rest;
+        }
+        Self::SIGNATURE_KEYWORDS
+            .iter()
+            .any(|keyword| line.starts_with(keyword))
+    }
+
+    /// Truncate content to a token budget while keeping its structure
+    ///
+    /// Lines of the requested region are kept first, then imports, then
+    /// signatures. Without a requested region, the remaining budget is
+    /// filled from the top of the file. Runs of dropped lines are replaced
+    /// by an omission marker.
+    ///
+    /// # Returns
+    /// Tuple of (formatted_content, lines_kept)
+    fn truncate_to_structure(
+        content: &str,
+        region: Option<Range<usize>>,
+        budget: usize,
+        max_line_length: usize,
+        line_numbers: bool,
+    ) -> (String, usize) {
+        let lines: Vec<String> = content
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| Self::format_line(idx, line, max_line_length, line_numbers))
+            .collect();
+        let mut keep = vec![false; lines.len()];
+        let mut used = 0;
+        let mut try_keep = |idx: usize, keep: &mut Vec<bool>| {
+            if keep[idx] {
+                return true;
+            }
+            let cost = Self::estimate_tokens(&lines[idx]) + 1;
+            if used + cost > budget {
+                return false;
+            }
+            keep[idx] = true;
+            used += cost;
+            true
+        };
+
+        if let Some(region) = &region {
+            for idx in region.clone() {
+                if !try_keep(idx, &mut keep) {
+                    break;
+                }
+            }
+        }
+        let raw: Vec<&str> = content.lines().collect();
+        for idx in (0..raw.len()).filter(|idx| Self::is_import_line(raw[*idx])) {
+            try_keep(idx, &mut keep);
+        }
+        for idx in (0..raw.len()).filter(|idx| Self::is_signature_line(raw[*idx])) {
+            try_keep(idx, &mut keep);
+        }
+        if region.is_none() {
+            for idx in 0..raw.len() {
+                if !try_keep(idx, &mut keep) {
+                    break;
+                }
+            }
+        }
+
+        let mut output = Vec::new();
+        let mut omitted = 0;
+        for (line, kept) in lines.iter().zip(&keep) {
+            if *kept {
+                if omitted > 0 {
+                    output.push(format!("... ({} lines omitted)", omitted));
+                    omitted = 0;
+                }
+                output.push(line.clone());
+            } else {
+                omitted += 1;
+            }
+        }
+        if omitted > 0 {
+            output.push(format!("... ({} lines omitted)", omitted));
+        }
+
+        let kept = keep.iter().filter(|kept| **kept).count();
+        (output.join("\n"), kept)
+    }
+
+    /// Format one line for output, truncating it and adding its line number
+    fn format_line(idx: usize, line: &str, max_line_length: usize, line_numbers: bool) -> String {
+        // Gap 7: Truncate line if too long (use "..." marker for OpenCode parity)
+        let truncated_line = if line.len() > max_line_length {
+            format!("{}...", &line[..max_line_length])
+        } else {
+            line.to_string()
+        };
+
+        // Format with line numbers if requested (OpenCode style: 00001| format)
+        if line_numbers {
+            format!("{:05}| {}", idx + 1, truncated_line)
+        } else {
+            truncated_line
+        }
+    }
+
     /// Filter content to specific line range (legacy method for backward compatibility)
     fn filter_lines(
         content: &str,
@@ -681,23 +957,7 @@ impl FileReadTool {
         let mut output_lines = Vec::new();
 
         for (idx, line) in lines.iter().enumerate().skip(offset).take(end_idx - offset) {
-            let line_num = idx + 1; // 1-indexed line number
-
-            // Gap 7: Truncate line if too long (use "..." marker for OpenCode parity)
-            let truncated_line = if line.len() > max_line_length {
-                format!("{}...", &line[..max_line_length])
-            } else {
-                line.to_string()
-            };
-
-            // Format with line numbers if requested (OpenCode style: 00001| format)
-            let formatted_line = if line_numbers {
-                format!("{:05}| {}", line_num, truncated_line)
-            } else {
-                truncated_line
-            };
-
-            output_lines.push(formatted_line);
+            output_lines.push(Self::format_line(idx, line, max_line_length, line_numbers));
         }
 
         let lines_read = output_lines.len();
@@ -736,6 +996,7 @@ mod tests {
             working_dir: None,
             block_env_files: Some(false),
             return_attachments: Some(false),
+            max_tokens: None,
         };
 
         let result = FileReadTool::read_file(&input).unwrap();
@@ -767,6 +1028,7 @@ mod tests {
             working_dir: None,
             block_env_files: Some(false),
             return_attachments: Some(false),
+            max_tokens: None,
         };
 
         let result = FileReadTool::read_file(&input).unwrap();
@@ -793,6 +1055,7 @@ mod tests {
             working_dir: None,
             block_env_files: Some(false),
             return_attachments: Some(false),
+            max_tokens: None,
         };
 
         let result = FileReadTool::read_file(&input).unwrap();
@@ -823,6 +1086,7 @@ mod tests {
             working_dir: None,
             block_env_files: Some(false),
             return_attachments: Some(false),
+            max_tokens: None,
         };
 
         let result = FileReadTool::read_file(&input).unwrap();
@@ -856,6 +1120,7 @@ mod tests {
             working_dir: None,
             block_env_files: Some(false),
             return_attachments: Some(false),
+            max_tokens: None,
         };
 
         let result = FileReadTool::read_file(&input).unwrap();
@@ -884,6 +1149,7 @@ mod tests {
             working_dir: None,
             block_env_files: Some(false),
             return_attachments: Some(false),
+            max_tokens: None,
         };
 
         let result = FileReadTool::read_file(&input).unwrap();
@@ -911,6 +1177,7 @@ mod tests {
             working_dir: None,
             block_env_files: Some(false),
             return_attachments: Some(false),
+            max_tokens: None,
         };
 
         let result = FileReadTool::read_file(&input).unwrap();
@@ -942,6 +1209,7 @@ mod tests {
             working_dir: None,
             block_env_files: Some(true),
             return_attachments: Some(false),
+            max_tokens: None,
         };
 
         let result = FileReadTool::read_file(&input).unwrap();
@@ -969,6 +1237,7 @@ mod tests {
             working_dir: None,
             block_env_files: Some(true),
             return_attachments: Some(false),
+            max_tokens: None,
         };
 
         let result = FileReadTool::read_file(&input).unwrap();
@@ -995,6 +1264,7 @@ mod tests {
             working_dir: None,
             block_env_files: Some(false),
             return_attachments: Some(false),
+            max_tokens: None,
         };
 
         let result = FileReadTool::read_file(&input).unwrap();
@@ -1026,9 +1296,121 @@ mod tests {
             working_dir: Some(temp_dir.path().to_string_lossy().to_string()),
             block_env_files: Some(false),
             return_attachments: Some(false),
+            max_tokens: None,
         };
 
         let result = FileReadTool::read_file(&input).unwrap();
         assert!(result.success);
     }
+
+    fn input_for(file_path: &Path) -> FileReadInput {
+        FileReadInput {
+            file_path: file_path.to_string_lossy().to_string(),
+            start_line: None,
+            end_line: None,
+            offset: None,
+            limit: None,
+            max_size_bytes: None,
+            detect_binary: None,
+            content_filter: None,
+            line_numbers: Some(false),
+            max_line_length: None,
+            working_dir: None,
+            block_env_files: Some(false),
+            return_attachments: Some(false),
+            max_tokens: None,
+        }
+    }
+
+    #[test]
+    fn test_session_read_unchanged_marker() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("lib.rs");
+        fs::write(&file_path, "line 1\nline 2\nline 3\nline 4").unwrap();
+        let tracker = SessionFileTracker::new();
+        let mut input = input_for(&file_path);
+
+        let first = FileReadTool::read_file_in_session(&input, "s1", &tracker).unwrap();
+        assert_eq!(first.lines_read, 4);
+
+        // A sub-range of what was already returned is not sent again
+        input.offset = Some(1);
+        input.limit = Some(2);
+        let second = FileReadTool::read_file_in_session(&input, "s1", &tracker).unwrap();
+        assert!(second.success);
+        assert_eq!(second.lines_read, 0);
+        assert!(second
+            .content
+            .unwrap()
+            .contains("Unchanged since last read"));
+
+        // Other sessions and changed content get the full text
+        let other = FileReadTool::read_file_in_session(&input, "s2", &tracker).unwrap();
+        assert_eq!(other.lines_read, 2);
+        fs::write(&file_path, "line 1\nchanged\nline 3\nline 4").unwrap();
+        let changed = FileReadTool::read_file_in_session(&input, "s1", &tracker).unwrap();
+        assert_eq!(changed.lines_read, 2);
+        assert!(changed.content.unwrap().contains("changed"));
+
+        // After the context is compacted the content is sent again
+        tracker.forget_context("s1").unwrap();
+        let forgotten = FileReadTool::read_file_in_session(&input, "s1", &tracker).unwrap();
+        assert_eq!(forgotten.lines_read, 2);
+    }
+
+    #[test]
+    fn test_token_budget_keeps_structure() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("lib.rs");
+        let mut content = String::from("use std::fs;\nuse std::path::Path;\n\n");
+        for i in 0..50 {
+            content.push_str(&format!(
+                "pub fn function_{}() {{\n    let value = {};\n    println!(\"{{}}\", value);\n}}\n",
+                i, i
+            ));
+        }
+        fs::write(&file_path, &content).unwrap();
+
+        let mut input = input_for(&file_path);
+        input.max_tokens = Some(1000);
+        let full = FileReadTool::read_file(&input).unwrap();
+        assert!(!full.content.unwrap().contains("truncated"));
+
+        // Without a region: imports, every signature, then the top of the file
+        input.max_tokens = Some(500);
+        let result = FileReadTool::read_file(&input).unwrap();
+        let output = result.content.unwrap();
+        assert!(output.contains("use std::path::Path;"));
+        assert!(output.contains("pub fn function_49()"));
+        assert!(output.contains("let value = 0;"));
+        assert!(!output.contains("let value = 49;"));
+        assert!(output.contains("lines omitted"));
+        assert!(output.contains("truncated to a budget of 500 tokens"));
+
+        // The requested region comes first
+        input.max_tokens = Some(150);
+        input.offset = Some(103);
+        input.limit = Some(40);
+        let result = FileReadTool::read_file(&input).unwrap();
+        let output = result.content.unwrap();
+        assert!(output.contains("pub fn function_25()"));
+        assert!(output.contains("let value = 25;"));
+        assert!(!output.contains("let value = 24;"));
+        assert!(output.contains("truncated to a budget of 150 tokens"));
+    }
+
+    #[test]
+    fn test_signature_and_import_detection() {
+        assert!(FileReadTool::is_signature_line(
+            "    pub(crate) async fn run() {"
+        ));
+        assert!(FileReadTool::is_signature_line(
+            "export default class App {"
+        ));
+        assert!(FileReadTool::is_signature_line("def main():"));
+        assert!(!FileReadTool::is_signature_line("    let fn_name = 1;"));
+        assert!(FileReadTool::is_import_line("from os import path"));
+        assert!(FileReadTool::is_import_line("const fs = require('fs');"));
+        assert!(!FileReadTool::is_import_line("from here on"));
+    }
 }
@@ -8,6 +8,7 @@
 //! - Agent permission integration via enabled(agent)
 //! - Plugin-to-tool conversion via from_plugin()
 //! - Surface methods: ids(), tools(), all()
+//! - Tool invocation policy enforcement via invoke()
 
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
@@ -17,9 +18,11 @@ use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 use tracing::{debug, warn};
 
+use crate::context::ToolContext;
 use crate::error::ToolError;
+use crate::policy::{PolicyDecision, ToolCategory, ToolInvocationPolicy};
 use crate::provider::{Provider, ProviderRegistry};
-use crate::tool::{Tool, ToolDefinition, ToolWrapper};
+use crate::tool::{Tool, ToolDefinition, ToolExecutionResult, ToolWrapper};
 
 /// Tool metadata for registry operations
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -66,6 +69,8 @@ pub struct ToolRegistry {
     provider_filter: Arc<RwLock<Option<HashSet<String>>>>,
     /// Registered tool wrappers
     tools: Arc<RwLock<HashMap<String, Arc<dyn Tool>>>>,
+    /// Policy consulted before invoking tools (None = all invocations allowed)
+    invocation_policy: Arc<RwLock<Option<Arc<dyn ToolInvocationPolicy>>>>,
 }
 
 impl ToolRegistry {
@@ -76,6 +81,7 @@ impl ToolRegistry {
             tool_metadata: Arc::new(RwLock::new(HashMap::new())),
             provider_filter: Arc::new(RwLock::new(None)),
             tools: Arc::new(RwLock::new(HashMap::new())),
+            invocation_policy: Arc::new(RwLock::new(None)),
         }
     }
 
@@ -269,6 +275,132 @@ impl ToolRegistry {
     pub async fn get_metadata(&self, tool_id: &str) -> Option<ToolMetadata> {
         self.tool_metadata.read().await.get(tool_id).cloned()
     }
+
+    /// Set the policy consulted before invoking tools
+    ///
+    /// Pass None to allow all invocations.
+    pub async fn set_invocation_policy(&self, policy: Option<Arc<dyn ToolInvocationPolicy>>) {
+        debug!("Setting tool invocation policy (enabled={})", policy.is_some());
+        *self.invocation_policy.write().await = policy;
+    }
+
+    /// Get the categories of a tool
+    ///
+    /// Categories come from the built-in tool table and the permissions declared
+    /// in the tool metadata. Tools from external providers (MCP, plugins) are
+    /// always in the MCP category; if they declare nothing else they are assumed
+    /// to write files, access the network and run commands.
+    pub async fn categories(&self, tool_id: &str) -> Vec<ToolCategory> {
+        let metadata = self.tool_metadata.read().await.get(tool_id).cloned();
+
+        let mut categories: Vec<ToolCategory> =
+            ToolCategory::for_builtin(tool_id).unwrap_or_default();
+        let external = match &metadata {
+            Some(meta) => {
+                categories.extend(
+                    meta.required_permissions
+                        .iter()
+                        .filter_map(|p| ToolCategory::from_permission(p)),
+                );
+                meta.provider_id != "builtin"
+            }
+            None => categories.is_empty(),
+        };
+
+        if external {
+            if categories.iter().all(|c| *c == ToolCategory::Mcp) {
+                categories.extend([
+                    ToolCategory::FilesystemWrite,
+                    ToolCategory::Network,
+                    ToolCategory::Shell,
+                ]);
+            }
+            categories.push(ToolCategory::Mcp);
+        }
+
+        categories.sort();
+        categories.dedup();
+        categories
+    }
+
+    /// Check the invocation policy for a tool
+    pub async fn check_policy(&self, tool_id: &str) -> PolicyDecision {
+        let policy = self.invocation_policy.read().await.clone();
+        match policy {
+            Some(policy) => policy.decide(tool_id, &self.categories(tool_id).await),
+            None => PolicyDecision::Allow,
+        }
+    }
+
+    /// Invoke a tool, enforcing the invocation policy
+    ///
+    /// Tools whose policy decision is [`PolicyDecision::Ask`] fail with
+    /// `APPROVAL_REQUIRED`; once the user approved, call [`Self::invoke_approved`].
+    pub async fn invoke(
+        &self,
+        tool_id: &str,
+        args: HashMap<String, serde_json::Value>,
+        ctx: &ToolContext,
+    ) -> Result<ToolExecutionResult, ToolError> {
+        self.invoke_with_approval(tool_id, args, ctx, false).await
+    }
+
+    /// Invoke a tool the user explicitly approved
+    ///
+    /// Approval only lifts [`PolicyDecision::Ask`]; denied tools stay denied.
+    pub async fn invoke_approved(
+        &self,
+        tool_id: &str,
+        args: HashMap<String, serde_json::Value>,
+        ctx: &ToolContext,
+    ) -> Result<ToolExecutionResult, ToolError> {
+        self.invoke_with_approval(tool_id, args, ctx, true).await
+    }
+
+    async fn invoke_with_approval(
+        &self,
+        tool_id: &str,
+        args: HashMap<String, serde_json::Value>,
+        ctx: &ToolContext,
+        approved: bool,
+    ) -> Result<ToolExecutionResult, ToolError> {
+        let tool = self.get_tool(tool_id).await.ok_or_else(|| {
+            ToolError::new("TOOL_NOT_FOUND", format!("Tool '{}' is not registered", tool_id))
+        })?;
+
+        match self.check_policy(tool_id).await {
+            PolicyDecision::Allow => {}
+            PolicyDecision::Ask if approved => {}
+            decision => {
+                let categories = self
+                    .categories(tool_id)
+                    .await
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                warn!(tool_id = %tool_id, ?decision, "Tool invocation blocked by policy");
+
+                return Err(if decision == PolicyDecision::Deny {
+                    ToolError::new(
+                        "POLICY_DENIED",
+                        format!("Tool '{}' is not allowed by the active tool policy", tool_id),
+                    )
+                    .with_details(format!("categories: {}", categories))
+                    .with_suggestion("Switch to a mode that allows this tool")
+                } else {
+                    ToolError::new(
+                        "APPROVAL_REQUIRED",
+                        format!("Tool '{}' requires approval before it can run", tool_id),
+                    )
+                    .with_details(format!("categories: {}", categories))
+                    .with_suggestion("Ask the user to approve the invocation")
+                });
+            }
+        }
+
+        ToolWrapper::new(tool).execute_with_validation(args, ctx).await
+    }
 }
 
 /// Plugin tool adapter that implements Tool trait
@@ -531,4 +663,107 @@ mod tests {
         let all = registry.all().await;
         assert_eq!(all.len(), 3);
     }
+
+    struct DenyWritesPolicy;
+
+    impl ToolInvocationPolicy for DenyWritesPolicy {
+        fn decide(&self, _tool_id: &str, categories: &[ToolCategory]) -> PolicyDecision {
+            if categories.contains(&ToolCategory::FilesystemWrite) {
+                PolicyDecision::Deny
+            } else if categories.contains(&ToolCategory::Shell) {
+                PolicyDecision::Ask
+            } else {
+                PolicyDecision::Allow
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_categories() {
+        let registry = ToolRegistry::new(Arc::new(ProviderRegistry::new()));
+
+        assert_eq!(registry.categories("write").await, vec![ToolCategory::FilesystemWrite]);
+
+        // External tools without declared permissions are treated conservatively
+        registry
+            .from_plugin(PluginTool {
+                name: "mcp_tool".to_string(),
+                description: "MCP tool".to_string(),
+                input_schema: serde_json::json!({}),
+                provider: Some("mcp".to_string()),
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            registry.categories("mcp_tool").await,
+            vec![
+                ToolCategory::FilesystemWrite,
+                ToolCategory::Network,
+                ToolCategory::Shell,
+                ToolCategory::Mcp,
+            ]
+        );
+
+        let metadata = ToolMetadata {
+            id: "mcp_reader".to_string(),
+            description: "Read-only MCP tool".to_string(),
+            provider_id: "mcp".to_string(),
+            enabled: true,
+            required_permissions: vec!["filesystem_read".to_string()],
+        };
+        registry
+            .register_tool(Arc::new(MockTool { id: "mcp_reader".to_string() }), metadata)
+            .await
+            .unwrap();
+        assert_eq!(
+            registry.categories("mcp_reader").await,
+            vec![ToolCategory::FilesystemRead, ToolCategory::Mcp]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_invoke_enforces_policy() {
+        let registry = ToolRegistry::new(Arc::new(ProviderRegistry::new()));
+        for id in ["read", "write", "bash"] {
+            let metadata = ToolMetadata {
+                id: id.to_string(),
+                description: "Test tool".to_string(),
+                provider_id: "builtin".to_string(),
+                enabled: true,
+                required_permissions: Vec::new(),
+            };
+            registry
+                .register_tool(Arc::new(MockTool { id: id.to_string() }), metadata)
+                .await
+                .unwrap();
+        }
+        let ctx = ToolContext::default();
+
+        // Without a policy every tool is allowed
+        assert!(registry.invoke("write", HashMap::new(), &ctx).await.is_ok());
+
+        registry
+            .set_invocation_policy(Some(Arc::new(DenyWritesPolicy)))
+            .await;
+
+        assert!(registry.invoke("read", HashMap::new(), &ctx).await.is_ok());
+
+        let err = registry.invoke("write", HashMap::new(), &ctx).await.unwrap_err();
+        assert_eq!(err.code, "POLICY_DENIED");
+        let err = registry
+            .invoke_approved("write", HashMap::new(), &ctx)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, "POLICY_DENIED");
+
+        let err = registry.invoke("bash", HashMap::new(), &ctx).await.unwrap_err();
+        assert_eq!(err.code, "APPROVAL_REQUIRED");
+        assert!(registry
+            .invoke_approved("bash", HashMap::new(), &ctx)
+            .await
+            .is_ok());
+
+        let err = registry.invoke("missing", HashMap::new(), &ctx).await.unwrap_err();
+        assert_eq!(err.code, "TOOL_NOT_FOUND");
+    }
 }
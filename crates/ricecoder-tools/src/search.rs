@@ -2,15 +2,16 @@
 //!
 //! Provides functionality to search the web using Exa AI, free APIs, or local search engines via MCP.
 //! Implements query validation, injection prevention, and pagination support.
+//! Configured [`SearchBackendRegistry`] backends take precedence over the built-in provider.
 
-use std::time::Instant;
+use std::{sync::Arc, time::Instant};
 
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use tokio::time::timeout;
 use tracing::{debug, info, warn};
 
-use crate::{error::ToolError, result::ToolResult};
+use crate::{error::ToolError, result::ToolResult, search_backend::SearchBackendRegistry};
 
 // GAP-3: Permission check integration (placeholder for now)
 // TODO: Integrate with ricecoder-permissions crate when available
@@ -210,6 +211,7 @@ pub struct SearchTool {
     http_client: reqwest::Client,
     provider: SearchProvider,
     mcp_available: bool,
+    backends: Option<Arc<SearchBackendRegistry>>,
 }
 
 impl SearchTool {
@@ -224,6 +226,7 @@ impl SearchTool {
             http_client,
             provider: SearchProvider::default(),
             mcp_available: false,
+            backends: None,
         }
     }
 
@@ -238,6 +241,7 @@ impl SearchTool {
             http_client,
             provider: SearchProvider::ExaAI { api_key: api_key.into() },
             mcp_available: false,
+            backends: None,
         }
     }
 
@@ -252,6 +256,7 @@ impl SearchTool {
             http_client,
             provider,
             mcp_available: false,
+            backends: None,
         }
     }
 
@@ -266,6 +271,7 @@ impl SearchTool {
             http_client,
             provider: SearchProvider::MCP,
             mcp_available,
+            backends: None,
         }
     }
 
@@ -275,6 +281,12 @@ impl SearchTool {
         self
     }
 
+    /// Search with configured backends instead of the built-in provider
+    pub fn with_backends(mut self, backends: Arc<SearchBackendRegistry>) -> Self {
+        self.backends = Some(backends);
+        self
+    }
+
     /// Validate search query for injection attacks and format
     pub fn validate_query(query: &str) -> Result<(), ToolError> {
         // Check for empty query
@@ -392,6 +404,12 @@ impl SearchTool {
 
     /// Internal search execution - routes to appropriate provider
     async fn execute_search(&self, input: &SearchInput) -> Result<SearchOutput, ToolError> {
+        if let Some(backends) = self.backends.as_ref().filter(|b| !b.is_empty()) {
+            let (output, backend) = backends.search(&self.http_client, input).await?;
+            debug!("Search served by backend {}", backend);
+            return Ok(output);
+        }
+
         match &self.provider {
             SearchProvider::ExaAI { api_key } => {
                 self.search_exa_ai(input, api_key).await
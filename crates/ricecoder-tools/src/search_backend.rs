@@ -0,0 +1,930 @@
+//! Pluggable web search backends
+//!
+//! The search tool can delegate to configured backends (Brave, a self-hosted
+//! SearXNG instance or Kagi) instead of its built-in provider. Backends are
+//! listed under the `web_search` key of the custom config section:
+//!
+//! ```json
+//! {
+//!   "web_search": {
+//!     "default_backend": "brave",
+//!     "backends": [
+//!       { "type": "brave", "api_key": { "env": "BRAVE_API_KEY" } },
+//!       { "type": "searxng", "base_url": "http://localhost:8888" }
+//!     ]
+//!   }
+//! }
+//! ```
+//!
+//! API keys are encrypted with ricecoder-security as soon as a backend is
+//! built and only decrypted for the request. Each backend has its own rate
+//! limit. Results are normalized into [`SearchResult`]s, and every search
+//! updates a per-backend quality score; the best scoring backend is tried
+//! first unless a default is configured, and the others serve as fallbacks.
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use ricecoder_security::{EncryptedData, KeyManager};
+use ricecoder_storage::Config;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+use crate::{
+    error::ToolError,
+    search::{SearchInput, SearchOutput, SearchResult},
+};
+
+/// Brave Search web endpoint
+const BRAVE_ENDPOINT: &str = "https://api.search.brave.com/res/v1/web/search";
+
+/// Kagi Search endpoint
+const KAGI_ENDPOINT: &str = "https://kagi.com/api/v0/search";
+
+/// Most results Brave returns for one request
+const BRAVE_MAX_COUNT: usize = 20;
+
+/// Brave's free plan allows one request per second
+const BRAVE_REQUESTS_PER_MINUTE: u32 = 60;
+
+/// Kagi does not publish a limit; stay well below abusive rates
+const KAGI_REQUESTS_PER_MINUTE: u32 = 30;
+
+/// Score of a backend that has not been used yet
+const NEUTRAL_SCORE: f64 = 0.5;
+
+/// A result as returned by a backend, before normalization
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RawSearchHit {
+    pub title: Option<String>,
+    pub url: String,
+    pub snippet: Option<String>,
+}
+
+/// A web search backend
+#[async_trait]
+pub trait SearchBackend: Send + Sync {
+    /// Backend identifier, e.g. `brave`
+    fn id(&self) -> &str;
+
+    /// Fetch at least `offset + limit` results for the input when available
+    async fn search(
+        &self,
+        client: &reqwest::Client,
+        input: &SearchInput,
+    ) -> Result<Vec<RawSearchHit>, ToolError>;
+}
+
+/// Where a backend's API key comes from
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchApiKey {
+    /// Read from an environment variable when the backend is built
+    Env(String),
+    /// Encrypted with the configured key manager
+    Encrypted(EncryptedData),
+}
+
+impl SearchApiKey {
+    /// Encrypt a plain key for storing in the config
+    pub fn encrypt(key_manager: &KeyManager, api_key: &str) -> Result<Self, ToolError> {
+        key_manager
+            .encrypt_api_key(api_key)
+            .map(SearchApiKey::Encrypted)
+            .map_err(secret_error)
+    }
+}
+
+impl fmt::Debug for SearchApiKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SearchApiKey::Env(name) => f.debug_tuple("Env").field(name).finish(),
+            SearchApiKey::Encrypted(_) => f.write_str("Encrypted(..)"),
+        }
+    }
+}
+
+/// An API key held encrypted until a request needs it
+struct BackendKey {
+    encrypted: EncryptedData,
+    key_manager: Arc<KeyManager>,
+}
+
+impl BackendKey {
+    /// Keys read from the environment are encrypted with a throwaway key
+    /// manager when none is configured; stored keys need the real one.
+    fn resolve(
+        backend: &str,
+        key: &SearchApiKey,
+        key_manager: Option<&Arc<KeyManager>>,
+    ) -> Result<Self, ToolError> {
+        let (encrypted, key_manager) = match key {
+            SearchApiKey::Encrypted(encrypted) => {
+                let key_manager = key_manager.cloned().ok_or_else(|| {
+                    ToolError::new(
+                        "CONFIG_ERROR",
+                        format!(
+                            "Search backend {} needs a key manager for its API key",
+                            backend
+                        ),
+                    )
+                    .with_suggestion(
+                        "Configure ricecoder-security or read the key from the environment",
+                    )
+                })?;
+                (encrypted.clone(), key_manager)
+            }
+            SearchApiKey::Env(name) => {
+                let value = std::env::var(name).map_err(|_| {
+                    ToolError::new(
+                        "CONFIG_ERROR",
+                        format!("API key for search backend {} is not set", backend),
+                    )
+                    .with_details(format!("Environment variable {} is missing", name))
+                    .with_suggestion(format!("Export {} or store an encrypted key", name))
+                })?;
+                let key_manager = match key_manager {
+                    Some(key_manager) => key_manager.clone(),
+                    None => Arc::new(
+                        KeyManager::new(&uuid::Uuid::new_v4().to_string()).map_err(secret_error)?,
+                    ),
+                };
+                let encrypted = key_manager.encrypt_api_key(&value).map_err(secret_error)?;
+                (encrypted, key_manager)
+            }
+        };
+        Ok(Self {
+            encrypted,
+            key_manager,
+        })
+    }
+
+    fn reveal(&self) -> Result<String, ToolError> {
+        self.key_manager
+            .decrypt_api_key(&self.encrypted)
+            .map_err(|e| {
+                ToolError::new("SECRET_ERROR", "Failed to decrypt search API key")
+                    .with_details(e.to_string())
+            })
+    }
+}
+
+fn secret_error(error: ricecoder_security::SecurityError) -> ToolError {
+    ToolError::new("SECRET_ERROR", "Failed to encrypt search API key")
+        .with_details(error.to_string())
+}
+
+/// Configuration of one search backend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum SearchBackendConfig {
+    Brave {
+        api_key: SearchApiKey,
+        #[serde(default)]
+        requests_per_minute: Option<u32>,
+    },
+    Searxng {
+        base_url: String,
+        #[serde(default)]
+        requests_per_minute: Option<u32>,
+    },
+    Kagi {
+        api_key: SearchApiKey,
+        #[serde(default)]
+        requests_per_minute: Option<u32>,
+    },
+}
+
+impl SearchBackendConfig {
+    /// Identifier of the backend this config builds
+    pub fn id(&self) -> &'static str {
+        match self {
+            SearchBackendConfig::Brave { .. } => "brave",
+            SearchBackendConfig::Searxng { .. } => "searxng",
+            SearchBackendConfig::Kagi { .. } => "kagi",
+        }
+    }
+
+    /// Rate limit of the backend, `None` for unlimited
+    pub fn requests_per_minute(&self) -> Option<u32> {
+        match self {
+            SearchBackendConfig::Brave {
+                requests_per_minute,
+                ..
+            } => requests_per_minute.or(Some(BRAVE_REQUESTS_PER_MINUTE)),
+            SearchBackendConfig::Searxng {
+                requests_per_minute,
+                ..
+            } => *requests_per_minute,
+            SearchBackendConfig::Kagi {
+                requests_per_minute,
+                ..
+            } => requests_per_minute.or(Some(KAGI_REQUESTS_PER_MINUTE)),
+        }
+    }
+
+    /// Build the backend, keeping its API key encrypted
+    pub fn build(
+        &self,
+        key_manager: Option<&Arc<KeyManager>>,
+    ) -> Result<Arc<dyn SearchBackend>, ToolError> {
+        let backend: Arc<dyn SearchBackend> = match self {
+            SearchBackendConfig::Brave { api_key, .. } => Arc::new(BraveBackend {
+                key: BackendKey::resolve(self.id(), api_key, key_manager)?,
+            }),
+            SearchBackendConfig::Searxng { base_url, .. } => {
+                let url = url::Url::parse(base_url).map_err(|e| {
+                    ToolError::new("CONFIG_ERROR", "Invalid SearXNG base URL")
+                        .with_details(format!("{}: {}", base_url, e))
+                })?;
+                Arc::new(SearxngBackend {
+                    base_url: url.as_str().trim_end_matches('/').to_string(),
+                })
+            }
+            SearchBackendConfig::Kagi { api_key, .. } => Arc::new(KagiBackend {
+                key: BackendKey::resolve(self.id(), api_key, key_manager)?,
+            }),
+        };
+        Ok(backend)
+    }
+}
+
+/// Search backends configured for the search tool
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WebSearchConfig {
+    #[serde(default)]
+    pub backends: Vec<SearchBackendConfig>,
+    /// Backend to try first; the best scoring one when unset
+    #[serde(default)]
+    pub default_backend: Option<String>,
+}
+
+impl WebSearchConfig {
+    /// Key of the section in [`Config::custom`]
+    pub const CONFIG_KEY: &'static str = "web_search";
+
+    /// Read the section from the RiceCoder config, if present
+    pub fn from_config(config: &Config) -> Result<Option<Self>, ToolError> {
+        config
+            .custom
+            .get(Self::CONFIG_KEY)
+            .map(|value| {
+                serde_json::from_value(value.clone()).map_err(|e| {
+                    ToolError::new("CONFIG_ERROR", "Invalid web_search configuration")
+                        .with_details(e.to_string())
+                })
+            })
+            .transpose()
+    }
+}
+
+/// Brave Search API
+struct BraveBackend {
+    key: BackendKey,
+}
+
+#[async_trait]
+impl SearchBackend for BraveBackend {
+    fn id(&self) -> &str {
+        "brave"
+    }
+
+    async fn search(
+        &self,
+        client: &reqwest::Client,
+        input: &SearchInput,
+    ) -> Result<Vec<RawSearchHit>, ToolError> {
+        let count = (input.get_offset() + input.get_limit()).min(BRAVE_MAX_COUNT);
+        let response = client
+            .get(BRAVE_ENDPOINT)
+            .query(&[("q", input.query.as_str()), ("count", &count.to_string())])
+            .header("Accept", "application/json")
+            .header("X-Subscription-Token", self.key.reveal()?)
+            .send()
+            .await
+            .map_err(|e| network_error("Brave", e))?;
+        let response: BraveResponse = check_status("Brave", response)?
+            .json()
+            .await
+            .map_err(|e| parse_error("Brave", e))?;
+
+        Ok(response
+            .web
+            .map(|web| web.results)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|r| RawSearchHit {
+                title: r.title,
+                url: r.url,
+                snippet: r.description,
+            })
+            .collect())
+    }
+}
+
+/// Self-hosted SearXNG instance
+struct SearxngBackend {
+    base_url: String,
+}
+
+#[async_trait]
+impl SearchBackend for SearxngBackend {
+    fn id(&self) -> &str {
+        "searxng"
+    }
+
+    async fn search(
+        &self,
+        client: &reqwest::Client,
+        input: &SearchInput,
+    ) -> Result<Vec<RawSearchHit>, ToolError> {
+        let response = client
+            .get(format!("{}/search", self.base_url))
+            .query(&[("q", input.query.as_str()), ("format", "json")])
+            .send()
+            .await
+            .map_err(|e| network_error("SearXNG", e))?;
+        let response: SearxngResponse = check_status("SearXNG", response)?
+            .json()
+            .await
+            .map_err(|e| parse_error("SearXNG", e))?;
+
+        Ok(response
+            .results
+            .into_iter()
+            .map(|r| RawSearchHit {
+                title: r.title,
+                url: r.url,
+                snippet: r.content,
+            })
+            .collect())
+    }
+}
+
+/// Kagi Search API
+struct KagiBackend {
+    key: BackendKey,
+}
+
+#[async_trait]
+impl SearchBackend for KagiBackend {
+    fn id(&self) -> &str {
+        "kagi"
+    }
+
+    async fn search(
+        &self,
+        client: &reqwest::Client,
+        input: &SearchInput,
+    ) -> Result<Vec<RawSearchHit>, ToolError> {
+        let limit = input.get_offset() + input.get_limit();
+        let response = client
+            .get(KAGI_ENDPOINT)
+            .query(&[("q", input.query.as_str()), ("limit", &limit.to_string())])
+            .header("Authorization", format!("Bot {}", self.key.reveal()?))
+            .send()
+            .await
+            .map_err(|e| network_error("Kagi", e))?;
+        let response: KagiResponse = check_status("Kagi", response)?
+            .json()
+            .await
+            .map_err(|e| parse_error("Kagi", e))?;
+
+        // Type 0 entries are search results; type 1 are related searches
+        Ok(response
+            .data
+            .into_iter()
+            .filter(|r| r.t == 0)
+            .filter_map(|r| {
+                Some(RawSearchHit {
+                    title: r.title,
+                    url: r.url?,
+                    snippet: r.snippet,
+                })
+            })
+            .collect())
+    }
+}
+
+fn check_status(
+    backend: &str,
+    response: reqwest::Response,
+) -> Result<reqwest::Response, ToolError> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let error = match status.as_u16() {
+        401 | 403 => ToolError::new("AUTH_ERROR", format!("{} rejected the API key", backend))
+            .with_suggestion("Check the API key configured for this backend"),
+        429 => ToolError::new("RATE_LIMITED", format!("{} rate limit exceeded", backend))
+            .with_suggestion("Lower requests_per_minute for this backend"),
+        _ => ToolError::new("API_ERROR", format!("{} search failed", backend)),
+    };
+    Err(error.with_details(format!("Status: {}", status)))
+}
+
+fn network_error(backend: &str, error: reqwest::Error) -> ToolError {
+    ToolError::new("NETWORK_ERROR", format!("Failed to connect to {}", backend))
+        .with_details(error.to_string())
+}
+
+fn parse_error(backend: &str, error: reqwest::Error) -> ToolError {
+    ToolError::new(
+        "PARSE_ERROR",
+        format!("Failed to parse {} response", backend),
+    )
+    .with_details(error.to_string())
+}
+
+/// Normalize backend hits into a page of search results
+///
+/// Hits without an http(s) URL are dropped, duplicate URLs keep their first
+/// occurrence, markup is stripped from titles and snippets, and ranks are
+/// assigned before pagination so they stay stable across pages.
+pub fn normalize_results(hits: Vec<RawSearchHit>, input: &SearchInput) -> SearchOutput {
+    let mut seen = HashSet::new();
+    let results: Vec<SearchResult> = hits
+        .into_iter()
+        .filter_map(|hit| {
+            let url = hit.url.trim();
+            let parsed = url::Url::parse(url).ok()?;
+            if !matches!(parsed.scheme(), "http" | "https") || !seen.insert(parsed.to_string()) {
+                return None;
+            }
+            let title = hit.title.as_deref().map(clean_text).unwrap_or_default();
+            let title = if title.is_empty() {
+                "Untitled".to_string()
+            } else {
+                title
+            };
+            let snippet = hit.snippet.as_deref().map(clean_text).unwrap_or_default();
+            Some((title, url.to_string(), snippet))
+        })
+        .enumerate()
+        .map(|(idx, (title, url, snippet))| SearchResult::new(title, url, snippet, idx + 1))
+        .collect();
+
+    let total = results.len();
+    let page = results
+        .into_iter()
+        .skip(input.get_offset())
+        .take(input.get_limit())
+        .collect();
+    SearchOutput::new(page, total)
+}
+
+/// Strip tags, decode common entities and collapse whitespace
+fn clean_text(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut in_tag = false;
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => stripped.push(c),
+            _ => {}
+        }
+    }
+    let decoded = stripped
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&");
+    decoded.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Sliding one-minute window of request times
+struct RateLimiter {
+    requests_per_minute: u32,
+    requests: Mutex<VecDeque<Instant>>,
+}
+
+impl RateLimiter {
+    fn new(requests_per_minute: u32) -> Self {
+        Self {
+            requests_per_minute,
+            requests: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Record a request, or return how long to wait for the next free slot
+    fn try_acquire(&self) -> Result<(), Duration> {
+        let window = Duration::from_secs(60);
+        let now = Instant::now();
+        let mut requests = self.requests.lock().unwrap_or_else(|e| e.into_inner());
+        while requests
+            .front()
+            .is_some_and(|at| now.duration_since(*at) >= window)
+        {
+            requests.pop_front();
+        }
+        if requests.len() < self.requests_per_minute as usize {
+            requests.push_back(now);
+            return Ok(());
+        }
+        let oldest = requests.front().copied().unwrap_or(now);
+        Err(window.saturating_sub(now.duration_since(oldest)))
+    }
+}
+
+/// Running quality statistics of one backend
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct BackendQuality {
+    pub successes: u32,
+    pub failures: u32,
+    /// Sum over successful searches of the fraction of requested results returned
+    pub fill: f64,
+    /// Sum of successful search latencies in milliseconds
+    pub latency_ms: u64,
+}
+
+impl BackendQuality {
+    /// Score between 0 and 1; unused backends score [`NEUTRAL_SCORE`]
+    ///
+    /// The success rate is weighted by how full result pages were and how
+    /// quickly they arrived.
+    pub fn score(&self) -> f64 {
+        let requests = self.successes + self.failures;
+        if requests == 0 {
+            return NEUTRAL_SCORE;
+        }
+        let success_rate = f64::from(self.successes) / f64::from(requests);
+        if self.successes == 0 {
+            return 0.0;
+        }
+        let fill = self.fill / f64::from(self.successes);
+        let latency_secs = self.latency_ms as f64 / 1000.0 / f64::from(self.successes);
+        success_rate * (0.7 * fill + 0.3 / (1.0 + latency_secs))
+    }
+
+    fn record_success(&mut self, results: usize, requested: usize, latency: Duration) {
+        self.successes += 1;
+        self.fill += (results as f64 / requested.max(1) as f64).min(1.0);
+        self.latency_ms += latency.as_millis() as u64;
+    }
+
+    fn record_failure(&mut self) {
+        self.failures += 1;
+    }
+}
+
+struct RegisteredBackend {
+    backend: Arc<dyn SearchBackend>,
+    limiter: Option<RateLimiter>,
+}
+
+/// Configured search backends with rate limits and quality scores
+#[derive(Default)]
+pub struct SearchBackendRegistry {
+    backends: Vec<RegisteredBackend>,
+    quality: Mutex<HashMap<String, BackendQuality>>,
+    default_backend: Option<String>,
+}
+
+impl SearchBackendRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build every configured backend
+    ///
+    /// `key_manager` is required for API keys stored encrypted in the config.
+    pub fn from_config(
+        config: &WebSearchConfig,
+        key_manager: Option<Arc<KeyManager>>,
+    ) -> Result<Self, ToolError> {
+        let mut registry = Self::new();
+        for backend in &config.backends {
+            registry = registry.with_backend(
+                backend.build(key_manager.as_ref())?,
+                backend.requests_per_minute(),
+            );
+        }
+        if let Some(default) = &config.default_backend {
+            registry = registry.with_default(default.clone())?;
+        }
+        Ok(registry)
+    }
+
+    /// Add a backend, optionally rate limited
+    pub fn with_backend(
+        mut self,
+        backend: Arc<dyn SearchBackend>,
+        requests_per_minute: Option<u32>,
+    ) -> Self {
+        self.backends.push(RegisteredBackend {
+            backend,
+            limiter: requests_per_minute.map(RateLimiter::new),
+        });
+        self
+    }
+
+    /// Try `id` first regardless of its score
+    pub fn with_default(mut self, id: impl Into<String>) -> Result<Self, ToolError> {
+        let id = id.into();
+        if !self.backends.iter().any(|b| b.backend.id() == id) {
+            return Err(ToolError::new(
+                "CONFIG_ERROR",
+                format!("Default search backend {} is not configured", id),
+            )
+            .with_suggestion("Add the backend to web_search.backends"));
+        }
+        self.default_backend = Some(id);
+        Ok(self)
+    }
+
+    /// Whether no backend is configured
+    pub fn is_empty(&self) -> bool {
+        self.backends.is_empty()
+    }
+
+    /// Backend tried first: the configured default or the best scoring one
+    pub fn default_backend(&self) -> Option<&str> {
+        self.ordered().first().map(|b| b.backend.id())
+    }
+
+    /// Quality statistics of a backend
+    pub fn quality(&self, id: &str) -> BackendQuality {
+        self.quality
+            .lock()
+            .map(|quality| quality.get(id).copied().unwrap_or_default())
+            .unwrap_or_default()
+    }
+
+    /// Search with the best available backend, falling back to the others
+    ///
+    /// Returns the normalized output and the ID of the backend that served
+    /// it. Rate-limited backends are skipped.
+    pub async fn search(
+        &self,
+        client: &reqwest::Client,
+        input: &SearchInput,
+    ) -> Result<(SearchOutput, String), ToolError> {
+        let mut last_error = None;
+        for registered in self.ordered() {
+            let id = registered.backend.id();
+            if let Some(wait) = registered
+                .limiter
+                .as_ref()
+                .and_then(|limiter| limiter.try_acquire().err())
+            {
+                debug!("Search backend {} rate limited for {:?}", id, wait);
+                last_error = Some(
+                    ToolError::new(
+                        "RATE_LIMITED",
+                        format!("Search backend {} is rate limited", id),
+                    )
+                    .with_details(format!("Next request allowed in {}s", wait.as_secs() + 1)),
+                );
+                continue;
+            }
+
+            let start = Instant::now();
+            match registered.backend.search(client, input).await {
+                Ok(hits) => {
+                    let output = normalize_results(hits, input);
+                    self.update_quality(id, |q| {
+                        q.record_success(output.results.len(), input.get_limit(), start.elapsed())
+                    });
+                    info!(
+                        "Search backend {} returned {} results",
+                        id,
+                        output.results.len()
+                    );
+                    return Ok((output, id.to_string()));
+                }
+                Err(err) => {
+                    warn!("Search backend {} failed: {}", id, err);
+                    self.update_quality(id, BackendQuality::record_failure);
+                    last_error = Some(err);
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            ToolError::new("NO_SEARCH_BACKEND", "No search backend is configured")
+                .with_suggestion("Add backends to the web_search config section")
+        }))
+    }
+
+    /// Backends in the order they are tried
+    fn ordered(&self) -> Vec<&RegisteredBackend> {
+        let quality = self
+            .quality
+            .lock()
+            .map(|quality| quality.clone())
+            .unwrap_or_default();
+        let score = |b: &RegisteredBackend| {
+            quality
+                .get(b.backend.id())
+                .map(BackendQuality::score)
+                .unwrap_or(NEUTRAL_SCORE)
+        };
+
+        let mut ordered: Vec<&RegisteredBackend> = self.backends.iter().collect();
+        // Stable sort keeps registration order between equal scores
+        ordered.sort_by(|a, b| score(b).total_cmp(&score(a)));
+        if let Some(default) = &self.default_backend {
+            if let Some(pos) = ordered.iter().position(|b| b.backend.id() == default) {
+                let preferred = ordered.remove(pos);
+                ordered.insert(0, preferred);
+            }
+        }
+        ordered
+    }
+
+    fn update_quality(&self, id: &str, update: impl FnOnce(&mut BackendQuality)) {
+        if let Ok(mut quality) = self.quality.lock() {
+            update(quality.entry(id.to_string()).or_default());
+        }
+    }
+}
+
+impl fmt::Debug for SearchBackendRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SearchBackendRegistry")
+            .field(
+                "backends",
+                &self
+                    .backends
+                    .iter()
+                    .map(|b| b.backend.id())
+                    .collect::<Vec<_>>(),
+            )
+            .field("default_backend", &self.default_backend)
+            .finish_non_exhaustive()
+    }
+}
+
+// =============================================================================
+// API Response Types
+// =============================================================================
+
+#[derive(Debug, Deserialize)]
+struct BraveResponse {
+    web: Option<BraveWeb>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BraveWeb {
+    #[serde(default)]
+    results: Vec<BraveResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BraveResult {
+    title: Option<String>,
+    url: String,
+    description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearxngResponse {
+    #[serde(default)]
+    results: Vec<SearxngResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearxngResult {
+    title: Option<String>,
+    url: String,
+    content: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct KagiResponse {
+    #[serde(default)]
+    data: Vec<KagiResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct KagiResult {
+    t: u8,
+    url: Option<String>,
+    title: Option<String>,
+    snippet: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FixedBackend {
+        id: &'static str,
+        hits: Option<Vec<RawSearchHit>>,
+    }
+
+    #[async_trait]
+    impl SearchBackend for FixedBackend {
+        fn id(&self) -> &str {
+            self.id
+        }
+
+        async fn search(
+            &self,
+            _client: &reqwest::Client,
+            _input: &SearchInput,
+        ) -> Result<Vec<RawSearchHit>, ToolError> {
+            self.hits
+                .clone()
+                .ok_or_else(|| ToolError::new("API_ERROR", "backend down"))
+        }
+    }
+
+    fn hit(title: &str, url: &str) -> RawSearchHit {
+        RawSearchHit {
+            title: Some(title.to_string()),
+            url: url.to_string(),
+            snippet: Some("A <strong>fast</strong> &amp; safe\n language".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_normalize_results() {
+        let hits = vec![
+            hit("Rust", "https://www.rust-lang.org/"),
+            hit("Duplicate", "https://www.rust-lang.org/"),
+            hit("Not web", "ftp://example.com/file"),
+            hit("", "https://doc.rust-lang.org/book/"),
+            hit("Crates", "https://crates.io/"),
+        ];
+        let output = normalize_results(hits, &SearchInput::new("rust").with_offset(1));
+
+        assert_eq!(output.total_count, 3);
+        assert_eq!(output.results.len(), 2);
+        assert_eq!(output.results[0].title, "Untitled");
+        assert_eq!(output.results[0].rank, 2);
+        assert_eq!(output.results[0].snippet, "A fast & safe language");
+    }
+
+    #[test]
+    fn test_config_builds_backends_with_encrypted_keys() {
+        let key_manager = Arc::new(KeyManager::new("test-password").unwrap());
+        let api_key = SearchApiKey::encrypt(&key_manager, "brave-key").unwrap();
+        let config: WebSearchConfig = serde_json::from_value(serde_json::json!({
+            "default_backend": "searxng",
+            "backends": [
+                { "type": "brave", "api_key": serde_json::to_value(&api_key).unwrap() },
+                { "type": "searxng", "base_url": "http://localhost:8888/" }
+            ]
+        }))
+        .unwrap();
+        assert!(!format!("{:?}", config).contains("brave-key"));
+
+        let registry = SearchBackendRegistry::from_config(&config, Some(key_manager)).unwrap();
+        assert_eq!(registry.default_backend(), Some("searxng"));
+
+        // Stored keys can only be decrypted with the key manager
+        assert!(SearchBackendRegistry::from_config(&config, None).is_err());
+    }
+
+    #[test]
+    fn test_rate_limiter() {
+        let limiter = RateLimiter::new(2);
+        assert!(limiter.try_acquire().is_ok());
+        assert!(limiter.try_acquire().is_ok());
+        let wait = limiter.try_acquire().unwrap_err();
+        assert!(wait > Duration::from_secs(59));
+    }
+
+    #[tokio::test]
+    async fn test_registry_falls_back_and_scores_backends() {
+        let registry = SearchBackendRegistry::new()
+            .with_backend(
+                Arc::new(FixedBackend {
+                    id: "down",
+                    hits: None,
+                }),
+                None,
+            )
+            .with_backend(
+                Arc::new(FixedBackend {
+                    id: "up",
+                    hits: Some(vec![hit("Rust", "https://www.rust-lang.org/")]),
+                }),
+                Some(1),
+            );
+        let client = reqwest::Client::new();
+        let input = SearchInput::new("rust").with_limit(1);
+
+        let (output, backend) = registry.search(&client, &input).await.unwrap();
+        assert_eq!(backend, "up");
+        assert_eq!(output.results.len(), 1);
+        assert_eq!(registry.quality("down").failures, 1);
+        assert_eq!(registry.default_backend(), Some("up"));
+
+        // "up" is rate limited now and "down" still fails
+        let err = registry.search(&client, &input).await.unwrap_err();
+        assert_eq!(err.code, "API_ERROR");
+        assert_eq!(registry.quality("down").failures, 2);
+    }
+}
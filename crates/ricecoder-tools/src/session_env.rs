@@ -0,0 +1,314 @@
+//! Per-session environment variables and secrets for tools
+//!
+//! A session can define variables and secrets that are injected into the
+//! execution environment of specific tools only, e.g. a deploy token for the
+//! `bash` tool. Secrets are kept encrypted with ricecoder-security and only
+//! decrypted when a tool that may see them runs. Values never appear in
+//! `Debug` output, and everything a session defined is dropped when the
+//! session ends.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    sync::{Arc, RwLock},
+};
+
+use ricecoder_security::{EncryptedData, KeyManager};
+
+use crate::error::ToolError;
+
+/// Text that replaces secret values in tool output
+pub const REDACTED: &str = "[REDACTED]";
+
+/// A value a session defined
+#[derive(Clone)]
+enum ScopedValue {
+    Plain(String),
+    Secret(EncryptedData),
+}
+
+/// A variable and the tools that may see it
+#[derive(Clone)]
+struct ScopedVar {
+    value: ScopedValue,
+    tools: HashSet<String>,
+}
+
+/// Variables and secrets defined by sessions, scoped to tools
+pub struct SessionEnvironment {
+    key_manager: Arc<KeyManager>,
+    sessions: RwLock<HashMap<String, HashMap<String, ScopedVar>>>,
+}
+
+impl SessionEnvironment {
+    /// Create an empty environment that encrypts secrets with `key_manager`
+    pub fn new(key_manager: Arc<KeyManager>) -> Self {
+        Self {
+            key_manager,
+            sessions: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Define a plain variable for the given tools
+    pub fn set_var<I, S>(
+        &self,
+        session_id: &str,
+        name: impl Into<String>,
+        value: impl Into<String>,
+        tools: I,
+    ) -> Result<(), ToolError>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.insert(
+            session_id,
+            name.into(),
+            ScopedValue::Plain(value.into()),
+            tools,
+        )
+    }
+
+    /// Define a secret for the given tools
+    ///
+    /// The value is encrypted right away and only decrypted for injection.
+    pub fn set_secret<I, S>(
+        &self,
+        session_id: &str,
+        name: impl Into<String>,
+        secret: &str,
+        tools: I,
+    ) -> Result<(), ToolError>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let encrypted = self.key_manager.encrypt_api_key(secret).map_err(|e| {
+            ToolError::new("SECRET_ERROR", "Failed to encrypt secret").with_details(e.to_string())
+        })?;
+        self.insert(
+            session_id,
+            name.into(),
+            ScopedValue::Secret(encrypted),
+            tools,
+        )
+    }
+
+    /// Define a secret that was already encrypted with this environment's key
+    pub fn set_encrypted_secret<I, S>(
+        &self,
+        session_id: &str,
+        name: impl Into<String>,
+        secret: EncryptedData,
+        tools: I,
+    ) -> Result<(), ToolError>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.insert(session_id, name.into(), ScopedValue::Secret(secret), tools)
+    }
+
+    /// Remove a variable or secret from a session
+    pub fn remove(&self, session_id: &str, name: &str) -> bool {
+        match self.sessions.write() {
+            Ok(mut sessions) => sessions
+                .get_mut(session_id)
+                .is_some_and(|vars| vars.remove(name).is_some()),
+            Err(_) => false,
+        }
+    }
+
+    /// Names defined by a session, sorted; values are never listed
+    pub fn names(&self, session_id: &str) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .sessions
+            .read()
+            .ok()
+            .and_then(|sessions| {
+                sessions
+                    .get(session_id)
+                    .map(|vars| vars.keys().cloned().collect())
+            })
+            .unwrap_or_default();
+        names.sort();
+        names
+    }
+
+    /// Resolve the variables a tool may see in a session
+    ///
+    /// Secrets are decrypted here; the result should be dropped as soon as
+    /// the tool has been started.
+    pub fn resolve(&self, session_id: &str, tool: &str) -> Result<ResolvedEnv, ToolError> {
+        let scoped: Vec<(String, ScopedValue)> = {
+            let sessions = self.sessions.read().map_err(lock_error)?;
+            sessions
+                .get(session_id)
+                .map(|vars| {
+                    vars.iter()
+                        .filter(|(_, var)| var.tools.contains(tool))
+                        .map(|(name, var)| (name.clone(), var.value.clone()))
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+
+        let mut resolved = ResolvedEnv::default();
+        for (name, value) in scoped {
+            match value {
+                ScopedValue::Plain(value) => resolved.vars.push((name, value)),
+                ScopedValue::Secret(encrypted) => {
+                    let value = self.key_manager.decrypt_api_key(&encrypted).map_err(|e| {
+                        ToolError::new("SECRET_ERROR", format!("Failed to decrypt secret {}", name))
+                            .with_details(e.to_string())
+                    })?;
+                    if !value.is_empty() {
+                        resolved.secrets.push(value.clone());
+                    }
+                    resolved.vars.push((name, value));
+                }
+            }
+        }
+        resolved.vars.sort_by(|a, b| a.0.cmp(&b.0));
+        // Longest first so a secret containing another is redacted whole
+        resolved.secrets.sort_by_key(|s| std::cmp::Reverse(s.len()));
+        Ok(resolved)
+    }
+
+    /// Drop everything a session defined
+    pub fn end_session(&self, session_id: &str) {
+        if let Ok(mut sessions) = self.sessions.write() {
+            sessions.remove(session_id);
+        }
+    }
+
+    fn insert<I, S>(
+        &self,
+        session_id: &str,
+        name: String,
+        value: ScopedValue,
+        tools: I,
+    ) -> Result<(), ToolError>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        if name.is_empty() || name.contains('=') || name.contains('\0') {
+            return Err(ToolError::new(
+                "INVALID_INPUT",
+                format!("Invalid environment variable name: {:?}", name),
+            ));
+        }
+        let tools: HashSet<String> = tools.into_iter().map(Into::into).collect();
+        if tools.is_empty() {
+            return Err(ToolError::new(
+                "INVALID_INPUT",
+                format!("Variable {} is not scoped to any tool", name),
+            )
+            .with_suggestion("List the tools that may see the variable"));
+        }
+
+        let mut sessions = self.sessions.write().map_err(lock_error)?;
+        sessions
+            .entry(session_id.to_string())
+            .or_default()
+            .insert(name, ScopedVar { value, tools });
+        Ok(())
+    }
+}
+
+impl fmt::Debug for SessionEnvironment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sessions = self.sessions.read().map(|s| s.len()).unwrap_or(0);
+        f.debug_struct("SessionEnvironment")
+            .field("sessions", &sessions)
+            .finish_non_exhaustive()
+    }
+}
+
+fn lock_error<T>(_: T) -> ToolError {
+    ToolError::new("INTERNAL_ERROR", "Session environment lock poisoned")
+}
+
+/// Variables resolved for one tool execution
+///
+/// `Debug` lists names only.
+#[derive(Clone, Default)]
+pub struct ResolvedEnv {
+    vars: Vec<(String, String)>,
+    secrets: Vec<String>,
+}
+
+impl ResolvedEnv {
+    /// Variables to set, sorted by name
+    pub fn vars(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.vars.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Whether there is nothing to inject
+    pub fn is_empty(&self) -> bool {
+        self.vars.is_empty()
+    }
+
+    /// Replace every secret value in `text` with [`REDACTED`]
+    pub fn redact(&self, text: &str) -> String {
+        self.secrets.iter().fold(text.to_string(), |text, secret| {
+            text.replace(secret.as_str(), REDACTED)
+        })
+    }
+}
+
+impl fmt::Debug for ResolvedEnv {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResolvedEnv")
+            .field(
+                "names",
+                &self.vars.iter().map(|(k, _)| k).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn environment() -> SessionEnvironment {
+        SessionEnvironment::new(Arc::new(KeyManager::new("test-password").unwrap()))
+    }
+
+    #[test]
+    fn test_values_are_scoped_to_tools() {
+        let env = environment();
+        env.set_var("s1", "DEPLOY_ENV", "staging", ["bash"])
+            .unwrap();
+        env.set_secret("s1", "DEPLOY_TOKEN", "tok-123", ["bash"])
+            .unwrap();
+        env.set_var("s1", "OTHER", "x", ["webfetch"]).unwrap();
+
+        let resolved = env.resolve("s1", "bash").unwrap();
+        let vars: Vec<_> = resolved.vars().collect();
+        assert_eq!(
+            vars,
+            vec![("DEPLOY_ENV", "staging"), ("DEPLOY_TOKEN", "tok-123")]
+        );
+        assert_eq!(resolved.redact("token=tok-123"), "token=[REDACTED]");
+        assert!(!format!("{:?}", resolved).contains("tok-123"));
+
+        assert!(env.resolve("s2", "bash").unwrap().is_empty());
+        assert!(env
+            .set_var("s1", "UNSCOPED", "x", Vec::<String>::new())
+            .is_err());
+    }
+
+    #[test]
+    fn test_end_session_clears_values() {
+        let env = environment();
+        env.set_secret("s1", "TOKEN", "secret", ["bash"]).unwrap();
+        assert_eq!(env.names("s1"), vec!["TOKEN".to_string()]);
+
+        env.end_session("s1");
+        assert!(env.names("s1").is_empty());
+        assert!(env.resolve("s1", "bash").unwrap().is_empty());
+    }
+}
@@ -1,9 +1,16 @@
 //! Todo tools for managing task lists
 //!
 //! Provides functionality to create, read, and update todos with persistent storage.
+//! Todos can be nested as subtasks and can depend on other todos; a pending
+//! todo whose dependencies are not finished is marked blocked.
 
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
 
+use chrono::{DateTime, Utc};
+use ricecoder_storage::{PathResolver, RuntimeStorageType};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use tracing::{debug, error, info};
@@ -46,6 +53,13 @@ impl std::fmt::Display for TodoStatus {
     }
 }
 
+impl TodoStatus {
+    /// Whether the todo no longer blocks its dependents
+    pub fn is_finished(self) -> bool {
+        matches!(self, TodoStatus::Completed | TodoStatus::Cancelled)
+    }
+}
+
 impl std::str::FromStr for TodoStatus {
     type Err = ToolError;
 
@@ -115,6 +129,10 @@ impl std::str::FromStr for TodoPriority {
 /// - `content` - Brief description (alias for `title`)
 /// - `status` - Current status
 /// - `priority` - Priority level
+/// - `parent_id` - Parent todo, for subtasks
+/// - `depends_on` - Todos that must be finished first
+/// - `due` / `effort_minutes` - Scheduling metadata
+/// - `step_id` - Linked execution plan step
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Todo {
     /// Unique identifier for the todo
@@ -130,6 +148,21 @@ pub struct Todo {
     pub status: TodoStatus,
     /// Priority level of the todo
     pub priority: TodoPriority,
+    /// Parent todo ID, when this todo is a subtask
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<String>,
+    /// IDs of todos that must be completed or cancelled before this one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+    /// When the todo is due
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due: Option<DateTime<Utc>>,
+    /// Estimated effort in minutes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effort_minutes: Option<u32>,
+    /// ID of the execution plan step this todo tracks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub step_id: Option<String>,
 }
 
 impl Todo {
@@ -162,6 +195,11 @@ impl Todo {
             description: None,
             status,
             priority,
+            parent_id: None,
+            depends_on: Vec::new(),
+            due: None,
+            effort_minutes: None,
+            step_id: None,
         })
     }
 
@@ -171,6 +209,36 @@ impl Todo {
         self
     }
 
+    /// Make this todo a subtask of another
+    pub fn with_parent(mut self, parent_id: impl Into<String>) -> Self {
+        self.parent_id = Some(parent_id.into());
+        self
+    }
+
+    /// Add a todo that must be finished before this one
+    pub fn with_dependency(mut self, todo_id: impl Into<String>) -> Self {
+        self.depends_on.push(todo_id.into());
+        self
+    }
+
+    /// Set the due date
+    pub fn with_due(mut self, due: DateTime<Utc>) -> Self {
+        self.due = Some(due);
+        self
+    }
+
+    /// Set the estimated effort in minutes
+    pub fn with_effort_minutes(mut self, minutes: u32) -> Self {
+        self.effort_minutes = Some(minutes);
+        self
+    }
+
+    /// Link this todo to an execution plan step
+    pub fn with_step(mut self, step_id: impl Into<String>) -> Self {
+        self.step_id = Some(step_id.into());
+        self
+    }
+
     /// Get the content
     pub fn content(&self) -> &str {
         &self.content
@@ -263,6 +331,7 @@ pub enum WriteMode {
 pub struct TodoStorage {
     storage_path: PathBuf,
     storage_mode: StorageMode,
+    storage_root: Option<PathBuf>,
 }
 
 impl TodoStorage {
@@ -271,6 +340,7 @@ impl TodoStorage {
         Self {
             storage_path: storage_path.into(),
             storage_mode: StorageMode::Global,
+            storage_root: None,
         }
     }
 
@@ -280,6 +350,13 @@ impl TodoStorage {
         self
     }
 
+    /// Set the ricecoder-storage base directory for session-scoped todos
+    /// (default: the global storage path)
+    pub fn with_storage_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.storage_root = Some(root.into());
+        self
+    }
+
     /// Get the default storage path (~/.ricecoder/todos.json)
     pub fn default_path() -> Result<PathBuf, ToolError> {
         if let Some(home_dir) = dirs::home_dir() {
@@ -292,26 +369,56 @@ impl TodoStorage {
         }
     }
 
-    /// Get session-scoped storage path
+    /// Get session-scoped storage path (storage/todo/<session_id>.json)
     pub fn session_path(session_id: &str) -> Result<PathBuf, ToolError> {
-        if let Some(home_dir) = dirs::home_dir() {
-            Ok(home_dir
+        let root = PathResolver::resolve_global_path().map_err(|e| {
+            ToolError::new(
+                "HOME_DIR_NOT_FOUND",
+                "Could not determine storage directory",
+            )
+            .with_details(e.to_string())
+            .with_suggestion("Set the HOME or RICECODER_HOME environment variable")
+        })?;
+        Ok(Self::session_path_in(&root, session_id))
+    }
+
+    fn session_path_in(root: &std::path::Path, session_id: &str) -> PathBuf {
+        PathResolver::runtime_storage_path(root, RuntimeStorageType::Todo)
+            .join(format!("{}.json", session_id))
+    }
+
+    /// Session path used before todos moved to ricecoder-storage
+    fn legacy_session_path(session_id: &str) -> Option<PathBuf> {
+        dirs::home_dir().map(|home_dir| {
+            home_dir
                 .join(".ricecoder")
                 .join("sessions")
                 .join(session_id)
-                .join("todos.json"))
-        } else {
-            Err(
-                ToolError::new("HOME_DIR_NOT_FOUND", "Could not determine home directory")
-                    .with_suggestion("Set the HOME environment variable"),
-            )
+                .join("todos.json")
+        })
+    }
+
+    /// Get the path to load from, falling back to the legacy session path
+    fn load_path(&self, session_id: Option<&str>) -> Result<PathBuf, ToolError> {
+        let path = self.effective_path(session_id)?;
+        if path.exists() || self.storage_root.is_some() {
+            return Ok(path);
+        }
+        match (self.storage_mode, session_id) {
+            (StorageMode::SessionScoped, Some(sid)) => Ok(Self::legacy_session_path(sid)
+                .filter(|legacy| legacy.exists())
+                .unwrap_or(path)),
+            _ => Ok(path),
         }
     }
 
     /// Get effective storage path based on mode and session
     fn effective_path(&self, session_id: Option<&str>) -> Result<PathBuf, ToolError> {
         match (self.storage_mode, session_id) {
-            (StorageMode::SessionScoped, Some(sid)) => Self::session_path(sid),
+            (StorageMode::SessionScoped, Some(sid)) => match &self.storage_root {
+                Some(root) => Ok(Self::session_path_in(root, sid)),
+                None => Self::session_path(sid),
+            },
             (StorageMode::SessionScoped, None) => Err(ToolError::new(
                 "MISSING_SESSION_ID",
                 "Session ID required for session-scoped storage",
@@ -323,7 +430,7 @@ impl TodoStorage {
 
     /// Load todos from storage (with session support)
     pub fn load_todos(&self, session_id: Option<&str>) -> Result<HashMap<String, Todo>, ToolError> {
-        let path = self.effective_path(session_id)?;
+        let path = self.load_path(session_id)?;
         debug!("Loading todos from: {:?}", path);
 
         // If file doesn't exist, return empty map
@@ -360,7 +467,7 @@ impl TodoStorage {
 
     /// Load todos as Vec (preserves order)
     pub fn load_todos_ordered(&self, session_id: Option<&str>) -> Result<Vec<Todo>, ToolError> {
-        let path = self.effective_path(session_id)?;
+        let path = self.load_path(session_id)?;
         debug!("Loading todos from: {:?}", path);
 
         // If file doesn't exist, return empty vec
@@ -538,6 +645,12 @@ impl TodoTools {
         self
     }
 
+    /// Set the ricecoder-storage base directory for session-scoped todos
+    pub fn with_storage_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.storage = self.storage.with_storage_root(root);
+        self
+    }
+
     /// Set write mode (replace or merge)
     pub fn with_write_mode(mut self, mode: WriteMode) -> Self {
         self.write_mode = mode;
@@ -607,6 +720,103 @@ impl TodoTools {
         Ok(())
     }
 
+    /// Validate subtask and dependency references across a whole todo list
+    ///
+    /// Parents and dependencies must exist, and neither may form a cycle.
+    pub fn validate_todo_graph(todos: &[Todo]) -> Result<(), ToolError> {
+        let by_id: HashMap<&str, &Todo> = todos.iter().map(|t| (t.id.as_str(), t)).collect();
+
+        for todo in todos {
+            if let Some(parent_id) = &todo.parent_id {
+                if !by_id.contains_key(parent_id.as_str()) || parent_id == &todo.id {
+                    return Err(ToolError::new(
+                        "INVALID_PARENT",
+                        format!("Todo {} has unknown parent: {}", todo.id, parent_id),
+                    )
+                    .with_suggestion("Set parent_id to the id of another todo in the list"));
+                }
+            }
+            for dependency in &todo.depends_on {
+                if !by_id.contains_key(dependency.as_str()) || dependency == &todo.id {
+                    return Err(ToolError::new(
+                        "INVALID_DEPENDENCY",
+                        format!("Todo {} depends on unknown todo: {}", todo.id, dependency),
+                    )
+                    .with_suggestion("List ids of other todos in depends_on"));
+                }
+            }
+
+            // Walk up the parents; a chain longer than the list is a cycle
+            let mut current = todo;
+            for _ in 0..=todos.len() {
+                match current.parent_id.as_deref().and_then(|id| by_id.get(id)) {
+                    Some(parent) => current = parent,
+                    None => break,
+                }
+                if current.id == todo.id {
+                    return Err(ToolError::new(
+                        "INVALID_PARENT",
+                        format!("Todo {} is its own ancestor", todo.id),
+                    )
+                    .with_suggestion("Remove the parent_id that closes the cycle"));
+                }
+            }
+        }
+
+        // Depth-first search for dependency cycles
+        fn visit<'a>(
+            id: &'a str,
+            by_id: &HashMap<&'a str, &'a Todo>,
+            visiting: &mut HashSet<&'a str>,
+            done: &mut HashSet<&'a str>,
+        ) -> Result<(), ToolError> {
+            if done.contains(id) {
+                return Ok(());
+            }
+            if !visiting.insert(id) {
+                return Err(ToolError::new(
+                    "DEPENDENCY_CYCLE",
+                    format!("Todo {} depends on itself through other todos", id),
+                )
+                .with_suggestion("Remove one of the dependencies in the cycle"));
+            }
+            for dependency in &by_id[id].depends_on {
+                visit(dependency, by_id, visiting, done)?;
+            }
+            visiting.remove(id);
+            done.insert(id);
+            Ok(())
+        }
+
+        let mut visiting = HashSet::new();
+        let mut done = HashSet::new();
+        for todo in todos {
+            visit(&todo.id, &by_id, &mut visiting, &mut done)?;
+        }
+        Ok(())
+    }
+
+    /// Block pending todos with unfinished dependencies and unblock the rest
+    ///
+    /// Only todos with dependencies are touched, so a todo marked blocked
+    /// for another reason stays blocked.
+    pub fn resolve_blocked(todos: &mut [Todo]) {
+        let finished: HashSet<String> = todos
+            .iter()
+            .filter(|t| t.status.is_finished())
+            .map(|t| t.id.clone())
+            .collect();
+
+        for todo in todos.iter_mut().filter(|t| !t.depends_on.is_empty()) {
+            let waiting = todo.depends_on.iter().any(|id| !finished.contains(id));
+            match todo.status {
+                TodoStatus::Pending if waiting => todo.status = TodoStatus::Blocked,
+                TodoStatus::Blocked if !waiting => todo.status = TodoStatus::Pending,
+                _ => {}
+            }
+        }
+    }
+
     /// Count incomplete todos (status != completed)
     fn count_incomplete(todos: &[Todo]) -> usize {
         todos.iter().filter(|t| t.status != TodoStatus::Completed).count()
@@ -686,8 +896,11 @@ impl TodoTools {
             WriteMode::Replace => {
                 // OpenCode compatible: replace entire list
                 debug!("Replace mode: replacing entire todo list");
-                self.storage.save_todos_ordered(&input.todos, session_id)?;
-                (input.todos.len(), 0, input.todos)
+                let mut todos = input.todos;
+                Self::validate_todo_graph(&todos)?;
+                Self::resolve_blocked(&mut todos);
+                self.storage.save_todos_ordered(&todos, session_id)?;
+                (todos.len(), 0, todos)
             }
             WriteMode::Merge => {
                 // RiceCoder default: merge by ID
@@ -708,12 +921,18 @@ impl TodoTools {
                     todos.insert(id, todo.clone());
                 }
 
-                // Save todos
-                self.storage.save_todos(&todos, session_id)?;
-
                 // Convert to Vec for output (sorted by ID for stable ordering)
-                let mut final_todos: Vec<Todo> = todos.values().cloned().collect();
+                let mut final_todos: Vec<Todo> = todos.into_values().collect();
                 final_todos.sort_by(|a, b| a.id.cmp(&b.id));
+                Self::validate_todo_graph(&final_todos)?;
+                Self::resolve_blocked(&mut final_todos);
+
+                // Save todos
+                let todos: HashMap<String, Todo> = final_todos
+                    .iter()
+                    .map(|todo| (todo.id.clone(), todo.clone()))
+                    .collect();
+                self.storage.save_todos(&todos, session_id)?;
 
                 (created, updated, final_todos)
             }
@@ -742,6 +961,25 @@ impl TodoTools {
         })
     }
 
+    /// Load every stored todo
+    ///
+    /// Preserves order if replace mode was used; merge mode sorts by
+    /// priority, then ID.
+    pub(crate) fn stored_todos(&self, session_id: Option<&str>) -> Result<Vec<Todo>, ToolError> {
+        if self.write_mode == WriteMode::Replace {
+            self.storage.load_todos_ordered(session_id)
+        } else {
+            // Merge mode: convert HashMap to Vec and sort
+            let todos_map = self.storage.load_todos(session_id)?;
+            let mut todos: Vec<Todo> = todos_map.values().cloned().collect();
+            todos.sort_by(|a, b| match b.priority.cmp(&a.priority) {
+                std::cmp::Ordering::Equal => a.id.cmp(&b.id),
+                other => other,
+            });
+            Ok(todos)
+        }
+    }
+
     /// Read todos with timeout enforcement (500ms)
     ///
     /// Attempts to use MCP provider if available, falls back to built-in implementation.
@@ -807,19 +1045,7 @@ impl TodoTools {
         // Fall back to built-in implementation
         debug!("Using built-in todoread implementation");
 
-        // Load todos (preserves order if replace mode was used)
-        let todos = if self.write_mode == WriteMode::Replace {
-            self.storage.load_todos_ordered(session_id)?
-        } else {
-            // Merge mode: convert HashMap to Vec and sort
-            let todos_map = self.storage.load_todos(session_id)?;
-            let mut todos: Vec<Todo> = todos_map.values().cloned().collect();
-            todos.sort_by(|a, b| match b.priority.cmp(&a.priority) {
-                std::cmp::Ordering::Equal => a.id.cmp(&b.id),
-                other => other,
-            });
-            todos
-        };
+        let todos = self.stored_todos(session_id)?;
 
         // Filter todos (RiceCoder extra - KEEP)
         let filtered: Vec<Todo> = if input.status_filter.is_some() || input.priority_filter.is_some() {
@@ -872,6 +1098,36 @@ impl Default for TodoTools {
     }
 }
 
+/// Arrange todos as a tree, depth first
+///
+/// Returns each todo with its depth; subtasks follow their parent in list
+/// order. Todos whose parent is not in the list are shown as roots.
+pub fn todo_tree(todos: &[Todo]) -> Vec<(usize, &Todo)> {
+    let ids: HashSet<&str> = todos.iter().map(|t| t.id.as_str()).collect();
+    let mut children: HashMap<&str, Vec<&Todo>> = HashMap::new();
+    let mut roots = Vec::new();
+    for todo in todos {
+        match todo.parent_id.as_deref().filter(|id| ids.contains(id)) {
+            Some(parent_id) => children.entry(parent_id).or_default().push(todo),
+            None => roots.push(todo),
+        }
+    }
+
+    let mut rows = Vec::with_capacity(todos.len());
+    let mut seen = HashSet::new();
+    let mut stack: Vec<(usize, &Todo)> = roots.into_iter().rev().map(|t| (0, t)).collect();
+    while let Some((depth, todo)) = stack.pop() {
+        if !seen.insert(todo.id.as_str()) {
+            continue;
+        }
+        rows.push((depth, todo));
+        if let Some(subtasks) = children.get(todo.id.as_str()) {
+            stack.extend(subtasks.iter().rev().map(|t| (depth + 1, *t)));
+        }
+    }
+    rows
+}
+
 /// Tool invoker wiring helpers (G-10)
 impl TodoTools {
     /// Create tool metadata for todowrite
@@ -897,7 +1153,15 @@ impl TodoTools {
                                     "type": "string",
                                     "enum": ["low", "medium", "high", "critical"]
                                 },
-                                "description": {"type": "string"}
+                                "description": {"type": "string"},
+                                "parent_id": {"type": "string"},
+                                "depends_on": {
+                                    "type": "array",
+                                    "items": {"type": "string"}
+                                },
+                                "due": {"type": "string", "format": "date-time"},
+                                "effort_minutes": {"type": "integer", "minimum": 0},
+                                "step_id": {"type": "string"}
                             },
                             "required": ["id", "content", "status", "priority"]
                         }
@@ -1331,4 +1595,146 @@ mod tests {
     fn test_cancelled_status_display() {
         assert_eq!(TodoStatus::Cancelled.to_string(), "cancelled");
     }
+
+    fn todo(id: &str) -> Todo {
+        Todo::new(
+            id,
+            format!("Task {}", id),
+            TodoStatus::Pending,
+            TodoPriority::Medium,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_todo_graph_validation() {
+        let valid = vec![
+            todo("1"),
+            todo("2").with_parent("1"),
+            todo("3").with_parent("1").with_dependency("2"),
+        ];
+        assert!(TodoTools::validate_todo_graph(&valid).is_ok());
+
+        let unknown_parent = vec![todo("1").with_parent("9")];
+        let err = TodoTools::validate_todo_graph(&unknown_parent).unwrap_err();
+        assert_eq!(err.code, "INVALID_PARENT");
+
+        let parent_cycle = vec![todo("1").with_parent("2"), todo("2").with_parent("1")];
+        let err = TodoTools::validate_todo_graph(&parent_cycle).unwrap_err();
+        assert_eq!(err.code, "INVALID_PARENT");
+
+        let dependency_cycle = vec![
+            todo("1").with_dependency("3"),
+            todo("2").with_dependency("1"),
+            todo("3").with_dependency("2"),
+        ];
+        let err = TodoTools::validate_todo_graph(&dependency_cycle).unwrap_err();
+        assert_eq!(err.code, "DEPENDENCY_CYCLE");
+    }
+
+    #[test]
+    fn test_dependencies_block_and_unblock() {
+        let temp_dir = TempDir::new().unwrap();
+        let tools = TodoTools::with_storage_path(temp_dir.path().join("todos.json"));
+
+        let output = tools
+            .write_todos(
+                TodowriteInput {
+                    todos: vec![todo("1"), todo("2").with_dependency("1")],
+                },
+                None,
+            )
+            .unwrap();
+        assert_eq!(output.metadata.todos[1].status, TodoStatus::Blocked);
+
+        let mut done = todo("1");
+        done.status = TodoStatus::Completed;
+        let output = tools
+            .write_todos(TodowriteInput { todos: vec![done] }, None)
+            .unwrap();
+        assert_eq!(output.metadata.todos[1].status, TodoStatus::Pending);
+
+        let err = tools
+            .write_todos(
+                TodowriteInput {
+                    todos: vec![todo("3").with_dependency("missing")],
+                },
+                None,
+            )
+            .unwrap_err();
+        assert_eq!(err.code, "INVALID_DEPENDENCY");
+    }
+
+    #[test]
+    fn test_todo_tree_order() {
+        let todos = vec![
+            todo("a"),
+            todo("b"),
+            todo("a2").with_parent("a"),
+            todo("a1").with_parent("a"),
+            todo("a1x").with_parent("a1"),
+            todo("orphan").with_parent("gone"),
+        ];
+
+        let rows: Vec<(usize, &str)> = todo_tree(&todos)
+            .into_iter()
+            .map(|(depth, todo)| (depth, todo.id.as_str()))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                (0, "a"),
+                (1, "a2"),
+                (1, "a1"),
+                (2, "a1x"),
+                (0, "b"),
+                (0, "orphan"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_metadata_round_trip_and_session_storage() {
+        let temp_dir = TempDir::new().unwrap();
+        let tools = TodoTools::with_storage_path(temp_dir.path().join("todos.json"))
+            .with_storage_mode(StorageMode::SessionScoped)
+            .with_storage_root(temp_dir.path());
+        let due = "2026-01-31T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let scheduled = todo("1").with_due(due).with_effort_minutes(90);
+
+        tools
+            .write_todos(
+                TodowriteInput {
+                    todos: vec![scheduled],
+                },
+                Some("s1"),
+            )
+            .unwrap();
+        let path = temp_dir.path().join("storage").join("todo").join("s1.json");
+        assert!(path.exists());
+
+        let read = tools
+            .read_todos(
+                TodoreadInput {
+                    status_filter: None,
+                    priority_filter: None,
+                },
+                Some("s1"),
+            )
+            .unwrap();
+        assert_eq!(read.metadata.todos[0].due, Some(due));
+        assert_eq!(read.metadata.todos[0].effort_minutes, Some(90));
+        assert!(tools
+            .read_todos(
+                TodoreadInput {
+                    status_filter: None,
+                    priority_filter: None,
+                },
+                Some("s2"),
+            )
+            .unwrap()
+            .metadata
+            .todos
+            .is_empty());
+    }
 }
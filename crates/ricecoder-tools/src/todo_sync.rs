@@ -0,0 +1,290 @@
+//! Two-way sync between todo lists and execution plans
+//!
+//! Every step of an [`ExecutionPlan`] is mirrored by a todo linked through
+//! `step_id`, nested under a todo for the plan itself. Step progress flows
+//! into the todos; todos an agent completes or cancels mark the matching
+//! pending steps completed or skipped, so the execution manager does not run
+//! them.
+
+use std::collections::HashMap;
+
+use ricecoder_execution::{ExecutionPlan, ExecutionStep, RiskLevel, StepStatus};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::{
+    error::ToolError,
+    todo::{Todo, TodoPriority, TodoStatus, TodoTools, TodowriteInput},
+};
+
+/// What a sync changed
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlanSyncReport {
+    /// Todos created for new steps
+    pub todos_created: usize,
+    /// Todos whose status followed their step
+    pub todos_updated: usize,
+    /// Steps whose status followed their todo
+    pub steps_updated: usize,
+}
+
+impl PlanSyncReport {
+    /// Whether the sync changed anything
+    pub fn is_empty(&self) -> bool {
+        self.todos_created == 0 && self.todos_updated == 0 && self.steps_updated == 0
+    }
+}
+
+/// Todo status that mirrors a step status
+pub fn todo_status_for_step(status: StepStatus) -> TodoStatus {
+    match status {
+        StepStatus::Pending => TodoStatus::Pending,
+        StepStatus::Running => TodoStatus::InProgress,
+        StepStatus::Completed => TodoStatus::Completed,
+        StepStatus::Skipped | StepStatus::RolledBack => TodoStatus::Cancelled,
+        StepStatus::Failed => TodoStatus::Blocked,
+    }
+}
+
+/// Build the todos that mirror a plan: one for the plan, one per step
+pub fn todos_from_plan(plan: &ExecutionPlan) -> Vec<Todo> {
+    let mut todos = Vec::with_capacity(plan.steps.len() + 1);
+    todos.push(plan_todo(plan));
+    todos.extend(plan.steps.iter().map(|step| step_todo(plan, step)));
+    rollup_plan_status(plan, &mut todos);
+    todos
+}
+
+/// Sync a todo list and a plan in both directions
+///
+/// Completed or cancelled todos first mark their still-pending steps
+/// completed or skipped. Steps then update the status of their todos, and
+/// steps without a todo get one.
+pub fn sync_plan_todos(todos: &mut Vec<Todo>, plan: &mut ExecutionPlan) -> PlanSyncReport {
+    let mut report = PlanSyncReport::default();
+
+    // Todos -> plan: only steps the execution manager has not started
+    let todo_status: HashMap<&str, TodoStatus> = todos
+        .iter()
+        .filter_map(|todo| Some((todo.step_id.as_deref()?, todo.status)))
+        .collect();
+    for step in plan.steps.iter_mut() {
+        if step.status != StepStatus::Pending {
+            continue;
+        }
+        let status = match todo_status.get(step.id.as_str()) {
+            Some(TodoStatus::Completed) => StepStatus::Completed,
+            Some(TodoStatus::Cancelled) => StepStatus::Skipped,
+            _ => continue,
+        };
+        debug!("Todo marked plan step {} as {:?}", step.id, status);
+        step.status = status;
+        report.steps_updated += 1;
+    }
+
+    // Plan -> todos
+    if !todos.iter().any(|todo| todo.id == plan.id) {
+        todos.push(plan_todo(plan));
+        report.todos_created += 1;
+    }
+    for step in &plan.steps {
+        let status = todo_status_for_step(step.status);
+        match todos
+            .iter_mut()
+            .find(|todo| todo.step_id.as_deref() == Some(step.id.as_str()))
+        {
+            Some(todo) => {
+                // A pending step has nothing to report; keep the agent's view
+                if step.status != StepStatus::Pending && todo.status != status {
+                    todo.status = status;
+                    report.todos_updated += 1;
+                }
+            }
+            None => {
+                todos.push(step_todo(plan, step));
+                report.todos_created += 1;
+            }
+        }
+    }
+    rollup_plan_status(plan, todos);
+    TodoTools::resolve_blocked(todos);
+
+    report
+}
+
+impl TodoTools {
+    /// Sync a session's todos with an execution plan and save them
+    ///
+    /// The plan is updated in place; register it with the execution manager
+    /// again to apply step changes.
+    pub fn sync_with_plan(
+        &self,
+        plan: &mut ExecutionPlan,
+        session_id: Option<&str>,
+    ) -> Result<PlanSyncReport, ToolError> {
+        let mut todos = self.stored_todos(session_id)?;
+        let report = sync_plan_todos(&mut todos, plan);
+        if !report.is_empty() {
+            self.write_todos(TodowriteInput { todos }, session_id)?;
+        }
+        Ok(report)
+    }
+}
+
+fn plan_todo(plan: &ExecutionPlan) -> Todo {
+    let mut todo = Todo::new(
+        plan.id.clone(),
+        non_empty(&plan.name, "Execution plan"),
+        TodoStatus::Pending,
+        priority_for_risk(plan.risk_score.level),
+    )
+    .expect("plan todo content is not empty");
+    let minutes = plan.estimated_duration.as_secs().div_ceil(60);
+    if minutes > 0 {
+        todo = todo.with_effort_minutes(u32::try_from(minutes).unwrap_or(u32::MAX));
+    }
+    todo
+}
+
+fn step_todo(plan: &ExecutionPlan, step: &ExecutionStep) -> Todo {
+    let mut todo = Todo::new(
+        step.id.clone(),
+        non_empty(&step.description, "Plan step"),
+        todo_status_for_step(step.status),
+        priority_for_risk(step.risk_score.level),
+    )
+    .expect("step todo content is not empty")
+    .with_parent(plan.id.clone())
+    .with_step(step.id.clone());
+
+    // Dependencies name steps or step groups
+    for dependency in &step.dependencies {
+        for other in &plan.steps {
+            let matches =
+                other.id == *dependency || other.group.as_deref() == Some(dependency.as_str());
+            if matches && other.id != step.id && !todo.depends_on.contains(&other.id) {
+                todo.depends_on.push(other.id.clone());
+            }
+        }
+    }
+    todo
+}
+
+/// Derive the plan todo's status from its step todos
+fn rollup_plan_status(plan: &ExecutionPlan, todos: &mut [Todo]) {
+    let steps: Vec<TodoStatus> = todos
+        .iter()
+        .filter(|todo| {
+            todo.parent_id.as_deref() == Some(plan.id.as_str()) && todo.step_id.is_some()
+        })
+        .map(|todo| todo.status)
+        .collect();
+    let status = if !steps.is_empty() && steps.iter().all(|s| s.is_finished()) {
+        TodoStatus::Completed
+    } else if steps.iter().any(|s| *s != TodoStatus::Pending) {
+        TodoStatus::InProgress
+    } else {
+        TodoStatus::Pending
+    };
+    if let Some(todo) = todos.iter_mut().find(|todo| todo.id == plan.id) {
+        todo.status = status;
+    }
+}
+
+fn priority_for_risk(level: RiskLevel) -> TodoPriority {
+    match level {
+        RiskLevel::Low => TodoPriority::Low,
+        RiskLevel::Medium => TodoPriority::Medium,
+        RiskLevel::High => TodoPriority::High,
+        RiskLevel::Critical => TodoPriority::Critical,
+    }
+}
+
+fn non_empty(text: &str, fallback: &str) -> String {
+    if text.trim().is_empty() {
+        fallback.to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ricecoder_execution::{StepAction, StepStatus};
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::todo::StorageMode;
+
+    fn plan() -> ExecutionPlan {
+        let mut first = ExecutionStep::new(
+            "Create module".to_string(),
+            StepAction::RunTests { pattern: None },
+        );
+        first.id = "step-1".to_string();
+        let mut second = ExecutionStep::new(
+            "Run tests".to_string(),
+            StepAction::RunTests { pattern: None },
+        );
+        second.id = "step-2".to_string();
+        second.dependencies = vec!["step-1".to_string()];
+        let mut plan = ExecutionPlan::new("Add module".to_string(), vec![first, second]);
+        plan.id = "plan-1".to_string();
+        plan
+    }
+
+    #[test]
+    fn test_todos_from_plan() {
+        let todos = todos_from_plan(&plan());
+        assert_eq!(todos.len(), 3);
+        assert_eq!(todos[0].id, "plan-1");
+        assert_eq!(todos[1].parent_id.as_deref(), Some("plan-1"));
+        assert_eq!(todos[2].step_id.as_deref(), Some("step-2"));
+        assert_eq!(todos[2].depends_on, vec!["step-1".to_string()]);
+    }
+
+    #[test]
+    fn test_sync_in_both_directions() {
+        let mut plan = plan();
+        let mut todos = Vec::new();
+        let report = sync_plan_todos(&mut todos, &mut plan);
+        assert_eq!(report.todos_created, 3);
+        // The second step waits for the first
+        assert_eq!(todos[2].status, TodoStatus::Blocked);
+
+        // The execution manager runs the first step
+        plan.steps[0].status = StepStatus::Completed;
+        sync_plan_todos(&mut todos, &mut plan);
+        assert_eq!(todos[1].status, TodoStatus::Completed);
+        assert_eq!(todos[2].status, TodoStatus::Pending);
+        assert_eq!(todos[0].status, TodoStatus::InProgress);
+
+        // The agent cancels the second step
+        todos[2].status = TodoStatus::Cancelled;
+        let report = sync_plan_todos(&mut todos, &mut plan);
+        assert_eq!(report.steps_updated, 1);
+        assert_eq!(plan.steps[1].status, StepStatus::Skipped);
+        assert_eq!(todos[0].status, TodoStatus::Completed);
+    }
+
+    #[test]
+    fn test_sync_with_plan_persists_session_todos() {
+        let temp_dir = TempDir::new().unwrap();
+        let tools = TodoTools::with_storage_path(temp_dir.path().join("todos.json"))
+            .with_storage_mode(StorageMode::SessionScoped)
+            .with_storage_root(temp_dir.path());
+        let mut plan = plan();
+
+        let report = tools.sync_with_plan(&mut plan, Some("session-1")).unwrap();
+        assert_eq!(report.todos_created, 3);
+        assert!(temp_dir
+            .path()
+            .join("storage")
+            .join("todo")
+            .join("session-1.json")
+            .exists());
+
+        let report = tools.sync_with_plan(&mut plan, Some("session-1")).unwrap();
+        assert!(report.is_empty());
+    }
+}
@@ -6,6 +6,7 @@
 //! - External directory permission gates
 //! - Write permission prompts
 //! - Must-read-before-overwrite enforcement
+//! - Sensitive path denial (`.env`, `~/.ssh`, ...) via ricecoder-safety
 //! - Post-write event publication
 //! - LSP diagnostics integration
 //! - Structured metadata return
@@ -25,6 +26,7 @@
 
 use ricecoder_files::writer::SafeWriter;
 use ricecoder_files::models::ConflictResolution;
+use ricecoder_safety::PathAccess;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use std::collections::HashMap;
@@ -42,6 +44,9 @@ pub enum WriteError {
     #[error("External directory access rejected: {0}")]
     ExternalDirectoryRejected(String),
     
+    #[error("{0}")]
+    SensitivePath(#[from] ricecoder_safety::PathDenied),
+    
     #[error("Must read file before overwriting: {0}")]
     MustReadFirst(String),
     
@@ -198,6 +203,9 @@ impl WriteTool {
             ));
         }
         
+        // Sensitive paths are denied unless the user approved an override
+        ricecoder_safety::sensitive_paths::global().check(&resolved_path, PathAccess::Write)?;
+        
         // Check if file exists
         let exists = resolved_path.exists();
         
@@ -435,6 +443,20 @@ mod tests {
         assert!(matches!(result.unwrap_err(), WriteError::ExternalDirectoryRejected(_)));
     }
     
+    #[tokio::test]
+    async fn test_sensitive_path_rejected() {
+        let (mut tool, temp_dir) = setup_test_tool();
+        
+        let input = WriteInput {
+            file_path: ".env".to_string(),
+            content: "TOKEN=secret".to_string(),
+        };
+        
+        let result = tool.execute(input).await;
+        assert!(matches!(result.unwrap_err(), WriteError::SensitivePath(_)));
+        assert!(!temp_dir.path().join(".env").exists());
+    }
+    
     #[tokio::test]
     async fn test_parent_directory_creation() {
         let (mut tool, temp_dir) = setup_test_tool();
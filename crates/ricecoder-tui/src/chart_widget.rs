@@ -0,0 +1,690 @@
+//! Terminal charts for monitoring panels
+//!
+//! This module provides line, bar and histogram charts that render with
+//! braille dots (line) or block elements (bar, histogram), so they work in
+//! any terminal without a graphics protocol. Axes scale automatically to the
+//! visible data.
+//!
+//! [`MetricChart`] binds a chart to a [`MetricsCollector`] query and only
+//! appends points newer than its last refresh, and [`DashboardView`] lays the
+//! panels of a monitoring [`Dashboard`] out on screen.
+
+use std::collections::{HashMap, VecDeque};
+
+use chrono::{DateTime, TimeDelta, Utc};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{Block, Borders, Widget},
+};
+use ricecoder_monitoring::{
+    metrics::MetricsCollector,
+    types::{Dashboard, DataPoint, Panel, PanelType},
+};
+
+/// Default number of points kept per chart
+const DEFAULT_CAPACITY: usize = 512;
+
+/// Partial block characters, from 1/8 to 8/8 height
+const BAR_BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Braille dot bits indexed by `[row][column]` within a 2×4 cell
+const BRAILLE_DOTS: [[u8; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+/// Chart type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChartKind {
+    /// Braille line chart of the most recent values
+    Line,
+    /// One block bar per value, most recent on the right
+    Bar,
+    /// Distribution of values over a number of buckets
+    Histogram { buckets: usize },
+}
+
+impl ChartKind {
+    /// Chart type used for a dashboard panel type
+    pub fn for_panel(panel_type: PanelType) -> Self {
+        match panel_type {
+            PanelType::Graph => ChartKind::Line,
+            PanelType::Heatmap => ChartKind::Histogram { buckets: 10 },
+            PanelType::Table | PanelType::Gauge | PanelType::Stat => ChartKind::Bar,
+        }
+    }
+}
+
+/// Value range of a chart axis
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AxisRange {
+    /// Lower bound
+    pub min: f64,
+    /// Upper bound
+    pub max: f64,
+}
+
+impl AxisRange {
+    /// Range covering `values`, widened to round tick values
+    ///
+    /// Returns `0..1` when there are no finite values.
+    pub fn auto(values: impl IntoIterator<Item = f64>) -> Self {
+        let (min, max) = values
+            .into_iter()
+            .filter(|v| v.is_finite())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+                (lo.min(v), hi.max(v))
+            });
+        if min > max {
+            return Self { min: 0.0, max: 1.0 };
+        }
+        Self::nice(min, max)
+    }
+
+    /// Range from zero (or the minimum, if negative) to the maximum
+    pub fn auto_from_zero(values: impl IntoIterator<Item = f64>) -> Self {
+        let range = Self::auto(values);
+        Self {
+            min: range.min.min(0.0),
+            max: range.max.max(0.0),
+        }
+    }
+
+    /// Widen `min..max` to multiples of a round step
+    pub fn nice(min: f64, max: f64) -> Self {
+        let span = if max > min {
+            max - min
+        } else {
+            // Flat data: give it some room around the value
+            max.abs().max(1.0)
+        };
+        let step = nice_step(span / 5.0);
+        let mut lo = (min / step).floor() * step;
+        let mut hi = (max / step).ceil() * step;
+        if hi <= lo {
+            lo -= step;
+            hi += step;
+        }
+        Self { min: lo, max: hi }
+    }
+
+    /// Position of `value` within the range, clamped to `0.0..=1.0`
+    pub fn normalize(&self, value: f64) -> f64 {
+        if self.max <= self.min {
+            return 0.0;
+        }
+        ((value - self.min) / (self.max - self.min)).clamp(0.0, 1.0)
+    }
+}
+
+/// Round step size (1, 2 or 5 times a power of ten) close to `raw`
+fn nice_step(raw: f64) -> f64 {
+    if raw <= 0.0 || !raw.is_finite() {
+        return 1.0;
+    }
+    let magnitude = 10f64.powf(raw.log10().floor());
+    let fraction = raw / magnitude;
+    let nice = if fraction <= 1.0 {
+        1.0
+    } else if fraction <= 2.0 {
+        2.0
+    } else if fraction <= 5.0 {
+        5.0
+    } else {
+        10.0
+    };
+    nice * magnitude
+}
+
+/// Short axis label for a value
+pub fn format_axis_value(value: f64) -> String {
+    let abs = value.abs();
+    if abs >= 1_000_000_000.0 {
+        format!("{:.1}G", value / 1_000_000_000.0)
+    } else if abs >= 1_000_000.0 {
+        format!("{:.1}M", value / 1_000_000.0)
+    } else if abs >= 10_000.0 {
+        format!("{:.1}k", value / 1_000.0)
+    } else if abs >= 100.0 || value.fract() == 0.0 {
+        format!("{:.0}", value)
+    } else {
+        format!("{:.2}", value)
+    }
+}
+
+/// Bounded series of timestamped values
+#[derive(Debug, Clone)]
+pub struct ChartSeries {
+    points: VecDeque<(DateTime<Utc>, f64)>,
+    capacity: usize,
+}
+
+impl Default for ChartSeries {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl ChartSeries {
+    /// Create a series keeping at most `capacity` points
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            points: VecDeque::with_capacity(capacity.min(DEFAULT_CAPACITY)),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Append a value, dropping the oldest one when full
+    pub fn push(&mut self, timestamp: DateTime<Utc>, value: f64) {
+        if self.points.len() == self.capacity {
+            self.points.pop_front();
+        }
+        self.points.push_back((timestamp, value));
+    }
+
+    /// Drop points older than `cutoff`
+    pub fn retain_since(&mut self, cutoff: DateTime<Utc>) {
+        while self.points.front().is_some_and(|(ts, _)| *ts < cutoff) {
+            self.points.pop_front();
+        }
+    }
+
+    /// Values, oldest first
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = f64> + ExactSizeIterator + '_ {
+        self.points.iter().map(|(_, value)| *value)
+    }
+
+    /// Most recent point
+    pub fn latest(&self) -> Option<(DateTime<Utc>, f64)> {
+        self.points.back().copied()
+    }
+
+    /// Number of points
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Whether the series has no points
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Remove all points
+    pub fn clear(&mut self) {
+        self.points.clear();
+    }
+}
+
+/// Chart widget
+#[derive(Debug, Clone)]
+pub struct ChartWidget {
+    /// Chart type
+    pub kind: ChartKind,
+    /// Title shown in the border
+    pub title: Option<String>,
+    /// Data to plot
+    pub series: ChartSeries,
+    /// Color of the plotted data
+    pub color: Color,
+    /// Color of borders and axis labels
+    pub axis_color: Color,
+}
+
+impl ChartWidget {
+    /// Create an empty chart
+    pub fn new(kind: ChartKind) -> Self {
+        Self {
+            kind,
+            title: None,
+            series: ChartSeries::default(),
+            color: Color::Cyan,
+            axis_color: Color::DarkGray,
+        }
+    }
+
+    /// Set the title
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Set the data and axis colors
+    pub fn with_colors(mut self, color: Color, axis_color: Color) -> Self {
+        self.color = color;
+        self.axis_color = axis_color;
+        self
+    }
+
+    /// Set how many points are kept
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.series = ChartSeries::new(capacity);
+        self
+    }
+
+    /// Render the bordered chart into `buf`
+    fn render_chart(&self, area: Rect, buf: &mut Buffer) {
+        let mut block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.axis_color));
+        if let Some(title) = &self.title {
+            let latest = self
+                .series
+                .latest()
+                .map(|(_, value)| format!(" {} ", format_axis_value(value)))
+                .unwrap_or_default();
+            block = block.title(format!(" {}{}", title, latest));
+        }
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if inner.width < 4 || inner.height < 2 {
+            return;
+        }
+        if self.series.is_empty() {
+            buf.set_string(
+                inner.x,
+                inner.y,
+                "no data",
+                Style::default().fg(self.axis_color),
+            );
+            return;
+        }
+
+        match self.kind {
+            ChartKind::Line => {
+                let plot_width = inner.width as usize;
+                let samples = plot_width * 2;
+                let skip = self.series.len().saturating_sub(samples);
+                let values: Vec<f64> = self.series.values().skip(skip).collect();
+                let range = AxisRange::auto(values.iter().copied());
+                let plot = self.draw_y_axis(inner, range, buf);
+                render_braille_line(&values, range, plot, Style::default().fg(self.color), buf);
+            }
+            ChartKind::Bar => {
+                let values: Vec<f64> = self.series.values().collect();
+                let range = AxisRange::auto_from_zero(values.iter().copied());
+                let plot = self.draw_y_axis(inner, range, buf);
+                let skip = values.len().saturating_sub(plot.width as usize);
+                render_bars(
+                    &values[skip..],
+                    range,
+                    1,
+                    plot,
+                    Style::default().fg(self.color),
+                    buf,
+                );
+            }
+            ChartKind::Histogram { buckets } => {
+                let values: Vec<f64> = self.series.values().collect();
+                let value_range = AxisRange::auto(values.iter().copied());
+                let counts = histogram_counts(&values, value_range, buckets.max(1));
+                let count_range = AxisRange::auto_from_zero(counts.iter().copied());
+
+                // Keep the bottom row for the bucket range labels
+                let bars = Rect {
+                    height: inner.height - 1,
+                    ..inner
+                };
+                let plot = self.draw_y_axis(bars, count_range, buf);
+                let bar_width = (plot.width as usize / counts.len()).max(1);
+                render_bars(
+                    &counts,
+                    count_range,
+                    bar_width,
+                    plot,
+                    Style::default().fg(self.color),
+                    buf,
+                );
+
+                let label_style = Style::default().fg(self.axis_color);
+                let bottom = inner.y + inner.height - 1;
+                let max_label = format_axis_value(value_range.max);
+                buf.set_string(
+                    plot.x,
+                    bottom,
+                    format_axis_value(value_range.min),
+                    label_style,
+                );
+                let max_x = (plot.x + plot.width).saturating_sub(max_label.chars().count() as u16);
+                buf.set_string(max_x.max(plot.x), bottom, max_label, label_style);
+            }
+        }
+    }
+
+    /// Draw min/max labels on the left and return the remaining plot area
+    fn draw_y_axis(&self, area: Rect, range: AxisRange, buf: &mut Buffer) -> Rect {
+        let top = format_axis_value(range.max);
+        let bottom = format_axis_value(range.min);
+        let label_width = top.chars().count().max(bottom.chars().count()) as u16;
+        // Leave at least a few columns to plot into
+        if label_width + 2 > area.width.saturating_sub(2) || area.height < 2 {
+            return area;
+        }
+
+        let style = Style::default().fg(self.axis_color);
+        buf.set_string(
+            area.x,
+            area.y,
+            format!("{:>w$}", top, w = label_width as usize),
+            style,
+        );
+        buf.set_string(
+            area.x,
+            area.y + area.height - 1,
+            format!("{:>w$}", bottom, w = label_width as usize),
+            style,
+        );
+        for y in area.y..area.y + area.height {
+            buf.set_string(area.x + label_width, y, "┤", style);
+        }
+
+        Rect {
+            x: area.x + label_width + 1,
+            width: area.width - label_width - 1,
+            ..area
+        }
+    }
+}
+
+impl Widget for &ChartWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.render_chart(area, buf);
+    }
+}
+
+/// Plot `values` as a braille line, one dot column per value
+fn render_braille_line(
+    values: &[f64],
+    range: AxisRange,
+    area: Rect,
+    style: Style,
+    buf: &mut Buffer,
+) {
+    let dot_width = area.width as usize * 2;
+    let dot_height = area.height as usize * 4;
+    if values.is_empty() || dot_width == 0 || dot_height == 0 {
+        return;
+    }
+
+    let mut cells = vec![0u8; area.width as usize * area.height as usize];
+    let mut set_dot = |x: usize, y: usize| {
+        let cell = (y / 4) * area.width as usize + x / 2;
+        cells[cell] |= BRAILLE_DOTS[y % 4][x % 2];
+    };
+    let dot_y = |value: f64| {
+        let y = ((1.0 - range.normalize(value)) * (dot_height - 1) as f64).round();
+        y as usize
+    };
+
+    // Right-align so the newest value is at the right edge
+    let offset = dot_width.saturating_sub(values.len());
+    let mut previous: Option<usize> = None;
+    for (i, value) in values.iter().take(dot_width).enumerate() {
+        let x = offset + i;
+        let y = dot_y(*value);
+        // Fill vertically towards the previous dot so steep changes stay connected
+        let (from, to) = match previous {
+            Some(prev) if prev < y => (prev + 1, y),
+            Some(prev) if prev > y => (y, prev - 1),
+            _ => (y, y),
+        };
+        for fill_y in from..=to {
+            set_dot(x, fill_y);
+        }
+        previous = Some(y);
+    }
+
+    for (idx, bits) in cells.into_iter().enumerate() {
+        if bits == 0 {
+            continue;
+        }
+        let x = area.x + (idx % area.width as usize) as u16;
+        let y = area.y + (idx / area.width as usize) as u16;
+        if let Some(ch) = char::from_u32(0x2800 + bits as u32) {
+            buf.set_string(x, y, ch.to_string(), style);
+        }
+    }
+}
+
+/// Plot `values` as vertical block bars of `bar_width` columns, left to right
+fn render_bars(
+    values: &[f64],
+    range: AxisRange,
+    bar_width: usize,
+    area: Rect,
+    style: Style,
+    buf: &mut Buffer,
+) {
+    let rows = area.height as usize;
+    for (i, value) in values.iter().enumerate() {
+        let eighths = (range.normalize(*value) * (rows * 8) as f64).round() as usize;
+        for column in 0..bar_width {
+            let x = i * bar_width + column;
+            if x >= area.width as usize {
+                return;
+            }
+            for row in 0..rows {
+                let filled = eighths.saturating_sub(row * 8).min(8);
+                if filled == 0 {
+                    break;
+                }
+                let y = area.y + area.height - 1 - row as u16;
+                buf.set_string(
+                    area.x + x as u16,
+                    y,
+                    BAR_BLOCKS[filled - 1].to_string(),
+                    style,
+                );
+            }
+        }
+    }
+}
+
+/// Count `values` into `buckets` equal-width buckets over `range`
+pub fn histogram_counts(values: &[f64], range: AxisRange, buckets: usize) -> Vec<f64> {
+    let mut counts = vec![0.0; buckets.max(1)];
+    let last = counts.len() - 1;
+    for value in values.iter().filter(|v| v.is_finite()) {
+        let bucket = (range.normalize(*value) * counts.len() as f64) as usize;
+        counts[bucket.min(last)] += 1.0;
+    }
+    counts
+}
+
+/// Metric query a chart is bound to
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricQuery {
+    /// Metric name
+    pub metric: String,
+    /// Labels a data point must carry to be plotted
+    pub labels: HashMap<String, String>,
+    /// How far back to plot; `None` keeps points until the series is full
+    pub window: Option<TimeDelta>,
+}
+
+impl MetricQuery {
+    /// Query all points of a metric
+    pub fn new(metric: impl Into<String>) -> Self {
+        Self {
+            metric: metric.into(),
+            labels: HashMap::new(),
+            window: None,
+        }
+    }
+
+    /// Only plot points carrying this label
+    pub fn with_label(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.labels.insert(key.into(), value.into());
+        self
+    }
+
+    /// Only plot points within this window of now
+    pub fn with_window(mut self, window: TimeDelta) -> Self {
+        self.window = Some(window);
+        self
+    }
+
+    /// Whether a data point matches the label filter
+    fn matches(&self, point: &DataPoint) -> bool {
+        self.labels
+            .iter()
+            .all(|(key, value)| point.labels.get(key) == Some(value))
+    }
+}
+
+/// Chart bound to a [`MetricsCollector`] query
+#[derive(Debug, Clone)]
+pub struct MetricChart {
+    /// Query the chart plots
+    pub query: MetricQuery,
+    /// Chart being drawn
+    pub chart: ChartWidget,
+    /// Timestamp of the newest point already plotted
+    last_seen: Option<DateTime<Utc>>,
+}
+
+impl MetricChart {
+    /// Bind a chart to a query
+    pub fn new(query: MetricQuery, chart: ChartWidget) -> Self {
+        Self {
+            query,
+            chart,
+            last_seen: None,
+        }
+    }
+
+    /// Chart for a dashboard panel
+    pub fn from_panel(panel: &Panel) -> Self {
+        Self::new(
+            MetricQuery::new(&panel.query),
+            ChartWidget::new(ChartKind::for_panel(panel.panel_type)).with_title(&panel.title),
+        )
+    }
+
+    /// Append points recorded since the last refresh
+    ///
+    /// Returns the number of new points.
+    pub fn refresh(&mut self, collector: &MetricsCollector) -> usize {
+        self.refresh_at(collector, Utc::now())
+    }
+
+    /// [`refresh`](Self::refresh) with an explicit current time
+    pub fn refresh_at(&mut self, collector: &MetricsCollector, now: DateTime<Utc>) -> usize {
+        let window_start = self.query.window.map(|window| now - window);
+        let since = match (self.last_seen, window_start) {
+            (Some(last), Some(start)) => Some(last.max(start)),
+            (last, start) => last.or(start),
+        };
+
+        let mut added = 0;
+        for point in collector.get_metric_data(&self.query.metric, since) {
+            if self.last_seen.is_some_and(|last| point.timestamp <= last)
+                || !self.query.matches(&point)
+            {
+                continue;
+            }
+            self.chart.series.push(point.timestamp, point.value);
+            self.last_seen = Some(point.timestamp);
+            added += 1;
+        }
+
+        if let Some(start) = window_start {
+            self.chart.series.retain_since(start);
+        }
+        added
+    }
+}
+
+impl Widget for &MetricChart {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.chart.render_chart(area, buf);
+    }
+}
+
+/// Monitoring dashboard rendered as a grid of charts
+///
+/// Panel positions and sizes use the dashboard's 12-column grid; rows are
+/// scaled so the whole dashboard fits the render area.
+#[derive(Debug, Clone)]
+pub struct DashboardView {
+    /// Dashboard name
+    pub name: String,
+    panels: Vec<(Panel, MetricChart)>,
+}
+
+impl DashboardView {
+    /// Number of grid columns panel positions refer to
+    pub const GRID_COLUMNS: u32 = 12;
+
+    /// Create a view with one chart per dashboard panel
+    pub fn new(dashboard: &Dashboard) -> Self {
+        Self {
+            name: dashboard.name.clone(),
+            panels: dashboard
+                .panels
+                .iter()
+                .map(|panel| (panel.clone(), MetricChart::from_panel(panel)))
+                .collect(),
+        }
+    }
+
+    /// Set chart colors, typically from the active theme
+    pub fn with_colors(mut self, color: Color, axis_color: Color) -> Self {
+        for (_, chart) in &mut self.panels {
+            chart.chart.color = color;
+            chart.chart.axis_color = axis_color;
+        }
+        self
+    }
+
+    /// Charts of the dashboard
+    pub fn charts(&self) -> impl Iterator<Item = &MetricChart> {
+        self.panels.iter().map(|(_, chart)| chart)
+    }
+
+    /// Refresh every chart, returning the number of new points
+    pub fn refresh(&mut self, collector: &MetricsCollector) -> usize {
+        let now = Utc::now();
+        self.panels
+            .iter_mut()
+            .map(|(_, chart)| chart.refresh_at(collector, now))
+            .sum()
+    }
+
+    /// Screen area of a panel within `area`
+    fn panel_area(panel: &Panel, area: Rect, grid_rows: u32) -> Rect {
+        let scale_x = |col: u32| {
+            area.x + (col.min(Self::GRID_COLUMNS) * area.width as u32 / Self::GRID_COLUMNS) as u16
+        };
+        let scale_y =
+            |row: u32| area.y + (row.min(grid_rows) * area.height as u32 / grid_rows) as u16;
+        let x = scale_x(panel.position.x);
+        let y = scale_y(panel.position.y);
+        let right = scale_x(panel.position.x + panel.width);
+        let bottom = scale_y(panel.position.y + panel.height);
+        Rect {
+            x,
+            y,
+            width: right.saturating_sub(x),
+            height: bottom.saturating_sub(y),
+        }
+    }
+}
+
+impl Widget for &DashboardView {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let grid_rows = self
+            .panels
+            .iter()
+            .map(|(panel, _)| panel.position.y + panel.height)
+            .max()
+            .unwrap_or(1)
+            .max(1);
+
+        for (panel, chart) in &self.panels {
+            let panel_area = DashboardView::panel_area(panel, area, grid_rows);
+            if panel_area.width > 0 && panel_area.height > 0 {
+                chart.render(panel_area, buf);
+            }
+        }
+    }
+}
@@ -5,11 +5,20 @@
 //! - Terminal OSC 52 sequences for remote sessions
 //! - TMUX compatibility for wrapped sessions
 //! - Special content formatting for code, messages, and transcripts
+//! - A bounded history of copied and pasted text and images
 
-use std::io::{self, Write};
+use std::{
+    borrow::Cow,
+    collections::VecDeque,
+    io::{self, Write},
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
 
 use thiserror::Error;
 
+use crate::performance::HistoryLimits;
+
 /// Clipboard error types
 #[derive(Debug, Error)]
 pub enum ClipboardError {
@@ -44,6 +53,14 @@ pub enum ClipboardError {
     /// Invalid base64 encoding
     #[error("Invalid base64 encoding for OSC 52: {0}")]
     InvalidBase64(String),
+
+    /// Backend cannot carry images
+    #[error("The {0:?} clipboard backend does not support images")]
+    ImagesNotSupported(ClipboardBackend),
+
+    /// Clipboard holds nothing usable
+    #[error("Clipboard is empty")]
+    Empty,
 }
 
 /// Maximum clipboard content size (100 MB)
@@ -213,21 +230,204 @@ pub enum ClipboardBackend {
     Tmux,
 }
 
+/// RGBA image held by the clipboard
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClipboardImage {
+    /// Width in pixels
+    pub width: usize,
+    /// Height in pixels
+    pub height: usize,
+    /// RGBA8 pixel data, row by row
+    pub bytes: Arc<[u8]>,
+}
+
+impl ClipboardImage {
+    /// Create an image from RGBA8 pixel data
+    pub fn new(width: usize, height: usize, bytes: impl Into<Arc<[u8]>>) -> Self {
+        Self {
+            width,
+            height,
+            bytes: bytes.into(),
+        }
+    }
+
+    /// Encode the image as PNG
+    pub fn to_png(&self) -> Result<Vec<u8>, ClipboardError> {
+        let buffer =
+            image::RgbaImage::from_raw(self.width as u32, self.height as u32, self.bytes.to_vec())
+                .ok_or_else(|| ClipboardError::CopyError("Invalid image data".to_string()))?;
+
+        let mut png = Vec::new();
+        buffer
+            .write_to(&mut io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .map_err(|e| ClipboardError::CopyError(e.to_string()))?;
+        Ok(png)
+    }
+}
+
+/// Content of a clipboard history entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClipboardData {
+    /// Plain text
+    Text(String),
+    /// Image
+    Image(ClipboardImage),
+}
+
+impl ClipboardData {
+    /// Size of the content in bytes
+    pub fn size(&self) -> usize {
+        match self {
+            ClipboardData::Text(text) => text.len(),
+            ClipboardData::Image(image) => image.bytes.len(),
+        }
+    }
+
+    /// One-line description for lists (e.g. `"fn main() {…"` or `"Image 640×480"`)
+    pub fn summary(&self, max_chars: usize) -> String {
+        match self {
+            ClipboardData::Text(text) => {
+                let line = text.lines().find(|l| !l.trim().is_empty()).unwrap_or("");
+                let line = line.trim();
+                if line.chars().count() > max_chars || text.trim().lines().nth(1).is_some() {
+                    let cut: String = line.chars().take(max_chars.saturating_sub(1)).collect();
+                    format!("{}…", cut)
+                } else {
+                    line.to_string()
+                }
+            }
+            ClipboardData::Image(image) => format!("Image {}×{}", image.width, image.height),
+        }
+    }
+}
+
+/// Clipboard history entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClipboardEntry {
+    /// Copied or pasted content
+    pub data: ClipboardData,
+    /// When the content was last copied or pasted
+    pub recorded_at: SystemTime,
+}
+
+/// Bounded, most-recent-first clipboard history
+///
+/// Recording content that is already in the history moves it to the front
+/// instead of adding a duplicate.
+#[derive(Debug, Clone)]
+pub struct ClipboardHistory {
+    entries: VecDeque<ClipboardEntry>,
+    capacity: usize,
+}
+
+impl Default for ClipboardHistory {
+    fn default() -> Self {
+        Self::from_limits(&HistoryLimits::default())
+    }
+}
+
+impl ClipboardHistory {
+    /// Create a history keeping at most `capacity` entries
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    /// Create a history sized by the configured clipboard limit
+    pub fn from_limits(limits: &HistoryLimits) -> Self {
+        Self::new(limits.max_clipboard_entries)
+    }
+
+    /// Record content as the most recent entry
+    pub fn record(&mut self, data: ClipboardData) {
+        if self.capacity == 0 {
+            return;
+        }
+        if let ClipboardData::Text(text) = &data {
+            if text.is_empty() {
+                return;
+            }
+        }
+        self.entries.retain(|entry| entry.data != data);
+        self.entries.push_front(ClipboardEntry {
+            data,
+            recorded_at: SystemTime::now(),
+        });
+        self.entries.truncate(self.capacity);
+    }
+
+    /// Entry at `index`, where 0 is the most recent
+    pub fn get(&self, index: usize) -> Option<&ClipboardEntry> {
+        self.entries.get(index)
+    }
+
+    /// Most recent entry
+    pub fn latest(&self) -> Option<&ClipboardEntry> {
+        self.entries.front()
+    }
+
+    /// Entries, most recent first
+    pub fn iter(&self) -> impl Iterator<Item = &ClipboardEntry> {
+        self.entries.iter()
+    }
+
+    /// Remove the entry at `index`
+    pub fn remove(&mut self, index: usize) -> Option<ClipboardEntry> {
+        self.entries.remove(index)
+    }
+
+    /// Number of entries
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the history is empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Maximum number of entries kept
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Forget all entries
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
 /// Clipboard manager for copy operations with multiple backends
+///
+/// Everything copied or pasted through the manager is recorded in its
+/// [`ClipboardHistory`].
 pub struct ClipboardManager {
     backend: ClipboardBackend,
+    history: Mutex<ClipboardHistory>,
 }
 
 impl ClipboardManager {
     /// Create a new clipboard manager with auto-detected backend
     pub fn new() -> Self {
-        let backend = Self::detect_backend();
-        Self { backend }
+        Self::with_backend(Self::detect_backend())
     }
 
     /// Create clipboard manager with specific backend
     pub fn with_backend(backend: ClipboardBackend) -> Self {
-        Self { backend }
+        Self {
+            backend,
+            history: Mutex::new(ClipboardHistory::default()),
+        }
+    }
+
+    /// Use a history with the given capacity
+    pub fn with_history_capacity(self, capacity: usize) -> Self {
+        Self {
+            history: Mutex::new(ClipboardHistory::new(capacity)),
+            ..self
+        }
     }
 
     /// Detect the best available clipboard backend
@@ -259,6 +459,89 @@ impl ClipboardManager {
             ClipboardBackend::System => Self::copy_text_system(text),
             ClipboardBackend::Osc52 => Osc52Clipboard::copy_text(text),
             ClipboardBackend::Tmux => TmuxClipboard::copy_text(text),
+        }?;
+        self.record(ClipboardData::Text(text.to_string()));
+        Ok(())
+    }
+
+    /// Copy an image to the system clipboard
+    ///
+    /// Terminal backends only carry text, so this fails unless the backend
+    /// is [`ClipboardBackend::System`].
+    pub fn copy_image(&self, image: ClipboardImage) -> Result<(), ClipboardError> {
+        if self.backend != ClipboardBackend::System {
+            return Err(ClipboardError::ImagesNotSupported(self.backend));
+        }
+        if image.bytes.len() > MAX_CLIPBOARD_SIZE {
+            return Err(ClipboardError::ContentTooLarge(image.bytes.len()));
+        }
+
+        let mut clipboard =
+            arboard::Clipboard::new().map_err(|e| ClipboardError::AccessError(e.to_string()))?;
+        clipboard
+            .set_image(arboard::ImageData {
+                width: image.width,
+                height: image.height,
+                bytes: Cow::Borrowed(&image.bytes),
+            })
+            .map_err(|e| ClipboardError::CopyError(e.to_string()))?;
+        self.record(ClipboardData::Image(image));
+        Ok(())
+    }
+
+    /// Read the system clipboard for pasting, preferring images over text
+    ///
+    /// The pasted content is recorded in the history.
+    pub fn paste(&self) -> Result<ClipboardData, ClipboardError> {
+        let mut clipboard =
+            arboard::Clipboard::new().map_err(|e| ClipboardError::AccessError(e.to_string()))?;
+        let data = if let Ok(image) = clipboard.get_image() {
+            ClipboardData::Image(ClipboardImage::new(
+                image.width,
+                image.height,
+                image.bytes.into_owned(),
+            ))
+        } else {
+            match clipboard.get_text() {
+                Ok(text) if !text.is_empty() => ClipboardData::Text(text),
+                Ok(_) => return Err(ClipboardError::Empty),
+                Err(e) => return Err(ClipboardError::ReadError(e.to_string())),
+            }
+        };
+        self.record(data.clone());
+        Ok(data)
+    }
+
+    /// Record content in the history without touching the clipboard
+    ///
+    /// Used for content that arrives by other means, such as bracketed
+    /// paste from the terminal.
+    pub fn record(&self, data: ClipboardData) {
+        if let Ok(mut history) = self.history.lock() {
+            history.record(data);
+        }
+    }
+
+    /// Snapshot of the history, most recent first
+    pub fn history(&self) -> Vec<ClipboardEntry> {
+        self.history
+            .lock()
+            .map(|history| history.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// History entry at `index`, where 0 is the most recent
+    pub fn history_entry(&self, index: usize) -> Option<ClipboardEntry> {
+        self.history
+            .lock()
+            .ok()
+            .and_then(|history| history.get(index).cloned())
+    }
+
+    /// Forget the clipboard history
+    pub fn clear_history(&self) {
+        if let Ok(mut history) = self.history.lock() {
+            history.clear();
         }
     }
 
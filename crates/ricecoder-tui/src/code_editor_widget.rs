@@ -3,14 +3,16 @@
 //! This module wraps edtui's EditorState and EditorView to provide a vim-capable
 //! code editor widget compatible with ratatui 0.29.
 
-use edtui::{EditorEventHandler, EditorState, EditorView, Lines};
+use edtui::{EditorEventHandler, EditorMode, EditorState, EditorView, Lines};
 use ratatui::{
     buffer::Buffer,
-    crossterm::event::KeyEvent as CrosstermKeyEvent,
+    crossterm::event::{KeyCode, KeyEvent as CrosstermKeyEvent, KeyModifiers},
     layout::Rect,
     widgets::{Block, Borders, Widget},
 };
 
+use crate::undo_history::{EditKind, HistoryAction, UndoHistory};
+
 /// Syntax highlighting theme (re-exported for API compatibility)
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SyntaxTheme {
@@ -117,12 +119,26 @@ impl CodeLine {
     }
 }
 
+/// Editor buffer and cursor, as stored in the undo history
+#[derive(Debug, Clone)]
+pub struct EditorSnapshot {
+    lines: Lines,
+    cursor: (usize, usize),
+}
+
+impl PartialEq for EditorSnapshot {
+    fn eq(&self, other: &Self) -> bool {
+        self.lines == other.lines
+    }
+}
+
 /// Code editor widget wrapping edtui for full vim editing capabilities
 pub struct CodeEditorWidget {
     editor_state: EditorState,
     event_handler: EditorEventHandler,
     title: String,
     show_borders: bool,
+    undo_history: UndoHistory<EditorSnapshot>,
 }
 
 impl CodeEditorWidget {
@@ -133,14 +149,35 @@ impl CodeEditorWidget {
             event_handler: EditorEventHandler::default(),
             title: "Code".to_string(),
             show_borders: true,
+            undo_history: UndoHistory::default(),
         }
     }
 
     /// Set the code content
     pub fn set_content(&mut self, content: &str) {
+        self.undo_history.record(&self.snapshot(), EditKind::Replace);
         self.editor_state = EditorState::new(Lines::from(content));
     }
 
+    /// Load a file and place the cursor at `line` and `column` (1-based)
+    pub fn open_location(
+        &mut self,
+        path: &std::path::Path,
+        line: u32,
+        column: Option<u32>,
+    ) -> std::io::Result<()> {
+        let content = std::fs::read_to_string(path)?;
+        self.set_content(&content);
+        self.undo_history.clear();
+
+        let row = (line.max(1) as usize - 1).min(self.editor_state.lines.len().saturating_sub(1));
+        let line_len = self.editor_state.lines.len_col(row).unwrap_or(0);
+        self.editor_state.cursor.row = row;
+        self.editor_state.cursor.col = (column.unwrap_or(1).max(1) as usize - 1).min(line_len);
+        self.title = format!("{}:{}", path.display(), line);
+        Ok(())
+    }
+
     /// Get the code content
     pub fn get_content(&self) -> String {
         // Convert Lines back to String
@@ -151,9 +188,89 @@ impl CodeEditorWidget {
 
     /// Handle key event for vim keybindings
     pub fn handle_key_event(&mut self, key: CrosstermKeyEvent) {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let insert_mode = self.editor_state.mode == EditorMode::Insert;
+
+        // Vim undo/redo keys use the shared history
+        if self.editor_state.mode == EditorMode::Normal {
+            match key.code {
+                KeyCode::Char('u') if !ctrl => {
+                    self.undo();
+                    return;
+                }
+                KeyCode::Char('r') if ctrl => {
+                    self.redo();
+                    return;
+                }
+                _ => {}
+            }
+        }
+
+        let before = self.snapshot();
+
         // Convert crossterm KeyEvent to edtui KeyEvent
         let edtui_key = edtui::events::KeyEvent::from(key);
         self.event_handler.on_key_event(edtui_key, &mut self.editor_state);
+
+        if self.editor_state.lines != before.lines {
+            // Only typing in insert mode coalesces; normal-mode commands are one step each
+            let kind = match key.code {
+                KeyCode::Char(c) if insert_mode && !ctrl => EditKind::for_char(c),
+                KeyCode::Enter | KeyCode::Tab if insert_mode => EditKind::InsertSeparator,
+                KeyCode::Backspace | KeyCode::Delete if insert_mode => EditKind::Delete,
+                _ => EditKind::Replace,
+            };
+            self.undo_history.record(&before, kind);
+        } else if self.snapshot().cursor != before.cursor {
+            self.undo_history.break_group();
+        }
+    }
+
+    /// Undo the last edit step
+    pub fn undo(&mut self) -> bool {
+        self.apply_history(HistoryAction::Undo)
+    }
+
+    /// Redo the last undone edit step
+    pub fn redo(&mut self) -> bool {
+        self.apply_history(HistoryAction::Redo)
+    }
+
+    /// Handle an `input.undo`/`input.redo` keybind action
+    ///
+    /// Returns false for other actions.
+    pub fn handle_action(&mut self, action_id: &str) -> bool {
+        match HistoryAction::from_action_id(action_id) {
+            Some(action) => {
+                self.apply_history(action);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Set how many undo steps are kept
+    pub fn set_undo_depth(&mut self, depth: usize) {
+        self.undo_history.set_max_depth(depth);
+    }
+
+    /// Current buffer and cursor
+    fn snapshot(&self) -> EditorSnapshot {
+        EditorSnapshot {
+            lines: self.editor_state.lines.clone(),
+            cursor: (self.editor_state.cursor.row, self.editor_state.cursor.col),
+        }
+    }
+
+    /// Undo or redo, restoring buffer and cursor
+    fn apply_history(&mut self, action: HistoryAction) -> bool {
+        let Some(snapshot) = self.undo_history.apply(action, &self.snapshot()) else {
+            return false;
+        };
+        self.editor_state.lines = snapshot.lines;
+        self.editor_state.cursor.row = snapshot.cursor.0;
+        self.editor_state.cursor.col = snapshot.cursor.1;
+        true
     }
 
     /// Set the title
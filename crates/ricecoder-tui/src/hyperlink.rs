@@ -0,0 +1,439 @@
+//! Terminal hyperlinks and file-location references
+//!
+//! Finds URLs and `path:line:col` references in rendered text, underlines
+//! them and, on terminals that support it, wraps them in OSC 8 escape
+//! sequences so they can be clicked. File locations can also be opened by
+//! the application itself, in the built-in editor or an external one.
+//!
+//! OSC 8 sequences cannot be stored in ratatui buffer cells without breaking
+//! width calculations, so links are collected into a [`HyperlinkMap`] while
+//! rendering and written straight to the terminal after each frame.
+
+use std::io::{self, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use lazy_static::lazy_static;
+use ratatui::{
+    buffer::{Buffer, Cell},
+    crossterm::{
+        cursor::MoveTo,
+        queue,
+        style::{Attribute, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor},
+    },
+    layout::Rect,
+    style::{Modifier, Style},
+    text::Line,
+};
+use regex::Regex;
+use unicode_width::UnicodeWidthStr;
+
+lazy_static! {
+    static ref URL_PATTERN: Regex = Regex::new(r#"https?://[^\s<>"'`]+"#).unwrap();
+    static ref FILE_LOCATION_PATTERN: Regex = Regex::new(
+        r"(?:[A-Za-z]:[\\/]|~/|/)?(?:[\w.\-]+[\\/])*[\w\-][\w.\-]*\.[A-Za-z0-9]+:(\d+)(?::(\d+))?"
+    )
+    .unwrap();
+}
+
+/// Wrap `text` in an OSC 8 hyperlink to `url`
+pub fn osc8(url: &str, text: &str) -> String {
+    format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", url, text)
+}
+
+/// A `path:line[:col]` reference, as printed by compilers, linters and tools
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileLocation {
+    /// Path as written (relative paths are resolved against a base directory)
+    pub path: PathBuf,
+    /// Line number, 1-based
+    pub line: u32,
+    /// Column number, 1-based
+    pub column: Option<u32>,
+}
+
+impl FileLocation {
+    /// Parse a single `path:line[:col]` reference
+    pub fn parse(text: &str) -> Option<Self> {
+        let captures = FILE_LOCATION_PATTERN.captures(text.trim())?;
+        let whole = captures.get(0)?;
+        if whole.start() != 0 || whole.end() != text.trim().len() {
+            return None;
+        }
+        Self::from_captures(&captures)
+    }
+
+    fn from_captures(captures: &regex::Captures) -> Option<Self> {
+        let whole = captures.get(0)?.as_str();
+        let line_start = captures.get(1)?.start() - captures.get(0)?.start();
+        Some(Self {
+            path: PathBuf::from(&whole[..line_start - 1]),
+            line: captures[1].parse().ok()?,
+            column: captures.get(2).and_then(|c| c.as_str().parse().ok()),
+        })
+    }
+
+    /// Absolute path, resolving `~/` and paths relative to `base`
+    pub fn resolve(&self, base: &Path) -> PathBuf {
+        if let Ok(rest) = self.path.strip_prefix("~") {
+            if let Some(home) = dirs::home_dir() {
+                return home.join(rest);
+            }
+        }
+        if self.path.is_absolute() {
+            self.path.clone()
+        } else {
+            base.join(&self.path)
+        }
+    }
+
+    /// `file://` URL of the resolved path
+    pub fn url(&self, base: &Path) -> String {
+        let path = self.resolve(base);
+        let path = path.to_string_lossy().replace('\\', "/");
+        if path.starts_with('/') {
+            format!("file://{}", path)
+        } else {
+            format!("file:///{}", path)
+        }
+    }
+}
+
+impl std::fmt::Display for FileLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.path.display(), self.line)?;
+        if let Some(column) = self.column {
+            write!(f, ":{}", column)?;
+        }
+        Ok(())
+    }
+}
+
+/// What a link points at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkTarget {
+    /// Web URL
+    Url(String),
+    /// Location in a file
+    File(FileLocation),
+}
+
+impl LinkTarget {
+    /// URL for the OSC 8 sequence
+    pub fn url(&self, base: &Path) -> String {
+        match self {
+            LinkTarget::Url(url) => url.clone(),
+            LinkTarget::File(location) => location.url(base),
+        }
+    }
+}
+
+/// Find URLs and file locations in `text`, by byte range
+pub fn find_links(text: &str) -> Vec<(Range<usize>, LinkTarget)> {
+    let mut links: Vec<(Range<usize>, LinkTarget)> = URL_PATTERN
+        .find_iter(text)
+        .map(|m| {
+            let url = m
+                .as_str()
+                .trim_end_matches(['.', ',', ';', ':', '!', '?', ')', ']']);
+            (
+                m.start()..m.start() + url.len(),
+                LinkTarget::Url(url.to_string()),
+            )
+        })
+        .collect();
+
+    for captures in FILE_LOCATION_PATTERN.captures_iter(text) {
+        let Some(whole) = captures.get(0) else {
+            continue;
+        };
+        // Only whole words, and never inside a URL
+        let preceded_by_word = text[..whole.start()]
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_alphanumeric() || matches!(c, '/' | '.' | ':' | '_'));
+        let in_url = links
+            .iter()
+            .any(|(range, _)| range.start < whole.end() && whole.start() < range.end);
+        if preceded_by_word || in_url {
+            continue;
+        }
+        if let Some(location) = FileLocation::from_captures(&captures) {
+            links.push((whole.range(), LinkTarget::File(location)));
+        }
+    }
+
+    links.sort_by_key(|(range, _)| range.start);
+    links
+}
+
+/// A link within a block of lines
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkRegion {
+    /// Line index
+    pub row: usize,
+    /// First column
+    pub column: u16,
+    /// Width in columns
+    pub width: u16,
+    /// Link target
+    pub target: LinkTarget,
+}
+
+/// Rendered lines together with the links they contain
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LinkedLines {
+    /// Rendered lines
+    pub lines: Vec<Line<'static>>,
+    /// Links, by line index and column
+    pub links: Vec<LinkRegion>,
+}
+
+impl LinkedLines {
+    /// Append a line, recording the URLs and file locations in its text
+    pub fn push_scanned(&mut self, line: Line<'static>) {
+        let row = self.lines.len();
+        self.links.extend(scan_line(&line, row));
+        self.lines.push(line);
+    }
+}
+
+/// Links in the text of `line`, which is at index `row`
+pub fn scan_line(line: &Line, row: usize) -> Vec<LinkRegion> {
+    let text: String = line
+        .spans
+        .iter()
+        .map(|span| span.content.as_ref())
+        .collect();
+    find_links(&text)
+        .into_iter()
+        .map(|(range, target)| LinkRegion {
+            row,
+            column: text[..range.start].width() as u16,
+            width: text[range].width() as u16,
+            target,
+        })
+        .collect()
+}
+
+/// A link placed on screen
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScreenLink {
+    /// Screen row
+    pub y: u16,
+    /// First screen column
+    pub x: u16,
+    /// Link target
+    pub target: LinkTarget,
+    /// Rendered cells, rewritten inside the OSC 8 sequence
+    pub cells: Vec<Cell>,
+}
+
+impl ScreenLink {
+    /// Whether the link covers screen position `(x, y)`
+    pub fn contains(&self, x: u16, y: u16) -> bool {
+        y == self.y && x >= self.x && x < self.x + self.cells.len() as u16
+    }
+}
+
+/// Links visible on screen in the current frame
+///
+/// Widgets register the areas whose text may contain links with
+/// [`HyperlinkMap::watch`], or place links they laid out themselves with
+/// [`HyperlinkMap::place`]. [`HyperlinkMap::scan`] runs once the frame is
+/// complete, so overlays drawn on top are taken into account.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HyperlinkMap {
+    watched: Vec<Rect>,
+    placed: Vec<(Rect, LinkRegion)>,
+    links: Vec<ScreenLink>,
+}
+
+impl HyperlinkMap {
+    /// Create an empty map
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Look for links in the text of `area` when the frame is scanned
+    pub fn watch(&mut self, area: Rect) {
+        self.watched.push(area);
+    }
+
+    /// Place links laid out for `area`, whose first visible line is `first_row`
+    pub fn place(&mut self, area: Rect, first_row: usize, regions: &[LinkRegion]) {
+        for region in regions {
+            let Some(offset) = region.row.checked_sub(first_row) else {
+                continue;
+            };
+            if offset >= area.height as usize || region.column >= area.width {
+                continue;
+            }
+            let width = region.width.min(area.width - region.column);
+            self.placed.push((
+                Rect::new(area.x + region.column, area.y + offset as u16, width, 1),
+                region.clone(),
+            ));
+        }
+    }
+
+    /// Collect links from the finished frame and underline them
+    pub fn scan(&mut self, buf: &mut Buffer) {
+        self.links.clear();
+        let area = buf.area;
+
+        for watched in std::mem::take(&mut self.watched) {
+            let watched = watched.intersection(area);
+            for y in watched.top()..watched.bottom() {
+                // Cells hidden behind wide characters are skipped, so text
+                // widths map back onto columns
+                let mut text = String::new();
+                let mut x = watched.left();
+                while x < watched.right() {
+                    let symbol = buf[(x, y)].symbol();
+                    text.push_str(symbol);
+                    x += symbol.width().max(1) as u16;
+                }
+                let line = Line::from(text);
+                for region in scan_line(&line, 0) {
+                    let rect = Rect::new(watched.x + region.column, y, region.width, 1);
+                    self.add(buf, rect, region.target);
+                }
+            }
+        }
+        for (rect, region) in std::mem::take(&mut self.placed) {
+            self.add(buf, rect.intersection(area), region.target);
+        }
+    }
+
+    fn add(&mut self, buf: &mut Buffer, rect: Rect, target: LinkTarget) {
+        if rect.is_empty() {
+            return;
+        }
+        buf.set_style(rect, Style::default().add_modifier(Modifier::UNDERLINED));
+        self.links.push(ScreenLink {
+            y: rect.y,
+            x: rect.x,
+            target,
+            cells: (rect.left()..rect.right())
+                .map(|x| buf[(x, rect.y)].clone())
+                .collect(),
+        });
+    }
+
+    /// Links found by the last scan
+    pub fn links(&self) -> &[ScreenLink] {
+        &self.links
+    }
+
+    /// Link at screen position `(x, y)`
+    pub fn target_at(&self, x: u16, y: u16) -> Option<&LinkTarget> {
+        self.links
+            .iter()
+            .find(|link| link.contains(x, y))
+            .map(|link| &link.target)
+    }
+
+    /// Rewrite the links on the terminal wrapped in OSC 8 sequences
+    ///
+    /// Relative file paths are resolved against `base`.
+    pub fn write_osc8<W: Write>(&self, out: &mut W, base: &Path) -> io::Result<()> {
+        for link in &self.links {
+            queue!(out, MoveTo(link.x, link.y))?;
+            write!(out, "\x1b]8;;{}\x1b\\", link.target.url(base))?;
+            for cell in &link.cells {
+                queue!(
+                    out,
+                    SetForegroundColor(cell.fg.into()),
+                    SetBackgroundColor(cell.bg.into()),
+                )?;
+                if cell.modifier.contains(Modifier::BOLD) {
+                    queue!(out, SetAttribute(Attribute::Bold))?;
+                }
+                if cell.modifier.contains(Modifier::UNDERLINED) {
+                    queue!(out, SetAttribute(Attribute::Underlined))?;
+                }
+                write!(out, "{}", cell.symbol())?;
+            }
+            write!(out, "\x1b]8;;\x1b\\")?;
+            queue!(out, SetAttribute(Attribute::Reset), ResetColor)?;
+        }
+        out.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_links() {
+        let text = "error at src/main.rs:12:5, see https://example.com/docs. (lib.rs:3)";
+        let links = find_links(text);
+        assert_eq!(links.len(), 3);
+
+        assert_eq!(&text[links[0].0.clone()], "src/main.rs:12:5");
+        assert_eq!(
+            links[0].1,
+            LinkTarget::File(FileLocation {
+                path: PathBuf::from("src/main.rs"),
+                line: 12,
+                column: Some(5),
+            })
+        );
+        assert_eq!(
+            links[1].1,
+            LinkTarget::Url("https://example.com/docs".to_string())
+        );
+        assert_eq!(&text[links[2].0.clone()], "lib.rs:3");
+
+        // Times, ratios and URL ports are not file locations
+        assert!(find_links("at 12:30:45, ratio 3:2").is_empty());
+        assert_eq!(find_links("http://localhost:8080/app.js:1").len(), 1);
+    }
+
+    #[test]
+    fn test_file_location_url() {
+        let location = FileLocation::parse("crates/app/src/lib.rs:7").unwrap();
+        assert_eq!(location.column, None);
+        assert_eq!(
+            location.url(Path::new("/work")),
+            "file:///work/crates/app/src/lib.rs"
+        );
+        assert_eq!(location.to_string(), "crates/app/src/lib.rs:7");
+        assert!(FileLocation::parse("see lib.rs:7").is_none());
+        assert_eq!(
+            osc8("https://a.b", "a"),
+            "\x1b]8;;https://a.b\x1b\\a\x1b]8;;\x1b\\"
+        );
+    }
+
+    #[test]
+    fn test_scan_buffer_and_write_osc8() {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 30, 2));
+        buf.set_string(0, 0, "  --> src/lib.rs:4:1", Style::default());
+        buf.set_string(0, 1, "plain text", Style::default());
+
+        let mut map = HyperlinkMap::new();
+        map.watch(Rect::new(0, 0, 30, 2));
+        map.scan(&mut buf);
+
+        assert_eq!(map.links().len(), 1);
+        assert!(map.target_at(6, 0).is_some());
+        assert!(map.target_at(5, 0).is_none());
+        assert!(buf[(6, 0)].modifier.contains(Modifier::UNDERLINED));
+
+        let mut out = Vec::new();
+        map.write_osc8(&mut out, Path::new("/repo")).unwrap();
+        let written = String::from_utf8(out).unwrap();
+        let open = written
+            .find("\x1b]8;;file:///repo/src/lib.rs\x1b\\")
+            .unwrap();
+        let close = written.rfind("\x1b]8;;\x1b\\").unwrap();
+        let linked: String = written[open..close]
+            .split('\x1b')
+            .filter_map(|part| part.rsplit_once('m').map(|(_, text)| text))
+            .collect();
+        assert_eq!(linked, "src/lib.rs:4:1");
+    }
+}
@@ -1,5 +1,7 @@
 //! Input handling for the TUI
 
+use crate::undo_history::{EditKind, HistoryAction, TextSnapshot, UndoHistory};
+
 /// Intent types for natural language input
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Intent {
@@ -99,6 +101,8 @@ pub struct ChatInputWidget {
     pub history_index: Option<usize>,
     /// Detected intent
     pub intent: Intent,
+    /// Undo/redo history of the current input
+    pub undo_history: UndoHistory<TextSnapshot>,
 }
 
 impl ChatInputWidget {
@@ -110,11 +114,14 @@ impl ChatInputWidget {
             history: Vec::new(),
             history_index: None,
             intent: Intent::Chat,
+            undo_history: UndoHistory::default(),
         }
     }
 
     /// Insert character at cursor
     pub fn insert_char(&mut self, ch: char) {
+        self.undo_history
+            .record(&self.snapshot(), EditKind::for_char(ch));
         self.text.insert(self.cursor, ch);
         self.cursor += 1;
         self.update_intent();
@@ -123,6 +130,7 @@ impl ChatInputWidget {
     /// Delete character before cursor
     pub fn backspace(&mut self) {
         if self.cursor > 0 {
+            self.undo_history.record(&self.snapshot(), EditKind::Delete);
             self.text.remove(self.cursor - 1);
             self.cursor -= 1;
             self.update_intent();
@@ -132,6 +140,7 @@ impl ChatInputWidget {
     /// Delete character at cursor
     pub fn delete(&mut self) {
         if self.cursor < self.text.len() {
+            self.undo_history.record(&self.snapshot(), EditKind::Delete);
             self.text.remove(self.cursor);
             self.update_intent();
         }
@@ -139,6 +148,7 @@ impl ChatInputWidget {
 
     /// Move cursor left
     pub fn move_left(&mut self) {
+        self.undo_history.break_group();
         if self.cursor > 0 {
             self.cursor -= 1;
         }
@@ -146,6 +156,7 @@ impl ChatInputWidget {
 
     /// Move cursor right
     pub fn move_right(&mut self) {
+        self.undo_history.break_group();
         if self.cursor < self.text.len() {
             self.cursor += 1;
         }
@@ -153,11 +164,13 @@ impl ChatInputWidget {
 
     /// Move cursor to start
     pub fn move_start(&mut self) {
+        self.undo_history.break_group();
         self.cursor = 0;
     }
 
     /// Move cursor to end
     pub fn move_end(&mut self) {
+        self.undo_history.break_group();
         self.cursor = self.text.len();
     }
 
@@ -169,14 +182,69 @@ impl ChatInputWidget {
         self.cursor = 0;
         self.history_index = None;
         self.intent = Intent::Chat;
+        // The submitted text lives on in the input history
+        self.undo_history.clear();
         input
     }
 
+    /// Clear the input
+    ///
+    /// The cleared text can be brought back with [`undo`](Self::undo).
+    pub fn clear(&mut self) {
+        if self.text.is_empty() {
+            return;
+        }
+        self.undo_history.record(&self.snapshot(), EditKind::Replace);
+        self.text.clear();
+        self.cursor = 0;
+        self.update_intent();
+    }
+
+    /// Undo the last edit step
+    pub fn undo(&mut self) -> bool {
+        self.apply_history(HistoryAction::Undo)
+    }
+
+    /// Redo the last undone edit step
+    pub fn redo(&mut self) -> bool {
+        self.apply_history(HistoryAction::Redo)
+    }
+
+    /// Handle an `input.undo`/`input.redo` keybind action
+    ///
+    /// Returns false for other actions.
+    pub fn handle_action(&mut self, action_id: &str) -> bool {
+        match HistoryAction::from_action_id(action_id) {
+            Some(action) => {
+                self.apply_history(action);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Current text and cursor
+    fn snapshot(&self) -> TextSnapshot {
+        TextSnapshot::single_line(&self.text, self.cursor)
+    }
+
+    /// Undo or redo, restoring text and cursor
+    fn apply_history(&mut self, action: HistoryAction) -> bool {
+        let Some(snapshot) = self.undo_history.apply(action, &self.snapshot()) else {
+            return false;
+        };
+        self.text = snapshot.text();
+        self.cursor = snapshot.cursor.1.min(self.text.len());
+        self.update_intent();
+        true
+    }
+
     /// Navigate history up
     pub fn history_up(&mut self) {
         if self.history.is_empty() {
             return;
         }
+        self.undo_history.record(&self.snapshot(), EditKind::Replace);
 
         match self.history_index {
             None => {
@@ -195,6 +263,9 @@ impl ChatInputWidget {
 
     /// Navigate history down
     pub fn history_down(&mut self) {
+        if self.history_index.is_some() {
+            self.undo_history.record(&self.snapshot(), EditKind::Replace);
+        }
         match self.history_index {
             Some(idx) if idx < self.history.len() - 1 => {
                 self.history_index = Some(idx + 1);
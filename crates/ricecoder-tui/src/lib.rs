@@ -35,6 +35,7 @@
 
 // === Core Modules (keep) ===
 pub mod banner;
+pub mod chart_widget;
 pub mod clipboard;
 pub mod code_editor_widget;
 pub mod command_blocks;
@@ -60,6 +61,7 @@ pub mod model {
 }
 
 // === Utility Modules (keep) ===
+pub mod hyperlink;
 pub mod image_integration;
 pub mod image_widget;
 pub mod input;
@@ -74,18 +76,27 @@ pub mod project_bootstrap;
 pub mod real_time_updates;
 pub mod scrollview_widget;
 pub mod status_bar;
+pub mod status_segments;
 pub mod style;
 pub mod terminal_state;
 pub mod textarea_widget;
 pub mod theme;
+pub mod transcript;
 pub mod tree_widget;
 pub mod ui_components;
+pub mod undo_history;
 
 // Re-export commonly used types
 // Accessibility exports removed - depends on old TEA system
 // pub use accessibility::{...};
 pub use banner::{BannerArea, BannerComponent, BannerComponentConfig};
-pub use clipboard::{ClipboardError, ClipboardManager, CopyFeedback, CopyOperation};
+pub use chart_widget::{
+    AxisRange, ChartKind, ChartSeries, ChartWidget, DashboardView, MetricChart, MetricQuery,
+};
+pub use clipboard::{
+    ClipboardData, ClipboardEntry, ClipboardError, ClipboardHistory, ClipboardImage,
+    ClipboardManager, CopyFeedback, CopyOperation,
+};
 pub use code_editor_widget::{CodeEditorWidget, CodeLine, Language, SyntaxTheme};
 pub use command_blocks::{Command, CommandBlock, CommandBlocksWidget, CommandStatus};
 // Old TEA system exports removed
@@ -94,6 +105,7 @@ pub use command_blocks::{Command, CommandBlock, CommandBlocksWidget, CommandStat
 // pub use diff::{DiffHunk, DiffLine, DiffLineType, DiffViewType, DiffWidget};
 pub use error::{KeybindError, StorageError, ToolError, TuiError, TuiResult};
 // pub use file_picker::FilePickerWidget; // Old TEA system
+pub use hyperlink::{FileLocation, HyperlinkMap, LinkRegion, LinkTarget, LinkedLines, ScreenLink};
 pub use image_integration::ImageIntegration;
 pub use image_widget::{ImageFormat, ImageWidget, RenderMode};
 pub use input::{ChatInputWidget, InputAnalyzer, Intent};
@@ -125,7 +137,10 @@ pub use popup_widget::{PopupButton, PopupType, PopupWidget};
 pub use progressive_enhancement::{
     FeatureLevel, FeatureToggles, ProgressiveEnhancement, RenderingStrategy,
 };
-pub use project_bootstrap::{BootstrapResult, ProjectBootstrap, ProjectInfo};
+pub use project_bootstrap::{
+    BootstrapChoices, BootstrapResult, LspSuggestion, ProjectBootstrap, ProjectDetection,
+    ProjectInfo, BOOTSTRAP_MODES, PROJECT_CONFIG_DIR,
+};
 // Old TEA system exports removed
 // pub use prompt::{ContextIndicators, PromptConfig, PromptWidget};
 // pub use reactive_ui_updates::{...};
@@ -139,13 +154,22 @@ pub use ricecoder_storage::config::TuiConfig;
 pub use scrollview_widget::ScrollViewWidget;
 // Session exports moved to ricecoder-sessions crate
 pub use ricecoder_themes::Theme;
+pub use status_segments::{
+    JobsSegment, LspHealth, LspHealthSegment, LspServerStatus, ModeSegment, ModelSegment,
+    PlacedSegment, SegmentContent, StatusBar, StatusContext, StatusSegment, TokenUsageSegment,
+    VcsSegment, VcsState,
+};
 pub use style::ColorSupport;
 pub use terminal_state::{
     ColorSupport as TerminalColorSupport, TerminalCapabilities, TerminalState, TerminalType,
 };
 pub use textarea_widget::TextAreaWidget;
 pub use tokio_util::sync::CancellationToken;
+pub use transcript::{
+    ToolCallBlock, ToolOutput, ToolOutputSource, TranscriptEntry, TranscriptView, TranscriptWidget,
+};
 pub use tree_widget::{TreeNode, TreeWidget};
+pub use undo_history::{EditKind, HistoryAction, TextSnapshot, UndoHistory};
 pub use ui_components::{
     LoadingManager, OptimisticUpdater, VirtualContent, VirtualList, VirtualNode, VirtualRenderer,
     VirtualStyle,
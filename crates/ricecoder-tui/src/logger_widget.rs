@@ -1,10 +1,18 @@
 //! Logger widget for displaying debug and info logs
 //!
 //! This module provides a widget for displaying application logs with filtering by level,
-//! scrolling, and search capabilities.
+//! scrolling, and search capabilities. URLs and `path:line:col` references in
+//! messages are returned as links for hyperlink rendering.
 
 use std::collections::VecDeque;
 
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+};
+
+use crate::hyperlink::LinkedLines;
+
 /// Log level
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum LogLevel {
@@ -42,6 +50,17 @@ impl LogLevel {
             LogLevel::Error => "\x1b[31m", // Red
         }
     }
+
+    /// Get the display color for the level
+    pub fn color(&self) -> Color {
+        match self {
+            LogLevel::Trace => Color::DarkGray,
+            LogLevel::Debug => Color::Cyan,
+            LogLevel::Info => Color::Green,
+            LogLevel::Warn => Color::Yellow,
+            LogLevel::Error => Color::Red,
+        }
+    }
 }
 
 /// Log entry
@@ -85,6 +104,19 @@ impl LogEntry {
             self.message
         )
     }
+
+    /// Get the log line with the level colored
+    pub fn to_line(&self) -> Line<'static> {
+        let module = self.module.as_deref().unwrap_or("app");
+        Line::from(vec![
+            Span::styled(
+                format!("[{}] ", self.timestamp),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(self.level.as_str(), Style::default().fg(self.level.color())),
+            Span::raw(format!(" {} - {}", module, self.message)),
+        ])
+    }
 }
 
 /// Logger widget for displaying logs
@@ -205,6 +237,15 @@ impl LoggerWidget {
             .collect()
     }
 
+    /// Get the visible logs as styled lines, with the links they contain
+    pub fn visible_lines(&self, height: usize) -> LinkedLines {
+        let mut rendered = LinkedLines::default();
+        for entry in self.visible_logs(height) {
+            rendered.push_scanned(entry.to_line());
+        }
+        rendered
+    }
+
     /// Set the minimum log level
     pub fn set_min_level(&mut self, level: LogLevel) {
         self.min_level = level;
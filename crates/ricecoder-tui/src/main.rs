@@ -3,13 +3,17 @@
 //! Modern TUI implementation using route-based architecture and AppContext backend wiring.
 
 use anyhow::Result;
-use ricecoder_storage::DefaultsManager;
+use ricecoder_beta::improvement::CrashReporter;
+use ricecoder_storage::{ConfigLoader, DefaultsManager};
+use ricecoder_tui::ProjectBootstrap;
 
 #[tokio::main]
 async fn main() -> Result<()> {
     // Initialize storage defaults (folder structure, default configs)
     // This is idempotent - won't overwrite existing user files
+    let mut first_run = false;
     if let Ok(defaults_manager) = DefaultsManager::with_default_path() {
+        first_run = defaults_manager.is_first_run();
         if let Err(e) = defaults_manager.initialize() {
             eprintln!("Warning: Failed to initialize defaults: {}", e);
             // Continue anyway - this is not fatal
@@ -23,8 +27,19 @@ async fn main() -> Result<()> {
 
     tracing::info!("Starting RiceCoder TUI...");
 
+    // Capture crashes locally when the user has consented to telemetry
+    if let Ok(config) = ConfigLoader::new().load_merged() {
+        CrashReporter::install_from_settings(&config.custom, env!("CARGO_PKG_VERSION"));
+    }
+
     // Create and run the TUI application
     let mut app = ricecoder_tui::tui::TuiApp::new()?;
+
+    // Walk new users through project setup unless it is already configured
+    let project = ProjectBootstrap::new(std::env::current_dir()?);
+    if first_run && project.needs_setup() {
+        app.open_bootstrap_wizard();
+    }
     
     // Run the event loop
     match app.run().await {
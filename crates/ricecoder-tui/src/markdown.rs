@@ -8,6 +8,9 @@ use syntect::{
     parsing::SyntaxSet,
     util::LinesWithEndings,
 };
+use unicode_width::UnicodeWidthStr;
+
+use crate::hyperlink::{find_links, FileLocation, LinkRegion, LinkTarget, LinkedLines};
 
 lazy_static! {
     pub static ref SYNTAX_SET: SyntaxSet = SyntaxSet::load_defaults_newlines();
@@ -270,6 +273,160 @@ impl MarkdownParser {
         output
     }
 
+    /// Render markdown elements to styled lines
+    ///
+    /// Links, bare URLs and `path:line:col` references are underlined and
+    /// returned as link regions, ready for a [`crate::hyperlink::HyperlinkMap`].
+    pub fn render_lines(elements: &[MarkdownElement]) -> LinkedLines {
+        let link_style = Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::UNDERLINED);
+        let mut rendered = LinkedLines::default();
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        let mut column: u16 = 0;
+
+        fn push_text(
+            spans: &mut Vec<Span<'static>>,
+            column: &mut u16,
+            links: &mut Vec<LinkRegion>,
+            row: usize,
+            text: &str,
+            style: Style,
+        ) {
+            for (range, target) in find_links(text) {
+                links.push(LinkRegion {
+                    row,
+                    column: *column + text[..range.start].width() as u16,
+                    width: text[range].width() as u16,
+                    target,
+                });
+            }
+            *column += text.width() as u16;
+            spans.push(Span::styled(text.to_string(), style));
+        }
+
+        let finish_line =
+            |rendered: &mut LinkedLines, spans: &mut Vec<Span<'static>>, column: &mut u16| {
+                rendered.lines.push(Line::from(std::mem::take(spans)));
+                *column = 0;
+            };
+
+        for element in elements {
+            let row = rendered.lines.len();
+            match element {
+                MarkdownElement::Text(text) => {
+                    push_text(
+                        &mut spans,
+                        &mut column,
+                        &mut rendered.links,
+                        row,
+                        text,
+                        Style::default(),
+                    );
+                }
+                MarkdownElement::Bold(text) => {
+                    let style = Style::default().add_modifier(Modifier::BOLD);
+                    push_text(
+                        &mut spans,
+                        &mut column,
+                        &mut rendered.links,
+                        row,
+                        text,
+                        style,
+                    );
+                }
+                MarkdownElement::Italic(text) => {
+                    let style = Style::default().add_modifier(Modifier::ITALIC);
+                    push_text(
+                        &mut spans,
+                        &mut column,
+                        &mut rendered.links,
+                        row,
+                        text,
+                        style,
+                    );
+                }
+                MarkdownElement::Code(text) => {
+                    let style = Style::default().fg(Color::Yellow);
+                    push_text(
+                        &mut spans,
+                        &mut column,
+                        &mut rendered.links,
+                        row,
+                        text,
+                        style,
+                    );
+                }
+                MarkdownElement::Link(text, url) => {
+                    let target = FileLocation::parse(url)
+                        .map(LinkTarget::File)
+                        .unwrap_or_else(|| LinkTarget::Url(url.clone()));
+                    rendered.links.push(LinkRegion {
+                        row,
+                        column,
+                        width: text.width() as u16,
+                        target,
+                    });
+                    column += text.width() as u16;
+                    spans.push(Span::styled(text.clone(), link_style));
+                }
+                MarkdownElement::Header(_, content) => {
+                    if !spans.is_empty() {
+                        finish_line(&mut rendered, &mut spans, &mut column);
+                    }
+                    let style = Style::default().add_modifier(Modifier::BOLD);
+                    let row = rendered.lines.len();
+                    push_text(
+                        &mut spans,
+                        &mut column,
+                        &mut rendered.links,
+                        row,
+                        content,
+                        style,
+                    );
+                    finish_line(&mut rendered, &mut spans, &mut column);
+                }
+                MarkdownElement::ListItem(content) => {
+                    if !spans.is_empty() {
+                        finish_line(&mut rendered, &mut spans, &mut column);
+                    }
+                    let row = rendered.lines.len();
+                    push_text(
+                        &mut spans,
+                        &mut column,
+                        &mut rendered.links,
+                        row,
+                        "• ",
+                        Style::default(),
+                    );
+                    push_text(
+                        &mut spans,
+                        &mut column,
+                        &mut rendered.links,
+                        row,
+                        content,
+                        Style::default(),
+                    );
+                    finish_line(&mut rendered, &mut spans, &mut column);
+                }
+                MarkdownElement::CodeBlock(lang, code) => {
+                    if !spans.is_empty() {
+                        finish_line(&mut rendered, &mut spans, &mut column);
+                    }
+                    rendered
+                        .lines
+                        .extend(Self::highlight(code, lang.as_deref()));
+                }
+                MarkdownElement::Newline => finish_line(&mut rendered, &mut spans, &mut column),
+            }
+        }
+        if !spans.is_empty() {
+            finish_line(&mut rendered, &mut spans, &mut column);
+        }
+
+        rendered
+    }
+
     /// Highlight code block using syntect
     pub fn highlight(code: &str, lang: Option<&str>) -> Vec<Line<'static>> {
         let ps = &SYNTAX_SET;
@@ -9,6 +9,7 @@ use std::{
     time::{Duration, Instant},
 };
 
+use ricecoder_performance::MemoryAttributionReport;
 use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 
@@ -380,6 +381,7 @@ pub struct MonitoringSystem {
     metrics_collector: MetricsCollector,
     profiler: std::sync::Arc<std::sync::RwLock<PerformanceProfiler>>,
     ux_metrics: UserExperienceMetrics,
+    memory_attribution: Option<MemoryAttributionReport>,
     enabled: bool,
 }
 
@@ -392,6 +394,7 @@ impl MonitoringSystem {
             metrics_collector: MetricsCollector::new(),
             profiler: std::sync::Arc::new(std::sync::RwLock::new(PerformanceProfiler::new())),
             ux_metrics: UserExperienceMetrics::new(),
+            memory_attribution: None,
             enabled: true,
         }
     }
@@ -472,6 +475,25 @@ impl MonitoringSystem {
         }
     }
 
+    /// Record a per-subsystem memory attribution report
+    pub fn record_memory_attribution(&mut self, report: MemoryAttributionReport) {
+        if self.enabled {
+            self.record_memory_usage(report.process_bytes as usize);
+            for usage in &report.subsystems {
+                self.metrics_collector.record_metric(
+                    &format!("memory.{}", usage.subsystem),
+                    usage.current_bytes as f64,
+                );
+            }
+            self.memory_attribution = Some(report);
+        }
+    }
+
+    /// Latest memory attribution report
+    pub fn memory_attribution(&self) -> Option<&MemoryAttributionReport> {
+        self.memory_attribution.as_ref()
+    }
+
     /// Generate monitoring report
     pub fn generate_report(&self) -> MonitoringReport {
         MonitoringReport {
@@ -479,6 +501,7 @@ impl MonitoringSystem {
             analytics: self.usage_analytics.generate_report(),
             ux_metrics: self.ux_metrics.generate_report(),
             metrics: self.metrics_collector.get_snapshot(),
+            memory_attribution: self.memory_attribution.clone(),
             timestamp: std::time::SystemTime::now(),
         }
     }
@@ -1212,6 +1235,7 @@ pub struct MonitoringReport {
     pub analytics: AnalyticsReport,
     pub ux_metrics: UserExperienceReport,
     pub metrics: HashMap<String, f64>,
+    pub memory_attribution: Option<MemoryAttributionReport>,
     pub timestamp: std::time::SystemTime,
 }
 
@@ -1299,6 +1323,17 @@ impl MonitoringReport {
             self.ux_metrics.feature_adoption_count
         ));
 
+        if let Some(memory) = &self.memory_attribution {
+            output.push_str("\n## Memory by Subsystem\n");
+            output.push_str(&memory.to_text());
+            for usage in memory.suspected_leaks() {
+                output.push_str(&format!(
+                    "- Possible leak: {} grew monotonically\n",
+                    usage.subsystem
+                ));
+            }
+        }
+
         output.push_str("\n## System Metrics\n");
         for (key, value) in &self.metrics {
             output.push_str(&format!("- {}: {:.2}\n", key, value));
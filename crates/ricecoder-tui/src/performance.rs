@@ -374,6 +374,12 @@ impl MemoryTracker {
 }
 
 /// Virtual scrolling manager for large content
+///
+/// Item heights are cached per render width; items that have not been
+/// measured yet are estimated with the average of the measured ones. Line
+/// offsets are kept as prefix sums that are only rebuilt after a height
+/// actually changes, so locating the item under a scroll position is a
+/// binary search rather than a walk over every item.
 #[derive(Debug)]
 pub struct VirtualScrollManager {
     /// Total number of items
@@ -386,6 +392,14 @@ pub struct VirtualScrollManager {
     pub item_heights: HashMap<usize, u16>,
     /// Average item height for estimation
     pub average_item_height: u16,
+    /// Width the cached heights were measured at
+    measured_width: u16,
+    /// Sum of all cached heights
+    measured_total: u64,
+    /// Line offset of each item, plus the total height as the last entry
+    line_offsets: Vec<usize>,
+    /// Whether `line_offsets` must be rebuilt
+    offsets_dirty: bool,
 }
 
 impl VirtualScrollManager {
@@ -397,6 +411,10 @@ impl VirtualScrollManager {
             scroll_offset: 0,
             item_heights: HashMap::new(),
             average_item_height: 1, // Default to 1 line per item
+            measured_width: 0,
+            measured_total: 0,
+            line_offsets: Vec::new(),
+            offsets_dirty: true,
         }
     }
 
@@ -433,17 +451,105 @@ impl VirtualScrollManager {
         index >= start && index < end
     }
 
+    /// Change the number of items, forgetting heights of removed items
+    pub fn set_total_items(&mut self, total_items: usize) {
+        if total_items < self.total_items {
+            let removed: Vec<usize> = self
+                .item_heights
+                .keys()
+                .copied()
+                .filter(|&index| index >= total_items)
+                .collect();
+            for index in removed {
+                self.invalidate_item(index);
+            }
+        }
+        self.total_items = total_items;
+        self.scroll_offset = self.scroll_offset.min(self.max_scroll_offset());
+        self.offsets_dirty = true;
+    }
+
+    /// Set the width heights are measured at
+    ///
+    /// Wrapped heights depend on the width, so a change drops every cached
+    /// height. Returns whether the cache was invalidated.
+    pub fn set_width(&mut self, width: u16) -> bool {
+        if width == self.measured_width {
+            return false;
+        }
+        self.measured_width = width;
+        self.clear_heights();
+        true
+    }
+
     /// Record item height for performance optimization
     pub fn record_item_height(&mut self, index: usize, height: u16) {
-        self.item_heights.insert(index, height);
-        // Update average height
-        let total_height: u32 = self.item_heights.values().map(|&h| h as u32).sum();
-        self.average_item_height = (total_height / self.item_heights.len() as u32) as u16;
+        let previous = self.item_heights.insert(index, height);
+        if previous == Some(height) {
+            return;
+        }
+        self.measured_total -= previous.unwrap_or(0) as u64;
+        self.measured_total += height as u64;
+        self.update_average();
+        self.offsets_dirty = true;
+    }
+
+    /// Cached height of an item, if it has been measured
+    pub fn cached_height(&self, index: usize) -> Option<u16> {
+        self.item_heights.get(&index).copied()
+    }
+
+    /// Height of an item, estimated when it has not been measured
+    pub fn item_height(&self, index: usize) -> u16 {
+        self.cached_height(index)
+            .unwrap_or(self.average_item_height)
+    }
+
+    /// Forget the cached height of an item whose content changed
+    pub fn invalidate_item(&mut self, index: usize) {
+        if let Some(height) = self.item_heights.remove(&index) {
+            self.measured_total -= height as u64;
+            self.update_average();
+            self.offsets_dirty = true;
+        }
+    }
+
+    /// Forget all cached heights
+    pub fn clear_heights(&mut self) {
+        self.item_heights.clear();
+        self.measured_total = 0;
+        self.average_item_height = 1;
+        self.offsets_dirty = true;
+    }
+
+    /// Line at which an item starts
+    pub fn item_offset(&mut self, index: usize) -> usize {
+        self.ensure_offsets();
+        self.line_offsets[index.min(self.total_items)]
+    }
+
+    /// Index of the item covering `line`
+    ///
+    /// Lines past the end map to the last item.
+    pub fn item_at_line(&mut self, line: usize) -> usize {
+        self.ensure_offsets();
+        if self.total_items == 0 {
+            return 0;
+        }
+        let index = self.line_offsets.partition_point(|&offset| offset <= line);
+        index.saturating_sub(1).min(self.total_items - 1)
+    }
+
+    /// Total content height in lines, using estimates for unmeasured items
+    pub fn total_height(&mut self) -> usize {
+        self.ensure_offsets();
+        self.line_offsets[self.total_items]
     }
 
     /// Estimate total content height
     pub fn estimated_total_height(&self) -> usize {
-        self.total_items * self.average_item_height as usize
+        let unmeasured = self.total_items.saturating_sub(self.item_heights.len());
+        self.measured_total as usize + unmeasured * self.average_item_height as usize
     }
 
     /// Get scroll percentage (0.0 to 1.0)
@@ -454,6 +560,29 @@ impl VirtualScrollManager {
             self.scroll_offset as f64 / self.max_scroll_offset() as f64
         }
     }
+
+    fn update_average(&mut self) {
+        self.average_item_height = if self.item_heights.is_empty() {
+            1
+        } else {
+            (self.measured_total / self.item_heights.len() as u64).max(1) as u16
+        };
+    }
+
+    fn ensure_offsets(&mut self) {
+        if !self.offsets_dirty && self.line_offsets.len() == self.total_items + 1 {
+            return;
+        }
+        self.line_offsets.clear();
+        self.line_offsets.reserve(self.total_items + 1);
+        let mut offset = 0;
+        for index in 0..self.total_items {
+            self.line_offsets.push(offset);
+            offset += self.item_height(index) as usize;
+        }
+        self.line_offsets.push(offset);
+        self.offsets_dirty = false;
+    }
 }
 
 /// Content cache for expensive operations
@@ -1803,6 +1932,8 @@ pub struct HistoryLimits {
     pub max_sessions: usize,
     /// Maximum number of undo operations
     pub max_undo_steps: usize,
+    /// Maximum number of clipboard history entries
+    pub max_clipboard_entries: usize,
     /// Maximum cache size in bytes
     pub max_cache_size: u64,
     /// Maximum memory usage in bytes
@@ -1815,6 +1946,7 @@ impl Default for HistoryLimits {
             max_messages: 10000,
             max_sessions: 100,
             max_undo_steps: 100,
+            max_clipboard_entries: 50,
             max_cache_size: 100 * 1024 * 1024,   // 100MB
             max_memory_usage: 500 * 1024 * 1024, // 500MB
         }
@@ -1845,6 +1977,12 @@ impl HistoryLimits {
         self
     }
 
+    /// Set maximum clipboard history entries
+    pub fn with_max_clipboard_entries(mut self, max: usize) -> Self {
+        self.max_clipboard_entries = max;
+        self
+    }
+
     /// Set maximum cache size
     pub fn with_max_cache_size(mut self, max_bytes: u64) -> Self {
         self.max_cache_size = max_bytes;
@@ -12,13 +12,25 @@ use std::{
 // use ricecoder_research::{ProjectAnalyzer, ProjectType, Language};
 
 // Use the proper project analyzer from ricecoder-research
+use ricecoder_external_lsp::{DefaultServerConfigs, ServerDiscovery};
+use ricecoder_providers::ProviderIntegration;
 pub use ricecoder_research::{
-    models::{Language, ProjectType},
+    models::{BuildTool, Language, ProjectType},
     ProjectAnalyzer,
 };
-use ricecoder_storage::ConfigLoader;
+use ricecoder_storage::{Config, ConfigFormat, ConfigLoader, ProjectStore};
 
-use crate::error::TuiResult;
+use crate::error::{TuiError, TuiResult};
+
+/// Project directory the bootstrap wizard writes its configuration to
+pub const PROJECT_CONFIG_DIR: &str = ".ricecoder";
+
+/// Modes offered by the bootstrap wizard, as (mode id, description)
+pub const BOOTSTRAP_MODES: [(&str, &str); 3] = [
+    ("code", "Write and edit code"),
+    ("ask", "Answer questions without editing files"),
+    ("vibe", "Rapid prototyping with fewer confirmations"),
+];
 
 /// Project bootstrap configuration and state
 #[derive(Debug, Clone)]
@@ -336,7 +348,7 @@ impl ProjectBootstrap {
         configs: &mut HashMap<String, serde_json::Value>,
     ) -> TuiResult<()> {
         // Look for .ricecoder directory or config files
-        let ricecoder_config = self.working_directory.join(".ricecoder");
+        let ricecoder_config = self.project_config_dir();
         if ricecoder_config.exists() {
             // Load project-specific ricecoder config
             let config_path = ricecoder_config.join("config.yaml");
@@ -397,6 +409,112 @@ impl ProjectBootstrap {
         Ok(integrations)
     }
 
+    /// Directory holding the project configuration
+    pub fn project_config_dir(&self) -> PathBuf {
+        self.working_directory.join(PROJECT_CONFIG_DIR)
+    }
+
+    /// Whether the project has no RiceCoder configuration yet
+    pub fn needs_setup(&self) -> bool {
+        ConfigLoader::find_config_file(&self.project_config_dir(), "config").is_none()
+    }
+
+    /// Detect everything the bootstrap wizard needs to know about the project
+    ///
+    /// Unlike [`ProjectBootstrap::bootstrap`] this reports every detected
+    /// language rather than a single primary one, and does not change the
+    /// bootstrap state.
+    pub fn detect(&self) -> TuiResult<ProjectDetection> {
+        let analyzer = ProjectAnalyzer::new();
+        let root = &self.working_directory;
+        let research_error = |e: ricecoder_research::ResearchError| TuiError::config(e.to_string());
+
+        let languages = analyzer.detect_languages(root).map_err(research_error)?;
+        let build_tools = analyzer.detect_build_tools(root).map_err(research_error)?;
+        let frameworks = analyzer
+            .identify_frameworks(root)
+            .map_err(research_error)?
+            .into_iter()
+            .map(|framework| framework.name)
+            .collect();
+
+        Ok(ProjectDetection {
+            project_type: self.detect_project_type()?,
+            lsp_servers: Self::suggest_lsp_servers(&languages),
+            languages,
+            build_tools,
+            frameworks,
+            has_project_config: !self.needs_setup(),
+        })
+    }
+
+    /// Suggest a language server for each language, noting which are installed
+    pub fn suggest_lsp_servers(languages: &[Language]) -> Vec<LspSuggestion> {
+        let registry = DefaultServerConfigs::all_tiers_registry();
+        languages
+            .iter()
+            .filter_map(|language| {
+                let language_id = lsp_language_id(language)?;
+                let server = registry.servers.get(language_id)?.first()?;
+                Some(LspSuggestion {
+                    language: language_id.to_string(),
+                    executable: server.executable.clone(),
+                    installed: ServerDiscovery::verify_executable(&server.executable).is_ok(),
+                    install_hint: ServerDiscovery::installation_instructions(
+                        language_id,
+                        &server.executable,
+                    ),
+                })
+            })
+            .collect()
+    }
+
+    /// Write the project configuration chosen in the bootstrap wizard
+    ///
+    /// Creates the project store layout under [`PROJECT_CONFIG_DIR`] and
+    /// writes `config.yaml` there. Returns the path of the written file.
+    pub fn write_project_config(
+        &self,
+        detection: &ProjectDetection,
+        choices: &BootstrapChoices,
+    ) -> TuiResult<PathBuf> {
+        let storage_error = |e: ricecoder_storage::StorageError| TuiError::Storage(e.into());
+
+        let config_dir = self.project_config_dir();
+        ProjectStore::new(config_dir.clone())
+            .initialize()
+            .map_err(storage_error)?;
+
+        let mut config = Config::default();
+        config.providers.default_provider = choices.provider.clone();
+        config.defaults.model = choices.model.clone();
+        config
+            .custom
+            .insert("mode".to_string(), serde_json::json!(choices.mode));
+        config.custom.insert(
+            "project".to_string(),
+            serde_json::json!({
+                "type": detection.project_type,
+                "languages": detection.languages,
+                "build_tools": detection
+                    .build_tools
+                    .iter()
+                    .map(BuildTool::name)
+                    .collect::<Vec<_>>(),
+                "test_command": detection.build_tools.first().map(BuildTool::test_command),
+            }),
+        );
+        config.custom.insert(
+            "lsp".to_string(),
+            serde_json::json!({ "servers": choices.lsp_servers }),
+        );
+
+        let config_path = config_dir.join("config.yaml");
+        ConfigLoader::save_to_file(&config, &config_path, ConfigFormat::Yaml)
+            .map_err(storage_error)?;
+        Ok(config_path)
+    }
+
     /// Get project information for display
     pub fn get_project_info(&self) -> Option<ProjectInfo> {
         if !self.bootstrapped {
@@ -412,6 +530,95 @@ impl ProjectBootstrap {
     }
 }
 
+/// Language server suggested for a detected language
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LspSuggestion {
+    /// Language id used by the LSP registry (e.g. `rust`)
+    pub language: String,
+    /// Server executable
+    pub executable: String,
+    /// Whether the executable was found on this machine
+    pub installed: bool,
+    /// How to install the server
+    pub install_hint: String,
+}
+
+/// What the bootstrap wizard detected about a project
+#[derive(Debug, Clone)]
+pub struct ProjectDetection {
+    /// Detected project type
+    pub project_type: ProjectType,
+    /// Every detected language, primary first
+    pub languages: Vec<Language>,
+    /// Detected build tools and package managers
+    pub build_tools: Vec<BuildTool>,
+    /// Names of detected frameworks
+    pub frameworks: Vec<String>,
+    /// Suggested language servers
+    pub lsp_servers: Vec<LspSuggestion>,
+    /// Whether a project configuration already exists
+    pub has_project_config: bool,
+}
+
+/// Choices made in the bootstrap wizard
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BootstrapChoices {
+    /// Whether to write the project configuration
+    pub create_config: bool,
+    /// Executables of the language servers to enable
+    pub lsp_servers: Vec<String>,
+    /// Mode id from [`BOOTSTRAP_MODES`]
+    pub mode: String,
+    /// Default provider
+    pub provider: Option<String>,
+    /// Default model of the provider
+    pub model: Option<String>,
+}
+
+impl BootstrapChoices {
+    /// Default choices for a detection: config on, installed servers on,
+    /// code mode and the first known provider
+    pub fn defaults_for(detection: &ProjectDetection) -> Self {
+        let provider = ProviderIntegration::available_providers()
+            .first()
+            .map(|provider| provider.to_string());
+        let model = provider.as_deref().and_then(|provider| {
+            ProviderIntegration::available_models_for_provider(provider)
+                .first()
+                .map(|model| model.to_string())
+        });
+        Self {
+            create_config: !detection.has_project_config,
+            lsp_servers: detection
+                .lsp_servers
+                .iter()
+                .filter(|server| server.installed)
+                .map(|server| server.executable.clone())
+                .collect(),
+            mode: BOOTSTRAP_MODES[0].0.to_string(),
+            provider,
+            model,
+        }
+    }
+}
+
+/// Language id used by the LSP server registry
+fn lsp_language_id(language: &Language) -> Option<&'static str> {
+    match language {
+        Language::Rust => Some("rust"),
+        Language::TypeScript => Some("typescript"),
+        Language::Python => Some("python"),
+        Language::Go => Some("go"),
+        Language::Java => Some("java"),
+        Language::Kotlin => Some("kotlin"),
+        Language::CSharp => Some("csharp"),
+        Language::Php => Some("php"),
+        Language::Ruby => Some("ruby"),
+        Language::Dart => Some("dart"),
+        Language::Swift | Language::Other(_) => None,
+    }
+}
+
 /// Project information for display
 #[derive(Debug, Clone)]
 pub struct ProjectInfo {
@@ -77,6 +77,10 @@ pub struct StatusBarWidget {
     pub search_status: Option<String>,
     /// Selection status
     pub selection_status: Option<String>,
+    /// Language server work-done progress (e.g. "rust: Indexing (40%)")
+    pub lsp_progress: Option<String>,
+    /// Pending key sequence hint (e.g. "g-  g:Top  d:Definition")
+    pub pending_keys: Option<String>,
     /// Whether to flash the status bar
     pub flash: bool,
 }
@@ -150,6 +154,8 @@ impl Default for StatusBarWidget {
             recording_status: None,
             search_status: None,
             selection_status: None,
+            lsp_progress: None,
+            pending_keys: None,
             flash: false,
         }
     }
@@ -251,6 +257,18 @@ impl StatusBarWidget {
         self
     }
 
+    /// Set language server progress
+    pub fn with_lsp_progress(mut self, progress: Option<String>) -> Self {
+        self.lsp_progress = progress;
+        self
+    }
+
+    /// Set pending key sequence hint
+    pub fn with_pending_keys(mut self, hint: Option<String>) -> Self {
+        self.pending_keys = hint;
+        self
+    }
+
     /// Set flash state
     pub fn with_flash(mut self, flash: bool) -> Self {
         self.flash = flash;
@@ -405,6 +423,17 @@ impl StatusBarWidget {
                 .add_modifier(Modifier::BOLD),
         ));
 
+        // Pending key sequence
+        if let Some(pending) = &self.pending_keys {
+            spans.push(Span::raw(" "));
+            spans.push(Span::styled(
+                pending.as_str(),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+
         // Recording status
         if let Some(recording) = &self.recording_status {
             spans.push(Span::raw(" "));
@@ -432,6 +461,15 @@ impl StatusBarWidget {
             ));
         }
 
+        // Language server progress
+        if let Some(progress) = &self.lsp_progress {
+            spans.push(Span::raw(" "));
+            spans.push(Span::styled(
+                format!("LSP:{}", progress),
+                Style::default().fg(Color::Magenta),
+            ));
+        }
+
         spans
     }
 }
@@ -0,0 +1,612 @@
+//! Composable status bar segments
+//!
+//! The status bar is assembled from [`StatusSegment`]s. Each segment renders
+//! itself from a [`StatusContext`] into a full form and, optionally, a shorter
+//! compact form. [`StatusBar`] orders the segments as configured in
+//! `tui.status_bar` and negotiates widths: when the segments do not fit, the
+//! lowest-priority segments are compacted first, then dropped, and a single
+//! remaining segment is truncated with `…`.
+//!
+//! Built-in segments are `mode`, `model`, `tokens`, `vcs`, `jobs` and `lsp`.
+//! Plugins add their own with [`StatusBar::register`]; a configured id that is
+//! registered later is picked up on the next render.
+
+use std::{fmt, sync::Arc};
+
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+};
+use ricecoder_storage::{StatusSegmentConfig, TuiStatusBarConfig};
+use ricecoder_themes::Theme;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::{
+    performance::JobQueueStats,
+    status_bar::{TokenLimitStatus, TokenUsage},
+};
+
+/// Separator drawn between segments
+const SEPARATOR: &str = " │ ";
+
+/// Priority of segments that do not override [`StatusSegment::priority`]
+pub const DEFAULT_SEGMENT_PRIORITY: u8 = 50;
+
+/// Version control state shown by the `vcs` segment
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VcsState {
+    /// Current branch
+    pub branch: String,
+    /// Whether the working tree has uncommitted changes
+    pub dirty: bool,
+    /// Commits ahead of the upstream branch
+    pub ahead: usize,
+    /// Commits behind the upstream branch
+    pub behind: usize,
+}
+
+/// Health of a language server
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LspHealth {
+    /// Initialized and answering requests
+    Ready,
+    /// Starting up or indexing
+    Starting,
+    /// Crashed or failed to start
+    Error,
+}
+
+/// Language server shown by the `lsp` segment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LspServerStatus {
+    /// Server name (e.g. `rust-analyzer`)
+    pub name: String,
+    /// Current health
+    pub health: LspHealth,
+}
+
+/// Data the status segments render from
+#[derive(Debug, Clone, Default)]
+pub struct StatusContext {
+    /// Current mode or agent
+    pub mode: String,
+    /// Current provider
+    pub provider: Option<String>,
+    /// Current model
+    pub model: Option<String>,
+    /// Token usage against the model's limit
+    pub token_usage: Option<TokenUsage>,
+    /// Preformatted token summary (e.g. "12.5K tokens | $0.03"), used when
+    /// no limit is known
+    pub token_summary: Option<String>,
+    /// Version control state
+    pub vcs: Option<VcsState>,
+    /// Background job queue
+    pub jobs: Option<JobQueueStats>,
+    /// Language servers
+    pub lsp_servers: Vec<LspServerStatus>,
+}
+
+/// Rendered segment text
+#[derive(Debug, Clone, PartialEq)]
+pub struct SegmentContent {
+    /// Full rendering
+    pub full: Vec<Span<'static>>,
+    /// Shorter rendering used when space is tight
+    pub compact: Option<Vec<Span<'static>>>,
+}
+
+impl SegmentContent {
+    /// Content with only a full rendering
+    pub fn new(full: Vec<Span<'static>>) -> Self {
+        Self {
+            full,
+            compact: None,
+        }
+    }
+
+    /// Add a compact rendering
+    pub fn with_compact(mut self, compact: Vec<Span<'static>>) -> Self {
+        self.compact = Some(compact);
+        self
+    }
+}
+
+/// A piece of the status bar
+///
+/// Segments are stateless renderers; anything they display comes from the
+/// [`StatusContext`] or from state the segment shares with its owner.
+pub trait StatusSegment: Send + Sync {
+    /// Stable id used in `tui.status_bar` configuration
+    fn id(&self) -> &str;
+
+    /// Truncation priority; higher priorities are kept longer
+    fn priority(&self) -> u8 {
+        DEFAULT_SEGMENT_PRIORITY
+    }
+
+    /// Render the segment, or `None` when there is nothing to show
+    fn render(&self, context: &StatusContext, theme: &Theme) -> Option<SegmentContent>;
+}
+
+/// Current mode (`mode`)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ModeSegment;
+
+impl StatusSegment for ModeSegment {
+    fn id(&self) -> &str {
+        "mode"
+    }
+
+    fn priority(&self) -> u8 {
+        90
+    }
+
+    fn render(&self, context: &StatusContext, theme: &Theme) -> Option<SegmentContent> {
+        if context.mode.is_empty() {
+            return None;
+        }
+        let style = Style::default()
+            .fg(theme.agent_colors.get(&context.mode))
+            .add_modifier(Modifier::BOLD);
+        let initial: String = context.mode.chars().take(1).collect();
+        Some(
+            SegmentContent::new(vec![Span::styled(context.mode.to_uppercase(), style)])
+                .with_compact(vec![Span::styled(initial.to_uppercase(), style)]),
+        )
+    }
+}
+
+/// Current provider and model (`model`)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ModelSegment;
+
+impl StatusSegment for ModelSegment {
+    fn id(&self) -> &str {
+        "model"
+    }
+
+    fn priority(&self) -> u8 {
+        80
+    }
+
+    fn render(&self, context: &StatusContext, theme: &Theme) -> Option<SegmentContent> {
+        let model = context.model.as_deref()?;
+        let style = Style::default().fg(theme.foreground);
+        let full = match context.provider.as_deref() {
+            Some(provider) => format!("{}/{}", provider, model),
+            None => model.to_string(),
+        };
+        Some(
+            SegmentContent::new(vec![Span::styled(full, style)])
+                .with_compact(vec![Span::styled(model.to_string(), style)]),
+        )
+    }
+}
+
+/// Token usage (`tokens`)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokenUsageSegment;
+
+impl StatusSegment for TokenUsageSegment {
+    fn id(&self) -> &str {
+        "tokens"
+    }
+
+    fn priority(&self) -> u8 {
+        60
+    }
+
+    fn render(&self, context: &StatusContext, theme: &Theme) -> Option<SegmentContent> {
+        if let Some(usage) = context.token_usage.as_ref().filter(|u| u.token_limit > 0) {
+            let percentage = usage.total_tokens as f64 / usage.token_limit as f64 * 100.0;
+            let (status, color) = if percentage >= 90.0 {
+                (TokenLimitStatus::Critical, theme.error)
+            } else if percentage >= 75.0 {
+                (TokenLimitStatus::Warning, theme.warning)
+            } else {
+                (TokenLimitStatus::Normal, theme.success)
+            };
+            let style = Style::default().fg(color);
+            return Some(
+                SegmentContent::new(vec![Span::styled(
+                    format!(
+                        "{} {}/{} ({:.0}%)",
+                        status.symbol(),
+                        usage.total_tokens,
+                        usage.token_limit,
+                        percentage
+                    ),
+                    style,
+                )])
+                .with_compact(vec![Span::styled(format!("{:.0}%", percentage), style)]),
+            );
+        }
+
+        let summary = context.token_summary.as_deref().filter(|s| !s.is_empty())?;
+        let style = Style::default().fg(theme.success);
+        let content = SegmentContent::new(vec![Span::styled(summary.to_string(), style)]);
+        // "12.5K tokens | $0.03" compacts to "12.5K tokens"
+        Some(match summary.split_once(" | ") {
+            Some((tokens, _)) => {
+                content.with_compact(vec![Span::styled(tokens.to_string(), style)])
+            }
+            None => content,
+        })
+    }
+}
+
+/// Version control branch and dirty state (`vcs`)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VcsSegment;
+
+impl StatusSegment for VcsSegment {
+    fn id(&self) -> &str {
+        "vcs"
+    }
+
+    fn render(&self, context: &StatusContext, theme: &Theme) -> Option<SegmentContent> {
+        let vcs = context.vcs.as_ref()?;
+        let style = Style::default().fg(if vcs.dirty {
+            theme.warning
+        } else {
+            theme.success
+        });
+        let branch = format!("{}{}", vcs.branch, if vcs.dirty { "*" } else { "" });
+
+        let mut full = vec![Span::styled(format!("⎇ {}", branch), style)];
+        if vcs.ahead > 0 || vcs.behind > 0 {
+            let mut sync = String::from(" ");
+            if vcs.ahead > 0 {
+                sync.push_str(&format!("↑{}", vcs.ahead));
+            }
+            if vcs.behind > 0 {
+                sync.push_str(&format!("↓{}", vcs.behind));
+            }
+            full.push(Span::styled(sync, Style::default().fg(theme.info)));
+        }
+        Some(SegmentContent::new(full).with_compact(vec![Span::styled(branch, style)]))
+    }
+}
+
+/// Background job queue (`jobs`)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JobsSegment;
+
+impl StatusSegment for JobsSegment {
+    fn id(&self) -> &str {
+        "jobs"
+    }
+
+    fn priority(&self) -> u8 {
+        30
+    }
+
+    fn render(&self, context: &StatusContext, theme: &Theme) -> Option<SegmentContent> {
+        let jobs = context
+            .jobs
+            .as_ref()
+            .filter(|jobs| jobs.active_jobs > 0 || jobs.queued_jobs > 0)?;
+        let style = Style::default().fg(theme.info);
+        let mut full = format!(
+            "⟳ {} job{}",
+            jobs.active_jobs,
+            if jobs.active_jobs == 1 { "" } else { "s" }
+        );
+        if jobs.queued_jobs > 0 {
+            full.push_str(&format!(", {} queued", jobs.queued_jobs));
+        }
+        Some(
+            SegmentContent::new(vec![Span::styled(full, style)]).with_compact(vec![Span::styled(
+                format!("⟳ {}", jobs.active_jobs + jobs.queued_jobs),
+                style,
+            )]),
+        )
+    }
+}
+
+/// Language server health (`lsp`)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LspHealthSegment;
+
+impl StatusSegment for LspHealthSegment {
+    fn id(&self) -> &str {
+        "lsp"
+    }
+
+    fn priority(&self) -> u8 {
+        40
+    }
+
+    fn render(&self, context: &StatusContext, theme: &Theme) -> Option<SegmentContent> {
+        if context.lsp_servers.is_empty() {
+            return None;
+        }
+        let color = |health: LspHealth| match health {
+            LspHealth::Ready => theme.success,
+            LspHealth::Starting => theme.warning,
+            LspHealth::Error => theme.error,
+        };
+        let muted = Style::default().fg(theme.text_muted);
+
+        let mut full = vec![Span::styled("LSP", muted)];
+        for server in &context.lsp_servers {
+            let symbol = match server.health {
+                LspHealth::Ready => "✓",
+                LspHealth::Starting => "⟳",
+                LspHealth::Error => "✗",
+            };
+            full.push(Span::styled(
+                format!(" {} {}", server.name, symbol),
+                Style::default().fg(color(server.health)),
+            ));
+        }
+
+        let ready = context
+            .lsp_servers
+            .iter()
+            .filter(|server| server.health == LspHealth::Ready)
+            .count();
+        let any = |health: LspHealth| context.lsp_servers.iter().any(|s| s.health == health);
+        let worst = if any(LspHealth::Error) {
+            LspHealth::Error
+        } else if any(LspHealth::Starting) {
+            LspHealth::Starting
+        } else {
+            LspHealth::Ready
+        };
+        let compact = vec![
+            Span::styled("LSP ", muted),
+            Span::styled(
+                format!("{}/{}", ready, context.lsp_servers.len()),
+                Style::default().fg(color(worst)),
+            ),
+        ];
+        Some(SegmentContent::new(full).with_compact(compact))
+    }
+}
+
+/// A segment placed in the status bar after width negotiation
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlacedSegment {
+    /// Segment id
+    pub id: String,
+    /// Spans to draw
+    pub spans: Vec<Span<'static>>,
+    /// Whether the compact rendering was used
+    pub compact: bool,
+    /// Whether the spans were cut to fit
+    pub truncated: bool,
+}
+
+/// Ordered, configurable set of status segments
+#[derive(Clone)]
+pub struct StatusBar {
+    segments: Vec<Arc<dyn StatusSegment>>,
+    /// Configured order and visibility; empty keeps registration order
+    layout: Vec<StatusSegmentConfig>,
+}
+
+impl fmt::Debug for StatusBar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StatusBar")
+            .field(
+                "segments",
+                &self.segments.iter().map(|s| s.id()).collect::<Vec<_>>(),
+            )
+            .field("layout", &self.layout)
+            .finish()
+    }
+}
+
+impl Default for StatusBar {
+    fn default() -> Self {
+        Self::with_builtin_segments()
+    }
+}
+
+impl StatusBar {
+    /// Create a status bar without segments
+    pub fn new() -> Self {
+        Self {
+            segments: Vec::new(),
+            layout: Vec::new(),
+        }
+    }
+
+    /// Create a status bar with the built-in segments
+    pub fn with_builtin_segments() -> Self {
+        let mut bar = Self::new();
+        bar.register(Arc::new(ModeSegment));
+        bar.register(Arc::new(ModelSegment));
+        bar.register(Arc::new(TokenUsageSegment));
+        bar.register(Arc::new(VcsSegment));
+        bar.register(Arc::new(JobsSegment));
+        bar.register(Arc::new(LspHealthSegment));
+        bar
+    }
+
+    /// Register a segment, replacing any segment with the same id
+    pub fn register(&mut self, segment: Arc<dyn StatusSegment>) {
+        match self.segments.iter().position(|s| s.id() == segment.id()) {
+            Some(index) => self.segments[index] = segment,
+            None => self.segments.push(segment),
+        }
+    }
+
+    /// Remove a segment by id
+    pub fn unregister(&mut self, id: &str) -> bool {
+        let before = self.segments.len();
+        self.segments.retain(|s| s.id() != id);
+        self.segments.len() != before
+    }
+
+    /// Apply the configured order and visibility
+    pub fn apply_config(&mut self, config: &TuiStatusBarConfig) {
+        self.layout = config.segments.clone();
+    }
+
+    /// Ids of the shown segments with their priorities, in display order
+    pub fn visible_segments(&self) -> Vec<(&str, u8)> {
+        self.ordered()
+            .into_iter()
+            .map(|(segment, priority)| (segment.id(), priority))
+            .collect()
+    }
+
+    fn ordered(&self) -> Vec<(&dyn StatusSegment, u8)> {
+        if self.layout.is_empty() {
+            return self
+                .segments
+                .iter()
+                .map(|s| (s.as_ref(), s.priority()))
+                .collect();
+        }
+        self.layout
+            .iter()
+            .filter(|entry| entry.visible)
+            .filter_map(|entry| {
+                let segment = self.segments.iter().find(|s| s.id() == entry.id)?;
+                Some((
+                    segment.as_ref(),
+                    entry.priority.unwrap_or_else(|| segment.priority()),
+                ))
+            })
+            .collect()
+    }
+
+    /// Render the segments and fit them into `width` columns
+    pub fn layout(&self, context: &StatusContext, theme: &Theme, width: u16) -> Vec<PlacedSegment> {
+        struct Candidate {
+            id: String,
+            priority: u8,
+            content: SegmentContent,
+            compact: bool,
+        }
+
+        impl Candidate {
+            fn width(&self) -> usize {
+                match (&self.content.compact, self.compact) {
+                    (Some(compact), true) => spans_width(compact),
+                    _ => spans_width(&self.content.full),
+                }
+            }
+        }
+
+        let mut candidates: Vec<Candidate> = self
+            .ordered()
+            .into_iter()
+            .filter_map(|(segment, priority)| {
+                Some(Candidate {
+                    id: segment.id().to_string(),
+                    priority,
+                    content: segment.render(context, theme)?,
+                    compact: false,
+                })
+            })
+            .collect();
+
+        let width = width as usize;
+        let separator = SEPARATOR.width();
+        let total = |candidates: &[Candidate]| {
+            candidates.iter().map(Candidate::width).sum::<usize>()
+                + separator * candidates.len().saturating_sub(1)
+        };
+
+        // Lowest priority first; among equals the rightmost goes first
+        let victim = |candidates: &[Candidate], compactable: bool| {
+            candidates
+                .iter()
+                .enumerate()
+                .filter(|(_, c)| !compactable || (!c.compact && c.content.compact.is_some()))
+                .min_by_key(|(index, c)| (c.priority, std::cmp::Reverse(*index)))
+                .map(|(index, _)| index)
+        };
+
+        let mut truncated = false;
+        while !candidates.is_empty() && total(&candidates) > width {
+            if let Some(index) = victim(&candidates, true) {
+                candidates[index].compact = true;
+            } else if candidates.len() > 1 {
+                if let Some(index) = victim(&candidates, false) {
+                    candidates.remove(index);
+                }
+            } else {
+                truncated = true;
+                break;
+            }
+        }
+
+        candidates
+            .into_iter()
+            .map(|candidate| {
+                let spans = match (candidate.content.compact, candidate.compact) {
+                    (Some(compact), true) => compact,
+                    _ => candidate.content.full,
+                };
+                PlacedSegment {
+                    id: candidate.id,
+                    spans: if truncated {
+                        truncate_spans(spans, width)
+                    } else {
+                        spans
+                    },
+                    compact: candidate.compact,
+                    truncated,
+                }
+            })
+            .collect()
+    }
+
+    /// Render the status line into `width` columns
+    pub fn render_line(&self, context: &StatusContext, theme: &Theme, width: u16) -> Line<'static> {
+        let mut spans = Vec::new();
+        for (index, segment) in self.layout(context, theme, width).into_iter().enumerate() {
+            if index > 0 {
+                spans.push(Span::styled(
+                    SEPARATOR,
+                    Style::default().fg(theme.text_muted),
+                ));
+            }
+            spans.extend(segment.spans);
+        }
+        Line::from(spans)
+    }
+}
+
+fn spans_width(spans: &[Span]) -> usize {
+    spans.iter().map(|span| span.content.width()).sum()
+}
+
+/// Cut spans to `width` columns, ending with `…`
+fn truncate_spans(spans: Vec<Span<'static>>, width: usize) -> Vec<Span<'static>> {
+    if spans_width(&spans) <= width {
+        return spans;
+    }
+    let mut remaining = width.saturating_sub(1);
+    let mut result = Vec::new();
+    let mut last_style = Style::default();
+    for span in spans {
+        last_style = span.style;
+        let mut text = String::new();
+        for ch in span.content.chars() {
+            let ch_width = ch.width().unwrap_or(0);
+            if ch_width > remaining {
+                remaining = 0;
+                break;
+            }
+            remaining -= ch_width;
+            text.push(ch);
+        }
+        if !text.is_empty() {
+            result.push(Span::styled(text, span.style));
+        }
+        if remaining == 0 {
+            break;
+        }
+    }
+    if width > 0 {
+        result.push(Span::styled("…", last_style));
+    }
+    result
+}
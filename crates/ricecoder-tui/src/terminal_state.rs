@@ -94,6 +94,8 @@ pub struct CapabilityOverrides {
     pub unicode_support: Option<bool>,
     /// Force reduced graphics mode
     pub force_reduced_graphics: Option<bool>,
+    /// Override OSC 8 hyperlink support detection
+    pub hyperlink_support: Option<bool>,
 }
 
 /// Terminal capabilities detected at startup
@@ -123,6 +125,8 @@ pub struct TerminalCapabilities {
     pub ansi_art_support: bool,
     /// Unicode support
     pub unicode_support: bool,
+    /// OSC 8 hyperlink support
+    pub hyperlink_support: bool,
     /// Running in SSH session
     pub is_ssh: bool,
     /// Running in TMUX
@@ -199,6 +203,9 @@ impl TerminalCapabilities {
             .unwrap_or_else(|| Self::detect_unicode_support());
         let is_ssh = Self::detect_ssh_session();
         let (is_tmux, tmux_version) = Self::detect_tmux_session_with_version();
+        let hyperlink_support = overrides.hyperlink_support.unwrap_or_else(|| {
+            Self::detect_hyperlink_support(&terminal_type, tmux_version.as_deref())
+        });
         let size = crossterm::terminal::size().unwrap_or((80, 24));
 
         let capabilities = Self {
@@ -213,6 +220,7 @@ impl TerminalCapabilities {
             block_graphics_support,
             ansi_art_support,
             unicode_support,
+            hyperlink_support,
             is_ssh,
             is_tmux,
             tmux_version,
@@ -233,6 +241,7 @@ impl TerminalCapabilities {
             block_graphics_support = capabilities.block_graphics_support,
             ansi_art_support = capabilities.ansi_art_support,
             unicode_support = capabilities.unicode_support,
+            hyperlink_support = capabilities.hyperlink_support,
             is_ssh = capabilities.is_ssh,
             is_tmux = capabilities.is_tmux,
             tmux_version = ?capabilities.tmux_version,
@@ -447,6 +456,59 @@ impl TerminalCapabilities {
         )
     }
 
+    /// Detect OSC 8 hyperlink support
+    ///
+    /// `FORCE_HYPERLINK` (as used by other terminal tools) overrides
+    /// detection. Inside TMUX, hyperlinks need tmux 3.4 or later.
+    fn detect_hyperlink_support(terminal_type: &TerminalType, tmux_version: Option<&str>) -> bool {
+        if let Ok(force) = env::var("FORCE_HYPERLINK") {
+            return force != "0";
+        }
+
+        if let Some(version) = tmux_version {
+            if !Self::tmux_supports_hyperlinks(version) {
+                return false;
+            }
+        }
+
+        // VTE-based terminals support hyperlinks since VTE 0.50
+        if env::var("VTE_VERSION")
+            .ok()
+            .and_then(|v| v.parse::<u32>().ok())
+            .is_some_and(|v| v >= 5000)
+        {
+            return true;
+        }
+
+        matches!(
+            terminal_type,
+            TerminalType::ITerm2
+                | TerminalType::WezTerm
+                | TerminalType::Kitty
+                | TerminalType::Alacritty
+                | TerminalType::WindowsTerminal
+                | TerminalType::GnomeTerminal
+                | TerminalType::Konsole
+                | TerminalType::VSCode
+                | TerminalType::Hyper
+                | TerminalType::Tabby
+                | TerminalType::Foot
+                | TerminalType::Rio
+                | TerminalType::Warp
+        )
+    }
+
+    /// Whether a `tmux -V` version string is 3.4 or later
+    fn tmux_supports_hyperlinks(version: &str) -> bool {
+        let number = version.trim_start_matches(|c: char| !c.is_ascii_digit());
+        let mut parts = number
+            .split(|c: char| !c.is_ascii_digit())
+            .map(|part| part.parse::<u32>().unwrap_or(0));
+        let major = parts.next().unwrap_or(0);
+        let minor = parts.next().unwrap_or(0);
+        (major, minor) >= (3, 4)
+    }
+
     /// Detect block graphics support
     ///
     /// Requirements: 4.1 - Detect graphics protocol support
@@ -579,6 +641,21 @@ impl TerminalCapabilities {
         }
     }
 
+    /// Get inline image protocol support for `ricecoder_images::ImageDisplay`
+    ///
+    /// WezTerm implements the iTerm2 protocol even when it is not reported as
+    /// iTerm2. Graphics are disabled when they should be reduced (e.g. over SSH).
+    pub fn image_protocol_support(&self) -> ricecoder_images::ProtocolSupport {
+        let graphics = !self.should_reduce_graphics();
+        ricecoder_images::ProtocolSupport::new(
+            format!("{:?}", self.terminal_type),
+            graphics && self.sixel_support,
+            graphics
+                && (self.iterm2_inline_images_support
+                    || self.terminal_type == TerminalType::WezTerm),
+        )
+    }
+
     /// Get appropriate color mode for ratatui
     ///
     /// Requirements: 4.2 - Use appropriate color mode
@@ -640,6 +717,9 @@ impl TerminalCapabilities {
         // Enable Unicode characters if supported
         optimizations.insert("unicode_chars".to_string(), self.unicode_support);
 
+        // Enable OSC 8 hyperlinks if supported
+        optimizations.insert("hyperlinks".to_string(), self.hyperlink_support);
+
         // Reduce graphics complexity for SSH
         optimizations.insert(
             "reduced_graphics".to_string(),
@@ -782,6 +862,7 @@ impl Default for TerminalCapabilities {
             block_graphics_support: true,
             ansi_art_support: true,
             unicode_support: true,
+            hyperlink_support: false,
             is_ssh: false,
             is_tmux: false,
             tmux_version: None,
@@ -4,7 +4,9 @@
 //! It supports multi-line input, vim mode, and integration with the TUI event loop.
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-use tui_textarea::TextArea;
+use tui_textarea::{CursorMove, TextArea};
+
+use crate::undo_history::{EditKind, HistoryAction, TextSnapshot, UndoHistory};
 
 /// Convert crossterm KeyEvent to tui_textarea Input
 /// This is needed because tui-textarea uses ratatui's re-exported crossterm types
@@ -89,13 +91,16 @@ pub struct TextAreaWidget {
     selection: Option<Selection>,
     /// History draft (saved before history navigation)
     history_draft: Option<String>,
+    /// Undo/redo history, kept across clear and set_text
+    undo_history: UndoHistory<TextSnapshot>,
 }
 
 impl TextAreaWidget {
     /// Create a new text area widget
     pub fn new(vim_mode: bool, max_height: u16) -> Self {
         let mut textarea = TextArea::default();
-        textarea.set_max_histories(50); // Enable undo/redo
+        // Undo/redo goes through the shared history instead
+        textarea.set_max_histories(0);
 
         Self {
             textarea,
@@ -108,6 +113,7 @@ impl TextAreaWidget {
             max_height,
             selection: None,
             history_draft: None,
+            undo_history: UndoHistory::default(),
         }
     }
 
@@ -123,16 +129,22 @@ impl TextAreaWidget {
             // Use clear() for empty text to ensure consistent state
             self.clear();
         } else {
+            self.undo_history.record(&self.snapshot(), EditKind::Replace);
             let lines: Vec<String> = text.lines().map(|s| s.to_string()).collect();
             self.textarea = TextArea::new(lines);
-            self.textarea.set_max_histories(50);
+            self.textarea.set_max_histories(0);
         }
     }
 
     /// Clear the input
+    ///
+    /// The cleared text can be brought back with [`undo`](Self::undo).
     pub fn clear(&mut self) {
+        if !self.textarea.is_empty() {
+            self.undo_history.record(&self.snapshot(), EditKind::Replace);
+        }
         self.textarea = TextArea::default();
-        self.textarea.set_max_histories(50);
+        self.textarea.set_max_histories(0);
     }
 
     /// Check if input is empty
@@ -188,12 +200,56 @@ impl TextAreaWidget {
 
     /// Undo last operation
     pub fn undo(&mut self) -> bool {
-        self.textarea.undo()
+        self.apply_history(HistoryAction::Undo)
     }
 
     /// Redo last undone operation
     pub fn redo(&mut self) -> bool {
-        self.textarea.redo()
+        self.apply_history(HistoryAction::Redo)
+    }
+
+    /// Handle an `input.undo`/`input.redo` keybind action
+    ///
+    /// Returns false for other actions.
+    pub fn handle_action(&mut self, action_id: &str) -> bool {
+        match HistoryAction::from_action_id(action_id) {
+            Some(action) => {
+                self.apply_history(action);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Set how many undo steps are kept
+    pub fn set_undo_depth(&mut self, depth: usize) {
+        self.undo_history.set_max_depth(depth);
+    }
+
+    /// Undo/redo history
+    pub fn undo_history(&self) -> &UndoHistory<TextSnapshot> {
+        &self.undo_history
+    }
+
+    /// Current text and cursor
+    fn snapshot(&self) -> TextSnapshot {
+        TextSnapshot::new(self.textarea.lines().to_vec(), self.textarea.cursor())
+    }
+
+    /// Undo or redo, restoring text and cursor
+    fn apply_history(&mut self, action: HistoryAction) -> bool {
+        let Some(snapshot) = self.undo_history.apply(action, &self.snapshot()) else {
+            return false;
+        };
+        self.textarea = TextArea::new(snapshot.lines);
+        self.textarea.set_max_histories(0);
+        let (row, col) = snapshot.cursor;
+        self.textarea.move_cursor(CursorMove::Jump(
+            row.min(u16::MAX as usize) as u16,
+            col.min(u16::MAX as usize) as u16,
+        ));
+        self.clear_selection();
+        true
     }
 
     // ========================================================================
@@ -319,7 +375,8 @@ impl TextAreaWidget {
     /// Paste text from clipboard at cursor position
     pub fn paste_from_clipboard(&mut self) -> Result<(), crate::clipboard::ClipboardError> {
         let text = crate::clipboard::ClipboardManager::read_text()?;
-        
+        self.undo_history.record(&self.snapshot(), EditKind::Replace);
+
         // Delete selection if exists
         if self.selection.is_some() {
             self.delete_selection();
@@ -388,12 +445,40 @@ impl Default for TextAreaWidget {
 impl TextAreaWidget {
     /// Handle keyboard input (crossterm KeyEvent)
     pub fn handle_key(&mut self, key: crossterm::event::KeyEvent) {
-        use crossterm::event::KeyCode;
-        
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let alt = key.modifiers.contains(KeyModifiers::ALT);
+
+        // tui-textarea's own undo/redo keys use the shared history
+        match key.code {
+            KeyCode::Char('u') if ctrl && !alt => {
+                self.undo();
+                return;
+            }
+            KeyCode::Char('r') if ctrl && !alt => {
+                self.redo();
+                return;
+            }
+            _ => {}
+        }
+
+        let before = self.snapshot();
+
         // Convert crossterm KeyEvent to tui_textarea::Input
         let input = key_event_to_input(key);
         self.textarea.input(input);
-        
+
+        if self.textarea.lines() != before.lines.as_slice() {
+            let kind = match key.code {
+                KeyCode::Char(c) if !ctrl && !alt => EditKind::for_char(c),
+                KeyCode::Enter | KeyCode::Tab => EditKind::InsertSeparator,
+                KeyCode::Backspace | KeyCode::Delete => EditKind::Delete,
+                _ => EditKind::Replace,
+            };
+            self.undo_history.record(&before, kind);
+        } else if self.textarea.cursor() != before.cursor {
+            self.undo_history.break_group();
+        }
+
         // Update selection if in visual mode
         if matches!(self.vim_state, VimMode::Visual | VimMode::VisualLine) {
             self.update_selection();
@@ -0,0 +1,756 @@
+//! Virtualized message transcript for long sessions
+//!
+//! [`TranscriptView`] keeps one lightweight entry per message or tool call
+//! and only lays out the entries that intersect the viewport. Heights are
+//! cached per width in a [`VirtualScrollManager`], so a session with
+//! thousands of messages costs about as much to draw as a short one.
+//!
+//! Tool outputs are the bulk of a long session. Finished tool calls start
+//! collapsed and keep only their line count; expanding one hydrates the
+//! output through a [`ToolOutputSource`] (usually the [`Session`] loaded
+//! from the session store) and collapsing it drops the text again.
+//!
+//! Tool calls that are still running pin their header to the top of the
+//! viewport once it scrolls out of view, so progress stays visible while
+//! reading earlier output.
+//!
+//! URLs and `path:line:col` references in visible messages and tool output
+//! are reported by [`TranscriptView::links`] so they can be clicked.
+
+use std::collections::{BTreeSet, HashMap};
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{StatefulWidget, Widget},
+};
+use ricecoder_sessions::{
+    models::ToolState, Message, MessagePart, MessageRole, Session, ToolStatus,
+};
+use ricecoder_themes::Theme;
+use unicode_width::UnicodeWidthChar;
+
+use crate::hyperlink::{scan_line, LinkRegion, LinkTarget};
+use crate::performance::VirtualScrollManager;
+
+/// Maximum number of pinned in-progress tool headers
+pub const MAX_STICKY_HEADERS: usize = 3;
+
+/// Columns tool output is indented by
+const OUTPUT_INDENT: u16 = 2;
+
+/// Looks up the full output of a tool call by call id
+pub trait ToolOutputSource {
+    /// Output of the tool call, or `None` if it is unknown
+    fn tool_output(&self, call_id: &str) -> Option<String>;
+}
+
+impl ToolOutputSource for [Message] {
+    fn tool_output(&self, call_id: &str) -> Option<String> {
+        self.iter().find_map(|message| {
+            message
+                .parts
+                .iter()
+                .enumerate()
+                .find(|(index, part)| {
+                    part_call_id(message, *index, part).as_deref() == Some(call_id)
+                })
+                .and_then(|(_, part)| part_output(part))
+        })
+    }
+}
+
+impl ToolOutputSource for Session {
+    fn tool_output(&self, call_id: &str) -> Option<String> {
+        self.history.tool_output(call_id)
+    }
+}
+
+impl ToolOutputSource for HashMap<String, String> {
+    fn tool_output(&self, call_id: &str) -> Option<String> {
+        self.get(call_id).cloned()
+    }
+}
+
+/// Output of a tool call
+#[derive(Debug, Clone, PartialEq)]
+pub enum ToolOutput {
+    /// Not held in memory; hydrated from a [`ToolOutputSource`] on expand
+    Unloaded {
+        /// Number of output lines
+        line_count: usize,
+    },
+    /// Output lines
+    Loaded(Vec<String>),
+}
+
+impl ToolOutput {
+    fn from_text(text: &str) -> Self {
+        ToolOutput::Loaded(text.lines().map(str::to_string).collect())
+    }
+
+    /// Number of output lines
+    pub fn line_count(&self) -> usize {
+        match self {
+            ToolOutput::Unloaded { line_count } => *line_count,
+            ToolOutput::Loaded(lines) => lines.len(),
+        }
+    }
+
+    /// Whether the output is held in memory
+    pub fn is_loaded(&self) -> bool {
+        matches!(self, ToolOutput::Loaded(_))
+    }
+}
+
+/// A tool call shown as a collapsible block
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolCallBlock {
+    /// Tool call id
+    pub call_id: String,
+    /// Tool name
+    pub tool_name: String,
+    /// Short description reported by the tool
+    pub title: Option<String>,
+    /// Execution status
+    pub status: ToolStatus,
+    /// Whether only the header is shown
+    pub collapsed: bool,
+    /// Tool output
+    pub output: ToolOutput,
+}
+
+impl ToolCallBlock {
+    /// Whether the tool call has not finished yet
+    pub fn is_in_progress(&self) -> bool {
+        matches!(self.status, ToolStatus::Pending | ToolStatus::Running)
+    }
+
+    /// Collapse the block and drop its output from memory
+    fn collapse(&mut self) {
+        self.collapsed = true;
+        self.output = ToolOutput::Unloaded {
+            line_count: self.output.line_count(),
+        };
+    }
+}
+
+/// One entry of the transcript
+#[derive(Debug, Clone, PartialEq)]
+pub enum TranscriptEntry {
+    /// Text of a message
+    Message {
+        /// Message author
+        role: MessageRole,
+        /// Message text
+        text: String,
+    },
+    /// A tool call
+    ToolCall(ToolCallBlock),
+}
+
+/// Virtualized transcript state
+#[derive(Debug)]
+pub struct TranscriptView {
+    entries: Vec<TranscriptEntry>,
+    /// Entry index of each tool call by call id
+    tool_index: HashMap<String, usize>,
+    /// Entry indices of tool calls that have not finished
+    in_progress: BTreeSet<usize>,
+    scroll: VirtualScrollManager,
+    /// First visible line
+    scroll_line: usize,
+    /// Viewport height of the last render
+    viewport_height: u16,
+    /// Whether the view sticks to the newest entry
+    follow: bool,
+    /// Links in the viewport of the last render
+    links: Vec<LinkRegion>,
+}
+
+impl Default for TranscriptView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TranscriptView {
+    /// Create an empty transcript that follows new entries
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            tool_index: HashMap::new(),
+            in_progress: BTreeSet::new(),
+            scroll: VirtualScrollManager::new(0, 0),
+            scroll_line: 0,
+            viewport_height: 0,
+            follow: true,
+            links: Vec::new(),
+        }
+    }
+
+    /// Build a transcript from a session history
+    pub fn from_messages(messages: &[Message]) -> Self {
+        let mut view = Self::new();
+        for message in messages {
+            view.push_message(message);
+        }
+        view
+    }
+
+    /// Entries of the transcript
+    pub fn entries(&self) -> &[TranscriptEntry] {
+        &self.entries
+    }
+
+    /// Number of entries
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the transcript is empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Append a message, splitting out its tool calls
+    pub fn push_message(&mut self, message: &Message) {
+        let mut text = String::new();
+        for (index, part) in message.parts.iter().enumerate() {
+            match part {
+                MessagePart::Text {
+                    text: part_text, ..
+                } => {
+                    if !text.is_empty() {
+                        text.push('\n');
+                    }
+                    text.push_str(part_text);
+                }
+                MessagePart::Tool {
+                    call_id,
+                    tool,
+                    state,
+                    ..
+                } => {
+                    self.flush_text(message.role, &mut text);
+                    let (status, title, output) = match state {
+                        ToolState::Pending { .. } => (ToolStatus::Pending, None, None),
+                        ToolState::Running { title, .. } => {
+                            (ToolStatus::Running, title.clone(), None)
+                        }
+                        ToolState::Completed { title, output, .. } => (
+                            ToolStatus::Complete,
+                            Some(title.clone()),
+                            Some(output.as_str()),
+                        ),
+                        ToolState::Error { error, .. } => {
+                            (ToolStatus::Error, None, Some(error.as_str()))
+                        }
+                    };
+                    self.push_tool(call_id.clone(), tool.clone(), title, status, output);
+                }
+                MessagePart::ToolInvocation(invocation) => {
+                    self.flush_text(message.role, &mut text);
+                    self.push_tool(
+                        legacy_call_id(message, index),
+                        invocation.tool_name.clone(),
+                        None,
+                        invocation.status,
+                        None,
+                    );
+                }
+                MessagePart::ToolResult(result) => {
+                    self.flush_text(message.role, &mut text);
+                    let output = part_output(part).unwrap_or_default();
+                    // A legacy result completes the pending invocation of the
+                    // same tool rather than adding a second block
+                    let pending = self.in_progress.iter().rev().copied().find(|&entry| {
+                        matches!(
+                            &self.entries[entry],
+                            TranscriptEntry::ToolCall(block) if block.tool_name == result.tool_name
+                        )
+                    });
+                    match pending {
+                        Some(entry) => {
+                            let call_id = legacy_call_id(message, index);
+                            self.rekey_tool(entry, call_id.clone());
+                            self.update_tool(&call_id, result.status, Some(&output));
+                        }
+                        None => self.push_tool(
+                            legacy_call_id(message, index),
+                            result.tool_name.clone(),
+                            None,
+                            result.status,
+                            Some(&output),
+                        ),
+                    }
+                }
+                _ => {}
+            }
+        }
+        self.flush_text(message.role, &mut text);
+    }
+
+    /// Update the status of a tool call, optionally with its final output
+    ///
+    /// A tool call that finishes successfully collapses and releases its
+    /// output; a failed one stays expanded so the error is visible.
+    pub fn update_tool(&mut self, call_id: &str, status: ToolStatus, output: Option<&str>) {
+        let Some(&entry) = self.tool_index.get(call_id) else {
+            return;
+        };
+        let TranscriptEntry::ToolCall(block) = &mut self.entries[entry] else {
+            return;
+        };
+        block.status = status;
+        if let Some(output) = output {
+            block.output = ToolOutput::from_text(output);
+        }
+        if block.is_in_progress() {
+            self.in_progress.insert(entry);
+        } else {
+            self.in_progress.remove(&entry);
+            if status == ToolStatus::Error {
+                block.collapsed = false;
+            } else {
+                block.collapse();
+            }
+        }
+        self.scroll.invalidate_item(entry);
+    }
+
+    /// Entry index of a tool call
+    pub fn find_tool(&self, call_id: &str) -> Option<usize> {
+        self.tool_index.get(call_id).copied()
+    }
+
+    /// Expand a tool block, hydrating its output from `source` if needed
+    ///
+    /// Returns `false` if the entry is not a tool call.
+    pub fn expand_tool(&mut self, index: usize, source: &(impl ToolOutputSource + ?Sized)) -> bool {
+        let Some(TranscriptEntry::ToolCall(block)) = self.entries.get_mut(index) else {
+            return false;
+        };
+        if !block.output.is_loaded() {
+            block.output = source
+                .tool_output(&block.call_id)
+                .map(|text| ToolOutput::from_text(&text))
+                .unwrap_or_else(|| ToolOutput::Loaded(vec!["(output unavailable)".to_string()]));
+        }
+        block.collapsed = false;
+        self.scroll.invalidate_item(index);
+        true
+    }
+
+    /// Collapse a tool block and release its output
+    pub fn collapse_tool(&mut self, index: usize) -> bool {
+        let Some(TranscriptEntry::ToolCall(block)) = self.entries.get_mut(index) else {
+            return false;
+        };
+        block.collapse();
+        self.scroll.invalidate_item(index);
+        true
+    }
+
+    /// Toggle a tool block between collapsed and expanded
+    pub fn toggle_tool(&mut self, index: usize, source: &(impl ToolOutputSource + ?Sized)) -> bool {
+        match self.entries.get(index) {
+            Some(TranscriptEntry::ToolCall(block)) if block.collapsed => {
+                self.expand_tool(index, source)
+            }
+            Some(TranscriptEntry::ToolCall(_)) => self.collapse_tool(index),
+            _ => false,
+        }
+    }
+
+    /// Entry index shown at `row` of the last rendered viewport
+    ///
+    /// Pinned headers map to their tool call.
+    pub fn entry_at_row(&mut self, row: u16) -> Option<usize> {
+        let sticky = self.sticky_headers();
+        if let Some(&entry) = sticky.get(row as usize) {
+            return Some(entry);
+        }
+        if self.entries.is_empty() {
+            return None;
+        }
+        Some(self.scroll.item_at_line(self.scroll_line + row as usize))
+    }
+
+    /// First visible line
+    pub fn scroll_line(&self) -> usize {
+        self.scroll_line
+    }
+
+    /// Whether the view follows new entries
+    pub fn is_following(&self) -> bool {
+        self.follow
+    }
+
+    /// Scroll towards older entries
+    pub fn scroll_up(&mut self, lines: usize) {
+        self.scroll_line = self.scroll_line.saturating_sub(lines);
+        self.follow = false;
+    }
+
+    /// Scroll towards newer entries, following again at the bottom
+    pub fn scroll_down(&mut self, lines: usize) {
+        let max = self.max_scroll_line();
+        self.scroll_line = (self.scroll_line + lines).min(max);
+        self.follow = self.scroll_line >= max;
+    }
+
+    /// Jump to the first entry
+    pub fn scroll_to_top(&mut self) {
+        self.scroll_line = 0;
+        self.follow = false;
+    }
+
+    /// Jump to the newest entry and follow new ones
+    pub fn scroll_to_bottom(&mut self) {
+        self.follow = true;
+    }
+
+    /// Links in the viewport of the last render, by viewport row
+    pub fn links(&self) -> &[LinkRegion] {
+        &self.links
+    }
+
+    /// Link at viewport position (`column`, `row`) of the last render
+    pub fn link_at(&self, column: u16, row: u16) -> Option<&LinkTarget> {
+        self.links
+            .iter()
+            .find(|link| {
+                link.row == row as usize
+                    && column >= link.column
+                    && column < link.column + link.width
+            })
+            .map(|link| &link.target)
+    }
+
+    /// Tool calls whose headers are pinned, oldest first
+    ///
+    /// An in-progress tool call is pinned once its header has scrolled
+    /// above the viewport.
+    pub fn sticky_headers(&mut self) -> Vec<usize> {
+        let mut pinned: Vec<usize> = Vec::new();
+        for &entry in self.in_progress.iter().rev() {
+            if pinned.len() == MAX_STICKY_HEADERS {
+                break;
+            }
+            if self.scroll.item_offset(entry) < self.scroll_line {
+                pinned.push(entry);
+            }
+        }
+        pinned.reverse();
+        pinned
+    }
+
+    fn max_scroll_line(&mut self) -> usize {
+        self.scroll
+            .total_height()
+            .saturating_sub(self.viewport_height as usize)
+    }
+
+    fn flush_text(&mut self, role: MessageRole, text: &mut String) {
+        if text.trim().is_empty() {
+            text.clear();
+            return;
+        }
+        self.push_entry(TranscriptEntry::Message {
+            role,
+            text: std::mem::take(text),
+        });
+    }
+
+    fn push_tool(
+        &mut self,
+        call_id: String,
+        tool_name: String,
+        title: Option<String>,
+        status: ToolStatus,
+        output: Option<&str>,
+    ) {
+        let mut block = ToolCallBlock {
+            call_id: call_id.clone(),
+            tool_name,
+            title,
+            status,
+            collapsed: false,
+            output: output
+                .map(ToolOutput::from_text)
+                .unwrap_or(ToolOutput::Loaded(Vec::new())),
+        };
+        if status == ToolStatus::Complete {
+            block.collapse();
+        }
+        if block.is_in_progress() {
+            self.in_progress.insert(self.entries.len());
+        }
+        self.tool_index.insert(call_id, self.entries.len());
+        self.push_entry(TranscriptEntry::ToolCall(block));
+    }
+
+    fn rekey_tool(&mut self, entry: usize, call_id: String) {
+        if let TranscriptEntry::ToolCall(block) = &mut self.entries[entry] {
+            self.tool_index.remove(&block.call_id);
+            block.call_id = call_id.clone();
+            self.tool_index.insert(call_id, entry);
+        }
+    }
+
+    fn push_entry(&mut self, entry: TranscriptEntry) {
+        self.entries.push(entry);
+        self.scroll.set_total_items(self.entries.len());
+    }
+
+    /// Lay out the viewport, measuring only the entries it shows
+    fn layout(&mut self, width: u16, height: u16, theme: &Theme) -> Vec<Line<'static>> {
+        self.scroll.set_width(width);
+        self.viewport_height = height;
+        self.links.clear();
+        if self.entries.is_empty() || height == 0 {
+            return Vec::new();
+        }
+
+        if self.follow {
+            // Measure from the end so the bottom is exact, not estimated
+            let mut filled = 0;
+            for index in (0..self.entries.len()).rev() {
+                filled += self.measure(index, width, theme) as usize;
+                if filled >= height as usize {
+                    break;
+                }
+            }
+        }
+        let max = self.max_scroll_line();
+        if self.follow || self.scroll_line > max {
+            self.scroll_line = max;
+        }
+
+        let first = self.scroll.item_at_line(self.scroll_line);
+        let mut skip = self.scroll_line - self.scroll.item_offset(first);
+        let mut lines = Vec::with_capacity(height as usize);
+        for index in first..self.entries.len() {
+            let entry_lines = entry_lines(&self.entries[index], width, theme);
+            self.scroll
+                .record_item_height(index, entry_lines.len() as u16);
+            lines.extend(entry_lines.into_iter().skip(skip));
+            skip = 0;
+            if lines.len() >= height as usize {
+                break;
+            }
+        }
+        lines.truncate(height as usize);
+        for (row, line) in lines.iter().enumerate() {
+            self.links.extend(scan_line(line, row));
+        }
+        lines
+    }
+
+    fn measure(&mut self, index: usize, width: u16, theme: &Theme) -> u16 {
+        if let Some(height) = self.scroll.cached_height(index) {
+            return height;
+        }
+        let height = entry_lines(&self.entries[index], width, theme).len() as u16;
+        self.scroll.record_item_height(index, height);
+        height
+    }
+}
+
+/// Renders a [`TranscriptView`]
+pub struct TranscriptWidget<'a> {
+    theme: &'a Theme,
+}
+
+impl<'a> TranscriptWidget<'a> {
+    /// Create a widget using `theme` colors
+    pub fn new(theme: &'a Theme) -> Self {
+        Self { theme }
+    }
+}
+
+impl StatefulWidget for TranscriptWidget<'_> {
+    type State = TranscriptView;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let lines = state.layout(area.width, area.height, self.theme);
+        for (row, line) in lines.iter().enumerate() {
+            buf.set_line(area.x, area.y + row as u16, line, area.width);
+        }
+
+        let sticky = state.sticky_headers();
+        state.links.retain(|link| link.row >= sticky.len());
+        for link in &state.links {
+            let link_area = Rect::new(
+                area.x + link.column,
+                area.y + link.row as u16,
+                link.width,
+                1,
+            )
+            .intersection(area);
+            buf.set_style(
+                link_area,
+                Style::default().add_modifier(Modifier::UNDERLINED),
+            );
+        }
+        for (row, &entry) in sticky.iter().enumerate().take(area.height as usize) {
+            if let TranscriptEntry::ToolCall(block) = &state.entries[entry] {
+                let y = area.y + row as u16;
+                let row_area = Rect::new(area.x, y, area.width, 1);
+                for x in row_area.left()..row_area.right() {
+                    buf[(x, y)].reset();
+                }
+                buf.set_style(row_area, Style::default().bg(self.theme.background_element));
+                buf.set_line(area.x, y, &tool_header(block, self.theme), area.width);
+            }
+        }
+
+        if state.entries.is_empty() {
+            Line::from(Span::styled(
+                "Start a conversation by typing a message below...",
+                Style::default().fg(self.theme.text_muted),
+            ))
+            .centered()
+            .render(area, buf);
+        }
+    }
+}
+
+/// Lines of an entry at `width`, including the trailing separator
+fn entry_lines(entry: &TranscriptEntry, width: u16, theme: &Theme) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    match entry {
+        TranscriptEntry::Message { role, text } => {
+            let (label, color) = match role {
+                MessageRole::User => ("You", theme.agent_colors.build),
+                MessageRole::Assistant => ("Assistant", theme.success),
+                MessageRole::System => ("System", theme.text_muted),
+            };
+            lines.push(Line::from(Span::styled(
+                format!("{}:", label),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            )));
+            let style = Style::default().fg(theme.foreground);
+            for line in text.lines() {
+                for chunk in wrap(line, width) {
+                    lines.push(Line::from(Span::styled(chunk, style)));
+                }
+            }
+        }
+        TranscriptEntry::ToolCall(block) => {
+            lines.push(tool_header(block, theme));
+            if !block.collapsed {
+                let style = Style::default().fg(if block.status == ToolStatus::Error {
+                    theme.error
+                } else {
+                    theme.text_muted
+                });
+                let indent = " ".repeat(OUTPUT_INDENT as usize);
+                let output_width = width.saturating_sub(OUTPUT_INDENT);
+                match &block.output {
+                    ToolOutput::Loaded(output) => {
+                        for line in output {
+                            for chunk in wrap(line, output_width) {
+                                lines.push(Line::from(Span::styled(
+                                    format!("{}{}", indent, chunk),
+                                    style,
+                                )));
+                            }
+                        }
+                    }
+                    ToolOutput::Unloaded { .. } => {
+                        lines.push(Line::from(Span::styled(format!("{}…", indent), style)));
+                    }
+                }
+            }
+        }
+    }
+    lines.push(Line::from(""));
+    lines
+}
+
+/// Header line of a tool block
+fn tool_header(block: &ToolCallBlock, theme: &Theme) -> Line<'static> {
+    let (marker, status, color) = match block.status {
+        ToolStatus::Pending => ("○", "pending", theme.text_muted),
+        ToolStatus::Running => ("◐", "running", theme.warning),
+        ToolStatus::Complete => ("●", "done", theme.success),
+        ToolStatus::Error => ("✕", "failed", theme.error),
+        ToolStatus::Cancelled => ("⊘", "cancelled", theme.text_muted),
+    };
+    let fold = if block.collapsed { "▸" } else { "▾" };
+    let mut spans = vec![
+        Span::styled(format!("{} {} ", fold, marker), Style::default().fg(color)),
+        Span::styled(
+            block.tool_name.clone(),
+            Style::default()
+                .fg(theme.foreground)
+                .add_modifier(Modifier::BOLD),
+        ),
+    ];
+    if let Some(title) = &block.title {
+        spans.push(Span::styled(
+            format!(" {}", title),
+            Style::default().fg(theme.foreground),
+        ));
+    }
+    let lines = block.output.line_count();
+    let detail = if block.collapsed && lines > 0 {
+        format!(" · {} · {} lines", status, lines)
+    } else {
+        format!(" · {}", status)
+    };
+    spans.push(Span::styled(detail, Style::default().fg(theme.text_muted)));
+    Line::from(spans)
+}
+
+/// Split `line` into chunks at most `width` columns wide
+fn wrap(line: &str, width: u16) -> Vec<String> {
+    let width = width.max(1) as usize;
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    let mut current_width = 0;
+    for ch in line.chars() {
+        let ch_width = ch.width().unwrap_or(0);
+        if current_width + ch_width > width && !current.is_empty() {
+            chunks.push(std::mem::take(&mut current));
+            current_width = 0;
+        }
+        current.push(ch);
+        current_width += ch_width;
+    }
+    chunks.push(current);
+    chunks
+}
+
+/// Call id used for legacy tool parts, which carry none
+fn legacy_call_id(message: &Message, part_index: usize) -> String {
+    format!("{}:{}", message.id, part_index)
+}
+
+fn part_call_id(message: &Message, index: usize, part: &MessagePart) -> Option<String> {
+    match part {
+        MessagePart::Tool { call_id, .. } => Some(call_id.clone()),
+        MessagePart::ToolResult(_) => Some(legacy_call_id(message, index)),
+        _ => None,
+    }
+}
+
+fn part_output(part: &MessagePart) -> Option<String> {
+    match part {
+        MessagePart::Tool { state, .. } => match state {
+            ToolState::Completed { output, .. } => Some(output.clone()),
+            ToolState::Error { error, .. } => Some(error.clone()),
+            _ => None,
+        },
+        MessagePart::ToolResult(result) => Some(match (&result.error, &result.result) {
+            (Some(error), _) => error.clone(),
+            (None, serde_json::Value::String(text)) => text.clone(),
+            (None, value) => serde_json::to_string_pretty(value).unwrap_or_default(),
+        }),
+        _ => None,
+    }
+}
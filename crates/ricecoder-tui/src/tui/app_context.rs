@@ -142,6 +142,8 @@ pub struct AppState {
     pub version: String,
     /// VCS branch (if in git repo)
     pub vcs_branch: Option<String>,
+    /// Whether the working tree has uncommitted changes
+    pub vcs_dirty: bool,
 
     // === Token Tracking ===
     /// Total tokens used in current session
@@ -255,6 +257,7 @@ impl Default for AppState {
                 .unwrap_or_else(|_| ".".to_string()),
             version: env!("CARGO_PKG_VERSION").to_string(),
             vcs_branch: None,
+            vcs_dirty: false,
             command_palette_visible: false,
             tips_hidden: false,
             terminal_title_enabled: true,
@@ -928,6 +931,19 @@ impl AppContext {
             }
         }
 
+        // Any porcelain output means uncommitted changes
+        let output = tokio::process::Command::new("git")
+            .args(["status", "--porcelain", "--untracked-files=no"])
+            .output()
+            .await;
+
+        if let Ok(output) = output {
+            if output.status.success() {
+                let mut state = self.state.write().await;
+                state.vcs_dirty = !output.stdout.is_empty();
+            }
+        }
+
         Ok(())
     }
 
@@ -1129,6 +1145,10 @@ impl AppContext {
                     temperature: Some(0.7),
                     max_tokens: Some(4096),
                     stream: false,
+                    top_p: None,
+                    stop: None,
+                    seed: None,
+                    reasoning_effort: None,
                 };
 
                 match mgr.chat(request).await {
@@ -1228,6 +1248,10 @@ impl AppContext {
                     temperature: Some(0.7),
                     max_tokens: Some(4096),
                     stream: false,
+                    top_p: None,
+                    stop: None,
+                    seed: None,
+                    reasoning_effort: None,
                 };
 
                 match mgr.chat(request).await {
@@ -0,0 +1,576 @@
+//! First-run project bootstrap wizard for the `/init` command
+//!
+//! Walks through what was detected about the project, which language
+//! servers to enable, whether to create the `.ricecoder/` configuration,
+//! the default mode and the default provider, then shows a summary before
+//! anything is written. The wizard opens by itself on the very first run
+//! in a project without configuration.
+//!
+//! Keys: `Enter`/`→` next step, `←` previous step, `↑`/`↓` select,
+//! `Space` toggle, `Esc` skip.
+
+use std::path::PathBuf;
+
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+use ricecoder_providers::ProviderIntegration;
+use ricecoder_themes::Theme;
+
+use crate::project_bootstrap::{
+    BootstrapChoices, ProjectDetection, BOOTSTRAP_MODES, PROJECT_CONFIG_DIR,
+};
+
+/// Whether `input` is an `/init` command
+pub fn parse_init_command(input: &str) -> bool {
+    input.trim() == "/init"
+}
+
+/// Wizard step
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WizardStep {
+    /// What was detected about the project
+    Detection,
+    /// Language servers to enable
+    LanguageServers,
+    /// Whether to write the project configuration
+    Config,
+    /// Default mode
+    Mode,
+    /// Default provider and model
+    Provider,
+    /// Review before writing
+    Summary,
+    /// Outcome of writing the configuration
+    Done,
+}
+
+impl WizardStep {
+    /// Steps in order, excluding [`WizardStep::Done`]
+    const ORDER: [WizardStep; 6] = [
+        WizardStep::Detection,
+        WizardStep::LanguageServers,
+        WizardStep::Config,
+        WizardStep::Mode,
+        WizardStep::Provider,
+        WizardStep::Summary,
+    ];
+
+    fn index(self) -> usize {
+        Self::ORDER
+            .iter()
+            .position(|step| *step == self)
+            .unwrap_or(Self::ORDER.len())
+    }
+
+    /// Title shown in the dialog border
+    pub fn title(self) -> &'static str {
+        match self {
+            WizardStep::Detection => "Project",
+            WizardStep::LanguageServers => "Language Servers",
+            WizardStep::Config => "Configuration",
+            WizardStep::Mode => "Mode",
+            WizardStep::Provider => "Provider",
+            WizardStep::Summary => "Summary",
+            WizardStep::Done => "Done",
+        }
+    }
+}
+
+/// Result of handling a key in the wizard
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BootstrapWizardAction {
+    /// Nothing to do for the caller
+    None,
+    /// Apply these choices and report back with [`BootstrapWizardState::finish`]
+    Finish(BootstrapChoices),
+    /// Wizard should be closed
+    Close,
+}
+
+/// Bootstrap wizard state
+#[derive(Debug, Clone)]
+pub struct BootstrapWizardState {
+    /// What was detected about the project
+    pub detection: ProjectDetection,
+    /// Choices so far
+    pub choices: BootstrapChoices,
+    /// Current step
+    pub step: WizardStep,
+    /// Selected row in list steps
+    pub selected: usize,
+    /// Written config path, or the error, once finished
+    pub outcome: Option<Result<Option<PathBuf>, String>>,
+}
+
+impl BootstrapWizardState {
+    /// Create the wizard with default choices for `detection`
+    pub fn new(detection: ProjectDetection) -> Self {
+        Self {
+            choices: BootstrapChoices::defaults_for(&detection),
+            detection,
+            step: WizardStep::Detection,
+            selected: 0,
+            outcome: None,
+        }
+    }
+
+    /// Record the outcome of applying the choices and show it
+    pub fn finish(&mut self, outcome: Result<Option<PathBuf>, String>) {
+        self.outcome = Some(outcome);
+        self.step = WizardStep::Done;
+    }
+
+    /// Handle a key press
+    pub fn handle_key(&mut self, code: KeyCode) -> BootstrapWizardAction {
+        if self.step == WizardStep::Done {
+            return BootstrapWizardAction::Close;
+        }
+        match code {
+            KeyCode::Esc => BootstrapWizardAction::Close,
+            KeyCode::Enter if self.step == WizardStep::Summary => {
+                BootstrapWizardAction::Finish(self.choices.clone())
+            }
+            KeyCode::Enter | KeyCode::Right | KeyCode::Tab => {
+                self.go_to(self.step.index() + 1);
+                BootstrapWizardAction::None
+            }
+            KeyCode::Left | KeyCode::BackTab => {
+                self.go_to(self.step.index().saturating_sub(1));
+                BootstrapWizardAction::None
+            }
+            KeyCode::Up | KeyCode::Down => {
+                let rows = self.rows();
+                if rows > 0 {
+                    self.selected = if code == KeyCode::Up {
+                        (self.selected + rows - 1) % rows
+                    } else {
+                        (self.selected + 1) % rows
+                    };
+                    self.select_row();
+                }
+                BootstrapWizardAction::None
+            }
+            KeyCode::Char(' ') => {
+                self.toggle_row();
+                BootstrapWizardAction::None
+            }
+            _ => BootstrapWizardAction::None,
+        }
+    }
+
+    fn go_to(&mut self, index: usize) {
+        self.step = WizardStep::ORDER[index.min(WizardStep::ORDER.len() - 1)];
+        // Start list steps on the current choice
+        self.selected = match self.step {
+            WizardStep::Mode => BOOTSTRAP_MODES
+                .iter()
+                .position(|(id, _)| *id == self.choices.mode)
+                .unwrap_or(0),
+            WizardStep::Provider => ProviderIntegration::available_providers()
+                .iter()
+                .position(|id| Some(*id) == self.choices.provider.as_deref())
+                .unwrap_or(0),
+            _ => 0,
+        };
+    }
+
+    /// Number of selectable rows in the current step
+    fn rows(&self) -> usize {
+        match self.step {
+            WizardStep::LanguageServers => self.detection.lsp_servers.len(),
+            WizardStep::Mode => BOOTSTRAP_MODES.len(),
+            WizardStep::Provider => ProviderIntegration::available_providers().len(),
+            _ => 0,
+        }
+    }
+
+    /// Moving the selection picks the row in single-choice steps
+    fn select_row(&mut self) {
+        match self.step {
+            WizardStep::Mode => self.choices.mode = BOOTSTRAP_MODES[self.selected].0.to_string(),
+            WizardStep::Provider => {
+                let provider = ProviderIntegration::available_providers()[self.selected];
+                self.choices.provider = Some(provider.to_string());
+                self.choices.model = ProviderIntegration::available_models_for_provider(provider)
+                    .first()
+                    .map(|model| model.to_string());
+            }
+            _ => {}
+        }
+    }
+
+    fn toggle_row(&mut self) {
+        match self.step {
+            WizardStep::LanguageServers => {
+                let Some(server) = self.detection.lsp_servers.get(self.selected) else {
+                    return;
+                };
+                let enabled = &mut self.choices.lsp_servers;
+                if let Some(pos) = enabled.iter().position(|e| *e == server.executable) {
+                    enabled.remove(pos);
+                } else {
+                    enabled.push(server.executable.clone());
+                }
+            }
+            WizardStep::Config => self.choices.create_config = !self.choices.create_config,
+            WizardStep::Provider => {
+                // Cycle through the models of the selected provider
+                let Some(provider) = self.choices.provider.as_deref() else {
+                    return;
+                };
+                let models = ProviderIntegration::available_models_for_provider(provider);
+                if models.is_empty() {
+                    return;
+                }
+                let next = self
+                    .choices
+                    .model
+                    .as_deref()
+                    .and_then(|model| models.iter().position(|m| *m == model))
+                    .map_or(0, |pos| (pos + 1) % models.len());
+                self.choices.model = Some(models[next].to_string());
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Render the wizard as a centered overlay
+pub fn render_bootstrap_wizard(
+    frame: &mut Frame,
+    area: Rect,
+    state: &BootstrapWizardState,
+    theme: &Theme,
+) {
+    let width = 72.min(area.width.saturating_sub(4));
+    let height = 22.min(area.height.saturating_sub(2));
+    let x = area.x + (area.width.saturating_sub(width)) / 2;
+    let y = area.y + (area.height.saturating_sub(height)) / 2;
+    let overlay_area = Rect {
+        x,
+        y,
+        width,
+        height,
+    };
+
+    frame.render_widget(Clear, overlay_area);
+
+    let text = Style::default().fg(theme.foreground);
+    let muted = Style::default().fg(theme.text_muted);
+    let heading = Style::default()
+        .fg(theme.border_active)
+        .add_modifier(Modifier::BOLD);
+    let row = |selected: bool, checked: bool, label: String| {
+        let marker = if checked { "[x] " } else { "[ ] " };
+        let style = if selected { heading } else { text };
+        Line::from(vec![
+            Span::styled(if selected { "› " } else { "  " }, heading),
+            Span::styled(marker, style),
+            Span::styled(label, style),
+        ])
+    };
+
+    let detection = &state.detection;
+    let choices = &state.choices;
+    let mut content: Vec<Line> = Vec::new();
+    match state.step {
+        WizardStep::Detection => {
+            let languages: Vec<String> = detection
+                .languages
+                .iter()
+                .map(|language| format!("{:?}", language))
+                .collect();
+            let tools: Vec<&str> = detection.build_tools.iter().map(|t| t.name()).collect();
+            content.push(Line::from(Span::styled(
+                "Detected in this project",
+                heading,
+            )));
+            content.push(Line::from(""));
+            for (label, value) in [
+                ("Type", format!("{:?}", detection.project_type)),
+                ("Languages", list_or_none(&languages)),
+                ("Build tools", list_or_none(&tools)),
+                ("Frameworks", list_or_none(&detection.frameworks)),
+            ] {
+                content.push(Line::from(vec![
+                    Span::styled(format!("{:<13}", label), muted),
+                    Span::styled(value, text),
+                ]));
+            }
+            if detection.has_project_config {
+                content.push(Line::from(""));
+                content.push(Line::from(Span::styled(
+                    format!("{}/ already has a configuration", PROJECT_CONFIG_DIR),
+                    Style::default().fg(theme.warning),
+                )));
+            }
+        }
+        WizardStep::LanguageServers => {
+            content.push(Line::from(Span::styled(
+                "Language servers to enable",
+                heading,
+            )));
+            content.push(Line::from(""));
+            if detection.lsp_servers.is_empty() {
+                content.push(Line::from(Span::styled(
+                    "No language server is known for the detected languages",
+                    muted,
+                )));
+            }
+            for (idx, server) in detection.lsp_servers.iter().enumerate() {
+                let enabled = choices.lsp_servers.contains(&server.executable);
+                let status = if server.installed {
+                    "installed"
+                } else {
+                    "not installed"
+                };
+                content.push(row(
+                    idx == state.selected,
+                    enabled,
+                    format!("{} ({}) · {}", server.executable, server.language, status),
+                ));
+            }
+            if let Some(server) = detection
+                .lsp_servers
+                .get(state.selected)
+                .filter(|server| !server.installed)
+            {
+                content.push(Line::from(""));
+                content.extend(
+                    server
+                        .install_hint
+                        .lines()
+                        .map(|line| Line::from(Span::styled(line.to_string(), muted))),
+                );
+            }
+        }
+        WizardStep::Config => {
+            content.push(Line::from(Span::styled("Project configuration", heading)));
+            content.push(Line::from(""));
+            content.push(row(
+                true,
+                choices.create_config,
+                format!("Create {}/config.yaml", PROJECT_CONFIG_DIR),
+            ));
+            content.push(Line::from(""));
+            content.push(Line::from(Span::styled(
+                "Stores the mode, provider, language servers and detected build tools \
+                 so every session in this project starts with them.",
+                muted,
+            )));
+        }
+        WizardStep::Mode => {
+            content.push(Line::from(Span::styled("Default mode", heading)));
+            content.push(Line::from(""));
+            for (idx, (id, description)) in BOOTSTRAP_MODES.iter().enumerate() {
+                content.push(row(
+                    idx == state.selected,
+                    choices.mode == *id,
+                    format!("{:<6} {}", id, description),
+                ));
+            }
+        }
+        WizardStep::Provider => {
+            content.push(Line::from(Span::styled("Default provider", heading)));
+            content.push(Line::from(""));
+            for (idx, provider) in ProviderIntegration::available_providers()
+                .iter()
+                .enumerate()
+            {
+                let chosen = choices.provider.as_deref() == Some(*provider);
+                let model = if chosen {
+                    choices
+                        .model
+                        .as_deref()
+                        .map(|model| format!(" · {}", model))
+                        .unwrap_or_default()
+                } else {
+                    String::new()
+                };
+                content.push(row(
+                    idx == state.selected,
+                    chosen,
+                    format!("{}{}", provider, model),
+                ));
+            }
+        }
+        WizardStep::Summary | WizardStep::Done => {
+            content.push(Line::from(Span::styled(
+                if state.step == WizardStep::Done {
+                    "Setup complete"
+                } else {
+                    "Review"
+                },
+                heading,
+            )));
+            content.push(Line::from(""));
+            for (label, value) in [
+                ("Mode", choices.mode.clone()),
+                (
+                    "Provider",
+                    match (&choices.provider, &choices.model) {
+                        (Some(provider), Some(model)) => format!("{} · {}", provider, model),
+                        (Some(provider), None) => provider.clone(),
+                        (None, _) => "none".to_string(),
+                    },
+                ),
+                ("LSP servers", list_or_none(&choices.lsp_servers)),
+                (
+                    "Config",
+                    if choices.create_config {
+                        format!("write {}/config.yaml", PROJECT_CONFIG_DIR)
+                    } else {
+                        "this session only".to_string()
+                    },
+                ),
+            ] {
+                content.push(Line::from(vec![
+                    Span::styled(format!("{:<13}", label), muted),
+                    Span::styled(value, text),
+                ]));
+            }
+            match &state.outcome {
+                Some(Ok(Some(path))) => {
+                    content.push(Line::from(""));
+                    content.push(Line::from(Span::styled(
+                        format!("Wrote {}", path.display()),
+                        Style::default().fg(theme.success),
+                    )));
+                }
+                Some(Err(error)) => {
+                    content.push(Line::from(""));
+                    content.push(Line::from(Span::styled(
+                        format!("Failed to write configuration: {}", error),
+                        Style::default().fg(theme.error),
+                    )));
+                }
+                Some(Ok(None)) | None => {}
+            }
+        }
+    }
+
+    content.push(Line::from(""));
+    let hint = match state.step {
+        WizardStep::Summary => "enter apply · ← back · esc skip",
+        WizardStep::Done => "any key to close",
+        WizardStep::LanguageServers | WizardStep::Config => {
+            "space toggle · enter next · ← back · esc skip"
+        }
+        WizardStep::Provider => "↑/↓ provider · space model · enter next · ← back · esc skip",
+        _ => "↑/↓ select · enter next · ← back · esc skip",
+    };
+    content.push(Line::from(Span::styled(hint, muted)));
+
+    let title = if state.step == WizardStep::Done {
+        " Project Setup ".to_string()
+    } else {
+        format!(
+            " Project Setup · {} ({}/{}) ",
+            state.step.title(),
+            state.step.index() + 1,
+            WizardStep::ORDER.len()
+        )
+    };
+    let wizard = Paragraph::new(content)
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border_active))
+                .style(Style::default().bg(theme.background_element)),
+        )
+        .wrap(Wrap { trim: false });
+
+    frame.render_widget(wizard, overlay_area);
+}
+
+fn list_or_none<S: AsRef<str>>(items: &[S]) -> String {
+    if items.is_empty() {
+        "none".to_string()
+    } else {
+        items
+            .iter()
+            .map(AsRef::as_ref)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project_bootstrap::{BuildTool, Language, LspSuggestion, ProjectType};
+
+    fn detection() -> ProjectDetection {
+        ProjectDetection {
+            project_type: ProjectType::Application,
+            languages: vec![Language::Rust],
+            build_tools: vec![BuildTool::Cargo],
+            frameworks: Vec::new(),
+            lsp_servers: vec![LspSuggestion {
+                language: "rust".to_string(),
+                executable: "rust-analyzer".to_string(),
+                installed: false,
+                install_hint: String::new(),
+            }],
+            has_project_config: false,
+        }
+    }
+
+    #[test]
+    fn test_parse_init_command() {
+        assert!(parse_init_command("/init"));
+        assert!(parse_init_command("  /init "));
+        assert!(!parse_init_command("/initialize"));
+    }
+
+    #[test]
+    fn test_walk_through_to_finish() {
+        let mut state = BootstrapWizardState::new(detection());
+        assert!(state.choices.create_config);
+        assert!(state.choices.lsp_servers.is_empty());
+
+        state.handle_key(KeyCode::Enter);
+        assert_eq!(state.step, WizardStep::LanguageServers);
+        state.handle_key(KeyCode::Char(' '));
+        assert_eq!(state.choices.lsp_servers, vec!["rust-analyzer".to_string()]);
+
+        state.handle_key(KeyCode::Enter);
+        state.handle_key(KeyCode::Enter);
+        assert_eq!(state.step, WizardStep::Mode);
+        state.handle_key(KeyCode::Down);
+        assert_eq!(state.choices.mode, BOOTSTRAP_MODES[1].0);
+
+        state.handle_key(KeyCode::Enter);
+        state.handle_key(KeyCode::Enter);
+        assert_eq!(state.step, WizardStep::Summary);
+        assert_eq!(
+            state.handle_key(KeyCode::Enter),
+            BootstrapWizardAction::Finish(state.choices.clone())
+        );
+
+        state.finish(Ok(None));
+        assert_eq!(state.step, WizardStep::Done);
+        assert_eq!(
+            state.handle_key(KeyCode::Char('x')),
+            BootstrapWizardAction::Close
+        );
+    }
+
+    #[test]
+    fn test_back_and_skip() {
+        let mut state = BootstrapWizardState::new(detection());
+        state.handle_key(KeyCode::Left);
+        assert_eq!(state.step, WizardStep::Detection);
+        state.handle_key(KeyCode::Right);
+        state.handle_key(KeyCode::Left);
+        assert_eq!(state.step, WizardStep::Detection);
+        assert_eq!(state.handle_key(KeyCode::Esc), BootstrapWizardAction::Close);
+    }
+}
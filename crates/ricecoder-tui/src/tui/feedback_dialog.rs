@@ -0,0 +1,360 @@
+//! Feedback dialog for the `/feedback` command
+//!
+//! Lets the user pick a category, give an optional 1-5 rating and write a
+//! comment. Submission produces a [`CapturedFeedback`]; the caller attaches
+//! the anonymized context (mode, provider, model) and hands it to the
+//! feedback capture service, which queues it locally when offline.
+//!
+//! Keys: `Tab`/`Shift+Tab` move between fields, `←`/`→` change the category
+//! or rating, `1`-`5` set the rating, `Enter` submits and `Esc` cancels.
+
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+use ricecoder_beta::improvement::{CapturedFeedback, FeedbackCategory};
+use ricecoder_themes::Theme;
+
+/// Extract the arguments of a `/feedback` command, if `input` is one
+pub fn parse_feedback_command(input: &str) -> Option<&str> {
+    let rest = input.trim().strip_prefix("/feedback")?;
+    if rest.is_empty() || rest.starts_with(char::is_whitespace) {
+        Some(rest.trim())
+    } else {
+        None
+    }
+}
+
+/// Field that currently receives input
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FeedbackField {
+    Category,
+    Rating,
+    #[default]
+    Text,
+}
+
+impl FeedbackField {
+    fn next(self) -> Self {
+        match self {
+            Self::Category => Self::Rating,
+            Self::Rating => Self::Text,
+            Self::Text => Self::Category,
+        }
+    }
+
+    fn previous(self) -> Self {
+        match self {
+            Self::Category => Self::Text,
+            Self::Rating => Self::Category,
+            Self::Text => Self::Rating,
+        }
+    }
+}
+
+/// Result of handling a key in the dialog
+#[derive(Debug, Clone, PartialEq)]
+pub enum FeedbackDialogAction {
+    /// Nothing to do for the caller
+    None,
+    /// Feedback is ready to be submitted
+    Submit(CapturedFeedback),
+    /// Dialog should be closed
+    Close,
+}
+
+/// Feedback dialog state
+#[derive(Debug, Clone, Default)]
+pub struct FeedbackDialogState {
+    /// Index into [`FeedbackCategory::all`]
+    pub category_idx: usize,
+    /// Optional 1-5 rating
+    pub rating: Option<u8>,
+    /// Free-text comment
+    pub text: String,
+    /// Focused field
+    pub field: FeedbackField,
+    /// Validation or submission error
+    pub error: Option<String>,
+    /// Confirmation shown after submission
+    pub status: Option<String>,
+}
+
+impl FeedbackDialogState {
+    /// Create a dialog prefilled from `/feedback` arguments
+    ///
+    /// A leading category word (e.g. `/feedback bug ...`) selects that category.
+    pub fn new(args: &str) -> Self {
+        let mut state = Self::default();
+        let args = args.trim();
+        let (first, rest) = args.split_once(char::is_whitespace).unwrap_or((args, ""));
+        match FeedbackCategory::parse(first) {
+            Some(category) => {
+                state.category_idx = FeedbackCategory::all()
+                    .iter()
+                    .position(|c| *c == category)
+                    .unwrap_or(0);
+                state.text = rest.trim().to_string();
+            }
+            None => {
+                state.category_idx = FeedbackCategory::all()
+                    .iter()
+                    .position(|c| *c == FeedbackCategory::General)
+                    .unwrap_or(0);
+                state.text = args.to_string();
+            }
+        }
+        state
+    }
+
+    /// Selected category
+    pub fn category(&self) -> FeedbackCategory {
+        FeedbackCategory::all()[self.category_idx % FeedbackCategory::all().len()]
+    }
+
+    /// Build the feedback from the current fields
+    pub fn feedback(&self) -> CapturedFeedback {
+        let feedback = CapturedFeedback::new(self.category(), self.text.trim());
+        match self.rating {
+            Some(rating) => feedback.with_rating(rating),
+            None => feedback,
+        }
+    }
+
+    /// Handle a key press
+    pub fn handle_key(&mut self, code: KeyCode, modifiers: KeyModifiers) -> FeedbackDialogAction {
+        if self.status.is_some() {
+            return FeedbackDialogAction::Close;
+        }
+
+        match code {
+            KeyCode::Esc => return FeedbackDialogAction::Close,
+            KeyCode::Enter => {
+                let feedback = self.feedback();
+                return match feedback.validate() {
+                    Ok(()) => {
+                        self.error = None;
+                        FeedbackDialogAction::Submit(feedback)
+                    }
+                    Err(e) => {
+                        self.error = Some(e.to_string());
+                        FeedbackDialogAction::None
+                    }
+                };
+            }
+            KeyCode::Tab => self.field = self.field.next(),
+            KeyCode::BackTab => self.field = self.field.previous(),
+            _ => match self.field {
+                FeedbackField::Category => self.handle_category_key(code),
+                FeedbackField::Rating => self.handle_rating_key(code),
+                FeedbackField::Text => self.handle_text_key(code, modifiers),
+            },
+        }
+
+        FeedbackDialogAction::None
+    }
+
+    fn handle_category_key(&mut self, code: KeyCode) {
+        let count = FeedbackCategory::all().len();
+        match code {
+            KeyCode::Left | KeyCode::Up => {
+                self.category_idx = (self.category_idx + count - 1) % count
+            }
+            KeyCode::Right | KeyCode::Down => self.category_idx = (self.category_idx + 1) % count,
+            _ => {}
+        }
+    }
+
+    fn handle_rating_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char(c @ '1'..='5') => self.rating = c.to_digit(10).map(|d| d as u8),
+            KeyCode::Char('0') | KeyCode::Backspace | KeyCode::Delete => self.rating = None,
+            KeyCode::Left | KeyCode::Down => {
+                self.rating = match self.rating {
+                    Some(1) | None => None,
+                    Some(r) => Some(r - 1),
+                }
+            }
+            KeyCode::Right | KeyCode::Up => {
+                self.rating = Some(self.rating.map_or(1, |r| (r + 1).min(5)));
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_text_key(&mut self, code: KeyCode, modifiers: KeyModifiers) {
+        match code {
+            KeyCode::Char('u') if modifiers.contains(KeyModifiers::CONTROL) => self.text.clear(),
+            KeyCode::Char(c) => self.text.push(c),
+            KeyCode::Backspace => {
+                self.text.pop();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Render the feedback dialog as a centered overlay
+pub fn render_feedback_dialog(
+    frame: &mut Frame,
+    area: Rect,
+    state: &FeedbackDialogState,
+    theme: &Theme,
+) {
+    let width = 70.min(area.width.saturating_sub(4));
+    let height = 14.min(area.height.saturating_sub(2));
+    let x = area.x + (area.width.saturating_sub(width)) / 2;
+    let y = area.y + (area.height.saturating_sub(height)) / 2;
+    let overlay_area = Rect {
+        x,
+        y,
+        width,
+        height,
+    };
+
+    frame.render_widget(Clear, overlay_area);
+
+    let label = |field: FeedbackField, text: &'static str| {
+        let style = if state.field == field {
+            Style::default()
+                .fg(theme.border_active)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text_muted)
+        };
+        Span::styled(format!("{:<10}", text), style)
+    };
+
+    let categories: Vec<Span> = FeedbackCategory::all()
+        .iter()
+        .enumerate()
+        .flat_map(|(idx, category)| {
+            let style = if idx == state.category_idx {
+                Style::default().fg(theme.background).bg(theme.primary)
+            } else {
+                Style::default().fg(theme.foreground)
+            };
+            [
+                Span::styled(format!(" {} ", category.label()), style),
+                Span::raw(" "),
+            ]
+        })
+        .collect();
+
+    let stars: String = (1..=5)
+        .map(|n| {
+            if state.rating.is_some_and(|r| n <= r) {
+                '★'
+            } else {
+                '☆'
+            }
+        })
+        .collect();
+    let rating_hint = match state.rating {
+        Some(r) => format!("  {}/5", r),
+        None => "  (optional)".to_string(),
+    };
+
+    let mut content = vec![
+        Line::from([vec![label(FeedbackField::Category, "Category")], categories].concat()),
+        Line::from(""),
+        Line::from(vec![
+            label(FeedbackField::Rating, "Rating"),
+            Span::styled(stars, Style::default().fg(theme.warning)),
+            Span::styled(rating_hint, Style::default().fg(theme.text_muted)),
+        ]),
+        Line::from(""),
+        Line::from(label(FeedbackField::Text, "Comment")),
+        Line::from(Span::styled(
+            if state.field == FeedbackField::Text && state.status.is_none() {
+                format!("{}█", state.text)
+            } else {
+                state.text.clone()
+            },
+            Style::default().fg(theme.foreground),
+        )),
+        Line::from(""),
+    ];
+
+    if let Some(status) = &state.status {
+        content.push(Line::from(Span::styled(
+            status.clone(),
+            Style::default().fg(theme.success),
+        )));
+        content.push(Line::from(Span::styled(
+            "Press any key to close",
+            Style::default().fg(theme.text_muted),
+        )));
+    } else {
+        if let Some(error) = &state.error {
+            content.push(Line::from(Span::styled(
+                error.clone(),
+                Style::default().fg(theme.error),
+            )));
+        }
+        content.push(Line::from(Span::styled(
+            "tab next field · ←/→ change · enter send · esc cancel",
+            Style::default().fg(theme.text_muted),
+        )));
+    }
+
+    let dialog = Paragraph::new(content)
+        .block(
+            Block::default()
+                .title(" Send Feedback ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border_active))
+                .style(Style::default().bg(theme.background_element)),
+        )
+        .wrap(Wrap { trim: false });
+
+    frame.render_widget(dialog, overlay_area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_feedback_command() {
+        assert_eq!(parse_feedback_command("/feedback"), Some(""));
+        assert_eq!(
+            parse_feedback_command("  /feedback bug crash "),
+            Some("bug crash")
+        );
+        assert_eq!(parse_feedback_command("/feedbacks"), None);
+        assert_eq!(parse_feedback_command("feedback"), None);
+    }
+
+    #[test]
+    fn test_prefill_category() {
+        let state = FeedbackDialogState::new("bug crashes on resize");
+        assert_eq!(state.category(), FeedbackCategory::Bug);
+        assert_eq!(state.text, "crashes on resize");
+
+        let state = FeedbackDialogState::new("love it");
+        assert_eq!(state.category(), FeedbackCategory::General);
+        assert_eq!(state.text, "love it");
+    }
+
+    #[test]
+    fn test_submit_requires_content() {
+        let mut state = FeedbackDialogState::new("");
+        assert_eq!(
+            state.handle_key(KeyCode::Enter, KeyModifiers::NONE),
+            FeedbackDialogAction::None
+        );
+        assert!(state.error.is_some());
+
+        state.handle_key(KeyCode::BackTab, KeyModifiers::NONE);
+        state.handle_key(KeyCode::Char('4'), KeyModifiers::NONE);
+        match state.handle_key(KeyCode::Enter, KeyModifiers::NONE) {
+            FeedbackDialogAction::Submit(feedback) => assert_eq!(feedback.rating, Some(4)),
+            other => panic!("expected submit, got {:?}", other),
+        }
+    }
+}
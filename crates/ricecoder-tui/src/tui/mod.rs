@@ -9,12 +9,17 @@
 //! - Real-time sync with sessions, providers, MCP status
 
 pub mod app_context;
+pub mod bootstrap_wizard;
 pub mod border;
 pub mod context;
 pub mod did_you_know;
+pub mod feedback_dialog;
 pub mod keybind_bridge;
+pub mod paste_preview;
 pub mod prompt;
 pub mod routes;
+pub mod telemetry_dialog;
+pub mod theme_editor;
 pub mod todo_item;
 
 pub use app_context::{
@@ -22,12 +27,17 @@ pub use app_context::{
     ProviderInfo, SessionSummary, SyncStatus,
     AppState as BackendAppState,  // Renamed to avoid conflict with TUI AppState
 };
+pub use bootstrap_wizard::{BootstrapWizardAction, BootstrapWizardState, WizardStep};
 pub use border::{SplitBorder, EMPTY_BORDER};
 pub use context::{
     Args, ArgsProvider, LazyProvider, LocalProvider, PromptRef, PromptRefProvider, SdkProvider,
     SimpleProvider,
 };
 pub use did_you_know::DidYouKnow;
+pub use feedback_dialog::{FeedbackDialogAction, FeedbackDialogState};
+pub use paste_preview::{PasteInsertion, PastePreviewAction, PastePreviewState};
+pub use telemetry_dialog::{TelemetryDialogAction, TelemetryDialogState};
+pub use theme_editor::{ThemeEditorAction, ThemeEditorState};
 pub use routes::{
     // Home route
     Home, HomeState, HomeTheme, HomeView, McpStatus,
@@ -37,11 +47,14 @@ pub use routes::{
     ItemStatus, KeybindHint, SessionFooter, SessionFooterTheme, SessionHeader, SessionHeaderTheme,
     SessionSidebar, SidebarItem, SidebarSection, SidebarTheme,
 };
-pub use todo_item::{TodoItem, TodoStatus};
+pub use todo_item::{TodoItem, TodoStatus, TodoTree};
 
 use anyhow::Result;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
+    event::{
+        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+        Event, KeyCode, KeyEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -53,11 +66,28 @@ use ratatui::{
     widgets::{Block, Borders, Clear, Paragraph, Wrap},
     Frame, Terminal,
 };
+use std::collections::HashMap;
 use std::io::{self, Stdout};
+use std::path::PathBuf;
 use std::time::Duration;
 
-use ricecoder_storage::TuiConfig;
-use ricecoder_themes::Theme;
+use ricecoder_beta::improvement::{
+    AnalyticsPipeline, AnalyticsPipelineConfig, CaptureOutcome, CapturedFeedback,
+    FeedbackCaptureService, FeedbackContext, FeedbackPipeline, FeedbackPipelineConfig,
+    FeedbackQueue,
+};
+use ricecoder_storage::{ConfigLoader, TuiConfig, TuiHyperlinkConfig};
+use ricecoder_themes::{Theme, ThemeManager, ThemeWatcher};
+
+use crate::{
+    clipboard::{ClipboardData, ClipboardManager},
+    code_editor_widget::CodeEditorWidget,
+    hyperlink::{HyperlinkMap, LinkTarget},
+    project_bootstrap::ProjectBootstrap,
+    status_segments::{StatusBar, StatusContext, VcsState},
+    terminal_state::TerminalCapabilities,
+};
+use prompt::ExternalEditor;
 
 /// Current route in the TUI
 #[derive(Debug, Clone, PartialEq)]
@@ -101,6 +131,20 @@ pub struct TuiState {
     pub theme: Theme,
     /// Token usage display string (e.g., "12.5K tokens | $0.03")
     pub token_display: String,
+    /// Feedback dialog (open while `/feedback` is active)
+    pub feedback_dialog: Option<FeedbackDialogState>,
+    /// Telemetry dialog (open while `/telemetry` is active)
+    pub telemetry_dialog: Option<TelemetryDialogState>,
+    /// Theme color editor (open while `/theme-edit` is active)
+    pub theme_editor: Option<ThemeEditorState>,
+    /// Project setup wizard (open on first run or while `/init` is active)
+    pub bootstrap_wizard: Option<BootstrapWizardState>,
+    /// Paste preview (open while confirming a multi-line, large or file paste)
+    pub paste_preview: Option<PastePreviewState>,
+    /// Status bar segments shown in the session footer
+    pub status_bar: StatusBar,
+    /// Version control state for the status bar
+    pub vcs: Option<VcsState>,
 }
 
 /// Session status
@@ -148,6 +192,13 @@ impl Default for TuiState {
             messages: Vec::new(),
             theme: Theme::default(), // Default dark theme
             token_display: String::new(),
+            feedback_dialog: None,
+            telemetry_dialog: None,
+            theme_editor: None,
+            bootstrap_wizard: None,
+            paste_preview: None,
+            status_bar: StatusBar::default(),
+            vcs: None,
         }
     }
 }
@@ -194,6 +245,26 @@ pub struct TuiApp {
     running: bool,
     /// Placeholder index for prompt
     placeholder_idx: usize,
+    /// In-product feedback capture (queues locally when offline)
+    feedback: FeedbackCaptureService,
+    /// Feature usage analytics (only aggregated counters are ever exported)
+    analytics: AnalyticsPipeline,
+    /// Active theme with user color overrides
+    theme_manager: ThemeManager,
+    /// Watches user theme files for hot reload
+    theme_watcher: Option<ThemeWatcher>,
+    /// System clipboard with copy/paste history
+    clipboard: ClipboardManager,
+    /// Links on screen in the last frame
+    hyperlinks: HyperlinkMap,
+    /// Links last written to the terminal as OSC 8 sequences
+    emitted_links: HyperlinkMap,
+    /// Whether links are written as OSC 8 hyperlinks
+    osc8_enabled: bool,
+    /// External editor for opening file links (built-in editor when unset)
+    link_editor: Option<String>,
+    /// Built-in editor opened from a file link
+    code_editor: Option<CodeEditorWidget>,
 }
 
 impl TuiApp {
@@ -202,7 +273,12 @@ impl TuiApp {
         // Setup terminal
         enable_raw_mode()?;
         let mut stdout = io::stdout();
-        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+        execute!(
+            stdout,
+            EnterAlternateScreen,
+            EnableMouseCapture,
+            EnableBracketedPaste
+        )?;
         let backend = CrosstermBackend::new(stdout);
         let terminal = Terminal::new(backend)?;
 
@@ -215,12 +291,59 @@ impl TuiApp {
         // Create backend application context
         let app_context = std::sync::Arc::new(AppContext::new());
 
+        // Feedback capture with an on-disk queue for offline use
+        let queue_path = FeedbackQueue::default_path()
+            .unwrap_or_else(|| std::env::temp_dir().join("ricecoder-feedback-queue.jsonl"));
+        let feedback = FeedbackCaptureService::new(FeedbackQueue::new(queue_path)).with_pipeline(
+            std::sync::Arc::new(FeedbackPipeline::new(FeedbackPipelineConfig::default())),
+        );
+
+        // Themes: custom theme files plus color overrides from config
+        let theme_manager = ThemeManager::new();
+        if let Ok(dir) = ThemeManager::custom_themes_directory() {
+            if let Err(e) = theme_manager.load_and_register_custom_themes(&dir) {
+                tracing::warn!("Failed to load custom themes: {}", e);
+            }
+        }
+        let mut state = TuiState::default();
+        let mut hyperlink_config = Default::default();
+        match ConfigLoader::new().load_merged() {
+            Ok(config) => {
+                if let Err(e) = theme_manager.load_from_config(&config.tui) {
+                    tracing::warn!("Failed to apply theme from config: {}", e);
+                }
+                state.status_bar.apply_config(&config.tui.status_bar);
+                hyperlink_config = config.tui.hyperlinks;
+            }
+            Err(e) => tracing::warn!("Failed to load config for theme: {}", e),
+        }
+        let TuiHyperlinkConfig { enabled, editor } = hyperlink_config;
+        let osc8_enabled =
+            enabled.unwrap_or_else(|| TerminalCapabilities::detect().hyperlink_support);
+        let theme_watcher = ThemeWatcher::for_user_themes()
+            .map_err(|e| tracing::warn!("Theme hot reload unavailable: {}", e))
+            .ok();
+
+        if let Ok(theme) = theme_manager.current() {
+            state.theme = theme;
+        }
+
         Ok(Self {
             terminal,
-            state: TuiState::default(),
+            state,
             app_context,
             running: true,
             placeholder_idx,
+            feedback,
+            analytics: AnalyticsPipeline::new(AnalyticsPipelineConfig::default()),
+            theme_manager,
+            theme_watcher,
+            clipboard: ClipboardManager::new(),
+            hyperlinks: HyperlinkMap::new(),
+            emitted_links: HyperlinkMap::new(),
+            osc8_enabled,
+            link_editor: editor,
+            code_editor: None,
         })
     }
 
@@ -235,15 +358,28 @@ impl TuiApp {
         // Sync initial state from backend
         self.sync_state_from_context().await;
 
+        // Deliver feedback queued while offline
+        if let Err(e) = self.feedback.flush().await {
+            tracing::warn!("Failed to flush queued feedback: {}", e);
+        }
+
         while self.running {
             // Capture state snapshot for rendering (avoids borrow conflict)
             let state = self.state.clone();
             let placeholder_idx = self.placeholder_idx;
             
             // Draw the UI with captured state
+            let mut links = HyperlinkMap::new();
+            let code_editor = &mut self.code_editor;
             self.terminal.draw(|frame| {
-                render_ui(frame, &state, placeholder_idx);
+                render_ui(frame, &state, placeholder_idx, &mut links);
+                if let Some(editor) = code_editor.as_mut() {
+                    render_code_editor(frame, editor);
+                    links = HyperlinkMap::new();
+                }
             })?;
+            self.hyperlinks = links;
+            self.emit_hyperlinks();
 
             // Handle events
             if event::poll(Duration::from_millis(50))? {
@@ -252,18 +388,30 @@ impl TuiApp {
                         self.handle_key(key.code, key.modifiers).await;
                     }
                     Event::Resize(_, _) => {
-                        // Terminal will auto-redraw
+                        // Terminal will auto-redraw; links are written again
+                        self.emitted_links = HyperlinkMap::new();
                     }
                     Event::Mouse(mouse) => {
                         self.handle_mouse(mouse);
                     }
+                    Event::Paste(text) => {
+                        self.handle_paste(text);
+                    }
                     _ => {}
                 }
             }
 
+            // Pick up edited theme files without a restart
+            self.poll_theme_changes();
+
             tokio::task::yield_now().await;
         }
 
+        // Send the aggregated telemetry window (no-op unless opted in)
+        if let Err(e) = self.analytics.export_telemetry().await {
+            tracing::warn!("Failed to export telemetry: {}", e);
+        }
+
         Ok(())
     }
 
@@ -291,25 +439,96 @@ impl TuiApp {
         
         // Sync token usage display
         self.state.token_display = backend_state.token_display();
-        
-        // VCS branch is tracked in backend_state.vcs_branch but not exposed in HomeState
-        // This can be used for status bar display in the future
+
+        // Sync VCS state for the status bar
+        self.state.vcs = backend_state.vcs_branch.clone().map(|branch| VcsState {
+            branch,
+            dirty: backend_state.vcs_dirty,
+            ..VcsState::default()
+        });
+    }
+
+    /// Re-apply theme files that changed on disk
+    fn poll_theme_changes(&mut self) {
+        let Some(watcher) = self.theme_watcher.as_mut() else {
+            return;
+        };
+        for path in watcher.poll_changes() {
+            match self.theme_manager.reload_theme_file(&path) {
+                // Keep an open editor's preview; it is re-derived on the next key
+                Ok(true) if self.state.theme_editor.is_none() => {
+                    if let Ok(theme) = self.theme_manager.current() {
+                        self.state.theme = theme;
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => tracing::warn!("Failed to reload theme {}: {}", path.display(), e),
+            }
+        }
     }
 }
 
 /// Standalone render function - avoids borrow conflicts with terminal
-fn render_ui(frame: &mut Frame, state: &TuiState, placeholder_idx: usize) {
+fn render_ui(
+    frame: &mut Frame,
+    state: &TuiState,
+    placeholder_idx: usize,
+    links: &mut HyperlinkMap,
+) {
     let area = frame.area();
 
     match &state.route {
         Route::Home => render_home(frame, area, state, placeholder_idx),
-        Route::Session { .. } => render_session(frame, area, state),
+        Route::Session { .. } => render_session(frame, area, state, links),
     }
 
     // Render command palette overlay if visible
     if state.command_palette_visible {
         render_command_palette(frame, area, state);
     }
+
+    // Render feedback dialog overlay if open
+    if let Some(dialog) = &state.feedback_dialog {
+        feedback_dialog::render_feedback_dialog(frame, area, dialog, &state.theme);
+    }
+
+    // Render telemetry dialog overlay if open
+    if let Some(dialog) = &state.telemetry_dialog {
+        telemetry_dialog::render_telemetry_dialog(frame, area, dialog, &state.theme);
+    }
+
+    // Render theme editor overlay if open
+    if let Some(editor) = &state.theme_editor {
+        theme_editor::render_theme_editor(frame, area, editor, &state.theme);
+    }
+
+    // Render project setup wizard overlay if open
+    if let Some(wizard) = &state.bootstrap_wizard {
+        bootstrap_wizard::render_bootstrap_wizard(frame, area, wizard, &state.theme);
+    }
+
+    // Render paste preview overlay if open
+    if let Some(preview) = &state.paste_preview {
+        paste_preview::render_paste_preview(frame, area, preview, &state.theme);
+    }
+
+    // Collect links once overlays are drawn
+    links.scan(frame.buffer_mut());
+}
+
+/// Render the built-in editor opened from a file link
+fn render_code_editor(frame: &mut Frame, editor: &mut CodeEditorWidget) {
+    let area = frame.area();
+    let width = area.width.saturating_sub(4).max(20).min(area.width);
+    let height = area.height.saturating_sub(2).max(5).min(area.height);
+    let editor_area = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+    frame.render_widget(Clear, editor_area);
+    frame.render_widget(editor, editor_area);
 }
 
 // ===== Standalone Render Functions =====
@@ -532,7 +751,7 @@ fn render_home_footer(frame: &mut Frame, area: Rect, state: &TuiState) {
 }
 
 /// Render session view with chat
-fn render_session(frame: &mut Frame, area: Rect, state: &TuiState) {
+fn render_session(frame: &mut Frame, area: Rect, state: &TuiState, links: &mut HyperlinkMap) {
     // Layout: header + messages + prompt + footer
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -548,7 +767,7 @@ fn render_session(frame: &mut Frame, area: Rect, state: &TuiState) {
     render_session_header(frame, chunks[0], state);
 
     // Messages area
-    render_messages(frame, chunks[1], state);
+    render_messages(frame, chunks[1], state, links);
 
     // Prompt (reuse placeholder_idx=0 for session)
     render_prompt(frame, chunks[2], state, 0);
@@ -590,7 +809,7 @@ fn render_session_header(frame: &mut Frame, area: Rect, state: &TuiState) {
 }
 
 /// Render chat messages
-fn render_messages(frame: &mut Frame, area: Rect, state: &TuiState) {
+fn render_messages(frame: &mut Frame, area: Rect, state: &TuiState, links: &mut HyperlinkMap) {
     if state.messages.is_empty() {
         let empty = Paragraph::new("Start a conversation by typing a message below...")
             .style(Style::default().fg(state.theme.text_muted))
@@ -617,6 +836,7 @@ fn render_messages(frame: &mut Frame, area: Rect, state: &TuiState) {
         .wrap(Wrap { trim: true })
         .scroll((0, 0));
     frame.render_widget(messages, area);
+    links.watch(area);
 }
 
 /// Render session footer
@@ -635,7 +855,34 @@ fn render_session_footer(frame: &mut Frame, area: Rect, state: &TuiState) {
         Span::styled(&status_text, Style::default().fg(state.theme.text_muted)),
     ]);
 
-    frame.render_widget(Paragraph::new(footer), area);
+    // Status segments take whatever space the hints leave on the right
+    let hints_width = (footer.width() as u16 + 2).min(area.width);
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(hints_width), Constraint::Min(0)])
+        .split(area);
+
+    frame.render_widget(Paragraph::new(footer), chunks[0]);
+
+    let segments = state
+        .status_bar
+        .render_line(&status_context(state), &state.theme, chunks[1].width);
+    frame.render_widget(
+        Paragraph::new(segments).alignment(Alignment::Right),
+        chunks[1],
+    );
+}
+
+/// Status bar data for the current state
+fn status_context(state: &TuiState) -> StatusContext {
+    StatusContext {
+        mode: state.current_agent.clone(),
+        provider: state.current_provider.clone(),
+        model: state.current_model.clone(),
+        token_summary: Some(state.token_display.clone()),
+        vcs: state.vcs.clone(),
+        ..StatusContext::default()
+    }
 }
 
 /// Render command palette overlay
@@ -659,6 +906,10 @@ fn render_command_palette(frame: &mut Frame, area: Rect, state: &TuiState) {
         "/agent - Switch agent",
         "/status - View status",
         "/help - Show help",
+        "/feedback - Send feedback",
+        "/telemetry - Review telemetry",
+        "/theme-edit - Edit theme colors",
+        "/init - Set up this project",
         "/exit - Exit app",
     ];
 
@@ -680,6 +931,11 @@ fn render_command_palette(frame: &mut Frame, area: Rect, state: &TuiState) {
     frame.render_widget(palette, overlay_area);
 }
 
+/// Project bootstrap for the current working directory
+fn current_project() -> ProjectBootstrap {
+    ProjectBootstrap::new(std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")))
+}
+
 // ===== TuiApp Implementation (event handling) =====
 
 impl TuiApp {
@@ -687,12 +943,52 @@ impl TuiApp {
     async fn handle_key(&mut self, code: KeyCode, modifiers: event::KeyModifiers) {
         let ctrl = modifiers.contains(event::KeyModifiers::CONTROL);
 
+        // Feedback dialog captures all keys except Ctrl+C
+        if self.state.feedback_dialog.is_some() && !(ctrl && code == KeyCode::Char('c')) {
+            self.handle_feedback_key(code, modifiers).await;
+            return;
+        }
+
+        // Telemetry dialog captures all keys except Ctrl+C
+        if self.state.telemetry_dialog.is_some() && !(ctrl && code == KeyCode::Char('c')) {
+            self.handle_telemetry_key(code);
+            return;
+        }
+
+        // Theme editor captures all keys except Ctrl+C
+        if self.state.theme_editor.is_some() && !(ctrl && code == KeyCode::Char('c')) {
+            self.handle_theme_editor_key(code, modifiers);
+            return;
+        }
+
+        // Project setup wizard captures all keys except Ctrl+C
+        if self.state.bootstrap_wizard.is_some() && !(ctrl && code == KeyCode::Char('c')) {
+            self.handle_bootstrap_wizard_key(code);
+            return;
+        }
+
+        // Paste preview captures all keys except Ctrl+C
+        if self.state.paste_preview.is_some() && !(ctrl && code == KeyCode::Char('c')) {
+            self.handle_paste_preview_key(code);
+            return;
+        }
+
+        // Editor opened from a file link captures all keys except Ctrl+C
+        if self.code_editor.is_some() && !(ctrl && code == KeyCode::Char('c')) {
+            self.handle_code_editor_key(code, modifiers);
+            return;
+        }
+
         // Global shortcuts
         match code {
             KeyCode::Char('c') if ctrl => {
                 self.running = false;
                 return;
             }
+            KeyCode::Char('v') if ctrl => {
+                self.paste_from_clipboard();
+                return;
+            }
             KeyCode::Esc => {
                 if self.state.command_palette_visible {
                     self.state.command_palette_visible = false;
@@ -740,6 +1036,13 @@ impl TuiApp {
                 self.state.prompt_input.pop();
             }
             KeyCode::Enter => {
+                if self.open_feedback_from_prompt()
+                    || self.open_telemetry_from_prompt()
+                    || self.open_theme_editor_from_prompt()
+                    || self.open_bootstrap_wizard_from_prompt()
+                {
+                    return;
+                }
                 if !self.state.prompt_input.is_empty() {
                     let prompt_content = self.state.prompt_input.clone();
                     self.state.prompt_input.clear();
@@ -797,6 +1100,13 @@ impl TuiApp {
                 self.state.prompt_input.pop();
             }
             KeyCode::Enter => {
+                if self.open_feedback_from_prompt()
+                    || self.open_telemetry_from_prompt()
+                    || self.open_theme_editor_from_prompt()
+                    || self.open_bootstrap_wizard_from_prompt()
+                {
+                    return;
+                }
                 if !self.state.prompt_input.is_empty() {
                     let prompt_content = self.state.prompt_input.clone();
                     self.state.prompt_input.clear();
@@ -833,10 +1143,272 @@ impl TuiApp {
         }
     }
 
+    /// Open the feedback dialog if the prompt holds a `/feedback` command
+    fn open_feedback_from_prompt(&mut self) -> bool {
+        let Some(args) = feedback_dialog::parse_feedback_command(&self.state.prompt_input) else {
+            return false;
+        };
+        self.state.feedback_dialog = Some(FeedbackDialogState::new(args));
+        self.state.prompt_input.clear();
+        true
+    }
+
+    /// Open the telemetry dialog if the prompt holds a `/telemetry` command
+    fn open_telemetry_from_prompt(&mut self) -> bool {
+        if !telemetry_dialog::parse_telemetry_command(&self.state.prompt_input) {
+            return false;
+        }
+        self.state.telemetry_dialog = Some(TelemetryDialogState::new(
+            &self.analytics.telemetry_config(),
+            &self.analytics.telemetry_preview(),
+        ));
+        self.state.prompt_input.clear();
+        true
+    }
+
+    /// Handle keys while the telemetry dialog is open
+    fn handle_telemetry_key(&mut self, code: KeyCode) {
+        let Some(dialog) = self.state.telemetry_dialog.as_mut() else {
+            return;
+        };
+
+        match dialog.handle_key(code) {
+            TelemetryDialogAction::None => {}
+            TelemetryDialogAction::Close => self.state.telemetry_dialog = None,
+            TelemetryDialogAction::SetOptIn(opt_in) => self.analytics.set_telemetry_opt_in(opt_in),
+        }
+    }
+
+    /// Open the theme editor if the prompt holds a `/theme-edit` command
+    fn open_theme_editor_from_prompt(&mut self) -> bool {
+        let Some(args) = theme_editor::parse_theme_edit_command(&self.state.prompt_input) else {
+            return false;
+        };
+        self.state.theme_editor = Some(ThemeEditorState::new(args));
+        self.state.prompt_input.clear();
+        true
+    }
+
+    /// Handle keys while the theme editor is open
+    fn handle_theme_editor_key(&mut self, code: KeyCode, modifiers: event::KeyModifiers) {
+        let Some(editor) = self.state.theme_editor.as_mut() else {
+            return;
+        };
+
+        let result = match editor.handle_key(code, modifiers) {
+            ThemeEditorAction::None => return,
+            ThemeEditorAction::Preview { name, value } => {
+                match self.theme_manager.preview_color_override(&name, &value) {
+                    Ok(theme) => {
+                        self.state.theme = theme;
+                        return;
+                    }
+                    Err(e) => Err(e),
+                }
+            }
+            ThemeEditorAction::Apply { name, value } => {
+                self.theme_manager.set_color_override(&name, &value)
+            }
+            ThemeEditorAction::Reset { name } => {
+                self.theme_manager.remove_color_override(&name).map(|_| ())
+            }
+            ThemeEditorAction::CancelPreview => Ok(()),
+            ThemeEditorAction::Close => {
+                self.state.theme_editor = None;
+                Ok(())
+            }
+        };
+        if let Err(e) = result {
+            tracing::warn!("Theme edit failed: {}", e);
+        }
+
+        // Drop any preview and show the committed theme
+        if let Ok(theme) = self.theme_manager.current() {
+            self.state.theme = theme;
+        }
+    }
+
+    /// Open the project setup wizard if the prompt holds an `/init` command
+    fn open_bootstrap_wizard_from_prompt(&mut self) -> bool {
+        if !bootstrap_wizard::parse_init_command(&self.state.prompt_input) {
+            return false;
+        }
+        self.state.prompt_input.clear();
+        self.open_bootstrap_wizard();
+        true
+    }
+
+    /// Open the project setup wizard for the current directory
+    pub fn open_bootstrap_wizard(&mut self) {
+        match current_project().detect() {
+            Ok(detection) => {
+                self.state.bootstrap_wizard = Some(BootstrapWizardState::new(detection));
+            }
+            Err(e) => tracing::warn!("Project detection failed: {}", e),
+        }
+    }
+
+    /// Handle keys while the project setup wizard is open
+    fn handle_bootstrap_wizard_key(&mut self, code: KeyCode) {
+        let Some(wizard) = self.state.bootstrap_wizard.as_mut() else {
+            return;
+        };
+
+        match wizard.handle_key(code) {
+            BootstrapWizardAction::None => {}
+            BootstrapWizardAction::Close => self.state.bootstrap_wizard = None,
+            BootstrapWizardAction::Finish(choices) => {
+                let outcome = if choices.create_config {
+                    current_project()
+                        .write_project_config(&wizard.detection, &choices)
+                        .map(Some)
+                        .map_err(|e| e.to_string())
+                } else {
+                    Ok(None)
+                };
+                if let Some(provider) = &choices.provider {
+                    self.state.current_provider = Some(provider.clone());
+                }
+                if let Some(model) = &choices.model {
+                    self.state.current_model = Some(model.clone());
+                }
+                wizard.finish(outcome);
+            }
+        }
+    }
+
+    /// Handle text pasted into the terminal (bracketed paste)
+    fn handle_paste(&mut self, text: String) {
+        self.clipboard.record(ClipboardData::Text(text));
+        self.preview_latest_paste();
+    }
+
+    /// Paste from the system clipboard, which may hold an image
+    fn paste_from_clipboard(&mut self) {
+        match self.clipboard.paste() {
+            Ok(_) => self.preview_latest_paste(),
+            Err(e) => tracing::debug!("Nothing to paste: {}", e),
+        }
+    }
+
+    /// Insert the latest clipboard history entry, previewing it first unless
+    /// it is short single-line text
+    fn preview_latest_paste(&mut self) {
+        let Some(preview) = PastePreviewState::new(self.clipboard.history()) else {
+            return;
+        };
+        if preview.preview.needs_confirmation() {
+            self.state.paste_preview = Some(preview);
+        } else {
+            self.insert_paste(preview.insertion());
+        }
+    }
+
+    /// Handle keys while the paste preview is open
+    fn handle_paste_preview_key(&mut self, code: KeyCode) {
+        let Some(preview) = self.state.paste_preview.as_mut() else {
+            return;
+        };
+
+        match preview.handle_key(code) {
+            PastePreviewAction::None => {}
+            PastePreviewAction::Close => self.state.paste_preview = None,
+            PastePreviewAction::Insert(insertion) => {
+                self.state.paste_preview = None;
+                self.insert_paste(insertion);
+            }
+        }
+    }
+
+    /// Insert pasted content into the prompt
+    ///
+    /// Files become `@path` references; images are saved as PNG files and
+    /// referenced the same way.
+    fn insert_paste(&mut self, insertion: PasteInsertion) {
+        let paths = match insertion {
+            PasteInsertion::Text(text) => {
+                self.state.prompt_input.push_str(&text.replace("\r\n", "\n"));
+                return;
+            }
+            PasteInsertion::FileReferences(paths) => paths,
+            PasteInsertion::Image(image) => {
+                let stamp = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map(|d| d.as_millis())
+                    .unwrap_or(0);
+                let path = std::env::temp_dir().join(format!("ricecoder-paste-{}.png", stamp));
+                let saved = image
+                    .to_png()
+                    .map_err(|e| e.to_string())
+                    .and_then(|png| std::fs::write(&path, png).map_err(|e| e.to_string()));
+                if let Err(e) = saved {
+                    tracing::warn!("Failed to save pasted image: {}", e);
+                    return;
+                }
+                vec![path]
+            }
+        };
+
+        for path in paths {
+            if !self.state.prompt_input.is_empty() && !self.state.prompt_input.ends_with(' ') {
+                self.state.prompt_input.push(' ');
+            }
+            self.state.prompt_input.push_str(&format!("@{} ", path.display()));
+        }
+    }
+
+    /// Handle keys while the feedback dialog is open
+    async fn handle_feedback_key(&mut self, code: KeyCode, modifiers: event::KeyModifiers) {
+        let Some(dialog) = self.state.feedback_dialog.as_mut() else {
+            return;
+        };
+
+        match dialog.handle_key(code, modifiers) {
+            FeedbackDialogAction::None => {}
+            FeedbackDialogAction::Close => self.state.feedback_dialog = None,
+            FeedbackDialogAction::Submit(feedback) => self.submit_feedback(feedback).await,
+        }
+    }
+
+    /// Record feedback with anonymized context, queueing it when offline
+    async fn submit_feedback(&mut self, feedback: CapturedFeedback) {
+        let mut context = FeedbackContext::new()
+            .with_mode(&self.state.current_agent)
+            .with_app_version(&self.state.home.version);
+        if let Some(provider) = &self.state.current_provider {
+            context = context.with_provider(provider);
+        }
+        if let Some(model) = &self.state.current_model {
+            context = context.with_model(model);
+        }
+
+        self.analytics
+            .track_feature_usage(None, "feedback.submit", HashMap::new());
+        let result = self.feedback.submit(feedback.with_context(context)).await;
+        let Some(dialog) = self.state.feedback_dialog.as_mut() else {
+            return;
+        };
+        match result {
+            Ok(CaptureOutcome::Recorded) => {
+                dialog.status = Some("Thanks! Your feedback was recorded.".to_string());
+            }
+            Ok(CaptureOutcome::Queued { pending }) => {
+                dialog.status = Some(format!(
+                    "Saved offline. {} queued feedback item(s) will be sent later.",
+                    pending
+                ));
+            }
+            Err(e) => dialog.error = Some(e.to_string()),
+        }
+    }
+
     /// Send message with streaming visual effect
     async fn send_with_streaming(&mut self, content: String) {
         // Set session status to running
         self.state.session_status = SessionStatus::Running;
+        self.analytics
+            .track_feature_usage(None, "chat.send", HashMap::new());
+        let started = std::time::Instant::now();
         
         // Get streaming response from AppContext
         match self.app_context.send_message_streaming(content).await {
@@ -851,17 +1423,21 @@ impl TuiApp {
                     // Redraw terminal to show progress
                     let state = self.state.clone();
                     let placeholder_idx = self.placeholder_idx;
+                    let mut links = HyperlinkMap::new();
                     if let Err(e) = self.terminal.draw(|frame| {
-                        render_ui(frame, &state, placeholder_idx);
+                        render_ui(frame, &state, placeholder_idx, &mut links);
                     }) {
                         tracing::warn!("Failed to redraw during streaming: {}", e);
                     }
+                    self.hyperlinks = links;
+                    self.emit_hyperlinks();
                 }
                 
                 // Mark streaming complete
                 if let Some(last_msg) = self.state.messages.last_mut() {
                     last_msg.is_streaming = false;
                 }
+                self.analytics.track_latency("chat.response", started.elapsed());
             }
             Err(e) => {
                 tracing::warn!("Failed to send message: {}", e);
@@ -905,9 +1481,153 @@ impl TuiApp {
     }
 
     /// Handle mouse events
-    fn handle_mouse(&mut self, _mouse: event::MouseEvent) {
-        // Mouse handling will be implemented later
+    fn handle_mouse(&mut self, mouse: event::MouseEvent) {
+        if mouse.kind != event::MouseEventKind::Down(event::MouseButton::Left)
+            || self.code_editor.is_some()
+        {
+            return;
+        }
+        if let Some(target) = self.hyperlinks.target_at(mouse.column, mouse.row).cloned() {
+            self.open_link(target);
+        }
+    }
+
+    /// Rewrite on-screen links as OSC 8 hyperlinks when they changed
+    fn emit_hyperlinks(&mut self) {
+        if !self.osc8_enabled || self.hyperlinks == self.emitted_links {
+            return;
+        }
+        let base = std::env::current_dir().unwrap_or_default();
+        if let Err(e) = self.hyperlinks.write_osc8(self.terminal.backend_mut(), &base) {
+            tracing::warn!("Failed to write hyperlinks: {}", e);
+        }
+        self.emitted_links = self.hyperlinks.clone();
     }
+
+    /// Open a clicked link
+    ///
+    /// URLs go to the system opener. File locations open in the configured
+    /// external editor, or in the built-in editor when none is configured.
+    fn open_link(&mut self, target: LinkTarget) {
+        match target {
+            LinkTarget::Url(url) => {
+                if let Err(e) = open_url(&url) {
+                    tracing::warn!("Failed to open {}: {}", url, e);
+                }
+            }
+            LinkTarget::File(location) => {
+                let path = location.resolve(&std::env::current_dir().unwrap_or_default());
+                if let Some(editor) = self.link_editor.clone() {
+                    let result = self.suspended(|| {
+                        ExternalEditor::open_location(
+                            Some(&editor),
+                            &path,
+                            location.line,
+                            location.column,
+                        )
+                    });
+                    match result {
+                        Ok(Ok(())) => {}
+                        Ok(Err(e)) => tracing::warn!("Failed to open {}: {}", location, e),
+                        Err(e) => tracing::warn!("Failed to restore terminal: {}", e),
+                    }
+                } else {
+                    let mut editor = CodeEditorWidget::default();
+                    match editor.open_location(&path, location.line, location.column) {
+                        Ok(()) => self.code_editor = Some(editor),
+                        Err(e) => tracing::warn!("Failed to open {}: {}", location, e),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Run `f` with the terminal handed back to the shell
+    fn suspended<T>(&mut self, f: impl FnOnce() -> T) -> Result<T> {
+        disable_raw_mode()?;
+        execute!(
+            self.terminal.backend_mut(),
+            LeaveAlternateScreen,
+            DisableMouseCapture,
+            DisableBracketedPaste
+        )?;
+        let result = f();
+        enable_raw_mode()?;
+        execute!(
+            self.terminal.backend_mut(),
+            EnterAlternateScreen,
+            EnableMouseCapture,
+            EnableBracketedPaste
+        )?;
+        self.terminal.clear()?;
+        self.emitted_links = HyperlinkMap::new();
+        Ok(result)
+    }
+
+    /// Handle keys for the editor opened from a file link
+    fn handle_code_editor_key(&mut self, code: KeyCode, modifiers: event::KeyModifiers) {
+        let Some(editor) = self.code_editor.as_mut() else {
+            return;
+        };
+        if code == KeyCode::Esc && editor.editor_state().mode == edtui::EditorMode::Normal {
+            self.code_editor = None;
+            return;
+        }
+        if let Some(key) = editor_key_event(code, modifiers) {
+            editor.handle_key_event(key);
+        }
+    }
+}
+
+/// Open a URL with the platform opener
+fn open_url(url: &str) -> io::Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        std::process::Command::new("open")
+    } else if cfg!(windows) {
+        let mut command = std::process::Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        std::process::Command::new("xdg-open")
+    };
+    command
+        .arg(url)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .map(|_| ())
+}
+
+/// Convert a key from the app's crossterm version to the editor's
+fn editor_key_event(
+    code: KeyCode,
+    modifiers: event::KeyModifiers,
+) -> Option<ratatui::crossterm::event::KeyEvent> {
+    use ratatui::crossterm::event::{KeyCode as EditorKeyCode, KeyEvent, KeyModifiers};
+
+    let code = match code {
+        KeyCode::Char(c) => EditorKeyCode::Char(c),
+        KeyCode::Enter => EditorKeyCode::Enter,
+        KeyCode::Esc => EditorKeyCode::Esc,
+        KeyCode::Backspace => EditorKeyCode::Backspace,
+        KeyCode::Delete => EditorKeyCode::Delete,
+        KeyCode::Tab => EditorKeyCode::Tab,
+        KeyCode::BackTab => EditorKeyCode::BackTab,
+        KeyCode::Left => EditorKeyCode::Left,
+        KeyCode::Right => EditorKeyCode::Right,
+        KeyCode::Up => EditorKeyCode::Up,
+        KeyCode::Down => EditorKeyCode::Down,
+        KeyCode::Home => EditorKeyCode::Home,
+        KeyCode::End => EditorKeyCode::End,
+        KeyCode::PageUp => EditorKeyCode::PageUp,
+        KeyCode::PageDown => EditorKeyCode::PageDown,
+        _ => return None,
+    };
+    Some(KeyEvent::new(
+        code,
+        KeyModifiers::from_bits_truncate(modifiers.bits()),
+    ))
 }
 
 impl Drop for TuiApp {
@@ -916,7 +1636,8 @@ impl Drop for TuiApp {
         let _ = execute!(
             self.terminal.backend_mut(),
             LeaveAlternateScreen,
-            DisableMouseCapture
+            DisableMouseCapture,
+            DisableBracketedPaste
         );
         let _ = self.terminal.show_cursor();
     }
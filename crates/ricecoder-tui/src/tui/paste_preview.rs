@@ -0,0 +1,401 @@
+//! Paste preview popup
+//!
+//! Shows what a paste will insert before it reaches the prompt: the size,
+//! the first lines of text, images by dimension and pasted file paths that
+//! will become file references. Large pastes carry a warning, and earlier
+//! clipboard history entries can be picked instead of the latest one.
+//!
+//! Keys: `Enter` insert, `Esc` cancel, `←`/`→` newer/older history entry,
+//! `Tab` insert file paths as text instead of references.
+
+use std::path::PathBuf;
+
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use ricecoder_themes::Theme;
+
+use crate::clipboard::{ClipboardData, ClipboardEntry, ClipboardImage};
+use crate::tui::prompt::Clipboard;
+
+/// Pastes at least this many bytes are flagged as large
+pub const LARGE_PASTE_BYTES: usize = 100 * 1024;
+/// Pastes with at least this many lines are flagged as large
+pub const LARGE_PASTE_LINES: usize = 2_000;
+/// Pastes at least this many bytes are flagged as huge
+pub const HUGE_PASTE_BYTES: usize = 1024 * 1024;
+/// Lines of text shown in the preview
+const PREVIEW_LINES: usize = 10;
+
+/// Size warning for a paste
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasteWarning {
+    /// Noticeably large; likely to use many tokens
+    Large,
+    /// Very large; probably pasted by mistake
+    Huge,
+}
+
+impl PasteWarning {
+    /// Warning for content of the given size, if any
+    pub fn for_size(bytes: usize, lines: usize) -> Option<Self> {
+        if bytes >= HUGE_PASTE_BYTES {
+            Some(PasteWarning::Huge)
+        } else if bytes >= LARGE_PASTE_BYTES || lines >= LARGE_PASTE_LINES {
+            Some(PasteWarning::Large)
+        } else {
+            None
+        }
+    }
+
+    /// Message shown in the preview
+    pub fn message(self) -> &'static str {
+        match self {
+            PasteWarning::Large => "Large paste: this will use a lot of context",
+            PasteWarning::Huge => "Huge paste: this will likely exceed the model's context",
+        }
+    }
+}
+
+/// What a paste would insert
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PastePreview {
+    /// Pasted content
+    pub data: ClipboardData,
+    /// Size in bytes
+    pub bytes: usize,
+    /// Number of text lines (0 for images)
+    pub lines: usize,
+    /// Existing files named by the text, if it consists only of paths
+    pub file_paths: Option<Vec<PathBuf>>,
+    /// Size warning
+    pub warning: Option<PasteWarning>,
+}
+
+impl PastePreview {
+    /// Build a preview for pasted content
+    pub fn new(data: ClipboardData) -> Self {
+        let bytes = data.size();
+        let (lines, file_paths) = match &data {
+            ClipboardData::Text(text) => (text.lines().count(), Clipboard::detect_file_paths(text)),
+            ClipboardData::Image(_) => (0, None),
+        };
+        Self {
+            warning: PasteWarning::for_size(bytes, lines),
+            data,
+            bytes,
+            lines,
+            file_paths,
+        }
+    }
+
+    /// Whether the paste should be confirmed rather than inserted directly
+    ///
+    /// Short single-line text goes straight into the prompt.
+    pub fn needs_confirmation(&self) -> bool {
+        self.lines > 1
+            || self.warning.is_some()
+            || self.file_paths.is_some()
+            || matches!(self.data, ClipboardData::Image(_))
+    }
+
+    /// First lines of the text, for display
+    pub fn preview_lines(&self) -> Vec<&str> {
+        match &self.data {
+            ClipboardData::Text(text) => text.lines().take(PREVIEW_LINES).collect(),
+            ClipboardData::Image(_) => Vec::new(),
+        }
+    }
+}
+
+/// Content to insert into the prompt
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PasteInsertion {
+    /// Text inserted as typed
+    Text(String),
+    /// References to files on disk
+    FileReferences(Vec<PathBuf>),
+    /// Image attachment
+    Image(ClipboardImage),
+}
+
+/// Action requested by the paste preview
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PastePreviewAction {
+    /// Nothing to do
+    None,
+    /// Insert the content and close
+    Insert(PasteInsertion),
+    /// Close without inserting
+    Close,
+}
+
+/// Paste preview popup state
+#[derive(Debug, Clone)]
+pub struct PastePreviewState {
+    /// Clipboard history, most recent first
+    pub entries: Vec<ClipboardEntry>,
+    /// Index of the entry being previewed
+    pub index: usize,
+    /// Preview of the selected entry
+    pub preview: PastePreview,
+    /// Insert detected file paths as references (otherwise as text)
+    pub as_references: bool,
+}
+
+impl PastePreviewState {
+    /// Preview the most recent clipboard history entry
+    ///
+    /// Returns `None` when the history is empty.
+    pub fn new(entries: Vec<ClipboardEntry>) -> Option<Self> {
+        let preview = PastePreview::new(entries.first()?.data.clone());
+        Some(Self {
+            entries,
+            index: 0,
+            preview,
+            as_references: true,
+        })
+    }
+
+    /// Content that Enter would insert
+    pub fn insertion(&self) -> PasteInsertion {
+        match (&self.preview.data, &self.preview.file_paths) {
+            (ClipboardData::Image(image), _) => PasteInsertion::Image(image.clone()),
+            (ClipboardData::Text(_), Some(paths)) if self.as_references => {
+                PasteInsertion::FileReferences(paths.clone())
+            }
+            (ClipboardData::Text(text), _) => PasteInsertion::Text(text.clone()),
+        }
+    }
+
+    fn select(&mut self, index: usize) {
+        if let Some(entry) = self.entries.get(index) {
+            self.index = index;
+            self.preview = PastePreview::new(entry.data.clone());
+        }
+    }
+
+    /// Handle a key press
+    pub fn handle_key(&mut self, code: KeyCode) -> PastePreviewAction {
+        match code {
+            KeyCode::Enter => PastePreviewAction::Insert(self.insertion()),
+            KeyCode::Esc => PastePreviewAction::Close,
+            KeyCode::Left => {
+                self.select(self.index.saturating_sub(1));
+                PastePreviewAction::None
+            }
+            KeyCode::Right => {
+                self.select(self.index + 1);
+                PastePreviewAction::None
+            }
+            KeyCode::Tab => {
+                self.as_references = !self.as_references;
+                PastePreviewAction::None
+            }
+            _ => PastePreviewAction::None,
+        }
+    }
+}
+
+/// Human-readable byte size
+fn format_size(bytes: usize) -> String {
+    match bytes {
+        b if b >= 1024 * 1024 => format!("{:.1} MB", b as f64 / (1024.0 * 1024.0)),
+        b if b >= 1024 => format!("{:.1} KB", b as f64 / 1024.0),
+        b => format!("{} B", b),
+    }
+}
+
+/// Render the paste preview as a centered overlay
+pub fn render_paste_preview(
+    frame: &mut Frame,
+    area: Rect,
+    state: &PastePreviewState,
+    theme: &Theme,
+) {
+    let width = 76.min(area.width.saturating_sub(4));
+    let height = 20.min(area.height.saturating_sub(2));
+    let x = area.x + (area.width.saturating_sub(width)) / 2;
+    let y = area.y + (area.height.saturating_sub(height)) / 2;
+    let overlay_area = Rect {
+        x,
+        y,
+        width,
+        height,
+    };
+
+    frame.render_widget(Clear, overlay_area);
+
+    let text = Style::default().fg(theme.foreground);
+    let muted = Style::default().fg(theme.text_muted);
+    let heading = Style::default()
+        .fg(theme.border_active)
+        .add_modifier(Modifier::BOLD);
+    let preview = &state.preview;
+
+    let size = match &preview.data {
+        ClipboardData::Text(_) => format!(
+            "{} · {} line{}",
+            format_size(preview.bytes),
+            preview.lines,
+            if preview.lines == 1 { "" } else { "s" }
+        ),
+        ClipboardData::Image(image) => format!(
+            "Image {}×{} · {}",
+            image.width,
+            image.height,
+            format_size(preview.bytes)
+        ),
+    };
+    let mut content = vec![Line::from(vec![
+        Span::styled(size, heading),
+        Span::styled(
+            format!("   entry {}/{}", state.index + 1, state.entries.len()),
+            muted,
+        ),
+    ])];
+    if let Some(warning) = preview.warning {
+        let color = match warning {
+            PasteWarning::Large => theme.warning,
+            PasteWarning::Huge => theme.error,
+        };
+        content.push(Line::from(Span::styled(
+            warning.message(),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        )));
+    }
+    content.push(Line::from(""));
+
+    match (&preview.file_paths, state.as_references) {
+        (Some(paths), true) => {
+            content.push(Line::from(Span::styled(
+                format!("Attach {} file reference(s):", paths.len()),
+                text,
+            )));
+            for path in paths.iter().take(PREVIEW_LINES) {
+                content.push(Line::from(vec![
+                    Span::styled("  @", heading),
+                    Span::styled(path.display().to_string(), text),
+                ]));
+            }
+        }
+        _ => {
+            let inner_width = width.saturating_sub(4) as usize;
+            for line in preview.preview_lines() {
+                let shown: String = line.chars().take(inner_width).collect();
+                content.push(Line::from(Span::styled(shown, text)));
+            }
+            if preview.lines > PREVIEW_LINES {
+                content.push(Line::from(Span::styled(
+                    format!("… {} more lines", preview.lines - PREVIEW_LINES),
+                    muted,
+                )));
+            }
+        }
+    }
+
+    let mut hints = String::from("Enter insert · Esc cancel · ←/→ history");
+    if preview.file_paths.is_some() {
+        hints.push_str(if state.as_references {
+            " · Tab paste as text"
+        } else {
+            " · Tab attach files"
+        });
+    }
+    content.push(Line::from(""));
+    content.push(Line::from(Span::styled(hints, muted)));
+
+    let block = Block::default()
+        .title(" Paste Preview ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.border_active))
+        .style(Style::default().bg(theme.background_panel));
+    frame.render_widget(Paragraph::new(content).block(block), overlay_area);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::SystemTime;
+
+    use super::*;
+
+    fn entry(data: ClipboardData) -> ClipboardEntry {
+        ClipboardEntry {
+            data,
+            recorded_at: SystemTime::now(),
+        }
+    }
+
+    #[test]
+    fn test_size_warnings() {
+        assert_eq!(PasteWarning::for_size(10, 1), None);
+        assert_eq!(
+            PasteWarning::for_size(LARGE_PASTE_BYTES, 1),
+            Some(PasteWarning::Large)
+        );
+        assert_eq!(
+            PasteWarning::for_size(10, LARGE_PASTE_LINES),
+            Some(PasteWarning::Large)
+        );
+        assert_eq!(
+            PasteWarning::for_size(HUGE_PASTE_BYTES, 1),
+            Some(PasteWarning::Huge)
+        );
+
+        let short = PastePreview::new(ClipboardData::Text("hello".to_string()));
+        assert!(!short.needs_confirmation());
+        let multi = PastePreview::new(ClipboardData::Text("a\nb".to_string()));
+        assert!(multi.needs_confirmation());
+    }
+
+    #[test]
+    fn test_browse_history_and_insert() {
+        let mut state = PastePreviewState::new(vec![
+            entry(ClipboardData::Text("newest".to_string())),
+            entry(ClipboardData::Image(ClipboardImage::new(
+                1,
+                1,
+                vec![0u8; 4],
+            ))),
+        ])
+        .unwrap();
+
+        assert_eq!(state.handle_key(KeyCode::Right), PastePreviewAction::None);
+        assert_eq!(state.index, 1);
+        assert!(matches!(state.insertion(), PasteInsertion::Image(_)));
+        state.handle_key(KeyCode::Right);
+        assert_eq!(state.index, 1);
+
+        state.handle_key(KeyCode::Left);
+        assert_eq!(
+            state.handle_key(KeyCode::Enter),
+            PastePreviewAction::Insert(PasteInsertion::Text("newest".to_string()))
+        );
+        assert_eq!(state.handle_key(KeyCode::Esc), PastePreviewAction::Close);
+        assert!(PastePreviewState::new(Vec::new()).is_none());
+    }
+
+    #[test]
+    fn test_file_paths_toggle_between_references_and_text() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("notes.md");
+        std::fs::write(&file, "# notes").unwrap();
+        let pasted = file.display().to_string();
+
+        let mut state =
+            PastePreviewState::new(vec![entry(ClipboardData::Text(pasted.clone()))]).unwrap();
+        assert!(state.preview.needs_confirmation());
+        assert_eq!(
+            state.insertion(),
+            PasteInsertion::FileReferences(vec![file])
+        );
+
+        state.handle_key(KeyCode::Tab);
+        assert_eq!(state.insertion(), PasteInsertion::Text(pasted));
+    }
+}
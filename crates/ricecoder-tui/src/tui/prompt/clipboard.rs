@@ -4,11 +4,12 @@
 //! - Plain text (with summarization for large pastes)
 //! - Images (base64 encoded)
 //! - Files (including SVG as text)
+//! - Pasted or dropped file paths (as file references)
 //!
 //! # DDD Layer: Infrastructure
 //! Clipboard integration for the prompt system.
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
 
 /// Result of a clipboard read
@@ -50,6 +51,8 @@ pub enum PastedContent {
         content: String,
         filename: String,
     },
+    /// Paths of existing files, inserted as file references
+    FileReferences(Vec<PathBuf>),
 }
 
 /// Configuration for paste behavior
@@ -149,6 +152,11 @@ impl Clipboard {
             return content;
         }
         
+        // Check if it's a list of file paths (e.g. files dropped on the terminal)
+        if let Some(paths) = Self::detect_file_paths(trimmed) {
+            return PastedContent::FileReferences(paths);
+        }
+        
         // Check if we should summarize
         let line_count = trimmed.matches('\n').count() + 1;
         let should_summarize = !config.disable_paste_summary
@@ -168,10 +176,7 @@ impl Clipboard {
     /// Try to read content from a file path
     fn try_read_file(text: &str) -> Option<PastedContent> {
         // Strip quotes and escape sequences
-        let filepath = text
-            .trim_matches('\'')
-            .trim_matches('"')
-            .replace("\\ ", " ");
+        let filepath = Self::unquote(text);
         
         // Skip URLs
         if filepath.starts_with("http://") || filepath.starts_with("https://") {
@@ -214,8 +219,94 @@ impl Clipboard {
         None
     }
     
+    /// Detect pasted text that consists only of paths to existing files
+    ///
+    /// Accepts one path per line or several shell-quoted paths on a line, as
+    /// terminals produce when files are dropped on them. `file://` URIs are
+    /// accepted; other URLs are not. Returns `None` if any part of the text
+    /// is not an existing file.
+    pub fn detect_file_paths(text: &str) -> Option<Vec<PathBuf>> {
+        let mut paths = Vec::new();
+        for line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            if let Some(path) = Self::existing_file(&Self::unquote(line)) {
+                paths.push(path);
+                continue;
+            }
+            for word in Self::split_shell_words(line)? {
+                paths.push(Self::existing_file(&word)?);
+            }
+        }
+        
+        if paths.is_empty() {
+            return None;
+        }
+        paths.dedup();
+        Some(paths)
+    }
+    
+    /// Resolve a candidate path to an existing file
+    fn existing_file(candidate: &str) -> Option<PathBuf> {
+        if candidate.starts_with("http://") || candidate.starts_with("https://") {
+            return None;
+        }
+        let candidate = candidate.strip_prefix("file://").unwrap_or(candidate);
+        let path = PathBuf::from(candidate);
+        path.is_file().then_some(path)
+    }
+    
+    /// Strip surrounding quotes and backslash-escaped spaces
+    fn unquote(text: &str) -> String {
+        text.trim_matches('\'')
+            .trim_matches('"')
+            .replace("\\ ", " ")
+    }
+    
+    /// Split a line into words using shell quoting rules
+    ///
+    /// Returns `None` for unbalanced quotes.
+    fn split_shell_words(line: &str) -> Option<Vec<String>> {
+        let mut words = Vec::new();
+        let mut word = String::new();
+        let mut in_word = false;
+        let mut quote: Option<char> = None;
+        let mut chars = line.chars();
+        
+        while let Some(c) = chars.next() {
+            match (quote, c) {
+                (Some(q), c) if c == q => quote = None,
+                (Some(_), c) => word.push(c),
+                (None, '\'' | '"') => {
+                    quote = Some(c);
+                    in_word = true;
+                }
+                (None, '\\') => {
+                    word.push(chars.next().unwrap_or('\\'));
+                    in_word = true;
+                }
+                (None, c) if c.is_whitespace() => {
+                    if in_word {
+                        words.push(std::mem::take(&mut word));
+                        in_word = false;
+                    }
+                }
+                (None, c) => {
+                    word.push(c);
+                    in_word = true;
+                }
+            }
+        }
+        
+        if quote.is_some() {
+            return None;
+        }
+        if in_word {
+            words.push(word);
+        }
+        Some(words)
+    }
+    
     /// Guess MIME type from file extension
-    fn guess_mime_type(path: &Path) -> String {
+    pub fn guess_mime_type(path: &Path) -> String {
         let ext = path.extension()
             .and_then(|e| e.to_str())
             .map(|e| e.to_lowercase())
@@ -366,4 +457,30 @@ mod tests {
             _ => panic!("Expected PlainText"),
         }
     }
+    
+    #[test]
+    fn test_pasted_file_paths_become_references() {
+        let dir = tempfile::tempdir().unwrap();
+        let plain = dir.path().join("main.rs");
+        let spaced = dir.path().join("my notes.md");
+        std::fs::write(&plain, "fn main() {}").unwrap();
+        std::fs::write(&spaced, "# notes").unwrap();
+        let config = PasteConfig::default();
+        
+        // Dropped files arrive shell-quoted on one line
+        let dropped = format!("{} '{}'", plain.display(), spaced.display());
+        match Clipboard::process_text(&dropped, &config, 0) {
+            PastedContent::FileReferences(paths) => assert_eq!(paths, vec![plain.clone(), spaced.clone()]),
+            other => panic!("Expected FileReferences, got {:?}", other),
+        }
+        
+        // One path per line, with escaped spaces
+        let listed = format!("file://{}\n{}", plain.display(), spaced.display().to_string().replace(' ', "\\ "));
+        assert_eq!(Clipboard::detect_file_paths(&listed), Some(vec![plain.clone(), spaced]));
+        
+        // Any missing file means the paste is ordinary text
+        let mixed = format!("{}\n{}", plain.display(), dir.path().join("missing.rs").display());
+        assert_eq!(Clipboard::detect_file_paths(&mixed), None);
+        assert_eq!(Clipboard::detect_file_paths("see main.rs for details"), None);
+    }
 }
@@ -1,6 +1,7 @@
 //! External editor integration for prompt
 //!
-//! Opens the user's preferred editor for composing longer prompts.
+//! Opens the user's preferred editor for composing longer prompts, or at a
+//! `path:line:col` location clicked in tool output or diagnostics.
 //! Supports $EDITOR, $VISUAL, and falls back to common editors.
 //!
 //! # DDD Layer: Infrastructure
@@ -9,7 +10,7 @@
 use std::env;
 use std::fs;
 use std::io::{self, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use tempfile::NamedTempFile;
 
@@ -201,6 +202,74 @@ impl ExternalEditor {
         }
     }
     
+    /// Open `path` at a line and column
+    ///
+    /// Uses `editor` if given, otherwise the user's preferred editor. Blocks
+    /// until a terminal editor exits.
+    pub fn open_location(
+        editor: Option<&str>,
+        path: &Path,
+        line: u32,
+        column: Option<u32>,
+    ) -> Result<(), EditorError> {
+        let editor = editor
+            .map(str::to_string)
+            .or_else(Self::find_editor)
+            .ok_or(EditorError::NoEditor)?;
+        let parts: Vec<&str> = editor.split_whitespace().collect();
+        let Some((cmd, args)) = parts.split_first() else {
+            return Err(EditorError::NoEditor);
+        };
+        
+        let status = Command::new(cmd)
+            .args(args)
+            .args(Self::location_args(cmd, path, line, column))
+            .status()
+            .map_err(|e| EditorError::CommandFailed(e.to_string()))?;
+        
+        if status.success() {
+            Ok(())
+        } else {
+            Err(EditorError::CommandFailed(format!(
+                "Editor exited with status: {}",
+                status
+            )))
+        }
+    }
+    
+    /// Arguments that make `editor` open `path` at a line and column
+    pub fn location_args(editor: &str, path: &Path, line: u32, column: Option<u32>) -> Vec<String> {
+        let name = Path::new(editor)
+            .file_stem()
+            .map(|n| n.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        let path = path.display().to_string();
+        let column = column.unwrap_or(1);
+        
+        match name.as_str() {
+            "code" | "code-insiders" | "codium" | "cursor" => {
+                vec!["--goto".to_string(), format!("{}:{}:{}", path, line, column)]
+            }
+            "subl" | "zed" | "hx" | "helix" | "micro" => {
+                vec![format!("{}:{}:{}", path, line, column)]
+            }
+            "vim" | "nvim" | "vi" | "gvim" | "mvim" => {
+                vec![format!("+call cursor({}, {})", line, column), path]
+            }
+            "nano" => vec![format!("+{},{}", line, column), path],
+            "emacs" | "emacsclient" => vec![format!("+{}:{}", line, column), path],
+            "idea" | "clion" | "rustrover" | "pycharm" | "goland" | "webstorm" => vec![
+                "--line".to_string(),
+                line.to_string(),
+                "--column".to_string(),
+                column.to_string(),
+                path,
+            ],
+            "notepad" => vec![path],
+            _ => vec![format!("+{}", line), path],
+        }
+    }
+    
     /// Open editor for a new prompt (empty)
     pub fn open_new() -> EditorResult {
         Self::open(&EditorConfig::default())
@@ -263,6 +332,23 @@ mod tests {
         }
     }
     
+    #[test]
+    fn test_location_args() {
+        let path = Path::new("src/main.rs");
+        assert_eq!(
+            ExternalEditor::location_args("code", path, 12, Some(5)),
+            vec!["--goto", "src/main.rs:12:5"]
+        );
+        assert_eq!(
+            ExternalEditor::location_args("/usr/bin/nvim", path, 12, None),
+            vec!["+call cursor(12, 1)", "src/main.rs"]
+        );
+        assert_eq!(
+            ExternalEditor::location_args("ed", path, 3, Some(2)),
+            vec!["+3", "src/main.rs"]
+        );
+    }
+    
     #[test]
     fn test_create_temp_file() {
         let content = "test content";
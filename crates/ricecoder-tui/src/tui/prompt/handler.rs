@@ -11,6 +11,8 @@
 //! # DDD Layer: Application
 //! Orchestrates prompt behavior and event handling.
 
+use std::path::Path;
+
 use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 use super::clipboard::{Clipboard, PasteConfig, PastedContent};
@@ -57,6 +59,14 @@ pub enum DialogRequest {
     ProviderSelect,
     AgentSelect,
     StashList,
+    /// Feedback capture, optionally prefilled with text
+    Feedback { text: String },
+    /// Telemetry opt-in and export preview
+    Telemetry,
+    /// Theme color editor, optionally selecting a color
+    ThemeEditor { color: String },
+    /// Project setup wizard
+    BootstrapWizard,
 }
 
 /// Toast variants
@@ -323,6 +333,11 @@ impl<'a> PromptHandler<'a> {
             } => {
                 self.insert_text_part(content, virtual_text);
             }
+            PastedContent::FileReferences(paths) => {
+                for path in paths {
+                    self.insert_file_reference(&path);
+                }
+            }
         }
 
         self.pending_events.push(PromptEvent::ContentChanged);
@@ -407,6 +422,43 @@ impl<'a> PromptHandler<'a> {
         self.sync_state();
     }
 
+    /// Insert a reference to a file on disk
+    ///
+    /// The file is attached by `file://` URL rather than by content, so it is
+    /// read when the prompt is submitted.
+    pub fn insert_file_reference(&mut self, path: &Path) {
+        let path = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+        let image_count = self.state.prompt.file_parts().count();
+        let mut part = FilePart {
+            mime: Clipboard::guess_mime_type(&path),
+            filename: path.file_name().map(|name| name.to_string_lossy().to_string()),
+            url: format!("file://{}", path.display()),
+            source: None,
+        };
+        let virtual_text = part.display_text(image_count);
+
+        let start = self.input.cursor().offset;
+        let end = start + virtual_text.len();
+        self.input.insert_text(&format!("{} ", virtual_text));
+
+        let extmark_id = self.extmarks.create(
+            start,
+            end,
+            virtual_text.clone(),
+            ExtmarkStyle::File,
+            self.prompt_part_type_id,
+        );
+
+        part.source = Some(
+            FileSource::new(path.display().to_string())
+                .with_text(TextSource::new(start, end, virtual_text)),
+        );
+        self.state.prompt.parts.push(PromptPart::File(part));
+        self.state.register_extmark(extmark_id, self.state.prompt.parts.len() - 1);
+
+        self.sync_state();
+    }
+
     /// Insert an agent mention
     pub fn insert_agent(&mut self, agent_name: &str) {
         let offset = self.input.cursor().offset;
@@ -460,7 +512,23 @@ impl<'a> PromptHandler<'a> {
             let parts: Vec<&str> = input.splitn(2, ' ').collect();
             let command = parts[0].trim_start_matches('/').to_string();
             let args = parts.get(1).map(|s| s.to_string()).unwrap_or_default();
-            self.pending_events.push(PromptEvent::CommandSubmit { command, args });
+            match command.as_str() {
+                "feedback" => self
+                    .pending_events
+                    .push(PromptEvent::OpenDialog(DialogRequest::Feedback { text: args })),
+                "telemetry" => self
+                    .pending_events
+                    .push(PromptEvent::OpenDialog(DialogRequest::Telemetry)),
+                "theme-edit" => self
+                    .pending_events
+                    .push(PromptEvent::OpenDialog(DialogRequest::ThemeEditor { color: args })),
+                "init" => self
+                    .pending_events
+                    .push(PromptEvent::OpenDialog(DialogRequest::BootstrapWizard)),
+                _ => self
+                    .pending_events
+                    .push(PromptEvent::CommandSubmit { command, args }),
+            }
         }
         // Normal submission
         else {
@@ -713,6 +781,25 @@ mod tests {
         assert!(handler.state().prompt.input.contains("[Pasted"));
     }
 
+    #[test]
+    fn test_handle_paste_file_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("lib.rs");
+        std::fs::write(&file, "pub fn demo() {}").unwrap();
+
+        let mut handler = PromptHandler::new();
+        handler.handle_paste(&file.display().to_string());
+
+        assert_eq!(handler.state().prompt.input, "[File: lib.rs] ");
+        match &handler.state().prompt.parts[..] {
+            [PromptPart::File(part)] => {
+                assert_eq!(part.url, format!("file://{}", file.display()));
+                assert_eq!(part.source.as_ref().unwrap().path, file.display().to_string());
+            }
+            parts => panic!("Expected one file part, got {:?}", parts),
+        }
+    }
+
     #[test]
     fn test_insert_agent() {
         let mut handler = PromptHandler::new();
@@ -739,6 +826,19 @@ mod tests {
         assert!(!config.interrupt.is_empty());
     }
 
+    #[test]
+    fn test_feedback_command_opens_dialog() {
+        let mut handler = PromptHandler::new();
+        handler.handle_paste("/feedback startup is slow");
+        handler.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        let events = handler.drain_events();
+        assert!(events.iter().any(|e| matches!(
+            e,
+            PromptEvent::OpenDialog(DialogRequest::Feedback { text }) if text == "startup is slow"
+        )));
+    }
+
     #[test]
     fn test_events_drain() {
         let mut handler = PromptHandler::new();
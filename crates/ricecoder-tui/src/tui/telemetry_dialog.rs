@@ -0,0 +1,193 @@
+//! Telemetry dialog for the `/telemetry` command
+//!
+//! Shows the opt-in state, the export endpoint and the exact JSON payload
+//! the next telemetry export would send. Only aggregated, noised counters
+//! appear in the payload; raw events never leave the machine.
+//!
+//! Keys: `o`/`Space` toggle the opt-in, `↑`/`↓` scroll the payload and
+//! `Esc`/`q` close the dialog.
+
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use ricecoder_beta::improvement::{TelemetryConfig, TelemetryReport};
+use ricecoder_themes::Theme;
+
+/// Whether `input` is a `/telemetry` command
+pub fn parse_telemetry_command(input: &str) -> bool {
+    input.trim() == "/telemetry"
+}
+
+/// Result of handling a key in the dialog
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TelemetryDialogAction {
+    /// Nothing to do for the caller
+    None,
+    /// The user changed the opt-in
+    SetOptIn(bool),
+    /// Dialog should be closed
+    Close,
+}
+
+/// Telemetry dialog state
+#[derive(Debug, Clone)]
+pub struct TelemetryDialogState {
+    /// Whether the user has opted in to export
+    pub opt_in: bool,
+    /// Configured export endpoint
+    pub endpoint: Option<String>,
+    /// Payload the next export would send, pretty-printed
+    pub payload: Vec<String>,
+    /// First visible payload line
+    pub scroll: usize,
+}
+
+impl TelemetryDialogState {
+    /// Create the dialog from the current settings and export preview
+    pub fn new(config: &TelemetryConfig, preview: &TelemetryReport) -> Self {
+        let payload = serde_json::to_string_pretty(preview)
+            .unwrap_or_else(|e| format!("Failed to render payload: {}", e))
+            .lines()
+            .map(str::to_string)
+            .collect();
+        Self {
+            opt_in: config.opt_in,
+            endpoint: config.endpoint.clone(),
+            payload,
+            scroll: 0,
+        }
+    }
+
+    /// Handle a key press
+    pub fn handle_key(&mut self, code: KeyCode) -> TelemetryDialogAction {
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') => TelemetryDialogAction::Close,
+            KeyCode::Char('o') | KeyCode::Char(' ') => {
+                self.opt_in = !self.opt_in;
+                TelemetryDialogAction::SetOptIn(self.opt_in)
+            }
+            KeyCode::Up => {
+                self.scroll = self.scroll.saturating_sub(1);
+                TelemetryDialogAction::None
+            }
+            KeyCode::Down => {
+                self.scroll = (self.scroll + 1).min(self.payload.len().saturating_sub(1));
+                TelemetryDialogAction::None
+            }
+            _ => TelemetryDialogAction::None,
+        }
+    }
+}
+
+/// Render the telemetry dialog as a centered overlay
+pub fn render_telemetry_dialog(
+    frame: &mut Frame,
+    area: Rect,
+    state: &TelemetryDialogState,
+    theme: &Theme,
+) {
+    let width = 76.min(area.width.saturating_sub(4));
+    let height = 24.min(area.height.saturating_sub(2));
+    let x = area.x + (area.width.saturating_sub(width)) / 2;
+    let y = area.y + (area.height.saturating_sub(height)) / 2;
+    let overlay_area = Rect {
+        x,
+        y,
+        width,
+        height,
+    };
+
+    frame.render_widget(Clear, overlay_area);
+
+    let (status, status_style) = match (state.opt_in, &state.endpoint) {
+        (true, Some(endpoint)) => (
+            format!("Opted in · sent to {}", endpoint),
+            Style::default().fg(theme.success),
+        ),
+        (true, None) => (
+            "Opted in · no endpoint configured, nothing is sent".to_string(),
+            Style::default().fg(theme.warning),
+        ),
+        (false, _) => (
+            "Not opted in · nothing is sent".to_string(),
+            Style::default().fg(theme.text_muted),
+        ),
+    };
+
+    let mut content = vec![
+        Line::from(Span::styled(
+            status,
+            status_style.add_modifier(Modifier::BOLD),
+        )),
+        Line::from(Span::styled(
+            "Raw events stay on this machine. The next export would send exactly:",
+            Style::default().fg(theme.text_muted),
+        )),
+        Line::from(""),
+    ];
+
+    // Header (3 lines), hint (2 lines) and borders (2 lines)
+    let visible = (height as usize).saturating_sub(7);
+    content.extend(
+        state
+            .payload
+            .iter()
+            .skip(state.scroll)
+            .take(visible)
+            .map(|line| {
+                Line::from(Span::styled(
+                    line.clone(),
+                    Style::default().fg(theme.foreground),
+                ))
+            }),
+    );
+
+    content.push(Line::from(""));
+    content.push(Line::from(Span::styled(
+        "o toggle opt-in · ↑/↓ scroll · esc close",
+        Style::default().fg(theme.text_muted),
+    )));
+
+    let dialog = Paragraph::new(content).block(
+        Block::default()
+            .title(" Telemetry ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border_active))
+            .style(Style::default().bg(theme.background_element)),
+    );
+
+    frame.render_widget(dialog, overlay_area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ricecoder_beta::improvement::TelemetryAggregator;
+
+    #[test]
+    fn test_parse_telemetry_command() {
+        assert!(parse_telemetry_command(" /telemetry "));
+        assert!(!parse_telemetry_command("/telemetry-off"));
+        assert!(!parse_telemetry_command("telemetry"));
+    }
+
+    #[test]
+    fn test_shows_payload_and_toggles_opt_in() {
+        let config = TelemetryConfig::default();
+        let preview = TelemetryAggregator::new().report(&config);
+        let mut state = TelemetryDialogState::new(&config, &preview);
+        assert!(state.payload.iter().any(|l| l.contains("feature_usage")));
+        assert!(!state.opt_in);
+
+        assert_eq!(
+            state.handle_key(KeyCode::Char('o')),
+            TelemetryDialogAction::SetOptIn(true)
+        );
+        assert_eq!(state.handle_key(KeyCode::Esc), TelemetryDialogAction::Close);
+    }
+}
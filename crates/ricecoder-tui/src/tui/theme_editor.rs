@@ -0,0 +1,297 @@
+//! Live theme color editor for the `/theme-edit` command
+//!
+//! Lists every overridable theme color with a swatch. Typing a value
+//! previews it immediately on the whole UI; `Enter` keeps it as an override,
+//! `Delete` removes the override of the selected color and `Esc` closes the
+//! editor, dropping any unapplied preview.
+//!
+//! Keys: `↑`/`↓` select a color, type a value (`#rrggbb` or a color name),
+//! `Enter` applies, `Delete` resets, `Esc` closes.
+
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use ricecoder_themes::{overrides::parse_color_value, theme_color, Theme, OVERRIDABLE_COLORS};
+
+/// Extract the arguments of a `/theme-edit` command, if `input` is one
+pub fn parse_theme_edit_command(input: &str) -> Option<&str> {
+    let rest = input.trim().strip_prefix("/theme-edit")?;
+    if rest.is_empty() || rest.starts_with(char::is_whitespace) {
+        Some(rest.trim())
+    } else {
+        None
+    }
+}
+
+/// Result of handling a key in the editor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ThemeEditorAction {
+    /// Nothing to do for the caller
+    None,
+    /// Show the UI with this color value, without keeping it
+    Preview { name: String, value: String },
+    /// Drop the preview and show the committed theme again
+    CancelPreview,
+    /// Keep this color value as an override
+    Apply { name: String, value: String },
+    /// Remove the override for this color
+    Reset { name: String },
+    /// Editor should be closed
+    Close,
+}
+
+/// Theme editor state
+#[derive(Debug, Clone, Default)]
+pub struct ThemeEditorState {
+    /// Index into [`OVERRIDABLE_COLORS`]
+    pub selected: usize,
+    /// Value being typed for the selected color
+    pub input: String,
+    /// Validation error for the current input
+    pub error: Option<String>,
+    /// Confirmation of the last applied change
+    pub status: Option<String>,
+}
+
+impl ThemeEditorState {
+    /// Create an editor, optionally selecting a color by name
+    pub fn new(args: &str) -> Self {
+        let wanted = args.trim().to_lowercase();
+        let selected = OVERRIDABLE_COLORS
+            .iter()
+            .position(|name| *name == wanted)
+            .unwrap_or(0);
+        Self {
+            selected,
+            ..Self::default()
+        }
+    }
+
+    /// Name of the selected color
+    pub fn selected_name(&self) -> &'static str {
+        OVERRIDABLE_COLORS[self.selected % OVERRIDABLE_COLORS.len()]
+    }
+
+    /// Handle a key press
+    pub fn handle_key(&mut self, code: KeyCode, modifiers: KeyModifiers) -> ThemeEditorAction {
+        let count = OVERRIDABLE_COLORS.len();
+        match code {
+            KeyCode::Esc => ThemeEditorAction::Close,
+            KeyCode::Up | KeyCode::Down => {
+                self.selected = if code == KeyCode::Up {
+                    (self.selected + count - 1) % count
+                } else {
+                    (self.selected + 1) % count
+                };
+                let had_input = !self.input.is_empty();
+                self.input.clear();
+                self.error = None;
+                if had_input {
+                    ThemeEditorAction::CancelPreview
+                } else {
+                    ThemeEditorAction::None
+                }
+            }
+            KeyCode::Enter => match parse_color_value(&self.input) {
+                Ok(_) => {
+                    self.status = Some(format!("{} = {}", self.selected_name(), self.input));
+                    self.error = None;
+                    ThemeEditorAction::Apply {
+                        name: self.selected_name().to_string(),
+                        value: std::mem::take(&mut self.input),
+                    }
+                }
+                Err(e) => {
+                    self.error = Some(e.to_string());
+                    ThemeEditorAction::None
+                }
+            },
+            KeyCode::Delete => {
+                self.input.clear();
+                self.status = Some(format!("{} reset", self.selected_name()));
+                ThemeEditorAction::Reset {
+                    name: self.selected_name().to_string(),
+                }
+            }
+            KeyCode::Char('u') if modifiers.contains(KeyModifiers::CONTROL) => {
+                self.input.clear();
+                self.error = None;
+                ThemeEditorAction::CancelPreview
+            }
+            KeyCode::Char(c) => {
+                self.input.push(c);
+                self.preview()
+            }
+            KeyCode::Backspace => {
+                self.input.pop();
+                self.preview()
+            }
+            _ => ThemeEditorAction::None,
+        }
+    }
+
+    /// Preview the input once it parses as a color
+    fn preview(&mut self) -> ThemeEditorAction {
+        self.status = None;
+        if self.input.is_empty() {
+            self.error = None;
+            return ThemeEditorAction::CancelPreview;
+        }
+        match parse_color_value(&self.input) {
+            Ok(_) => {
+                self.error = None;
+                ThemeEditorAction::Preview {
+                    name: self.selected_name().to_string(),
+                    value: self.input.clone(),
+                }
+            }
+            Err(_) => {
+                self.error = Some("not a color yet".to_string());
+                ThemeEditorAction::None
+            }
+        }
+    }
+}
+
+/// Render the theme editor as a centered overlay
+pub fn render_theme_editor(frame: &mut Frame, area: Rect, state: &ThemeEditorState, theme: &Theme) {
+    let width = 60.min(area.width.saturating_sub(4));
+    let height = 22.min(area.height.saturating_sub(2));
+    let x = area.x + (area.width.saturating_sub(width)) / 2;
+    let y = area.y + (area.height.saturating_sub(height)) / 2;
+    let overlay_area = Rect {
+        x,
+        y,
+        width,
+        height,
+    };
+
+    frame.render_widget(Clear, overlay_area);
+
+    // Borders (2), input (2) and hint (2)
+    let visible = (height as usize).saturating_sub(6).max(1);
+    let first = state
+        .selected
+        .saturating_sub(visible / 2)
+        .min(OVERRIDABLE_COLORS.len().saturating_sub(visible));
+
+    let mut content: Vec<Line> = OVERRIDABLE_COLORS
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(visible)
+        .map(|(idx, name)| {
+            let color = theme_color(theme, name).unwrap_or(theme.foreground);
+            let name_style = if idx == state.selected {
+                Style::default()
+                    .fg(theme.border_active)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.foreground)
+            };
+            Line::from(vec![
+                Span::styled("██ ", Style::default().fg(color)),
+                Span::styled(format!("{:<26}", name), name_style),
+                Span::styled(color.to_string(), Style::default().fg(theme.text_muted)),
+            ])
+        })
+        .collect();
+
+    content.push(Line::from(""));
+    let feedback = match (&state.error, &state.status) {
+        (Some(error), _) => Span::styled(format!("  {}", error), Style::default().fg(theme.error)),
+        (None, Some(status)) => {
+            Span::styled(format!("  {}", status), Style::default().fg(theme.success))
+        }
+        (None, None) => Span::raw(""),
+    };
+    content.push(Line::from(vec![
+        Span::styled(
+            format!("{}: ", state.selected_name()),
+            Style::default().fg(theme.text_muted),
+        ),
+        Span::styled(
+            format!("{}█", state.input),
+            Style::default().fg(theme.foreground),
+        ),
+        feedback,
+    ]));
+    content.push(Line::from(""));
+    content.push(Line::from(Span::styled(
+        "↑/↓ select · type to preview · enter apply · del reset · esc close",
+        Style::default().fg(theme.text_muted),
+    )));
+
+    let editor = Paragraph::new(content).block(
+        Block::default()
+            .title(" Theme Colors ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border_active))
+            .style(Style::default().bg(theme.background_element)),
+    );
+
+    frame.render_widget(editor, overlay_area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_str(state: &mut ThemeEditorState, text: &str) -> ThemeEditorAction {
+        let mut action = ThemeEditorAction::None;
+        for c in text.chars() {
+            action = state.handle_key(KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        action
+    }
+
+    #[test]
+    fn test_parse_theme_edit_command() {
+        assert_eq!(parse_theme_edit_command("/theme-edit"), Some(""));
+        assert_eq!(
+            parse_theme_edit_command("/theme-edit diff.added"),
+            Some("diff.added")
+        );
+        assert_eq!(parse_theme_edit_command("/theme-editor"), None);
+    }
+
+    #[test]
+    fn test_preview_then_apply() {
+        let mut state = ThemeEditorState::new("diff.added");
+        assert_eq!(state.selected_name(), "diff.added");
+
+        assert_eq!(type_str(&mut state, "#12"), ThemeEditorAction::None);
+        assert!(state.error.is_some());
+        assert_eq!(
+            type_str(&mut state, "3456"),
+            ThemeEditorAction::Preview {
+                name: "diff.added".to_string(),
+                value: "#123456".to_string()
+            }
+        );
+        assert_eq!(
+            state.handle_key(KeyCode::Enter, KeyModifiers::NONE),
+            ThemeEditorAction::Apply {
+                name: "diff.added".to_string(),
+                value: "#123456".to_string()
+            }
+        );
+        assert!(state.input.is_empty());
+    }
+
+    #[test]
+    fn test_moving_selection_cancels_preview() {
+        let mut state = ThemeEditorState::new("");
+        type_str(&mut state, "red");
+        assert_eq!(
+            state.handle_key(KeyCode::Down, KeyModifiers::NONE),
+            ThemeEditorAction::CancelPreview
+        );
+        assert_eq!(state.selected_name(), OVERRIDABLE_COLORS[1]);
+    }
+}
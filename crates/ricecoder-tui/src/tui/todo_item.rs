@@ -1,7 +1,8 @@
 //! Todo item widget for displaying task status
 //!
 //! This module provides a widget for rendering todo items with status indicators
-//! (completed, in progress, pending) and styled text.
+//! (completed, in progress, pending, blocked, cancelled) and styled text, and a
+//! [`TodoTree`] widget that renders subtasks indented under their parent.
 //!
 //! # Examples
 //!
@@ -30,6 +31,10 @@ pub enum TodoStatus {
     InProgress,
     /// Task is pending
     Pending,
+    /// Task is waiting on another task
+    Blocked,
+    /// Task is no longer needed
+    Cancelled,
 }
 
 impl TodoStatus {
@@ -39,6 +44,8 @@ impl TodoStatus {
             TodoStatus::Completed => "✓",
             TodoStatus::InProgress => "•",
             TodoStatus::Pending => " ",
+            TodoStatus::Blocked => "!",
+            TodoStatus::Cancelled => "-",
         }
     }
 
@@ -48,6 +55,8 @@ impl TodoStatus {
             TodoStatus::Completed => Color::Green,
             TodoStatus::InProgress => Color::Yellow,
             TodoStatus::Pending => Color::DarkGray,
+            TodoStatus::Blocked => Color::Red,
+            TodoStatus::Cancelled => Color::DarkGray,
         }
     }
 }
@@ -58,6 +67,7 @@ pub struct TodoItem {
     status: TodoStatus,
     content: String,
     muted_color: Color,
+    depth: usize,
 }
 
 impl TodoItem {
@@ -67,9 +77,16 @@ impl TodoItem {
             status,
             content: content.into(),
             muted_color: Color::DarkGray,
+            depth: 0,
         }
     }
 
+    /// Set the nesting depth; subtasks are indented two columns per level
+    pub fn depth(mut self, depth: usize) -> Self {
+        self.depth = depth;
+        self
+    }
+
     /// Set the muted color for pending items
     pub fn muted_color(mut self, color: Color) -> Self {
         self.muted_color = color;
@@ -79,14 +96,14 @@ impl TodoItem {
     /// Get the status indicator as a styled span
     fn status_span(&self) -> Span {
         Span::styled(
-            format!("[{}] ", self.status.char()),
+            format!("{}[{}] ", "  ".repeat(self.depth), self.status.char()),
             Style::default().fg(self.status.color()),
         )
     }
 
     /// Get the content as a styled span
     fn content_span(&self) -> Span {
-        let color = if self.status == TodoStatus::Pending {
+        let color = if matches!(self.status, TodoStatus::Pending | TodoStatus::Cancelled) {
             self.muted_color
         } else {
             self.status.color()
@@ -106,6 +123,36 @@ impl Widget for TodoItem {
     }
 }
 
+/// Todo list widget that renders one item per row, indented by depth
+#[derive(Debug, Clone, Default)]
+pub struct TodoTree {
+    items: Vec<TodoItem>,
+}
+
+impl TodoTree {
+    /// Create a tree from items in display order
+    ///
+    /// Each item's [`depth`](TodoItem::depth) sets its indentation, so
+    /// subtasks should directly follow their parent.
+    pub fn new(items: Vec<TodoItem>) -> Self {
+        Self { items }
+    }
+
+    /// Number of rows the tree needs
+    pub fn height(&self) -> u16 {
+        u16::try_from(self.items.len()).unwrap_or(u16::MAX)
+    }
+}
+
+impl Widget for TodoTree {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        for (row, item) in (area.y..area.bottom()).zip(self.items) {
+            let line = Line::from(vec![item.status_span(), item.content_span()]);
+            buf.set_line(area.x, row, &line, area.width);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -136,4 +183,22 @@ mod tests {
         let item = TodoItem::new(TodoStatus::Pending, "Test").muted_color(Color::Gray);
         assert_eq!(item.muted_color, Color::Gray);
     }
+
+    #[test]
+    fn test_todo_tree_indents_subtasks() {
+        let tree = TodoTree::new(vec![
+            TodoItem::new(TodoStatus::InProgress, "Parent"),
+            TodoItem::new(TodoStatus::Blocked, "Child").depth(1),
+        ]);
+        assert_eq!(tree.height(), 2);
+
+        let area = Rect::new(0, 0, 20, 2);
+        let mut buf = Buffer::empty(area);
+        tree.render(area, &mut buf);
+
+        let row =
+            |y: u16| -> String { (0..20).map(|x| buf[(x, y)].symbol().to_string()).collect() };
+        assert!(row(0).starts_with("[•] Parent"));
+        assert!(row(1).starts_with("  [!] Child"));
+    }
 }
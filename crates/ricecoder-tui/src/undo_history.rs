@@ -0,0 +1,335 @@
+//! Undo/redo history shared by the text input widgets
+//!
+//! [`UndoHistory`] stores snapshots of a widget's editable state taken
+//! before each edit. Consecutive edits of the same kind are coalesced into a
+//! single undo step at word granularity: typing `hello world` produces two
+//! steps (`hello ` and `world`), and a run of deletions is one step. Cursor
+//! movement or an edit of a different kind closes the current step.
+//!
+//! Widgets call [`UndoHistory::record`] with the state *before* an edit,
+//! [`UndoHistory::break_group`] when the cursor moves, and map the
+//! `input.undo`/`input.redo` keybind actions through [`HistoryAction`].
+
+use std::collections::VecDeque;
+
+use crate::performance::HistoryLimits;
+
+/// Kind of edit, used to decide whether edits coalesce
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditKind {
+    /// Inserted a word character (alphanumeric or `_`)
+    InsertWord,
+    /// Inserted whitespace, punctuation or a newline
+    InsertSeparator,
+    /// Deleted text
+    Delete,
+    /// Replaced the content wholesale (clear, paste, history recall)
+    Replace,
+}
+
+impl EditKind {
+    /// Edit kind for inserting `ch`
+    pub fn for_char(ch: char) -> Self {
+        if ch.is_alphanumeric() || ch == '_' {
+            EditKind::InsertWord
+        } else {
+            EditKind::InsertSeparator
+        }
+    }
+
+    /// Whether an edit of kind `next` continues a step that began with `self`
+    fn continues_with(self, next: EditKind) -> bool {
+        matches!(
+            (self, next),
+            (EditKind::InsertWord, EditKind::InsertWord)
+                | (EditKind::InsertWord, EditKind::InsertSeparator)
+                | (EditKind::InsertSeparator, EditKind::InsertSeparator)
+                | (EditKind::Delete, EditKind::Delete)
+        )
+    }
+}
+
+/// History keybind action
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryAction {
+    /// Revert the last edit step
+    Undo,
+    /// Re-apply the last undone step
+    Redo,
+}
+
+impl HistoryAction {
+    /// Keybind action id for undo
+    pub const UNDO_ACTION_ID: &'static str = "input.undo";
+    /// Keybind action id for redo
+    pub const REDO_ACTION_ID: &'static str = "input.redo";
+
+    /// Map a keybind action id to a history action
+    pub fn from_action_id(action_id: &str) -> Option<Self> {
+        match action_id {
+            Self::UNDO_ACTION_ID => Some(HistoryAction::Undo),
+            Self::REDO_ACTION_ID => Some(HistoryAction::Redo),
+            _ => None,
+        }
+    }
+}
+
+/// Snapshot of a text buffer and its cursor
+///
+/// Snapshots compare by text only, so restoring one never counts as a
+/// no-op just because the cursor moved.
+#[derive(Debug, Clone, Default)]
+pub struct TextSnapshot {
+    /// Buffer lines
+    pub lines: Vec<String>,
+    /// Cursor as (row, column)
+    pub cursor: (usize, usize),
+}
+
+impl TextSnapshot {
+    /// Create a snapshot
+    pub fn new(lines: Vec<String>, cursor: (usize, usize)) -> Self {
+        Self { lines, cursor }
+    }
+
+    /// Snapshot of single-line text with the cursor at `column`
+    pub fn single_line(text: &str, column: usize) -> Self {
+        Self::new(vec![text.to_string()], (0, column))
+    }
+
+    /// Buffer text with lines joined by `\n`
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+}
+
+impl PartialEq for TextSnapshot {
+    fn eq(&self, other: &Self) -> bool {
+        self.lines == other.lines
+    }
+}
+
+/// Undo/redo history over snapshots of type `S`
+#[derive(Debug, Clone)]
+pub struct UndoHistory<S> {
+    undo: VecDeque<S>,
+    redo: Vec<S>,
+    /// Kind of the edit that opened the current step, if one is open
+    open_step: Option<EditKind>,
+    max_depth: usize,
+    coalesce: bool,
+}
+
+impl<S> Default for UndoHistory<S> {
+    fn default() -> Self {
+        Self::from_limits(&HistoryLimits::default())
+    }
+}
+
+impl<S> UndoHistory<S> {
+    /// Create a history keeping at most `max_depth` undo steps
+    pub fn new(max_depth: usize) -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            open_step: None,
+            max_depth,
+            coalesce: true,
+        }
+    }
+
+    /// Create a history sized by the configured undo limit
+    pub fn from_limits(limits: &HistoryLimits) -> Self {
+        Self::new(limits.max_undo_steps)
+    }
+
+    /// Enable or disable word-level coalescing
+    pub fn with_coalescing(mut self, coalesce: bool) -> Self {
+        self.coalesce = coalesce;
+        self
+    }
+
+    /// Maximum number of undo steps kept
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// Change the maximum depth, dropping the oldest steps if needed
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth;
+        while self.undo.len() > max_depth {
+            self.undo.pop_front();
+        }
+    }
+
+    /// Close the current step so the next edit starts a new one
+    pub fn break_group(&mut self) {
+        self.open_step = None;
+    }
+
+    /// Whether there is a step to undo
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Whether there is a step to redo
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Number of undo steps
+    pub fn len(&self) -> usize {
+        self.undo.len()
+    }
+
+    /// Whether the history is empty
+    pub fn is_empty(&self) -> bool {
+        self.undo.is_empty() && self.redo.is_empty()
+    }
+
+    /// Forget all steps
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.open_step = None;
+    }
+}
+
+impl<S: Clone + PartialEq> UndoHistory<S> {
+    /// Record the state before an edit of `kind`
+    ///
+    /// Starts a new undo step unless the edit continues the open one.
+    /// Any redo steps are discarded.
+    pub fn record(&mut self, before: &S, kind: EditKind) {
+        self.redo.clear();
+        let continues =
+            self.coalesce && self.open_step.is_some_and(|open| open.continues_with(kind));
+        // A separator after a word keeps the step open but stops it from
+        // absorbing the next word
+        self.open_step = match (self.open_step, kind) {
+            (Some(EditKind::InsertWord), EditKind::InsertSeparator) if continues => {
+                Some(EditKind::InsertSeparator)
+            }
+            _ if continues => self.open_step,
+            _ => Some(kind),
+        };
+        if continues || self.max_depth == 0 {
+            return;
+        }
+        if self.undo.back() == Some(before) {
+            return;
+        }
+        if self.undo.len() == self.max_depth {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(before.clone());
+    }
+
+    /// Revert to the state before the last step
+    ///
+    /// `current` is kept for redo. Returns `None` when there is nothing to
+    /// undo.
+    pub fn undo(&mut self, current: &S) -> Option<S> {
+        self.open_step = None;
+        while let Some(previous) = self.undo.pop_back() {
+            // Skip steps whose edit turned out to be a no-op
+            if previous != *current {
+                self.redo.push(current.clone());
+                return Some(previous);
+            }
+        }
+        None
+    }
+
+    /// Re-apply the last undone step
+    pub fn redo(&mut self, current: &S) -> Option<S> {
+        self.open_step = None;
+        let next = self.redo.pop()?;
+        if self.undo.len() >= self.max_depth {
+            self.undo.pop_front();
+        }
+        if self.max_depth > 0 {
+            self.undo.push_back(current.clone());
+        }
+        Some(next)
+    }
+
+    /// Apply a history action to `current`
+    pub fn apply(&mut self, action: HistoryAction, current: &S) -> Option<S> {
+        match action {
+            HistoryAction::Undo => self.undo(current),
+            HistoryAction::Redo => self.redo(current),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_text(history: &mut UndoHistory<String>, text: &mut String, input: &str) {
+        for ch in input.chars() {
+            history.record(text, EditKind::for_char(ch));
+            text.push(ch);
+        }
+    }
+
+    #[test]
+    fn test_typing_coalesces_per_word() {
+        let mut history = UndoHistory::new(10);
+        let mut text = String::new();
+        type_text(&mut history, &mut text, "hello world");
+        assert_eq!(history.len(), 2);
+
+        text = history.undo(&text).unwrap();
+        assert_eq!(text, "hello ");
+        text = history.undo(&text).unwrap();
+        assert_eq!(text, "");
+        assert!(history.undo(&text).is_none());
+
+        text = history.redo(&text).unwrap();
+        assert_eq!(text, "hello ");
+        text = history.redo(&text).unwrap();
+        assert_eq!(text, "hello world");
+    }
+
+    #[test]
+    fn test_clear_is_undoable_and_cursor_move_breaks_steps() {
+        let mut history = UndoHistory::new(10);
+        let mut text = String::new();
+        type_text(&mut history, &mut text, "abc");
+        history.break_group();
+        type_text(&mut history, &mut text, "def");
+        assert_eq!(history.len(), 2);
+
+        history.record(&text, EditKind::Replace);
+        text.clear();
+        assert_eq!(history.undo(&text).as_deref(), Some("abcdef"));
+    }
+
+    #[test]
+    fn test_depth_limit_and_new_edit_drops_redo() {
+        let mut history = UndoHistory::new(2).with_coalescing(false);
+        let mut text = String::new();
+        type_text(&mut history, &mut text, "abc");
+        assert_eq!(history.len(), 2);
+
+        text = history.undo(&text).unwrap();
+        assert!(history.can_redo());
+        history.record(&text, EditKind::Delete);
+        assert!(!history.can_redo());
+    }
+
+    #[test]
+    fn test_history_action_from_keybind() {
+        assert_eq!(
+            HistoryAction::from_action_id("input.undo"),
+            Some(HistoryAction::Undo)
+        );
+        assert_eq!(
+            HistoryAction::from_action_id("input.redo"),
+            Some(HistoryAction::Redo)
+        );
+        assert_eq!(HistoryAction::from_action_id("input.submit"), None);
+    }
+}
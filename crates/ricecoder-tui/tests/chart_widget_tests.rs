@@ -0,0 +1,132 @@
+//! Chart widget tests
+//!
+//! Tests for axis scaling, rendering and metric binding of terminal charts.
+
+use std::collections::HashMap;
+
+use chrono::{TimeDelta, Utc};
+use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+use ricecoder_monitoring::{
+    dashboards::DashboardManager, metrics::MetricsCollector, types::MetricsConfig,
+};
+use ricecoder_tui::chart_widget::{
+    histogram_counts, AxisRange, ChartKind, ChartWidget, DashboardView, MetricChart, MetricQuery,
+};
+
+fn rendered(widget: impl Widget, width: u16, height: u16) -> String {
+    let area = Rect::new(0, 0, width, height);
+    let mut buffer = Buffer::empty(area);
+    widget.render(area, &mut buffer);
+    buffer.content.iter().map(|c| c.symbol()).collect()
+}
+
+#[test]
+fn test_axis_range_rounds_to_nice_steps() {
+    let range = AxisRange::auto([3.2, 47.9, 12.0]);
+    assert_eq!(
+        range,
+        AxisRange {
+            min: 0.0,
+            max: 50.0
+        }
+    );
+
+    let flat = AxisRange::auto([5.0, 5.0]);
+    assert!(flat.min < 5.0 && flat.max > 5.0);
+
+    assert_eq!(
+        AxisRange::auto(std::iter::empty()),
+        AxisRange { min: 0.0, max: 1.0 }
+    );
+    assert_eq!(AxisRange::auto_from_zero([20.0, 30.0]).min, 0.0);
+}
+
+#[test]
+fn test_histogram_counts_every_value_once() {
+    let values = [1.0, 2.0, 2.5, 9.0, 10.0];
+    let counts = histogram_counts(
+        &values,
+        AxisRange {
+            min: 0.0,
+            max: 10.0,
+        },
+        5,
+    );
+    assert_eq!(counts, vec![1.0, 2.0, 0.0, 0.0, 2.0]);
+}
+
+#[test]
+fn test_line_chart_renders_braille() {
+    let mut chart = ChartWidget::new(ChartKind::Line).with_title("cpu");
+    for (i, value) in [1.0, 4.0, 2.0, 8.0, 3.0].into_iter().enumerate() {
+        chart
+            .series
+            .push(Utc::now() + TimeDelta::seconds(i as i64), value);
+    }
+
+    let content = rendered(&chart, 20, 6);
+    assert!(content.contains("cpu"));
+    assert!(content
+        .chars()
+        .any(|c| ('\u{2801}'..='\u{28ff}').contains(&c)));
+}
+
+#[test]
+fn test_bar_chart_renders_blocks() {
+    let mut chart = ChartWidget::new(ChartKind::Bar);
+    chart.series.push(Utc::now(), 0.0);
+    chart.series.push(Utc::now(), 10.0);
+
+    let content = rendered(&chart, 12, 6);
+    assert!(content.contains('█'));
+}
+
+#[test]
+fn test_empty_chart_shows_placeholder() {
+    let chart = ChartWidget::new(ChartKind::Histogram { buckets: 4 });
+    assert!(rendered(&chart, 20, 4).contains("no data"));
+}
+
+#[test]
+fn test_metric_chart_refreshes_incrementally() {
+    let collector = MetricsCollector::new(MetricsConfig {
+        enabled: false,
+        collection_interval: TimeDelta::seconds(60),
+        retention_period: TimeDelta::hours(1),
+        exporters: Vec::new(),
+    });
+    let metric = "chart_tests.latency";
+    let mut chart = MetricChart::new(
+        MetricQuery::new(metric).with_label("route", "a"),
+        ChartWidget::new(ChartKind::Line),
+    );
+
+    let labels = |route: &str| HashMap::from([("route".to_string(), route.to_string())]);
+    collector.record_metric(metric, 1.0, labels("a"));
+    collector.record_metric(metric, 2.0, labels("b"));
+    assert_eq!(chart.refresh(&collector), 1);
+    assert_eq!(chart.refresh(&collector), 0);
+
+    std::thread::sleep(std::time::Duration::from_millis(2));
+    collector.record_metric(metric, 3.0, labels("a"));
+    assert_eq!(chart.refresh(&collector), 1);
+    assert_eq!(
+        chart.chart.series.values().collect::<Vec<_>>(),
+        vec![1.0, 3.0]
+    );
+}
+
+#[test]
+fn test_dashboard_view_renders_every_panel() {
+    let mut manager = DashboardManager::new();
+    manager.create_system_dashboard();
+    let dashboard = manager.get_dashboard("system-overview").unwrap();
+
+    let view = DashboardView::new(dashboard);
+    assert_eq!(view.charts().count(), dashboard.panels.len());
+
+    let content = rendered(&view, 80, 24);
+    for panel in &dashboard.panels {
+        assert!(content.contains(&panel.title), "{}", panel.title);
+    }
+}
@@ -0,0 +1,71 @@
+//! Clipboard history tests
+//!
+//! Tests for the bounded copy/paste history kept by the clipboard manager.
+
+use ricecoder_tui::{
+    clipboard::ClipboardBackend, ClipboardData, ClipboardHistory, ClipboardImage, ClipboardManager,
+    HistoryLimits,
+};
+
+fn text(value: &str) -> ClipboardData {
+    ClipboardData::Text(value.to_string())
+}
+
+#[test]
+fn test_history_is_bounded_and_most_recent_first() {
+    let limits = HistoryLimits::default().with_max_clipboard_entries(3);
+    let mut history = ClipboardHistory::from_limits(&limits);
+    for value in ["one", "two", "three", "four"] {
+        history.record(text(value));
+    }
+
+    assert_eq!(history.len(), 3);
+    let values: Vec<_> = history.iter().map(|entry| entry.data.clone()).collect();
+    assert_eq!(values, vec![text("four"), text("three"), text("two")]);
+
+    // Recording an existing entry moves it to the front
+    history.record(text("two"));
+    assert_eq!(history.len(), 3);
+    assert_eq!(history.latest().unwrap().data, text("two"));
+
+    // Empty text is never recorded
+    history.record(text(""));
+    assert_eq!(history.latest().unwrap().data, text("two"));
+}
+
+#[test]
+fn test_history_holds_images() {
+    let mut history = ClipboardHistory::new(5);
+    let image = ClipboardImage::new(2, 1, vec![255u8; 8]);
+    history.record(text("caption"));
+    history.record(ClipboardData::Image(image.clone()));
+
+    let latest = &history.latest().unwrap().data;
+    assert_eq!(latest, &ClipboardData::Image(image.clone()));
+    assert_eq!(latest.size(), 8);
+    assert_eq!(latest.summary(20), "Image 2×1");
+    assert!(!image.to_png().unwrap().is_empty());
+
+    assert_eq!(history.remove(0).unwrap().data, ClipboardData::Image(image));
+    assert_eq!(history.latest().unwrap().data, text("caption"));
+}
+
+#[test]
+fn test_manager_records_pastes() {
+    let manager = ClipboardManager::with_backend(ClipboardBackend::Osc52).with_history_capacity(2);
+    manager.record(text("first"));
+    manager.record(text("second\nline"));
+
+    let history = manager.history();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].data.summary(20), "second…");
+    assert_eq!(manager.history_entry(1).unwrap().data, text("first"));
+
+    // Terminal backends cannot carry images
+    assert!(manager
+        .copy_image(ClipboardImage::new(1, 1, vec![0u8; 4]))
+        .is_err());
+
+    manager.clear_history();
+    assert!(manager.history().is_empty());
+}
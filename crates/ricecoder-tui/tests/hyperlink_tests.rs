@@ -0,0 +1,135 @@
+//! Hyperlink tests
+//!
+//! Tests for link detection in markdown, log and transcript output, and for
+//! writing on-screen links as OSC 8 hyperlinks.
+
+use std::path::{Path, PathBuf};
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Modifier,
+    text::Line,
+    widgets::{StatefulWidget, Widget},
+};
+use ricecoder_sessions::{Message, MessageRole};
+use ricecoder_themes::Theme;
+use ricecoder_tui::{
+    hyperlink::{FileLocation, HyperlinkMap, LinkTarget},
+    logger_widget::LoggerWidget,
+    markdown::{MarkdownElement, MarkdownParser},
+    transcript::{TranscriptView, TranscriptWidget},
+};
+
+fn file(path: &str, line: u32, column: Option<u32>) -> LinkTarget {
+    LinkTarget::File(FileLocation {
+        path: PathBuf::from(path),
+        line,
+        column,
+    })
+}
+
+fn text(line: &Line) -> String {
+    line.spans
+        .iter()
+        .map(|span| span.content.as_ref())
+        .collect()
+}
+
+#[test]
+fn test_markdown_links_become_regions() {
+    let rendered = MarkdownParser::render_lines(&[
+        MarkdownElement::Text("Fails at src/main.rs:7:3 ".to_string()),
+        MarkdownElement::Link("docs".to_string(), "https://example.com".to_string()),
+        MarkdownElement::Newline,
+        MarkdownElement::Link("handler".to_string(), "src/handler.rs:40".to_string()),
+    ]);
+
+    assert_eq!(rendered.lines.len(), 2);
+    assert_eq!(rendered.links.len(), 3);
+
+    let first = text(&rendered.lines[0]);
+    assert_eq!(rendered.links[0].row, 0);
+    assert_eq!(
+        rendered.links[0].column as usize,
+        first.find("src/main.rs").unwrap()
+    );
+    assert_eq!(rendered.links[0].target, file("src/main.rs", 7, Some(3)));
+    assert_eq!(
+        rendered.links[1].target,
+        LinkTarget::Url("https://example.com".to_string())
+    );
+    assert_eq!(rendered.links[2].row, 1);
+    assert_eq!(rendered.links[2].target, file("src/handler.rs", 40, None));
+}
+
+#[test]
+fn test_log_lines_carry_file_locations() {
+    let mut logger = LoggerWidget::new(10);
+    logger.info("build started");
+    logger.error("error[E0308]: mismatched types at crates/core/src/lib.rs:12:9");
+
+    let rendered = logger.visible_lines(10);
+    assert_eq!(rendered.lines.len(), 2);
+    assert_eq!(rendered.links.len(), 1);
+
+    let link = &rendered.links[0];
+    assert_eq!(link.row, 1);
+    assert_eq!(link.width as usize, "crates/core/src/lib.rs:12:9".len());
+    assert_eq!(link.target, file("crates/core/src/lib.rs", 12, Some(9)));
+}
+
+#[test]
+fn test_transcript_link_at_viewport_position() {
+    let mut view = TranscriptView::from_messages(&[Message::new(
+        MessageRole::Assistant,
+        "Fixed the panic in src/parser.rs:88:14".to_string(),
+    )]);
+    let area = Rect::new(0, 0, 60, 6);
+    let mut buffer = Buffer::empty(area);
+    let theme = Theme::default();
+    TranscriptWidget::new(&theme).render(area, &mut buffer, &mut view);
+
+    let link = view
+        .links()
+        .first()
+        .expect("file location is a link")
+        .clone();
+    assert_eq!(link.target, file("src/parser.rs", 88, Some(14)));
+    assert_eq!(
+        view.link_at(link.column + 2, link.row as u16),
+        Some(&link.target)
+    );
+    assert_eq!(
+        view.link_at(link.column + link.width, link.row as u16),
+        None
+    );
+    assert!(buffer[(link.column, link.row as u16)]
+        .modifier
+        .contains(Modifier::UNDERLINED));
+}
+
+#[test]
+fn test_map_scans_frame_and_writes_osc8() {
+    let area = Rect::new(0, 0, 40, 2);
+    let mut buffer = Buffer::empty(area);
+    Line::from("see https://example.com/a now").render(area, &mut buffer);
+
+    let mut links = HyperlinkMap::new();
+    links.watch(area);
+    links.scan(&mut buffer);
+
+    assert_eq!(links.links().len(), 1);
+    assert_eq!(
+        links.target_at(6, 0),
+        Some(&LinkTarget::Url("https://example.com/a".to_string()))
+    );
+    assert_eq!(links.target_at(2, 0), None);
+
+    let mut out = Vec::new();
+    links.write_osc8(&mut out, Path::new("/work")).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    let start = out.find("\x1b]8;;https://example.com/a\x1b\\").unwrap();
+    let end = out.find("\x1b]8;;\x1b\\").unwrap();
+    assert!(start < end);
+}
@@ -0,0 +1,74 @@
+//! Project bootstrap tests
+//!
+//! Tests for first-run detection and writing the project configuration
+//! chosen in the bootstrap wizard.
+
+use std::fs;
+
+use ricecoder_storage::ConfigLoader;
+use ricecoder_tui::project_bootstrap::{
+    BootstrapChoices, BuildTool, Language, ProjectBootstrap, PROJECT_CONFIG_DIR,
+};
+use tempfile::TempDir;
+
+fn rust_project() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    fs::write(
+        dir.path().join("Cargo.toml"),
+        "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    fs::write(dir.path().join("Cargo.lock"), "").unwrap();
+    fs::create_dir(dir.path().join("src")).unwrap();
+    fs::write(dir.path().join("src/main.rs"), "fn main() {}\n").unwrap();
+    dir
+}
+
+#[test]
+fn test_detect_rust_project() {
+    let dir = rust_project();
+    let bootstrap = ProjectBootstrap::new(dir.path().to_path_buf());
+    assert!(bootstrap.needs_setup());
+
+    let detection = bootstrap.detect().unwrap();
+    assert!(detection.languages.contains(&Language::Rust));
+    assert_eq!(detection.build_tools, vec![BuildTool::Cargo]);
+    assert!(!detection.has_project_config);
+    assert!(detection
+        .lsp_servers
+        .iter()
+        .any(|server| server.language == "rust"));
+}
+
+#[test]
+fn test_write_project_config_round_trips() {
+    let dir = rust_project();
+    let bootstrap = ProjectBootstrap::new(dir.path().to_path_buf());
+    let detection = bootstrap.detect().unwrap();
+    let choices = BootstrapChoices {
+        create_config: true,
+        lsp_servers: vec!["rust-analyzer".to_string()],
+        mode: "ask".to_string(),
+        provider: Some("openai".to_string()),
+        model: Some("gpt-4".to_string()),
+    };
+
+    let path = bootstrap
+        .write_project_config(&detection, &choices)
+        .unwrap();
+    assert_eq!(
+        path,
+        dir.path().join(PROJECT_CONFIG_DIR).join("config.yaml")
+    );
+    assert!(!bootstrap.needs_setup());
+
+    let config = ConfigLoader::load_from_file(&path).unwrap();
+    assert_eq!(config.providers.default_provider.as_deref(), Some("openai"));
+    assert_eq!(config.defaults.model.as_deref(), Some("gpt-4"));
+    assert_eq!(config.custom["mode"], "ask");
+    assert_eq!(config.custom["project"]["build_tools"][0], "cargo");
+    assert_eq!(config.custom["project"]["test_command"], "cargo test");
+    assert_eq!(config.custom["lsp"]["servers"][0], "rust-analyzer");
+
+    assert!(bootstrap.detect().unwrap().has_project_config);
+}
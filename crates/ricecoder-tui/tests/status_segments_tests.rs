@@ -0,0 +1,147 @@
+//! Status bar segment tests
+//!
+//! Tests for segment ordering, visibility and width negotiation of the
+//! composable status bar.
+
+use std::sync::Arc;
+
+use ratatui::text::{Line, Span};
+use ricecoder_storage::{StatusSegmentConfig, TuiStatusBarConfig};
+use ricecoder_themes::Theme;
+use ricecoder_tui::{
+    performance::JobQueueStats,
+    status_segments::{
+        LspHealth, LspServerStatus, SegmentContent, StatusBar, StatusContext, StatusSegment,
+        VcsState,
+    },
+};
+
+fn context() -> StatusContext {
+    StatusContext {
+        mode: "build".to_string(),
+        provider: Some("anthropic".to_string()),
+        model: Some("claude-sonnet".to_string()),
+        token_summary: Some("12.5K tokens | $0.03".to_string()),
+        vcs: Some(VcsState {
+            branch: "main".to_string(),
+            dirty: true,
+            ahead: 1,
+            behind: 0,
+        }),
+        jobs: Some(JobQueueStats {
+            queued_jobs: 1,
+            active_jobs: 2,
+            max_concurrent: 4,
+            total_submitted: 3,
+        }),
+        lsp_servers: vec![LspServerStatus {
+            name: "rust-analyzer".to_string(),
+            health: LspHealth::Ready,
+        }],
+        ..StatusContext::default()
+    }
+}
+
+fn text(line: &Line) -> String {
+    line.spans
+        .iter()
+        .map(|span| span.content.as_ref())
+        .collect()
+}
+
+fn entry(id: &str, visible: bool, priority: Option<u8>) -> StatusSegmentConfig {
+    StatusSegmentConfig {
+        id: id.to_string(),
+        visible,
+        priority,
+    }
+}
+
+struct ClockSegment;
+
+impl StatusSegment for ClockSegment {
+    fn id(&self) -> &str {
+        "clock"
+    }
+
+    fn render(&self, _context: &StatusContext, _theme: &Theme) -> Option<SegmentContent> {
+        Some(SegmentContent::new(vec![Span::raw("12:00")]))
+    }
+}
+
+#[test]
+fn test_builtin_segments_render_in_order() {
+    let bar = StatusBar::default();
+    let line = text(&bar.render_line(&context(), &Theme::default(), 200));
+
+    assert_eq!(
+        line,
+        "BUILD │ anthropic/claude-sonnet │ 12.5K tokens | $0.03 │ ⎇ main* ↑1 │ ⟳ 2 jobs, 1 queued │ LSP rust-analyzer ✓"
+    );
+}
+
+#[test]
+fn test_config_orders_and_hides_segments() {
+    let mut bar = StatusBar::default();
+    bar.apply_config(&TuiStatusBarConfig {
+        segments: vec![
+            entry("vcs", true, None),
+            entry("mode", true, None),
+            entry("tokens", false, None),
+            entry("clock", true, None),
+        ],
+    });
+    assert_eq!(
+        bar.visible_segments()
+            .iter()
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>(),
+        vec!["vcs", "mode"]
+    );
+
+    // A configured plugin segment shows up once registered
+    bar.register(Arc::new(ClockSegment));
+    let line = text(&bar.render_line(&context(), &Theme::default(), 200));
+    assert_eq!(line, "⎇ main* ↑1 │ BUILD │ 12:00");
+}
+
+#[test]
+fn test_narrow_width_compacts_then_drops_low_priority_segments() {
+    let bar = StatusBar::default();
+    let ctx = context();
+    let theme = Theme::default();
+
+    // Jobs (priority 30) compacts before anything else
+    let wide = bar.layout(&ctx, &theme, 200);
+    assert!(wide.iter().all(|segment| !segment.compact));
+    let full_width = text(&bar.render_line(&ctx, &theme, 200)).chars().count() as u16;
+    let placed = bar.layout(&ctx, &theme, full_width - 1);
+    let jobs = placed.iter().find(|s| s.id == "jobs").unwrap();
+    assert!(jobs.compact);
+    assert!(placed.iter().filter(|s| s.compact).count() == 1);
+
+    // Very narrow: only the mode survives
+    let placed = bar.layout(&ctx, &theme, 12);
+    assert_eq!(placed.len(), 1);
+    assert_eq!(placed[0].id, "mode");
+    assert!(placed[0].compact);
+}
+
+#[test]
+fn test_priority_override_and_truncation() {
+    let mut bar = StatusBar::default();
+    bar.apply_config(&TuiStatusBarConfig {
+        segments: vec![entry("mode", true, Some(10)), entry("model", true, None)],
+    });
+    let ctx = context();
+    let theme = Theme::default();
+
+    // Mode now yields to the model
+    let placed = bar.layout(&ctx, &theme, 14);
+    assert_eq!(placed.len(), 1);
+    assert_eq!(placed[0].id, "model");
+
+    // The lone model segment is cut to fit
+    let line = text(&bar.render_line(&ctx, &theme, 8));
+    assert_eq!(line, "claude-…");
+}
@@ -0,0 +1,164 @@
+//! Transcript virtualization tests
+//!
+//! Tests for height caching, tool output hydration and sticky headers of
+//! the virtualized message transcript.
+
+use std::collections::HashMap;
+
+use ratatui::{buffer::Buffer, layout::Rect, widgets::StatefulWidget};
+use ricecoder_sessions::{
+    models::{CompletedTime, TimeRange, ToolState},
+    Message, MessagePart, MessageRole, ToolStatus,
+};
+use ricecoder_themes::Theme;
+use ricecoder_tui::{
+    performance::VirtualScrollManager,
+    transcript::{ToolOutput, TranscriptEntry, TranscriptView, TranscriptWidget},
+};
+
+fn tool_message(call_id: &str, state: ToolState) -> Message {
+    let mut message = Message::new(MessageRole::Assistant, String::new());
+    message.parts = vec![MessagePart::Tool {
+        id: None,
+        session_id: None,
+        message_id: None,
+        call_id: call_id.to_string(),
+        tool: "bash".to_string(),
+        state,
+        metadata: None,
+    }];
+    message
+}
+
+fn completed(output: &str) -> ToolState {
+    ToolState::Completed {
+        input: HashMap::new(),
+        output: output.to_string(),
+        title: "cargo test".to_string(),
+        metadata: HashMap::new(),
+        time: CompletedTime {
+            start: 0,
+            end: 1,
+            compacted: None,
+        },
+        attachments: None,
+    }
+}
+
+fn running() -> ToolState {
+    ToolState::Running {
+        input: HashMap::new(),
+        title: Some("cargo build".to_string()),
+        metadata: None,
+        time: TimeRange {
+            start: 0,
+            end: None,
+        },
+    }
+}
+
+fn rendered_rows(view: &mut TranscriptView, width: u16, height: u16) -> Vec<String> {
+    let area = Rect::new(0, 0, width, height);
+    let mut buffer = Buffer::empty(area);
+    let theme = Theme::default();
+    TranscriptWidget::new(&theme).render(area, &mut buffer, view);
+    (0..height)
+        .map(|y| (0..width).map(|x| buffer[(x, y)].symbol()).collect())
+        .collect()
+}
+
+#[test]
+fn test_scroll_manager_line_offsets() {
+    let mut scroll = VirtualScrollManager::new(4, 2);
+    scroll.set_width(80);
+    scroll.record_item_height(0, 3);
+    scroll.record_item_height(1, 5);
+    scroll.record_item_height(2, 1);
+    scroll.record_item_height(3, 3);
+
+    assert_eq!(scroll.item_offset(2), 8);
+    assert_eq!(scroll.total_height(), 12);
+    assert_eq!(scroll.item_at_line(0), 0);
+    assert_eq!(scroll.item_at_line(3), 1);
+    assert_eq!(scroll.item_at_line(8), 2);
+    assert_eq!(scroll.item_at_line(100), 3);
+
+    scroll.invalidate_item(1);
+    assert_eq!(scroll.cached_height(1), None);
+    assert_eq!(scroll.item_height(1), 2);
+
+    assert!(scroll.set_width(40));
+    assert!(scroll.item_heights.is_empty());
+    assert_eq!(scroll.estimated_total_height(), 4);
+}
+
+#[test]
+fn test_finished_tool_output_is_hydrated_on_expand() {
+    let messages = vec![
+        Message::new(MessageRole::User, "run the tests".to_string()),
+        tool_message("call-1", completed("line 1\nline 2\nline 3")),
+    ];
+    let mut view = TranscriptView::from_messages(&messages);
+    assert_eq!(view.len(), 2);
+
+    let index = view.find_tool("call-1").unwrap();
+    let TranscriptEntry::ToolCall(block) = &view.entries()[index] else {
+        panic!("expected a tool call");
+    };
+    assert!(block.collapsed);
+    assert_eq!(block.output, ToolOutput::Unloaded { line_count: 3 });
+
+    assert!(view.toggle_tool(index, messages.as_slice()));
+    let TranscriptEntry::ToolCall(block) = &view.entries()[index] else {
+        panic!("expected a tool call");
+    };
+    assert!(!block.collapsed);
+    assert_eq!(block.output.line_count(), 3);
+    assert!(rendered_rows(&mut view, 40, 10)
+        .iter()
+        .any(|row| row.contains("line 2")));
+
+    assert!(view.toggle_tool(index, messages.as_slice()));
+    let TranscriptEntry::ToolCall(block) = &view.entries()[index] else {
+        panic!("expected a tool call");
+    };
+    assert!(!block.output.is_loaded());
+}
+
+#[test]
+fn test_large_transcript_follows_newest_message() {
+    let messages: Vec<Message> = (0..5000)
+        .map(|i| Message::new(MessageRole::Assistant, format!("message {}", i)))
+        .collect();
+    let mut view = TranscriptView::from_messages(&messages);
+
+    let rows = rendered_rows(&mut view, 40, 12);
+    assert!(rows.iter().any(|row| row.contains("message 4999")));
+    assert!(view.is_following());
+
+    view.scroll_to_top();
+    let rows = rendered_rows(&mut view, 40, 12);
+    assert!(rows[1].contains("message 0"));
+    assert!(!view.is_following());
+}
+
+#[test]
+fn test_running_tool_header_sticks_to_top() {
+    let mut messages = vec![tool_message("call-1", running())];
+    messages
+        .extend((0..50).map(|i| Message::new(MessageRole::Assistant, format!("progress {}", i))));
+    let mut view = TranscriptView::from_messages(&messages);
+
+    view.scroll_to_top();
+    rendered_rows(&mut view, 40, 10);
+    assert!(view.sticky_headers().is_empty());
+
+    view.scroll_down(20);
+    let rows = rendered_rows(&mut view, 40, 10);
+    assert_eq!(view.sticky_headers(), vec![0]);
+    assert!(rows[0].contains("bash"));
+    assert_eq!(view.entry_at_row(0), Some(0));
+
+    view.update_tool("call-1", ToolStatus::Complete, Some("ok"));
+    assert!(view.sticky_headers().is_empty());
+}
@@ -0,0 +1,63 @@
+//! Undo/redo tests for the text input widgets
+//!
+//! Tests for word-level coalescing and recovering cleared input.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ricecoder_tui::{ChatInputWidget, TextAreaWidget};
+
+fn type_keys(widget: &mut TextAreaWidget, text: &str) {
+    for c in text.chars() {
+        widget.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+    }
+}
+
+#[test]
+fn test_textarea_undo_restores_cleared_text() {
+    let mut widget = TextAreaWidget::default();
+    type_keys(&mut widget, "hello world");
+    widget.clear();
+    assert!(widget.is_empty());
+
+    assert!(widget.undo());
+    assert_eq!(widget.text(), "hello world");
+    assert_eq!(widget.cursor_position(), (0, 11));
+}
+
+#[test]
+fn test_textarea_undo_is_word_level() {
+    let mut widget = TextAreaWidget::default();
+    type_keys(&mut widget, "hello world");
+
+    widget.handle_key(KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL));
+    assert_eq!(widget.text(), "hello ");
+    widget.handle_key(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL));
+    assert_eq!(widget.text(), "hello world");
+
+    assert!(widget.handle_action("input.undo"));
+    assert!(widget.handle_action("input.undo"));
+    assert_eq!(widget.text(), "");
+    assert!(!widget.handle_action("input.submit"));
+}
+
+#[test]
+fn test_chat_input_undo_redo() {
+    let mut input = ChatInputWidget::new();
+    for c in "fix bug".chars() {
+        input.insert_char(c);
+    }
+    input.backspace();
+    input.backspace();
+    assert_eq!(input.text, "fix b");
+
+    assert!(input.undo());
+    assert_eq!(input.text, "fix bug");
+
+    input.clear();
+    assert!(input.undo());
+    assert_eq!(input.text, "fix bug");
+
+    assert!(input.undo());
+    assert_eq!(input.text, "fix ");
+    assert!(input.redo());
+    assert_eq!(input.text, "fix bug");
+}
@@ -13,6 +13,7 @@ pub enum AppError {
     Index { operation: String, message: String },
     Search { query: String, message: String },
     Config(String),
+    AccessDenied { path: String, reason: String },
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -39,6 +40,7 @@ impl fmt::Display for AppError {
             AppError::Index { operation, message } => write!(f, "Index {} failed: {}", operation, message),
             AppError::Search { query, message } => write!(f, "Search for '{}' failed: {}", query, message),
             AppError::Config(msg) => write!(f, "Configuration error: {}", msg),
+            AppError::AccessDenied { path, reason } => write!(f, "Access to '{}' denied: {}", path, reason),
         }
     }
 }
@@ -74,4 +76,10 @@ mod tests {
         let error = AppError::Config("missing key".to_string());
         assert!(error.to_string().contains("missing key"));
     }
+
+    #[test]
+    fn test_access_denied_display() {
+        let error = AppError::AccessDenied { path: ".env".to_string(), reason: "sensitive path".to_string() };
+        assert_eq!(error.to_string(), "Access to '.env' denied: sensitive path");
+    }
 }
@@ -0,0 +1,76 @@
+//! Query Matching Helpers
+//!
+//! Shared translation of domain search queries into compiled regexes, used by
+//! use cases and by infrastructure adapters that verify candidate files.
+
+use regex::{Regex, RegexBuilder};
+
+use crate::application::errors::{AppError, AppResult};
+use crate::domain::{SearchMatch, SearchQuery};
+
+/// Compile a search query into a regex honouring its options
+///
+/// Literal queries are escaped, whole-word queries are wrapped in `\b`
+/// anchors and case-insensitive queries set the `i` flag.
+pub fn build_query_regex(query: &SearchQuery) -> AppResult<Regex> {
+    let mut pattern = if query.is_regex() {
+        query.query().to_string()
+    } else {
+        regex::escape(query.query())
+    };
+    if query.is_whole_words() {
+        pattern = format!(r"\b(?:{})\b", pattern);
+    }
+
+    RegexBuilder::new(&pattern)
+        .case_insensitive(!query.is_case_sensitive())
+        .build()
+        .map_err(|e| AppError::Validation {
+            message: format!("Invalid regex: {}", e)
+        })
+}
+
+/// Find every match of `regex` in `content`
+///
+/// Line numbers are 1-based and columns are 0-based byte offsets into the line.
+pub fn find_query_matches(regex: &Regex, content: &str) -> Vec<SearchMatch> {
+    let mut line_number = 1;
+    let mut line_start = 0;
+    let mut scanned = 0;
+
+    regex.find_iter(content)
+        .map(|m| {
+            for (offset, _) in content[scanned..m.start()].match_indices('\n') {
+                line_number += 1;
+                line_start = scanned + offset + 1;
+            }
+            scanned = m.start();
+            SearchMatch::new(line_number, m.start() - line_start, m.as_str().to_string())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_query_regex_options() {
+        let query = SearchQuery::new("a.b", false, true, false).unwrap();
+        let regex = build_query_regex(&query).unwrap();
+        assert!(regex.is_match("x A.B y"));
+        assert!(!regex.is_match("axb"));
+        assert!(!regex.is_match("ca.b"));
+    }
+
+    #[test]
+    fn test_find_query_matches_positions() {
+        let regex = build_query_regex(&SearchQuery::regex(r"fn \w+").unwrap()).unwrap();
+        let matches = find_query_matches(&regex, "fn a() {}\n\n  fn b() {} fn c() {}\n");
+
+        let positions: Vec<(usize, usize, &str)> = matches.iter()
+            .map(|m| (m.line_number(), m.column_start(), m.matched_text()))
+            .collect();
+        assert_eq!(positions, vec![(1, 0, "fn a"), (3, 2, "fn b"), (3, 12, "fn c")]);
+    }
+}
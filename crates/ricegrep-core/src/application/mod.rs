@@ -4,11 +4,15 @@
 //! hexagonal architecture (ports and adapters) pattern.
 
 pub mod errors;
+pub mod matching;
+pub mod path_guard;
 pub mod ports;
 pub mod use_cases;
 pub mod services;
 
 pub use errors::*;
+pub use matching::*;
+pub use path_guard::*;
 pub use ports::*;
 pub use use_cases::*;
 pub use services::*;
@@ -0,0 +1,205 @@
+//! Path Guards - Repositories that enforce a PathAccessPolicy
+//!
+//! Wrap any [`FileRepository`] or [`IndexRepository`] so every use case built
+//! on top of it respects the same path policy: reads and writes of denied
+//! paths fail with [`AppError::AccessDenied`], and denied files are dropped
+//! from search results.
+
+use std::ops::ControlFlow;
+
+use crate::application::errors::AppResult;
+use crate::application::ports::{
+    FileAccess, FileIndexEntry, FileRepository, IndexRepository, PathAccessPolicy, SearchCancellation,
+};
+use crate::domain::{FilePath, SearchQuery, SearchResult};
+
+/// File repository that checks a policy before touching a path
+#[derive(Debug, Clone)]
+pub struct GuardedFileRepository<F, P> {
+    inner: F,
+    policy: P,
+}
+
+impl<F: FileRepository, P: PathAccessPolicy> GuardedFileRepository<F, P> {
+    pub fn new(inner: F, policy: P) -> Self {
+        GuardedFileRepository { inner, policy }
+    }
+
+    pub fn inner(&self) -> &F { &self.inner }
+}
+
+impl<F: FileRepository, P: PathAccessPolicy> FileRepository for GuardedFileRepository<F, P> {
+    fn read(&self, path: &FilePath) -> AppResult<String> {
+        self.policy.check(path, FileAccess::Read)?;
+        self.inner.read(path)
+    }
+
+    fn write(&self, path: &FilePath, content: &str) -> AppResult<()> {
+        self.policy.check(path, FileAccess::Write)?;
+        self.inner.write(path, content)
+    }
+
+    fn exists(&self, path: &FilePath) -> bool {
+        self.inner.exists(path)
+    }
+
+    fn delete(&self, path: &FilePath) -> AppResult<()> {
+        self.policy.check(path, FileAccess::Write)?;
+        self.inner.delete(path)
+    }
+
+    fn ensure_parent_dirs(&self, path: &FilePath) -> AppResult<()> {
+        self.policy.check(path, FileAccess::Write)?;
+        self.inner.ensure_parent_dirs(path)
+    }
+}
+
+/// Index repository that leaves denied files out of search results
+#[derive(Debug, Clone)]
+pub struct GuardedIndexRepository<I, P> {
+    inner: I,
+    policy: P,
+}
+
+impl<I: IndexRepository, P: PathAccessPolicy> GuardedIndexRepository<I, P> {
+    pub fn new(inner: I, policy: P) -> Self {
+        GuardedIndexRepository { inner, policy }
+    }
+
+    pub fn inner(&self) -> &I { &self.inner }
+
+    fn allowed(&self, result: &SearchResult) -> bool {
+        self.policy.check(result.file_path(), FileAccess::Search).is_ok()
+    }
+}
+
+impl<I: IndexRepository, P: PathAccessPolicy> IndexRepository for GuardedIndexRepository<I, P> {
+    fn get_metadata(&self, path: &FilePath) -> Option<FileIndexEntry> {
+        self.inner.get_metadata(path)
+    }
+
+    fn update_metadata(&self, entry: FileIndexEntry) -> AppResult<()> {
+        self.inner.update_metadata(entry)
+    }
+
+    fn remove_metadata(&self, path: &FilePath) -> AppResult<()> {
+        self.inner.remove_metadata(path)
+    }
+
+    fn search(&self, query: &SearchQuery) -> AppResult<Vec<SearchResult>> {
+        let mut results = self.inner.search(query)?;
+        results.retain(|result| self.allowed(result));
+        Ok(results)
+    }
+
+    fn search_streaming(
+        &self,
+        query: &SearchQuery,
+        cancellation: &SearchCancellation,
+        on_result: &mut dyn FnMut(SearchResult) -> ControlFlow<()>,
+    ) -> AppResult<()> {
+        self.inner.search_streaming(query, cancellation, &mut |result| {
+            if self.allowed(&result) { on_result(result) } else { ControlFlow::Continue(()) }
+        })
+    }
+
+    fn needs_reindex(&self, path: &FilePath, modified_at: u64, size: u64) -> bool {
+        self.inner.needs_reindex(path, modified_at, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use crate::application::errors::AppError;
+    use crate::domain::SearchMatch;
+
+    /// Denies every path ending in `.env`
+    struct EnvPolicy;
+
+    impl PathAccessPolicy for EnvPolicy {
+        fn check(&self, path: &FilePath, _access: FileAccess) -> AppResult<()> {
+            if path.file_name() == Some(".env") {
+                return Err(AppError::AccessDenied {
+                    path: path.as_path().display().to_string(),
+                    reason: "sensitive path".to_string(),
+                });
+            }
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct MemoryFileRepo {
+        files: RefCell<HashMap<String, String>>,
+    }
+
+    impl FileRepository for MemoryFileRepo {
+        fn read(&self, path: &FilePath) -> AppResult<String> {
+            Ok(self.files.borrow().get(&path.as_path().display().to_string()).cloned().unwrap_or_default())
+        }
+        fn write(&self, path: &FilePath, content: &str) -> AppResult<()> {
+            self.files.borrow_mut().insert(path.as_path().display().to_string(), content.to_string());
+            Ok(())
+        }
+        fn exists(&self, path: &FilePath) -> bool {
+            self.files.borrow().contains_key(&path.as_path().display().to_string())
+        }
+        fn delete(&self, path: &FilePath) -> AppResult<()> {
+            self.files.borrow_mut().remove(&path.as_path().display().to_string());
+            Ok(())
+        }
+        fn ensure_parent_dirs(&self, _path: &FilePath) -> AppResult<()> { Ok(()) }
+    }
+
+    struct FixedIndexRepo {
+        paths: Vec<&'static str>,
+    }
+
+    impl IndexRepository for FixedIndexRepo {
+        fn get_metadata(&self, _path: &FilePath) -> Option<FileIndexEntry> { None }
+        fn update_metadata(&self, _entry: FileIndexEntry) -> AppResult<()> { Ok(()) }
+        fn remove_metadata(&self, _path: &FilePath) -> AppResult<()> { Ok(()) }
+
+        fn search(&self, query: &SearchQuery) -> AppResult<Vec<SearchResult>> {
+            Ok(self.paths.iter()
+                .map(|p| SearchResult::new(
+                    FilePath::new(p).unwrap(),
+                    vec![SearchMatch::new(1, 0, query.query().to_string())],
+                ))
+                .collect())
+        }
+    }
+
+    #[test]
+    fn test_guarded_file_repository_denies_sensitive_paths() {
+        let repo = GuardedFileRepository::new(MemoryFileRepo::default(), EnvPolicy);
+        let env = FilePath::new("app/.env").unwrap();
+        let main = FilePath::new("app/main.rs").unwrap();
+
+        assert!(matches!(repo.write(&env, "TOKEN=x"), Err(AppError::AccessDenied { .. })));
+        assert!(!repo.inner().exists(&env));
+        assert!(matches!(repo.read(&env), Err(AppError::AccessDenied { .. })));
+
+        repo.write(&main, "fn main() {}").unwrap();
+        assert_eq!(repo.read(&main).unwrap(), "fn main() {}");
+    }
+
+    #[test]
+    fn test_guarded_index_repository_filters_results() {
+        let repo = GuardedIndexRepository::new(FixedIndexRepo { paths: vec!["a.rs", ".env", "b.rs"] }, EnvPolicy);
+        let query = SearchQuery::new("TOKEN", false, false, false).unwrap();
+
+        let results = repo.search(&query).unwrap();
+        assert_eq!(results.len(), 2);
+
+        let mut streamed = Vec::new();
+        repo.search_streaming(&query, &SearchCancellation::new(), &mut |result| {
+            streamed.push(result.file_path().as_path().display().to_string());
+            ControlFlow::Continue(())
+        }).unwrap();
+        assert_eq!(streamed, vec!["a.rs".to_string(), "b.rs".to_string()]);
+    }
+}
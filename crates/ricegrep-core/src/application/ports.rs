@@ -1,6 +1,10 @@
 //! Application Ports (Repository Traits)
 
-use crate::domain::{FilePath, SearchQuery, SearchResult, DomainEvent};
+use std::ops::ControlFlow;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::domain::{FilePath, SearchQuery, SearchResult, SearchMatch, DomainEvent, SourceLanguage, StructuralPattern};
 use crate::application::errors::{AppResult, AppError, IoOperation};
 
 /// Repository trait for file operations
@@ -35,12 +39,54 @@ impl FileIndexEntry {
     }
 }
 
+/// Cooperative cancellation flag shared between a search and its caller
+///
+/// Clones share the same flag, so the caller keeps one clone and cancels it
+/// while the search checks another.
+#[derive(Debug, Clone, Default)]
+pub struct SearchCancellation {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl SearchCancellation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
 /// Repository trait for search index operations
 pub trait IndexRepository {
     fn get_metadata(&self, path: &FilePath) -> Option<FileIndexEntry>;
     fn update_metadata(&self, entry: FileIndexEntry) -> AppResult<()>;
     fn remove_metadata(&self, path: &FilePath) -> AppResult<()>;
     fn search(&self, query: &SearchQuery) -> AppResult<Vec<SearchResult>>;
+
+    /// Search, handing each file's result to `on_result` as soon as it is found
+    ///
+    /// Implementations should stop when `on_result` returns `Break` and check
+    /// `cancellation` between files. The default implementation runs
+    /// [`IndexRepository::search`] and replays its results.
+    fn search_streaming(
+        &self,
+        query: &SearchQuery,
+        cancellation: &SearchCancellation,
+        on_result: &mut dyn FnMut(SearchResult) -> ControlFlow<()>,
+    ) -> AppResult<()> {
+        for result in self.search(query)? {
+            if cancellation.is_cancelled() || on_result(result).is_break() {
+                break;
+            }
+        }
+        Ok(())
+    }
     
     fn needs_reindex(&self, path: &FilePath, modified_at: u64, size: u64) -> bool {
         match self.get_metadata(path) {
@@ -50,6 +96,26 @@ pub trait IndexRepository {
     }
 }
 
+/// Port for AST-aware matching of structural patterns
+///
+/// Implementations parse `source` with the grammar for `language` and return
+/// one match per syntax node matching `pattern`, with captures filled in.
+pub trait StructuralMatcher {
+    fn supports(&self, language: SourceLanguage) -> bool;
+    fn find_matches(&self, language: SourceLanguage, pattern: &StructuralPattern, source: &str) -> AppResult<Vec<SearchMatch>>;
+}
+
+/// Kind of access checked by a [`PathAccessPolicy`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FileAccess { Read, Write, Search }
+
+/// Port deciding which paths may be touched at all, e.g. to keep secrets
+/// such as `.env` files out of reach
+pub trait PathAccessPolicy {
+    /// Fails with [`AppError::AccessDenied`] when `path` is off limits
+    fn check(&self, path: &FilePath, access: FileAccess) -> AppResult<()>;
+}
+
 /// Repository trait for event publishing
 pub trait EventPublisher {
     fn publish(&self, event: &DomainEvent);
@@ -68,4 +134,13 @@ mod tests {
         assert!(!entry.is_stale(1000, 100));
         assert!(entry.is_stale(2000, 100));
     }
+
+    #[test]
+    fn test_search_cancellation_shared_between_clones() {
+        let cancellation = SearchCancellation::new();
+        let observer = cancellation.clone();
+        assert!(!observer.is_cancelled());
+        cancellation.cancel();
+        assert!(observer.is_cancelled());
+    }
 }
@@ -11,14 +11,22 @@
 //!
 //! # Available Use Cases
 //! - `EditFileUseCase` - Find and replace patterns in files
+//! - `ReplaceInFilesUseCase` - Find and replace across matched files with preview
 //! - `SearchFilesUseCase` - Search for patterns across files
+//! - `StructuralSearchUseCase` - AST-aware search with metavariable captures
 //! - `WriteFileUseCase` - Write content to files with validation
 
 pub mod edit_file;
+pub mod replace_in_files;
 pub mod search_files;
+pub mod structural_search;
 pub mod write_file;
 
 // Re-export use cases for ergonomic imports
 pub use edit_file::{EditFileUseCase, EditFileRequest, EditFileResponse};
-pub use search_files::{SearchFilesUseCase, SearchFilesRequest, SearchFilesResponse};
+pub use replace_in_files::{
+    ReplaceInFilesUseCase, ReplaceInFilesRequest, ReplaceInFilesResponse, FileReplacementPreview, ReplacementHunk,
+};
+pub use search_files::{SearchFilesUseCase, SearchFilesRequest, SearchFilesResponse, SearchStreamSummary};
+pub use structural_search::{StructuralSearchUseCase, StructuralSearchRequest, StructuralSearchResponse};
 pub use write_file::{WriteFileUseCase, WriteFileRequest, WriteFileResponse};
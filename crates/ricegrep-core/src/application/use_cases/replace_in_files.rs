@@ -0,0 +1,482 @@
+//! Replace In Files Use Case
+//!
+//! Orchestrates find-and-replace across every file matching a search query.
+//! All files are read and previewed before anything is written, so a failure
+//! part-way through never leaves a partially applied replacement behind.
+
+use regex::Regex;
+
+use crate::application::{AppResult, AppError, FileRepository, IndexRepository, EventPublisher, build_query_regex};
+use crate::domain::{FilePath, SearchQuery, DomainEvent};
+
+/// Request for replacing across files
+#[derive(Debug, Clone)]
+pub struct ReplaceInFilesRequest {
+    /// Search pattern (literal or regex)
+    pub pattern: String,
+    /// Replacement template; for regex patterns `$1` / `${name}` reference
+    /// capture groups and `$$` is a literal `$`
+    pub replacement: String,
+    /// Whether pattern is regex
+    pub is_regex: bool,
+    /// Whether search is case-sensitive
+    pub case_sensitive: bool,
+    /// Whether to match whole words only
+    pub whole_word: bool,
+    /// Optional path filter
+    pub path_filter: Option<String>,
+    /// Files to leave untouched even if they match
+    pub excluded_files: Vec<String>,
+    /// Whether to preview without writing
+    pub dry_run: bool,
+}
+
+impl ReplaceInFilesRequest {
+    /// Create a literal replacement request (previews only)
+    pub fn literal(pattern: impl Into<String>, replacement: impl Into<String>) -> Self {
+        ReplaceInFilesRequest {
+            pattern: pattern.into(),
+            replacement: replacement.into(),
+            is_regex: false,
+            case_sensitive: true,
+            whole_word: false,
+            path_filter: None,
+            excluded_files: Vec::new(),
+            dry_run: true,
+        }
+    }
+
+    /// Create a regex replacement request (previews only)
+    pub fn regex(pattern: impl Into<String>, replacement: impl Into<String>) -> Self {
+        ReplaceInFilesRequest {
+            is_regex: true,
+            ..Self::literal(pattern, replacement)
+        }
+    }
+
+    /// Exclude a file from the replacement
+    pub fn exclude(mut self, file_path: impl Into<String>) -> Self {
+        self.excluded_files.push(file_path.into());
+        self
+    }
+
+    /// Write the replacements instead of only previewing them
+    pub fn apply(mut self) -> Self {
+        self.dry_run = false;
+        self
+    }
+}
+
+/// A contiguous block of changed lines
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplacementHunk {
+    /// First changed line in the original file (1-based)
+    pub old_start: usize,
+    /// Original lines
+    pub old_lines: Vec<String>,
+    /// First changed line in the updated file (1-based)
+    pub new_start: usize,
+    /// Updated lines
+    pub new_lines: Vec<String>,
+}
+
+/// Preview of the replacements in a single file
+#[derive(Debug, Clone)]
+pub struct FileReplacementPreview {
+    /// Path to the file
+    pub file_path: String,
+    /// Number of matches replaced
+    pub replacements: usize,
+    /// Changed line blocks, in file order
+    pub hunks: Vec<ReplacementHunk>,
+    /// Whether the new content was written
+    pub applied: bool,
+}
+
+impl FileReplacementPreview {
+    /// Render the preview as a unified diff (without context lines)
+    pub fn diff(&self) -> String {
+        let mut diff = format!("--- a/{}\n+++ b/{}\n", self.file_path, self.file_path);
+        for hunk in &self.hunks {
+            diff.push_str(&format!(
+                "@@ -{},{} +{},{} @@\n",
+                hunk.old_start,
+                hunk.old_lines.len(),
+                hunk.new_start,
+                hunk.new_lines.len()
+            ));
+            for line in &hunk.old_lines {
+                diff.push_str(&format!("-{}\n", line));
+            }
+            for line in &hunk.new_lines {
+                diff.push_str(&format!("+{}\n", line));
+            }
+        }
+        diff
+    }
+}
+
+/// Response from a replace operation
+#[derive(Debug, Clone)]
+pub struct ReplaceInFilesResponse {
+    /// Per-file previews (only files with at least one replacement)
+    pub files: Vec<FileReplacementPreview>,
+    /// Total matches replaced across files
+    pub total_replacements: usize,
+    /// Matching files skipped because they were excluded
+    pub excluded_files: Vec<String>,
+    /// Whether this was a dry run
+    pub was_dry_run: bool,
+}
+
+/// Use case for find-and-replace across files
+///
+/// # Example
+/// ```ignore
+/// let use_case = ReplaceInFilesUseCase::new(file_repo, index_repo, event_publisher);
+/// let preview = use_case.execute(ReplaceInFilesRequest::regex(r"old_(\w+)", "new_$1"))?;
+/// println!("{}", preview.files[0].diff());
+///
+/// let request = ReplaceInFilesRequest::regex(r"old_(\w+)", "new_$1")
+///     .exclude("src/generated.rs")
+///     .apply();
+/// let response = use_case.execute(request)?;
+/// ```
+pub struct ReplaceInFilesUseCase<F: FileRepository, I: IndexRepository, E: EventPublisher> {
+    file_repo: F,
+    index_repo: I,
+    event_publisher: E,
+}
+
+/// Byte range of a match and the text replacing it
+struct Replacement {
+    start: usize,
+    end: usize,
+    text: String,
+}
+
+impl<F: FileRepository, I: IndexRepository, E: EventPublisher> ReplaceInFilesUseCase<F, I, E> {
+    /// Create a new replace in files use case
+    pub fn new(file_repo: F, index_repo: I, event_publisher: E) -> Self {
+        ReplaceInFilesUseCase {
+            file_repo,
+            index_repo,
+            event_publisher,
+        }
+    }
+
+    /// Execute the replace operation
+    pub fn execute(&self, request: ReplaceInFilesRequest) -> AppResult<ReplaceInFilesResponse> {
+        // 1. Create domain search query and matcher
+        let query = SearchQuery::new(
+            &request.pattern,
+            request.case_sensitive,
+            request.whole_word,
+            request.is_regex,
+        ).map_err(|e| AppError::Validation {
+            message: e.to_string()
+        })?;
+        let regex = build_query_regex(&query)?;
+
+        // 2. Find candidate files via repository
+        let mut candidates: Vec<FilePath> = self.index_repo.search(&query)?
+            .into_iter()
+            .map(|result| result.file_path().clone())
+            .collect();
+        if let Some(ref filter) = request.path_filter {
+            candidates.retain(|path| path.as_path().to_string_lossy().contains(filter));
+        }
+
+        // 3. Build previews for every non-excluded file before writing anything
+        let mut excluded_files = Vec::new();
+        let mut pending = Vec::new();
+        for file_path in candidates {
+            let path_str = file_path.as_path().to_string_lossy().to_string();
+            if request.excluded_files.contains(&path_str) {
+                excluded_files.push(path_str);
+                continue;
+            }
+
+            let content = self.file_repo.read(&file_path)?;
+            let replacements = Self::find_replacements(&regex, &query, &request.replacement, &content);
+            if replacements.is_empty() {
+                continue;
+            }
+
+            let new_content = Self::apply_replacements(&content, &replacements);
+            let preview = FileReplacementPreview {
+                file_path: path_str,
+                replacements: replacements.len(),
+                hunks: Self::build_hunks(&content, &replacements),
+                applied: false,
+            };
+            pending.push((file_path, new_content, preview));
+        }
+
+        // 4. Write if not dry run
+        let mut files = Vec::with_capacity(pending.len());
+        for (file_path, new_content, mut preview) in pending {
+            if !request.dry_run {
+                self.file_repo.write(&file_path, &new_content)?;
+                preview.applied = true;
+            }
+
+            self.event_publisher.publish(&DomainEvent::FileEditExecuted {
+                file_path: preview.file_path.clone(),
+                pattern: request.pattern.clone(),
+                replacement: request.replacement.clone(),
+                matches_replaced: preview.replacements,
+                was_dry_run: request.dry_run,
+            });
+            files.push(preview);
+        }
+
+        let total_replacements = files.iter().map(|f| f.replacements).sum();
+
+        Ok(ReplaceInFilesResponse {
+            files,
+            total_replacements,
+            excluded_files,
+            was_dry_run: request.dry_run,
+        })
+    }
+
+    fn find_replacements(regex: &Regex, query: &SearchQuery, template: &str, content: &str) -> Vec<Replacement> {
+        regex.captures_iter(content)
+            .map(|caps| {
+                let whole = caps.get(0).expect("capture group 0 always participates");
+                let text = if query.is_regex() {
+                    let mut expanded = String::new();
+                    caps.expand(template, &mut expanded);
+                    expanded
+                } else {
+                    template.to_string()
+                };
+                Replacement { start: whole.start(), end: whole.end(), text }
+            })
+            .collect()
+    }
+
+    fn apply_replacements(content: &str, replacements: &[Replacement]) -> String {
+        let mut result = String::with_capacity(content.len());
+        let mut last = 0;
+        for replacement in replacements {
+            result.push_str(&content[last..replacement.start]);
+            result.push_str(&replacement.text);
+            last = replacement.end;
+        }
+        result.push_str(&content[last..]);
+        result
+    }
+
+    /// Group replacements by the lines they touch and render each group as a hunk
+    fn build_hunks(content: &str, replacements: &[Replacement]) -> Vec<ReplacementHunk> {
+        let line_starts: Vec<usize> = std::iter::once(0)
+            .chain(content.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        let line_of = |offset: usize| line_starts.partition_point(|&start| start <= offset) - 1;
+        let line_end = |line: usize| {
+            line_starts.get(line + 1).map(|&next| next - 1).unwrap_or(content.len())
+        };
+
+        // (first line, last line, replacements) per group of overlapping lines
+        let mut groups: Vec<(usize, usize, Vec<&Replacement>)> = Vec::new();
+        for replacement in replacements {
+            let first = line_of(replacement.start);
+            let last = line_of(replacement.end.saturating_sub(1).max(replacement.start));
+            match groups.last_mut() {
+                Some((_, group_last, members)) if first <= *group_last => {
+                    *group_last = (*group_last).max(last);
+                    members.push(replacement);
+                }
+                _ => groups.push((first, last, vec![replacement])),
+            }
+        }
+
+        let mut line_delta: isize = 0;
+        groups.into_iter()
+            .map(|(first, last, members)| {
+                let start = line_starts[first];
+                let end = line_end(last);
+                let old_text = &content[start..end];
+
+                let mut new_text = String::new();
+                let mut cursor = start;
+                for replacement in members {
+                    new_text.push_str(&content[cursor..replacement.start]);
+                    new_text.push_str(&replacement.text);
+                    cursor = replacement.end.max(cursor);
+                }
+                new_text.push_str(&content[cursor.min(end)..end]);
+
+                let old_lines: Vec<String> = old_text.split('\n').map(str::to_string).collect();
+                let new_lines: Vec<String> = new_text.split('\n').map(str::to_string).collect();
+                let hunk = ReplacementHunk {
+                    old_start: first + 1,
+                    new_start: (first as isize + 1 + line_delta) as usize,
+                    old_lines,
+                    new_lines,
+                };
+                line_delta += hunk.new_lines.len() as isize - hunk.old_lines.len() as isize;
+                hunk
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::FileIndexEntry;
+    use crate::domain::SearchResult;
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    // Test doubles
+    struct TestFileRepo {
+        files: RefCell<HashMap<String, String>>,
+    }
+
+    impl TestFileRepo {
+        fn get_content(&self, path: &str) -> String {
+            self.files.borrow()[path].clone()
+        }
+    }
+
+    impl FileRepository for TestFileRepo {
+        fn read(&self, path: &FilePath) -> AppResult<String> {
+            let path_str = path.as_path().to_string_lossy().to_string();
+            self.files.borrow().get(&path_str).cloned()
+                .ok_or_else(|| AppError::Io {
+                    operation: crate::application::IoOperation::Read,
+                    path: path_str,
+                    source: std::io::Error::new(std::io::ErrorKind::NotFound, "not found"),
+                })
+        }
+
+        fn write(&self, path: &FilePath, content: &str) -> AppResult<()> {
+            let path_str = path.as_path().to_string_lossy().to_string();
+            self.files.borrow_mut().insert(path_str, content.to_string());
+            Ok(())
+        }
+
+        fn exists(&self, path: &FilePath) -> bool {
+            self.files.borrow().contains_key(path.as_path().to_string_lossy().as_ref())
+        }
+
+        fn delete(&self, _path: &FilePath) -> AppResult<()> { Ok(()) }
+        fn ensure_parent_dirs(&self, _path: &FilePath) -> AppResult<()> { Ok(()) }
+    }
+
+    /// Returns every known file as a candidate
+    struct TestIndexRepo {
+        paths: Vec<String>,
+    }
+
+    impl IndexRepository for TestIndexRepo {
+        fn get_metadata(&self, _path: &FilePath) -> Option<FileIndexEntry> { None }
+        fn update_metadata(&self, _entry: FileIndexEntry) -> AppResult<()> { Ok(()) }
+        fn remove_metadata(&self, _path: &FilePath) -> AppResult<()> { Ok(()) }
+
+        fn search(&self, _query: &SearchQuery) -> AppResult<Vec<SearchResult>> {
+            Ok(self.paths.iter()
+                .map(|p| SearchResult::new(FilePath::new(p).unwrap(), Vec::new()))
+                .collect())
+        }
+    }
+
+    struct TestEventPublisher {
+        events: RefCell<Vec<DomainEvent>>,
+    }
+
+    impl EventPublisher for TestEventPublisher {
+        fn publish(&self, event: &DomainEvent) {
+            self.events.borrow_mut().push(event.clone());
+        }
+    }
+
+    fn use_case() -> ReplaceInFilesUseCase<TestFileRepo, TestIndexRepo, TestEventPublisher> {
+        let files = HashMap::from([
+            ("src/a.rs".to_string(), "let old_value = 1;\nlet other = 2;\nprint(old_value, old_count);\n".to_string()),
+            ("src/b.rs".to_string(), "fn old_count() {}\n".to_string()),
+            ("src/c.rs".to_string(), "nothing here\n".to_string()),
+        ]);
+        let mut paths: Vec<String> = files.keys().cloned().collect();
+        paths.sort();
+        ReplaceInFilesUseCase::new(
+            TestFileRepo { files: RefCell::new(files) },
+            TestIndexRepo { paths },
+            TestEventPublisher { events: RefCell::new(Vec::new()) },
+        )
+    }
+
+    #[test]
+    fn test_dry_run_previews_without_writing() {
+        let use_case = use_case();
+        let request = ReplaceInFilesRequest::regex(r"old_(\w+)", "new_${1}");
+
+        let response = use_case.execute(request).unwrap();
+
+        assert!(response.was_dry_run);
+        assert_eq!(response.files.len(), 2);
+        assert_eq!(response.total_replacements, 4);
+        let a = &response.files[0];
+        assert!(!a.applied);
+        assert_eq!(a.hunks.len(), 2);
+        assert_eq!(a.hunks[1].old_start, 3);
+        assert_eq!(a.hunks[1].new_lines, vec!["print(new_value, new_count);".to_string()]);
+        assert!(a.diff().contains("-let old_value = 1;\n+let new_value = 1;\n"));
+        assert!(use_case.file_repo.get_content("src/a.rs").contains("old_value"));
+        assert_eq!(use_case.event_publisher.events.borrow().len(), 2);
+    }
+
+    #[test]
+    fn test_apply_with_exclusion() {
+        let use_case = use_case();
+        let request = ReplaceInFilesRequest::literal("old_count", "$count")
+            .exclude("src/b.rs")
+            .apply();
+
+        let response = use_case.execute(request).unwrap();
+
+        assert_eq!(response.files.len(), 1);
+        assert!(response.files[0].applied);
+        assert_eq!(response.excluded_files, vec!["src/b.rs".to_string()]);
+        assert!(use_case.file_repo.get_content("src/a.rs").contains("print(old_value, $count);"));
+        assert_eq!(use_case.file_repo.get_content("src/b.rs"), "fn old_count() {}\n");
+    }
+
+    #[test]
+    fn test_multiline_replacement_shifts_new_start() {
+        let use_case = use_case();
+        let request = ReplaceInFilesRequest::literal("let", "let\n   ").apply();
+
+        let response = use_case.execute(request).unwrap();
+
+        let hunks = &response.files[0].hunks;
+        assert_eq!(hunks[0].old_start, 1);
+        assert_eq!(hunks[0].new_lines.len(), 2);
+        assert_eq!(hunks[1].old_start, 2);
+        assert_eq!(hunks[1].new_start, 3);
+    }
+
+    #[test]
+    fn test_whole_word_case_insensitive() {
+        let use_case = use_case();
+        let mut request = ReplaceInFilesRequest::literal("OLD_VALUE", "renamed");
+        request.case_sensitive = false;
+        request.whole_word = true;
+
+        let response = use_case.execute(request).unwrap();
+
+        assert_eq!(response.total_replacements, 2);
+    }
+
+    #[test]
+    fn test_invalid_regex() {
+        let use_case = use_case();
+        let request = ReplaceInFilesRequest::regex("(unclosed", "x");
+
+        assert!(matches!(use_case.execute(request), Err(AppError::Validation { .. })));
+    }
+}
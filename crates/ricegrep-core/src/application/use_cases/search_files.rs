@@ -1,8 +1,11 @@
 //! Search Files Use Case
 //!
-//! Orchestrates search operations across indexed files.
+//! Orchestrates search operations across indexed files, either collecting all
+//! results or streaming them to a callback as they are found.
 
-use crate::application::{AppResult, IndexRepository, EventPublisher};
+use std::ops::ControlFlow;
+
+use crate::application::{AppResult, IndexRepository, EventPublisher, SearchCancellation};
 use crate::domain::{SearchQuery, SearchResult, DomainEvent};
 
 /// Request for searching files
@@ -59,6 +62,19 @@ pub struct SearchFilesResponse {
     pub truncated: bool,
 }
 
+/// Summary of a streaming search, returned once the stream ends
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchStreamSummary {
+    /// Results (files with matches) delivered to the callback
+    pub results_delivered: usize,
+    /// Total matches delivered
+    pub total_matches: usize,
+    /// Whether the stream stopped at `max_results` with more results pending
+    pub truncated: bool,
+    /// Whether the stream stopped because it was cancelled
+    pub cancelled: bool,
+}
+
 /// Use case for searching files
 ///
 /// # Example
@@ -136,6 +152,59 @@ impl<I: IndexRepository, E: EventPublisher> SearchFilesUseCase<I, E> {
             truncated,
         })
     }
+
+    /// Execute the search, streaming each result to `on_result` as it is found
+    ///
+    /// The stream ends when the index is exhausted, after `max_results`
+    /// results (the summary is then marked truncated if another result was
+    /// pending) or once `cancellation` is cancelled.
+    pub fn execute_streaming<C>(
+        &self,
+        request: SearchFilesRequest,
+        cancellation: &SearchCancellation,
+        mut on_result: C,
+    ) -> AppResult<SearchStreamSummary>
+    where
+        C: FnMut(&SearchResult),
+    {
+        let query = SearchQuery::new(
+            &request.pattern,
+            request.case_sensitive,
+            request.whole_word,
+            request.is_regex,
+        ).map_err(|e| crate::application::AppError::Validation {
+            message: e.to_string()
+        })?;
+
+        let mut summary = SearchStreamSummary::default();
+        self.index_repo.search_streaming(&query, cancellation, &mut |result| {
+            if cancellation.is_cancelled() {
+                summary.cancelled = true;
+                return ControlFlow::Break(());
+            }
+            if let Some(ref filter) = request.path_filter {
+                if !result.file_path().as_path().to_string_lossy().contains(filter.as_str()) {
+                    return ControlFlow::Continue(());
+                }
+            }
+            if request.max_results.is_some_and(|max| summary.results_delivered >= max) {
+                summary.truncated = true;
+                return ControlFlow::Break(());
+            }
+
+            self.event_publisher.publish(&DomainEvent::SearchExecuted {
+                file_path: result.file_path().as_path().to_string_lossy().to_string(),
+                matches_found: result.matches().len(),
+            });
+            summary.results_delivered += 1;
+            summary.total_matches += result.matches().len();
+            on_result(&result);
+            ControlFlow::Continue(())
+        })?;
+
+        summary.cancelled |= cancellation.is_cancelled() && !summary.truncated;
+        Ok(summary)
+    }
 }
 
 #[cfg(test)]
@@ -182,6 +251,26 @@ mod tests {
         }
     }
 
+    /// Yields one single-match result per path
+    struct StreamingIndexRepo {
+        paths: Vec<&'static str>,
+    }
+
+    impl IndexRepository for StreamingIndexRepo {
+        fn get_metadata(&self, _path: &FilePath) -> Option<FileIndexEntry> { None }
+        fn update_metadata(&self, _entry: FileIndexEntry) -> AppResult<()> { Ok(()) }
+        fn remove_metadata(&self, _path: &FilePath) -> AppResult<()> { Ok(()) }
+
+        fn search(&self, query: &SearchQuery) -> AppResult<Vec<SearchResult>> {
+            Ok(self.paths.iter()
+                .map(|p| SearchResult::new(
+                    FilePath::new(p).unwrap(),
+                    vec![crate::domain::SearchMatch::new(1, 0, query.query().to_string())],
+                ))
+                .collect())
+        }
+    }
+
     struct TestEventPublisher {
         events: RefCell<Vec<DomainEvent>>,
     }
@@ -260,4 +349,62 @@ mod tests {
         // No events for empty results
         assert_eq!(use_case.event_publisher.event_count(), 0);
     }
+
+    fn streaming_use_case() -> SearchFilesUseCase<StreamingIndexRepo, TestEventPublisher> {
+        let index_repo = StreamingIndexRepo { paths: vec!["src/a.rs", "src/b.rs", "tests/c.rs"] };
+        SearchFilesUseCase::new(index_repo, TestEventPublisher::new())
+    }
+
+    #[test]
+    fn test_streaming_delivers_results_in_order() {
+        let use_case = streaming_use_case();
+        let mut seen = Vec::new();
+
+        let summary = use_case
+            .execute_streaming(SearchFilesRequest::literal("TODO"), &SearchCancellation::new(), |result| {
+                seen.push(result.file_path().as_path().to_string_lossy().to_string());
+            })
+            .unwrap();
+
+        assert_eq!(seen, vec!["src/a.rs", "src/b.rs", "tests/c.rs"]);
+        assert_eq!(summary.results_delivered, 3);
+        assert_eq!(summary.total_matches, 3);
+        assert!(!summary.truncated && !summary.cancelled);
+        assert_eq!(use_case.event_publisher.event_count(), 3);
+    }
+
+    #[test]
+    fn test_streaming_cap_sets_truncated() {
+        let use_case = streaming_use_case();
+        let mut request = SearchFilesRequest::literal("TODO");
+        request.max_results = Some(2);
+        request.path_filter = Some("src/".to_string());
+
+        let summary = use_case.execute_streaming(request.clone(), &SearchCancellation::new(), |_| {}).unwrap();
+        assert_eq!(summary.results_delivered, 2);
+        assert!(!summary.truncated);
+
+        request.path_filter = None;
+        let summary = use_case.execute_streaming(request, &SearchCancellation::new(), |_| {}).unwrap();
+        assert_eq!(summary.results_delivered, 2);
+        assert!(summary.truncated);
+    }
+
+    #[test]
+    fn test_streaming_cancellation_stops_early() {
+        let use_case = streaming_use_case();
+        let cancellation = SearchCancellation::new();
+        let mut delivered = 0;
+
+        let summary = use_case
+            .execute_streaming(SearchFilesRequest::literal("TODO"), &cancellation.clone(), |_| {
+                delivered += 1;
+                cancellation.cancel();
+            })
+            .unwrap();
+
+        assert_eq!(delivered, 1);
+        assert!(summary.cancelled);
+        assert!(!summary.truncated);
+    }
 }
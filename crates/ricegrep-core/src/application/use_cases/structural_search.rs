@@ -0,0 +1,271 @@
+//! Structural Search Use Case
+//!
+//! Orchestrates AST-aware searches: resolves each file's language from its
+//! extension, reads it and delegates matching to a `StructuralMatcher`.
+
+use crate::application::{AppResult, AppError, FileRepository, StructuralMatcher, EventPublisher};
+use crate::domain::{FilePath, SearchResult, SourceLanguage, StructuralPattern, DomainEvent};
+
+/// Request for a structural search
+#[derive(Debug, Clone)]
+pub struct StructuralSearchRequest {
+    /// Code template (e.g. `fn $NAME($$$ARGS) { $$$ }`) or tree-sitter query
+    pub pattern: String,
+    /// Whether pattern is a raw tree-sitter query
+    pub is_query: bool,
+    /// Files to search
+    pub paths: Vec<String>,
+    /// Force a language instead of routing by file extension
+    pub language: Option<SourceLanguage>,
+    /// Maximum results (files with matches) to return
+    pub max_results: Option<usize>,
+}
+
+impl StructuralSearchRequest {
+    /// Create a code template search request
+    pub fn template(pattern: impl Into<String>, paths: Vec<String>) -> Self {
+        StructuralSearchRequest {
+            pattern: pattern.into(),
+            is_query: false,
+            paths,
+            language: None,
+            max_results: None,
+        }
+    }
+
+    /// Create a tree-sitter query search request
+    pub fn query(query: impl Into<String>, paths: Vec<String>) -> Self {
+        StructuralSearchRequest {
+            pattern: query.into(),
+            is_query: true,
+            paths,
+            language: None,
+            max_results: None,
+        }
+    }
+}
+
+/// Response from a structural search
+#[derive(Debug, Clone)]
+pub struct StructuralSearchResponse {
+    /// Search results (only files with matches)
+    pub results: Vec<SearchResult>,
+    /// Total matches found
+    pub total_matches: usize,
+    /// Whether results were truncated
+    pub truncated: bool,
+    /// Files skipped because their language is unknown or unsupported
+    pub skipped_files: Vec<String>,
+}
+
+/// Use case for structural (AST-aware) search
+///
+/// # Example
+/// ```ignore
+/// let use_case = StructuralSearchUseCase::new(file_repo, matcher, event_publisher);
+/// let request = StructuralSearchRequest::template("fn $NAME($$$ARGS) { $$$ }", paths);
+/// let response = use_case.execute(request)?;
+/// ```
+pub struct StructuralSearchUseCase<F: FileRepository, M: StructuralMatcher, E: EventPublisher> {
+    file_repo: F,
+    matcher: M,
+    event_publisher: E,
+}
+
+impl<F: FileRepository, M: StructuralMatcher, E: EventPublisher> StructuralSearchUseCase<F, M, E> {
+    /// Create a new structural search use case
+    pub fn new(file_repo: F, matcher: M, event_publisher: E) -> Self {
+        StructuralSearchUseCase {
+            file_repo,
+            matcher,
+            event_publisher,
+        }
+    }
+
+    /// Execute the structural search
+    pub fn execute(&self, request: StructuralSearchRequest) -> AppResult<StructuralSearchResponse> {
+        // 1. Create domain pattern
+        let pattern = if request.is_query {
+            StructuralPattern::query(&request.pattern)
+        } else {
+            StructuralPattern::template(&request.pattern)
+        }
+        .map_err(|e| AppError::Validation {
+            message: e.to_string(),
+        })?;
+
+        let mut results = Vec::new();
+        let mut skipped_files = Vec::new();
+        let mut truncated = false;
+
+        for path in &request.paths {
+            // 2. Route by language
+            let file_path = FilePath::new(path)?;
+            let language = match request.language.or_else(|| SourceLanguage::from_path(&file_path)) {
+                Some(language) if self.matcher.supports(language) => language,
+                _ => {
+                    skipped_files.push(path.clone());
+                    continue;
+                }
+            };
+
+            // 3. Match file content
+            let content = self.file_repo.read(&file_path)?;
+            let matches = self.matcher.find_matches(language, &pattern, &content)?;
+            if matches.is_empty() {
+                continue;
+            }
+
+            // 4. Apply max results limit
+            if request.max_results.is_some_and(|max| results.len() >= max) {
+                truncated = true;
+                break;
+            }
+            results.push(SearchResult::new(file_path, matches));
+        }
+
+        // 5. Calculate total matches
+        let total_matches: usize = results.iter()
+            .map(|r| r.matches().len())
+            .sum();
+
+        // 6. Publish search events
+        for result in &results {
+            self.event_publisher.publish(&DomainEvent::SearchExecuted {
+                file_path: result.file_path().as_path().to_string_lossy().to_string(),
+                matches_found: result.matches().len(),
+            });
+        }
+
+        Ok(StructuralSearchResponse {
+            results,
+            total_matches,
+            truncated,
+            skipped_files,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::SearchMatch;
+    use std::cell::RefCell;
+    use std::collections::{BTreeMap, HashMap};
+
+    // Test doubles
+    struct TestFileRepo {
+        files: HashMap<String, String>,
+    }
+
+    impl FileRepository for TestFileRepo {
+        fn read(&self, path: &FilePath) -> AppResult<String> {
+            let key = path.as_path().to_string_lossy().to_string();
+            self.files.get(&key).cloned().ok_or_else(|| AppError::Validation {
+                message: format!("missing {}", key),
+            })
+        }
+        fn write(&self, _path: &FilePath, _content: &str) -> AppResult<()> { Ok(()) }
+        fn exists(&self, path: &FilePath) -> bool {
+            self.files.contains_key(path.as_path().to_string_lossy().as_ref())
+        }
+        fn delete(&self, _path: &FilePath) -> AppResult<()> { Ok(()) }
+        fn ensure_parent_dirs(&self, _path: &FilePath) -> AppResult<()> { Ok(()) }
+    }
+
+    /// Matches lines starting with the pattern's first word, capturing the next word as NAME
+    struct TestMatcher;
+
+    impl StructuralMatcher for TestMatcher {
+        fn supports(&self, language: SourceLanguage) -> bool {
+            language == SourceLanguage::Rust
+        }
+
+        fn find_matches(&self, _language: SourceLanguage, pattern: &StructuralPattern, source: &str) -> AppResult<Vec<SearchMatch>> {
+            let keyword = pattern.pattern().split_whitespace().next().unwrap_or_default();
+            Ok(source.lines().enumerate()
+                .filter(|(_, line)| line.starts_with(keyword))
+                .map(|(i, line)| {
+                    let name = line.split(|c: char| !c.is_alphanumeric()).nth(1).unwrap_or_default();
+                    let captures = BTreeMap::from([("NAME".to_string(), name.to_string())]);
+                    SearchMatch::new(i + 1, 0, line.to_string()).with_captures(captures)
+                })
+                .collect())
+        }
+    }
+
+    struct TestEventPublisher {
+        events: RefCell<Vec<DomainEvent>>,
+    }
+
+    impl EventPublisher for TestEventPublisher {
+        fn publish(&self, event: &DomainEvent) {
+            self.events.borrow_mut().push(event.clone());
+        }
+    }
+
+    fn use_case() -> StructuralSearchUseCase<TestFileRepo, TestMatcher, TestEventPublisher> {
+        let files = HashMap::from([
+            ("src/a.rs".to_string(), "fn alpha() {}\nlet x = 1;\nfn beta() {}".to_string()),
+            ("src/b.rs".to_string(), "fn gamma() {}".to_string()),
+            ("src/c.rs".to_string(), "struct Empty;".to_string()),
+            ("script.py".to_string(), "def fn(): pass".to_string()),
+        ]);
+        StructuralSearchUseCase::new(
+            TestFileRepo { files },
+            TestMatcher,
+            TestEventPublisher { events: RefCell::new(Vec::new()) },
+        )
+    }
+
+    fn paths() -> Vec<String> {
+        ["src/a.rs", "src/b.rs", "src/c.rs", "script.py", "README.md"]
+            .iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn test_structural_search_routes_by_language() {
+        let use_case = use_case();
+        let request = StructuralSearchRequest::template("fn $NAME($$$ARGS) { $$$ }", paths());
+
+        let response = use_case.execute(request).unwrap();
+
+        assert_eq!(response.results.len(), 2);
+        assert_eq!(response.total_matches, 3);
+        assert_eq!(response.skipped_files, vec!["script.py".to_string(), "README.md".to_string()]);
+        assert_eq!(response.results[0].matches()[1].capture("NAME"), Some("beta"));
+        assert_eq!(use_case.event_publisher.events.borrow().len(), 2);
+    }
+
+    #[test]
+    fn test_structural_search_language_override() {
+        let use_case = use_case();
+        let mut request = StructuralSearchRequest::template("def $NAME", vec!["script.py".to_string()]);
+        request.language = Some(SourceLanguage::Rust);
+
+        let response = use_case.execute(request).unwrap();
+
+        assert_eq!(response.total_matches, 1);
+        assert!(response.skipped_files.is_empty());
+    }
+
+    #[test]
+    fn test_structural_search_max_results() {
+        let use_case = use_case();
+        let mut request = StructuralSearchRequest::template("fn $NAME", paths());
+        request.max_results = Some(1);
+
+        let response = use_case.execute(request).unwrap();
+
+        assert_eq!(response.results.len(), 1);
+        assert!(response.truncated);
+    }
+
+    #[test]
+    fn test_structural_search_invalid_pattern() {
+        let use_case = use_case();
+        let request = StructuralSearchRequest::query("(function_item", paths());
+
+        assert!(matches!(use_case.execute(request), Err(AppError::Validation { .. })));
+    }
+}
@@ -3,6 +3,8 @@
 //! Aggregates represent clusters of domain objects treated as a single unit.
 //! They enforce business invariants and emit domain events when they change.
 
+use std::collections::BTreeMap;
+
 use crate::domain::value_objects::{FilePath, EditPattern};
 use crate::domain::events::DomainEvent;
 use crate::domain::errors::{DomainError, DomainResult};
@@ -69,20 +71,30 @@ pub struct SearchResult {
 }
 
 /// A single search match
+///
+/// Structural matches additionally carry the text bound to each captured
+/// metavariable (or query capture), keyed by name.
 #[derive(Debug, Clone)]
 pub struct SearchMatch {
     line_number: usize,
     column_start: usize,
     matched_text: String,
+    captures: BTreeMap<String, String>,
 }
 
 impl SearchMatch {
     pub fn new(line_number: usize, column_start: usize, matched_text: String) -> Self {
-        SearchMatch { line_number, column_start, matched_text }
+        SearchMatch { line_number, column_start, matched_text, captures: BTreeMap::new() }
+    }
+    pub fn with_captures(mut self, captures: BTreeMap<String, String>) -> Self {
+        self.captures = captures;
+        self
     }
     pub fn line_number(&self) -> usize { self.line_number }
     pub fn column_start(&self) -> usize { self.column_start }
     pub fn matched_text(&self) -> &str { &self.matched_text }
+    pub fn captures(&self) -> &BTreeMap<String, String> { &self.captures }
+    pub fn capture(&self, name: &str) -> Option<&str> { self.captures.get(name).map(String::as_str) }
 }
 
 impl SearchResult {
@@ -124,4 +136,12 @@ mod tests {
         let result = SearchResult::new(file_path, vec![SearchMatch::new(1, 0, "test".to_string())]);
         assert_eq!(result.total_matches(), 1);
     }
+
+    #[test]
+    fn test_search_match_captures() {
+        let captures = BTreeMap::from([("NAME".to_string(), "main".to_string())]);
+        let search_match = SearchMatch::new(1, 0, "fn main() {}".to_string()).with_captures(captures);
+        assert_eq!(search_match.capture("NAME"), Some("main"));
+        assert_eq!(search_match.capture("ARGS"), None);
+    }
 }
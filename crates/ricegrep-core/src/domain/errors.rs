@@ -14,6 +14,8 @@ pub enum DomainError {
     InvalidEditPattern(String),
     /// Search query validation errors
     InvalidSearchQuery(String),
+    /// Structural pattern validation errors
+    InvalidStructuralPattern(String),
     /// File edit business rule violations
     InvalidFileEdit(String),
     /// General validation error
@@ -26,6 +28,7 @@ impl fmt::Display for DomainError {
             DomainError::InvalidFilePath(msg) => write!(f, "Invalid file path: {}", msg),
             DomainError::InvalidEditPattern(msg) => write!(f, "Invalid edit pattern: {}", msg),
             DomainError::InvalidSearchQuery(msg) => write!(f, "Invalid search query: {}", msg),
+            DomainError::InvalidStructuralPattern(msg) => write!(f, "Invalid structural pattern: {}", msg),
             DomainError::InvalidFileEdit(msg) => write!(f, "Invalid file edit: {}", msg),
             DomainError::ValidationError(msg) => write!(f, "Validation error: {}", msg),
         }
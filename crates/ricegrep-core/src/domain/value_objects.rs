@@ -119,6 +119,202 @@ impl SearchQuery {
     pub fn regex(query: &str) -> DomainResult<Self> { Self::new(query, false, false, true) }
 }
 
+/// Source language used to route structural searches to a grammar
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SourceLanguage {
+    Rust,
+    Python,
+    JavaScript,
+    TypeScript,
+    Tsx,
+    Java,
+    Go,
+    C,
+    Cpp,
+}
+
+impl SourceLanguage {
+    /// Resolve a language from a file extension (case-insensitive)
+    pub fn from_extension(ext: &str) -> Option<Self> {
+        match ext.to_ascii_lowercase().as_str() {
+            "rs" => Some(SourceLanguage::Rust),
+            "py" | "pyi" => Some(SourceLanguage::Python),
+            "js" | "jsx" | "mjs" | "cjs" => Some(SourceLanguage::JavaScript),
+            "ts" | "mts" | "cts" => Some(SourceLanguage::TypeScript),
+            "tsx" => Some(SourceLanguage::Tsx),
+            "java" => Some(SourceLanguage::Java),
+            "go" => Some(SourceLanguage::Go),
+            "c" | "h" => Some(SourceLanguage::C),
+            "cc" | "cpp" | "cxx" | "hh" | "hpp" | "hxx" => Some(SourceLanguage::Cpp),
+            _ => None,
+        }
+    }
+
+    /// Resolve the language of a file from its extension
+    pub fn from_path(path: &FilePath) -> Option<Self> {
+        path.extension().and_then(Self::from_extension)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SourceLanguage::Rust => "rust",
+            SourceLanguage::Python => "python",
+            SourceLanguage::JavaScript => "javascript",
+            SourceLanguage::TypeScript => "typescript",
+            SourceLanguage::Tsx => "tsx",
+            SourceLanguage::Java => "java",
+            SourceLanguage::Go => "go",
+            SourceLanguage::C => "c",
+            SourceLanguage::Cpp => "cpp",
+        }
+    }
+}
+
+impl std::fmt::Display for SourceLanguage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// A metavariable in a structural pattern
+///
+/// `$NAME` matches exactly one syntax node, `$$$NAME` matches zero or more
+/// sibling nodes. Metavariables named `_` (or starting with `_`) and the bare
+/// `$$$` wildcard match without being captured.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Metavariable {
+    name: String,
+    multi: bool,
+    placeholder: String,
+}
+
+impl Metavariable {
+    pub fn name(&self) -> &str { &self.name }
+    pub fn is_multi(&self) -> bool { self.multi }
+    /// Identifier substituted for the metavariable before the pattern is parsed
+    pub fn placeholder(&self) -> &str { &self.placeholder }
+    pub fn is_captured(&self) -> bool { !self.name.is_empty() && !self.name.starts_with('_') }
+}
+
+/// Whether a structural pattern is a code template or a raw tree-sitter query
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StructuralPatternKind {
+    /// Code with metavariables, e.g. `fn $NAME($$$ARGS) { $$$ }`
+    Template,
+    /// Tree-sitter S-expression query; `@name` captures become match captures
+    Query,
+}
+
+/// A validated structural (AST-aware) search pattern
+///
+/// Templates are rewritten into parseable code by replacing every metavariable
+/// with a placeholder identifier; adapters parse that code with the target
+/// grammar and match the resulting tree against the source tree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructuralPattern {
+    pattern: String,
+    kind: StructuralPatternKind,
+    metavariables: Vec<Metavariable>,
+    template_source: String,
+}
+
+impl StructuralPattern {
+    const PLACEHOLDER_PREFIX: &'static str = "__rg_mv_";
+
+    /// Create a code template pattern
+    pub fn template(pattern: &str) -> DomainResult<Self> {
+        if pattern.trim().is_empty() {
+            return Err(DomainError::InvalidStructuralPattern("Pattern cannot be empty".to_string()));
+        }
+
+        let mut metavariables: Vec<Metavariable> = Vec::new();
+        let mut template_source = String::with_capacity(pattern.len());
+        let chars: Vec<char> = pattern.chars().collect();
+        let mut i = 0;
+
+        while i < chars.len() {
+            if chars[i] != '$' {
+                template_source.push(chars[i]);
+                i += 1;
+                continue;
+            }
+
+            let multi = chars[i..].starts_with(&['$', '$', '$']);
+            let name_start = if multi { i + 3 } else { i + 1 };
+            let mut name_end = name_start;
+            while name_end < chars.len() && Self::is_name_char(chars[name_end], name_end == name_start) {
+                name_end += 1;
+            }
+            let name: String = chars[name_start..name_end].iter().collect();
+
+            if name.is_empty() && !multi {
+                // A lone `$` is literal source text (e.g. JavaScript identifiers)
+                template_source.push('$');
+                i += 1;
+                continue;
+            }
+
+            let existing = metavariables.iter().find(|m| !m.name.is_empty() && m.name == name);
+            let placeholder = match existing {
+                Some(m) if m.multi != multi => {
+                    return Err(DomainError::InvalidStructuralPattern(format!(
+                        "Metavariable '{}' used both as single and multi match", name
+                    )));
+                }
+                Some(m) => m.placeholder.clone(),
+                None => {
+                    let placeholder = format!("{}{}", Self::PLACEHOLDER_PREFIX, metavariables.len());
+                    metavariables.push(Metavariable { name, multi, placeholder: placeholder.clone() });
+                    placeholder
+                }
+            };
+            template_source.push_str(&placeholder);
+            i = name_end;
+        }
+
+        Ok(StructuralPattern {
+            pattern: pattern.to_string(),
+            kind: StructuralPatternKind::Template,
+            metavariables,
+            template_source,
+        })
+    }
+
+    /// Create a raw tree-sitter query pattern
+    pub fn query(query: &str) -> DomainResult<Self> {
+        if query.trim().is_empty() {
+            return Err(DomainError::InvalidStructuralPattern("Query cannot be empty".to_string()));
+        }
+        if EditPattern::is_valid_regex_basic(query).is_err() {
+            return Err(DomainError::InvalidStructuralPattern(format!("Unbalanced query: {}", query)));
+        }
+        Ok(StructuralPattern {
+            pattern: query.to_string(),
+            kind: StructuralPatternKind::Query,
+            metavariables: Vec::new(),
+            template_source: query.to_string(),
+        })
+    }
+
+    pub fn pattern(&self) -> &str { &self.pattern }
+    pub fn kind(&self) -> StructuralPatternKind { self.kind }
+    pub fn metavariables(&self) -> &[Metavariable] { &self.metavariables }
+    /// Pattern source with metavariables replaced by placeholder identifiers
+    pub fn template_source(&self) -> &str { &self.template_source }
+
+    /// Look up the metavariable a placeholder identifier stands for
+    pub fn metavariable_for(&self, placeholder: &str) -> Option<&Metavariable> {
+        if !placeholder.starts_with(Self::PLACEHOLDER_PREFIX) {
+            return None;
+        }
+        self.metavariables.iter().find(|m| m.placeholder == placeholder)
+    }
+
+    fn is_name_char(ch: char, first: bool) -> bool {
+        ch.is_ascii_uppercase() || ch == '_' || (!first && ch.is_ascii_digit())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -145,4 +341,39 @@ mod tests {
         let query = SearchQuery::simple("test").unwrap();
         assert!(!query.is_case_sensitive());
     }
+
+    #[test]
+    fn test_source_language_from_extension() {
+        assert_eq!(SourceLanguage::from_extension("RS"), Some(SourceLanguage::Rust));
+        assert_eq!(SourceLanguage::from_extension("tsx"), Some(SourceLanguage::Tsx));
+        assert_eq!(SourceLanguage::from_extension("md"), None);
+        let path = FilePath::new("src/app.py").unwrap();
+        assert_eq!(SourceLanguage::from_path(&path), Some(SourceLanguage::Python));
+    }
+
+    #[test]
+    fn test_structural_pattern_metavariables() {
+        let pattern = StructuralPattern::template("fn $NAME($$$ARGS) { $$$ }").unwrap();
+        let names: Vec<(&str, bool)> = pattern.metavariables().iter().map(|m| (m.name(), m.is_multi())).collect();
+        assert_eq!(names, vec![("NAME", false), ("ARGS", true), ("", true)]);
+        assert_eq!(pattern.template_source(), "fn __rg_mv_0(__rg_mv_1) { __rg_mv_2 }");
+        assert!(!pattern.metavariables()[2].is_captured());
+        assert_eq!(pattern.metavariable_for("__rg_mv_1").map(|m| m.name()), Some("ARGS"));
+    }
+
+    #[test]
+    fn test_structural_pattern_reuses_placeholder() {
+        let pattern = StructuralPattern::template("$A == $A").unwrap();
+        assert_eq!(pattern.metavariables().len(), 1);
+        assert_eq!(pattern.template_source(), "__rg_mv_0 == __rg_mv_0");
+        assert!(StructuralPattern::template("$A($$$A)").is_err());
+        assert!(StructuralPattern::template("  ").is_err());
+    }
+
+    #[test]
+    fn test_structural_pattern_literal_dollar() {
+        let pattern = StructuralPattern::template("$el.$(x)").unwrap();
+        assert!(pattern.metavariables().is_empty());
+        assert_eq!(pattern.template_source(), "$el.$(x)");
+    }
 }
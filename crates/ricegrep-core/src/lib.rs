@@ -54,7 +54,7 @@ pub mod application;
 // Re-export commonly used types at crate root for convenience
 pub use domain::{
     // Value Objects
-    FilePath, EditPattern, SearchQuery,
+    FilePath, EditPattern, SearchQuery, SourceLanguage, StructuralPattern, StructuralPatternKind, Metavariable,
     // Aggregates
     FileEdit, SearchResult, SearchMatch,
     // Events
@@ -67,10 +67,13 @@ pub use application::{
     // Errors
     AppError, AppResult, IoOperation,
     // Repository Traits (Ports)
-    FileRepository, IndexRepository, EventPublisher, FileIndexEntry,
+    FileRepository, IndexRepository, StructuralMatcher, EventPublisher, FileIndexEntry, SearchCancellation,
+    PathAccessPolicy, FileAccess, GuardedFileRepository, GuardedIndexRepository,
     // Use Cases
     EditFileUseCase, EditFileRequest, EditFileResponse,
-    SearchFilesUseCase, SearchFilesRequest, SearchFilesResponse,
+    ReplaceInFilesUseCase, ReplaceInFilesRequest, ReplaceInFilesResponse, FileReplacementPreview, ReplacementHunk,
+    SearchFilesUseCase, SearchFilesRequest, SearchFilesResponse, SearchStreamSummary,
+    StructuralSearchUseCase, StructuralSearchRequest, StructuralSearchResponse,
     WriteFileUseCase, WriteFileRequest, WriteFileResponse,
     // Services
     AppServices, AppServicesBuilder,
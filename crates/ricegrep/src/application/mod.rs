@@ -12,8 +12,9 @@
 //!
 //! # Re-exported Types
 //! - Errors: `AppError`, `AppResult`, `IoOperation`
-//! - Repository Traits: `FileRepository`, `IndexRepository`, `EventPublisher`, `FileIndexEntry`
-//! - Use Cases: `EditFileUseCase`, `SearchFilesUseCase`, `WriteFileUseCase` + Request/Response types
+//! - Repository Traits: `FileRepository`, `IndexRepository`, `StructuralMatcher`, `EventPublisher`, `FileIndexEntry`
+//! - Path Guards: `PathAccessPolicy`, `FileAccess`, `GuardedFileRepository`, `GuardedIndexRepository`
+//! - Use Cases: `EditFileUseCase`, `ReplaceInFilesUseCase`, `SearchFilesUseCase`, `StructuralSearchUseCase`, `WriteFileUseCase` + Request/Response types
 //! - Services: `AppServices`, `AppServicesBuilder`
 
 // Re-export everything from ricegrep-core's application module
@@ -24,10 +25,13 @@ pub use ricegrep_core::{
     // Errors
     AppError, AppResult, IoOperation,
     // Repository Traits (Ports)
-    FileRepository, IndexRepository, EventPublisher, FileIndexEntry,
+    FileRepository, IndexRepository, StructuralMatcher, EventPublisher, FileIndexEntry, SearchCancellation,
+    PathAccessPolicy, FileAccess, GuardedFileRepository, GuardedIndexRepository,
     // Use Cases
     EditFileUseCase, EditFileRequest, EditFileResponse,
-    SearchFilesUseCase, SearchFilesRequest, SearchFilesResponse,
+    ReplaceInFilesUseCase, ReplaceInFilesRequest, ReplaceInFilesResponse, FileReplacementPreview, ReplacementHunk,
+    SearchFilesUseCase, SearchFilesRequest, SearchFilesResponse, SearchStreamSummary,
+    StructuralSearchUseCase, StructuralSearchRequest, StructuralSearchResponse,
     WriteFileUseCase, WriteFileRequest, WriteFileResponse,
     // Services
     AppServices, AppServicesBuilder,
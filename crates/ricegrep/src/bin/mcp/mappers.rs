@@ -65,6 +65,7 @@ pub fn map_error_to_message(error: &AppError) -> String {
         AppError::Config(msg) => {
             format!("Configuration error: {}", msg)
         }
+        AppError::AccessDenied { reason, .. } => reason.clone(),
     }
 }
 
@@ -11,7 +11,7 @@
 //! ```
 //!
 //! # Re-exported Types
-//! - Value Objects: `FilePath`, `EditPattern`, `SearchQuery`
+//! - Value Objects: `FilePath`, `EditPattern`, `SearchQuery`, `SourceLanguage`, `StructuralPattern`
 //! - Aggregates: `FileEdit`, `SearchResult`, `SearchMatch`
 //! - Events: `DomainEvent`
 //! - Errors: `DomainError`, `DomainResult`
@@ -22,7 +22,7 @@ pub use ricegrep_core::domain::*;
 // Also re-export at module level for backwards compatibility
 pub use ricegrep_core::{
     // Value Objects
-    FilePath, EditPattern, SearchQuery,
+    FilePath, EditPattern, SearchQuery, SourceLanguage, StructuralPattern, StructuralPatternKind, Metavariable,
     // Aggregates  
     FileEdit, SearchResult, SearchMatch,
     // Events
@@ -19,13 +19,22 @@
 //! # Adapters
 //! - `FsFileRepository` - File operations using `std::fs`
 //! - `MetadataIndexRepository` - Index operations using existing `metadata_gating` module
+//! - `TrigramIndexRepository` - Persistent, incremental trigram index with mmap-backed reads
 //! - `TracingEventPublisher` - Event publishing using `tracing` crate
+//! - `TreeSitterStructuralMatcher` - Structural search using tree-sitter grammars
+//! - `SensitivePathAccessPolicy` - Sensitive path denial using `ricecoder-safety`
 
 pub mod file_repository;
 pub mod index_repository;
+pub mod trigram_index;
 pub mod event_publisher;
+pub mod structural_matcher;
+pub mod path_policy;
 
 // Re-export for ergonomic imports
 pub use file_repository::FsFileRepository;
 pub use index_repository::MetadataIndexRepository;
+pub use trigram_index::TrigramIndexRepository;
 pub use event_publisher::TracingEventPublisher;
+pub use structural_matcher::TreeSitterStructuralMatcher;
+pub use path_policy::SensitivePathAccessPolicy;
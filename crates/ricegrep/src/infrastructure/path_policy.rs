@@ -0,0 +1,79 @@
+//! Sensitive Path PathAccessPolicy Implementation
+//!
+//! Implements the `PathAccessPolicy` trait with ricecoder-safety's sensitive
+//! path policy, so ricegrep denies the same paths (`.env`, `~/.ssh`, globs
+//! from `safety.yaml`) as the RiceCoder file tools.
+
+use std::sync::Arc;
+
+use ricecoder_safety::{sensitive_paths, PathAccess, SensitivePathPolicy};
+
+use crate::application::{AppError, AppResult, FileAccess, PathAccessPolicy};
+use crate::domain::FilePath;
+
+/// `PathAccessPolicy` backed by a `SensitivePathPolicy`
+///
+/// Wrap repositories with `GuardedFileRepository` and
+/// `GuardedIndexRepository` to enforce it.
+#[derive(Debug, Clone)]
+pub struct SensitivePathAccessPolicy {
+    /// Fixed policy; `None` follows the process-wide policy
+    policy: Option<Arc<SensitivePathPolicy>>,
+}
+
+impl SensitivePathAccessPolicy {
+    /// Follow the process-wide policy, including overrides the user approved
+    pub fn new() -> Self {
+        SensitivePathAccessPolicy { policy: None }
+    }
+
+    /// Use a specific policy
+    pub fn with_policy(policy: Arc<SensitivePathPolicy>) -> Self {
+        SensitivePathAccessPolicy {
+            policy: Some(policy),
+        }
+    }
+}
+
+impl Default for SensitivePathAccessPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PathAccessPolicy for SensitivePathAccessPolicy {
+    fn check(&self, path: &FilePath, access: FileAccess) -> AppResult<()> {
+        let access = match access {
+            FileAccess::Read => PathAccess::Read,
+            FileAccess::Write => PathAccess::Write,
+            FileAccess::Search => PathAccess::Search,
+        };
+        let policy = self.policy.clone().unwrap_or_else(sensitive_paths::global);
+        policy
+            .check(path.as_path(), access)
+            .map_err(|denied| AppError::AccessDenied {
+                path: denied.path.display().to_string(),
+                reason: denied.to_string(),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_denies_env_files() {
+        let policy =
+            SensitivePathAccessPolicy::with_policy(Arc::new(SensitivePathPolicy::with_defaults()));
+
+        let env = FilePath::new("project/.env").unwrap();
+        assert!(matches!(
+            policy.check(&env, FileAccess::Read),
+            Err(AppError::AccessDenied { .. })
+        ));
+
+        let source = FilePath::new("project/src/main.rs").unwrap();
+        assert!(policy.check(&source, FileAccess::Search).is_ok());
+    }
+}
@@ -0,0 +1,334 @@
+//! Tree-sitter StructuralMatcher Implementation
+//!
+//! Implements the `StructuralMatcher` trait by parsing both the pattern and the
+//! source with the grammar for the file's language.
+//!
+//! Code templates are matched node by node: the pattern tree must have the
+//! same node kinds and token text as the source subtree, except where a
+//! metavariable placeholder stands in. `$NAME` binds exactly one node and
+//! `$$$NAME` binds any run of sibling nodes (including none); a metavariable
+//! used twice must bind identical text both times. Comments are ignored on
+//! both sides. Raw tree-sitter queries are run as-is and their `@captures`
+//! become match captures.
+
+use std::collections::BTreeMap;
+
+use streaming_iterator::StreamingIterator;
+use tree_sitter::{Language, Node, Parser, Query, QueryCursor, Tree};
+
+use crate::application::{AppError, AppResult, StructuralMatcher};
+use crate::domain::{SearchMatch, SourceLanguage, StructuralPattern, StructuralPatternKind};
+
+/// Tree-sitter based implementation of `StructuralMatcher`
+///
+/// # Example
+/// ```ignore
+/// use ricegrep::infrastructure::TreeSitterStructuralMatcher;
+/// use ricegrep::application::StructuralMatcher;
+/// use ricegrep::domain::{SourceLanguage, StructuralPattern};
+///
+/// let matcher = TreeSitterStructuralMatcher::new();
+/// let pattern = StructuralPattern::template("fn $NAME($$$ARGS) { $$$ }")?;
+/// let matches = matcher.find_matches(SourceLanguage::Rust, &pattern, source)?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TreeSitterStructuralMatcher;
+
+type Captures = BTreeMap<String, String>;
+
+impl TreeSitterStructuralMatcher {
+    /// Create a new tree-sitter matcher
+    pub fn new() -> Self {
+        TreeSitterStructuralMatcher
+    }
+
+    fn ts_language(language: SourceLanguage) -> Language {
+        match language {
+            SourceLanguage::Rust => tree_sitter_rust::LANGUAGE.into(),
+            SourceLanguage::Python => tree_sitter_python::LANGUAGE.into(),
+            SourceLanguage::JavaScript => tree_sitter_javascript::LANGUAGE.into(),
+            SourceLanguage::TypeScript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+            SourceLanguage::Tsx => tree_sitter_typescript::LANGUAGE_TSX.into(),
+            SourceLanguage::Java => tree_sitter_java::LANGUAGE.into(),
+            SourceLanguage::Go => tree_sitter_go::LANGUAGE.into(),
+            SourceLanguage::C => tree_sitter_c::LANGUAGE.into(),
+            SourceLanguage::Cpp => tree_sitter_cpp::LANGUAGE.into(),
+        }
+    }
+
+    fn parse(language: SourceLanguage, source: &str) -> AppResult<Tree> {
+        let mut parser = Parser::new();
+        parser
+            .set_language(&Self::ts_language(language))
+            .map_err(|e| Self::search_error(source, e.to_string()))?;
+        parser
+            .parse(source, None)
+            .ok_or_else(|| Self::search_error(source, format!("failed to parse {} source", language)))
+    }
+
+    fn search_error(query: &str, message: String) -> AppError {
+        AppError::Search {
+            query: query.to_string(),
+            message,
+        }
+    }
+
+    fn to_match(node: Node<'_>, source: &str, captures: Captures) -> SearchMatch {
+        let start = node.start_position();
+        SearchMatch::new(start.row + 1, start.column, source[node.byte_range()].to_string())
+            .with_captures(captures)
+    }
+
+    fn find_template_matches(
+        language: SourceLanguage,
+        pattern: &StructuralPattern,
+        source: &str,
+    ) -> AppResult<Vec<SearchMatch>> {
+        let template = pattern.template_source();
+        let pattern_tree = Self::parse(language, template)?;
+        let pattern_root = Self::pattern_root(pattern_tree.root_node());
+        if pattern_root.byte_range().is_empty() {
+            return Err(Self::search_error(pattern.pattern(), "pattern has no syntax nodes".to_string()));
+        }
+
+        let source_tree = Self::parse(language, source)?;
+        let template_matcher = TemplateMatcher { pattern, template, source };
+        let mut matches = Vec::new();
+        let mut stack = vec![source_tree.root_node()];
+
+        while let Some(node) = stack.pop() {
+            if let Some(captures) = template_matcher.match_node(pattern_root, node, Captures::new()) {
+                let captures = captures
+                    .into_iter()
+                    .filter(|(name, _)| !name.is_empty() && !name.starts_with('_'))
+                    .collect();
+                matches.push(Self::to_match(node, source, captures));
+            }
+            let mut cursor = node.walk();
+            let children: Vec<Node<'_>> = node.children(&mut cursor).collect();
+            stack.extend(children.into_iter().rev());
+        }
+
+        Ok(matches)
+    }
+
+    /// Skip wrapper nodes (e.g. `source_file`, `expression_statement`) that
+    /// span exactly one child
+    fn pattern_root(mut node: Node<'_>) -> Node<'_> {
+        loop {
+            let children = significant_children(node);
+            match children.as_slice() {
+                [child] if child.byte_range() == node.byte_range() || node.parent().is_none() => {
+                    node = *child;
+                }
+                _ => return node,
+            }
+        }
+    }
+
+    fn find_query_matches(
+        language: SourceLanguage,
+        pattern: &StructuralPattern,
+        source: &str,
+    ) -> AppResult<Vec<SearchMatch>> {
+        let ts_language = Self::ts_language(language);
+        let query = Query::new(&ts_language, pattern.pattern())
+            .map_err(|e| Self::search_error(pattern.pattern(), e.to_string()))?;
+        let tree = Self::parse(language, source)?;
+        let capture_names = query.capture_names();
+
+        let mut cursor = QueryCursor::new();
+        let mut query_matches = cursor.matches(&query, tree.root_node(), source.as_bytes());
+        let mut matches = Vec::new();
+
+        while let Some(query_match) = query_matches.next() {
+            // The match spans the outermost captured node
+            let Some(node) = query_match
+                .captures
+                .iter()
+                .map(|capture| capture.node)
+                .max_by_key(|node| node.byte_range().len())
+            else {
+                continue;
+            };
+            let captures = query_match
+                .captures
+                .iter()
+                .map(|capture| {
+                    (
+                        capture_names[capture.index as usize].to_string(),
+                        source[capture.node.byte_range()].to_string(),
+                    )
+                })
+                .collect();
+            matches.push(Self::to_match(node, source, captures));
+        }
+
+        Ok(matches)
+    }
+}
+
+impl StructuralMatcher for TreeSitterStructuralMatcher {
+    fn supports(&self, _language: SourceLanguage) -> bool {
+        true
+    }
+
+    fn find_matches(
+        &self,
+        language: SourceLanguage,
+        pattern: &StructuralPattern,
+        source: &str,
+    ) -> AppResult<Vec<SearchMatch>> {
+        match pattern.kind() {
+            StructuralPatternKind::Template => Self::find_template_matches(language, pattern, source),
+            StructuralPatternKind::Query => Self::find_query_matches(language, pattern, source),
+        }
+    }
+}
+
+/// Children that take part in matching (comments and other extras are skipped)
+fn significant_children(node: Node<'_>) -> Vec<Node<'_>> {
+    let mut cursor = node.walk();
+    node.children(&mut cursor).filter(|child| !child.is_extra()).collect()
+}
+
+struct TemplateMatcher<'a> {
+    pattern: &'a StructuralPattern,
+    template: &'a str,
+    source: &'a str,
+}
+
+impl TemplateMatcher<'_> {
+    fn metavariable(&self, pattern_node: Node<'_>) -> Option<(&str, bool)> {
+        let text = self.template[pattern_node.byte_range()].trim();
+        self.pattern
+            .metavariable_for(text)
+            .map(|metavariable| (metavariable.name(), metavariable.is_multi()))
+    }
+
+    /// Bind `text` to a metavariable, failing if it is already bound to other text
+    fn bind(&self, name: &str, text: &str, mut captures: Captures) -> Option<Captures> {
+        if name.is_empty() {
+            return Some(captures);
+        }
+        match captures.get(name) {
+            Some(bound) if bound != text => None,
+            Some(_) => Some(captures),
+            None => {
+                captures.insert(name.to_string(), text.to_string());
+                Some(captures)
+            }
+        }
+    }
+
+    fn match_node(&self, pattern_node: Node<'_>, source_node: Node<'_>, captures: Captures) -> Option<Captures> {
+        if let Some((name, _)) = self.metavariable(pattern_node) {
+            return self.bind(name, &self.source[source_node.byte_range()], captures);
+        }
+        if pattern_node.kind_id() != source_node.kind_id() {
+            return None;
+        }
+
+        let pattern_children = significant_children(pattern_node);
+        let source_children = significant_children(source_node);
+        if pattern_children.is_empty() {
+            let matches = source_children.is_empty()
+                && self.template[pattern_node.byte_range()] == self.source[source_node.byte_range()];
+            return matches.then_some(captures);
+        }
+
+        self.match_children(&pattern_children, &source_children, captures)
+    }
+
+    fn match_children(&self, pattern: &[Node<'_>], source: &[Node<'_>], captures: Captures) -> Option<Captures> {
+        let Some((&first, rest)) = pattern.split_first() else {
+            return source.is_empty().then_some(captures);
+        };
+
+        match self.metavariable(first) {
+            Some((name, true)) => {
+                // Try the shortest run first so later siblings keep their matches
+                (0..=source.len()).find_map(|taken| {
+                    let text = match (source.first(), source[..taken].last()) {
+                        (Some(start), Some(end)) => &self.source[start.start_byte()..end.end_byte()],
+                        _ => "",
+                    };
+                    let captures = self.bind(name, text, captures.clone())?;
+                    self.match_children(rest, &source[taken..], captures)
+                })
+            }
+            _ => {
+                let (&head, tail) = source.split_first()?;
+                let captures = self.match_node(first, head, captures)?;
+                self.match_children(rest, tail, captures)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template_matches(language: SourceLanguage, pattern: &str, source: &str) -> Vec<SearchMatch> {
+        let pattern = StructuralPattern::template(pattern).unwrap();
+        TreeSitterStructuralMatcher::new()
+            .find_matches(language, &pattern, source)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_rust_function_template() {
+        let source = "fn add(a: i32, b: i32) {\n    a + b;\n}\n\nfn empty() {}\n\nstruct Point;\n";
+        let matches = template_matches(SourceLanguage::Rust, "fn $NAME($$$ARGS) { $$$ }", source);
+
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].line_number(), 1);
+        assert_eq!(matches[0].capture("NAME"), Some("add"));
+        assert_eq!(matches[0].capture("ARGS"), Some("a: i32, b: i32"));
+        assert_eq!(matches[1].line_number(), 5);
+        assert_eq!(matches[1].capture("NAME"), Some("empty"));
+        assert_eq!(matches[1].capture("ARGS"), Some(""));
+        assert!(!matches[1].captures().contains_key(""));
+    }
+
+    #[test]
+    fn test_repeated_metavariable_must_bind_same_text() {
+        let source = "def f(x, y):\n    return x == x or x == y\n";
+        let matches = template_matches(SourceLanguage::Python, "$A == $A", source);
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].matched_text(), "x == x");
+        assert_eq!(matches[0].capture("A"), Some("x"));
+    }
+
+    #[test]
+    fn test_template_ignores_comments_and_whitespace() {
+        let source = "console.log(\n  /* value */ value,\n  1\n);\nother.log(value);\n";
+        let matches = template_matches(SourceLanguage::JavaScript, "console.log($$$ARGS)", source);
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].capture("ARGS"), Some("value,\n  1"));
+    }
+
+    #[test]
+    fn test_query_pattern_captures() {
+        let source = "fn alpha() {}\nfn beta() {}\n";
+        let pattern = StructuralPattern::query("(function_item name: (identifier) @name) @function").unwrap();
+        let matches = TreeSitterStructuralMatcher::new()
+            .find_matches(SourceLanguage::Rust, &pattern, source)
+            .unwrap();
+
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[1].capture("name"), Some("beta"));
+        assert_eq!(matches[1].matched_text(), "fn beta() {}");
+    }
+
+    #[test]
+    fn test_invalid_query_is_search_error() {
+        let pattern = StructuralPattern::query("(not_a_node_kind)").unwrap();
+        let result = TreeSitterStructuralMatcher::new().find_matches(SourceLanguage::Rust, &pattern, "fn a() {}");
+
+        assert!(matches!(result, Err(AppError::Search { .. })));
+    }
+}
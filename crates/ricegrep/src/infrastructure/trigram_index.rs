@@ -0,0 +1,801 @@
+//! Trigram-based IndexRepository Implementation
+//!
+//! Implements the `IndexRepository` trait with a persistent trigram index so
+//! repeated searches only read files that can possibly match.
+//!
+//! # Layout
+//! - A compacted **snapshot** on disk, memory-mapped on open. Posting lists
+//!   are read straight from the mapping via binary search over a sorted
+//!   trigram table; nothing but the file table is decoded up front.
+//! - An in-memory **overlay** of files (re)indexed or removed since the
+//!   snapshot was written. Overlay entries shadow the snapshot and are folded
+//!   into a new snapshot by [`TrigramIndexRepository::save`].
+//!
+//! # Staleness
+//! Every search first stats the indexed files and reindexes those whose
+//! mtime or size changed (or drops them if they were deleted), so results are
+//! never served from outdated content. New files enter the index through
+//! [`TrigramIndexRepository::apply_change`] or
+//! [`TrigramIndexRepository::index_workspace`].
+//!
+//! # Streaming
+//! Candidate files are verified in path order and each file's result is
+//! handed out as soon as it is verified, so the first results arrive after a
+//! handful of file reads regardless of workspace size.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs::{self, File};
+use std::hash::Hasher;
+use std::io::{BufWriter, Write};
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use std::time::UNIX_EPOCH;
+
+use memmap2::{Mmap, MmapOptions};
+
+use crate::application::{
+    build_query_regex, find_query_matches, AppError, AppResult, FileIndexEntry, IndexRepository,
+    SearchCancellation,
+};
+use crate::domain::{FilePath, SearchQuery, SearchResult};
+use crate::indexing_optimization::{FileChangeEvent, FileChangeKind};
+
+const MAGIC: &[u8; 8] = b"RGTRI001";
+const TABLE_ENTRY_SIZE: usize = 12;
+
+/// Files larger than this are not indexed
+const MAX_INDEXED_FILE_SIZE: u64 = 8 * 1024 * 1024;
+
+type Trigram = u32;
+
+/// Trigram-based implementation of `IndexRepository`
+///
+/// # Example
+/// ```ignore
+/// use ricegrep::infrastructure::TrigramIndexRepository;
+///
+/// let repo = TrigramIndexRepository::open(".", ".ricegrep/trigram.idx")?;
+/// repo.index_workspace()?;
+/// repo.save()?;
+/// let results = repo.search(&SearchQuery::simple("TODO")?)?;
+/// ```
+pub struct TrigramIndexRepository {
+    root: PathBuf,
+    index_path: PathBuf,
+    state: RwLock<IndexState>,
+}
+
+#[derive(Default)]
+struct IndexState {
+    snapshot: Option<Snapshot>,
+    /// Metadata for every indexed file, keyed by path
+    files: HashMap<String, FileIndexEntry>,
+    /// Changes since the snapshot: `Some` holds the file's trigrams, `None`
+    /// marks a removal
+    overlay: HashMap<String, Option<BTreeSet<Trigram>>>,
+}
+
+/// Memory-mapped, compacted index
+///
+/// Format (little endian):
+/// `magic[8] file_count:u32 trigram_count:u32`, then per file
+/// `path_len:u32 path modified_at:u64 size:u64 hash_len:u32 hash`, then
+/// `trigram_count` sorted `(trigram:u32 offset:u32 len:u32)` entries, then
+/// the posting lists as `u32` file ids.
+struct Snapshot {
+    mmap: Mmap,
+    paths: Vec<String>,
+    table_offset: usize,
+    trigram_count: usize,
+    postings_offset: usize,
+}
+
+impl Snapshot {
+    fn load(path: &Path) -> AppResult<(Self, Vec<FileIndexEntry>)> {
+        let file = File::open(path).map_err(|e| index_error("load", e))?;
+        // Safety: the snapshot is only replaced via rename, never written in place
+        let mmap = unsafe { MmapOptions::new().map(&file) }.map_err(|e| index_error("load", e))?;
+
+        let mut reader = ByteReader { bytes: &mmap, offset: 0 };
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(index_error("load", "not a trigram index"));
+        }
+        let file_count = reader.u32()? as usize;
+        let trigram_count = reader.u32()? as usize;
+
+        let mut paths = Vec::with_capacity(file_count);
+        let mut entries = Vec::with_capacity(file_count);
+        for _ in 0..file_count {
+            let path = reader.string()?;
+            let modified_at = reader.u64()?;
+            let size = reader.u64()?;
+            let hash = reader.string()?;
+            let mut entry = FileIndexEntry::new(path.clone(), modified_at, size);
+            entry.content_hash = (!hash.is_empty()).then_some(hash);
+            paths.push(path);
+            entries.push(entry);
+        }
+
+        let table_offset = reader.offset;
+        let postings_offset = table_offset + trigram_count * TABLE_ENTRY_SIZE;
+        if postings_offset > mmap.len() {
+            return Err(index_error("load", "trigram table truncated"));
+        }
+
+        let snapshot = Snapshot { mmap, paths, table_offset, trigram_count, postings_offset };
+        Ok((snapshot, entries))
+    }
+
+    fn table_entry(&self, index: usize) -> (Trigram, usize, usize) {
+        let start = self.table_offset + index * TABLE_ENTRY_SIZE;
+        let field = |i: usize| read_u32(&self.mmap, start + i * 4) as usize;
+        (field(0) as Trigram, field(1), field(2))
+    }
+
+    /// Snapshot file ids containing `trigram`
+    fn postings(&self, trigram: Trigram) -> Vec<u32> {
+        let (mut low, mut high) = (0, self.trigram_count);
+        while low < high {
+            let mid = (low + high) / 2;
+            let (candidate, offset, len) = self.table_entry(mid);
+            match candidate.cmp(&trigram) {
+                std::cmp::Ordering::Less => low = mid + 1,
+                std::cmp::Ordering::Greater => high = mid,
+                std::cmp::Ordering::Equal => {
+                    let start = self.postings_offset + offset * 4;
+                    if start + len * 4 > self.mmap.len() {
+                        return Vec::new();
+                    }
+                    return (0..len).map(|i| read_u32(&self.mmap, start + i * 4)).collect();
+                }
+            }
+        }
+        Vec::new()
+    }
+
+    /// Trigram sets of every snapshot file, rebuilt from the posting lists
+    fn file_trigrams(&self) -> Vec<BTreeSet<Trigram>> {
+        let mut trigrams = vec![BTreeSet::new(); self.paths.len()];
+        for index in 0..self.trigram_count {
+            let (trigram, _, _) = self.table_entry(index);
+            for file_id in self.postings(trigram) {
+                if let Some(set) = trigrams.get_mut(file_id as usize) {
+                    set.insert(trigram);
+                }
+            }
+        }
+        trigrams
+    }
+}
+
+impl TrigramIndexRepository {
+    /// Open the index for the workspace at `root`, loading the snapshot at
+    /// `index_path` if it exists
+    pub fn open(root: impl Into<PathBuf>, index_path: impl Into<PathBuf>) -> AppResult<Self> {
+        let root = root.into();
+        let index_path = index_path.into();
+        let mut state = IndexState::default();
+
+        if index_path.exists() {
+            let (snapshot, entries) = Snapshot::load(&index_path)?;
+            state.files = entries.into_iter().map(|e| (e.path.clone(), e)).collect();
+            state.snapshot = Some(snapshot);
+        }
+
+        Ok(TrigramIndexRepository { root, index_path, state: RwLock::new(state) })
+    }
+
+    /// Number of indexed files
+    pub fn file_count(&self) -> usize {
+        self.read_state().map(|state| state.files.len()).unwrap_or(0)
+    }
+
+    /// Number of changes not yet folded into the on-disk snapshot
+    pub fn pending_changes(&self) -> usize {
+        self.read_state().map(|state| state.overlay.len()).unwrap_or(0)
+    }
+
+    /// (Re)index a single file if it is new or stale
+    ///
+    /// Returns whether the index changed. Missing, binary, oversized and
+    /// non-UTF-8 files are removed from the index.
+    pub fn index_file(&self, path: &FilePath) -> AppResult<bool> {
+        let key = self.key(path.as_path());
+        let mut state = self.write_state("index")?;
+        self.refresh(&mut state, &key)
+    }
+
+    /// Index every file under the root (respecting ignore files) and drop
+    /// files that no longer exist
+    ///
+    /// Returns the number of files added, updated or removed.
+    pub fn index_workspace(&self) -> AppResult<usize> {
+        let mut seen = HashSet::new();
+        let mut changed = 0;
+        let mut state = self.write_state("index")?;
+
+        for entry in ignore::WalkBuilder::new(&self.root).build().flatten() {
+            if !entry.file_type().is_some_and(|t| t.is_file()) || entry.path() == self.index_path {
+                continue;
+            }
+            let key = self.key(entry.path());
+            if self.refresh(&mut state, &key)? {
+                changed += 1;
+            }
+            seen.insert(key);
+        }
+
+        let removed: Vec<String> = state.files.keys().filter(|k| !seen.contains(*k)).cloned().collect();
+        for key in removed {
+            Self::remove(&mut state, &key);
+            changed += 1;
+        }
+        Ok(changed)
+    }
+
+    /// Apply a file change event (e.g. from the watcher's debounce buffer)
+    pub fn apply_change(&self, event: &FileChangeEvent) -> AppResult<()> {
+        let key = self.key(&event.path);
+        let mut state = self.write_state("update")?;
+        match event.kind {
+            FileChangeKind::Delete => {
+                Self::remove(&mut state, &key);
+            }
+            FileChangeKind::Create | FileChangeKind::Modify => {
+                self.refresh(&mut state, &key)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Indexed files whose mtime or size no longer match the filesystem
+    pub fn stale_files(&self) -> Vec<String> {
+        let Ok(state) = self.read_state() else {
+            return Vec::new();
+        };
+        let mut stale: Vec<String> = state.files.values()
+            .filter(|entry| match self.stat(&entry.path) {
+                Some((modified_at, size)) => entry.is_stale(modified_at, size),
+                None => true,
+            })
+            .map(|entry| entry.path.clone())
+            .collect();
+        stale.sort();
+        stale
+    }
+
+    /// Write a compacted snapshot containing all pending changes
+    pub fn save(&self) -> AppResult<()> {
+        let mut guard = self.write_state("save")?;
+        let state = &mut *guard;
+
+        let snapshot_trigrams = state.snapshot.as_ref().map(Snapshot::file_trigrams).unwrap_or_default();
+        let snapshot_paths = state.snapshot.as_ref().map(|s| s.paths.clone()).unwrap_or_default();
+        let mut file_trigrams: HashMap<String, BTreeSet<Trigram>> = snapshot_paths.into_iter()
+            .zip(snapshot_trigrams)
+            .collect();
+        for (key, change) in &state.overlay {
+            match change {
+                Some(set) => file_trigrams.insert(key.clone(), set.clone()),
+                None => file_trigrams.remove(key),
+            };
+        }
+
+        let mut paths: Vec<&String> = state.files.keys().collect();
+        paths.sort();
+        let mut postings: BTreeMap<Trigram, Vec<u32>> = BTreeMap::new();
+        for (file_id, path) in paths.iter().enumerate() {
+            for &trigram in file_trigrams.get(*path).into_iter().flatten() {
+                postings.entry(trigram).or_default().push(file_id as u32);
+            }
+        }
+
+        // Release the mapping before replacing the file underneath it
+        state.snapshot = None;
+        if let Some(parent) = self.index_path.parent() {
+            fs::create_dir_all(parent).map_err(|e| index_error("save", e))?;
+        }
+        let temp_path = self.index_path.with_extension("tmp");
+        Self::write_snapshot(&temp_path, &paths, &state.files, &postings)
+            .map_err(|e| index_error("save", e))?;
+        fs::rename(&temp_path, &self.index_path).map_err(|e| index_error("save", e))?;
+
+        let (snapshot, _) = Snapshot::load(&self.index_path)?;
+        state.snapshot = Some(snapshot);
+        state.overlay.clear();
+        Ok(())
+    }
+
+    fn write_snapshot(
+        path: &Path,
+        paths: &[&String],
+        files: &HashMap<String, FileIndexEntry>,
+        postings: &BTreeMap<Trigram, Vec<u32>>,
+    ) -> std::io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(MAGIC)?;
+        writer.write_all(&(paths.len() as u32).to_le_bytes())?;
+        writer.write_all(&(postings.len() as u32).to_le_bytes())?;
+
+        for path in paths {
+            let entry = &files[*path];
+            let hash = entry.content_hash.as_deref().unwrap_or_default();
+            writer.write_all(&(path.len() as u32).to_le_bytes())?;
+            writer.write_all(path.as_bytes())?;
+            writer.write_all(&entry.modified_at.to_le_bytes())?;
+            writer.write_all(&entry.size.to_le_bytes())?;
+            writer.write_all(&(hash.len() as u32).to_le_bytes())?;
+            writer.write_all(hash.as_bytes())?;
+        }
+
+        let mut offset = 0u32;
+        for (trigram, ids) in postings {
+            writer.write_all(&trigram.to_le_bytes())?;
+            writer.write_all(&offset.to_le_bytes())?;
+            writer.write_all(&(ids.len() as u32).to_le_bytes())?;
+            offset += ids.len() as u32;
+        }
+        for id in postings.values().flatten() {
+            writer.write_all(&id.to_le_bytes())?;
+        }
+        writer.flush()
+    }
+
+    /// Reindex `key` if it is new or stale; returns whether the index changed
+    fn refresh(&self, state: &mut IndexState, key: &str) -> AppResult<bool> {
+        let Some((modified_at, size)) = self.stat(key) else {
+            return Ok(Self::remove(state, key));
+        };
+        if state.files.get(key).is_some_and(|entry| !entry.is_stale(modified_at, size)) {
+            return Ok(false);
+        }
+
+        let content = (size <= MAX_INDEXED_FILE_SIZE && !FilePath::new(key)?.is_likely_binary())
+            .then(|| fs::read(self.resolve(key)).ok())
+            .flatten()
+            .filter(|bytes| !bytes.contains(&0))
+            .and_then(|bytes| String::from_utf8(bytes).ok());
+        let Some(content) = content else {
+            return Ok(Self::remove(state, key));
+        };
+
+        let entry = FileIndexEntry::with_hash(key.to_string(), modified_at, size, content_hash(&content));
+        state.files.insert(key.to_string(), entry);
+        state.overlay.insert(key.to_string(), Some(trigrams(&content)));
+        Ok(true)
+    }
+
+    /// Remove `key` from the index; returns whether it was indexed
+    fn remove(state: &mut IndexState, key: &str) -> bool {
+        let was_indexed = state.files.remove(key).is_some();
+        if was_indexed {
+            state.overlay.insert(key.to_string(), None);
+        }
+        was_indexed
+    }
+
+    /// Paths of indexed files that may contain every trigram in `required`
+    fn candidates(state: &IndexState, required: &BTreeSet<Trigram>) -> Vec<String> {
+        if required.is_empty() {
+            return state.files.keys().cloned().collect();
+        }
+
+        let mut candidates: Option<HashSet<String>> = None;
+        for &trigram in required {
+            let mut matching: HashSet<String> = state.snapshot.iter()
+                .flat_map(|snapshot| {
+                    snapshot.postings(trigram).into_iter()
+                        .filter_map(|id| snapshot.paths.get(id as usize).cloned())
+                })
+                .filter(|path| !state.overlay.contains_key(path) && state.files.contains_key(path))
+                .collect();
+            matching.extend(state.overlay.iter()
+                .filter(|(_, set)| set.as_ref().is_some_and(|set| set.contains(&trigram)))
+                .map(|(path, _)| path.clone()));
+
+            let narrowed = match candidates {
+                Some(current) => current.intersection(&matching).cloned().collect(),
+                None => matching,
+            };
+            if narrowed.is_empty() {
+                return Vec::new();
+            }
+            candidates = Some(narrowed);
+        }
+        candidates.unwrap_or_default().into_iter().collect()
+    }
+
+    fn key(&self, path: &Path) -> String {
+        path.strip_prefix(&self.root).unwrap_or(path).to_string_lossy().to_string()
+    }
+
+    fn resolve(&self, key: &str) -> PathBuf {
+        self.root.join(key)
+    }
+
+    fn stat(&self, key: &str) -> Option<(u64, u64)> {
+        let metadata = fs::metadata(self.resolve(key)).ok()?;
+        if !metadata.is_file() {
+            return None;
+        }
+        let modified_at = metadata.modified().ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        Some((modified_at, metadata.len()))
+    }
+
+    fn read_state(&self) -> AppResult<std::sync::RwLockReadGuard<'_, IndexState>> {
+        self.state.read().map_err(|_| index_error("read", "Failed to acquire lock"))
+    }
+
+    fn write_state(&self, operation: &str) -> AppResult<std::sync::RwLockWriteGuard<'_, IndexState>> {
+        self.state.write().map_err(|_| index_error(operation, "Failed to acquire lock"))
+    }
+}
+
+impl IndexRepository for TrigramIndexRepository {
+    fn get_metadata(&self, path: &FilePath) -> Option<FileIndexEntry> {
+        let key = self.key(path.as_path());
+        self.read_state().ok()?.files.get(&key).cloned()
+    }
+
+    /// Records the metadata and (re)indexes the file's current content
+    fn update_metadata(&self, entry: FileIndexEntry) -> AppResult<()> {
+        let key = self.key(Path::new(&entry.path));
+        let mut state = self.write_state("update")?;
+        state.files.remove(&key);
+        self.refresh(&mut state, &key)?;
+        if let Some(indexed) = state.files.get_mut(&key) {
+            indexed.modified_at = entry.modified_at;
+            indexed.size = entry.size;
+            if entry.content_hash.is_some() {
+                indexed.content_hash = entry.content_hash;
+            }
+        }
+        Ok(())
+    }
+
+    fn remove_metadata(&self, path: &FilePath) -> AppResult<()> {
+        let key = self.key(path.as_path());
+        let mut state = self.write_state("remove")?;
+        Self::remove(&mut state, &key);
+        Ok(())
+    }
+
+    fn search(&self, query: &SearchQuery) -> AppResult<Vec<SearchResult>> {
+        let mut results = Vec::new();
+        self.search_streaming(query, &SearchCancellation::new(), &mut |result| {
+            results.push(result);
+            ControlFlow::Continue(())
+        })?;
+        Ok(results)
+    }
+
+    fn search_streaming(
+        &self,
+        query: &SearchQuery,
+        cancellation: &SearchCancellation,
+        on_result: &mut dyn FnMut(SearchResult) -> ControlFlow<()>,
+    ) -> AppResult<()> {
+        let regex = build_query_regex(query)?;
+
+        // Bring stale entries up to date before trusting the posting lists
+        let stale = self.stale_files();
+        if !stale.is_empty() {
+            let mut state = self.write_state("refresh")?;
+            for key in &stale {
+                self.refresh(&mut state, key)?;
+            }
+        }
+
+        let state = self.read_state()?;
+        let mut candidates = Self::candidates(&state, &required_trigrams(query));
+        drop(state);
+        candidates.sort();
+
+        for key in candidates {
+            if cancellation.is_cancelled() {
+                break;
+            }
+            let Ok(content) = fs::read_to_string(self.resolve(&key)) else {
+                continue;
+            };
+            let matches = find_query_matches(&regex, &content);
+            if !matches.is_empty() && on_result(SearchResult::new(FilePath::new(&key)?, matches)).is_break() {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn index_error(operation: &str, message: impl ToString) -> AppError {
+    AppError::Index {
+        operation: operation.to_string(),
+        message: message.to_string(),
+    }
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(bytes[offset..offset + 4].try_into().expect("4-byte slice"))
+}
+
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn take(&mut self, len: usize) -> AppResult<&'a [u8]> {
+        let end = self.offset.checked_add(len).filter(|&end| end <= self.bytes.len())
+            .ok_or_else(|| index_error("load", "unexpected end of index"))?;
+        let slice = &self.bytes[self.offset..end];
+        self.offset = end;
+        Ok(slice)
+    }
+
+    fn u32(&mut self) -> AppResult<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().expect("4-byte slice")))
+    }
+
+    fn u64(&mut self) -> AppResult<u64> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().expect("8-byte slice")))
+    }
+
+    fn string(&mut self) -> AppResult<String> {
+        let len = self.u32()? as usize;
+        String::from_utf8(self.take(len)?.to_vec()).map_err(|e| index_error("load", e))
+    }
+}
+
+fn content_hash(content: &str) -> String {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    hasher.write(content.as_bytes());
+    format!("{:016x}", hasher.finish())
+}
+
+/// Case-folded byte trigrams of `text`
+fn trigrams(text: &str) -> BTreeSet<Trigram> {
+    text.as_bytes()
+        .windows(3)
+        .map(|w| u32::from_le_bytes([w[0].to_ascii_lowercase(), w[1].to_ascii_lowercase(), w[2].to_ascii_lowercase(), 0]))
+        .collect()
+}
+
+/// Trigrams every matching file must contain
+///
+/// Literal queries contribute all their trigrams. For regexes only runs of
+/// literal characters that are certainly part of every match count;
+/// alternations and groups make the whole pattern opaque.
+///
+/// Trigrams are only folded to ASCII lowercase, so case-insensitive queries
+/// with non-ASCII characters are not pruned at all.
+fn required_trigrams(query: &SearchQuery) -> BTreeSet<Trigram> {
+    if !query.is_case_sensitive() && !query.query().is_ascii() {
+        return BTreeSet::new();
+    }
+    literal_trigrams(query)
+}
+
+fn literal_trigrams(query: &SearchQuery) -> BTreeSet<Trigram> {
+    if !query.is_regex() {
+        return trigrams(query.query());
+    }
+
+    let pattern = query.query();
+    if pattern.contains(['|', '(']) {
+        return BTreeSet::new();
+    }
+
+    fn flush(run: &mut String, required: &mut BTreeSet<Trigram>) {
+        required.extend(trigrams(run));
+        run.clear();
+    }
+
+    let mut required = BTreeSet::new();
+    let mut run = String::new();
+    let mut chars = pattern.chars();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => match chars.next() {
+                Some(escaped) if escaped.is_ascii_punctuation() => run.push(escaped),
+                _ => flush(&mut run, &mut required),
+            },
+            '[' => {
+                flush(&mut run, &mut required);
+                for class_char in chars.by_ref() {
+                    if class_char == ']' {
+                        break;
+                    }
+                }
+            }
+            '?' | '*' => {
+                // The preceding character is optional or repeated
+                run.pop();
+                flush(&mut run, &mut required);
+            }
+            '{' => {
+                // A counted repetition, possibly zero times; its bounds are not text
+                run.pop();
+                flush(&mut run, &mut required);
+                for bound_char in chars.by_ref() {
+                    if bound_char == '}' {
+                        break;
+                    }
+                }
+            }
+            '.' | '+' | '^' | '$' | '}' | ')' | ']' => flush(&mut run, &mut required),
+            _ => run.push(ch),
+        }
+    }
+    flush(&mut run, &mut required);
+    required
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+    use tempfile::TempDir;
+
+    fn workspace() -> TempDir {
+        let dir = TempDir::new().expect("Failed to create temp dir");
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("src/main.rs"), "fn main() {\n    println!(\"hello world\");\n}\n").unwrap();
+        fs::write(dir.path().join("src/lib.rs"), "pub fn helper() -> u32 { 42 }\n").unwrap();
+        fs::write(dir.path().join("notes.txt"), "Hello again\n").unwrap();
+        dir
+    }
+
+    fn open(dir: &TempDir) -> TrigramIndexRepository {
+        TrigramIndexRepository::open(dir.path(), dir.path().join(".ricegrep/trigram.idx")).unwrap()
+    }
+
+    fn result_paths(results: &[SearchResult]) -> Vec<String> {
+        results.iter().map(|r| r.file_path().as_path().to_string_lossy().to_string()).collect()
+    }
+
+    #[test]
+    fn test_index_and_search() {
+        let dir = workspace();
+        let repo = open(&dir);
+        assert_eq!(repo.index_workspace().unwrap(), 3);
+
+        let results = repo.search(&SearchQuery::simple("hello").unwrap()).unwrap();
+        assert_eq!(result_paths(&results), vec!["notes.txt", "src/main.rs"]);
+        assert_eq!(results[1].matches()[0].line_number(), 2);
+
+        let results = repo.search(&SearchQuery::case_sensitive("hello").unwrap()).unwrap();
+        assert_eq!(result_paths(&results), vec!["src/main.rs"]);
+    }
+
+    #[test]
+    fn test_snapshot_roundtrip() {
+        let dir = workspace();
+        let repo = open(&dir);
+        repo.index_workspace().unwrap();
+        repo.save().unwrap();
+        assert_eq!(repo.pending_changes(), 0);
+
+        let reopened = open(&dir);
+        assert_eq!(reopened.file_count(), 3);
+        assert!(reopened.get_metadata(&FilePath::new("src/lib.rs").unwrap()).unwrap().content_hash.is_some());
+        assert_eq!(reopened.index_workspace().unwrap(), 0);
+
+        let results = reopened.search(&SearchQuery::regex(r"fn \w+\(\) -> u32").unwrap()).unwrap();
+        assert_eq!(result_paths(&results), vec!["src/lib.rs"]);
+    }
+
+    #[test]
+    fn test_incremental_change_events() {
+        let dir = workspace();
+        let repo = open(&dir);
+        repo.index_workspace().unwrap();
+        repo.save().unwrap();
+
+        fs::write(dir.path().join("src/new.rs"), "// needle\n").unwrap();
+        fs::remove_file(dir.path().join("notes.txt")).unwrap();
+        for (path, kind) in [("src/new.rs", FileChangeKind::Create), ("notes.txt", FileChangeKind::Delete)] {
+            repo.apply_change(&FileChangeEvent { path: dir.path().join(path), kind, timestamp: Instant::now() }).unwrap();
+        }
+        assert_eq!(repo.pending_changes(), 2);
+
+        let results = repo.search(&SearchQuery::simple("needle").unwrap()).unwrap();
+        assert_eq!(result_paths(&results), vec!["src/new.rs"]);
+        assert!(repo.get_metadata(&FilePath::new("notes.txt").unwrap()).is_none());
+
+        repo.save().unwrap();
+        let reopened = open(&dir);
+        assert_eq!(reopened.file_count(), 3);
+        let results = reopened.search(&SearchQuery::simple("needle").unwrap()).unwrap();
+        assert_eq!(result_paths(&results), vec!["src/new.rs"]);
+    }
+
+    #[test]
+    fn test_stale_files_are_reindexed_on_search() {
+        let dir = workspace();
+        let repo = open(&dir);
+        repo.index_workspace().unwrap();
+
+        fs::write(dir.path().join("src/lib.rs"), "pub fn helper() -> u32 { 42 } // changed marker\n").unwrap();
+        assert_eq!(repo.stale_files(), vec!["src/lib.rs".to_string()]);
+
+        let results = repo.search(&SearchQuery::simple("changed marker").unwrap()).unwrap();
+        assert_eq!(result_paths(&results), vec!["src/lib.rs"]);
+        assert!(repo.stale_files().is_empty());
+    }
+
+    #[test]
+    fn test_streaming_search_stops_on_break_and_cancel() {
+        let dir = workspace();
+        let repo = open(&dir);
+        repo.index_workspace().unwrap();
+        let query = SearchQuery::simple("l").unwrap();
+
+        let mut seen = Vec::new();
+        repo.search_streaming(&query, &SearchCancellation::new(), &mut |result| {
+            seen.push(result.file_path().as_path().to_string_lossy().to_string());
+            ControlFlow::Break(())
+        }).unwrap();
+        assert_eq!(seen, vec!["notes.txt"]);
+
+        let cancellation = SearchCancellation::new();
+        cancellation.cancel();
+        let mut delivered = 0;
+        repo.search_streaming(&query, &cancellation, &mut |_| {
+            delivered += 1;
+            ControlFlow::Continue(())
+        }).unwrap();
+        assert_eq!(delivered, 0);
+    }
+
+    #[test]
+    fn test_required_trigrams_for_regex() {
+        let query = SearchQuery::regex(r"fn \w+_tests?").unwrap();
+        let expected: BTreeSet<Trigram> = trigrams("fn ").union(&trigrams("_test")).copied().collect();
+        assert_eq!(required_trigrams(&query), expected);
+
+        assert!(required_trigrams(&SearchQuery::regex("foo|bar").unwrap()).is_empty());
+
+        // Repetition bounds are not literal text
+        let query = SearchQuery::regex(r"id_a{10,20}_end").unwrap();
+        let expected: BTreeSet<Trigram> = trigrams("id_").union(&trigrams("_end")).copied().collect();
+        assert_eq!(required_trigrams(&query), expected);
+    }
+
+    #[test]
+    fn test_quantifier_bounds_do_not_prune_matches() {
+        let dir = workspace();
+        fs::write(dir.path().join("src/ids.rs"), "const ID: &str = \"aaaaaaaaaaaa\";\n").unwrap();
+        let repo = open(&dir);
+        repo.index_workspace().unwrap();
+
+        let results = repo.search(&SearchQuery::regex(r"a{10,20}").unwrap()).unwrap();
+        assert_eq!(result_paths(&results), vec!["src/ids.rs"]);
+    }
+
+    #[test]
+    fn test_case_insensitive_unicode_queries_are_not_pruned() {
+        let dir = workspace();
+        fs::write(dir.path().join("cafe.txt"), "CAFÉ CRÈME\n").unwrap();
+        let repo = open(&dir);
+        repo.index_workspace().unwrap();
+
+        assert!(required_trigrams(&SearchQuery::simple("café").unwrap()).is_empty());
+        let results = repo.search(&SearchQuery::simple("café crème").unwrap()).unwrap();
+        assert_eq!(result_paths(&results), vec!["cafe.txt"]);
+    }
+
+    #[test]
+    fn test_corrupt_snapshot_is_rejected() {
+        let dir = workspace();
+        let index_path = dir.path().join("broken.idx");
+        fs::write(&index_path, b"not an index").unwrap();
+
+        let result = TrigramIndexRepository::open(dir.path(), &index_path);
+        assert!(matches!(result, Err(AppError::Index { .. })));
+    }
+}